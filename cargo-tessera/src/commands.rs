@@ -1,10 +1,17 @@
 pub mod android;
 pub mod build;
+pub mod bundle;
 pub mod check;
 pub mod dev;
+pub mod doctor;
+#[cfg(target_os = "macos")]
+pub mod ios;
 pub mod new;
 pub mod plugin;
+pub mod preview;
+pub mod profile;
 pub mod profiling;
+pub mod trace;
 pub mod web;
 
 use std::{fs, path::PathBuf};
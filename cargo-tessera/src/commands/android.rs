@@ -897,7 +897,7 @@ fn find_device<'a>(env: &'a AndroidEnv, serial: &str) -> Result<Device<'a>> {
         .ok_or_else(|| anyhow!("Device {serial} not found. Run `adb devices` to list targets."))
 }
 
-fn display_path(path: &Path) -> String {
+pub(super) fn display_path(path: &Path) -> String {
     let text = path.as_os_str().to_string_lossy().into_owned();
     if let Some(rest) = text.strip_prefix(r"\\?\UNC\") {
         format!(r"\\{}", rest)
@@ -1575,29 +1575,29 @@ fn copy_dir_all(source: &Path, target: &Path) -> Result<()> {
 }
 
 #[derive(Debug, Deserialize)]
-struct Manifest {
+pub(super) struct Manifest {
     package: Option<PackageSection>,
     lib: Option<LibSection>,
 }
 
 impl Manifest {
-    fn load() -> Result<Self> {
+    pub(super) fn load() -> Result<Self> {
         let contents = fs::read_to_string("Cargo.toml").context("Failed to read Cargo.toml")?;
         toml::from_str(&contents).context("Failed to parse Cargo.toml")
     }
 
-    fn load_from(dir: &Path) -> Result<Self> {
+    pub(super) fn load_from(dir: &Path) -> Result<Self> {
         let cargo_path = dir.join("Cargo.toml");
         let contents = fs::read_to_string(&cargo_path)
             .with_context(|| format!("Failed to read Cargo.toml from {}", dir.display()))?;
         toml::from_str(&contents).context("Failed to parse Cargo.toml")
     }
 
-    fn package_name(&self) -> Option<String> {
+    pub(super) fn package_name(&self) -> Option<String> {
         self.package.as_ref().and_then(|p| p.name.clone())
     }
 
-    fn lib_name(&self) -> Option<String> {
+    pub(super) fn lib_name(&self) -> Option<String> {
         self.lib.as_ref().and_then(|l| l.name.clone())
     }
 
@@ -1644,11 +1644,11 @@ struct AndroidManifestConfig {
 }
 
 #[derive(Debug, Deserialize, Default)]
-struct AppToml {
-    identifier: Option<String>,
+pub(super) struct AppToml {
+    pub(super) identifier: Option<String>,
 }
 
-fn load_app_toml(root: &Path) -> Result<AppToml> {
+pub(super) fn load_app_toml(root: &Path) -> Result<AppToml> {
     let path = root.join("tessera-app.toml");
     if !path.exists() {
         return Ok(AppToml::default());
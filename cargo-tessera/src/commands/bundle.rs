@@ -0,0 +1,373 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::{Context, Result, anyhow, bail};
+use cargo_metadata::{MetadataCommand, Package};
+use clap::ValueEnum;
+use serde::Deserialize;
+
+use crate::output;
+
+use super::build;
+
+mod icon;
+mod linux;
+mod macos;
+mod windows;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum BundleFormat {
+    /// macOS application bundle
+    App,
+    /// macOS disk image containing the application bundle
+    Dmg,
+    /// Windows Installer package (requires WiX Toolset v4+)
+    Msi,
+    /// Windows setup executable (requires NSIS)
+    Nsis,
+    /// Self-contained Linux executable (requires appimagetool)
+    Appimage,
+    /// Debian package (requires dpkg-deb)
+    Deb,
+    /// RPM package (requires rpmbuild)
+    Rpm,
+}
+
+impl BundleFormat {
+    fn os(self) -> TargetOs {
+        match self {
+            Self::App | Self::Dmg => TargetOs::MacOs,
+            Self::Msi | Self::Nsis => TargetOs::Windows,
+            Self::Appimage | Self::Deb | Self::Rpm => TargetOs::Linux,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::App => "app",
+            Self::Dmg => "dmg",
+            Self::Msi => "msi",
+            Self::Nsis => "nsis",
+            Self::Appimage => "appimage",
+            Self::Deb => "deb",
+            Self::Rpm => "rpm",
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum TargetOs {
+    MacOs,
+    Windows,
+    Linux,
+}
+
+impl TargetOs {
+    fn detect(target: Option<&str>) -> Result<Self> {
+        let name = target.unwrap_or(std::env::consts::OS);
+        if name.contains("apple-darwin") || name == "macos" {
+            Ok(Self::MacOs)
+        } else if name.contains("windows") {
+            Ok(Self::Windows)
+        } else if name.contains("linux") {
+            Ok(Self::Linux)
+        } else {
+            bail!("Bundling is not supported for `{name}`")
+        }
+    }
+
+    fn default_formats(self) -> Vec<BundleFormat> {
+        match self {
+            Self::MacOs => vec![BundleFormat::App, BundleFormat::Dmg],
+            Self::Windows => vec![BundleFormat::Msi],
+            Self::Linux => vec![BundleFormat::Deb, BundleFormat::Appimage],
+        }
+    }
+}
+
+pub struct BundleOptions<'a> {
+    pub package: Option<&'a str>,
+    pub bin: Option<&'a str>,
+    pub target: Option<&'a str>,
+    pub formats: Vec<BundleFormat>,
+}
+
+/// `[package.metadata.tessera.bundle]` of the app manifest.
+#[derive(Debug, Deserialize, Default)]
+#[serde(default, rename_all = "kebab-case")]
+struct BundleManifestConfig {
+    name: Option<String>,
+    identifier: Option<String>,
+    icon: Option<PathBuf>,
+    publisher: Option<String>,
+    copyright: Option<String>,
+    category: Option<String>,
+    deep_link_schemes: Vec<String>,
+    windows_upgrade_code: Option<String>,
+}
+
+/// Everything the platform bundlers need to know about the app.
+struct BundleContext {
+    /// Name shown to users, e.g. the `.app` and start menu entry.
+    product_name: String,
+    /// Cargo package name, also used for package manager entries.
+    package_name: String,
+    version: String,
+    description: String,
+    identifier: String,
+    publisher: String,
+    copyright: Option<String>,
+    homepage: Option<String>,
+    license: Option<String>,
+    category: String,
+    deep_link_schemes: Vec<String>,
+    windows_upgrade_code: Option<String>,
+    icon: Option<PathBuf>,
+    target: Option<String>,
+    binary: PathBuf,
+    bin_name: String,
+    out_dir: PathBuf,
+}
+
+impl BundleContext {
+    fn new(options: &BundleOptions<'_>, os: TargetOs) -> Result<Self> {
+        let metadata = MetadataCommand::new()
+            .no_deps()
+            .exec()
+            .context("Failed to read Cargo metadata for bundling")?;
+        let package = match options.package {
+            Some(name) => metadata
+                .packages
+                .iter()
+                .find(|candidate| candidate.name.as_ref() == name)
+                .ok_or_else(|| anyhow!("Cargo package `{name}` was not found"))?,
+            None => metadata.root_package().ok_or_else(|| {
+                anyhow!("Unable to determine the package to bundle. Provide --package.")
+            })?,
+        };
+        let package_name: &str = package.name.as_ref();
+        let bin_name = select_bin(package, options.bin)?;
+        let package_dir = package
+            .manifest_path
+            .parent()
+            .ok_or_else(|| anyhow!("Package manifest has no parent directory"))?
+            .as_std_path()
+            .to_path_buf();
+        let config = load_bundle_config(package)?;
+
+        let mut release_dir = metadata.target_directory.as_std_path().to_path_buf();
+        if let Some(target) = options.target {
+            release_dir.push(target);
+        }
+        release_dir.push("release");
+        let exe_suffix = if os == TargetOs::Windows { ".exe" } else { "" };
+
+        let identifier = match config.identifier {
+            Some(identifier) => identifier,
+            None => super::android::load_app_toml(&package_dir)?
+                .identifier
+                .unwrap_or_else(|| format!("com.example.{}", package_name.replace('-', "_"))),
+        };
+        let icon = config.icon.map(|icon| package_dir.join(icon));
+        if let Some(icon) = &icon
+            && !icon.is_file()
+        {
+            bail!("Bundle icon not found: {}", icon.display());
+        }
+
+        Ok(Self {
+            product_name: config.name.unwrap_or_else(|| package_name.to_string()),
+            package_name: package_name.to_string(),
+            version: package.version.to_string(),
+            description: package
+                .description
+                .clone()
+                .unwrap_or_else(|| package_name.to_string()),
+            identifier,
+            publisher: config
+                .publisher
+                .or_else(|| package.authors.first().map(|author| strip_email(author)))
+                .unwrap_or_else(|| package_name.to_string()),
+            copyright: config.copyright,
+            homepage: package.homepage.clone(),
+            license: package.license.clone(),
+            category: config.category.unwrap_or_else(|| "Utility".to_string()),
+            deep_link_schemes: config.deep_link_schemes,
+            windows_upgrade_code: config.windows_upgrade_code,
+            icon,
+            target: options.target.map(str::to_string),
+            binary: release_dir.join(format!("{bin_name}{exe_suffix}")),
+            bin_name,
+            out_dir: release_dir.join("bundle"),
+        })
+    }
+
+    /// CPU architecture of the bundled binary, as named by the target triple.
+    fn arch(&self) -> &str {
+        match &self.target {
+            Some(target) => target.split('-').next().unwrap_or(target),
+            None => std::env::consts::ARCH,
+        }
+    }
+
+    /// Clean directory for one format's intermediate files.
+    fn work_dir(&self, format: BundleFormat) -> Result<PathBuf> {
+        let dir = self.out_dir.join(".work").join(format.name());
+        if dir.exists() {
+            fs::remove_dir_all(&dir)
+                .with_context(|| format!("Failed to clean {}", dir.display()))?;
+        }
+        fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        Ok(dir)
+    }
+}
+
+fn select_bin(package: &Package, requested: Option<&str>) -> Result<String> {
+    let package_name: &str = package.name.as_ref();
+    let bins: Vec<&str> = package
+        .targets
+        .iter()
+        .filter(|target| target.is_bin())
+        .map(|target| target.name.as_str())
+        .collect();
+    if let Some(requested) = requested {
+        return bins
+            .iter()
+            .find(|bin| **bin == requested)
+            .map(|bin| bin.to_string())
+            .ok_or_else(|| anyhow!("Binary `{requested}` not found in `{package_name}`"));
+    }
+    match bins.as_slice() {
+        [] => bail!("Package `{package_name}` has no binary to bundle"),
+        [bin] => Ok(bin.to_string()),
+        _ => bins
+            .iter()
+            .find(|bin| **bin == package_name)
+            .map(|bin| bin.to_string())
+            .ok_or_else(|| {
+                anyhow!("Package `{package_name}` has several binaries; choose one with --bin")
+            }),
+    }
+}
+
+fn load_bundle_config(package: &Package) -> Result<BundleManifestConfig> {
+    let Some(bundle) = package
+        .metadata
+        .get("tessera")
+        .and_then(|tessera| tessera.get("bundle"))
+    else {
+        return Ok(BundleManifestConfig::default());
+    };
+    serde_json::from_value(bundle.clone())
+        .context("Failed to parse [package.metadata.tessera.bundle]")
+}
+
+fn strip_email(author: &str) -> String {
+    author
+        .split_once('<')
+        .map_or(author, |(name, _)| name)
+        .trim()
+        .to_string()
+}
+
+pub fn execute(options: BundleOptions<'_>) -> Result<()> {
+    let os = TargetOs::detect(options.target)?;
+    let mut formats = if options.formats.is_empty() {
+        os.default_formats()
+    } else {
+        options.formats.clone()
+    };
+    formats.sort();
+    formats.dedup();
+    if let Some(format) = formats.iter().find(|format| format.os() != os) {
+        bail!(
+            "`{}` bundles can't be made for {}",
+            format.name(),
+            options.target.unwrap_or(std::env::consts::OS)
+        );
+    }
+
+    let ctx = BundleContext::new(&options, os)?;
+    build::execute(true, options.target, options.package, None, false, None)?;
+    if !ctx.binary.is_file() {
+        bail!("Built binary not found at {}", ctx.binary.display());
+    }
+    fs::create_dir_all(&ctx.out_dir)
+        .with_context(|| format!("Failed to create {}", ctx.out_dir.display()))?;
+
+    let mut app_bundle = None;
+    for format in formats {
+        output::status(
+            "Bundling",
+            format!("{} ({})", ctx.product_name, format.name()),
+        );
+        let artifact = match format {
+            BundleFormat::App => {
+                let app = macos::bundle_app(&ctx)?;
+                app_bundle = Some(app.clone());
+                app
+            }
+            BundleFormat::Dmg => {
+                let app = match &app_bundle {
+                    Some(app) => app.clone(),
+                    None => macos::bundle_app(&ctx)?,
+                };
+                macos::bundle_dmg(&ctx, &app)?
+            }
+            BundleFormat::Msi => windows::bundle_msi(&ctx)?,
+            BundleFormat::Nsis => windows::bundle_nsis(&ctx)?,
+            BundleFormat::Appimage => linux::bundle_appimage(&ctx)?,
+            BundleFormat::Deb => linux::bundle_deb(&ctx)?,
+            BundleFormat::Rpm => linux::bundle_rpm(&ctx)?,
+        };
+        output::status("Bundle", artifact.display().to_string());
+    }
+
+    let _ = fs::remove_dir_all(ctx.out_dir.join(".work"));
+    Ok(())
+}
+
+/// Runs a packaging tool, explaining how to get it if it's missing.
+fn run_tool(cmd: &mut Command, tool: &str, install_hint: &str) -> Result<()> {
+    let status = match cmd.status() {
+        Ok(status) => status,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            bail!("`{tool}` was not found. {install_hint}")
+        }
+        Err(err) => return Err(err).with_context(|| format!("Failed to run {tool}")),
+    };
+    if !status.success() {
+        bail!("{tool} failed with {status}");
+    }
+    Ok(())
+}
+
+fn copy_file(from: &Path, to: &Path) -> Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::copy(from, to)
+        .with_context(|| format!("Failed to copy {} to {}", from.display(), to.display()))?;
+    Ok(())
+}
+
+fn write_file(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Escapes text for XML attribute values and element content.
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
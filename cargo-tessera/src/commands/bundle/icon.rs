@@ -0,0 +1,108 @@
+//! Platform icons generated from the single source image.
+
+use std::{io::Cursor, path::Path};
+
+use anyhow::{Context, Result};
+use image::{DynamicImage, ImageFormat, imageops::FilterType};
+
+/// Sizes of the PNGs in a `.icns` file with their entry types.
+const ICNS_ENTRIES: [(&[u8; 4], u32); 7] = [
+    (b"icp4", 16),
+    (b"icp5", 32),
+    (b"icp6", 64),
+    (b"ic07", 128),
+    (b"ic08", 256),
+    (b"ic09", 512),
+    (b"ic10", 1024),
+];
+/// Sizes of the PNGs in a Windows `.ico` file.
+const ICO_SIZES: [u32; 6] = [16, 24, 32, 48, 64, 256];
+/// Sizes installed into the Linux `hicolor` icon theme.
+pub(super) const LINUX_SIZES: [u32; 5] = [32, 64, 128, 256, 512];
+
+pub(super) fn load(path: &Path) -> Result<DynamicImage> {
+    image::open(path).with_context(|| format!("Failed to read icon {}", path.display()))
+}
+
+/// Encodes `image` scaled to `size` × `size` as PNG.
+pub(super) fn png(image: &DynamicImage, size: u32) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    image
+        .resize_exact(size, size, FilterType::Lanczos3)
+        .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+        .context("Failed to encode icon")?;
+    Ok(bytes)
+}
+
+/// Encodes a macOS `.icns` icon family of PNG entries.
+pub(super) fn icns(image: &DynamicImage) -> Result<Vec<u8>> {
+    let mut entries = Vec::new();
+    for (kind, size) in ICNS_ENTRIES {
+        let data = png(image, size)?;
+        entries.extend_from_slice(kind);
+        entries.extend_from_slice(&(data.len() as u32 + 8).to_be_bytes());
+        entries.extend_from_slice(&data);
+    }
+    let mut icns = Vec::with_capacity(entries.len() + 8);
+    icns.extend_from_slice(b"icns");
+    icns.extend_from_slice(&(entries.len() as u32 + 8).to_be_bytes());
+    icns.extend_from_slice(&entries);
+    Ok(icns)
+}
+
+/// Encodes a Windows `.ico` file of PNG entries.
+pub(super) fn ico(image: &DynamicImage) -> Result<Vec<u8>> {
+    let images = ICO_SIZES
+        .iter()
+        .map(|&size| Ok((size, png(image, size)?)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let header_len = 6 + 16 * images.len();
+    let mut ico = Vec::new();
+    ico.extend_from_slice(&0u16.to_le_bytes());
+    ico.extend_from_slice(&1u16.to_le_bytes());
+    ico.extend_from_slice(&(images.len() as u16).to_le_bytes());
+    let mut offset = header_len;
+    for (size, data) in &images {
+        // A stored size of 0 means 256.
+        let stored = if *size >= 256 { 0 } else { *size as u8 };
+        ico.extend_from_slice(&[stored, stored, 0, 0]);
+        ico.extend_from_slice(&1u16.to_le_bytes());
+        ico.extend_from_slice(&32u16.to_le_bytes());
+        ico.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        ico.extend_from_slice(&(offset as u32).to_le_bytes());
+        offset += data.len();
+    }
+    for (_, data) in images {
+        ico.extend_from_slice(&data);
+    }
+    Ok(ico)
+}
+
+#[cfg(test)]
+mod tests {
+    use image::RgbaImage;
+
+    use super::*;
+
+    #[test]
+    fn icon_containers_index_every_entry() {
+        let image = DynamicImage::ImageRgba8(RgbaImage::new(64, 64));
+
+        let icns = icns(&image).expect("icns should encode");
+        assert_eq!(&icns[..4], b"icns");
+        assert_eq!(
+            u32::from_be_bytes(icns[4..8].try_into().unwrap()) as usize,
+            icns.len()
+        );
+        assert_eq!(&icns[8..12], b"icp4");
+
+        let ico = ico(&image).expect("ico should encode");
+        assert_eq!(u16::from_le_bytes([ico[4], ico[5]]), ICO_SIZES.len() as u16);
+        let last = 6 + 16 * (ICO_SIZES.len() - 1);
+        assert_eq!(ico[last], 0, "256px entries store a size of 0");
+        let offset = u32::from_le_bytes(ico[last + 12..last + 16].try_into().unwrap()) as usize;
+        let len = u32::from_le_bytes(ico[last + 8..last + 12].try_into().unwrap()) as usize;
+        assert_eq!(offset + len, ico.len());
+    }
+}
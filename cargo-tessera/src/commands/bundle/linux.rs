@@ -0,0 +1,226 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::Result;
+
+use super::{BundleContext, BundleFormat, copy_file, icon, run_tool, write_file};
+
+/// Builds a Debian package with `dpkg-deb`.
+pub(super) fn bundle_deb(ctx: &BundleContext) -> Result<PathBuf> {
+    let root = ctx.work_dir(BundleFormat::Deb)?;
+    install_files(ctx, &root.join("usr"))?;
+
+    let mut control = format!(
+        "Package: {}\nVersion: {}\nArchitecture: {}\nMaintainer: {}\nPriority: optional\nSection: misc\n",
+        debian_package_name(&ctx.package_name),
+        ctx.version,
+        debian_arch(ctx.arch()),
+        ctx.publisher,
+    );
+    if let Some(homepage) = &ctx.homepage {
+        control.push_str(&format!("Homepage: {homepage}\n"));
+    }
+    control.push_str(&format!("Description: {}\n", ctx.description));
+    write_file(&root.join("DEBIAN").join("control"), control)?;
+
+    let deb = ctx.out_dir.join(format!(
+        "{}_{}_{}.deb",
+        debian_package_name(&ctx.package_name),
+        ctx.version,
+        debian_arch(ctx.arch())
+    ));
+    run_tool(
+        Command::new("dpkg-deb")
+            .args(["--build", "--root-owner-group"])
+            .arg(&root)
+            .arg(&deb),
+        "dpkg-deb",
+        "Install the `dpkg` package.",
+    )?;
+    Ok(deb)
+}
+
+/// Builds an RPM package with `rpmbuild`.
+pub(super) fn bundle_rpm(ctx: &BundleContext) -> Result<PathBuf> {
+    let work_dir = ctx.work_dir(BundleFormat::Rpm)?;
+    let staging = work_dir.join("root");
+    let files = install_files(ctx, &staging.join("usr"))?;
+    // RPM versions can't contain `-`; `~` sorts prereleases first.
+    let version = ctx.version.replace('-', "~");
+
+    let mut spec = format!(
+        "Name: {}\nVersion: {}\nRelease: 1\nSummary: {}\nLicense: {}\n",
+        ctx.package_name,
+        version,
+        ctx.description,
+        ctx.license.as_deref().unwrap_or("Proprietary"),
+    );
+    if let Some(homepage) = &ctx.homepage {
+        spec.push_str(&format!("URL: {homepage}\n"));
+    }
+    spec.push_str(&format!(
+        "\n%description\n{}\n\n%install\ncp -a {}/. %{{buildroot}}/\n\n%files\n",
+        ctx.description,
+        staging.display()
+    ));
+    for file in files {
+        spec.push_str(&format!("/{}\n", file.display()));
+    }
+    let spec_path = work_dir.join(format!("{}.spec", ctx.package_name));
+    write_file(&spec_path, spec)?;
+
+    let rpm_dir = work_dir.join("rpms");
+    run_tool(
+        Command::new("rpmbuild")
+            .arg("-bb")
+            .arg("--target")
+            .arg(ctx.arch())
+            .arg("--define")
+            .arg(format!("_topdir {}", work_dir.join("rpmbuild").display()))
+            .arg("--define")
+            .arg(format!("_rpmdir {}", rpm_dir.display()))
+            .arg("--define")
+            .arg("_build_id_links none")
+            .arg(&spec_path),
+        "rpmbuild",
+        "Install the `rpm-build` package.",
+    )?;
+
+    let rpm = ctx.out_dir.join(format!(
+        "{}-{}-1.{}.rpm",
+        ctx.package_name,
+        version,
+        ctx.arch()
+    ));
+    let built = rpm_dir
+        .join(ctx.arch())
+        .join(rpm.file_name().expect("rpm path has a file name"));
+    copy_file(&built, &rpm)?;
+    Ok(rpm)
+}
+
+/// Builds an AppImage with `appimagetool`.
+pub(super) fn bundle_appimage(ctx: &BundleContext) -> Result<PathBuf> {
+    let app_dir = ctx.work_dir(BundleFormat::Appimage)?.join("AppDir");
+    install_files(ctx, &app_dir.join("usr"))?;
+
+    // appimagetool expects the entry, desktop file and icon at the root.
+    write_file(
+        &app_dir.join("AppRun"),
+        format!(
+            "#!/bin/sh\nHERE=\"$(dirname \"$(readlink -f \"$0\")\")\"\nexec \"$HERE/usr/bin/{}\" \"$@\"\n",
+            ctx.bin_name
+        ),
+    )?;
+    set_executable(&app_dir.join("AppRun"))?;
+    write_file(
+        &app_dir.join(format!("{}.desktop", ctx.identifier)),
+        desktop_entry(ctx),
+    )?;
+    if let Some(path) = &ctx.icon {
+        write_file(
+            &app_dir.join(format!("{}.png", ctx.identifier)),
+            icon::png(&icon::load(path)?, 256)?,
+        )?;
+    }
+
+    let appimage = ctx.out_dir.join(format!(
+        "{}_{}_{}.AppImage",
+        ctx.product_name.replace(' ', "_"),
+        ctx.version,
+        ctx.arch()
+    ));
+    run_tool(
+        Command::new("appimagetool")
+            .env("ARCH", ctx.arch())
+            .arg(&app_dir)
+            .arg(&appimage),
+        "appimagetool",
+        "Download it from https://github.com/AppImage/appimagetool/releases.",
+    )?;
+    Ok(appimage)
+}
+
+/// Installs the binary, desktop entry and icons below `usr`, returning the
+/// installed paths relative to the file system root.
+fn install_files(ctx: &BundleContext, usr: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let binary = Path::new("bin").join(&ctx.bin_name);
+    copy_file(&ctx.binary, &usr.join(&binary))?;
+    set_executable(&usr.join(&binary))?;
+    files.push(binary);
+
+    let desktop = Path::new("share")
+        .join("applications")
+        .join(format!("{}.desktop", ctx.identifier));
+    write_file(&usr.join(&desktop), desktop_entry(ctx))?;
+    files.push(desktop);
+
+    if let Some(path) = &ctx.icon {
+        let image = icon::load(path)?;
+        for size in icon::LINUX_SIZES {
+            let icon_path = Path::new("share")
+                .join("icons")
+                .join("hicolor")
+                .join(format!("{size}x{size}"))
+                .join("apps")
+                .join(format!("{}.png", ctx.identifier));
+            write_file(&usr.join(&icon_path), icon::png(&image, size)?)?;
+            files.push(icon_path);
+        }
+    }
+    Ok(files
+        .into_iter()
+        .map(|file| Path::new("usr").join(file))
+        .collect())
+}
+
+fn desktop_entry(ctx: &BundleContext) -> String {
+    let mut entry = format!(
+        "[Desktop Entry]\nType=Application\nName={}\nComment={}\nExec={} %u\nTerminal=false\nCategories={};\n",
+        ctx.product_name, ctx.description, ctx.bin_name, ctx.category
+    );
+    if ctx.icon.is_some() {
+        entry.push_str(&format!("Icon={}\n", ctx.identifier));
+    }
+    if !ctx.deep_link_schemes.is_empty() {
+        let mime_types: String = ctx
+            .deep_link_schemes
+            .iter()
+            .map(|scheme| format!("x-scheme-handler/{scheme};"))
+            .collect();
+        entry.push_str(&format!("MimeType={mime_types}\n"));
+    }
+    entry
+}
+
+fn debian_package_name(name: &str) -> String {
+    name.to_ascii_lowercase().replace('_', "-")
+}
+
+fn debian_arch(arch: &str) -> &str {
+    match arch {
+        "x86_64" => "amd64",
+        "aarch64" => "arm64",
+        "x86" | "i686" => "i386",
+        "arm" | "armv7" => "armhf",
+        other => other,
+    }
+}
+
+#[cfg(unix)]
+fn set_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    use anyhow::Context;
+
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))
+        .with_context(|| format!("Failed to make {} executable", path.display()))
+}
+
+#[cfg(not(unix))]
+fn set_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
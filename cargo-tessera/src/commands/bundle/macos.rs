@@ -0,0 +1,124 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::{Context, Result, bail};
+
+use super::{BundleContext, BundleFormat, copy_file, icon, run_tool, write_file, xml_escape};
+
+/// Builds `<product>.app` next to the other bundles.
+pub(super) fn bundle_app(ctx: &BundleContext) -> Result<PathBuf> {
+    let app = ctx.out_dir.join(format!("{}.app", ctx.product_name));
+    if app.exists() {
+        fs::remove_dir_all(&app).with_context(|| format!("Failed to clean {}", app.display()))?;
+    }
+    let contents = app.join("Contents");
+    copy_file(&ctx.binary, &contents.join("MacOS").join(&ctx.bin_name))?;
+
+    let icon_file = match &ctx.icon {
+        Some(path) => {
+            let image = icon::load(path)?;
+            let file_name = format!("{}.icns", ctx.bin_name);
+            write_file(
+                &contents.join("Resources").join(&file_name),
+                icon::icns(&image)?,
+            )?;
+            Some(file_name)
+        }
+        None => None,
+    };
+    write_file(
+        &contents.join("Info.plist"),
+        info_plist(ctx, icon_file.as_deref()),
+    )?;
+    Ok(app)
+}
+
+/// Packs `app` into a compressed disk image with an Applications shortcut.
+pub(super) fn bundle_dmg(ctx: &BundleContext, app: &Path) -> Result<PathBuf> {
+    let staging = ctx.work_dir(BundleFormat::Dmg)?;
+    let staged_app = staging.join(app.file_name().expect("app bundle has a file name"));
+    let status = Command::new("cp")
+        .arg("-R")
+        .arg(app)
+        .arg(&staged_app)
+        .status()
+        .context("Failed to copy the app bundle")?;
+    if !status.success() {
+        bail!("Failed to copy {}", app.display());
+    }
+    #[cfg(unix)]
+    std::os::unix::fs::symlink("/Applications", staging.join("Applications"))
+        .context("Failed to link /Applications")?;
+
+    let dmg = ctx.out_dir.join(format!(
+        "{}_{}_{}.dmg",
+        ctx.product_name,
+        ctx.version,
+        ctx.arch()
+    ));
+    run_tool(
+        Command::new("hdiutil")
+            .args(["create", "-ov", "-format", "UDZO", "-volname"])
+            .arg(&ctx.product_name)
+            .arg("-srcfolder")
+            .arg(&staging)
+            .arg(&dmg),
+        "hdiutil",
+        "Disk images can only be created on macOS.",
+    )?;
+    Ok(dmg)
+}
+
+fn info_plist(ctx: &BundleContext, icon_file: Option<&str>) -> String {
+    let mut entries = vec![
+        ("CFBundleDevelopmentRegion", "en".to_string()),
+        ("CFBundleDisplayName", ctx.product_name.clone()),
+        ("CFBundleExecutable", ctx.bin_name.clone()),
+        ("CFBundleIdentifier", ctx.identifier.clone()),
+        ("CFBundleInfoDictionaryVersion", "6.0".to_string()),
+        ("CFBundleName", ctx.product_name.clone()),
+        ("CFBundlePackageType", "APPL".to_string()),
+        ("CFBundleShortVersionString", ctx.version.clone()),
+        ("CFBundleVersion", ctx.version.clone()),
+    ];
+    if let Some(icon_file) = icon_file {
+        entries.push(("CFBundleIconFile", icon_file.to_string()));
+    }
+    if let Some(copyright) = &ctx.copyright {
+        entries.push(("NSHumanReadableCopyright", copyright.clone()));
+    }
+
+    let mut plist = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" ",
+        "\"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n",
+        "<plist version=\"1.0\">\n<dict>\n",
+    ));
+    for (key, value) in entries {
+        plist.push_str(&format!(
+            "  <key>{key}</key>\n  <string>{}</string>\n",
+            xml_escape(&value)
+        ));
+    }
+    plist.push_str("  <key>NSHighResolutionCapable</key>\n  <true/>\n");
+    if !ctx.deep_link_schemes.is_empty() {
+        plist.push_str("  <key>CFBundleURLTypes</key>\n  <array>\n    <dict>\n");
+        plist.push_str(&format!(
+            "      <key>CFBundleURLName</key>\n      <string>{}</string>\n",
+            xml_escape(&ctx.identifier)
+        ));
+        plist.push_str("      <key>CFBundleURLSchemes</key>\n      <array>\n");
+        for scheme in &ctx.deep_link_schemes {
+            plist.push_str(&format!(
+                "        <string>{}</string>\n",
+                xml_escape(scheme)
+            ));
+        }
+        plist.push_str("      </array>\n    </dict>\n  </array>\n");
+    }
+    plist.push_str("</dict>\n</plist>\n");
+    plist
+}
@@ -0,0 +1,258 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::Result;
+
+use super::{BundleContext, BundleFormat, icon, run_tool, write_file, xml_escape};
+
+/// Builds an MSI with WiX Toolset v4 or later.
+pub(super) fn bundle_msi(ctx: &BundleContext) -> Result<PathBuf> {
+    let work_dir = ctx.work_dir(BundleFormat::Msi)?;
+    let icon = write_ico(ctx, &work_dir)?;
+    let wxs = work_dir.join("main.wxs");
+    write_file(&wxs, wix_source(ctx, icon.as_ref()))?;
+
+    let msi = ctx.out_dir.join(format!(
+        "{}_{}_{}.msi",
+        ctx.product_name,
+        ctx.version,
+        ctx.arch()
+    ));
+    let arch = if ctx.arch() == "aarch64" {
+        "arm64"
+    } else {
+        "x64"
+    };
+    run_tool(
+        Command::new("wix")
+            .arg("build")
+            .arg(&wxs)
+            .args(["-arch", arch, "-o"])
+            .arg(&msi),
+        "wix",
+        "Install it with `dotnet tool install --global wix`.",
+    )?;
+    Ok(msi)
+}
+
+/// Builds a setup executable with NSIS.
+pub(super) fn bundle_nsis(ctx: &BundleContext) -> Result<PathBuf> {
+    let work_dir = ctx.work_dir(BundleFormat::Nsis)?;
+    let icon = write_ico(ctx, &work_dir)?;
+    let setup = ctx.out_dir.join(format!(
+        "{}_{}_{}-setup.exe",
+        ctx.product_name,
+        ctx.version,
+        ctx.arch()
+    ));
+    let script = work_dir.join("installer.nsi");
+    write_file(&script, nsis_script(ctx, icon.as_ref(), &setup))?;
+
+    run_tool(
+        Command::new("makensis").arg("-V2").arg(&script),
+        "makensis",
+        "Install NSIS from https://nsis.sourceforge.io.",
+    )?;
+    Ok(setup)
+}
+
+fn write_ico(ctx: &BundleContext, work_dir: &Path) -> Result<Option<PathBuf>> {
+    let Some(path) = &ctx.icon else {
+        return Ok(None);
+    };
+    let ico = work_dir.join("icon.ico");
+    write_file(&ico, icon::ico(&icon::load(path)?)?)?;
+    Ok(Some(ico))
+}
+
+fn exe_name(ctx: &BundleContext) -> String {
+    format!("{}.exe", ctx.bin_name)
+}
+
+fn wix_source(ctx: &BundleContext, icon: Option<&PathBuf>) -> String {
+    let product = xml_escape(&ctx.product_name);
+    let exe = xml_escape(&exe_name(ctx));
+    let upgrade_code = ctx
+        .windows_upgrade_code
+        .clone()
+        .unwrap_or_else(|| derived_guid(&ctx.identifier));
+
+    let mut wxs = format!(
+        r#"<Wix xmlns="http://wixtoolset.org/schemas/v4/wxs">
+  <Package Name="{product}" Manufacturer="{publisher}" Version="{version}" UpgradeCode="{upgrade_code}" Scope="perMachine">
+    <MajorUpgrade DowngradeErrorMessage="A newer version of [ProductName] is already installed." />
+    <MediaTemplate EmbedCab="yes" />
+"#,
+        publisher = xml_escape(&ctx.publisher),
+        version = msi_version(&ctx.version),
+    );
+    if let Some(icon) = icon {
+        wxs.push_str(&format!(
+            "    <Icon Id=\"AppIcon\" SourceFile=\"{}\" />\n    <Property Id=\"ARPPRODUCTICON\" Value=\"AppIcon\" />\n",
+            xml_escape(&icon.display().to_string())
+        ));
+    }
+    wxs.push_str(&format!(
+        r#"    <StandardDirectory Id="ProgramFiles64Folder">
+      <Directory Id="INSTALLFOLDER" Name="{product}">
+        <Component Id="MainExecutable">
+          <File Source="{binary}" Name="{exe}" KeyPath="yes" />
+        </Component>
+      </Directory>
+    </StandardDirectory>
+    <StandardDirectory Id="ProgramMenuFolder">
+      <Component Id="StartMenuShortcut">
+        <Shortcut Id="AppShortcut" Name="{product}" Target="[INSTALLFOLDER]{exe}" WorkingDirectory="INSTALLFOLDER" />
+        <RegistryValue Root="HKCU" Key="Software\{identifier}" Name="shortcut" Type="integer" Value="1" KeyPath="yes" />
+      </Component>
+    </StandardDirectory>
+"#,
+        binary = xml_escape(&ctx.binary.display().to_string()),
+        identifier = xml_escape(&ctx.identifier),
+    ));
+    for (index, scheme) in ctx.deep_link_schemes.iter().enumerate() {
+        let scheme = xml_escape(scheme);
+        wxs.push_str(&format!(
+            r#"    <Component Id="DeepLink{index}" Directory="INSTALLFOLDER">
+      <RegistryKey Root="HKCR" Key="{scheme}">
+        <RegistryValue Type="string" Value="URL:{product}" KeyPath="yes" />
+        <RegistryValue Name="URL Protocol" Type="string" Value="" />
+        <RegistryValue Key="shell\open\command" Type="string" Value="&quot;[INSTALLFOLDER]{exe}&quot; &quot;%1&quot;" />
+      </RegistryKey>
+    </Component>
+"#
+        ));
+    }
+    wxs.push_str("    <Feature Id=\"Main\">\n");
+    wxs.push_str("      <ComponentRef Id=\"MainExecutable\" />\n");
+    wxs.push_str("      <ComponentRef Id=\"StartMenuShortcut\" />\n");
+    for index in 0..ctx.deep_link_schemes.len() {
+        wxs.push_str(&format!("      <ComponentRef Id=\"DeepLink{index}\" />\n"));
+    }
+    wxs.push_str("    </Feature>\n  </Package>\n</Wix>\n");
+    wxs
+}
+
+fn nsis_script(ctx: &BundleContext, icon: Option<&PathBuf>, setup: &Path) -> String {
+    let product = nsis_escape(&ctx.product_name);
+    let exe = nsis_escape(&exe_name(ctx));
+    let uninstall_key = format!(
+        "Software\\Microsoft\\Windows\\CurrentVersion\\Uninstall\\{}",
+        nsis_escape(&ctx.identifier)
+    );
+
+    let mut script = String::from("Unicode true\n!include \"MUI2.nsh\"\n");
+    script.push_str(&format!("Name \"{product}\"\n"));
+    script.push_str(&format!(
+        "OutFile \"{}\"\n",
+        nsis_escape(&setup.display().to_string())
+    ));
+    script.push_str(&format!("InstallDir \"$PROGRAMFILES64\\{product}\"\n"));
+    script.push_str("RequestExecutionLevel admin\n");
+    if let Some(icon) = icon {
+        let icon = nsis_escape(&icon.display().to_string());
+        script.push_str(&format!(
+            "!define MUI_ICON \"{icon}\"\n!define MUI_UNICON \"{icon}\"\n"
+        ));
+    }
+    script.push_str(concat!(
+        "!insertmacro MUI_PAGE_DIRECTORY\n",
+        "!insertmacro MUI_PAGE_INSTFILES\n",
+        "!insertmacro MUI_UNPAGE_CONFIRM\n",
+        "!insertmacro MUI_UNPAGE_INSTFILES\n",
+        "!insertmacro MUI_LANGUAGE \"English\"\n\n",
+        "Section \"Install\"\n",
+        "  SetOutPath \"$INSTDIR\"\n",
+    ));
+    script.push_str(&format!(
+        "  File \"/oname={exe}\" \"{}\"\n",
+        nsis_escape(&ctx.binary.display().to_string())
+    ));
+    script.push_str("  WriteUninstaller \"$INSTDIR\\uninstall.exe\"\n");
+    script.push_str(&format!(
+        "  CreateShortcut \"$SMPROGRAMS\\{product}.lnk\" \"$INSTDIR\\{exe}\"\n"
+    ));
+    for (name, value) in [
+        ("DisplayName", product.clone()),
+        ("DisplayVersion", nsis_escape(&ctx.version)),
+        ("Publisher", nsis_escape(&ctx.publisher)),
+        ("DisplayIcon", format!("$INSTDIR\\{exe}")),
+        (
+            "UninstallString",
+            "$\\\"$INSTDIR\\uninstall.exe$\\\"".to_string(),
+        ),
+    ] {
+        script.push_str(&format!(
+            "  WriteRegStr HKLM \"{uninstall_key}\" \"{name}\" \"{value}\"\n"
+        ));
+    }
+    for scheme in &ctx.deep_link_schemes {
+        let scheme = nsis_escape(scheme);
+        script.push_str(&format!(
+            "  WriteRegStr HKCR \"{scheme}\" \"\" \"URL:{product}\"\n  WriteRegStr HKCR \"{scheme}\" \"URL Protocol\" \"\"\n  WriteRegStr HKCR \"{scheme}\\shell\\open\\command\" \"\" \"$\\\"$INSTDIR\\{exe}$\\\" $\\\"%1$\\\"\"\n"
+        ));
+    }
+    script.push_str("SectionEnd\n\nSection \"Uninstall\"\n");
+    script.push_str(&format!(
+        "  Delete \"$INSTDIR\\{exe}\"\n  Delete \"$INSTDIR\\uninstall.exe\"\n  RMDir \"$INSTDIR\"\n  Delete \"$SMPROGRAMS\\{product}.lnk\"\n  DeleteRegKey HKLM \"{uninstall_key}\"\n"
+    ));
+    for scheme in &ctx.deep_link_schemes {
+        script.push_str(&format!(
+            "  DeleteRegKey HKCR \"{}\"\n",
+            nsis_escape(scheme)
+        ));
+    }
+    script.push_str("SectionEnd\n");
+    script
+}
+
+fn nsis_escape(text: &str) -> String {
+    text.replace('$', "$$").replace('"', "$\\\"")
+}
+
+/// MSI versions are up to three numbers; prerelease and build parts are
+/// dropped.
+fn msi_version(version: &str) -> String {
+    version
+        .split(['-', '+'])
+        .next()
+        .unwrap_or(version)
+        .split('.')
+        .take(3)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// A GUID that stays the same for the same `seed`, so upgrades of an app
+/// replace the installed version.
+fn derived_guid(seed: &str) -> String {
+    fn fnv1a(seed: &str, basis: u64) -> u64 {
+        seed.bytes().fold(basis, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+        })
+    }
+    let high = fnv1a(seed, 0xcbf2_9ce4_8422_2325);
+    let low = fnv1a(seed, high);
+    let bytes = [high.to_be_bytes(), low.to_be_bytes()].concat();
+    let hex: String = bytes
+        .iter()
+        .enumerate()
+        .map(|(index, byte)| match index {
+            // Version 5 (name-based) and the RFC 4122 variant.
+            6 => (byte & 0x0f) | 0x50,
+            8 => (byte & 0x3f) | 0x80,
+            _ => *byte,
+        })
+        .map(|byte| format!("{byte:02X}"))
+        .collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
@@ -1,11 +1,15 @@
 use std::{
+    env::consts::{DLL_PREFIX, DLL_SUFFIX},
+    fs,
+    io::Write,
     path::{Path, PathBuf},
-    process::{Child, Command},
+    process::{Child, Command, Stdio},
     sync::mpsc::channel,
     time::{Duration, Instant},
 };
 
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow};
+use cargo_metadata::MetadataCommand;
 use notify::{Event, EventKind, RecursiveMode, Watcher};
 use tessera_build::{AssetBackend, load_tessera_config_from_dir, resolve_assets_dir};
 
@@ -13,18 +17,198 @@ use crate::{color_check, output};
 
 use super::find_package_dir;
 
+/// App feature that links `tessera-dylib`, making the app hot reloadable.
+const HOT_RELOAD_FEATURE: &str = "hot-reload";
+/// Files whose changes need a full rebuild and restart.
+const RESTART_FILES: [&str; 4] = [
+    "Cargo.toml",
+    "build.rs",
+    "tessera-app.toml",
+    "tessera-config.toml",
+];
+
+/// What a finished build is used for.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BuildKind {
+    /// Launch the app binary.
+    Launch,
+    /// Load the rebuilt app library into the running app.
+    Reload,
+}
+
+/// What the dev loop builds, runs and watches.
+pub(super) struct DevTarget<'a> {
+    /// Package to build and run, the current one if `None`.
+    pub package: Option<&'a str>,
+    /// Manifest to build with instead of the one in the current directory.
+    pub manifest_path: Option<&'a Path>,
+    /// Target directory to build into instead of the workspace's own.
+    pub target_dir: Option<&'a Path>,
+    /// Package directory whose sources, manifest and assets are watched.
+    pub watch_dir: PathBuf,
+    /// Arguments passed to the app.
+    pub app_args: Vec<String>,
+    /// `TESSERA_HOT_RELOAD` value when the app is hot reloadable.
+    pub hot_reload_mode: &'static str,
+    /// Whether to run the Tessera color check before building.
+    pub color_check: bool,
+}
+
+impl DevTarget<'_> {
+    /// Applies the manifest and target directory to a cargo command.
+    fn configure(&self, cmd: &mut Command) {
+        if let Some(pkg) = self.package {
+            cmd.arg("-p").arg(pkg);
+        }
+        if let Some(manifest_path) = self.manifest_path {
+            cmd.arg("--manifest-path").arg(manifest_path);
+        }
+        if let Some(target_dir) = self.target_dir {
+            cmd.env("CARGO_TARGET_DIR", target_dir);
+        }
+    }
+}
+
+/// Where `cargo rustc` leaves the app library and where copies are loaded
+/// from.
+struct HotReload {
+    artifact: PathBuf,
+    reload_dir: PathBuf,
+    generation: u32,
+}
+
+impl HotReload {
+    fn resolve(target: &DevTarget<'_>, release: bool) -> Result<Option<Self>> {
+        let mut command = MetadataCommand::new();
+        command.no_deps();
+        if let Some(manifest_path) = target.manifest_path {
+            command.manifest_path(manifest_path);
+        }
+        if let Some(target_dir) = target.target_dir {
+            command.env("CARGO_TARGET_DIR", target_dir);
+        }
+        let metadata = command
+            .exec()
+            .context("Failed to read Cargo metadata for hot reload")?;
+        let package = match target.package {
+            Some(package) => metadata
+                .packages
+                .iter()
+                .find(|candidate| candidate.name.as_ref() == package),
+            None => metadata.root_package(),
+        };
+        let Some(package) = package else {
+            return Ok(None);
+        };
+        if !package.features.contains_key(HOT_RELOAD_FEATURE) {
+            return Ok(None);
+        }
+        let Some(lib) = package.targets.iter().find(|target| target.is_lib()) else {
+            return Ok(None);
+        };
+
+        let profile_dir =
+            metadata
+                .target_directory
+                .as_std_path()
+                .join(if release { "release" } else { "debug" });
+        let lib_name = lib.name.replace('-', "_");
+        Ok(Some(Self {
+            artifact: profile_dir.join(format!("{DLL_PREFIX}{lib_name}{DLL_SUFFIX}")),
+            reload_dir: profile_dir.join("hot-reload"),
+            generation: 0,
+        }))
+    }
+
+    /// Copies the rebuilt library to a fresh path, since the running app
+    /// keeps every library it loaded open.
+    fn stage(&mut self) -> Result<PathBuf> {
+        fs::create_dir_all(&self.reload_dir)
+            .with_context(|| format!("Failed to create {}", self.reload_dir.display()))?;
+        self.generation += 1;
+        let file_name = self
+            .artifact
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| anyhow!("Invalid library path {}", self.artifact.display()))?;
+        let staged = self
+            .reload_dir
+            .join(format!("{file_name}-{}{DLL_SUFFIX}", self.generation));
+        fs::copy(&self.artifact, &staged)
+            .with_context(|| format!("Failed to copy {}", self.artifact.display()))?;
+        Ok(staged)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn execute(
     verbose: bool,
     package: Option<&str>,
     release: bool,
     profiling_output: Option<&Path>,
     debug_dirty_overlay: bool,
+    inspector: bool,
+    devtools: bool,
+    asset_backend_override: Option<AssetBackend>,
+) -> Result<()> {
+    // Determine the package directory to watch
+    let watch_dir = if let Some(pkg) = package {
+        find_package_dir(pkg)?
+    } else {
+        PathBuf::from(".")
+    };
+    run(
+        DevTarget {
+            package,
+            manifest_path: None,
+            target_dir: None,
+            watch_dir,
+            app_args: Vec::new(),
+            hot_reload_mode: "1",
+            color_check: true,
+        },
+        verbose,
+        release,
+        profiling_output,
+        debug_dirty_overlay,
+        inspector,
+        devtools,
+        asset_backend_override,
+    )
+}
+
+/// Builds and runs `target`, rebuilding and reloading it as its sources
+/// change.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn run(
+    target: DevTarget<'_>,
+    verbose: bool,
+    release: bool,
+    profiling_output: Option<&Path>,
+    debug_dirty_overlay: bool,
+    inspector: bool,
+    devtools: bool,
     asset_backend_override: Option<AssetBackend>,
 ) -> Result<()> {
-    let feature_overrides = build_tessera_ui_features(profiling_output, debug_dirty_overlay);
-    run_color_check(package, &feature_overrides)?;
+    let package = target.package;
+    let mut hot_reload = HotReload::resolve(&target, release)?;
+    let mut feature_overrides =
+        build_tessera_ui_features(profiling_output, debug_dirty_overlay, inspector, devtools);
+    if hot_reload.is_some() {
+        feature_overrides.push(HOT_RELOAD_FEATURE.to_string());
+    }
+    if target.color_check {
+        run_color_check(package, &feature_overrides)?;
+    }
 
-    output::status("Starting", "dev server (auto rebuild/restart)");
+    if hot_reload.is_some() {
+        output::status("Starting", "dev server (hot reload)");
+    } else {
+        output::status("Starting", "dev server (auto rebuild/restart)");
+        output::note(format!(
+            "add a `{HOT_RELOAD_FEATURE}` feature enabling `tessera-dylib` to keep the app running across rebuilds"
+        ));
+    }
     if let Some(pkg) = package {
         output::status("Package", format!("`{}`", pkg));
     }
@@ -34,6 +218,12 @@ pub fn execute(
     if debug_dirty_overlay {
         output::status("DirtyOverlay", "enabled");
     }
+    if inspector {
+        output::status("Inspector", "enabled (press F12 in the app)");
+    }
+    if devtools {
+        output::status("Devtools", "enabled (ws://127.0.0.1:9330)");
+    }
     output::status("Watching", "for file changes");
 
     let (tx, rx) = channel();
@@ -44,16 +234,16 @@ pub fn execute(
                 EventKind::Modify(_) | EventKind::Create(_) | EventKind::Remove(_)
             )
         {
-            let _ = tx.send(());
+            let needs_restart = event.paths.iter().any(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| RESTART_FILES.contains(&name))
+            });
+            let _ = tx.send(needs_restart);
         }
     })?;
 
-    // Determine the package directory to watch
-    let package_dir = if let Some(pkg) = package {
-        find_package_dir(pkg)?
-    } else {
-        PathBuf::from(".")
-    };
+    let package_dir = &target.watch_dir;
     let asset_backend = resolve_asset_backend(asset_backend_override)?;
     output::status("Assets", format!("backend `{}`", asset_backend.as_str()));
 
@@ -69,27 +259,23 @@ pub fn execute(
     }
 
     // Watch Cargo.toml and build.rs in the package directory
-    for file in [
-        "Cargo.toml",
-        "build.rs",
-        "tessera-app.toml",
-        "tessera-config.toml",
-    ] {
+    for file in RESTART_FILES {
         let path = package_dir.join(file);
         if path.exists() {
             watcher.watch(&path, RecursiveMode::NonRecursive)?;
         }
     }
-    if let Some(config) = load_tessera_config_from_dir(&package_dir)?
-        && let Some(assets_dir) = resolve_assets_dir(&package_dir, Some(&config))
+    if let Some(config) = load_tessera_config_from_dir(package_dir)?
+        && let Some(assets_dir) = resolve_assets_dir(package_dir, Some(&config))
         && assets_dir.exists()
     {
         watcher.watch(&assets_dir, RecursiveMode::Recursive)?;
     }
 
     let mut child: Option<Child> = None;
-    let mut build_child: Option<Child> = None;
+    let mut build_child: Option<(Child, BuildKind)> = None;
     let mut pending_change = true;
+    let mut pending_restart = true;
     let mut color_check_current = true;
     let mut last_change = Instant::now() - Duration::from_secs(1);
     let debounce_window = Duration::from_millis(300);
@@ -97,13 +283,14 @@ pub fn execute(
     loop {
         // Wait for file changes (or time out to check running processes)
         match rx.recv_timeout(Duration::from_millis(100)) {
-            Ok(_) => {
+            Ok(needs_restart) => {
                 pending_change = true;
+                pending_restart |= needs_restart;
                 color_check_current = false;
                 last_change = Instant::now();
 
                 // Cancel an in-flight build so we only build once per stable tree.
-                if let Some(mut active_build) = build_child.take() {
+                if let Some((mut active_build, _)) = build_child.take() {
                     output::status("Canceling", "in-progress build due to changes");
                     let _ = active_build.kill();
                     let _ = active_build.wait();
@@ -115,7 +302,7 @@ pub fn execute(
 
         // Kick off a build once the tree is quiet and no build is currently running.
         if pending_change && build_child.is_none() && last_change.elapsed() >= debounce_window {
-            if !color_check_current {
+            if target.color_check && !color_check_current {
                 match run_color_check(package, &feature_overrides) {
                     Ok(()) => {
                         color_check_current = true;
@@ -129,32 +316,42 @@ pub fn execute(
                 }
             }
 
-            // Kill previous process
-            if let Some(mut c) = child.take() {
-                let _ = c.kill();
-                let _ = c.wait();
-            }
-
-            output::status("Building", "project");
+            let kind = if hot_reload.is_some() && child.is_some() && !pending_restart {
+                BuildKind::Reload
+            } else {
+                BuildKind::Launch
+            };
 
             let mut build_cmd = Command::new("cargo");
-            build_cmd.arg("build");
+            if kind == BuildKind::Reload {
+                output::status("Building", "app library for hot reload");
+                build_cmd.args(["rustc", "--lib", "--crate-type", "dylib"]);
+            } else {
+                // Kill previous process
+                if let Some(mut c) = child.take() {
+                    let _ = c.kill();
+                    let _ = c.wait();
+                }
+                output::status("Building", "project");
+                build_cmd.arg("build");
+            }
             if release {
                 build_cmd.arg("--release");
             }
             if verbose {
                 build_cmd.arg("-v");
             }
-            if let Some(pkg) = package {
-                build_cmd.arg("-p").arg(pkg);
-            }
+            target.configure(&mut build_cmd);
             configure_tessera_ui_features(&mut build_cmd, &feature_overrides, profiling_output);
             build_cmd.env("TESSERA_ASSET_BACKEND", asset_backend.as_str());
 
             match build_cmd.spawn() {
                 Ok(c) => {
-                    build_child = Some(c);
+                    build_child = Some((c, kind));
                     pending_change = false;
+                    if kind == BuildKind::Launch {
+                        pending_restart = false;
+                    }
                 }
                 Err(e) => {
                     output::error(format!("failed to start build: {e}"));
@@ -163,28 +360,49 @@ pub fn execute(
         }
 
         // Monitor build progress so we can relaunch the app when ready.
-        if let Some(mut active_build) = build_child.take() {
+        if let Some((mut active_build, kind)) = build_child.take() {
             match active_build.try_wait() {
                 Ok(Some(status)) => {
                     if !status.success() {
                         output::warn("build failed; waiting for changes");
                     } else if pending_change {
                         output::status("Rebuilding", "new changes arrived during build");
+                    } else if kind == BuildKind::Reload {
+                        let sent = match (hot_reload.as_mut(), child.as_mut()) {
+                            (Some(hot_reload), Some(running_child)) => {
+                                send_reload(hot_reload, running_child)
+                            }
+                            _ => Err(anyhow!("the app is no longer running")),
+                        };
+                        match sent {
+                            Ok(()) => output::status("Reloaded", "app library"),
+                            Err(err) => {
+                                output::warn(format!("hot reload failed, restarting: {err}"));
+                                pending_change = true;
+                                pending_restart = true;
+                            }
+                        }
                     } else {
                         let mut run_cmd = Command::new("cargo");
                         run_cmd.arg("run");
                         if verbose {
                             run_cmd.arg("-v");
                         }
-                        if let Some(pkg) = package {
-                            run_cmd.arg("-p").arg(pkg);
-                        }
+                        target.configure(&mut run_cmd);
                         configure_tessera_ui_features(
                             &mut run_cmd,
                             &feature_overrides,
                             profiling_output,
                         );
+                        if !target.app_args.is_empty() {
+                            run_cmd.arg("--").args(&target.app_args);
+                        }
                         run_cmd.env("TESSERA_ASSET_BACKEND", asset_backend.as_str());
+                        if hot_reload.is_some() {
+                            run_cmd
+                                .env("TESSERA_HOT_RELOAD", target.hot_reload_mode)
+                                .stdin(Stdio::piped());
+                        }
 
                         match run_cmd.spawn() {
                             Ok(c) => {
@@ -198,7 +416,7 @@ pub fn execute(
                     }
                 }
                 Ok(None) => {
-                    build_child = Some(active_build);
+                    build_child = Some((active_build, kind));
                 }
                 Err(err) => {
                     output::warn(format!("failed to check build status: {err}"));
@@ -233,7 +451,7 @@ pub fn execute(
     }
 
     // Cleanup
-    if let Some(mut build) = build_child {
+    if let Some((mut build, _)) = build_child {
         let _ = build.kill();
         let _ = build.wait();
     }
@@ -245,9 +463,22 @@ pub fn execute(
     Ok(())
 }
 
+/// Tells the running app to load the library that was just built.
+fn send_reload(hot_reload: &mut HotReload, child: &mut Child) -> Result<()> {
+    let staged = hot_reload.stage()?;
+    let stdin = child
+        .stdin
+        .as_mut()
+        .ok_or_else(|| anyhow!("the app was started without hot reload"))?;
+    writeln!(stdin, "{}", staged.display()).context("Failed to reach the running app")?;
+    stdin.flush().context("Failed to reach the running app")
+}
+
 fn build_tessera_ui_features(
     profiling_output: Option<&Path>,
     debug_dirty_overlay: bool,
+    inspector: bool,
+    devtools: bool,
 ) -> Vec<String> {
     let mut features = Vec::new();
     if profiling_output.is_some() {
@@ -256,6 +487,12 @@ fn build_tessera_ui_features(
     if debug_dirty_overlay {
         features.push("tessera-ui/debug-dirty-overlay".to_string());
     }
+    if inspector {
+        features.push("tessera-ui/inspector".to_string());
+    }
+    if devtools {
+        features.push("tessera-ui/devtools".to_string());
+    }
     features
 }
 
@@ -0,0 +1,407 @@
+use std::{collections::HashSet, path::PathBuf, process::Command};
+
+use anyhow::{Result, bail};
+use cargo_mobile2::{
+    android::{env::Env as AndroidEnv, target::Target as AndroidTarget},
+    target::TargetTrait,
+};
+
+use crate::output;
+
+/// Oldest rustc that builds the Tessera crates (edition 2024 with let
+/// chains).
+const MIN_RUST_VERSION: (u32, u32) = (1, 88);
+const WASM_TARGET: &str = "wasm32-unknown-unknown";
+#[cfg(target_os = "macos")]
+const IOS_TARGETS: [&str; 2] = ["aarch64-apple-ios", "aarch64-apple-ios-sim"];
+
+/// Counts problems while printing each check's result.
+#[derive(Default)]
+struct Report {
+    errors: usize,
+    warnings: usize,
+}
+
+impl Report {
+    fn ok(&mut self, label: &str, message: impl AsRef<str>) {
+        output::status(label, message);
+    }
+
+    /// Something an optional platform or feature needs is missing.
+    fn warn(&mut self, message: impl AsRef<str>, fix: impl AsRef<str>) {
+        self.warnings += 1;
+        output::warn(message);
+        output::step(format!("fix: {}", fix.as_ref()));
+    }
+
+    /// Tessera apps can't be built or run on this machine until it's fixed.
+    fn error(&mut self, message: impl AsRef<str>, fix: impl AsRef<str>) {
+        self.errors += 1;
+        output::error(message);
+        output::step(format!("fix: {}", fix.as_ref()));
+    }
+}
+
+/// Checks the toolchains, SDKs, system libraries and GPU drivers Tessera
+/// apps need, printing how to fix anything that's missing.
+pub fn execute() -> Result<()> {
+    let mut report = Report::default();
+    let installed_targets = check_toolchain(&mut report);
+    check_android(&mut report, installed_targets.as_ref());
+    #[cfg(target_os = "macos")]
+    check_ios(&mut report, installed_targets.as_ref());
+    check_web(&mut report, installed_targets.as_ref());
+    #[cfg(target_os = "linux")]
+    check_display_libs(&mut report);
+    check_gpu(&mut report);
+
+    if report.errors > 0 {
+        bail!(
+            "{} problem(s) and {} warning(s) found",
+            report.errors,
+            report.warnings
+        );
+    }
+    if report.warnings > 0 {
+        output::status(
+            "Finished",
+            format!(
+                "{} warning(s); desktop development is ready",
+                report.warnings
+            ),
+        );
+    } else {
+        output::status("Finished", "no problems found");
+    }
+    Ok(())
+}
+
+/// Checks rustc and cargo, returning the installed Rust targets when rustup
+/// manages the toolchain.
+fn check_toolchain(report: &mut Report) -> Option<HashSet<String>> {
+    match command_stdout(Command::new("rustc").arg("--version")) {
+        Some(version) => match rustc_version(&version) {
+            Some(found) if found < MIN_RUST_VERSION => report.error(
+                format!(
+                    "{version} is too old; Tessera needs Rust {}.{} or newer",
+                    MIN_RUST_VERSION.0, MIN_RUST_VERSION.1
+                ),
+                "run `rustup update`",
+            ),
+            _ => report.ok("Rust", version),
+        },
+        None => report.error("rustc was not found", "install Rust from https://rustup.rs"),
+    }
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    match command_stdout(Command::new(cargo).arg("--version")) {
+        Some(version) => report.ok("Cargo", version),
+        None => report.error("cargo was not found", "install Rust from https://rustup.rs"),
+    }
+
+    match command_stdout(Command::new("rustup").args(["target", "list", "--installed"])) {
+        Some(targets) => Some(targets.lines().map(str::to_string).collect()),
+        None => {
+            report.warn(
+                "rustup was not found; Rust targets for Android, iOS and the web can't be checked",
+                "install Rust with https://rustup.rs, or make sure your toolchain provides the targets you build for",
+            );
+            None
+        }
+    }
+}
+
+/// Android builds go through Gradle, which needs the SDK, the NDK and a JDK.
+fn check_android(report: &mut Report, installed_targets: Option<&HashSet<String>>) {
+    let env = match AndroidEnv::new() {
+        Ok(env) => env,
+        Err(err) => {
+            report.warn(
+                format!("Android builds are unavailable: {err}"),
+                "install the Android SDK and NDK (e.g. with Android Studio), then set `ANDROID_HOME` and `NDK_HOME`",
+            );
+            return;
+        }
+    };
+    let sdk_version = env.sdk_version().map_or_else(
+        |_| "unknown version".to_string(),
+        |version| version.to_string(),
+    );
+    report.ok(
+        "Android SDK",
+        format!("{} ({sdk_version})", env.android_home()),
+    );
+    let ndk_version = env.ndk.version().map_or_else(
+        |_| "unknown version".to_string(),
+        |version| version.to_string(),
+    );
+    report.ok(
+        "Android NDK",
+        format!("{} ({ndk_version})", env.ndk.home().display()),
+    );
+
+    let adb = env
+        .platform_tools_path()
+        .join(format!("adb{}", std::env::consts::EXE_SUFFIX));
+    if adb.is_file() {
+        report.ok("adb", adb.display().to_string());
+    } else {
+        report.warn(
+            "adb was not found in the Android SDK; `cargo tessera android dev` can't install apps",
+            "install \"Android SDK Platform-Tools\" with the SDK manager",
+        );
+    }
+
+    match java_version() {
+        Some(version) => report.ok("Java", version),
+        None => report.warn(
+            "no JDK was found; Gradle needs one to build Android apps",
+            "install JDK 17 or newer and set `JAVA_HOME` (Android Studio bundles one under `jbr`)",
+        ),
+    }
+
+    if let Some(installed) = installed_targets {
+        let missing: Vec<&str> = AndroidTarget::all()
+            .values()
+            .map(|target| target.triple)
+            .filter(|triple| !installed.contains(*triple))
+            .collect();
+        if missing.is_empty() {
+            report.ok("Android", "all Rust targets installed");
+        } else {
+            report.warn(
+                format!("Rust targets missing for Android: {}", missing.join(", ")),
+                format!("run `rustup target add {}`", missing.join(" ")),
+            );
+        }
+    }
+}
+
+fn java_version() -> Option<String> {
+    let java = std::env::var_os("JAVA_HOME")
+        .map(|home| {
+            PathBuf::from(home)
+                .join("bin")
+                .join(format!("java{}", std::env::consts::EXE_SUFFIX))
+        })
+        .filter(|java| java.is_file())
+        .unwrap_or_else(|| PathBuf::from("java"));
+    // `java -version` prints to stderr.
+    let output = Command::new(java).arg("-version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let version = String::from_utf8_lossy(&output.stderr);
+    version.lines().next().map(|line| line.trim().to_string())
+}
+
+#[cfg(target_os = "macos")]
+fn check_ios(report: &mut Report, installed_targets: Option<&HashSet<String>>) {
+    match command_stdout(Command::new("xcodebuild").arg("-version")) {
+        Some(version) => report.ok("Xcode", version.lines().next().unwrap_or_default()),
+        None => {
+            report.warn(
+                "Xcode was not found; iOS builds are unavailable",
+                "install Xcode from the App Store, then run `sudo xcode-select -s /Applications/Xcode.app`",
+            );
+            return;
+        }
+    }
+    match command_stdout(Command::new("xcodegen").arg("--version")) {
+        Some(version) => report.ok("XcodeGen", version),
+        None => report.warn(
+            "xcodegen was not found; `cargo tessera ios init` can't generate the Xcode project",
+            "run `brew install xcodegen`",
+        ),
+    }
+    if let Some(installed) = installed_targets {
+        let missing: Vec<&str> = IOS_TARGETS
+            .into_iter()
+            .filter(|triple| !installed.contains(*triple))
+            .collect();
+        if missing.is_empty() {
+            report.ok("iOS", "all Rust targets installed");
+        } else {
+            report.warn(
+                format!("Rust targets missing for iOS: {}", missing.join(", ")),
+                format!("run `rustup target add {}`", missing.join(" ")),
+            );
+        }
+    }
+}
+
+fn check_web(report: &mut Report, installed_targets: Option<&HashSet<String>>) {
+    let Some(installed) = installed_targets else {
+        return;
+    };
+    if installed.contains(WASM_TARGET) {
+        report.ok("Web", format!("{WASM_TARGET} installed"));
+    } else {
+        report.warn(
+            format!("Rust target {WASM_TARGET} is missing; web builds are unavailable"),
+            format!("run `rustup target add {WASM_TARGET}`"),
+        );
+    }
+}
+
+/// Windows are opened through Wayland or X11, whose client libraries are
+/// loaded at runtime.
+#[cfg(target_os = "linux")]
+fn check_display_libs(report: &mut Report) {
+    const WAYLAND_LIBS: [(&str, &str); 2] = [
+        ("libwayland-client.so.0", "libwayland-client0"),
+        ("libxkbcommon.so.0", "libxkbcommon0"),
+    ];
+    const X11_LIBS: [(&str, &str); 4] = [
+        ("libX11.so.6", "libx11-6"),
+        ("libXcursor.so.1", "libxcursor1"),
+        ("libXi.so.6", "libxi6"),
+        ("libxkbcommon-x11.so.0", "libxkbcommon-x11-0"),
+    ];
+
+    let wayland_session = std::env::var_os("WAYLAND_DISPLAY").is_some();
+    let x11_session = std::env::var_os("DISPLAY").is_some();
+    let missing_wayland = missing_libs(&WAYLAND_LIBS);
+    let missing_x11 = missing_libs(&X11_LIBS);
+
+    for (name, session, missing) in [
+        ("Wayland", wayland_session, &missing_wayland),
+        ("X11", x11_session, &missing_x11),
+    ] {
+        if missing.is_empty() {
+            report.ok(name, "client libraries found");
+            continue;
+        }
+        let libs: Vec<&str> = missing.iter().map(|(lib, _)| *lib).collect();
+        let packages: Vec<&str> = missing.iter().map(|(_, package)| *package).collect();
+        let message = format!("{name} libraries not found: {}", libs.join(", "));
+        let fix = format!(
+            "install {} (Debian/Ubuntu package names)",
+            packages.join(" ")
+        );
+        if session {
+            report.error(message, fix);
+        } else {
+            report.warn(message, fix);
+        }
+    }
+
+    if !wayland_session && !x11_session {
+        report.warn(
+            "neither WAYLAND_DISPLAY nor DISPLAY is set; app windows can't be opened",
+            "run Tessera apps from a graphical session",
+        );
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn missing_libs(libs: &[(&'static str, &'static str)]) -> Vec<(&'static str, &'static str)> {
+    libs.iter()
+        .copied()
+        // SAFETY: these are system client libraries whose initializers
+        // have no preconditions; they are unloaded right away.
+        .filter(|(lib, _)| unsafe { libloading::Library::new(lib) }.is_err())
+        .collect()
+}
+
+/// Checks the graphics drivers by asking wgpu for an adapter the way the
+/// renderer does.
+fn check_gpu(report: &mut Report) {
+    #[cfg(target_os = "linux")]
+    {
+        // SAFETY: the Vulkan loader's initializers have no preconditions.
+        if unsafe { libloading::Library::new("libvulkan.so.1") }.is_err() {
+            report.warn(
+                "the Vulkan loader (libvulkan.so.1) was not found; rendering falls back to OpenGL",
+                "install libvulkan1 and your GPU's Vulkan driver (e.g. mesa-vulkan-drivers)",
+            );
+        }
+    }
+
+    let mut instance_desc = wgpu::InstanceDescriptor::new_without_display_handle();
+    #[cfg(not(target_os = "windows"))]
+    {
+        instance_desc.backends = wgpu::Backends::all();
+    }
+    #[cfg(target_os = "windows")]
+    {
+        instance_desc.backends = wgpu::Backends::DX12;
+    }
+    let instance = wgpu::Instance::new(instance_desc);
+    let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+        power_preference: wgpu::PowerPreference::default(),
+        compatible_surface: None,
+        force_fallback_adapter: false,
+    }));
+    let adapter = match adapter {
+        Ok(adapter) => adapter,
+        Err(err) => {
+            report.error(
+                format!("no GPU adapter is available: {err}"),
+                gpu_driver_fix(),
+            );
+            return;
+        }
+    };
+    let info = adapter.get_info();
+    let driver = [info.driver.as_str(), info.driver_info.as_str()]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    report.ok(
+        "GPU",
+        format!("{} ({:?}, {driver})", info.name, info.backend),
+    );
+    if info.device_type == wgpu::DeviceType::Cpu {
+        report.warn(
+            format!("{} renders on the CPU; apps will be slow", info.name),
+            gpu_driver_fix(),
+        );
+    }
+}
+
+fn gpu_driver_fix() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "install the latest graphics driver from your GPU vendor; Tessera needs DirectX 12"
+    } else if cfg!(target_os = "macos") {
+        "update macOS; Tessera renders with Metal"
+    } else {
+        "install Mesa (mesa-vulkan-drivers) or your GPU vendor's Vulkan driver"
+    }
+}
+
+/// Runs `cmd`, returning its trimmed stdout if it succeeded.
+fn command_stdout(cmd: &mut Command) -> Option<String> {
+    let output = cmd.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Major and minor version from `rustc --version` output.
+fn rustc_version(output: &str) -> Option<(u32, u32)> {
+    let version = output.strip_prefix("rustc ")?.split_whitespace().next()?;
+    let mut parts = version.split(['.', '-']);
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rustc_versions() {
+        assert_eq!(
+            rustc_version("rustc 1.92.0-nightly (2a9bacf61 2025-10-01)"),
+            Some((1, 92))
+        );
+        assert_eq!(
+            rustc_version("rustc 1.85.1 (4eb161250 2025-03-15)"),
+            Some((1, 85))
+        );
+        assert_eq!(rustc_version("cargo 1.90.0"), None);
+    }
+}
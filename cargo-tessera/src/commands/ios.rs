@@ -0,0 +1,621 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::mpsc::channel,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result, anyhow, bail};
+use cargo_metadata::MetadataCommand;
+use cargo_mobile2::{
+    ChildHandle,
+    apple::{
+        config::{Config as AppleConfig, Raw as RawAppleConfig},
+        device::{self, Device},
+        target::{BuildConfig, Target},
+    },
+    config::app::{App, Raw as RawAppConfig},
+    env::Env,
+    opts::{NoiseLevel, Profile},
+    os::replace_path_separator,
+    target::TargetTrait,
+    util,
+};
+use handlebars::{
+    Context as TemplateContext, Handlebars, Helper, HelperResult, Output, RenderContext,
+    handlebars_helper,
+};
+use include_dir::{Dir, include_dir};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tessera_build::AssetBackend;
+
+use crate::{
+    color_check, output,
+    template::{write_template_dir, write_template_file},
+};
+
+use super::{
+    android::{Manifest, display_path, load_app_toml},
+    find_package_dir,
+};
+
+const IOS_PROJECT_DIR: &str = "gen/apple";
+const IOS_DEVICE_TARGET: &str = "aarch64-apple-ios";
+const XCODE_TEMPLATE_DIR: Dir<'_> =
+    include_dir!("$CARGO_MANIFEST_DIR/../tessera-mobile/templates/platforms/xcode");
+
+#[derive(Debug)]
+pub struct BuildOptions {
+    pub release: bool,
+    pub package: Option<String>,
+    pub simulator: bool,
+}
+
+#[derive(Debug)]
+pub struct DevOptions {
+    pub release: bool,
+    pub package: Option<String>,
+    pub device: Option<String>,
+}
+
+pub struct RustBuildOptions {
+    pub configuration: String,
+    pub platform: String,
+    pub sdk_root: PathBuf,
+    pub arches: Vec<String>,
+    pub package: Option<String>,
+}
+
+struct IosContext {
+    package_dir: Option<PathBuf>,
+    package_name: String,
+    lib_name: String,
+    release: bool,
+    target_dir: PathBuf,
+    config: AppleConfig,
+}
+
+/// `[package.metadata.tessera.ios]` of the app manifest.
+#[derive(Debug, Deserialize, Default)]
+struct IosManifestConfig {
+    development_team: Option<String>,
+    ios_version: Option<String>,
+}
+
+impl IosContext {
+    fn new(package: Option<String>, release: bool) -> Result<Self> {
+        let package_dir = package
+            .as_deref()
+            .and_then(|pkg| find_package_dir(pkg).ok());
+
+        let manifest = if let Some(dir) = &package_dir {
+            Manifest::load_from(dir)?
+        } else {
+            Manifest::load()?
+        };
+        let package_name = package.or(manifest.package_name()).ok_or_else(|| {
+            anyhow!(
+                "Unable to determine workspace package. Provide --package or run in a package directory."
+            )
+        })?;
+
+        let root_dir = package_dir
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .canonicalize()
+            .with_context(|| "Failed to resolve project root")?;
+        let ios_cfg = load_ios_manifest_config(&root_dir)?;
+        let app_toml = load_app_toml(&root_dir)?;
+        let target_dir = MetadataCommand::new()
+            .manifest_path(root_dir.join("Cargo.toml"))
+            .exec()
+            .context("Failed to resolve Cargo target directory")?
+            .target_directory
+            .into_std_path_buf();
+
+        let identifier = app_toml
+            .identifier
+            .clone()
+            .unwrap_or_else(|| format!("com.example.{package_name}"));
+        let lib_name = manifest
+            .lib_name()
+            .unwrap_or_else(|| package_name.replace('-', "_"));
+        let raw_app = RawAppConfig {
+            name: package_name.clone(),
+            lib_name: Some(lib_name.clone()),
+            stylized_name: None,
+            identifier: apple_identifier(&identifier),
+            asset_dir: None,
+            template_pack: None,
+        };
+        let app = App::from_raw(root_dir, raw_app).context("Failed to build iOS app metadata")?;
+
+        // Simulator builds are not signed, so a missing team only matters
+        // once a device build asks Xcode for provisioning.
+        let development_team = ios_cfg.development_team.or_else(|| {
+            RawAppleConfig::detect()
+                .ok()
+                .and_then(|raw| raw.development_team)
+        });
+        let raw_apple = RawAppleConfig {
+            development_team,
+            project_dir: Some(IOS_PROJECT_DIR.to_string()),
+            ios_version: ios_cfg.ios_version,
+            ..RawAppleConfig::default()
+        };
+        let config =
+            AppleConfig::from_raw(app, Some(raw_apple)).context("Failed to build iOS config")?;
+
+        Ok(Self {
+            package_dir,
+            package_name,
+            lib_name,
+            release,
+            target_dir,
+            config,
+        })
+    }
+
+    fn profile(&self) -> Profile {
+        if self.release {
+            Profile::Release
+        } else {
+            Profile::Debug
+        }
+    }
+
+    fn ensure_project(&self) -> Result<()> {
+        if !self.config.project_dir_exists() {
+            return Err(anyhow!(
+                "iOS project not initialized. Run `cargo tessera ios init` first."
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn load_ios_manifest_config(root_dir: &Path) -> Result<IosManifestConfig> {
+    let path = root_dir.join("Cargo.toml");
+    let contents =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let manifest: toml::Value = toml::from_str(&contents).context("Failed to parse Cargo.toml")?;
+    let Some(ios) = manifest
+        .get("package")
+        .and_then(|package| package.get("metadata"))
+        .and_then(|metadata| metadata.get("tessera"))
+        .and_then(|tessera| tessera.get("ios"))
+    else {
+        return Ok(IosManifestConfig::default());
+    };
+    ios.clone()
+        .try_into()
+        .context("Failed to parse [package.metadata.tessera.ios]")
+}
+
+fn run_ios_color_check(ctx: &IosContext, target: &str) -> Result<()> {
+    color_check::run(color_check::CheckOptions {
+        target_selection: color_check::TargetSelection::lib_only(),
+        ..color_check::CheckOptions::new(Some(&ctx.package_name), Some(target))
+    })
+}
+
+/// Bundle identifiers only allow letters, digits, `-` and `.`.
+fn apple_identifier(identifier: &str) -> String {
+    identifier
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// The simulator target matching the host CPU.
+fn simulator_target() -> &'static Target<'static> {
+    let arch = if cfg!(target_arch = "aarch64") {
+        "arm64-sim"
+    } else {
+        "x86_64"
+    };
+    Target::for_arch(arch).expect("iOS simulator target is always defined")
+}
+
+fn device_target() -> &'static Target<'static> {
+    Target::all()
+        .values()
+        .find(|target| target.triple == IOS_DEVICE_TARGET)
+        .expect("iOS device target is always defined")
+}
+
+pub fn init(skip_targets_install: bool) -> Result<()> {
+    let ctx = IosContext::new(None, false)?;
+    let project_exists = ctx.config.project_dir_exists();
+    if project_exists {
+        output::warn(format!(
+            "iOS project already exists at {}",
+            display_path(&ctx.config.project_dir())
+        ));
+    }
+
+    if !skip_targets_install {
+        Target::install_all().map_err(|err| anyhow!("Failed to install iOS targets: {err}"))?;
+    }
+
+    let project_dir = ctx.config.project_dir();
+    let mut handlebars = Handlebars::new();
+    handlebars.register_escape_fn(handlebars::no_escape);
+    register_helpers(&mut handlebars);
+    let data = build_xcode_template_data(&ctx)?;
+
+    if !project_exists {
+        write_template_dir(&XCODE_TEMPLATE_DIR, &project_dir, &handlebars, &data)?;
+        // Assets are embedded into the Rust library; Xcode only needs the
+        // folder that `project.yml` references to exist.
+        let asset_dir = project_dir.join("assets");
+        fs::create_dir_all(&asset_dir)
+            .with_context(|| format!("Failed to create assets dir {}", asset_dir.display()))?;
+    } else {
+        write_template_file(
+            &XCODE_TEMPLATE_DIR,
+            Path::new("project.yml.hbs"),
+            &project_dir,
+            &handlebars,
+            &data,
+        )?;
+    }
+
+    let status = Command::new("xcodegen")
+        .args(["generate", "--no-env", "--spec"])
+        .arg(project_dir.join("project.yml"))
+        .status()
+        .context("Failed to run xcodegen. Install it with `brew install xcodegen`.")?;
+    if !status.success() {
+        bail!("xcodegen failed to generate the Xcode project");
+    }
+
+    output::status(
+        "Generated",
+        format!("iOS project at {}", display_path(&project_dir)),
+    );
+    Ok(())
+}
+
+fn register_helpers(handlebars: &mut Handlebars<'static>) {
+    handlebars_helper!(join: |list: Vec<String>| list.join(", "));
+
+    handlebars_helper!(snake_case: |s: str| {
+        s.chars()
+            .map(|c| {
+                if c == '-' || c == ' ' {
+                    '_'
+                } else {
+                    c.to_ascii_lowercase()
+                }
+            })
+            .collect::<String>()
+    });
+
+    handlebars.register_helper("join", Box::new(join));
+    handlebars.register_helper("snake-case", Box::new(snake_case));
+    handlebars.register_helper("prefix-path", Box::new(prefix_path));
+}
+
+/// Prefixes a path relative to the package root with the way back from the
+/// Xcode project.
+fn prefix_path(
+    helper: &Helper<'_>,
+    _: &Handlebars<'_>,
+    ctx: &TemplateContext,
+    _: &mut RenderContext<'_, '_>,
+    out: &mut dyn Output,
+) -> HelperResult {
+    let path = helper
+        .param(0)
+        .and_then(|param| param.value().as_str())
+        .unwrap_or_default();
+    let root_dir_rel = ctx
+        .data()
+        .get("root-dir-rel")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    out.write(&format!("{root_dir_rel}/{path}"))?;
+    Ok(())
+}
+
+fn build_xcode_template_data(ctx: &IosContext) -> Result<Value> {
+    let root_dir_rel = util::relativize_path(ctx.config.app().root_dir(), ctx.config.project_dir());
+    let root_dir_rel = replace_path_separator(root_dir_rel.into_os_string())
+        .to_string_lossy()
+        .into_owned();
+
+    let app = serde_json::to_value(ctx.config.app())
+        .context("Failed to serialize app metadata for templates")?;
+    let apple = serde_json::to_value(&ctx.config)
+        .context("Failed to serialize iOS config for templates")?;
+    let simulator = simulator_target();
+    let valid_archs = if cfg!(target_arch = "x86_64") {
+        vec!["arm64", "x86_64"]
+    } else {
+        vec!["arm64"]
+    };
+
+    Ok(json!({
+        "app": app,
+        "apple": apple,
+        "root-dir-rel": root_dir_rel,
+        "file-groups": [format!("{root_dir_rel}/src")],
+        "ios-valid-archs": valid_archs,
+        "ios-sim-arch": simulator.triple,
+    }))
+}
+
+pub fn build(opts: BuildOptions) -> Result<()> {
+    let ctx = IosContext::new(opts.package, opts.release)?;
+    ctx.ensure_project()?;
+    let target = if opts.simulator {
+        simulator_target()
+    } else {
+        device_target()
+    };
+    run_ios_color_check(&ctx, target.triple)?;
+
+    output::status(
+        "Building",
+        format!(
+            "iOS app ({}, target: {}, release: {})",
+            ctx.package_name,
+            target.triple,
+            if ctx.release { "yes" } else { "no" }
+        ),
+    );
+
+    let env = Env::new()?;
+    let build_config = if opts.simulator {
+        BuildConfig::new().skip_codesign()
+    } else {
+        BuildConfig::new().allow_provisioning_updates()
+    };
+    target
+        .build(
+            None,
+            &ctx.config,
+            &env,
+            NoiseLevel::Polite,
+            ctx.profile(),
+            build_config,
+        )
+        .map_err(|err| anyhow!("Xcode build failed: {err}"))?;
+
+    output::status("Finished", "iOS build");
+    output::status("Package", ctx.package_name.clone());
+    output::status("Project", display_path(&ctx.config.workspace_path()));
+    Ok(())
+}
+
+pub fn dev(opts: DevOptions) -> Result<()> {
+    let ctx = IosContext::new(opts.package, opts.release)?;
+    ctx.ensure_project()?;
+    let device_query = opts.device.as_deref().ok_or_else(|| {
+        anyhow!("--device <id or name> is required for ios dev (a device or simulator name)")
+    })?;
+    let env = Env::new()?;
+    let device = find_device(&env, device_query)?;
+    run_ios_color_check(&ctx, device.target().triple)?;
+
+    output::status(
+        "Running",
+        format!(
+            "iOS app {} on {} (release: {})",
+            ctx.package_name,
+            device.name(),
+            if ctx.release { "yes" } else { "no" }
+        ),
+    );
+    output::status("Watching", "for file changes");
+
+    let (tx, rx) = channel();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+        if let Ok(event) = res
+            && matches!(
+                event.kind,
+                EventKind::Modify(_) | EventKind::Create(_) | EventKind::Remove(_)
+            )
+        {
+            let _ = tx.send(());
+        }
+    })?;
+
+    let watch_dir = ctx
+        .package_dir
+        .clone()
+        .unwrap_or_else(|| PathBuf::from("."));
+    let src_path = watch_dir.join("src");
+    if !src_path.exists() {
+        return Err(anyhow!(
+            "Source directory not found: {}",
+            src_path.display()
+        ));
+    }
+    watcher.watch(&src_path, RecursiveMode::Recursive)?;
+    for file in [
+        "Cargo.toml",
+        "build.rs",
+        "tessera-app.toml",
+        "tessera-config.toml",
+    ] {
+        let path = watch_dir.join(file);
+        if path.exists() {
+            watcher.watch(&path, RecursiveMode::NonRecursive)?;
+        }
+    }
+
+    let profile = ctx.profile();
+    let mut run_child: Option<ChildHandle> = None;
+    let mut pending_change = true;
+    let mut last_change = Instant::now() - Duration::from_secs(1);
+    let debounce_window = Duration::from_millis(300);
+
+    loop {
+        match rx.recv_timeout(Duration::from_millis(100)) {
+            Ok(_) => {
+                pending_change = true;
+                last_change = Instant::now();
+
+                if let Some(active_run) = run_child.take() {
+                    output::status("Canceling", "in-progress iOS deploy due to changes");
+                    let _ = active_run.kill();
+                    let _ = active_run.wait();
+                }
+            }
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+            Err(_) => break,
+        }
+
+        if pending_change && run_child.is_none() && last_change.elapsed() >= debounce_window {
+            while rx.try_recv().is_ok() {}
+
+            output::status("Deploying", "building and installing on device");
+
+            match device.run(&ctx.config, &env, NoiseLevel::Polite, true, profile) {
+                Ok(child) => {
+                    run_child = Some(child);
+                    pending_change = false;
+                }
+                Err(err) => {
+                    output::error(format!("failed to start deploy: {err}"));
+                    pending_change = false;
+                }
+            }
+        }
+
+        if let Some(active_run) = run_child.take() {
+            match active_run.try_wait() {
+                Ok(Some(output)) => {
+                    if !output.status.success() {
+                        output::warn("app exited with an error; waiting for changes");
+                    }
+                }
+                Ok(None) => {
+                    run_child = Some(active_run);
+                }
+                Err(err) => {
+                    output::warn(format!("failed to check deploy status: {err}"));
+                }
+            }
+        }
+    }
+
+    if let Some(run) = run_child {
+        let _ = run.kill();
+        let _ = run.wait();
+    }
+
+    Ok(())
+}
+
+/// Finds a connected device by id or name, or else a simulator by name and
+/// opens it.
+fn find_device<'a>(env: &Env, query: &str) -> Result<Device<'a>> {
+    let devices = device::list_devices(env).unwrap_or_else(|err| {
+        output::warn(format!("failed to list iOS devices: {err}"));
+        Default::default()
+    });
+    if let Some(device) = devices
+        .into_iter()
+        .find(|device| device.id() == query || device.name() == query)
+    {
+        return Ok(device);
+    }
+
+    let simulator = device::list_simulators(env)
+        .map_err(|err| anyhow!("Failed to list simulators: {err}"))?
+        .into_iter()
+        .find(|simulator| simulator.name() == query)
+        .ok_or_else(|| {
+            anyhow!(
+                "Device or simulator `{query}` not found. Run `xcrun devicectl list devices` or `xcrun simctl list devices` to list targets."
+            )
+        })?;
+    simulator
+        .start_detached(env)
+        .context("Failed to open the simulator")?;
+    Ok(simulator.into())
+}
+
+/// Builds the static library Xcode links, for each architecture it asks for.
+///
+/// Runs from the project's "Build Rust Code" phase with the Xcode build
+/// settings passed as arguments.
+pub fn rust_build(opts: RustBuildOptions) -> Result<()> {
+    let release = opts.configuration.eq_ignore_ascii_case("release");
+    let ctx = IosContext::new(opts.package, release)?;
+    let profile = ctx.profile();
+
+    let include_dir = opts.sdk_root.join("usr/include");
+    if !include_dir.is_dir() {
+        bail!(
+            "SDK root {} has no usr/include directory",
+            opts.sdk_root.display()
+        );
+    }
+    let isysroot = format!("-isysroot {}", opts.sdk_root.display());
+    let simulator = opts.platform.contains("Simulator");
+    let targets = if simulator {
+        vec![simulator_target()]
+    } else {
+        opts.arches
+            .iter()
+            .map(|arch| {
+                Target::for_arch(arch)
+                    .ok_or_else(|| anyhow!("Unsupported iOS architecture `{arch}`"))
+            })
+            .collect::<Result<Vec<_>>>()?
+    };
+
+    let lib_file = format!("lib{}.a", ctx.lib_name);
+    for target in targets {
+        output::status(
+            "Compiling",
+            format!("{} for {}", ctx.package_name, target.triple),
+        );
+        let env_triple = target.triple.replace('-', "_");
+        let mut cmd = Command::new("cargo");
+        cmd.current_dir(ctx.config.app().root_dir())
+            .args(["rustc", "--lib", "--crate-type", "staticlib"])
+            .args(["--package", &ctx.package_name, "--target", target.triple])
+            .env(format!("CFLAGS_{env_triple}"), &isysroot)
+            .env(format!("CXXFLAGS_{env_triple}"), &isysroot)
+            .env(format!("OBJC_INCLUDE_PATH_{env_triple}"), &include_dir)
+            .env("TESSERA_ASSET_BACKEND", AssetBackend::Embed.as_str());
+        if release {
+            cmd.arg("--release");
+        }
+        let status = cmd.status().context("Failed to run cargo rustc")?;
+        if !status.success() {
+            bail!("Rust build failed for {}", target.triple);
+        }
+
+        let built = ctx
+            .target_dir
+            .join(target.triple)
+            .join(profile.as_str())
+            .join(&lib_file);
+        let dest_dir = ctx
+            .config
+            .project_dir()
+            .join("Sources")
+            .join(target.triple)
+            .join(&opts.configuration);
+        fs::create_dir_all(&dest_dir)
+            .with_context(|| format!("Failed to create {}", dest_dir.display()))?;
+        fs::copy(&built, dest_dir.join(&lib_file))
+            .with_context(|| format!("Failed to copy {}", built.display()))?;
+    }
+    Ok(())
+}
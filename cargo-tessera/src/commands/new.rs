@@ -1,15 +1,172 @@
-use std::{fs, path::Path, time::Instant};
+use std::{
+    fmt, fs,
+    io::IsTerminal,
+    path::{Path, PathBuf},
+    time::Instant,
+};
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, anyhow};
+use clap::ValueEnum;
 use handlebars::Handlebars;
 use include_dir::{Dir, include_dir};
-use inquire::{Select as ChoicePrompt, Text, error::CustomUserError, validator::Validation};
+use inquire::{
+    Confirm, Select as ChoicePrompt, Text, error::CustomUserError, validator::Validation,
+};
 use serde_json::json;
 
 use crate::{output, template::write_template_dir_at};
 
 static TEMPLATES: Dir = include_dir!("$CARGO_MANIFEST_DIR/templates");
 
+/// Template directories that aren't project templates.
+const NON_PROJECT_DIRS: [&str; 2] = ["plugin", "shared"];
+/// Template for a workspace of reusable components instead of an app.
+const LIBRARY_TEMPLATE: &str = "component-library";
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum ComponentsChoice {
+    /// Material 3 components from tessera-components
+    Material,
+    /// Only tessera-ui; bring your own components
+    None,
+}
+
+impl fmt::Display for ComponentsChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Material => "tessera-components (Material 3)",
+            Self::None => "none",
+        })
+    }
+}
+
+/// Project options given on the command line. Options left out are asked for
+/// interactively, or take their defaults with `yes` or without a terminal.
+#[derive(Debug, Default)]
+pub struct NewOptions {
+    pub router: Option<bool>,
+    pub components: Option<ComponentsChoice>,
+    pub workspace: Option<bool>,
+    pub ci: Option<bool>,
+    pub identifier: Option<String>,
+    pub yes: bool,
+}
+
+struct ProjectOptions {
+    router: bool,
+    components: ComponentsChoice,
+    workspace: bool,
+    ci: bool,
+    identifier: String,
+}
+
+impl NewOptions {
+    fn resolve(self, name: &str, template: &str) -> Result<ProjectOptions> {
+        let interactive = !self.yes && std::io::stdin().is_terminal();
+        let library = template == LIBRARY_TEMPLATE;
+        let default_identifier = format!("com.tessera.{}", name.replace('-', "_"));
+
+        let ask = |value: Option<bool>, prompt: &str, help: &str, default: bool| match value {
+            Some(value) => Ok(value),
+            None if interactive => Confirm::new(prompt)
+                .with_help_message(help)
+                .with_default(default)
+                .prompt()
+                .map_err(anyhow::Error::from),
+            None => Ok(default),
+        };
+
+        let components = match self.components {
+            Some(components) => components,
+            None if interactive && !library => ChoicePrompt::new(
+                "Components package",
+                vec![ComponentsChoice::Material, ComponentsChoice::None],
+            )
+            .prompt()?,
+            None => ComponentsChoice::Material,
+        };
+        if library && components == ComponentsChoice::None {
+            anyhow::bail!("The `{LIBRARY_TEMPLATE}` template builds on tessera-components");
+        }
+        let router = if library {
+            false
+        } else {
+            ask(
+                self.router,
+                "Add the shard router?",
+                "tessera-shard pages with a home destination",
+                false,
+            )?
+        };
+        // Component libraries are always workspaces of the library and its
+        // pipelines crate.
+        let workspace = library
+            || ask(
+                self.workspace,
+                "Use a workspace layout?",
+                "puts the app under crates/ so more crates can join it",
+                false,
+            )?;
+        let ci = ask(
+            self.ci,
+            "Add GitHub Actions CI?",
+            "formatting, clippy, tests and a wasm check",
+            false,
+        )?;
+        let identifier = match self.identifier {
+            Some(identifier) => {
+                validate_identifier(&identifier).map_err(|err| anyhow!(err))?;
+                identifier
+            }
+            None if interactive && !library => Text::new("App identifier")
+                .with_help_message("Android package / bundle id, e.g. com.example.app")
+                .with_default(&default_identifier)
+                .with_validator(|input: &str| -> Result<Validation, CustomUserError> {
+                    Ok(match validate_identifier(input) {
+                        Ok(()) => Validation::Valid,
+                        Err(err) => Validation::Invalid(err.into()),
+                    })
+                })
+                .prompt()?,
+            None => default_identifier,
+        };
+
+        Ok(ProjectOptions {
+            router,
+            components,
+            workspace,
+            ci,
+            identifier,
+        })
+    }
+}
+
+/// Checks an app identifier, which Android uses as its package name.
+fn validate_identifier(identifier: &str) -> Result<(), String> {
+    let segments: Vec<&str> = identifier.split('.').collect();
+    if segments.len() < 2 {
+        return Err(format!(
+            "`{identifier}` needs at least two segments, e.g. com.example.app"
+        ));
+    }
+    for segment in segments {
+        let starts_with_letter = segment
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic());
+        if !starts_with_letter
+            || !segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            return Err(format!(
+                "`{segment}` in `{identifier}` must start with a letter and contain only letters, digits and '_'"
+            ));
+        }
+    }
+    Ok(())
+}
+
 /// Prompt for project name interactively with validation
 pub fn prompt_project_name() -> Result<String> {
     let validator = |input: &str| -> Result<Validation, CustomUserError> {
@@ -58,32 +215,47 @@ pub fn prompt_project_name() -> Result<String> {
     Ok(name.trim().to_string())
 }
 
-pub fn execute(name: &str, template: &str) -> Result<()> {
+pub fn execute(name: &str, template: &str, options: NewOptions) -> Result<()> {
     let project_dir = Path::new(name);
 
     // Check if directory already exists
     if project_dir.exists() {
         anyhow::bail!("Directory '{}' already exists", name);
     }
+    if NON_PROJECT_DIRS.contains(&template) {
+        anyhow::bail!("Template '{}' is not a project template", template);
+    }
+    let options = options.resolve(name, template)?;
 
     // Create project directory
     fs::create_dir_all(project_dir).context("Failed to create project directory")?;
 
     // Generate project from template
     let started = Instant::now();
-    generate_from_template(project_dir, template)?;
+    generate_from_template(project_dir, template, &options)?;
 
     let duration = output::format_duration(started.elapsed());
+    let kind = if template == LIBRARY_TEMPLATE {
+        "component library"
+    } else {
+        "app"
+    };
     output::status(
         "Created",
         format!(
-            "tessera app `{}` (template `{}`) in {}",
-            name, template, duration
+            "tessera {} `{}` (template `{}`) in {}",
+            kind, name, template, duration
         ),
     );
     output::note("Next steps:");
     output::step(format!("cd {}", name));
-    output::step("cargo tessera dev");
+    if template == LIBRARY_TEMPLATE {
+        output::step(format!("cargo tessera preview -p {name}"));
+    } else if options.workspace {
+        output::step(format!("cargo tessera dev -p {name}"));
+    } else {
+        output::step("cargo tessera dev");
+    }
 
     Ok(())
 }
@@ -93,7 +265,7 @@ pub fn select_template_interactive() -> Result<String> {
     let templates: Vec<String> = TEMPLATES
         .dirs()
         .filter_map(|d| d.path().file_name()?.to_str().map(|s| s.to_string()))
-        .filter(|name| name != "plugin")
+        .filter(|name| !NON_PROJECT_DIRS.contains(&name.as_str()))
         .collect();
 
     if templates.is_empty() {
@@ -111,14 +283,16 @@ pub fn select_template_interactive() -> Result<String> {
     Ok(selection)
 }
 
-fn generate_from_template(project_dir: &Path, template: &str) -> Result<()> {
-    if template == "plugin" {
-        anyhow::bail!("Template '{}' is reserved for plugins", template);
-    }
+fn generate_from_template(
+    project_dir: &Path,
+    template: &str,
+    options: &ProjectOptions,
+) -> Result<()> {
     // Find template directory
     let template_dir = TEMPLATES
         .get_dir(template)
         .ok_or_else(|| anyhow::anyhow!("Template '{}' not found", template))?;
+    let library = template == LIBRARY_TEMPLATE;
 
     let project_name = project_dir
         .file_name()
@@ -134,14 +308,68 @@ fn generate_from_template(project_dir: &Path, template: &str) -> Result<()> {
     let data = json!({
         "project_name": project_name,
         "project_name_snake": project_name_snake,
+        "project_name_pascal": pascal_case(&project_name),
         "lib_name": lib_name,
+        "identifier": options.identifier,
+        "material": options.components == ComponentsChoice::Material,
+        "router": options.router,
+        "app": !library,
     });
 
-    write_template_dir_at(
-        template_dir,
-        project_dir,
-        template_dir.path(),
-        &handlebars,
-        &data,
-    )
+    // Apps in a workspace live under `crates/`; the library template lays out
+    // its own workspace.
+    let crate_dir: PathBuf = if options.workspace && !library {
+        project_dir.join("crates").join(&project_name)
+    } else {
+        project_dir.to_path_buf()
+    };
+    let mut layers = vec![(template_dir, crate_dir.as_path())];
+    if options.router {
+        layers.push((shared_dir("router")?, crate_dir.as_path()));
+    }
+    if options.workspace && !library {
+        layers.push((shared_dir("workspace")?, project_dir));
+    }
+    if options.ci {
+        layers.push((shared_dir("ci")?, project_dir));
+    }
+    // Later layers replace files of earlier ones, e.g. the router's `app.rs`.
+    for (dir, out_dir) in layers {
+        write_template_dir_at(dir, out_dir, dir.path(), &handlebars, &data)?;
+    }
+    Ok(())
+}
+
+fn shared_dir(name: &str) -> Result<&'static Dir<'static>> {
+    TEMPLATES
+        .get_dir(Path::new("shared").join(name))
+        .ok_or_else(|| anyhow!("Shared template '{name}' not found"))
+}
+
+/// `my-widgets` → `MyWidgets`, for type names derived from the project name.
+fn pascal_case(name: &str) -> String {
+    name.split(['-', '_'])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identifiers_need_reverse_domain_segments() {
+        assert!(validate_identifier("com.example.my_app").is_ok());
+        assert!(validate_identifier("app").is_err());
+        assert!(validate_identifier("com.1example").is_err());
+        assert!(validate_identifier("com.my-app").is_err());
+        assert_eq!(pascal_case("my-widgets_kit"), "MyWidgetsKit");
+    }
 }
@@ -0,0 +1,226 @@
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result, anyhow, bail};
+use cargo_metadata::{MetadataCommand, Package};
+
+use crate::output;
+
+use super::dev::{self, DevTarget};
+
+/// Package name of the generated preview window crate.
+const HOST_PACKAGE: &str = "tessera-preview-host";
+/// `TESSERA_HOT_RELOAD` value that makes reloads only pick up new previews.
+const PREVIEWS_MODE: &str = "previews";
+
+pub struct PreviewOptions<'a> {
+    pub component: Option<&'a str>,
+    pub package: Option<&'a str>,
+    pub verbose: bool,
+    pub release: bool,
+}
+
+/// Opens a window listing the package's `#[preview]` components.
+///
+/// Previews are collected from the package library, so a small host crate
+/// depending on it is generated under the target directory and run through
+/// the hot reloading dev loop.
+pub fn execute(options: PreviewOptions<'_>) -> Result<()> {
+    let metadata = MetadataCommand::new()
+        .no_deps()
+        .exec()
+        .context("Failed to read Cargo metadata for previews")?;
+    let package = match options.package {
+        Some(name) => metadata
+            .packages
+            .iter()
+            .find(|candidate| candidate.name.as_ref() == name)
+            .ok_or_else(|| anyhow!("Cargo package `{name}` was not found"))?,
+        None => metadata.root_package().ok_or_else(|| {
+            anyhow!("Unable to determine the package to preview. Provide --package.")
+        })?,
+    };
+    let package_name: &str = package.name.as_ref();
+    if !package.targets.iter().any(|target| target.is_lib()) {
+        bail!("Package `{package_name}` has no library; previews are collected from it");
+    }
+    let package_dir = package
+        .manifest_path
+        .parent()
+        .ok_or_else(|| anyhow!("Package manifest has no parent directory"))?
+        .as_std_path()
+        .to_path_buf();
+
+    let target_dir = metadata.target_directory.as_std_path();
+    let host_dir = target_dir.join("tessera-preview").join(package_name);
+    write_host(&host_dir, package, &package_dir)?;
+    // Start from the workspace's lockfile so the host resolves the same
+    // versions and reuses their build artifacts.
+    let lockfile = metadata.workspace_root.as_std_path().join("Cargo.lock");
+    let host_lockfile = host_dir.join("Cargo.lock");
+    if lockfile.is_file() && !host_lockfile.exists() {
+        fs::copy(&lockfile, &host_lockfile)
+            .with_context(|| format!("Failed to copy {}", lockfile.display()))?;
+    }
+
+    output::status("Previewing", format!("`{package_name}`"));
+    let manifest_path = host_dir.join("Cargo.toml");
+    dev::run(
+        DevTarget {
+            package: None,
+            manifest_path: Some(&manifest_path),
+            target_dir: Some(target_dir),
+            watch_dir: package_dir,
+            app_args: options.component.map(str::to_string).into_iter().collect(),
+            hot_reload_mode: PREVIEWS_MODE,
+            color_check: false,
+        },
+        options.verbose,
+        options.release,
+        None,
+        false,
+        false,
+        false,
+        None,
+    )
+}
+
+/// Writes the host crate, leaving unchanged files alone so they don't
+/// trigger rebuilds.
+fn write_host(host_dir: &Path, package: &Package, package_dir: &Path) -> Result<()> {
+    let tessera_ui = package
+        .dependencies
+        .iter()
+        .find(|dep| dep.name == "tessera-ui")
+        .ok_or_else(|| anyhow!("Package `{}` doesn't depend on tessera-ui", package.name))?;
+    let tessera_ui_path = tessera_ui.path.as_ref().map(|path| path.as_std_path());
+    let source = |name: &str| -> String {
+        match package.dependencies.iter().find(|dep| dep.name == name) {
+            Some(dep) => match &dep.path {
+                Some(path) => format!("path = {}", toml_string(path.as_str())),
+                None => format!("version = {}", toml_string(&dep.req.to_string())),
+            },
+            // Take crates the package doesn't use from where tessera-ui comes
+            // from.
+            None => match tessera_ui_path.and_then(Path::parent) {
+                Some(dir) => format!("path = {}", toml_string(&dir.join(name).to_string_lossy())),
+                None => format!("version = {}", toml_string(&tessera_ui.req.to_string())),
+            },
+        }
+    };
+
+    let manifest = format!(
+        r#"# Generated by `cargo tessera preview`; changes are overwritten.
+[package]
+name = "{HOST_PACKAGE}"
+version = "0.0.0"
+edition = "2024"
+publish = false
+
+[[bin]]
+name = "tessera-preview"
+path = "src/main.rs"
+
+[features]
+hot-reload = ["dep:tessera-dylib"]
+
+[dependencies]
+previewed = {{ package = {package}, path = {package_dir} }}
+tessera-ui = {{ {tessera_ui} }}
+tessera-components = {{ {tessera_components}, features = ["preview-host"] }}
+tessera-dylib = {{ {tessera_dylib}, optional = true }}
+
+[workspace]
+"#,
+        package = toml_string(package.name.as_ref()),
+        package_dir = toml_string(&package_dir.to_string_lossy()),
+        tessera_ui = source("tessera-ui"),
+        tessera_components = source("tessera-components"),
+        tessera_dylib = source("tessera-dylib"),
+    );
+    let lib = r#"//! Generated by `cargo tessera preview`; changes are overwritten.
+
+// Links the previewed package so its `#[preview]` registrations are
+// collected, including from hot reloaded copies of this library.
+use previewed as _;
+"#;
+    let packages: String = preview_packages(package)?
+        .iter()
+        .map(|path| format!("\n    .package(previewed::{path})"))
+        .collect();
+    let main = format!(
+        r#"//! Generated by `cargo tessera preview`; changes are overwritten.
+
+use tessera_components::{{ComponentsPackage, preview_host::preview_host}};
+use tessera_preview_host as _;
+use tessera_ui::{{EntryPoint, renderer::TesseraConfig}};
+
+fn main() {{
+    let initial_preview = std::env::args().nth(1);
+    let entry = EntryPoint::new(move || {{
+        let mut host = preview_host();
+        if let Some(name) = &initial_preview {{
+            host = host.initial_preview(name.clone());
+        }}
+    }})
+    .package(ComponentsPackage){packages}
+    .config(TesseraConfig {{
+        window_title: {title:?}.into(),
+        ..Default::default()
+    }});
+    if let Err(err) = entry.run_desktop() {{
+        eprintln!("Preview window failed to run: {{err}}");
+    }}
+}}
+"#,
+        title = format!("{} previews", package.name),
+    );
+
+    write_if_changed(&host_dir.join("Cargo.toml"), &manifest)?;
+    write_if_changed(&host_dir.join("src").join("lib.rs"), lib)?;
+    write_if_changed(&host_dir.join("src").join("main.rs"), &main)
+}
+
+/// Packages listed in `[package.metadata.tessera.preview]`, as paths inside
+/// the previewed library.
+fn preview_packages(package: &Package) -> Result<Vec<String>> {
+    let Some(packages) = package
+        .metadata
+        .get("tessera")
+        .and_then(|tessera| tessera.get("preview"))
+        .and_then(|preview| preview.get("packages"))
+    else {
+        return Ok(Vec::new());
+    };
+    let packages: Vec<String> = serde_json::from_value(packages.clone())
+        .context("Failed to parse [package.metadata.tessera.preview] packages")?;
+    for path in &packages {
+        let is_path = path.split("::").all(|segment| {
+            segment
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        });
+        if !is_path {
+            bail!("Preview package `{path}` is not a path like `MyPackage` or `module::MyPackage`");
+        }
+    }
+    Ok(packages)
+}
+
+fn write_if_changed(path: &Path, contents: &str) -> Result<()> {
+    if fs::read_to_string(path).is_ok_and(|current| current == contents) {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn toml_string(value: &str) -> String {
+    toml::Value::String(value.to_string()).to_string()
+}
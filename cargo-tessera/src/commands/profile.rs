@@ -0,0 +1,209 @@
+use std::{
+    fs,
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::{Context, Result, bail};
+use tessera_build::AssetBackend;
+
+use crate::output;
+
+use super::profiling;
+
+/// Perfetto only loads `?url=` traces from this address.
+const PERFETTO_ADDR: (&str, u16) = ("127.0.0.1", 9001);
+const PERFETTO_ORIGIN: &str = "https://ui.perfetto.dev";
+
+pub struct ProfileOptions<'a> {
+    pub package: Option<&'a str>,
+    pub debug: bool,
+    pub output: &'a Path,
+    pub open: bool,
+}
+
+/// Runs the app with profiling enabled, then summarizes the recording and
+/// opens its trace in Perfetto.
+pub fn execute(options: ProfileOptions<'_>) -> Result<()> {
+    let trace_path = options.output.to_path_buf();
+    let records_path = trace_path.with_extension("jsonl");
+    if let Some(parent) = trace_path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    // Stale recordings would be mistaken for this run's if the app fails to
+    // start.
+    for path in [&trace_path, &records_path] {
+        if path.exists() {
+            fs::remove_file(path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+    }
+
+    let profile = if options.debug { "debug" } else { "release" };
+    match options.package {
+        Some(package) => output::status("Profiling", format!("`{package}` ({profile})")),
+        None => output::status("Profiling", format!("app ({profile})")),
+    }
+    output::note("close the app window to finish recording");
+
+    let mut cmd = Command::new("cargo");
+    cmd.arg("run");
+    if !options.debug {
+        cmd.arg("--release");
+    }
+    if let Some(package) = options.package {
+        cmd.arg("-p").arg(package);
+    }
+    cmd.arg("--features")
+        .arg("tessera-ui/profiling")
+        .env("TESSERA_PROFILING_OUTPUT", absolute(&records_path)?)
+        .env("TESSERA_PROFILING_TRACE", absolute(&trace_path)?)
+        .env("TESSERA_ASSET_BACKEND", AssetBackend::Embed.as_str());
+    let status = cmd.status().context("Failed to run cargo run")?;
+    if !trace_path.is_file() {
+        if !status.success() {
+            bail!("The app failed to start ({status})");
+        }
+        bail!(
+            "No trace was recorded at {}; does the app use tessera-ui's renderer?",
+            trace_path.display()
+        );
+    }
+    if !status.success() {
+        output::warn(format!("the app exited with {status}"));
+    }
+
+    finish_trace(&trace_path)?;
+    if records_path.is_file()
+        && let Err(err) = profiling::analyze(&records_path, false, 5, 1, true, None)
+    {
+        output::warn(format!("failed to summarize the recording: {err:#}"));
+    }
+    output::status("Trace", trace_path.display().to_string());
+    output::status("Records", records_path.display().to_string());
+
+    if options.open {
+        open_in_perfetto(&trace_path)?;
+    } else {
+        output::note(format!(
+            "open the trace at {PERFETTO_ORIGIN} or chrome://tracing"
+        ));
+    }
+    Ok(())
+}
+
+fn absolute(path: &Path) -> Result<PathBuf> {
+    std::path::absolute(path).with_context(|| format!("Failed to resolve {}", path.display()))
+}
+
+/// Closes the streamed JSON array so strict JSON tools can read the trace.
+fn finish_trace(path: &Path) -> Result<()> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let finished = close_json_array(&contents);
+    if finished.len() != contents.len() {
+        fs::write(path, finished).with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(())
+}
+
+fn close_json_array(contents: &str) -> String {
+    let trimmed = contents.trim_end().trim_end_matches(',');
+    if trimmed.ends_with(']') {
+        return contents.to_string();
+    }
+    format!("{trimmed}\n]\n")
+}
+
+/// Serves the trace to Perfetto's web UI until it has been fetched.
+fn open_in_perfetto(path: &Path) -> Result<()> {
+    let listener = match TcpListener::bind(PERFETTO_ADDR) {
+        Ok(listener) => listener,
+        Err(err) => {
+            output::warn(format!(
+                "failed to serve the trace on port {}: {err}",
+                PERFETTO_ADDR.1
+            ));
+            output::note(format!(
+                "open {PERFETTO_ORIGIN} and drop {} into it",
+                path.display()
+            ));
+            return Ok(());
+        }
+    };
+    let trace = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let url = format!(
+        "{PERFETTO_ORIGIN}/#!/?url=http://{}:{}/trace.json",
+        PERFETTO_ADDR.0, PERFETTO_ADDR.1
+    );
+    output::status("Opening", &url);
+    open_browser(&url);
+    output::note("waiting for Perfetto to load the trace (Ctrl+C to stop)");
+
+    for stream in listener.incoming() {
+        let stream = stream.context("Failed to accept a connection from Perfetto")?;
+        match serve_trace(stream, &trace) {
+            Ok(true) => return Ok(()),
+            Ok(false) => {}
+            Err(err) => output::warn(format!("failed to serve the trace: {err:#}")),
+        }
+    }
+    Ok(())
+}
+
+/// Answers one request, returning whether it fetched the trace.
+fn serve_trace(mut stream: TcpStream, trace: &[u8]) -> Result<bool> {
+    let mut buffer = [0_u8; 4096];
+    let bytes_read = stream
+        .read(&mut buffer)
+        .context("Failed to read HTTP request")?;
+    let request = String::from_utf8_lossy(&buffer[..bytes_read]);
+    let method = request.split_whitespace().next().unwrap_or_default();
+    let (status, body): (&str, &[u8]) = match method {
+        "GET" => ("200 OK", trace),
+        "OPTIONS" => ("204 No Content", &[]),
+        _ => ("405 Method Not Allowed", &[]),
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nAccess-Control-Allow-Origin: {PERFETTO_ORIGIN}\r\nConnection: close\r\n\r\n",
+        body.len()
+    )
+    .context("Failed to write HTTP response headers")?;
+    stream
+        .write_all(body)
+        .context("Failed to write HTTP response body")?;
+    Ok(method == "GET")
+}
+
+fn open_browser(url: &str) {
+    let spawn_result = if cfg!(target_os = "windows") {
+        Command::new("cmd").args(["/C", "start", "", url]).spawn()
+    } else if cfg!(target_os = "macos") {
+        Command::new("open").arg(url).spawn()
+    } else {
+        Command::new("xdg-open").arg(url).spawn()
+    };
+
+    if let Err(err) = spawn_result {
+        output::warn(format!("failed to open browser automatically: {err}"));
+        output::note(format!("open {url}"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::close_json_array;
+
+    #[test]
+    fn closes_streamed_trace_arrays() {
+        assert_eq!(close_json_array("[\n{}"), "[\n{}\n]\n");
+        assert_eq!(close_json_array("[\n{},\n{},\n"), "[\n{},\n{}\n]\n");
+        assert_eq!(close_json_array("[\n{}\n]\n"), "[\n{}\n]\n");
+    }
+}
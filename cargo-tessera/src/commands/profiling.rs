@@ -50,11 +50,13 @@ enum RedrawReason {
     MouseInput,
     MouseWheel,
     TouchInput,
+    TouchpadGesture,
     ScaleFactorChanged,
     KeyboardInput,
     ModifiersChanged,
     ImeEvent,
     FocusChanged,
+    FileDragAndDrop,
     RuntimeInvalidation,
     RuntimeFrameAwaiter,
 }
@@ -1642,11 +1644,13 @@ fn redraw_reason_label(reason: RedrawReason) -> &'static str {
         RedrawReason::MouseInput => "mouse_input",
         RedrawReason::MouseWheel => "mouse_wheel",
         RedrawReason::TouchInput => "touch_input",
+        RedrawReason::TouchpadGesture => "touchpad_gesture",
         RedrawReason::ScaleFactorChanged => "scale_factor_changed",
         RedrawReason::KeyboardInput => "keyboard_input",
         RedrawReason::ModifiersChanged => "modifiers_changed",
         RedrawReason::ImeEvent => "ime_event",
         RedrawReason::FocusChanged => "focus_changed",
+        RedrawReason::FileDragAndDrop => "file_drag_and_drop",
         RedrawReason::RuntimeInvalidation => "runtime_invalidation",
         RedrawReason::RuntimeFrameAwaiter => "runtime_frame_awaiter",
     }
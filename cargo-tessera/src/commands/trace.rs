@@ -0,0 +1,374 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufRead, BufReader, Write},
+    path::Path,
+};
+
+use anyhow::{Context, Result, bail};
+use comfy_table::{Cell, Color, ContentArrangement, Row, Table, presets::UTF8_FULL};
+use owo_colors::OwoColorize;
+use serde::Deserialize;
+
+use crate::output;
+
+#[derive(Deserialize)]
+struct TraceFileHeader {
+    version: u32,
+    format: String,
+}
+
+#[derive(Deserialize)]
+struct FrameRecord {
+    frame: u64,
+    screen: SizeRecord,
+    resources: Vec<ResourceRecord>,
+    external_resources: Vec<ExternalResourceRecord>,
+    ops: Vec<OpRecord>,
+}
+
+#[derive(Deserialize)]
+struct OpRecord {
+    index: usize,
+    kind: String,
+    type_name: Option<String>,
+    pos: PosRecord,
+    size: SizeRecord,
+    opacity: f32,
+    read: Option<String>,
+    write: Option<String>,
+    deps: Vec<usize>,
+    barrier: Option<BarrierRecord>,
+    clip: Option<ClipRecord>,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+struct PosRecord {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+struct SizeRecord {
+    w: i32,
+    h: i32,
+}
+
+#[derive(Deserialize)]
+struct ResourceRecord {
+    w: i32,
+    h: i32,
+    format: String,
+}
+
+#[derive(Deserialize)]
+struct ExternalResourceRecord {
+    handle_id: u32,
+    w: i32,
+    h: i32,
+    format: String,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum BarrierRecord {
+    Global,
+    PaddedLocal {
+        top: i32,
+        right: i32,
+        bottom: i32,
+        left: i32,
+    },
+    Absolute {
+        x: i32,
+        y: i32,
+        w: i32,
+        h: i32,
+    },
+}
+
+#[derive(Deserialize)]
+struct ClipRecord {
+    shape: String,
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32,
+}
+
+/// Options for replaying a command trace.
+pub struct ViewOptions<'a> {
+    pub path: &'a Path,
+    pub frame: Option<u64>,
+    pub step: bool,
+    pub filter: Option<&'a str>,
+    pub skip_invalid: bool,
+}
+
+#[derive(Default)]
+struct Summary {
+    frames: u64,
+    ops: u64,
+    barriers: u64,
+    ops_by_type: HashMap<String, u64>,
+}
+
+/// Replays a command trace captured with the `command-trace` feature.
+pub fn view(options: ViewOptions<'_>) -> Result<()> {
+    let ViewOptions {
+        path,
+        frame,
+        step,
+        filter,
+        skip_invalid,
+    } = options;
+
+    output::status("Replaying", path.display().to_string());
+    let file = File::open(path)
+        .with_context(|| format!("failed to open command trace at {}", path.display()))?;
+    let mut lines = BufReader::new(file).lines().enumerate();
+
+    let header = loop {
+        let Some((line_idx, line_result)) = lines.next() else {
+            bail!("no command trace records found");
+        };
+        let line = line_result.with_context(|| format!("failed to read line {}", line_idx + 1))?;
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let header: TraceFileHeader = serde_json::from_str(trimmed)
+            .with_context(|| format!("invalid command trace header at line {}", line_idx + 1))?;
+        break header;
+    };
+    if header.format != "tessera-command-trace" {
+        bail!("unsupported command trace format `{}`", header.format);
+    }
+    if header.version != 1 {
+        bail!(
+            "unsupported command trace version {}; expected version 1",
+            header.version
+        );
+    }
+
+    let mut summary = Summary::default();
+    for (line_idx, line_result) in lines {
+        let line = line_result.with_context(|| format!("failed to read line {}", line_idx + 1))?;
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let record: FrameRecord = match serde_json::from_str(trimmed) {
+            Ok(record) => record,
+            Err(err) => {
+                if skip_invalid {
+                    continue;
+                }
+                bail!("invalid frame record at line {}: {}", line_idx + 1, err);
+            }
+        };
+        if frame.is_some_and(|frame| frame != record.frame) {
+            continue;
+        }
+
+        record_summary(&record, &mut summary);
+        print_frame(&record, filter);
+        if step && !wait_for_next_frame()? {
+            break;
+        }
+        if frame.is_some() {
+            break;
+        }
+    }
+
+    if summary.frames == 0 {
+        match frame {
+            Some(frame) => bail!("frame {frame} not found in command trace"),
+            None => bail!("no frames found in command trace"),
+        }
+    }
+    print_summary(&summary);
+    Ok(())
+}
+
+fn record_summary(record: &FrameRecord, summary: &mut Summary) {
+    summary.frames += 1;
+    for op in &record.ops {
+        summary.ops += 1;
+        if op.barrier.is_some() {
+            summary.barriers += 1;
+        }
+        *summary.ops_by_type.entry(op_label(op)).or_default() += 1;
+    }
+}
+
+fn print_frame(record: &FrameRecord, filter: Option<&str>) {
+    let barriers = record.ops.iter().filter(|op| op.barrier.is_some()).count();
+    println!(
+        "\n{} {} ({}x{}): {} ops, {} barriers",
+        "Frame".bold(),
+        record.frame.bold(),
+        record.screen.w,
+        record.screen.h,
+        record.ops.len(),
+        barriers
+    );
+    for (index, resource) in record.resources.iter().enumerate() {
+        println!(
+            "  local:{index} {}x{} {}",
+            resource.w, resource.h, resource.format
+        );
+    }
+    for resource in &record.external_resources {
+        println!(
+            "  external:{} {}x{} {}",
+            resource.handle_id, resource.w, resource.h, resource.format
+        );
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(Row::from(vec![
+            Cell::new("#").fg(Color::Cyan),
+            Cell::new("Kind").fg(Color::Cyan),
+            Cell::new("Command").fg(Color::Cyan),
+            Cell::new("Rect").fg(Color::Cyan),
+            Cell::new("Opacity").fg(Color::Cyan),
+            Cell::new("Read -> Write").fg(Color::Cyan),
+            Cell::new("Deps").fg(Color::Cyan),
+            Cell::new("Barrier").fg(Color::Cyan),
+        ]));
+
+    let mut clip_depth = 0usize;
+    for op in &record.ops {
+        if op.kind == "clip_pop" {
+            clip_depth = clip_depth.saturating_sub(1);
+        }
+        let label = op_label(op);
+        let matches = filter.is_none_or(|filter| label.contains(filter));
+        if matches {
+            let barrier = op.barrier.as_ref().map(format_barrier).unwrap_or_default();
+            let barrier_cell = if op.barrier.is_some() {
+                Cell::new(barrier).fg(Color::Yellow)
+            } else {
+                Cell::new(barrier)
+            };
+            let deps = op
+                .deps
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            table.add_row(Row::from(vec![
+                Cell::new(op.index),
+                Cell::new(&op.kind),
+                Cell::new(format!("{}{}", "  ".repeat(clip_depth), label)),
+                Cell::new(format!(
+                    "{},{} {}x{}",
+                    op.pos.x, op.pos.y, op.size.w, op.size.h
+                )),
+                Cell::new(format!("{:.2}", op.opacity)),
+                Cell::new(format!(
+                    "{} -> {}",
+                    op.read.as_deref().unwrap_or("-"),
+                    op.write.as_deref().unwrap_or("-")
+                )),
+                Cell::new(deps),
+                barrier_cell,
+            ]));
+        }
+        if op.kind == "clip_push" {
+            clip_depth += 1;
+        }
+    }
+    println!("{table}");
+}
+
+fn print_summary(summary: &Summary) {
+    println!(
+        "\n{} {} frames, {:.1} ops/frame, {:.1} barriers/frame",
+        "Summary:".bold(),
+        summary.frames,
+        summary.ops as f64 / summary.frames as f64,
+        summary.barriers as f64 / summary.frames as f64
+    );
+    let mut by_type: Vec<_> = summary.ops_by_type.iter().collect();
+    by_type.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+    for (label, count) in by_type {
+        println!("  {count:>8}  {label}");
+    }
+}
+
+fn wait_for_next_frame() -> Result<bool> {
+    eprint!("{}", "[enter] next frame, [q] quit: ".dimmed());
+    io::stderr().flush()?;
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input)? == 0 {
+        return Ok(false);
+    }
+    Ok(!input.trim().eq_ignore_ascii_case("q"))
+}
+
+fn op_label(op: &OpRecord) -> String {
+    if let Some(clip) = &op.clip {
+        return format!(
+            "clip {} {},{} {}x{}",
+            clip.shape, clip.x, clip.y, clip.w, clip.h
+        );
+    }
+    op.type_name
+        .as_deref()
+        .map(short_type_name)
+        .unwrap_or_else(|| op.kind.clone())
+}
+
+fn format_barrier(barrier: &BarrierRecord) -> String {
+    match barrier {
+        BarrierRecord::Global => "global".to_string(),
+        BarrierRecord::PaddedLocal {
+            top,
+            right,
+            bottom,
+            left,
+        } => format!("local +{top}/{right}/{bottom}/{left}"),
+        BarrierRecord::Absolute { x, y, w, h } => format!("abs {x},{y} {w}x{h}"),
+    }
+}
+
+/// Strips module paths from a Rust type name, keeping generic arguments.
+fn short_type_name(type_name: &str) -> String {
+    let mut output = String::with_capacity(type_name.len());
+    let mut segment_start = 0;
+    let mut chars = type_name.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch == ':' && chars.peek() == Some(&':') {
+            chars.next();
+            output.truncate(segment_start);
+            continue;
+        }
+        output.push(ch);
+        if !(ch.is_alphanumeric() || ch == '_') {
+            segment_start = output.len();
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::short_type_name;
+
+    #[test]
+    fn short_type_name_strips_paths() {
+        assert_eq!(
+            short_type_name("tessera_ui::renderer::drawer::sdf::SdfShapeCommand"),
+            "SdfShapeCommand"
+        );
+        assert_eq!(
+            short_type_name("app::Wrapper<core::option::Option<app::Inner>>"),
+            "Wrapper<Option<Inner>>"
+        );
+    }
+}
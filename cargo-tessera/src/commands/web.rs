@@ -449,7 +449,8 @@ fn ensure_lib_has_wasm_start(project_dir: &Path) -> Result<bool> {
     let mut content = fs::read_to_string(&lib_path)
         .with_context(|| format!("Failed to read {}", lib_path.display()))?;
 
-    if content.contains("wasm_bindgen(start)") {
+    // `web(start)` in `#[tessera_ui::entry]` generates the start function.
+    if content.contains("wasm_bindgen(start)") || content.contains("web(start") {
         return Ok(false);
     }
 
@@ -6,7 +6,12 @@ use tessera_build::AssetBackend;
 
 use commands::{
     android::{self, AndroidFormat},
-    plugin, web,
+    bundle::{BundleFormat, BundleOptions},
+    new::{ComponentsChoice, NewOptions},
+    plugin,
+    preview::PreviewOptions,
+    profile::ProfileOptions,
+    web,
 };
 
 mod color_check;
@@ -45,6 +50,24 @@ enum TesseraCommands {
         /// Use a specific template
         #[arg(short, long)]
         template: Option<String>,
+        /// Add the tessera-shard router with a home page
+        #[arg(long, value_name = "BOOL")]
+        router: Option<bool>,
+        /// Components package the app builds on
+        #[arg(long, value_enum)]
+        components: Option<ComponentsChoice>,
+        /// Put the app under `crates/` in a Cargo workspace
+        #[arg(long, value_name = "BOOL")]
+        workspace: Option<bool>,
+        /// Add a GitHub Actions CI workflow
+        #[arg(long, value_name = "BOOL")]
+        ci: Option<bool>,
+        /// App identifier, used as the Android package name
+        #[arg(long, value_name = "ID")]
+        android_package: Option<String>,
+        /// Use defaults for options not given instead of asking
+        #[arg(short, long)]
+        yes: bool,
     },
     /// Start development server with auto rebuild/restart
     Dev {
@@ -64,10 +87,53 @@ enum TesseraCommands {
         /// Overlay dirty replay regions with a translucent debug color
         #[arg(long)]
         debug_dirty_overlay: bool,
+        /// Enable the component inspector, toggled with F12 in the app
+        #[arg(long)]
+        inspector: bool,
+        /// Serve the devtools WebSocket protocol on 127.0.0.1:9330
+        #[arg(long)]
+        devtools: bool,
         /// Override asset backend
         #[arg(long, value_enum)]
         asset_backend: Option<AssetBackendArg>,
     },
+    /// Open a window listing the package's `#[preview]` components, hot
+    /// reloading them on changes
+    Preview {
+        /// Name of the preview to show first
+        component: Option<String>,
+        /// Specify package whose previews to show
+        #[arg(short, long)]
+        package: Option<String>,
+        /// Enable release mode
+        #[arg(short, long)]
+        release: bool,
+        /// Enable verbose output
+        #[arg(short, long)]
+        verbose: bool,
+    },
+    /// Run the app with profiling enabled, then open a timeline of its
+    /// frames in Perfetto
+    Profile {
+        /// Specify package to run
+        #[arg(short, long)]
+        package: Option<String>,
+        /// Profile an unoptimized build instead of a release build
+        #[arg(long)]
+        debug: bool,
+        /// Chrome trace output path; profiler JSONL records are written next
+        /// to it
+        #[arg(
+            short,
+            long,
+            value_name = "FILE",
+            default_value = "profiles/trace.json"
+        )]
+        output: PathBuf,
+        /// Don't open the trace in Perfetto
+        #[arg(long)]
+        no_open: bool,
+    },
     /// Build the project for release (native targets)
     Build {
         /// Build in release mode
@@ -90,6 +156,24 @@ enum TesseraCommands {
         #[arg(long, value_enum)]
         asset_backend: Option<AssetBackendArg>,
     },
+    /// Build a release binary and package it as a desktop installer
+    Bundle {
+        /// Specify package to bundle
+        #[arg(short, long)]
+        package: Option<String>,
+        /// Binary to bundle when the package has several
+        #[arg(long)]
+        bin: Option<String>,
+        /// Target triple (passed to cargo build)
+        #[arg(short, long)]
+        target: Option<String>,
+        /// Bundle formats to produce (defaults depend on the target platform)
+        #[arg(long = "format", value_enum)]
+        formats: Vec<BundleFormat>,
+    },
+    /// Check the toolchains, SDKs, system libraries and GPU drivers Tessera
+    /// needs, and explain how to fix what's missing
+    Doctor,
     /// Check the project for compilation errors and Tessera color violations
     Check {
         /// Specify package to check
@@ -149,11 +233,34 @@ enum TesseraCommands {
         #[command(subcommand)]
         command: ProfilingCommands,
     },
+    /// Replay a draw command trace captured with the `command-trace` feature
+    Trace {
+        /// Path to tessera command trace JSONL file
+        path: PathBuf,
+        /// Show only this frame
+        #[arg(long)]
+        frame: Option<u64>,
+        /// Wait for Enter between frames
+        #[arg(long)]
+        step: bool,
+        /// Show only commands whose name contains this text
+        #[arg(long)]
+        filter: Option<String>,
+        /// Skip frame lines that fail parsing
+        #[arg(long)]
+        skip_invalid: bool,
+    },
     /// Android-specific build and development commands
     Android {
         #[command(subcommand)]
         command: AndroidCommands,
     },
+    /// iOS-specific build and development commands (macOS only)
+    #[cfg(target_os = "macos")]
+    Ios {
+        #[command(subcommand)]
+        command: IosCommands,
+    },
     /// Web-specific initialization and development commands
     Web {
         #[command(subcommand)]
@@ -182,6 +289,23 @@ enum AndroidCommands {
     RustBuild(AndroidRustBuildArgs),
 }
 
+#[cfg(target_os = "macos")]
+#[derive(Subcommand)]
+enum IosCommands {
+    /// Initialize iOS project (Xcode) for Tessera app
+    Init {
+        /// Skip installing Rust targets automatically
+        #[arg(long)]
+        skip_targets_install: bool,
+    },
+    /// Build the iOS app using Xcode
+    Build(IosBuildArgs),
+    /// Run/install the app on an iOS device or simulator
+    Dev(IosDevArgs),
+    /// Build the Rust static library for Xcode (used by the Xcode project)
+    RustBuild(IosRustBuildArgs),
+}
+
 #[derive(Subcommand)]
 enum WebCommands {
     /// Initialize minimal browser host and wasm entry support for a Tessera app
@@ -367,6 +491,53 @@ struct AndroidRustBuildArgs {
     asset_backend: Option<AssetBackendArg>,
 }
 
+#[cfg(target_os = "macos")]
+#[derive(Args)]
+struct IosBuildArgs {
+    /// Build in release mode
+    #[arg(long, short)]
+    release: bool,
+    /// Override package/binary name (-p)
+    #[arg(long, short)]
+    package: Option<String>,
+    /// Build for the iOS simulator instead of devices
+    #[arg(long)]
+    simulator: bool,
+}
+
+#[cfg(target_os = "macos")]
+#[derive(Args)]
+struct IosDevArgs {
+    /// Run in release mode
+    #[arg(long, short)]
+    release: bool,
+    /// Override package/binary name (-p)
+    #[arg(long, short)]
+    package: Option<String>,
+    /// Device id or name, or a simulator name
+    #[arg(long, short)]
+    device: Option<String>,
+}
+
+#[cfg(target_os = "macos")]
+#[derive(Args)]
+struct IosRustBuildArgs {
+    /// Xcode build configuration (Debug or Release)
+    #[arg(long)]
+    configuration: String,
+    /// Xcode platform display name (e.g. iOS or iOS Simulator)
+    #[arg(long)]
+    platform: String,
+    /// Path of the SDK Xcode builds against
+    #[arg(long)]
+    sdk_root: PathBuf,
+    /// Architectures Xcode builds for (e.g. arm64)
+    arches: Vec<String>,
+    /// Override package/binary name (-p)
+    #[arg(long, short)]
+    package: Option<String>,
+}
+
 fn main() -> ExitCode {
     if let Err(err) = run() {
         print_error(&err);
@@ -380,7 +551,16 @@ fn run() -> Result<()> {
 
     match command {
         Commands::Tessera(args) => match args.command {
-            TesseraCommands::New { name, template } => {
+            TesseraCommands::New {
+                name,
+                template,
+                router,
+                components,
+                workspace,
+                ci,
+                android_package,
+                yes,
+            } => {
                 let name = match name {
                     Some(n) => n,
                     None => commands::new::prompt_project_name()?,
@@ -389,7 +569,18 @@ fn run() -> Result<()> {
                     Some(t) => t,
                     None => commands::new::select_template_interactive()?,
                 };
-                commands::new::execute(&name, &template)?;
+                commands::new::execute(
+                    &name,
+                    &template,
+                    NewOptions {
+                        router,
+                        components,
+                        workspace,
+                        ci,
+                        identifier: android_package,
+                        yes,
+                    },
+                )?;
             }
             TesseraCommands::Plugin { command } => match command {
                 PluginCommands::New { name, template } => {
@@ -410,6 +601,8 @@ fn run() -> Result<()> {
                 release,
                 profiling_output,
                 debug_dirty_overlay,
+                inspector,
+                devtools,
                 asset_backend,
             } => {
                 commands::dev::execute(
@@ -418,9 +611,24 @@ fn run() -> Result<()> {
                     release,
                     profiling_output.as_deref(),
                     debug_dirty_overlay,
+                    inspector,
+                    devtools,
                     asset_backend.map(AssetBackendArg::to_backend),
                 )?;
             }
+            TesseraCommands::Preview {
+                component,
+                package,
+                release,
+                verbose,
+            } => {
+                commands::preview::execute(PreviewOptions {
+                    component: component.as_deref(),
+                    package: package.as_deref(),
+                    verbose,
+                    release,
+                })?;
+            }
             TesseraCommands::Build {
                 release,
                 target,
@@ -438,6 +646,35 @@ fn run() -> Result<()> {
                     asset_backend.map(AssetBackendArg::to_backend),
                 )?;
             }
+            TesseraCommands::Profile {
+                package,
+                debug,
+                output,
+                no_open,
+            } => {
+                commands::profile::execute(ProfileOptions {
+                    package: package.as_deref(),
+                    debug,
+                    output: &output,
+                    open: !no_open,
+                })?;
+            }
+            TesseraCommands::Bundle {
+                package,
+                bin,
+                target,
+                formats,
+            } => {
+                commands::bundle::execute(BundleOptions {
+                    package: package.as_deref(),
+                    bin: bin.as_deref(),
+                    target: target.as_deref(),
+                    formats,
+                })?;
+            }
+            TesseraCommands::Doctor => {
+                commands::doctor::execute()?;
+            }
             TesseraCommands::Check {
                 package,
                 target,
@@ -529,6 +766,21 @@ fn run() -> Result<()> {
                     )?;
                 }
             },
+            TesseraCommands::Trace {
+                path,
+                frame,
+                step,
+                filter,
+                skip_invalid,
+            } => {
+                commands::trace::view(commands::trace::ViewOptions {
+                    path: &path,
+                    frame,
+                    step,
+                    filter: filter.as_deref(),
+                    skip_invalid,
+                })?;
+            }
             TesseraCommands::Android { command } => match command {
                 AndroidCommands::Init {
                     skip_targets_install,
@@ -568,6 +820,37 @@ fn run() -> Result<()> {
                     })?;
                 }
             },
+            #[cfg(target_os = "macos")]
+            TesseraCommands::Ios { command } => match command {
+                IosCommands::Init {
+                    skip_targets_install,
+                } => {
+                    commands::ios::init(skip_targets_install)?;
+                }
+                IosCommands::Build(build_args) => {
+                    commands::ios::build(commands::ios::BuildOptions {
+                        release: build_args.release,
+                        package: build_args.package,
+                        simulator: build_args.simulator,
+                    })?;
+                }
+                IosCommands::Dev(dev_args) => {
+                    commands::ios::dev(commands::ios::DevOptions {
+                        release: dev_args.release,
+                        package: dev_args.package,
+                        device: dev_args.device,
+                    })?;
+                }
+                IosCommands::RustBuild(build_args) => {
+                    commands::ios::rust_build(commands::ios::RustBuildOptions {
+                        configuration: build_args.configuration,
+                        platform: build_args.platform,
+                        sdk_root: build_args.sdk_root,
+                        arches: build_args.arches,
+                        package: build_args.package,
+                    })?;
+                }
+            },
             TesseraCommands::Web { command } => match command {
                 WebCommands::Init { package } => {
                     web::init(package.as_deref())?;
@@ -1,4 +1,7 @@
-use std::{fs, path::Path};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 use anyhow::{Context, Result, anyhow};
 use handlebars::Handlebars;
@@ -17,6 +20,23 @@ fn write_if_changed(path: &Path, new_bytes: &[u8]) -> Result<()> {
     fs::write(path, new_bytes).with_context(|| format!("Failed to write {}", path.display()))
 }
 
+/// Renders handlebars expressions in a template path, e.g. the
+/// `{{app.name}}` directories of the Xcode template.
+fn render_path(
+    path: &Path,
+    handlebars: &Handlebars<'_>,
+    data: &serde_json::Value,
+) -> Result<PathBuf> {
+    let text = path.to_string_lossy();
+    if !text.contains("{{") {
+        return Ok(path.to_path_buf());
+    }
+    let rendered = handlebars
+        .render_template(&text, data)
+        .with_context(|| format!("Failed to render path {}", path.display()))?;
+    Ok(PathBuf::from(rendered))
+}
+
 pub fn write_template_dir(
     dir: &Dir<'_>,
     out_root: &Path,
@@ -42,7 +62,7 @@ pub fn write_template_dir_at(
                 } else {
                     subdir.path()
                 };
-                let out_dir = out_root.join(rel_path);
+                let out_dir = out_root.join(render_path(rel_path, handlebars, data)?);
                 fs::create_dir_all(&out_dir)
                     .with_context(|| format!("Failed to create directory {}", out_dir.display()))?;
                 write_template_dir_at(subdir, out_root, base, handlebars, data)?;
@@ -53,7 +73,7 @@ pub fn write_template_dir_at(
                 } else {
                     file.path()
                 };
-                let mut out_path = out_root.join(rel_path);
+                let mut out_path = out_root.join(render_path(rel_path, handlebars, data)?);
                 let is_template = out_path.extension().is_some_and(|ext| ext == "hbs");
                 if is_template {
                     out_path.set_extension("");
@@ -147,7 +167,7 @@ pub fn write_template_file(
 
 #[cfg(windows)]
 fn mark_gradlew_executable_with_git(out_path: &Path) {
-    use std::{path::PathBuf, process::Command};
+    use std::process::Command;
 
     let parent = out_path.parent().unwrap_or_else(|| Path::new("."));
     let Ok(output) = Command::new("git")
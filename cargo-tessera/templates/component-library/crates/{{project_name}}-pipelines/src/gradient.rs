@@ -0,0 +1,137 @@
+//! Linear gradient fills.
+
+use tessera_ui::{
+    Color, DrawCommand, InstancedDrawCommand, PxPosition, PxSize,
+    renderer::drawer::{
+        InstanceBuffer, clip_depth_stencil_state,
+        pipeline::{DrawContext, DrawablePipeline, FrameContext},
+    },
+    wgpu::{self, include_wgsl},
+};
+
+/// Fills a node with a left-to-right linear gradient.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GradientCommand {
+    /// Color at the left edge.
+    pub start: Color,
+    /// Color at the right edge.
+    pub end: Color,
+}
+
+/// Per-instance data for [`GradientCommand`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct GradientInstance {
+    rect: [f32; 4],
+    start: [f32; 4],
+    end: [f32; 4],
+    screen_size: [f32; 2],
+}
+
+impl DrawCommand for GradientCommand {
+    fn apply_opacity(&mut self, opacity: f32) {
+        let opacity = opacity.clamp(0.0, 1.0);
+        self.start = self.start.with_alpha(self.start.a * opacity);
+        self.end = self.end.with_alpha(self.end.a * opacity);
+    }
+}
+
+impl InstancedDrawCommand for GradientCommand {
+    type Instance = GradientInstance;
+
+    fn instance(&self, size: PxSize, position: PxPosition, target_size: PxSize) -> Self::Instance {
+        GradientInstance {
+            rect: [
+                position.x.to_f32(),
+                position.y.to_f32(),
+                size.width.to_f32(),
+                size.height.to_f32(),
+            ],
+            start: self.start.to_array(),
+            end: self.end.to_array(),
+            screen_size: [target_size.width.to_f32(), target_size.height.to_f32()],
+        }
+    }
+}
+
+/// Draws batches of [`GradientCommand`]s.
+pub struct GradientPipeline {
+    pipeline: wgpu::RenderPipeline,
+    instances: InstanceBuffer<GradientInstance>,
+}
+
+impl GradientPipeline {
+    /// Creates the pipeline for the surface format and sample count.
+    pub fn new(
+        device: &wgpu::Device,
+        config: &wgpu::SurfaceConfiguration,
+        pipeline_cache: Option<&wgpu::PipelineCache>,
+        sample_count: u32,
+    ) -> Self {
+        let shader = device.create_shader_module(include_wgsl!("gradient/gradient.wgsl"));
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Gradient Pipeline Layout"),
+            bind_group_layouts: &[],
+            immediate_size: 0,
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Gradient Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: InstanceBuffer::<GradientInstance>::stride(),
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &wgpu::vertex_attr_array![
+                        0 => Float32x4,
+                        1 => Float32x4,
+                        2 => Float32x4,
+                        3 => Float32x2
+                    ],
+                }],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: config.format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: Some(clip_depth_stencil_state()),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview_mask: None,
+            cache: pipeline_cache,
+        });
+
+        Self {
+            pipeline,
+            instances: InstanceBuffer::new("Gradient Instance Buffer"),
+        }
+    }
+}
+
+impl DrawablePipeline<GradientCommand> for GradientPipeline {
+    fn begin_frame(&mut self, _context: &FrameContext<'_>) {
+        self.instances.reset();
+    }
+
+    fn draw(&mut self, context: &mut DrawContext<GradientCommand>) {
+        let Some(instances) = context.bind_instances(&mut self.instances, 0) else {
+            return;
+        };
+
+        context.render_pass.set_pipeline(&self.pipeline);
+        // The quad's corners come from the vertex index.
+        context.render_pass.draw(0..6, instances);
+    }
+}
@@ -0,0 +1,43 @@
+struct InstanceInput {
+    @location(0) rect: vec4<f32>,
+    @location(1) start: vec4<f32>,
+    @location(2) end: vec4<f32>,
+    @location(3) screen_size: vec2<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) progress: f32,
+    @location(1) start: vec4<f32>,
+    @location(2) end: vec4<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32, instance: InstanceInput) -> VertexOutput {
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+    );
+    let corner = corners[vertex_index];
+    let pixel_pos = instance.rect.xy + corner * instance.rect.zw;
+    let clip = vec2<f32>(
+        (pixel_pos.x / instance.screen_size.x) * 2.0 - 1.0,
+        (pixel_pos.y / instance.screen_size.y) * -2.0 + 1.0,
+    );
+
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(clip, 0.0, 1.0);
+    out.progress = corner.x;
+    out.start = instance.start;
+    out.end = instance.end;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return mix(in.start, in.end, in.progress);
+}
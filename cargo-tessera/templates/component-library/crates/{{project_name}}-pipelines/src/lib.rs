@@ -0,0 +1,27 @@
+//! Render pipelines for the components of this workspace.
+//!
+//! Components push the draw commands defined here, and [`PipelinesModule`]
+//! registers the pipelines that draw them.
+
+pub mod gradient;
+
+use tessera_ui::{PipelineContext, RenderModule};
+
+use crate::gradient::GradientPipeline;
+
+/// Registers every pipeline of this crate.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PipelinesModule;
+
+impl RenderModule for PipelinesModule {
+    fn register_pipelines(&self, context: &mut PipelineContext<'_>) {
+        let resources = context.resources();
+        let gradient = GradientPipeline::new(
+            resources.device,
+            resources.surface_config,
+            resources.pipeline_cache,
+            resources.sample_count,
+        );
+        context.register_draw_pipeline(gradient);
+    }
+}
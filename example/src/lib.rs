@@ -12,30 +12,25 @@ use pages::custom_shader::CustomShaderPackage;
 
 #[cfg(target_family = "wasm")]
 use tessera_ui::renderer::WebConfig;
-#[cfg(target_family = "wasm")]
-use wasm_bindgen::{JsValue, prelude::wasm_bindgen};
 
-#[tessera_ui::entry]
+#[tessera_ui::entry(
+    packages = [
+        tessera_components::ComponentsPackage,
+        tessera_platform::PlatformPackage,
+        CustomShaderPackage,
+    ],
+    config = TesseraConfig {
+        window: WindowConfig {
+            decorations: false,
+            ..Default::default()
+        },
+        #[cfg(target_family = "wasm")]
+        web: WebConfig::default().with_canvas_id(env!("CARGO_CRATE_NAME")),
+        ..Default::default()
+    },
+    desktop(main),
+    web(start),
+)]
 pub fn run() -> EntryPoint {
     EntryPoint::new(app)
-        .config(TesseraConfig {
-            window: WindowConfig {
-                decorations: false,
-                ..Default::default()
-            },
-            #[cfg(target_family = "wasm")]
-            web: WebConfig::default().with_canvas_id(env!("CARGO_CRATE_NAME")),
-            ..Default::default()
-        })
-        .package(tessera_components::ComponentsPackage)
-        .package(tessera_platform::PlatformPackage)
-        .package(CustomShaderPackage)
-}
-
-#[cfg(target_family = "wasm")]
-#[wasm_bindgen(start)]
-pub fn start() -> Result<(), JsValue> {
-    run()
-        .run_web()
-        .map_err(|err| JsValue::from_str(&err.to_string()))
 }
@@ -2,9 +2,7 @@
 
 #[cfg(not(target_family = "wasm"))]
 fn main() {
-    if let Err(err) = example_lib::run().run_desktop() {
-        eprintln!("App failed to run: {err}");
-    }
+    example_lib::desktop_main();
 }
 
 #[cfg(target_family = "wasm")]
@@ -10,7 +10,10 @@ use tessera_ui::{
     layout::layout,
     px::{Px, PxPosition, PxRect, PxSize},
     receive_frame_nanos, remember,
-    renderer::drawer::pipeline::{DrawContext, DrawablePipeline},
+    renderer::drawer::{
+        clip_depth_stencil_state,
+        pipeline::{DrawContext, DrawablePipeline},
+    },
     tessera, use_context, wgpu,
 };
 
@@ -255,7 +258,7 @@ impl RaymarchPipeline {
                 compilation_options: Default::default(),
             }),
             primitive: wgpu::PrimitiveState::default(),
-            depth_stencil: None,
+            depth_stencil: Some(clip_depth_stencil_state()),
             multisample: wgpu::MultisampleState {
                 count: sample_count,
                 mask: !0,
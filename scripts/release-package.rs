@@ -58,6 +58,7 @@ const GITHUB_OWNER: &str = "tessera-ui";
 const GITHUB_REPO: &str = "tessera";
 const PUBLISHABLE_PACKAGES: &[&str] = &[
     "tessera-ui",
+    "tessera-dylib",
     "tessera-foundation",
     "tessera-glyphon",
     "tessera-components",
@@ -0,0 +1,275 @@
+//! Animated switching between content built for different states.
+//!
+//! ## Usage
+//!
+//! Crossfade or slide between tab pages, counters, or loading and loaded
+//! views when the value they are built from changes.
+use tessera_ui::{
+    ComputedData, FrameNanosControl, MeasurementError, Modifier, Px, PxPosition, RenderSlotWith,
+    animation::{Animatable, AnimationSpec},
+    current_frame_nanos, key,
+    layout::{LayoutPolicy, LayoutResult, MeasureScope, layout},
+    receive_frame_nanos, remember,
+    system_preferences::system_preferences,
+    tessera,
+};
+
+use crate::{
+    alignment::Alignment,
+    animated_visibility::{VisibilityTransition, alignment_bias, transition_layer},
+    modifier::ModifierExt as _,
+};
+
+/// Transitions played on the new and old content when the state changes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContentTransform {
+    enter: VisibilityTransition,
+    exit: VisibilityTransition,
+}
+
+impl ContentTransform {
+    /// Shows the new content with `enter` while the old one leaves with
+    /// `exit`. The switch runs with the spec of `enter`.
+    pub fn new(enter: VisibilityTransition, exit: VisibilityTransition) -> Self {
+        Self { enter, exit }
+    }
+
+    /// Fades the old content out while the new one fades in.
+    pub fn crossfade() -> Self {
+        Self::new(VisibilityTransition::fade(), VisibilityTransition::fade())
+    }
+}
+
+impl Default for ContentTransform {
+    /// Returns [`ContentTransform::crossfade`].
+    fn default() -> Self {
+        Self::crossfade()
+    }
+}
+
+/// Content currently shown and the content it replaces.
+struct ContentSwitch<T> {
+    next_id: u64,
+    current: (u64, T),
+    /// Outgoing content and how far it was shown when the switch began.
+    previous: Option<(u64, T, f32)>,
+    progress: Animatable<f32>,
+}
+
+impl<T: Clone + PartialEq + Send + Sync + 'static> ContentSwitch<T> {
+    fn new(state: T) -> Self {
+        Self {
+            next_id: 1,
+            current: (0, state),
+            previous: None,
+            progress: Animatable::new(1.0),
+        }
+    }
+
+    /// Replaces the current content, dropping content still leaving from an
+    /// earlier switch.
+    fn switch_to(&mut self, state: T, spec: Option<AnimationSpec<f32>>, frame_nanos: u64) {
+        let shown = self.progress.value().clamp(0.0, 1.0);
+        let (id, outgoing) = std::mem::replace(&mut self.current, (self.next_id, state));
+        self.next_id += 1;
+        match spec {
+            Some(spec) => {
+                self.previous = Some((id, outgoing, shown));
+                self.progress.snap_to(0.0);
+                self.progress.animate_to(1.0, spec, frame_nanos);
+            }
+            None => {
+                self.previous = None;
+                self.progress.snap_to(1.0);
+            }
+        }
+    }
+
+    fn tick(&mut self, frame_nanos: u64) -> bool {
+        let running = self.progress.tick(frame_nanos);
+        if !running {
+            self.previous = None;
+        }
+        running
+    }
+}
+
+/// # animated_content
+///
+/// Animates between the content built for successive values of a state.
+///
+/// Each value keeps its remembered state until it has left. The container
+/// resizes from the old content's size to the new one during the switch and
+/// clips what overflows.
+///
+/// ## Usage
+///
+/// Build content from `target_state`; when it changes, the content built for
+/// the old value leaves while the content for the new value enters.
+///
+/// ## Parameters
+///
+/// - `target_state` — value the shown content is built from.
+/// - `content` — builds the content for a value.
+/// - `transform` — optional transitions for the switch; defaults to
+///   [`ContentTransform::crossfade`].
+/// - `alignment` — optional alignment of content smaller than the container;
+///   defaults to [`Alignment::TopStart`].
+/// - `modifier` — modifier chain applied to the container.
+///
+/// ## Examples
+///
+/// ```
+/// use tessera_components::{animated_content::animated_content, text::text};
+///
+/// # use tessera_ui::tessera;
+/// # #[tessera]
+/// # fn component() {
+/// let count = 3;
+/// animated_content(count, |count: i32| {
+///     text().content(count.to_string());
+/// });
+/// # }
+/// # component();
+/// ```
+#[tessera]
+pub fn animated_content<T: Clone + PartialEq + Send + Sync + 'static>(
+    target_state: T,
+    #[prop(render_slot_with)] content: RenderSlotWith<T>,
+    transform: Option<ContentTransform>,
+    alignment: Option<Alignment>,
+    modifier: Option<Modifier>,
+) {
+    let transform = transform.unwrap_or_default();
+    let alignment = alignment.unwrap_or_default();
+    let modifier = modifier.unwrap_or_default();
+    let switch = remember(|| ContentSwitch::new(target_state.clone()));
+    if switch.with(|switch| switch.current.1 != target_state) {
+        let spec = (!system_preferences().reduce_motion).then(|| transform.enter.animation_spec());
+        let frame_nanos = current_frame_nanos();
+        switch.with_mut(|switch| switch.switch_to(target_state.clone(), spec, frame_nanos));
+    }
+    if switch.with(|switch| switch.previous.is_some()) {
+        receive_frame_nanos(move |frame_nanos| {
+            if switch.with_mut(|switch| switch.tick(frame_nanos)) {
+                FrameNanosControl::Continue
+            } else {
+                FrameNanosControl::Stop
+            }
+        });
+    }
+
+    // Springs may overshoot; no effect is meaningful outside the range.
+    let progress = switch
+        .with(|switch| switch.progress.value())
+        .clamp(0.0, 1.0);
+    let mut layers = Vec::with_capacity(2);
+    switch.with(|switch| {
+        if let Some((id, state, shown)) = switch.previous.clone() {
+            layers.push((id, state, transform.exit.clone(), shown * (1.0 - progress)));
+        }
+        let (id, state) = switch.current.clone();
+        layers.push((id, state, transform.enter.clone(), progress));
+    });
+    let switching = layers.len() > 1;
+    let modifier = if switching {
+        modifier.clip_to_bounds()
+    } else {
+        modifier
+    };
+
+    layout()
+        .modifier(modifier)
+        .layout_policy(AnimatedContentLayout {
+            alignment,
+            progress,
+        })
+        .child(move || {
+            for (id, state, transition, progress) in layers.clone() {
+                key(id, || {
+                    transition_layer(transition, progress, Modifier::new(), move || {
+                        content.render(state.clone());
+                    });
+                });
+            }
+        });
+}
+
+#[derive(Clone, PartialEq)]
+struct AnimatedContentLayout {
+    alignment: Alignment,
+    progress: f32,
+}
+
+impl LayoutPolicy for AnimatedContentLayout {
+    fn measure(&self, input: &MeasureScope<'_>) -> Result<LayoutResult, MeasurementError> {
+        let parent_constraint = *input.parent_constraint().as_ref();
+        let child_constraint = input.parent_constraint().without_min();
+        let children = input.children();
+        let mut sizes = Vec::with_capacity(children.len());
+        for child in &children {
+            sizes.push(child.measure(&child_constraint)?.size());
+        }
+        let size = switch_size(&sizes, self.progress);
+        let width = parent_constraint.width.clamp(size.width);
+        let height = parent_constraint.height.clamp(size.height);
+
+        let (bias_x, bias_y) = alignment_bias(self.alignment);
+        let mut result = LayoutResult::default();
+        for (child, child_size) in children.iter().zip(&sizes) {
+            result.place_child(
+                *child,
+                PxPosition::new(
+                    (width - child_size.width).mul_f32(bias_x),
+                    (height - child_size.height).mul_f32(bias_y),
+                ),
+            );
+        }
+        Ok(result.with_size(ComputedData { width, height }))
+    }
+}
+
+/// Returns the container size between the outgoing and incoming content.
+fn switch_size(sizes: &[ComputedData], progress: f32) -> ComputedData {
+    let mix = |from: Px, to: Px| from + (to - from).mul_f32(progress);
+    match sizes {
+        [] => ComputedData::ZERO,
+        [only] => *only,
+        [.., from, to] => ComputedData {
+            width: mix(from.width, to.width),
+            height: mix(from.height, to.height),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tessera_ui::animation::{AnimationSpec, TweenSpec};
+
+    use super::ContentSwitch;
+
+    #[test]
+    fn interrupted_switch_keeps_outgoing_visibility_and_settles() {
+        let spec = || {
+            Some(AnimationSpec::from(TweenSpec::new(Duration::from_millis(
+                100,
+            ))))
+        };
+        let mut switch = ContentSwitch::new("a");
+        switch.switch_to("b", spec(), 0);
+        assert!(switch.tick(50_000_000));
+        let shown = switch.progress.value();
+        assert!(shown > 0.0 && shown < 1.0);
+
+        switch.switch_to("c", spec(), 50_000_000);
+        let (id, state, outgoing) = switch.previous.expect("b should be leaving");
+        assert_eq!((id, state, outgoing), (1, "b", shown));
+        assert_eq!(switch.current, (2, "c"));
+
+        assert!(!switch.tick(200_000_000));
+        assert!(switch.previous.is_none());
+        assert_eq!(switch.progress.value(), 1.0);
+    }
+}
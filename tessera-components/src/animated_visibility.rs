@@ -0,0 +1,323 @@
+//! Animated enter and exit transitions for conditional content.
+//!
+//! ## Usage
+//!
+//! Show or hide panels, banners and list details with a fade, slide or
+//! expand instead of popping them in and out.
+use tessera_ui::{
+    ComputedData, MeasurementError, Modifier, Px, PxPosition, RenderSlot,
+    animation::{AnimationSpec, animate_as_state_with},
+    layout::{LayoutPolicy, LayoutResult, MeasureScope, layout},
+    tessera,
+};
+
+use crate::{alignment::Alignment, modifier::ModifierExt as _};
+
+/// How content looks while hidden, and how it moves between hidden and
+/// shown.
+///
+/// Effects combine with [`VisibilityTransition::and`]. Used as an enter
+/// transition the content animates from this hidden look to fully shown;
+/// used as an exit transition it animates back.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VisibilityTransition {
+    alpha: Option<f32>,
+    slide: Option<(f32, f32)>,
+    expand: Option<Expand>,
+    spec: AnimationSpec<f32>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Expand {
+    from: Alignment,
+    horizontal: bool,
+    vertical: bool,
+}
+
+impl VisibilityTransition {
+    /// No effect; the content appears or disappears at once.
+    pub fn none() -> Self {
+        Self {
+            alpha: None,
+            slide: None,
+            expand: None,
+            spec: AnimationSpec::default(),
+        }
+    }
+
+    /// Fades the content from fully transparent.
+    pub fn fade() -> Self {
+        Self::fade_from(0.0)
+    }
+
+    /// Fades the content from `alpha`.
+    pub fn fade_from(alpha: f32) -> Self {
+        Self {
+            alpha: Some(alpha.clamp(0.0, 1.0)),
+            ..Self::none()
+        }
+    }
+
+    /// Slides the content from an offset given as fractions of its own size,
+    /// so `slide(0.0, 1.0)` comes up from one full height below.
+    pub fn slide(x: f32, y: f32) -> Self {
+        Self {
+            slide: Some((x, y)),
+            ..Self::none()
+        }
+    }
+
+    /// Grows the clip bounds from zero size at `from` to the full content.
+    pub fn expand(from: Alignment) -> Self {
+        Self::expand_axes(from, true, true)
+    }
+
+    /// Grows the clip bounds horizontally, anchored at `from`.
+    pub fn expand_horizontally(from: Alignment) -> Self {
+        Self::expand_axes(from, true, false)
+    }
+
+    /// Grows the clip bounds vertically, anchored at `from`.
+    pub fn expand_vertically(from: Alignment) -> Self {
+        Self::expand_axes(from, false, true)
+    }
+
+    fn expand_axes(from: Alignment, horizontal: bool, vertical: bool) -> Self {
+        Self {
+            expand: Some(Expand {
+                from,
+                horizontal,
+                vertical,
+            }),
+            ..Self::none()
+        }
+    }
+
+    /// Combines the effects of `self` and `other`. Effects set on `other`
+    /// win, and the animation spec of `self` is kept.
+    pub fn and(self, other: Self) -> Self {
+        Self {
+            alpha: other.alpha.or(self.alpha),
+            slide: other.slide.or(self.slide),
+            expand: other.expand.or(self.expand),
+            spec: self.spec,
+        }
+    }
+
+    /// Sets how the transition progresses. Defaults to a spring.
+    pub fn spec(mut self, spec: impl Into<AnimationSpec<f32>>) -> Self {
+        self.spec = spec.into();
+        self
+    }
+
+    pub(crate) fn animation_spec(&self) -> AnimationSpec<f32> {
+        self.spec.clone()
+    }
+
+    fn alpha_at(&self, progress: f32) -> f32 {
+        self.alpha
+            .map_or(1.0, |alpha| alpha + (1.0 - alpha) * progress)
+    }
+
+    fn clips_at(&self, progress: f32) -> bool {
+        self.expand.is_some() && progress < 1.0
+    }
+
+    /// Returns the visible size and content offset at `progress`.
+    fn frame_at(&self, progress: f32, content: ComputedData) -> (ComputedData, PxPosition) {
+        let hidden = 1.0 - progress;
+        let (mut x, mut y) = self.slide.map_or((Px::ZERO, Px::ZERO), |(x, y)| {
+            (
+                content.width.mul_f32(x * hidden),
+                content.height.mul_f32(y * hidden),
+            )
+        });
+        let mut size = content;
+        if let Some(expand) = self.expand {
+            let (bias_x, bias_y) = alignment_bias(expand.from);
+            if expand.horizontal {
+                size.width = content.width.mul_f32(progress);
+                x += (size.width - content.width).mul_f32(bias_x);
+            }
+            if expand.vertical {
+                size.height = content.height.mul_f32(progress);
+                y += (size.height - content.height).mul_f32(bias_y);
+            }
+        }
+        (size, PxPosition::new(x, y))
+    }
+}
+
+impl Default for VisibilityTransition {
+    /// Fades and expands from the top start corner.
+    fn default() -> Self {
+        Self::fade().and(Self::expand(Alignment::TopStart))
+    }
+}
+
+pub(crate) fn alignment_bias(alignment: Alignment) -> (f32, f32) {
+    match alignment {
+        Alignment::TopStart => (0.0, 0.0),
+        Alignment::TopCenter => (0.5, 0.0),
+        Alignment::TopEnd => (1.0, 0.0),
+        Alignment::CenterStart => (0.0, 0.5),
+        Alignment::Center => (0.5, 0.5),
+        Alignment::CenterEnd => (1.0, 0.5),
+        Alignment::BottomStart => (0.0, 1.0),
+        Alignment::BottomCenter => (0.5, 1.0),
+        Alignment::BottomEnd => (1.0, 1.0),
+    }
+}
+
+/// # animated_visibility
+///
+/// Shows or hides content with enter and exit transitions.
+///
+/// While `visible` is false the content keeps rendering, with its remembered
+/// state, until the exit transition completes; afterwards it is removed and
+/// takes no space. Content that is visible on the first frame appears without
+/// animating.
+///
+/// ## Usage
+///
+/// Wrap content that appears conditionally.
+///
+/// ## Parameters
+///
+/// - `visible` — whether the content should be shown; defaults to `true`.
+/// - `enter` — optional transition used when showing; defaults to
+///   [`VisibilityTransition::default`].
+/// - `exit` — optional transition used when hiding; defaults to
+///   [`VisibilityTransition::default`].
+/// - `modifier` — modifier chain applied to the container.
+/// - `child` — content shown and hidden by the container.
+///
+/// ## Examples
+///
+/// ```
+/// use tessera_components::{
+///     alignment::Alignment,
+///     animated_visibility::{VisibilityTransition, animated_visibility},
+///     text::text,
+/// };
+///
+/// # use tessera_ui::tessera;
+/// # #[tessera]
+/// # fn component() {
+/// animated_visibility()
+///     .visible(true)
+///     .enter(VisibilityTransition::fade().and(VisibilityTransition::slide(0.0, 1.0)))
+///     .exit(
+///         VisibilityTransition::fade()
+///             .and(VisibilityTransition::expand_vertically(Alignment::TopStart)),
+///     )
+///     .child(|| {
+///         text().content("Saved");
+///     });
+/// # }
+/// # component();
+/// ```
+#[tessera]
+pub fn animated_visibility(
+    visible: Option<bool>,
+    enter: Option<VisibilityTransition>,
+    exit: Option<VisibilityTransition>,
+    modifier: Option<Modifier>,
+    child: Option<RenderSlot>,
+) {
+    let visible = visible.unwrap_or(true);
+    let transition = if visible {
+        enter.unwrap_or_default()
+    } else {
+        exit.unwrap_or_default()
+    };
+    let modifier = modifier.unwrap_or_default();
+    let child = child.unwrap_or_else(RenderSlot::empty);
+    let target = if visible { 1.0 } else { 0.0 };
+    // Springs may overshoot; no effect is meaningful outside the range.
+    let progress = animate_as_state_with(target, transition.spec.clone()).clamp(0.0, 1.0);
+    let rendered = visible || progress > 0.0;
+    transition_layer(transition, progress, modifier, move || {
+        if rendered {
+            child.render();
+        }
+    });
+}
+
+/// Renders `child` as it looks at `progress` of `transition`, where `1.0` is
+/// fully shown.
+pub(crate) fn transition_layer(
+    transition: VisibilityTransition,
+    progress: f32,
+    modifier: Modifier,
+    child: impl Fn() + Send + Sync + 'static,
+) {
+    let mut modifier = modifier.alpha(transition.alpha_at(progress));
+    if transition.clips_at(progress) {
+        modifier = modifier.clip_to_bounds();
+    }
+    layout()
+        .modifier(modifier)
+        .layout_policy(AnimatedVisibilityLayout {
+            transition,
+            progress,
+        })
+        .child(child);
+}
+
+#[derive(Clone, PartialEq)]
+struct AnimatedVisibilityLayout {
+    transition: VisibilityTransition,
+    progress: f32,
+}
+
+impl LayoutPolicy for AnimatedVisibilityLayout {
+    fn measure(&self, input: &MeasureScope<'_>) -> Result<LayoutResult, MeasurementError> {
+        let parent_constraint = *input.parent_constraint().as_ref();
+        let Some(child) = input.children().first().copied() else {
+            return Ok(LayoutResult::new(ComputedData {
+                width: parent_constraint.width.clamp(Px::ZERO),
+                height: parent_constraint.height.clamp(Px::ZERO),
+            }));
+        };
+        let content = child
+            .measure(&input.parent_constraint().without_min())?
+            .size();
+        let (size, position) = self.transition.frame_at(self.progress, content);
+        let mut result = LayoutResult::default();
+        result.place_child(child, position);
+        Ok(result.with_size(ComputedData {
+            width: parent_constraint.width.clamp(size.width),
+            height: parent_constraint.height.clamp(size.height),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tessera_ui::{ComputedData, Px, PxPosition};
+
+    use crate::alignment::Alignment;
+
+    use super::VisibilityTransition;
+
+    #[test]
+    fn expand_and_slide_follow_progress() {
+        let content = ComputedData {
+            width: Px(100),
+            height: Px(40),
+        };
+        let transition = VisibilityTransition::slide(0.5, 0.0).and(
+            VisibilityTransition::expand_vertically(Alignment::BottomStart),
+        );
+
+        let (size, position) = transition.frame_at(0.5, content);
+        assert_eq!((size.width, size.height), (Px(100), Px(20)));
+        assert_eq!(position, PxPosition::new(Px(25), Px(-20)));
+
+        let (size, position) = transition.frame_at(1.0, content);
+        assert_eq!((size.width, size.height), (Px(100), Px(40)));
+        assert_eq!(position, PxPosition::ZERO);
+        assert!(!transition.clips_at(1.0));
+    }
+}
@@ -1,11 +1,26 @@
 //! Animation mapping for UI components.
-///
+//!
+//! Both mappings honor the system reduced-motion preference by jumping
+//! between the start and end values instead of animating.
+
+use tessera_ui::system_preferences::system_preferences;
+
+/// Snaps progress to the nearest end when the user prefers reduced motion.
+fn reduced_motion_progress(progress: f32) -> Option<f32> {
+    system_preferences()
+        .reduce_motion
+        .then(|| if progress < 0.5 { 0.0 } else { 1.0 })
+}
+
 /// Cubic ease-in-out mapping (smooth start and end).
 /// Input: linear progress in [0.0, 1.0].
 /// Output: eased progress in [0.0, 1.0].
 pub(crate) fn easing(progress: f32) -> f32 {
     // Cubic ease-in-out
     let t = progress.clamp(0.0, 1.0);
+    if let Some(snapped) = reduced_motion_progress(t) {
+        return snapped;
+    }
     if t < 0.5 {
         4.0 * t * t * t
     } else {
@@ -25,9 +40,13 @@ pub(crate) fn easing(progress: f32) -> f32 {
 ///
 /// # Returns
 ///
-/// A value that starts at 0.0, overshoots 1.0, and settles at 1.0.
+/// A value that starts at 0.0, overshoots 1.0, and settles at 1.0. When the
+/// user prefers reduced motion, the value jumps from 0.0 to 1.0 halfway.
 pub fn spring(progress: f32, stiffness: f32, damping: f32) -> f32 {
     let t = progress.clamp(0.0, 1.0);
+    if let Some(snapped) = reduced_motion_progress(t) {
+        return snapped;
+    }
 
     // Boundary checks to avoid expensive math at start/end
     if t <= 0.0 {
@@ -0,0 +1,692 @@
+//! Images loaded off the UI thread through shared memory and disk caches.
+//!
+//! ## Usage
+//!
+//! Show remote or on-disk photos in feeds and grids without stalling frames.
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        Arc, OnceLock,
+        atomic::{AtomicU64, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+use image::GenericImageView;
+use lru::LruCache;
+use parking_lot::{Mutex, RwLock};
+use tessera_ui::{
+    AxisConstraint, ComputedData, FrameNanosControl, LayoutResult, MeasurementError, Modifier, Px,
+    PxPosition, PxSize, RenderSlot, State, current_frame_nanos,
+    layout::{LayoutPolicy, MeasureScope, RenderInput, RenderPolicy, layout},
+    receive_frame_nanos, remember, tessera,
+};
+use thiserror::Error;
+
+use crate::{
+    image::{ImageData, decode_dynamic_image},
+    pipelines::image::command::ImageCommand,
+};
+
+const WORKER_COUNT: usize = 4;
+const DEFAULT_MEMORY_CACHE_BYTES: usize = 64 * 1024 * 1024;
+const DEFAULT_DISK_CACHE_BYTES: u64 = 256 * 1024 * 1024;
+const FADE_DURATION_NANOS: u64 = 200_000_000;
+#[cfg(feature = "network-image")]
+const MAX_DOWNLOAD_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Where an [`async_image`] reads its encoded bytes from.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ImageSource {
+    /// An `http` or `https` URL, fetched when the `network-image` feature is
+    /// enabled.
+    Url(String),
+    /// A file on the local filesystem.
+    Path(PathBuf),
+    /// Encoded bytes already in memory.
+    Bytes(Arc<[u8]>),
+}
+
+impl From<String> for ImageSource {
+    /// Treats `http://` and `https://` strings as URLs and anything else as
+    /// a path.
+    fn from(value: String) -> Self {
+        if value.starts_with("http://") || value.starts_with("https://") {
+            Self::Url(value)
+        } else {
+            Self::Path(PathBuf::from(value))
+        }
+    }
+}
+
+impl From<&str> for ImageSource {
+    fn from(value: &str) -> Self {
+        Self::from(value.to_owned())
+    }
+}
+
+impl From<PathBuf> for ImageSource {
+    fn from(value: PathBuf) -> Self {
+        Self::Path(value)
+    }
+}
+
+impl From<&Path> for ImageSource {
+    fn from(value: &Path) -> Self {
+        Self::Path(value.to_path_buf())
+    }
+}
+
+impl From<Vec<u8>> for ImageSource {
+    fn from(value: Vec<u8>) -> Self {
+        Self::Bytes(value.into())
+    }
+}
+
+impl From<Arc<[u8]>> for ImageSource {
+    fn from(value: Arc<[u8]>) -> Self {
+        Self::Bytes(value)
+    }
+}
+
+/// Errors reported while loading an [`async_image`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum AsyncImageError {
+    /// Reading a local file failed.
+    #[error("failed to read image: {0}")]
+    Io(String),
+    /// Downloading a URL failed or network loading is disabled.
+    #[error("failed to download image: {0}")]
+    Network(String),
+    /// The bytes are not a supported image format.
+    #[error("failed to decode image: {0}")]
+    Decode(String),
+}
+
+/// Limits and location of the caches shared by every [`async_image`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImageCacheConfig {
+    /// Decoded bytes kept in memory before the least recently used images are
+    /// dropped.
+    pub memory_bytes: usize,
+    /// Directory for downloaded images, or `None` to disable the disk cache.
+    pub disk_dir: Option<PathBuf>,
+    /// Size of the disk cache before the oldest downloads are removed.
+    pub disk_bytes: u64,
+}
+
+impl Default for ImageCacheConfig {
+    fn default() -> Self {
+        Self {
+            memory_bytes: DEFAULT_MEMORY_CACHE_BYTES,
+            disk_dir: Some(std::env::temp_dir().join("tessera-image-cache")),
+            disk_bytes: DEFAULT_DISK_CACHE_BYTES,
+        }
+    }
+}
+
+/// Replaces the shared cache configuration used by later loads.
+pub fn configure_image_cache(config: ImageCacheConfig) {
+    let loader = loader();
+    loader.memory.lock().trim(config.memory_bytes);
+    *loader.config.write() = config;
+}
+
+/// Drops every decoded image held in memory; the disk cache is kept.
+pub fn clear_image_memory_cache() {
+    loader().memory.lock().trim(0);
+}
+
+type CacheKey = (ImageSource, Option<(u32, u32)>);
+type LoadResult = Result<LoadedImage, AsyncImageError>;
+
+#[derive(Clone, Debug, PartialEq)]
+struct LoadedImage {
+    data: Arc<ImageData>,
+    // Size before downsampling, so layout doesn't depend on the decode size.
+    intrinsic: PxSize,
+    // Size the image was downsampled to cover, or `None` at full resolution.
+    target: Option<(u32, u32)>,
+}
+
+impl LoadedImage {
+    fn covers(&self, target: Option<(u32, u32)>) -> bool {
+        match (self.target, target) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some((width, height)), Some((target_width, target_height))) => {
+                width >= target_width && height >= target_height
+            }
+        }
+    }
+
+    fn byte_len(&self) -> usize {
+        self.data.data.len()
+    }
+}
+
+#[derive(Default)]
+struct PendingLoad {
+    result: Mutex<Option<LoadResult>>,
+}
+
+impl PendingLoad {
+    fn finished(result: LoadResult) -> Arc<Self> {
+        Arc::new(Self {
+            result: Mutex::new(Some(result)),
+        })
+    }
+
+    fn result(&self) -> Option<LoadResult> {
+        self.result.lock().clone()
+    }
+
+    fn is_done(&self) -> bool {
+        self.result.lock().is_some()
+    }
+}
+
+struct MemoryCache {
+    entries: LruCache<CacheKey, LoadedImage>,
+    bytes: usize,
+}
+
+impl MemoryCache {
+    fn insert(&mut self, key: CacheKey, image: LoadedImage, budget: usize) {
+        self.bytes += image.byte_len();
+        if let Some(previous) = self.entries.put(key, image) {
+            self.bytes -= previous.byte_len();
+        }
+        self.trim(budget);
+    }
+
+    fn trim(&mut self, budget: usize) {
+        while self.bytes > budget
+            && let Some((_, image)) = self.entries.pop_lru()
+        {
+            self.bytes -= image.byte_len();
+        }
+    }
+
+    /// Returns the sharpest cached variant of `source`, at any size.
+    fn best(&self, source: &ImageSource) -> Option<LoadedImage> {
+        self.entries
+            .iter()
+            .filter(|((cached, _), _)| cached == source)
+            .max_by_key(|(_, image)| image.byte_len())
+            .map(|(_, image)| image.clone())
+    }
+}
+
+struct ImageLoader {
+    config: RwLock<ImageCacheConfig>,
+    memory: Mutex<MemoryCache>,
+    pending: Mutex<HashMap<CacheKey, Arc<PendingLoad>>>,
+    jobs: mpsc::Sender<(CacheKey, Arc<PendingLoad>)>,
+}
+
+fn loader() -> &'static ImageLoader {
+    static LOADER: OnceLock<ImageLoader> = OnceLock::new();
+    LOADER.get_or_init(|| {
+        let (jobs, receiver) = mpsc::channel::<(CacheKey, Arc<PendingLoad>)>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        for index in 0..WORKER_COUNT {
+            let receiver = receiver.clone();
+            let spawned = thread::Builder::new()
+                .name(format!("tessera-image-{index}"))
+                .spawn(move || {
+                    loop {
+                        let job = receiver.lock().recv();
+                        let Ok((key, pending)) = job else {
+                            break;
+                        };
+                        loader().run(key, &pending);
+                    }
+                });
+            if let Err(error) = spawned {
+                tracing::error!("failed to start image loader thread: {error}");
+            }
+        }
+        ImageLoader {
+            config: RwLock::new(ImageCacheConfig::default()),
+            memory: Mutex::new(MemoryCache {
+                entries: LruCache::unbounded(),
+                bytes: 0,
+            }),
+            pending: Mutex::new(HashMap::new()),
+            jobs,
+        }
+    })
+}
+
+impl ImageLoader {
+    /// Returns the load for `key`, joining one already in flight instead of
+    /// decoding the same image twice.
+    fn request(&self, key: CacheKey) -> Arc<PendingLoad> {
+        if let Some(image) = self.memory.lock().entries.get(&key) {
+            return PendingLoad::finished(Ok(image.clone()));
+        }
+        let mut pending = self.pending.lock();
+        if let Some(load) = pending.get(&key) {
+            return load.clone();
+        }
+        let load = Arc::new(PendingLoad::default());
+        if self.jobs.send((key.clone(), load.clone())).is_err() {
+            return PendingLoad::finished(Err(AsyncImageError::Io(
+                "image loader is not running".to_owned(),
+            )));
+        }
+        pending.insert(key, load.clone());
+        load
+    }
+
+    fn run(&self, key: CacheKey, pending: &PendingLoad) {
+        let config = self.config.read().clone();
+        let result = load_image(&key.0, key.1, &config);
+        if let Ok(image) = &result {
+            self.memory
+                .lock()
+                .insert(key.clone(), image.clone(), config.memory_bytes);
+        }
+        *pending.result.lock() = Some(result);
+        self.pending.lock().remove(&key);
+    }
+}
+
+fn load_image(
+    source: &ImageSource,
+    target: Option<(u32, u32)>,
+    config: &ImageCacheConfig,
+) -> LoadResult {
+    let bytes = match source {
+        ImageSource::Url(url) => Cow::Owned(fetch(url, config)?),
+        ImageSource::Path(path) => {
+            Cow::Owned(fs::read(path).map_err(|error| AsyncImageError::Io(error.to_string()))?)
+        }
+        ImageSource::Bytes(bytes) => Cow::Borrowed(bytes.as_ref()),
+    };
+    let decoded = image::load_from_memory(&bytes)
+        .map_err(|error| AsyncImageError::Decode(error.to_string()))?;
+    let (width, height) = decoded.dimensions();
+    let downsampled = target.and_then(|target| downsampled_size((width, height), target));
+    let decoded = match downsampled {
+        Some((width, height)) => decoded.thumbnail(width, height),
+        None => decoded,
+    };
+    Ok(LoadedImage {
+        data: Arc::new(decode_dynamic_image(decoded)),
+        intrinsic: PxSize::new(Px(width as i32), Px(height as i32)),
+        target: downsampled.and(target),
+    })
+}
+
+/// Smallest size with the source's aspect ratio that still covers `target`,
+/// or `None` when the source is no larger than that.
+fn downsampled_size(source: (u32, u32), target: (u32, u32)) -> Option<(u32, u32)> {
+    let (width, height) = (f64::from(source.0), f64::from(source.1));
+    let scale = (f64::from(target.0) / width).max(f64::from(target.1) / height);
+    (scale < 1.0).then(|| {
+        (
+            ((width * scale).round() as u32).max(1),
+            ((height * scale).round() as u32).max(1),
+        )
+    })
+}
+
+fn fetch(url: &str, config: &ImageCacheConfig) -> Result<Vec<u8>, AsyncImageError> {
+    let cached = config
+        .disk_dir
+        .as_ref()
+        .map(|dir| dir.join(cache_file_name(url)));
+    if let Some(path) = &cached
+        && let Ok(bytes) = fs::read(path)
+    {
+        return Ok(bytes);
+    }
+    let bytes = download(url)?;
+    if let (Some(dir), Some(path)) = (&config.disk_dir, &cached)
+        && let Err(error) = store_on_disk(dir, path, &bytes, config.disk_bytes)
+    {
+        tracing::warn!("failed to cache {url} on disk: {error}");
+    }
+    Ok(bytes)
+}
+
+#[cfg(feature = "network-image")]
+fn download(url: &str) -> Result<Vec<u8>, AsyncImageError> {
+    let mut response = ureq::get(url)
+        .call()
+        .map_err(|error| AsyncImageError::Network(error.to_string()))?;
+    response
+        .body_mut()
+        .with_config()
+        .limit(MAX_DOWNLOAD_BYTES)
+        .read_to_vec()
+        .map_err(|error| AsyncImageError::Network(error.to_string()))
+}
+
+#[cfg(not(feature = "network-image"))]
+fn download(url: &str) -> Result<Vec<u8>, AsyncImageError> {
+    Err(AsyncImageError::Network(format!(
+        "loading {url} requires the `network-image` feature"
+    )))
+}
+
+fn cache_file_name(url: &str) -> String {
+    // FNV-1a keeps file names stable across builds, unlike `DefaultHasher`.
+    let hash = url.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    format!("{hash:016x}")
+}
+
+fn store_on_disk(dir: &Path, path: &Path, bytes: &[u8], budget: u64) -> std::io::Result<()> {
+    static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    fs::create_dir_all(dir)?;
+    // Write then rename so concurrent readers never see a partial file.
+    let temp = path.with_extension(format!(
+        "{}.tmp",
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::write(&temp, bytes)?;
+    fs::rename(&temp, path)?;
+
+    let mut files = fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            metadata
+                .is_file()
+                .then(|| (entry.path(), metadata.len(), metadata.modified().ok()))
+        })
+        .collect::<Vec<_>>();
+    let mut total: u64 = files.iter().map(|(_, len, _)| len).sum();
+    files.sort_by_key(|(_, _, modified)| *modified);
+    for (file, len, _) in files {
+        if total <= budget {
+            break;
+        }
+        if fs::remove_file(file).is_ok() {
+            total -= len;
+        }
+    }
+    Ok(())
+}
+
+#[derive(Default)]
+struct AsyncImageState {
+    source: Option<ImageSource>,
+    // `None` until measured, then the size to downsample to, which is `None`
+    // itself when the layout is unbounded.
+    display_size: Option<Option<(u32, u32)>>,
+    load: Option<Arc<PendingLoad>>,
+    image: Option<LoadedImage>,
+    error: Option<AsyncImageError>,
+    fade_start: Option<u64>,
+}
+
+impl AsyncImageState {
+    fn reset(&mut self, source: ImageSource) {
+        // A cached variant shows immediately, without fading, even if a
+        // sharper one is still needed.
+        let image = loader().memory.lock().best(&source);
+        *self = Self {
+            source: Some(source),
+            display_size: self.display_size,
+            image,
+            ..Self::default()
+        };
+    }
+
+    fn wants_load(&self) -> bool {
+        self.error.is_none()
+            && self.load.is_none()
+            && self
+                .display_size
+                .is_some_and(|size| self.image.as_ref().is_none_or(|image| !image.covers(size)))
+    }
+
+    fn needs_frames(&self) -> bool {
+        self.display_size.is_none()
+            || self.wants_load()
+            || self.load.is_some()
+            || self.fade_start.is_some()
+    }
+
+    fn is_stale(&self) -> bool {
+        self.wants_load()
+            || self.fade_start.is_some()
+            || self.load.as_ref().is_some_and(|load| load.is_done())
+    }
+
+    fn sync(&mut self, frame_nanos: u64, fade_in: bool) {
+        if self.wants_load()
+            && let (Some(source), Some(size)) = (&self.source, self.display_size)
+        {
+            self.load = Some(loader().request((source.clone(), size)));
+        }
+        if let Some(result) = self.load.as_ref().and_then(|load| load.result()) {
+            self.load = None;
+            match result {
+                Ok(image) => {
+                    if self.image.is_none() && fade_in {
+                        self.fade_start = Some(frame_nanos);
+                    }
+                    self.image = Some(image);
+                }
+                Err(error) => {
+                    tracing::warn!("failed to load image: {error}");
+                    self.error = Some(error);
+                }
+            }
+        }
+        if self
+            .fade_start
+            .is_some_and(|start| frame_nanos.saturating_sub(start) >= FADE_DURATION_NANOS)
+        {
+            self.fade_start = None;
+        }
+    }
+
+    fn opacity(&self, frame_nanos: u64) -> f32 {
+        self.fade_start.map_or(1.0, |start| {
+            (frame_nanos.saturating_sub(start) as f32 / FADE_DURATION_NANOS as f32).min(1.0)
+        })
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct AsyncImageLayout {
+    state: State<AsyncImageState>,
+    image: Option<LoadedImage>,
+    opacity: f32,
+}
+
+impl LayoutPolicy for AsyncImageLayout {
+    fn measure(&self, input: &MeasureScope<'_>) -> Result<LayoutResult, MeasurementError> {
+        let parent = input.parent_constraint();
+        let display_size = parent
+            .width()
+            .max
+            .zip(parent.height().max)
+            .map(|(width, height)| (width.0.max(1) as u32, height.0.max(1) as u32));
+        if self
+            .state
+            .with(|state| state.display_size != Some(display_size))
+        {
+            self.state
+                .with_mut(|state| state.display_size = Some(display_size));
+        }
+
+        let mut result = LayoutResult::default();
+        let mut slot = None;
+        for child in input.children() {
+            let measured = child.measure(&parent.without_min())?;
+            slot = Some(measured);
+        }
+        let (width, height) = match &self.image {
+            Some(image) => (
+                parent.width().clamp(image.intrinsic.width),
+                parent.height().clamp(image.intrinsic.height),
+            ),
+            None => {
+                let fill =
+                    |axis: AxisConstraint, content: Px| axis.clamp(axis.max.unwrap_or(content));
+                (
+                    fill(parent.width(), slot.map_or(Px::ZERO, |slot| slot.width)),
+                    fill(parent.height(), slot.map_or(Px::ZERO, |slot| slot.height)),
+                )
+            }
+        };
+        if let Some(slot) = slot {
+            result.place_child(
+                slot,
+                PxPosition::new(
+                    Px((width.0 - slot.width.0) / 2),
+                    Px((height.0 - slot.height.0) / 2),
+                ),
+            );
+        }
+        Ok(result.with_size(ComputedData { width, height }))
+    }
+}
+
+impl RenderPolicy for AsyncImageLayout {
+    fn record(&self, input: &mut RenderInput<'_>) {
+        let Some(image) = &self.image else {
+            return;
+        };
+        input
+            .metadata_mut()
+            .fragment_mut()
+            .push_draw_command(ImageCommand {
+                data: image.data.clone(),
+                opacity: self.opacity,
+                rotation: 0.0,
+            });
+    }
+}
+
+/// # async_image
+///
+/// Loads an image from a URL, file or bytes off the UI thread, downsampled to
+/// its display size and cached in memory and on disk.
+///
+/// ## Usage
+///
+/// Show remote thumbnails and photos in lists and grids.
+///
+/// ## Parameters
+///
+/// - `source` — URL, path or encoded bytes; `http(s)://` strings load over the
+///   network when the `network-image` feature is enabled.
+/// - `placeholder` — optional slot shown while loading.
+/// - `error` — optional slot shown when loading fails.
+/// - `fade_in` — optional toggle for fading freshly loaded images in; on by
+///   default.
+/// - `modifier` — optional modifier chain applied to the image.
+///
+/// ## Examples
+///
+/// ```
+/// use tessera_components::{async_image::async_image, progress::circular_progress_indicator};
+/// # use tessera_components::theme::{MaterialTheme, material_theme};
+/// # use tessera_ui::tessera;
+///
+/// # #[tessera]
+/// # fn component() {
+/// # material_theme()
+/// #     .theme(|| MaterialTheme::default())
+/// #     .child(|| {
+/// async_image("https://example.com/photo.jpg").placeholder(|| {
+///     circular_progress_indicator();
+/// });
+/// # });
+/// # }
+/// # component();
+/// ```
+#[tessera]
+pub fn async_image(
+    #[prop(into)] source: ImageSource,
+    placeholder: Option<RenderSlot>,
+    error: Option<RenderSlot>,
+    fade_in: Option<bool>,
+    modifier: Option<Modifier>,
+) {
+    let fade_in = fade_in.unwrap_or(true);
+    let state = remember(AsyncImageState::default);
+    if state.with(|state| state.source.as_ref() != Some(&source)) {
+        state.with_mut(|state| state.reset(source));
+    }
+    if state.with(AsyncImageState::needs_frames) {
+        receive_frame_nanos(move |frame_nanos| {
+            if state.with(AsyncImageState::is_stale) {
+                state.with_mut(|state| state.sync(frame_nanos, fade_in));
+            }
+            if state.with(AsyncImageState::needs_frames) {
+                FrameNanosControl::Continue
+            } else {
+                FrameNanosControl::Stop
+            }
+        });
+    }
+
+    let (image, failed, opacity) = state.with(|state| {
+        (
+            state.image.clone(),
+            state.error.is_some(),
+            state.opacity(current_frame_nanos()),
+        )
+    });
+    let slot = match (&image, failed) {
+        (Some(_), _) => None,
+        (None, true) => error,
+        (None, false) => placeholder,
+    };
+    let policy = AsyncImageLayout {
+        state,
+        image,
+        opacity,
+    };
+    layout()
+        .modifier(modifier.unwrap_or_default())
+        .layout_policy(policy.clone())
+        .render_policy(policy)
+        .child(move || {
+            if let Some(slot) = &slot {
+                slot.render();
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn downsampling_covers_the_display_size() {
+        assert_eq!(downsampled_size((4000, 3000), (200, 200)), Some((267, 200)));
+        assert_eq!(
+            downsampled_size((1000, 4000), (300, 300)),
+            Some((300, 1200))
+        );
+        assert_eq!(downsampled_size((100, 80), (200, 200)), None);
+    }
+
+    #[test]
+    fn string_sources_pick_url_or_path() {
+        assert_eq!(
+            ImageSource::from("https://example.com/a.png"),
+            ImageSource::Url("https://example.com/a.png".to_owned())
+        );
+        assert_eq!(
+            ImageSource::from("assets/a.png"),
+            ImageSource::Path(PathBuf::from("assets/a.png"))
+        );
+    }
+}
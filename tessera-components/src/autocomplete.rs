@@ -0,0 +1,505 @@
+//! Text fields suggesting completions while typing.
+//!
+//! ## Usage
+//!
+//! Complete search queries, addresses, tags and other free-form input.
+use std::{sync::Arc, time::Duration};
+
+use parking_lot::Mutex;
+use tessera_ui::{
+    CallbackWith, Color, Dp, FrameNanosControl, Modifier, State, current_frame_nanos,
+    receive_frame_nanos, remember, tessera, use_context, winit,
+};
+
+use crate::{
+    alignment::Alignment,
+    lazy_list::{LazyListController, lazy_column},
+    modifier::{ModifierExt as _, with_keyboard_preview_input},
+    popover::popover,
+    shape_def::Shape,
+    surface::{SurfaceStyle, surface},
+    text::text,
+    text_edit_core::TextSelection,
+    text_field::{TextFieldDefaults, text_field},
+    text_input::TextInputController,
+    theme::{MaterialAlpha, MaterialTheme},
+};
+
+const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(300);
+const SUGGESTION_HEIGHT: Dp = Dp(48.0);
+const SUGGESTION_HORIZONTAL_PADDING: Dp = Dp(16.0);
+const MAX_VISIBLE_SUGGESTIONS: usize = 5;
+const MENU_ELEVATION: Dp = Dp(3.0);
+
+type SuggestionSlot = Arc<Mutex<Option<(u64, Vec<String>)>>>;
+
+/// Query handed to the suggestion provider of an [`autocomplete`].
+///
+/// Requests can be moved to other threads and answered once results are
+/// ready; answers to queries that were typed over are ignored.
+#[derive(Clone)]
+pub struct SuggestionRequest {
+    query: String,
+    generation: u64,
+    slot: SuggestionSlot,
+}
+
+impl SuggestionRequest {
+    /// Returns the text to suggest completions for.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Delivers the suggestions for this query.
+    pub fn respond(&self, suggestions: Vec<String>) {
+        *self.slot.lock() = Some((self.generation, suggestions));
+    }
+}
+
+/// Query, provider results and highlighted suggestion of an
+/// [`autocomplete`].
+pub struct AutocompleteController {
+    query: String,
+    results: Vec<String>,
+    highlighted: Option<usize>,
+    expanded: bool,
+    loading: bool,
+    /// Frame time at which the pending query is sent to the provider.
+    due_nanos: Option<u64>,
+    generation: u64,
+    slot: SuggestionSlot,
+}
+
+impl AutocompleteController {
+    /// Creates a controller with an empty query.
+    pub fn new() -> Self {
+        Self {
+            query: String::new(),
+            results: Vec::new(),
+            highlighted: None,
+            expanded: false,
+            loading: false,
+            due_nanos: None,
+            generation: 0,
+            slot: SuggestionSlot::default(),
+        }
+    }
+
+    /// Returns the text typed into the field.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Returns whether the suggestion list is shown when it has entries.
+    pub fn is_expanded(&self) -> bool {
+        self.expanded
+    }
+
+    /// Returns whether the provider is working on the current query.
+    pub fn is_loading(&self) -> bool {
+        self.due_nanos.is_some() || self.loading
+    }
+
+    /// Hides the suggestion list until the query changes.
+    pub fn dismiss(&mut self) {
+        self.expanded = false;
+        self.highlighted = None;
+    }
+
+    fn query_changed(&mut self, query: String, long_enough: bool, due_nanos: Option<u64>) {
+        self.query = query;
+        self.highlighted = None;
+        self.expanded = long_enough;
+        if long_enough {
+            self.due_nanos = due_nanos;
+        } else {
+            // Outdated answers must not reopen the list.
+            self.generation += 1;
+            self.due_nanos = None;
+            self.loading = false;
+            self.results.clear();
+        }
+    }
+
+    fn needs_poll(&self, frame_nanos: u64) -> bool {
+        self.slot.lock().is_some() || self.due_nanos.is_some_and(|due| frame_nanos >= due)
+    }
+
+    /// Takes delivered results and returns the request to send once the
+    /// debounce has elapsed.
+    fn poll(&mut self, frame_nanos: u64) -> Option<SuggestionRequest> {
+        let delivered = self.slot.lock().take();
+        if let Some((generation, results)) = delivered
+            && generation == self.generation
+        {
+            self.results = results;
+            self.highlighted = None;
+            self.loading = false;
+        }
+        if !self.due_nanos.is_some_and(|due| frame_nanos >= due) {
+            return None;
+        }
+        self.due_nanos = None;
+        self.generation += 1;
+        self.loading = true;
+        Some(SuggestionRequest {
+            query: self.query.clone(),
+            generation: self.generation,
+            slot: self.slot.clone(),
+        })
+    }
+
+    fn move_highlight(&mut self, count: usize, forward: bool) -> Option<usize> {
+        if count == 0 {
+            return None;
+        }
+        let next = match (self.highlighted, forward) {
+            (None, true) => 0,
+            (None, false) => count - 1,
+            (Some(index), true) => (index + 1) % count,
+            (Some(index), false) => (index + count - 1) % count,
+        };
+        self.highlighted = Some(next);
+        self.expanded = true;
+        Some(next)
+    }
+
+    fn select(&mut self, value: &str) {
+        self.query = value.to_string();
+        self.expanded = false;
+        self.highlighted = None;
+        self.due_nanos = None;
+    }
+}
+
+impl Default for AutocompleteController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the suggestions containing `query`, ignoring case, with those
+/// starting with it first.
+fn filter_suggestions(suggestions: &[String], query: &str) -> Vec<String> {
+    let query = query.to_lowercase();
+    let (mut prefixed, mut contained) = (Vec::new(), Vec::new());
+    for suggestion in suggestions {
+        let lowered = suggestion.to_lowercase();
+        if lowered.starts_with(&query) {
+            prefixed.push(suggestion.clone());
+        } else if lowered.contains(&query) {
+            contained.push(suggestion.clone());
+        }
+    }
+    prefixed.append(&mut contained);
+    prefixed
+}
+
+/// # autocomplete
+///
+/// Text field showing a list of completions for the typed text.
+///
+/// Fixed `suggestions` are filtered by the typed text. A `provider` instead
+/// receives each query once typing pauses for `debounce` and may answer later
+/// from any thread. The arrow keys move through the list, Enter picks the
+/// highlighted suggestion and Escape hides the list.
+///
+/// Render inside an [`overlay_host`](crate::overlay::overlay_host) so the list
+/// is drawn above the content below the field.
+///
+/// ## Usage
+///
+/// Suggest search queries, tags or other values while the user types.
+///
+/// ## Parameters
+///
+/// - `suggestions` — optional fixed suggestions, filtered by the typed text
+/// - `provider` — optional callback producing suggestions for a query; used
+///   instead of `suggestions` when set
+/// - `on_select` — optional callback invoked with the picked suggestion
+/// - `on_query_change` — optional callback invoked with the typed text
+/// - `debounce` — optional pause in typing before `provider` is asked; defaults
+///   to 300 ms
+/// - `min_query_length` — optional number of characters needed before
+///   suggestions show; defaults to `1`
+/// - `label` — optional label of the field
+/// - `placeholder` — optional placeholder shown while the field is empty
+/// - `initial_text` — optional text the field starts with
+/// - `enabled` — whether the field accepts input; defaults to `true`
+/// - `controller` — optional controller holding the query and suggestions
+/// - `modifier` — optional modifier chain applied to the field
+///
+/// ## Examples
+///
+/// ```
+/// # use tessera_ui::tessera;
+/// # #[tessera]
+/// # fn component() {
+/// use tessera_components::{autocomplete::autocomplete, overlay::overlay_host};
+/// # use tessera_components::theme::{MaterialTheme, material_theme};
+///
+/// # material_theme()
+/// #     .theme(|| MaterialTheme::default())
+/// #     .child(|| {
+/// overlay_host().content(|| {
+///     autocomplete()
+///         .label("Language")
+///         .suggestions(vec![
+///             "Rust".to_string(),
+///             "Ruby".to_string(),
+///             "Zig".to_string(),
+///         ])
+///         .on_select(|language: String| println!("picked {language}"));
+/// });
+/// # });
+/// # }
+/// # component();
+/// ```
+#[tessera]
+pub fn autocomplete(
+    suggestions: Option<Vec<String>>,
+    provider: Option<CallbackWith<SuggestionRequest>>,
+    on_select: Option<CallbackWith<String>>,
+    on_query_change: Option<CallbackWith<String>>,
+    debounce: Option<Duration>,
+    min_query_length: Option<usize>,
+    #[prop(into)] label: Option<String>,
+    #[prop(into)] placeholder: Option<String>,
+    #[prop(into)] initial_text: Option<String>,
+    enabled: Option<bool>,
+    controller: Option<State<AutocompleteController>>,
+    modifier: Option<Modifier>,
+) {
+    let debounce = debounce.unwrap_or(DEFAULT_DEBOUNCE);
+    let min_query_length = min_query_length.unwrap_or(1);
+    let controller = controller.unwrap_or_else(|| remember(AutocompleteController::new));
+    let text_controller = remember(|| {
+        let mut controller = TextInputController::new(
+            TextFieldDefaults::FONT_SIZE,
+            Some(TextFieldDefaults::LINE_HEIGHT),
+        );
+        if let Some(text) = &initial_text {
+            controller.set_text(text);
+        }
+        controller
+    });
+    let list = remember(LazyListController::new);
+
+    if controller.with(AutocompleteController::is_loading) {
+        receive_frame_nanos(move |frame_nanos| {
+            if controller.with(|controller| controller.needs_poll(frame_nanos)) {
+                let request = controller.with_mut(|controller| controller.poll(frame_nanos));
+                if let (Some(request), Some(provider)) = (request, provider) {
+                    provider.call(request);
+                }
+            }
+            if controller.with(AutocompleteController::is_loading) {
+                FrameNanosControl::Continue
+            } else {
+                FrameNanosControl::Stop
+            }
+        });
+    }
+
+    let (query, expanded, highlighted) = controller.with(|controller| {
+        (
+            controller.query.clone(),
+            controller.expanded,
+            controller.highlighted,
+        )
+    });
+    let visible = Arc::new(match provider {
+        Some(_) => controller.with(|controller| controller.results.clone()),
+        None => filter_suggestions(suggestions.as_deref().unwrap_or_default(), &query),
+    });
+
+    let pick = CallbackWith::new(move |value: String| {
+        controller.with_mut(|controller| controller.select(&value));
+        text_controller.with_mut(|text| {
+            text.set_text_and_selection(&value, TextSelection::collapsed(value.len()));
+        });
+        if let Some(on_select) = on_select {
+            on_select.call(value);
+        }
+    });
+    let on_change = CallbackWith::new(move |text: String| {
+        let long_enough = text.chars().count() >= min_query_length;
+        let due_nanos = provider.map(|_| current_frame_nanos() + debounce.as_nanos() as u64);
+        controller.with_mut(|controller| {
+            controller.query_changed(text.clone(), long_enough, due_nanos);
+        });
+        if let Some(on_query_change) = on_query_change {
+            on_query_change.call(text.clone());
+        }
+        text
+    });
+
+    let key_suggestions = visible.clone();
+    let anchor_modifier = with_keyboard_preview_input(Modifier::new(), move |mut input| {
+        let count = key_suggestions.len();
+        let mut picked = None;
+        input.keyboard_events.retain(|event| {
+            if event.state != winit::event::ElementState::Pressed || count == 0 {
+                return true;
+            }
+            use winit::keyboard::{Key, NamedKey};
+            match &event.logical_key {
+                Key::Named(key @ (NamedKey::ArrowDown | NamedKey::ArrowUp)) => {
+                    let forward = *key == NamedKey::ArrowDown;
+                    let index =
+                        controller.with_mut(|controller| controller.move_highlight(count, forward));
+                    if let Some(index) = index {
+                        let item_height = SUGGESTION_HEIGHT.to_px();
+                        let viewport = item_height * count.min(MAX_VISIBLE_SUGGESTIONS) as i32;
+                        list.with_mut(|list| {
+                            list.reveal_uniform_item(index, item_height, viewport)
+                        });
+                    }
+                    false
+                }
+                Key::Named(NamedKey::Enter) => {
+                    let highlighted = controller
+                        .with(|controller| controller.expanded.then_some(controller.highlighted))
+                        .flatten();
+                    match highlighted {
+                        Some(index) => {
+                            picked = key_suggestions.get(index).cloned();
+                            false
+                        }
+                        None => true,
+                    }
+                }
+                Key::Named(NamedKey::Escape)
+                    if controller.with(AutocompleteController::is_expanded) =>
+                {
+                    controller.with_mut(AutocompleteController::dismiss);
+                    false
+                }
+                _ => true,
+            }
+        });
+        if let Some(value) = picked {
+            pick.call(value);
+        }
+    });
+
+    let is_open = expanded && !visible.is_empty();
+    popover(
+        move || {
+            text_field()
+                .modifier(modifier.clone().unwrap_or_default())
+                .enabled_optional(enabled)
+                .label_optional(label.clone())
+                .placeholder_optional(placeholder.clone())
+                .on_change(on_change)
+                .controller(text_controller);
+        },
+        move || {
+            suggestion_menu(visible.as_ref().clone(), highlighted, pick, list);
+        },
+    )
+    .modifier(anchor_modifier)
+    .is_open(is_open)
+    .on_dismiss(move || controller.with_mut(AutocompleteController::dismiss))
+    .focus_on_open(false)
+    .match_anchor_width(true);
+}
+
+#[tessera]
+fn suggestion_menu(
+    suggestions: Vec<String>,
+    highlighted: Option<usize>,
+    on_pick: CallbackWith<String>,
+    list: State<LazyListController>,
+) {
+    let scheme = use_context::<MaterialTheme>()
+        .expect("MaterialTheme must be provided")
+        .get()
+        .color_scheme;
+    let height = Dp(SUGGESTION_HEIGHT.0 * suggestions.len().min(MAX_VISIBLE_SUGGESTIONS) as f64);
+    let suggestions = Arc::new(suggestions);
+    surface()
+        .style(SurfaceStyle::Filled {
+            color: scheme.surface_container,
+        })
+        .shape(Shape::rounded_rectangle(Dp(4.0)))
+        .elevation(MENU_ELEVATION)
+        .modifier(Modifier::new().fill_max_width())
+        .child(move || {
+            let suggestions = suggestions.clone();
+            lazy_column()
+                .modifier(Modifier::new().fill_max_width().height(height))
+                .estimated_item_size(SUGGESTION_HEIGHT)
+                .controller(list)
+                .items(suggestions.len(), move |index| {
+                    suggestion_item(
+                        suggestions[index].clone(),
+                        highlighted == Some(index),
+                        on_pick,
+                    );
+                });
+        });
+}
+
+#[tessera]
+fn suggestion_item(value: String, highlighted: bool, on_pick: CallbackWith<String>) {
+    let theme = use_context::<MaterialTheme>()
+        .expect("MaterialTheme must be provided")
+        .get();
+    let scheme = theme.color_scheme;
+    let background = if highlighted {
+        scheme.on_surface.with_alpha(MaterialAlpha::FOCUSED)
+    } else {
+        Color::TRANSPARENT
+    };
+    let label = value.clone();
+    surface()
+        .style(SurfaceStyle::Filled { color: background })
+        .modifier(Modifier::new().fill_max_width().height(SUGGESTION_HEIGHT))
+        .content_alignment(Alignment::CenterStart)
+        .on_click(move || on_pick.call(value.clone()))
+        .child(move || {
+            text()
+                .modifier(
+                    Modifier::new().padding_symmetric(SUGGESTION_HORIZONTAL_PADDING, Dp::ZERO),
+                )
+                .content(label.clone())
+                .style(theme.typography.body_large)
+                .color(scheme.on_surface)
+                .max_lines(1);
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AutocompleteController, filter_suggestions};
+
+    #[test]
+    fn prefix_matches_come_before_other_matches() {
+        let suggestions = ["Paris", "Sparta", "Lyon", "parma"].map(String::from);
+        assert_eq!(
+            filter_suggestions(&suggestions, "PAR"),
+            ["Paris", "parma", "Sparta"].map(String::from)
+        );
+    }
+
+    #[test]
+    fn answers_to_outdated_queries_are_ignored() {
+        let mut controller = AutocompleteController::new();
+        controller.query_changed("ru".to_string(), true, Some(100));
+        assert!(controller.poll(50).is_none());
+        let outdated = controller.poll(100).expect("debounce has elapsed");
+        assert_eq!(outdated.query(), "ru");
+
+        controller.query_changed("rus".to_string(), true, Some(200));
+        let current = controller.poll(200).expect("debounce has elapsed");
+        outdated.respond(vec!["Ruby".to_string()]);
+        controller.poll(201);
+        assert!(controller.is_loading());
+
+        current.respond(vec!["Rust".to_string()]);
+        controller.poll(202);
+        assert!(!controller.is_loading());
+        assert_eq!(controller.results, ["Rust"]);
+    }
+}
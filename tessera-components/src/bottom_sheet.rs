@@ -5,11 +5,11 @@
 //! Used to show contextual information or actions in a modal sheet.
 use std::time::Duration;
 
-use tessera_foundation::gesture::DragRecognizer;
 use tessera_ui::{
     AxisConstraint, Callback, CallbackWith, Constraint, Dp, FocusScopeNode, FocusTraversalPolicy,
     LayoutResult, MeasurementError, Modifier, Px, PxPosition, RenderSlot, State,
     current_frame_nanos,
+    gesture::DragRecognizer,
     layout::{LayoutPolicy, MeasureScope, layout},
     modifier::FocusModifierExt as _,
     provide_context, receive_frame_nanos, remember, tessera, use_context, winit,
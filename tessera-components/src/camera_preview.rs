@@ -0,0 +1,524 @@
+//! Live camera preview with still capture.
+//!
+//! ## Usage
+//!
+//! Show the camera feed for scanners, video calls and profile photos.
+use std::{io::Cursor, sync::Arc, thread, time::Duration};
+
+use image::{DynamicImage, ImageDecoder as _, codecs::jpeg::JpegDecoder};
+use parking_lot::{Condvar, Mutex};
+use tessera_platform::camera::{CameraFrame, CameraSession, open_camera};
+use tessera_ui::{
+    CallbackWith, Color, ComputedData, Dp, FrameNanosControl, LayoutResult, MeasurementError,
+    Modifier, Px, PxSize, State,
+    layout::{LayoutPolicy, MeasureScope, RenderInput, RenderPolicy, layout},
+    receive_frame_nanos,
+    renderer::{
+        ImportedTexture,
+        drawer::{TextureAlphaMode, TextureCommand},
+    },
+    tessera,
+};
+
+use crate::{
+    alignment::Alignment,
+    boxed::boxed,
+    icon::icon,
+    image::{ImageData, decode_dynamic_image},
+    material_icons::filled,
+    modifier::ModifierExt as _,
+    video::{FrameUploader, VideoFrameData},
+};
+
+pub use tessera_platform::camera::{CameraError, CameraFacing};
+
+/// Requested preview size in sensor orientation; the platform picks the
+/// closest supported one.
+const PREVIEW_WIDTH: u32 = 1280;
+const PREVIEW_HEIGHT: u32 = 720;
+const DEFAULT_PREVIEW_WIDTH: Dp = Dp(320.0);
+const DEFAULT_ASPECT_RATIO: f32 = 3.0 / 4.0;
+const POLL_INTERVAL: Duration = Duration::from_millis(4);
+const RETRY_INTERVAL: Duration = Duration::from_secs(1);
+const UNAVAILABLE_ICON_SIZE: Dp = Dp(48.0);
+
+struct Preview {
+    facing: CameraFacing,
+    capture_requested: bool,
+    captures: Vec<ImageData>,
+    texture: Option<ImportedTexture>,
+    frame_size: Option<PxSize>,
+    error: Option<CameraError>,
+    revision: u64,
+    shutdown: bool,
+}
+
+struct Shared {
+    preview: Mutex<Preview>,
+    wake: Condvar,
+}
+
+impl Shared {
+    fn update(&self, f: impl FnOnce(&mut Preview)) {
+        let mut preview = self.preview.lock();
+        f(&mut preview);
+        preview.revision = preview.revision.wrapping_add(1);
+        drop(preview);
+        self.wake.notify_all();
+    }
+
+    fn fail(&self, error: CameraError) {
+        // Retries keep failing the same way until something changes.
+        if self.preview.lock().error.as_ref() == Some(&error) {
+            return;
+        }
+        tracing::warn!("camera preview: {error}");
+        self.update(|preview| preview.error = Some(error));
+    }
+
+    /// Sleeps until `timeout` passes or the controller changes.
+    fn wait(&self, facing: CameraFacing, timeout: Option<Duration>) {
+        let mut preview = self.preview.lock();
+        if preview.shutdown || preview.facing != facing {
+            return;
+        }
+        match timeout {
+            Some(timeout) => {
+                self.wake.wait_for(&mut preview, timeout);
+            }
+            None => self.wake.wait(&mut preview),
+        }
+    }
+}
+
+/// Camera state of a [`camera_preview`], streaming frames on a background
+/// thread.
+///
+/// The camera closes when the controller is dropped.
+pub struct CameraController {
+    shared: Arc<Shared>,
+    /// Revision the component last rendered, to rebuild on new frames.
+    revision: u64,
+}
+
+impl CameraController {
+    /// Opens the camera facing `facing`.
+    ///
+    /// Request [`Permission::Camera`](tessera_platform::permissions::Permission::Camera)
+    /// first; the preview starts once it is granted.
+    pub fn new(facing: CameraFacing) -> Self {
+        let shared = Arc::new(Shared {
+            preview: Mutex::new(Preview {
+                facing,
+                capture_requested: false,
+                captures: Vec::new(),
+                texture: None,
+                frame_size: None,
+                error: None,
+                revision: 0,
+                shutdown: false,
+            }),
+            wake: Condvar::new(),
+        });
+        let worker = shared.clone();
+        thread::Builder::new()
+            .name("tessera-camera".to_string())
+            .spawn(move || run_camera(worker))
+            .expect("failed to spawn the camera thread");
+        Self {
+            shared,
+            revision: 0,
+        }
+    }
+
+    /// Returns the camera in use.
+    pub fn facing(&self) -> CameraFacing {
+        self.shared.preview.lock().facing
+    }
+
+    /// Switches to the camera facing `facing`.
+    pub fn set_facing(&mut self, facing: CameraFacing) {
+        self.shared.update(|preview| {
+            if preview.facing != facing {
+                preview.facing = facing;
+                preview.error = None;
+            }
+        });
+    }
+
+    /// Takes a still picture, delivered to the preview's `on_capture`.
+    pub fn capture(&mut self) {
+        self.shared
+            .update(|preview| preview.capture_requested = true);
+    }
+
+    /// Returns the upright size of the preview frames, once the first one is
+    /// shown.
+    pub fn preview_size(&self) -> Option<PxSize> {
+        self.shared.preview.lock().frame_size
+    }
+
+    /// Returns the last camera error, if any.
+    pub fn error(&self) -> Option<CameraError> {
+        self.shared.preview.lock().error.clone()
+    }
+
+    fn texture(&self) -> Option<ImportedTexture> {
+        self.shared.preview.lock().texture.clone()
+    }
+
+    /// Returns whether frames may still arrive; unsupported platforms and
+    /// missing cameras never deliver any.
+    fn is_active(&self) -> bool {
+        !matches!(
+            self.shared.preview.lock().error,
+            Some(CameraError::Unsupported | CameraError::NotFound)
+        )
+    }
+
+    fn take_captures(&self) -> Vec<ImageData> {
+        std::mem::take(&mut self.shared.preview.lock().captures)
+    }
+
+    fn is_stale(&self) -> bool {
+        self.shared.preview.lock().revision != self.revision
+    }
+
+    fn sync(&mut self) {
+        self.revision = self.shared.preview.lock().revision;
+    }
+}
+
+impl Drop for CameraController {
+    fn drop(&mut self) {
+        self.shared.update(|preview| preview.shutdown = true);
+    }
+}
+
+fn run_camera(shared: Arc<Shared>) {
+    let mut uploader = FrameUploader::default();
+    let mut session: Option<(CameraFacing, CameraSession)> = None;
+    loop {
+        let facing = {
+            let preview = shared.preview.lock();
+            if preview.shutdown {
+                return;
+            }
+            preview.facing
+        };
+        if session.as_ref().is_none_or(|(opened, _)| *opened != facing) {
+            // Only one camera can be open, so close the old one first.
+            session = None;
+            match open_camera(facing, PREVIEW_WIDTH, PREVIEW_HEIGHT) {
+                Ok(opened) => {
+                    shared.update(|preview| preview.error = None);
+                    session = Some((facing, opened));
+                }
+                Err(error) => {
+                    // The app may still get resumed or granted the permission;
+                    // other errors need a different camera.
+                    let retry = matches!(
+                        error,
+                        CameraError::Unavailable | CameraError::PermissionDenied
+                    );
+                    shared.fail(error);
+                    shared.wait(facing, retry.then_some(RETRY_INTERVAL));
+                }
+            }
+            continue;
+        }
+        let Some((_, session)) = &session else {
+            continue;
+        };
+
+        if let Some(error) = session.take_error() {
+            shared.fail(error);
+        }
+        let capture = std::mem::take(&mut shared.preview.lock().capture_requested);
+        if capture && let Err(error) = session.capture() {
+            shared.fail(error);
+        }
+        if let Some(jpeg) = session.take_capture() {
+            match decode_capture(&jpeg) {
+                Ok(image) => shared.update(|preview| preview.captures.push(image)),
+                Err(error) => shared.fail(CameraError::Platform(error.to_string())),
+            }
+        }
+        let Some(frame) = session.take_frame() else {
+            shared.wait(facing, Some(POLL_INTERVAL));
+            continue;
+        };
+        let Some((width, height, pixels)) = upright_rgba(&frame) else {
+            continue;
+        };
+        match uploader.present(VideoFrameData::Rgba {
+            width,
+            height,
+            pixels,
+        }) {
+            Ok((texture, size)) => shared.update(|preview| {
+                preview.frame_size = Some(size);
+                preview.texture.get_or_insert(texture);
+            }),
+            Err(error) => shared.fail(CameraError::Platform(error.to_string())),
+        }
+    }
+}
+
+/// Decodes a still picture, applying its EXIF orientation.
+fn decode_capture(jpeg: &[u8]) -> Result<ImageData, image::ImageError> {
+    let mut decoder = JpegDecoder::new(Cursor::new(jpeg))?;
+    let orientation = decoder.orientation()?;
+    let mut image = DynamicImage::from_decoder(decoder)?;
+    image.apply_orientation(orientation);
+    Ok(decode_dynamic_image(image))
+}
+
+/// Converts an I420 frame to upright, optionally mirrored, RGBA pixels.
+fn upright_rgba(frame: &CameraFrame) -> Option<(u32, u32, Vec<u8>)> {
+    let (width, height) = (frame.width as usize, frame.height as usize);
+    if width == 0 || height == 0 || width % 2 != 0 || height % 2 != 0 {
+        return None;
+    }
+    let luma = width * height;
+    let chroma_width = width / 2;
+    let chroma = chroma_width * (height / 2);
+    if frame.i420.len() != luma + 2 * chroma {
+        return None;
+    }
+    let (y_plane, chroma_planes) = frame.i420.split_at(luma);
+    let (u_plane, v_plane) = chroma_planes.split_at(chroma);
+
+    let rotation = frame.rotation % 360;
+    let (out_width, out_height) = if rotation == 90 || rotation == 270 {
+        (height, width)
+    } else {
+        (width, height)
+    };
+    let mut pixels = Vec::with_capacity(out_width * out_height * 4);
+    for out_y in 0..out_height {
+        for out_x in 0..out_width {
+            let out_x = if frame.mirrored {
+                out_width - 1 - out_x
+            } else {
+                out_x
+            };
+            let (x, y) = match rotation {
+                90 => (out_y, height - 1 - out_x),
+                180 => (width - 1 - out_x, height - 1 - out_y),
+                270 => (width - 1 - out_y, out_x),
+                _ => (out_x, out_y),
+            };
+            let chroma_index = y / 2 * chroma_width + x / 2;
+            pixels.extend_from_slice(&yuv_to_rgba(
+                y_plane[y * width + x],
+                u_plane[chroma_index],
+                v_plane[chroma_index],
+            ));
+        }
+    }
+    Some((out_width as u32, out_height as u32, pixels))
+}
+
+/// BT.601 limited-range conversion, as produced by camera sensors.
+fn yuv_to_rgba(y: u8, u: u8, v: u8) -> [u8; 4] {
+    let c = (i32::from(y) - 16) * 298;
+    let d = i32::from(u) - 128;
+    let e = i32::from(v) - 128;
+    let channel = |value: i32| ((value + 128) >> 8).clamp(0, 255) as u8;
+    [
+        channel(c + 409 * e),
+        channel(c - 100 * d - 208 * e),
+        channel(c + 516 * d),
+        255,
+    ]
+}
+
+/// Normalized source rect that crops `content` to fill `container` without
+/// distortion.
+fn fill_uv_rect(container: ComputedData, content: PxSize) -> [f32; 4] {
+    let container_aspect = container.width.to_f32() / container.height.to_f32();
+    let content_aspect = content.width.to_f32() / content.height.to_f32();
+    if !container_aspect.is_finite() || !content_aspect.is_finite() || content_aspect <= 0.0 {
+        return [0.0, 0.0, 1.0, 1.0];
+    }
+    if content_aspect > container_aspect {
+        let width = container_aspect / content_aspect;
+        [(1.0 - width) / 2.0, 0.0, width, 1.0]
+    } else {
+        let height = content_aspect / container_aspect;
+        [0.0, (1.0 - height) / 2.0, 1.0, height]
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct CameraSurfaceLayout {
+    texture: Option<ImportedTexture>,
+    frame_size: Option<PxSize>,
+}
+
+impl LayoutPolicy for CameraSurfaceLayout {
+    fn measure(&self, input: &MeasureScope<'_>) -> Result<LayoutResult, MeasurementError> {
+        let parent = input.parent_constraint();
+        let aspect_ratio = self
+            .frame_size
+            .filter(|size| size.width > Px::ZERO && size.height > Px::ZERO)
+            .map_or(DEFAULT_ASPECT_RATIO, |size| {
+                size.width.to_f32() / size.height.to_f32()
+            });
+        let width = parent.width();
+        let width = width.clamp(width.max.unwrap_or(DEFAULT_PREVIEW_WIDTH.to_px()));
+        let height = parent
+            .height()
+            .clamp(Px::saturating_from_f32(width.to_f32() / aspect_ratio));
+        Ok(LayoutResult::new(ComputedData { width, height }))
+    }
+}
+
+impl RenderPolicy for CameraSurfaceLayout {
+    fn record(&self, input: &mut RenderInput<'_>) {
+        let (Some(texture), Some(frame_size)) = (&self.texture, self.frame_size) else {
+            return;
+        };
+        let mut metadata = input.metadata_mut();
+        let Some(size) = metadata.computed_data() else {
+            return;
+        };
+        let uv_rect = fill_uv_rect(size, frame_size);
+        let command = TextureCommand::new(texture.clone())
+            .with_uv_rect(uv_rect)
+            .with_alpha_mode(TextureAlphaMode::Opaque);
+        metadata.fragment_mut().push_draw_command(command);
+    }
+}
+
+/// # camera_preview
+///
+/// Streams the device camera, upright for the current display orientation and
+/// cropped to fill the preview without distortion.
+///
+/// ## Usage
+///
+/// Show a viewfinder for scanning codes, video calls or taking a profile photo.
+///
+/// ## Parameters
+///
+/// - `controller` — camera state; switch cameras and take pictures through it.
+/// - `on_capture` — optional callback receiving each picture taken with
+///   [`CameraController::capture`].
+/// - `modifier` — optional modifier chain applied to the preview.
+///
+/// Only Android is supported so far; other platforms show a placeholder and
+/// report [`CameraError::Unsupported`].
+///
+/// ## Examples
+///
+/// ```
+/// # use tessera_ui::{remember, tessera};
+/// use tessera_components::camera_preview::{CameraController, CameraFacing, camera_preview};
+/// use tessera_components::image::ImageData;
+/// # use tessera_components::theme::{MaterialTheme, material_theme};
+///
+/// # #[tessera]
+/// # fn component() {
+/// # material_theme()
+/// #     .theme(|| MaterialTheme::default())
+/// #     .child(|| {
+/// let controller = remember(|| CameraController::new(CameraFacing::Back));
+/// camera_preview(controller).on_capture(|picture: ImageData| {
+///     assert!(picture.width > 0);
+/// });
+/// # });
+/// # }
+/// # component();
+/// ```
+#[tessera]
+pub fn camera_preview(
+    controller: State<CameraController>,
+    on_capture: Option<CallbackWith<ImageData>>,
+    modifier: Option<Modifier>,
+) {
+    if controller.with(CameraController::is_active) {
+        receive_frame_nanos(move |_| {
+            let active = controller.with(CameraController::is_active);
+            if controller.with(CameraController::is_stale) {
+                controller.with_mut(CameraController::sync);
+            }
+            let captures = controller.with(CameraController::take_captures);
+            if let Some(on_capture) = on_capture {
+                for picture in captures {
+                    on_capture.call(picture);
+                }
+            }
+            if active {
+                FrameNanosControl::Continue
+            } else {
+                FrameNanosControl::Stop
+            }
+        });
+    }
+
+    let surface = CameraSurfaceLayout {
+        texture: controller.with(CameraController::texture),
+        frame_size: controller.with(CameraController::preview_size),
+    };
+    let unavailable = surface.texture.is_none() && controller.with(|c| c.error().is_some());
+    boxed()
+        .modifier(
+            modifier
+                .unwrap_or_default()
+                .background(Color::BLACK)
+                .clip_to_bounds(),
+        )
+        .alignment(Alignment::Center)
+        .children(move || {
+            layout()
+                .layout_policy(surface.clone())
+                .render_policy(surface.clone());
+            if unavailable {
+                icon()
+                    .painter(filled::VIDEOCAM_OFF_SVG)
+                    .size(UNAVAILABLE_ICON_SIZE)
+                    .tint(Color::WHITE.with_alpha(0.6));
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upright_rgba_rotates_then_mirrors() {
+        let mut frame = CameraFrame {
+            width: 2,
+            height: 2,
+            rotation: 90,
+            mirrored: false,
+            // Only the top-right pixel is white.
+            i420: vec![16, 235, 16, 16, 128, 128],
+        };
+        let reds = |frame: &CameraFrame| {
+            let (width, height, pixels) = upright_rgba(frame).expect("valid frame");
+            assert_eq!((width, height), (2, 2));
+            pixels.chunks(4).map(|pixel| pixel[0]).collect::<Vec<_>>()
+        };
+        assert_eq!(reds(&frame), [0, 0, 0, 255]);
+
+        frame.mirrored = true;
+        assert_eq!(reds(&frame), [0, 0, 255, 0]);
+    }
+
+    #[test]
+    fn fill_uv_rect_crops_the_longer_side() {
+        let square = ComputedData {
+            width: Px::new(300),
+            height: Px::new(300),
+        };
+        let wide = fill_uv_rect(square, PxSize::new(Px::new(400), Px::new(200)));
+        assert_eq!(wide, [0.25, 0.0, 0.5, 1.0]);
+
+        let tall = fill_uv_rect(square, PxSize::new(Px::new(200), Px::new(400)));
+        assert_eq!(tall, [0.0, 0.25, 1.0, 0.5]);
+    }
+}
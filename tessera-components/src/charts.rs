@@ -0,0 +1,1551 @@
+//! Line, area, bar, pie and sparkline charts.
+//!
+//! ## Usage
+//!
+//! Visualize trends, comparisons and proportions in dashboards and reports.
+use std::{
+    f32::consts::{FRAC_PI_2, TAU},
+    time::Duration,
+};
+
+use tessera_ui::{
+    AxisConstraint, Color, ComputedData, Constraint, Dp, FrameNanosControl, LayoutResult,
+    MeasurementError, Modifier, ParentConstraint, Px, PxPosition, PxRect, RenderSlot, State,
+    animation::{Animatable, AnimationSpec},
+    current_frame_nanos,
+    layout::{LayoutPolicy, MeasureScope, RenderInput, RenderPolicy, layout},
+    receive_frame_nanos, remember,
+    renderer::drawer::path::{LineCap, LineJoin, PathBuilder, PathCommand, StrokeStyle},
+    system_preferences::system_preferences,
+    tessera, use_context,
+};
+
+use crate::{
+    alignment::CrossAxisAlignment,
+    column::column,
+    flow_row::flow_row,
+    modifier::{ModifierExt as _, with_pointer_preview_input},
+    overlay::{
+        OverlayAlign, OverlayHandle, OverlayHost, OverlayPlacement, OverlayRequest, OverlaySide,
+    },
+    row::row,
+    shape_def::Shape,
+    spacer::spacer,
+    text::text,
+    theme::{MaterialColorScheme, MaterialTheme},
+    tooltip::tooltip_container,
+};
+
+const TRANSITION_DURATION: Duration = Duration::from_millis(450);
+const DEFAULT_CHART_WIDTH: Dp = Dp(320.0);
+const DEFAULT_CHART_HEIGHT: Dp = Dp(200.0);
+const SPARKLINE_WIDTH: Dp = Dp(80.0);
+const SPARKLINE_HEIGHT: Dp = Dp(24.0);
+const Y_AXIS_WIDTH: Dp = Dp(40.0);
+const X_AXIS_HEIGHT: Dp = Dp(20.0);
+const AXIS_LABEL_GAP: Dp = Dp(4.0);
+const PLOT_PADDING: Dp = Dp(8.0);
+const TICK_COUNT: usize = 5;
+const LINE_WIDTH: Dp = Dp(2.0);
+const GRID_WIDTH: Dp = Dp(1.0);
+const POINT_RADIUS: Dp = Dp(4.0);
+const BAR_GROUP_FRACTION: f32 = 0.7;
+const AREA_ALPHA: f32 = 0.24;
+const PIE_HOVER_GROWTH: Dp = Dp(6.0);
+const TOOLTIP_GAP: Dp = Dp(8.0);
+const LEGEND_SWATCH: Dp = Dp(10.0);
+const LEGEND_SWATCH_GAP: Dp = Dp(6.0);
+const LEGEND_SPACING: Dp = Dp(12.0);
+const LEGEND_PADDING: Dp = Dp(8.0);
+
+/// Named values plotted by a chart, one value per category label.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartSeries {
+    name: String,
+    values: Vec<f32>,
+    color: Option<Color>,
+}
+
+impl ChartSeries {
+    /// Creates a series drawn with the next color of the theme palette.
+    pub fn new(name: impl Into<String>, values: Vec<f32>) -> Self {
+        Self {
+            name: name.into(),
+            values,
+            color: None,
+        }
+    }
+
+    /// Draws the series with `color` instead of the palette.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+fn palette_color(scheme: &MaterialColorScheme, index: usize) -> Color {
+    let palette = [
+        scheme.primary,
+        scheme.tertiary,
+        scheme.secondary,
+        scheme.error,
+        scheme.outline,
+    ];
+    palette[index % palette.len()]
+}
+
+fn finite(value: f32) -> f32 {
+    if value.is_finite() { value } else { 0.0 }
+}
+
+fn format_value(value: f32) -> String {
+    let text = format!("{value:.2}");
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+/// Fills the available width and uses the default height unless constrained.
+fn fill_size(parent: ParentConstraint<'_>, default_width: Dp, default_height: Dp) -> ComputedData {
+    let width = parent.width();
+    ComputedData {
+        width: width.clamp(width.max.unwrap_or(default_width.to_px())),
+        height: parent.height().clamp(default_height.to_px()),
+    }
+}
+
+/// Animates plotted values from the previous dataset to the current one.
+#[derive(Clone, Debug)]
+struct ChartTransition {
+    from: Vec<Vec<f32>>,
+    to: Vec<Vec<f32>>,
+    progress: Animatable<f32>,
+}
+
+impl Default for ChartTransition {
+    fn default() -> Self {
+        Self {
+            from: Vec::new(),
+            to: Vec::new(),
+            progress: Animatable::new(1.0),
+        }
+    }
+}
+
+impl ChartTransition {
+    /// Starts moving toward `target`, or jumps there without a `spec`.
+    fn retarget(
+        &mut self,
+        target: Vec<Vec<f32>>,
+        spec: Option<AnimationSpec<f32>>,
+        frame_nanos: u64,
+    ) {
+        self.from = self.current();
+        self.to = target;
+        match spec {
+            Some(spec) => {
+                self.progress.snap_to(0.0);
+                self.progress.animate_to(1.0, spec, frame_nanos);
+            }
+            None => self.progress.snap_to(1.0),
+        }
+    }
+
+    /// Returns the values at the current progress; values without a previous
+    /// counterpart grow from zero.
+    fn current(&self) -> Vec<Vec<f32>> {
+        let t = self.progress.value();
+        self.to
+            .iter()
+            .enumerate()
+            .map(|(series, values)| {
+                values
+                    .iter()
+                    .enumerate()
+                    .map(|(index, &to)| {
+                        let from = self
+                            .from
+                            .get(series)
+                            .and_then(|values| values.get(index))
+                            .copied()
+                            .unwrap_or(0.0);
+                        from + (to - from) * t
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+fn animate_values(transition: State<ChartTransition>, target: Vec<Vec<f32>>) -> Vec<Vec<f32>> {
+    if transition.with(|transition| transition.to != target) {
+        let spec = (!system_preferences().reduce_motion)
+            .then(|| AnimationSpec::tween(TRANSITION_DURATION));
+        let frame_nanos = current_frame_nanos();
+        transition.with_mut(|transition| transition.retarget(target, spec, frame_nanos));
+    }
+    if transition.with(|transition| transition.progress.is_running()) {
+        receive_frame_nanos(move |frame_nanos| {
+            if transition.with_mut(|transition| transition.progress.tick(frame_nanos)) {
+                FrameNanosControl::Continue
+            } else {
+                FrameNanosControl::Stop
+            }
+        });
+    }
+    transition.with(ChartTransition::current)
+}
+
+/// Draw commands recorded for a chart layout, reused until the layout or its
+/// size changes.
+struct ChartPaths<L> {
+    recorded: Option<(L, ComputedData)>,
+    commands: Vec<PathCommand>,
+}
+
+impl<L> Default for ChartPaths<L> {
+    fn default() -> Self {
+        Self {
+            recorded: None,
+            commands: Vec::new(),
+        }
+    }
+}
+
+impl<L: Clone + PartialEq> ChartPaths<L> {
+    fn commands(
+        &mut self,
+        layout: &L,
+        size: ComputedData,
+        build: impl FnOnce() -> Vec<PathCommand>,
+    ) -> Vec<PathCommand> {
+        let recorded = self
+            .recorded
+            .as_ref()
+            .is_some_and(|(recorded, recorded_size)| recorded == layout && *recorded_size == size);
+        if !recorded {
+            self.commands = build();
+            self.recorded = Some((layout.clone(), size));
+        }
+        self.commands.clone()
+    }
+}
+
+fn record_chart_paths<L: Clone + PartialEq + Send + Sync + 'static>(
+    input: &mut RenderInput<'_>,
+    paths: State<ChartPaths<L>>,
+    layout: &L,
+    build: impl FnOnce(ComputedData) -> Vec<PathCommand>,
+) {
+    let mut metadata = input.metadata_mut();
+    let Some(size) = metadata.computed_data() else {
+        return;
+    };
+    let commands = paths.with_mut(|paths| paths.commands(layout, size, || build(size)));
+    for command in commands {
+        metadata.fragment_mut().push_draw_command(command);
+    }
+}
+
+/// Hovered data point, with the window origin and size of the chart it was
+/// found in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ChartHover {
+    index: usize,
+    origin: PxPosition,
+    size: ComputedData,
+}
+
+fn update_hover(
+    hover: State<Option<ChartHover>>,
+    input: &tessera_ui::PointerInput<'_>,
+    index: Option<usize>,
+) {
+    let origin = match (input.cursor_position_abs(), input.cursor_position_rel) {
+        (Some(abs), Some(rel)) => abs - rel,
+        _ => PxPosition::ZERO,
+    };
+    let next = index.map(|index| ChartHover {
+        index,
+        origin,
+        size: input.computed_data,
+    });
+    if hover.get() != next {
+        hover.set(next);
+    }
+}
+
+/// Shows `bubble` above `anchor` in the overlay host; returns whether it has
+/// to be drawn inline because there is no host.
+fn show_tooltip(
+    host: Option<OverlayHost>,
+    handle: State<OverlayHandle>,
+    anchor: Option<PxRect>,
+    bubble: RenderSlot,
+) -> bool {
+    let Some(host) = host else {
+        return anchor.is_some();
+    };
+    handle.with(|handle| match anchor {
+        Some(anchor) => host.show(
+            handle,
+            OverlayRequest {
+                anchor,
+                placement: OverlayPlacement::new(OverlaySide::Above, OverlayAlign::Center)
+                    .gap(TOOLTIP_GAP),
+                content: bubble,
+                on_dismiss: None,
+            },
+        ),
+        None => host.hide(handle),
+    });
+    false
+}
+
+fn window_anchor(hover: ChartHover, point: [f32; 2]) -> PxRect {
+    PxRect {
+        x: hover.origin.x + Px::saturating_from_f32(point[0]),
+        y: hover.origin.y + Px::saturating_from_f32(point[1]),
+        width: Px::ZERO,
+        height: Px::ZERO,
+    }
+}
+
+/// Places an inline tooltip above `anchor`, kept inside the chart.
+fn place_bubble(
+    result: &mut LayoutResult,
+    input: &MeasureScope<'_>,
+    bubble: tessera_ui::layout::LayoutChild<'_>,
+    anchor: [f32; 2],
+    size: ComputedData,
+) -> Result<(), MeasurementError> {
+    let measured = bubble
+        .measure(&input.parent_constraint().without_min())?
+        .size();
+    let (width, height) = (measured.width.to_f32(), measured.height.to_f32());
+    let gap = TOOLTIP_GAP.to_px().to_f32();
+    let x = (anchor[0] - width / 2.0).clamp(0.0, (size.width.to_f32() - width).max(0.0));
+    let above = anchor[1] - gap - height;
+    let y = if above >= 0.0 { above } else { anchor[1] + gap };
+    result.place_child(
+        bubble,
+        PxPosition::new(Px::saturating_from_f32(x), Px::saturating_from_f32(y)),
+    );
+    Ok(())
+}
+
+/// Appends a circular arc from `start` to `end` radians, continuing from the
+/// arc's start point.
+fn arc_to(builder: &mut PathBuilder, center: [f32; 2], radius: f32, start: f32, end: f32) {
+    let segments = ((end - start).abs() / FRAC_PI_2).ceil().max(1.0) as usize;
+    let step = (end - start) / segments as f32;
+    let k = 4.0 / 3.0 * (step / 4.0).tan() * radius;
+    for segment in 0..segments {
+        let a0 = start + step * segment as f32;
+        let a1 = a0 + step;
+        let p0 = polar(center, radius, a0);
+        let p1 = polar(center, radius, a1);
+        builder.cubic_to(
+            [p0[0] - k * a0.sin(), p0[1] + k * a0.cos()],
+            [p1[0] + k * a1.sin(), p1[1] - k * a1.cos()],
+            p1,
+        );
+    }
+}
+
+fn polar(center: [f32; 2], radius: f32, angle: f32) -> [f32; 2] {
+    [
+        center[0] + radius * angle.cos(),
+        center[1] + radius * angle.sin(),
+    ]
+}
+
+fn circle(builder: &mut PathBuilder, center: [f32; 2], radius: f32) {
+    builder.move_to(center[0] + radius, center[1]);
+    arc_to(builder, center, radius, 0.0, TAU);
+    builder.close();
+}
+
+fn rect(builder: &mut PathBuilder, x: f32, y: f32, width: f32, height: f32) {
+    builder
+        .move_to(x, y)
+        .line_to(x + width, y)
+        .line_to(x + width, y + height)
+        .line_to(x, y + height)
+        .close();
+}
+
+fn line_stroke() -> StrokeStyle {
+    StrokeStyle::new(LINE_WIDTH.to_px().to_f32())
+        .cap(LineCap::Round)
+        .join(LineJoin::Round)
+}
+
+/// Value range and gridline spacing of a chart axis.
+#[derive(Clone, Debug, PartialEq)]
+struct ValueAxis {
+    min: f32,
+    max: f32,
+    step: f32,
+}
+
+impl ValueAxis {
+    /// Creates an axis covering `values` and zero with round tick values.
+    fn new(values: impl IntoIterator<Item = f32>) -> Self {
+        let (low, high) = values
+            .into_iter()
+            .fold((0.0_f32, 0.0_f32), |(low, high), value| {
+                (low.min(value), high.max(value))
+            });
+        let high = if high - low <= f32::EPSILON {
+            low + 1.0
+        } else {
+            high
+        };
+        let step = nice_step((high - low) / (TICK_COUNT - 1) as f32);
+        Self {
+            min: (low / step).floor() * step,
+            max: (high / step).ceil() * step,
+            step,
+        }
+    }
+
+    fn ticks(&self) -> Vec<f32> {
+        let count = ((self.max - self.min) / self.step).round() as usize;
+        (0..=count)
+            .map(|index| self.min + self.step * index as f32)
+            .collect()
+    }
+
+    /// Returns how far up the axis `value` lies, from 0 to 1.
+    fn fraction(&self, value: f32) -> f32 {
+        ((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+    }
+
+    fn label(&self, value: f32) -> String {
+        let decimals = if self.step >= 1.0 {
+            0
+        } else {
+            // Steps are powers of ten times 1, 2 or 5; the margin absorbs
+            // rounding in the logarithm.
+            (-self.step.log10() - 1e-3).ceil() as usize
+        };
+        let value = if value.abs() < self.step * 1e-3 {
+            0.0
+        } else {
+            value
+        };
+        format!("{value:.decimals$}")
+    }
+}
+
+/// Rounds `rough` to 1, 2 or 5 times a power of ten.
+fn nice_step(rough: f32) -> f32 {
+    let magnitude = 10_f32.powf(rough.log10().floor());
+    let residual = rough / magnitude;
+    let nice = if residual < 1.5 {
+        1.0
+    } else if residual < 3.0 {
+        2.0
+    } else if residual < 7.0 {
+        5.0
+    } else {
+        10.0
+    };
+    nice * magnitude
+}
+
+/// Area inside the axes of a line, area or bar chart.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct PlotArea {
+    left: f32,
+    top: f32,
+    width: f32,
+    height: f32,
+}
+
+impl PlotArea {
+    fn new(size: ComputedData) -> Self {
+        let left = Y_AXIS_WIDTH.to_px().to_f32();
+        let top = PLOT_PADDING.to_px().to_f32();
+        Self {
+            left,
+            top,
+            width: (size.width.to_f32() - left - PLOT_PADDING.to_px().to_f32()).max(0.0),
+            height: (size.height.to_f32() - top - X_AXIS_HEIGHT.to_px().to_f32()).max(0.0),
+        }
+    }
+
+    fn right(&self) -> f32 {
+        self.left + self.width
+    }
+
+    fn bottom(&self) -> f32 {
+        self.top + self.height
+    }
+
+    fn band(&self, categories: usize) -> f32 {
+        self.width / categories.max(1) as f32
+    }
+
+    fn category_x(&self, index: usize, categories: usize) -> f32 {
+        self.left + self.band(categories) * (index as f32 + 0.5)
+    }
+
+    fn category_at(&self, x: f32, categories: usize) -> Option<usize> {
+        if categories == 0 || x < self.left || x >= self.right() {
+            return None;
+        }
+        Some((((x - self.left) / self.band(categories)) as usize).min(categories - 1))
+    }
+
+    fn value_y(&self, value: f32, axis: &ValueAxis) -> f32 {
+        self.bottom() - self.height * axis.fraction(value)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CartesianKind {
+    Line,
+    Area,
+    Bar,
+}
+
+/// Point above the highest value of a category, where its tooltip opens.
+fn cartesian_anchor(
+    plot: PlotArea,
+    axis: &ValueAxis,
+    values: &[Vec<f32>],
+    index: usize,
+    categories: usize,
+) -> [f32; 2] {
+    let top = values
+        .iter()
+        .filter_map(|values| values.get(index).copied())
+        .fold(0.0_f32, f32::max);
+    [plot.category_x(index, categories), plot.value_y(top, axis)]
+}
+
+#[derive(Clone, PartialEq)]
+struct CartesianChartLayout {
+    kind: CartesianKind,
+    values: Vec<Vec<f32>>,
+    colors: Vec<Color>,
+    categories: usize,
+    axis: ValueAxis,
+    grid_color: Color,
+    highlight_color: Color,
+    hovered: Option<usize>,
+    has_bubble: bool,
+    paths: State<ChartPaths<CartesianChartLayout>>,
+}
+
+impl CartesianChartLayout {
+    fn commands(&self, plot: PlotArea) -> Vec<PathCommand> {
+        let mut commands = Vec::new();
+        let categories = self.categories;
+
+        let mut grid = PathBuilder::new();
+        for tick in self.axis.ticks() {
+            let y = plot.value_y(tick, &self.axis);
+            grid.move_to(plot.left, y).line_to(plot.right(), y);
+        }
+        commands.push(PathCommand::stroke(
+            grid.build(),
+            StrokeStyle::new(GRID_WIDTH.to_px().to_f32()),
+            self.grid_color,
+        ));
+
+        if let (Some(index), CartesianKind::Bar) = (self.hovered, self.kind) {
+            let band = plot.band(categories);
+            let mut highlight = PathBuilder::new();
+            rect(
+                &mut highlight,
+                plot.left + band * index as f32,
+                plot.top,
+                band,
+                plot.height,
+            );
+            commands.push(PathCommand::fill(highlight.build(), self.highlight_color));
+        }
+
+        let baseline = plot.value_y(0.0, &self.axis);
+        let series_count = self.values.len().max(1) as f32;
+        for (series, (values, &color)) in self.values.iter().zip(&self.colors).enumerate() {
+            let values = &values[..values.len().min(categories)];
+            match self.kind {
+                CartesianKind::Bar => {
+                    let group = plot.band(categories) * BAR_GROUP_FRACTION;
+                    let width = group / series_count;
+                    let mut bars = PathBuilder::new();
+                    for (index, &value) in values.iter().enumerate() {
+                        let x = plot.category_x(index, categories) - group / 2.0
+                            + width * series as f32;
+                        let y = plot.value_y(value, &self.axis);
+                        rect(&mut bars, x, y.min(baseline), width, (y - baseline).abs());
+                    }
+                    commands.push(PathCommand::fill(bars.build(), color));
+                }
+                CartesianKind::Line | CartesianKind::Area => {
+                    let points: Vec<[f32; 2]> = values
+                        .iter()
+                        .enumerate()
+                        .map(|(index, &value)| {
+                            [
+                                plot.category_x(index, categories),
+                                plot.value_y(value, &self.axis),
+                            ]
+                        })
+                        .collect();
+                    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+                        continue;
+                    };
+                    if self.kind == CartesianKind::Area {
+                        let mut area = PathBuilder::new();
+                        area.move_to(first[0], baseline);
+                        for point in &points {
+                            area.line_to(point[0], point[1]);
+                        }
+                        area.line_to(last[0], baseline).close();
+                        commands.push(PathCommand::fill(
+                            area.build(),
+                            color.with_alpha(color.a * AREA_ALPHA),
+                        ));
+                    }
+                    let mut line = PathBuilder::new();
+                    line.move_to(first[0], first[1]);
+                    for point in &points[1..] {
+                        line.line_to(point[0], point[1]);
+                    }
+                    commands.push(PathCommand::stroke(line.build(), line_stroke(), color));
+                }
+            }
+        }
+
+        if let Some(index) = self.hovered.filter(|_| self.kind != CartesianKind::Bar) {
+            let x = plot.category_x(index, categories);
+            let mut guide = PathBuilder::new();
+            guide.move_to(x, plot.top).line_to(x, plot.bottom());
+            commands.push(PathCommand::stroke(
+                guide.build(),
+                StrokeStyle::new(GRID_WIDTH.to_px().to_f32()),
+                self.highlight_color,
+            ));
+            for (values, &color) in self.values.iter().zip(&self.colors) {
+                if let Some(&value) = values.get(index) {
+                    let mut point = PathBuilder::new();
+                    circle(
+                        &mut point,
+                        [x, plot.value_y(value, &self.axis)],
+                        POINT_RADIUS.to_px().to_f32(),
+                    );
+                    commands.push(PathCommand::fill(point.build(), color));
+                }
+            }
+        }
+
+        commands.retain(|command| !command.is_empty());
+        commands
+    }
+}
+
+impl LayoutPolicy for CartesianChartLayout {
+    fn measure(&self, input: &MeasureScope<'_>) -> Result<LayoutResult, MeasurementError> {
+        let size = fill_size(
+            input.parent_constraint(),
+            DEFAULT_CHART_WIDTH,
+            DEFAULT_CHART_HEIGHT,
+        );
+        let plot = PlotArea::new(size);
+        let gap = AXIS_LABEL_GAP.to_px().to_f32();
+        let mut result = LayoutResult::default();
+        let mut children = input.children().into_iter();
+
+        let tick_constraint = Constraint::new(
+            AxisConstraint::new(Px::ZERO, Some(Px::saturating_from_f32(plot.left - gap))),
+            AxisConstraint::NONE,
+        );
+        for tick in self.axis.ticks() {
+            let Some(child) = children.next() else {
+                break;
+            };
+            let measured = child.measure(&tick_constraint)?.size();
+            let x = plot.left - gap - measured.width.to_f32();
+            let y = plot.value_y(tick, &self.axis) - measured.height.to_f32() / 2.0;
+            result.place_child(
+                child,
+                PxPosition::new(Px::saturating_from_f32(x), Px::saturating_from_f32(y)),
+            );
+        }
+
+        let label_constraint = Constraint::new(
+            AxisConstraint::new(
+                Px::ZERO,
+                Some(Px::saturating_from_f32(plot.band(self.categories))),
+            ),
+            AxisConstraint::NONE,
+        );
+        for index in 0..self.categories {
+            let Some(child) = children.next() else {
+                break;
+            };
+            let measured = child.measure(&label_constraint)?.size();
+            let x = plot.category_x(index, self.categories) - measured.width.to_f32() / 2.0;
+            result.place_child(
+                child,
+                PxPosition::new(
+                    Px::saturating_from_f32(x),
+                    Px::saturating_from_f32(plot.bottom() + gap),
+                ),
+            );
+        }
+
+        if let (true, Some(index), Some(bubble)) = (self.has_bubble, self.hovered, children.next())
+        {
+            let anchor = cartesian_anchor(plot, &self.axis, &self.values, index, self.categories);
+            place_bubble(&mut result, input, bubble, anchor, size)?;
+        }
+
+        Ok(result.with_size(size))
+    }
+}
+
+impl RenderPolicy for CartesianChartLayout {
+    fn record(&self, input: &mut RenderInput<'_>) {
+        record_chart_paths(input, self.paths, self, |size| {
+            self.commands(PlotArea::new(size))
+        });
+    }
+}
+
+#[tessera]
+fn cartesian_chart(
+    kind: CartesianKind,
+    labels: Vec<String>,
+    series: Vec<ChartSeries>,
+    show_legend: Option<bool>,
+    modifier: Option<Modifier>,
+) {
+    let theme = use_context::<MaterialTheme>()
+        .expect("MaterialTheme must be provided")
+        .get();
+    let scheme = theme.color_scheme;
+    let label_style = theme.typography.label_small;
+    let label_color = scheme.on_surface_variant;
+    let show_legend = show_legend.unwrap_or(series.len() > 1);
+    let categories = series
+        .iter()
+        .map(|series| series.values.len())
+        .fold(labels.len(), usize::max);
+    let target: Vec<Vec<f32>> = series
+        .iter()
+        .map(|series| series.values.iter().copied().map(finite).collect())
+        .collect();
+    let axis = ValueAxis::new(target.iter().flatten().copied());
+    let colors: Vec<Color> = series
+        .iter()
+        .enumerate()
+        .map(|(index, series)| {
+            series
+                .color
+                .unwrap_or_else(|| palette_color(&scheme, index))
+        })
+        .collect();
+
+    let transition = remember(ChartTransition::default);
+    let values = animate_values(transition, target.clone());
+    let hover = remember(|| None::<ChartHover>);
+    let handle = remember(OverlayHandle::new);
+    let paths = remember(ChartPaths::default);
+    let host = use_context::<OverlayHost>().map(|host| host.get());
+
+    let hovered = hover.get().filter(|hover| hover.index < categories);
+    let anchor = hovered.map(|hover| {
+        let point = cartesian_anchor(
+            PlotArea::new(hover.size),
+            &axis,
+            &target,
+            hover.index,
+            categories,
+        );
+        window_anchor(hover, point)
+    });
+    let bubble = {
+        let index = hovered.map_or(0, |hover| hover.index);
+        let title = labels
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| (index + 1).to_string());
+        let entries: Vec<(String, Color, String)> = series
+            .iter()
+            .zip(&colors)
+            .filter_map(|(series, &color)| {
+                let value = series.values.get(index).copied()?;
+                Some((series.name.clone(), color, format_value(finite(value))))
+            })
+            .collect();
+        RenderSlot::new(move || {
+            chart_tooltip(title.clone(), entries.clone());
+        })
+    };
+    let inline = show_tooltip(host, handle, anchor, bubble);
+
+    let ticks = axis.ticks();
+    let policy = CartesianChartLayout {
+        kind,
+        values,
+        colors: colors.clone(),
+        categories,
+        axis: axis.clone(),
+        grid_color: scheme.outline_variant,
+        highlight_color: scheme.on_surface.with_alpha(0.08),
+        hovered: hovered.map(|hover| hover.index),
+        has_bubble: inline,
+        paths,
+    };
+    let chart_modifier = with_pointer_preview_input(modifier.unwrap_or_default(), move |input| {
+        let index = input
+            .cursor_position_rel
+            .filter(|_| input.is_hovered())
+            .and_then(|position| {
+                PlotArea::new(input.computed_data).category_at(position.x.to_f32(), categories)
+            });
+        update_hover(hover, &input, index);
+    });
+    let legend: Vec<(String, Color)> = series
+        .iter()
+        .map(|series| series.name.clone())
+        .zip(colors)
+        .collect();
+
+    column().children(move || {
+        let policy = policy.clone();
+        let axis = axis.clone();
+        let ticks = ticks.clone();
+        let labels = labels.clone();
+        layout()
+            .modifier(chart_modifier.clone())
+            .layout_policy(policy.clone())
+            .render_policy(policy)
+            .child(move || {
+                for &tick in &ticks {
+                    text()
+                        .content(axis.label(tick))
+                        .style(label_style)
+                        .color(label_color);
+                }
+                for index in 0..categories {
+                    text()
+                        .content(labels.get(index).cloned().unwrap_or_default())
+                        .style(label_style)
+                        .color(label_color)
+                        .max_lines(1);
+                }
+                if inline {
+                    bubble.render();
+                }
+            });
+        if show_legend {
+            chart_legend(legend.clone());
+        }
+    });
+}
+
+/// # line_chart
+///
+/// Plots one or more series as lines across category labels.
+///
+/// ## Usage
+///
+/// Show trends over time, such as daily visitors or monthly revenue.
+///
+/// ## Parameters
+///
+/// - `labels` — category labels along the horizontal axis.
+/// - `series` — series to plot, one value per label.
+/// - `show_legend` — optional legend toggle; shown by default for more than one
+///   series.
+/// - `modifier` — optional modifier chain applied to the plot.
+///
+/// ## Examples
+///
+/// ```
+/// # use tessera_ui::tessera;
+/// # #[tessera]
+/// # fn component() {
+/// use tessera_components::charts::{ChartSeries, line_chart};
+/// # use tessera_components::theme::{MaterialTheme, material_theme};
+/// # material_theme()
+/// #     .theme(|| MaterialTheme::default())
+/// #     .child(|| {
+/// line_chart(
+///     vec!["Mon".into(), "Tue".into(), "Wed".into()],
+///     vec![ChartSeries::new("Visitors", vec![120.0, 180.0, 150.0])],
+/// );
+/// # });
+/// # }
+/// # component();
+/// ```
+#[tessera]
+pub fn line_chart(
+    labels: Vec<String>,
+    series: Vec<ChartSeries>,
+    show_legend: Option<bool>,
+    modifier: Option<Modifier>,
+) {
+    cartesian_chart(CartesianKind::Line, labels, series)
+        .show_legend_optional(show_legend)
+        .modifier_optional(modifier);
+}
+
+/// # area_chart
+///
+/// Plots one or more series as lines with the area below them filled.
+///
+/// ## Usage
+///
+/// Emphasize volumes over time, such as traffic or storage usage.
+///
+/// ## Parameters
+///
+/// - `labels` — category labels along the horizontal axis.
+/// - `series` — series to plot, one value per label.
+/// - `show_legend` — optional legend toggle; shown by default for more than one
+///   series.
+/// - `modifier` — optional modifier chain applied to the plot.
+///
+/// ## Examples
+///
+/// ```
+/// # use tessera_ui::tessera;
+/// # #[tessera]
+/// # fn component() {
+/// use tessera_components::charts::{ChartSeries, area_chart};
+/// # use tessera_components::theme::{MaterialTheme, material_theme};
+/// # material_theme()
+/// #     .theme(|| MaterialTheme::default())
+/// #     .child(|| {
+/// area_chart(
+///     vec!["Q1".into(), "Q2".into(), "Q3".into(), "Q4".into()],
+///     vec![ChartSeries::new("Storage", vec![2.0, 3.5, 4.0, 6.5])],
+/// );
+/// # });
+/// # }
+/// # component();
+/// ```
+#[tessera]
+pub fn area_chart(
+    labels: Vec<String>,
+    series: Vec<ChartSeries>,
+    show_legend: Option<bool>,
+    modifier: Option<Modifier>,
+) {
+    cartesian_chart(CartesianKind::Area, labels, series)
+        .show_legend_optional(show_legend)
+        .modifier_optional(modifier);
+}
+
+/// # bar_chart
+///
+/// Plots one or more series as grouped vertical bars per category.
+///
+/// ## Usage
+///
+/// Compare quantities across categories, such as sales per region.
+///
+/// ## Parameters
+///
+/// - `labels` — category labels along the horizontal axis.
+/// - `series` — series to plot, one value per label.
+/// - `show_legend` — optional legend toggle; shown by default for more than one
+///   series.
+/// - `modifier` — optional modifier chain applied to the plot.
+///
+/// ## Examples
+///
+/// ```
+/// # use tessera_ui::tessera;
+/// # #[tessera]
+/// # fn component() {
+/// use tessera_components::charts::{ChartSeries, bar_chart};
+/// # use tessera_components::theme::{MaterialTheme, material_theme};
+/// # material_theme()
+/// #     .theme(|| MaterialTheme::default())
+/// #     .child(|| {
+/// bar_chart(
+///     vec!["North".into(), "South".into()],
+///     vec![
+///         ChartSeries::new("2024", vec![42.0, 35.0]),
+///         ChartSeries::new("2025", vec![48.0, 31.0]),
+///     ],
+/// );
+/// # });
+/// # }
+/// # component();
+/// ```
+#[tessera]
+pub fn bar_chart(
+    labels: Vec<String>,
+    series: Vec<ChartSeries>,
+    show_legend: Option<bool>,
+    modifier: Option<Modifier>,
+) {
+    cartesian_chart(CartesianKind::Bar, labels, series)
+        .show_legend_optional(show_legend)
+        .modifier_optional(modifier);
+}
+
+/// Start and end angles of each pie slice, clockwise from the top.
+fn slice_angles(values: &[f32]) -> Vec<(f32, f32)> {
+    let total: f32 = values.iter().map(|value| value.max(0.0)).sum();
+    let mut start = -FRAC_PI_2;
+    values
+        .iter()
+        .map(|value| {
+            let sweep = if total > 0.0 {
+                value.max(0.0) / total * TAU
+            } else {
+                0.0
+            };
+            let angles = (start, start + sweep);
+            start += sweep;
+            angles
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct PieGeometry {
+    center: [f32; 2],
+    radius: f32,
+    hole: f32,
+}
+
+impl PieGeometry {
+    fn new(size: ComputedData, hole_ratio: f32) -> Self {
+        let radius = (size.width.to_f32().min(size.height.to_f32()) / 2.0
+            - PIE_HOVER_GROWTH.to_px().to_f32())
+        .max(0.0);
+        Self {
+            center: [size.width.to_f32() / 2.0, size.height.to_f32() / 2.0],
+            radius,
+            hole: radius * hole_ratio.clamp(0.0, 0.95),
+        }
+    }
+
+    fn slice_at(&self, values: &[f32], position: [f32; 2]) -> Option<usize> {
+        let dx = position[0] - self.center[0];
+        let dy = position[1] - self.center[1];
+        let distance = dx.hypot(dy);
+        if distance > self.radius || distance < self.hole {
+            return None;
+        }
+        // Angles run clockwise from the top, like the slices.
+        let angle = (dy.atan2(dx) + FRAC_PI_2).rem_euclid(TAU) - FRAC_PI_2;
+        slice_angles(values)
+            .iter()
+            .position(|&(start, end)| start < end && angle >= start && angle < end)
+    }
+
+    /// Middle of a slice, where its tooltip opens.
+    fn anchor(&self, values: &[f32], index: usize) -> Option<[f32; 2]> {
+        let (start, end) = slice_angles(values).get(index).copied()?;
+        Some(polar(
+            self.center,
+            (self.radius + self.hole) / 2.0,
+            (start + end) / 2.0,
+        ))
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct PieChartLayout {
+    values: Vec<f32>,
+    colors: Vec<Color>,
+    hole_ratio: f32,
+    hovered: Option<usize>,
+    has_bubble: bool,
+    paths: State<ChartPaths<PieChartLayout>>,
+}
+
+impl PieChartLayout {
+    fn commands(&self, size: ComputedData) -> Vec<PathCommand> {
+        let geometry = PieGeometry::new(size, self.hole_ratio);
+        let growth = PIE_HOVER_GROWTH.to_px().to_f32();
+        slice_angles(&self.values)
+            .into_iter()
+            .zip(&self.colors)
+            .enumerate()
+            .filter(|(_, ((start, end), _))| end > start)
+            .map(|(index, ((start, end), &color))| {
+                let radius = if self.hovered == Some(index) {
+                    geometry.radius + growth
+                } else {
+                    geometry.radius
+                };
+                let mut slice = PathBuilder::new();
+                let outer = polar(geometry.center, radius, start);
+                slice.move_to(outer[0], outer[1]);
+                arc_to(&mut slice, geometry.center, radius, start, end);
+                if geometry.hole > 0.0 {
+                    let inner = polar(geometry.center, geometry.hole, end);
+                    slice.line_to(inner[0], inner[1]);
+                    arc_to(&mut slice, geometry.center, geometry.hole, end, start);
+                } else {
+                    slice.line_to(geometry.center[0], geometry.center[1]);
+                }
+                slice.close();
+                PathCommand::fill(slice.build(), color)
+            })
+            .collect()
+    }
+}
+
+impl LayoutPolicy for PieChartLayout {
+    fn measure(&self, input: &MeasureScope<'_>) -> Result<LayoutResult, MeasurementError> {
+        let size = fill_size(
+            input.parent_constraint(),
+            DEFAULT_CHART_HEIGHT,
+            DEFAULT_CHART_HEIGHT,
+        );
+        let mut result = LayoutResult::default();
+        let geometry = PieGeometry::new(size, self.hole_ratio);
+        let anchor = self
+            .hovered
+            .and_then(|index| geometry.anchor(&self.values, index));
+        if let (true, Some(anchor), Some(bubble)) =
+            (self.has_bubble, anchor, input.children().first().copied())
+        {
+            place_bubble(&mut result, input, bubble, anchor, size)?;
+        }
+        Ok(result.with_size(size))
+    }
+}
+
+impl RenderPolicy for PieChartLayout {
+    fn record(&self, input: &mut RenderInput<'_>) {
+        record_chart_paths(input, self.paths, self, |size| self.commands(size));
+    }
+}
+
+/// # pie_chart
+///
+/// Shows the share of each value in a total as slices of a pie or donut.
+///
+/// ## Usage
+///
+/// Present proportions, such as budget categories or traffic sources.
+///
+/// ## Parameters
+///
+/// - `labels` — slice labels, shown in the legend and tooltips.
+/// - `values` — slice values; negative values are drawn as empty slices.
+/// - `hole_ratio` — optional inner radius as a fraction of the outer one;
+///   values above zero draw a donut.
+/// - `colors` — optional slice colors; defaults to the theme palette.
+/// - `show_legend` — optional legend toggle; shown by default.
+/// - `modifier` — optional modifier chain applied to the pie.
+///
+/// ## Examples
+///
+/// ```
+/// # use tessera_ui::tessera;
+/// # #[tessera]
+/// # fn component() {
+/// use tessera_components::charts::pie_chart;
+/// # use tessera_components::theme::{MaterialTheme, material_theme};
+/// # material_theme()
+/// #     .theme(|| MaterialTheme::default())
+/// #     .child(|| {
+/// pie_chart(
+///     vec!["Rent".into(), "Food".into(), "Travel".into()],
+///     vec![1200.0, 450.0, 300.0],
+/// )
+/// .hole_ratio(0.6);
+/// # });
+/// # }
+/// # component();
+/// ```
+#[tessera]
+pub fn pie_chart(
+    labels: Vec<String>,
+    values: Vec<f32>,
+    hole_ratio: Option<f32>,
+    colors: Option<Vec<Color>>,
+    show_legend: Option<bool>,
+    modifier: Option<Modifier>,
+) {
+    let scheme = use_context::<MaterialTheme>()
+        .expect("MaterialTheme must be provided")
+        .get()
+        .color_scheme;
+    let hole_ratio = hole_ratio.unwrap_or(0.0);
+    let show_legend = show_legend.unwrap_or(true);
+    let target: Vec<f32> = values.iter().copied().map(finite).collect();
+    let colors: Vec<Color> = (0..target.len())
+        .map(|index| {
+            colors
+                .as_ref()
+                .and_then(|colors| colors.get(index).copied())
+                .unwrap_or_else(|| palette_color(&scheme, index))
+        })
+        .collect();
+
+    let transition = remember(ChartTransition::default);
+    let animated = animate_values(transition, vec![target.clone()])
+        .pop()
+        .unwrap_or_default();
+    let hover = remember(|| None::<ChartHover>);
+    let handle = remember(OverlayHandle::new);
+    let paths = remember(ChartPaths::default);
+    let host = use_context::<OverlayHost>().map(|host| host.get());
+
+    let hovered = hover.get().filter(|hover| hover.index < target.len());
+    let anchor = hovered.and_then(|hover| {
+        let point = PieGeometry::new(hover.size, hole_ratio).anchor(&target, hover.index)?;
+        Some(window_anchor(hover, point))
+    });
+    let label = |index: usize| {
+        labels
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| (index + 1).to_string())
+    };
+    let bubble = {
+        let index = hovered.map_or(0, |hover| hover.index);
+        let total: f32 = target.iter().map(|value| value.max(0.0)).sum();
+        let value = target.get(index).copied().unwrap_or_default();
+        let share = if total > 0.0 {
+            value.max(0.0) / total * 100.0
+        } else {
+            0.0
+        };
+        let title = label(index);
+        let entries = vec![(
+            format_value(value),
+            colors.get(index).copied().unwrap_or(scheme.primary),
+            format!("{share:.0}%"),
+        )];
+        RenderSlot::new(move || {
+            chart_tooltip(title.clone(), entries.clone());
+        })
+    };
+    let inline = show_tooltip(host, handle, anchor, bubble);
+
+    let policy = PieChartLayout {
+        values: animated,
+        colors: colors.clone(),
+        hole_ratio,
+        hovered: hovered.map(|hover| hover.index),
+        has_bubble: inline,
+        paths,
+    };
+    let hit_values = target.clone();
+    let chart_modifier = with_pointer_preview_input(modifier.unwrap_or_default(), move |input| {
+        let index = input
+            .cursor_position_rel
+            .filter(|_| input.is_hovered())
+            .and_then(|position| {
+                PieGeometry::new(input.computed_data, hole_ratio)
+                    .slice_at(&hit_values, [position.x.to_f32(), position.y.to_f32()])
+            });
+        update_hover(hover, &input, index);
+    });
+    let legend: Vec<(String, Color)> = (0..target.len()).map(label).zip(colors).collect();
+
+    column()
+        .cross_axis_alignment(CrossAxisAlignment::Center)
+        .children(move || {
+            let policy = policy.clone();
+            layout()
+                .modifier(chart_modifier.clone())
+                .layout_policy(policy.clone())
+                .render_policy(policy)
+                .child(move || {
+                    if inline {
+                        bubble.render();
+                    }
+                });
+            if show_legend {
+                chart_legend(legend.clone());
+            }
+        });
+}
+
+#[derive(Clone, PartialEq)]
+struct SparklineLayout {
+    values: Vec<f32>,
+    color: Color,
+    fill: bool,
+    paths: State<ChartPaths<SparklineLayout>>,
+}
+
+impl SparklineLayout {
+    fn commands(&self, size: ComputedData) -> Vec<PathCommand> {
+        let inset = LINE_WIDTH.to_px().to_f32();
+        let width = (size.width.to_f32() - inset * 2.0).max(0.0);
+        let height = (size.height.to_f32() - inset * 2.0).max(0.0);
+        let (low, high) = self
+            .values
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(low, high), &value| {
+                (low.min(value), high.max(value))
+            });
+        let span = high - low;
+        let step = width / (self.values.len().saturating_sub(1).max(1)) as f32;
+        let points: Vec<[f32; 2]> = self
+            .values
+            .iter()
+            .enumerate()
+            .map(|(index, &value)| {
+                let fraction = if span > f32::EPSILON {
+                    (value - low) / span
+                } else {
+                    0.5
+                };
+                [
+                    inset + step * index as f32,
+                    inset + height * (1.0 - fraction),
+                ]
+            })
+            .collect();
+        let (Some(first), Some(last)) = (points.first(), points.last()) else {
+            return Vec::new();
+        };
+
+        let mut commands = Vec::new();
+        if self.fill {
+            let bottom = inset + height;
+            let mut area = PathBuilder::new();
+            area.move_to(first[0], bottom);
+            for point in &points {
+                area.line_to(point[0], point[1]);
+            }
+            area.line_to(last[0], bottom).close();
+            commands.push(PathCommand::fill(
+                area.build(),
+                self.color.with_alpha(self.color.a * AREA_ALPHA),
+            ));
+        }
+        let mut line = PathBuilder::new();
+        line.move_to(first[0], first[1]);
+        for point in &points[1..] {
+            line.line_to(point[0], point[1]);
+        }
+        commands.push(PathCommand::stroke(line.build(), line_stroke(), self.color));
+        commands.retain(|command| !command.is_empty());
+        commands
+    }
+}
+
+impl LayoutPolicy for SparklineLayout {
+    fn measure(&self, input: &MeasureScope<'_>) -> Result<LayoutResult, MeasurementError> {
+        let parent = input.parent_constraint();
+        Ok(LayoutResult::new(ComputedData {
+            width: parent.width().clamp(SPARKLINE_WIDTH.to_px()),
+            height: parent.height().clamp(SPARKLINE_HEIGHT.to_px()),
+        }))
+    }
+}
+
+impl RenderPolicy for SparklineLayout {
+    fn record(&self, input: &mut RenderInput<'_>) {
+        record_chart_paths(input, self.paths, self, |size| self.commands(size));
+    }
+}
+
+/// # sparkline
+///
+/// Draws a small line of values without axes, scaled to their range.
+///
+/// ## Usage
+///
+/// Show a trend inline next to a number, such as in a table cell or card.
+///
+/// ## Parameters
+///
+/// - `values` — values to draw, from left to right.
+/// - `color` — optional line color; defaults to the theme primary color.
+/// - `fill` — optional toggle filling the area below the line.
+/// - `modifier` — optional modifier chain applied to the sparkline.
+///
+/// ## Examples
+///
+/// ```
+/// # use tessera_ui::tessera;
+/// # #[tessera]
+/// # fn component() {
+/// use tessera_components::charts::sparkline;
+/// # use tessera_components::theme::{MaterialTheme, material_theme};
+/// # material_theme()
+/// #     .theme(|| MaterialTheme::default())
+/// #     .child(|| {
+/// sparkline(vec![3.0, 5.0, 4.0, 8.0, 7.0]).fill(true);
+/// # });
+/// # }
+/// # component();
+/// ```
+#[tessera]
+pub fn sparkline(
+    values: Vec<f32>,
+    color: Option<Color>,
+    fill: Option<bool>,
+    modifier: Option<Modifier>,
+) {
+    let scheme = use_context::<MaterialTheme>()
+        .expect("MaterialTheme must be provided")
+        .get()
+        .color_scheme;
+    let target: Vec<f32> = values.into_iter().map(finite).collect();
+    let transition = remember(ChartTransition::default);
+    let values = animate_values(transition, vec![target])
+        .pop()
+        .unwrap_or_default();
+    let paths = remember(ChartPaths::default);
+    let policy = SparklineLayout {
+        values,
+        color: color.unwrap_or(scheme.primary),
+        fill: fill.unwrap_or(false),
+        paths,
+    };
+    layout()
+        .modifier(modifier.unwrap_or_default())
+        .layout_policy(policy.clone())
+        .render_policy(policy);
+}
+
+#[tessera]
+fn chart_legend(entries: Vec<(String, Color)>) {
+    let label_style = use_context::<MaterialTheme>()
+        .expect("MaterialTheme must be provided")
+        .get()
+        .typography
+        .label_medium;
+    flow_row()
+        .modifier(Modifier::new().padding_symmetric(Dp::ZERO, LEGEND_PADDING))
+        .item_spacing(LEGEND_SPACING)
+        .line_spacing(LEGEND_SWATCH_GAP)
+        .children(move || {
+            for (name, color) in entries.clone() {
+                row()
+                    .cross_axis_alignment(CrossAxisAlignment::Center)
+                    .children(move || {
+                        legend_swatch(color);
+                        text().content(name.clone()).style(label_style);
+                    });
+            }
+        });
+}
+
+fn legend_swatch(color: Color) {
+    spacer().modifier(
+        Modifier::new()
+            .size(LEGEND_SWATCH, LEGEND_SWATCH)
+            .background_with_shape(color, Shape::Ellipse),
+    );
+    spacer().modifier(Modifier::new().width(LEGEND_SWATCH_GAP));
+}
+
+/// Tooltip listing a title and a colored `name: value` line per entry.
+#[tessera]
+fn chart_tooltip(title: String, entries: Vec<(String, Color, String)>) {
+    tooltip_container(RenderSlot::new(move || {
+        let title = title.clone();
+        let entries = entries.clone();
+        column().children(move || {
+            text().content(title.clone());
+            for (name, color, value) in entries.clone() {
+                row()
+                    .cross_axis_alignment(CrossAxisAlignment::Center)
+                    .children(move || {
+                        legend_swatch(color);
+                        text().content(format!("{name}: {value}"));
+                    });
+            }
+        });
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn value_axis_rounds_to_readable_ticks() {
+        let axis = ValueAxis::new([12.0, 87.0, 45.0]);
+        assert_eq!(axis.ticks(), vec![0.0, 20.0, 40.0, 60.0, 80.0, 100.0]);
+        assert_eq!(axis.label(40.0), "40");
+        assert_eq!(axis.fraction(50.0), 0.5);
+
+        let axis = ValueAxis::new([-0.25, 0.18]);
+        let labels: Vec<String> = axis
+            .ticks()
+            .into_iter()
+            .map(|tick| axis.label(tick))
+            .collect();
+        assert_eq!(labels, ["-0.3", "-0.2", "-0.1", "0.0", "0.1", "0.2"]);
+
+        assert_eq!(ValueAxis::new([]).ticks().len(), 6);
+    }
+
+    #[test]
+    fn transitions_grow_new_values_and_pie_slices_hit_clockwise() {
+        let mut transition = ChartTransition::default();
+        transition.retarget(
+            vec![vec![10.0, 20.0]],
+            Some(AnimationSpec::tween(TRANSITION_DURATION)),
+            0,
+        );
+        assert_eq!(transition.current(), vec![vec![0.0, 0.0]]);
+        assert!(
+            !transition
+                .progress
+                .tick(TRANSITION_DURATION.as_nanos() as u64)
+        );
+        assert_eq!(transition.current(), vec![vec![10.0, 20.0]]);
+        transition.retarget(vec![vec![5.0]], None, 0);
+        assert_eq!(transition.current(), vec![vec![5.0]]);
+
+        let size = ComputedData {
+            width: Px::new(200),
+            height: Px::new(200),
+        };
+        let geometry = PieGeometry::new(size, 0.5);
+        let values = [1.0, 1.0, 2.0];
+        // Slices run clockwise from the top: top right, bottom right, left half.
+        assert_eq!(geometry.slice_at(&values, [150.0, 60.0]), Some(0));
+        assert_eq!(geometry.slice_at(&values, [150.0, 150.0]), Some(1));
+        assert_eq!(geometry.slice_at(&values, [50.0, 100.0]), Some(2));
+        assert_eq!(geometry.slice_at(&values, [100.0, 100.0]), None);
+    }
+
+    #[test]
+    fn chart_paths_are_rebuilt_only_when_layout_or_size_changes() {
+        let size = ComputedData {
+            width: Px::new(100),
+            height: Px::new(50),
+        };
+        let mut paths = ChartPaths::default();
+        let mut builds = 0;
+        let mut record = |layout: u32, size: ComputedData| {
+            paths.commands(&layout, size, || {
+                builds += 1;
+                Vec::new()
+            });
+        };
+        record(1, size);
+        record(1, size);
+        record(
+            1,
+            ComputedData {
+                width: Px::new(120),
+                ..size
+            },
+        );
+        record(2, size);
+        assert_eq!(builds, 3);
+    }
+}
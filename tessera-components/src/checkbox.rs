@@ -5,10 +5,10 @@
 //! Use in forms, settings, or lists to enable boolean selections.
 use std::time::Duration;
 
-use tessera_foundation::gesture::TapRecognizer;
 use tessera_ui::{
     CallbackWith, Color, Dp, Modifier, PxSize, RenderSlot, State, accesskit::Role,
-    current_frame_nanos, receive_frame_nanos, remember, tessera, use_context,
+    current_frame_nanos, gesture::TapRecognizer, receive_frame_nanos, remember, tessera,
+    use_context,
 };
 
 use crate::{
@@ -0,0 +1,717 @@
+//! Material Design 3 data tables for browsing rows of structured data.
+//!
+//! ## Usage
+//!
+//! List files, orders or records with sortable and resizable columns.
+use std::collections::BTreeSet;
+
+use tessera_ui::{
+    AxisConstraint, CallbackWith, Dp, Modifier, Px, RenderSlotWith, State,
+    accesskit::Role,
+    gesture::{DragAxis, DragRecognizer, DragSettings, TapRecognizer},
+    modifier::CursorModifierExt as _,
+    provide_context, remember, tessera, use_context, winit,
+};
+
+use crate::{
+    alignment::{Alignment, CrossAxisAlignment},
+    boxed::boxed,
+    column::column,
+    divider::horizontal_divider,
+    icon::icon,
+    lazy_list::lazy_column,
+    material_icons::filled,
+    modifier::{ModifierExt as _, SemanticsArgs, with_pointer_input},
+    pos_misc::is_position_inside_bounds,
+    row::row,
+    text::text,
+    theme::MaterialTheme,
+};
+
+const HEADER_HEIGHT: Dp = Dp(56.0);
+const ROW_HEIGHT: Dp = Dp(52.0);
+const CELL_HORIZONTAL_PADDING: Dp = Dp(16.0);
+const SORT_ICON_SIZE: Dp = Dp(18.0);
+const RESIZE_HANDLE_WIDTH: Dp = Dp(8.0);
+
+/// Describes one column of a [`data_table`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataTableColumn {
+    title: String,
+    width: Dp,
+    min_width: Dp,
+    sortable: bool,
+    resizable: bool,
+}
+
+impl DataTableColumn {
+    /// Creates a resizable, unsorted column titled `title`.
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            width: Dp(160.0),
+            min_width: Dp(48.0),
+            sortable: false,
+            resizable: true,
+        }
+    }
+
+    /// Sets the initial width. Defaults to 160dp.
+    pub fn width(mut self, width: Dp) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Sets the width the column cannot be resized below. Defaults to 48dp.
+    pub fn min_width(mut self, min_width: Dp) -> Self {
+        self.min_width = min_width;
+        self
+    }
+
+    /// Sets whether clicking the header requests sorting by this column.
+    pub fn sortable(mut self, sortable: bool) -> Self {
+        self.sortable = sortable;
+        self
+    }
+
+    /// Sets whether the column can be resized by dragging its header edge.
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+}
+
+/// Order of a sorted column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SortDirection {
+    /// Smallest values first.
+    #[default]
+    Ascending,
+    /// Largest values first.
+    Descending,
+}
+
+impl SortDirection {
+    /// Returns the opposite direction.
+    pub fn reversed(self) -> Self {
+        match self {
+            Self::Ascending => Self::Descending,
+            Self::Descending => Self::Ascending,
+        }
+    }
+}
+
+/// Column a table is sorted by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DataTableSort {
+    /// Index of the column in the `columns` passed to [`data_table`].
+    pub column: usize,
+    /// Sort order.
+    pub direction: SortDirection,
+}
+
+impl DataTableSort {
+    /// Returns the sort requested by clicking the header of `column`: the
+    /// current column reverses, any other column sorts ascending.
+    fn clicked(current: Option<Self>, column: usize) -> Self {
+        match current {
+            Some(sort) if sort.column == column => Self {
+                column,
+                direction: sort.direction.reversed(),
+            },
+            _ => Self {
+                column,
+                direction: SortDirection::Ascending,
+            },
+        }
+    }
+}
+
+/// How clicking rows changes the selection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum DataTableSelectionMode {
+    /// Rows cannot be selected.
+    None,
+    /// A click selects one row.
+    #[default]
+    Single,
+    /// A click selects one row, Ctrl-click toggles a row and Shift-click
+    /// selects the range from the last clicked row.
+    Multiple,
+}
+
+/// Column layout and row selection of a [`data_table`].
+///
+/// Rows are identified by their index, so an application that sorts or
+/// filters its data should clear or remap the selection.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DataTableController {
+    /// Column widths indexed by column.
+    widths: Vec<Px>,
+    /// Column indices in display order.
+    order: Vec<usize>,
+    selection: BTreeSet<usize>,
+    /// Row a Shift-click extends the selection from.
+    anchor: Option<usize>,
+    /// Display position and offset of the header being dragged.
+    dragging: Option<(usize, Px)>,
+}
+
+impl DataTableController {
+    /// Creates a controller with no selection and the columns' own widths.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns whether `row` is selected.
+    pub fn is_selected(&self, row: usize) -> bool {
+        self.selection.contains(&row)
+    }
+
+    /// Returns the selected rows in ascending order.
+    pub fn selected_rows(&self) -> Vec<usize> {
+        self.selection.iter().copied().collect()
+    }
+
+    /// Replaces the selection.
+    pub fn set_selection(&mut self, rows: impl IntoIterator<Item = usize>) {
+        self.selection = rows.into_iter().collect();
+        self.anchor = self.selection.first().copied();
+    }
+
+    /// Clears the selection.
+    pub fn clear_selection(&mut self) {
+        self.selection.clear();
+        self.anchor = None;
+    }
+
+    /// Returns the column indices in display order.
+    pub fn column_order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Returns the current width of `column`, once the table has been shown.
+    pub fn column_width(&self, column: usize) -> Option<Px> {
+        self.widths.get(column).copied()
+    }
+
+    /// Resets widths and order when the table is shown with a different set
+    /// of columns.
+    fn sync_columns(&mut self, columns: &[DataTableColumn]) {
+        if self.widths.len() != columns.len() {
+            self.widths = columns.iter().map(|column| column.width.to_px()).collect();
+            self.order = (0..columns.len()).collect();
+            self.dragging = None;
+        }
+    }
+
+    fn resize(&mut self, column: usize, delta: Px, min_width: Px) {
+        if let Some(width) = self.widths.get_mut(column) {
+            *width = (*width + delta).max(min_width);
+        }
+    }
+
+    fn drag_column(&mut self, position: usize, delta: Px) {
+        let offset = match self.dragging {
+            Some((dragged, offset)) if dragged == position => offset + delta,
+            _ => delta,
+        };
+        self.dragging = Some((position, offset));
+    }
+
+    fn drop_column(&mut self) {
+        let Some((from, offset)) = self.dragging.take() else {
+            return;
+        };
+        let widths: Vec<Px> = self
+            .order
+            .iter()
+            .map(|&column| self.widths[column])
+            .collect();
+        let to = reorder_target(&widths, from, offset);
+        if to != from {
+            let column = self.order.remove(from);
+            self.order.insert(to, column);
+        }
+    }
+
+    /// Applies a click on `row`; returns whether the selection changed.
+    fn click_row(
+        &mut self,
+        row: usize,
+        mode: DataTableSelectionMode,
+        extend: bool,
+        toggle: bool,
+    ) -> bool {
+        let previous = self.selection.clone();
+        match mode {
+            DataTableSelectionMode::None => return false,
+            DataTableSelectionMode::Single => {
+                self.selection = BTreeSet::from([row]);
+                self.anchor = Some(row);
+            }
+            DataTableSelectionMode::Multiple => match self.anchor {
+                Some(anchor) if extend => {
+                    if !toggle {
+                        self.selection.clear();
+                    }
+                    self.selection.extend(anchor.min(row)..=anchor.max(row));
+                }
+                _ if toggle => {
+                    if !self.selection.remove(&row) {
+                        self.selection.insert(row);
+                    }
+                    self.anchor = Some(row);
+                }
+                _ => {
+                    self.selection = BTreeSet::from([row]);
+                    self.anchor = Some(row);
+                }
+            },
+        }
+        self.selection != previous
+    }
+}
+
+/// Returns the display position a column lands at when the one at `from` is
+/// dragged by `offset`, judged by where the dragged column's center is.
+fn reorder_target(widths: &[Px], from: usize, offset: Px) -> usize {
+    let start = widths[..from]
+        .iter()
+        .fold(Px::ZERO, |sum, width| sum + *width);
+    let center = start + offset + Px(widths[from].0 / 2);
+    let mut end = Px::ZERO;
+    for (position, width) in widths.iter().enumerate() {
+        end += *width;
+        if center < end {
+            return position;
+        }
+    }
+    widths.len() - 1
+}
+
+/// # data_table
+///
+/// Shows rows of data in columns with a fixed header.
+///
+/// Only visible rows are built, and the header stays in place while rows
+/// scroll. Clicking a sortable header calls `on_sort_change`; the application
+/// sorts its data and passes the new sort back. Columns are resized by dragging
+/// the edge of their header and, when enabled, reordered by dragging the header
+/// itself. Cells receive the row index and the column's index in `columns`,
+/// which does not change when columns are reordered.
+///
+/// ## Usage
+///
+/// Browse large record sets on desktop.
+///
+/// ## Parameters
+///
+/// - `columns` — the table's columns
+/// - `row_count` — number of rows
+/// - `cell` — builds the cell for a `(row, column)` pair
+/// - `sort` — optional column the data is currently sorted by, shown in its
+///   header
+/// - `on_sort_change` — optional callback with the sort requested by clicking a
+///   sortable header
+/// - `selection_mode` — optional row selection behavior; defaults to
+///   [`DataTableSelectionMode::Single`]
+/// - `on_selection_change` — optional callback with the selected rows after a
+///   click changes them
+/// - `reorderable_columns` — whether headers can be dragged to reorder columns;
+///   defaults to `false`
+/// - `row_height` — optional row height; defaults to 52dp
+/// - `controller` — optional controller holding column layout and selection
+/// - `modifier` — optional modifier chain applied to the table
+///
+/// ## Examples
+///
+/// ```
+/// # use tessera_ui::tessera;
+/// # #[tessera]
+/// # fn component() {
+/// use tessera_components::{
+///     data_table::{DataTableColumn, DataTableSelectionMode, data_table},
+///     text::text,
+/// };
+/// use tessera_ui::Dp;
+/// # use tessera_components::theme::{MaterialTheme, material_theme};
+///
+/// let files = [("notes.txt", 12), ("photo.png", 2048)];
+/// # material_theme()
+/// #     .theme(|| MaterialTheme::default())
+/// #     .child(move || {
+/// data_table(
+///     vec![
+///         DataTableColumn::new("Name").sortable(true),
+///         DataTableColumn::new("Size (KB)").width(Dp(96.0)),
+///     ],
+///     files.len(),
+///     move |(row, column): (usize, usize)| {
+///         let (name, size) = files[row];
+///         if column == 0 {
+///             text().content(name);
+///         } else {
+///             text().content(size.to_string());
+///         }
+///     },
+/// )
+/// .selection_mode(DataTableSelectionMode::Multiple)
+/// .reorderable_columns(true);
+/// # });
+/// # }
+/// # component();
+/// ```
+#[tessera]
+pub fn data_table(
+    columns: Vec<DataTableColumn>,
+    row_count: usize,
+    #[prop(render_slot_with)] cell: RenderSlotWith<(usize, usize)>,
+    sort: Option<DataTableSort>,
+    on_sort_change: Option<CallbackWith<DataTableSort>>,
+    selection_mode: Option<DataTableSelectionMode>,
+    on_selection_change: Option<CallbackWith<Vec<usize>>>,
+    reorderable_columns: Option<bool>,
+    row_height: Option<Dp>,
+    controller: Option<State<DataTableController>>,
+    modifier: Option<Modifier>,
+) {
+    let selection_mode = selection_mode.unwrap_or_default();
+    let reorderable = reorderable_columns.unwrap_or(false);
+    let row_height = row_height.unwrap_or(ROW_HEIGHT);
+    let controller = controller.unwrap_or_else(|| remember(DataTableController::new));
+    if controller.with(|controller| controller.widths.len() != columns.len()) {
+        controller.with_mut(|controller| controller.sync_columns(&columns));
+    }
+    let modifier = modifier.unwrap_or_default().semantics(SemanticsArgs {
+        role: Some(Role::Table),
+        ..Default::default()
+    });
+    let typography = use_context::<MaterialTheme>()
+        .expect("MaterialTheme must be provided")
+        .get()
+        .typography;
+
+    column().modifier(modifier).children(move || {
+        let columns = columns.clone();
+        provide_context(
+            || typography.title_small,
+            move || {
+                data_table_header(
+                    columns.clone(),
+                    sort,
+                    on_sort_change,
+                    reorderable,
+                    controller,
+                );
+            },
+        );
+        horizontal_divider();
+        provide_context(
+            || typography.body_medium,
+            move || {
+                lazy_column()
+                    .modifier(Modifier::new().fill_max_width().weight(1.0))
+                    .estimated_item_size(row_height)
+                    .items(row_count, move |row| {
+                        data_table_row(
+                            row,
+                            cell,
+                            row_height,
+                            selection_mode,
+                            on_selection_change,
+                            controller,
+                        );
+                    });
+            },
+        );
+    });
+}
+
+#[tessera]
+fn data_table_header(
+    columns: Vec<DataTableColumn>,
+    sort: Option<DataTableSort>,
+    on_sort_change: Option<CallbackWith<DataTableSort>>,
+    reorderable: bool,
+    controller: State<DataTableController>,
+) {
+    let order = controller.with(|controller| controller.order.clone());
+    row()
+        .modifier(Modifier::new().fill_max_width().height(HEADER_HEIGHT))
+        .cross_axis_alignment(CrossAxisAlignment::Center)
+        .children(move || {
+            for (position, &index) in order.iter().enumerate() {
+                let Some(spec) = columns.get(index).cloned() else {
+                    continue;
+                };
+                data_table_header_cell(
+                    index,
+                    position,
+                    spec,
+                    sort,
+                    on_sort_change,
+                    reorderable,
+                    controller,
+                );
+            }
+        });
+}
+
+#[tessera]
+fn data_table_header_cell(
+    index: usize,
+    position: usize,
+    spec: DataTableColumn,
+    sort: Option<DataTableSort>,
+    on_sort_change: Option<CallbackWith<DataTableSort>>,
+    reorderable: bool,
+    controller: State<DataTableController>,
+) {
+    let direction = sort
+        .filter(|sort| sort.column == index)
+        .map(|sort| sort.direction);
+    let on_sort_change = on_sort_change.filter(|_| spec.sortable);
+    let (width, offset) = controller.with(|controller| {
+        let offset = controller
+            .dragging
+            .filter(|(dragged, _)| *dragged == position)
+            .map_or(Px::ZERO, |(_, offset)| offset);
+        (controller.widths[index], offset)
+    });
+    let tap_recognizer = remember(TapRecognizer::default);
+    let drag_recognizer = remember(|| {
+        DragRecognizer::new(DragSettings {
+            axis: Some(DragAxis::Horizontal),
+            ..Default::default()
+        })
+    });
+    let base = Modifier::new()
+        .constrain(Some(AxisConstraint::exact(width)), None)
+        .fill_max_height()
+        .offset(offset.into(), Dp::ZERO)
+        .semantics(SemanticsArgs {
+            role: Some(Role::ColumnHeader),
+            label: Some(spec.title.clone()),
+            ..Default::default()
+        });
+    let modifier = with_pointer_input(base, move |mut input| {
+        let is_inside = input
+            .cursor_position_rel
+            .is_some_and(|position| is_position_inside_bounds(input.computed_data, position));
+        if reorderable {
+            // The header follows the drag, so deltas are taken in window
+            // coordinates.
+            let cursor = input.cursor_position_abs();
+            let drag = drag_recognizer.with_mut(|recognizer| {
+                recognizer.update(
+                    input.pass,
+                    input.pointer_changes.as_mut_slice(),
+                    cursor,
+                    is_inside,
+                )
+            });
+            if drag.updated {
+                controller.with_mut(|controller| controller.drag_column(position, drag.delta_x));
+            }
+            if drag.ended {
+                controller.with_mut(DataTableController::drop_column);
+            }
+        }
+        let tap = tap_recognizer.with_mut(|recognizer| {
+            recognizer.update(
+                input.pass,
+                input.pointer_changes.as_mut_slice(),
+                input.cursor_position_rel,
+                is_inside,
+            )
+        });
+        if tap.tapped
+            && let Some(on_sort_change) = on_sort_change
+        {
+            on_sort_change.call(DataTableSort::clicked(sort, index));
+        }
+    });
+
+    row()
+        .modifier(modifier)
+        .cross_axis_alignment(CrossAxisAlignment::Center)
+        .children(move || {
+            let title = spec.title.clone();
+            row()
+                .modifier(
+                    Modifier::new()
+                        .weight(1.0)
+                        .padding_symmetric(CELL_HORIZONTAL_PADDING, Dp::ZERO)
+                        .clip_to_bounds(),
+                )
+                .cross_axis_alignment(CrossAxisAlignment::Center)
+                .children(move || {
+                    text().content(title.clone()).max_lines(1);
+                    if let Some(direction) = direction {
+                        let arrow = match direction {
+                            SortDirection::Ascending => filled::ARROW_UPWARD_SVG,
+                            SortDirection::Descending => filled::ARROW_DOWNWARD_SVG,
+                        };
+                        icon().painter(arrow).size(SORT_ICON_SIZE);
+                    }
+                });
+            if spec.resizable {
+                column_resize_handle(index, spec.min_width, controller);
+            }
+        });
+}
+
+#[tessera]
+fn column_resize_handle(index: usize, min_width: Dp, controller: State<DataTableController>) {
+    let drag_recognizer = remember(|| {
+        DragRecognizer::new(DragSettings {
+            axis: Some(DragAxis::Horizontal),
+            ..Default::default()
+        })
+    });
+    let base = Modifier::new()
+        .width(RESIZE_HANDLE_WIDTH)
+        .fill_max_height()
+        .hover_cursor_icon(winit::window::CursorIcon::ColResize);
+    let modifier = with_pointer_input(base, move |mut input| {
+        let is_inside = input
+            .cursor_position_rel
+            .is_some_and(|position| is_position_inside_bounds(input.computed_data, position));
+        // The handle moves with the column edge, so deltas are taken in
+        // window coordinates.
+        let cursor = input.cursor_position_abs();
+        let drag = drag_recognizer.with_mut(|recognizer| {
+            recognizer.update(
+                input.pass,
+                input.pointer_changes.as_mut_slice(),
+                cursor,
+                is_inside,
+            )
+        });
+        if drag.updated {
+            controller.with_mut(|controller| {
+                controller.resize(index, drag.delta_x, min_width.to_px());
+            });
+        }
+    });
+    boxed().modifier(modifier).children(|| {});
+}
+
+#[tessera]
+fn data_table_row(
+    row_index: usize,
+    cell: RenderSlotWith<(usize, usize)>,
+    row_height: Dp,
+    selection_mode: DataTableSelectionMode,
+    on_selection_change: Option<CallbackWith<Vec<usize>>>,
+    controller: State<DataTableController>,
+) {
+    let (selected, cells) = controller.with(|controller| {
+        let cells: Vec<(usize, Px)> = controller
+            .order
+            .iter()
+            .map(|&column| (column, controller.widths[column]))
+            .collect();
+        (controller.is_selected(row_index), cells)
+    });
+    let scheme = use_context::<MaterialTheme>()
+        .expect("MaterialTheme must be provided")
+        .get()
+        .color_scheme;
+    let tap_recognizer = remember(TapRecognizer::default);
+    let mut base = Modifier::new()
+        .fill_max_width()
+        .height(row_height)
+        .semantics(SemanticsArgs {
+            role: Some(Role::Row),
+            ..Default::default()
+        });
+    if selected {
+        base = base.background(scheme.secondary_container);
+    }
+    let modifier = with_pointer_input(base, move |mut input| {
+        let is_inside = input
+            .cursor_position_rel
+            .is_some_and(|position| is_position_inside_bounds(input.computed_data, position));
+        let tap = tap_recognizer.with_mut(|recognizer| {
+            recognizer.update(
+                input.pass,
+                input.pointer_changes.as_mut_slice(),
+                input.cursor_position_rel,
+                is_inside,
+            )
+        });
+        if !tap.tapped {
+            return;
+        }
+        let extend = input.key_modifiers.shift_key();
+        let toggle = input.key_modifiers.control_key() || input.key_modifiers.super_key();
+        let changed = controller
+            .with_mut(|controller| controller.click_row(row_index, selection_mode, extend, toggle));
+        if changed && let Some(on_selection_change) = on_selection_change {
+            on_selection_change.call(controller.with(DataTableController::selected_rows));
+        }
+    });
+
+    column()
+        .modifier(Modifier::new().fill_max_width())
+        .children(move || {
+            let cells = cells.clone();
+            row()
+                .modifier(modifier.clone())
+                .cross_axis_alignment(CrossAxisAlignment::Center)
+                .children(move || {
+                    for &(column, width) in &cells {
+                        boxed()
+                            .alignment(Alignment::CenterStart)
+                            .modifier(
+                                Modifier::new()
+                                    .constrain(Some(AxisConstraint::exact(width)), None)
+                                    .fill_max_height()
+                                    .padding_symmetric(CELL_HORIZONTAL_PADDING, Dp::ZERO)
+                                    .clip_to_bounds(),
+                            )
+                            .children(move || {
+                                cell.render((row_index, column));
+                            });
+                    }
+                });
+            horizontal_divider();
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use tessera_ui::Px;
+
+    use super::{DataTableController, DataTableSelectionMode, reorder_target};
+
+    #[test]
+    fn shift_and_ctrl_clicks_extend_and_toggle_selection() {
+        let mut controller = DataTableController::new();
+        let mode = DataTableSelectionMode::Multiple;
+        assert!(controller.click_row(2, mode, false, false));
+        assert!(controller.click_row(5, mode, true, false));
+        assert_eq!(controller.selected_rows(), vec![2, 3, 4, 5]);
+
+        assert!(controller.click_row(3, mode, false, true));
+        assert_eq!(controller.selected_rows(), vec![2, 4, 5]);
+
+        assert!(controller.click_row(1, mode, true, true));
+        assert_eq!(controller.selected_rows(), vec![1, 2, 3, 4, 5]);
+
+        assert!(controller.click_row(4, DataTableSelectionMode::Single, true, true));
+        assert_eq!(controller.selected_rows(), vec![4]);
+        assert!(!controller.click_row(0, DataTableSelectionMode::None, false, false));
+    }
+
+    #[test]
+    fn dragged_column_lands_where_its_center_is() {
+        let widths = [Px(100), Px(50), Px(100)];
+        assert_eq!(reorder_target(&widths, 0, Px(40)), 0);
+        assert_eq!(reorder_target(&widths, 0, Px(60)), 1);
+        assert_eq!(reorder_target(&widths, 0, Px(500)), 2);
+        assert_eq!(reorder_target(&widths, 2, Px(-120)), 0);
+    }
+}
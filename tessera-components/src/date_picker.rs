@@ -543,6 +543,7 @@ fn date_picker_inner(args: DatePickerConfig) {
                                         .style(TextStyle {
                                             font_size: typography.title_small.font_size,
                                             line_height: typography.title_small.line_height,
+                                            font_weight: typography.title_small.font_weight,
                                         })
                                         .color(scheme.on_surface_variant);
                                 };
@@ -554,6 +555,7 @@ fn date_picker_inner(args: DatePickerConfig) {
                                         .style(TextStyle {
                                             font_size: typography.headline_small.font_size,
                                             line_height: typography.headline_small.line_height,
+                                            font_weight: typography.headline_small.font_weight,
                                         })
                                         .color(scheme.on_surface);
                                 };
@@ -0,0 +1,1218 @@
+//! Dockable panels arranged in tab groups, splits and floating windows.
+//!
+//! ## Usage
+//!
+//! Lay out the tool windows of editors and other tool-heavy apps.
+use std::sync::Arc;
+
+use tessera_ui::{
+    CallbackWith, Color, ComputedData, Constraint, Dp, MeasurementError, Modifier, Px, PxPosition,
+    RenderSlot, RenderSlotWith, State,
+    accesskit::Role,
+    dnd::{
+        self, DragAndDropModifierExt as _, DragPayload, DragSource, DropEvent, DropOutcome,
+        DropTarget,
+    },
+    gesture::{DragRecognizer, DragSettings, TapRecognizer, TapSettings},
+    key,
+    layout::{LayoutPolicy, LayoutResult, MeasureScope, layout},
+    modifier::CursorModifierExt as _,
+    provide_context, remember, tessera, use_context, winit,
+};
+
+use crate::{
+    alignment::{Alignment, CrossAxisAlignment},
+    boxed::boxed,
+    column::column,
+    icon::icon,
+    material_icons::filled,
+    modifier::{ModifierExt as _, SemanticsArgs, with_pointer_input},
+    painter::Painter,
+    pos_misc::is_position_inside_bounds,
+    row::row,
+    shape_def::Shape,
+    spacer::spacer,
+    split_pane::{SplitAxis, split_pane},
+    surface::{SurfaceStyle, surface},
+    text::text,
+    theme::MaterialTheme,
+};
+
+const TAB_STRIP_HEIGHT: Dp = Dp(36.0);
+const TAB_HORIZONTAL_PADDING: Dp = Dp(12.0);
+const TAB_ICON_SIZE: Dp = Dp(18.0);
+const TAB_ICON_SPACING: Dp = Dp(8.0);
+const MIN_PANE_SIZE: Dp = Dp(120.0);
+const FLOATING_WIDTH: Dp = Dp(320.0);
+const FLOATING_HEIGHT: Dp = Dp(240.0);
+const FLOATING_ELEVATION: Dp = Dp(6.0);
+const FLOATING_GRIP_SIZE: Dp = Dp(20.0);
+/// Where a torn-off tab lands relative to the pointer, so the pointer ends
+/// up over the floating group's tab strip.
+const FLOATING_GRAB_OFFSET: Dp = Dp(24.0);
+const DROP_ZONE_ALPHA: f32 = 0.16;
+
+/// Edge of a tab group a panel is docked against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DockEdge {
+    /// Left of the group.
+    Left,
+    /// Right of the group.
+    Right,
+    /// Above the group.
+    Top,
+    /// Below the group.
+    Bottom,
+}
+
+/// Docked part of a [`dock_area`] layout.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "saveable", derive(serde::Serialize, serde::Deserialize))]
+pub enum DockNode {
+    /// Panels shown as tabs, one at a time.
+    Tabs {
+        /// Keys of the panels, in tab order.
+        panels: Vec<u64>,
+        /// Index of the visible panel.
+        active: usize,
+    },
+    /// Two nodes divided by a draggable handle.
+    Split {
+        /// Direction in which the nodes are placed.
+        axis: SplitAxis,
+        /// Share of the space given to `first`.
+        ratio: f32,
+        /// Left or top node.
+        first: Box<DockNode>,
+        /// Right or bottom node.
+        second: Box<DockNode>,
+    },
+}
+
+impl DockNode {
+    /// Creates a tab group showing its first panel.
+    pub fn tabs(panels: impl IntoIterator<Item = u64>) -> Self {
+        Self::Tabs {
+            panels: panels.into_iter().collect(),
+            active: 0,
+        }
+    }
+
+    /// Creates a split giving `first` the `ratio` share of the space.
+    pub fn split(axis: SplitAxis, ratio: f32, first: DockNode, second: DockNode) -> Self {
+        Self::Split {
+            axis,
+            ratio: ratio.clamp(0.0, 1.0),
+            first: Box::new(first),
+            second: Box::new(second),
+        }
+    }
+
+    /// Returns whether a panel is docked in this node.
+    pub fn contains(&self, panel: u64) -> bool {
+        match self {
+            Self::Tabs { panels, .. } => panels.contains(&panel),
+            Self::Split { first, second, .. } => first.contains(panel) || second.contains(panel),
+        }
+    }
+
+    fn first_panel(&self) -> Option<u64> {
+        match self {
+            Self::Tabs { panels, .. } => panels.first().copied(),
+            Self::Split { first, .. } => first.first_panel(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        matches!(self, Self::Tabs { panels, .. } if panels.is_empty())
+    }
+
+    fn panels(&self, out: &mut Vec<u64>) {
+        match self {
+            Self::Tabs { panels, .. } => out.extend(panels),
+            Self::Split { first, second, .. } => {
+                first.panels(out);
+                second.panels(out);
+            }
+        }
+    }
+
+    fn group_mut(&mut self, panel: u64) -> Option<(&mut Vec<u64>, &mut usize)> {
+        match self {
+            Self::Tabs { panels, active } if panels.contains(&panel) => Some((panels, active)),
+            Self::Tabs { .. } => None,
+            Self::Split { first, second, .. } => {
+                if first.contains(panel) {
+                    first.group_mut(panel)
+                } else {
+                    second.group_mut(panel)
+                }
+            }
+        }
+    }
+
+    fn first_group_mut(&mut self) -> (&mut Vec<u64>, &mut usize) {
+        match self {
+            Self::Tabs { panels, active } => (panels, active),
+            Self::Split { first, .. } => first.first_group_mut(),
+        }
+    }
+
+    /// Removes a panel, replacing splits left with an empty side by their
+    /// other side.
+    fn remove(&mut self, panel: u64) -> bool {
+        match self {
+            Self::Tabs { panels, active } => remove_tab(panels, active, panel),
+            Self::Split { first, second, .. } => {
+                if !(first.remove(panel) || second.remove(panel)) {
+                    return false;
+                }
+                if first.is_empty() {
+                    *self = std::mem::take(second.as_mut());
+                } else if second.is_empty() {
+                    *self = std::mem::take(first.as_mut());
+                }
+                true
+            }
+        }
+    }
+
+    /// Splits the group holding `anchor`, placing `panel` at `edge`.
+    fn split_at(&mut self, anchor: u64, panel: u64, edge: DockEdge) -> bool {
+        match self {
+            Self::Tabs { panels, .. } if panels.contains(&anchor) => {
+                let existing = std::mem::take(self);
+                let added = Self::tabs([panel]);
+                *self = match edge {
+                    DockEdge::Left => Self::split(SplitAxis::Horizontal, 0.5, added, existing),
+                    DockEdge::Right => Self::split(SplitAxis::Horizontal, 0.5, existing, added),
+                    DockEdge::Top => Self::split(SplitAxis::Vertical, 0.5, added, existing),
+                    DockEdge::Bottom => Self::split(SplitAxis::Vertical, 0.5, existing, added),
+                };
+                true
+            }
+            Self::Tabs { .. } => false,
+            Self::Split { first, second, .. } => {
+                first.split_at(anchor, panel, edge) || second.split_at(anchor, panel, edge)
+            }
+        }
+    }
+
+    fn set_ratio(&mut self, id: SplitId, new_ratio: f32) {
+        if let Self::Split {
+            ratio,
+            first,
+            second,
+            ..
+        } = self
+        {
+            if split_id(first, second) == id {
+                *ratio = new_ratio;
+            } else {
+                first.set_ratio(id, new_ratio);
+                second.set_ratio(id, new_ratio);
+            }
+        }
+    }
+}
+
+impl Default for DockNode {
+    fn default() -> Self {
+        Self::tabs([])
+    }
+}
+
+/// Identifies a split by the first panel on each side, which stays stable
+/// while panels move elsewhere in the layout.
+type SplitId = (Option<u64>, Option<u64>);
+
+fn split_id(first: &DockNode, second: &DockNode) -> SplitId {
+    (first.first_panel(), second.first_panel())
+}
+
+fn remove_tab(panels: &mut Vec<u64>, active: &mut usize, panel: u64) -> bool {
+    let Some(index) = panels.iter().position(|&key| key == panel) else {
+        return false;
+    };
+    panels.remove(index);
+    if index < *active || *active >= panels.len() {
+        *active = active.saturating_sub(1);
+    }
+    true
+}
+
+/// Tab group floating above the docked panels of a [`dock_area`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "saveable", derive(serde::Serialize, serde::Deserialize))]
+pub struct FloatingDock {
+    panels: Vec<u64>,
+    active: usize,
+    x: f64,
+    y: f64,
+}
+
+impl FloatingDock {
+    /// Returns the keys of the panels, in tab order.
+    pub fn panels(&self) -> &[u64] {
+        &self.panels
+    }
+
+    /// Returns the key of the visible panel.
+    pub fn active_panel(&self) -> Option<u64> {
+        self.panels.get(self.active).copied()
+    }
+
+    /// Returns the offset of the group from the top-left of the dock area.
+    pub fn position(&self) -> (Dp, Dp) {
+        (Dp(self.x), Dp(self.y))
+    }
+}
+
+/// Layout of the panels in a [`dock_area`].
+///
+/// With the `saveable` feature the controller is serializable, so layouts
+/// can also be stored outside of `save_key`.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "saveable", derive(serde::Serialize, serde::Deserialize))]
+pub struct DockController {
+    root: DockNode,
+    floating: Vec<FloatingDock>,
+}
+
+impl DockController {
+    /// Creates a controller with `root` docked and nothing floating.
+    pub fn new(root: DockNode) -> Self {
+        Self {
+            root,
+            floating: Vec::new(),
+        }
+    }
+
+    /// Returns the docked part of the layout.
+    pub fn root(&self) -> &DockNode {
+        &self.root
+    }
+
+    /// Returns the floating tab groups, bottom-most first.
+    pub fn floating(&self) -> &[FloatingDock] {
+        &self.floating
+    }
+
+    /// Returns whether a panel is docked or floating.
+    pub fn contains(&self, panel: u64) -> bool {
+        self.root.contains(panel)
+            || self
+                .floating
+                .iter()
+                .any(|floating| floating.panels.contains(&panel))
+    }
+
+    /// Shows a panel in its tab group.
+    pub fn activate(&mut self, panel: u64) {
+        if let Some((panels, active)) = self.group_mut(panel)
+            && let Some(index) = panels.iter().position(|&key| key == panel)
+        {
+            *active = index;
+        }
+    }
+
+    /// Removes a panel from the layout.
+    pub fn close(&mut self, panel: u64) -> bool {
+        if self.root.remove(panel) {
+            return true;
+        }
+        let Some(index) = self
+            .floating
+            .iter()
+            .position(|floating| floating.panels.contains(&panel))
+        else {
+            return false;
+        };
+        let floating = &mut self.floating[index];
+        remove_tab(&mut floating.panels, &mut floating.active, panel);
+        if floating.panels.is_empty() {
+            self.floating.remove(index);
+        }
+        true
+    }
+
+    /// Moves a panel into the tab group of `beside`, or next to that group
+    /// when `edge` is set.
+    ///
+    /// Floating groups cannot be split, so an edge next to a floating panel
+    /// adds a tab instead.
+    pub fn dock(&mut self, panel: u64, beside: u64, edge: Option<DockEdge>) {
+        let beside = if panel == beside {
+            // Splitting a panel off its own group needs another panel of that
+            // group to anchor the split.
+            let other = edge
+                .and_then(|_| self.group_mut(panel))
+                .and_then(|(panels, _)| panels.iter().copied().find(|&key| key != panel));
+            let Some(other) = other else {
+                return;
+            };
+            other
+        } else {
+            beside
+        };
+        if !self.contains(beside) {
+            return;
+        }
+        self.close(panel);
+        if let Some(edge) = edge
+            && self.root.split_at(beside, panel, edge)
+        {
+            return;
+        }
+        if let Some((panels, active)) = self.group_mut(beside) {
+            panels.push(panel);
+            *active = panels.len() - 1;
+        }
+    }
+
+    /// Moves a panel into a new floating group at the given offset from the
+    /// top-left of the dock area.
+    pub fn float(&mut self, panel: u64, x: Dp, y: Dp) {
+        self.close(panel);
+        self.floating.push(FloatingDock {
+            panels: vec![panel],
+            active: 0,
+            x: x.0.max(0.0),
+            y: y.0.max(0.0),
+        });
+    }
+
+    fn group_mut(&mut self, panel: u64) -> Option<(&mut Vec<u64>, &mut usize)> {
+        if self.root.contains(panel) {
+            return self.root.group_mut(panel);
+        }
+        self.floating
+            .iter_mut()
+            .find(|floating| floating.panels.contains(&panel))
+            .map(|floating| (&mut floating.panels, &mut floating.active))
+    }
+
+    fn drop_panel(&mut self, panel: u64, anchor: Option<u64>, edge: Option<DockEdge>) {
+        match anchor {
+            Some(anchor) => self.dock(panel, anchor, edge),
+            // Only an empty root has no anchor.
+            None => {
+                self.close(panel);
+                self.root = DockNode::tabs([panel]);
+            }
+        }
+    }
+
+    fn move_floating(&mut self, panel: u64, dx: Dp, dy: Dp) {
+        if let Some(floating) = self
+            .floating
+            .iter_mut()
+            .find(|floating| floating.panels.contains(&panel))
+        {
+            floating.x = (floating.x + dx.0).max(0.0);
+            floating.y = (floating.y + dy.0).max(0.0);
+        }
+    }
+
+    fn raise(&mut self, panel: u64) {
+        if let Some(index) = self
+            .floating
+            .iter()
+            .position(|floating| floating.panels.contains(&panel))
+            && index + 1 < self.floating.len()
+        {
+            let floating = self.floating.remove(index);
+            self.floating.push(floating);
+        }
+    }
+
+    /// Drops panels that are no longer provided and docks new ones into the
+    /// first tab group.
+    fn sync_panels(&mut self, keys: &[u64]) {
+        let mut present = Vec::new();
+        self.root.panels(&mut present);
+        for floating in &self.floating {
+            present.extend(&floating.panels);
+        }
+        for &panel in &present {
+            if !keys.contains(&panel) {
+                self.close(panel);
+            }
+        }
+        let (panels, _) = self.root.first_group_mut();
+        for &key in keys {
+            if !present.contains(&key) {
+                panels.push(key);
+            }
+        }
+    }
+
+    fn matches(&self, keys: &[u64]) -> bool {
+        let mut present = Vec::new();
+        self.root.panels(&mut present);
+        for floating in &self.floating {
+            present.extend(&floating.panels);
+        }
+        present.len() == keys.len() && keys.iter().all(|key| present.contains(key))
+    }
+}
+
+/// Panel shown by a [`dock_area`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct DockPanel {
+    key: u64,
+    title: String,
+    icon: Option<Painter>,
+    closable: bool,
+}
+
+impl DockPanel {
+    /// Creates a closable panel identified by `key`.
+    pub fn new(key: u64, title: impl Into<String>) -> Self {
+        Self {
+            key,
+            title: title.into(),
+            icon: None,
+            closable: true,
+        }
+    }
+
+    /// Sets the icon shown before the title on the panel's tab.
+    pub fn icon(mut self, icon: impl Into<Painter>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Sets whether the panel's tab shows a close button.
+    pub fn closable(mut self, closable: bool) -> Self {
+        self.closable = closable;
+        self
+    }
+}
+
+/// Payload of a dragged tab.
+#[derive(Clone, Copy)]
+struct DockTabDrag(u64);
+
+#[derive(Clone)]
+struct DockContext {
+    controller: State<DockController>,
+    panels: Arc<Vec<DockPanel>>,
+    content: RenderSlotWith<u64>,
+    on_close: Option<CallbackWith<u64>>,
+    /// Last pointer position of a tab drag, relative to the dock area.
+    drag_position: State<Option<PxPosition>>,
+}
+
+impl PartialEq for DockContext {
+    fn eq(&self, other: &Self) -> bool {
+        self.controller == other.controller
+            && Arc::ptr_eq(&self.panels, &other.panels)
+            && self.content == other.content
+            && self.on_close == other.on_close
+            && self.drag_position == other.drag_position
+    }
+}
+
+impl DockContext {
+    fn panel(&self, key: u64) -> Option<DockPanel> {
+        self.panels.iter().find(|panel| panel.key == key).cloned()
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct DockAreaLayout {
+    floating: Vec<(Px, Px)>,
+}
+
+impl LayoutPolicy for DockAreaLayout {
+    fn measure(&self, input: &MeasureScope<'_>) -> Result<LayoutResult, MeasurementError> {
+        let parent = input.parent_constraint();
+        let width = parent
+            .width()
+            .resolve_max()
+            .expect("dock_area requires a bounded width");
+        let height = parent
+            .height()
+            .resolve_max()
+            .expect("dock_area requires a bounded height");
+        let children = input.children();
+        let mut result = LayoutResult::default();
+        let root = children
+            .first()
+            .copied()
+            .expect("docked content should exist");
+        let size = root.measure(&Constraint::exact(width, height))?.size();
+        result.place_child(root, PxPosition::ZERO);
+        for (child, &(x, y)) in children.iter().skip(1).zip(&self.floating) {
+            let floating = child.measure(&parent.without_min())?.size();
+            // Keep floating groups reachable when the area shrinks.
+            let x = x.min(width - floating.width).max(Px::ZERO);
+            let y = y.min(height - floating.height).max(Px::ZERO);
+            result.place_child(*child, PxPosition::new(x, y));
+        }
+        Ok(result.with_size(size))
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct DropZonesLayout {
+    edges: bool,
+}
+
+impl LayoutPolicy for DropZonesLayout {
+    fn measure(&self, input: &MeasureScope<'_>) -> Result<LayoutResult, MeasurementError> {
+        let parent = input.parent_constraint();
+        let width = parent.width().resolve_max().unwrap_or(Px::ZERO);
+        let height = parent.height().resolve_max().unwrap_or(Px::ZERO);
+        let (quarter_w, quarter_h) = (Px(width.0 / 4), Px(height.0 / 4));
+        let middle_w = width - quarter_w * 2;
+        // Center first, then the edge strips along the outer quarters.
+        let zones = if self.edges {
+            vec![
+                (quarter_w, quarter_h, middle_w, height - quarter_h * 2),
+                (Px::ZERO, Px::ZERO, quarter_w, height),
+                (width - quarter_w, Px::ZERO, quarter_w, height),
+                (quarter_w, Px::ZERO, middle_w, quarter_h),
+                (quarter_w, height - quarter_h, middle_w, quarter_h),
+            ]
+        } else {
+            vec![(Px::ZERO, Px::ZERO, width, height)]
+        };
+        let mut result = LayoutResult::default();
+        for (child, (x, y, zone_width, zone_height)) in input.children().iter().zip(zones) {
+            child.measure(&Constraint::exact(zone_width, zone_height))?;
+            result.place_child(*child, PxPosition::new(x, y));
+        }
+        Ok(result.with_size(ComputedData { width, height }))
+    }
+}
+
+/// # dock_area
+///
+/// Arranges panels in tab groups that can be split, rearranged and floated.
+///
+/// Dragging a tab onto another group adds it to that group, and dropping it on
+/// a group's edge splits the group. Tabs dropped outside of any group float
+/// above the docked panels; drag a floating group by its grip. With a
+/// `save_key` and the `saveable` feature, the layout is restored after the app
+/// restarts.
+///
+/// Wrap the app in [`crate::drag_preview::drag_preview_host`] to show the
+/// dragged tab under the pointer. Floating groups are drawn inside the dock
+/// area, as tessera renders a single window.
+///
+/// ## Usage
+///
+/// Lay out the tool windows of an editor.
+///
+/// ## Parameters
+///
+/// - `panels` — panels that can be shown; panels missing from the layout are
+///   added to the first tab group
+/// - `content` — renders the content of the panel with the given key
+/// - `initial_layout` — optional docked layout used before any change; defaults
+///   to every panel in one tab group
+/// - `on_close` — optional callback invoked with the key of a closed panel
+/// - `save_key` — optional key the layout is saved under; requires the
+///   `saveable` feature and is ignored without it
+/// - `controller` — optional controller holding the layout
+/// - `modifier` — optional modifier chain applied to the dock area
+///
+/// ## Examples
+///
+/// ```
+/// # use tessera_ui::tessera;
+/// # #[tessera]
+/// # fn component() {
+/// use tessera_components::{
+///     dock::{DockNode, DockPanel, dock_area},
+///     split_pane::SplitAxis,
+///     text::text,
+/// };
+/// # use tessera_components::theme::{MaterialTheme, material_theme};
+///
+/// # material_theme()
+/// #     .theme(|| MaterialTheme::default())
+/// #     .child(|| {
+/// dock_area(
+///     vec![
+///         DockPanel::new(1, "Explorer"),
+///         DockPanel::new(2, "Editor").closable(false),
+///         DockPanel::new(3, "Terminal"),
+///     ],
+///     |key: u64| {
+///         text().content(format!("Panel {key}"));
+///     },
+/// )
+/// .initial_layout(DockNode::split(
+///     SplitAxis::Horizontal,
+///     0.25,
+///     DockNode::tabs([1]),
+///     DockNode::split(
+///         SplitAxis::Vertical,
+///         0.7,
+///         DockNode::tabs([2]),
+///         DockNode::tabs([3]),
+///     ),
+/// ))
+/// .save_key("editor.dock");
+/// # });
+/// # }
+/// # component();
+/// ```
+#[tessera]
+pub fn dock_area(
+    panels: Vec<DockPanel>,
+    #[prop(render_slot_with)] content: RenderSlotWith<u64>,
+    initial_layout: Option<DockNode>,
+    on_close: Option<CallbackWith<u64>>,
+    #[prop(into)] save_key: Option<String>,
+    controller: Option<State<DockController>>,
+    modifier: Option<Modifier>,
+) {
+    let keys: Vec<u64> = panels.iter().map(|panel| panel.key).collect();
+    let controller = controller.unwrap_or_else(|| {
+        remember_controller(save_key.as_deref(), || {
+            DockController::new(initial_layout.unwrap_or_else(|| DockNode::tabs(keys.clone())))
+        })
+    });
+    if controller.with(|controller| !controller.matches(&keys)) {
+        controller.with_mut(|controller| controller.sync_panels(&keys));
+    }
+    let tab_dragging = remember(|| false);
+    let drag_position = remember(|| None::<PxPosition>);
+    let context = DockContext {
+        controller,
+        panels: Arc::new(panels),
+        content,
+        on_close,
+        drag_position,
+    };
+
+    let modifier = with_pointer_input(modifier.unwrap_or_default(), move |input| {
+        let dragging = dnd::current_payload().is_some_and(|payload| payload.is::<DockTabDrag>());
+        if tab_dragging.get() != dragging {
+            tab_dragging.set(dragging);
+        }
+        if dragging
+            && let Some(preview) = dnd::drag_preview()
+            && let (Some(abs), Some(rel)) = (input.cursor_position_abs(), input.cursor_position_rel)
+        {
+            drag_position.set(Some(preview.position - (abs - rel)));
+        }
+    });
+    let (root, floating) =
+        controller.with(|controller| (controller.root.clone(), controller.floating.clone()));
+    let positions = floating
+        .iter()
+        .map(|floating| (Dp(floating.x).to_px(), Dp(floating.y).to_px()))
+        .collect();
+    let dragging = tab_dragging.get();
+
+    layout()
+        .modifier(modifier)
+        .layout_policy(DockAreaLayout {
+            floating: positions,
+        })
+        .child(move || {
+            dock_node(&root, dragging, &context);
+            for floating in &floating {
+                let floating = floating.clone();
+                let context = context.clone();
+                key(floating.panels.first().copied(), move || {
+                    floating_dock(floating.clone(), dragging, context.clone());
+                });
+            }
+        });
+}
+
+#[cfg(feature = "saveable")]
+fn remember_controller(
+    save_key: Option<&str>,
+    init: impl FnOnce() -> DockController,
+) -> State<DockController> {
+    match save_key {
+        Some(key) => tessera_ui::saveable::remember_saveable(key, init),
+        None => remember(init),
+    }
+}
+
+#[cfg(not(feature = "saveable"))]
+fn remember_controller(
+    _save_key: Option<&str>,
+    init: impl FnOnce() -> DockController,
+) -> State<DockController> {
+    remember(init)
+}
+
+fn dock_node(node: &DockNode, dragging: bool, context: &DockContext) {
+    match node {
+        DockNode::Tabs { panels, active } => {
+            let panels = panels.clone();
+            let active = *active;
+            let context = context.clone();
+            key(panels.first().copied(), move || {
+                dock_group(panels.clone(), active, false, dragging, context.clone());
+            });
+        }
+        DockNode::Split {
+            axis,
+            ratio,
+            first,
+            second,
+        } => {
+            let id = split_id(first, second);
+            let (axis, ratio) = (*axis, *ratio);
+            let (first, second) = (first.as_ref().clone(), second.as_ref().clone());
+            let context = context.clone();
+            key(id, move || {
+                let controller = context.controller;
+                let (first, second) = (first.clone(), second.clone());
+                let (first_context, second_context) = (context.clone(), context.clone());
+                split_pane(
+                    move || dock_node(&first, dragging, &first_context),
+                    move || dock_node(&second, dragging, &second_context),
+                )
+                .modifier(Modifier::new().fill_max_size())
+                .axis(axis)
+                .initial_ratio(ratio)
+                .min_first_size(MIN_PANE_SIZE)
+                .min_second_size(MIN_PANE_SIZE)
+                .on_resize(move |ratio: f32| {
+                    controller.with_mut(|controller| controller.root.set_ratio(id, ratio));
+                });
+            });
+        }
+    }
+}
+
+#[tessera]
+fn floating_dock(floating: FloatingDock, dragging: bool, context: DockContext) {
+    let shape = Shape::rounded_rectangle(Dp(12.0));
+    let panels = floating.panels.clone();
+    let active = floating.active;
+    surface()
+        .style(SurfaceStyle::Filled {
+            color: use_context::<MaterialTheme>()
+                .expect("MaterialTheme must be provided")
+                .get()
+                .color_scheme
+                .surface_container_high,
+        })
+        .shape(shape)
+        .elevation(FLOATING_ELEVATION)
+        .block_input(true)
+        .modifier(
+            Modifier::new()
+                .size(FLOATING_WIDTH, FLOATING_HEIGHT)
+                .clip_to_bounds(),
+        )
+        .child(move || {
+            dock_group(panels.clone(), active, true, dragging, context.clone());
+        });
+}
+
+#[tessera]
+fn dock_group(
+    panels: Vec<u64>,
+    active: usize,
+    floating: bool,
+    dragging: bool,
+    context: DockContext,
+) {
+    let scheme = use_context::<MaterialTheme>()
+        .expect("MaterialTheme must be provided")
+        .get()
+        .color_scheme;
+    // An empty group is only ever the root, which drops dock into directly.
+    let anchor = panels.first().copied();
+    let active_panel = panels.get(active).copied();
+    let controller = context.controller;
+    let strip = Modifier::new()
+        .fill_max_width()
+        .height(TAB_STRIP_HEIGHT)
+        .background(scheme.surface_container)
+        .semantics(SemanticsArgs {
+            role: Some(Role::TabList),
+            ..Default::default()
+        })
+        .drop_target(tab_drop_target(anchor, None, controller, None));
+
+    column()
+        .modifier(Modifier::new().fill_max_size())
+        .children(move || {
+            let panels = panels.clone();
+            let context = context.clone();
+            row()
+                .modifier(strip.clone())
+                .cross_axis_alignment(CrossAxisAlignment::Center)
+                .children(move || {
+                    if floating && let Some(anchor) = anchor {
+                        floating_grip(anchor, context.controller);
+                    }
+                    for &panel_key in &panels {
+                        let Some(panel) = context.panel(panel_key) else {
+                            continue;
+                        };
+                        let active = Some(panel_key) == active_panel;
+                        let context = context.clone();
+                        key(panel_key, move || {
+                            dock_tab(panel.clone(), active, context.clone());
+                        });
+                    }
+                });
+            let context = context.clone();
+            boxed()
+                .modifier(
+                    Modifier::new()
+                        .fill_max_width()
+                        .weight(1.0)
+                        .clip_to_bounds(),
+                )
+                .children(move || {
+                    if let Some(panel) = active_panel {
+                        let content = context.content;
+                        key(panel, move || {
+                            content.render(panel);
+                        });
+                    }
+                    if dragging {
+                        drop_zones(anchor, !floating, context.controller);
+                    }
+                });
+        });
+}
+
+#[tessera]
+fn dock_tab(panel: DockPanel, active: bool, context: DockContext) {
+    let theme = use_context::<MaterialTheme>()
+        .expect("MaterialTheme must be provided")
+        .get();
+    let scheme = theme.color_scheme;
+    let key = panel.key;
+    let controller = context.controller;
+    let drag_position = context.drag_position;
+    let tap_recognizer = remember(TapRecognizer::default);
+    let title = panel.title.clone();
+    let preview_title = title.clone();
+    let source = DragSource::new(move |()| Some(DragPayload::new(DockTabDrag(key))))
+        .preview(RenderSlot::new(move || {
+            tab_drag_preview(preview_title.clone());
+        }))
+        .on_drag_end(move |outcome: DropOutcome| {
+            if outcome == DropOutcome::Canceled
+                && let Some(position) = drag_position.get()
+            {
+                let x = Dp::from(position.x).0 - FLOATING_GRAB_OFFSET.0;
+                let y = Dp::from(position.y).0 - FLOATING_GRAB_OFFSET.0;
+                controller.with_mut(|controller| controller.float(key, Dp(x), Dp(y)));
+            }
+        });
+
+    let mut base = Modifier::new()
+        .height(TAB_STRIP_HEIGHT)
+        .padding_symmetric(TAB_HORIZONTAL_PADDING, Dp::ZERO)
+        .semantics(SemanticsArgs {
+            role: Some(Role::Tab),
+            label: Some(title.clone()),
+            ..Default::default()
+        })
+        .drag_source(source);
+    if active {
+        base = base.background(scheme.surface);
+    }
+    let modifier = with_pointer_input(base, move |mut input| {
+        let is_inside = input
+            .cursor_position_rel
+            .is_some_and(|position| is_position_inside_bounds(input.computed_data, position));
+        let tap = tap_recognizer.with_mut(|recognizer| {
+            recognizer.update(
+                input.pass,
+                input.pointer_changes.as_mut_slice(),
+                input.cursor_position_rel,
+                is_inside,
+            )
+        });
+        if tap.tapped {
+            controller.with_mut(|controller| {
+                controller.activate(key);
+                controller.raise(key);
+            });
+        }
+    });
+    let content_color = if active {
+        scheme.on_surface
+    } else {
+        scheme.on_surface_variant
+    };
+
+    row()
+        .modifier(modifier)
+        .cross_axis_alignment(CrossAxisAlignment::Center)
+        .children(move || {
+            if let Some(painter) = panel.icon.clone() {
+                icon()
+                    .painter(painter)
+                    .size(TAB_ICON_SIZE)
+                    .tint(content_color);
+                spacer().modifier(Modifier::new().width(TAB_ICON_SPACING));
+            }
+            let title = title.clone();
+            provide_context(
+                || theme.typography.title_small,
+                move || {
+                    text()
+                        .content(title.clone())
+                        .color(content_color)
+                        .max_lines(1);
+                },
+            );
+            if panel.closable {
+                spacer().modifier(Modifier::new().width(TAB_ICON_SPACING));
+                close_tab_button(key, content_color, context.clone());
+            }
+        });
+}
+
+#[tessera]
+fn close_tab_button(key: u64, tint: Color, context: DockContext) {
+    let tap_recognizer = remember(|| {
+        // Consumed on press so closing a tab never starts dragging it.
+        TapRecognizer::new(TapSettings {
+            consume_on_press: true,
+            consume_on_release: true,
+            consume_on_tap: true,
+            ..Default::default()
+        })
+    });
+    let modifier = with_pointer_input(
+        Modifier::new()
+            .size(TAB_ICON_SIZE, TAB_ICON_SIZE)
+            .hover_cursor_icon(winit::window::CursorIcon::Pointer)
+            .semantics(SemanticsArgs {
+                role: Some(Role::Button),
+                label: Some("Close".to_string()),
+                ..Default::default()
+            }),
+        move |mut input| {
+            let is_inside = input
+                .cursor_position_rel
+                .is_some_and(|position| is_position_inside_bounds(input.computed_data, position));
+            let tap = tap_recognizer.with_mut(|recognizer| {
+                recognizer.update(
+                    input.pass,
+                    input.pointer_changes.as_mut_slice(),
+                    input.cursor_position_rel,
+                    is_inside,
+                )
+            });
+            if tap.tapped
+                && context
+                    .controller
+                    .with_mut(|controller| controller.close(key))
+                && let Some(on_close) = context.on_close
+            {
+                on_close.call(key);
+            }
+        },
+    );
+    boxed().modifier(modifier).children(move || {
+        icon()
+            .painter(filled::CLOSE_SVG)
+            .size(TAB_ICON_SIZE)
+            .tint(tint);
+    });
+}
+
+#[tessera]
+fn tab_drag_preview(title: String) {
+    let theme = use_context::<MaterialTheme>()
+        .expect("MaterialTheme must be provided")
+        .get();
+    surface()
+        .style(SurfaceStyle::Filled {
+            color: theme.color_scheme.surface_container_highest,
+        })
+        .shape(Shape::rounded_rectangle(Dp(8.0)))
+        .elevation(FLOATING_ELEVATION)
+        .modifier(
+            Modifier::new()
+                .height(TAB_STRIP_HEIGHT)
+                .padding_symmetric(TAB_HORIZONTAL_PADDING, Dp::ZERO),
+        )
+        .content_alignment(Alignment::Center)
+        .child(move || {
+            let title = title.clone();
+            provide_context(
+                || theme.typography.title_small,
+                move || {
+                    text().content(title.clone()).max_lines(1);
+                },
+            );
+        });
+}
+
+#[tessera]
+fn floating_grip(anchor: u64, controller: State<DockController>) {
+    let tint = use_context::<MaterialTheme>()
+        .expect("MaterialTheme must be provided")
+        .get()
+        .color_scheme
+        .on_surface_variant;
+    let drag_recognizer = remember(|| DragRecognizer::new(DragSettings::default()));
+    let modifier = with_pointer_input(
+        Modifier::new()
+            .padding_symmetric(TAB_ICON_SPACING, Dp::ZERO)
+            .hover_cursor_icon(winit::window::CursorIcon::Move),
+        move |mut input| {
+            let is_inside = input
+                .cursor_position_rel
+                .is_some_and(|position| is_position_inside_bounds(input.computed_data, position));
+            // The grip moves with its group, so deltas are taken in window
+            // coordinates.
+            let cursor = input.cursor_position_abs();
+            let drag = drag_recognizer.with_mut(|recognizer| {
+                recognizer.update(
+                    input.pass,
+                    input.pointer_changes.as_mut_slice(),
+                    cursor,
+                    is_inside,
+                )
+            });
+            if drag.started {
+                controller.with_mut(|controller| controller.raise(anchor));
+            }
+            if drag.updated {
+                controller.with_mut(|controller| {
+                    controller.move_floating(anchor, drag.delta_x.into(), drag.delta_y.into());
+                });
+            }
+        },
+    );
+    boxed().modifier(modifier).children(move || {
+        icon()
+            .painter(filled::DRAG_INDICATOR_SVG)
+            .size(FLOATING_GRIP_SIZE)
+            .tint(tint);
+    });
+}
+
+#[tessera]
+fn drop_zones(anchor: Option<u64>, edges: bool, controller: State<DockController>) {
+    layout()
+        .modifier(Modifier::new().fill_max_size())
+        .layout_policy(DropZonesLayout { edges })
+        .child(move || {
+            drop_zone(anchor, None, controller);
+            if edges {
+                for edge in [
+                    DockEdge::Left,
+                    DockEdge::Right,
+                    DockEdge::Top,
+                    DockEdge::Bottom,
+                ] {
+                    drop_zone(anchor, Some(edge), controller);
+                }
+            }
+        });
+}
+
+#[tessera]
+fn drop_zone(anchor: Option<u64>, edge: Option<DockEdge>, controller: State<DockController>) {
+    let highlight = use_context::<MaterialTheme>()
+        .expect("MaterialTheme must be provided")
+        .get()
+        .color_scheme
+        .primary
+        .with_alpha(DROP_ZONE_ALPHA);
+    let hovered = remember(|| false);
+    let mut modifier = Modifier::new().fill_max_size().drop_target(tab_drop_target(
+        anchor,
+        edge,
+        controller,
+        Some(hovered),
+    ));
+    if hovered.get() {
+        modifier = modifier.background(highlight);
+    }
+    spacer().modifier(modifier);
+}
+
+fn tab_drop_target(
+    anchor: Option<u64>,
+    edge: Option<DockEdge>,
+    controller: State<DockController>,
+    hovered: Option<State<bool>>,
+) -> DropTarget {
+    let target = DropTarget::new(move |event: DropEvent| {
+        let Some(&DockTabDrag(panel)) = event.payload.get::<DockTabDrag>() else {
+            return false;
+        };
+        controller.with_mut(|controller| controller.drop_panel(panel, anchor, edge));
+        true
+    })
+    .accepts(|payload: DragPayload| payload.is::<DockTabDrag>());
+    match hovered {
+        Some(hovered) => target.on_hover_changed(move |is_hovered: bool| hovered.set(is_hovered)),
+        None => target,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tessera_ui::Dp;
+
+    use super::{DockController, DockEdge, DockNode, SplitAxis};
+
+    #[test]
+    fn docking_at_an_edge_splits_and_emptied_splits_collapse() {
+        let mut controller = DockController::new(DockNode::tabs([1, 2]));
+        controller.dock(2, 1, Some(DockEdge::Right));
+        assert_eq!(
+            controller.root(),
+            &DockNode::split(
+                SplitAxis::Horizontal,
+                0.5,
+                DockNode::tabs([1]),
+                DockNode::tabs([2]),
+            )
+        );
+
+        controller.dock(2, 1, None);
+        assert_eq!(
+            controller.root(),
+            &DockNode::Tabs {
+                panels: vec![1, 2],
+                active: 1,
+            }
+        );
+
+        // A lone panel cannot be split off its own group.
+        let mut lone = DockController::new(DockNode::tabs([1]));
+        lone.dock(1, 1, Some(DockEdge::Left));
+        assert_eq!(lone.root(), &DockNode::tabs([1]));
+    }
+
+    #[test]
+    fn floating_panels_leave_and_rejoin_the_docked_layout() {
+        let mut controller = DockController::new(DockNode::split(
+            SplitAxis::Vertical,
+            0.5,
+            DockNode::tabs([1]),
+            DockNode::tabs([2]),
+        ));
+        controller.float(2, Dp(40.0), Dp(-10.0));
+        assert_eq!(controller.root(), &DockNode::tabs([1]));
+        assert_eq!(controller.floating()[0].panels(), &[2]);
+        assert_eq!(controller.floating()[0].position(), (Dp(40.0), Dp(0.0)));
+
+        controller.dock(1, 2, None);
+        assert!(!controller.root().contains(1) && controller.contains(1));
+        assert_eq!(controller.floating()[0].active_panel(), Some(1));
+
+        controller.close(1);
+        controller.close(2);
+        assert!(controller.floating().is_empty());
+    }
+}
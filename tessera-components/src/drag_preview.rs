@@ -0,0 +1,101 @@
+//! Overlay host for drag-and-drop previews.
+//!
+//! ## Usage
+//!
+//! Wrap app content so dragged items render above the tree at the pointer.
+use tessera_ui::{
+    MeasurementError, Modifier, Px, PxPosition, RenderSlot,
+    dnd::{self, DragPreview},
+    layout::{LayoutPolicy, LayoutResult, MeasureScope, layout},
+    remember, tessera,
+};
+
+use crate::modifier::with_pointer_input;
+
+#[derive(Clone, PartialEq)]
+struct DragPreviewLayout {
+    position: Option<PxPosition>,
+}
+
+impl LayoutPolicy for DragPreviewLayout {
+    fn measure(&self, input: &MeasureScope<'_>) -> Result<LayoutResult, MeasurementError> {
+        let mut result = LayoutResult::default();
+        let children = input.children();
+        let child_constraint = input.parent_constraint().without_min();
+        let main_content = children
+            .first()
+            .copied()
+            .expect("main content should exist");
+        let main_size = main_content.measure(&child_constraint)?;
+        result.place_child(main_content, PxPosition::new(Px::ZERO, Px::ZERO));
+
+        if let (Some(preview), Some(position)) = (children.get(1).copied(), self.position) {
+            preview.measure(&child_constraint)?;
+            result.place_child(preview, position);
+        }
+
+        Ok(result.with_size(main_size.size()))
+    }
+}
+
+/// # drag_preview_host
+///
+/// Renders the preview of the active drag session above its content.
+///
+/// ## Usage
+///
+/// Wrap the app root once so drag sources created with
+/// [`tessera_ui::dnd::DragSource::preview`] show their preview under the
+/// pointer while dragging.
+///
+/// ## Parameters
+///
+/// - `modifier` — optional modifier chain applied to the host
+/// - `content` — optional content rendered below the preview
+///
+/// ## Examples
+///
+/// ```
+/// # use tessera_ui::tessera;
+/// # #[tessera]
+/// # fn component() {
+/// use tessera_components::{drag_preview::drag_preview_host, text::text};
+///
+/// drag_preview_host().content(|| {
+///     text().content("Drag items here");
+/// });
+/// assert!(!tessera_ui::dnd::is_dragging());
+/// # }
+/// # component();
+/// ```
+#[tessera]
+pub fn drag_preview_host(modifier: Option<Modifier>, content: Option<RenderSlot>) {
+    let content = content.unwrap_or_else(RenderSlot::empty);
+    let preview = remember(|| None::<DragPreview>);
+    let current = preview.get();
+
+    let modifier = with_pointer_input(modifier.unwrap_or_default(), move |input| {
+        let latest = dnd::drag_preview().map(|mut latest| {
+            if let (Some(abs), Some(rel)) = (input.cursor_position_abs(), input.cursor_position_rel)
+            {
+                latest.position = latest.position - (abs - rel);
+            }
+            latest
+        });
+        if preview.with(|preview| *preview != latest) {
+            preview.set(latest);
+        }
+    });
+
+    layout()
+        .modifier(modifier)
+        .layout_policy(DragPreviewLayout {
+            position: current.map(|preview| preview.position),
+        })
+        .child(move || {
+            content.render();
+            if let Some(preview) = current {
+                preview.content.render();
+            }
+        });
+}
@@ -0,0 +1,604 @@
+//! Text fields that pick one or more values from a dropdown menu.
+//!
+//! ## Usage
+//!
+//! Choose a country, category, assignees or other values from a known list.
+use std::sync::Arc;
+
+use tessera_ui::{
+    CallbackWith, Color, Dp, Modifier, RenderSlot, State,
+    gesture::{TapRecognizer, TapSettings},
+    remember, tessera, use_context, winit,
+};
+
+use crate::{
+    boxed::boxed,
+    chip::chip,
+    column::column,
+    flow_row::flow_row,
+    icon::icon,
+    lazy_list::{LazyListController, lazy_column},
+    material_icons::filled,
+    menus::menu_item,
+    modifier::{ModifierExt as _, with_keyboard_preview_input, with_pointer_preview_input},
+    painter::Painter,
+    popover::popover,
+    pos_misc::is_position_inside_bounds,
+    shape_def::Shape,
+    surface::{SurfaceStyle, surface},
+    text::text,
+    text_edit_core::TextSelection,
+    text_field::{TextFieldDefaults, text_field},
+    text_input::TextInputController,
+    theme::{MaterialAlpha, MaterialTheme},
+};
+
+const OPTION_HEIGHT: Dp = Dp(48.0);
+const MAX_VISIBLE_OPTIONS: usize = 6;
+const MENU_ELEVATION: Dp = Dp(3.0);
+const CHIP_SPACING: Dp = Dp(8.0);
+const SUPPORTING_TEXT_PADDING: Dp = Dp(16.0);
+
+/// How many options an [`exposed_dropdown_menu`] can select.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ExposedDropdownMenuSelectionMode {
+    /// Picking an option replaces the selection and closes the menu.
+    #[default]
+    Single,
+    /// Picking an option toggles it and keeps the menu open; selected
+    /// options show as chips below the field.
+    Multiple,
+}
+
+/// Selection, open state and validation state of an
+/// [`exposed_dropdown_menu`].
+///
+/// Options are identified by their index.
+pub struct ExposedDropdownMenuController {
+    selected: Vec<usize>,
+    expanded: bool,
+    query: String,
+    /// Position of the highlighted option among the options matching the
+    /// query.
+    highlighted: Option<usize>,
+    touched: bool,
+    error: Option<String>,
+}
+
+impl ExposedDropdownMenuController {
+    /// Creates a controller with nothing selected.
+    pub fn new() -> Self {
+        Self::with_selection(Vec::new())
+    }
+
+    /// Creates a controller with the options at `selected` selected.
+    pub fn with_selection(selected: Vec<usize>) -> Self {
+        Self {
+            selected,
+            expanded: false,
+            query: String::new(),
+            highlighted: None,
+            touched: false,
+            error: None,
+        }
+    }
+
+    /// Returns the indices of the selected options in selection order.
+    pub fn selected(&self) -> &[usize] {
+        &self.selected
+    }
+
+    /// Replaces the selection.
+    pub fn set_selected(&mut self, selected: Vec<usize>) {
+        self.selected = selected;
+    }
+
+    /// Returns whether the menu is open.
+    pub fn is_expanded(&self) -> bool {
+        self.expanded
+    }
+
+    /// Opens the menu.
+    pub fn expand(&mut self) {
+        self.expanded = true;
+    }
+
+    /// Closes the menu and clears the search query.
+    pub fn collapse(&mut self) {
+        if self.expanded {
+            // Leaving the menu counts as visiting the field, like blurring
+            // a text field.
+            self.touched = true;
+        }
+        self.expanded = false;
+        self.query.clear();
+        self.highlighted = None;
+    }
+
+    /// Returns the validation error shown under the field, if any.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref().filter(|_| self.touched)
+    }
+
+    /// Shows validation errors even if the field was never visited and
+    /// returns whether the selection is valid.
+    ///
+    /// Call this when submitting a form.
+    pub fn validate(&mut self) -> bool {
+        self.touched = true;
+        self.error.is_none()
+    }
+
+    /// Clears the selection and hides validation errors.
+    pub fn reset(&mut self) {
+        self.collapse();
+        self.selected.clear();
+        self.touched = false;
+    }
+
+    fn pick(&mut self, index: usize, mode: ExposedDropdownMenuSelectionMode) {
+        match mode {
+            ExposedDropdownMenuSelectionMode::Single => {
+                self.selected = vec![index];
+                self.collapse();
+            }
+            ExposedDropdownMenuSelectionMode::Multiple => {
+                if let Some(position) = self.selected.iter().position(|&i| i == index) {
+                    self.selected.remove(position);
+                } else {
+                    self.selected.push(index);
+                }
+                self.touched = true;
+            }
+        }
+    }
+
+    fn move_highlight(&mut self, count: usize, forward: bool) -> Option<usize> {
+        if count == 0 {
+            return None;
+        }
+        let next = match (self.highlighted, forward) {
+            (None, true) => 0,
+            (None, false) => count - 1,
+            (Some(position), true) => (position + 1) % count,
+            (Some(position), false) => (position + count - 1) % count,
+        };
+        self.highlighted = Some(next);
+        Some(next)
+    }
+}
+
+impl Default for ExposedDropdownMenuController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the indices of the options containing `query`, ignoring case,
+/// with those starting with it first.
+fn matching_options(options: &[String], query: &str) -> Vec<usize> {
+    let query = query.to_lowercase();
+    let (mut prefixed, mut contained) = (Vec::new(), Vec::new());
+    for (index, option) in options.iter().enumerate() {
+        let lowered = option.to_lowercase();
+        if lowered.starts_with(&query) {
+            prefixed.push(index);
+        } else if lowered.contains(&query) {
+            contained.push(index);
+        }
+    }
+    prefixed.append(&mut contained);
+    prefixed
+}
+
+/// # exposed_dropdown_menu
+///
+/// Material exposed dropdown menu showing the selected options in a text
+/// field.
+///
+/// Clicking the field, or pressing Down, Enter or Space while it is focused,
+/// opens the menu; the arrow keys move through the options, Enter picks one and
+/// Escape closes the menu. When `searchable` is set, typing into the field
+/// filters the options.
+///
+/// `validator` receives the selected indices and returns the message to show
+/// when the selection is invalid. Errors show once the user has left the menu
+/// or after [`ExposedDropdownMenuController::validate`] is called, so call it
+/// when submitting a form.
+///
+/// Render inside an [`overlay_host`](crate::overlay::overlay_host) so the menu
+/// is drawn above the content below the field.
+///
+/// ## Usage
+///
+/// Let users pick from a list of options in forms.
+///
+/// ## Parameters
+///
+/// - `options` — labels of the options that can be picked
+/// - `selection_mode` — optional single or multiple selection; defaults to
+///   [`ExposedDropdownMenuSelectionMode::Single`]
+/// - `searchable` — whether typing into the field filters the options; defaults
+///   to `false`
+/// - `on_selection_change` — optional callback invoked with the selected
+///   indices after they change
+/// - `validator` — optional callback returning an error message for an invalid
+///   selection
+/// - `label` — optional label of the field
+/// - `placeholder` — optional placeholder shown while nothing is selected
+/// - `supporting_text` — optional helper text shown under the field while there
+///   is no error
+/// - `enabled` — whether the selection can be changed; defaults to `true`
+/// - `controller` — optional controller holding the selection
+/// - `modifier` — optional modifier chain applied to the field
+///
+/// ## Examples
+///
+/// ```
+/// # use tessera_ui::tessera;
+/// # #[tessera]
+/// # fn component() {
+/// use tessera_components::{
+///     exposed_dropdown_menu::{
+///         ExposedDropdownMenuController, ExposedDropdownMenuSelectionMode, exposed_dropdown_menu,
+///     },
+///     overlay::overlay_host,
+/// };
+/// use tessera_ui::remember;
+/// # use tessera_components::theme::{MaterialTheme, material_theme};
+///
+/// # material_theme()
+/// #     .theme(|| MaterialTheme::default())
+/// #     .child(|| {
+/// overlay_host().content(|| {
+///     let controller = remember(ExposedDropdownMenuController::new);
+///     exposed_dropdown_menu(vec!["Design".to_string(), "Backend".to_string()])
+///         .label("Teams")
+///         .selection_mode(ExposedDropdownMenuSelectionMode::Multiple)
+///         .validator(|selected: Vec<usize>| {
+///             selected
+///                 .is_empty()
+///                 .then(|| "Pick at least one team".to_string())
+///         })
+///         .controller(controller);
+///     assert!(!controller.with_mut(|controller| controller.validate()));
+/// });
+/// # });
+/// # }
+/// # component();
+/// ```
+#[tessera]
+pub fn exposed_dropdown_menu(
+    options: Vec<String>,
+    selection_mode: Option<ExposedDropdownMenuSelectionMode>,
+    searchable: Option<bool>,
+    on_selection_change: Option<CallbackWith<Vec<usize>>>,
+    validator: Option<CallbackWith<Vec<usize>, Option<String>>>,
+    #[prop(into)] label: Option<String>,
+    #[prop(into)] placeholder: Option<String>,
+    #[prop(into)] supporting_text: Option<String>,
+    enabled: Option<bool>,
+    controller: Option<State<ExposedDropdownMenuController>>,
+    modifier: Option<Modifier>,
+) {
+    let theme = use_context::<MaterialTheme>()
+        .expect("MaterialTheme must be provided")
+        .get();
+    let scheme = theme.color_scheme;
+    let mode = selection_mode.unwrap_or_default();
+    let searchable = searchable.unwrap_or(false);
+    let enabled = enabled.unwrap_or(true);
+    let controller = controller.unwrap_or_else(|| remember(ExposedDropdownMenuController::new));
+    let text_controller = remember(|| {
+        TextInputController::new(
+            TextFieldDefaults::FONT_SIZE,
+            Some(TextFieldDefaults::LINE_HEIGHT),
+        )
+    });
+    let list = remember(LazyListController::new);
+    let tap_recognizer = remember(|| TapRecognizer::new(TapSettings::default()));
+
+    let (selected, expanded, query, highlighted) = controller.with(|controller| {
+        (
+            controller.selected.clone(),
+            controller.expanded && enabled,
+            controller.query.clone(),
+            controller.highlighted,
+        )
+    });
+    let error = validator.and_then(|validator| validator.call(selected.clone()));
+    if controller.with(|controller| controller.error != error) {
+        controller.with_mut(|controller| controller.error = error.clone());
+    }
+
+    // The field shows the search query while searching and the selection
+    // otherwise; chips show the selection in multiple mode.
+    let shown_text = match mode {
+        _ if expanded && searchable => query.clone(),
+        ExposedDropdownMenuSelectionMode::Single => selected
+            .first()
+            .and_then(|&index| options.get(index).cloned())
+            .unwrap_or_default(),
+        ExposedDropdownMenuSelectionMode::Multiple => String::new(),
+    };
+    if text_controller.with(|text| text.text() != shown_text) {
+        text_controller.with_mut(|text| {
+            text.set_text_and_selection(&shown_text, TextSelection::collapsed(shown_text.len()));
+        });
+    }
+
+    let options = Arc::new(options);
+    let visible = Arc::new(if searchable && !query.is_empty() {
+        matching_options(&options, &query)
+    } else {
+        (0..options.len()).collect()
+    });
+
+    let notify = move || {
+        if let Some(on_selection_change) = on_selection_change {
+            on_selection_change.call(controller.with(|controller| controller.selected.clone()));
+        }
+    };
+    let pick = CallbackWith::new(move |index: usize| {
+        controller.with_mut(|controller| controller.pick(index, mode));
+        notify();
+    });
+    let on_change = CallbackWith::new(move |text: String| {
+        controller.with_mut(|controller| {
+            controller.query = text.clone();
+            controller.highlighted = None;
+            controller.expanded = true;
+        });
+        text
+    });
+
+    let key_visible = visible.clone();
+    let anchor_modifier = with_keyboard_preview_input(Modifier::new(), move |mut input| {
+        if !enabled {
+            return;
+        }
+        let count = key_visible.len();
+        let mut picked = None;
+        input.keyboard_events.retain(|event| {
+            if event.state != winit::event::ElementState::Pressed {
+                return true;
+            }
+            use winit::keyboard::{Key, NamedKey};
+            let expanded = controller.with(ExposedDropdownMenuController::is_expanded);
+            match &event.logical_key {
+                Key::Named(key @ (NamedKey::ArrowDown | NamedKey::ArrowUp)) => {
+                    let forward = *key == NamedKey::ArrowDown;
+                    let position = controller.with_mut(|controller| {
+                        controller.expand();
+                        controller.move_highlight(count, forward)
+                    });
+                    if let Some(position) = position {
+                        let item_height = OPTION_HEIGHT.to_px();
+                        let viewport = item_height * count.min(MAX_VISIBLE_OPTIONS) as i32;
+                        list.with_mut(|list| {
+                            list.reveal_uniform_item(position, item_height, viewport);
+                        });
+                    }
+                    false
+                }
+                Key::Named(NamedKey::Enter) if expanded => {
+                    let highlighted = controller.with(|controller| controller.highlighted);
+                    picked = highlighted.and_then(|position| key_visible.get(position).copied());
+                    false
+                }
+                Key::Named(NamedKey::Enter) => {
+                    controller.with_mut(ExposedDropdownMenuController::expand);
+                    false
+                }
+                Key::Named(NamedKey::Space) if !searchable && !expanded => {
+                    controller.with_mut(ExposedDropdownMenuController::expand);
+                    false
+                }
+                Key::Named(NamedKey::Escape) if expanded => {
+                    controller.with_mut(ExposedDropdownMenuController::collapse);
+                    false
+                }
+                _ => true,
+            }
+        });
+        if let Some(index) = picked {
+            pick.call(index);
+        }
+    });
+    // Presses on an open menu's field are taken by the popover to close it,
+    // so a tap only ever opens the menu.
+    let anchor_modifier = with_pointer_preview_input(anchor_modifier, move |mut input| {
+        let is_inside = input
+            .cursor_position_rel
+            .is_some_and(|position| is_position_inside_bounds(input.computed_data, position));
+        let tap = tap_recognizer.with_mut(|recognizer| {
+            recognizer.update(
+                input.pass,
+                input.pointer_changes.as_mut_slice(),
+                input.cursor_position_rel,
+                is_inside,
+            )
+        });
+        if tap.tapped && enabled {
+            controller.with_mut(ExposedDropdownMenuController::expand);
+        }
+    });
+
+    let field_error = error.is_some() && controller.with(|controller| controller.touched);
+    let arrow = Painter::from(if expanded {
+        filled::ARROW_DROP_UP_SVG
+    } else {
+        filled::ARROW_DROP_DOWN_SVG
+    });
+    let supporting = if field_error {
+        error.map(|message| (message, scheme.error))
+    } else {
+        supporting_text.map(|text| (text, scheme.on_surface_variant))
+    };
+    let field_modifier = modifier.unwrap_or_default();
+    column().children(move || {
+        let field_modifier = field_modifier.clone();
+        let label = label.clone();
+        let placeholder = placeholder.clone();
+        let arrow = arrow.clone();
+        let menu_options = options.clone();
+        let menu_visible = visible.clone();
+        let menu_selected = selected.clone();
+        popover(
+            move || {
+                let arrow = arrow.clone();
+                let error_color = field_error.then_some(scheme.error);
+                text_field()
+                    .modifier(field_modifier.clone())
+                    .enabled(enabled)
+                    .read_only(!searchable)
+                    .label_optional(label.clone())
+                    .placeholder_optional(placeholder.clone())
+                    .border_color_optional(error_color)
+                    .focus_border_color_optional(error_color)
+                    .on_change(on_change)
+                    .trailing_icon(RenderSlot::new(move || {
+                        icon().painter(arrow.clone());
+                    }))
+                    .controller(text_controller);
+            },
+            move || {
+                options_menu(
+                    menu_options.as_ref().clone(),
+                    menu_visible.as_ref().clone(),
+                    menu_selected.clone(),
+                    highlighted,
+                    pick,
+                    list,
+                );
+            },
+        )
+        .modifier(anchor_modifier.clone())
+        .is_open(expanded && !visible.is_empty())
+        .on_dismiss(move || controller.with_mut(ExposedDropdownMenuController::collapse))
+        .focus_on_open(false)
+        .match_anchor_width(true);
+
+        if mode == ExposedDropdownMenuSelectionMode::Multiple && !selected.is_empty() {
+            selected_chips(options.as_ref().clone(), selected.clone(), enabled, pick);
+        }
+        if let Some((message, color)) = supporting.clone() {
+            text()
+                .modifier(Modifier::new().padding_symmetric(SUPPORTING_TEXT_PADDING, Dp(4.0)))
+                .content(message)
+                .style(theme.typography.body_small)
+                .color(color);
+        }
+    });
+}
+
+#[tessera]
+fn selected_chips(
+    options: Vec<String>,
+    selected: Vec<usize>,
+    enabled: bool,
+    on_remove: CallbackWith<usize>,
+) {
+    flow_row()
+        .modifier(Modifier::new().padding_symmetric(Dp::ZERO, Dp(4.0)))
+        .item_spacing(CHIP_SPACING)
+        .line_spacing(CHIP_SPACING)
+        .children(move || {
+            for &index in &selected {
+                let Some(option) = options.get(index) else {
+                    continue;
+                };
+                chip()
+                    .input(option.clone())
+                    .trailing_icon(filled::CLOSE_SVG)
+                    .enabled(enabled)
+                    .on_click(move || on_remove.call(index));
+            }
+        });
+}
+
+#[tessera]
+fn options_menu(
+    options: Vec<String>,
+    visible: Vec<usize>,
+    selected: Vec<usize>,
+    highlighted: Option<usize>,
+    on_pick: CallbackWith<usize>,
+    list: State<LazyListController>,
+) {
+    let theme = use_context::<MaterialTheme>()
+        .expect("MaterialTheme must be provided")
+        .get();
+    let scheme = theme.color_scheme;
+    let height = Dp(OPTION_HEIGHT.0 * visible.len().min(MAX_VISIBLE_OPTIONS) as f64);
+    let options = Arc::new(options);
+    let visible = Arc::new(visible);
+    let selected = Arc::new(selected);
+    surface()
+        .style(SurfaceStyle::Filled {
+            color: scheme.surface_container,
+        })
+        .shape(Shape::rounded_rectangle(Dp(4.0)))
+        .elevation(MENU_ELEVATION)
+        .modifier(Modifier::new().fill_max_width())
+        .child(move || {
+            let options = options.clone();
+            let visible = visible.clone();
+            let selected = selected.clone();
+            lazy_column()
+                .modifier(Modifier::new().fill_max_width().height(height))
+                .estimated_item_size(OPTION_HEIGHT)
+                .controller(list)
+                .items(visible.len(), move |position| {
+                    let index = visible[position];
+                    let background = if highlighted == Some(position) {
+                        scheme.on_surface.with_alpha(MaterialAlpha::FOCUSED)
+                    } else {
+                        Color::TRANSPARENT
+                    };
+                    let label = options[index].clone();
+                    let is_selected = selected.contains(&index);
+                    boxed()
+                        .modifier(Modifier::new().fill_max_width().background(background))
+                        .children(move || {
+                            menu_item()
+                                .label(label.clone())
+                                .selected(is_selected)
+                                .height(OPTION_HEIGHT)
+                                .on_click(move || on_pick.call(index));
+                        });
+                });
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ExposedDropdownMenuController, ExposedDropdownMenuSelectionMode, matching_options,
+    };
+
+    #[test]
+    fn search_matches_prefixes_first() {
+        let options = ["Austria", "France", "Australia", "Mauritius"].map(String::from);
+        assert_eq!(matching_options(&options, "au"), [0, 2, 3]);
+    }
+
+    #[test]
+    fn multiple_mode_toggles_and_errors_show_once_touched() {
+        let mut controller = ExposedDropdownMenuController::new();
+        controller.error = Some("Required".to_string());
+        assert_eq!(controller.error(), None);
+
+        controller.expand();
+        controller.pick(2, ExposedDropdownMenuSelectionMode::Multiple);
+        controller.pick(0, ExposedDropdownMenuSelectionMode::Multiple);
+        controller.pick(2, ExposedDropdownMenuSelectionMode::Multiple);
+        assert_eq!(controller.selected(), [0]);
+        assert!(controller.is_expanded());
+        assert_eq!(controller.error(), Some("Required"));
+
+        controller.pick(1, ExposedDropdownMenuSelectionMode::Single);
+        assert_eq!(controller.selected(), [1]);
+        assert!(!controller.is_expanded());
+    }
+}
@@ -5,12 +5,12 @@
 //! Toggle boolean state over visible surface-backed content.
 use std::time::Duration;
 
-use tessera_foundation::gesture::TapRecognizer;
 use tessera_ui::{
     CallbackWith, Color, ComputedData, Constraint, Dp, LayoutResult, MeasurementError, Modifier,
     Px, PxPosition, State,
     accesskit::{Role, Toggled},
     current_frame_nanos,
+    gesture::TapRecognizer,
     layout::{LayoutPolicy, MeasureScope, PlacementScope, layout},
     receive_frame_nanos, remember, tessera, use_context,
 };
@@ -3,12 +3,12 @@
 //! ## Usage
 //!
 //! Use to select a value from a continuous range.
-use tessera_foundation::gesture::{DragRecognizer, TapRecognizer};
 use tessera_ui::{
     CallbackWith, Color, ComputedData, Constraint, Dp, FocusProperties, FocusRequester,
     LayoutResult, MeasurementError, Modifier, PointerInput, PointerInputModifierNode, Px,
     PxPosition, State,
     accesskit::Role,
+    gesture::{DragRecognizer, TapRecognizer},
     layout::{LayoutPolicy, MeasureScope, layout},
     modifier::{CursorModifierExt as _, FocusModifierExt as _, ModifierCapabilityExt as _},
     remember, tessera,
@@ -6,7 +6,7 @@
 use std::sync::{Arc, OnceLock};
 
 use tessera_ui::{
-    AssetExt, AxisConstraint, Color, ComputedData, Dp, LayoutResult, MeasurementError, Px,
+    AssetExt, AxisConstraint, Color, ComputedData, Dp, LayoutResult, MeasurementError, Px, PxSize,
     layout::{LayoutPolicy, MeasureScope, RenderInput, RenderPolicy, layout},
     tessera, use_context,
 };
@@ -164,6 +164,21 @@ impl RenderPolicy for IconLayout {
                 let command = ImageCommand {
                     data: data.clone(),
                     opacity: 1.0,
+                    rotation: self.rotation,
+                };
+                metadata.fragment_mut().push_draw_command(command);
+            }
+            Painter::Svg(data) => {
+                let Some(size) = metadata.computed_data() else {
+                    return;
+                };
+                let Some(raster) = data.rasterize(PxSize::new(size.width, size.height)) else {
+                    return;
+                };
+                let command = ImageCommand {
+                    data: raster,
+                    opacity: 1.0,
+                    rotation: self.rotation,
                 };
                 metadata.fragment_mut().push_draw_command(command);
             }
@@ -244,6 +259,10 @@ fn intrinsic_dimensions(content: &Painter) -> (Px, Px) {
             px_from_f32(data.viewport_height),
         ),
         Painter::Raster(data) => (clamp_u32_to_px(data.width), clamp_u32_to_px(data.height)),
+        Painter::Svg(data) => (
+            px_from_f32(data.viewport_width),
+            px_from_f32(data.viewport_height),
+        ),
     }
 }
 
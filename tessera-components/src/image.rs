@@ -10,7 +10,7 @@ use std::{
 
 use image::GenericImageView;
 use tessera_ui::{
-    AssetExt, Color, ComputedData, LayoutResult, MeasurementError, Modifier, Px,
+    AssetExt, Color, ComputedData, LayoutResult, MeasurementError, Modifier, Px, PxSize,
     layout::{LayoutPolicy, MeasureScope, RenderInput, RenderPolicy, layout},
     tessera,
 };
@@ -23,6 +23,7 @@ use crate::{
         image::command::ImageCommand,
         image_vector::command::{ImageVectorCommand, ImageVectorData},
     },
+    svg::{SvgData, SvgLoadError, TryIntoSvgData},
 };
 
 pub use crate::pipelines::image::command::ImageData;
@@ -48,7 +49,7 @@ pub trait TryIntoImageData {
     fn try_into_image_data(self) -> Result<ImageData, ImageLoadError>;
 }
 
-fn placeholder_image_data() -> Arc<ImageData> {
+pub(crate) fn placeholder_image_data() -> Arc<ImageData> {
     Arc::new(ImageData {
         data: Arc::new(vec![0, 0, 0, 0]),
         width: 1,
@@ -56,7 +57,7 @@ fn placeholder_image_data() -> Arc<ImageData> {
     })
 }
 
-fn decode_dynamic_image(decoded: image::DynamicImage) -> ImageData {
+pub(crate) fn decode_dynamic_image(decoded: image::DynamicImage) -> ImageData {
     let (width, height) = decoded.dimensions();
     ImageData {
         data: Arc::new(decoded.to_rgba8().into_raw()),
@@ -145,6 +146,12 @@ impl ImageBuilder {
         self
     }
 
+    /// Replaces the image content with a parsed SVG document.
+    pub fn svg(mut self, data: impl Into<Arc<SvgData>>) -> Self {
+        self.props.painter = Some(Painter::Svg(data.into()));
+        self
+    }
+
     /// Decodes raster image data from bytes/path/asset input and stores it.
     pub fn try_raster<T>(mut self, source: T) -> Result<Self, ImageLoadError>
     where
@@ -163,6 +170,15 @@ impl ImageBuilder {
         Ok(self)
     }
 
+    /// Parses an SVG document from bytes/path input and stores it.
+    pub fn try_svg<T>(mut self, source: T) -> Result<Self, SvgLoadError>
+    where
+        T: TryIntoSvgData,
+    {
+        self.props.painter = Some(Painter::Svg(source.try_into_svg_data()?));
+        Ok(self)
+    }
+
     /// Decodes raster image data from an asset handle and stores it.
     pub fn try_raster_asset<T>(mut self, asset: T) -> Result<Self, ImageLoadError>
     where
@@ -205,6 +221,10 @@ impl LayoutPolicy for ImageLayout {
                 clamp_f32_to_px(data.viewport_height),
             ),
             Painter::Raster(data) => (Px(data.width as i32), Px(data.height as i32)),
+            Painter::Svg(data) => (
+                clamp_f32_to_px(data.viewport_width),
+                clamp_f32_to_px(data.viewport_height),
+            ),
         };
 
         let width = input.parent_constraint().width().clamp(intrinsic_width);
@@ -221,6 +241,7 @@ impl RenderPolicy for ImageLayout {
                 let image_command = ImageCommand {
                     data: data.clone(),
                     opacity: 1.0,
+                    rotation: 0.0,
                 };
                 input
                     .metadata_mut()
@@ -239,6 +260,20 @@ impl RenderPolicy for ImageLayout {
                     .fragment_mut()
                     .push_draw_command(vector_command);
             }
+            Painter::Svg(data) => {
+                let mut metadata = input.metadata_mut();
+                let Some(size) = metadata.computed_data() else {
+                    return;
+                };
+                let Some(raster) = data.rasterize(PxSize::new(size.width, size.height)) else {
+                    return;
+                };
+                metadata.fragment_mut().push_draw_command(ImageCommand {
+                    data: raster,
+                    opacity: 1.0,
+                    rotation: 0.0,
+                });
+            }
         }
     }
 }
@@ -250,11 +285,11 @@ impl RenderPolicy for ImageLayout {
 ///
 /// ## Usage
 ///
-/// Display a raster or vector asset using a shared painter payload.
+/// Display a raster, vector or SVG asset using a shared painter payload.
 ///
 /// ## Parameters
 ///
-/// - `painter` - optional painter payload for vector or raster imagery.
+/// - `painter` - optional painter payload for vector, SVG or raster imagery.
 /// - `modifier` - node-local layout, drawing, and interaction modifiers.
 ///
 /// ## Examples
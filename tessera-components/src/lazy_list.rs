@@ -53,6 +53,9 @@ const DEFAULT_VIEWPORT_ITEMS: usize = 8;
 pub struct LazyListController {
     scroll: ScrollableController,
     cache: LazyListCache,
+    /// Position requested while the list is mounted, applied on its next
+    /// build because the mounted list keeps its own scroll state.
+    requested_position: Option<PxPosition>,
 }
 
 impl Default for LazyListController {
@@ -68,6 +71,7 @@ impl LazyListController {
         Self {
             scroll: ScrollableController::new(),
             cache: LazyListCache::default(),
+            requested_position: None,
         }
     }
 
@@ -80,6 +84,27 @@ impl LazyListController {
     pub fn scroll_controller_mut(&mut self) -> &mut ScrollableController {
         &mut self.scroll
     }
+
+    /// Instantly scrolls a mounted list to `position`.
+    pub fn scroll_to_position(&mut self, position: PxPosition) {
+        self.scroll.set_scroll_position(position);
+        self.requested_position = Some(position);
+    }
+
+    /// Scrolls a column of `item_height` tall items shown in a `viewport`
+    /// tall list just enough to show the item at `index`.
+    pub(crate) fn reveal_uniform_item(&mut self, index: usize, item_height: Px, viewport: Px) {
+        let offset = -self.scroll.child_position().y;
+        let top = item_height * index as i32;
+        let offset = if top < offset {
+            top
+        } else if top + item_height > offset + viewport {
+            top + item_height - viewport
+        } else {
+            return;
+        };
+        self.scroll_to_position(PxPosition::new(Px::ZERO, -offset));
+    }
 }
 
 fn hash_key<K>(key: K) -> u64
@@ -198,6 +223,11 @@ fn lazy_column_slots(args: LazyListSlotsArgs) {
     if should_restore_position {
         scroll_controller.with_mut(|sc| sc.set_scroll_position(saved_position));
     }
+    if args.controller.with(|c| c.requested_position.is_some())
+        && let Some(position) = args.controller.with_mut(|c| c.requested_position.take())
+    {
+        scroll_controller.with_mut(|sc| sc.set_scroll_position(position));
+    }
 
     let item_spacing = sanitize_spacing(Px::from(args.item_spacing));
     let estimated_item_main = ensure_positive_px(Px::from(args.estimated_item_size));
@@ -544,6 +574,11 @@ fn lazy_row_slots(args: LazyListSlotsArgs) {
     if should_restore_position {
         scroll_controller.with_mut(|sc| sc.set_scroll_position(saved_position));
     }
+    if args.controller.with(|c| c.requested_position.is_some())
+        && let Some(position) = args.controller.with_mut(|c| c.requested_position.take())
+    {
+        scroll_controller.with_mut(|sc| sc.set_scroll_position(position));
+    }
 
     let item_spacing = sanitize_spacing(Px::from(args.item_spacing));
     let estimated_item_main = ensure_positive_px(Px::from(args.estimated_item_size));
@@ -62,20 +62,31 @@
 )]
 
 mod animation;
+pub mod animated_content;
+pub mod animated_visibility;
 pub mod app_bar;
+pub mod async_image;
+pub mod autocomplete;
 pub mod badge;
 pub mod bottom_sheet;
 pub mod boxed;
 pub mod button;
 pub mod button_groups;
+#[cfg(feature = "camera")]
+pub mod camera_preview;
 pub mod card;
+pub mod charts;
 pub mod checkbox;
 mod checkmark;
 pub mod chip;
 pub mod column;
+pub mod data_table;
 pub mod date_picker;
 pub mod dialog;
 pub mod divider;
+pub mod dock;
+pub mod drag_preview;
+pub mod exposed_dropdown_menu;
 pub mod floating_action_button;
 pub mod flow_column;
 pub mod flow_row;
@@ -100,15 +111,20 @@ pub mod modifier;
 pub mod navigation_bar;
 pub mod navigation_rail;
 mod nested_scroll;
+pub mod overlay;
 mod padding_utils;
 pub mod pager;
 pub mod painter;
 pub mod pipelines;
+pub mod popover;
 pub mod pos_misc;
+#[cfg(feature = "preview-host")]
+pub mod preview_host;
 pub mod progress;
 pub mod pull_refresh;
 pub mod radio_button;
 pub mod res;
+pub mod rich_text_editor;
 pub mod ripple_state;
 pub mod row;
 pub mod scaffold;
@@ -122,7 +138,9 @@ pub mod slider;
 pub mod snackbar;
 pub mod spacer;
 pub mod split_buttons;
+pub mod split_pane;
 pub mod surface;
+pub mod svg;
 pub mod switch;
 pub mod tabs;
 pub mod text;
@@ -131,6 +149,11 @@ pub mod text_field;
 pub mod text_input;
 pub mod theme;
 pub mod time_picker;
+pub mod tooltip;
+pub mod tree_view;
+#[cfg(feature = "video")]
+pub mod video;
+pub mod zoomable_image;
 
 use tessera_platform::PlatformPackage;
 use tessera_ui::{EntryRegistry, PipelineContext, RenderModule, TesseraPackage};
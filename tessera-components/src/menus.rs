@@ -11,6 +11,7 @@ use tessera_ui::{
     FocusTraversalPolicy, LayoutResult, MeasurementError, Modifier, ParentConstraint, Px,
     PxPosition, PxSize, RenderSlot, State,
     accesskit::Role,
+    gesture::SecondaryActivationRecognizer,
     layout::{LayoutPolicy, MeasureScope, layout},
     modifier::FocusModifierExt as _,
     provide_context, remember, tessera, use_context, winit,
@@ -20,10 +21,11 @@ use crate::{
     alignment::CrossAxisAlignment,
     checkmark::checkmark,
     column::column,
+    divider::horizontal_divider,
     icon::icon,
     modifier::{ModifierExt as _, with_keyboard_input, with_pointer_input},
     painter::Painter,
-    pos_misc::is_position_in_rect,
+    pos_misc::{is_position_in_rect, is_position_inside_bounds},
     row::row,
     shape_def::Shape,
     spacer::spacer,
@@ -40,6 +42,7 @@ const MENU_HORIZONTAL_PADDING: Dp = Dp(16.0);
 const MENU_LEADING_SIZE: Dp = Dp(20.0);
 const MENU_ITEM_HEIGHT: Dp = Dp(48.0);
 const MENU_TRAILING_SPACING: Dp = Dp(16.0);
+const MENU_DIVIDER_VERTICAL_PADDING: Dp = Dp(8.0);
 
 fn default_menu_width() -> AxisConstraint {
     AxisConstraint::new(Px::from(MENU_MIN_WIDTH), Some(Px::from(MENU_MAX_WIDTH)))
@@ -554,6 +557,110 @@ pub fn menu_provider(
         });
 }
 
+/// # context_menu
+///
+/// Opens a Material Design 3 menu at the pointer when content is right
+/// clicked or long pressed.
+///
+/// The menu opens with its top-start corner at the pointer, shifted to stay
+/// within the available space. Focus moves into the menu and arrow keys move
+/// between items. Submenus, separators and disabled items work as in
+/// [`menu_provider`]. Escape or a click outside the menu closes it.
+///
+/// ## Usage
+///
+/// Offer actions for the item under the pointer, such as copy, rename or delete
+/// on a file row.
+///
+/// ## Parameters
+///
+/// - `trigger_content` — content that opens the menu when right clicked or long
+///   pressed
+/// - `menu_items` — menu content, usually [`menu_item`] and [`menu_divider`]
+/// - `enabled` — whether right clicks and long presses open the menu; defaults
+///   to `true`
+/// - `on_dismiss` — optional callback when the menu closes without an item
+///   being chosen
+/// - `controller` — optional external controller, for example to close the menu
+///   from elsewhere
+/// - `modifier` — optional modifier chain applied to the trigger container
+///
+/// ## Examples
+///
+/// ```
+/// # use tessera_ui::tessera;
+/// # #[tessera]
+/// # fn component() {
+/// use tessera_components::{
+///     menus::{context_menu, menu_divider, menu_item},
+///     text::text,
+/// };
+/// # use tessera_components::theme::{MaterialTheme, material_theme};
+///
+/// # material_theme()
+/// #     .theme(|| MaterialTheme::default())
+/// #     .child(|| {
+/// context_menu(
+///     || {
+///         text().content("report.pdf");
+///     },
+///     || {
+///         menu_item().label("Open").on_click(|| {});
+///         menu_item().label("Rename").on_click(|| {});
+///         menu_divider();
+///         menu_item().label("Delete").enabled(false);
+///     },
+/// );
+/// # });
+/// # }
+/// # component();
+/// ```
+#[tessera]
+pub fn context_menu(
+    trigger_content: RenderSlot,
+    menu_items: RenderSlot,
+    enabled: Option<bool>,
+    on_dismiss: Option<Callback>,
+    controller: Option<State<MenuController>>,
+    modifier: Option<Modifier>,
+) {
+    let enabled = enabled.unwrap_or(true);
+    let controller = controller.unwrap_or_else(|| remember(MenuController::new));
+    let recognizer = remember(SecondaryActivationRecognizer::default);
+    let modifier = with_pointer_input(modifier.unwrap_or_default(), move |mut input| {
+        if !enabled {
+            return;
+        }
+        let is_inside = input
+            .cursor_position_rel
+            .is_some_and(|position| is_position_inside_bounds(input.computed_data, position));
+        let activation = recognizer.with_mut(|recognizer| {
+            recognizer.update(
+                input.pass,
+                input.pointer_changes.as_mut_slice(),
+                input.cursor_position_rel,
+                is_inside,
+            )
+        });
+        if let Some(activation) = activation {
+            controller.with_mut(|menu| menu.open_at(MenuAnchor::at(activation.position)));
+        }
+    });
+
+    layout().modifier(modifier).child(move || {
+        menu_provider()
+            .placement(MenuPlacement::BelowStart)
+            .offset([Dp::ZERO, Dp::ZERO])
+            .on_dismiss_optional(on_dismiss)
+            // The provider syncs the controller to `is_open`, so pass through
+            // what the pointer handler set.
+            .is_open(controller.with(|menu| menu.is_open()))
+            .controller(controller)
+            .main_content_shared(trigger_content)
+            .menu_content_shared(menu_items);
+    });
+}
+
 #[tessera]
 fn menu_panel(
     provider: Option<MenuProviderConfig>,
@@ -823,6 +930,48 @@ pub fn menu_item(
     menu_item_inner().args(args);
 }
 
+/// # menu_divider
+///
+/// Separates groups of items inside a menu.
+///
+/// ## Usage
+///
+/// Place between [`menu_item`]s to group related actions.
+///
+/// ## Examples
+///
+/// ```
+/// # use tessera_ui::tessera;
+/// # #[tessera]
+/// # fn component() {
+/// use tessera_components::menus::{menu_divider, menu_item, menu_provider};
+/// # use tessera_components::theme::{MaterialTheme, material_theme};
+///
+/// # material_theme()
+/// #     .theme(|| MaterialTheme::default())
+/// #     .child(|| {
+/// menu_provider().is_open(true).menu_content(|| {
+///     menu_item().label("Cut");
+///     menu_divider();
+///     menu_item().label("Select all");
+/// });
+/// # });
+/// # }
+/// # component();
+/// ```
+#[tessera]
+pub fn menu_divider() {
+    layout()
+        .modifier(
+            Modifier::new()
+                .fill_max_width()
+                .padding_symmetric(Dp::ZERO, MENU_DIVIDER_VERTICAL_PADDING),
+        )
+        .child(|| {
+            horizontal_divider();
+        });
+}
+
 #[tessera]
 fn menu_item_surface(
     item: Option<MenuItemConfig>,
@@ -10,8 +10,8 @@ mod visual;
 
 use tessera_foundation::modifier::ModifierExt as FoundationModifierExt;
 use tessera_ui::{
-    AxisConstraint, Callback, CallbackWith, Color, Dp, Modifier,
-    modifier::ModifierCapabilityExt as _,
+    AxisConstraint, Callback, CallbackWith, Color, Dp, FocusManager, Modifier,
+    modifier::ModifierCapabilityExt as _, winit::window::ResizeDirection,
 };
 
 pub use tessera_foundation::modifier::{
@@ -27,12 +27,21 @@ use crate::{alignment::Alignment, shape_def::Shape};
 use interaction::{
     apply_block_touch_propagation_modifier, apply_clickable_modifier, apply_selectable_modifier,
     apply_toggleable_modifier, apply_window_drag_region_modifier,
+    apply_window_resize_region_modifier,
+};
+use visual::{
+    AlphaModifierNode, BackgroundModifierNode, BorderModifierNode, ClipModifierNode,
+    ShapeClipModifierNode,
 };
-use visual::{AlphaModifierNode, BackgroundModifierNode, BorderModifierNode, ClipModifierNode};
 
 pub use shadow::ShadowArgs;
+pub use visual::FocusRing;
 
-pub(crate) use interaction::{with_keyboard_input, with_pointer_input};
+pub(crate) use interaction::{
+    with_keyboard_input, with_keyboard_preview_input, with_pointer_input,
+    with_pointer_preview_input,
+};
+pub(crate) use visual::shape_clip;
 
 /// Extensions for composing reusable wrapper behavior around component
 /// subtrees.
@@ -55,6 +64,9 @@ pub trait ModifierExt {
     /// Clips descendants to this modifier's bounds.
     fn clip_to_bounds(self) -> Modifier;
 
+    /// Clips descendants to `shape` resolved against this modifier's bounds.
+    fn clip_to_shape(self, shape: Shape) -> Modifier;
+
     /// Draws a background behind the subtree.
     fn background(self, color: Color) -> Modifier;
 
@@ -67,6 +79,10 @@ pub trait ModifierExt {
     /// Draws a border stroke above the subtree using a custom shape.
     fn border_with_shape(self, width: Dp, color: Color, shape: Shape) -> Modifier;
 
+    /// Draws `ring` over the content while `focused` is true and focus was
+    /// moved with the keyboard.
+    fn focus_ring(self, focused: bool, ring: FocusRing) -> Modifier;
+
     /// Adds a shadow with advanced configuration options.
     fn shadow(self, args: &ShadowArgs) -> Modifier;
 
@@ -154,6 +170,12 @@ pub trait ModifierExt {
 
     /// Marks this subtree as a draggable window region.
     fn window_drag_region(self) -> Modifier;
+
+    /// Marks this subtree as a window resize handle for the given edge.
+    ///
+    /// Takes effect while native decorations are hidden, for example along
+    /// the borders of a custom title bar layout.
+    fn window_resize_region(self, direction: ResizeDirection) -> Modifier;
 }
 
 impl ModifierExt for Modifier {
@@ -186,6 +208,14 @@ impl ModifierExt for Modifier {
         self.push_draw(ClipModifierNode)
     }
 
+    fn clip_to_shape(self, shape: Shape) -> Modifier {
+        if shape == Shape::RECTANGLE {
+            return self.clip_to_bounds();
+        }
+
+        self.push_draw(ShapeClipModifierNode { shape })
+    }
+
     fn background(self, color: Color) -> Modifier {
         self.background_with_shape(color, Shape::RECTANGLE)
     }
@@ -214,6 +244,14 @@ impl ModifierExt for Modifier {
         })
     }
 
+    fn focus_ring(self, focused: bool, ring: FocusRing) -> Modifier {
+        if !focused || !FocusManager::current().is_focus_visible() {
+            return self;
+        }
+
+        self.border_with_shape(ring.width, ring.color, ring.shape)
+    }
+
     fn shadow(self, args: &ShadowArgs) -> Modifier {
         shadow::apply_shadow_modifier(self, args.clone())
     }
@@ -343,4 +381,8 @@ impl ModifierExt for Modifier {
     fn window_drag_region(self) -> Modifier {
         apply_window_drag_region_modifier(self)
     }
+
+    fn window_resize_region(self, direction: ResizeDirection) -> Modifier {
+        apply_window_resize_region_modifier(self, direction)
+    }
 }
@@ -11,19 +11,17 @@
 
 use std::sync::{Arc, Mutex};
 
-use tessera_foundation::{
-    gesture::{LongPressRecognizer, TapRecognizer},
-    modifier::{
-        ClickableArgs, InteractionState, PointerEventContext, SelectableArgs, ToggleableArgs,
-    },
+use tessera_foundation::modifier::{
+    ClickableArgs, InteractionState, PointerEventContext, SelectableArgs, ToggleableArgs,
 };
 use tessera_ui::{
     AccessibilityActionHandler, AccessibilityNode, Callback, CallbackWith, ComputedData,
     FocusRequester, FocusState, KeyboardInput, KeyboardInputModifierNode, Modifier, PointerInput,
     PointerInputModifierNode, PxPosition, PxSize, SemanticsModifierNode, State,
     accesskit::{self, Action, Toggled},
+    gesture::{LongPressRecognizer, TapRecognizer},
     modifier::{CursorModifierExt as _, FocusModifierExt as _, ModifierCapabilityExt as _},
-    winit::window::CursorIcon,
+    winit::window::{CursorIcon, ResizeDirection},
 };
 
 use crate::pos_misc::is_position_in_rect;
@@ -63,6 +61,13 @@ where
     base.push_pointer_input(ClosurePointerInputModifierNode { handler })
 }
 
+pub(crate) fn with_pointer_preview_input<F>(base: Modifier, handler: F) -> Modifier
+where
+    F: for<'a> Fn(PointerInput<'a>) + Send + Sync + 'static,
+{
+    base.push_pointer_preview_input(ClosurePointerInputModifierNode { handler })
+}
+
 pub(crate) fn with_keyboard_input<F>(base: Modifier, handler: F) -> Modifier
 where
     F: for<'a> Fn(KeyboardInput<'a>) + Send + Sync + 'static,
@@ -70,8 +75,15 @@ where
     base.push_keyboard_input(ClosureKeyboardInputModifierNode { handler })
 }
 
+pub(crate) fn with_keyboard_preview_input<F>(base: Modifier, handler: F) -> Modifier
+where
+    F: for<'a> Fn(KeyboardInput<'a>) + Send + Sync + 'static,
+{
+    base.push_keyboard_preview_input(ClosureKeyboardInputModifierNode { handler })
+}
+
 fn has_keyboard_activation_event(
-    keyboard_events: &[tessera_ui::winit::event::KeyEvent],
+    keyboard_events: &[tessera_ui::KeyEvent],
     modifiers: tessera_ui::winit::keyboard::ModifiersState,
 ) -> bool {
     if modifiers.control_key() || modifiers.alt_key() || modifiers.super_key() {
@@ -713,6 +725,61 @@ impl PointerInputModifierNode for WindowDragRegionPointerModifierNode {
     }
 }
 
+#[cfg(not(any(target_family = "wasm", target_os = "android", target_os = "ios")))]
+struct WindowResizeRegionPointerModifierNode {
+    direction: ResizeDirection,
+    tap_recognizer: TapRecognizerHandle,
+}
+
+#[cfg(not(any(target_family = "wasm", target_os = "android", target_os = "ios")))]
+impl PointerInputModifierNode for WindowResizeRegionPointerModifierNode {
+    fn on_pointer_input(&self, mut input: PointerInput<'_>) {
+        let within_bounds = input
+            .cursor_position_rel
+            .map(|pos| {
+                is_position_in_rect(
+                    pos,
+                    PxPosition::ZERO,
+                    input.computed_data.width,
+                    input.computed_data.height,
+                )
+            })
+            .unwrap_or(false);
+
+        if !within_bounds {
+            return;
+        }
+
+        let tap_result = self.tap_recognizer.with_mut(|recognizer| {
+            recognizer.update(
+                input.pass,
+                input.pointer_changes.as_mut_slice(),
+                input.cursor_position_rel,
+                within_bounds,
+            )
+        });
+
+        if tap_result.pressed {
+            input.drag_resize_window(self.direction);
+        }
+        input.block_all();
+    }
+}
+
+#[cfg(not(any(target_family = "wasm", target_os = "android", target_os = "ios")))]
+fn resize_cursor_icon(direction: ResizeDirection) -> CursorIcon {
+    match direction {
+        ResizeDirection::East => CursorIcon::EResize,
+        ResizeDirection::North => CursorIcon::NResize,
+        ResizeDirection::NorthEast => CursorIcon::NeResize,
+        ResizeDirection::NorthWest => CursorIcon::NwResize,
+        ResizeDirection::South => CursorIcon::SResize,
+        ResizeDirection::SouthEast => CursorIcon::SeResize,
+        ResizeDirection::SouthWest => CursorIcon::SwResize,
+        ResizeDirection::West => CursorIcon::WResize,
+    }
+}
+
 struct BlockTouchPropagationPointerModifierNode;
 
 impl PointerInputModifierNode for BlockTouchPropagationPointerModifierNode {
@@ -931,6 +998,27 @@ pub(crate) fn apply_window_drag_region_modifier(base: Modifier) -> Modifier {
     base
 }
 
+#[cfg(not(any(target_family = "wasm", target_os = "android", target_os = "ios")))]
+pub(crate) fn apply_window_resize_region_modifier(
+    base: Modifier,
+    direction: ResizeDirection,
+) -> Modifier {
+    let tap_recognizer = TapRecognizerHandle::new(None);
+    base.hover_cursor_icon(resize_cursor_icon(direction))
+        .push_pointer_input(WindowResizeRegionPointerModifierNode {
+            direction,
+            tap_recognizer,
+        })
+}
+
+#[cfg(any(target_family = "wasm", target_os = "android", target_os = "ios"))]
+pub(crate) fn apply_window_resize_region_modifier(
+    base: Modifier,
+    _direction: ResizeDirection,
+) -> Modifier {
+    base
+}
+
 pub(crate) fn apply_block_touch_propagation_modifier(base: Modifier) -> Modifier {
     base.push_pointer_input(BlockTouchPropagationPointerModifierNode)
 }
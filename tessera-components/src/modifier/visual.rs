@@ -4,13 +4,39 @@
 //!
 //! Apply basic visual effects like alpha, clipping, and shape borders.
 
-use tessera_ui::{Color, Dp, DrawModifierContent, DrawModifierContext, DrawModifierNode, PxSize};
+use tessera_ui::{
+    ClipShape, Color, Dp, DrawModifierContent, DrawModifierContext, DrawModifierNode, PxPosition,
+    PxRect, PxSize,
+};
 
 use crate::{
     pipelines::shape::command::ShapeCommand,
     shape_def::{ResolvedShape, Shape},
+    theme::MaterialTheme,
 };
 
+/// Outline drawn around a node while it has keyboard-visible focus.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FocusRing {
+    /// Outline width.
+    pub width: Dp,
+    /// Outline color.
+    pub color: Color,
+    /// Outline shape, usually matching the node's container shape.
+    pub shape: Shape,
+}
+
+impl FocusRing {
+    /// Creates a rectangular ring in the secondary color of `theme`.
+    pub fn from_theme(theme: &MaterialTheme) -> Self {
+        Self {
+            width: Dp(3.0),
+            color: theme.color_scheme.secondary,
+            shape: Shape::RECTANGLE,
+        }
+    }
+}
+
 fn shape_background_command(color: Color, shape: Shape, size: PxSize) -> ShapeCommand {
     match shape.resolve_for_size(size) {
         ResolvedShape::Rounded {
@@ -25,6 +51,17 @@ fn shape_background_command(color: Color, shape: Shape, size: PxSize) -> ShapeCo
     }
 }
 
+/// Resolves `shape` into a clip shape covering a node of `size`.
+pub(crate) fn shape_clip(shape: Shape, size: PxSize) -> ClipShape {
+    let rect = PxRect::from_position_size(PxPosition::ZERO, size);
+    match shape.resolve_for_size(size) {
+        ResolvedShape::Rounded { corner_radii, .. } => {
+            ClipShape::RoundedRect { rect, corner_radii }
+        }
+        ResolvedShape::Ellipse => ClipShape::Ellipse(rect),
+    }
+}
+
 fn shape_border_command(color: Color, width: Dp, shape: Shape, size: PxSize) -> ShapeCommand {
     let border_width = width.to_pixels_f32();
     match shape.resolve_for_size(size) {
@@ -69,6 +106,24 @@ impl DrawModifierNode for ClipModifierNode {
     }
 }
 
+#[derive(Clone, Copy)]
+pub(crate) struct ShapeClipModifierNode {
+    pub shape: Shape,
+}
+
+impl DrawModifierNode for ShapeClipModifierNode {
+    fn draw(&self, ctx: &mut DrawModifierContext<'_, '_>, content: &mut dyn DrawModifierContent) {
+        {
+            let mut metadata = ctx.render_input.metadata_mut();
+            let size = metadata
+                .computed_data()
+                .expect("clip modifier must have computed size before record");
+            metadata.set_clip_shape(shape_clip(self.shape, size.into()));
+        }
+        content.draw(ctx.render_input);
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct BackgroundModifierNode {
     pub color: Color,
@@ -0,0 +1,448 @@
+//! Overlay layer for content anchored to components, such as tooltips.
+//!
+//! ## Usage
+//!
+//! Wrap the app root so tooltips and other anchored popups draw above all
+//! content.
+use std::{
+    collections::BTreeMap,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+};
+
+use parking_lot::{Mutex, RwLock};
+use tessera_ui::{
+    AxisConstraint, Callback, ComputedData, Constraint, CursorEventContent, Dp, MeasurementError,
+    Modifier, Px, PxPosition, PxRect, RenderSlot, State, key,
+    layout::{LayoutPolicy, LayoutResult, MeasureScope, layout},
+    provide_context, remember, tessera,
+};
+
+use crate::modifier::with_pointer_preview_input;
+
+static NEXT_OVERLAY_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Side of the anchor an overlay opens on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverlaySide {
+    /// Open above the anchor.
+    Above,
+    /// Open below the anchor.
+    #[default]
+    Below,
+    /// Open to the left of the anchor.
+    Left,
+    /// Open to the right of the anchor.
+    Right,
+}
+
+impl OverlaySide {
+    fn opposite(self) -> Self {
+        match self {
+            Self::Above => Self::Below,
+            Self::Below => Self::Above,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+}
+
+/// How an overlay lines up with its anchor along the side it opens on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverlayAlign {
+    /// Align the leading edges of the overlay and the anchor.
+    #[default]
+    Start,
+    /// Center the overlay on the anchor.
+    Center,
+    /// Align the trailing edges of the overlay and the anchor.
+    End,
+}
+
+/// Where an overlay is placed relative to its anchor.
+///
+/// An overlay that does not fit on its side opens on the opposite side when
+/// there is more room there, then shifts to stay inside the overlay host.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OverlayPlacement {
+    side: OverlaySide,
+    align: OverlayAlign,
+    gap: Dp,
+}
+
+impl OverlayPlacement {
+    /// Opens on `side` of the anchor, lined up as `align`.
+    pub fn new(side: OverlaySide, align: OverlayAlign) -> Self {
+        Self {
+            side,
+            align,
+            gap: Dp(0.0),
+        }
+    }
+
+    /// Sets the space between the anchor and the overlay.
+    pub fn gap(mut self, gap: Dp) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Returns the position of an overlay of `size` next to `anchor`,
+    /// ignoring any bounds.
+    pub(crate) fn position(&self, anchor: PxRect, size: ComputedData) -> PxPosition {
+        self.position_on(self.side, anchor, size)
+    }
+
+    /// Returns the position of an overlay of `size` next to `anchor`, flipped
+    /// and shifted to stay inside `bounds`.
+    pub(crate) fn position_within(
+        &self,
+        anchor: PxRect,
+        size: ComputedData,
+        bounds: ComputedData,
+    ) -> PxPosition {
+        let gap = Px::from(self.gap);
+        let room = |side| match side {
+            OverlaySide::Above => anchor.y - gap,
+            OverlaySide::Below => bounds.height - (anchor.y + anchor.height + gap),
+            OverlaySide::Left => anchor.x - gap,
+            OverlaySide::Right => bounds.width - (anchor.x + anchor.width + gap),
+        };
+        let needed = match self.side {
+            OverlaySide::Above | OverlaySide::Below => size.height,
+            OverlaySide::Left | OverlaySide::Right => size.width,
+        };
+        let opposite = self.side.opposite();
+        let side = if room(self.side) < needed && room(opposite) > room(self.side) {
+            opposite
+        } else {
+            self.side
+        };
+        let position = self.position_on(side, anchor, size);
+        PxPosition::new(
+            clamp_into(position.x, size.width, bounds.width),
+            clamp_into(position.y, size.height, bounds.height),
+        )
+    }
+
+    fn position_on(&self, side: OverlaySide, anchor: PxRect, size: ComputedData) -> PxPosition {
+        let gap = Px::from(self.gap);
+        let along = |start: Px, extent: Px, length: Px| match self.align {
+            OverlayAlign::Start => start,
+            OverlayAlign::Center => start + (extent - length).mul_f32(0.5),
+            OverlayAlign::End => start + extent - length,
+        };
+        match side {
+            OverlaySide::Above => PxPosition::new(
+                along(anchor.x, anchor.width, size.width),
+                anchor.y - gap - size.height,
+            ),
+            OverlaySide::Below => PxPosition::new(
+                along(anchor.x, anchor.width, size.width),
+                anchor.y + anchor.height + gap,
+            ),
+            OverlaySide::Left => PxPosition::new(
+                anchor.x - gap - size.width,
+                along(anchor.y, anchor.height, size.height),
+            ),
+            OverlaySide::Right => PxPosition::new(
+                anchor.x + anchor.width + gap,
+                along(anchor.y, anchor.height, size.height),
+            ),
+        }
+    }
+}
+
+impl Default for OverlayPlacement {
+    fn default() -> Self {
+        Self::new(OverlaySide::default(), OverlayAlign::default())
+    }
+}
+
+/// Keeps `start` inside `0..bound` for a length of `length`, preferring the
+/// start edge when it does not fit.
+fn clamp_into(start: Px, length: Px, bound: Px) -> Px {
+    start.min(bound - length).max(Px::ZERO)
+}
+
+/// Identity of the overlay shown by one component instance.
+///
+/// Dropping the handle, which happens when the owning instance is dropped,
+/// retires its overlay.
+pub(crate) struct OverlayHandle {
+    id: u64,
+    alive: Arc<AtomicBool>,
+    /// Overlay last passed to the host.
+    shown: Mutex<Option<OverlayRequest>>,
+}
+
+impl OverlayHandle {
+    pub(crate) fn new() -> Self {
+        Self {
+            id: NEXT_OVERLAY_ID.fetch_add(1, Ordering::Relaxed),
+            alive: Arc::new(AtomicBool::new(true)),
+            shown: Mutex::new(None),
+        }
+    }
+}
+
+impl Drop for OverlayHandle {
+    fn drop(&mut self) {
+        self.alive.store(false, Ordering::Relaxed);
+    }
+}
+
+/// Content shown by an overlay host next to an anchor.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) struct OverlayRequest {
+    /// Anchor rect in window coordinates.
+    pub(crate) anchor: PxRect,
+    pub(crate) placement: OverlayPlacement,
+    pub(crate) content: RenderSlot,
+    /// Called when the pointer is pressed outside the overlay; the press does
+    /// not reach the content below.
+    pub(crate) on_dismiss: Option<Callback>,
+}
+
+#[derive(Clone)]
+struct OverlayEntry {
+    request: OverlayRequest,
+    alive: Arc<AtomicBool>,
+}
+
+impl OverlayEntry {
+    fn is_alive(&self) -> bool {
+        self.alive.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Default)]
+struct OverlayEntries {
+    entries: BTreeMap<u64, OverlayEntry>,
+}
+
+/// Overlay host of the current subtree, provided by [`overlay_host`].
+#[derive(Clone, Copy)]
+pub(crate) struct OverlayHost {
+    entries: State<OverlayEntries>,
+}
+
+impl OverlayHost {
+    /// Shows `request` until [`OverlayHost::hide`] is called or `handle` is
+    /// dropped.
+    pub(crate) fn show(&self, handle: &OverlayHandle, request: OverlayRequest) {
+        let mut shown = handle.shown.lock();
+        if *shown == Some(request) {
+            return;
+        }
+        *shown = Some(request);
+        let entry = OverlayEntry {
+            request,
+            alive: handle.alive.clone(),
+        };
+        self.entries.with_mut(|entries| {
+            entries.entries.insert(handle.id, entry);
+        });
+    }
+
+    /// Removes the overlay of `handle`.
+    pub(crate) fn hide(&self, handle: &OverlayHandle) {
+        if handle.shown.lock().take().is_some() {
+            self.entries.with_mut(|entries| {
+                entries.entries.remove(&handle.id);
+            });
+        }
+    }
+}
+
+#[derive(Clone)]
+struct OverlayHostLayout {
+    /// Id, anchor rect and placement of each overlay, relative to the host.
+    overlays: Vec<(u64, PxRect, OverlayPlacement)>,
+    /// Bounds of each overlay from the last measure, for outside presses.
+    bounds: Arc<RwLock<Vec<(u64, PxRect)>>>,
+}
+
+impl PartialEq for OverlayHostLayout {
+    fn eq(&self, other: &Self) -> bool {
+        self.overlays == other.overlays
+    }
+}
+
+impl LayoutPolicy for OverlayHostLayout {
+    fn measure(&self, input: &MeasureScope<'_>) -> Result<LayoutResult, MeasurementError> {
+        let mut result = LayoutResult::default();
+        let children = input.children();
+        let child_constraint = input.parent_constraint().without_min();
+        let main_content = children
+            .first()
+            .copied()
+            .expect("main content should exist");
+        let main_size = main_content.measure(&child_constraint)?.size();
+        result.place_child(main_content, PxPosition::ZERO);
+
+        let overlay_constraint = Constraint::new(
+            AxisConstraint::new(Px::ZERO, Some(main_size.width)),
+            AxisConstraint::new(Px::ZERO, Some(main_size.height)),
+        );
+        let mut bounds = Vec::with_capacity(self.overlays.len());
+        for (overlay, (id, anchor, placement)) in children.iter().skip(1).zip(&self.overlays) {
+            let size = overlay.measure(&overlay_constraint)?.size();
+            let position = placement.position_within(*anchor, size, main_size);
+            result.place_child(*overlay, position);
+            bounds.push((*id, PxRect::from_position_size(position, size.into())));
+        }
+        *self.bounds.write() = bounds;
+
+        Ok(result.with_size(main_size))
+    }
+}
+
+/// # overlay_host
+///
+/// Draws tooltips, popovers and other anchored overlays of its content above
+/// that content.
+///
+/// ## Usage
+///
+/// Wrap the app root once so overlays are drawn above every component, kept
+/// inside the window, and dismissed by presses outside them.
+///
+/// ## Parameters
+///
+/// - `modifier` — optional modifier chain applied to the host
+/// - `content` — optional content whose overlays the host draws
+///
+/// ## Examples
+///
+/// ```
+/// # use tessera_ui::tessera;
+/// # #[tessera]
+/// # fn component() {
+/// use tessera_components::{
+///     overlay::{OverlayAlign, OverlayPlacement, OverlaySide, overlay_host},
+///     text::text,
+/// };
+///
+/// let placement = OverlayPlacement::new(OverlaySide::Above, OverlayAlign::Center);
+/// assert_ne!(placement, OverlayPlacement::default());
+/// overlay_host().content(|| {
+///     text().content("App content");
+/// });
+/// # }
+/// # component();
+/// ```
+#[tessera]
+pub fn overlay_host(modifier: Option<Modifier>, content: Option<RenderSlot>) {
+    let content = content.unwrap_or_else(RenderSlot::empty);
+    let entries = remember(OverlayEntries::default);
+    let origin = remember(|| PxPosition::ZERO);
+    let overlays: Vec<(u64, OverlayEntry)> = entries.with(|entries| {
+        entries
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.is_alive())
+            .map(|(id, entry)| (*id, entry.clone()))
+            .collect()
+    });
+    let host_origin = origin.get();
+    let bounds: Arc<RwLock<Vec<(u64, PxRect)>>> = remember(Arc::default).get();
+
+    let modifier = with_pointer_preview_input(modifier.unwrap_or_default(), {
+        let bounds = bounds.clone();
+        move |mut input| {
+            if let (Some(abs), Some(rel)) = (input.cursor_position_abs(), input.cursor_position_rel)
+            {
+                let latest = abs - rel;
+                if origin.get() != latest {
+                    origin.set(latest);
+                }
+            }
+            // Owners retire their overlays when dropped, without a build of
+            // the host to observe it.
+            if entries.with(|entries| entries.entries.values().any(|entry| !entry.is_alive())) {
+                entries.with_mut(|entries| entries.entries.retain(|_, entry| entry.is_alive()));
+            }
+
+            let pressed = input.pointer_changes.iter().any(|change| {
+                !change.is_consumed() && matches!(change.content, CursorEventContent::Pressed(_))
+            });
+            let Some(cursor) = input.cursor_position_rel.filter(|_| pressed) else {
+                return;
+            };
+            let on_dismiss = entries.with(|entries| {
+                bounds.read().iter().rev().find_map(|(id, rect)| {
+                    let entry = entries.entries.get(id).filter(|entry| entry.is_alive())?;
+                    let on_dismiss = entry.request.on_dismiss?;
+                    Some((!rect.contains(cursor)).then_some(on_dismiss))
+                })
+            });
+            if let Some(Some(on_dismiss)) = on_dismiss {
+                on_dismiss.call();
+                input.consume_pointer_changes();
+            }
+        }
+    });
+
+    layout()
+        .modifier(modifier)
+        .layout_policy(OverlayHostLayout {
+            overlays: overlays
+                .iter()
+                .map(|(id, entry)| {
+                    let anchor = entry.request.anchor;
+                    let anchor = PxRect {
+                        x: anchor.x - host_origin.x,
+                        y: anchor.y - host_origin.y,
+                        ..anchor
+                    };
+                    (*id, anchor, entry.request.placement)
+                })
+                .collect(),
+            bounds,
+        })
+        .child(move || {
+            provide_context(
+                || OverlayHost { entries },
+                || {
+                    content.render();
+                    for (id, entry) in overlays.clone() {
+                        key(id, || entry.request.content.render());
+                    }
+                },
+            );
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use tessera_ui::{ComputedData, Px, PxPosition, PxRect};
+
+    use super::{OverlayAlign, OverlayPlacement, OverlaySide};
+
+    #[test]
+    fn overlay_flips_and_shifts_to_stay_in_bounds() {
+        let bounds = ComputedData {
+            width: Px(200),
+            height: Px(100),
+        };
+        let size = ComputedData {
+            width: Px(80),
+            height: Px(30),
+        };
+        let placement = OverlayPlacement::new(OverlaySide::Above, OverlayAlign::Center);
+
+        let anchor = PxRect::new(Px(60), Px(50), Px(40), Px(20));
+        assert_eq!(
+            placement.position_within(anchor, size, bounds),
+            PxPosition::new(Px(40), Px(20))
+        );
+
+        let anchor = PxRect::new(Px(170), Px(10), Px(20), Px(20));
+        assert_eq!(
+            placement.position_within(anchor, size, bounds),
+            PxPosition::new(Px(120), Px(30))
+        );
+    }
+}
@@ -3,16 +3,18 @@
 //! ## Usage
 //!
 //! Show onboarding steps or media carousels that snap between pages.
-use tessera_foundation::gesture::{
-    DragAxis, DragRecognizer, DragSettings, ScrollRecognizer, ScrollSettings,
-};
 use tessera_ui::{
     AxisConstraint, CallbackWith, ComputedData, Constraint, Dp, FocusProperties, KeyboardInput,
     KeyboardInputModifierNode, LayoutResult, MeasurementError, Modifier, PointerInput,
-    PointerInputModifierNode, Px, PxPosition, ScrollDeltaUnit, ScrollEventSource, State, key,
+    PointerInputModifierNode, Px, PxPosition, ScrollDeltaUnit, ScrollEventSource, State,
+    VelocityTracker,
+    gesture::{DragAxis, DragRecognizer, DragSettings, ScrollRecognizer, ScrollSettings},
+    key,
     layout::{LayoutPolicy, MeasureScope, PlacementScope, RenderInput, RenderPolicy, layout},
     modifier::{FocusModifierExt as _, ModifierCapabilityExt as _},
-    normalize_platform_scroll_delta, receive_frame_nanos, remember, tessera, winit,
+    normalize_platform_scroll_delta, receive_frame_nanos, remember, tessera,
+    time::{self, Instant},
+    winit,
 };
 
 use crate::{
@@ -22,6 +24,7 @@ use crate::{
 const DEFAULT_SNAP_THRESHOLD: f32 = 0.5;
 const DEFAULT_SCROLL_SMOOTHING: f32 = 0.12;
 const SNAP_IDLE_TIME_NANOS: u64 = 120_000_000;
+const PAGE_FLING_VELOCITY_THRESHOLD: f32 = 400.0;
 
 /// Describes how a pager page is sized along the scroll axis.
 #[derive(Clone, Copy, Debug, PartialEq, Default)]
@@ -150,6 +153,8 @@ pub struct PagerController {
     last_frame_nanos: Option<u64>,
     last_scroll_frame_nanos: Option<u64>,
     is_dragging: bool,
+    velocity_tracker: VelocityTracker,
+    fling_target_page: Option<usize>,
     initialized: bool,
 }
 
@@ -167,6 +172,8 @@ impl PagerController {
             last_frame_nanos: None,
             last_scroll_frame_nanos: None,
             is_dragging: false,
+            velocity_tracker: VelocityTracker::new(),
+            fling_target_page: None,
             initialized: false,
         }
     }
@@ -185,6 +192,7 @@ impl PagerController {
     pub fn jump_to_page(&mut self, page: usize) {
         let page = self.clamp_page(page);
         self.current_page = page;
+        self.fling_target_page = None;
         let offset = self.offset_for_page(page);
         self.scroll_offset = offset;
         self.target_offset = offset;
@@ -195,6 +203,7 @@ impl PagerController {
     /// Scrolls toward the requested page using snap smoothing.
     pub fn scroll_to_page(&mut self, page: usize) {
         let page = self.clamp_page(page);
+        self.fling_target_page = None;
         self.target_offset = self.offset_for_page(page);
         self.last_scroll_frame_nanos = None;
     }
@@ -261,7 +270,9 @@ impl PagerController {
             .unwrap_or(true);
 
         if idle && !self.is_dragging {
-            let target_page = self.snap_target_page(snap_threshold);
+            let target_page = self
+                .fling_target_page
+                .unwrap_or_else(|| self.snap_target_page(snap_threshold));
             self.target_offset = self.offset_for_page(target_page);
         }
 
@@ -297,16 +308,52 @@ impl PagerController {
         self.scroll_offset = self.clamp_offset(self.scroll_offset + delta);
         self.target_offset = self.scroll_offset;
         self.last_scroll_frame_nanos = Some(frame_nanos);
+        self.fling_target_page = None;
         self.update_current_page_from_offset();
     }
 
-    fn start_drag(&mut self, frame_nanos: u64) {
+    fn apply_drag_delta(&mut self, delta: f32, frame_nanos: u64, timestamp: Instant) {
+        self.velocity_tracker.add_delta(timestamp, delta, 0.0);
+        self.apply_scroll_delta(delta, frame_nanos);
+    }
+
+    fn start_drag(&mut self, frame_nanos: u64, timestamp: Instant) {
         self.is_dragging = true;
         self.last_scroll_frame_nanos = Some(frame_nanos);
+        self.velocity_tracker.reset();
+        self.velocity_tracker.add_delta(timestamp, 0.0, 0.0);
     }
 
     fn end_drag(&mut self) {
         self.is_dragging = false;
+        self.velocity_tracker.reset();
+    }
+
+    /// Ends a drag and, when released fast enough, targets the neighbouring
+    /// page in the fling direction regardless of the snap threshold.
+    fn end_drag_with_fling(&mut self, now: Instant) {
+        let velocity = self
+            .velocity_tracker
+            .velocity(now)
+            .map_or(0.0, |(velocity, _)| velocity);
+        self.end_drag();
+        let distance = self.page_distance();
+        if velocity.abs() < PAGE_FLING_VELOCITY_THRESHOLD
+            || distance <= f32::EPSILON
+            || self.page_count == 0
+        {
+            return;
+        }
+        let page_float = -self.scroll_offset / distance;
+        let target = if velocity < 0.0 {
+            page_float.ceil()
+        } else {
+            page_float.floor()
+        };
+        if target.is_finite() {
+            let max_page = self.page_count.saturating_sub(1) as f32;
+            self.fling_target_page = Some(target.clamp(0.0, max_page) as usize);
+        }
     }
 
     fn is_dragging(&self) -> bool {
@@ -805,9 +852,10 @@ impl PointerInputModifierNode for PagerPointerModifierNode {
                 is_cursor_in_component,
             )
         });
+        let now = time::now();
         if drag_result.started {
             self.controller
-                .with_mut(|controller| controller.start_drag(frame_nanos));
+                .with_mut(|controller| controller.start_drag(frame_nanos, now));
         }
 
         let drag_delta = self
@@ -815,11 +863,12 @@ impl PointerInputModifierNode for PagerPointerModifierNode {
             .scroll_delta(drag_result.delta_x.to_f32(), drag_result.delta_y.to_f32());
         if drag_result.updated && drag_delta.abs() >= 0.01 {
             self.controller
-                .with_mut(|controller| controller.apply_scroll_delta(drag_delta, frame_nanos));
+                .with_mut(|controller| controller.apply_drag_delta(drag_delta, frame_nanos, now));
         }
 
         if drag_result.ended {
-            self.controller.with_mut(|controller| controller.end_drag());
+            self.controller
+                .with_mut(|controller| controller.end_drag_with_fling(now));
         }
     }
 }
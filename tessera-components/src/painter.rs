@@ -1,4 +1,4 @@
-//! Unified painter content for vector and raster imagery.
+//! Unified painter content for vector, SVG and raster imagery.
 //!
 //! ## Usage
 //!
@@ -14,6 +14,7 @@ use thiserror::Error;
 use crate::{
     image::{ImageData, ImageLoadError, TryIntoImageData},
     image_vector::{ImageVectorData, ImageVectorLoadError, TryIntoImageVectorData},
+    svg::{SvgData, TryIntoSvgData},
 };
 
 /// Shared visual content that can be rendered by image-like components.
@@ -23,6 +24,8 @@ pub enum Painter {
     Vector(Arc<ImageVectorData>),
     /// Raster content backed by decoded image pixels.
     Raster(Arc<ImageData>),
+    /// SVG content rasterized at the laid-out size.
+    Svg(Arc<SvgData>),
 }
 
 impl From<ImageVectorData> for Painter {
@@ -49,6 +52,18 @@ impl From<Arc<ImageData>> for Painter {
     }
 }
 
+impl From<SvgData> for Painter {
+    fn from(data: SvgData) -> Self {
+        Self::Svg(Arc::new(data))
+    }
+}
+
+impl From<Arc<SvgData>> for Painter {
+    fn from(data: Arc<SvgData>) -> Self {
+        Self::Svg(data)
+    }
+}
+
 impl From<crate::material_icons::Asset> for Painter {
     fn from(asset: crate::material_icons::Asset) -> Self {
         Self::Vector(asset.into())
@@ -130,6 +145,18 @@ impl TryIntoPainter for Arc<ImageVectorData> {
     }
 }
 
+impl TryIntoPainter for SvgData {
+    fn try_into_painter(self) -> Result<Painter, PainterLoadError> {
+        Ok(Painter::from(self))
+    }
+}
+
+impl TryIntoPainter for Arc<SvgData> {
+    fn try_into_painter(self) -> Result<Painter, PainterLoadError> {
+        Ok(Painter::from(self))
+    }
+}
+
 impl TryIntoPainter for Vec<u8> {
     fn try_into_painter(self) -> Result<Painter, PainterLoadError> {
         try_decode_bytes(self.as_slice())
@@ -166,11 +193,27 @@ impl TryIntoPainter for &Path {
     }
 }
 
+// Parsed SVGs that tessellation cannot draw are still renderable by resvg.
+fn falls_back_to_svg(error: &ImageVectorLoadError) -> bool {
+    matches!(
+        error,
+        ImageVectorLoadError::UnsupportedFeature(_)
+            | ImageVectorLoadError::TransformFailed
+            | ImageVectorLoadError::Tessellation(_)
+            | ImageVectorLoadError::EmptyGeometry
+    )
+}
+
 fn try_decode_bytes(bytes: &[u8]) -> Result<Painter, PainterLoadError> {
     let vector = bytes.try_into_image_vector_data();
     match vector {
         Ok(data) => Ok(Painter::Vector(data)),
         Err(vector) => {
+            if falls_back_to_svg(&vector)
+                && let Ok(data) = bytes.try_into_svg_data()
+            {
+                return Ok(Painter::Svg(data));
+            }
             let raster = bytes.try_into_image_data();
             match raster {
                 Ok(data) => Ok(Painter::Raster(Arc::new(data))),
@@ -185,6 +228,11 @@ fn try_decode_path(path: &Path) -> Result<Painter, PainterLoadError> {
     match vector {
         Ok(data) => Ok(Painter::Vector(data)),
         Err(vector) => {
+            if falls_back_to_svg(&vector)
+                && let Ok(data) = path.try_into_svg_data()
+            {
+                return Ok(Painter::Svg(data));
+            }
             let raster = path.try_into_image_data();
             match raster {
                 Ok(data) => Ok(Painter::Raster(Arc::new(data))),
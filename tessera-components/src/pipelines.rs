@@ -23,7 +23,10 @@ mod draw;
 
 /// Register all draw and compute pipelines required by this crate.
 pub fn register_pipelines(context: &mut tessera_ui::PipelineContext<'_>) {
+    text::load_bundled_fonts();
     draw::register(context);
     compute::register(context);
     composite::register(context);
+    #[cfg(feature = "video")]
+    crate::video::install_renderer(context);
 }
@@ -2,7 +2,10 @@ use bytemuck::{Pod, Zeroable};
 use encase::{ShaderType, UniformBuffer};
 use glam::{Vec2, Vec4};
 use tessera_ui::{
-    renderer::drawer::pipeline::{DrawContext, DrawablePipeline},
+    renderer::drawer::{
+        clip_depth_stencil_state,
+        pipeline::{DrawContext, DrawablePipeline},
+    },
     wgpu::{self, include_wgsl, util::DeviceExt},
 };
 
@@ -217,7 +220,7 @@ impl CheckmarkPipeline {
                 polygon_mode: wgpu::PolygonMode::Fill,
                 conservative: false,
             },
-            depth_stencil: None,
+            depth_stencil: Some(clip_depth_stencil_state()),
             multisample: wgpu::MultisampleState {
                 count: sample_count,
                 mask: !0,
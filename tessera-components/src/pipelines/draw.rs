@@ -9,7 +9,6 @@ use crate::pipelines::{
     shadow::pipeline::{ShadowCompositePipeline, ShadowMaskPipeline},
     shape::pipeline::ShapePipeline,
     simple_rect::pipeline::SimpleRectPipeline,
-    text::pipeline::GlyphonTextRender,
 };
 
 pub(super) fn register(context: &mut PipelineContext<'_>) {
@@ -18,7 +17,6 @@ pub(super) fn register(context: &mut PipelineContext<'_>) {
     register_shadow(context);
     register_progress_arc(context);
     register_checkmark(context);
-    register_text(context);
     register_fluid_glass(context);
     register_image(context);
     register_image_vector(context);
@@ -85,17 +83,6 @@ fn register_checkmark(context: &mut PipelineContext<'_>) {
     context.register_draw_pipeline(pipeline);
 }
 
-fn register_text(context: &mut PipelineContext<'_>) {
-    let resources = context.resources();
-    let pipeline = GlyphonTextRender::new(
-        resources.device,
-        resources.queue,
-        resources.surface_config,
-        resources.sample_count,
-    );
-    context.register_draw_pipeline(pipeline);
-}
-
 fn register_fluid_glass(context: &mut PipelineContext<'_>) {
     let resources = context.resources();
     let pipeline = FluidGlassPipeline::new(
@@ -6,7 +6,10 @@ use lru::LruCache;
 use tessera_ui::{
     PxPosition, PxSize,
     px::PxRect,
-    renderer::drawer::pipeline::{DrawContext, DrawablePipeline},
+    renderer::drawer::{
+        clip_depth_stencil_state,
+        pipeline::{DrawContext, DrawablePipeline},
+    },
     wgpu,
 };
 
@@ -447,7 +450,7 @@ impl FluidGlassPipeline {
                 cull_mode: Some(wgpu::Face::Back),
                 ..Default::default()
             },
-            depth_stencil: None,
+            depth_stencil: Some(clip_depth_stencil_state()),
             multisample: wgpu::MultisampleState {
                 count: sample_count,
                 mask: !0,
@@ -47,6 +47,8 @@ pub struct ImageCommand {
     pub data: Arc<ImageData>,
     /// Opacity multiplier applied to the sampled image.
     pub opacity: f32,
+    /// Clockwise rotation in degrees about the center of the draw rect.
+    pub rotation: f32,
 }
 
 impl DrawCommand for ImageCommand {
@@ -7,6 +7,8 @@ struct Uniforms {
     rect: vec4<f32>,
     is_bgra: u32,
     opacity: f32,
+    rotation: f32,
+    aspect: f32,
 };
 @group(0) @binding(2)
 var<uniform> uniforms: Uniforms;
@@ -32,7 +34,15 @@ fn vs_main(@builtin(vertex_index) in_vertex_index: u32) -> VertexOutput {
     );
 
     var out: VertexOutput;
-    let pos = vertices[in_vertex_index] * uniforms.rect.zw + uniforms.rect.xy;
+    // Rotate in pixel space so non-square targets don't shear the image.
+    let offset = vertices[in_vertex_index] * uniforms.rect.zw;
+    let c = cos(uniforms.rotation);
+    let s = sin(uniforms.rotation);
+    let rotated = vec2<f32>(
+        offset.x * c + offset.y * s / uniforms.aspect,
+        offset.y * c - offset.x * s * uniforms.aspect
+    );
+    let pos = rotated + uniforms.rect.xy;
     out.clip_position = vec4<f32>(pos, 0.0, 1.0);
     out.tex_coords = tex_coords[in_vertex_index];
     return out;
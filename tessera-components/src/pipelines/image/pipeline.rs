@@ -4,7 +4,10 @@ use encase::{ShaderType, UniformBuffer};
 use glam::Vec4;
 use tessera_ui::{
     PxPosition, PxSize,
-    renderer::drawer::pipeline::{DrawContext, DrawablePipeline},
+    renderer::drawer::{
+        clip_depth_stencil_state,
+        pipeline::{DrawContext, DrawablePipeline},
+    },
     wgpu,
 };
 
@@ -15,6 +18,8 @@ struct ImageUniforms {
     rect: Vec4,
     is_bgra: u32,
     opacity: f32,
+    rotation: f32,
+    aspect: f32,
 }
 
 struct ImageResources {
@@ -100,7 +105,7 @@ impl ImagePipeline {
                 compilation_options: Default::default(),
             }),
             primitive: wgpu::PrimitiveState::default(),
-            depth_stencil: None,
+            depth_stencil: Some(clip_depth_stencil_state()),
             multisample: wgpu::MultisampleState {
                 count: sample_count,
                 mask: !0,
@@ -137,6 +142,7 @@ impl ImagePipeline {
         target_size: PxSize,
         config: &wgpu::SurfaceConfiguration,
         opacity: f32,
+        rotation: f32,
     ) -> ImageUniforms {
         // Convert pixel positions/sizes into normalized device coordinates and size
         // ratios.
@@ -159,6 +165,8 @@ impl ImagePipeline {
             rect,
             is_bgra: if is_bgra { 1 } else { 0 },
             opacity,
+            rotation: rotation.to_radians(),
+            aspect: target_size.width.to_f32() / target_size.height.to_f32().max(1.0),
         }
     }
 
@@ -269,6 +277,7 @@ impl DrawablePipeline<ImageCommand> for ImagePipeline {
                 context.target_size,
                 context.config,
                 command.opacity,
+                command.rotation,
             );
 
             let mut buffer = UniformBuffer::new(Vec::new());
@@ -4,7 +4,10 @@ use encase::{ShaderType, UniformBuffer};
 use glam::{Vec2, Vec4};
 use tessera_ui::{
     Color, PxPosition, PxSize,
-    renderer::drawer::pipeline::{DrawContext, DrawablePipeline},
+    renderer::drawer::{
+        clip_depth_stencil_state,
+        pipeline::{DrawContext, DrawablePipeline},
+    },
     wgpu::{self, util::DeviceExt},
 };
 
@@ -243,7 +246,7 @@ impl ImageVectorPipeline {
                 compilation_options: Default::default(),
             }),
             primitive: wgpu::PrimitiveState::default(),
-            depth_stencil: None,
+            depth_stencil: Some(clip_depth_stencil_state()),
             multisample: wgpu::MultisampleState {
                 count: sample_count,
                 mask: !0,
@@ -3,7 +3,10 @@ use glam::{Vec2, Vec4};
 use tessera_ui::{
     PxSize,
     px::PxPosition,
-    renderer::drawer::pipeline::{DrawContext, DrawablePipeline},
+    renderer::drawer::{
+        clip_depth_stencil_state,
+        pipeline::{DrawContext, DrawablePipeline},
+    },
     wgpu::{self, include_wgsl, util::DeviceExt},
 };
 
@@ -93,7 +96,7 @@ impl ProgressArcPipeline {
                 polygon_mode: wgpu::PolygonMode::Fill,
                 conservative: false,
             },
-            depth_stencil: None,
+            depth_stencil: Some(clip_depth_stencil_state()),
             multisample: wgpu::MultisampleState {
                 count: sample_count,
                 mask: !0,
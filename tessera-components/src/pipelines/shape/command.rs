@@ -1,5 +1,5 @@
 use glam::{Vec2, Vec4};
-use tessera_ui::{Color, DrawCommand, DrawRegion, PaddingRect, PxPosition, PxSize};
+use tessera_ui::{Color, DrawCommand, DrawRegion, PaddingRect, Paint, PxPosition, PxSize};
 
 use super::pipeline::ShapeUniforms;
 
@@ -105,6 +105,27 @@ pub enum ShapeCommand {
         /// Width of the border
         border_width: f32,
     },
+    /// A rectangle filled with a [`Paint`].
+    ///
+    /// Image paint textures are sampled with straight alpha and tiled by the
+    /// shader.
+    PaintedRect {
+        /// Paint of the rectangle
+        paint: Paint,
+        /// Corner radii of the rectangle (tl, tr, br, bl)
+        corner_radii: [f32; 4],
+        /// G2 exponent per corner (tl, tr, br, bl).
+        /// k=2.0 results in standard G1 circular corners.
+        corner_g2: [f32; 4],
+    },
+    /// An ellipse filled with a [`Paint`].
+    ///
+    /// Image paint textures are sampled with straight alpha and tiled by the
+    /// shader.
+    PaintedEllipse {
+        /// Paint of the ellipse
+        paint: Paint,
+    },
 }
 
 impl DrawCommand for ShapeCommand {
@@ -166,6 +187,9 @@ impl DrawCommand for ShapeCommand {
                 scale_color(color, factor);
                 scale_color(border_color, factor);
             }
+            ShapeCommand::PaintedRect { paint, .. } | ShapeCommand::PaintedEllipse { paint } => {
+                paint.apply_opacity(factor);
+            }
         }
     }
 
@@ -205,6 +229,29 @@ impl Default for RippleProps {
     }
 }
 
+impl ShapeCommand {
+    /// Returns the paint evaluated through the shared paint storage, or `None`
+    /// for shapes drawn with plain colors.
+    pub(crate) fn shader_paint(&self) -> Option<&Paint> {
+        match self {
+            ShapeCommand::PaintedRect { paint, .. } | ShapeCommand::PaintedEllipse { paint }
+                if !paint.is_solid() =>
+            {
+                Some(paint)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Returns the color used by the plain fill path for solid paints.
+fn solid_color(paint: &Paint) -> Color {
+    match paint {
+        Paint::Solid(color) => *color,
+        _ => Color::TRANSPARENT,
+    }
+}
+
 pub(crate) fn rect_to_uniforms(
     command: &ShapeCommand,
     size: PxSize,
@@ -340,6 +387,28 @@ pub(crate) fn rect_to_uniforms(
             5.0,
             None,
         ),
+        ShapeCommand::PaintedRect {
+            paint,
+            corner_radii,
+            corner_g2,
+        } => (
+            solid_color(paint),
+            Color::TRANSPARENT,
+            *corner_radii,
+            *corner_g2,
+            0.0,
+            0.0,
+            None,
+        ),
+        ShapeCommand::PaintedEllipse { paint } => (
+            solid_color(paint),
+            Color::TRANSPARENT,
+            [-1.0, -1.0, -1.0, -1.0],
+            [0.0; 4],
+            0.0,
+            0.0,
+            None,
+        ),
     };
 
     let width = size.width;
@@ -382,5 +451,6 @@ pub(crate) fn rect_to_uniforms(
         ]
         .into(),
         screen_size: Vec2::ZERO, // Will be populated in the pipeline
+        paint_index: -1,         // Assigned in the pipeline for painted shapes
     }
 }
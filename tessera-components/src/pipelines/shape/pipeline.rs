@@ -7,14 +7,18 @@
 mod cache;
 mod draw;
 
-use std::{collections::HashMap, num::NonZeroUsize, sync::Arc};
+use std::{borrow::Cow, collections::HashMap, num::NonZeroUsize, sync::Arc};
 
-use encase::ShaderType;
+use encase::{ShaderType, StorageBuffer};
 use glam::{Vec2, Vec4};
 use lru::LruCache;
 use tessera_ui::{
-    PxPosition, PxSize,
-    renderer::drawer::pipeline::{DrawContext, DrawablePipeline},
+    Paint, PxPosition, PxSize,
+    renderer::drawer::{
+        TransientBufferPool, clip_depth_stencil_state,
+        paint::{MAX_GRADIENT_STOPS, PAINT_WGSL},
+        pipeline::{DrawContext, DrawablePipeline, FrameContext},
+    },
     wgpu::{self, include_wgsl, util::DeviceExt},
 };
 
@@ -58,6 +62,34 @@ pub struct ShapeUniforms {
     pub border_width: f32,
     pub position: Vec4, // x, y, width, height
     pub screen_size: Vec2,
+    /// Index into the batch's paint storage, or -1 for plain colors.
+    pub paint_index: i32,
+}
+
+/// Storage-buffer encoding of a [`Paint`], laid out like the shared WGSL
+/// `Paint` struct.
+///
+/// Only painted shapes carry one, so plain fills keep their instance size.
+#[derive(ShaderType, Clone, Copy, Debug, Default, PartialEq)]
+pub struct ShapePaint {
+    pub header: Vec4,
+    pub geometry: Vec4,
+    pub color: Vec4,
+    pub offsets: [Vec4; MAX_GRADIENT_STOPS / 4],
+    pub colors: [Vec4; MAX_GRADIENT_STOPS],
+}
+
+impl ShapePaint {
+    pub(crate) fn from_paint(paint: &Paint) -> Self {
+        let uniform = paint.uniform();
+        Self {
+            header: uniform.header.into(),
+            geometry: uniform.geometry.into(),
+            color: uniform.color.into(),
+            offsets: uniform.offsets.map(Vec4::from),
+            colors: uniform.colors.map(Vec4::from),
+        }
+    }
 }
 
 #[derive(PartialEq, ShaderType)]
@@ -66,10 +98,21 @@ struct ShapeInstances {
     instances: Vec<ShapeUniforms>,
 }
 
+#[derive(PartialEq, ShaderType)]
+struct ShapePaints {
+    #[shader(size(runtime))]
+    paints: Vec<ShapePaint>,
+}
+
 /// Pipeline for rendering vector shapes in UI components.
 pub struct ShapePipeline {
     pipeline: wgpu::RenderPipeline,
     bind_group_layout: wgpu::BindGroupLayout,
+    paint_texture_bind_group_layout: wgpu::BindGroupLayout,
+    paint_sampler: wgpu::Sampler,
+    // Bound when a batch has no painted shapes or no image paint.
+    empty_paint_buffer: wgpu::Buffer,
+    empty_paint_texture_bind_group: wgpu::BindGroup,
     quad_vertex_buffer: wgpu::Buffer,
     quad_index_buffer: wgpu::Buffer,
     sample_count: u32,
@@ -81,6 +124,7 @@ pub struct ShapePipeline {
     heat_tracker: HashMap<ShapeCacheKey, ShapeHeatTracker>,
     current_frame: u32,
     render_format: wgpu::TextureFormat,
+    buffer_pool: TransientBufferPool,
 }
 
 impl ShapePipeline {
@@ -92,25 +136,69 @@ impl ShapePipeline {
         pipeline_cache: Option<&wgpu::PipelineCache>,
         sample_count: u32,
     ) -> Self {
-        let shader = gpu.create_shader_module(include_wgsl!("shape.wgsl"));
+        let shader = gpu.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Shape Shader"),
+            source: wgpu::ShaderSource::Wgsl(Cow::Owned(format!(
+                "{PAINT_WGSL}\n{}",
+                include_str!("shape.wgsl")
+            ))),
+        });
 
         let bind_group_layout = gpu.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            entries: &[wgpu::BindGroupLayoutEntry {
-                binding: 0,
-                visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
-                ty: wgpu::BindingType::Buffer {
-                    ty: wgpu::BufferBindingType::Storage { read_only: true },
-                    has_dynamic_offset: false,
-                    min_binding_size: None,
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
                 },
-                count: None,
-            }],
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
             label: Some("shape_bind_group_layout"),
         });
 
+        let paint_texture_bind_group_layout =
+            gpu.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("Shape Paint Texture Layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            multisampled: false,
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        },
+                        count: None,
+                    },
+                ],
+            });
+
         let pipeline_layout = gpu.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("Shape Pipeline Layout"),
-            bind_group_layouts: &[Some(&bind_group_layout)],
+            bind_group_layouts: &[
+                Some(&bind_group_layout),
+                Some(&paint_texture_bind_group_layout),
+            ],
             immediate_size: 0,
         });
 
@@ -136,7 +224,7 @@ impl ShapePipeline {
                 polygon_mode: wgpu::PolygonMode::Fill,
                 conservative: false,
             },
-            depth_stencil: None,
+            depth_stencil: Some(clip_depth_stencil_state()),
             multisample: wgpu::MultisampleState {
                 count: sample_count,
                 mask: !0,
@@ -194,6 +282,51 @@ impl ShapePipeline {
             ..Default::default()
         });
 
+        // Tiling is applied in the shader, so edges only need clamping.
+        let paint_sampler = gpu.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Shape Paint Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::MipmapFilterMode::Nearest,
+            ..Default::default()
+        });
+
+        let mut empty_paints = StorageBuffer::new(Vec::<u8>::new());
+        empty_paints
+            .write(&ShapePaints {
+                paints: vec![ShapePaint::default()],
+            })
+            .expect("buffer write failed");
+        let empty_paint_buffer = gpu.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Shape Empty Paint Buffer"),
+            contents: empty_paints.as_ref(),
+            usage: wgpu::BufferUsages::STORAGE,
+        });
+
+        let empty_paint_texture = gpu.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Shape Empty Paint Texture"),
+            size: wgpu::Extent3d {
+                width: 1,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let empty_paint_texture_bind_group = create_paint_texture_bind_group(
+            gpu,
+            &paint_texture_bind_group_layout,
+            &paint_sampler,
+            &empty_paint_texture.create_view(&wgpu::TextureViewDescriptor::default()),
+        );
+
         let cache_texture_bind_group_layout =
             gpu.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
                 label: Some("Shape Cache Texture Layout"),
@@ -264,7 +397,7 @@ impl ShapePipeline {
                 polygon_mode: wgpu::PolygonMode::Fill,
                 conservative: false,
             },
-            depth_stencil: None,
+            depth_stencil: Some(clip_depth_stencil_state()),
             multisample: wgpu::MultisampleState {
                 count: sample_count,
                 mask: !0,
@@ -287,6 +420,10 @@ impl ShapePipeline {
         Self {
             pipeline,
             bind_group_layout,
+            paint_texture_bind_group_layout,
+            paint_sampler,
+            empty_paint_buffer,
+            empty_paint_texture_bind_group,
             quad_vertex_buffer,
             quad_index_buffer,
             sample_count,
@@ -300,11 +437,38 @@ impl ShapePipeline {
             heat_tracker: HashMap::new(),
             current_frame: 0,
             render_format: config.format,
+            buffer_pool: TransientBufferPool::new("Shape Transient Buffer"),
         }
     }
 }
 
+fn create_paint_texture_bind_group(
+    gpu: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    sampler: &wgpu::Sampler,
+    view: &wgpu::TextureView,
+) -> wgpu::BindGroup {
+    gpu.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("shape_paint_texture_bind_group"),
+        layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::Sampler(sampler),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::TextureView(view),
+            },
+        ],
+    })
+}
+
 impl DrawablePipeline<ShapeCommand> for ShapePipeline {
+    fn begin_frame(&mut self, _context: &FrameContext<'_>) {
+        self.buffer_pool.begin_frame();
+    }
+
     fn draw(&mut self, context: &mut DrawContext<ShapeCommand>) {
         if context.commands.is_empty() {
             return;
@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use encase::{ShaderSize, StorageBuffer};
 use glam::Vec2;
-use tessera_ui::{Color, PxPosition, PxSize, wgpu};
+use tessera_ui::{Color, PxPosition, PxSize, renderer::drawer::CLIP_STENCIL_FORMAT, wgpu};
 
 use super::{
     super::command::{RippleProps, ShapeCommand, rect_to_uniforms},
@@ -222,6 +222,8 @@ impl ShapeCacheKey {
                 width,
                 height,
             }),
+            // Gradients are cheap to evaluate and rarely static enough to cache.
+            ShapeCommand::PaintedRect { .. } | ShapeCommand::PaintedEllipse { .. } => None,
         }
     }
 }
@@ -294,6 +296,31 @@ impl ShapePipeline {
             view_formats: &[],
         });
         let cache_view = cache_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        // The shape pipeline tests against the clip stencil, so the cache pass
+        // needs an (always empty) stencil attachment as well.
+        let stencil_texture = gpu.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Shape Cache Stencil Texture"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: self.sample_count,
+            dimension: wgpu::TextureDimension::D2,
+            format: CLIP_STENCIL_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let stencil_view = stencil_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let stencil_attachment = wgpu::RenderPassDepthStencilAttachment {
+            view: &stencil_view,
+            depth_ops: None,
+            stencil_ops: Some(wgpu::Operations {
+                load: wgpu::LoadOp::Clear(0),
+                store: wgpu::StoreOp::Discard,
+            }),
+        };
 
         let mut uniforms = rect_to_uniforms(command, size, PxPosition::ZERO);
         uniforms.screen_size = [width as f32, height as f32].into();
@@ -315,10 +342,16 @@ impl ShapePipeline {
 
         let bind_group = gpu.create_bind_group(&wgpu::BindGroupDescriptor {
             layout: &self.bind_group_layout,
-            entries: &[wgpu::BindGroupEntry {
-                binding: 0,
-                resource: storage_buffer.as_entire_binding(),
-            }],
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: storage_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: self.empty_paint_buffer.as_entire_binding(),
+                },
+            ],
             label: Some("shape_cache_bind_group"),
         });
 
@@ -329,6 +362,7 @@ impl ShapePipeline {
         let run_pass = |pass: &mut wgpu::RenderPass<'_>| {
             pass.set_pipeline(&self.pipeline);
             pass.set_bind_group(0, &bind_group, &[]);
+            pass.set_bind_group(1, &self.empty_paint_texture_bind_group, &[]);
             pass.set_vertex_buffer(0, self.quad_vertex_buffer.slice(..));
             pass.set_index_buffer(self.quad_index_buffer.slice(..), wgpu::IndexFormat::Uint16);
             pass.draw_indexed(0..6, 0, 0..uniforms.instances.len() as u32);
@@ -363,6 +397,7 @@ impl ShapePipeline {
                         },
                         depth_slice: None,
                     })],
+                    depth_stencil_attachment: Some(stencil_attachment),
                     ..Default::default()
                 });
                 run_pass(&mut pass);
@@ -379,6 +414,7 @@ impl ShapePipeline {
                     },
                     depth_slice: None,
                 })],
+                depth_stencil_attachment: Some(stencil_attachment),
                 ..Default::default()
             });
             run_pass(&mut pass);
@@ -1,12 +1,13 @@
 use std::sync::Arc;
 
-use encase::{ShaderSize, ShaderType, StorageBuffer};
+use encase::{ShaderType, StorageBuffer};
 use glam::{Vec2, Vec4};
 use tessera_ui::{PxPosition, PxSize, wgpu};
 
 use super::{
     super::command::{ShapeCommand, rect_to_uniforms},
-    CachedInstanceBatch, ShapeCacheEntry, ShapeInstances, ShapePipeline, ShapeUniforms,
+    CachedInstanceBatch, ShapeCacheEntry, ShapeInstances, ShapePaint, ShapePaints, ShapePipeline,
+    ShapeUniforms, create_paint_texture_bind_group,
 };
 
 #[repr(C)]
@@ -26,20 +27,61 @@ struct CachedRectInstances {
 fn build_instances(
     commands: &[(&ShapeCommand, PxSize, PxPosition)],
     target_size: PxSize,
-) -> Vec<ShapeUniforms> {
-    commands
+) -> (Vec<ShapeUniforms>, Vec<ShapePaint>) {
+    let mut paints = Vec::new();
+    let instances = commands
         .iter()
-        .flat_map(|(command, size, start_pos)| {
+        .map(|(command, size, start_pos)| {
             let mut uniforms = rect_to_uniforms(command, *size, *start_pos);
             uniforms.screen_size = [target_size.width.to_f32(), target_size.height.to_f32()].into();
-            vec![uniforms]
+            if let Some(paint) = command.shader_paint() {
+                uniforms.paint_index = paints.len() as i32;
+                paints.push(ShapePaint::from_paint(paint));
+            }
+            uniforms
         })
-        .collect()
+        .collect();
+    (instances, paints)
+}
+
+/// Splits `indices` into consecutive runs that sample at most one image
+/// paint texture each, preserving draw order.
+fn image_paint_runs<'a>(
+    commands: &[(&'a ShapeCommand, PxSize, PxPosition)],
+    indices: &[usize],
+) -> Vec<(Vec<usize>, Option<&'a wgpu::TextureView>)> {
+    let mut runs: Vec<(Vec<usize>, Option<&wgpu::TextureView>)> = Vec::new();
+    for &index in indices {
+        let view = commands[index]
+            .0
+            .shader_paint()
+            .and_then(|paint| paint.image_view());
+        match runs.last_mut() {
+            Some((run, run_view)) if view.is_none() || run_view.is_none() || *run_view == view => {
+                run.push(index);
+                if view.is_some() {
+                    *run_view = view;
+                }
+            }
+            _ => runs.push((vec![index], view)),
+        }
+    }
+    runs
+}
+
+/// Binds only the written prefix of a pooled buffer so runtime-sized arrays
+/// keep their exact length.
+fn written_binding(buffer: &wgpu::Buffer, len: usize) -> wgpu::BindingResource<'_> {
+    wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+        buffer,
+        offset: 0,
+        size: wgpu::BufferSize::new(len as u64),
+    })
 }
 
 impl ShapePipeline {
     pub(super) fn draw_uncached_batch(
-        &self,
+        &mut self,
         gpu: &wgpu::Device,
         gpu_queue: &wgpu::Queue,
         target_size: PxSize,
@@ -47,48 +89,109 @@ impl ShapePipeline {
         commands: &[(&ShapeCommand, PxSize, PxPosition)],
         indices: &[usize],
     ) {
-        if indices.is_empty() {
-            return;
+        for (run, image_view) in image_paint_runs(commands, indices) {
+            self.draw_uncached_run(
+                gpu,
+                gpu_queue,
+                target_size,
+                render_pass,
+                commands,
+                &run,
+                image_view,
+            );
         }
+    }
 
+    #[allow(clippy::too_many_arguments)]
+    fn draw_uncached_run(
+        &mut self,
+        gpu: &wgpu::Device,
+        gpu_queue: &wgpu::Queue,
+        target_size: PxSize,
+        render_pass: &mut wgpu::RenderPass<'_>,
+        commands: &[(&ShapeCommand, PxSize, PxPosition)],
+        indices: &[usize],
+        image_view: Option<&wgpu::TextureView>,
+    ) {
         let subset: Vec<_> = indices.iter().map(|&i| commands[i]).collect();
-        let instances = build_instances(&subset, target_size);
+        let (instances, paints) = build_instances(&subset, target_size);
         if instances.is_empty() {
             return;
         }
 
-        let storage_buffer = gpu.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Shape Storage Buffer"),
-            size: 16 + ShapeUniforms::SHADER_SIZE.get() * instances.len() as u64,
-            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
-
         let uniforms = ShapeInstances { instances };
         let mut buffer_content = StorageBuffer::new(Vec::<u8>::new());
         buffer_content
             .write(&uniforms)
             .expect("buffer write failed");
-        gpu_queue.write_buffer(&storage_buffer, 0, buffer_content.as_ref());
+        let content = buffer_content.as_ref();
+        let storage_buffer = self.buffer_pool.acquire(
+            gpu,
+            content.len() as u64,
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        );
+        gpu_queue.write_buffer(&storage_buffer, 0, content);
+
+        let paint_buffer = if paints.is_empty() {
+            None
+        } else {
+            let mut paint_content = StorageBuffer::new(Vec::<u8>::new());
+            paint_content
+                .write(&ShapePaints { paints })
+                .expect("buffer write failed");
+            let paint_content = paint_content.into_inner();
+            let buffer = self.buffer_pool.acquire(
+                gpu,
+                paint_content.len() as u64,
+                wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            );
+            gpu_queue.write_buffer(&buffer, 0, &paint_content);
+            Some((buffer, paint_content.len()))
+        };
+        let paint_binding = match &paint_buffer {
+            Some((buffer, len)) => written_binding(buffer, *len),
+            None => self.empty_paint_buffer.as_entire_binding(),
+        };
 
         let bind_group = gpu.create_bind_group(&wgpu::BindGroupDescriptor {
             layout: &self.bind_group_layout,
-            entries: &[wgpu::BindGroupEntry {
-                binding: 0,
-                resource: storage_buffer.as_entire_binding(),
-            }],
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: written_binding(&storage_buffer, content.len()),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: paint_binding,
+                },
+            ],
             label: Some("shape_bind_group"),
         });
+        let image_bind_group = image_view.map(|view| {
+            create_paint_texture_bind_group(
+                gpu,
+                &self.paint_texture_bind_group_layout,
+                &self.paint_sampler,
+                view,
+            )
+        });
 
         render_pass.set_pipeline(&self.pipeline);
         render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.set_bind_group(
+            1,
+            image_bind_group
+                .as_ref()
+                .unwrap_or(&self.empty_paint_texture_bind_group),
+            &[],
+        );
         render_pass.set_vertex_buffer(0, self.quad_vertex_buffer.slice(..));
         render_pass.set_index_buffer(self.quad_index_buffer.slice(..), wgpu::IndexFormat::Uint16);
         render_pass.draw_indexed(0..6, 0, 0..uniforms.instances.len() as u32);
     }
 
     pub(super) fn draw_cached_run(
-        &self,
+        &mut self,
         gpu: &wgpu::Device,
         gpu_queue: &wgpu::Queue,
         target_size: PxSize,
@@ -120,19 +223,19 @@ impl ShapePipeline {
             .write(&rect_instances)
             .expect("buffer write failed");
 
-        let instance_buffer = gpu.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Shape Cache Instance Buffer"),
-            size: buffer_content.as_ref().len() as u64,
-            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
-        gpu_queue.write_buffer(&instance_buffer, 0, buffer_content.as_ref());
+        let content = buffer_content.as_ref();
+        let instance_buffer = self.buffer_pool.acquire(
+            gpu,
+            content.len() as u64,
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        );
+        gpu_queue.write_buffer(&instance_buffer, 0, content);
 
         let transform_bind_group = gpu.create_bind_group(&wgpu::BindGroupDescriptor {
             layout: &self.cache_transform_bind_group_layout,
             entries: &[wgpu::BindGroupEntry {
                 binding: 0,
-                resource: instance_buffer.as_entire_binding(),
+                resource: written_binding(&instance_buffer, content.len()),
             }],
             label: Some("shape_cache_transform_bind_group"),
         });
@@ -158,3 +261,40 @@ impl ShapePipeline {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use tessera_ui::{Color, Paint, Px};
+
+    use super::*;
+
+    #[test]
+    fn only_painted_shapes_get_paint_entries() {
+        let solid = ShapeCommand::Rect {
+            color: Color::RED,
+            corner_radii: [0.0; 4],
+            corner_g2: [2.0; 4],
+        };
+        let solid_paint = ShapeCommand::PaintedEllipse {
+            paint: Paint::Solid(Color::BLUE),
+        };
+        let gradient = ShapeCommand::PaintedEllipse {
+            paint: Paint::horizontal(&[Color::RED, Color::BLUE]),
+        };
+        let size = PxSize::new(Px(10), Px(10));
+        let commands = [
+            (&solid, size, PxPosition::ZERO),
+            (&gradient, size, PxPosition::ZERO),
+            (&solid_paint, size, PxPosition::ZERO),
+        ];
+
+        let (instances, paints) = build_instances(&commands, size);
+
+        let indices: Vec<i32> = instances
+            .iter()
+            .map(|uniforms| uniforms.paint_index)
+            .collect();
+        assert_eq!(indices, vec![-1, 0, -1]);
+        assert_eq!(paints.len(), 1);
+    }
+}
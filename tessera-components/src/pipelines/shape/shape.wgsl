@@ -1,3 +1,6 @@
+// `Paint`, `paint_color` and `paint_image_uv` are provided by the shared paint
+// helpers prepended at pipeline creation.
+
 struct ShapeUniforms {
     corner_radii: vec4f,       // x:tl, y:tr, z:br, w:bl
     corner_g2: vec4f,          // x:tl, y:tr, z:br, w:bl
@@ -9,6 +12,7 @@ struct ShapeUniforms {
     border_width: f32,
     position: vec4f,           // x, y, width, height
     screen_size: vec2f,
+    paint_index: i32,          // -1 for plain colors
 };
 
 const MODE_FILL: f32 = 0.0;
@@ -26,6 +30,15 @@ struct ShapeInstances {
 @group(0) @binding(0)
 var<storage, read> uniforms: ShapeInstances;
 
+// Paints of the painted shapes in this batch, indexed by `paint_index`.
+@group(0) @binding(1)
+var<storage, read> paints: array<Paint>;
+
+@group(1) @binding(0)
+var paint_sampler: sampler;
+@group(1) @binding(1)
+var paint_texture: texture_2d<f32>;
+
 struct VertexInput {
     @location(0) position: vec2f,
     @builtin(instance_index) instance_index: u32,
@@ -185,6 +198,21 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4f {
         if shape_mask <= EPS_DISCARD {
             discard;
         }
+        if instance.paint_index >= 0 {
+            let paint = paints[instance.paint_index];
+            let local_px = p_object + half_size;
+            if paint_is_image(paint) {
+                let uv = paint_image_uv(paint, local_px, size);
+                if uv.x < 0.0 || uv.y < 0.0 {
+                    discard;
+                }
+                // Explicit LOD because this branch is not in uniform control flow.
+                let texel = textureSampleLevel(paint_texture, paint_sampler, uv, 0.0) * paint.color;
+                return vec4f(texel.rgb * texel.a, texel.a) * shape_mask;
+            }
+            // `paint_color` is already premultiplied.
+            return paint_color(paint, local_px, size) * shape_mask;
+        }
         final_color = vec4f(instance.primary_color.rgb, instance.primary_color.a * shape_mask);
     } else if mode == MODE_OUTLINE {
         if instance.border_width <= 0.0 {
@@ -1,4 +1,4 @@
-use tessera_ui::{Color, DrawCommand};
+use tessera_ui::{Color, DrawCommand, InstancedDrawCommand, PxPosition, PxSize};
 
 /// Draw command for the simple rectangle pipeline.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -7,6 +7,15 @@ pub struct SimpleRectCommand {
     pub color: Color,
 }
 
+/// Per-instance data for [`SimpleRectCommand`].
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct SimpleRectInstance {
+    rect: [f32; 4],
+    color: [f32; 4],
+    screen_size: [f32; 2],
+}
+
 impl DrawCommand for SimpleRectCommand {
     fn apply_opacity(&mut self, opacity: f32) {
         self.color = self
@@ -14,3 +23,20 @@ impl DrawCommand for SimpleRectCommand {
             .with_alpha(self.color.a * opacity.clamp(0.0, 1.0));
     }
 }
+
+impl InstancedDrawCommand for SimpleRectCommand {
+    type Instance = SimpleRectInstance;
+
+    fn instance(&self, size: PxSize, position: PxPosition, target_size: PxSize) -> Self::Instance {
+        SimpleRectInstance {
+            rect: [
+                position.x.to_f32(),
+                position.y.to_f32(),
+                size.width.to_f32(),
+                size.height.to_f32(),
+            ],
+            color: self.color.to_array(),
+            screen_size: [target_size.width.to_f32(), target_size.height.to_f32()],
+        }
+    }
+}
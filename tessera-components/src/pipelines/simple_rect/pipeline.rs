@@ -1,13 +1,12 @@
-use encase::{ShaderSize, ShaderType, StorageBuffer};
-use glam::{Vec2, Vec4};
 use tessera_ui::{
-    PxSize,
-    px::PxPosition,
-    renderer::drawer::pipeline::{DrawContext, DrawablePipeline},
+    renderer::drawer::{
+        InstanceBuffer, clip_depth_stencil_state,
+        pipeline::{DrawContext, DrawablePipeline, FrameContext},
+    },
     wgpu::{self, include_wgsl, util::DeviceExt},
 };
 
-use super::command::SimpleRectCommand;
+use super::command::{SimpleRectCommand, SimpleRectInstance};
 
 #[repr(C)]
 #[derive(Copy, Clone, PartialEq, Debug, bytemuck::Pod, bytemuck::Zeroable)]
@@ -15,25 +14,12 @@ struct Vertex {
     position: [f32; 2],
 }
 
-#[derive(ShaderType, Clone, Copy, Debug, PartialEq)]
-struct RectUniform {
-    position: Vec4,
-    color: Vec4,
-    screen_size: Vec2,
-}
-
-#[derive(PartialEq, ShaderType)]
-struct RectInstances {
-    #[shader(size(runtime))]
-    instances: Vec<RectUniform>,
-}
-
 /// Render pipeline for drawing batches of simple rectangles.
 pub struct SimpleRectPipeline {
     pipeline: wgpu::RenderPipeline,
-    bind_group_layout: wgpu::BindGroupLayout,
     quad_vertex_buffer: wgpu::Buffer,
     quad_index_buffer: wgpu::Buffer,
+    instances: InstanceBuffer<SimpleRectInstance>,
 }
 
 impl SimpleRectPipeline {
@@ -46,23 +32,9 @@ impl SimpleRectPipeline {
     ) -> Self {
         let shader = gpu.create_shader_module(include_wgsl!("simple_rect.wgsl"));
 
-        let bind_group_layout = gpu.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            entries: &[wgpu::BindGroupLayoutEntry {
-                binding: 0,
-                visibility: wgpu::ShaderStages::VERTEX,
-                ty: wgpu::BindingType::Buffer {
-                    ty: wgpu::BufferBindingType::Storage { read_only: true },
-                    has_dynamic_offset: false,
-                    min_binding_size: None,
-                },
-                count: None,
-            }],
-            label: Some("simple_rect_bind_group_layout"),
-        });
-
         let pipeline_layout = gpu.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("Simple Rect Pipeline Layout"),
-            bind_group_layouts: &[Some(&bind_group_layout)],
+            bind_group_layouts: &[],
             immediate_size: 0,
         });
 
@@ -72,11 +44,22 @@ impl SimpleRectPipeline {
             vertex: wgpu::VertexState {
                 module: &shader,
                 entry_point: Some("vs_main"),
-                buffers: &[wgpu::VertexBufferLayout {
-                    array_stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,
-                    step_mode: wgpu::VertexStepMode::Vertex,
-                    attributes: &wgpu::vertex_attr_array![0 => Float32x2],
-                }],
+                buffers: &[
+                    wgpu::VertexBufferLayout {
+                        array_stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,
+                        step_mode: wgpu::VertexStepMode::Vertex,
+                        attributes: &wgpu::vertex_attr_array![0 => Float32x2],
+                    },
+                    wgpu::VertexBufferLayout {
+                        array_stride: InstanceBuffer::<SimpleRectInstance>::stride(),
+                        step_mode: wgpu::VertexStepMode::Instance,
+                        attributes: &wgpu::vertex_attr_array![
+                            1 => Float32x4,
+                            2 => Float32x4,
+                            3 => Float32x2
+                        ],
+                    },
+                ],
                 compilation_options: Default::default(),
             },
             primitive: wgpu::PrimitiveState {
@@ -88,7 +71,7 @@ impl SimpleRectPipeline {
                 polygon_mode: wgpu::PolygonMode::Fill,
                 conservative: false,
             },
-            depth_stencil: None,
+            depth_stencil: Some(clip_depth_stencil_state()),
             multisample: wgpu::MultisampleState {
                 count: sample_count,
                 mask: !0,
@@ -137,80 +120,30 @@ impl SimpleRectPipeline {
 
         Self {
             pipeline,
-            bind_group_layout,
             quad_vertex_buffer,
             quad_index_buffer,
+            instances: InstanceBuffer::new("Simple Rect Instance Buffer"),
         }
     }
 }
 
-fn build_instances(
-    commands: &[(&SimpleRectCommand, PxSize, PxPosition)],
-    target_size: PxSize,
-) -> Vec<RectUniform> {
-    commands
-        .iter()
-        .map(|(command, size, position)| RectUniform {
-            position: Vec4::new(
-                position.x.raw() as f32,
-                position.y.raw() as f32,
-                size.width.raw() as f32,
-                size.height.raw() as f32,
-            ),
-            color: Vec4::from_array(command.color.to_array()),
-            screen_size: Vec2::new(target_size.width.to_f32(), target_size.height.to_f32()),
-        })
-        .collect()
-}
-
 impl DrawablePipeline<SimpleRectCommand> for SimpleRectPipeline {
-    fn draw(&mut self, context: &mut DrawContext<SimpleRectCommand>) {
-        if context.commands.is_empty() {
-            return;
-        }
+    fn begin_frame(&mut self, _context: &FrameContext<'_>) {
+        self.instances.reset();
+    }
 
-        let instances = build_instances(context.commands, context.target_size);
-        if instances.is_empty() {
+    fn draw(&mut self, context: &mut DrawContext<SimpleRectCommand>) {
+        let Some(instances) = context.bind_instances(&mut self.instances, 1) else {
             return;
-        }
-
-        let uniform_buffer = context.device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Simple Rect Storage Buffer"),
-            size: 16 + RectUniform::SHADER_SIZE.get() * instances.len() as u64,
-            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
-
-        let uniforms = RectInstances { instances };
-        let mut buffer_content = StorageBuffer::new(Vec::<u8>::new());
-        buffer_content
-            .write(&uniforms)
-            .expect("buffer write failed");
-        context
-            .queue
-            .write_buffer(&uniform_buffer, 0, buffer_content.as_ref());
-
-        let bind_group = context
-            .device
-            .create_bind_group(&wgpu::BindGroupDescriptor {
-                layout: &self.bind_group_layout,
-                entries: &[wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: uniform_buffer.as_entire_binding(),
-                }],
-                label: Some("simple_rect_bind_group"),
-            });
+        };
 
         context.render_pass.set_pipeline(&self.pipeline);
-        context.render_pass.set_bind_group(0, &bind_group, &[]);
         context
             .render_pass
             .set_vertex_buffer(0, self.quad_vertex_buffer.slice(..));
         context
             .render_pass
             .set_index_buffer(self.quad_index_buffer.slice(..), wgpu::IndexFormat::Uint16);
-        context
-            .render_pass
-            .draw_indexed(0..6, 0, 0..context.commands.len() as u32);
+        context.render_pass.draw_indexed(0..6, 0, instances);
     }
 }
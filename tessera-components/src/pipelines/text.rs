@@ -1,4 +1,113 @@
-//! Text rendering pipeline.
+//! Bundled fonts for the core text pipeline.
+//!
+//! Text shaping and rendering live in
+//! [`tessera_ui::renderer::drawer::text`]. This module only loads the fonts
+//! shipped with this crate into the shared font system on platforms whose
+//! system fonts are missing or unusable.
 
-pub mod command;
-pub mod pipeline;
+#[cfg(target_os = "android")]
+use glyphon::fontdb;
+#[cfg(target_os = "android")]
+use tessera_ui::renderer::drawer::text::write_font_system;
+#[cfg(target_family = "wasm")]
+use tessera_ui::renderer::drawer::text::{FontFamily, FontRegistry, font_registry};
+
+#[cfg(target_family = "wasm")]
+use crate::res;
+#[cfg(target_family = "wasm")]
+use std::io;
+#[cfg(target_family = "wasm")]
+use tessera_ui::AssetExt;
+
+#[cfg(target_os = "android")]
+fn is_android_emoji_face(face: &fontdb::FaceInfo) -> bool {
+    let post_script = face.post_script_name.as_str();
+    if post_script.contains("NotoColorEmoji") {
+        return true;
+    }
+
+    face.families
+        .iter()
+        .any(|(name, _)| name.contains("Noto Color Emoji"))
+}
+
+#[cfg(target_os = "android")]
+fn remove_system_emoji_faces(db: &mut fontdb::Database) -> usize {
+    let ids: Vec<_> = db
+        .faces()
+        .filter(|face| {
+            matches!(
+                &face.source,
+                fontdb::Source::File(_) | fontdb::Source::SharedFile(_, _)
+            ) && is_android_emoji_face(face)
+        })
+        .map(|face| face.id)
+        .collect();
+
+    // Android ships COLRv1 emoji fonts, which swash cannot rasterize yet.
+    for id in &ids {
+        db.remove_face(*id);
+    }
+
+    ids.len()
+}
+
+#[cfg(target_os = "android")]
+fn load_embedded_android_emoji_font(db: &mut fontdb::Database) -> usize {
+    let before = db.len();
+    let data = include_bytes_zstd::include_bytes_zstd!("assets/NotoColorEmoji_COLRv0.ttf", 19);
+    if data.is_empty() {
+        return 0;
+    }
+    db.load_font_data(data);
+    db.len().saturating_sub(before)
+}
+
+/// Replaces the system emoji font with the bundled COLRv0 one.
+#[cfg(target_os = "android")]
+pub(super) fn load_bundled_fonts() {
+    let mut font_system = write_font_system();
+    let db = font_system.db_mut();
+    // Use the bundled COLRv0 emoji font to avoid COLRv1 rendering gaps.
+    remove_system_emoji_faces(db);
+    load_embedded_android_emoji_font(db);
+}
+
+#[cfg(target_family = "wasm")]
+fn load_asset_font_data(
+    registry: &mut FontRegistry,
+    asset: res::Asset,
+) -> io::Result<Vec<FontFamily>> {
+    let bytes = asset.read()?;
+    Ok(registry.register_font_data(bytes.as_ref().to_vec()))
+}
+
+/// Loads the bundled fonts, as browsers expose no system fonts.
+#[cfg(target_family = "wasm")]
+pub(super) fn load_bundled_fonts() {
+    let mut registry = font_registry();
+    if let Err(err) = load_asset_font_data(&mut registry, res::NOTOSANSSC_REGULAR_OTF) {
+        eprintln!("wasm fontdb: failed to load Noto Sans SC asset: {err}");
+    }
+    match load_asset_font_data(&mut registry, res::NOTOCOLOREMOJI_COLRV0_TTF) {
+        Ok(families) => {
+            for family in families {
+                registry.add_common_fallback(family);
+            }
+        }
+        Err(err) => eprintln!("wasm fontdb: failed to load emoji font asset: {err}"),
+    }
+    for generic in [
+        FontFamily::SansSerif,
+        FontFamily::Serif,
+        FontFamily::Monospace,
+        FontFamily::Cursive,
+        FontFamily::Fantasy,
+    ] {
+        registry.set_generic_family(generic, "Noto Sans SC");
+    }
+}
+
+/// System fonts are used as is.
+#[cfg(not(any(target_os = "android", target_family = "wasm")))]
+pub(super) fn load_bundled_fonts() {}
@@ -0,0 +1,296 @@
+//! Popovers anchoring arbitrary content to a component.
+//!
+//! ## Usage
+//!
+//! Open dropdowns, pickers and rich previews next to the control that triggered
+//! them.
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use tessera_ui::{
+    AxisConstraint, Callback, Constraint, FocusScopeNode, FocusTraversalPolicy, MeasurementError,
+    Modifier, PlacementModifierNode, PxPosition, PxRect, PxSize, RenderSlot, State,
+    layout::{LayoutPolicy, LayoutResult, MeasureScope, layout},
+    modifier::{FocusModifierExt as _, ModifierCapabilityExt as _},
+    remember, tessera, use_context, winit,
+};
+
+use crate::{
+    modifier::with_keyboard_input,
+    overlay::{OverlayHandle, OverlayHost, OverlayPlacement, OverlayRequest},
+};
+
+/// Reports the window-space rect of a popover anchor while it is open.
+struct AnchorBoundsNode {
+    size: Arc<RwLock<PxSize>>,
+    anchor: State<Option<PxRect>>,
+}
+
+impl PartialEq for AnchorBoundsNode {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.size, &other.size) && self.anchor == other.anchor
+    }
+}
+
+impl PlacementModifierNode for AnchorBoundsNode {
+    fn transform_position(&self, position: PxPosition) -> PxPosition {
+        let rect = PxRect::from_position_size(position, *self.size.read());
+        if self.anchor.get() != Some(rect) {
+            self.anchor.set(Some(rect));
+        }
+        position
+    }
+}
+
+#[derive(Clone)]
+struct PopoverLayout {
+    placement: OverlayPlacement,
+    /// Anchor size from the last measure, read by [`AnchorBoundsNode`].
+    size: Arc<RwLock<PxSize>>,
+}
+
+impl PartialEq for PopoverLayout {
+    fn eq(&self, other: &Self) -> bool {
+        self.placement == other.placement && Arc::ptr_eq(&self.size, &other.size)
+    }
+}
+
+impl LayoutPolicy for PopoverLayout {
+    fn measure(&self, input: &MeasureScope<'_>) -> Result<LayoutResult, MeasurementError> {
+        let mut result = LayoutResult::default();
+        let children = input.children();
+        let child_constraint = input.parent_constraint().without_min();
+        let anchor = children
+            .first()
+            .copied()
+            .expect("anchor content should exist");
+        let anchor_size = anchor.measure(&child_constraint)?.size();
+        result.place_child(anchor, PxPosition::ZERO);
+        *self.size.write() = anchor_size.into();
+
+        if let Some(popup) = children.get(1).copied() {
+            let size = popup.measure(&child_constraint)?.size();
+            let anchor_rect = PxRect::from_position_size(PxPosition::ZERO, anchor_size.into());
+            result.place_child(popup, self.placement.position(anchor_rect, size));
+        }
+
+        Ok(result.with_size(anchor_size))
+    }
+}
+
+/// Gives the popup the width of its anchor.
+#[derive(Clone)]
+struct MatchAnchorWidthLayout {
+    anchor: Arc<RwLock<PxSize>>,
+}
+
+impl PartialEq for MatchAnchorWidthLayout {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.anchor, &other.anchor)
+    }
+}
+
+impl LayoutPolicy for MatchAnchorWidthLayout {
+    fn measure(&self, input: &MeasureScope<'_>) -> Result<LayoutResult, MeasurementError> {
+        let mut result = LayoutResult::default();
+        let width = self.anchor.read().width;
+        let height = input.parent_constraint().height().without_min();
+        let content = input
+            .children()
+            .first()
+            .copied()
+            .expect("popover content should exist");
+        let size = content
+            .measure(&Constraint::new(AxisConstraint::exact(width), height))?
+            .size();
+        result.place_child(content, PxPosition::ZERO);
+        Ok(result.with_size(size))
+    }
+}
+
+/// # popover
+///
+/// Anchors arbitrary content to a component while open, for dropdowns,
+/// pickers and other popups.
+///
+/// Inside an [`overlay_host`](crate::overlay::overlay_host) the popover is
+/// drawn above all content, opens on the other side of the anchor when there is
+/// no room, and a press outside it calls `on_dismiss` without reaching the
+/// content below; otherwise it is drawn with the anchor. Focus moves into the
+/// popover when it opens, Tab cycles within it, Escape calls `on_dismiss`, and
+/// focus returns to where it was when it closes. Popovers that complement a
+/// text field, such as suggestion lists, set `focus_on_open` to `false` so
+/// typing continues in the field.
+///
+/// ## Usage
+///
+/// Show a panel next to the control that opened it.
+///
+/// ## Parameters
+///
+/// - `anchor_content` — the component the popover is anchored to
+/// - `popover_content` — content shown while open; it draws its own container
+/// - `is_open` — whether the popover is shown; defaults to `false`
+/// - `on_dismiss` — optional callback when the popover asks to close; set
+///   `is_open` to `false` in response
+/// - `placement` — optional placement relative to the anchor; defaults to
+///   below, aligned to the start edge
+/// - `dismiss_on_outside_press` — whether presses outside the popover call
+///   `on_dismiss`; defaults to `true`
+/// - `focus_on_open` — whether focus moves into the popover when it opens;
+///   defaults to `true`
+/// - `match_anchor_width` — whether the popover is as wide as its anchor;
+///   defaults to `false`
+/// - `modifier` — optional modifier chain applied to the anchor container
+///
+/// ## Examples
+///
+/// ```
+/// # use tessera_ui::tessera;
+/// # #[tessera]
+/// # fn component() {
+/// use tessera_components::{
+///     button::button, overlay::overlay_host, popover::popover, surface::surface, text::text,
+/// };
+/// use tessera_ui::remember;
+/// # use tessera_components::theme::{MaterialTheme, material_theme};
+///
+/// # material_theme()
+/// #     .theme(|| MaterialTheme::default())
+/// #     .child(|| {
+/// overlay_host().content(|| {
+///     let open = remember(|| false);
+///     popover(
+///         move || {
+///             button().on_click(move || open.set(true)).child(|| {
+///                 text().content("Filters");
+///             });
+///         },
+///         || {
+///             surface().child(|| {
+///                 text().content("Only unread");
+///             });
+///         },
+///     )
+///     .is_open(open.get())
+///     .on_dismiss(move || open.set(false));
+///     assert!(!open.get());
+/// });
+/// # });
+/// # }
+/// # component();
+/// ```
+#[tessera]
+pub fn popover(
+    anchor_content: RenderSlot,
+    popover_content: RenderSlot,
+    is_open: Option<bool>,
+    on_dismiss: Option<Callback>,
+    placement: Option<OverlayPlacement>,
+    dismiss_on_outside_press: Option<bool>,
+    focus_on_open: Option<bool>,
+    match_anchor_width: Option<bool>,
+    modifier: Option<Modifier>,
+) {
+    let is_open = is_open.unwrap_or(false);
+    let on_dismiss = on_dismiss.unwrap_or_default();
+    let placement = placement.unwrap_or_default();
+    let dismiss_on_outside_press = dismiss_on_outside_press.unwrap_or(true);
+    let focus_on_open = focus_on_open.unwrap_or(true);
+    let match_anchor_width = match_anchor_width.unwrap_or(false);
+    let handle = remember(OverlayHandle::new);
+    let anchor = remember(|| None::<PxRect>);
+    let size: Arc<RwLock<PxSize>> = remember(Arc::default).get();
+    let host = use_context::<OverlayHost>().map(|host| host.get());
+
+    if !is_open && anchor.with(Option::is_some) {
+        // A closed popover stops tracking its anchor; drop the stale rect so
+        // reopening waits for a fresh one.
+        anchor.set(None);
+    }
+    let popup_anchor = size.clone();
+    let popup = RenderSlot::new(move || {
+        if match_anchor_width {
+            // The anchor is measured before the popup, inline and in the
+            // overlay host alike.
+            layout()
+                .layout_policy(MatchAnchorWidthLayout {
+                    anchor: popup_anchor.clone(),
+                })
+                .child(move || {
+                    popover_panel(on_dismiss, popover_content, focus_on_open);
+                });
+        } else {
+            popover_panel(on_dismiss, popover_content, focus_on_open);
+        }
+    });
+    let anchor_rect = anchor.get().filter(|_| is_open);
+    if let Some(host) = host {
+        handle.with(|handle| match anchor_rect {
+            Some(anchor) => host.show(
+                handle,
+                OverlayRequest {
+                    anchor,
+                    placement,
+                    content: popup,
+                    on_dismiss: dismiss_on_outside_press.then_some(on_dismiss),
+                },
+            ),
+            None => host.hide(handle),
+        });
+    }
+    let inline = host.is_none() && is_open;
+
+    let mut modifier = modifier.unwrap_or_default();
+    if is_open {
+        modifier = modifier.push_placement(AnchorBoundsNode {
+            size: size.clone(),
+            anchor,
+        });
+    }
+
+    layout()
+        .modifier(modifier)
+        .layout_policy(PopoverLayout { placement, size })
+        .child(move || {
+            anchor_content.render();
+            if inline {
+                popup.render();
+            }
+        });
+}
+
+#[tessera]
+fn popover_panel(on_dismiss: Callback, content: RenderSlot, focus_on_open: bool) {
+    let focus_scope = remember(FocusScopeNode::new).get();
+    let focused = remember(|| false);
+    if focus_on_open && !focused.get() {
+        focus_scope.restore_focus();
+        focused.set(true);
+    }
+    let modifier = with_keyboard_input(
+        Modifier::new()
+            .focus_restorer_with(focus_scope, None)
+            .focus_traversal_policy(
+                FocusTraversalPolicy::linear()
+                    .wrap(true)
+                    .tab_navigation(true),
+            ),
+        move |mut input| {
+            let escape = input.keyboard_events.iter().any(|event| {
+                event.state == winit::event::ElementState::Pressed
+                    && matches!(
+                        event.physical_key,
+                        winit::keyboard::PhysicalKey::Code(winit::keyboard::KeyCode::Escape)
+                    )
+            });
+            if escape {
+                on_dismiss.call();
+                input.block_keyboard();
+            }
+        },
+    );
+    layout().modifier(modifier).child(move || {
+        content.render();
+    });
+}
@@ -0,0 +1,333 @@
+//! Window that lists and renders `#[preview]` components.
+//!
+//! ## Usage
+//!
+//! Run by `cargo tessera preview` to browse a package's previews and check
+//! them at different sizes, font scales, themes and locales.
+
+use tessera_ui::{
+    Dp, Modifier, State,
+    preview::{Preview, PreviewLocale, PreviewTheme, previews},
+    provide_context,
+    saveable::remember_saveable,
+    tessera,
+};
+
+use crate::{
+    alignment::{Alignment, CrossAxisAlignment},
+    boxed::boxed,
+    column::column,
+    divider::{horizontal_divider, vertical_divider},
+    lazy_list::lazy_column,
+    list_item::list_item,
+    modifier::ModifierExt as _,
+    row::row,
+    slider::slider,
+    spacer::spacer,
+    surface::surface,
+    switch::switch,
+    text::text,
+    text_input::text_input,
+    theme::{
+        DEFAULT_COLOR, MaterialColorScheme, MaterialTheme, MaterialTypography, material_theme,
+    },
+};
+
+const MIN_SIZE: f32 = 120.0;
+const MAX_SIZE: f32 = 1920.0;
+const MIN_FONT_SCALE: f32 = 0.5;
+const MAX_FONT_SCALE: f32 = 2.0;
+const DEFAULT_LOCALE: &str = "en-US";
+
+/// Knob values, saved so they survive hot reloads of the previews.
+#[derive(Clone, Copy)]
+struct Knobs {
+    selected: State<String>,
+    fixed_size: State<bool>,
+    width: State<f32>,
+    height: State<f32>,
+    font_scale: State<f32>,
+    dark: State<bool>,
+    locale: State<String>,
+}
+
+impl Knobs {
+    fn remember(initial_preview: Option<&str>) -> Self {
+        let initial = initial_preview
+            .and_then(find)
+            .or_else(|| previews().first().copied());
+        let size = initial.and_then(Preview::size);
+        Self {
+            selected: remember_saveable("tessera.preview.selected", || {
+                initial.map_or_else(|| initial_preview.unwrap_or_default().to_string(), key)
+            }),
+            fixed_size: remember_saveable("tessera.preview.fixed_size", || size.is_some()),
+            width: remember_saveable("tessera.preview.width", || {
+                size.map_or(360.0, |(width, _)| width.0 as f32)
+            }),
+            height: remember_saveable("tessera.preview.height", || {
+                size.map_or(640.0, |(_, height)| height.0 as f32)
+            }),
+            font_scale: remember_saveable("tessera.preview.font_scale", || 1.0),
+            dark: remember_saveable("tessera.preview.dark", || {
+                initial.and_then(Preview::theme) == Some(PreviewTheme::Dark)
+            }),
+            locale: remember_saveable("tessera.preview.locale", || DEFAULT_LOCALE.to_string()),
+        }
+    }
+
+    /// Selects `preview` and applies the size and theme it asks for.
+    fn select(&self, preview: &Preview) {
+        self.selected.set(key(preview));
+        match preview.size() {
+            Some((width, height)) => {
+                self.fixed_size.set(true);
+                self.width.set(width.0 as f32);
+                self.height.set(height.0 as f32);
+            }
+            None => self.fixed_size.set(false),
+        }
+        if let Some(theme) = preview.theme() {
+            self.dark.set(theme == PreviewTheme::Dark);
+        }
+    }
+}
+
+/// Identifies a preview uniquely, unlike its name.
+fn key(preview: &Preview) -> String {
+    format!("{}::{}", preview.module_path(), preview.name())
+}
+
+/// Finds a preview by name or by `module::name`.
+fn find(query: &str) -> Option<&'static Preview> {
+    previews()
+        .into_iter()
+        .find(|preview| preview.name() == query || key(preview) == query)
+}
+
+/// # preview_host
+///
+/// Lists every registered preview and renders the selected one with knobs
+/// for its size, font scale, theme and locale.
+///
+/// The knobs are saveable, so they survive hot reloads of the previews.
+///
+/// ## Usage
+///
+/// Use as the root of a preview window, as `cargo tessera preview` does.
+///
+/// ## Parameters
+///
+/// - `initial_preview` — optional name or `module::name` of the preview to show
+///   first.
+///
+/// ## Examples
+///
+/// ```
+/// use tessera_components::preview_host::preview_host;
+/// use tessera_ui::EntryPoint;
+///
+/// let entry = EntryPoint::new(|| {
+///     preview_host().initial_preview("Dark card");
+/// })
+/// .package(tessera_components::ComponentsPackage);
+/// ```
+#[tessera]
+pub fn preview_host(#[prop(into)] initial_preview: Option<String>) {
+    let knobs = Knobs::remember(initial_preview.as_deref());
+    material_theme().child(move || {
+        surface()
+            .modifier(Modifier::new().fill_max_size())
+            .child(move || {
+                row()
+                    .modifier(Modifier::new().fill_max_size())
+                    .children(move || {
+                        preview_list(knobs);
+                        vertical_divider();
+                        column()
+                            .modifier(Modifier::new().weight(1.0).fill_max_height())
+                            .children(move || {
+                                knob_bar(knobs);
+                                horizontal_divider();
+                                preview_frame(knobs);
+                            });
+                    });
+            });
+    });
+}
+
+fn preview_list(knobs: Knobs) {
+    let previews = previews();
+    if previews.is_empty() {
+        boxed()
+            .modifier(Modifier::new().width(Dp(280.0)).padding_all(Dp(16.0)))
+            .children(|| {
+                text().content("No previews found. Annotate components with #[preview].");
+            });
+        return;
+    }
+    lazy_column()
+        .modifier(Modifier::new().width(Dp(280.0)).fill_max_height())
+        .content_padding(Dp(8.0))
+        .items(previews.len(), move |index| {
+            let preview = previews[index];
+            list_item()
+                .headline(preview.name())
+                .supporting_text(preview.module_path())
+                .selected(knobs.selected.with(|selected| *selected == key(preview)))
+                .on_click(move || knobs.select(preview));
+        });
+}
+
+fn knob_bar(knobs: Knobs) {
+    let fixed_size = knobs.fixed_size.get();
+    column()
+        .modifier(Modifier::new().fill_max_width().padding_all(Dp(12.0)))
+        .children(move || {
+            row()
+                .cross_axis_alignment(CrossAxisAlignment::Center)
+                .children(move || {
+                    switch()
+                        .checked(fixed_size)
+                        .on_toggle(move |checked| knobs.fixed_size.set(checked))
+                        .accessibility_label("Fixed size");
+                    spacer().modifier(Modifier::new().width(Dp(8.0)));
+                    if fixed_size {
+                        size_slider("Width", knobs.width);
+                        size_slider("Height", knobs.height);
+                    } else {
+                        text().content("Fill the window");
+                    }
+                });
+            spacer().modifier(Modifier::new().height(Dp(8.0)));
+            row()
+                .cross_axis_alignment(CrossAxisAlignment::Center)
+                .children(move || {
+                    let font_scale = knobs.font_scale.get();
+                    text().content(format!("Font scale {font_scale:.2}×"));
+                    spacer().modifier(Modifier::new().width(Dp(8.0)));
+                    slider()
+                        .modifier(Modifier::new().width(Dp(160.0)))
+                        .value(to_fraction(font_scale, MIN_FONT_SCALE, MAX_FONT_SCALE))
+                        .on_change(move |value| {
+                            knobs.font_scale.set(from_fraction(
+                                value,
+                                MIN_FONT_SCALE,
+                                MAX_FONT_SCALE,
+                            ));
+                        })
+                        .accessibility_label("Font scale");
+                    spacer().modifier(Modifier::new().width(Dp(24.0)));
+                    switch()
+                        .checked(knobs.dark.get())
+                        .on_toggle(move |checked| knobs.dark.set(checked))
+                        .accessibility_label("Dark theme");
+                    spacer().modifier(Modifier::new().width(Dp(8.0)));
+                    text().content("Dark theme");
+                    spacer().modifier(Modifier::new().width(Dp(24.0)));
+                    text().content("Locale");
+                    spacer().modifier(Modifier::new().width(Dp(8.0)));
+                    text_input()
+                        .min_width(Dp(120.0))
+                        .single_line(true)
+                        .initial_text(knobs.locale.get())
+                        .on_change(move |locale: String| {
+                            knobs.locale.set(locale.clone());
+                            locale
+                        })
+                        .accessibility_label("Locale");
+                });
+        });
+}
+
+fn size_slider(label: &'static str, value: State<f32>) {
+    let current = value.get();
+    text().content(format!("{label} {current:.0}dp"));
+    spacer().modifier(Modifier::new().width(Dp(8.0)));
+    slider()
+        .modifier(Modifier::new().width(Dp(160.0)))
+        .value(to_fraction(current, MIN_SIZE, MAX_SIZE))
+        .on_change(move |fraction| value.set(from_fraction(fraction, MIN_SIZE, MAX_SIZE).round()))
+        .accessibility_label(label);
+    spacer().modifier(Modifier::new().width(Dp(16.0)));
+}
+
+fn preview_frame(knobs: Knobs) {
+    let selected = knobs.selected.get();
+    let preview = find(&selected);
+    let font_scale = knobs.font_scale.get();
+    let color_scheme = if knobs.dark.get() {
+        MaterialColorScheme::dark_from_seed(DEFAULT_COLOR)
+    } else {
+        MaterialColorScheme::light_from_seed(DEFAULT_COLOR)
+    };
+    let locale = knobs.locale.get();
+    let frame_modifier = if knobs.fixed_size.get() {
+        Modifier::new().size(
+            Dp(f64::from(knobs.width.get())),
+            Dp(f64::from(knobs.height.get())),
+        )
+    } else {
+        Modifier::new().fill_max_size()
+    };
+
+    boxed()
+        .alignment(Alignment::Center)
+        .modifier(
+            Modifier::new()
+                .weight(1.0)
+                .fill_max_width()
+                .padding_all(Dp(24.0)),
+        )
+        .children(move || {
+            let Some(preview) = preview else {
+                text().content(format!("No preview named `{selected}`"));
+                return;
+            };
+            let color_scheme = color_scheme.clone();
+            let locale = locale.clone();
+            let frame_modifier = frame_modifier.clone();
+            material_theme()
+                .theme(move || MaterialTheme {
+                    color_scheme: color_scheme.clone(),
+                    typography: MaterialTypography::default().scaled(font_scale),
+                    ..MaterialTheme::default()
+                })
+                .child(move || {
+                    let locale = locale.clone();
+                    let frame_modifier = frame_modifier.clone();
+                    provide_context(
+                        move || PreviewLocale(locale),
+                        move || {
+                            surface().modifier(frame_modifier).child(move || {
+                                preview.render();
+                            });
+                        },
+                    );
+                });
+        });
+}
+
+fn to_fraction(value: f32, min: f32, max: f32) -> f32 {
+    ((value - min) / (max - min)).clamp(0.0, 1.0)
+}
+
+fn from_fraction(fraction: f32, min: f32, max: f32) -> f32 {
+    min + fraction.clamp(0.0, 1.0) * (max - min)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn knob_fractions_round_trip() {
+        let fraction = to_fraction(1.25, MIN_FONT_SCALE, MAX_FONT_SCALE);
+        assert_eq!(fraction, 0.5);
+        assert_eq!(
+            from_fraction(fraction, MIN_FONT_SCALE, MAX_FONT_SCALE),
+            1.25
+        );
+        assert_eq!(to_fraction(4000.0, MIN_SIZE, MAX_SIZE), 1.0);
+    }
+}
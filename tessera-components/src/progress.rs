@@ -9,7 +9,7 @@ use tessera_ui::{
     accesskit::Role,
     layout::{LayoutPolicy, MeasureScope, RenderInput, RenderPolicy, layout},
     receive_frame_nanos, remember, tessera,
-    time::Instant,
+    time::{self, Instant},
     use_context,
 };
 
@@ -267,7 +267,7 @@ impl RenderPolicy for CircularProgressLayout {
                     });
             }
         } else {
-            let elapsed_ms = Instant::now()
+            let elapsed_ms = time::now()
                 .saturating_duration_since(self.animation_start)
                 .as_millis() as f32;
             let cycle_ms = elapsed_ms % 6000.0;
@@ -362,7 +362,7 @@ fn lerp(a: f32, b: f32, t: f32) -> f32 {
 }
 
 fn linear_cycle_progress(start: Instant, duration_ms: u32) -> f32 {
-    let elapsed_ms = Instant::now().saturating_duration_since(start).as_millis() as u64;
+    let elapsed_ms = time::now().saturating_duration_since(start).as_millis() as u64;
     let duration_ms = duration_ms.max(1) as u64;
     (elapsed_ms % duration_ms) as f32 / duration_ms as f32
 }
@@ -552,7 +552,7 @@ pub fn linear_progress_indicator(
     let draw_stop_indicator = draw_stop_indicator.unwrap_or(true);
 
     layout().modifier(modifier).child(move || {
-        let animation_start = remember(Instant::now);
+        let animation_start = remember(time::now);
         let frame_tick = remember(|| 0_u64);
         let should_receive_frames = remember(|| progress.is_none());
         should_receive_frames.set(progress.is_none());
@@ -769,7 +769,7 @@ pub fn circular_progress_indicator(
     let track_color = track_color.unwrap_or(scheme.secondary_container);
     let stroke_cap = stroke_cap.unwrap_or_default();
     let gap_size = gap_size.unwrap_or(ProgressIndicatorDefaults::CIRCULAR_INDICATOR_TRACK_GAP_SIZE);
-    let animation_start = remember(Instant::now);
+    let animation_start = remember(time::now);
     let frame_tick = remember(|| 0_u64);
     let should_receive_frames = remember(|| progress.is_none());
     should_receive_frames.set(progress.is_none());
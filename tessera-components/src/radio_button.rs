@@ -6,12 +6,11 @@
 
 use std::time::Duration;
 
-use tessera_foundation::gesture::TapRecognizer;
 use tessera_ui::{
     AxisConstraint, Callback, CallbackWith, Color, Dp, FocusState, FocusTraversalPolicy, Modifier,
-    Px, PxSize, RenderSlot, State, accesskit::Role, current_frame_nanos, layout::layout,
-    modifier::FocusModifierExt as _, provide_context, receive_frame_nanos, remember, tessera,
-    use_context,
+    Px, PxSize, RenderSlot, State, accesskit::Role, current_frame_nanos, gesture::TapRecognizer,
+    layout::layout, modifier::FocusModifierExt as _, provide_context, receive_frame_nanos,
+    remember, tessera, use_context,
 };
 
 use crate::{
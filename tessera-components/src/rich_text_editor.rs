@@ -0,0 +1,1007 @@
+//! Text editor with bold, italic, underline, links and lists.
+//!
+//! ## Usage
+//!
+//! Write notes, comments and messages with formatting.
+use std::{ops::Range, sync::Arc};
+
+use parking_lot::Mutex;
+use tessera_ui::{
+    CallbackWith, Dp, Modifier, State, remember,
+    renderer::drawer::text::{AttributedString, FontWeight, SpanStyle, TextSpan},
+    tessera, winit,
+};
+
+use crate::{
+    column::column,
+    icon_button::{IconButtonVariant, icon_button},
+    material_icons::filled,
+    modifier::{ModifierExt as _, with_keyboard_preview_input},
+    painter::Painter,
+    row::row,
+    text_edit_core::TextSelection,
+    text_field::{TextFieldDefaults, TextFieldLineLimit, text_field},
+    text_input::TextInputController,
+};
+
+const BULLET_PREFIX: &str = "• ";
+const MAX_HISTORY: usize = 100;
+const TOOLBAR_SPACING: Dp = Dp(4.0);
+
+/// Last fragment copied from a rich text editor, so pasting the same text
+/// back keeps its formatting. The system clipboard only carries plain text.
+static CLIPBOARD: Mutex<Option<(String, Vec<TextSpan>)>> = Mutex::new(None);
+
+/// Character formatting toggled by a [`rich_text_editor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RichTextFormat {
+    /// Bold weight.
+    Bold,
+    /// Italic style.
+    Italic,
+    /// Underline decoration.
+    Underline,
+}
+
+impl RichTextFormat {
+    fn is_set(self, style: &SpanStyle) -> bool {
+        match self {
+            Self::Bold => style
+                .weight
+                .is_some_and(|weight| weight >= FontWeight::BOLD),
+            Self::Italic => style.italic,
+            Self::Underline => style.underline,
+        }
+    }
+
+    fn set(self, style: &mut SpanStyle, on: bool) {
+        match self {
+            Self::Bold => style.weight = on.then_some(FontWeight::BOLD),
+            Self::Italic => style.italic = on,
+            Self::Underline => style.underline = on,
+        }
+    }
+}
+
+/// Paragraph list kind of a [`rich_text_editor`] line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RichTextList {
+    /// Lines prefixed with a bullet.
+    Bullet,
+    /// Lines prefixed with consecutive numbers.
+    Numbered,
+}
+
+impl RichTextList {
+    fn marker(self, number: usize) -> String {
+        match self {
+            Self::Bullet => BULLET_PREFIX.to_string(),
+            Self::Numbered => format!("{number}. "),
+        }
+    }
+}
+
+/// Returns the list kind and marker length of a line.
+fn list_marker(line: &str) -> Option<(RichTextList, usize)> {
+    if line.starts_with(BULLET_PREFIX) {
+        return Some((RichTextList::Bullet, BULLET_PREFIX.len()));
+    }
+    let digits = line.bytes().take_while(u8::is_ascii_digit).count();
+    (digits > 0 && line[digits..].starts_with(". ")).then_some((RichTextList::Numbered, digits + 2))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RichTextCommand {
+    Format(RichTextFormat),
+    List(RichTextList),
+    Undo,
+    Redo,
+}
+
+impl RichTextCommand {
+    const ALL: [Self; 7] = [
+        Self::Format(RichTextFormat::Bold),
+        Self::Format(RichTextFormat::Italic),
+        Self::Format(RichTextFormat::Underline),
+        Self::List(RichTextList::Bullet),
+        Self::List(RichTextList::Numbered),
+        Self::Undo,
+        Self::Redo,
+    ];
+
+    fn icon(self) -> Painter {
+        match self {
+            Self::Format(RichTextFormat::Bold) => filled::FORMAT_BOLD_SVG.into(),
+            Self::Format(RichTextFormat::Italic) => filled::FORMAT_ITALIC_SVG.into(),
+            Self::Format(RichTextFormat::Underline) => filled::FORMAT_UNDERLINED_SVG.into(),
+            Self::List(RichTextList::Bullet) => filled::FORMAT_LIST_BULLETED_SVG.into(),
+            Self::List(RichTextList::Numbered) => filled::FORMAT_LIST_NUMBERED_SVG.into(),
+            Self::Undo => filled::UNDO_SVG.into(),
+            Self::Redo => filled::REDO_SVG.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct RichTextDocument {
+    text: String,
+    /// Sorted, non-overlapping spans with non-default styles.
+    spans: Vec<TextSpan>,
+}
+
+impl RichTextDocument {
+    fn style_at(&self, offset: usize) -> SpanStyle {
+        self.spans
+            .iter()
+            .find(|span| span.range.contains(&offset))
+            .map(|span| span.style.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns styled runs covering the whole text.
+    fn runs(&self) -> Vec<TextSpan> {
+        let mut runs = Vec::with_capacity(self.spans.len() * 2 + 1);
+        let mut covered = 0;
+        for span in &self.spans {
+            if covered < span.range.start {
+                runs.push(TextSpan {
+                    range: covered..span.range.start,
+                    style: SpanStyle::default(),
+                });
+            }
+            runs.push(span.clone());
+            covered = span.range.end;
+        }
+        if covered < self.text.len() {
+            runs.push(TextSpan {
+                range: covered..self.text.len(),
+                style: SpanStyle::default(),
+            });
+        }
+        runs
+    }
+
+    /// Replaces `range` with `text` drawn with `style`, moving the spans and
+    /// `tracked` offsets behind it.
+    fn replace(
+        &mut self,
+        range: Range<usize>,
+        text: &str,
+        style: &SpanStyle,
+        tracked: &mut [usize],
+    ) {
+        let shift = |offset: usize| {
+            if offset >= range.end {
+                offset - range.len() + text.len()
+            } else {
+                offset.min(range.start)
+            }
+        };
+        self.spans = self
+            .spans
+            .iter()
+            .map(|span| TextSpan {
+                range: shift(span.range.start)..shift(span.range.end),
+                style: span.style.clone(),
+            })
+            .collect();
+        for offset in tracked.iter_mut() {
+            *offset = shift(*offset);
+        }
+        self.text.replace_range(range.clone(), text);
+        self.apply_style(range.start..range.start + text.len(), |current| {
+            *current = style.clone();
+        });
+    }
+
+    fn apply_style(&mut self, range: Range<usize>, update: impl Fn(&mut SpanStyle)) {
+        let mut spans = Vec::new();
+        for run in self.runs() {
+            let start = run.range.start.max(range.start);
+            let end = run.range.end.min(range.end);
+            if start >= end {
+                spans.push(run);
+                continue;
+            }
+            if run.range.start < start {
+                spans.push(TextSpan {
+                    range: run.range.start..start,
+                    style: run.style.clone(),
+                });
+            }
+            let mut style = run.style.clone();
+            update(&mut style);
+            spans.push(TextSpan {
+                range: start..end,
+                style,
+            });
+            if end < run.range.end {
+                spans.push(TextSpan {
+                    range: end..run.range.end,
+                    style: run.style,
+                });
+            }
+        }
+        self.spans = normalize_spans(spans);
+    }
+
+    /// Returns the spans inside `range`, relative to its start.
+    fn slice_spans(&self, range: Range<usize>) -> Vec<TextSpan> {
+        self.spans
+            .iter()
+            .filter_map(|span| {
+                let start = span.range.start.max(range.start);
+                let end = span.range.end.min(range.end);
+                (start < end).then(|| TextSpan {
+                    range: start - range.start..end - range.start,
+                    style: span.style.clone(),
+                })
+            })
+            .collect()
+    }
+
+    fn marker_at(&self, line_start: usize) -> Option<(RichTextList, usize)> {
+        list_marker(&self.text[line_start..line_end(&self.text, line_start)])
+    }
+
+    /// Rewrites the numbers of every numbered list so each counts from one.
+    fn renumber_lists(&mut self, tracked: &mut [usize]) {
+        let mut number = 0;
+        let mut start = 0;
+        loop {
+            match self.marker_at(start) {
+                Some((RichTextList::Numbered, len)) => {
+                    number += 1;
+                    let marker = RichTextList::Numbered.marker(number);
+                    if self.text[start..start + len] != marker {
+                        self.replace(start..start + len, &marker, &SpanStyle::default(), tracked);
+                    }
+                }
+                _ => number = 0,
+            }
+            let end = line_end(&self.text, start);
+            if end == self.text.len() {
+                break;
+            }
+            start = end + 1;
+        }
+    }
+}
+
+fn normalize_spans(mut spans: Vec<TextSpan>) -> Vec<TextSpan> {
+    spans.retain(|span| !span.range.is_empty() && span.style != SpanStyle::default());
+    spans.sort_by_key(|span| span.range.start);
+    let mut merged: Vec<TextSpan> = Vec::with_capacity(spans.len());
+    for span in spans {
+        match merged.last_mut() {
+            Some(last) if last.range.end == span.range.start && last.style == span.style => {
+                last.range.end = span.range.end;
+            }
+            _ => merged.push(span),
+        }
+    }
+    merged
+}
+
+fn line_start(text: &str, offset: usize) -> usize {
+    text[..offset].rfind('\n').map_or(0, |index| index + 1)
+}
+
+fn line_end(text: &str, offset: usize) -> usize {
+    text[offset..]
+        .find('\n')
+        .map_or(text.len(), |index| offset + index)
+}
+
+/// Returns the start offsets of the lines overlapping `range`.
+fn line_starts(text: &str, range: Range<usize>) -> Vec<usize> {
+    let mut starts = vec![line_start(text, range.start)];
+    starts.extend(
+        text[range.clone()]
+            .match_indices('\n')
+            .map(|(index, _)| range.start + index + 1),
+    );
+    starts
+}
+
+/// Returns the start of the change and its end in `old` and `new`.
+fn changed_range(old: &str, new: &str) -> (usize, usize, usize) {
+    let prefix: usize = old
+        .chars()
+        .zip(new.chars())
+        .take_while(|(a, b)| a == b)
+        .map(|(c, _)| c.len_utf8())
+        .sum();
+    let suffix: usize = old[prefix..]
+        .chars()
+        .rev()
+        .zip(new[prefix..].chars().rev())
+        .take_while(|(a, b)| a == b)
+        .map(|(c, _)| c.len_utf8())
+        .sum();
+    (prefix, old.len() - suffix, new.len() - suffix)
+}
+
+#[derive(Clone)]
+struct Snapshot {
+    document: RichTextDocument,
+    selection: Range<usize>,
+}
+
+/// Formatted content, selection and edit history of a
+/// [`rich_text_editor`].
+pub struct RichTextEditorController {
+    document: RichTextDocument,
+    selection: Range<usize>,
+    /// Style for text typed at the given caret offset, set by toggling a
+    /// format without a selection.
+    typing_style: Option<(usize, SpanStyle)>,
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+    /// Caret after the last typed character, so a run of typing undoes as
+    /// one step.
+    typing_end: Option<usize>,
+    /// Bumped whenever the content must be pushed into the text field.
+    revision: u64,
+    /// Bumped whenever the content changes.
+    version: u64,
+}
+
+impl Default for RichTextEditorController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RichTextEditorController {
+    /// Creates a controller with empty content.
+    pub fn new() -> Self {
+        Self {
+            document: RichTextDocument::default(),
+            selection: 0..0,
+            typing_style: None,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            typing_end: None,
+            revision: 0,
+            version: 0,
+        }
+    }
+
+    /// Creates a controller showing `content`.
+    pub fn with_content(content: &AttributedString) -> Self {
+        let mut controller = Self::new();
+        controller.document = document_from(content);
+        controller
+    }
+
+    /// Replaces the content, keeping the previous one in the undo history.
+    pub fn set_content(&mut self, content: &AttributedString) {
+        self.push_undo();
+        self.document = document_from(content);
+        self.selection = self.document.text.len()..self.document.text.len();
+        self.changed();
+    }
+
+    /// Returns the content with its formatting.
+    pub fn to_attributed_string(&self) -> AttributedString {
+        self.document
+            .runs()
+            .into_iter()
+            .fold(AttributedString::new(), |content, run| {
+                let text = &self.document.text[run.range];
+                if run.style == SpanStyle::default() {
+                    content.push(text)
+                } else {
+                    content.push_styled(text, run.style)
+                }
+            })
+    }
+
+    /// Returns the plain text of the content.
+    pub fn text(&self) -> &str {
+        &self.document.text
+    }
+
+    /// Returns the selected byte range.
+    pub fn selection(&self) -> Range<usize> {
+        self.selection.clone()
+    }
+
+    /// Selects a byte range, clamped to the text.
+    pub fn set_selection(&mut self, selection: Range<usize>) {
+        let clamp = |offset: usize| {
+            let mut offset = offset.min(self.document.text.len());
+            while !self.document.text.is_char_boundary(offset) {
+                offset -= 1;
+            }
+            offset
+        };
+        let selection =
+            clamp(selection.start.min(selection.end))..clamp(selection.end.max(selection.start));
+        if self.selection != selection {
+            self.sync_selection(selection);
+            self.revision += 1;
+        }
+    }
+
+    /// Returns whether `format` applies to the whole selection, or to text
+    /// typed at the caret.
+    pub fn is_format_active(&self, format: RichTextFormat) -> bool {
+        if self.selection.is_empty() {
+            return format.is_set(&self.caret_style());
+        }
+        self.document
+            .runs()
+            .iter()
+            .filter(|run| {
+                run.range.start < self.selection.end && self.selection.start < run.range.end
+            })
+            .all(|run| format.is_set(&run.style))
+    }
+
+    /// Toggles `format` on the selection, or for text typed at the caret.
+    pub fn toggle_format(&mut self, format: RichTextFormat) {
+        let on = !self.is_format_active(format);
+        if self.selection.is_empty() {
+            let mut style = self.caret_style();
+            format.set(&mut style, on);
+            self.typing_style = Some((self.selection.start, style));
+            return;
+        }
+        self.push_undo();
+        self.document
+            .apply_style(self.selection.clone(), |style| format.set(style, on));
+        self.changed();
+    }
+
+    /// Links the selection to `target`, or removes links with `None`.
+    pub fn set_link(&mut self, target: Option<&str>) {
+        if self.selection.is_empty() {
+            return;
+        }
+        let target: Option<Arc<str>> = target.map(Into::into);
+        self.push_undo();
+        self.document.apply_style(self.selection.clone(), |style| {
+            style.underline = target.is_some();
+            style.link = target.clone();
+        });
+        self.changed();
+    }
+
+    /// Returns the link target at the caret.
+    pub fn link_at_cursor(&self) -> Option<&str> {
+        let caret = self.selection.start;
+        self.document
+            .spans
+            .iter()
+            .find(|span| span.range.start <= caret && caret <= span.range.end)
+            .and_then(|span| span.style.link.as_deref())
+    }
+
+    /// Returns the list kind of the line holding the caret.
+    pub fn list_at_cursor(&self) -> Option<RichTextList> {
+        let start = line_start(&self.document.text, self.selection.start);
+        self.document.marker_at(start).map(|(list, _)| list)
+    }
+
+    /// Turns the selected lines into a `list`, or back into paragraphs when
+    /// they already are one.
+    pub fn toggle_list(&mut self, list: RichTextList) {
+        self.push_undo();
+        let starts = line_starts(&self.document.text, self.selection.clone());
+        let remove = starts.iter().all(|&start| {
+            self.document
+                .marker_at(start)
+                .is_some_and(|(kind, _)| kind == list)
+        });
+        let marker = if remove {
+            String::new()
+        } else {
+            list.marker(1)
+        };
+        let mut tracked = [self.selection.start, self.selection.end];
+        for &start in starts.iter().rev() {
+            let len = self.document.marker_at(start).map_or(0, |(_, len)| len);
+            self.document.replace(
+                start..start + len,
+                &marker,
+                &SpanStyle::default(),
+                &mut tracked,
+            );
+        }
+        self.document.renumber_lists(&mut tracked);
+        self.selection = tracked[0]..tracked[1];
+        self.changed();
+    }
+
+    /// Returns whether there is an edit to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Returns whether there is an undone edit to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Reverts the last edit.
+    pub fn undo(&mut self) {
+        if let Some(snapshot) = self.undo.pop() {
+            self.redo.push(self.snapshot());
+            self.restore(snapshot);
+        }
+    }
+
+    /// Reapplies the last undone edit.
+    pub fn redo(&mut self) {
+        if let Some(snapshot) = self.redo.pop() {
+            self.undo.push(self.snapshot());
+            self.restore(snapshot);
+        }
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            document: self.document.clone(),
+            selection: self.selection.clone(),
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.document = snapshot.document;
+        self.selection = snapshot.selection;
+        self.typing_style = None;
+        self.changed();
+    }
+
+    fn push_undo(&mut self) {
+        self.undo.push(self.snapshot());
+        if self.undo.len() > MAX_HISTORY {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+        self.typing_end = None;
+    }
+
+    fn changed(&mut self) {
+        self.typing_end = None;
+        self.revision += 1;
+        self.version += 1;
+    }
+
+    fn sync_selection(&mut self, selection: Range<usize>) {
+        if self.typing_end != Some(selection.start) || !selection.is_empty() {
+            self.typing_end = None;
+        }
+        self.selection = selection;
+    }
+
+    /// Style of text typed at the caret.
+    fn caret_style(&self) -> SpanStyle {
+        let caret = self.selection.start;
+        match &self.typing_style {
+            Some((offset, style)) if *offset == caret => style.clone(),
+            _ => self.inherited_style(caret, self.selection.end),
+        }
+    }
+
+    /// Style of text replacing `start..end`, taken from the character before.
+    fn inherited_style(&self, start: usize, end: usize) -> SpanStyle {
+        let Some(previous) = self.document.text[..start].chars().next_back() else {
+            return SpanStyle::default();
+        };
+        let mut style = self.document.style_at(start - previous.len_utf8());
+        // Typing right after a link must not extend it.
+        if style.link.is_some() && self.document.style_at(end).link != style.link {
+            style.link = None;
+            style.underline = false;
+        }
+        style
+    }
+
+    /// Applies an edit made in the text field and returns the text to show,
+    /// which differs when a list was continued or ended.
+    fn apply_field_text(&mut self, text: String) -> String {
+        if text == self.document.text {
+            return text;
+        }
+        let (start, old_end, new_end) = changed_range(&self.document.text, &text);
+        let inserted = &text[start..new_end];
+        let typed = start == old_end && inserted.chars().count() == 1;
+        let continues_typing =
+            typed && self.typing_end == Some(start) && !inserted.chars().any(char::is_whitespace);
+        if !continues_typing {
+            self.push_undo();
+        }
+
+        let style = match &self.typing_style {
+            Some((offset, style)) if *offset == start => style.clone(),
+            _ => self.inherited_style(start, old_end),
+        };
+        let pasted = CLIPBOARD
+            .lock()
+            .as_ref()
+            .filter(|(copied, _)| inserted.chars().nth(1).is_some() && copied == inserted)
+            .map(|(_, spans)| spans.clone());
+        self.document
+            .replace(start..old_end, inserted, &style, &mut []);
+        for span in pasted.into_iter().flatten() {
+            let range = start + span.range.start..start + span.range.end;
+            self.document
+                .apply_style(range, |current| *current = span.style.clone());
+        }
+
+        let caret = if inserted == "\n" {
+            self.continue_list(start)
+        } else {
+            new_end
+        };
+        let mut tracked = [caret];
+        self.document.renumber_lists(&mut tracked);
+        self.selection = tracked[0]..tracked[0];
+        self.typing_style = None;
+        self.typing_end = typed.then_some(tracked[0]);
+        self.version += 1;
+        if self.document.text != text {
+            self.revision += 1;
+        }
+        self.document.text.clone()
+    }
+
+    /// Continues the list of the line a newline was typed at, or ends it
+    /// when that item is empty. Returns the caret after the edit.
+    fn continue_list(&mut self, newline: usize) -> usize {
+        let start = line_start(&self.document.text, newline);
+        let Some((list, len)) = list_marker(&self.document.text[start..newline]) else {
+            return newline + 1;
+        };
+        if start + len == newline {
+            self.document
+                .replace(start..newline + 1, "", &SpanStyle::default(), &mut []);
+            return start;
+        }
+        let marker = list.marker(1);
+        let after = newline + 1;
+        self.document
+            .replace(after..after, &marker, &SpanStyle::default(), &mut []);
+        after + marker.len()
+    }
+
+    /// Remembers the formatting of `range` for a paste of the same text.
+    fn copy_range(&self, range: Range<usize>) {
+        if range.is_empty() || range.end > self.document.text.len() {
+            return;
+        }
+        let text = self.document.text[range.clone()].to_string();
+        *CLIPBOARD.lock() = Some((text, self.document.slice_spans(range)));
+    }
+
+    fn is_command_active(&self, command: RichTextCommand) -> bool {
+        match command {
+            RichTextCommand::Format(format) => self.is_format_active(format),
+            RichTextCommand::List(list) => self.list_at_cursor() == Some(list),
+            RichTextCommand::Undo | RichTextCommand::Redo => false,
+        }
+    }
+
+    fn is_command_available(&self, command: RichTextCommand) -> bool {
+        match command {
+            RichTextCommand::Undo => self.can_undo(),
+            RichTextCommand::Redo => self.can_redo(),
+            RichTextCommand::Format(_) | RichTextCommand::List(_) => true,
+        }
+    }
+
+    fn run(&mut self, command: RichTextCommand) {
+        match command {
+            RichTextCommand::Format(format) => self.toggle_format(format),
+            RichTextCommand::List(list) => self.toggle_list(list),
+            RichTextCommand::Undo => self.undo(),
+            RichTextCommand::Redo => self.redo(),
+        }
+    }
+}
+
+fn document_from(content: &AttributedString) -> RichTextDocument {
+    let text = content.text().to_string();
+    let spans = content
+        .spans()
+        .iter()
+        .filter(|span| {
+            span.range.end <= text.len()
+                && text.is_char_boundary(span.range.start)
+                && text.is_char_boundary(span.range.end)
+        })
+        .cloned()
+        .collect();
+    RichTextDocument {
+        text,
+        spans: normalize_spans(spans),
+    }
+}
+
+/// Returns the editor command bound to a shortcut key.
+fn shortcut_command(key: &winit::keyboard::Key, shift: bool) -> Option<RichTextCommand> {
+    let winit::keyboard::Key::Character(character) = key else {
+        return None;
+    };
+    match character.to_lowercase().as_str() {
+        "b" => Some(RichTextCommand::Format(RichTextFormat::Bold)),
+        "i" => Some(RichTextCommand::Format(RichTextFormat::Italic)),
+        "u" => Some(RichTextCommand::Format(RichTextFormat::Underline)),
+        "z" if shift => Some(RichTextCommand::Redo),
+        "z" => Some(RichTextCommand::Undo),
+        "y" => Some(RichTextCommand::Redo),
+        _ => None,
+    }
+}
+
+/// # rich_text_editor
+///
+/// Multi-line text field with a formatting toolbar for bold, italic,
+/// underline, lists and undo.
+///
+/// ## Usage
+///
+/// Compose notes, comments or messages that keep their formatting.
+///
+/// ## Parameters
+///
+/// - `controller` — optional controller holding content, selection and history.
+/// - `on_change` — optional callback receiving the content after each edit.
+/// - `label` — optional label text.
+/// - `placeholder` — optional placeholder text.
+/// - `toolbar` — whether the formatting toolbar is shown; defaults to `true`.
+/// - `enabled` — whether the editor accepts input; defaults to `true`.
+/// - `modifier` — optional modifier chain applied to the editor.
+///
+/// ## Examples
+///
+/// ```
+/// # use tessera_ui::tessera;
+/// # #[tessera]
+/// # fn component() {
+/// use tessera_components::rich_text_editor::{
+///     RichTextEditorController, RichTextFormat, rich_text_editor,
+/// };
+/// use tessera_ui::{remember, renderer::drawer::text::AttributedString};
+/// # use tessera_components::theme::{MaterialTheme, material_theme};
+/// # material_theme()
+/// #     .theme(|| MaterialTheme::default())
+/// #     .child(|| {
+/// let controller = remember(|| {
+///     let mut controller = RichTextEditorController::new();
+///     controller.set_content(&AttributedString::new().push("Release notes"));
+///     controller.set_selection(0..7);
+///     controller.toggle_format(RichTextFormat::Bold);
+///     controller
+/// });
+/// assert!(controller.with(|c| c.is_format_active(RichTextFormat::Bold)));
+///
+/// rich_text_editor()
+///     .controller(controller)
+///     .label("Notes")
+///     .on_change(|content: AttributedString| println!("{}", content.text()));
+/// # });
+/// # }
+/// # component();
+/// ```
+#[tessera]
+pub fn rich_text_editor(
+    controller: Option<State<RichTextEditorController>>,
+    on_change: Option<CallbackWith<AttributedString>>,
+    #[prop(into)] label: Option<String>,
+    #[prop(into)] placeholder: Option<String>,
+    toolbar: Option<bool>,
+    enabled: Option<bool>,
+    modifier: Option<Modifier>,
+) {
+    let toolbar = toolbar.unwrap_or(true);
+    let enabled = enabled.unwrap_or(true);
+    let controller = controller.unwrap_or_else(|| remember(RichTextEditorController::new));
+    let text_controller = remember(|| {
+        TextInputController::new(
+            TextFieldDefaults::FONT_SIZE,
+            Some(TextFieldDefaults::LINE_HEIGHT),
+        )
+    });
+    let applied = remember(|| None::<u64>);
+    let notified = remember(|| controller.with(|controller| controller.version));
+
+    let revision = controller.with(|controller| controller.revision);
+    if applied.with(|applied| *applied != Some(revision)) {
+        let (text, selection, spans) = controller.with(|controller| {
+            (
+                controller.document.text.clone(),
+                controller.selection.clone(),
+                controller.document.spans.clone(),
+            )
+        });
+        text_controller.with_mut(|field| {
+            field.set_text_and_selection(
+                &text,
+                TextSelection {
+                    start: selection.start,
+                    end: selection.end,
+                },
+            );
+            field.set_text_spans(spans);
+        });
+        applied.set(Some(revision));
+    } else {
+        let selection = text_controller.with(|field| field.selection().ordered_range());
+        if controller.with(|controller| controller.selection != selection) {
+            controller.with_mut(|controller| controller.sync_selection(selection));
+        }
+        let spans = controller.with(|controller| controller.document.spans.clone());
+        // Spans are resynced once the composition is committed.
+        let stale = text_controller
+            .with(|field| field.composition().is_none() && field.text_spans() != spans.as_slice());
+        if stale {
+            text_controller.with_mut(|field| field.set_text_spans(spans));
+        }
+    }
+
+    let notify = move || {
+        let version = controller.with(|controller| controller.version);
+        if notified.get() != version {
+            notified.set(version);
+            if let Some(on_change) = on_change {
+                on_change.call(controller.with(RichTextEditorController::to_attributed_string));
+            }
+        }
+    };
+    let run = CallbackWith::new(move |command: RichTextCommand| {
+        controller.with_mut(|controller| controller.run(command));
+        notify();
+    });
+    let on_field_change = CallbackWith::new(move |text: String| {
+        let text = controller.with_mut(|controller| controller.apply_field_text(text));
+        notify();
+        text
+    });
+
+    let modifier = with_keyboard_preview_input(modifier.unwrap_or_default(), move |mut input| {
+        if !enabled {
+            return;
+        }
+        let shortcut = input.key_modifiers.control_key() || input.key_modifiers.super_key();
+        let shift = input.key_modifiers.shift_key();
+        if !shortcut {
+            return;
+        }
+        let mut commands = Vec::new();
+        input.keyboard_events.retain(|event| {
+            if event.state != winit::event::ElementState::Pressed {
+                return true;
+            }
+            if let Some(command) = shortcut_command(&event.logical_key, shift) {
+                commands.push(command);
+                return false;
+            }
+            let copies = matches!(
+                &event.logical_key,
+                winit::keyboard::Key::Character(character)
+                    if matches!(character.to_lowercase().as_str(), "c" | "x")
+            );
+            if copies {
+                let selection = text_controller.with(|field| field.selection().ordered_range());
+                controller.with(|controller| controller.copy_range(selection));
+            }
+            true
+        });
+        for command in commands {
+            run.call(command);
+        }
+    });
+
+    column().modifier(modifier).children(move || {
+        if toolbar {
+            rich_text_toolbar(controller, text_controller, enabled, run);
+        }
+        text_field()
+            .modifier(Modifier::new().fill_max_width())
+            .controller(text_controller)
+            .line_limit(TextFieldLineLimit::MultiLine)
+            .enabled(enabled)
+            .label_optional(label.clone())
+            .placeholder_optional(placeholder.clone())
+            .on_change(on_field_change);
+    });
+}
+
+#[tessera]
+fn rich_text_toolbar(
+    controller: State<RichTextEditorController>,
+    text_controller: State<TextInputController>,
+    enabled: bool,
+    run: CallbackWith<RichTextCommand>,
+) {
+    row()
+        .modifier(Modifier::new().padding_symmetric(Dp::ZERO, TOOLBAR_SPACING))
+        .children(move || {
+            for command in RichTextCommand::ALL {
+                let (active, available) = controller.with(|controller| {
+                    (
+                        controller.is_command_active(command),
+                        controller.is_command_available(command),
+                    )
+                });
+                let variant = if active {
+                    IconButtonVariant::FilledTonal
+                } else {
+                    IconButtonVariant::Standard
+                };
+                icon_button()
+                    .icon(command.icon())
+                    .variant(variant)
+                    .enabled(enabled && available)
+                    .on_click(move || {
+                        run.call(command);
+                        text_controller.with(|field| field.focus_handler().request_focus());
+                    });
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formatting_moves_with_edits_and_toggles_off() {
+        let mut controller = RichTextEditorController::new();
+        controller.apply_field_text("hello world".to_string());
+        controller.set_selection(0..5);
+        controller.toggle_format(RichTextFormat::Bold);
+        assert!(controller.is_format_active(RichTextFormat::Bold));
+
+        controller.set_selection(0..0);
+        controller.apply_field_text("oh hello world".to_string());
+        let content = controller.to_attributed_string();
+        assert_eq!(content.spans().len(), 1);
+        assert_eq!(content.spans()[0].range, 3..8);
+
+        controller.apply_field_text("oh hello! world".to_string());
+        assert_eq!(controller.to_attributed_string().spans()[0].range, 3..9);
+
+        controller.set_selection(3..9);
+        controller.toggle_format(RichTextFormat::Bold);
+        assert!(controller.to_attributed_string().spans().is_empty());
+    }
+
+    #[test]
+    fn lists_continue_end_and_undo_by_step() {
+        let mut controller = RichTextEditorController::new();
+        for text in ["a", "ab", "ab\n", "ab\nc"] {
+            controller.apply_field_text(text.to_string());
+        }
+        controller.set_selection(0..controller.text().len());
+        controller.toggle_list(RichTextList::Numbered);
+        assert_eq!(controller.text(), "1. ab\n2. c");
+
+        let end = controller.text().len();
+        controller.set_selection(end..end);
+        assert_eq!(
+            controller.apply_field_text("1. ab\n2. c\n".to_string()),
+            "1. ab\n2. c\n3. "
+        );
+        assert_eq!(
+            controller.apply_field_text("1. ab\n2. c\n3. \n".to_string()),
+            "1. ab\n2. c\n"
+        );
+
+        for _ in 0..3 {
+            controller.undo();
+        }
+        assert_eq!(controller.text(), "ab\nc");
+        controller.undo();
+        assert_eq!(controller.text(), "ab");
+        controller.undo();
+        assert_eq!(controller.text(), "");
+        assert!(!controller.can_undo());
+        controller.redo();
+        assert_eq!(controller.text(), "ab");
+    }
+}
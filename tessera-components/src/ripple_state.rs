@@ -7,7 +7,10 @@
 
 use std::time::Duration;
 
-use tessera_ui::{Dp, PxSize, current_frame_nanos, time::Instant};
+use tessera_ui::{
+    Dp, PxSize, current_frame_nanos,
+    time::{self, Instant},
+};
 
 use crate::theme::MaterialAlpha;
 
@@ -171,7 +174,7 @@ impl RippleState {
     /// state.start_animation([0.5, 0.5]);
     /// ```
     pub fn start_animation(&mut self, click_pos: [f32; 2]) {
-        let now = Instant::now();
+        let now = time::now();
         self.animation = Some(RippleAnimationState {
             start_instant: now,
             start_frame_nanos: current_frame_nanos(),
@@ -187,7 +190,7 @@ impl RippleState {
         size: PxSize,
         spec: RippleSpec,
     ) {
-        let now = Instant::now();
+        let now = time::now();
         let size = [size.width.to_f32(), size.height.to_f32()];
         let center = if spec.bounded { click_pos } else { [0.5, 0.5] };
 
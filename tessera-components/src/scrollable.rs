@@ -4,18 +4,16 @@
 //!
 //! Use to display content that might overflow the available space.
 pub(crate) mod scrollbar;
-use std::{collections::VecDeque, time::Duration};
-
-use tessera_foundation::gesture::{ScrollRecognizer, TapRecognizer};
 use tessera_ui::{
-    AxisConstraint, CallbackWith, Color, ComputedData, Constraint, Dp, LayoutResult,
+    AxisConstraint, CallbackWith, Color, ComputedData, Constraint, Dp, FlingDecay, LayoutResult,
     MeasurementError, Modifier, PointerInput, PointerInputModifierNode, Px, PxPosition, RenderSlot,
-    ScrollDeltaUnit, ScrollEventSource, State, current_frame_nanos,
+    ScrollDeltaUnit, ScrollEventSource, State, VelocityTracker, current_frame_nanos,
     focus::FocusRevealRequest,
+    gesture::{ScrollRecognizer, TapRecognizer},
     layout::{LayoutPolicy, MeasureScope, PlacementScope, RenderInput, RenderPolicy, layout},
     modifier::{FocusModifierExt as _, ModifierCapabilityExt as _},
     normalize_platform_scroll_delta, receive_frame_nanos, remember, tessera,
-    time::Instant,
+    time::{self, Instant},
     use_context,
 };
 
@@ -28,45 +26,12 @@ use crate::{
     scrollable::scrollbar::{ScrollBarState, scrollbar_h, scrollbar_v},
 };
 
-const SCROLL_INERTIA_DECAY_CONSTANT: f32 = 5.0;
-const SCROLL_INERTIA_MIN_VELOCITY: f32 = 10.0;
-const SCROLL_INERTIA_START_THRESHOLD: f32 = 50.0;
-const SCROLL_INERTIA_MAX_VELOCITY: f32 = 6000.0;
-const SCROLL_VELOCITY_SAMPLE_WINDOW: Duration = Duration::from_millis(90);
-const SCROLL_VELOCITY_IDLE_CUTOFF: Duration = Duration::from_millis(65);
-
-#[derive(Clone, PartialEq)]
-struct ScrollVelocityTracker {
-    samples: VecDeque<(Instant, f32, f32)>,
-    last_sample_time: Instant,
-}
-
 #[derive(Clone, PartialEq)]
 struct ActiveInertia {
-    velocity_x: f32,
-    velocity_y: f32,
+    fling: FlingDecay,
     last_tick_time: Instant,
 }
 
-fn clamp_inertia_velocity(vx: f32, vy: f32) -> (f32, f32) {
-    if !vx.is_finite() || !vy.is_finite() {
-        return (0.0, 0.0);
-    }
-
-    let magnitude_sq = vx * vx + vy * vy;
-    if !magnitude_sq.is_finite() {
-        return (0.0, 0.0);
-    }
-
-    let magnitude = magnitude_sq.sqrt();
-    if magnitude > SCROLL_INERTIA_MAX_VELOCITY && SCROLL_INERTIA_MAX_VELOCITY > 0.0 {
-        let scale = SCROLL_INERTIA_MAX_VELOCITY / magnitude;
-        return (vx * scale, vy * scale);
-    }
-
-    (vx, vy)
-}
-
 fn normalize_scroll_delta(
     delta_x: f32,
     delta_y: f32,
@@ -127,7 +92,7 @@ pub struct ScrollableController {
     /// The state for horizontal scrollbar
     scrollbar_state_h: ScrollBarState,
     /// Velocity tracking for touch-driven inertia.
-    velocity_tracker: Option<ScrollVelocityTracker>,
+    velocity_tracker: Option<VelocityTracker>,
     /// Active inertia state after a touch release.
     active_inertia: Option<ActiveInertia>,
 }
@@ -286,22 +251,18 @@ impl ScrollableController {
         self.cancel_inertia();
         let tracker = self
             .velocity_tracker
-            .get_or_insert_with(|| ScrollVelocityTracker::new(now));
-        tracker.push_delta(now, dx, dy);
+            .get_or_insert_with(VelocityTracker::new);
+        tracker.add_delta(now, dx, dy);
     }
 
     fn resolve_touch_velocity(&mut self, now: Instant) -> ScrollVelocity {
         let Some(mut tracker) = self.velocity_tracker.take() else {
             return ScrollVelocity::ZERO;
         };
-        if let Some((avg_vx, avg_vy)) = tracker.resolve(now) {
-            let velocity_magnitude = (avg_vx * avg_vx + avg_vy * avg_vy).sqrt();
-            if velocity_magnitude > SCROLL_INERTIA_START_THRESHOLD {
-                let (vx, vy) = clamp_inertia_velocity(avg_vx, avg_vy);
-                return ScrollVelocity::new(vx, vy);
-            }
-        }
-        ScrollVelocity::ZERO
+        tracker
+            .fling_velocity(now)
+            .map(|(vx, vy)| ScrollVelocity::new(vx, vy))
+            .unwrap_or(ScrollVelocity::ZERO)
     }
 
     fn start_inertia(&mut self, now: Instant, velocity: ScrollVelocity) {
@@ -309,8 +270,7 @@ impl ScrollableController {
             return;
         }
         self.active_inertia = Some(ActiveInertia {
-            velocity_x: velocity.x,
-            velocity_y: velocity.y,
+            fling: FlingDecay::new(velocity.x, velocity.y),
             last_tick_time: now,
         });
     }
@@ -339,8 +299,7 @@ impl ScrollableController {
             return;
         }
 
-        let delta_x = inertia.velocity_x * delta_time;
-        let delta_y = inertia.velocity_y * delta_time;
+        let (delta_x, delta_y) = inertia.fling.step(delta_time);
         if delta_x.abs() > 0.01 || delta_y.abs() > 0.01 {
             let new_target = self.target_position.saturating_offset(
                 Px::saturating_from_f32(delta_x),
@@ -357,21 +316,15 @@ impl ScrollableController {
             let consumed_y = constrained_target.y.to_f32() - self.target_position.y.to_f32();
             self.target_position = constrained_target;
             if consumed_x.abs() <= f32::EPSILON {
-                inertia.velocity_x = 0.0;
+                inertia.fling.stop_x();
             }
             if consumed_y.abs() <= f32::EPSILON {
-                inertia.velocity_y = 0.0;
+                inertia.fling.stop_y();
             }
         }
 
-        let decay = (-SCROLL_INERTIA_DECAY_CONSTANT * delta_time).exp();
-        inertia.velocity_x *= decay;
-        inertia.velocity_y *= decay;
         inertia.last_tick_time = now;
-
-        if inertia.velocity_x.abs() >= SCROLL_INERTIA_MIN_VELOCITY
-            || inertia.velocity_y.abs() >= SCROLL_INERTIA_MIN_VELOCITY
-        {
+        if !inertia.fling.is_finished() {
             self.active_inertia = Some(inertia);
         }
     }
@@ -391,87 +344,6 @@ impl ScrollableController {
     }
 }
 
-impl ScrollVelocityTracker {
-    fn new(now: Instant) -> Self {
-        Self {
-            samples: VecDeque::new(),
-            last_sample_time: now,
-        }
-    }
-
-    fn push_delta(&mut self, now: Instant, dx: f32, dy: f32) {
-        let delta_time = now.duration_since(self.last_sample_time).as_secs_f32();
-        self.last_sample_time = now;
-        if delta_time <= 0.0 {
-            return;
-        }
-
-        let vx = dx / delta_time;
-        let vy = dy / delta_time;
-        let (vx, vy) = clamp_inertia_velocity(vx, vy);
-        self.samples.push_back((now, vx, vy));
-        self.prune(now);
-    }
-
-    fn resolve(&mut self, now: Instant) -> Option<(f32, f32)> {
-        self.prune(now);
-
-        if self.samples.is_empty() {
-            return None;
-        }
-
-        let idle_time = now.duration_since(self.last_sample_time);
-
-        let mut weighted_sum_x = 0.0f32;
-        let mut weighted_sum_y = 0.0f32;
-        let mut total_weight = 0.0f32;
-        let window_secs = SCROLL_VELOCITY_SAMPLE_WINDOW
-            .as_secs_f32()
-            .max(f32::EPSILON);
-
-        for &(timestamp, vx, vy) in &self.samples {
-            let age_secs = now
-                .duration_since(timestamp)
-                .as_secs_f32()
-                .clamp(0.0, window_secs);
-            let weight = (window_secs - age_secs).max(0.0);
-            if weight > 0.0 {
-                weighted_sum_x += vx * weight;
-                weighted_sum_y += vy * weight;
-                total_weight += weight;
-            }
-        }
-
-        if total_weight <= f32::EPSILON {
-            self.samples.clear();
-            return None;
-        }
-
-        let avg_x = weighted_sum_x / total_weight;
-        let avg_y = weighted_sum_y / total_weight;
-
-        let damping = 1.0 - idle_time.as_secs_f32() / SCROLL_VELOCITY_IDLE_CUTOFF.as_secs_f32();
-        let damping = damping.clamp(0.0, 1.0);
-        let (avg_x, avg_y) = clamp_inertia_velocity(avg_x * damping, avg_y * damping);
-
-        Some((avg_x, avg_y))
-    }
-
-    fn is_idle(&self, now: Instant) -> bool {
-        now.duration_since(self.last_sample_time) >= SCROLL_VELOCITY_IDLE_CUTOFF
-    }
-
-    fn prune(&mut self, now: Instant) {
-        while let Some(&(timestamp, _, _)) = self.samples.front() {
-            if now.duration_since(timestamp) > SCROLL_VELOCITY_SAMPLE_WINDOW {
-                self.samples.pop_front();
-            } else {
-                break;
-            }
-        }
-    }
-}
-
 #[derive(Clone, PartialEq)]
 struct ScrollableAlongsideLayout {
     vertical: bool,
@@ -1000,7 +872,7 @@ impl PointerInputModifierNode for ScrollableViewportPointerModifierNode {
             .cursor_position_rel
             .map(|pos| is_position_inside_bounds(input.computed_data, pos))
             .unwrap_or(false);
-        let now = Instant::now();
+        let now = time::now();
         let frame_nanos = current_frame_nanos();
         let should_handle_scroll = is_cursor_in_component;
         let tap_result = self.tap_recognizer.with_mut(|recognizer| {
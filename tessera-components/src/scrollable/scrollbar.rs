@@ -1,12 +1,12 @@
 use std::sync::Arc;
 
 use parking_lot::RwLock;
-use tessera_foundation::gesture::{DragRecognizer, TapRecognizer};
 use tessera_ui::{
     AccessibilityActionHandler, AccessibilityNode, AxisConstraint, Color, ComputedData, Constraint,
     Dp, LayoutResult, MeasurementError, Modifier, Px, PxPosition, SemanticsModifierNode, State,
     accesskit::{Action, Role},
     current_frame_nanos,
+    gesture::{DragRecognizer, TapRecognizer},
     layout::{LayoutPolicy, MeasureScope, PlacementScope, layout},
     modifier::ModifierCapabilityExt as _,
     receive_frame_nanos, remember, tessera,
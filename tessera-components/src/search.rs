@@ -4,10 +4,9 @@
 //!
 //! Use to collect search queries and show suggestions or results as the user
 //! types.
-use tessera_foundation::gesture::TapRecognizer;
 use tessera_ui::{
-    CallbackWith, Color, Dp, Modifier, RenderSlot, State, layout::layout, remember, tessera,
-    use_context, winit,
+    CallbackWith, Color, Dp, Modifier, RenderSlot, State, gesture::TapRecognizer, layout::layout,
+    remember, tessera, use_context, winit,
 };
 
 use crate::{
@@ -3,12 +3,12 @@
 //! ## Usage
 //!
 //! Use to allow users to select a value from a continuous range.
-use tessera_foundation::gesture::{DragRecognizer, TapRecognizer};
 use tessera_ui::{
     AccessibilityActionHandler, AccessibilityNode, AxisConstraint, CallbackWith, Color,
     ComputedData, Constraint, Dp, FocusProperties, FocusRequester, LayoutResult, MeasurementError,
     Modifier, PointerInput, PointerInputModifierNode, Px, PxPosition, SemanticsModifierNode, State,
     accesskit::{Action, Role},
+    gesture::{DragRecognizer, TapRecognizer},
     layout::{LayoutPolicy, MeasureScope, layout},
     modifier::{CursorModifierExt as _, FocusModifierExt as _, ModifierCapabilityExt as _},
     remember, tessera, use_context,
@@ -1,8 +1,8 @@
-use tessera_foundation::gesture::{DragRecognizer, TapRecognizer};
 use tessera_ui::{
     AccessibilityActionHandler, AccessibilityNode, CallbackWith, ComputedData, FocusRequester,
     PointerInput, Px, PxPosition, State,
     accesskit::{Action, Role},
+    gesture::{DragRecognizer, TapRecognizer},
 };
 
 use super::{ACCESSIBILITY_STEP, SliderArgs, SliderController, SliderLayout};
@@ -128,6 +128,9 @@ pub(super) fn handle_slider_state(
             is_in_component,
         )
     });
+    if let Some(pointer_id) = drag_recognizer.with(|recognizer| recognizer.active_pointer()) {
+        input.capture_pointer(pointer_id);
+    }
 
     let mut new_value: Option<f32> = None;
 
@@ -336,6 +339,9 @@ pub(super) fn handle_range_slider_state(
             is_in_component,
         )
     });
+    if let Some(pointer_id) = drag_recognizer.with(|recognizer| recognizer.active_pointer()) {
+        input.capture_pointer(pointer_id);
+    }
 
     let mut new_start: Option<f32> = None;
     let mut new_end: Option<f32> = None;
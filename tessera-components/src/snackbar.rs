@@ -7,7 +7,10 @@
 use std::{collections::VecDeque, time::Duration};
 
 use tessera_ui::{
-    Callback, CallbackWith, Color, Dp, Modifier, State, current_frame_nanos, layout::layout,
+    Callback, CallbackWith, Color, Dp, Modifier, State,
+    accessibility::{Politeness, announce},
+    current_frame_nanos,
+    layout::layout,
     provide_context, receive_frame_nanos, remember, tessera, use_context,
 };
 
@@ -258,8 +261,11 @@ impl SnackbarHostState {
             self.advance_queue();
         }
 
-        if self.current.is_some() && self.current_started_frame_nanos.is_none() {
+        if let Some(current) = &self.current
+            && self.current_started_frame_nanos.is_none()
+        {
             self.current_started_frame_nanos = Some(frame_nanos);
+            announce(current.resolved.message.clone(), Politeness::Polite);
         }
 
         let mut should_dismiss = false;
@@ -635,6 +641,8 @@ pub fn snackbar(
 ///
 /// Display queued snackbars driven by a [`SnackbarHostState`].
 ///
+/// Each message is also announced to screen readers when it appears.
+///
 /// ## Usage
 ///
 /// Use with [`scaffold`](crate::scaffold::scaffold) to show transient messages
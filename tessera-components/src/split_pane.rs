@@ -0,0 +1,606 @@
+//! Resizable split panes divided by a draggable handle.
+//!
+//! ## Usage
+//!
+//! Put a sidebar, inspector or preview next to the main content.
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use tessera_ui::{
+    AxisConstraint, CallbackWith, ComputedData, Constraint, Dp, MeasurementError, Modifier, Px,
+    PxPosition, RenderSlot, State,
+    accesskit::Role,
+    gesture::{DragAxis, DragRecognizer, DragSettings, TapRecognizer, TapSettings},
+    layout::{LayoutPolicy, LayoutResult, MeasureScope, layout},
+    modifier::{CursorModifierExt as _, FocusModifierExt as _},
+    remember, tessera, use_context, winit,
+};
+
+use crate::{
+    alignment::Alignment,
+    boxed::boxed,
+    column::column,
+    divider::{horizontal_divider, vertical_divider},
+    icon::icon,
+    material_icons::filled,
+    modifier::{ModifierExt as _, SemanticsArgs, with_keyboard_input, with_pointer_input},
+    painter::Painter,
+    pos_misc::is_position_inside_bounds,
+    row::row,
+    theme::MaterialTheme,
+};
+
+const HANDLE_THICKNESS: Dp = Dp(8.0);
+const COLLAPSIBLE_HANDLE_THICKNESS: Dp = Dp(16.0);
+const COLLAPSE_ICON_SIZE: Dp = Dp(16.0);
+const KEYBOARD_STEP: Dp = Dp(16.0);
+
+/// Direction in which a [`split_pane`] places its panes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "saveable", derive(serde::Serialize, serde::Deserialize))]
+pub enum SplitAxis {
+    /// Panes side by side, divided by a vertical handle.
+    #[default]
+    Horizontal,
+    /// Panes stacked, divided by a horizontal handle.
+    Vertical,
+}
+
+/// One of the two panes of a [`split_pane`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "saveable", derive(serde::Serialize, serde::Deserialize))]
+pub enum SplitPanePane {
+    /// The left or top pane.
+    First,
+    /// The right or bottom pane.
+    Second,
+}
+
+/// Size ratio and collapsed pane of a [`split_pane`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "saveable", derive(serde::Serialize, serde::Deserialize))]
+pub struct SplitPaneController {
+    ratio: f32,
+    collapsed: Option<SplitPanePane>,
+}
+
+impl SplitPaneController {
+    /// Creates a controller giving the first pane `ratio` of the space.
+    pub fn new(ratio: f32) -> Self {
+        Self {
+            ratio: ratio.clamp(0.0, 1.0),
+            collapsed: None,
+        }
+    }
+
+    /// Returns the share of the space given to the first pane, before size
+    /// limits apply.
+    pub fn ratio(&self) -> f32 {
+        self.ratio
+    }
+
+    /// Sets the share of the space given to the first pane.
+    pub fn set_ratio(&mut self, ratio: f32) {
+        self.ratio = ratio.clamp(0.0, 1.0);
+    }
+
+    /// Returns the collapsed pane, if any.
+    pub fn collapsed(&self) -> Option<SplitPanePane> {
+        self.collapsed
+    }
+
+    /// Collapses `pane`, giving all space to the other one.
+    pub fn collapse(&mut self, pane: SplitPanePane) {
+        self.collapsed = Some(pane);
+    }
+
+    /// Restores a collapsed pane to its previous size.
+    pub fn expand(&mut self) {
+        self.collapsed = None;
+    }
+
+    /// Moves the handle by `delta`, expanding a collapsed pane.
+    fn resize_by(&mut self, delta: Px, usable: Px, limits: PaneLimits) {
+        if usable <= Px::ZERO {
+            return;
+        }
+        let first = first_pane_size(usable, self.ratio, self.collapsed, limits);
+        let first = limits.clamp_first(first + delta, usable);
+        self.ratio = first.0 as f32 / usable.0 as f32;
+        self.collapsed = None;
+    }
+}
+
+impl Default for SplitPaneController {
+    fn default() -> Self {
+        Self::new(0.5)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct PaneLimits {
+    min_first: Px,
+    max_first: Option<Px>,
+    min_second: Px,
+    max_second: Option<Px>,
+}
+
+impl PaneLimits {
+    /// Clamps the first pane's size so both panes respect their limits; when
+    /// the limits conflict, the first pane's minimum wins.
+    fn clamp_first(&self, first: Px, usable: Px) -> Px {
+        let mut high = usable - self.min_second;
+        if let Some(max_first) = self.max_first {
+            high = high.min(max_first);
+        }
+        let mut low = self.min_first;
+        if let Some(max_second) = self.max_second {
+            low = low.max(usable - max_second);
+        }
+        first.min(high).max(low).max(Px::ZERO).min(usable)
+    }
+}
+
+fn first_pane_size(
+    usable: Px,
+    ratio: f32,
+    collapsed: Option<SplitPanePane>,
+    limits: PaneLimits,
+) -> Px {
+    match collapsed {
+        Some(SplitPanePane::First) => Px::ZERO,
+        Some(SplitPanePane::Second) => usable,
+        None => limits.clamp_first(Px((usable.0 as f32 * ratio).round() as i32), usable),
+    }
+}
+
+/// Space shared by the panes in the last measure, read by the handle.
+#[derive(Clone, Default)]
+struct UsableSize(Arc<RwLock<Px>>);
+
+impl UsableSize {
+    fn get(&self) -> Px {
+        *self.0.read()
+    }
+}
+
+impl PartialEq for UsableSize {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct SplitPaneLayout {
+    axis: SplitAxis,
+    ratio: f32,
+    collapsed: Option<SplitPanePane>,
+    limits: PaneLimits,
+    handle: Px,
+    usable: UsableSize,
+}
+
+impl LayoutPolicy for SplitPaneLayout {
+    fn measure(&self, input: &MeasureScope<'_>) -> Result<LayoutResult, MeasurementError> {
+        let parent = input.parent_constraint();
+        let (main, cross) = match self.axis {
+            SplitAxis::Horizontal => (parent.width(), parent.height()),
+            SplitAxis::Vertical => (parent.height(), parent.width()),
+        };
+        let main = main
+            .resolve_max()
+            .expect("split_pane requires a bounded size along its axis");
+        let usable = (main - self.handle).max(Px::ZERO);
+        *self.usable.0.write() = usable;
+        let first = first_pane_size(usable, self.ratio, self.collapsed, self.limits);
+        let cross_constraint = match cross.resolve_max() {
+            Some(max) => AxisConstraint::exact(max),
+            None => cross.without_min(),
+        };
+        let constraint = |size: Px| match self.axis {
+            SplitAxis::Horizontal => Constraint::new(AxisConstraint::exact(size), cross_constraint),
+            SplitAxis::Vertical => Constraint::new(cross_constraint, AxisConstraint::exact(size)),
+        };
+        let position = |offset: Px| match self.axis {
+            SplitAxis::Horizontal => PxPosition::new(offset, Px::ZERO),
+            SplitAxis::Vertical => PxPosition::new(Px::ZERO, offset),
+        };
+
+        let children = input.children();
+        let mut result = LayoutResult::default();
+        let mut cross_size = Px::ZERO;
+        let mut offset = Px::ZERO;
+        for (child, size) in children.iter().zip([first, self.handle, usable - first]) {
+            let measured = child.measure(&constraint(size))?.size();
+            cross_size = cross_size.max(match self.axis {
+                SplitAxis::Horizontal => measured.height,
+                SplitAxis::Vertical => measured.width,
+            });
+            result.place_child(*child, position(offset));
+            offset += size;
+        }
+        let cross_size = cross.clamp(cross_size);
+        Ok(result.with_size(match self.axis {
+            SplitAxis::Horizontal => ComputedData {
+                width: main,
+                height: cross_size,
+            },
+            SplitAxis::Vertical => ComputedData {
+                width: cross_size,
+                height: main,
+            },
+        }))
+    }
+}
+
+/// # split_pane
+///
+/// Divides space between two panes with a handle that resizes them.
+///
+/// Dragging the handle resizes the panes within their size limits, and the
+/// arrow keys move it when it is focused. With `collapsible` set, buttons on
+/// the handle collapse either pane and restore it. With a `save_key` and the
+/// `saveable` feature, the sizes are restored after the app restarts.
+///
+/// ## Usage
+///
+/// Show a resizable sidebar or inspector beside the main content.
+///
+/// ## Parameters
+///
+/// - `first` — left or top pane
+/// - `second` — right or bottom pane
+/// - `axis` — optional direction of the panes; defaults to
+///   [`SplitAxis::Horizontal`]
+/// - `initial_ratio` — optional share of the space first given to the first
+///   pane; defaults to `0.5`
+/// - `min_first_size` / `max_first_size` — optional size limits of the first
+///   pane
+/// - `min_second_size` / `max_second_size` — optional size limits of the second
+///   pane
+/// - `collapsible` — whether the handle shows collapse buttons; defaults to
+///   `false`
+/// - `on_resize` — optional callback invoked with the new ratio after the
+///   handle is moved
+/// - `save_key` — optional key the sizes are saved under; requires the
+///   `saveable` feature and is ignored without it
+/// - `controller` — optional controller holding the ratio and collapsed pane
+/// - `modifier` — optional modifier chain applied to the container
+///
+/// ## Examples
+///
+/// ```
+/// # use tessera_ui::tessera;
+/// # #[tessera]
+/// # fn component() {
+/// use tessera_components::{split_pane::split_pane, text::text};
+/// use tessera_ui::Dp;
+/// # use tessera_components::theme::{MaterialTheme, material_theme};
+///
+/// # material_theme()
+/// #     .theme(|| MaterialTheme::default())
+/// #     .child(|| {
+/// split_pane(
+///     || {
+///         text().content("Files");
+///     },
+///     || {
+///         text().content("Editor");
+///     },
+/// )
+/// .initial_ratio(0.25)
+/// .min_first_size(Dp(160.0))
+/// .collapsible(true)
+/// .save_key("editor.sidebar");
+/// # });
+/// # }
+/// # component();
+/// ```
+#[tessera]
+pub fn split_pane(
+    first: RenderSlot,
+    second: RenderSlot,
+    axis: Option<SplitAxis>,
+    initial_ratio: Option<f32>,
+    min_first_size: Option<Dp>,
+    max_first_size: Option<Dp>,
+    min_second_size: Option<Dp>,
+    max_second_size: Option<Dp>,
+    collapsible: Option<bool>,
+    on_resize: Option<CallbackWith<f32>>,
+    #[prop(into)] save_key: Option<String>,
+    controller: Option<State<SplitPaneController>>,
+    modifier: Option<Modifier>,
+) {
+    let axis = axis.unwrap_or_default();
+    let collapsible = collapsible.unwrap_or(false);
+    let controller = controller
+        .unwrap_or_else(|| remember_controller(save_key.as_deref(), initial_ratio.unwrap_or(0.5)));
+    let limits = PaneLimits {
+        min_first: min_first_size.map_or(Px::ZERO, |size| size.to_px()),
+        max_first: max_first_size.map(|size| size.to_px()),
+        min_second: min_second_size.map_or(Px::ZERO, |size| size.to_px()),
+        max_second: max_second_size.map(|size| size.to_px()),
+    };
+    let handle = if collapsible {
+        COLLAPSIBLE_HANDLE_THICKNESS
+    } else {
+        HANDLE_THICKNESS
+    };
+    let usable: UsableSize = remember(UsableSize::default).get();
+    let SplitPaneController { ratio, collapsed } = controller.get();
+
+    layout()
+        .modifier(modifier.unwrap_or_default())
+        .layout_policy(SplitPaneLayout {
+            axis,
+            ratio,
+            collapsed,
+            limits,
+            handle: handle.to_px(),
+            usable: usable.clone(),
+        })
+        .child(move || {
+            layout()
+                .modifier(Modifier::new().clip_to_bounds())
+                .child(move || {
+                    first.render();
+                });
+            split_pane_handle(
+                axis,
+                limits,
+                collapsible,
+                usable.clone(),
+                on_resize,
+                controller,
+            );
+            layout()
+                .modifier(Modifier::new().clip_to_bounds())
+                .child(move || {
+                    second.render();
+                });
+        });
+}
+
+#[cfg(feature = "saveable")]
+fn remember_controller(save_key: Option<&str>, ratio: f32) -> State<SplitPaneController> {
+    match save_key {
+        Some(key) => {
+            tessera_ui::saveable::remember_saveable(key, || SplitPaneController::new(ratio))
+        }
+        None => remember(|| SplitPaneController::new(ratio)),
+    }
+}
+
+#[cfg(not(feature = "saveable"))]
+fn remember_controller(_save_key: Option<&str>, ratio: f32) -> State<SplitPaneController> {
+    remember(|| SplitPaneController::new(ratio))
+}
+
+#[tessera]
+fn split_pane_handle(
+    axis: SplitAxis,
+    limits: PaneLimits,
+    collapsible: bool,
+    usable: UsableSize,
+    on_resize: Option<CallbackWith<f32>>,
+    controller: State<SplitPaneController>,
+) {
+    let scheme = use_context::<MaterialTheme>()
+        .expect("MaterialTheme must be provided")
+        .get()
+        .color_scheme;
+    let drag_recognizer = remember(|| {
+        DragRecognizer::new(DragSettings {
+            axis: Some(match axis {
+                SplitAxis::Horizontal => DragAxis::Horizontal,
+                SplitAxis::Vertical => DragAxis::Vertical,
+            }),
+            ..Default::default()
+        })
+    });
+    let (cursor_icon, decrease_key, increase_key) = match axis {
+        SplitAxis::Horizontal => (
+            winit::window::CursorIcon::ColResize,
+            winit::keyboard::NamedKey::ArrowLeft,
+            winit::keyboard::NamedKey::ArrowRight,
+        ),
+        SplitAxis::Vertical => (
+            winit::window::CursorIcon::RowResize,
+            winit::keyboard::NamedKey::ArrowUp,
+            winit::keyboard::NamedKey::ArrowDown,
+        ),
+    };
+    let ratio = controller.with(|controller| controller.ratio);
+    let base = Modifier::new()
+        .fill_max_size()
+        .hover_cursor_icon(cursor_icon)
+        .semantics(SemanticsArgs {
+            role: Some(Role::Splitter),
+            numeric_value: Some(ratio as f64),
+            numeric_range: Some((0.0, 1.0)),
+            focusable: true,
+            ..Default::default()
+        })
+        .focusable();
+    let pointer_usable = usable.clone();
+    let modifier = with_pointer_input(base, move |mut input| {
+        let is_inside = input
+            .cursor_position_rel
+            .is_some_and(|position| is_position_inside_bounds(input.computed_data, position));
+        // The handle follows the drag, so deltas are taken in window
+        // coordinates.
+        let cursor = input.cursor_position_abs();
+        let drag = drag_recognizer.with_mut(|recognizer| {
+            recognizer.update(
+                input.pass,
+                input.pointer_changes.as_mut_slice(),
+                cursor,
+                is_inside,
+            )
+        });
+        if drag.updated {
+            let delta = match axis {
+                SplitAxis::Horizontal => drag.delta_x,
+                SplitAxis::Vertical => drag.delta_y,
+            };
+            resize(controller, delta, pointer_usable.get(), limits, on_resize);
+        }
+    });
+    let modifier = with_keyboard_input(modifier, move |mut input| {
+        let mut delta = Px::ZERO;
+        for event in input.keyboard_events.iter() {
+            if event.state != winit::event::ElementState::Pressed {
+                continue;
+            }
+            match &event.logical_key {
+                winit::keyboard::Key::Named(key) if *key == decrease_key => {
+                    delta -= KEYBOARD_STEP.to_px();
+                }
+                winit::keyboard::Key::Named(key) if *key == increase_key => {
+                    delta += KEYBOARD_STEP.to_px();
+                }
+                _ => {}
+            }
+        }
+        if delta != Px::ZERO {
+            resize(controller, delta, usable.get(), limits, on_resize);
+            input.block_keyboard();
+        }
+    });
+
+    let line_color = scheme.outline_variant;
+    boxed()
+        .modifier(modifier)
+        .alignment(Alignment::Center)
+        .children(move || {
+            match axis {
+                SplitAxis::Horizontal => {
+                    vertical_divider().color(line_color);
+                }
+                SplitAxis::Vertical => {
+                    horizontal_divider().color(line_color);
+                }
+            }
+            if collapsible {
+                collapse_buttons(axis, controller);
+            }
+        });
+}
+
+fn resize(
+    controller: State<SplitPaneController>,
+    delta: Px,
+    usable: Px,
+    limits: PaneLimits,
+    on_resize: Option<CallbackWith<f32>>,
+) {
+    let ratio = controller.with_mut(|controller| {
+        controller.resize_by(delta, usable, limits);
+        controller.ratio
+    });
+    if let Some(on_resize) = on_resize {
+        on_resize.call(ratio);
+    }
+}
+
+#[tessera]
+fn collapse_buttons(axis: SplitAxis, controller: State<SplitPaneController>) {
+    let collapsed = controller.with(|controller| controller.collapsed);
+    let (toward_first, toward_second) = match axis {
+        SplitAxis::Horizontal => (filled::CHEVRON_LEFT_SVG, filled::CHEVRON_RIGHT_SVG),
+        SplitAxis::Vertical => (filled::EXPAND_LESS_SVG, filled::EXPAND_MORE_SVG),
+    };
+    let buttons = move || {
+        // Each button collapses the pane it points at, or restores the
+        // other pane when that one is collapsed.
+        if collapsed != Some(SplitPanePane::First) {
+            collapse_button(toward_first.into(), SplitPanePane::First, controller);
+        }
+        if collapsed != Some(SplitPanePane::Second) {
+            collapse_button(toward_second.into(), SplitPanePane::Second, controller);
+        }
+    };
+    match axis {
+        SplitAxis::Horizontal => {
+            column().children(buttons);
+        }
+        SplitAxis::Vertical => {
+            row().children(buttons);
+        }
+    }
+}
+
+#[tessera]
+fn collapse_button(painter: Painter, pane: SplitPanePane, controller: State<SplitPaneController>) {
+    let tap_recognizer = remember(|| {
+        // Consumed so the press does not start a drag of the handle.
+        TapRecognizer::new(TapSettings {
+            consume_on_press: true,
+            consume_on_release: true,
+            consume_on_tap: true,
+            ..Default::default()
+        })
+    });
+    let modifier = with_pointer_input(
+        Modifier::new()
+            .size(COLLAPSE_ICON_SIZE, COLLAPSE_ICON_SIZE)
+            .hover_cursor_icon(winit::window::CursorIcon::Pointer),
+        move |mut input| {
+            let is_inside = input
+                .cursor_position_rel
+                .is_some_and(|position| is_position_inside_bounds(input.computed_data, position));
+            let tap = tap_recognizer.with_mut(|recognizer| {
+                recognizer.update(
+                    input.pass,
+                    input.pointer_changes.as_mut_slice(),
+                    input.cursor_position_rel,
+                    is_inside,
+                )
+            });
+            if tap.tapped {
+                controller.with_mut(|controller| match controller.collapsed {
+                    Some(_) => controller.expand(),
+                    None => controller.collapse(pane),
+                });
+            }
+        },
+    );
+    boxed().modifier(modifier).children(move || {
+        icon().painter(painter.clone()).size(COLLAPSE_ICON_SIZE);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use tessera_ui::Px;
+
+    use super::{PaneLimits, SplitPaneController, SplitPanePane, first_pane_size};
+
+    #[test]
+    fn pane_limits_clamp_ratio_and_drag_expands_collapsed_pane() {
+        let limits = PaneLimits {
+            min_first: Px(100),
+            max_first: Some(Px(600)),
+            min_second: Px(200),
+            max_second: None,
+        };
+        let usable = Px(1000);
+        assert_eq!(first_pane_size(usable, 0.05, None, limits), Px(100));
+        assert_eq!(first_pane_size(usable, 0.5, None, limits), Px(500));
+        assert_eq!(first_pane_size(usable, 0.9, None, limits), Px(600));
+        assert_eq!(
+            first_pane_size(usable, 0.5, Some(SplitPanePane::First), limits),
+            Px::ZERO
+        );
+
+        let mut controller = SplitPaneController::new(0.5);
+        controller.collapse(SplitPanePane::First);
+        controller.resize_by(Px(50), usable, limits);
+        assert_eq!(controller.collapsed(), None);
+        assert_eq!(
+            first_pane_size(usable, controller.ratio(), None, limits),
+            Px(100)
+        );
+    }
+}
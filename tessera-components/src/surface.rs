@@ -3,13 +3,13 @@
 //! ## Usage
 //!
 //! Use as a base for buttons, cards, or any styled and interactive region.
-use tessera_foundation::gesture::{LongPressRecognizer, TapRecognizer};
 use tessera_ui::{
     Callback, Color, ComputedData, Constraint, Dp, FocusProperties, FocusRequester, LayoutResult,
     MeasurementError, Modifier, PointerInput, PointerInputModifierNode, Px, PxPosition, PxSize,
     RenderSlot, State,
     accesskit::Role,
     current_frame_nanos,
+    gesture::{LongPressRecognizer, TapRecognizer},
     layout::{LayoutPolicy, MeasureScope, RenderInput, RenderPolicy, layout},
     modifier::ModifierCapabilityExt as _,
     provide_context, receive_frame_nanos, remember, tessera, use_context,
@@ -20,7 +20,7 @@ use crate::{
     alignment::Alignment,
     modifier::{
         ClickableArgs, InteractionState, ModifierExt, PointerEventContext, SemanticsArgs,
-        ShadowArgs,
+        ShadowArgs, shape_clip,
     },
     pipelines::{shape::command::ShapeCommand, simple_rect::command::SimpleRectCommand},
     pos_misc::is_position_inside_bounds,
@@ -600,6 +600,13 @@ impl RenderPolicy for SurfaceLayout {
 
             metadata.fragment_mut().push_draw_command(drawable);
         }
+
+        if self.args.child.is_some() {
+            metadata.set_clip_shape(shape_clip(
+                self.args.shape,
+                PxSize::new(size.width, size.height),
+            ));
+        }
     }
 }
 
@@ -0,0 +1,286 @@
+//! SVG documents rasterized at layout resolution with a shared cache.
+//!
+//! ## Usage
+//!
+//! Draw SVGs with gradients, masks, filters or text that tessellation rejects.
+use std::{
+    fmt, fs,
+    hash::{DefaultHasher, Hash, Hasher},
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    sync::{Arc, OnceLock},
+};
+
+use lru::LruCache;
+use parking_lot::Mutex;
+use resvg::tiny_skia::{Pixmap, Transform};
+use tessera_ui::{AssetExt, PxSize};
+use thiserror::Error;
+use usvg::Tree;
+
+use crate::pipelines::image::command::ImageData;
+
+const RASTER_CACHE_CAPACITY: usize = 64;
+// Keeps zoomed or oversized layouts from allocating unbounded pixmaps.
+const MAX_RASTER_DIMENSION: u32 = 4096;
+
+/// Errors that can occur while parsing SVG documents for rasterization.
+#[derive(Debug, Error)]
+pub enum SvgLoadError {
+    /// Failed to read a file from disk.
+    #[error("failed to read SVG from {path}: {source}")]
+    Io {
+        /// Failing path.
+        path: String,
+        /// Underlying IO error.
+        #[source]
+        source: std::io::Error,
+    },
+    /// Failed to read bytes from an asset handle.
+    #[error("failed to read SVG bytes from asset: {source}")]
+    AssetRead {
+        /// Underlying IO error.
+        #[source]
+        source: std::io::Error,
+    },
+    /// SVG parsing failed.
+    #[error("failed to parse SVG: {0}")]
+    Parse(#[from] usvg::Error),
+    /// The SVG viewport dimensions are invalid.
+    #[error("SVG viewport must have finite, positive size")]
+    InvalidViewport,
+}
+
+/// Parsed SVG document that is rasterized on demand.
+///
+/// Unlike [`crate::image_vector::ImageVectorData`], every SVG feature that
+/// resvg understands is supported, at the cost of re-rasterizing whenever the
+/// drawn size changes.
+#[derive(Clone)]
+pub struct SvgData {
+    tree: Arc<Tree>,
+    id: u64,
+    /// Intrinsic width taken from the SVG viewport.
+    pub viewport_width: f32,
+    /// Intrinsic height taken from the SVG viewport.
+    pub viewport_height: f32,
+}
+
+impl SvgData {
+    /// Parses SVG bytes, resolving relative references against
+    /// `resources_dir`.
+    pub fn from_bytes(bytes: &[u8], resources_dir: Option<PathBuf>) -> Result<Self, SvgLoadError> {
+        let mut hasher = DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        resources_dir.hash(&mut hasher);
+        let options = usvg::Options {
+            resources_dir,
+            ..Default::default()
+        };
+        let tree = Tree::from_data(bytes, &options)?;
+        let size = tree.size();
+        if !size.width().is_finite()
+            || !size.height().is_finite()
+            || size.width() <= 0.0
+            || size.height() <= 0.0
+        {
+            return Err(SvgLoadError::InvalidViewport);
+        }
+
+        Ok(Self {
+            viewport_width: size.width(),
+            viewport_height: size.height(),
+            tree: Arc::new(tree),
+            id: hasher.finish(),
+        })
+    }
+
+    /// Returns the document rasterized to `size` physical pixels.
+    ///
+    /// Results are cached by document and physical size, which already
+    /// reflects the window scale factor, so a DPI change rasterizes afresh
+    /// while repeated frames at the same size reuse the pixels. Returns `None`
+    /// for empty sizes.
+    pub fn rasterize(&self, size: PxSize) -> Option<Arc<ImageData>> {
+        let key = RasterKey {
+            id: self.id,
+            width: raster_dimension(size.width.0)?,
+            height: raster_dimension(size.height.0)?,
+        };
+        if let Some(data) = raster_cache().lock().get(&key) {
+            return Some(data.clone());
+        }
+        let data = Arc::new(render(&self.tree, key.width, key.height)?);
+        raster_cache().lock().put(key, data.clone());
+        Some(data)
+    }
+}
+
+impl PartialEq for SvgData {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for SvgData {}
+
+impl Hash for SvgData {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl fmt::Debug for SvgData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SvgData")
+            .field("viewport_width", &self.viewport_width)
+            .field("viewport_height", &self.viewport_height)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Converts a source into a shared parsed SVG document.
+pub trait TryIntoSvgData {
+    /// Convert this source into a parsed SVG document.
+    fn try_into_svg_data(self) -> Result<Arc<SvgData>, SvgLoadError>;
+}
+
+/// Parses an SVG document from an asset handle.
+pub fn try_svg_asset<T>(asset: T) -> Result<Arc<SvgData>, SvgLoadError>
+where
+    T: AssetExt,
+{
+    let bytes = asset
+        .read()
+        .map_err(|source| SvgLoadError::AssetRead { source })?;
+    bytes.as_ref().try_into_svg_data()
+}
+
+fn load_svg_from_path(path: &Path) -> Result<SvgData, SvgLoadError> {
+    let bytes = fs::read(path).map_err(|source| SvgLoadError::Io {
+        path: path.to_string_lossy().into_owned(),
+        source,
+    })?;
+    let resources_dir = path.parent().map(|parent| parent.to_path_buf());
+    SvgData::from_bytes(&bytes, resources_dir)
+}
+
+impl TryIntoSvgData for SvgData {
+    fn try_into_svg_data(self) -> Result<Arc<SvgData>, SvgLoadError> {
+        Ok(Arc::new(self))
+    }
+}
+
+impl TryIntoSvgData for Vec<u8> {
+    fn try_into_svg_data(self) -> Result<Arc<SvgData>, SvgLoadError> {
+        Ok(Arc::new(SvgData::from_bytes(&self, None)?))
+    }
+}
+
+impl TryIntoSvgData for &[u8] {
+    fn try_into_svg_data(self) -> Result<Arc<SvgData>, SvgLoadError> {
+        Ok(Arc::new(SvgData::from_bytes(self, None)?))
+    }
+}
+
+impl TryIntoSvgData for String {
+    fn try_into_svg_data(self) -> Result<Arc<SvgData>, SvgLoadError> {
+        Ok(Arc::new(load_svg_from_path(Path::new(&self))?))
+    }
+}
+
+impl TryIntoSvgData for &str {
+    fn try_into_svg_data(self) -> Result<Arc<SvgData>, SvgLoadError> {
+        Ok(Arc::new(load_svg_from_path(Path::new(self))?))
+    }
+}
+
+impl TryIntoSvgData for PathBuf {
+    fn try_into_svg_data(self) -> Result<Arc<SvgData>, SvgLoadError> {
+        Ok(Arc::new(load_svg_from_path(self.as_path())?))
+    }
+}
+
+impl TryIntoSvgData for &Path {
+    fn try_into_svg_data(self) -> Result<Arc<SvgData>, SvgLoadError> {
+        Ok(Arc::new(load_svg_from_path(self)?))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct RasterKey {
+    id: u64,
+    width: u32,
+    height: u32,
+}
+
+fn raster_cache() -> &'static Mutex<LruCache<RasterKey, Arc<ImageData>>> {
+    static CACHE: OnceLock<Mutex<LruCache<RasterKey, Arc<ImageData>>>> = OnceLock::new();
+    CACHE.get_or_init(|| {
+        Mutex::new(LruCache::new(
+            NonZeroUsize::new(RASTER_CACHE_CAPACITY).expect("SVG cache capacity must be > 0"),
+        ))
+    })
+}
+
+fn raster_dimension(px: i32) -> Option<u32> {
+    (px > 0).then(|| (px as u32).min(MAX_RASTER_DIMENSION))
+}
+
+fn render(tree: &Tree, width: u32, height: u32) -> Option<ImageData> {
+    let mut pixmap = Pixmap::new(width, height)?;
+    let size = tree.size();
+    let transform =
+        Transform::from_scale(width as f32 / size.width(), height as f32 / size.height());
+    resvg::render(tree, transform, &mut pixmap.as_mut());
+
+    // The image pipeline blends straight alpha, while tiny-skia premultiplies.
+    let data = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect();
+    Some(ImageData {
+        data: Arc::new(data),
+        width,
+        height,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tessera_ui::Px;
+
+    use super::*;
+
+    const GRADIENT_SVG: &[u8] = br##"<svg xmlns="http://www.w3.org/2000/svg" width="8" height="4">
+        <defs><linearGradient id="g"><stop offset="0" stop-color="#f00"/><stop offset="1" stop-color="#00f"/></linearGradient></defs>
+        <rect width="8" height="4" fill="url(#g)"/>
+    </svg>"##;
+
+    #[test]
+    fn rasterizes_at_the_requested_physical_size() {
+        let svg = SvgData::from_bytes(GRADIENT_SVG, None).expect("valid svg");
+        assert_eq!((svg.viewport_width, svg.viewport_height), (8.0, 4.0));
+
+        let image = svg.rasterize(PxSize::new(Px(32), Px(16))).expect("raster");
+        assert_eq!((image.width, image.height), (32, 16));
+        assert_eq!(image.data.len(), 32 * 16 * 4);
+        assert_eq!(image.data[3], 255);
+        assert!(image.data[0] > image.data[2]);
+
+        let cached = svg.rasterize(PxSize::new(Px(32), Px(16))).expect("raster");
+        assert!(Arc::ptr_eq(&image, &cached));
+        let larger = svg.rasterize(PxSize::new(Px(64), Px(32))).expect("raster");
+        assert!(!Arc::ptr_eq(&image, &larger));
+    }
+
+    #[test]
+    fn empty_sizes_are_not_rasterized() {
+        let svg = SvgData::from_bytes(GRADIENT_SVG, None).expect("valid svg");
+        assert!(svg.rasterize(PxSize::new(Px(0), Px(16))).is_none());
+    }
+}
@@ -5,12 +5,12 @@
 //! Use to control a boolean on/off state.
 use std::time::Duration;
 
-use tessera_foundation::gesture::TapRecognizer;
 use tessera_ui::{
     AxisConstraint, CallbackWith, Color, ComputedData, Constraint, Dp, LayoutResult,
     MeasurementError, Modifier, Px, PxPosition, PxSize, RenderSlot, State,
     accesskit::Role,
     current_frame_nanos,
+    gesture::TapRecognizer,
     layout::{LayoutPolicy, MeasureScope, PlacementScope, layout},
     receive_frame_nanos, remember, tessera, use_context,
 };
@@ -6,11 +6,11 @@
 use std::sync::Arc;
 
 use parking_lot::Mutex;
-use tessera_foundation::gesture::{ScrollRecognizer, ScrollSettings};
 use tessera_ui::{
     AxisConstraint, Color, ComputedData, Constraint, Dp, FocusRequester, FocusState, LayoutResult,
     MeasurementError, Modifier, Px, PxPosition, RenderSlot, State,
     accesskit::Role,
+    gesture::{ScrollRecognizer, ScrollSettings},
     layout::{LayoutPolicy, MeasureScope, RenderInput, RenderPolicy, layout},
     modifier::FocusModifierExt as _,
     provide_context, receive_frame_nanos, remember, tessera, use_context,
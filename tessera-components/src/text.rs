@@ -1,52 +1,79 @@
-//! Single-style text rendering.
+//! Text rendering with styled spans, links and inline content.
 //!
 //! ## Usage
 //!
-//! Display labels, headings, and other text content.
+//! Display labels, headings, hyperlinks, mentions and icons inside text.
 use tessera_ui::{
-    Color, ComputedData, Dp, LayoutPolicy, LayoutResult, MeasurementError, Modifier, Px,
-    PxPosition, RenderInput, RenderPolicy,
+    CallbackWith, Color, ComputedData, Dp, LayoutPolicy, LayoutResult, MeasurementError, Modifier,
+    Px, PxPosition, RenderInput, RenderPolicy, RenderSlotWith, State,
     accesskit::Role,
+    gesture::TapRecognizer,
+    key,
     layout::{MeasureScope, layout},
+    remember,
+    renderer::drawer::{
+        sdf::{SdfShape, SdfShapeCommand},
+        text::{
+            AttributedString, FontFamily, FontFeature, FontSettings, FontVariation, FontWeight,
+            Hyphens, LineBreaking, TextCommand, TextConstraint, TextData, TextMeasureInfo,
+            TextOverflow,
+        },
+    },
     tessera, use_context,
 };
 
 use crate::{
-    modifier::{ModifierExt as _, SemanticsArgs},
-    pipelines::text::{
-        command::{TextCommand, TextConstraint},
-        pipeline::TextData,
-    },
+    modifier::{ModifierExt as _, SemanticsArgs, with_pointer_input},
+    pos_misc::is_position_inside_bounds,
     theme::{ContentColor, MaterialTheme, TextStyle},
 };
 
-pub use crate::pipelines::text::pipeline::{read_font_system, write_font_system};
+pub use tessera_ui::renderer::drawer::text::{
+    OBJECT_REPLACEMENT, PlaceholderAlignment, SpanStyle, read_font_system, write_font_system,
+};
 
 /// # text
 ///
-/// Renders a block of text with a single, uniform style.
+/// Renders a block of text, optionally with styled spans, links and inline
+/// components.
 ///
 /// ## Usage
 ///
-/// Display simple text content. For more complex styling or editing, see other
-/// components.
+/// Display text content, hyperlinks, mentions or icons inside a paragraph.
+/// For editing, see the text field components.
 ///
 /// ## Parameters
 ///
 /// - `modifier` — modifier chain applied to the text node.
-/// - `content` — text content to display.
+/// - `content` — plain or [`AttributedString`] text to display.
 /// - `color` — optional text color override.
 /// - `style` — optional text style override for typography presets.
 /// - `size` — optional font size override.
 /// - `line_height` — optional line height override.
+/// - `font_family` — optional font family; defaults to sans-serif.
+/// - `font_weight` — optional font weight override.
+/// - `font_variations` — optional variable font axis coordinates; see
+///   [`FontVariation`] for the supported axes.
+/// - `font_features` — optional OpenType feature settings.
+/// - `max_lines` — optional maximum number of visible lines.
+/// - `overflow` — how text past the line limit or height is shown; defaults to
+///   clipping.
+/// - `hyphens` — optional hyphenation at soft hyphens.
+/// - `on_text_layout` — optional callback receiving each measurement, e.g. to
+///   show a tooltip when the text is truncated.
+/// - `inline_content` — optional slot rendering the child for each placeholder
+///   key of `content`; children are measured and drawn in the placeholder
+///   boxes.
+/// - `on_link_click` — optional callback receiving the link target of a clicked
+///   span.
 /// - `accessibility_label` — optional accessibility label override.
 /// - `accessibility_description` — optional accessibility description override.
 ///
 /// ## Examples
 ///
 /// ```
-/// use tessera_components::text::text;
-/// use tessera_ui::{Color, Dp, tessera};
+/// use tessera_components::text::{SpanStyle, text};
+/// use tessera_ui::{AttributedString, Color, Dp, tessera};
 ///
 /// #[tessera]
 /// fn demo() {
@@ -56,19 +83,39 @@ pub use crate::pipelines::text::pipeline::{read_font_system, write_font_system};
 ///         .style(tessera_components::theme::TextStyle {
 ///             font_size: Dp(32.0),
 ///             line_height: Some(Dp(40.0)),
+///             ..Default::default()
 ///         });
 /// }
 ///
 /// demo();
+///
+/// let mention = AttributedString::new().push("Ping ").push_styled(
+///     "@tessera",
+///     SpanStyle::new().underline().link("user:tessera"),
+/// );
+/// assert_eq!(
+///     mention.spans()[0].style.link.as_deref(),
+///     Some("user:tessera")
+/// );
 /// ```
 #[tessera]
 pub fn text(
     modifier: Option<Modifier>,
-    #[prop(into)] content: Option<String>,
+    #[prop(into)] content: Option<AttributedString>,
     color: Option<Color>,
     style: Option<TextStyle>,
     size: Option<Dp>,
     line_height: Option<Dp>,
+    #[prop(into)] font_family: Option<FontFamily>,
+    font_weight: Option<FontWeight>,
+    font_variations: Option<Vec<FontVariation>>,
+    font_features: Option<Vec<FontFeature>>,
+    max_lines: Option<u32>,
+    overflow: Option<TextOverflow>,
+    hyphens: Option<Hyphens>,
+    on_text_layout: Option<CallbackWith<TextMeasureInfo>>,
+    #[prop(render_slot_with)] inline_content: Option<RenderSlotWith<String>>,
+    on_link_click: Option<CallbackWith<String>>,
     #[prop(into)] accessibility_label: Option<String>,
     #[prop(into)] accessibility_description: Option<String>,
 ) {
@@ -85,9 +132,9 @@ pub fn text(
         .or_else(|| theme.map(|t| t.get().color_scheme.on_surface))
         .unwrap_or_else(|| ContentColor::default().current);
     let size = size.unwrap_or(resolved_style.font_size);
-    let accessibility_label = accessibility_label
-        .clone()
-        .or_else(|| (!content.is_empty()).then(|| content.clone()));
+    let accessibility_label = accessibility_label.clone().or_else(|| {
+        (!content.text().is_empty()).then(|| content.text().replace(OBJECT_REPLACEMENT, ""))
+    });
     let semantics = SemanticsArgs {
         role: Some(Role::Label),
         label: accessibility_label,
@@ -98,24 +145,89 @@ pub fn text(
         .or(resolved_style.line_height)
         .unwrap_or(Dp(size.0 * 1.2));
 
+    let placeholder_keys: Vec<String> = content
+        .placeholders()
+        .iter()
+        .map(|placeholder| placeholder.key.to_string())
+        .collect();
+    let sized_text = remember(|| None::<AttributedString>);
     let policy = TextLayout {
         text: content.clone(),
         color,
         size,
         line_height,
+        font: FontSettings {
+            family: font_family.unwrap_or_default(),
+            weight: font_weight.unwrap_or(resolved_style.font_weight),
+            variations: font_variations.unwrap_or_default(),
+            features: font_features.unwrap_or_default(),
+        },
+        line_breaking: LineBreaking {
+            max_lines,
+            overflow: overflow.unwrap_or_default(),
+            hyphens: hyphens.unwrap_or_default(),
+        },
+        on_text_layout,
+        sized_text,
     };
+    let modifier = match on_link_click {
+        Some(on_link_click) => {
+            let tap_recognizer = remember(TapRecognizer::default);
+            let policy = policy.clone();
+            with_pointer_input(modifier, move |mut input| {
+                let cursor = input.cursor_position_rel;
+                let inside = cursor.is_some_and(|position| {
+                    is_position_inside_bounds(input.computed_data, position)
+                });
+                let tap = tap_recognizer.with_mut(|recognizer| {
+                    recognizer.update(
+                        input.pass,
+                        input.pointer_changes.as_mut_slice(),
+                        cursor,
+                        inside,
+                    )
+                });
+                let Some(position) = cursor.filter(|_| tap.tapped) else {
+                    return;
+                };
+                let data = policy.text_data(input.computed_data);
+                if let Some(link) = data.link_at(position) {
+                    input.consume_pointer_changes();
+                    on_link_click.call(link.to_string());
+                }
+            })
+        }
+        None => modifier,
+    };
+
     layout()
         .modifier(modifier.semantics(semantics))
         .layout_policy(policy.clone())
-        .render_policy(policy);
+        .render_policy(policy)
+        .child(move || {
+            let Some(inline_content) = inline_content else {
+                return;
+            };
+            for placeholder_key in &placeholder_keys {
+                key(placeholder_key, || {
+                    inline_content.render(placeholder_key.clone())
+                });
+            }
+        });
 }
 
 #[derive(Clone)]
 struct TextLayout {
-    text: String,
+    text: AttributedString,
     color: Color,
     size: Dp,
     line_height: Dp,
+    font: FontSettings,
+    line_breaking: LineBreaking,
+    on_text_layout: Option<CallbackWith<TextMeasureInfo>>,
+    /// The text with the measured placeholder sizes, written by the measure
+    /// pass so drawing and hit-testing reuse the cached layout.
+    sized_text: State<Option<AttributedString>>,
 }
 
 impl PartialEq for TextLayout {
@@ -124,6 +236,34 @@ impl PartialEq for TextLayout {
             && self.color == other.color
             && self.size == other.size
             && self.line_height == other.line_height
+            && self.font == other.font
+            && self.line_breaking == other.line_breaking
+            && self.on_text_layout == other.on_text_layout
+    }
+}
+
+impl TextLayout {
+    /// Returns the text as laid out by the last measure pass.
+    fn laid_out_text(&self) -> AttributedString {
+        if self.text.placeholders().is_empty() {
+            return self.text.clone();
+        }
+        self.sized_text
+            .with(Clone::clone)
+            .unwrap_or_else(|| self.text.clone())
+    }
+
+    /// Returns the cached layout for the measured `size`.
+    fn text_data(&self, size: ComputedData) -> TextData {
+        TextData::get(
+            self.laid_out_text(),
+            self.color,
+            self.size.to_pixels_f32(),
+            self.line_height.to_pixels_f32(),
+            &self.font,
+            self.line_breaking,
+            [size.width.raw() as u32, size.height.raw() as u32],
+        )
     }
 }
 
@@ -132,21 +272,48 @@ impl LayoutPolicy for TextLayout {
         let max_width = input.parent_constraint().width().resolve_max();
         let max_height = input.parent_constraint().height().resolve_max();
 
+        let children = input.children();
+        let child_constraint = input.parent_constraint().without_min();
+        let mut text = self.text.clone();
+        for (index, child) in children.iter().enumerate() {
+            let size = child.measure(&child_constraint)?;
+            text.set_placeholder_size(index, size.width.to_f32(), size.height.to_f32());
+        }
+
         let info = TextData::measure(
-            self.text.clone(),
+            text.clone(),
             self.color,
             self.size.to_pixels_f32(),
             self.line_height.to_pixels_f32(),
+            &self.font,
+            self.line_breaking,
             TextConstraint {
                 max_width: max_width.map(|px: Px| px.to_f32()),
                 max_height: max_height.map(|px: Px| px.to_f32()),
             },
         );
+        if let Some(on_text_layout) = &self.on_text_layout {
+            on_text_layout.call(info);
+        }
 
-        Ok(LayoutResult::new(ComputedData {
+        let size = ComputedData {
             width: info.size[0].into(),
             height: info.size[1].into(),
-        }))
+        };
+        let mut result = LayoutResult::new(size);
+        if !self.text.placeholders().is_empty() {
+            if self.sized_text.with(|sized| sized.as_ref() != Some(&text)) {
+                self.sized_text.set(Some(text));
+            }
+            let data = self.text_data(size);
+            for (child, rect) in children.iter().zip(data.placeholder_rects()) {
+                // Children of placeholders dropped by truncation stay unplaced.
+                if let Some(rect) = rect {
+                    result.place_child(*child, PxPosition::new(rect.x, rect.y));
+                }
+            }
+        }
+        Ok(result)
     }
 }
 
@@ -159,22 +326,22 @@ impl RenderPolicy for TextLayout {
                 .expect("ComputedData must exist during record")
         };
 
-        // Use TextData::get() with the computed bounds to retrieve cached data
-        let text_data = TextData::get(
-            self.text.clone(),
-            self.color,
-            self.size.to_pixels_f32(),
-            self.line_height.to_pixels_f32(),
-            [computed.width.raw() as u32, computed.height.raw() as u32],
-        );
+        // Use the computed bounds to retrieve the data cached by measure
+        let text_data = self.text_data(computed);
+        let decorations = text_data.decorations();
 
         let drawable = TextCommand {
             data: text_data,
             offset: PxPosition::ZERO,
         };
-        input
-            .metadata_mut()
-            .fragment_mut()
-            .push_draw_command(drawable);
+        let mut metadata = input.metadata_mut();
+        let fragment = metadata.fragment_mut();
+        fragment.push_draw_command(drawable);
+        for decoration in decorations {
+            fragment.push_draw_command_in(
+                SdfShapeCommand::new(SdfShape::RECT, decoration.color),
+                decoration.rect,
+            );
+        }
     }
 }
@@ -29,22 +29,25 @@ use glyphon::{
 };
 use tessera_platform::clipboard;
 use tessera_ui::{
-    CallbackWith, Color, ComputedData, Dp, FocusRequester, LayoutResult, MeasurementError, Px,
-    PxPosition, State, current_frame_nanos,
+    CallbackWith, Color, ComputedData, Dp, FocusRequester, ImePreedit, LayoutResult,
+    MeasurementError, Px, PxPosition, State, current_frame_nanos,
     layout::{LayoutPolicy, MeasureScope, RenderInput, RenderPolicy, layout},
-    receive_frame_nanos, tessera,
+    receive_frame_nanos,
+    renderer::drawer::{
+        sdf::{SdfShape, SdfShapeCommand},
+        text::{
+            AttributedString, FontSettings, TextCommand, TextConstraint, TextData, TextSpan,
+            hit_test, write_font_system,
+        },
+    },
+    tessera,
     time::Instant,
     winit,
 };
 use winit::keyboard::NamedKey;
 
 use crate::{
-    pipelines::text::{
-        command::{TextCommand, TextConstraint},
-        pipeline::{TextData, write_font_system},
-    },
-    selection_highlight_rect::selection_highlight_rect,
-    text_edit_core::cursor::CURSOR_WIDRH,
+    selection_highlight_rect::selection_highlight_rect, text_edit_core::cursor::CURSOR_WIDRH,
 };
 
 /// Display-only text transform output with offset mapping between raw and
@@ -156,8 +159,10 @@ struct DerivedGeometryInput {
     cursor_offset: usize,
     selection: Option<TextSelection>,
     composition_range: Option<Range<usize>>,
+    clause_range: Option<Range<usize>>,
     raw_editor: RawEditorSnapshot,
     raw_composition: Option<(Cursor, Cursor)>,
+    raw_clause: Option<(Cursor, Cursor)>,
 }
 
 struct PointerActionContext<'a> {
@@ -218,77 +223,6 @@ fn boundary_index_for_offset(boundaries: &[usize], offset: usize) -> usize {
         .saturating_sub(1)
 }
 
-fn is_word_character(character: char) -> bool {
-    character.is_alphanumeric() || character == '_'
-}
-
-fn next_word_boundary(text: &str, offset: usize) -> usize {
-    let boundaries = char_boundary_offsets(text);
-    let characters: Vec<char> = text.chars().collect();
-    let mut index = boundary_index_for_offset(&boundaries, offset.min(text.len()));
-
-    if index >= characters.len() {
-        return text.len();
-    }
-
-    if !is_word_character(characters[index]) {
-        while index < characters.len() && !is_word_character(characters[index]) {
-            index += 1;
-        }
-    }
-    while index < characters.len() && is_word_character(characters[index]) {
-        index += 1;
-    }
-
-    boundaries[index.min(boundaries.len().saturating_sub(1))]
-}
-
-fn previous_word_boundary(text: &str, offset: usize) -> usize {
-    let boundaries = char_boundary_offsets(text);
-    let characters: Vec<char> = text.chars().collect();
-    let mut index = boundary_index_for_offset(&boundaries, offset.min(text.len()));
-
-    while index > 0 && !is_word_character(characters[index - 1]) {
-        index -= 1;
-    }
-    while index > 0 && is_word_character(characters[index - 1]) {
-        index -= 1;
-    }
-
-    boundaries[index]
-}
-
-fn word_range_at_offset(text: &str, offset: usize) -> Option<Range<usize>> {
-    let boundaries = char_boundary_offsets(text);
-    let characters: Vec<char> = text.chars().collect();
-    if characters.is_empty() {
-        return None;
-    }
-
-    let mut index = boundary_index_for_offset(&boundaries, offset.min(text.len()));
-    if index >= characters.len() {
-        index = characters.len().saturating_sub(1);
-    }
-
-    if !is_word_character(characters[index]) {
-        let next_index = (index..characters.len()).find(|&i| is_word_character(characters[i]));
-        let previous_index = (0..index).rev().find(|&i| is_word_character(characters[i]));
-        index = next_index.or(previous_index)?;
-    }
-
-    let mut start = index;
-    while start > 0 && is_word_character(characters[start - 1]) {
-        start -= 1;
-    }
-
-    let mut end = index + 1;
-    while end < characters.len() && is_word_character(characters[end]) {
-        end += 1;
-    }
-
-    Some(boundaries[start]..boundaries[end])
-}
-
 fn line_range_at_offset(text: &str, offset: usize) -> Range<usize> {
     let clamped_offset = offset.min(text.len());
     let start = text[..clamped_offset]
@@ -480,6 +414,8 @@ struct TextEditState {
     text_color: Color,
     cursor_color: Color,
     single_line: bool,
+    /// Styled ranges of the text, set once the editor shows rich text.
+    spans: Option<Vec<TextSpan>>,
 }
 
 impl TextEditState {
@@ -498,6 +434,12 @@ impl TextEditState {
         editor_content(&self.editor)
     }
 
+    fn attributed_text(&self) -> Option<AttributedString> {
+        self.spans
+            .as_ref()
+            .map(|spans| attributed_text(&self.text(), spans))
+    }
+
     fn text_color(&self) -> Color {
         self.text_color
     }
@@ -565,7 +507,7 @@ impl TextEditState {
                 TextSelection {
                     start: self
                         .editor
-                        .with_buffer(|buffer| cursor_to_text_offset_in_buffer(buffer, anchor)),
+                        .with_buffer(|buffer| hit_test::cursor_to_offset(buffer, anchor)),
                     end: self.cursor_offset(),
                 }
             }
@@ -686,12 +628,12 @@ impl TextEditState {
 
     fn cursor_to_text_offset(&self, cursor: Cursor) -> usize {
         self.editor
-            .with_buffer(|buffer| cursor_to_text_offset_in_buffer(buffer, cursor))
+            .with_buffer(|buffer| hit_test::cursor_to_offset(buffer, cursor))
     }
 
     fn text_offset_to_cursor(&self, offset: usize) -> Cursor {
         self.editor
-            .with_buffer(|buffer| text_offset_to_cursor_in_buffer(buffer, offset))
+            .with_buffer(|buffer| hit_test::offset_to_cursor(buffer, offset))
     }
 
     fn set_wrap(&mut self, wrap: glyphon::Wrap) {
@@ -770,6 +712,16 @@ impl TextEditState {
 
     fn set_text_and_selection(&mut self, text: &str, selection: TextSelection) {
         self.editor.with_buffer_mut(|buffer| {
+            if let Some(spans) = &self.spans {
+                TextData::set_attributed_buffer_text(
+                    buffer,
+                    &attributed_text(text, spans),
+                    self.text_color,
+                    &FontSettings::default(),
+                );
+                buffer.set_redraw(true);
+                return;
+            }
             let color = glyphon::Color::rgba(
                 (self.text_color.r * 255.0) as u8,
                 (self.text_color.g * 255.0) as u8,
@@ -789,10 +741,10 @@ impl TextEditState {
         });
 
         let start = self.editor.with_buffer(|buffer| {
-            text_offset_to_cursor_in_buffer(buffer, selection.start.min(text.len()))
+            hit_test::offset_to_cursor(buffer, selection.start.min(text.len()))
         });
         let end = self.editor.with_buffer(|buffer| {
-            text_offset_to_cursor_in_buffer(buffer, selection.end.min(text.len()))
+            hit_test::offset_to_cursor(buffer, selection.end.min(text.len()))
         });
         let editor_selection = if selection.is_collapsed() {
             None
@@ -1031,6 +983,8 @@ impl TextScrollControllerState {
 
 struct TextImeState {
     composition: Option<ImeComposition>,
+    /// Clause being converted, relative to the composition start.
+    active_clause: Option<Range<usize>>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -1059,6 +1013,7 @@ impl TextImeState {
 
     fn clear(&mut self) {
         self.composition = None;
+        self.active_clause = None;
     }
 
     fn composition_range(&self) -> Option<Range<usize>> {
@@ -1076,6 +1031,23 @@ impl TextImeState {
         })
     }
 
+    fn clause_range(&self) -> Option<Range<usize>> {
+        let composition = self.composition_range()?;
+        let clause = self.active_clause.as_ref()?;
+        let start = (composition.start + clause.start).min(composition.end);
+        let end = (composition.start + clause.end).min(composition.end);
+        (start < end).then_some(start..end)
+    }
+
+    fn clause_cursor_range(&self, edit_state: &TextEditState) -> Option<(Cursor, Cursor)> {
+        self.clause_range().map(|range| {
+            (
+                edit_state.text_offset_to_cursor(range.start),
+                edit_state.text_offset_to_cursor(range.end),
+            )
+        })
+    }
+
     fn plan_event(
         &self,
         selection: TextSelection,
@@ -1098,6 +1070,21 @@ impl TextImeState {
             .unwrap_or_else(|| selection.ordered_range());
 
         match event {
+            // An empty preedit only ends the composition; without one there is
+            // nothing to remove and the selection must stay intact.
+            winit::event::Ime::Preedit(text, _) | winit::event::Ime::Commit(text)
+                if text.is_empty() && self.composition.is_none() =>
+            {
+                None
+            }
+            winit::event::Ime::Disabled => self.composition.as_ref().map(|composition| {
+                PlannedImeEvent::Edit(PlannedImeEdit {
+                    replacement_range: composition.range.clone(),
+                    replacement_text: String::new(),
+                    selection: TextSelection::collapsed(composition.range.start),
+                    composition_range: None,
+                })
+            }),
             winit::event::Ime::Commit(text) => Some(PlannedImeEvent::Edit(PlannedImeEdit {
                 replacement_range: replacement_range.clone(),
                 replacement_text: text.clone(),
@@ -1140,6 +1127,17 @@ impl TextImeState {
             range: result.replaced_range.clone(),
             selection: result.selection.clone(),
         });
+        if self.composition.is_none() {
+            self.active_clause = None;
+        }
+    }
+
+    fn update_preedit(&mut self, event: &winit::event::Ime) {
+        self.active_clause = ImePreedit::from_event(event).and_then(|preedit| {
+            preedit
+                .active_clause()
+                .filter(|_| self.composition.is_some())
+        });
     }
 }
 
@@ -1367,17 +1365,13 @@ impl TextLayoutState {
         let buffer = self.buffer()?;
         let transformed_text = self.cloned_transformed_text()?;
         let cursor =
-            text_offset_to_cursor_in_buffer(&buffer, transformed_text.map_from_raw(cursor_offset));
+            hit_test::offset_to_cursor(&buffer, transformed_text.map_from_raw(cursor_offset));
         let selection = selection.map(|selection| {
             let ordered = selection.ordered_range();
-            let start = text_offset_to_cursor_in_buffer(
-                &buffer,
-                transformed_text.map_from_raw(ordered.start),
-            );
-            let end = text_offset_to_cursor_in_buffer(
-                &buffer,
-                transformed_text.map_from_raw(ordered.end),
-            );
+            let start =
+                hit_test::offset_to_cursor(&buffer, transformed_text.map_from_raw(ordered.start));
+            let end =
+                hit_test::offset_to_cursor(&buffer, transformed_text.map_from_raw(ordered.end));
             if selection.start <= selection.end {
                 (start, end)
             } else {
@@ -1405,15 +1399,17 @@ impl TextLayoutState {
             cursor_offset,
             selection,
             composition_range,
+            clause_range,
             raw_editor,
             raw_composition,
+            raw_clause,
         } = input;
 
         if let Some((display_editor, transformed_text)) =
             self.display_editor(cursor_offset, selection)
         {
             let composition_rects = display_editor.with_buffer(|buffer| {
-                composition_range
+                let mut rects = composition_range
                     .clone()
                     .map(|composition| {
                         compute_transformed_composition_rects(
@@ -1422,7 +1418,15 @@ impl TextLayoutState {
                             composition,
                         )
                     })
-                    .unwrap_or_default()
+                    .unwrap_or_default();
+                if let Some(clause) = clause_range.clone() {
+                    rects.extend(compute_transformed_clause_rects(
+                        buffer,
+                        &transformed_text,
+                        clause,
+                    ));
+                }
+                rects
             });
             return Some(DerivedLayoutGeometry {
                 selection_rects: compute_selection_rects(&display_editor),
@@ -1434,8 +1438,16 @@ impl TextLayoutState {
 
         let raw_editor =
             self.raw_layout_editor(raw_editor.buffer, raw_editor.cursor, raw_editor.selection);
-        let composition_rects = raw_editor
-            .with_buffer(|buffer| compute_composition_rects_for_range(buffer, raw_composition));
+        let composition_rects = raw_editor.with_buffer(|buffer| {
+            let mut rects = compute_composition_rects_for_range(buffer, raw_composition);
+            if let Some((start, end)) = raw_clause {
+                rects.extend(underline_rects(
+                    compute_range_rects(buffer, start, end),
+                    CLAUSE_UNDERLINE_HEIGHT,
+                ));
+            }
+            rects
+        });
         Some(DerivedLayoutGeometry {
             selection_rects: compute_selection_rects(&raw_editor),
             composition_rects,
@@ -1510,10 +1522,10 @@ impl TextLayoutState {
             let display_offset = transformed_text.map_from_raw(cursor_offset);
             let next_display_offset = match motion {
                 cosmic_text::Motion::LeftWord => {
-                    previous_word_boundary(transformed_text.text(), display_offset)
+                    hit_test::previous_word_boundary(transformed_text.text(), display_offset)
                 }
                 cosmic_text::Motion::RightWord => {
-                    next_word_boundary(transformed_text.text(), display_offset)
+                    hit_test::next_word_boundary(transformed_text.text(), display_offset)
                 }
                 _ => {
                     let (transformed_text, display_offset, next_display_offset) =
@@ -1558,7 +1570,9 @@ impl TextLayoutState {
             });
         }
         let display_range = match mode {
-            DragSelectionMode::Word => word_range_at_offset(transformed_text.text(), hit_offset)?,
+            DragSelectionMode::Word => {
+                hit_test::word_range_at(transformed_text.text(), hit_offset)?
+            }
             DragSelectionMode::Line => line_range_at_offset(transformed_text.text(), hit_offset),
             DragSelectionMode::Character => unreachable!(),
         };
@@ -1581,7 +1595,7 @@ impl TextLayoutState {
             self.display_hit_offset(cursor_offset, selection, x, y)?;
         let display_range = match action {
             glyphon::Action::DoubleClick { .. } => {
-                word_range_at_offset(transformed_text.text(), hit_offset)?
+                hit_test::word_range_at(transformed_text.text(), hit_offset)?
             }
             glyphon::Action::TripleClick { .. } => {
                 line_range_at_offset(transformed_text.text(), hit_offset)
@@ -1617,7 +1631,7 @@ impl TextLayoutState {
             });
         }
         let target = match mode {
-            DragSelectionMode::Word => word_range_at_offset(text, hit_offset)?,
+            DragSelectionMode::Word => hit_test::word_range_at(text, hit_offset)?,
             DragSelectionMode::Line => line_range_at_offset(text, hit_offset),
             DragSelectionMode::Character => unreachable!(),
         };
@@ -1642,7 +1656,7 @@ impl TextLayoutState {
             y,
         )?;
         let target = match action {
-            glyphon::Action::DoubleClick { .. } => word_range_at_offset(text, hit_offset)?,
+            glyphon::Action::DoubleClick { .. } => hit_test::word_range_at(text, hit_offset)?,
             glyphon::Action::TripleClick { .. } => line_range_at_offset(text, hit_offset),
             _ => return None,
         };
@@ -1674,7 +1688,7 @@ impl TextLayoutState {
             Selection::Normal(anchor) | Selection::Line(anchor) | Selection::Word(anchor) => anchor,
         };
         let anchor_offset =
-            display_editor.with_buffer(|buffer| cursor_to_text_offset_in_buffer(buffer, anchor));
+            display_editor.with_buffer(|buffer| hit_test::cursor_to_offset(buffer, anchor));
         let cursor_offset = editor_cursor_offset(display_editor);
         Some(TextSelection {
             start: transformed_text.map_to_raw(anchor_offset),
@@ -1927,7 +1941,7 @@ impl TextEditorController {
             &mut write_font_system(),
             glyphon::Metrics::new(size.to_pixels_f32(), line_height_px.to_f32()),
         );
-        buffer.set_wrap(&mut write_font_system(), glyphon::Wrap::Glyph);
+        buffer.set_wrap(&mut write_font_system(), glyphon::Wrap::WordOrGlyph);
         let scroll_state = TextScrollControllerState::new(buffer.scroll());
         let editor = glyphon::Editor::new(buffer);
         let text_color = Color::BLACK;
@@ -1943,6 +1957,7 @@ impl TextEditorController {
                 text_color,
                 cursor_color,
                 single_line: false,
+                spans: None,
             },
             selection_state: TextSelectionState {
                 selection_color,
@@ -1954,7 +1969,10 @@ impl TextEditorController {
                 drag_origin_selection: None,
             },
             scroll_state,
-            ime_state: TextImeState { composition: None },
+            ime_state: TextImeState {
+                composition: None,
+                active_clause: None,
+            },
             layout_state: TextLayoutState::new(),
         }
     }
@@ -1996,7 +2014,14 @@ impl TextEditorController {
                 (self.edit_state.buffer_clone(), None)
             };
 
-        let text_data = TextData::from_buffer(text_buffer.clone());
+        let text_data = match self.edit_state.attributed_text() {
+            Some(text) if transformed_text.is_none() => TextData::from_attributed_buffer(
+                text_buffer.clone(),
+                text,
+                self.edit_state.text_color(),
+            ),
+            _ => TextData::from_buffer(text_buffer.clone()),
+        };
         self.layout_state.set_text_snapshot_for_constraint(
             &constraint,
             text_buffer,
@@ -2083,6 +2108,28 @@ impl TextEditorController {
         self.set_text_and_selection(&current_text, selection);
     }
 
+    /// Styles byte ranges of the text, e.g. for rich text editing.
+    ///
+    /// Edits do not move the spans, so callers replace them as the text
+    /// changes. Ranges past the end of the text are ignored, and a display
+    /// transform shows its output unstyled.
+    pub fn set_text_spans(&mut self, spans: Vec<TextSpan>) {
+        if self.edit_state.spans.as_ref() == Some(&spans) {
+            return;
+        }
+        self.edit_state.spans = Some(spans);
+        let text = self.text();
+        let selection = self.selection();
+        // Unlike set_text_and_selection, keeps an IME composition going.
+        self.edit_state.set_text_and_selection(&text, selection);
+        self.invalidate_text_layout();
+    }
+
+    /// Returns the spans set by [`TextEditorController::set_text_spans`].
+    pub fn text_spans(&self) -> &[TextSpan] {
+        self.edit_state.spans.as_deref().unwrap_or_default()
+    }
+
     /// Returns the cursor color.
     pub fn cursor_color(&self) -> Color {
         self.edit_state.cursor_color()
@@ -2225,12 +2272,14 @@ impl TextEditorController {
             cursor_offset: self.cursor_offset(),
             selection: self.has_selection().then(|| self.selection()),
             composition_range: self.ime_state.composition_range(),
+            clause_range: self.ime_state.clause_range(),
             raw_editor: RawEditorSnapshot {
                 buffer: self.current_editor_buffer_clone(),
                 cursor: self.edit_state.cursor(),
                 selection: self.selection_cursor_range(),
             },
             raw_composition: self.ime_state.composition_cursor_range(&self.edit_state),
+            raw_clause: self.ime_state.clause_cursor_range(&self.edit_state),
         })
     }
 
@@ -2417,7 +2466,7 @@ impl TextEditorController {
     /// An optional vector of `glyphon::Action` to be applied to the editor.
     pub fn map_key_event_to_action(
         &mut self,
-        key_event: winit::event::KeyEvent,
+        key_event: tessera_ui::KeyEvent,
         key_modifiers: winit::keyboard::ModifiersState,
     ) -> Option<Vec<glyphon::Action>> {
         let is_ctrl = key_modifiers.control_key() || key_modifiers.super_key();
@@ -2657,6 +2706,12 @@ impl TextEditorController {
         self.ime_state.commit_edit_result(plan, result);
     }
 
+    /// Tracks the clause being converted so it is drawn with a thick
+    /// underline.
+    pub(crate) fn update_preedit(&mut self, event: &winit::event::Ime) {
+        self.ime_state.update_preedit(event);
+    }
+
     pub(crate) fn selected_text(&self) -> Option<String> {
         self.edit_state.selected_text()
     }
@@ -2834,6 +2889,24 @@ impl TextEditorController {
         true
     }
 
+    /// Moves the selection without changing the text, clamping both ends to
+    /// character boundaries.
+    pub(crate) fn set_selection(&mut self, selection: TextSelection) {
+        let text = self.text();
+        let clamp = |offset: usize| {
+            let mut offset = offset.min(text.len());
+            while !text.is_char_boundary(offset) {
+                offset -= 1;
+            }
+            offset
+        };
+        let selection = TextSelection {
+            start: clamp(selection.start),
+            end: clamp(selection.end),
+        };
+        self.set_text_and_selection(&text, selection);
+    }
+
     pub(crate) fn set_text_and_selection(&mut self, text: &str, selection: TextSelection) {
         self.edit_state.set_text_and_selection(text, selection);
         self.reset_cursor_blink();
@@ -2855,7 +2928,7 @@ impl TextEditorController {
         if self.edit_state.single_line() {
             glyphon::Wrap::None
         } else {
-            glyphon::Wrap::Glyph
+            glyphon::Wrap::WordOrGlyph
         }
     }
 
@@ -2919,43 +2992,11 @@ fn compute_selection_rects(editor: &glyphon::Editor) -> Vec<RectDef> {
 }
 
 fn editor_cursor_offset(editor: &glyphon::Editor<'_>) -> usize {
-    editor.with_buffer(|buffer| cursor_to_text_offset_in_buffer(buffer, editor.cursor()))
-}
-
-fn fallback_buffer_hit(buffer: &glyphon::Buffer, x: f32, y: f32) -> Option<Cursor> {
-    let mut closest_line_y = None::<(f32, f32)>;
-
-    for run in buffer.layout_runs() {
-        let line_top = run.line_top;
-        let line_bottom = line_top + run.line_height;
-        let distance = if y < line_top {
-            line_top - y
-        } else if y >= line_bottom {
-            y - line_bottom
-        } else {
-            0.0
-        };
-        let line_mid_y = line_top + (run.line_height * 0.5);
-
-        match closest_line_y {
-            Some((best_distance, _)) if best_distance <= distance => {}
-            _ => {
-                closest_line_y = Some((distance, line_mid_y));
-            }
-        }
-    }
-
-    let (_, line_mid_y) = closest_line_y?;
-    buffer.hit(x, line_mid_y)
+    editor.with_buffer(|buffer| hit_test::cursor_to_offset(buffer, editor.cursor()))
 }
 
 fn editor_hit_offset(editor: &glyphon::Editor<'_>, x: i32, y: i32) -> Option<usize> {
-    let cursor = editor.with_buffer(|buffer| {
-        buffer
-            .hit(x as f32, y as f32)
-            .or_else(|| fallback_buffer_hit(buffer, x as f32, y as f32))
-    })?;
-    Some(editor.with_buffer(|buffer| cursor_to_text_offset_in_buffer(buffer, cursor)))
+    editor.with_buffer(|buffer| hit_test::hit_test(buffer, x as f32, y as f32))
 }
 
 fn editor_motion_offset(
@@ -2968,43 +3009,7 @@ fn editor_motion_offset(
             .cursor_motion(&mut write_font_system(), cursor, None, motion)
             .map(|(next_cursor, _)| next_cursor)
     })?;
-    Some(editor.with_buffer(|buffer| cursor_to_text_offset_in_buffer(buffer, next_cursor)))
-}
-
-fn cursor_to_text_offset_in_buffer(buffer: &glyphon::Buffer, cursor: Cursor) -> usize {
-    let mut offset = 0usize;
-    for (line_index, line) in buffer.lines.iter().enumerate() {
-        let line_len = line.text().len();
-        if line_index == cursor.line {
-            return offset + cursor.index.min(line_len);
-        }
-        offset += line_len + line.ending().as_str().len();
-    }
-    offset
-}
-
-fn text_offset_to_cursor_in_buffer(buffer: &glyphon::Buffer, offset: usize) -> Cursor {
-    if buffer.lines.is_empty() {
-        return Cursor::new(0, 0);
-    }
-
-    let mut remaining = offset;
-    for (line_index, line) in buffer.lines.iter().enumerate() {
-        let line_len = line.text().len();
-        if remaining <= line_len {
-            return Cursor::new(line_index, remaining);
-        }
-
-        let line_total_len = line_len + line.ending().as_str().len();
-        if remaining < line_total_len {
-            return Cursor::new(line_index, line_len);
-        }
-        remaining = remaining.saturating_sub(line_total_len);
-    }
-
-    let last_line_index = buffer.lines.len().saturating_sub(1);
-    let last_line_len = buffer.lines[last_line_index].text().len();
-    Cursor::new(last_line_index, last_line_len)
+    Some(editor.with_buffer(|buffer| hit_test::cursor_to_offset(buffer, next_cursor)))
 }
 
 fn compute_range_rects(
@@ -3012,39 +3017,31 @@ fn compute_range_rects(
     range_start: Cursor,
     range_end: Cursor,
 ) -> Vec<RectDef> {
-    let mut rects = Vec::new();
-    let (range_start, range_end) = if cursor_to_text_offset_in_buffer(buffer, range_start)
-        <= cursor_to_text_offset_in_buffer(buffer, range_end)
-    {
-        (range_start, range_end)
-    } else {
-        (range_end, range_start)
-    };
-
-    for run in buffer.layout_runs() {
-        let line_top = Px(run.line_top as i32);
-        let line_height = Px(run.line_height as i32);
-
-        if let Some((x, w)) = run.highlight(range_start, range_end) {
-            rects.push(RectDef {
-                x: Px(x as i32),
-                y: line_top,
-                width: Px(w as i32),
-                height: line_height,
-            });
-        }
-    }
-
-    rects
+    let start = hit_test::cursor_to_offset(buffer, range_start);
+    let end = hit_test::cursor_to_offset(buffer, range_end);
+    hit_test::selection_rects(buffer, start..end)
+        .into_iter()
+        .map(|rect| RectDef {
+            x: rect.x,
+            y: rect.y,
+            width: rect.width,
+            height: rect.height,
+        })
+        .collect()
 }
 
+const COMPOSITION_UNDERLINE_HEIGHT: Px = Px(2);
+const CLAUSE_UNDERLINE_HEIGHT: Px = Px(4);
+
 fn composition_underline_rects(rects: Vec<RectDef>) -> Vec<RectDef> {
-    const UNDERLINE_HEIGHT: Px = Px(2);
+    underline_rects(rects, COMPOSITION_UNDERLINE_HEIGHT)
+}
 
+fn underline_rects(rects: Vec<RectDef>, underline_height: Px) -> Vec<RectDef> {
     rects
         .into_iter()
         .map(|mut rect| {
-            let height = UNDERLINE_HEIGHT.min(rect.height.max(Px(1)));
+            let height = underline_height.min(rect.height.max(Px(1)));
             rect.y += rect.height - height;
             rect.height = height;
             rect
@@ -3057,12 +3054,24 @@ fn compute_transformed_composition_rects(
     transformed_text: &TransformedText,
     raw_range: Range<usize>,
 ) -> Vec<RectDef> {
-    let start =
-        text_offset_to_cursor_in_buffer(buffer, transformed_text.map_from_raw(raw_range.start));
-    let end = text_offset_to_cursor_in_buffer(buffer, transformed_text.map_from_raw(raw_range.end));
+    let start = hit_test::offset_to_cursor(buffer, transformed_text.map_from_raw(raw_range.start));
+    let end = hit_test::offset_to_cursor(buffer, transformed_text.map_from_raw(raw_range.end));
     composition_underline_rects(compute_range_rects(buffer, start, end))
 }
 
+fn compute_transformed_clause_rects(
+    buffer: &glyphon::Buffer,
+    transformed_text: &TransformedText,
+    raw_range: Range<usize>,
+) -> Vec<RectDef> {
+    let start = hit_test::offset_to_cursor(buffer, transformed_text.map_from_raw(raw_range.start));
+    let end = hit_test::offset_to_cursor(buffer, transformed_text.map_from_raw(raw_range.end));
+    underline_rects(
+        compute_range_rects(buffer, start, end),
+        CLAUSE_UNDERLINE_HEIGHT,
+    )
+}
+
 fn compute_composition_rects_for_range(
     buffer: &glyphon::Buffer,
     composition: Option<(Cursor, Cursor)>,
@@ -3124,16 +3133,14 @@ fn editor_selection(editor: &glyphon::Editor<'_>) -> TextSelection {
     match editor.selection() {
         glyphon::cosmic_text::Selection::None => {
             let cursor = editor.cursor();
-            let offset =
-                editor.with_buffer(|buffer| cursor_to_text_offset_in_buffer(buffer, cursor));
+            let offset = editor.with_buffer(|buffer| hit_test::cursor_to_offset(buffer, cursor));
             TextSelection::collapsed(offset)
         }
         glyphon::cosmic_text::Selection::Normal(anchor)
         | glyphon::cosmic_text::Selection::Line(anchor)
         | glyphon::cosmic_text::Selection::Word(anchor) => TextSelection {
-            start: editor.with_buffer(|buffer| cursor_to_text_offset_in_buffer(buffer, anchor)),
-            end: editor
-                .with_buffer(|buffer| cursor_to_text_offset_in_buffer(buffer, editor.cursor())),
+            start: editor.with_buffer(|buffer| hit_test::cursor_to_offset(buffer, anchor)),
+            end: editor.with_buffer(|buffer| hit_test::cursor_to_offset(buffer, editor.cursor())),
         },
     }
 }
@@ -3155,6 +3162,25 @@ fn rebase_offset(before: &str, after: &str, offset: usize) -> usize {
     TransformedText::from_strings(before, after.to_string()).map_from_raw(offset.min(before.len()))
 }
 
+/// Builds the text with `spans`, dropping the parts of spans that are out of
+/// order, past the end of the text or split a character.
+fn attributed_text(text: &str, spans: &[TextSpan]) -> AttributedString {
+    let mut attributed = AttributedString::new();
+    let mut covered = 0;
+    for span in spans {
+        let start = span.range.start.max(covered);
+        let end = span.range.end.min(text.len());
+        if start >= end || !text.is_char_boundary(start) || !text.is_char_boundary(end) {
+            continue;
+        }
+        attributed = attributed
+            .push(&text[covered..start])
+            .push_styled(&text[start..end], span.style.clone());
+        covered = end;
+    }
+    attributed.push(&text[covered..])
+}
+
 fn glyphon_color(color: Color) -> glyphon::Color {
     glyphon::Color::rgba(
         (color.r * 255.0) as u8,
@@ -3379,11 +3405,19 @@ impl RenderPolicy for TextEditLayout {
         let mut metadata = input.metadata_mut();
         metadata.set_clips_children(true);
         if let Some(text_data) = self.controller.with(|c| c.current_text_data()) {
+            let decorations = text_data.decorations();
             let drawable = TextCommand {
                 data: text_data,
                 offset: PxPosition::ZERO,
             };
-            metadata.fragment_mut().push_draw_command(drawable);
+            let fragment = metadata.fragment_mut();
+            fragment.push_draw_command(drawable);
+            for decoration in decorations {
+                fragment.push_draw_command_in(
+                    SdfShapeCommand::new(SdfShape::RECT, decoration.color),
+                    decoration.rect,
+                );
+            }
         }
     }
 }
@@ -3472,10 +3506,10 @@ mod tests {
     use super::{
         ClickType, RectDef, TextEditorController, TextLayoutCacheKey, TextSelection,
         TransformedText, active_ime_rect, build_display_buffer, build_display_editor,
-        compute_transformed_composition_rects, text_offset_to_cursor_in_buffer, write_font_system,
+        compute_transformed_composition_rects, write_font_system,
     };
-    use crate::pipelines::text::command::TextConstraint;
     use glyphon::{Action as GlyphonAction, Edit as _, cosmic_text::Motion};
+    use tessera_ui::renderer::drawer::text::{TextConstraint, hit_test};
     use tessera_ui::winit::keyboard::NamedKey;
     use tessera_ui::{ComputedData, Dp, Px};
 
@@ -3689,8 +3723,8 @@ mod tests {
         let cursor = raw_editor.cursor();
 
         raw_editor.with_buffer(|buffer| {
-            assert_eq!(super::cursor_to_text_offset_in_buffer(buffer, anchor), 4);
-            assert_eq!(super::cursor_to_text_offset_in_buffer(buffer, cursor), 1);
+            assert_eq!(hit_test::cursor_to_offset(buffer, anchor), 4);
+            assert_eq!(hit_test::cursor_to_offset(buffer, cursor), 1);
         });
     }
 
@@ -4021,6 +4055,21 @@ mod tests {
         assert!(controller.editor().selection_bounds().is_none());
     }
 
+    #[test]
+    fn move_cursor_with_motion_steps_over_grapheme_clusters() {
+        // "a", Devanagari "ki" (consonant plus vowel sign), then a ZWJ family.
+        let text = "a\u{915}\u{93F}\u{1F468}\u{200D}\u{1F469}";
+        let mut controller = controller_with_text(text);
+        controller.set_text_and_selection(text, TextSelection::collapsed(1));
+
+        assert!(controller.move_cursor_with_motion(Motion::Right));
+        assert_eq!(controller.cursor_offset(), 7);
+        assert!(controller.move_cursor_with_motion(Motion::Right));
+        assert_eq!(controller.cursor_offset(), text.len());
+        assert!(controller.move_cursor_with_motion(Motion::Left));
+        assert_eq!(controller.cursor_offset(), 7);
+    }
+
     #[test]
     fn extend_selection_with_word_motion_reaches_next_word_boundary() {
         let mut controller = controller_with_text("hello world");
@@ -4106,6 +4155,16 @@ mod tests {
         assert_eq!(controller.selection(), TextSelection { start: 0, end: 5 });
     }
 
+    #[test]
+    fn set_selection_clamps_to_character_boundaries() {
+        let mut controller = controller_with_text("héllo");
+
+        controller.set_selection(TextSelection { start: 2, end: 42 });
+
+        assert_eq!(controller.text(), "héllo");
+        assert_eq!(controller.selection(), TextSelection { start: 1, end: 6 });
+    }
+
     #[test]
     fn apply_pointer_action_maps_raw_double_click_to_word_selection() {
         let mut controller = controller_with_text("foo bar");
@@ -4269,7 +4328,7 @@ mod tests {
         let display_offset = 4;
         let display_editor = build_display_editor(
             buffer.clone(),
-            text_offset_to_cursor_in_buffer(&buffer, display_offset),
+            hit_test::offset_to_cursor(&buffer, display_offset),
             None,
         );
         let (x, y) = display_editor
@@ -4295,7 +4354,7 @@ mod tests {
             .expect("raw layout buffer should exist");
         let editor = build_display_editor(
             buffer.clone(),
-            text_offset_to_cursor_in_buffer(&buffer, "hello".len()),
+            hit_test::offset_to_cursor(&buffer, "hello".len()),
             None,
         );
         let (x, y) = editor
@@ -4320,7 +4379,7 @@ mod tests {
         let display_offset = transformed.map_from_raw(4);
         let display_editor = build_display_editor(
             buffer,
-            text_offset_to_cursor_in_buffer(
+            hit_test::offset_to_cursor(
                 &controller
                     .snapshot_buffer()
                     .expect("transformed layout buffer should exist"),
@@ -4350,12 +4409,12 @@ mod tests {
             .expect("transformed layout buffer should exist");
         let start_editor = build_display_editor(
             buffer.clone(),
-            text_offset_to_cursor_in_buffer(&buffer, transformed.map_from_raw(4)),
+            hit_test::offset_to_cursor(&buffer, transformed.map_from_raw(4)),
             None,
         );
         let end_editor = build_display_editor(
             buffer,
-            text_offset_to_cursor_in_buffer(
+            hit_test::offset_to_cursor(
                 &controller
                     .snapshot_buffer()
                     .expect("transformed layout buffer should exist"),
@@ -4393,12 +4452,12 @@ mod tests {
             .expect("transformed layout buffer should exist");
         let start_editor = build_display_editor(
             buffer.clone(),
-            text_offset_to_cursor_in_buffer(&buffer, transformed.map_from_raw(4)),
+            hit_test::offset_to_cursor(&buffer, transformed.map_from_raw(4)),
             None,
         );
         let end_editor = build_display_editor(
             buffer,
-            text_offset_to_cursor_in_buffer(
+            hit_test::offset_to_cursor(
                 &controller
                     .snapshot_buffer()
                     .expect("transformed layout buffer should exist"),
@@ -4436,12 +4495,12 @@ mod tests {
             .expect("transformed layout buffer should exist");
         let start_editor = build_display_editor(
             buffer.clone(),
-            text_offset_to_cursor_in_buffer(&buffer, transformed.map_from_raw(6)),
+            hit_test::offset_to_cursor(&buffer, transformed.map_from_raw(6)),
             None,
         );
         let end_editor = build_display_editor(
             buffer,
-            text_offset_to_cursor_in_buffer(
+            hit_test::offset_to_cursor(
                 &controller
                     .snapshot_buffer()
                     .expect("transformed layout buffer should exist"),
@@ -4491,7 +4550,7 @@ mod tests {
 
         let display_editor = build_display_editor(
             buffer,
-            text_offset_to_cursor_in_buffer(
+            hit_test::offset_to_cursor(
                 &controller
                     .snapshot_buffer()
                     .expect("transformed layout buffer should exist"),
@@ -4522,12 +4581,12 @@ mod tests {
             .expect("transformed layout buffer should exist");
         let start_editor = build_display_editor(
             buffer.clone(),
-            text_offset_to_cursor_in_buffer(&buffer, transformed.map_from_raw(5)),
+            hit_test::offset_to_cursor(&buffer, transformed.map_from_raw(5)),
             None,
         );
         let end_editor = build_display_editor(
             buffer,
-            text_offset_to_cursor_in_buffer(
+            hit_test::offset_to_cursor(
                 &controller
                     .snapshot_buffer()
                     .expect("transformed layout buffer should exist"),
@@ -4566,12 +4625,12 @@ mod tests {
             .expect("transformed layout buffer should exist");
         let start_editor = build_display_editor(
             buffer.clone(),
-            text_offset_to_cursor_in_buffer(&buffer, transformed.map_from_raw(5)),
+            hit_test::offset_to_cursor(&buffer, transformed.map_from_raw(5)),
             None,
         );
         let end_editor = build_display_editor(
             buffer,
-            text_offset_to_cursor_in_buffer(
+            hit_test::offset_to_cursor(
                 &controller
                     .snapshot_buffer()
                     .expect("transformed layout buffer should exist"),
@@ -4773,7 +4832,7 @@ mod tests {
             glyphon::Wrap::None,
             glyphon::cosmic_text::Scroll::default(),
         );
-        let expected_start = text_offset_to_cursor_in_buffer(&buffer, transformed.map_from_raw(3));
+        let expected_start = hit_test::offset_to_cursor(&buffer, transformed.map_from_raw(3));
         let expected_x = build_display_editor(buffer.clone(), expected_start, None)
             .cursor_position()
             .expect("cursor position should be available")
@@ -4,10 +4,10 @@
 //!
 //! Collect short-form inputs like names, passwords, or search queries.
 use glyphon::Action as GlyphonAction;
-use tessera_foundation::gesture::{TapRecognizer, TapSettings};
 use tessera_ui::{
     Callback, CallbackWith, Color, ComputedData, Constraint, Dp, LayoutPolicy, LayoutResult,
-    MeasurementError, Modifier, PressKeyEventType, Px, PxPosition, RenderSlot, State,
+    MeasurementError, Modifier, Px, PxPosition, RenderSlot, State,
+    gesture::{SecondaryActivationRecognizer, SecondaryActivationSettings, TapRecognizer},
     layout::{MeasureScope, layout},
     modifier::CursorModifierExt as _,
     provide_context, remember, tessera, use_context, winit,
@@ -43,6 +43,10 @@ impl TextFieldDefaults {
     pub const MIN_HEIGHT: Dp = Dp(56.0);
     /// Default padding applied on all sides.
     pub const CONTENT_PADDING: Dp = Dp(16.0);
+    /// Default font size of the input text.
+    pub const FONT_SIZE: Dp = Dp(16.0);
+    /// Default line height of the input text.
+    pub const LINE_HEIGHT: Dp = Dp(24.0);
     /// Default outline border width for outlined fields.
     pub const OUTLINED_BORDER_WIDTH: Dp = Dp(1.0);
     /// Default focused border width for outlined fields.
@@ -273,8 +277,8 @@ impl Default for TextFieldProps {
             accessibility_label: None,
             accessibility_description: None,
             initial_text: None,
-            font_size: Dp(16.0),
-            line_height: Some(Dp(24.0)),
+            font_size: TextFieldDefaults::FONT_SIZE,
+            line_height: Some(TextFieldDefaults::LINE_HEIGHT),
             label: None,
             placeholder: None,
             leading_icon: None,
@@ -586,6 +590,7 @@ fn outlined_floating_label(
                 .style(TextStyle {
                     font_size: label_font_size,
                     line_height: Some(label_line_height),
+                    ..TextStyle::default()
                 });
         });
 }
@@ -623,6 +628,7 @@ fn text_field_content(
         TextStyle {
             font_size: style.font_size,
             line_height: Some(style.line_height.unwrap_or(Dp(style.font_size.0 * 1.2))),
+            ..style
         }
     };
     let label_resting_style = {
@@ -630,6 +636,7 @@ fn text_field_content(
         TextStyle {
             font_size: style.font_size,
             line_height: Some(style.line_height.unwrap_or(Dp(style.font_size.0 * 1.2))),
+            ..style
         }
     };
     let placeholder_style = placeholder_text_style(&theme);
@@ -1178,9 +1185,9 @@ pub fn text_field(
     let render_args = args.clone();
 
     let focus_tap_recognizer = remember(TapRecognizer::default);
-    let context_menu_tap_recognizer = remember(|| {
-        TapRecognizer::new(TapSettings {
-            button: PressKeyEventType::Right,
+    let context_menu_recognizer = remember(|| {
+        SecondaryActivationRecognizer::new(SecondaryActivationSettings {
+            consume: false,
             ..Default::default()
         })
     });
@@ -1194,7 +1201,7 @@ pub fn text_field(
         let is_inside = cursor_pos
             .map(|pos| is_position_inside_bounds(input.computed_data, pos))
             .unwrap_or(false);
-        let context_menu_tap_result = context_menu_tap_recognizer.with_mut(|recognizer| {
+        let context_menu_activation = context_menu_recognizer.with_mut(|recognizer| {
             recognizer.update(
                 input.pass,
                 input.pointer_changes.as_mut_slice(),
@@ -1223,11 +1230,9 @@ pub fn text_field(
                 );
             }
 
-            if context_menu_tap_result.tapped
-                && let Some(cursor_pos) = cursor_pos
-            {
+            if let Some(activation) = context_menu_activation {
                 menu_controller.with_mut(|menu| {
-                    menu.open_at(MenuAnchor::at(cursor_pos));
+                    menu.open_at(MenuAnchor::at(activation.position));
                 });
             }
         }
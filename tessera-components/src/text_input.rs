@@ -4,12 +4,14 @@
 //!
 //! Embed as a bare text input surface when you need to build custom styling.
 use glyphon::Action as GlyphonAction;
-use tessera_foundation::gesture::{ScrollRecognizer, ScrollResult, TapRecognizer};
 use tessera_ui::{
-    AccessibilityActionHandler, AccessibilityNode, Callback, CallbackWith, Color, ComputedData, Dp,
-    ImeInput, ImeInputModifierNode, ImeRequest, KeyboardInput, KeyboardInputModifierNode, Modifier,
-    PointerInput, PointerInputModifierNode, Px, PxPosition, PxSize, SemanticsModifierNode, State,
+    AccessibilityActionData, AccessibilityActionHandler, AccessibilityNode, Callback, CallbackWith,
+    Color, ComputedData, Dp, ImeInput, ImeInputModifierNode, ImeRequest, KeyboardInput,
+    KeyboardInputModifierNode, Modifier, PointerInput, PointerInputModifierNode, Px, PxPosition,
+    PxSize, SemanticsModifierNode, State,
+    accessibility::current_action_data,
     accesskit::{Action, Role},
+    gesture::{ScrollRecognizer, ScrollResult, TapRecognizer},
     layout::layout,
     modifier::{CursorModifierExt as _, FocusModifierExt as _, ModifierCapabilityExt as _},
     remember, tessera, use_context, winit,
@@ -979,7 +981,10 @@ fn handle_text_input_ime(
                     *on_change,
                     *input_transform,
                 );
-                controller.with_mut(|c| c.commit_ime_edit(&plan, &result));
+                controller.with_mut(|c| {
+                    c.commit_ime_edit(&plan, &result);
+                    c.update_preedit(&event);
+                });
             }
         }
     }
@@ -1406,7 +1411,8 @@ fn apply_text_input_semantics(
     accessibility.label = args.accessibility_label.clone();
     accessibility.description = args.accessibility_description.clone();
 
-    let current_text = state.with(|c| c.text());
+    let (current_text, selection) = state.with(|c| (c.text(), c.selection()));
+    accessibility.text_selection = Some(selection.start..selection.end);
     accessibility.value = (!current_text.is_empty()).then_some(current_text);
 
     accessibility.is_editable_text = args.enabled && !args.read_only;
@@ -1418,6 +1424,11 @@ fn apply_text_input_semantics(
 
     if args.enabled {
         let on_submit = args.on_submit;
+        let editable = !args.read_only;
+        let single_line = args.single_line;
+        let on_change = args.on_change;
+        let input_transform = args.input_transform;
+        let state = *state;
         *action_handler = Some(Box::new(move |action| match action {
             Action::Focus => focus.request_focus(),
             Action::Blur => focus.clear_focus(),
@@ -1428,6 +1439,34 @@ fn apply_text_input_semantics(
                     focus.request_focus();
                 }
             }
+            Action::SetValue if editable => {
+                if let Some(AccessibilityActionData::Value(value)) = current_action_data() {
+                    let value = if single_line {
+                        value.replace(['\n', '\r'], "")
+                    } else {
+                        value
+                    };
+                    let len = state.with(|c| c.text().len());
+                    replace_text_range_with_selection(
+                        &state,
+                        0..len,
+                        &value,
+                        TextSelection::collapsed(value.len()),
+                        on_change,
+                        input_transform,
+                    );
+                }
+            }
+            Action::SetTextSelection => {
+                if let Some(AccessibilityActionData::TextSelection(range)) = current_action_data() {
+                    state.with_mut(|c| {
+                        c.set_selection(TextSelection {
+                            start: range.start,
+                            end: range.end,
+                        })
+                    });
+                }
+            }
             _ => {}
         }));
     } else {
@@ -2086,6 +2125,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn plan_ime_edit_empty_preedit_without_composition_keeps_selection() {
+        assert_eq!(
+            plan_ime_event_for_state(
+                TextSelection { start: 1, end: 3 },
+                None,
+                false,
+                &winit::event::Ime::Preedit(String::new(), None),
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn plan_ime_edit_disabled_discards_pending_composition() {
+        let composition = ImeComposition {
+            range: 2..4,
+            selection: TextSelection::collapsed(4),
+        };
+        assert_eq!(
+            plan_ime_event_for_state(
+                TextSelection::collapsed(4),
+                Some(composition),
+                false,
+                &winit::event::Ime::Disabled,
+            ),
+            Some(PlannedImeEvent::Edit(PlannedImeEdit {
+                replacement_range: 2..4,
+                replacement_text: String::new(),
+                selection: TextSelection::collapsed(2),
+                composition_range: None,
+            }))
+        );
+    }
+
     #[test]
     fn plan_ime_edit_preedit_without_cursor_offset_preserves_previous_composition_selection() {
         let composition = ImeComposition {
@@ -8,11 +8,14 @@ use material_color_utilities::{
     dynamiccolor::{DynamicSchemeBuilder, MaterialDynamicColors, SpecVersion, Variant},
     hct::Hct,
 };
-use tessera_ui::{CallbackWith, Color, Dp, RenderSlot, provide_context, tessera};
+use tessera_ui::{
+    CallbackWith, Color, Dp, FontWeight, RenderSlot, provide_context_keyed,
+    system_preferences::system_preferences, tessera,
+};
 
 use crate::shape_def::Shape;
 
-const DEFAULT_COLOR: Color = Color::from_rgb(0.4039, 0.3137, 0.6431); // #6750A4
+pub(crate) const DEFAULT_COLOR: Color = Color::from_rgb(0.4039, 0.3137, 0.6431); // #6750A4
 
 /// Ambient content color used by text and icons when no explicit tint is
 /// provided.
@@ -76,14 +79,16 @@ pub fn content_color_for(background: Color, scheme: &MaterialColorScheme) -> Opt
     scheme.content_color_for(background)
 }
 
-/// A simple text style used by components to derive default font size and line
-/// height.
+/// A simple text style used by components to derive default font size, line
+/// height and weight.
 #[derive(Clone, PartialEq, Copy, Debug)]
 pub struct TextStyle {
     /// Font size in density-independent pixels (dp).
     pub font_size: Dp,
     /// Optional line height override in density-independent pixels (dp).
     pub line_height: Option<Dp>,
+    /// Font weight.
+    pub font_weight: FontWeight,
 }
 
 impl Default for TextStyle {
@@ -91,6 +96,20 @@ impl Default for TextStyle {
         Self {
             font_size: Dp(16.0),
             line_height: Some(Dp(24.0)),
+            font_weight: FontWeight::NORMAL,
+        }
+    }
+}
+
+impl TextStyle {
+    /// Returns the style with its font size and line height multiplied by
+    /// `factor`.
+    pub fn scaled(self, factor: f32) -> Self {
+        let factor = f64::from(factor);
+        Self {
+            font_size: Dp(self.font_size.0 * factor),
+            line_height: self.line_height.map(|height| Dp(height.0 * factor)),
+            ..self
         }
     }
 }
@@ -136,67 +155,106 @@ impl Default for MaterialTypography {
             display_large: TextStyle {
                 font_size: Dp(57.0),
                 line_height: Some(Dp(64.0)),
+                font_weight: FontWeight::NORMAL,
             },
             display_medium: TextStyle {
                 font_size: Dp(45.0),
                 line_height: Some(Dp(52.0)),
+                font_weight: FontWeight::NORMAL,
             },
             display_small: TextStyle {
                 font_size: Dp(36.0),
                 line_height: Some(Dp(44.0)),
+                font_weight: FontWeight::NORMAL,
             },
             headline_large: TextStyle {
                 font_size: Dp(32.0),
                 line_height: Some(Dp(40.0)),
+                font_weight: FontWeight::NORMAL,
             },
             headline_medium: TextStyle {
                 font_size: Dp(28.0),
                 line_height: Some(Dp(36.0)),
+                font_weight: FontWeight::NORMAL,
             },
             headline_small: TextStyle {
                 font_size: Dp(24.0),
                 line_height: Some(Dp(32.0)),
+                font_weight: FontWeight::NORMAL,
             },
             title_large: TextStyle {
                 font_size: Dp(22.0),
                 line_height: Some(Dp(28.0)),
+                font_weight: FontWeight::NORMAL,
             },
             title_medium: TextStyle {
                 font_size: Dp(16.0),
                 line_height: Some(Dp(24.0)),
+                font_weight: FontWeight::MEDIUM,
             },
             title_small: TextStyle {
                 font_size: Dp(14.0),
                 line_height: Some(Dp(20.0)),
+                font_weight: FontWeight::MEDIUM,
             },
             body_large: TextStyle {
                 font_size: Dp(16.0),
                 line_height: Some(Dp(24.0)),
+                font_weight: FontWeight::NORMAL,
             },
             body_medium: TextStyle {
                 font_size: Dp(14.0),
                 line_height: Some(Dp(20.0)),
+                font_weight: FontWeight::NORMAL,
             },
             body_small: TextStyle {
                 font_size: Dp(12.0),
                 line_height: Some(Dp(16.0)),
+                font_weight: FontWeight::NORMAL,
             },
             label_large: TextStyle {
                 font_size: Dp(14.0),
                 line_height: Some(Dp(20.0)),
+                font_weight: FontWeight::MEDIUM,
             },
             label_medium: TextStyle {
                 font_size: Dp(12.0),
                 line_height: Some(Dp(16.0)),
+                font_weight: FontWeight::MEDIUM,
             },
             label_small: TextStyle {
                 font_size: Dp(11.0),
                 line_height: Some(Dp(16.0)),
+                font_weight: FontWeight::MEDIUM,
             },
         }
     }
 }
 
+impl MaterialTypography {
+    /// Returns the typography with every style scaled by `factor`, e.g. to
+    /// follow a font scale setting.
+    pub fn scaled(self, factor: f32) -> Self {
+        Self {
+            display_large: self.display_large.scaled(factor),
+            display_medium: self.display_medium.scaled(factor),
+            display_small: self.display_small.scaled(factor),
+            headline_large: self.headline_large.scaled(factor),
+            headline_medium: self.headline_medium.scaled(factor),
+            headline_small: self.headline_small.scaled(factor),
+            title_large: self.title_large.scaled(factor),
+            title_medium: self.title_medium.scaled(factor),
+            title_small: self.title_small.scaled(factor),
+            body_large: self.body_large.scaled(factor),
+            body_medium: self.body_medium.scaled(factor),
+            body_small: self.body_small.scaled(factor),
+            label_large: self.label_large.scaled(factor),
+            label_medium: self.label_medium.scaled(factor),
+            label_small: self.label_small.scaled(factor),
+        }
+    }
+}
+
 /// Material shape scale used by components to resolve default container shapes.
 #[derive(Clone, PartialEq, Copy, Debug)]
 pub struct MaterialShapes {
@@ -250,6 +308,19 @@ impl MaterialTheme {
     pub fn from_seed(seed: Color, is_dark: bool) -> Self {
         Self::from_color_scheme(scheme_from_seed(seed, is_dark))
     }
+
+    /// Create a theme that follows the system light or dark mode, seeded with
+    /// the system accent color when the platform reports one.
+    ///
+    /// Pass it to [`material_theme`] to switch themes live as the user changes
+    /// their system settings.
+    pub fn auto() -> Self {
+        let preferences = system_preferences();
+        Self::from_seed(
+            preferences.accent_color.unwrap_or(DEFAULT_COLOR),
+            preferences.color_scheme.is_dark(),
+        )
+    }
 }
 
 /// # material_theme
@@ -263,7 +334,10 @@ impl MaterialTheme {
 ///
 /// ## Parameters
 ///
-/// - `theme` — optional theme producer for descendants.
+/// - `theme` — optional theme producer for descendants. It runs again when the
+///   system color scheme or accent color changes, so producers like
+///   [`MaterialTheme::auto`] follow the system without resetting descendant
+///   state.
 /// - `child` — optional content rendered with the provided theme.
 ///
 /// ## Examples
@@ -294,12 +368,14 @@ impl MaterialTheme {
 pub fn material_theme(theme: Option<CallbackWith<(), MaterialTheme>>, child: Option<RenderSlot>) {
     let theme = theme.unwrap_or_else(|| CallbackWith::new(|()| MaterialTheme::default()));
     let child = child.unwrap_or_else(RenderSlot::empty);
-    provide_context(
-        move || theme.call(()),
-        move || {
-            child.render();
-        },
+    let preferences = system_preferences();
+    let appearance = (
+        preferences.color_scheme,
+        preferences
+            .accent_color
+            .map(|color| color.to_array().map(f32::to_bits)),
     );
+    provide_context_keyed(appearance, move || theme.call(()), move || child.render());
 }
 
 /// A Material Design color scheme, which can be light or dark,
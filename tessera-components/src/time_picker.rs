@@ -328,6 +328,7 @@ fn time_picker_inner(args: TimePickerConfig) {
                             .style(TextStyle {
                                 font_size: typography.headline_small.font_size,
                                 line_height: typography.headline_small.line_height,
+                                font_weight: typography.headline_small.font_weight,
                             })
                             .color(scheme.on_surface_variant);
                     };
@@ -558,6 +559,7 @@ fn time_stepper_column(
                         .style(TextStyle {
                             font_size: typography.label_small.font_size,
                             line_height: typography.label_small.line_height,
+                            font_weight: typography.label_small.font_weight,
                         })
                         .color(scheme.on_surface_variant);
                 };
@@ -0,0 +1,360 @@
+//! Material Design 3 plain tooltips for labeling controls.
+//!
+//! ## Usage
+//!
+//! Explain icon buttons and other compact controls on hover or long press.
+use std::time::Duration;
+
+use tessera_ui::{
+    CursorEventContent, Dp, FrameNanosControl, MeasurementError, Modifier, PointerInput,
+    PointerInputModifierNode, PxPosition, PxRect, RenderSlot, State, current_frame_nanos,
+    layout::{LayoutPolicy, LayoutResult, MeasureScope, layout},
+    modifier::ModifierCapabilityExt as _,
+    provide_context, receive_frame_nanos, remember, tessera, use_context,
+};
+
+use crate::{
+    alignment::Alignment,
+    modifier::{ModifierExt as _, SemanticsArgs},
+    overlay::{
+        OverlayAlign, OverlayHandle, OverlayHost, OverlayPlacement, OverlayRequest, OverlaySide,
+    },
+    surface::surface,
+    theme::MaterialTheme,
+};
+
+const HOVER_DELAY: Duration = Duration::from_millis(500);
+const LONG_PRESS_DELAY: Duration = Duration::from_millis(500);
+const LONG_PRESS_LINGER: Duration = Duration::from_millis(1500);
+const TOOLTIP_GAP: Dp = Dp(4.0);
+const TOOLTIP_MIN_HEIGHT: Dp = Dp(24.0);
+const TOOLTIP_MAX_WIDTH: Dp = Dp(200.0);
+const TOOLTIP_HORIZONTAL_PADDING: Dp = Dp(8.0);
+const TOOLTIP_VERTICAL_PADDING: Dp = Dp(4.0);
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_nanos() as u64
+}
+
+/// Hover and press state deciding when a tooltip is shown.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct TooltipTrigger {
+    /// Window-space rect of the anchor, as last seen by pointer input.
+    anchor: Option<PxRect>,
+    hovered: bool,
+    pressed: bool,
+    /// Frame time at which the hover or press waiting to show began.
+    since: Option<u64>,
+    /// Frame time at which a tooltip shown by a long press hides.
+    hide_at: Option<u64>,
+    visible: bool,
+}
+
+impl TooltipTrigger {
+    fn hover(&mut self, hovered: bool, frame_nanos: u64) {
+        if hovered == self.hovered {
+            return;
+        }
+        self.hovered = hovered;
+        if hovered {
+            if !self.visible && !self.pressed {
+                self.since = Some(frame_nanos);
+            }
+        } else if !self.pressed && self.hide_at.is_none() {
+            self.hide();
+        }
+    }
+
+    /// Hides the tooltip, as a click acts on the anchor, and waits for a long
+    /// press instead.
+    fn press(&mut self, frame_nanos: u64) {
+        self.hide();
+        self.pressed = true;
+        self.since = Some(frame_nanos);
+    }
+
+    fn release(&mut self, frame_nanos: u64) {
+        self.pressed = false;
+        if self.visible {
+            self.hide_at = Some(frame_nanos + nanos(LONG_PRESS_LINGER));
+        } else {
+            self.since = None;
+        }
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+        self.since = None;
+        self.hide_at = None;
+    }
+
+    fn is_pending(&self) -> bool {
+        self.hide_at.is_some() || (self.since.is_some() && !self.visible)
+    }
+
+    /// Advances the delays to `frame_nanos`; returns whether one is still
+    /// running.
+    fn tick(&mut self, frame_nanos: u64) -> bool {
+        if let Some(hide_at) = self.hide_at {
+            if frame_nanos >= hide_at {
+                self.hide();
+            }
+        } else if let Some(since) = self.since {
+            let delay = if self.pressed {
+                LONG_PRESS_DELAY
+            } else {
+                HOVER_DELAY
+            };
+            if frame_nanos.saturating_sub(since) >= nanos(delay) {
+                self.visible = true;
+                self.since = None;
+            }
+        }
+        self.is_pending()
+    }
+}
+
+struct TooltipPointerModifierNode {
+    trigger: State<TooltipTrigger>,
+}
+
+impl PointerInputModifierNode for TooltipPointerModifierNode {
+    fn on_pointer_input(&self, input: PointerInput<'_>) {
+        let frame_nanos = current_frame_nanos();
+        let current = self.trigger.get();
+        let mut next = current;
+        if let (Some(abs), Some(rel)) = (input.cursor_position_abs(), input.cursor_position_rel) {
+            next.anchor = Some(PxRect::from_position_size(
+                abs - rel,
+                input.computed_data.into(),
+            ));
+        }
+        for change in input.pointer_changes.iter() {
+            if change.is_consumed() {
+                continue;
+            }
+            match change.content {
+                CursorEventContent::Pressed(_) => next.press(frame_nanos),
+                CursorEventContent::Released(_) => next.release(frame_nanos),
+                _ => {}
+            }
+        }
+        next.hover(input.is_hovered(), frame_nanos);
+        if next != current {
+            self.trigger.set(next);
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct TooltipLayout {
+    placement: OverlayPlacement,
+}
+
+impl LayoutPolicy for TooltipLayout {
+    fn measure(&self, input: &MeasureScope<'_>) -> Result<LayoutResult, MeasurementError> {
+        let mut result = LayoutResult::default();
+        let children = input.children();
+        let child_constraint = input.parent_constraint().without_min();
+        let anchor = children
+            .first()
+            .copied()
+            .expect("anchor content should exist");
+        let anchor_size = anchor.measure(&child_constraint)?.size();
+        result.place_child(anchor, PxPosition::ZERO);
+
+        if let Some(bubble) = children.get(1).copied() {
+            let size = bubble.measure(&child_constraint)?.size();
+            let anchor_rect = PxRect::from_position_size(PxPosition::ZERO, anchor_size.into());
+            result.place_child(bubble, self.placement.position(anchor_rect, size));
+        }
+
+        Ok(result.with_size(anchor_size))
+    }
+}
+
+/// # tooltip
+///
+/// Shows a short label next to a control while it is hovered or long pressed.
+///
+/// The tooltip shows after hovering for a moment, or during a long press and
+/// briefly after it. Inside an [`overlay_host`](crate::overlay::overlay_host)
+/// it is drawn above all content and kept inside the host, opening on the other
+/// side of the anchor when there is no room; otherwise it is drawn with the
+/// anchor and may be covered by later siblings.
+///
+/// ## Usage
+///
+/// Name icon-only buttons and explain compact controls without taking layout
+/// space.
+///
+/// ## Parameters
+///
+/// - `anchor_content` — the control the tooltip describes
+/// - `tooltip_content` — content of the tooltip, usually a short text
+/// - `description` — optional text announced by assistive technologies for the
+///   anchor; usually the same as the tooltip text
+/// - `placement` — optional placement relative to the anchor; defaults to
+///   centered above with a small gap
+/// - `modifier` — optional modifier chain applied to the anchor container
+///
+/// ## Examples
+///
+/// ```
+/// # use tessera_ui::tessera;
+/// # #[tessera]
+/// # fn component() {
+/// use tessera_components::{
+///     overlay::{OverlayAlign, OverlayPlacement, OverlaySide, overlay_host},
+///     text::text,
+///     tooltip::tooltip,
+/// };
+/// # use tessera_components::theme::{MaterialTheme, material_theme};
+///
+/// # material_theme()
+/// #     .theme(|| MaterialTheme::default())
+/// #     .child(|| {
+/// overlay_host().content(|| {
+///     tooltip(
+///         || {
+///             text().content("Save");
+///         },
+///         || {
+///             text().content("Save the document");
+///         },
+///     )
+///     .description("Save the document")
+///     .placement(OverlayPlacement::new(
+///         OverlaySide::Below,
+///         OverlayAlign::Center,
+///     ));
+/// });
+/// # });
+/// # }
+/// # component();
+/// ```
+#[tessera]
+pub fn tooltip(
+    anchor_content: RenderSlot,
+    tooltip_content: RenderSlot,
+    #[prop(into)] description: Option<String>,
+    placement: Option<OverlayPlacement>,
+    modifier: Option<Modifier>,
+) {
+    let placement = placement.unwrap_or_else(|| {
+        OverlayPlacement::new(OverlaySide::Above, OverlayAlign::Center).gap(TOOLTIP_GAP)
+    });
+    let trigger = remember(TooltipTrigger::default);
+    let handle = remember(OverlayHandle::new);
+    let host = use_context::<OverlayHost>().map(|host| host.get());
+
+    let state = trigger.get();
+    if state.is_pending() {
+        receive_frame_nanos(move |frame_nanos| {
+            let current = trigger.get();
+            let mut next = current;
+            let pending = next.tick(frame_nanos);
+            if next != current {
+                trigger.set(next);
+            }
+            if pending {
+                FrameNanosControl::Continue
+            } else {
+                FrameNanosControl::Stop
+            }
+        });
+    }
+
+    let bubble = RenderSlot::new(move || {
+        tooltip_container(tooltip_content);
+    });
+    let anchor = state.anchor.filter(|_| state.visible);
+    if let Some(host) = host {
+        handle.with(|handle| match anchor {
+            Some(anchor) => host.show(
+                handle,
+                OverlayRequest {
+                    anchor,
+                    placement,
+                    content: bubble,
+                    on_dismiss: None,
+                },
+            ),
+            None => host.hide(handle),
+        });
+    }
+    let inline = host.is_none() && anchor.is_some();
+
+    let mut modifier = modifier
+        .unwrap_or_default()
+        .push_pointer_preview_input(TooltipPointerModifierNode { trigger });
+    if let Some(description) = description {
+        modifier = modifier.semantics(SemanticsArgs {
+            tooltip: Some(description),
+            ..Default::default()
+        });
+    }
+
+    layout()
+        .modifier(modifier)
+        .layout_policy(TooltipLayout { placement })
+        .child(move || {
+            anchor_content.render();
+            if inline {
+                bubble.render();
+            }
+        });
+}
+
+#[tessera]
+pub(crate) fn tooltip_container(content: RenderSlot) {
+    let theme = use_context::<MaterialTheme>()
+        .expect("MaterialTheme must be provided")
+        .get();
+    let scheme = theme.color_scheme;
+    let typography = theme.typography;
+    surface()
+        .modifier(Modifier::new().size_in(
+            None,
+            Some(TOOLTIP_MAX_WIDTH),
+            Some(TOOLTIP_MIN_HEIGHT),
+            None,
+        ))
+        .style(scheme.inverse_surface.into())
+        .shape(theme.shapes.extra_small)
+        .content_alignment(Alignment::CenterStart)
+        .content_color(scheme.inverse_on_surface)
+        .child(move || {
+            let modifier = Modifier::new()
+                .padding_symmetric(TOOLTIP_HORIZONTAL_PADDING, TOOLTIP_VERTICAL_PADDING);
+            layout().modifier(modifier).child(move || {
+                provide_context(|| typography.body_small, move || content.render());
+            });
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LONG_PRESS_DELAY, LONG_PRESS_LINGER, TooltipTrigger, nanos};
+
+    #[test]
+    fn long_press_shows_and_lingers_after_release() {
+        let mut trigger = TooltipTrigger::default();
+        trigger.hover(true, 0);
+        trigger.press(0);
+        assert!(trigger.tick(nanos(LONG_PRESS_DELAY) / 2));
+        assert!(!trigger.visible);
+
+        assert!(!trigger.tick(nanos(LONG_PRESS_DELAY)));
+        assert!(trigger.visible);
+
+        let released = nanos(LONG_PRESS_DELAY) * 2;
+        trigger.release(released);
+        trigger.hover(false, released);
+        assert!(trigger.tick(released + nanos(LONG_PRESS_LINGER) / 2));
+        assert!(trigger.visible);
+
+        assert!(!trigger.tick(released + nanos(LONG_PRESS_LINGER)));
+        assert!(!trigger.visible);
+    }
+}
@@ -0,0 +1,852 @@
+//! Hierarchical tree views with expandable nodes.
+//!
+//! ## Usage
+//!
+//! Browse file systems, outlines and settings categories.
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    sync::Arc,
+};
+
+use tessera_ui::{
+    CallbackWith, Dp, FocusRequester, FocusState, Modifier, Px, State,
+    accesskit::Role,
+    gesture::{DragAxis, DragRecognizer, DragSettings, TapRecognizer, TapSettings},
+    key,
+    modifier::FocusModifierExt as _,
+    remember, tessera, use_context, winit,
+};
+
+use crate::{
+    alignment::{Alignment, CrossAxisAlignment},
+    animated_visibility::{VisibilityTransition, animated_visibility},
+    boxed::boxed,
+    column::column,
+    icon::icon,
+    material_icons::filled,
+    modifier::{ModifierExt as _, SemanticsArgs, with_keyboard_input, with_pointer_input},
+    painter::Painter,
+    pos_misc::is_position_inside_bounds,
+    row::row,
+    spacer::spacer,
+    text::text,
+    theme::MaterialTheme,
+};
+
+const ROW_HEIGHT: Dp = Dp(40.0);
+const INDENT: Dp = Dp(24.0);
+const EXPAND_ICON_SIZE: Dp = Dp(20.0);
+const LEADING_ICON_SIZE: Dp = Dp(20.0);
+const ROW_HORIZONTAL_PADDING: Dp = Dp(8.0);
+const FOCUS_BORDER_WIDTH: Dp = Dp(2.0);
+
+#[derive(Clone, Debug, PartialEq)]
+enum TreeChildren {
+    None,
+    Unloaded,
+    Loaded(Vec<TreeNode>),
+}
+
+/// A node of a [`tree_view`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeNode {
+    key: u64,
+    label: String,
+    icon: Option<Painter>,
+    children: TreeChildren,
+}
+
+impl TreeNode {
+    /// Creates a node without children. `key` must be unique in the tree.
+    pub fn leaf(key: u64, label: impl Into<String>) -> Self {
+        Self {
+            key,
+            label: label.into(),
+            icon: None,
+            children: TreeChildren::None,
+        }
+    }
+
+    /// Creates a node with `children`.
+    pub fn branch(key: u64, label: impl Into<String>, children: Vec<TreeNode>) -> Self {
+        Self {
+            children: TreeChildren::Loaded(children),
+            ..Self::leaf(key, label)
+        }
+    }
+
+    /// Creates a node whose children are loaded when it is first expanded;
+    /// the tree calls `on_load_children` with its key.
+    pub fn unloaded(key: u64, label: impl Into<String>) -> Self {
+        Self {
+            children: TreeChildren::Unloaded,
+            ..Self::leaf(key, label)
+        }
+    }
+
+    /// Sets an icon shown before the label.
+    pub fn icon(mut self, icon: impl Into<Painter>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+}
+
+/// Requested move of a node, reported by [`tree_view`] when a node is
+/// dragged to a new place.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TreeMove {
+    /// Key of the moved node.
+    pub key: u64,
+    /// Key of the new parent, or `None` for the top level.
+    pub parent: Option<u64>,
+    /// Index among the new parent's children once the node has been removed
+    /// from its old place.
+    pub index: usize,
+}
+
+/// How clicks and keys change the selection of a [`tree_view`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum TreeSelectionMode {
+    /// Nodes cannot be selected.
+    None,
+    /// One node is selected at a time.
+    #[default]
+    Single,
+    /// Ctrl toggles a node and Shift selects the range from the last
+    /// selected node.
+    Multiple,
+}
+
+/// Expansion, selection and keyboard focus of a [`tree_view`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TreeViewController {
+    expanded: HashSet<u64>,
+    selection: BTreeSet<u64>,
+    /// Node a Shift-selection extends from.
+    anchor: Option<u64>,
+    /// Node moved by the arrow keys.
+    focused: Option<u64>,
+    has_focus: bool,
+    /// Key and offset of the node being dragged.
+    dragging: Option<(u64, Px)>,
+}
+
+impl TreeViewController {
+    /// Creates a controller with every node collapsed and nothing selected.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns whether the node with `key` is expanded.
+    pub fn is_expanded(&self, key: u64) -> bool {
+        self.expanded.contains(&key)
+    }
+
+    /// Expands the node with `key`.
+    pub fn expand(&mut self, key: u64) {
+        self.expanded.insert(key);
+    }
+
+    /// Collapses the node with `key`.
+    pub fn collapse(&mut self, key: u64) {
+        self.expanded.remove(&key);
+    }
+
+    /// Returns whether the node with `key` is selected.
+    pub fn is_selected(&self, key: u64) -> bool {
+        self.selection.contains(&key)
+    }
+
+    /// Returns the keys of the selected nodes.
+    pub fn selected_keys(&self) -> Vec<u64> {
+        self.selection.iter().copied().collect()
+    }
+
+    /// Replaces the selection.
+    pub fn set_selection(&mut self, keys: impl IntoIterator<Item = u64>) {
+        self.selection = keys.into_iter().collect();
+        self.anchor = self.selection.first().copied();
+    }
+
+    /// Clears the selection.
+    pub fn clear_selection(&mut self) {
+        self.selection.clear();
+        self.anchor = None;
+    }
+
+    /// Selects `key` as a click would; returns whether the selection changed.
+    fn select(
+        &mut self,
+        key: u64,
+        visible: &[VisibleNode],
+        mode: TreeSelectionMode,
+        extend: bool,
+        toggle: bool,
+    ) -> bool {
+        let previous = self.selection.clone();
+        let position = |key| visible.iter().position(|node| node.key == key);
+        match mode {
+            TreeSelectionMode::None => return false,
+            TreeSelectionMode::Single => {
+                self.selection = BTreeSet::from([key]);
+                self.anchor = Some(key);
+            }
+            TreeSelectionMode::Multiple => {
+                let range = self
+                    .anchor
+                    .and_then(position)
+                    .zip(position(key))
+                    .filter(|_| extend);
+                if let Some((from, to)) = range {
+                    if !toggle {
+                        self.selection.clear();
+                    }
+                    let range = from.min(to)..=from.max(to);
+                    self.selection
+                        .extend(visible[range].iter().map(|node| node.key));
+                } else if toggle {
+                    if !self.selection.remove(&key) {
+                        self.selection.insert(key);
+                    }
+                    self.anchor = Some(key);
+                } else {
+                    self.selection = BTreeSet::from([key]);
+                    self.anchor = Some(key);
+                }
+            }
+        }
+        self.selection != previous
+    }
+
+    fn handle_key(
+        &mut self,
+        key: TreeKey,
+        visible: &[VisibleNode],
+        mode: TreeSelectionMode,
+        extend: bool,
+        toggle: bool,
+    ) -> Option<KeyOutcome> {
+        let last = visible.len().checked_sub(1)?;
+        let current = self
+            .focused
+            .and_then(|key| visible.iter().position(|node| node.key == key));
+        let mut outcome = KeyOutcome::default();
+        let target = match (key, current) {
+            (TreeKey::Up, Some(position)) => position.saturating_sub(1),
+            (TreeKey::Down, Some(position)) => (position + 1).min(last),
+            (TreeKey::Up | TreeKey::Down | TreeKey::Home, _) => 0,
+            (TreeKey::End, _) => last,
+            (_, None) => return None,
+            (TreeKey::Right, Some(position)) => {
+                let node = visible[position];
+                if !node.expandable {
+                    return None;
+                }
+                if !self.is_expanded(node.key) {
+                    self.expand(node.key);
+                    outcome.load = (!node.loaded).then_some(node.key);
+                    return Some(outcome);
+                }
+                match visible.get(position + 1) {
+                    Some(child) if child.parent == Some(node.key) => position + 1,
+                    _ => return Some(outcome),
+                }
+            }
+            (TreeKey::Left, Some(position)) => {
+                let node = visible[position];
+                if self.is_expanded(node.key) {
+                    self.collapse(node.key);
+                    return Some(outcome);
+                }
+                let parent = node.parent?;
+                visible.iter().position(|node| node.key == parent)?
+            }
+            (TreeKey::Select, Some(position)) => {
+                outcome.selection_changed =
+                    self.select(visible[position].key, visible, mode, extend, toggle);
+                return Some(outcome);
+            }
+        };
+        let target = visible[target].key;
+        self.focused = Some(target);
+        if !toggle {
+            outcome.selection_changed = self.select(target, visible, mode, extend, false);
+        }
+        Some(outcome)
+    }
+
+    fn drag(&mut self, key: u64, delta: Px) {
+        let offset = match self.dragging {
+            Some((dragged, offset)) if dragged == key => offset + delta,
+            _ => delta,
+        };
+        self.dragging = Some((key, offset));
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TreeKey {
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    Select,
+}
+
+impl TreeKey {
+    fn from_key(key: &winit::keyboard::Key) -> Option<Self> {
+        use winit::keyboard::{Key, NamedKey};
+        match key {
+            Key::Named(NamedKey::ArrowUp) => Some(Self::Up),
+            Key::Named(NamedKey::ArrowDown) => Some(Self::Down),
+            Key::Named(NamedKey::ArrowLeft) => Some(Self::Left),
+            Key::Named(NamedKey::ArrowRight) => Some(Self::Right),
+            Key::Named(NamedKey::Home) => Some(Self::Home),
+            Key::Named(NamedKey::End) => Some(Self::End),
+            Key::Named(NamedKey::Enter | NamedKey::Space) => Some(Self::Select),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct KeyOutcome {
+    selection_changed: bool,
+    /// Node expanded for the first time whose children must be loaded.
+    load: Option<u64>,
+}
+
+/// A node currently shown, in display order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct VisibleNode {
+    key: u64,
+    parent: Option<u64>,
+    /// Index among its siblings.
+    index: usize,
+    expandable: bool,
+    loaded: bool,
+}
+
+fn visible_nodes(nodes: &[TreeNode], expanded: &HashSet<u64>) -> Vec<VisibleNode> {
+    fn visit(
+        nodes: &[TreeNode],
+        parent: Option<u64>,
+        expanded: &HashSet<u64>,
+        out: &mut Vec<VisibleNode>,
+    ) {
+        for (index, node) in nodes.iter().enumerate() {
+            out.push(VisibleNode {
+                key: node.key,
+                parent,
+                index,
+                expandable: node.children != TreeChildren::None,
+                loaded: node.children != TreeChildren::Unloaded,
+            });
+            if let TreeChildren::Loaded(children) = &node.children
+                && expanded.contains(&node.key)
+            {
+                visit(children, Some(node.key), expanded, out);
+            }
+        }
+    }
+    let mut out = Vec::new();
+    visit(nodes, None, expanded, &mut out);
+    out
+}
+
+/// Returns where a node dragged by `offset` from visible position `from`
+/// lands, or `None` when it stays or would move into its own subtree.
+fn drop_target(
+    visible: &[VisibleNode],
+    from: usize,
+    offset: Px,
+    row_height: Px,
+) -> Option<TreeMove> {
+    let rows = (offset.0 as f32 / row_height.0.max(1) as f32).round() as isize;
+    let to = (from as isize + rows).clamp(0, visible.len() as isize - 1) as usize;
+    if to == from {
+        return None;
+    }
+    let dragged = visible[from];
+    let target = visible[to];
+    let parents: HashMap<u64, Option<u64>> =
+        visible.iter().map(|node| (node.key, node.parent)).collect();
+    let mut ancestor = Some(target.key);
+    while let Some(key) = ancestor {
+        if key == dragged.key {
+            return None;
+        }
+        ancestor = parents.get(&key).copied().flatten();
+    }
+    // Moving down places the node after the target, moving up before it.
+    let after = to > from;
+    let shifted = target.parent == dragged.parent && dragged.index < target.index;
+    let index = match (after, shifted) {
+        (true, true) => target.index,
+        (true, false) => target.index + 1,
+        (false, _) => target.index,
+    };
+    Some(TreeMove {
+        key: dragged.key,
+        parent: target.parent,
+        index,
+    })
+}
+
+#[derive(Clone)]
+struct TreeContext {
+    visible: Arc<Vec<VisibleNode>>,
+    selection_mode: TreeSelectionMode,
+    on_selection_change: Option<CallbackWith<Vec<u64>>>,
+    on_load_children: Option<CallbackWith<u64>>,
+    on_move: Option<CallbackWith<TreeMove>>,
+    controller: State<TreeViewController>,
+    focus: FocusRequester,
+}
+
+impl PartialEq for TreeContext {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.visible, &other.visible)
+            && self.selection_mode == other.selection_mode
+            && self.on_selection_change == other.on_selection_change
+            && self.on_load_children == other.on_load_children
+            && self.on_move == other.on_move
+            && self.controller == other.controller
+            && self.focus == other.focus
+    }
+}
+
+impl TreeContext {
+    fn notify_selection(&self) {
+        if let Some(on_selection_change) = self.on_selection_change {
+            on_selection_change.call(self.controller.with(TreeViewController::selected_keys));
+        }
+    }
+
+    fn load(&self, key: Option<u64>) {
+        if let (Some(key), Some(on_load_children)) = (key, self.on_load_children) {
+            on_load_children.call(key);
+        }
+    }
+}
+
+/// # tree_view
+///
+/// Shows a hierarchy of nodes that expand and collapse.
+///
+/// Clicking the arrow of a node expands or collapses it with an animation;
+/// clicking the node selects it. When the tree has focus, Up and Down move
+/// between nodes, Right expands a node or moves to its first child, Left
+/// collapses a node or moves to its parent, and Enter or Space selects.
+/// Children of [`TreeNode::unloaded`] nodes are requested through
+/// `on_load_children` the first time the node expands. With `on_move` set,
+/// nodes can be dragged to a new place; the application applies the reported
+/// [`TreeMove`] to its data.
+///
+/// ## Usage
+///
+/// Browse nested data such as folders.
+///
+/// ## Parameters
+///
+/// - `roots` — top-level nodes
+/// - `selection_mode` — optional selection behavior; defaults to
+///   [`TreeSelectionMode::Single`]
+/// - `on_selection_change` — optional callback with the selected keys after
+///   they change
+/// - `on_load_children` — optional callback with the key of an unloaded node
+///   that was expanded
+/// - `on_move` — optional callback with a drag-and-drop move; dragging is
+///   disabled without it
+/// - `controller` — optional controller holding expansion and selection
+/// - `modifier` — optional modifier chain applied to the tree
+///
+/// ## Examples
+///
+/// ```
+/// # use tessera_ui::tessera;
+/// # #[tessera]
+/// # fn component() {
+/// use tessera_components::tree_view::{TreeNode, TreeSelectionMode, tree_view};
+/// # use tessera_components::theme::{MaterialTheme, material_theme};
+///
+/// # material_theme()
+/// #     .theme(|| MaterialTheme::default())
+/// #     .child(|| {
+/// tree_view(vec![
+///     TreeNode::branch(
+///         1,
+///         "src",
+///         vec![TreeNode::leaf(2, "lib.rs"), TreeNode::leaf(3, "main.rs")],
+///     ),
+///     TreeNode::unloaded(4, "target"),
+/// ])
+/// .selection_mode(TreeSelectionMode::Multiple)
+/// .on_load_children(|key: u64| {
+///     let _ = key;
+/// });
+/// # });
+/// # }
+/// # component();
+/// ```
+#[tessera]
+pub fn tree_view(
+    roots: Vec<TreeNode>,
+    selection_mode: Option<TreeSelectionMode>,
+    on_selection_change: Option<CallbackWith<Vec<u64>>>,
+    on_load_children: Option<CallbackWith<u64>>,
+    on_move: Option<CallbackWith<TreeMove>>,
+    controller: Option<State<TreeViewController>>,
+    modifier: Option<Modifier>,
+) {
+    let controller = controller.unwrap_or_else(|| remember(TreeViewController::new));
+    let focus = remember(FocusRequester::new).get();
+    let visible =
+        Arc::new(controller.with(|controller| visible_nodes(&roots, &controller.expanded)));
+    let context = TreeContext {
+        visible: visible.clone(),
+        selection_mode: selection_mode.unwrap_or_default(),
+        on_selection_change,
+        on_load_children,
+        on_move,
+        controller,
+        focus,
+    };
+
+    let base = modifier
+        .unwrap_or_default()
+        .focus_requester(focus)
+        .focusable()
+        .on_focus_changed(move |focus_state: FocusState| {
+            let has_focus = focus_state.has_focus();
+            if controller.with(|controller| controller.has_focus != has_focus) {
+                controller.with_mut(|controller| controller.has_focus = has_focus);
+            }
+        })
+        .semantics(SemanticsArgs {
+            role: Some(Role::Tree),
+            ..Default::default()
+        });
+    let keyboard_context = context.clone();
+    let modifier = with_keyboard_input(base, move |mut input| {
+        let context = &keyboard_context;
+        let extend = input.key_modifiers.shift_key();
+        let toggle = input.key_modifiers.control_key() || input.key_modifiers.super_key();
+        let mut handled = false;
+        for event in input.keyboard_events.iter() {
+            if event.state != winit::event::ElementState::Pressed {
+                continue;
+            }
+            let Some(key) = TreeKey::from_key(&event.logical_key) else {
+                continue;
+            };
+            let outcome = context.controller.with_mut(|controller| {
+                controller.handle_key(
+                    key,
+                    &context.visible,
+                    context.selection_mode,
+                    extend,
+                    toggle,
+                )
+            });
+            if let Some(outcome) = outcome {
+                handled = true;
+                if outcome.selection_changed {
+                    context.notify_selection();
+                }
+                context.load(outcome.load);
+            }
+        }
+        if handled {
+            input.block_keyboard();
+        }
+    });
+
+    column().modifier(modifier).children(move || {
+        tree_level(&roots, 0, &context);
+    });
+}
+
+fn tree_level(nodes: &[TreeNode], depth: usize, context: &TreeContext) {
+    for node in nodes {
+        let node = node.clone();
+        let context = context.clone();
+        key(node.key, move || {
+            tree_node(node.clone(), depth, context.clone());
+        });
+    }
+}
+
+#[tessera]
+fn tree_node(node: TreeNode, depth: usize, context: TreeContext) {
+    let expanded = context
+        .controller
+        .with(|controller| controller.is_expanded(node.key));
+    tree_row(node.clone(), depth, expanded, context.clone());
+    if let TreeChildren::Loaded(children) = node.children {
+        let transition = VisibilityTransition::fade()
+            .and(VisibilityTransition::expand_vertically(Alignment::TopStart));
+        animated_visibility()
+            .visible(expanded)
+            .enter(transition.clone())
+            .exit(transition)
+            .child(move || {
+                let children = children.clone();
+                let context = context.clone();
+                column().children(move || {
+                    tree_level(&children, depth + 1, &context);
+                });
+            });
+    }
+}
+
+#[tessera]
+fn tree_row(node: TreeNode, depth: usize, expanded: bool, context: TreeContext) {
+    let key = node.key;
+    let controller = context.controller;
+    let (selected, focused, offset) = controller.with(|controller| {
+        let offset = controller
+            .dragging
+            .filter(|(dragged, _)| *dragged == key)
+            .map_or(Px::ZERO, |(_, offset)| offset);
+        (
+            controller.is_selected(key),
+            controller.has_focus && controller.focused == Some(key),
+            offset,
+        )
+    });
+    let scheme = use_context::<MaterialTheme>()
+        .expect("MaterialTheme must be provided")
+        .get()
+        .color_scheme;
+    let tap_recognizer = remember(TapRecognizer::default);
+    let drag_recognizer = remember(|| {
+        DragRecognizer::new(DragSettings {
+            axis: Some(DragAxis::Vertical),
+            ..Default::default()
+        })
+    });
+
+    let mut base = Modifier::new()
+        .fill_max_width()
+        .height(ROW_HEIGHT)
+        .offset(Dp::ZERO, offset.into())
+        .semantics(SemanticsArgs {
+            role: Some(Role::TreeItem),
+            label: Some(node.label.clone()),
+            ..Default::default()
+        });
+    if selected {
+        base = base.background(scheme.secondary_container);
+    }
+    if focused {
+        base = base.border(FOCUS_BORDER_WIDTH, scheme.secondary);
+    }
+    let row_context = context.clone();
+    let modifier = with_pointer_input(base, move |mut input| {
+        let context = &row_context;
+        let is_inside = input
+            .cursor_position_rel
+            .is_some_and(|position| is_position_inside_bounds(input.computed_data, position));
+        if let Some(on_move) = context.on_move {
+            // The row follows the drag, so deltas are taken in window
+            // coordinates.
+            let cursor = input.cursor_position_abs();
+            let drag = drag_recognizer.with_mut(|recognizer| {
+                recognizer.update(
+                    input.pass,
+                    input.pointer_changes.as_mut_slice(),
+                    cursor,
+                    is_inside,
+                )
+            });
+            if drag.updated {
+                controller.with_mut(|controller| controller.drag(key, drag.delta_y));
+            }
+            if drag.ended
+                && let Some((_, offset)) =
+                    controller.with_mut(|controller| controller.dragging.take())
+                && let Some(from) = context.visible.iter().position(|node| node.key == key)
+                && let Some(target) =
+                    drop_target(&context.visible, from, offset, ROW_HEIGHT.to_px())
+            {
+                on_move.call(target);
+            }
+        }
+        let tap = tap_recognizer.with_mut(|recognizer| {
+            recognizer.update(
+                input.pass,
+                input.pointer_changes.as_mut_slice(),
+                input.cursor_position_rel,
+                is_inside,
+            )
+        });
+        if tap.tapped {
+            context.focus.request_focus();
+            let extend = input.key_modifiers.shift_key();
+            let toggle = input.key_modifiers.control_key() || input.key_modifiers.super_key();
+            let changed = controller.with_mut(|controller| {
+                controller.focused = Some(key);
+                controller.select(
+                    key,
+                    &context.visible,
+                    context.selection_mode,
+                    extend,
+                    toggle,
+                )
+            });
+            if changed {
+                context.notify_selection();
+            }
+        }
+    });
+
+    let expandable = node.children != TreeChildren::None;
+    let loaded = node.children != TreeChildren::Unloaded;
+    row()
+        .modifier(modifier)
+        .cross_axis_alignment(CrossAxisAlignment::Center)
+        .children(move || {
+            let indent = Dp(ROW_HORIZONTAL_PADDING.0 + INDENT.0 * depth as f64);
+            spacer().modifier(Modifier::new().width(indent));
+            if expandable {
+                tree_expand_toggle(key, expanded, loaded, context.clone());
+            } else {
+                spacer().modifier(Modifier::new().size(EXPAND_ICON_SIZE, EXPAND_ICON_SIZE));
+            }
+            if let Some(leading) = node.icon.clone() {
+                spacer().modifier(Modifier::new().width(ROW_HORIZONTAL_PADDING));
+                icon().painter(leading).size(LEADING_ICON_SIZE);
+            }
+            spacer().modifier(Modifier::new().width(ROW_HORIZONTAL_PADDING));
+            text().content(node.label.clone()).max_lines(1);
+        });
+}
+
+#[tessera]
+fn tree_expand_toggle(key: u64, expanded: bool, loaded: bool, context: TreeContext) {
+    let tap_recognizer = remember(|| {
+        // Consumed so the click does not also select the row.
+        TapRecognizer::new(TapSettings {
+            consume_on_release: true,
+            consume_on_tap: true,
+            ..Default::default()
+        })
+    });
+    let controller = context.controller;
+    let modifier = with_pointer_input(
+        Modifier::new().size(EXPAND_ICON_SIZE, EXPAND_ICON_SIZE),
+        move |mut input| {
+            let is_inside = input
+                .cursor_position_rel
+                .is_some_and(|position| is_position_inside_bounds(input.computed_data, position));
+            let tap = tap_recognizer.with_mut(|recognizer| {
+                recognizer.update(
+                    input.pass,
+                    input.pointer_changes.as_mut_slice(),
+                    input.cursor_position_rel,
+                    is_inside,
+                )
+            });
+            if !tap.tapped {
+                return;
+            }
+            if expanded {
+                controller.with_mut(|controller| controller.collapse(key));
+            } else {
+                controller.with_mut(|controller| controller.expand(key));
+                context.load((!loaded).then_some(key));
+            }
+        },
+    );
+    boxed()
+        .modifier(modifier)
+        .alignment(Alignment::Center)
+        .children(move || {
+            icon()
+                .painter(filled::CHEVRON_RIGHT_SVG)
+                .size(EXPAND_ICON_SIZE)
+                .rotation(if expanded { 90.0 } else { 0.0 });
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use tessera_ui::Px;
+
+    use super::{
+        TreeKey, TreeMove, TreeNode, TreeSelectionMode, TreeViewController, drop_target,
+        visible_nodes,
+    };
+
+    fn tree() -> Vec<TreeNode> {
+        vec![
+            TreeNode::branch(
+                1,
+                "a",
+                vec![TreeNode::leaf(2, "a1"), TreeNode::leaf(3, "a2")],
+            ),
+            TreeNode::unloaded(4, "b"),
+            TreeNode::leaf(5, "c"),
+        ]
+    }
+
+    #[test]
+    fn arrow_keys_expand_collapse_and_move_focus() {
+        let roots = tree();
+        let mode = TreeSelectionMode::Single;
+        let mut controller = TreeViewController::new();
+        let visible = visible_nodes(&roots, &controller.expanded);
+        controller.handle_key(TreeKey::Down, &visible, mode, false, false);
+        assert_eq!(controller.focused, Some(1));
+
+        controller.handle_key(TreeKey::Right, &visible, mode, false, false);
+        assert!(controller.is_expanded(1));
+        let visible = visible_nodes(&roots, &controller.expanded);
+        controller.handle_key(TreeKey::Right, &visible, mode, false, false);
+        assert_eq!(controller.focused, Some(2));
+        controller.handle_key(TreeKey::Down, &visible, mode, true, false);
+        assert_eq!(controller.selected_keys(), vec![3]);
+
+        controller.handle_key(TreeKey::Left, &visible, mode, false, false);
+        assert_eq!(controller.focused, Some(1));
+        controller.handle_key(TreeKey::Left, &visible, mode, false, false);
+        assert!(!controller.is_expanded(1));
+
+        let visible = visible_nodes(&roots, &controller.expanded);
+        controller.handle_key(TreeKey::Down, &visible, mode, false, false);
+        let outcome = controller
+            .handle_key(TreeKey::Right, &visible, mode, false, false)
+            .expect("unloaded node should expand");
+        assert_eq!(outcome.load, Some(4));
+    }
+
+    #[test]
+    fn dropped_node_moves_among_target_siblings() {
+        let roots = tree();
+        let expanded = HashSet::from([1]);
+        let visible = visible_nodes(&roots, &expanded);
+        let row = Px(40);
+        assert_eq!(
+            drop_target(&visible, 1, Px(45), row),
+            Some(TreeMove {
+                key: 2,
+                parent: Some(1),
+                index: 1,
+            })
+        );
+        assert_eq!(
+            drop_target(&visible, 4, Px(-70), row),
+            Some(TreeMove {
+                key: 5,
+                parent: Some(1),
+                index: 1,
+            })
+        );
+        assert_eq!(drop_target(&visible, 0, Px(80), row), None);
+    }
+}
@@ -0,0 +1,790 @@
+//! Video playback on imported GPU textures.
+//!
+//! ## Usage
+//!
+//! Play clips, tutorials and recordings decoded by a pluggable backend.
+use std::{
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use parking_lot::{Condvar, Mutex, RwLock};
+use tessera_ui::{
+    Color, ComputedData, Dp, FrameNanosControl, LayoutResult, MeasurementError, Modifier,
+    PipelineContext, Px, PxRect, PxSize, State,
+    layout::{LayoutPolicy, MeasureScope, RenderInput, RenderPolicy, layout},
+    receive_frame_nanos, remember,
+    renderer::{
+        ExternalTextureRegistry, ImportedTexture,
+        drawer::{TextureAlphaMode, TextureCommand},
+    },
+    tessera, use_context, wgpu,
+};
+use thiserror::Error;
+
+use crate::{
+    alignment::CrossAxisAlignment, column::column, icon_button::icon_button,
+    material_icons::filled, modifier::ModifierExt as _, row::row, slider::slider, text::text,
+    theme::MaterialTheme,
+};
+
+const DEFAULT_VIDEO_WIDTH: Dp = Dp(320.0);
+const DEFAULT_ASPECT_RATIO: f32 = 16.0 / 9.0;
+const CONTROLS_SPACING: Dp = Dp(8.0);
+/// Recycled upload textures kept per player; decoders rarely run more than a
+/// couple of frames ahead of the renderer.
+const MAX_POOLED_TEXTURES: usize = 4;
+
+/// Renderer handles captured by [`install_renderer`], replaced whenever the
+/// renderer is recreated.
+static RENDERER: RwLock<Option<VideoRenderer>> = RwLock::new(None);
+
+#[derive(Clone)]
+struct VideoRenderer {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    textures: ExternalTextureRegistry,
+}
+
+pub(crate) fn install_renderer(context: &PipelineContext<'_>) {
+    let resources = context.resources();
+    *RENDERER.write() = Some(VideoRenderer {
+        device: resources.device.clone(),
+        queue: resources.queue.clone(),
+        textures: context.external_textures(),
+    });
+}
+
+/// Returns the renderer's device and queue, or `None` before the component
+/// pipelines are registered.
+///
+/// Hardware decoders create their [`VideoFrameData::Texture`] frames on this
+/// device.
+pub fn render_device() -> Option<(wgpu::Device, wgpu::Queue)> {
+    RENDERER
+        .read()
+        .as_ref()
+        .map(|renderer| (renderer.device.clone(), renderer.queue.clone()))
+}
+
+/// Errors reported by a [`VideoDecoder`] or while presenting its frames.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum VideoError {
+    /// The backend failed to open, decode or seek the stream.
+    #[error("video decoding failed: {0}")]
+    Decode(String),
+    /// An RGBA frame's pixel buffer does not match its dimensions.
+    #[error("frame of {width}x{height} expects {expected} bytes, got {actual}")]
+    InvalidFrame {
+        /// Frame width in pixels.
+        width: u32,
+        /// Frame height in pixels.
+        height: u32,
+        /// Expected buffer length.
+        expected: usize,
+        /// Actual buffer length.
+        actual: usize,
+    },
+    /// Frames arrived before the component pipelines were registered.
+    #[error("renderer is not available; register the component pipelines first")]
+    RendererUnavailable,
+}
+
+/// Pixel data of a decoded [`VideoFrame`].
+pub enum VideoFrameData {
+    /// Tightly packed 8-bit sRGB RGBA pixels, uploaded by the player.
+    Rgba {
+        /// Frame width in pixels.
+        width: u32,
+        /// Frame height in pixels.
+        height: u32,
+        /// `width * height * 4` bytes, row by row.
+        pixels: Vec<u8>,
+    },
+    /// A texture already on the GPU, created on [`render_device`] with
+    /// [`wgpu::TextureUsages::TEXTURE_BINDING`] and a filterable float format.
+    Texture(wgpu::Texture),
+}
+
+/// A decoded frame and the media time at which it is shown.
+pub struct VideoFrame {
+    /// Presentation timestamp from the start of the stream.
+    pub timestamp: Duration,
+    /// Pixel data.
+    pub data: VideoFrameData,
+}
+
+/// Backend that demuxes and decodes a video stream, e.g. GStreamer, FFmpeg or
+/// a platform media framework.
+///
+/// The player calls the decoder from its own thread and paces frames by their
+/// timestamps. Backends with an audio track play it themselves and follow
+/// [`set_playing`](Self::set_playing) and [`set_volume`](Self::set_volume).
+pub trait VideoDecoder: Send + 'static {
+    /// Returns the stream duration, if known.
+    fn duration(&self) -> Option<Duration>;
+
+    /// Decodes the next frame in presentation order, or returns `Ok(None)` at
+    /// the end of the stream.
+    fn next_frame(&mut self) -> Result<Option<VideoFrame>, VideoError>;
+
+    /// Moves to `position`; the next decoded frame is the first one shown at or
+    /// after it.
+    fn seek(&mut self, position: Duration) -> Result<(), VideoError>;
+
+    /// Starts or pauses audio output.
+    fn set_playing(&mut self, _playing: bool) {}
+
+    /// Sets the audio volume between 0.0 and 1.0.
+    fn set_volume(&mut self, _volume: f32) {}
+}
+
+struct Playback {
+    playing: bool,
+    ended: bool,
+    /// Set until the decoder thread presents a frame for the current position,
+    /// so a paused player still shows the first frame and seek results.
+    awaiting_frame: bool,
+    seek: Option<Duration>,
+    position: Duration,
+    duration: Option<Duration>,
+    volume: f32,
+    muted: bool,
+    texture: Option<ImportedTexture>,
+    frame_size: Option<PxSize>,
+    error: Option<VideoError>,
+    revision: u64,
+    shutdown: bool,
+}
+
+struct Shared {
+    playback: Mutex<Playback>,
+    wake: Condvar,
+}
+
+impl Shared {
+    fn update(&self, f: impl FnOnce(&mut Playback)) {
+        let mut playback = self.playback.lock();
+        f(&mut playback);
+        playback.revision = playback.revision.wrapping_add(1);
+        drop(playback);
+        self.wake.notify_all();
+    }
+
+    fn fail(&self, error: VideoError) {
+        tracing::warn!("video playback stopped: {error}");
+        self.update(|playback| {
+            playback.playing = false;
+            playback.awaiting_frame = false;
+            playback.error = Some(error);
+        });
+    }
+}
+
+/// Playback state of a [`video`], driving a [`VideoDecoder`] on a background
+/// thread.
+///
+/// The thread stops when the controller is dropped.
+pub struct VideoController {
+    shared: Arc<Shared>,
+    /// Revision the component last rendered, to rebuild on new frames.
+    revision: u64,
+}
+
+impl VideoController {
+    /// Starts a paused player that shows the first frame of `decoder`.
+    pub fn new(decoder: impl VideoDecoder) -> Self {
+        let shared = Arc::new(Shared {
+            playback: Mutex::new(Playback {
+                playing: false,
+                ended: false,
+                awaiting_frame: true,
+                seek: None,
+                position: Duration::ZERO,
+                duration: decoder.duration(),
+                volume: 1.0,
+                muted: false,
+                texture: None,
+                frame_size: None,
+                error: None,
+                revision: 0,
+                shutdown: false,
+            }),
+            wake: Condvar::new(),
+        });
+        let worker = shared.clone();
+        thread::Builder::new()
+            .name("tessera-video".to_string())
+            .spawn(move || run_decoder(decoder, worker))
+            .expect("failed to spawn the video decoder thread");
+        Self {
+            shared,
+            revision: 0,
+        }
+    }
+
+    /// Starts playback, from the beginning if the video has ended.
+    pub fn play(&mut self) {
+        self.shared.update(|playback| {
+            if playback.ended {
+                playback.ended = false;
+                playback.seek = Some(Duration::ZERO);
+                playback.position = Duration::ZERO;
+            }
+            playback.playing = true;
+            playback.error = None;
+        });
+    }
+
+    /// Pauses playback on the current frame.
+    pub fn pause(&mut self) {
+        self.shared.update(|playback| playback.playing = false);
+    }
+
+    /// Plays if paused and pauses if playing.
+    pub fn toggle_playback(&mut self) {
+        if self.is_playing() {
+            self.pause();
+        } else {
+            self.play();
+        }
+    }
+
+    /// Returns whether the video is playing.
+    pub fn is_playing(&self) -> bool {
+        self.shared.playback.lock().playing
+    }
+
+    /// Returns whether playback reached the end of the stream.
+    pub fn has_ended(&self) -> bool {
+        self.shared.playback.lock().ended
+    }
+
+    /// Jumps to `position`, clamped to the duration when it is known.
+    pub fn seek(&mut self, position: Duration) {
+        self.shared.update(|playback| {
+            let position = playback
+                .duration
+                .map_or(position, |duration| position.min(duration));
+            playback.seek = Some(position);
+            playback.position = position;
+            playback.ended = false;
+            playback.awaiting_frame = true;
+        });
+    }
+
+    /// Returns the timestamp of the displayed frame.
+    pub fn position(&self) -> Duration {
+        self.shared.playback.lock().position
+    }
+
+    /// Returns the stream duration, if the decoder knows it.
+    pub fn duration(&self) -> Option<Duration> {
+        self.shared.playback.lock().duration
+    }
+
+    /// Sets the audio volume between 0.0 and 1.0.
+    pub fn set_volume(&mut self, volume: f32) {
+        self.shared
+            .update(|playback| playback.volume = volume.clamp(0.0, 1.0));
+    }
+
+    /// Returns the audio volume.
+    pub fn volume(&self) -> f32 {
+        self.shared.playback.lock().volume
+    }
+
+    /// Mutes or unmutes audio without changing the volume.
+    pub fn set_muted(&mut self, muted: bool) {
+        self.shared.update(|playback| playback.muted = muted);
+    }
+
+    /// Returns whether audio is muted.
+    pub fn is_muted(&self) -> bool {
+        self.shared.playback.lock().muted
+    }
+
+    /// Returns the size of the decoded frames, once the first one is shown.
+    pub fn video_size(&self) -> Option<PxSize> {
+        self.shared.playback.lock().frame_size
+    }
+
+    /// Returns the error that stopped playback, if any.
+    pub fn error(&self) -> Option<VideoError> {
+        self.shared.playback.lock().error.clone()
+    }
+
+    fn texture(&self) -> Option<ImportedTexture> {
+        self.shared.playback.lock().texture.clone()
+    }
+
+    fn is_active(&self) -> bool {
+        let playback = self.shared.playback.lock();
+        playback.playing || playback.awaiting_frame
+    }
+
+    fn is_stale(&self) -> bool {
+        self.shared.playback.lock().revision != self.revision
+    }
+
+    fn sync(&mut self) {
+        self.revision = self.shared.playback.lock().revision;
+    }
+}
+
+impl Drop for VideoController {
+    fn drop(&mut self) {
+        self.shared.update(|playback| playback.shutdown = true);
+    }
+}
+
+/// Uploads frames into one imported texture, recycling the textures the
+/// renderer has released.
+#[derive(Default)]
+pub(crate) struct FrameUploader {
+    texture: Option<ImportedTexture>,
+    pool: Arc<Mutex<Vec<wgpu::Texture>>>,
+}
+
+impl FrameUploader {
+    pub(crate) fn present(
+        &mut self,
+        data: VideoFrameData,
+    ) -> Result<(ImportedTexture, PxSize), VideoError> {
+        let renderer = RENDERER
+            .read()
+            .clone()
+            .ok_or(VideoError::RendererUnavailable)?;
+        let (texture, recycle) = match data {
+            VideoFrameData::Rgba {
+                width,
+                height,
+                pixels,
+            } => (self.upload(&renderer, width, height, &pixels)?, true),
+            VideoFrameData::Texture(texture) => (texture, false),
+        };
+        let size = PxSize::new(
+            Px::new(texture.width() as i32),
+            Px::new(texture.height() as i32),
+        );
+        let pool = self.pool.clone();
+        let released = texture.clone();
+        let on_release = move || {
+            let mut pool = pool.lock();
+            if recycle && pool.len() < MAX_POOLED_TEXTURES {
+                pool.push(released);
+            }
+        };
+        match &self.texture {
+            Some(imported) => imported.replace_with_release(texture, on_release),
+            None => {
+                self.texture = Some(renderer.textures.import_with_release(texture, on_release));
+            }
+        }
+        let imported = self.texture.clone().expect("texture was just imported");
+        Ok((imported, size))
+    }
+
+    fn upload(
+        &self,
+        renderer: &VideoRenderer,
+        width: u32,
+        height: u32,
+        pixels: &[u8],
+    ) -> Result<wgpu::Texture, VideoError> {
+        let expected = width as usize * height as usize * 4;
+        if pixels.len() != expected || expected == 0 {
+            return Err(VideoError::InvalidFrame {
+                width,
+                height,
+                expected,
+                actual: pixels.len(),
+            });
+        }
+        let size = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+        let texture = {
+            let mut pool = self.pool.lock();
+            pool.retain(|texture| texture.width() == width && texture.height() == height);
+            pool.pop()
+        }
+        .unwrap_or_else(|| {
+            renderer.device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("video_frame"),
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[],
+            })
+        });
+        renderer.queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            pixels,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * width),
+                rows_per_image: Some(height),
+            },
+            size,
+        );
+        Ok(texture)
+    }
+}
+
+fn run_decoder(mut decoder: impl VideoDecoder, shared: Arc<Shared>) {
+    let mut uploader = FrameUploader::default();
+    let mut audio_playing = false;
+    let mut audio_volume = None;
+    // Wall time at which the frame with the given timestamp is due.
+    let mut clock: Option<(Instant, Duration)> = None;
+    loop {
+        let mut playback = shared.playback.lock();
+        while !playback.shutdown
+            && !playback.playing
+            && !playback.awaiting_frame
+            && playback.seek.is_none()
+        {
+            shared.wake.wait(&mut playback);
+        }
+        if playback.shutdown {
+            return;
+        }
+        let playing = playback.playing;
+        let volume = if playback.muted { 0.0 } else { playback.volume };
+        let seek = playback.seek.take();
+        drop(playback);
+
+        if audio_volume != Some(volume) {
+            decoder.set_volume(volume);
+            audio_volume = Some(volume);
+        }
+        if let Some(position) = seek {
+            clock = None;
+            if let Err(error) = decoder.seek(position) {
+                shared.fail(error);
+                continue;
+            }
+        }
+        if audio_playing != playing {
+            decoder.set_playing(playing);
+            audio_playing = playing;
+            clock = None;
+        }
+
+        let frame = match decoder.next_frame() {
+            Ok(Some(frame)) => frame,
+            Ok(None) => {
+                if audio_playing {
+                    decoder.set_playing(false);
+                    audio_playing = false;
+                }
+                shared.update(|playback| {
+                    playback.playing = false;
+                    playback.awaiting_frame = false;
+                    playback.ended = playback.seek.is_none();
+                });
+                continue;
+            }
+            Err(error) => {
+                shared.fail(error);
+                continue;
+            }
+        };
+
+        if playing {
+            let (start, origin) = *clock.get_or_insert((Instant::now(), frame.timestamp));
+            let due = start + frame.timestamp.saturating_sub(origin);
+            let mut playback = shared.playback.lock();
+            while !playback.shutdown && playback.playing && playback.seek.is_none() {
+                let now = Instant::now();
+                if now >= due {
+                    break;
+                }
+                shared.wake.wait_for(&mut playback, due - now);
+            }
+            // The frame is stale once the user seeks away from it.
+            if playback.shutdown || playback.seek.is_some() {
+                continue;
+            }
+        }
+
+        match uploader.present(frame.data) {
+            Ok((texture, size)) => shared.update(|playback| {
+                if playback.seek.is_none() {
+                    playback.position = frame.timestamp;
+                    playback.awaiting_frame = false;
+                }
+                playback.frame_size = Some(size);
+                playback.texture.get_or_insert(texture);
+            }),
+            Err(error) => shared.fail(error),
+        }
+    }
+}
+
+/// Largest rect with the aspect ratio of `content`, centered in `container`.
+fn fit_rect(container: ComputedData, content: PxSize) -> PxRect {
+    let (container_width, container_height) = (container.width.to_f32(), container.height.to_f32());
+    let (content_width, content_height) = (content.width.to_f32(), content.height.to_f32());
+    if content_width <= 0.0 || content_height <= 0.0 {
+        return PxRect::new(Px::ZERO, Px::ZERO, container.width, container.height);
+    }
+    let scale = (container_width / content_width).min(container_height / content_height);
+    let width = content_width * scale;
+    let height = content_height * scale;
+    PxRect::new(
+        Px::saturating_from_f32(((container_width - width) / 2.0).round()),
+        Px::saturating_from_f32(((container_height - height) / 2.0).round()),
+        Px::saturating_from_f32(width.round()),
+        Px::saturating_from_f32(height.round()),
+    )
+}
+
+fn format_time(time: Duration) -> String {
+    let seconds = time.as_secs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct VideoSurfaceLayout {
+    texture: Option<ImportedTexture>,
+    frame_size: Option<PxSize>,
+}
+
+impl LayoutPolicy for VideoSurfaceLayout {
+    fn measure(&self, input: &MeasureScope<'_>) -> Result<LayoutResult, MeasurementError> {
+        let parent = input.parent_constraint();
+        let aspect_ratio = self
+            .frame_size
+            .filter(|size| size.width > Px::ZERO && size.height > Px::ZERO)
+            .map_or(DEFAULT_ASPECT_RATIO, |size| {
+                size.width.to_f32() / size.height.to_f32()
+            });
+        let width = parent.width();
+        let width = width.clamp(width.max.unwrap_or(DEFAULT_VIDEO_WIDTH.to_px()));
+        let height = parent
+            .height()
+            .clamp(Px::saturating_from_f32(width.to_f32() / aspect_ratio));
+        Ok(LayoutResult::new(ComputedData { width, height }))
+    }
+}
+
+impl RenderPolicy for VideoSurfaceLayout {
+    fn record(&self, input: &mut RenderInput<'_>) {
+        let (Some(texture), Some(frame_size)) = (&self.texture, self.frame_size) else {
+            return;
+        };
+        let mut metadata = input.metadata_mut();
+        let Some(size) = metadata.computed_data() else {
+            return;
+        };
+        let bounds = fit_rect(size, frame_size);
+        let command =
+            TextureCommand::new(texture.clone()).with_alpha_mode(TextureAlphaMode::Opaque);
+        metadata
+            .fragment_mut()
+            .push_draw_command_in(command, bounds);
+    }
+}
+
+/// # video
+///
+/// Plays a video decoded by a [`VideoDecoder`], letterboxed to keep its aspect
+/// ratio, with play/pause, seek and mute controls.
+///
+/// ## Usage
+///
+/// Play tutorials, clips and screen recordings inside an app.
+///
+/// ## Parameters
+///
+/// - `controller` — playback state created from a decoder backend.
+/// - `show_controls` — optional toggle for the control bar; shown by default.
+/// - `modifier` — optional modifier chain applied to the player.
+///
+/// ## Examples
+///
+/// ```
+/// # use std::time::Duration;
+/// # use tessera_ui::{remember, tessera};
+/// use tessera_components::video::{VideoController, VideoDecoder, VideoError, VideoFrame, video};
+/// # use tessera_components::theme::{MaterialTheme, material_theme};
+///
+/// struct Silence;
+///
+/// impl VideoDecoder for Silence {
+///     fn duration(&self) -> Option<Duration> {
+///         Some(Duration::ZERO)
+///     }
+///
+///     fn next_frame(&mut self) -> Result<Option<VideoFrame>, VideoError> {
+///         Ok(None)
+///     }
+///
+///     fn seek(&mut self, _position: Duration) -> Result<(), VideoError> {
+///         Ok(())
+///     }
+/// }
+///
+/// # #[tessera]
+/// # fn component() {
+/// # material_theme()
+/// #     .theme(|| MaterialTheme::default())
+/// #     .child(|| {
+/// let controller = remember(|| VideoController::new(Silence));
+/// video(controller);
+/// # });
+/// # }
+/// # component();
+/// ```
+#[tessera]
+pub fn video(
+    controller: State<VideoController>,
+    show_controls: Option<bool>,
+    modifier: Option<Modifier>,
+) {
+    let show_controls = show_controls.unwrap_or(true);
+    if controller.with(VideoController::is_active) {
+        receive_frame_nanos(move |_| {
+            let active = controller.with(VideoController::is_active);
+            if controller.with(VideoController::is_stale) {
+                controller.with_mut(VideoController::sync);
+            }
+            if active {
+                FrameNanosControl::Continue
+            } else {
+                FrameNanosControl::Stop
+            }
+        });
+    }
+
+    let surface = VideoSurfaceLayout {
+        texture: controller.with(VideoController::texture),
+        frame_size: controller.with(VideoController::video_size),
+    };
+    column()
+        .modifier(modifier.unwrap_or_default())
+        .cross_axis_alignment(CrossAxisAlignment::Stretch)
+        .children(move || {
+            layout()
+                .modifier(Modifier::new().background(Color::BLACK).clip_to_bounds())
+                .layout_policy(surface.clone())
+                .render_policy(surface.clone());
+            if show_controls {
+                video_controls(controller);
+            }
+        });
+}
+
+#[tessera]
+fn video_controls(controller: State<VideoController>) {
+    let theme = use_context::<MaterialTheme>()
+        .expect("MaterialTheme must be provided")
+        .get();
+    let label_style = theme.typography.label_medium;
+    let label_color = theme.color_scheme.on_surface_variant;
+    let (playing, muted, position, duration) = controller.with(|controller| {
+        (
+            controller.is_playing(),
+            controller.is_muted(),
+            controller.position(),
+            controller.duration(),
+        )
+    });
+    let progress = duration
+        .filter(|duration| !duration.is_zero())
+        .map_or(0.0, |duration| {
+            position.as_secs_f32() / duration.as_secs_f32()
+        });
+    let time = match duration {
+        Some(duration) => format!("{} / {}", format_time(position), format_time(duration)),
+        None => format_time(position),
+    };
+
+    row()
+        .modifier(Modifier::new().padding_symmetric(CONTROLS_SPACING, Dp::ZERO))
+        .cross_axis_alignment(CrossAxisAlignment::Center)
+        .children(move || {
+            icon_button()
+                .icon(if playing {
+                    filled::PAUSE_SVG
+                } else {
+                    filled::PLAY_ARROW_SVG
+                })
+                .on_click(move || controller.with_mut(VideoController::toggle_playback));
+            slider()
+                .modifier(Modifier::new().weight(1.0))
+                .value(progress.clamp(0.0, 1.0))
+                .disabled(duration.is_none())
+                .accessibility_label("Seek")
+                .on_change(move |fraction: f32| {
+                    if let Some(duration) = duration {
+                        controller.with_mut(|controller| {
+                            controller.seek(duration.mul_f32(fraction));
+                        });
+                    }
+                });
+            text()
+                .modifier(Modifier::new().padding_symmetric(CONTROLS_SPACING, Dp::ZERO))
+                .content(time.clone())
+                .style(label_style)
+                .color(label_color);
+            icon_button()
+                .icon(if muted {
+                    filled::VOLUME_OFF_SVG
+                } else {
+                    filled::VOLUME_UP_SVG
+                })
+                .on_click(move || {
+                    controller.with_mut(|controller| controller.set_muted(!muted));
+                });
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_rect_letterboxes_and_pillarboxes() {
+        let container = ComputedData {
+            width: Px::new(400),
+            height: Px::new(400),
+        };
+        let wide = fit_rect(container, PxSize::new(Px::new(1920), Px::new(1080)));
+        assert_eq!(
+            wide,
+            PxRect::new(Px::ZERO, Px::new(88), Px::new(400), Px::new(225))
+        );
+
+        let tall = fit_rect(container, PxSize::new(Px::new(100), Px::new(200)));
+        assert_eq!(
+            tall,
+            PxRect::new(Px::new(100), Px::ZERO, Px::new(200), Px::new(400))
+        );
+    }
+
+    #[test]
+    fn format_time_adds_hours_only_when_needed() {
+        assert_eq!(format_time(Duration::from_secs(5)), "0:05");
+        assert_eq!(format_time(Duration::from_millis(754_900)), "12:34");
+        assert_eq!(format_time(Duration::from_secs(3_723)), "1:02:03");
+    }
+}
@@ -0,0 +1,679 @@
+//! Zoomable image viewer with pinch, pan, fling and double-tap zoom.
+//!
+//! ## Usage
+//!
+//! Let users inspect photos, maps and diagrams in galleries and detail views.
+use std::time::Duration;
+
+use tessera_ui::{
+    Color, ComputedData, FlingDecay, FrameNanosControl, LayoutResult, MeasurementError, Modifier,
+    PointerInput, PointerInputModifierNode, Px, PxRect, PxSize, State, VelocityTracker,
+    gesture::{Gesture, GestureDetector, GestureSettings},
+    layout::{LayoutPolicy, MeasureScope, RenderInput, RenderPolicy, layout},
+    modifier::ModifierCapabilityExt as _,
+    receive_frame_nanos, remember, rubber_band, tessera,
+    time::{self, Instant},
+};
+
+use crate::{
+    image::placeholder_image_data,
+    image_vector::TintMode,
+    modifier::ModifierExt as _,
+    painter::Painter,
+    pipelines::{image::command::ImageCommand, image_vector::command::ImageVectorCommand},
+};
+
+const MIN_SCALE: f32 = 1.0;
+const DEFAULT_MAX_SCALE: f32 = 4.0;
+const DEFAULT_DOUBLE_TAP_SCALE: f32 = 2.5;
+const DOUBLE_TAP_TIMEOUT: Duration = Duration::from_millis(300);
+// Exponent applied to pinch factors beyond the scale limits.
+const ZOOM_RESISTANCE: f32 = 0.3;
+const SETTLE_DURATION_NANOS: u64 = 250_000_000;
+
+/// Scale, offset and rotation of the image inside the viewer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ZoomTransform {
+    scale: f32,
+    offset_x: f32,
+    offset_y: f32,
+    // Radians, clockwise.
+    rotation: f32,
+}
+
+impl Default for ZoomTransform {
+    fn default() -> Self {
+        Self {
+            scale: MIN_SCALE,
+            offset_x: 0.0,
+            offset_y: 0.0,
+            rotation: 0.0,
+        }
+    }
+}
+
+impl ZoomTransform {
+    /// Scales by `factor` while keeping the content under `focus` in place.
+    ///
+    /// `focus` is relative to the viewport center.
+    fn zoom_about(&mut self, factor: f32, focus: (f32, f32)) {
+        self.scale *= factor;
+        self.offset_x = focus.0 - (focus.0 - self.offset_x) * factor;
+        self.offset_y = focus.1 - (focus.1 - self.offset_y) * factor;
+    }
+
+    /// Rotates clockwise by `radians` around `focus`, relative to the viewport
+    /// center.
+    fn rotate_about(&mut self, radians: f32, focus: (f32, f32)) {
+        let (sin, cos) = radians.sin_cos();
+        let (x, y) = (self.offset_x - focus.0, self.offset_y - focus.1);
+        self.offset_x = focus.0 + x * cos - y * sin;
+        self.offset_y = focus.1 + x * sin + y * cos;
+        self.rotation += radians;
+    }
+
+    fn lerp(self, to: Self, fraction: f32) -> Self {
+        let mix = |from: f32, to: f32| from + (to - from) * fraction;
+        Self {
+            scale: mix(self.scale, to.scale),
+            offset_x: mix(self.offset_x, to.offset_x),
+            offset_y: mix(self.offset_y, to.offset_y),
+            rotation: mix(self.rotation, to.rotation),
+        }
+    }
+}
+
+/// Viewport size and the size of the image fitted inside it at scale 1.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ZoomGeometry {
+    viewport: (f32, f32),
+    content: (f32, f32),
+}
+
+impl ZoomGeometry {
+    fn new(viewport: PxSize, intrinsic: PxSize) -> Self {
+        let viewport = (viewport.width.to_f32(), viewport.height.to_f32());
+        let (width, height) = (intrinsic.width.to_f32(), intrinsic.height.to_f32());
+        let content = if width <= 0.0 || height <= 0.0 {
+            viewport
+        } else {
+            let fit = (viewport.0 / width).min(viewport.1 / height);
+            (width * fit, height * fit)
+        };
+        Self { viewport, content }
+    }
+
+    /// Maximum offset on each axis before the rotated content uncovers the
+    /// viewport edge it overflows.
+    fn limits(&self, transform: &ZoomTransform) -> (f32, f32) {
+        let (sin, cos) = transform.rotation.sin_cos();
+        let (sin, cos) = (sin.abs(), cos.abs());
+        let (width, height) = self.content;
+        let extent_x = transform.scale * (width * cos + height * sin);
+        let extent_y = transform.scale * (width * sin + height * cos);
+        (
+            ((extent_x - self.viewport.0) / 2.0).max(0.0),
+            ((extent_y - self.viewport.1) / 2.0).max(0.0),
+        )
+    }
+
+    fn is_in_bounds(&self, transform: &ZoomTransform, max_scale: f32) -> bool {
+        let (limit_x, limit_y) = self.limits(transform);
+        (MIN_SCALE..=max_scale).contains(&transform.scale)
+            && transform.offset_x.abs() <= limit_x + 0.5
+            && transform.offset_y.abs() <= limit_y + 0.5
+    }
+
+    /// Pulls `transform` back inside the scale limits, zooming about `focus`,
+    /// and then inside the pan limits.
+    fn settle(
+        &self,
+        mut transform: ZoomTransform,
+        focus: (f32, f32),
+        max_scale: f32,
+    ) -> ZoomTransform {
+        let scale = transform.scale.clamp(MIN_SCALE, max_scale);
+        transform.zoom_about(scale / transform.scale, focus);
+        transform.scale = scale;
+        let (limit_x, limit_y) = self.limits(&transform);
+        transform.offset_x = transform.offset_x.clamp(-limit_x, limit_x);
+        transform.offset_y = transform.offset_y.clamp(-limit_y, limit_y);
+        transform
+    }
+
+    /// Damps the parts of the offsets that pan past the limits.
+    fn rubber_banded(&self, mut transform: ZoomTransform) -> ZoomTransform {
+        let (limit_x, limit_y) = self.limits(&transform);
+        let band = |offset: f32, limit: f32, extent: f32| {
+            let edge = offset.clamp(-limit, limit);
+            edge + rubber_band(offset - edge, extent)
+        };
+        transform.offset_x = band(transform.offset_x, limit_x, self.viewport.0);
+        transform.offset_y = band(transform.offset_y, limit_y, self.viewport.1);
+        transform
+    }
+
+    fn to_center(&self, x: Px, y: Px) -> (f32, f32) {
+        (
+            x.to_f32() - self.viewport.0 / 2.0,
+            y.to_f32() - self.viewport.1 / 2.0,
+        )
+    }
+}
+
+/// Scale reached by pinching `scale` by `factor`, resisted beyond the limits.
+fn resisted_scale(scale: f32, factor: f32, max_scale: f32) -> f32 {
+    let next = scale * factor;
+    if (next > max_scale && factor > 1.0) || (next < MIN_SCALE && factor < 1.0) {
+        scale * factor.powf(ZOOM_RESISTANCE)
+    } else {
+        next
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ZoomAnimation {
+    Settle {
+        from: ZoomTransform,
+        to: ZoomTransform,
+        start_nanos: Option<u64>,
+    },
+    Fling {
+        decay: FlingDecay,
+        limits: (f32, f32),
+        last_nanos: Option<u64>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ZoomConfig {
+    max_scale: f32,
+    double_tap_scale: f32,
+    rotation_enabled: bool,
+}
+
+/// Zoom state of a [`zoomable_image`], shared so apps can read or reset it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ZoomableImageController {
+    transform: ZoomTransform,
+    // Offsets before rubber banding, while a gesture is in progress.
+    raw: Option<ZoomTransform>,
+    animation: Option<ZoomAnimation>,
+    velocity: VelocityTracker,
+    focus: (f32, f32),
+}
+
+impl ZoomableImageController {
+    /// Creates a controller showing the whole image.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current zoom relative to the image fitted inside the viewer.
+    pub fn scale(&self) -> f32 {
+        self.transform.scale
+    }
+
+    /// Offset of the image center from the viewer center, in pixels.
+    pub fn offset(&self) -> (f32, f32) {
+        (self.transform.offset_x, self.transform.offset_y)
+    }
+
+    /// Clockwise rotation in degrees.
+    pub fn rotation(&self) -> f32 {
+        self.transform.rotation.to_degrees()
+    }
+
+    /// Returns `true` when the image is zoomed in past its fitted size.
+    pub fn is_zoomed(&self) -> bool {
+        self.transform.scale > MIN_SCALE + f32::EPSILON
+    }
+
+    /// Animates back to the whole, unrotated image.
+    pub fn reset(&mut self) {
+        self.animate_to(ZoomTransform::default());
+    }
+
+    fn animate_to(&mut self, to: ZoomTransform) {
+        self.animation = Some(ZoomAnimation::Settle {
+            from: self.transform,
+            to,
+            start_nanos: None,
+        });
+    }
+
+    fn is_animating(&self) -> bool {
+        self.animation.is_some()
+    }
+
+    fn handle_gesture(
+        &mut self,
+        gesture: Gesture,
+        geometry: ZoomGeometry,
+        config: ZoomConfig,
+        now: Instant,
+    ) {
+        match gesture {
+            Gesture::PanStart { .. } => {
+                self.begin_gesture();
+                self.velocity.add_delta(now, 0.0, 0.0);
+            }
+            Gesture::Pan { delta_x, delta_y } => {
+                let (delta_x, delta_y) = (delta_x.to_f32(), delta_y.to_f32());
+                self.update_gesture(geometry, |raw| {
+                    raw.offset_x += delta_x;
+                    raw.offset_y += delta_y;
+                });
+                self.velocity.add_delta(now, delta_x, delta_y);
+            }
+            Gesture::PanEnd => {
+                self.raw = None;
+                let velocity = self.velocity.fling_velocity(now);
+                self.velocity.reset();
+                if !geometry.is_in_bounds(&self.transform, config.max_scale) {
+                    self.settle(geometry, config);
+                } else if let Some((velocity_x, velocity_y)) = velocity {
+                    self.animation = Some(ZoomAnimation::Fling {
+                        decay: FlingDecay::new(velocity_x, velocity_y),
+                        limits: geometry.limits(&self.transform),
+                        last_nanos: None,
+                    });
+                }
+            }
+            Gesture::TransformStart { centroid } => {
+                self.begin_gesture();
+                self.focus = geometry.to_center(centroid.x, centroid.y);
+            }
+            Gesture::Transform {
+                centroid,
+                pan_x,
+                pan_y,
+                zoom,
+                rotation,
+            } => {
+                let focus = geometry.to_center(centroid.x, centroid.y);
+                self.update_gesture(geometry, |raw| {
+                    let scale = resisted_scale(raw.scale, zoom, config.max_scale);
+                    raw.zoom_about(scale / raw.scale, focus);
+                    if config.rotation_enabled {
+                        raw.rotate_about(rotation, focus);
+                    }
+                    raw.offset_x += pan_x.to_f32();
+                    raw.offset_y += pan_y.to_f32();
+                });
+                self.focus = focus;
+            }
+            Gesture::TransformEnd => {
+                self.raw = None;
+                self.settle(geometry, config);
+            }
+            Gesture::DoubleTap { position } | Gesture::SmartMagnify { position } => {
+                if self.is_zoomed() {
+                    self.animate_to(ZoomTransform {
+                        rotation: self.transform.rotation,
+                        ..ZoomTransform::default()
+                    });
+                } else {
+                    let mut target = self.transform;
+                    let scale = config.double_tap_scale.clamp(MIN_SCALE, config.max_scale);
+                    target.zoom_about(
+                        scale / target.scale,
+                        geometry.to_center(position.x, position.y),
+                    );
+                    self.animate_to(geometry.settle(target, (0.0, 0.0), config.max_scale));
+                }
+            }
+            Gesture::Tap { .. } | Gesture::LongPress { .. } => {}
+        }
+    }
+
+    fn begin_gesture(&mut self) {
+        self.animation = None;
+        self.raw = None;
+        self.velocity.reset();
+    }
+
+    fn update_gesture(&mut self, geometry: ZoomGeometry, update: impl FnOnce(&mut ZoomTransform)) {
+        let mut raw = self.raw.unwrap_or(self.transform);
+        update(&mut raw);
+        self.transform = geometry.rubber_banded(raw);
+        self.raw = Some(raw);
+    }
+
+    fn settle(&mut self, geometry: ZoomGeometry, config: ZoomConfig) {
+        let target = geometry.settle(self.transform, self.focus, config.max_scale);
+        if target != self.transform {
+            self.animate_to(target);
+        }
+    }
+
+    fn tick(&mut self, frame_nanos: u64) {
+        let Some(animation) = self.animation.as_mut() else {
+            return;
+        };
+        match animation {
+            ZoomAnimation::Settle {
+                from,
+                to,
+                start_nanos,
+            } => {
+                let start = *start_nanos.get_or_insert(frame_nanos);
+                let progress = (frame_nanos.saturating_sub(start) as f32
+                    / SETTLE_DURATION_NANOS as f32)
+                    .min(1.0);
+                let eased = 1.0 - (1.0 - progress).powi(3);
+                self.transform = from.lerp(*to, eased);
+                if progress >= 1.0 {
+                    self.animation = None;
+                }
+            }
+            ZoomAnimation::Fling {
+                decay,
+                limits,
+                last_nanos,
+            } => {
+                let last = last_nanos.replace(frame_nanos).unwrap_or(frame_nanos);
+                let elapsed = frame_nanos.saturating_sub(last) as f32 / 1_000_000_000.0;
+                let (distance_x, distance_y) = decay.step(elapsed);
+                let transform = &mut self.transform;
+                let offset_x = transform.offset_x + distance_x;
+                transform.offset_x = offset_x.clamp(-limits.0, limits.0);
+                if transform.offset_x != offset_x {
+                    decay.stop_x();
+                }
+                let offset_y = transform.offset_y + distance_y;
+                transform.offset_y = offset_y.clamp(-limits.1, limits.1);
+                if transform.offset_y != offset_y {
+                    decay.stop_y();
+                }
+                if decay.is_finished() {
+                    self.animation = None;
+                }
+            }
+        }
+    }
+}
+
+struct ZoomableImagePointerModifierNode {
+    controller: State<ZoomableImageController>,
+    detector: State<GestureDetector>,
+    intrinsic_size: PxSize,
+    config: ZoomConfig,
+}
+
+impl PointerInputModifierNode for ZoomableImagePointerModifierNode {
+    fn on_pointer_input(&self, mut input: PointerInput<'_>) {
+        let gestures = self
+            .detector
+            .with_mut(|detector| detector.update(&mut input));
+        if gestures.is_empty() {
+            return;
+        }
+        let viewport = PxSize::new(input.computed_data.width, input.computed_data.height);
+        let geometry = ZoomGeometry::new(viewport, self.intrinsic_size);
+        let now = time::now();
+        self.controller.with_mut(|controller| {
+            for gesture in gestures {
+                controller.handle_gesture(gesture, geometry, self.config, now);
+            }
+        });
+    }
+}
+
+fn intrinsic_size(painter: &Painter) -> PxSize {
+    match painter {
+        Painter::Raster(data) => PxSize::new(Px(data.width as i32), Px(data.height as i32)),
+        Painter::Vector(data) => PxSize::new(
+            Px::saturating_from_f32(data.viewport_width.round()),
+            Px::saturating_from_f32(data.viewport_height.round()),
+        ),
+        Painter::Svg(data) => PxSize::new(
+            Px::saturating_from_f32(data.viewport_width.round()),
+            Px::saturating_from_f32(data.viewport_height.round()),
+        ),
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct ZoomableImageLayout {
+    painter: Painter,
+    transform: ZoomTransform,
+}
+
+impl LayoutPolicy for ZoomableImageLayout {
+    fn measure(&self, input: &MeasureScope<'_>) -> Result<LayoutResult, MeasurementError> {
+        let intrinsic = intrinsic_size(&self.painter);
+        let parent = input.parent_constraint();
+        let width = parent.width();
+        let height = parent.height();
+        Ok(LayoutResult::new(ComputedData {
+            width: width.clamp(width.max.unwrap_or(intrinsic.width)),
+            height: height.clamp(height.max.unwrap_or(intrinsic.height)),
+        }))
+    }
+}
+
+impl RenderPolicy for ZoomableImageLayout {
+    fn record(&self, input: &mut RenderInput<'_>) {
+        let mut metadata = input.metadata_mut();
+        let Some(viewport) = metadata.computed_data() else {
+            return;
+        };
+        let geometry = ZoomGeometry::new(
+            PxSize::new(viewport.width, viewport.height),
+            intrinsic_size(&self.painter),
+        );
+        let transform = self.transform;
+        let width = geometry.content.0 * transform.scale;
+        let height = geometry.content.1 * transform.scale;
+        let bounds = PxRect::new(
+            Px::saturating_from_f32(
+                (geometry.viewport.0 / 2.0 + transform.offset_x - width / 2.0).round(),
+            ),
+            Px::saturating_from_f32(
+                (geometry.viewport.1 / 2.0 + transform.offset_y - height / 2.0).round(),
+            ),
+            Px::saturating_from_f32(width.round()),
+            Px::saturating_from_f32(height.round()),
+        );
+        let rotation = transform.rotation.to_degrees();
+        let fragment = metadata.fragment_mut();
+        match &self.painter {
+            Painter::Raster(data) => {
+                let command = ImageCommand {
+                    data: data.clone(),
+                    opacity: 1.0,
+                    rotation,
+                };
+                fragment.push_draw_command_in(command, bounds);
+            }
+            Painter::Vector(data) => {
+                let command = ImageVectorCommand {
+                    data: data.clone(),
+                    tint: Color::WHITE,
+                    tint_mode: TintMode::Multiply,
+                    rotation,
+                };
+                fragment.push_draw_command_in(command, bounds);
+            }
+            Painter::Svg(data) => {
+                // Whole-number detail levels keep pinching from rasterizing
+                // every frame while staying sharp at the current zoom.
+                let detail = transform.scale.ceil().max(1.0);
+                let raster_size = PxSize::new(
+                    Px::saturating_from_f32((geometry.content.0 * detail).round()),
+                    Px::saturating_from_f32((geometry.content.1 * detail).round()),
+                );
+                let Some(raster) = data.rasterize(raster_size) else {
+                    return;
+                };
+                let command = ImageCommand {
+                    data: raster,
+                    opacity: 1.0,
+                    rotation,
+                };
+                fragment.push_draw_command_in(command, bounds);
+            }
+        }
+    }
+}
+
+/// # zoomable_image
+///
+/// Shows an image that can be pinch-zoomed, panned with fling, zoomed by
+/// double tap and optionally rotated with two fingers.
+///
+/// ## Usage
+///
+/// Inspect photos, maps and diagrams in gallery and detail screens.
+///
+/// ## Parameters
+///
+/// - `painter` — image content; set with the `painter` builder method.
+/// - `max_scale` — optional maximum zoom relative to the fitted image; defaults
+///   to 4.
+/// - `double_tap_scale` — optional zoom reached by double tapping; defaults to
+///   2.5.
+/// - `rotation_enabled` — optional toggle for two-finger rotation; off by
+///   default.
+/// - `controller` — optional shared state to read or reset the zoom.
+/// - `modifier` — optional modifier chain applied to the viewer.
+///
+/// ## Examples
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use tessera_components::{image::ImageData, zoomable_image::zoomable_image};
+/// # use tessera_components::theme::{MaterialTheme, material_theme};
+/// # use tessera_ui::tessera;
+///
+/// # #[tessera]
+/// # fn component() {
+/// # material_theme()
+/// #     .theme(|| MaterialTheme::default())
+/// #     .child(|| {
+/// let photo = ImageData {
+///     data: Arc::new(vec![255; 4 * 4 * 4]),
+///     width: 4,
+///     height: 4,
+/// };
+/// zoomable_image()
+///     .painter(photo)
+///     .max_scale(6.0)
+///     .rotation_enabled(true);
+/// # });
+/// # }
+/// # component();
+/// ```
+#[tessera]
+pub fn zoomable_image(
+    #[prop(skip_setter)] painter: Option<Painter>,
+    max_scale: Option<f32>,
+    double_tap_scale: Option<f32>,
+    rotation_enabled: Option<bool>,
+    controller: Option<State<ZoomableImageController>>,
+    modifier: Option<Modifier>,
+) {
+    let painter = painter.unwrap_or_else(|| Painter::Raster(placeholder_image_data()));
+    let config = ZoomConfig {
+        max_scale: max_scale.unwrap_or(DEFAULT_MAX_SCALE).max(MIN_SCALE),
+        double_tap_scale: double_tap_scale.unwrap_or(DEFAULT_DOUBLE_TAP_SCALE),
+        rotation_enabled: rotation_enabled.unwrap_or(false),
+    };
+    let controller = controller.unwrap_or_else(|| remember(ZoomableImageController::new));
+    let detector = remember(|| {
+        GestureDetector::new(GestureSettings {
+            double_tap_timeout: Some(DOUBLE_TAP_TIMEOUT),
+            long_press_threshold: None,
+            ..GestureSettings::default()
+        })
+    });
+
+    if controller.with(ZoomableImageController::is_animating) {
+        receive_frame_nanos(move |frame_nanos| {
+            let animating = controller.with_mut(|controller| {
+                controller.tick(frame_nanos);
+                controller.is_animating()
+            });
+            if animating {
+                FrameNanosControl::Continue
+            } else {
+                FrameNanosControl::Stop
+            }
+        });
+    }
+
+    let modifier = modifier
+        .unwrap_or_default()
+        .clip_to_bounds()
+        .push_pointer_input(ZoomableImagePointerModifierNode {
+            controller,
+            detector,
+            intrinsic_size: intrinsic_size(&painter),
+            config,
+        });
+    let policy = ZoomableImageLayout {
+        painter,
+        transform: controller.with(|controller| controller.transform),
+    };
+    layout()
+        .modifier(modifier)
+        .layout_policy(policy.clone())
+        .render_policy(policy);
+}
+
+impl ZoomableImageBuilder {
+    /// Sets the image content from a raster or vector painter.
+    pub fn painter(mut self, painter: impl Into<Painter>) -> Self {
+        self.props.painter = Some(painter.into());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rubber_banding_damps_only_the_overscroll() {
+        let geometry = ZoomGeometry::new(
+            PxSize::new(Px::new(400), Px::new(400)),
+            PxSize::new(Px::new(400), Px::new(400)),
+        );
+        let zoomed = ZoomTransform {
+            scale: 2.0,
+            offset_x: 150.0,
+            offset_y: -300.0,
+            rotation: 0.0,
+        };
+        let banded = geometry.rubber_banded(zoomed);
+        assert_eq!(banded.offset_x, 150.0);
+        assert!(banded.offset_y < -200.0 && banded.offset_y > -300.0);
+    }
+
+    #[test]
+    fn settle_keeps_focus_and_clamps_to_bounds() {
+        let geometry = ZoomGeometry::new(
+            PxSize::new(Px::new(400), Px::new(400)),
+            PxSize::new(Px::new(800), Px::new(400)),
+        );
+        assert_eq!(geometry.content, (400.0, 200.0));
+
+        let mut zoomed = ZoomTransform::default();
+        zoomed.zoom_about(2.0, (100.0, 0.0));
+        assert_eq!((zoomed.offset_x, zoomed.offset_y), (-100.0, 0.0));
+        assert_eq!(geometry.limits(&zoomed), (200.0, 0.0));
+
+        let overshot = ZoomTransform {
+            scale: 6.0,
+            offset_x: 2_000.0,
+            offset_y: -600.0,
+            rotation: 0.0,
+        };
+        let settled = geometry.settle(overshot, (0.0, 0.0), 4.0);
+        assert_eq!(settled.scale, 4.0);
+        assert_eq!(settled.offset_x, 600.0);
+        assert_eq!(settled.offset_y, -200.0);
+    }
+}
@@ -0,0 +1,19 @@
+//! Links `tessera-ui` as a shared library.
+//!
+//! Hot reloading loads a rebuilt copy of the app library into the running
+//! process. Both copies must use the same `tessera-ui` runtime, so apps depend
+//! on this crate behind a `hot-reload` feature that `cargo tessera dev`
+//! enables:
+//!
+//! ```toml
+//! [features]
+//! hot-reload = ["dep:tessera-dylib"]
+//!
+//! [dependencies]
+//! tessera-dylib = { version = "2", optional = true }
+//! ```
+//!
+//! Never enable it for release builds.
+
+#[allow(unused_imports)]
+use tessera_ui as _;
@@ -13,6 +13,5 @@
 )]
 
 pub mod alignment;
-pub mod gesture;
 pub mod modifier;
 pub mod shape_def;
@@ -10,11 +10,10 @@ use std::sync::{Arc, Mutex};
 use tessera_ui::{
     Callback, CallbackWith, FocusProperties, FocusRequester, Modifier, PointerInput,
     PointerInputModifierNode, Px, PxPosition, PxSize, State, accesskit,
+    gesture::{DragAxis, DragRecognizer, DragSettings, LongPressRecognizer, TapRecognizer},
     modifier::ModifierCapabilityExt as _,
 };
 
-use crate::gesture::{DragAxis, DragRecognizer, DragSettings, LongPressRecognizer, TapRecognizer};
-
 /// Context for pointer press/release callbacks.
 #[derive(Clone, PartialEq, Copy, Debug)]
 pub struct PointerEventContext {
@@ -322,7 +321,7 @@ struct DraggablePointerModifierNode {
 }
 
 impl PointerInputModifierNode for DraggablePointerModifierNode {
-    fn on_pointer_input(&self, input: PointerInput<'_>) {
+    fn on_pointer_input(&self, mut input: PointerInput<'_>) {
         if !self.enabled {
             return;
         }
@@ -342,6 +341,12 @@ impl PointerInputModifierNode for DraggablePointerModifierNode {
             );
             (was_dragging, drag_result)
         });
+        if let Some(pointer_id) = self
+            .drag_recognizer
+            .with_mut(|recognizer| recognizer.active_pointer())
+        {
+            input.capture_pointer(pointer_id);
+        }
 
         if drag_result.started {
             if let Some(interaction_state) = self.interaction_state {
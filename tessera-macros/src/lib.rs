@@ -11,27 +11,53 @@
     rustdoc::invalid_html_tags
 )]
 
-use std::hash::{DefaultHasher, Hash, Hasher};
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+};
 
 use proc_macro::TokenStream;
+use proc_macro2::Span;
 use quote::{format_ident, quote};
 use syn::{
     Block, Expr, FnArg, GenericArgument, GenericParam, Generics, Ident, ItemFn, Pat, Path,
-    PathArguments, Token, Type, parse::Parse, parse_macro_input, parse_quote, visit_mut::VisitMut,
+    PathArguments, Stmt, Token, Type, parse::Parse, parse_macro_input, parse_quote,
+    parse_quote_spanned, spanned::Spanned, visit_mut::VisitMut,
 };
 
-/// Helper: parse crate path from attribute TokenStream
-fn parse_crate_path(attr: proc_macro::TokenStream) -> syn::Result<syn::Path> {
-    if attr.is_empty() {
-        // Default to `tessera_ui` if no path is provided
-        Ok(syn::parse_quote!(::tessera_ui))
-    } else {
-        // Parse the provided path, e.g., `crate` or `tessera_ui`
-        let tokens: proc_macro2::TokenStream = attr.clone().into();
-        syn::parse(attr).map_err(|_| {
-            syn::Error::new_spanned(tokens, "expected a crate path like `crate` or `tessera_ui`")
-        })
+/// Arguments of `#[tessera(...)]`.
+struct TesseraAttrConfig {
+    crate_path: syn::Path,
+    memo: bool,
+}
+
+/// Helper: parse `#[tessera]` arguments, a crate path and/or `memo`
+fn parse_tessera_attr(attr: proc_macro::TokenStream) -> syn::Result<TesseraAttrConfig> {
+    let args = syn::parse::Parser::parse(
+        syn::punctuated::Punctuated::<syn::Path, Token![,]>::parse_terminated,
+        attr,
+    )?;
+    let mut crate_path = None;
+    let mut memo = false;
+    for arg in args {
+        if arg.is_ident("memo") {
+            if memo {
+                return Err(syn::Error::new_spanned(arg, "duplicate `memo` argument"));
+            }
+            memo = true;
+        } else if crate_path.is_some() {
+            return Err(syn::Error::new_spanned(
+                arg,
+                "expected a single crate path like `crate` or `tessera_ui`, and optionally `memo`",
+            ));
+        } else {
+            crate_path = Some(arg);
+        }
     }
+    Ok(TesseraAttrConfig {
+        crate_path: crate_path.unwrap_or_else(|| syn::parse_quote!(::tessera_ui)),
+        memo,
+    })
 }
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -40,6 +66,13 @@ struct SetterAttrConfig {
     into: bool,
 }
 
+/// Role of a render slot parameter marked `#[children]` or `#[slot]`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum SlotKind {
+    Children,
+    Named,
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 enum PropHelperKind {
     Callback,
@@ -48,9 +81,19 @@ enum PropHelperKind {
     RenderSlotWith,
 }
 
-#[derive(Clone, Copy, Debug, Default)]
+/// Value used for a prop the call site leaves unset.
+#[derive(Clone)]
+enum PropDefault {
+    /// `#[prop(default)]`: `Default::default()`.
+    Trait,
+    /// `#[prop(default = expr)]`.
+    Expr(Box<Expr>),
+}
+
+#[derive(Clone, Default)]
 struct PropFieldAttrConfig {
     skip_eq: bool,
+    default: Option<PropDefault>,
 }
 
 fn parse_setter_attr(attrs: &[syn::Attribute]) -> syn::Result<SetterAttrConfig> {
@@ -75,6 +118,9 @@ fn parse_setter_attr(attrs: &[syn::Attribute]) -> syn::Result<SetterAttrConfig>
                         config.into = true;
                         return Ok(());
                     }
+                    if meta.path.is_ident("default") && meta.input.peek(Token![=]) {
+                        meta.value()?.parse::<Expr>()?;
+                    }
                     Ok(())
                 })?;
             }
@@ -103,6 +149,17 @@ fn parse_prop_field_attr(attrs: &[syn::Attribute]) -> syn::Result<PropFieldAttrC
                 config.skip_eq = true;
                 return Ok(());
             }
+            if meta.path.is_ident("default") {
+                if config.default.is_some() {
+                    return Err(meta.error("duplicate `default` in #[prop(...)]"));
+                }
+                config.default = Some(if meta.input.peek(Token![=]) {
+                    PropDefault::Expr(Box::new(meta.value()?.parse()?))
+                } else {
+                    PropDefault::Trait
+                });
+                return Ok(());
+            }
             if meta.path.is_ident("skip") {
                 return Err(meta.error("unsupported field option `skip`"));
             }
@@ -116,13 +173,35 @@ fn parse_prop_field_attr(attrs: &[syn::Attribute]) -> syn::Result<PropFieldAttrC
             }
 
             Err(meta.error(
-                "unsupported field #[prop(...)] option; expected setter options (`skip_setter`/`into`) or compare option (`skip_eq`)",
+                "unsupported field #[prop(...)] option; expected setter options (`skip_setter`/`into`), compare option (`skip_eq`) or `default`",
             ))
         })?;
     }
     Ok(config)
 }
 
+fn parse_slot_attr(attrs: &[syn::Attribute]) -> syn::Result<Option<SlotKind>> {
+    let mut slot = None;
+    for attr in attrs {
+        let kind = if attr.path().is_ident("children") {
+            SlotKind::Children
+        } else if attr.path().is_ident("slot") {
+            SlotKind::Named
+        } else {
+            continue;
+        };
+        attr.meta.require_path_only()?;
+        if slot.is_some() {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "a parameter takes at most one of `#[children]` and `#[slot]`",
+            ));
+        }
+        slot = Some(kind);
+    }
+    Ok(slot)
+}
+
 fn option_inner_type(ty: &Type) -> Option<Type> {
     let Type::Path(type_path) = ty else {
         return None;
@@ -234,6 +313,8 @@ struct ShardMacroArgs {
     shard_crate_path: Option<Path>,
     state_type: Option<Type>,
     lifecycle: Option<Ident>,
+    transition: Option<Expr>,
+    saveable: bool,
 }
 
 #[cfg(feature = "shard")]
@@ -306,6 +387,16 @@ impl Parse for ShardMacroArgs {
         let mut args = ShardMacroArgs::default();
         while !input.is_empty() {
             let key: Ident = input.parse()?;
+            if key == "saveable" {
+                if args.saveable {
+                    return Err(syn::Error::new(key.span(), "duplicate `saveable` argument"));
+                }
+                args.saveable = true;
+                if input.peek(Token![,]) {
+                    input.parse::<Token![,]>()?;
+                }
+                continue;
+            }
             input.parse::<Token![=]>()?;
             match key.to_string().as_str() {
                 "crate_path" => {
@@ -341,10 +432,281 @@ impl Parse for ShardMacroArgs {
                     }
                     args.lifecycle = Some(input.parse::<Ident>()?);
                 }
+                "transition" => {
+                    if args.transition.is_some() {
+                        return Err(syn::Error::new(
+                            key.span(),
+                            "duplicate `transition` argument",
+                        ));
+                    }
+                    args.transition = Some(input.parse::<Expr>()?);
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        "unsupported #[shard(...)] argument; expected `state`, `lifecycle`, `transition`, `saveable`, `crate_path`, or `shard_crate_path`",
+                    ));
+                }
+            }
+
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(args)
+    }
+}
+
+/// Entry settings given at the top level of `#[entry(...)]` or inside one of
+/// its platform blocks.
+#[derive(Default)]
+struct EntrySettings {
+    packages: Vec<Expr>,
+    plugins: Vec<Expr>,
+    modules: Vec<Expr>,
+    deep_link_schemes: Vec<Expr>,
+    config: Option<Expr>,
+    log_filter: Option<Expr>,
+    /// Whether the platform's startup function is generated.
+    start: bool,
+}
+
+impl EntrySettings {
+    /// Parses a bare flag inside the `platform(...)` block.
+    fn parse_flag(&mut self, platform: &str, key: &Ident) -> syn::Result<()> {
+        match (platform, key.to_string().as_str()) {
+            ("desktop", "main") | ("web", "start") => {
+                if self.start {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!("duplicate `{key}` flag"),
+                    ));
+                }
+                self.start = true;
+                Ok(())
+            }
+            _ => Err(syn::Error::new(
+                key.span(),
+                "unsupported flag; expected `main` in `desktop(...)` or `start` in `web(...)`",
+            )),
+        }
+    }
+
+    /// Parses one `key = value` setting.
+    fn parse_setting(
+        &mut self,
+        key: &Ident,
+        input: syn::parse::ParseStream<'_>,
+    ) -> syn::Result<()> {
+        input.parse::<Token![=]>()?;
+        match key.to_string().as_str() {
+            "packages" => self.packages.extend(parse_expr_list(input)?),
+            "plugins" => self.plugins.extend(parse_expr_list(input)?),
+            "modules" => self.modules.extend(parse_expr_list(input)?),
+            "deep_link_schemes" => self.deep_link_schemes.extend(parse_expr_list(input)?),
+            "config" => {
+                if self.config.is_some() {
+                    return Err(syn::Error::new(key.span(), "duplicate `config` argument"));
+                }
+                self.config = Some(input.parse()?);
+            }
+            "log_filter" => {
+                if self.log_filter.is_some() {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        "duplicate `log_filter` argument",
+                    ));
+                }
+                self.log_filter = Some(input.parse()?);
+            }
+            _ => {
+                return Err(syn::Error::new(
+                    key.span(),
+                    "unsupported #[entry(...)] argument; expected `packages`, `plugins`, `modules`, `config`, `deep_link_schemes`, `log_filter`, or a `desktop(...)`, `android(...)`, `ios(...)` or `web(...)` block",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Tokens rebinding `entry` with these settings applied, if there are
+    /// any.
+    fn apply_tokens(&self) -> Option<proc_macro2::TokenStream> {
+        if self.packages.is_empty()
+            && self.plugins.is_empty()
+            && self.modules.is_empty()
+            && self.deep_link_schemes.is_empty()
+            && self.config.is_none()
+            && self.log_filter.is_none()
+        {
+            return None;
+        }
+        let packages = &self.packages;
+        let plugins = &self.plugins;
+        let modules = &self.modules;
+        let deep_link_schemes = &self.deep_link_schemes;
+        let config = self.config.iter();
+        let log_filter = self.log_filter.iter();
+        Some(quote! {
+            let entry = entry
+                #(.package(#packages))*
+                #(.plugin(#plugins))*
+                #(.module(#modules))*
+                #(.config(#config))*
+                #(.deep_link_scheme(#deep_link_schemes))*
+                #(.log_filter(#log_filter))*;
+        })
+    }
+}
+
+/// Arguments of `#[entry(...)]`.
+#[derive(Default)]
+struct EntryMacroArgs {
+    crate_path: Option<Path>,
+    common: EntrySettings,
+    desktop: Option<EntrySettings>,
+    android: Option<EntrySettings>,
+    ios: Option<EntrySettings>,
+    web: Option<EntrySettings>,
+}
+
+impl Parse for EntryMacroArgs {
+    fn parse(input: syn::parse::ParseStream<'_>) -> syn::Result<Self> {
+        let mut args = EntryMacroArgs::default();
+        // Keeps `#[entry(crate)]` and `#[entry(tessera_ui)]` working.
+        if input.peek(Token![crate])
+            || input.peek(Token![::])
+            || (input.peek(Ident) && !input.peek2(Token![=]) && !input.peek2(syn::token::Paren))
+        {
+            args.crate_path = Some(input.parse()?);
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+            if input.peek(syn::token::Paren) {
+                let platform_name = key.to_string();
+                let platform = match platform_name.as_str() {
+                    "desktop" => &mut args.desktop,
+                    "android" => &mut args.android,
+                    "ios" => &mut args.ios,
+                    "web" => &mut args.web,
+                    _ => {
+                        return Err(syn::Error::new(
+                            key.span(),
+                            "unsupported platform block; expected `desktop`, `android`, `ios`, or `web`",
+                        ));
+                    }
+                };
+                if platform.is_some() {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!("duplicate `{key}` block"),
+                    ));
+                }
+                let content;
+                syn::parenthesized!(content in input);
+                let mut settings = EntrySettings::default();
+                while !content.is_empty() {
+                    let key: Ident = content.parse()?;
+                    if content.peek(Token![=]) {
+                        settings.parse_setting(&key, &content)?;
+                    } else {
+                        settings.parse_flag(&platform_name, &key)?;
+                    }
+                    if content.peek(Token![,]) {
+                        content.parse::<Token![,]>()?;
+                    }
+                }
+                *platform = Some(settings);
+            } else if key == "crate_path" {
+                if args.crate_path.is_some() {
+                    return Err(syn::Error::new(key.span(), "duplicate crate path"));
+                }
+                input.parse::<Token![=]>()?;
+                args.crate_path = Some(input.parse()?);
+            } else {
+                args.common.parse_setting(&key, input)?;
+            }
+
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(args)
+    }
+}
+
+/// Helper: parse a bracketed, comma-separated list of expressions.
+fn parse_expr_list(input: syn::parse::ParseStream<'_>) -> syn::Result<Vec<Expr>> {
+    let list: syn::ExprArray = input.parse()?;
+    Ok(list.elems.into_iter().collect())
+}
+
+/// Arguments of `#[preview(...)]`.
+#[derive(Default)]
+struct PreviewMacroArgs {
+    crate_path: Option<Path>,
+    name: Option<syn::LitStr>,
+    size: Option<(f64, f64)>,
+    theme: Option<Ident>,
+}
+
+impl Parse for PreviewMacroArgs {
+    fn parse(input: syn::parse::ParseStream<'_>) -> syn::Result<Self> {
+        let mut args = PreviewMacroArgs::default();
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            match key.to_string().as_str() {
+                "crate_path" => {
+                    if args.crate_path.is_some() {
+                        return Err(syn::Error::new(
+                            key.span(),
+                            "duplicate `crate_path` argument",
+                        ));
+                    }
+                    args.crate_path = Some(input.parse::<Path>()?);
+                }
+                "name" => {
+                    if args.name.is_some() {
+                        return Err(syn::Error::new(key.span(), "duplicate `name` argument"));
+                    }
+                    args.name = Some(input.parse::<syn::LitStr>()?);
+                }
+                "size" => {
+                    if args.size.is_some() {
+                        return Err(syn::Error::new(key.span(), "duplicate `size` argument"));
+                    }
+                    let size: syn::ExprTuple = input.parse()?;
+                    if size.elems.len() != 2 {
+                        return Err(syn::Error::new_spanned(
+                            size,
+                            "expected `size = (width, height)` in dp",
+                        ));
+                    }
+                    let width = parse_dp_literal(&size.elems[0])?;
+                    let height = parse_dp_literal(&size.elems[1])?;
+                    args.size = Some((width, height));
+                }
+                "theme" => {
+                    if args.theme.is_some() {
+                        return Err(syn::Error::new(key.span(), "duplicate `theme` argument"));
+                    }
+                    let theme: Ident = input.parse()?;
+                    if theme != "light" && theme != "dark" {
+                        return Err(syn::Error::new(
+                            theme.span(),
+                            "expected `theme = light` or `theme = dark`",
+                        ));
+                    }
+                    args.theme = Some(theme);
+                }
                 _ => {
                     return Err(syn::Error::new(
                         key.span(),
-                        "unsupported #[shard(...)] argument; expected `state`, `lifecycle`, `crate_path`, or `shard_crate_path`",
+                        "unsupported #[preview(...)] argument; expected `name`, `size`, `theme`, or `crate_path`",
                     ));
                 }
             }
@@ -357,6 +719,24 @@ impl Parse for ShardMacroArgs {
     }
 }
 
+/// Helper: parse a dp value written as a number literal.
+fn parse_dp_literal(expr: &Expr) -> syn::Result<f64> {
+    match expr {
+        Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit),
+            ..
+        }) => lit.base10_parse(),
+        Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Float(lit),
+            ..
+        }) => lit.base10_parse(),
+        _ => Err(syn::Error::new_spanned(
+            expr,
+            "expected a number of dp like `360` or `360.0`",
+        )),
+    }
+}
+
 /// Helper: tokens to register a component node
 fn register_node_tokens(crate_path: &syn::Path, fn_name: &syn::Ident) -> proc_macro2::TokenStream {
     quote! {
@@ -379,7 +759,7 @@ fn strict_prop_signature(sig: &syn::Signature) -> Result<ComponentPropSignature,
         return Ok(ComponentPropSignature::Unit);
     }
 
-    let mut fields = Vec::new();
+    let mut fields: Vec<PropFieldSpec> = Vec::new();
     for arg in &sig.inputs {
         let FnArg::Typed(arg) = arg else {
             return Err(syn::Error::new_spanned(
@@ -402,43 +782,170 @@ fn strict_prop_signature(sig: &syn::Signature) -> Result<ComponentPropSignature,
             ));
         }
 
-        if matches!(arg.ty.as_ref(), Type::Reference(_)) {
+        let field_setter_attr = parse_setter_attr(&arg.attrs)?;
+        let prop_attr = parse_prop_field_attr(&arg.attrs)?;
+        let slot = parse_slot_attr(&arg.attrs)?;
+        if slot.is_some() {
+            validate_slot_param(arg, &prop_attr)?;
+        }
+        if slot == Some(SlotKind::Children)
+            && fields
+                .iter()
+                .any(|field| field.slot == Some(SlotKind::Children))
+        {
+            return Err(syn::Error::new_spanned(
+                arg,
+                "a component takes at most one `#[children]` parameter; mark other slots with `#[slot]`",
+            ));
+        }
+        let borrowed = borrowed_prop_referent(arg.ty.as_ref())?;
+        if prop_attr.default.is_some() && option_inner_type(arg.ty.as_ref()).is_some() {
             return Err(syn::Error::new_spanned(
                 &arg.ty,
-                "component parameters must be owned types; borrowed parameter types are not supported",
+                "`#[prop(default)]` requires a non-`Option` parameter; `Option` props already default to `None`",
             ));
         }
-
-        let field_setter_attr = parse_setter_attr(&arg.attrs)?;
-        let prop_attr = parse_prop_field_attr(&arg.attrs)?;
+        let ty = match &borrowed {
+            Some(referent) => {
+                if field_setter_attr.into {
+                    return Err(syn::Error::new_spanned(
+                        &arg.ty,
+                        "`#[prop(into)]` is not supported on borrowed parameters",
+                    ));
+                }
+                let owned_ty: Type = parse_quote!(<#referent as ::std::borrow::ToOwned>::Owned);
+                if option_inner_type(arg.ty.as_ref()).is_some() {
+                    parse_quote!(::core::option::Option<#owned_ty>)
+                } else {
+                    owned_ty
+                }
+            }
+            None => (*arg.ty).clone(),
+        };
         fields.push(PropFieldSpec {
             ident: pat_ident.ident.clone(),
-            ty: (*arg.ty).clone(),
+            helper: infer_prop_helper_kind(&ty),
+            ty,
+            borrowed,
             setter: field_setter_attr,
-            helper: infer_prop_helper_kind(arg.ty.as_ref()),
             skip_eq: prop_attr.skip_eq,
+            default: prop_attr.default,
+            slot,
         });
     }
 
     Ok(ComponentPropSignature::Params(fields))
 }
 
+/// Checks that a `#[children]` or `#[slot]` parameter is a render slot whose
+/// handle takes part in replay comparison.
+fn validate_slot_param(arg: &syn::PatType, prop_attr: &PropFieldAttrConfig) -> syn::Result<()> {
+    if !matches!(
+        infer_prop_helper_kind(arg.ty.as_ref()),
+        Some(PropHelperKind::RenderSlot | PropHelperKind::RenderSlotWith)
+    ) {
+        return Err(syn::Error::new_spanned(
+            &arg.ty,
+            "slot parameters must be `RenderSlot`, `RenderSlotWith<T>`, or an `Option` of them",
+        ));
+    }
+    if prop_attr.skip_eq {
+        return Err(syn::Error::new_spanned(
+            arg,
+            "slot parameters cannot use `skip_eq`; replay compares slot handles to pick up new content",
+        ));
+    }
+    Ok(())
+}
+
+/// Returns `T` for a borrowed parameter type `&T` or `Option<&T>`.
+///
+/// Borrowed props are stored as `<T as ToOwned>::Owned` and lent to the
+/// component body, so `title: &str` keeps a `String` in the props.
+fn borrowed_prop_referent(ty: &Type) -> syn::Result<Option<Type>> {
+    let value_ty = option_inner_type(ty).unwrap_or_else(|| ty.clone());
+    let Type::Reference(reference) = value_ty else {
+        return Ok(None);
+    };
+    if reference.mutability.is_some() {
+        return Err(syn::Error::new_spanned(
+            ty,
+            "component parameters cannot be mutable references",
+        ));
+    }
+    Ok(Some((*reference.elem).clone()))
+}
+
 /// Helper: tokens to attach replay metadata to the current component node.
 fn replay_register_tokens(
     crate_path: &syn::Path,
     runner_name: &syn::Ident,
     prop_type: &syn::Type,
     props_expr: proc_macro2::TokenStream,
+    memo: bool,
 ) -> proc_macro2::TokenStream {
+    let set_replay = if memo {
+        format_ident!("set_current_memo_component_replay")
+    } else {
+        format_ident!("set_current_component_replay")
+    };
     quote! {
         let __tessera_component_reused = {
             let __tessera_runner =
                 #crate_path::__private::make_component_runner::<#prop_type>(#runner_name);
-            #crate_path::__private::set_current_component_replay(__tessera_runner, #props_expr)
+            #crate_path::__private::#set_replay(__tessera_runner, #props_expr)
         };
     }
 }
 
+/// Returns the `Result<(), E>` a fallible component returns, if any.
+fn component_result_type(output: &syn::ReturnType) -> syn::Result<Option<&Type>> {
+    let syn::ReturnType::Type(_, ty) = output else {
+        return Ok(None);
+    };
+    let is_result = matches!(
+        ty.as_ref(),
+        Type::Path(path)
+            if path.qself.is_none()
+                && path
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|segment| segment.ident == "Result")
+    );
+    if !is_result {
+        return Err(syn::Error::new_spanned(
+            output,
+            "#[tessera] components must not return a value; return `Result<(), E>` to report errors to an error boundary",
+        ));
+    }
+    Ok(Some(ty))
+}
+
+/// Helper: tokens running the component body.
+///
+/// A fallible body runs in a closure so `?` and `return Err(..)` stay local,
+/// and its error is reported to the nearest error boundary.
+fn component_body_tokens(
+    crate_path: &syn::Path,
+    fn_name: &Ident,
+    block: &Block,
+    result_ty: Option<&Type>,
+) -> proc_macro2::TokenStream {
+    let Some(result_ty) = result_ty else {
+        return quote!(#block);
+    };
+    quote! {
+        let __tessera_body_result: #result_ty = (|| -> #result_ty #block)();
+        if let ::core::result::Result::Err(__tessera_error) = __tessera_body_result {
+            #crate_path::error_boundary::report_component_error(
+                stringify!(#fn_name),
+                __tessera_error,
+            );
+        }
+    }
+}
+
 /// Helper: compile-time assertion that component props implement `Prop`.
 fn prop_assert_tokens(crate_path: &syn::Path, prop_type: &syn::Type) -> proc_macro2::TokenStream {
     quote! {
@@ -449,16 +956,66 @@ fn prop_assert_tokens(crate_path: &syn::Path, prop_type: &syn::Type) -> proc_mac
     }
 }
 
+/// Helper: tokens lending a borrowed prop to the component body.
+fn borrowed_field_resolution(
+    field: &PropFieldSpec,
+    referent: &Type,
+    fn_name: &Ident,
+) -> proc_macro2::TokenStream {
+    let ident = &field.ident;
+    if option_inner_type(&field.ty).is_some() {
+        return quote! {
+            let #ident: ::core::option::Option<&#referent> = __tessera_props
+                .#ident
+                .as_ref()
+                .map(::std::borrow::Borrow::<#referent>::borrow);
+        };
+    }
+
+    if let Some(default) = &field.default {
+        let default = prop_default_tokens(default, &parse_quote!(&#referent));
+        return quote! {
+            let #ident: &#referent = match __tessera_props.#ident.as_ref() {
+                Some(value) => ::std::borrow::Borrow::<#referent>::borrow(value),
+                None => #default,
+            };
+        };
+    }
+
+    let missing_prop_message = format!(
+        "missing required prop `{}` for component `{}`",
+        ident, fn_name
+    );
+    quote! {
+        let #ident: &#referent = ::std::borrow::Borrow::<#referent>::borrow(
+            __tessera_props.#ident.as_ref().unwrap_or_else(|| panic!(#missing_prop_message)),
+        );
+    }
+}
+
 struct PropFieldSpec {
     ident: Ident,
+    /// Stored type; the owned counterpart of a borrowed parameter.
     ty: Type,
+    /// Referent `T` of a parameter declared as `&T` or `Option<&T>`.
+    borrowed: Option<Type>,
     setter: SetterAttrConfig,
     helper: Option<PropHelperKind>,
     skip_eq: bool,
+    default: Option<PropDefault>,
+    slot: Option<SlotKind>,
 }
 
 fn is_required_component_field(field: &PropFieldSpec) -> bool {
-    option_inner_type(&field.ty).is_none()
+    option_inner_type(&field.ty).is_none() && field.default.is_none()
+}
+
+/// Helper: expression of type `ty` used when a defaulted prop is unset.
+fn prop_default_tokens(default: &PropDefault, ty: &Type) -> proc_macro2::TokenStream {
+    match default {
+        PropDefault::Trait => quote!(<#ty as ::core::default::Default>::default()),
+        PropDefault::Expr(expr) => quote!(#expr),
+    }
 }
 
 fn stored_value_ty(field_ty: &Type) -> Type {
@@ -481,6 +1038,15 @@ fn generate_default_setter_method_for_path(
     let ident = &field.ident;
     let method_doc = format!("Set `{ident}`.");
     let field_ty = &field.ty;
+    if let Some(referent) = &field.borrowed {
+        return Ok(Some(quote! {
+            #[doc = #method_doc]
+            pub fn #ident(mut self, #ident: &#referent) -> Self {
+                self.#field_path = Some(<#referent as ::std::borrow::ToOwned>::to_owned(#ident));
+                self
+            }
+        }));
+    }
     if let Some(inner_ty) = option_inner_type(field_ty) {
         let method = if field.setter.into {
             quote! {
@@ -535,6 +1101,19 @@ fn generate_optional_setter_method_for_path(
     let optional_ident = format_ident!("{}_optional", ident);
     let method_doc = format!("Set `{ident}` from an optional value.");
 
+    if let Some(referent) = &field.borrowed {
+        return Some(quote! {
+            #[doc = #method_doc]
+            pub fn #optional_ident(
+                mut self,
+                #ident: ::core::option::Option<&#referent>,
+            ) -> Self {
+                self.#field_path = #ident.map(<#referent as ::std::borrow::ToOwned>::to_owned);
+                self
+            }
+        });
+    }
+
     Some(quote! {
         #[doc = #method_doc]
         pub fn #optional_ident(mut self, #ident: ::core::option::Option<#inner_ty>) -> Self {
@@ -552,8 +1131,24 @@ fn generate_helper_setter_methods(
 ) -> syn::Result<proc_macro2::TokenStream> {
     let ident = &field.ident;
     let shared_ident = format_ident!("{}_shared", ident);
-    let helper_doc = format!("Set `{ident}` from a closure.");
-    let shared_doc = format!("Set `{ident}` from a shared handle.");
+    let (helper_doc, shared_doc) = match field.slot {
+        Some(SlotKind::Children) => (
+            format!(
+                "Set the children rendered in `{ident}`.\n\nThe closure runs when the component renders the slot. Its handle stays stable across rebuilds, so new content only rebuilds the components that rendered it."
+            ),
+            format!("Set the children rendered in `{ident}` from a shared handle."),
+        ),
+        Some(SlotKind::Named) => (
+            format!(
+                "Set the `{ident}` slot.\n\nThe closure runs when the component renders the slot. Its handle stays stable across rebuilds, so new content only rebuilds the components that rendered it."
+            ),
+            format!("Set the `{ident}` slot from a shared handle."),
+        ),
+        None => (
+            format!("Set `{ident}` from a closure."),
+            format!("Set `{ident}` from a shared handle."),
+        ),
+    };
     let value_ty = stored_value_ty(&field.ty);
 
     match helper {
@@ -792,6 +1387,15 @@ fn generate_constructor_param_and_assignment(
         }
     }
 
+    if let Some(referent) = &field.borrowed {
+        let param = quote!(#ident: &#referent);
+        let assignment = quote! {
+            __tessera_builder.props.#ident =
+                Some(<#referent as ::std::borrow::ToOwned>::to_owned(#ident));
+        };
+        return Ok((param, assignment));
+    }
+
     let constructor_param = if field.setter.into || helper_uses_into {
         quote!(#ident: impl Into<#field_ty>)
     } else {
@@ -1057,122 +1661,190 @@ fn component_type_id_tokens(
     }
 }
 
+/// Instruments control flow in a component body with group guards.
+///
+/// Group ids are derived from the grouped code rather than its position: an
+/// `if` is identified by its condition, a match arm by the scrutinee and its
+/// pattern, and a loop by its head. Adding or removing other branches
+/// therefore keeps the `remember` state of unchanged ones. Identical anchors
+/// are told apart by their order, and `#[group("label")]` replaces the anchor
+/// with an explicit label.
 struct ControlFlowInstrumenter {
-    /// counter to generate unique IDs in current function
-    counter: usize,
     /// seed to prevent ID collisions across functions
     seed: u64,
+    /// number of groups already generated for each anchor
+    occurrences: HashMap<String, usize>,
+    /// first invalid `#[group(...)]` attribute
+    error: Option<syn::Error>,
 }
 
 impl ControlFlowInstrumenter {
     fn new(seed: u64) -> Self {
-        Self { counter: 0, seed }
+        Self {
+            seed,
+            occurrences: HashMap::new(),
+            error: None,
+        }
     }
 
-    /// Generate the next unique group ID
-    fn next_group_id(&mut self) -> u64 {
+    /// Generate the group ID for the next group anchored at `anchor`
+    fn next_group_id(&mut self, anchor: String) -> u64 {
         let mut hasher = DefaultHasher::new();
         self.seed.hash(&mut hasher);
-        self.counter.hash(&mut hasher);
-        self.counter += 1;
+        anchor.hash(&mut hasher);
+        let occurrence = self.occurrences.entry(anchor).or_default();
+        occurrence.hash(&mut hasher);
+        *occurrence += 1;
         hasher.finish()
     }
 
+    /// Anchor for a group: the explicit label if any, else `kind` followed by
+    /// the tokens of `head`.
+    fn anchor(
+        &mut self,
+        attrs: &mut Vec<syn::Attribute>,
+        kind: &str,
+        head: proc_macro2::TokenStream,
+    ) -> String {
+        match take_group_label(attrs) {
+            Ok(Some(label)) => format!("label {} {kind}", label.value()),
+            Ok(None) => format!("{kind} {head}"),
+            Err(err) => {
+                self.error.get_or_insert(err);
+                format!("{kind} {head}")
+            }
+        }
+    }
+
+    /// Guard statement opening a new group, spanned at `span`.
+    ///
+    /// `span` points at the user code being grouped, so diagnostics involving
+    /// the guard do not fall back to the macro invocation.
+    fn group_guard_stmt(&mut self, guard: &str, anchor: String, span: Span) -> Stmt {
+        let group_id = self.next_group_id(anchor);
+        let span = Span::mixed_site().located_at(span);
+        let guard = Ident::new(guard, span);
+        // Use fully-qualified path ::tessera_ui to avoid relying on a crate alias
+        parse_quote_spanned! {span=>
+            let _group_guard = ::tessera_ui::__private::#guard::new(#group_id);
+        }
+    }
+
     /// Wrap an expression in a GroupGuard block
     ///
     /// Before transform: expr
     /// After transform: { let _group_guard =
     /// ::tessera_ui::__private::GroupGuard::new(#id); expr }
-    fn wrap_expr_in_group(&mut self, expr: &mut Expr) {
+    ///
+    /// The new braces take the span of `expr`.
+    fn wrap_expr_in_group(&mut self, expr: &mut Expr, anchor: String) {
         // Recursively visit sub-expressions (depth-first) to ensure nested structures
         // are wrapped
         self.visit_expr_mut(expr);
-        let group_id = self.next_group_id();
-        // Use fully-qualified path ::tessera_ui to avoid relying on a crate alias
-        let original_expr = &expr;
-        let new_expr: Expr = parse_quote! {
-            {
-                let _group_guard = ::tessera_ui::__private::GroupGuard::new(#group_id);
-                #original_expr
-            }
-        };
-        *expr = new_expr;
+        let span = expr.span();
+        let guard = self.group_guard_stmt("GroupGuard", anchor, span);
+        let original_expr = std::mem::replace(expr, Expr::Verbatim(Default::default()));
+        *expr = Expr::Block(syn::ExprBlock {
+            attrs: Vec::new(),
+            label: None,
+            block: Block {
+                brace_token: syn::token::Brace(span),
+                stmts: vec![guard, Stmt::Expr(original_expr, None)],
+            },
+        });
     }
 
     /// Wrap a block in a GroupGuard block
-    fn wrap_block_in_group(&mut self, block: &mut Block) {
+    ///
+    /// The guard is inserted into the block itself, which keeps its braces
+    /// and their spans.
+    fn wrap_block_in_group(&mut self, block: &mut Block, anchor: String) {
         // Recursively instrument nested expressions before wrapping the block
         self.visit_block_mut(block);
-
-        let group_id = self.next_group_id();
-        let original_stmts = &block.stmts;
-
-        let new_block: Block = parse_quote! {
-            {
-                let _group_guard = ::tessera_ui::__private::GroupGuard::new(#group_id);
-                #(#original_stmts)*
-            }
-        };
-
-        *block = new_block;
+        let guard = self.group_guard_stmt("GroupGuard", anchor, block.brace_token.span.join());
+        block.stmts.insert(0, guard);
     }
 
     /// Wrap a block in a path-only group block.
-    fn wrap_block_in_path_group(&mut self, block: &mut Block) {
+    fn wrap_block_in_path_group(&mut self, block: &mut Block, anchor: String) {
         self.visit_block_mut(block);
+        let guard = self.group_guard_stmt("PathGroupGuard", anchor, block.brace_token.span.join());
+        block.stmts.insert(0, guard);
+    }
+}
 
-        let group_id = self.next_group_id();
-        let original_stmts = &block.stmts;
-
-        let new_block: Block = parse_quote! {
-            {
-                let _group_guard = ::tessera_ui::__private::PathGroupGuard::new(#group_id);
-                #(#original_stmts)*
-            }
-        };
-
-        *block = new_block;
+/// Removes a `#[group("label")]` attribute and returns its label.
+fn take_group_label(attrs: &mut Vec<syn::Attribute>) -> syn::Result<Option<syn::LitStr>> {
+    let Some(index) = attrs.iter().position(|attr| attr.path().is_ident("group")) else {
+        return Ok(None);
+    };
+    let attr = attrs.remove(index);
+    if let Some(duplicate) = attrs.iter().find(|attr| attr.path().is_ident("group")) {
+        return Err(syn::Error::new_spanned(
+            duplicate,
+            "duplicate `#[group(...)]` attribute",
+        ));
     }
+    attr.parse_args::<syn::LitStr>()
+        .map(Some)
+        .map_err(|_| syn::Error::new_spanned(attr, "expected `#[group(\"label\")]`"))
 }
 
 impl VisitMut for ControlFlowInstrumenter {
     fn visit_expr_if_mut(&mut self, i: &mut syn::ExprIf) {
+        let cond = &i.cond;
+        let anchor = self.anchor(&mut i.attrs, "if", quote!(#cond));
         self.visit_expr_mut(&mut i.cond);
-        self.wrap_block_in_group(&mut i.then_branch);
+        self.wrap_block_in_group(&mut i.then_branch, anchor.clone());
         if let Some((_, else_branch)) = &mut i.else_branch {
+            let else_anchor = format!("{anchor} else");
             match &mut **else_branch {
                 Expr::Block(block_expr) => {
-                    self.wrap_block_in_group(&mut block_expr.block);
+                    self.wrap_block_in_group(&mut block_expr.block, else_anchor);
                 }
                 Expr::If(_) => {
                     self.visit_expr_mut(else_branch);
                 }
                 _ => {
-                    self.wrap_expr_in_group(else_branch);
+                    self.wrap_expr_in_group(else_branch, else_anchor);
                 }
             }
         }
     }
 
     fn visit_expr_match_mut(&mut self, m: &mut syn::ExprMatch) {
+        let scrutinee = &m.expr;
+        let anchor = self.anchor(&mut m.attrs, "match", quote!(#scrutinee));
         self.visit_expr_mut(&mut m.expr);
         for arm in &mut m.arms {
-            self.wrap_expr_in_group(&mut arm.body);
+            let pat = &arm.pat;
+            let guard = arm
+                .guard
+                .as_ref()
+                .map(|(if_token, guard)| quote!(#if_token #guard));
+            let arm_anchor = self.anchor(&mut arm.attrs, "arm", quote!(#pat #guard));
+            self.wrap_expr_in_group(&mut arm.body, format!("{anchor} {arm_anchor}"));
         }
     }
 
     fn visit_expr_for_loop_mut(&mut self, f: &mut syn::ExprForLoop) {
+        let (pat, expr) = (&f.pat, &f.expr);
+        let anchor = self.anchor(&mut f.attrs, "for", quote!(#pat in #expr));
         self.visit_expr_mut(&mut f.expr);
-        self.wrap_block_in_path_group(&mut f.body);
+        self.wrap_block_in_path_group(&mut f.body, anchor);
     }
 
     fn visit_expr_while_mut(&mut self, w: &mut syn::ExprWhile) {
+        let cond = &w.cond;
+        let anchor = self.anchor(&mut w.attrs, "while", quote!(#cond));
         self.visit_expr_mut(&mut w.cond);
-        self.wrap_block_in_path_group(&mut w.body);
+        self.wrap_block_in_path_group(&mut w.body, anchor);
     }
 
     fn visit_expr_loop_mut(&mut self, l: &mut syn::ExprLoop) {
-        self.wrap_block_in_path_group(&mut l.body);
+        let label = &l.label;
+        let anchor = self.anchor(&mut l.attrs, "loop", quote!(#label));
+        self.wrap_block_in_path_group(&mut l.body, anchor);
     }
 }
 
@@ -1190,6 +1862,107 @@ impl VisitMut for ControlFlowInstrumenter {
 ///
 /// * Attribute arguments select the Tessera crate path. Use `#[tessera]` for
 ///   normal external authoring, or `#[tessera(crate)]` inside Tessera crates.
+/// * Add `memo`, as in `#[tessera(memo)]` or `#[tessera(crate, memo)]`, to opt
+///   into [memoization](#memoization).
+///
+/// # Props
+///
+/// Each function parameter becomes a field of a generated props struct that
+/// is compared on replay. Plain parameters are passed to the generated
+/// constructor, `Option<T>` parameters get builder setters. Borrowed
+/// parameters such as `title: &str` or `items: Option<&[Item]>` are stored
+/// as their owned counterpart and lent to the body.
+///
+/// Mark a plain parameter with `#[prop(default)]` or
+/// `#[prop(default = expr)]` to make it optional at call sites: it gets a
+/// builder setter, and the body sees `Default::default()` or `expr` when the
+/// setter is not called.
+///
+/// ```rust,ignore
+/// #[tessera]
+/// fn card(
+///     title: &str,
+///     #[prop(default = Dp(1.0))] elevation: Dp,
+///     on_click: Option<Callback>,
+/// ) { /* ... */ }
+///
+/// card("Inbox").elevation(Dp(2.0)).on_click(|| open_inbox());
+/// ```
+///
+/// # Slots
+///
+/// Take child content as a `RenderSlot` (or `RenderSlotWith<T>`) parameter,
+/// never as a bare closure: slot handles are stable across rebuilds and are
+/// compared on replay, so a container is reused while its content is
+/// unchanged. Mark the main content `#[children]` and other slots `#[slot]`;
+/// the macro checks their types and documents the generated setters, which
+/// accept closures directly.
+///
+/// ```rust,ignore
+/// #[tessera]
+/// fn panel(#[children] content: RenderSlot, #[slot] header: Option<RenderSlot>) {
+///     if let Some(header) = header {
+///         header.render();
+///     }
+///     content.render();
+/// }
+///
+/// panel(|| body_text()).header(|| title_text());
+/// ```
+///
+/// # Errors
+///
+/// A component may return `Result<(), E>` where `E: Display`, so its body can
+/// use `?`. An `Err` is reported to the nearest enclosing `error_boundary`,
+/// which renders its fallback instead of the failed content; without a
+/// boundary the error is logged. Callers still see a plain component call.
+///
+/// ```rust,ignore
+/// #[tessera]
+/// fn settings_page() -> Result<(), std::io::Error> {
+///     let config = std::fs::read_to_string("settings.toml")?;
+///     settings_form(&config);
+///     Ok(())
+/// }
+/// ```
+///
+/// # Memoization
+///
+/// Replay already skips an unchanged component below a component that
+/// rebuilds. `#[tessera(memo)]` goes further: the component is skipped,
+/// together with every component it calls, even when the whole tree is
+/// rebuilt, as long as its props equal the last frame's, the contexts it sees
+/// are the same, and nothing it reads was invalidated. Last frame's subtree is
+/// reused as is.
+///
+/// Use it for expensive subtrees whose inputs rarely change. A memoized
+/// component must get everything it shows from its props, contexts and
+/// states; values read from elsewhere, such as globals, are not tracked.
+///
+/// ```rust,ignore
+/// #[tessera(memo)]
+/// fn message_list(messages: &[Message]) {
+///     for message in messages {
+///         message_row(message);
+///     }
+/// }
+/// ```
+///
+/// # Control Flow
+///
+/// `remember` state inside an `if`, `match` arm or loop belongs to that
+/// branch. Branches are identified by their code: an `if` by its condition, a
+/// match arm by the scrutinee and pattern, a loop by its head. Adding or
+/// removing other branches keeps the state of the unchanged ones; editing the
+/// condition itself resets it. To keep state across such edits, or to tell
+/// apart branches with identical conditions, label the statement or arm:
+///
+/// ```rust,ignore
+/// #[group("details")]
+/// if expanded.get() {
+///     details_panel();
+/// }
+/// ```
 ///
 /// # When NOT to Use
 ///
@@ -1201,8 +1974,8 @@ impl VisitMut for ControlFlowInstrumenter {
 ///   shard state.
 #[proc_macro_attribute]
 pub fn tessera(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let crate_path: syn::Path = match parse_crate_path(attr) {
-        Ok(path) => path,
+    let TesseraAttrConfig { crate_path, memo } = match parse_tessera_attr(attr) {
+        Ok(config) => config,
         Err(err) => return err.to_compile_error().into(),
     };
 
@@ -1234,14 +2007,10 @@ pub fn tessera(attr: TokenStream, item: TokenStream) -> TokenStream {
         .to_compile_error()
         .into();
     }
-    if !matches!(input_fn.sig.output, syn::ReturnType::Default) {
-        return syn::Error::new_spanned(
-            &input_fn.sig.output,
-            "#[tessera] components must not return a value",
-        )
-        .to_compile_error()
-        .into();
-    }
+    let result_ty = match component_result_type(&input_fn.sig.output) {
+        Ok(ty) => ty.cloned(),
+        Err(err) => return err.to_compile_error().into(),
+    };
 
     let fn_name = &input_fn.sig.ident;
     let fn_vis = &input_fn.vis;
@@ -1263,7 +2032,10 @@ pub fn tessera(attr: TokenStream, item: TokenStream) -> TokenStream {
     // Modify the function body to instrument control flow with GroupGuard
     let mut instrumenter = ControlFlowInstrumenter::new(seed);
     instrumenter.visit_block_mut(&mut input_fn.block);
-    let fn_block = &input_fn.block;
+    if let Some(err) = instrumenter.error {
+        return err.to_compile_error().into();
+    }
+    let fn_block = component_body_tokens(&crate_path, fn_name, &input_fn.block, result_ty.as_ref());
     let generics = &input_fn.sig.generics;
     let mut item_generics = generics.clone();
     item_generics.where_clause = None;
@@ -1276,7 +2048,8 @@ pub fn tessera(attr: TokenStream, item: TokenStream) -> TokenStream {
     let expanded = match &prop_signature {
         ComponentPropSignature::Unit => {
             let props_ident = hidden_props_ident(fn_name);
-            let fn_sig = &input_fn.sig;
+            let mut fn_sig = input_fn.sig.clone();
+            fn_sig.output = syn::ReturnType::Default;
             let unit_runner_ident = format_ident!("__tessera_{}_unit_runner", fn_name);
             let (const_marker_defs, marker_field_def) = generic_marker_tokens(fn_name, generics);
             let has_generic_marker = marker_field_def.is_some();
@@ -1292,6 +2065,7 @@ pub fn tessera(attr: TokenStream, item: TokenStream) -> TokenStream {
                 &unit_runner_ident,
                 &prop_type,
                 quote!(&__tessera_unit_props),
+                memo,
             );
             let marker_field_def = marker_field_def
                 .map(|field| quote!(#field,))
@@ -1403,12 +2177,24 @@ pub fn tessera(attr: TokenStream, item: TokenStream) -> TokenStream {
                 .map(|field| {
                     let ident = &field.ident;
                     let ty = &field.ty;
+                    if let Some(referent) = &field.borrowed {
+                        return borrowed_field_resolution(field, referent, fn_name);
+                    }
                     if option_inner_type(ty).is_some() {
                         return quote! {
                             let #ident: #ty = __tessera_props.#ident.clone();
                         };
                     }
 
+                    if let Some(default) = &field.default {
+                        let default = prop_default_tokens(default, ty);
+                        return quote! {
+                            let #ident: #ty = __tessera_props.#ident.clone().unwrap_or_else(|| {
+                                #default
+                            });
+                        };
+                    }
+
                     let missing_prop_message = format!(
                         "missing required prop `{}` for component `{}`",
                         ident, fn_name
@@ -1432,6 +2218,7 @@ pub fn tessera(attr: TokenStream, item: TokenStream) -> TokenStream {
                 &impl_ident,
                 &prop_type,
                 quote!(__tessera_props),
+                memo,
             );
             let marker_field_def = marker_field_def
                 .map(|field| quote!(#field,))
@@ -1545,14 +2332,51 @@ pub fn tessera(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// # Usage
 ///
 /// Annotate a public zero-argument function that returns
-/// `tessera_ui::EntryPoint`.
+/// `tessera_ui::EntryPoint`. The macro generates `android_main` and the
+/// `start_app` function the Xcode project calls on iOS, both running the same
+/// entry. `desktop(main)` also generates a public `desktop_main` for the
+/// desktop binary to call and exports the entry for `cargo tessera dev` to
+/// load after hot reloading the library. `web(start)` generates a `wasm_main`
+/// function marked `#[wasm_bindgen(start)]`. Leave them out when the crate
+/// defines its own desktop or web startup.
+///
+/// ```rust,ignore
+/// #[tessera_ui::entry(
+///     packages = [tessera_components::ComponentsPackage],
+///     config = TesseraConfig {
+///         window_title: "Notes".into(),
+///         ..Default::default()
+///     },
+///     log_filter = "warn,notes=debug",
+///     desktop(main),
+///     web(start, config = TesseraConfig {
+///         web: WebConfig::default().with_canvas_id("notes"),
+///         ..Default::default()
+///     }),
+/// )]
+/// pub fn run() -> EntryPoint {
+///     EntryPoint::new(app)
+/// }
+/// ```
+///
+/// # Parameters
+///
+/// * `packages = [..]`, `plugins = [..]`, `modules = [..]` – registered in
+///   order, after whatever the function body registered.
+/// * `config = expr` – the `TesseraConfig` to run with.
+/// * `deep_link_schemes = [..]` – launch link schemes to accept.
+/// * `log_filter = "..."` – log filter used when `RUST_LOG` is not set.
+/// * `desktop(..)`, `android(..)`, `ios(..)`, `web(..)` – the same settings for
+///   one platform only, applied after the shared ones; a platform `config`
+///   replaces the shared one.
+/// * `main` in `desktop(..)`, `start` in `web(..)` – generate the desktop and
+///   web startup functions described above.
+/// * A leading crate path like `crate` or `crate_path = path` – path of
+///   `tessera_ui`.
 #[proc_macro_attribute]
 pub fn entry(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let crate_path: syn::Path = match parse_crate_path(attr) {
-        Ok(path) => path,
-        Err(err) => return err.to_compile_error().into(),
-    };
-    let mut input_fn = parse_macro_input!(item as ItemFn);
+    let args = parse_macro_input!(attr as EntryMacroArgs);
+    let input_fn = parse_macro_input!(item as ItemFn);
 
     if !input_fn.sig.inputs.is_empty() {
         return syn::Error::new_spanned(
@@ -1563,11 +2387,87 @@ pub fn entry(attr: TokenStream, item: TokenStream) -> TokenStream {
         .into();
     }
 
-    input_fn.attrs.retain(|attr| !attr.path().is_ident("entry"));
-    let fn_name = &input_fn.sig.ident;
+    let crate_path = args
+        .crate_path
+        .unwrap_or_else(|| syn::parse_quote!(::tessera_ui));
+    let mut attrs = input_fn.attrs.clone();
+    attrs.retain(|attr| !attr.path().is_ident("entry"));
+    let vis = &input_fn.vis;
+    let sig = &input_fn.sig;
+    let block = &input_fn.block;
+    let fn_name = &sig.ident;
+    let common = args.common.apply_tokens();
+    let platform_tokens = |cfg: proc_macro2::TokenStream, settings: &Option<EntrySettings>| {
+        let apply = settings.as_ref()?.apply_tokens()?;
+        Some(quote! {
+            #[cfg(#cfg)]
+            #apply
+        })
+    };
+    let desktop_cfg = quote!(all(
+        not(target_os = "android"),
+        not(target_os = "ios"),
+        not(target_family = "wasm")
+    ));
+    let desktop = platform_tokens(desktop_cfg.clone(), &args.desktop);
+    let android = platform_tokens(quote!(target_os = "android"), &args.android);
+    let ios = platform_tokens(quote!(target_os = "ios"), &args.ios);
+    let web = platform_tokens(quote!(target_family = "wasm"), &args.web);
+    let wasm_bindgen_path = quote!(#crate_path::__private::wasm_bindgen);
+    let desktop_main = args
+        .desktop
+        .as_ref()
+        .is_some_and(|settings| settings.start)
+        .then(|| {
+            quote! {
+                /// Runs the app on desktop platforms.
+                #[cfg(#desktop_cfg)]
+                #[allow(dead_code)]
+                pub fn desktop_main() {
+                    if let Err(err) = #fn_name().run_desktop() {
+                        eprintln!("App failed to run: {err}");
+                    }
+                }
+
+                /// Returns the app's entry to a process `cargo tessera dev` hot reloads.
+                #[cfg(#desktop_cfg)]
+                #[doc(hidden)]
+                #[unsafe(no_mangle)]
+                pub fn tessera_hot_reload_entry() -> #crate_path::EntryPoint {
+                    #fn_name()
+                }
+            }
+        });
+    let wasm_main = args
+        .web
+        .as_ref()
+        .is_some_and(|settings| settings.start)
+        .then(|| {
+            quote! {
+                /// Runs the app in the browser once the module is loaded.
+                #[cfg(target_family = "wasm")]
+                #[#wasm_bindgen_path::prelude::wasm_bindgen(start, wasm_bindgen = #wasm_bindgen_path)]
+                pub fn wasm_main() -> ::core::result::Result<(), #wasm_bindgen_path::JsValue> {
+                    #fn_name()
+                        .run_web()
+                        .map_err(|err| #wasm_bindgen_path::JsValue::from_str(&err.to_string()))
+                }
+            }
+        });
 
     let expanded = quote! {
-        #input_fn
+        #(#attrs)*
+        #vis #sig {
+            let entry = (move || -> #crate_path::EntryPoint #block)();
+            #common
+            #desktop
+            #android
+            #ios
+            #web
+            entry
+        }
+
+        #desktop_main
 
         #[cfg(target_os = "android")]
         #[unsafe(no_mangle)]
@@ -1576,6 +2476,98 @@ pub fn entry(attr: TokenStream, item: TokenStream) -> TokenStream {
                 eprintln!("App failed to run: {err}");
             }
         }
+
+        #[cfg(target_os = "ios")]
+        #[unsafe(no_mangle)]
+        pub extern "C" fn start_app() {
+            if let Err(err) = #fn_name().run_ios() {
+                eprintln!("App failed to run: {err}");
+            }
+        }
+
+        #wasm_main
+    };
+
+    expanded.into()
+}
+
+/// Registers a zero-argument component as a preview.
+///
+/// # Usage
+///
+/// Place it above `#[tessera]` on a function without parameters. Preview
+/// runners list the registered components with
+/// `tessera_ui::preview::previews` and render the selected one on its own.
+///
+/// ```rust,ignore
+/// #[preview(name = "Filled button", size = (200, 80), theme = dark)]
+/// #[tessera]
+/// fn filled_button_preview() {
+///     button(|| text("Save"));
+/// }
+/// ```
+///
+/// # Parameters
+///
+/// * `name = "..."` – name shown by the runner; defaults to the function name.
+/// * `size = (width, height)` – size to render at, in dp.
+/// * `theme = light` or `theme = dark` – theme the runner applies.
+/// * `crate_path = path` – path of `tessera_ui`, for use inside the crate.
+#[proc_macro_attribute]
+pub fn preview(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as PreviewMacroArgs);
+    let input_fn = parse_macro_input!(item as ItemFn);
+
+    if !input_fn.sig.inputs.is_empty() || !input_fn.sig.generics.params.is_empty() {
+        return syn::Error::new_spanned(
+            &input_fn.sig,
+            "#[preview] components must not take parameters or generics",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let crate_path = args
+        .crate_path
+        .unwrap_or_else(|| syn::parse_quote!(::tessera_ui));
+    let fn_name = &input_fn.sig.ident;
+    let name = args
+        .name
+        .unwrap_or_else(|| syn::LitStr::new(&fn_name.to_string(), fn_name.span()));
+    let size = match args.size {
+        Some((width, height)) => {
+            let width = proc_macro2::Literal::f64_suffixed(width);
+            let height = proc_macro2::Literal::f64_suffixed(height);
+            quote! {
+                ::core::option::Option::Some((#crate_path::Dp(#width), #crate_path::Dp(#height)))
+            }
+        }
+        None => quote!(::core::option::Option::None),
+    };
+    let theme = match args.theme {
+        Some(theme) => {
+            let variant = if theme == "dark" {
+                format_ident!("Dark", span = theme.span())
+            } else {
+                format_ident!("Light", span = theme.span())
+            };
+            quote!(::core::option::Option::Some(#crate_path::preview::PreviewTheme::#variant))
+        }
+        None => quote!(::core::option::Option::None),
+    };
+
+    let expanded = quote! {
+        #input_fn
+
+        #crate_path::__private::inventory::submit! {
+            #crate_path::__private::preview(
+                #name,
+                module_path!(),
+                #size,
+                #theme,
+                #fn_name,
+            )
+        }
     };
 
     expanded.into()
@@ -1605,6 +2597,25 @@ pub fn entry(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// Route-scoped state is removed on route pop/clear. Scope-scoped state is
 /// removed when the hosting `shard_home` is dropped.
 ///
+/// # Transitions
+///
+/// `#[shard(transition = RouterTransition::slide())]` sets the animation
+/// played when the destination is pushed or popped. Without it the router
+/// controller's default transition is used.
+///
+/// # Saving
+///
+/// `#[shard(saveable)]` derives `Serialize` and `Deserialize` for the
+/// destination and implements `tessera_shard::router::SerializableDestination`,
+/// so `RouterController::save_state` can save it. Every parameter type must
+/// be serializable. Combined with `state = T`, the shard state is saved as
+/// well and `T` must be serializable too.
+///
+/// # Errors
+///
+/// Like `#[tessera]` components, a shard may return `Result<(), E>` and use
+/// `?` in its body; an `Err` is reported to the nearest `error_boundary`.
+///
 /// # Parameter Transformation
 ///
 /// * Function parameters are treated as explicit destination props.
@@ -1724,8 +2735,53 @@ pub fn shard(attr: TokenStream, input: TokenStream) -> TokenStream {
         None => quote! { #shard_crate_path::router::ShardStateLifeCycle::Shard },
     };
 
+    let transition_method = shard_args.transition.map_or_else(
+        proc_macro2::TokenStream::new,
+        |transition| {
+            quote! {
+                fn transition(&self) -> ::core::option::Option<#shard_crate_path::router::RouterTransition> {
+                    ::core::option::Option::Some(#transition)
+                }
+            }
+        },
+    );
+
+    let saveable = shard_args.saveable;
+    let serde_crate_path = format!("{}::__private::serde", quote!(#shard_crate_path));
+    let (destination_derives, save_method) = if saveable {
+        (
+            quote! {
+                #[derive(
+                    #shard_crate_path::__private::serde::Serialize,
+                    #shard_crate_path::__private::serde::Deserialize,
+                )]
+                #[serde(crate = #serde_crate_path)]
+            },
+            quote! {
+                fn save(&self) -> ::core::option::Option<#shard_crate_path::router::SavedDestination> {
+                    #shard_crate_path::router::SavedDestination::new(self)
+                }
+            },
+        )
+    } else {
+        (
+            proc_macro2::TokenStream::new(),
+            proc_macro2::TokenStream::new(),
+        )
+    };
+    let shard_state_accessor = if saveable {
+        quote! { with_current_router_saveable_shard_state }
+    } else {
+        quote! { with_current_router_shard_state }
+    };
+
     let func_body = func.block;
     let func_name_str = func.sig.ident.to_string();
+    // Keeps `?` in a fallible body typed inside the shard state closure.
+    let func_body_output = match &func.sig.output {
+        syn::ReturnType::Default => proc_macro2::TokenStream::new(),
+        syn::ReturnType::Type(arrow, ty) => quote!(#arrow #ty),
+    };
 
     let func_attrs = &func.attrs;
     let func_vis = &func.vis;
@@ -1769,6 +2825,13 @@ pub fn shard(attr: TokenStream, input: TokenStream) -> TokenStream {
         &format!("{}Destination", func_name_str.to_upper_camel_case()),
         func_name.span(),
     );
+    let serializable_impl = if saveable {
+        quote! {
+            impl #shard_crate_path::router::SerializableDestination for #struct_name {}
+        }
+    } else {
+        proc_macro2::TokenStream::new()
+    };
     // Generate fields for the new struct that will implement `RouterDestination`
     let dest_fields = shard_params
         .iter()
@@ -1803,10 +2866,13 @@ pub fn shard(attr: TokenStream, input: TokenStream) -> TokenStream {
         if let Some(state_type) = state_type {
             quote! {
                 #[derive(Clone, PartialEq)]
+                #destination_derives
                 #func_vis struct #struct_name {
                     #(#dest_fields),*
                 }
 
+                #serializable_impl
+
                 impl #shard_crate_path::router::RouterDestination for #struct_name {
                     fn exec_component(&self) {
                         let mut __tessera_builder = #func_name();
@@ -1817,6 +2883,14 @@ pub fn shard(attr: TokenStream, input: TokenStream) -> TokenStream {
                     fn destination_id() -> &'static str {
                         concat!(module_path!(), "::", #func_name_str)
                     }
+
+                    fn shard_id(&self) -> &'static str {
+                        concat!(module_path!(), "::", #func_name_str)
+                    }
+
+                    #transition_method
+
+                    #save_method
                 }
 
                 #(#func_attrs)*
@@ -1825,11 +2899,11 @@ pub fn shard(attr: TokenStream, input: TokenStream) -> TokenStream {
                     const SHARD_ID: &str = concat!(module_path!(), "::", #func_name_str);
                     let __router_controller = #router_controller_resolution;
                     #router_binding
-                    #shard_crate_path::__private::with_current_router_shard_state::<#state_type, _, _>(
+                    #shard_crate_path::__private::#shard_state_accessor::<#state_type, _, _>(
                         SHARD_ID,
                         #state_lifecycle_tokens,
                         __router_controller,
-                        |state| {
+                        |state| #func_body_output {
                             #func_body
                         },
                     )
@@ -1838,10 +2912,13 @@ pub fn shard(attr: TokenStream, input: TokenStream) -> TokenStream {
         } else {
             quote! {
                 #[derive(Clone, PartialEq)]
+                #destination_derives
                 #func_vis struct #struct_name {
                     #(#dest_fields),*
                 }
 
+                #serializable_impl
+
                 impl #shard_crate_path::router::RouterDestination for #struct_name {
                     fn exec_component(&self) {
                         let mut __tessera_builder = #func_name();
@@ -1852,6 +2929,14 @@ pub fn shard(attr: TokenStream, input: TokenStream) -> TokenStream {
                     fn destination_id() -> &'static str {
                         concat!(module_path!(), "::", #func_name_str)
                     }
+
+                    fn shard_id(&self) -> &'static str {
+                        concat!(module_path!(), "::", #func_name_str)
+                    }
+
+                    #transition_method
+
+                    #save_method
                 }
 
                 #(#func_attrs)*
@@ -0,0 +1,7 @@
+//! Diagnostics reported for common component authoring mistakes.
+
+#[test]
+fn ui() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
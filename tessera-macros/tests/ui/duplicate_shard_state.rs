@@ -0,0 +1,8 @@
+struct PageState;
+
+#[tessera_macros::shard(state = PageState, state = PageState)]
+fn page() {}
+
+fn main() {
+    let _ = PageState;
+}
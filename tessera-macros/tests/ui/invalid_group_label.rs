@@ -0,0 +1,7 @@
+#[tessera_macros::tessera]
+fn panel() {
+    #[group(42)]
+    if true {}
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+struct Counter;
+
+impl Counter {
+    #[tessera_macros::tessera]
+    fn render(&self) {}
+}
+
+fn main() {
+    let _ = Counter;
+}
@@ -0,0 +1,4 @@
+#[tessera_macros::tessera]
+fn counter(value: &mut u32) {}
+
+fn main() {}
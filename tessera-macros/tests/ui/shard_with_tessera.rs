@@ -0,0 +1,5 @@
+#[tessera_macros::shard]
+#[tessera_macros::tessera]
+fn page() {}
+
+fn main() {}
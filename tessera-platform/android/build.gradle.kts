@@ -22,4 +22,5 @@ android {
 }
 
 dependencies {
+    implementation("androidx.core:core:1.13.1")
 }
@@ -0,0 +1,304 @@
+package com.tessera.platform
+
+import android.Manifest
+import android.annotation.SuppressLint
+import android.app.Activity
+import android.content.Context
+import android.content.pm.PackageManager
+import android.graphics.ImageFormat
+import android.hardware.camera2.CameraCaptureSession
+import android.hardware.camera2.CameraCharacteristics
+import android.hardware.camera2.CameraDevice
+import android.hardware.camera2.CameraManager
+import android.hardware.camera2.CaptureRequest
+import android.media.Image
+import android.media.ImageReader
+import android.os.Handler
+import android.os.HandlerThread
+import android.util.Size
+import android.view.Surface
+import java.nio.ByteBuffer
+import java.nio.ByteOrder
+import kotlin.math.abs
+
+object CameraPlugin {
+    private const val OPENED = 0
+    private const val PERMISSION_DENIED = 1
+    private const val NOT_FOUND = 2
+    private const val FAILED = 3
+
+    // Frames start with width, height, rotation and flags as little-endian
+    // ints, followed by tightly packed I420 planes.
+    private const val HEADER_SIZE = 16
+    private const val FLAG_MIRRORED = 1
+
+    private var generation = 0
+    private var thread: HandlerThread? = null
+    private var handler: Handler? = null
+    private var device: CameraDevice? = null
+    private var session: CameraCaptureSession? = null
+    private var previewReader: ImageReader? = null
+    private var stillReader: ImageReader? = null
+    private var sensorOrientation = 0
+    private var frontFacing = false
+
+    @Volatile
+    private var displayRotation = 0
+
+    @Volatile
+    private var latestFrame: ByteArray? = null
+
+    @Volatile
+    private var latestCapture: ByteArray? = null
+
+    @Volatile
+    private var lastError: String? = null
+
+    @SuppressLint("MissingPermission")
+    @JvmStatic
+    @Synchronized
+    fun open(activity: Activity, facing: String, width: Int, height: Int): Int {
+        close(activity)
+        if (activity.checkSelfPermission(Manifest.permission.CAMERA) !=
+            PackageManager.PERMISSION_GRANTED
+        ) {
+            return PERMISSION_DENIED
+        }
+        val manager = activity.getSystemService(Context.CAMERA_SERVICE) as? CameraManager
+            ?: return NOT_FOUND
+        val lensFacing = if (facing == "front") {
+            CameraCharacteristics.LENS_FACING_FRONT
+        } else {
+            CameraCharacteristics.LENS_FACING_BACK
+        }
+        return try {
+            val id = manager.cameraIdList.firstOrNull {
+                manager.getCameraCharacteristics(it).get(CameraCharacteristics.LENS_FACING) ==
+                    lensFacing
+            } ?: return NOT_FOUND
+            val characteristics = manager.getCameraCharacteristics(id)
+            val configs = characteristics.get(CameraCharacteristics.SCALER_STREAM_CONFIGURATION_MAP)
+                ?: return FAILED
+            val previewSize = closestSize(
+                configs.getOutputSizes(ImageFormat.YUV_420_888),
+                width,
+                height,
+            ) ?: return FAILED
+            val stillSize = configs.getOutputSizes(ImageFormat.JPEG)
+                .maxByOrNull { it.width.toLong() * it.height } ?: return FAILED
+            sensorOrientation = characteristics.get(CameraCharacteristics.SENSOR_ORIENTATION) ?: 0
+            frontFacing = lensFacing == CameraCharacteristics.LENS_FACING_FRONT
+            displayRotation = displayDegrees(activity)
+
+            val thread = HandlerThread("TesseraCamera").also { it.start() }
+            val handler = Handler(thread.looper)
+            this.thread = thread
+            this.handler = handler
+            val previewReader = ImageReader.newInstance(
+                previewSize.width,
+                previewSize.height,
+                ImageFormat.YUV_420_888,
+                2,
+            )
+            previewReader.setOnImageAvailableListener({ reader ->
+                reader.acquireLatestImage()?.use { latestFrame = packFrame(it) }
+            }, handler)
+            val stillReader = ImageReader.newInstance(
+                stillSize.width,
+                stillSize.height,
+                ImageFormat.JPEG,
+                1,
+            )
+            stillReader.setOnImageAvailableListener({ reader ->
+                reader.acquireLatestImage()?.use { image ->
+                    val buffer = image.planes[0].buffer
+                    latestCapture = ByteArray(buffer.remaining()).also { buffer.get(it) }
+                }
+            }, handler)
+            this.previewReader = previewReader
+            this.stillReader = stillReader
+            manager.openCamera(id, deviceCallback(generation), handler)
+            OPENED
+        } catch (error: Exception) {
+            close(activity)
+            lastError = error.toString()
+            FAILED
+        }
+    }
+
+    @JvmStatic
+    @Synchronized
+    fun close(activity: Activity) {
+        generation += 1
+        session?.close()
+        device?.close()
+        previewReader?.close()
+        stillReader?.close()
+        thread?.quitSafely()
+        session = null
+        device = null
+        previewReader = null
+        stillReader = null
+        thread = null
+        handler = null
+        latestFrame = null
+        latestCapture = null
+    }
+
+    @JvmStatic
+    fun takeFrame(activity: Activity): ByteArray {
+        displayRotation = displayDegrees(activity)
+        val frame = latestFrame ?: return ByteArray(0)
+        latestFrame = null
+        return frame
+    }
+
+    @JvmStatic
+    @Synchronized
+    fun capture(activity: Activity): Boolean {
+        val session = session ?: return false
+        val device = device ?: return false
+        val surface = stillReader?.surface ?: return false
+        return try {
+            val request = device.createCaptureRequest(CameraDevice.TEMPLATE_STILL_CAPTURE).apply {
+                addTarget(surface)
+                set(CaptureRequest.JPEG_ORIENTATION, frameRotation())
+            }
+            session.capture(request.build(), null, handler)
+            true
+        } catch (error: Exception) {
+            lastError = error.toString()
+            false
+        }
+    }
+
+    @JvmStatic
+    fun takeCapture(activity: Activity): ByteArray {
+        val capture = latestCapture ?: return ByteArray(0)
+        latestCapture = null
+        return capture
+    }
+
+    @JvmStatic
+    fun takeError(activity: Activity): String {
+        val error = lastError ?: return ""
+        lastError = null
+        return error
+    }
+
+    private fun deviceCallback(openGeneration: Int) = object : CameraDevice.StateCallback() {
+        override fun onOpened(camera: CameraDevice) {
+            synchronized(this@CameraPlugin) {
+                val previewSurface = previewReader?.surface
+                val stillSurface = stillReader?.surface
+                // The camera was closed or reopened while this one was opening.
+                if (openGeneration != generation || previewSurface == null || stillSurface == null) {
+                    camera.close()
+                    return
+                }
+                device = camera
+                try {
+                    @Suppress("DEPRECATION")
+                    camera.createCaptureSession(
+                        listOf(previewSurface, stillSurface),
+                        sessionCallback(openGeneration, previewSurface),
+                        handler,
+                    )
+                } catch (error: Exception) {
+                    lastError = error.toString()
+                }
+            }
+        }
+
+        override fun onDisconnected(camera: CameraDevice) {
+            camera.close()
+            lastError = "camera disconnected"
+        }
+
+        override fun onError(camera: CameraDevice, error: Int) {
+            camera.close()
+            lastError = "camera error $error"
+        }
+    }
+
+    private fun sessionCallback(openGeneration: Int, previewSurface: Surface) =
+        object : CameraCaptureSession.StateCallback() {
+            override fun onConfigured(captureSession: CameraCaptureSession) {
+                synchronized(this@CameraPlugin) {
+                    if (openGeneration != generation) {
+                        captureSession.close()
+                        return
+                    }
+                    session = captureSession
+                    try {
+                        val request = captureSession.device
+                            .createCaptureRequest(CameraDevice.TEMPLATE_PREVIEW)
+                            .apply {
+                                addTarget(previewSurface)
+                                set(
+                                    CaptureRequest.CONTROL_AF_MODE,
+                                    CaptureRequest.CONTROL_AF_MODE_CONTINUOUS_PICTURE,
+                                )
+                            }
+                        captureSession.setRepeatingRequest(request.build(), null, handler)
+                    } catch (error: Exception) {
+                        lastError = error.toString()
+                    }
+                }
+            }
+
+            override fun onConfigureFailed(captureSession: CameraCaptureSession) {
+                lastError = "camera session configuration failed"
+            }
+        }
+
+    // Clockwise degrees that turn sensor images upright on the current display.
+    private fun frameRotation(): Int = if (frontFacing) {
+        (sensorOrientation + displayRotation) % 360
+    } else {
+        (sensorOrientation - displayRotation + 360) % 360
+    }
+
+    private fun packFrame(image: Image): ByteArray {
+        val width = image.width
+        val height = image.height
+        val frame = ByteArray(HEADER_SIZE + width * height * 3 / 2)
+        ByteBuffer.wrap(frame, 0, HEADER_SIZE)
+            .order(ByteOrder.LITTLE_ENDIAN)
+            .putInt(width)
+            .putInt(height)
+            .putInt(frameRotation())
+            .putInt(if (frontFacing) FLAG_MIRRORED else 0)
+        var offset = HEADER_SIZE
+        for ((index, plane) in image.planes.withIndex()) {
+            val planeWidth = if (index == 0) width else width / 2
+            val planeHeight = if (index == 0) height else height / 2
+            val buffer = plane.buffer
+            for (row in 0 until planeHeight) {
+                val rowStart = row * plane.rowStride
+                if (plane.pixelStride == 1) {
+                    buffer.position(rowStart)
+                    buffer.get(frame, offset, planeWidth)
+                    offset += planeWidth
+                } else {
+                    for (column in 0 until planeWidth) {
+                        frame[offset++] = buffer.get(rowStart + column * plane.pixelStride)
+                    }
+                }
+            }
+        }
+        return frame
+    }
+
+    private fun closestSize(sizes: Array<Size>?, width: Int, height: Int): Size? =
+        sizes?.minByOrNull { abs(it.width - width) + abs(it.height - height) }
+
+    @Suppress("DEPRECATION")
+    private fun displayDegrees(activity: Activity): Int =
+        when (activity.windowManager.defaultDisplay.rotation) {
+            Surface.ROTATION_90 -> 90
+            Surface.ROTATION_180 -> 180
+            Surface.ROTATION_270 -> 270
+            else -> 0
+        }
+}
@@ -0,0 +1,119 @@
+package com.tessera.platform
+
+import android.Manifest
+import android.app.Activity
+import android.app.Notification
+import android.app.NotificationChannel
+import android.app.NotificationManager
+import android.app.PendingIntent
+import android.content.Intent
+import android.content.pm.PackageManager
+import android.net.Uri
+import android.os.Build
+
+object NotificationsPlugin {
+    private const val EXTRA_NOTIFICATION_ID = "com.tessera.platform.NOTIFICATION_ID"
+    private const val DEFAULT_CHANNEL_ID = "tessera_default"
+
+    @JvmStatic
+    fun createChannel(
+        activity: Activity,
+        id: String,
+        name: String,
+        description: String,
+        importance: Int,
+    ) {
+        if (Build.VERSION.SDK_INT < Build.VERSION_CODES.O) {
+            return
+        }
+        val manager = activity.getSystemService(NotificationManager::class.java) ?: return
+        val channel = NotificationChannel(id, name, importance)
+        if (description.isNotEmpty()) {
+            channel.description = description
+        }
+        manager.createNotificationChannel(channel)
+    }
+
+    @JvmStatic
+    fun requestPermission(activity: Activity) {
+        if (!hasPermission(activity)) {
+            activity.requestPermissions(arrayOf(Manifest.permission.POST_NOTIFICATIONS), 0)
+        }
+    }
+
+    @JvmStatic
+    fun show(
+        activity: Activity,
+        channelId: String,
+        id: Int,
+        title: String,
+        body: String,
+        deepLink: String,
+    ): Boolean {
+        val manager = activity.getSystemService(NotificationManager::class.java) ?: return false
+        if (!hasPermission(activity)) {
+            return false
+        }
+        val channel = channelId.ifEmpty { defaultChannel(activity) }
+
+        val intent = activity.packageManager.getLaunchIntentForPackage(activity.packageName)
+            ?: Intent(activity, activity.javaClass)
+        intent.flags = Intent.FLAG_ACTIVITY_SINGLE_TOP or Intent.FLAG_ACTIVITY_CLEAR_TOP
+        intent.putExtra(EXTRA_NOTIFICATION_ID, id)
+        if (deepLink.isNotEmpty()) {
+            intent.action = Intent.ACTION_VIEW
+            intent.data = Uri.parse(deepLink)
+        }
+        val contentIntent = PendingIntent.getActivity(
+            activity,
+            id,
+            intent,
+            PendingIntent.FLAG_UPDATE_CURRENT or PendingIntent.FLAG_IMMUTABLE,
+        )
+
+        val builder = if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.O) {
+            Notification.Builder(activity, channel)
+        } else {
+            @Suppress("DEPRECATION")
+            Notification.Builder(activity)
+        }
+        builder
+            .setSmallIcon(activity.applicationInfo.icon)
+            .setContentTitle(title)
+            .setContentIntent(contentIntent)
+            .setAutoCancel(true)
+        if (body.isNotEmpty()) {
+            builder.setContentText(body)
+        }
+        manager.notify(id, builder.build())
+        return true
+    }
+
+    @JvmStatic
+    fun takeClickedNotification(activity: Activity): Int {
+        val intent = activity.intent ?: return -1
+        val id = intent.getIntExtra(EXTRA_NOTIFICATION_ID, -1)
+        intent.removeExtra(EXTRA_NOTIFICATION_ID)
+        return id
+    }
+
+    private fun hasPermission(activity: Activity): Boolean {
+        if (Build.VERSION.SDK_INT < Build.VERSION_CODES.TIRAMISU) {
+            return true
+        }
+        return activity.checkSelfPermission(Manifest.permission.POST_NOTIFICATIONS) ==
+            PackageManager.PERMISSION_GRANTED
+    }
+
+    private fun defaultChannel(activity: Activity): String {
+        val label = activity.applicationInfo.loadLabel(activity.packageManager).toString()
+        createChannel(
+            activity,
+            DEFAULT_CHANNEL_ID,
+            label,
+            "",
+            NotificationManager.IMPORTANCE_DEFAULT,
+        )
+        return DEFAULT_CHANNEL_ID
+    }
+}
@@ -0,0 +1,96 @@
+package com.tessera.platform
+
+import android.Manifest
+import android.app.Activity
+import android.app.Application
+import android.content.pm.PackageManager
+import android.os.Build
+import android.os.Bundle
+
+object PermissionsPlugin {
+    private const val REQUEST_CODE = 0x7e55
+
+    @Volatile
+    private var requestFinished = false
+    private var lifecycleCallbacks: Application.ActivityLifecycleCallbacks? = null
+
+    @JvmStatic
+    fun isGranted(activity: Activity, permission: String): Boolean {
+        val names = manifestNames(permission)
+        return names.isEmpty() || names.any {
+            activity.checkSelfPermission(it) == PackageManager.PERMISSION_GRANTED
+        }
+    }
+
+    @JvmStatic
+    fun request(activity: Activity, permissions: String): Boolean {
+        val missing = permissions.lines()
+            .filter { it.isNotEmpty() && !isGranted(activity, it) }
+            .flatMap { manifestNames(it) }
+            .distinct()
+        if (missing.isEmpty()) {
+            return false
+        }
+        watchForResume(activity.application)
+        activity.requestPermissions(missing.toTypedArray(), REQUEST_CODE)
+        return true
+    }
+
+    @JvmStatic
+    fun takeRequestFinished(activity: Activity): Boolean {
+        if (!requestFinished) {
+            return false
+        }
+        requestFinished = false
+        return true
+    }
+
+    // The permission dialog pauses the activity, so the next resume means the
+    // user answered it.
+    @Synchronized
+    private fun watchForResume(application: Application) {
+        lifecycleCallbacks?.let { application.unregisterActivityLifecycleCallbacks(it) }
+        requestFinished = false
+        val callbacks = object : Application.ActivityLifecycleCallbacks {
+            override fun onActivityResumed(activity: Activity) {
+                application.unregisterActivityLifecycleCallbacks(this)
+                synchronized(this@PermissionsPlugin) {
+                    if (lifecycleCallbacks === this) {
+                        lifecycleCallbacks = null
+                    }
+                }
+                requestFinished = true
+            }
+
+            override fun onActivityCreated(activity: Activity, savedInstanceState: Bundle?) {}
+            override fun onActivityStarted(activity: Activity) {}
+            override fun onActivityPaused(activity: Activity) {}
+            override fun onActivityStopped(activity: Activity) {}
+            override fun onActivitySaveInstanceState(activity: Activity, outState: Bundle) {}
+            override fun onActivityDestroyed(activity: Activity) {}
+        }
+        lifecycleCallbacks = callbacks
+        application.registerActivityLifecycleCallbacks(callbacks)
+    }
+
+    private fun manifestNames(permission: String): List<String> = when (permission) {
+        "camera" -> listOf(Manifest.permission.CAMERA)
+        "microphone" -> listOf(Manifest.permission.RECORD_AUDIO)
+        "location" -> listOf(
+            Manifest.permission.ACCESS_FINE_LOCATION,
+            Manifest.permission.ACCESS_COARSE_LOCATION,
+        )
+        "contacts" -> listOf(Manifest.permission.READ_CONTACTS)
+        "notifications" -> if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.TIRAMISU) {
+            listOf(Manifest.permission.POST_NOTIFICATIONS)
+        } else {
+            emptyList()
+        }
+        "media" -> if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.TIRAMISU) {
+            listOf(Manifest.permission.READ_MEDIA_IMAGES, Manifest.permission.READ_MEDIA_VIDEO)
+        } else {
+            listOf(Manifest.permission.READ_EXTERNAL_STORAGE)
+        }
+        else -> listOf(permission)
+    }
+}
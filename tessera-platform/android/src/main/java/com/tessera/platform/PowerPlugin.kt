@@ -0,0 +1,45 @@
+package com.tessera.platform
+
+import android.app.Activity
+import android.content.Context
+import android.content.Intent
+import android.content.IntentFilter
+import android.os.BatteryManager
+import android.os.PowerManager
+
+object PowerPlugin {
+    @JvmStatic
+    fun batteryLevel(activity: Activity): Float {
+        val battery = batteryIntent(activity) ?: return -1f
+        if (!battery.getBooleanExtra(BatteryManager.EXTRA_PRESENT, false)) {
+            return -1f
+        }
+        val level = battery.getIntExtra(BatteryManager.EXTRA_LEVEL, -1)
+        val scale = battery.getIntExtra(BatteryManager.EXTRA_SCALE, -1)
+        if (level < 0 || scale <= 0) {
+            return -1f
+        }
+        return level.toFloat() / scale
+    }
+
+    @JvmStatic
+    fun batteryStatus(activity: Activity): Int {
+        val battery = batteryIntent(activity) ?: return BatteryManager.BATTERY_STATUS_UNKNOWN
+        return battery.getIntExtra(
+            BatteryManager.EXTRA_STATUS,
+            BatteryManager.BATTERY_STATUS_UNKNOWN,
+        )
+    }
+
+    @JvmStatic
+    fun isPowerSaveMode(activity: Activity): Boolean {
+        val power = activity.getSystemService(Context.POWER_SERVICE) as? PowerManager
+            ?: return false
+        return power.isPowerSaveMode
+    }
+
+    // The battery broadcast is sticky, so registering without a receiver
+    // returns the latest status.
+    private fun batteryIntent(activity: Activity): Intent? =
+        activity.registerReceiver(null, IntentFilter(Intent.ACTION_BATTERY_CHANGED))
+}
@@ -0,0 +1,68 @@
+package com.tessera.platform
+
+import android.app.Activity
+import android.content.ActivityNotFoundException
+import android.content.Intent
+import android.net.Uri
+import androidx.core.content.FileProvider
+import java.io.File
+
+object SharePlugin {
+    private const val AUTHORITY_SUFFIX = ".tessera.fileprovider"
+
+    @JvmStatic
+    fun share(activity: Activity, title: String, text: String, paths: String): Boolean {
+        val uris = try {
+            paths.lines().filter { it.isNotEmpty() }.map { contentUri(activity, it) }
+        } catch (_: IllegalArgumentException) {
+            // The file is outside the directories the provider exposes.
+            return false
+        }
+        val intent = when (uris.size) {
+            0 -> Intent(Intent.ACTION_SEND).setType("text/plain")
+            1 -> Intent(Intent.ACTION_SEND)
+                .setType(mimeType(activity, uris))
+                .putExtra(Intent.EXTRA_STREAM, uris[0])
+            else -> Intent(Intent.ACTION_SEND_MULTIPLE)
+                .setType(mimeType(activity, uris))
+                .putParcelableArrayListExtra(Intent.EXTRA_STREAM, ArrayList(uris))
+        }
+        if (text.isNotEmpty()) {
+            intent.putExtra(Intent.EXTRA_TEXT, text)
+        }
+        if (title.isNotEmpty()) {
+            intent.putExtra(Intent.EXTRA_SUBJECT, title)
+        }
+        intent.addFlags(Intent.FLAG_GRANT_READ_URI_PERMISSION)
+        return start(activity, Intent.createChooser(intent, title.ifEmpty { null }))
+    }
+
+    @JvmStatic
+    fun openWith(activity: Activity, path: String): Boolean {
+        val uri = try {
+            contentUri(activity, path)
+        } catch (_: IllegalArgumentException) {
+            return false
+        }
+        val intent = Intent(Intent.ACTION_VIEW)
+            .setDataAndType(uri, activity.contentResolver.getType(uri) ?: "*/*")
+            .addFlags(Intent.FLAG_GRANT_READ_URI_PERMISSION)
+        return start(activity, Intent.createChooser(intent, null))
+    }
+
+    private fun contentUri(activity: Activity, path: String): Uri =
+        FileProvider.getUriForFile(activity, activity.packageName + AUTHORITY_SUFFIX, File(path))
+
+    private fun mimeType(activity: Activity, uris: List<Uri>): String {
+        val types = uris.map { activity.contentResolver.getType(it) ?: "*/*" }.distinct()
+        return types.singleOrNull() ?: "*/*"
+    }
+
+    private fun start(activity: Activity, intent: Intent): Boolean =
+        try {
+            activity.startActivity(intent)
+            true
+        } catch (_: ActivityNotFoundException) {
+            false
+        }
+}
@@ -0,0 +1,394 @@
+//! Camera streaming for Tessera platform plugins.
+//!
+//! ## Usage
+//!
+//! Stream preview frames from the front or back camera and take still
+//! pictures, e.g. for a document scanner or a profile photo.
+//!
+//! Only Android is supported so far; [`open_camera`] returns
+//! [`CameraError::Unsupported`] elsewhere. Request
+//! [`Permission::Camera`](crate::permissions::Permission::Camera) before
+//! opening a camera, and declare `android.permission.CAMERA` in the app
+//! manifest.
+use std::{
+    fmt,
+    sync::{
+        Arc, OnceLock,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use parking_lot::RwLock;
+use tessera_ui::{Plugin, PluginContext, PluginResult};
+
+#[cfg(target_os = "android")]
+use tracing::warn;
+
+#[cfg(target_os = "android")]
+use tessera_ui::android::{ActivityRef, activity};
+#[cfg(target_os = "android")]
+use tessera_ui::winit::platform::android::activity::AndroidApp;
+
+#[cfg(target_os = "android")]
+tessera_ui::android::jni_bind! {
+    class "com.tessera.platform.CameraPlugin" as CameraPluginJni {
+        /// Opens the first camera facing `"back"` or `"front"`, streaming
+        /// frames close to the requested size. Returns a status code.
+        fn open(activity: ActivityRef, facing: &str, width: i32, height: i32) -> i32;
+        /// Closes the open camera.
+        fn close(activity: ActivityRef) -> ();
+        /// Returns the newest preview frame since the previous call, or an
+        /// empty array.
+        fn takeFrame(activity: ActivityRef) -> Vec<u8>;
+        /// Starts a still capture, returning whether it started.
+        fn capture(activity: ActivityRef) -> bool;
+        /// Returns the newest finished still as JPEG, or an empty array.
+        fn takeCapture(activity: ActivityRef) -> Vec<u8>;
+        /// Returns the last asynchronous camera error, or an empty string.
+        fn takeError(activity: ActivityRef) -> String;
+    }
+}
+
+/// Camera plugin that reopens the active camera when the app resumes.
+#[derive(Clone, Debug)]
+pub struct CameraPlugin;
+
+impl CameraPlugin {
+    /// Creates a camera plugin.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for CameraPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Plugin for CameraPlugin {
+    fn on_resumed(&mut self, context: &PluginContext) -> PluginResult {
+        #[cfg(target_os = "android")]
+        {
+            let mut state = camera_state().write();
+            state.android_app = Some(context.android_app().clone());
+            if let Some(active) = state.active
+                && let Err(err) = state.open_platform(active.facing, active.size)
+            {
+                warn!("Failed to reopen the camera: {err}");
+            }
+        }
+        #[cfg(not(target_os = "android"))]
+        let _ = context;
+        Ok(())
+    }
+
+    fn on_suspended(&mut self, _context: &PluginContext) -> PluginResult {
+        // Android takes the camera away from background apps, so release it
+        // and keep the session to reopen on resume.
+        camera_state().write().release_platform();
+        Ok(())
+    }
+
+    fn on_shutdown(&mut self, _context: &PluginContext) -> PluginResult {
+        let mut state = camera_state().write();
+        state.release_platform();
+        state.active = None;
+        Ok(())
+    }
+}
+
+/// Which way a camera faces.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CameraFacing {
+    /// The main camera on the back of the device.
+    #[default]
+    Back,
+    /// The selfie camera facing the user.
+    Front,
+}
+
+/// Errors returned while opening or using a camera.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CameraError {
+    /// This platform has no camera support yet.
+    Unsupported,
+    /// The app has not resumed yet, or is in the background.
+    Unavailable,
+    /// The app does not hold the camera permission.
+    PermissionDenied,
+    /// No camera faces the requested way.
+    NotFound,
+    /// The platform camera API failed.
+    Platform(String),
+}
+
+impl fmt::Display for CameraError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsupported => write!(f, "cameras are not supported on this platform"),
+            Self::Unavailable => write!(f, "the camera is unavailable while the app is inactive"),
+            Self::PermissionDenied => write!(f, "the camera permission is not granted"),
+            Self::NotFound => write!(f, "no camera faces the requested way"),
+            Self::Platform(message) => write!(f, "camera error: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for CameraError {}
+
+/// A preview frame in the camera's sensor orientation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CameraFrame {
+    /// Frame width in pixels.
+    pub width: u32,
+    /// Frame height in pixels.
+    pub height: u32,
+    /// Clockwise rotation in degrees (0, 90, 180 or 270) that turns the frame
+    /// upright on the current display.
+    pub rotation: u32,
+    /// Whether the upright frame should be flipped horizontally so it looks
+    /// like a mirror, as for front cameras.
+    pub mirrored: bool,
+    /// I420 planes: full-resolution Y, then half-resolution U and V.
+    pub i420: Vec<u8>,
+}
+
+impl CameraFrame {
+    #[cfg(target_os = "android")]
+    fn parse(bytes: Vec<u8>) -> Option<Self> {
+        const HEADER_SIZE: usize = 16;
+        let int = |index: usize| {
+            let start = index * 4;
+            bytes
+                .get(start..start + 4)
+                .and_then(|bytes| bytes.try_into().ok())
+                .map(u32::from_le_bytes)
+        };
+        let (width, height, rotation, flags) = (int(0)?, int(1)?, int(2)?, int(3)?);
+        let planes = width as usize * height as usize * 3 / 2;
+        if bytes.len() != HEADER_SIZE + planes {
+            return None;
+        }
+        Some(Self {
+            width,
+            height,
+            rotation,
+            mirrored: flags & 1 != 0,
+            i420: bytes[HEADER_SIZE..].to_vec(),
+        })
+    }
+}
+
+/// An open camera, closed when dropped.
+///
+/// Opening another camera replaces this one, after which it yields no more
+/// frames.
+#[derive(Debug)]
+pub struct CameraSession {
+    id: u64,
+}
+
+/// Opens the camera facing `facing`, streaming preview frames close to
+/// `preferred_width` by `preferred_height` in sensor orientation.
+///
+/// Only one camera is open at a time. May be called from any thread.
+pub fn open_camera(
+    facing: CameraFacing,
+    preferred_width: u32,
+    preferred_height: u32,
+) -> Result<CameraSession, CameraError> {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+    let size = (preferred_width, preferred_height);
+    let mut state = camera_state().write();
+    state.open_platform(facing, size)?;
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    state.active = Some(ActiveCamera { id, facing, size });
+    Ok(CameraSession { id })
+}
+
+impl CameraSession {
+    /// Returns the newest preview frame since the previous call, if any.
+    pub fn take_frame(&self) -> Option<CameraFrame> {
+        camera_state().read().take_frame_platform(self.id)
+    }
+
+    /// Starts taking a still picture; fetch it with
+    /// [`take_capture`](Self::take_capture).
+    pub fn capture(&self) -> Result<(), CameraError> {
+        camera_state().read().capture_platform(self.id)
+    }
+
+    /// Returns the newest finished still picture as JPEG bytes, if any.
+    ///
+    /// The picture is upright, either in its pixels or through its EXIF
+    /// orientation.
+    pub fn take_capture(&self) -> Option<Vec<u8>> {
+        camera_state().read().take_capture_platform(self.id)
+    }
+
+    /// Returns the last error reported by the camera since the previous call.
+    pub fn take_error(&self) -> Option<CameraError> {
+        camera_state().read().take_error_platform(self.id)
+    }
+}
+
+impl Drop for CameraSession {
+    fn drop(&mut self) {
+        let mut state = camera_state().write();
+        if state.active.is_some_and(|active| active.id == self.id) {
+            state.release_platform();
+            state.active = None;
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(not(target_os = "android"), allow(dead_code))]
+struct ActiveCamera {
+    id: u64,
+    facing: CameraFacing,
+    size: (u32, u32),
+}
+
+#[derive(Default)]
+struct CameraState {
+    #[cfg(target_os = "android")]
+    android_app: Option<AndroidApp>,
+    active: Option<ActiveCamera>,
+}
+
+impl CameraState {
+    #[cfg_attr(not(target_os = "android"), allow(dead_code))]
+    fn is_active(&self, id: u64) -> bool {
+        self.active.is_some_and(|active| active.id == id)
+    }
+
+    #[cfg(target_os = "android")]
+    fn release_platform(&mut self) {
+        if let (Some(android_app), Some(_)) = (self.android_app.take(), self.active)
+            && let Err(err) = CameraPluginJni::close(&android_app, activity(&android_app))
+        {
+            warn!("Android camera close failed: {err}");
+        }
+    }
+
+    #[cfg(not(target_os = "android"))]
+    fn release_platform(&mut self) {}
+
+    #[cfg(target_os = "android")]
+    fn open_platform(&self, facing: CameraFacing, size: (u32, u32)) -> Result<(), CameraError> {
+        let android_app = self.android_app.as_ref().ok_or(CameraError::Unavailable)?;
+        let facing = match facing {
+            CameraFacing::Back => "back",
+            CameraFacing::Front => "front",
+        };
+        let clamp = |value: u32| i32::try_from(value).unwrap_or(i32::MAX);
+        let status = CameraPluginJni::open(
+            android_app,
+            activity(android_app),
+            facing,
+            clamp(size.0),
+            clamp(size.1),
+        )
+        .map_err(|err| CameraError::Platform(err.to_string()))?;
+        // Status codes from `CameraPlugin.kt`.
+        match status {
+            0 => Ok(()),
+            1 => Err(CameraError::PermissionDenied),
+            2 => Err(CameraError::NotFound),
+            _ => Err(self
+                .take_error_platform_unchecked()
+                .unwrap_or_else(|| CameraError::Platform("failed to open camera".to_string()))),
+        }
+    }
+
+    #[cfg(not(target_os = "android"))]
+    fn open_platform(&self, _facing: CameraFacing, _size: (u32, u32)) -> Result<(), CameraError> {
+        Err(CameraError::Unsupported)
+    }
+
+    #[cfg(target_os = "android")]
+    fn take_frame_platform(&self, id: u64) -> Option<CameraFrame> {
+        let android_app = self.android_app.as_ref().filter(|_| self.is_active(id))?;
+        match CameraPluginJni::takeFrame(android_app, activity(android_app)) {
+            Ok(bytes) if bytes.is_empty() => None,
+            Ok(bytes) => CameraFrame::parse(bytes),
+            Err(err) => {
+                warn!("Android camera frame lookup failed: {err}");
+                None
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "android"))]
+    fn take_frame_platform(&self, _id: u64) -> Option<CameraFrame> {
+        None
+    }
+
+    #[cfg(target_os = "android")]
+    fn capture_platform(&self, id: u64) -> Result<(), CameraError> {
+        let android_app = self
+            .android_app
+            .as_ref()
+            .filter(|_| self.is_active(id))
+            .ok_or(CameraError::Unavailable)?;
+        match CameraPluginJni::capture(android_app, activity(android_app)) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(self
+                .take_error_platform_unchecked()
+                .unwrap_or(CameraError::Unavailable)),
+            Err(err) => Err(CameraError::Platform(err.to_string())),
+        }
+    }
+
+    #[cfg(not(target_os = "android"))]
+    fn capture_platform(&self, _id: u64) -> Result<(), CameraError> {
+        Err(CameraError::Unsupported)
+    }
+
+    #[cfg(target_os = "android")]
+    fn take_capture_platform(&self, id: u64) -> Option<Vec<u8>> {
+        let android_app = self.android_app.as_ref().filter(|_| self.is_active(id))?;
+        match CameraPluginJni::takeCapture(android_app, activity(android_app)) {
+            Ok(bytes) => (!bytes.is_empty()).then_some(bytes),
+            Err(err) => {
+                warn!("Android camera capture lookup failed: {err}");
+                None
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "android"))]
+    fn take_capture_platform(&self, _id: u64) -> Option<Vec<u8>> {
+        None
+    }
+
+    #[cfg(target_os = "android")]
+    fn take_error_platform(&self, id: u64) -> Option<CameraError> {
+        if !self.is_active(id) {
+            return None;
+        }
+        self.take_error_platform_unchecked()
+    }
+
+    #[cfg(target_os = "android")]
+    fn take_error_platform_unchecked(&self) -> Option<CameraError> {
+        let android_app = self.android_app.as_ref()?;
+        match CameraPluginJni::takeError(android_app, activity(android_app)) {
+            Ok(message) if message.is_empty() => None,
+            Ok(message) => Some(CameraError::Platform(message)),
+            Err(err) => Some(CameraError::Platform(err.to_string())),
+        }
+    }
+
+    #[cfg(not(target_os = "android"))]
+    fn take_error_platform(&self, _id: u64) -> Option<CameraError> {
+        None
+    }
+}
+
+fn camera_state() -> &'static Arc<RwLock<CameraState>> {
+    static STATE: OnceLock<Arc<RwLock<CameraState>>> = OnceLock::new();
+    STATE.get_or_init(|| Arc::new(RwLock::new(CameraState::default())))
+}
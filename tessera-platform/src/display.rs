@@ -0,0 +1,107 @@
+//! Monitor information for Tessera platform plugins.
+//!
+//! ## Usage
+//!
+//! List connected monitors with their resolution, refresh rate and scale, pick
+//! a frame-rate policy for the monitor the window is on, and move the window
+//! to another monitor.
+//!
+//! These services use the window registered by
+//! [`WindowPlugin`](crate::window::WindowPlugin) and return nothing before the
+//! app has resumed.
+use tessera_ui::{
+    Px, PxPosition, PxSize,
+    winit::{dpi::PhysicalPosition, monitor::MonitorHandle},
+};
+
+use crate::window::with_desktop;
+
+/// A connected monitor.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MonitorInfo {
+    /// Index of the monitor in [`monitors`].
+    pub index: usize,
+    /// Human-readable name, when the platform reports one.
+    pub name: Option<String>,
+    /// Resolution in physical pixels.
+    pub size: PxSize,
+    /// Top-left corner on the virtual desktop, in physical pixels.
+    pub position: PxPosition,
+    /// Physical pixels per logical pixel.
+    pub scale_factor: f64,
+    /// Refresh rate of the current video mode in hertz, when known.
+    pub refresh_rate_hz: Option<f32>,
+    /// Whether this is the primary monitor.
+    pub is_primary: bool,
+    /// Whether the app window is on this monitor.
+    pub is_current: bool,
+}
+
+/// Returns every connected monitor.
+pub fn monitors() -> Vec<MonitorInfo> {
+    with_desktop(|desktop| {
+        let window = desktop.window();
+        let primary = window.primary_monitor();
+        let current = window.current_monitor();
+        window
+            .available_monitors()
+            .enumerate()
+            .map(|(index, monitor)| {
+                let is_primary = primary.as_ref() == Some(&monitor);
+                let is_current = current.as_ref() == Some(&monitor);
+                monitor_info(index, &monitor, is_primary, is_current)
+            })
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
+/// Returns the monitor the app window is on.
+pub fn current_monitor() -> Option<MonitorInfo> {
+    monitors().into_iter().find(|monitor| monitor.is_current)
+}
+
+/// Centers the app window on the monitor at `index` in [`monitors`].
+///
+/// Returns whether the monitor exists. Some platforms, such as Wayland, do not
+/// let apps position their windows.
+pub fn move_to_monitor(index: usize) -> bool {
+    with_desktop(|desktop| {
+        let window = desktop.window();
+        let Some(monitor) = window.available_monitors().nth(index) else {
+            return false;
+        };
+        let monitor_size = monitor.size();
+        let window_size = window.outer_size();
+        let origin = monitor.position();
+        let offset = |monitor: u32, window: u32| (monitor.saturating_sub(window) / 2) as i32;
+        window.set_outer_position(PhysicalPosition::new(
+            origin.x + offset(monitor_size.width, window_size.width),
+            origin.y + offset(monitor_size.height, window_size.height),
+        ));
+        true
+    })
+    .unwrap_or(false)
+}
+
+fn monitor_info(
+    index: usize,
+    monitor: &MonitorHandle,
+    is_primary: bool,
+    is_current: bool,
+) -> MonitorInfo {
+    let size = monitor.size();
+    let position = monitor.position();
+    MonitorInfo {
+        index,
+        name: monitor.name(),
+        size: PxSize::new(Px::new(size.width as i32), Px::new(size.height as i32)),
+        position: PxPosition::new(Px::new(position.x), Px::new(position.y)),
+        scale_factor: monitor.scale_factor(),
+        refresh_rate_hz: monitor
+            .refresh_rate_millihertz()
+            .map(|millihertz| millihertz as f32 / 1000.0),
+        is_primary,
+        is_current,
+    }
+}
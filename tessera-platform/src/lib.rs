@@ -2,7 +2,9 @@
 //!
 //! ## Usage
 //!
-//! Register platform plugins like clipboard and window access at app startup.
+//! Register platform plugins like camera, clipboard, native menus,
+//! notifications, permissions, power status, sharing, tray icons and window
+//! access at app startup.
 #![deny(
     missing_docs,
     clippy::unwrap_used,
@@ -11,12 +13,30 @@
     rustdoc::invalid_html_tags
 )]
 
+pub mod camera;
 pub mod clipboard;
+pub mod display;
+pub mod menu;
+pub mod notifications;
+pub mod permissions;
+pub mod power;
+pub mod share;
+pub mod tray;
 pub mod window;
 
 use tessera_ui::{EntryRegistry, TesseraPackage};
 
+pub use camera::{CameraError, CameraFacing, CameraFrame, CameraPlugin, CameraSession};
 pub use clipboard::{Clipboard, ClipboardPlugin};
+pub use display::MonitorInfo;
+pub use menu::{MenuBar, MenuItem, MenuPlugin, Submenu};
+pub use notifications::{
+    Notification, NotificationChannel, NotificationId, NotificationImportance, NotificationsPlugin,
+};
+pub use permissions::{Permission, PermissionResults, PermissionStatus, PermissionsPlugin};
+pub use power::{BatteryState, BatteryStatus, PowerPlugin};
+pub use share::{ShareContent, SharePlugin};
+pub use tray::{Tray, TrayIconImage, TrayMenuItem, TrayPlugin};
 pub use window::WindowPlugin;
 
 /// Package that registers platform plugins.
@@ -32,7 +52,14 @@ impl PlatformPackage {
 
 impl TesseraPackage for PlatformPackage {
     fn register(self, registry: &mut EntryRegistry) {
+        registry.register_plugin(CameraPlugin::new());
         registry.register_plugin(ClipboardPlugin::new());
+        registry.register_plugin(MenuPlugin::new());
+        registry.register_plugin(NotificationsPlugin::new());
+        registry.register_plugin(PermissionsPlugin::new());
+        registry.register_plugin(PowerPlugin::new());
+        registry.register_plugin(SharePlugin::new());
+        registry.register_plugin(TrayPlugin::new());
         registry.register_plugin(WindowPlugin::new());
     }
 }
@@ -0,0 +1,581 @@
+//! Native application menus for Tessera platform plugins.
+//!
+//! ## Usage
+//!
+//! Give desktop apps a File, Edit and View menu bar with accelerators, and open
+//! native context menus at the pointer, with entries running their closures
+//! on the UI thread.
+//!
+//! Menus are declarative: call [`set_menu_bar`] again with a new [`MenuBar`]
+//! to change labels or enabled state. The menu bar is the global menu bar on
+//! macOS, where the first menu becomes the application menu, and a window menu
+//! on Windows. Linux windows have no native menu bar or context menu host, so
+//! both calls do nothing there.
+//!
+//! Only macOS handles accelerators in the native menu. Attach
+//! [`MenuBar::shortcuts`] to the root component's modifier so accelerators work
+//! on every platform.
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock},
+};
+
+use parking_lot::RwLock;
+use tessera_ui::{
+    DesktopPlatformContext, KeyChord, Plugin, PluginContext, PluginResult, PxPosition, Shortcut,
+};
+
+/// Menu plugin that wires native application and context menus.
+#[derive(Clone, Debug)]
+pub struct MenuPlugin;
+
+impl MenuPlugin {
+    /// Creates a menu plugin.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for MenuPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Plugin for MenuPlugin {
+    fn on_resumed(&mut self, context: &PluginContext) -> PluginResult {
+        menu_state().write().desktop = Some(context.desktop().clone());
+        Ok(())
+    }
+
+    fn on_suspended(&mut self, _context: &PluginContext) -> PluginResult {
+        menu_state().write().desktop = None;
+        Ok(())
+    }
+
+    fn on_shutdown(&mut self, _context: &PluginContext) -> PluginResult {
+        remove_menu_bar();
+        menu_state().write().desktop = None;
+        Ok(())
+    }
+}
+
+/// An entry in a menu.
+#[derive(Clone)]
+pub enum MenuItem {
+    /// A clickable entry.
+    Action {
+        /// User-visible label.
+        label: String,
+        /// Key chord shown next to the label.
+        accelerator: Option<KeyChord>,
+        /// Whether the entry can be clicked.
+        enabled: bool,
+        /// Runs on the UI thread when the entry is clicked.
+        on_click: Arc<dyn Fn() + Send + Sync>,
+    },
+    /// A separator line.
+    Separator,
+    /// A nested menu.
+    Submenu(Submenu),
+}
+
+impl MenuItem {
+    /// Creates an enabled entry that runs `on_click`.
+    pub fn action(label: impl Into<String>, on_click: impl Fn() + Send + Sync + 'static) -> Self {
+        Self::Action {
+            label: label.into(),
+            accelerator: None,
+            enabled: true,
+            on_click: Arc::new(on_click),
+        }
+    }
+
+    /// Creates a separator line.
+    pub fn separator() -> Self {
+        Self::Separator
+    }
+
+    /// Creates a nested menu.
+    pub fn submenu(submenu: Submenu) -> Self {
+        Self::Submenu(submenu)
+    }
+
+    /// Sets the accelerator of an entry from a chord such as `"Mod+S"`.
+    ///
+    /// Other items are unaffected.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `chord` cannot be parsed, like [`Shortcut::parse`].
+    pub fn accelerator(mut self, chord: &str) -> Self {
+        if let Self::Action { accelerator, .. } = &mut self {
+            let chord = KeyChord::parse(chord)
+                .unwrap_or_else(|err| panic!("invalid menu accelerator `{chord}`: {err}"));
+            *accelerator = Some(chord);
+        }
+        self
+    }
+
+    /// Sets whether an entry or nested menu can be clicked. Separators are
+    /// unaffected.
+    pub fn enabled(mut self, value: bool) -> Self {
+        match &mut self {
+            Self::Action { enabled, .. } => *enabled = value,
+            Self::Submenu(submenu) => submenu.enabled = value,
+            Self::Separator => {}
+        }
+        self
+    }
+}
+
+/// A titled menu of entries.
+#[derive(Clone)]
+pub struct Submenu {
+    label: String,
+    enabled: bool,
+    items: Vec<MenuItem>,
+}
+
+impl Submenu {
+    /// Creates an empty, enabled menu.
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            enabled: true,
+            items: Vec::new(),
+        }
+    }
+
+    /// Appends an entry.
+    pub fn item(mut self, item: MenuItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    /// Sets whether the menu can be opened.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+}
+
+/// The menus of an application menu bar.
+#[derive(Clone, Default)]
+pub struct MenuBar {
+    menus: Vec<Submenu>,
+}
+
+impl MenuBar {
+    /// Creates an empty menu bar.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a top-level menu.
+    pub fn menu(mut self, menu: Submenu) -> Self {
+        self.menus.push(menu);
+        self
+    }
+
+    /// Returns a window shortcut for every entry with an accelerator.
+    ///
+    /// Entries in disabled menus get disabled shortcuts. Call this while
+    /// building a component, like any code that creates shortcuts.
+    pub fn shortcuts(&self) -> Vec<Shortcut> {
+        fn collect(items: &[MenuItem], enabled: bool, shortcuts: &mut Vec<Shortcut>) {
+            for item in items {
+                match item {
+                    MenuItem::Action {
+                        accelerator: Some(chord),
+                        enabled: item_enabled,
+                        on_click,
+                        ..
+                    } => {
+                        let on_click = on_click.clone();
+                        shortcuts.push(
+                            Shortcut::new(chord.clone(), move || on_click())
+                                .enabled(enabled && *item_enabled),
+                        );
+                    }
+                    MenuItem::Submenu(submenu) => {
+                        collect(&submenu.items, enabled && submenu.enabled, shortcuts);
+                    }
+                    MenuItem::Action { .. } | MenuItem::Separator => {}
+                }
+            }
+        }
+
+        let mut shortcuts = Vec::new();
+        for menu in &self.menus {
+            collect(&menu.items, menu.enabled, &mut shortcuts);
+        }
+        shortcuts
+    }
+}
+
+/// Shows `menu_bar`, replacing the current menu bar.
+///
+/// Must be called on the UI thread.
+pub fn set_menu_bar(menu_bar: MenuBar) {
+    let mut state = menu_state().write();
+    let Some(desktop) = state.desktop.clone() else {
+        return;
+    };
+    state.clear_actions(MENU_BAR_OWNER);
+    let menus = menu_bar
+        .menus
+        .into_iter()
+        .map(|menu| state.register(MENU_BAR_OWNER, MenuItem::Submenu(menu)))
+        .collect();
+    drop(state);
+    native::set_menu_bar(&desktop, menus);
+}
+
+/// Removes the menu bar.
+///
+/// Must be called on the UI thread.
+pub fn remove_menu_bar() {
+    let mut state = menu_state().write();
+    state.clear_actions(MENU_BAR_OWNER);
+    let desktop = state.desktop.clone();
+    drop(state);
+    if let Some(desktop) = desktop {
+        native::remove_menu_bar(&desktop);
+    }
+}
+
+/// Opens a context menu with `items` at `position`, in physical pixels
+/// relative to the window.
+///
+/// Must be called on the UI thread. Returns once the menu is dismissed.
+pub fn show_context_menu(items: Vec<MenuItem>, position: PxPosition) {
+    let mut state = menu_state().write();
+    let Some(desktop) = state.desktop.clone() else {
+        return;
+    };
+    state.clear_actions(CONTEXT_MENU_OWNER);
+    let entries = items
+        .into_iter()
+        .map(|item| state.register(CONTEXT_MENU_OWNER, item))
+        .collect();
+    drop(state);
+    native::show_context_menu(&desktop, entries, position);
+}
+
+const MENU_BAR_OWNER: &str = "tessera-menu-bar";
+const CONTEXT_MENU_OWNER: &str = "tessera-context-menu";
+
+#[derive(Default)]
+struct MenuState {
+    desktop: Option<DesktopPlatformContext>,
+    /// Owned because menus outlive the component that set them, and
+    /// slot-backed callbacks are freed with their component.
+    actions: HashMap<String, Arc<dyn Fn() + Send + Sync>>,
+    next_id: u64,
+}
+
+impl MenuState {
+    /// Replaces the callbacks of `item` with ids owned by `owner`.
+    fn register(&mut self, owner: &str, item: MenuItem) -> NativeEntry {
+        match item {
+            MenuItem::Action {
+                label,
+                accelerator,
+                enabled,
+                on_click,
+            } => {
+                self.next_id += 1;
+                let id = format!("{owner}-{}", self.next_id);
+                self.actions.insert(id.clone(), on_click);
+                NativeEntry::Action {
+                    id,
+                    label,
+                    accelerator,
+                    enabled,
+                }
+            }
+            MenuItem::Separator => NativeEntry::Separator,
+            MenuItem::Submenu(submenu) => NativeEntry::Submenu {
+                label: submenu.label,
+                enabled: submenu.enabled,
+                entries: submenu
+                    .items
+                    .into_iter()
+                    .map(|item| self.register(owner, item))
+                    .collect(),
+            },
+        }
+    }
+
+    fn clear_actions(&mut self, owner: &str) {
+        self.actions.retain(|id, _| !id.starts_with(owner));
+    }
+}
+
+fn menu_state() -> &'static Arc<RwLock<MenuState>> {
+    static STATE: OnceLock<Arc<RwLock<MenuState>>> = OnceLock::new();
+    STATE.get_or_init(|| Arc::new(RwLock::new(MenuState::default())))
+}
+
+/// Menu entry handed to the native menu, with callbacks replaced by ids.
+#[cfg_attr(not(any(target_os = "windows", target_os = "macos")), allow(dead_code))]
+enum NativeEntry {
+    Action {
+        id: String,
+        label: String,
+        accelerator: Option<KeyChord>,
+        enabled: bool,
+    },
+    Separator,
+    Submenu {
+        label: String,
+        enabled: bool,
+        entries: Vec<NativeEntry>,
+    },
+}
+
+/// Routes native menu clicks to the tray or to app menus.
+///
+/// Native menus report clicks through one process-wide handler, which the tray
+/// shares.
+#[cfg(any(target_os = "linux", target_os = "windows", target_os = "macos"))]
+pub(crate) fn install_menu_event_handler() {
+    static INSTALL: std::sync::Once = std::sync::Once::new();
+    INSTALL.call_once(|| {
+        muda::MenuEvent::set_event_handler(Some(|event: muda::MenuEvent| {
+            let id = event.id.as_ref();
+            if crate::tray::dispatch_menu_event(id) {
+                return;
+            }
+            let state = menu_state().read();
+            let Some(on_click) = state.actions.get(id).cloned() else {
+                return;
+            };
+            if let Some(desktop) = &state.desktop {
+                desktop.run_on_ui_thread(move || on_click());
+            }
+        }));
+    });
+}
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+mod native {
+    use std::cell::RefCell;
+
+    use muda::{
+        ContextMenu, IsMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu,
+        accelerator::Accelerator,
+        dpi::{PhysicalPosition, Position},
+    };
+    use tessera_ui::{
+        DesktopPlatformContext, KeyChord, PxPosition,
+        winit::raw_window_handle::{HasWindowHandle, RawWindowHandle},
+    };
+    use tracing::warn;
+
+    use super::{NativeEntry, install_menu_event_handler};
+
+    thread_local! {
+        static MENU_BAR: RefCell<Option<Menu>> = const { RefCell::new(None) };
+    }
+
+    pub(super) fn set_menu_bar(desktop: &DesktopPlatformContext, menus: Vec<NativeEntry>) {
+        install_menu_event_handler();
+        let menu = match build_menu(menus) {
+            Ok(menu) => menu,
+            Err(err) => {
+                warn!("Failed to build menu bar: {err}");
+                return;
+            }
+        };
+        remove_menu_bar(desktop);
+        if let Err(err) = attach(desktop, &menu) {
+            warn!("Failed to show menu bar: {err}");
+            return;
+        }
+        MENU_BAR.with(|slot| *slot.borrow_mut() = Some(menu));
+    }
+
+    pub(super) fn remove_menu_bar(desktop: &DesktopPlatformContext) {
+        let Some(menu) = MENU_BAR.with(|slot| slot.borrow_mut().take()) else {
+            return;
+        };
+        if let Err(err) = detach(desktop, &menu) {
+            warn!("Failed to remove menu bar: {err}");
+        }
+    }
+
+    pub(super) fn show_context_menu(
+        desktop: &DesktopPlatformContext,
+        entries: Vec<NativeEntry>,
+        position: PxPosition,
+    ) {
+        install_menu_event_handler();
+        let menu = match build_menu(entries) {
+            Ok(menu) => menu,
+            Err(err) => {
+                warn!("Failed to build context menu: {err}");
+                return;
+            }
+        };
+        let position = Some(Position::Physical(PhysicalPosition::new(
+            position.x.0,
+            position.y.0,
+        )));
+        let Ok(handle) = desktop.window().window_handle() else {
+            return;
+        };
+        // SAFETY: the handle belongs to the live window of this context.
+        match handle.as_raw() {
+            #[cfg(target_os = "windows")]
+            RawWindowHandle::Win32(handle) => unsafe {
+                menu.show_context_menu_for_hwnd(handle.hwnd.get(), position);
+            },
+            #[cfg(target_os = "macos")]
+            RawWindowHandle::AppKit(handle) => unsafe {
+                menu.show_context_menu_for_nsview(handle.ns_view.as_ptr(), position);
+            },
+            _ => {}
+        }
+    }
+
+    fn build_menu(entries: Vec<NativeEntry>) -> muda::Result<Menu> {
+        let menu = Menu::new();
+        for entry in entries {
+            menu.append(build_entry(entry)?.as_ref())?;
+        }
+        Ok(menu)
+    }
+
+    fn build_entry(entry: NativeEntry) -> muda::Result<Box<dyn IsMenuItem>> {
+        Ok(match entry {
+            NativeEntry::Action {
+                id,
+                label,
+                accelerator,
+                enabled,
+            } => Box::new(MenuItem::with_id(
+                id,
+                label,
+                enabled,
+                accelerator.as_ref().and_then(native_accelerator),
+            )),
+            NativeEntry::Separator => Box::new(PredefinedMenuItem::separator()),
+            NativeEntry::Submenu {
+                label,
+                enabled,
+                entries,
+            } => {
+                let submenu = Submenu::new(label, enabled);
+                for entry in entries {
+                    submenu.append(build_entry(entry)?.as_ref())?;
+                }
+                Box::new(submenu)
+            }
+        })
+    }
+
+    /// Both chord formats use `Ctrl+Shift+S` style names, so the displayed
+    /// chord parses as a native accelerator for all but a few keys.
+    fn native_accelerator(chord: &KeyChord) -> Option<Accelerator> {
+        chord
+            .to_string()
+            .parse()
+            .inspect_err(|err| warn!("Menu accelerator `{chord}` is not supported: {err}"))
+            .ok()
+    }
+
+    #[cfg(target_os = "windows")]
+    fn attach(desktop: &DesktopPlatformContext, menu: &Menu) -> muda::Result<()> {
+        match desktop
+            .window()
+            .window_handle()
+            .map(|handle| handle.as_raw())
+        {
+            // SAFETY: the handle belongs to the live window of this context.
+            Ok(RawWindowHandle::Win32(handle)) => unsafe { menu.init_for_hwnd(handle.hwnd.get()) },
+            _ => Ok(()),
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    fn detach(desktop: &DesktopPlatformContext, menu: &Menu) -> muda::Result<()> {
+        match desktop
+            .window()
+            .window_handle()
+            .map(|handle| handle.as_raw())
+        {
+            // SAFETY: the handle belongs to the live window of this context.
+            Ok(RawWindowHandle::Win32(handle)) => unsafe {
+                menu.remove_for_hwnd(handle.hwnd.get())
+            },
+            _ => Ok(()),
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    fn attach(_desktop: &DesktopPlatformContext, menu: &Menu) -> muda::Result<()> {
+        menu.init_for_nsapp();
+        Ok(())
+    }
+
+    #[cfg(target_os = "macos")]
+    fn detach(_desktop: &DesktopPlatformContext, menu: &Menu) -> muda::Result<()> {
+        menu.remove_for_nsapp();
+        Ok(())
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+mod native {
+    use tessera_ui::{DesktopPlatformContext, PxPosition};
+
+    use super::NativeEntry;
+
+    pub(super) fn set_menu_bar(_desktop: &DesktopPlatformContext, _menus: Vec<NativeEntry>) {}
+
+    pub(super) fn remove_menu_bar(_desktop: &DesktopPlatformContext) {}
+
+    pub(super) fn show_context_menu(
+        _desktop: &DesktopPlatformContext,
+        _entries: Vec<NativeEntry>,
+        _position: PxPosition,
+    ) {
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[test]
+    fn registered_handlers_are_owned_per_menu() {
+        static OPENS: AtomicUsize = AtomicUsize::new(0);
+        let mut state = MenuState::default();
+        state.register(
+            MENU_BAR_OWNER,
+            MenuItem::submenu(
+                Submenu::new("File")
+                    .item(MenuItem::action("Open", || {
+                        OPENS.fetch_add(1, Ordering::SeqCst);
+                    }))
+                    .item(MenuItem::separator()),
+            ),
+        );
+        state.register(CONTEXT_MENU_OWNER, MenuItem::action("Copy", || {}));
+        assert_eq!(state.actions.len(), 2);
+
+        state.clear_actions(CONTEXT_MENU_OWNER);
+        let (id, open) = state
+            .actions
+            .iter()
+            .next()
+            .expect("menu bar action must stay registered");
+        assert!(id.starts_with(MENU_BAR_OWNER));
+        open();
+        assert_eq!(OPENS.load(Ordering::SeqCst), 1);
+    }
+}
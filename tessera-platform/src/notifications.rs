@@ -0,0 +1,462 @@
+//! System notifications for Tessera platform plugins.
+//!
+//! ## Usage
+//!
+//! Show desktop toasts and Android notifications from app actions, and bring
+//! users back into the app when they click one, optionally to a router
+//! destination through a deep link.
+//!
+//! Clicks run the notification's callback and queue its deep link on the UI
+//! thread, so a `shard_home` with a `DeepLinkRegistry` navigates on its own.
+//! Desktop clicks are reported on Linux; Windows and macOS show the toast
+//! without click-through.
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock},
+};
+
+use parking_lot::RwLock;
+use tessera_ui::{DesktopPlatformContext, Plugin, PluginContext, PluginResult};
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "linux",
+    target_os = "windows",
+    target_os = "macos"
+))]
+use tracing::warn;
+
+#[cfg(target_os = "android")]
+use tessera_ui::android::{ActivityRef, activity};
+#[cfg(target_os = "android")]
+use tessera_ui::winit::platform::android::activity::AndroidApp;
+
+#[cfg(target_os = "android")]
+tessera_ui::android::jni_bind! {
+    class "com.tessera.platform.NotificationsPlugin" as NotificationsPluginJni {
+        /// Creates or updates a notification channel.
+        fn createChannel(
+            activity: ActivityRef,
+            id: &str,
+            name: &str,
+            description: &str,
+            importance: i32
+        ) -> ();
+        /// Asks for the notification permission when the platform requires it.
+        fn requestPermission(activity: ActivityRef) -> ();
+        /// Posts a notification, returning whether it was shown.
+        fn show(
+            activity: ActivityRef,
+            channel_id: &str,
+            id: i32,
+            title: &str,
+            body: &str,
+            deep_link: &str
+        ) -> bool;
+        /// Returns the id of the notification that opened the activity, or -1.
+        fn takeClickedNotification(activity: ActivityRef) -> i32;
+    }
+}
+
+/// Notifications plugin that wires platform notification services.
+#[derive(Clone, Debug)]
+pub struct NotificationsPlugin;
+
+impl NotificationsPlugin {
+    /// Creates a notifications plugin.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for NotificationsPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Plugin for NotificationsPlugin {
+    fn on_resumed(&mut self, context: &PluginContext) -> PluginResult {
+        let mut state = notifications_state().write();
+        state.desktop = Some(context.desktop().clone());
+        #[cfg(target_os = "android")]
+        {
+            let android_app = context.android_app().clone();
+            // Tapping a notification resumes the activity with its intent.
+            let clicked = NotificationsPluginJni::takeClickedNotification(
+                &android_app,
+                activity(&android_app),
+            );
+            state.android_app = Some(android_app);
+            drop(state);
+            match clicked {
+                Ok(id) if id >= 0 => dispatch_click(NotificationId(id as u32)),
+                Ok(_) => {}
+                Err(err) => warn!("Android notification click lookup failed: {err}"),
+            }
+        }
+        Ok(())
+    }
+
+    fn on_suspended(&mut self, _context: &PluginContext) -> PluginResult {
+        notifications_state().write().release_platform();
+        Ok(())
+    }
+
+    fn on_shutdown(&mut self, _context: &PluginContext) -> PluginResult {
+        notifications_state().write().release_platform();
+        Ok(())
+    }
+}
+
+/// How strongly a notification channel interrupts the user.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum NotificationImportance {
+    /// Shown silently in the notification shade.
+    Low,
+    /// Makes a sound.
+    #[default]
+    Default,
+    /// Makes a sound and peeks onto the screen.
+    High,
+}
+
+impl NotificationImportance {
+    #[cfg(target_os = "android")]
+    fn android_value(self) -> i32 {
+        // Values of `NotificationManager.IMPORTANCE_*`.
+        match self {
+            Self::Low => 2,
+            Self::Default => 3,
+            Self::High => 4,
+        }
+    }
+}
+
+/// An Android notification channel.
+///
+/// Android 8 and later only show notifications posted to a channel, and let
+/// users mute each channel separately. Desktop platforms ignore channels.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NotificationChannel {
+    id: String,
+    name: String,
+    description: Option<String>,
+    importance: NotificationImportance,
+}
+
+impl NotificationChannel {
+    /// Creates a channel with a stable id and a user-visible name.
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            description: None,
+            importance: NotificationImportance::default(),
+        }
+    }
+
+    /// Sets the user-visible channel description.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the channel importance.
+    pub fn importance(mut self, importance: NotificationImportance) -> Self {
+        self.importance = importance;
+        self
+    }
+}
+
+/// A notification to show.
+#[derive(Clone)]
+pub struct Notification {
+    title: String,
+    body: Option<String>,
+    channel_id: Option<String>,
+    deep_link: Option<String>,
+    on_click: Option<ClickHandler>,
+}
+
+impl Notification {
+    /// Creates a notification with a title.
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            body: None,
+            channel_id: None,
+            deep_link: None,
+            on_click: None,
+        }
+    }
+
+    /// Sets the body text.
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// Posts the notification to the channel with this id on Android.
+    ///
+    /// Without a channel, a default channel named after the app is used.
+    pub fn channel(mut self, channel_id: impl Into<String>) -> Self {
+        self.channel_id = Some(channel_id.into());
+        self
+    }
+
+    /// Queues this deep link when the notification is clicked.
+    ///
+    /// On Android the link is also attached to the launch intent, so it opens
+    /// the right destination when the app was not running.
+    pub fn deep_link(mut self, uri: impl Into<String>) -> Self {
+        self.deep_link = Some(uri.into());
+        self
+    }
+
+    /// Runs `on_click` on the UI thread when the notification is clicked.
+    ///
+    /// The notification owns the closure, so it can still run after the
+    /// component that showed the notification has left the tree.
+    pub fn on_click(mut self, on_click: impl Fn() + Send + Sync + 'static) -> Self {
+        self.on_click = Some(Arc::new(on_click));
+        self
+    }
+}
+
+/// Identifies a shown notification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NotificationId(u32);
+
+/// Creates or updates a notification channel on Android.
+pub fn create_channel(channel: &NotificationChannel) {
+    #[cfg(target_os = "android")]
+    {
+        let Some(android_app) = notifications_state().read().android_app.clone() else {
+            return;
+        };
+        if let Err(err) = NotificationsPluginJni::createChannel(
+            &android_app,
+            activity(&android_app),
+            &channel.id,
+            &channel.name,
+            channel.description.as_deref().unwrap_or_default(),
+            channel.importance.android_value(),
+        ) {
+            warn!("Android notification channel creation failed: {err}");
+        }
+    }
+    #[cfg(not(target_os = "android"))]
+    {
+        let _ = channel;
+    }
+}
+
+/// Asks the user to allow notifications where the platform requires it.
+///
+/// Android 13 and later drop notifications until the permission is granted.
+pub fn request_permission() {
+    #[cfg(target_os = "android")]
+    {
+        let Some(android_app) = notifications_state().read().android_app.clone() else {
+            return;
+        };
+        if let Err(err) =
+            NotificationsPluginJni::requestPermission(&android_app, activity(&android_app))
+        {
+            warn!("Android notification permission request failed: {err}");
+        }
+    }
+}
+
+/// Shows a notification.
+///
+/// Returns `None` when notifications are unavailable, for example before the
+/// app has resumed or on platforms without a notification service.
+pub fn show(notification: Notification) -> Option<NotificationId> {
+    let mut state = notifications_state().write();
+    state.desktop.as_ref()?;
+    let id = state.insert_target(ClickTarget {
+        on_click: notification.on_click.clone(),
+        deep_link: notification.deep_link.clone(),
+    });
+    let shown = state.show_platform(id, &notification);
+    if !shown {
+        state.targets.remove(&id);
+    }
+    shown.then_some(id)
+}
+
+type ClickHandler = Arc<dyn Fn() + Send + Sync>;
+
+struct ClickTarget {
+    on_click: Option<ClickHandler>,
+    deep_link: Option<String>,
+}
+
+#[derive(Default)]
+struct NotificationsState {
+    desktop: Option<DesktopPlatformContext>,
+    #[cfg(target_os = "android")]
+    android_app: Option<AndroidApp>,
+    next_id: u32,
+    targets: HashMap<NotificationId, ClickTarget>,
+}
+
+impl NotificationsState {
+    fn insert_target(&mut self, target: ClickTarget) -> NotificationId {
+        self.next_id = self.next_id.wrapping_add(1).max(1);
+        let id = NotificationId(self.next_id);
+        self.targets.insert(id, target);
+        id
+    }
+
+    fn release_platform(&mut self) {
+        self.desktop = None;
+        #[cfg(target_os = "android")]
+        {
+            self.android_app = None;
+        }
+    }
+
+    #[cfg(target_os = "android")]
+    fn show_platform(&mut self, id: NotificationId, notification: &Notification) -> bool {
+        let Some(android_app) = self.android_app.as_ref() else {
+            return false;
+        };
+        match NotificationsPluginJni::show(
+            android_app,
+            activity(android_app),
+            notification.channel_id.as_deref().unwrap_or_default(),
+            id.0 as i32,
+            &notification.title,
+            notification.body.as_deref().unwrap_or_default(),
+            notification.deep_link.as_deref().unwrap_or_default(),
+        ) {
+            Ok(shown) => shown,
+            Err(err) => {
+                warn!("Android notification failed: {err}");
+                false
+            }
+        }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "windows", target_os = "macos"))]
+    fn show_platform(&mut self, id: NotificationId, notification: &Notification) -> bool {
+        let mut toast = notify_rust::Notification::new();
+        toast.summary(&notification.title);
+        if let Some(body) = &notification.body {
+            toast.body(body);
+        }
+        #[cfg(target_os = "linux")]
+        toast.action("default", "Open");
+        let handle = match toast.show() {
+            Ok(handle) => handle,
+            Err(err) => {
+                warn!("Desktop notification failed: {err}");
+                return false;
+            }
+        };
+        #[cfg(target_os = "linux")]
+        if let Some(desktop) = self.desktop.clone() {
+            std::thread::spawn(move || {
+                handle.wait_for_action(|action| {
+                    let clicked = action == "default";
+                    desktop.run_on_ui_thread(move || {
+                        if clicked {
+                            dispatch_click(id);
+                        } else {
+                            notifications_state().write().targets.remove(&id);
+                        }
+                    });
+                });
+            });
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            // Clicks are not reported here, so nothing will use the target.
+            let _ = handle;
+            self.targets.remove(&id);
+        }
+        true
+    }
+
+    #[cfg(not(any(
+        target_os = "android",
+        target_os = "linux",
+        target_os = "windows",
+        target_os = "macos"
+    )))]
+    fn show_platform(&mut self, _id: NotificationId, _notification: &Notification) -> bool {
+        false
+    }
+}
+
+fn notifications_state() -> &'static Arc<RwLock<NotificationsState>> {
+    static STATE: OnceLock<Arc<RwLock<NotificationsState>>> = OnceLock::new();
+    STATE.get_or_init(|| Arc::new(RwLock::new(NotificationsState::default())))
+}
+
+/// Routes a click into the app. Must run on the UI thread.
+fn dispatch_click(id: NotificationId) {
+    let (target, desktop) = {
+        let mut state = notifications_state().write();
+        (state.targets.remove(&id), state.desktop.clone())
+    };
+    let Some(target) = target else {
+        return;
+    };
+    if let Some(desktop) = desktop {
+        desktop.window().focus_window();
+    }
+    if let Some(on_click) = target.on_click {
+        on_click();
+    }
+    if let Some(uri) = target.deep_link {
+        tessera_ui::deep_link::push_deep_link(uri);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use tessera_ui::deep_link::take_deep_links;
+
+    use super::*;
+
+    #[test]
+    fn notification_ids_skip_zero_when_wrapping() {
+        let mut state = NotificationsState {
+            next_id: u32::MAX - 1,
+            ..Default::default()
+        };
+        let target = || ClickTarget {
+            on_click: None,
+            deep_link: None,
+        };
+
+        assert_eq!(state.insert_target(target()), NotificationId(u32::MAX));
+        assert_eq!(state.insert_target(target()), NotificationId(1));
+        assert_eq!(state.targets.len(), 2);
+    }
+
+    #[test]
+    fn clicks_run_the_handler_and_queue_the_deep_link_once() {
+        static CLICKS: AtomicUsize = AtomicUsize::new(0);
+        let id = notifications_state().write().insert_target(ClickTarget {
+            on_click: Some(Arc::new(|| {
+                CLICKS.fetch_add(1, Ordering::SeqCst);
+            })),
+            deep_link: Some("app://inbox".to_string()),
+        });
+
+        dispatch_click(id);
+        assert_eq!(CLICKS.load(Ordering::SeqCst), 1);
+        assert_eq!(take_deep_links(), vec!["app://inbox".to_string()]);
+
+        dispatch_click(id);
+        assert_eq!(CLICKS.load(Ordering::SeqCst), 1);
+        assert!(take_deep_links().is_empty());
+    }
+}
@@ -0,0 +1,313 @@
+//! Runtime permission requests for Tessera platform plugins.
+//!
+//! ## Usage
+//!
+//! Ask for camera, microphone, location and similar permissions before using
+//! them, and react to the user's answer from a callback on the UI thread.
+//!
+//! Android apps must still declare each permission in their own manifest.
+//! Other platforms have no runtime permission model here and report every
+//! permission as granted; the system prompts on first use where it needs to.
+use std::sync::{Arc, OnceLock};
+
+use parking_lot::RwLock;
+use tessera_ui::{CallbackWith, DesktopPlatformContext, Plugin, PluginContext, PluginResult};
+
+#[cfg(target_os = "android")]
+use tracing::warn;
+
+#[cfg(target_os = "android")]
+use tessera_ui::android::{ActivityRef, activity};
+#[cfg(target_os = "android")]
+use tessera_ui::winit::platform::android::activity::AndroidApp;
+
+#[cfg(target_os = "android")]
+tessera_ui::android::jni_bind! {
+    class "com.tessera.platform.PermissionsPlugin" as PermissionsPluginJni {
+        /// Returns whether a permission key or manifest name is granted.
+        fn isGranted(activity: ActivityRef, permission: &str) -> bool;
+        /// Shows the system dialog for the missing newline-separated
+        /// permissions, returning whether it was shown.
+        fn request(activity: ActivityRef, permissions: &str) -> bool;
+        /// Returns whether the user answered the last dialog since the
+        /// previous call.
+        fn takeRequestFinished(activity: ActivityRef) -> bool;
+    }
+}
+
+/// Permissions plugin that wires platform permission services.
+#[derive(Clone, Debug)]
+pub struct PermissionsPlugin;
+
+impl PermissionsPlugin {
+    /// Creates a permissions plugin.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for PermissionsPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Plugin for PermissionsPlugin {
+    fn on_resumed(&mut self, context: &PluginContext) -> PluginResult {
+        let mut state = permissions_state().write();
+        state.desktop = Some(context.desktop().clone());
+        #[cfg(target_os = "android")]
+        {
+            state.android_app = Some(context.android_app().clone());
+        }
+        Ok(())
+    }
+
+    fn on_suspended(&mut self, _context: &PluginContext) -> PluginResult {
+        permissions_state().write().release_platform();
+        Ok(())
+    }
+
+    fn on_shutdown(&mut self, _context: &PluginContext) -> PluginResult {
+        let mut state = permissions_state().write();
+        state.release_platform();
+        state.pending = None;
+        Ok(())
+    }
+}
+
+/// A runtime permission.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Permission {
+    /// Take pictures and record video.
+    Camera,
+    /// Record audio.
+    Microphone,
+    /// Read the device location. Approximate access counts as granted.
+    Location,
+    /// Post notifications.
+    Notifications,
+    /// Read shared images and videos.
+    Media,
+    /// Read the user's contacts.
+    Contacts,
+    /// Any other Android permission by its manifest name, such as
+    /// `android.permission.BLUETOOTH_CONNECT`.
+    Android(String),
+}
+
+impl Permission {
+    #[cfg(target_os = "android")]
+    fn android_key(&self) -> &str {
+        // Resolved to manifest names for the running Android version by
+        // `PermissionsPlugin.kt`.
+        match self {
+            Self::Camera => "camera",
+            Self::Microphone => "microphone",
+            Self::Location => "location",
+            Self::Notifications => "notifications",
+            Self::Media => "media",
+            Self::Contacts => "contacts",
+            Self::Android(name) => name,
+        }
+    }
+}
+
+/// Whether the app holds a permission.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PermissionStatus {
+    /// The app may use the permission.
+    Granted,
+    /// The user has not granted the permission, or refused it.
+    Denied,
+}
+
+impl PermissionStatus {
+    /// Returns whether the permission is granted.
+    pub fn is_granted(self) -> bool {
+        self == Self::Granted
+    }
+}
+
+/// The outcome of [`request_permissions`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PermissionResults {
+    statuses: Vec<(Permission, PermissionStatus)>,
+}
+
+impl PermissionResults {
+    /// Returns the status of a requested permission, or
+    /// [`PermissionStatus::Denied`] for one that was not requested.
+    pub fn status(&self, permission: &Permission) -> PermissionStatus {
+        self.statuses
+            .iter()
+            .find(|(requested, _)| requested == permission)
+            .map_or(PermissionStatus::Denied, |(_, status)| *status)
+    }
+
+    /// Returns whether every requested permission is granted.
+    pub fn all_granted(&self) -> bool {
+        self.statuses.iter().all(|(_, status)| status.is_granted())
+    }
+
+    /// Iterates over the requested permissions and their statuses.
+    pub fn iter(&self) -> impl Iterator<Item = (&Permission, PermissionStatus)> {
+        self.statuses
+            .iter()
+            .map(|(permission, status)| (permission, *status))
+    }
+}
+
+/// Returns whether the app holds `permission`.
+pub fn permission_status(permission: &Permission) -> PermissionStatus {
+    permissions_state().read().status_platform(permission)
+}
+
+/// Asks the user for the permissions the app does not hold yet.
+///
+/// `on_result` runs on the UI thread once the user answers, or right away
+/// when nothing needs asking. A request made while another one is showing is
+/// answered with the current statuses. Does nothing before the app has
+/// resumed.
+pub fn request_permissions(
+    permissions: impl IntoIterator<Item = Permission>,
+    on_result: impl Into<CallbackWith<PermissionResults>>,
+) {
+    let request = PendingRequest {
+        permissions: permissions.into_iter().collect(),
+        on_result: on_result.into(),
+    };
+    let mut state = permissions_state().write();
+    let Some(desktop) = state.desktop.clone() else {
+        return;
+    };
+    if state.pending.is_none() && state.request_platform(&request.permissions) {
+        state.pending = Some(request);
+        return;
+    }
+    drop(state);
+    desktop.run_on_ui_thread(move || request.finish());
+}
+
+struct PendingRequest {
+    permissions: Vec<Permission>,
+    on_result: CallbackWith<PermissionResults>,
+}
+
+impl PendingRequest {
+    /// Reports the current statuses. Must run on the UI thread.
+    fn finish(self) {
+        let statuses = {
+            let state = permissions_state().read();
+            self.permissions
+                .into_iter()
+                .map(|permission| {
+                    let status = state.status_platform(&permission);
+                    (permission, status)
+                })
+                .collect()
+        };
+        self.on_result.call(PermissionResults { statuses });
+    }
+}
+
+#[derive(Default)]
+struct PermissionsState {
+    desktop: Option<DesktopPlatformContext>,
+    #[cfg(target_os = "android")]
+    android_app: Option<AndroidApp>,
+    pending: Option<PendingRequest>,
+}
+
+impl PermissionsState {
+    fn release_platform(&mut self) {
+        self.desktop = None;
+        #[cfg(target_os = "android")]
+        {
+            self.android_app = None;
+        }
+    }
+
+    #[cfg(target_os = "android")]
+    fn status_platform(&self, permission: &Permission) -> PermissionStatus {
+        let Some(android_app) = self.android_app.as_ref() else {
+            return PermissionStatus::Denied;
+        };
+        match PermissionsPluginJni::isGranted(
+            android_app,
+            activity(android_app),
+            permission.android_key(),
+        ) {
+            Ok(true) => PermissionStatus::Granted,
+            Ok(false) => PermissionStatus::Denied,
+            Err(err) => {
+                warn!("Android permission lookup failed: {err}");
+                PermissionStatus::Denied
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "android"))]
+    fn status_platform(&self, _permission: &Permission) -> PermissionStatus {
+        PermissionStatus::Granted
+    }
+
+    /// Shows the system dialog, returning whether the caller should wait for
+    /// an answer.
+    #[cfg(target_os = "android")]
+    fn request_platform(&self, permissions: &[Permission]) -> bool {
+        let (Some(android_app), Some(desktop)) = (self.android_app.clone(), self.desktop.clone())
+        else {
+            return false;
+        };
+        let keys = permissions
+            .iter()
+            .map(Permission::android_key)
+            .collect::<Vec<_>>()
+            .join("\n");
+        match PermissionsPluginJni::request(&android_app, activity(&android_app), &keys) {
+            Ok(true) => {}
+            Ok(false) => return false,
+            Err(err) => {
+                warn!("Android permission request failed: {err}");
+                return false;
+            }
+        }
+        // The answer arrives through an activity lifecycle callback that Rust
+        // cannot observe directly, so poll for it off the UI thread.
+        std::thread::spawn(move || {
+            loop {
+                std::thread::sleep(std::time::Duration::from_millis(100));
+                if permissions_state().read().pending.is_none() {
+                    return;
+                }
+                match PermissionsPluginJni::takeRequestFinished(
+                    &android_app,
+                    activity(&android_app),
+                ) {
+                    Ok(false) => continue,
+                    Ok(true) => {}
+                    Err(err) => warn!("Android permission result lookup failed: {err}"),
+                }
+                desktop.run_on_ui_thread(|| {
+                    let pending = permissions_state().write().pending.take();
+                    if let Some(request) = pending {
+                        request.finish();
+                    }
+                });
+                return;
+            }
+        });
+        true
+    }
+
+    #[cfg(not(target_os = "android"))]
+    fn request_platform(&self, _permissions: &[Permission]) -> bool {
+        false
+    }
+}
+
+fn permissions_state() -> &'static Arc<RwLock<PermissionsState>> {
+    static STATE: OnceLock<Arc<RwLock<PermissionsState>>> = OnceLock::new();
+    STATE.get_or_init(|| Arc::new(RwLock::new(PermissionsState::default())))
+}
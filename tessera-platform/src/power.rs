@@ -0,0 +1,251 @@
+//! Battery and power-saver status for Tessera platform plugins.
+//!
+//! ## Usage
+//!
+//! Lower frame rates, pause background work or skip heavy effects while the
+//! device runs on a low battery or the user turned on power saving.
+//!
+//! Values are read when requested, so poll them from a timer or on resume
+//! rather than every frame.
+use std::sync::{Arc, OnceLock};
+
+use parking_lot::RwLock;
+use tessera_ui::{Plugin, PluginContext, PluginResult};
+
+#[cfg(any(target_os = "android", target_os = "windows"))]
+use tracing::warn;
+
+#[cfg(target_os = "android")]
+use tessera_ui::android::{ActivityRef, activity};
+#[cfg(target_os = "android")]
+use tessera_ui::winit::platform::android::activity::AndroidApp;
+
+#[cfg(target_os = "android")]
+tessera_ui::android::jni_bind! {
+    class "com.tessera.platform.PowerPlugin" as PowerPluginJni {
+        /// Returns the battery level in `0.0..=1.0`, or a negative value
+        /// without a battery.
+        fn batteryLevel(activity: ActivityRef) -> f32;
+        /// Returns the `BatteryManager.BATTERY_STATUS_*` value.
+        fn batteryStatus(activity: ActivityRef) -> i32;
+        /// Whether battery saver is on.
+        fn isPowerSaveMode(activity: ActivityRef) -> bool;
+    }
+}
+
+/// Power plugin that wires platform battery and power services.
+#[derive(Clone, Debug)]
+pub struct PowerPlugin;
+
+impl PowerPlugin {
+    /// Creates a power plugin.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for PowerPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Plugin for PowerPlugin {
+    fn on_resumed(&mut self, context: &PluginContext) -> PluginResult {
+        let mut state = power_state().write();
+        state.resumed = true;
+        #[cfg(target_os = "android")]
+        {
+            state.android_app = Some(context.android_app().clone());
+        }
+        #[cfg(not(target_os = "android"))]
+        let _ = context;
+        Ok(())
+    }
+
+    fn on_suspended(&mut self, _context: &PluginContext) -> PluginResult {
+        power_state().write().release_platform();
+        Ok(())
+    }
+
+    fn on_shutdown(&mut self, _context: &PluginContext) -> PluginResult {
+        power_state().write().release_platform();
+        Ok(())
+    }
+}
+
+/// Whether the battery is charging.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BatteryState {
+    /// Plugged in and charging.
+    Charging,
+    /// Running on battery.
+    Discharging,
+    /// Plugged in and fully charged.
+    Full,
+    /// Plugged in but not charging.
+    NotCharging,
+    /// The platform did not report a state.
+    Unknown,
+}
+
+/// Charge level and state of the device battery.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BatteryStatus {
+    /// Charge level in `0.0..=1.0`.
+    pub level: f32,
+    /// Whether the battery is charging.
+    pub state: BatteryState,
+}
+
+impl BatteryStatus {
+    /// Returns whether the device runs on battery power.
+    pub fn is_discharging(&self) -> bool {
+        self.state == BatteryState::Discharging
+    }
+}
+
+/// Returns the battery status, or `None` on devices without a battery.
+pub fn battery_status() -> Option<BatteryStatus> {
+    let state = power_state().read();
+    if !state.resumed {
+        return None;
+    }
+    state.battery_platform()
+}
+
+/// Returns whether the user turned on battery saver or a power-saving
+/// profile.
+pub fn is_power_saver_enabled() -> bool {
+    let state = power_state().read();
+    state.resumed && state.power_saver_platform()
+}
+
+#[derive(Default)]
+struct PowerState {
+    resumed: bool,
+    #[cfg(target_os = "android")]
+    android_app: Option<AndroidApp>,
+}
+
+impl PowerState {
+    fn release_platform(&mut self) {
+        self.resumed = false;
+        #[cfg(target_os = "android")]
+        {
+            self.android_app = None;
+        }
+    }
+
+    #[cfg(target_os = "android")]
+    fn battery_platform(&self) -> Option<BatteryStatus> {
+        let android_app = self.android_app.as_ref()?;
+        let level = PowerPluginJni::batteryLevel(android_app, activity(android_app));
+        let status = PowerPluginJni::batteryStatus(android_app, activity(android_app));
+        match level.and_then(|level| Ok((level, status?))) {
+            Ok((level, _)) if level < 0.0 => None,
+            Ok((level, status)) => Some(BatteryStatus {
+                level,
+                // Values of `BatteryManager.BATTERY_STATUS_*`.
+                state: match status {
+                    2 => BatteryState::Charging,
+                    3 => BatteryState::Discharging,
+                    4 => BatteryState::NotCharging,
+                    5 => BatteryState::Full,
+                    _ => BatteryState::Unknown,
+                },
+            }),
+            Err(err) => {
+                warn!("Android battery status failed: {err}");
+                None
+            }
+        }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "windows", target_os = "macos"))]
+    fn battery_platform(&self) -> Option<BatteryStatus> {
+        use starship_battery::{Manager, State};
+
+        let manager = Manager::new().ok()?;
+        let battery = manager.batteries().ok()?.find_map(Result::ok)?;
+        Some(BatteryStatus {
+            level: battery.state_of_charge().value.clamp(0.0, 1.0),
+            state: match battery.state() {
+                State::Charging => BatteryState::Charging,
+                State::Discharging => BatteryState::Discharging,
+                State::Full => BatteryState::Full,
+                State::Empty => BatteryState::Discharging,
+                State::Unknown => BatteryState::Unknown,
+            },
+        })
+    }
+
+    #[cfg(not(any(
+        target_os = "android",
+        target_os = "linux",
+        target_os = "windows",
+        target_os = "macos"
+    )))]
+    fn battery_platform(&self) -> Option<BatteryStatus> {
+        None
+    }
+
+    #[cfg(target_os = "android")]
+    fn power_saver_platform(&self) -> bool {
+        let Some(android_app) = self.android_app.as_ref() else {
+            return false;
+        };
+        PowerPluginJni::isPowerSaveMode(android_app, activity(android_app))
+            .inspect_err(|err| warn!("Android power saver lookup failed: {err}"))
+            .unwrap_or(false)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn power_saver_platform(&self) -> bool {
+        // Set through power-profiles-daemon by GNOME and KDE; desktops
+        // without it have no power saver.
+        std::process::Command::new("powerprofilesctl")
+            .arg("get")
+            .output()
+            .is_ok_and(|output| {
+                output.status.success()
+                    && String::from_utf8_lossy(&output.stdout).trim() == "power-saver"
+            })
+    }
+
+    #[cfg(target_os = "windows")]
+    fn power_saver_platform(&self) -> bool {
+        use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+        let mut status = SYSTEM_POWER_STATUS::default();
+        // SAFETY: `status` is a live, correctly sized out-parameter.
+        match unsafe { GetSystemPowerStatus(&mut status) } {
+            // Set to 1 while battery saver is on.
+            Ok(()) => status.SystemStatusFlag == 1,
+            Err(err) => {
+                warn!("Failed to read the power status: {err}");
+                false
+            }
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    fn power_saver_platform(&self) -> bool {
+        objc2_foundation::NSProcessInfo::processInfo().isLowPowerModeEnabled()
+    }
+
+    #[cfg(not(any(
+        target_os = "android",
+        target_os = "linux",
+        target_os = "windows",
+        target_os = "macos"
+    )))]
+    fn power_saver_platform(&self) -> bool {
+        false
+    }
+}
+
+fn power_state() -> &'static Arc<RwLock<PowerState>> {
+    static STATE: OnceLock<Arc<RwLock<PowerState>>> = OnceLock::new();
+    STATE.get_or_init(|| Arc::new(RwLock::new(PowerState::default())))
+}
@@ -0,0 +1,420 @@
+//! Share sheet and open-with integration for Tessera platform plugins.
+//!
+//! ## Usage
+//!
+//! Hand text and files to other apps from a button callback: send a link
+//! through the Android chooser, the macOS sharing picker or Windows Share, or
+//! open an exported file in the user's default app.
+//!
+//! Linux desktops have no share sheet, so [`share`] returns `false` there;
+//! [`open_with`] works everywhere. On Android, shared and opened files must
+//! live in the app's internal or external files or cache directories.
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, OnceLock},
+};
+
+use parking_lot::RwLock;
+use tessera_ui::{DesktopPlatformContext, Plugin, PluginContext, PluginResult, PxPosition};
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "linux",
+    target_os = "windows",
+    target_os = "macos"
+))]
+use tracing::warn;
+
+#[cfg(target_os = "android")]
+use tessera_ui::android::{ActivityRef, activity};
+#[cfg(target_os = "android")]
+use tessera_ui::winit::platform::android::activity::AndroidApp;
+
+#[cfg(target_os = "android")]
+tessera_ui::android::jni_bind! {
+    class "com.tessera.platform.SharePlugin" as SharePluginJni {
+        /// Opens the share chooser, returning whether it was shown.
+        fn share(activity: ActivityRef, title: &str, text: &str, paths: &str) -> bool;
+        /// Opens a file in an app chosen by the user.
+        fn openWith(activity: ActivityRef, path: &str) -> bool;
+    }
+}
+
+/// Share plugin that wires platform share sheets.
+#[derive(Clone, Debug)]
+pub struct SharePlugin;
+
+impl SharePlugin {
+    /// Creates a share plugin.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for SharePlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Plugin for SharePlugin {
+    fn on_resumed(&mut self, context: &PluginContext) -> PluginResult {
+        let mut state = share_state().write();
+        state.desktop = Some(context.desktop().clone());
+        #[cfg(target_os = "android")]
+        {
+            state.android_app = Some(context.android_app().clone());
+        }
+        Ok(())
+    }
+
+    fn on_suspended(&mut self, _context: &PluginContext) -> PluginResult {
+        share_state().write().release_platform();
+        Ok(())
+    }
+
+    fn on_shutdown(&mut self, _context: &PluginContext) -> PluginResult {
+        share_state().write().release_platform();
+        Ok(())
+    }
+}
+
+/// Text and files to share.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    not(any(target_os = "android", target_os = "windows", target_os = "macos")),
+    allow(dead_code)
+)]
+pub struct ShareContent {
+    title: Option<String>,
+    text: Option<String>,
+    files: Vec<PathBuf>,
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    anchor: Option<PxPosition>,
+}
+
+impl ShareContent {
+    /// Creates content that shares `text`, such as a message or a link.
+    pub fn text(text: impl Into<String>) -> Self {
+        Self {
+            text: Some(text.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Creates content that shares the files at `paths`.
+    pub fn files(paths: impl IntoIterator<Item = impl Into<PathBuf>>) -> Self {
+        Self {
+            files: paths.into_iter().map(Into::into).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Sets the title of the share sheet, also used as the subject by mail
+    /// apps.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Adds a file to share.
+    pub fn file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.files.push(path.into());
+        self
+    }
+
+    /// Points the macOS sharing picker at `position`, in physical pixels
+    /// relative to the window, such as the share button.
+    ///
+    /// Without an anchor the picker opens at the window's top-left corner.
+    pub fn anchor(mut self, position: PxPosition) -> Self {
+        self.anchor = Some(position);
+        self
+    }
+}
+
+/// Opens the platform share sheet with `content`.
+///
+/// Must be called on the UI thread. Returns whether a share sheet was shown.
+pub fn share(content: ShareContent) -> bool {
+    let state = share_state().read();
+    if state.desktop.is_none() || (content.text.is_none() && content.files.is_empty()) {
+        return false;
+    }
+    state.share_platform(&content)
+}
+
+/// Opens the file at `path` in the default app, or lets the user pick an app
+/// on Android.
+///
+/// Returns whether an app was launched.
+pub fn open_with(path: impl AsRef<Path>) -> bool {
+    let path = path.as_ref();
+    #[cfg(target_os = "android")]
+    {
+        let Some(android_app) = share_state().read().android_app.clone() else {
+            return false;
+        };
+        match SharePluginJni::openWith(
+            &android_app,
+            activity(&android_app),
+            &path.to_string_lossy(),
+        ) {
+            Ok(opened) => opened,
+            Err(err) => {
+                warn!("Android open with failed: {err}");
+                false
+            }
+        }
+    }
+    #[cfg(any(target_os = "linux", target_os = "windows", target_os = "macos"))]
+    {
+        let opener = if cfg!(target_os = "macos") {
+            "open"
+        } else if cfg!(target_os = "windows") {
+            "explorer"
+        } else {
+            "xdg-open"
+        };
+        match std::process::Command::new(opener).arg(path).spawn() {
+            Ok(_) => true,
+            Err(err) => {
+                warn!("Failed to open {} with {opener}: {err}", path.display());
+                false
+            }
+        }
+    }
+    #[cfg(not(any(
+        target_os = "android",
+        target_os = "linux",
+        target_os = "windows",
+        target_os = "macos"
+    )))]
+    {
+        let _ = path;
+        false
+    }
+}
+
+#[derive(Default)]
+struct ShareState {
+    desktop: Option<DesktopPlatformContext>,
+    #[cfg(target_os = "android")]
+    android_app: Option<AndroidApp>,
+}
+
+impl ShareState {
+    fn release_platform(&mut self) {
+        self.desktop = None;
+        #[cfg(target_os = "android")]
+        {
+            self.android_app = None;
+        }
+    }
+
+    #[cfg(target_os = "android")]
+    fn share_platform(&self, content: &ShareContent) -> bool {
+        let Some(android_app) = self.android_app.as_ref() else {
+            return false;
+        };
+        let paths = content
+            .files
+            .iter()
+            .map(|path| path.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("\n");
+        match SharePluginJni::share(
+            android_app,
+            activity(android_app),
+            content.title.as_deref().unwrap_or_default(),
+            content.text.as_deref().unwrap_or_default(),
+            &paths,
+        ) {
+            Ok(shown) => shown,
+            Err(err) => {
+                warn!("Android share failed: {err}");
+                false
+            }
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    fn share_platform(&self, content: &ShareContent) -> bool {
+        let Some(desktop) = self.desktop.as_ref() else {
+            return false;
+        };
+        macos::share(desktop, content)
+    }
+
+    #[cfg(target_os = "windows")]
+    fn share_platform(&self, content: &ShareContent) -> bool {
+        let Some(desktop) = self.desktop.as_ref() else {
+            return false;
+        };
+        match windows_share::share(desktop, content) {
+            Ok(()) => true,
+            Err(err) => {
+                warn!("Windows share failed: {err}");
+                false
+            }
+        }
+    }
+
+    #[cfg(not(any(target_os = "android", target_os = "windows", target_os = "macos")))]
+    fn share_platform(&self, _content: &ShareContent) -> bool {
+        false
+    }
+}
+
+fn share_state() -> &'static Arc<RwLock<ShareState>> {
+    static STATE: OnceLock<Arc<RwLock<ShareState>>> = OnceLock::new();
+    STATE.get_or_init(|| Arc::new(RwLock::new(ShareState::default())))
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use objc2::{MainThreadMarker, rc::Retained, runtime::AnyObject};
+    use objc2_app_kit::{NSSharingServicePicker, NSView};
+    use objc2_foundation::{NSArray, NSPoint, NSRect, NSRectEdge, NSSize, NSString, NSURL};
+    use tessera_ui::{
+        DesktopPlatformContext,
+        winit::raw_window_handle::{HasWindowHandle, RawWindowHandle},
+    };
+
+    use super::{ShareContent, warn};
+
+    thread_local! {
+        /// Keeps the picker alive while it is on screen.
+        static PICKER: std::cell::RefCell<Option<Retained<NSSharingServicePicker>>> =
+            const { std::cell::RefCell::new(None) };
+    }
+
+    pub(super) fn share(desktop: &DesktopPlatformContext, content: &ShareContent) -> bool {
+        let Some(mtm) = MainThreadMarker::new() else {
+            warn!("Sharing must start on the main thread");
+            return false;
+        };
+        let Ok(RawWindowHandle::AppKit(handle)) = desktop
+            .window()
+            .window_handle()
+            .map(|handle| handle.as_raw())
+        else {
+            return false;
+        };
+        // SAFETY: the view comes from winit's handle for the live window and
+        // is used on the main thread.
+        let Some(view) = (unsafe { Retained::<NSView>::retain(handle.ns_view.as_ptr().cast()) })
+        else {
+            return false;
+        };
+
+        let mut items: Vec<Retained<AnyObject>> = Vec::new();
+        if let Some(text) = &content.text {
+            items.push(Retained::into_super(Retained::into_super(
+                NSString::from_str(text),
+            )));
+        }
+        for path in &content.files {
+            let url = NSURL::fileURLWithPath(&NSString::from_str(&path.to_string_lossy()));
+            items.push(Retained::into_super(Retained::into_super(url)));
+        }
+        let items = NSArray::from_retained_slice(&items);
+        // SAFETY: every item is an NSString or NSURL, which the picker accepts.
+        let picker = unsafe {
+            NSSharingServicePicker::initWithItems(NSSharingServicePicker::alloc(mtm), &items)
+        };
+
+        let scale = desktop.window().scale_factor();
+        let (x, y) = content
+            .anchor
+            .map(|anchor| (f64::from(anchor.x.0) / scale, f64::from(anchor.y.0) / scale))
+            .unwrap_or_default();
+        let y = if view.isFlipped() {
+            y
+        } else {
+            view.bounds().size.height - y
+        };
+        let rect = NSRect::new(NSPoint::new(x, y), NSSize::new(1.0, 1.0));
+        picker.showRelativeToRect_ofView_preferredEdge(rect, &view, NSRectEdge::MinY);
+        PICKER.with(|slot| *slot.borrow_mut() = Some(picker));
+        true
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod windows_share {
+    use std::cell::RefCell;
+
+    use tessera_ui::{
+        DesktopPlatformContext,
+        winit::raw_window_handle::{HasWindowHandle, RawWindowHandle},
+    };
+    use windows::{
+        ApplicationModel::DataTransfer::{DataRequestedEventArgs, DataTransferManager},
+        Foundation::{Collections::IIterable, TypedEventHandler},
+        Storage::{IStorageItem, StorageFile},
+        Win32::{Foundation::HWND, UI::Shell::IDataTransferManagerInterop},
+        core::{Error, HSTRING, Interface, Ref, Result, factory},
+    };
+
+    use super::ShareContent;
+
+    thread_local! {
+        /// The handler filling the current share request, removed before the
+        /// next share replaces it.
+        static DATA_REQUESTED: RefCell<Option<(DataTransferManager, i64)>> =
+            const { RefCell::new(None) };
+    }
+
+    pub(super) fn share(desktop: &DesktopPlatformContext, content: &ShareContent) -> Result<()> {
+        let Ok(RawWindowHandle::Win32(handle)) = desktop
+            .window()
+            .window_handle()
+            .map(|handle| handle.as_raw())
+        else {
+            return Err(Error::empty());
+        };
+        let hwnd = HWND(handle.hwnd.get() as *mut _);
+        let interop = factory::<DataTransferManager, IDataTransferManagerInterop>()?;
+        // SAFETY: `hwnd` is the live window of this context.
+        let manager: DataTransferManager = unsafe { interop.GetForWindow(hwnd)? };
+
+        if let Some((manager, token)) = DATA_REQUESTED.with(|slot| slot.borrow_mut().take()) {
+            manager.RemoveDataRequested(token)?;
+        }
+        // Windows requires a title for every share.
+        let title = HSTRING::from(content.title.as_deref().unwrap_or(" "));
+        let text = content.text.as_deref().map(HSTRING::from);
+        let files = content
+            .files
+            .iter()
+            .map(|path| HSTRING::from(path.as_os_str()))
+            .collect::<Vec<_>>();
+        let token = manager.DataRequested(&TypedEventHandler::new(
+            move |_, args: Ref<DataRequestedEventArgs>| {
+                let data = args.ok()?.Request()?.Data()?;
+                data.Properties()?.SetTitle(&title)?;
+                if let Some(text) = &text {
+                    data.SetText(text)?;
+                }
+                if !files.is_empty() {
+                    let items = files
+                        .iter()
+                        .map(|path| {
+                            StorageFile::GetFileFromPathAsync(path)?
+                                .get()?
+                                .cast::<IStorageItem>()
+                                .map(Some)
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    data.SetStorageItemsReadOnly(&IIterable::from(items))?;
+                }
+                Ok(())
+            },
+        ))?;
+        DATA_REQUESTED.with(|slot| *slot.borrow_mut() = Some((manager, token)));
+
+        // SAFETY: `hwnd` is the live window of this context.
+        unsafe { interop.ShowShareUIForWindow(hwnd) }
+    }
+}
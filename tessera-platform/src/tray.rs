@@ -0,0 +1,418 @@
+//! System tray icons for Tessera platform plugins.
+//!
+//! ## Usage
+//!
+//! Keep long-running apps reachable from the Windows notification area, the
+//! macOS menu bar or a Linux status notifier, with a tooltip and a menu of
+//! actions, and minimize them to the tray instead of the taskbar.
+//!
+//! Call [`set_tray`] from the UI thread. Menu and icon callbacks run on the UI
+//! thread too. On Linux the tray lives on its own GTK thread, so it needs GTK
+//! 3 and a desktop with a status notifier host.
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock},
+};
+
+use parking_lot::RwLock;
+use tessera_ui::{DesktopPlatformContext, Plugin, PluginContext, PluginResult};
+
+#[cfg(any(target_os = "linux", target_os = "windows", target_os = "macos"))]
+use std::cell::RefCell;
+
+#[cfg(any(target_os = "linux", target_os = "windows", target_os = "macos"))]
+use tracing::warn;
+
+/// Tray plugin that wires platform tray icon services.
+#[derive(Clone, Debug)]
+pub struct TrayPlugin;
+
+impl TrayPlugin {
+    /// Creates a tray plugin.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for TrayPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Plugin for TrayPlugin {
+    fn on_resumed(&mut self, context: &PluginContext) -> PluginResult {
+        tray_state().write().desktop = Some(context.desktop().clone());
+        Ok(())
+    }
+
+    fn on_suspended(&mut self, _context: &PluginContext) -> PluginResult {
+        tray_state().write().desktop = None;
+        Ok(())
+    }
+
+    fn on_shutdown(&mut self, _context: &PluginContext) -> PluginResult {
+        remove_tray();
+        tray_state().write().desktop = None;
+        Ok(())
+    }
+}
+
+/// Image shown as the tray icon.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrayIconImage {
+    rgba: Vec<u8>,
+    width: u32,
+    height: u32,
+}
+
+impl TrayIconImage {
+    /// Creates an icon from tightly packed RGBA pixels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rgba` does not hold `width * height` pixels.
+    pub fn from_rgba(rgba: Vec<u8>, width: u32, height: u32) -> Self {
+        assert_eq!(
+            rgba.len(),
+            width as usize * height as usize * 4,
+            "tray icon pixels do not match its {width}x{height} size"
+        );
+        Self {
+            rgba,
+            width,
+            height,
+        }
+    }
+}
+
+/// An entry in the tray menu.
+#[derive(Clone)]
+pub enum TrayMenuItem {
+    /// A clickable entry.
+    Action {
+        /// User-visible label.
+        label: String,
+        /// Whether the entry can be clicked.
+        enabled: bool,
+        /// Runs on the UI thread when the entry is clicked.
+        on_click: Arc<dyn Fn() + Send + Sync>,
+    },
+    /// A separator line.
+    Separator,
+}
+
+impl TrayMenuItem {
+    /// Creates an enabled entry that runs `on_click`.
+    pub fn action(label: impl Into<String>, on_click: impl Fn() + Send + Sync + 'static) -> Self {
+        Self::Action {
+            label: label.into(),
+            enabled: true,
+            on_click: Arc::new(on_click),
+        }
+    }
+
+    /// Creates a separator line.
+    pub fn separator() -> Self {
+        Self::Separator
+    }
+
+    /// Sets whether an entry can be clicked. Separators are unaffected.
+    pub fn enabled(mut self, value: bool) -> Self {
+        if let Self::Action { enabled, .. } = &mut self {
+            *enabled = value;
+        }
+        self
+    }
+}
+
+/// A tray icon with its tooltip and menu.
+#[derive(Clone)]
+pub struct Tray {
+    icon: TrayIconImage,
+    tooltip: Option<String>,
+    menu: Vec<TrayMenuItem>,
+    hide_on_minimize: bool,
+    on_click: Option<ClickHandler>,
+}
+
+impl Tray {
+    /// Creates a tray with an icon and no menu.
+    pub fn new(icon: TrayIconImage) -> Self {
+        Self {
+            icon,
+            tooltip: None,
+            menu: Vec::new(),
+            hide_on_minimize: false,
+            on_click: None,
+        }
+    }
+
+    /// Sets the tooltip shown when hovering the icon.
+    pub fn tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Appends a menu entry. The menu opens on right click.
+    pub fn item(mut self, item: TrayMenuItem) -> Self {
+        self.menu.push(item);
+        self
+    }
+
+    /// Hides the window to the tray when it is minimized through
+    /// [`window::minimize`](crate::window::minimize).
+    ///
+    /// Clicking the icon shows the window again.
+    pub fn hide_on_minimize(mut self, hide_on_minimize: bool) -> Self {
+        self.hide_on_minimize = hide_on_minimize;
+        self
+    }
+
+    /// Runs `on_click` on the UI thread when the icon is left-clicked.
+    pub fn on_click(mut self, on_click: impl Fn() + Send + Sync + 'static) -> Self {
+        self.on_click = Some(Arc::new(on_click));
+        self
+    }
+}
+
+/// Shows `tray`, replacing the current tray icon.
+pub fn set_tray(tray: Tray) {
+    let mut state = tray_state().write();
+    state.next_generation += 1;
+    let generation = state.next_generation;
+    state.menu_callbacks.clear();
+    let mut items = Vec::with_capacity(tray.menu.len());
+    for (index, item) in tray.menu.into_iter().enumerate() {
+        match item {
+            TrayMenuItem::Action {
+                label,
+                enabled,
+                on_click,
+            } => {
+                let id = format!("tessera-tray-{generation}-{index}");
+                state.menu_callbacks.insert(id.clone(), on_click);
+                items.push(NativeMenuItem::Action { id, label, enabled });
+            }
+            TrayMenuItem::Separator => items.push(NativeMenuItem::Separator),
+        }
+    }
+    state.on_click = tray.on_click;
+    state.hide_on_minimize = tray.hide_on_minimize;
+    state.shown = true;
+    drop(state);
+
+    native::show(NativeTray {
+        icon: tray.icon,
+        tooltip: tray.tooltip,
+        items,
+    });
+}
+
+/// Removes the tray icon and shows the window if it was hidden to the tray.
+pub fn remove_tray() {
+    let mut state = tray_state().write();
+    if !std::mem::take(&mut state.shown) {
+        return;
+    }
+    state.menu_callbacks.clear();
+    state.on_click = None;
+    state.hide_on_minimize = false;
+    if let Some(desktop) = &state.desktop {
+        desktop.show();
+    }
+    drop(state);
+    native::remove();
+}
+
+/// Hides the window while a tray icon is shown to bring it back.
+pub fn hide_to_tray() {
+    let state = tray_state().read();
+    if state.shown
+        && let Some(desktop) = &state.desktop
+    {
+        desktop.hide();
+    }
+}
+
+/// Shows the window again after [`hide_to_tray`].
+pub fn restore_from_tray() {
+    if let Some(desktop) = &tray_state().read().desktop {
+        desktop.show();
+    }
+}
+
+/// Runs the tray menu entry with `id`, returning whether the tray owns it.
+#[cfg(any(target_os = "linux", target_os = "windows", target_os = "macos"))]
+pub(crate) fn dispatch_menu_event(id: &str) -> bool {
+    let state = tray_state().read();
+    let Some(on_click) = state.menu_callbacks.get(id).cloned() else {
+        return false;
+    };
+    if let Some(desktop) = &state.desktop {
+        desktop.run_on_ui_thread(move || on_click());
+    }
+    true
+}
+
+/// Returns whether minimizing should hide the window to the tray instead.
+pub(crate) fn hides_on_minimize() -> bool {
+    let state = tray_state().read();
+    state.shown && state.hide_on_minimize
+}
+
+/// Tray handlers are owned because the tray outlives the component that set
+/// it, and slot-backed callbacks are freed with their component.
+type ClickHandler = Arc<dyn Fn() + Send + Sync>;
+
+#[derive(Default)]
+struct TrayState {
+    desktop: Option<DesktopPlatformContext>,
+    shown: bool,
+    hide_on_minimize: bool,
+    on_click: Option<ClickHandler>,
+    menu_callbacks: HashMap<String, ClickHandler>,
+    next_generation: u64,
+}
+
+fn tray_state() -> &'static Arc<RwLock<TrayState>> {
+    static STATE: OnceLock<Arc<RwLock<TrayState>>> = OnceLock::new();
+    STATE.get_or_init(|| Arc::new(RwLock::new(TrayState::default())))
+}
+
+/// Menu item handed to the native tray, with callbacks replaced by ids.
+#[cfg_attr(
+    not(any(target_os = "linux", target_os = "windows", target_os = "macos")),
+    allow(dead_code)
+)]
+enum NativeMenuItem {
+    Action {
+        id: String,
+        label: String,
+        enabled: bool,
+    },
+    Separator,
+}
+
+#[cfg_attr(
+    not(any(target_os = "linux", target_os = "windows", target_os = "macos")),
+    allow(dead_code)
+)]
+struct NativeTray {
+    icon: TrayIconImage,
+    tooltip: Option<String>,
+    items: Vec<NativeMenuItem>,
+}
+
+#[cfg(any(target_os = "linux", target_os = "windows", target_os = "macos"))]
+mod native {
+    use std::sync::Once;
+
+    use tray_icon::{
+        Icon, MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent,
+        menu::{Menu, MenuItem, PredefinedMenuItem},
+    };
+
+    use super::{NativeMenuItem, NativeTray, RefCell, tray_state, warn};
+
+    thread_local! {
+        static TRAY: RefCell<Option<TrayIcon>> = const { RefCell::new(None) };
+    }
+
+    pub(super) fn show(tray: NativeTray) {
+        install_event_handlers();
+        run_on_tray_thread(move || {
+            // Drop the old icon first so the two never show at once.
+            TRAY.with(|slot| slot.borrow_mut().take());
+            match build(tray) {
+                Ok(icon) => TRAY.with(|slot| *slot.borrow_mut() = Some(icon)),
+                Err(err) => warn!("Failed to create tray icon: {err}"),
+            }
+        });
+    }
+
+    pub(super) fn remove() {
+        run_on_tray_thread(|| {
+            TRAY.with(|slot| slot.borrow_mut().take());
+        });
+    }
+
+    fn build(tray: NativeTray) -> Result<TrayIcon, Box<dyn std::error::Error>> {
+        let menu = Menu::new();
+        for item in tray.items {
+            match item {
+                NativeMenuItem::Action { id, label, enabled } => {
+                    menu.append(&MenuItem::with_id(id, label, enabled, None))?;
+                }
+                NativeMenuItem::Separator => menu.append(&PredefinedMenuItem::separator())?,
+            }
+        }
+        let icon = Icon::from_rgba(tray.icon.rgba, tray.icon.width, tray.icon.height)?;
+        let mut builder = TrayIconBuilder::new()
+            .with_icon(icon)
+            .with_menu(Box::new(menu))
+            .with_menu_on_left_click(false);
+        if let Some(tooltip) = tray.tooltip {
+            builder = builder.with_tooltip(tooltip);
+        }
+        Ok(builder.build()?)
+    }
+
+    /// Routes clicks, which arrive on the tray thread, to the UI thread.
+    fn install_event_handlers() {
+        crate::menu::install_menu_event_handler();
+        static INSTALL: Once = Once::new();
+        INSTALL.call_once(|| {
+            TrayIconEvent::set_event_handler(Some(|event: TrayIconEvent| {
+                let TrayIconEvent::Click {
+                    button: MouseButton::Left,
+                    button_state: MouseButtonState::Up,
+                    ..
+                } = event
+                else {
+                    return;
+                };
+                let state = tray_state().read();
+                let Some(desktop) = &state.desktop else {
+                    return;
+                };
+                desktop.show();
+                if let Some(on_click) = state.on_click.clone() {
+                    desktop.run_on_ui_thread(move || on_click());
+                }
+            }));
+        });
+    }
+
+    /// GTK must own the tray on Linux, so it gets a thread running the GTK
+    /// main loop.
+    #[cfg(target_os = "linux")]
+    fn run_on_tray_thread(task: impl FnOnce() + Send + 'static) {
+        static GTK_THREAD: Once = Once::new();
+        GTK_THREAD.call_once(|| {
+            std::thread::spawn(|| {
+                if let Err(err) = gtk::init() {
+                    warn!("Failed to initialize GTK for the tray icon: {err}");
+                    return;
+                }
+                gtk::main();
+            });
+        });
+        gtk::glib::idle_add_once(task);
+    }
+
+    /// Windows and macOS own the tray on the UI thread.
+    #[cfg(not(target_os = "linux"))]
+    fn run_on_tray_thread(task: impl FnOnce() + Send + 'static) {
+        task();
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+mod native {
+    use super::NativeTray;
+
+    pub(super) fn show(_tray: NativeTray) {}
+
+    pub(super) fn remove() {}
+}
@@ -62,7 +62,14 @@ pub fn with_desktop<R>(f: impl FnOnce(&DesktopPlatformContext) -> R) -> Option<R
 
 /// Minimizes the current application window when desktop services are
 /// available.
+///
+/// Hides the window to the tray instead when the current
+/// [`Tray`](crate::tray::Tray) asks for it.
 pub fn minimize() {
+    if crate::tray::hides_on_minimize() {
+        crate::tray::hide_to_tray();
+        return;
+    }
     let _ = with_desktop(|desktop| desktop.minimize());
 }
 
@@ -83,3 +90,12 @@ pub fn toggle_maximize() {
 pub fn close() {
     let _ = with_desktop(|desktop| desktop.request_close());
 }
+
+/// Shows or hides the native title bar and borders of the current application
+/// window when desktop services are available.
+///
+/// Hide them to draw a custom title bar with
+/// `Modifier::window_drag_region` and `Modifier::window_resize_region`.
+pub fn set_decorations(decorations: bool) {
+    let _ = with_desktop(|desktop| desktop.set_decorations(decorations));
+}
@@ -1,10 +1,12 @@
 #![allow(missing_docs)]
 
+use serde::{Serialize, de::DeserializeOwned};
 use tessera_ui::State;
 
 use crate::{ShardState, ShardStateLifeCycle, router::RouterController};
 
 pub use crate::__resolve_router_controller as current_router_controller;
+pub use serde;
 
 pub fn with_current_router_shard_state<T, F, R>(
     shard_id: &str,
@@ -18,3 +20,16 @@ where
 {
     crate::router::with_current_router_shard_state(shard_id, life_cycle, controller, f)
 }
+
+pub fn with_current_router_saveable_shard_state<T, F, R>(
+    shard_id: &str,
+    life_cycle: ShardStateLifeCycle,
+    controller: State<RouterController>,
+    f: F,
+) -> R
+where
+    T: Default + Serialize + DeserializeOwned + Send + Sync + 'static,
+    F: FnOnce(ShardState<T>) -> R,
+{
+    crate::router::with_current_router_saveable_shard_state(shard_id, life_cycle, controller, f)
+}
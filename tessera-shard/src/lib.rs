@@ -22,6 +22,6 @@ pub use tessera_macros::shard;
 
 pub use crate::{
     async_support::task_handles,
-    router::{RouterController, RouterDestination, shard_home},
+    router::{DeepLinkRegistry, RouterController, RouterDestination, shard_home, shared_element},
     state::{ShardState, ShardStateLifeCycle},
 };
@@ -2,16 +2,44 @@
 //!
 //! ## Usage
 //!
-//! Mount `shard_home` at the app shell root to render the current shard page.
+//! Mount `shard_home` at the app shell root to render the current shard page,
+//! register a `DeepLinkRegistry` to open shards from external links, and
+//! attach a `RouterTransition` to animate navigation. Mount named `shard_home`s
+//! inside a shard page for panes with their own back stack, such as the two
+//! sides of a master/detail layout. Give a `shard_home` a save key to restore
+//! its navigation stack after process death. Push pickers and dialogs with
+//! `push_for_result` to await the value they are popped with, and give
+//! `shard_home` `RouteGuards` to put destinations behind a login wall. Add a
+//! navigation listener to a controller to log analytics breadcrumbs. Wrap the
+//! same item on two screens in a `shared_element` with one key to morph it
+//! between them during animated navigation.
 
 mod controller;
+mod deep_link;
 mod destination;
+mod guard;
 mod home;
+mod observer;
+mod result;
+mod saved;
+mod shared_element;
 mod state;
+mod transition;
 
 pub use controller::RouterController;
+pub use deep_link::{DeepLinkError, DeepLinkRegistry, RouteParams};
 pub use destination::RouterDestination;
+pub use guard::{GuardDecision, RouteGuards};
 pub use home::shard_home;
+pub use observer::{NavigationAction, NavigationEvent, NavigationListenerId};
+pub use result::NavigationResult;
+pub use saved::{DestinationRestorer, SavedDestination, SavedRouterState, SerializableDestination};
+pub use shared_element::shared_element;
+pub use transition::{
+    RouterTransition, TransitionAxis, TransitionDirection, TransitionFrame, TransitionRole,
+    TransitionStyle,
+};
 
-pub(crate) use home::with_current_router_shard_state;
-pub(crate) use state::{RouteId, RouteShardKey, RouterContext};
+pub(crate) use home::{with_current_router_saveable_shard_state, with_current_router_shard_state};
+pub(crate) use saved::SavedRoute;
+pub(crate) use state::{ChildScopeKey, RouteId, RouteScope, RouteShardKey, RouterContext};
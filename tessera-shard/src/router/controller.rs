@@ -1,23 +1,141 @@
 use std::{collections::HashSet, sync::Arc};
 
+use dashmap::DashMap;
+use futures_util::FutureExt;
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use tessera_ui::{State, current_frame_nanos, system_preferences::system_preferences};
+
 use crate::{
-    router::{RouteId, RouteShardKey, RouterDestination},
+    async_support::task_handles::TaskHandles,
+    router::{
+        ChildScopeKey, DeepLinkError, DeepLinkRegistry, DestinationRestorer, GuardDecision,
+        NavigationAction, NavigationEvent, NavigationListenerId, NavigationResult, RouteGuards,
+        RouteId, RouteShardKey, RouterDestination, RouterTransition, SavedRoute, SavedRouterState,
+        TransitionDirection, TransitionFrame, TransitionRole, TransitionStyle,
+        guard::{GuardCheck, GuardFuture},
+        observer::NavigationLog,
+        result::{PendingResult, result_channel, send_result},
+        shared_element::{SharedElements, SharedTransition},
+        transition::ease,
+    },
     state::{
-        ShardState, ShardStateLifeCycle, ShardStateMap, init_or_get_shard_state_in_map,
-        recycle_shard_state_slot,
+        ShardState, ShardStateLifeCycle, ShardStateMap, ShardStateSlot,
+        init_or_get_shard_state_in_map, recycle_shard_state_slot,
     },
 };
 
+#[derive(Clone)]
 struct RouteEntry {
     route_id: RouteId,
     destination: Arc<dyn RouterDestination>,
 }
 
+struct ActiveTransition {
+    spec: RouterTransition,
+    direction: TransitionDirection,
+    exiting: RouteEntry,
+    /// Whether the exiting route left the stack and must be pruned at the end.
+    exiting_removed: bool,
+    start_frame_nanos: u64,
+}
+
+impl ActiveTransition {
+    fn progress_at(&self, frame_nanos: u64) -> f32 {
+        let elapsed = frame_nanos.saturating_sub(self.start_frame_nanos);
+        ease(elapsed as f32 / self.spec.duration_nanos() as f32)
+    }
+}
+
+type SaveShardState = fn(ShardStateSlot) -> Option<Value>;
+
+/// Redirects followed by one navigation before it is cancelled as a loop.
+const MAX_GUARD_REDIRECTS: usize = 8;
+
+#[derive(Clone, Copy)]
+enum NavigationKind {
+    Push,
+    Replace,
+    Reset,
+}
+
+/// A navigation that still has to pass the guards.
+struct Navigation {
+    destination: Arc<dyn RouterDestination>,
+    kind: NavigationKind,
+    /// Result awaited from the destination; dropped on redirect.
+    result: Option<PendingResult>,
+    next_guard: usize,
+    redirects: usize,
+}
+
+impl Navigation {
+    fn new(
+        destination: Arc<dyn RouterDestination>,
+        kind: NavigationKind,
+        result: Option<PendingResult>,
+    ) -> Self {
+        Self {
+            destination,
+            kind,
+            result,
+            next_guard: 0,
+            redirects: 0,
+        }
+    }
+
+    /// Retargets the navigation, or returns `false` once it redirected too
+    /// often.
+    fn redirect_to(&mut self, destination: Arc<dyn RouterDestination>) -> bool {
+        self.redirects += 1;
+        self.destination = destination;
+        self.result = None;
+        self.next_guard = 0;
+        self.redirects <= MAX_GUARD_REDIRECTS
+    }
+}
+
+/// A navigation waiting for an async guard.
+struct PendingNavigation {
+    navigation: Navigation,
+    decision: NavigationResult<GuardDecision>,
+    /// Aborts the guard future when the navigation is dropped.
+    _task: TaskHandles,
+}
+
+/// A route rendered by `shard_home` for the current frame.
+pub(crate) struct VisibleRoute {
+    pub(crate) route_id: RouteId,
+    pub(crate) destination: Arc<dyn RouterDestination>,
+    pub(crate) style: TransitionStyle,
+}
+
 /// Reactive navigation controller for one shard tree.
+///
+/// Every `shard_home` owns an independent back stack. A `shard_home` mounted
+/// with a `name` inside another one registers as a child scope of the
+/// enclosing router, which can then address it with [`Self::child_scope`].
 pub struct RouterController {
     route_stack: Vec<RouteEntry>,
+    transition: Option<ActiveTransition>,
+    default_transition: RouterTransition,
+    parent: Option<State<RouterController>>,
+    child_scopes: DashMap<ChildScopeKey, State<RouterController>>,
     scope_shards: ShardStateMap<String>,
     route_shards: ShardStateMap<RouteShardKey>,
+    /// Serializers of the saveable entries of `scope_shards`.
+    saveable_scope_shards: DashMap<String, SaveShardState>,
+    /// Serializers of the saveable entries of `route_shards`.
+    saveable_route_shards: DashMap<RouteShardKey, SaveShardState>,
+    /// Restored shard state waiting for its shard to be built.
+    restored_scope_shards: DashMap<String, Value>,
+    restored_route_shards: DashMap<RouteShardKey, Value>,
+    /// Results awaited from routes pushed with [`Self::push_for_result`].
+    pending_results: DashMap<RouteId, PendingResult>,
+    guards: Option<Arc<RouteGuards>>,
+    pending_navigation: Option<PendingNavigation>,
+    navigation_log: NavigationLog,
+    shared_elements: Arc<SharedElements>,
 }
 
 impl RouterController {
@@ -25,8 +143,21 @@ impl RouterController {
     pub fn new() -> Self {
         Self {
             route_stack: Vec::new(),
+            transition: None,
+            default_transition: RouterTransition::none(),
+            parent: None,
+            child_scopes: DashMap::new(),
             scope_shards: Default::default(),
             route_shards: Default::default(),
+            saveable_scope_shards: DashMap::new(),
+            saveable_route_shards: DashMap::new(),
+            restored_scope_shards: DashMap::new(),
+            restored_route_shards: DashMap::new(),
+            pending_results: DashMap::new(),
+            guards: None,
+            pending_navigation: None,
+            navigation_log: NavigationLog::default(),
+            shared_elements: Arc::default(),
         }
     }
 
@@ -44,17 +175,114 @@ impl RouterController {
         router
     }
 
+    /// Set the transition used by destinations that do not define their own.
+    pub fn set_default_transition(&mut self, transition: RouterTransition) {
+        self.default_transition = transition;
+    }
+
+    /// Check every later push, replace and reset with `guards`.
+    pub fn set_guards(&mut self, guards: RouteGuards) {
+        self.set_shared_guards(Arc::new(guards));
+    }
+
+    pub(crate) fn set_shared_guards(&mut self, guards: Arc<RouteGuards>) {
+        self.guards = Some(guards);
+    }
+
+    pub(crate) fn has_guards(&self, guards: &Arc<RouteGuards>) -> bool {
+        self.guards
+            .as_ref()
+            .is_some_and(|current| Arc::ptr_eq(current, guards))
+    }
+
     /// Push a destination onto the stack.
     pub fn push<T: RouterDestination + 'static>(&mut self, destination: T) {
         self.push_shared(Arc::new(destination));
     }
 
     /// Push a shared destination onto the stack.
+    ///
+    /// The destination is shown once the guards allow it, see
+    /// [`Self::set_guards`].
     pub fn push_shared(&mut self, destination: Arc<dyn RouterDestination>) {
+        self.navigate(Navigation::new(destination, NavigationKind::Push, None));
+    }
+
+    fn push_route(&mut self, destination: Arc<dyn RouterDestination>) {
+        let spec = self.transition_of(&*destination);
+        if let Some(covered) = self.route_stack.last().cloned() {
+            self.begin_transition(spec, TransitionDirection::Push, covered, false);
+        }
+        let shard_id = destination.shard_id();
         self.route_stack.push(RouteEntry {
             route_id: RouteId::new(),
             destination,
         });
+        self.record(NavigationAction::Pushed { shard_id });
+    }
+
+    /// Push a destination and wait for the value it is popped with.
+    ///
+    /// The returned future resolves once the destination leaves the stack:
+    /// to `Some` when it is popped with [`Self::pop_with_result`] and a value
+    /// of type `R`, otherwise to `None`. It also resolves to `None` when a
+    /// guard cancels or redirects the navigation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tessera_shard::router::{RouterController, RouterDestination};
+    ///
+    /// struct ColorPicker;
+    ///
+    /// impl RouterDestination for ColorPicker {
+    ///     fn exec_component(&self) {}
+    ///     fn destination_id() -> &'static str {
+    ///         "color_picker"
+    ///     }
+    /// }
+    ///
+    /// let mut router = RouterController::new();
+    /// let color = router.push_for_result::<u32, _>(ColorPicker);
+    /// router.pop_with_result(0xff0000_u32);
+    /// assert_eq!(
+    ///     futures_util::FutureExt::now_or_never(color),
+    ///     Some(Some(0xff0000))
+    /// );
+    /// ```
+    pub fn push_for_result<R, T>(&mut self, destination: T) -> NavigationResult<R>
+    where
+        R: Send + 'static,
+        T: RouterDestination + 'static,
+    {
+        self.push_shared_for_result(Arc::new(destination))
+    }
+
+    /// Push a shared destination and wait for the value it is popped with.
+    ///
+    /// See [`Self::push_for_result`].
+    pub fn push_shared_for_result<R: Send + 'static>(
+        &mut self,
+        destination: Arc<dyn RouterDestination>,
+    ) -> NavigationResult<R> {
+        let (pending, result) = result_channel();
+        self.navigate(Navigation::new(
+            destination,
+            NavigationKind::Push,
+            Some(pending),
+        ));
+        result
+    }
+
+    /// Resolve `uri` with `registry` and push the resulting destination.
+    pub fn open_deep_link(
+        &mut self,
+        registry: &DeepLinkRegistry,
+        uri: &str,
+    ) -> Result<(), DeepLinkError> {
+        let destination = registry.resolve(uri)?;
+        self.push_shared(destination);
+        Ok(())
     }
 
     /// Pop the top destination from the stack.
@@ -62,8 +290,33 @@ impl RouterController {
     /// Returns `None` if the stack is empty.
     pub fn pop(&mut self) -> Option<Arc<dyn RouterDestination>> {
         let removed = self.route_stack.pop()?;
-        self.prune_route_shards(removed.route_id);
-        Some(removed.destination)
+        self.pending_results.remove(&removed.route_id);
+        let destination = removed.destination.clone();
+        if self.route_stack.is_empty() {
+            self.finish_transition();
+            self.prune_route_shards(removed.route_id);
+        } else {
+            let spec = self.transition_of(&*destination);
+            self.begin_transition(spec, TransitionDirection::Pop, removed, true);
+        }
+        self.record(NavigationAction::Popped {
+            shard_id: destination.shard_id(),
+        });
+        Some(destination)
+    }
+
+    /// Pop the top destination and deliver `result` to its caller.
+    ///
+    /// `result` is dropped unless the destination was pushed with
+    /// [`Self::push_for_result`] for a value of type `R`.
+    pub fn pop_with_result<R: 'static>(&mut self, result: R) -> Option<Arc<dyn RouterDestination>> {
+        let pending = self
+            .current_route_id()
+            .and_then(|route_id| self.pending_results.remove(&route_id));
+        if let Some((_, pending)) = pending {
+            send_result(pending, result);
+        }
+        self.pop()
     }
 
     /// Replace the top destination.
@@ -73,19 +326,42 @@ impl RouterController {
         &mut self,
         destination: T,
     ) -> Option<Arc<dyn RouterDestination>> {
-        let previous = self.pop();
-        self.push(destination);
-        previous
+        self.replace_shared(Arc::new(destination))
     }
 
     /// Replace the top destination with a shared destination.
+    ///
+    /// Returns the replaced destination, or `None` if the stack was empty or
+    /// the guards did not allow the replacement right away.
     pub fn replace_shared(
         &mut self,
         destination: Arc<dyn RouterDestination>,
     ) -> Option<Arc<dyn RouterDestination>> {
-        let previous = self.pop();
-        self.push_shared(destination);
-        previous
+        self.navigate(Navigation::new(destination, NavigationKind::Replace, None))
+    }
+
+    fn replace_route(
+        &mut self,
+        destination: Arc<dyn RouterDestination>,
+    ) -> Option<Arc<dyn RouterDestination>> {
+        let Some(removed) = self.route_stack.pop() else {
+            self.push_route(destination);
+            return None;
+        };
+        self.pending_results.remove(&removed.route_id);
+        let previous = removed.destination.clone();
+        let action = NavigationAction::Replaced {
+            from: previous.shard_id(),
+            to: destination.shard_id(),
+        };
+        let spec = self.transition_of(&*destination);
+        self.begin_transition(spec, TransitionDirection::Push, removed, true);
+        self.route_stack.push(RouteEntry {
+            route_id: RouteId::new(),
+            destination,
+        });
+        self.record(action);
+        Some(previous)
     }
 
     /// Whether the stack is empty.
@@ -112,16 +388,192 @@ impl RouterController {
             .is_some_and(|current| current.type_id() == std::any::TypeId::of::<D>())
     }
 
+    /// Whether a push or pop transition is still animating.
+    pub fn is_transitioning(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    /// Shard ids of the destinations in the stack, bottom first.
+    pub fn current_stack(&self) -> Vec<&'static str> {
+        self.route_stack
+            .iter()
+            .map(|entry| entry.destination.shard_id())
+            .collect()
+    }
+
+    /// Call `listener` after every change of the stack.
+    ///
+    /// The listener runs while the controller is being mutated, so it must
+    /// not access the router state; record the event or hand it to another
+    /// task instead.
+    pub fn add_navigation_listener<F>(&mut self, listener: F) -> NavigationListenerId
+    where
+        F: Fn(&NavigationEvent) + Send + Sync + 'static,
+    {
+        self.navigation_log.add_listener(listener)
+    }
+
+    /// Remove a listener added with [`Self::add_navigation_listener`].
+    ///
+    /// Returns `false` if the listener was already removed.
+    pub fn remove_navigation_listener(&mut self, id: NavigationListenerId) -> bool {
+        self.navigation_log.remove_listener(id)
+    }
+
+    /// The most recent navigation events, oldest first.
+    ///
+    /// Attach them to crash reports to see how the user got to a screen.
+    pub fn breadcrumbs(&self) -> impl Iterator<Item = &NavigationEvent> {
+        self.navigation_log.breadcrumbs()
+    }
+
+    /// Set how many breadcrumbs are kept; the default is 32.
+    pub fn set_breadcrumb_limit(&mut self, limit: usize) {
+        self.navigation_log.set_breadcrumb_limit(limit);
+    }
+
+    /// Whether a navigation is waiting for an async guard.
+    pub fn is_navigation_pending(&self) -> bool {
+        self.pending_navigation.is_some()
+    }
+
+    /// Drop the navigation waiting for an async guard.
+    ///
+    /// Returns `false` if no navigation was waiting.
+    pub fn cancel_pending_navigation(&mut self) -> bool {
+        self.pending_navigation.take().is_some()
+    }
+
+    pub(crate) fn is_pending_navigation_decided(&self) -> bool {
+        self.pending_navigation
+            .as_ref()
+            .is_some_and(|pending| pending.decision.is_ready())
+    }
+
+    /// Continues the waiting navigation once its async guard decided.
+    pub(crate) fn resume_pending_navigation(&mut self) {
+        if !self.is_pending_navigation_decided() {
+            return;
+        }
+        let Some(mut pending) = self.pending_navigation.take() else {
+            return;
+        };
+        let mut navigation = pending.navigation;
+        // A guard task that panicked or was aborted cancels the navigation.
+        match (&mut pending.decision).now_or_never().flatten() {
+            Some(GuardDecision::Allow) => {
+                self.run_guards(navigation);
+            }
+            Some(GuardDecision::Redirect(destination)) if navigation.redirect_to(destination) => {
+                self.run_guards(navigation);
+            }
+            _ => {}
+        }
+    }
+
+    /// The router hosting this one, if it was mounted as a named child scope.
+    pub fn parent(&self) -> Option<State<RouterController>> {
+        self.parent.filter(State::is_alive)
+    }
+
+    /// The child scope called `name` mounted by the current destination.
+    ///
+    /// Scopes mounted outside of any destination of this router, for example
+    /// next to its `shard_home`, are found as well. Returns `None` when no
+    /// such scope is mounted.
+    pub fn child_scope(&self, name: &str) -> Option<State<RouterController>> {
+        [self.current_route_id(), None]
+            .into_iter()
+            .find_map(|route_id| {
+                let key = ChildScopeKey {
+                    route_id,
+                    name: name.to_owned(),
+                };
+                self.child_scopes.get(&key).map(|scope| *scope)
+            })
+            .filter(State::is_alive)
+    }
+
+    /// Records `scope` as the child called `name` of the route `route_id`.
+    pub(crate) fn register_child_scope(
+        &self,
+        route_id: Option<RouteId>,
+        name: &str,
+        scope: State<RouterController>,
+    ) {
+        self.child_scopes.insert(
+            ChildScopeKey {
+                route_id,
+                name: name.to_owned(),
+            },
+            scope,
+        );
+    }
+
+    pub(crate) fn set_parent(&mut self, parent: Option<State<RouterController>>) {
+        self.parent = parent;
+    }
+
+    pub(crate) fn has_parent(&self, parent: Option<State<RouterController>>) -> bool {
+        self.parent == parent
+    }
+
     pub(crate) fn current_route_id(&self) -> Option<RouteId> {
         self.route_stack.last().map(|entry| entry.route_id)
     }
 
-    pub(crate) fn exec_current(&self) -> bool {
-        let Some(entry) = self.route_stack.last() else {
-            return false;
+    /// Routes to render at `frame_nanos`, bottom first.
+    pub(crate) fn visible_routes(&self, frame_nanos: u64) -> Vec<VisibleRoute> {
+        let Some(current) = self.route_stack.last() else {
+            return Vec::new();
         };
-        entry.destination.exec_component();
-        true
+        let Some(transition) = &self.transition else {
+            return vec![VisibleRoute {
+                route_id: current.route_id,
+                destination: current.destination.clone(),
+                style: TransitionStyle::IDENTITY,
+            }];
+        };
+
+        let progress = transition.progress_at(frame_nanos);
+        let route = |entry: &RouteEntry, role| VisibleRoute {
+            route_id: entry.route_id,
+            destination: entry.destination.clone(),
+            style: transition.spec.style(TransitionFrame {
+                progress,
+                direction: transition.direction,
+                role,
+            }),
+        };
+        let entering = route(current, TransitionRole::Entering);
+        let exiting = route(&transition.exiting, TransitionRole::Exiting);
+        // The page moving over the other one is drawn last.
+        match transition.direction {
+            TransitionDirection::Push => vec![exiting, entering],
+            TransitionDirection::Pop => vec![entering, exiting],
+        }
+    }
+
+    /// Routes and progress of the running transition for shared elements.
+    pub(crate) fn shared_transition(&self, frame_nanos: u64) -> Option<SharedTransition> {
+        let transition = self.transition.as_ref()?;
+        Some(SharedTransition {
+            entering: self.current_route_id()?,
+            exiting: transition.exiting.route_id,
+            progress: transition.progress_at(frame_nanos),
+        })
+    }
+
+    pub(crate) fn shared_elements(&self) -> Arc<SharedElements> {
+        self.shared_elements.clone()
+    }
+
+    /// Whether the running transition has not reached its end at `frame_nanos`.
+    pub(crate) fn is_transition_running_at(&self, frame_nanos: u64) -> bool {
+        self.transition.as_ref().is_some_and(|transition| {
+            frame_nanos.saturating_sub(transition.start_frame_nanos)
+                < transition.spec.duration_nanos()
+        })
     }
 
     /// Get or initialize route-scoped state and provide it to `f`.
@@ -140,6 +592,22 @@ impl RouterController {
         life_cycle: ShardStateLifeCycle,
         f: F,
     ) -> R
+    where
+        T: Default + Send + Sync + 'static,
+        F: FnOnce(ShardState<T>) -> R,
+    {
+        self.init_or_get_for_route(None, id, life_cycle, f)
+    }
+
+    /// Like [`Self::init_or_get_with_lifecycle`], for the route rendering the
+    /// calling shard rather than the top of the stack.
+    pub(crate) fn init_or_get_for_route<T, F, R>(
+        &self,
+        route_id: Option<RouteId>,
+        id: &str,
+        life_cycle: ShardStateLifeCycle,
+        f: F,
+    ) -> R
     where
         T: Default + Send + Sync + 'static,
         F: FnOnce(ShardState<T>) -> R,
@@ -149,7 +617,7 @@ impl RouterController {
                 init_or_get_shard_state_in_map(&self.scope_shards, id.to_owned(), id, "scope", f)
             }
             ShardStateLifeCycle::Shard => {
-                let route_id = self.current_route_id().unwrap_or_else(|| {
+                let route_id = route_id.or(self.current_route_id()).unwrap_or_else(|| {
                     panic!("route-scoped shard state requires a non-empty router stack")
                 });
                 init_or_get_shard_state_in_map(
@@ -166,8 +634,141 @@ impl RouterController {
         }
     }
 
+    /// Like [`Self::init_or_get_for_route`], for state that
+    /// [`Self::save_state`] saves and that is filled from a restored state when
+    /// first created.
+    pub(crate) fn init_or_get_saveable_for_route<T, F, R>(
+        &self,
+        route_id: Option<RouteId>,
+        id: &str,
+        life_cycle: ShardStateLifeCycle,
+        f: F,
+    ) -> R
+    where
+        T: Default + Serialize + DeserializeOwned + Send + Sync + 'static,
+        F: FnOnce(ShardState<T>) -> R,
+    {
+        let restored = match life_cycle {
+            ShardStateLifeCycle::Scope => {
+                self.saveable_scope_shards
+                    .insert(id.to_owned(), save_shard_state::<T>);
+                self.restored_scope_shards
+                    .remove(id)
+                    .map(|(_, value)| value)
+            }
+            ShardStateLifeCycle::Shard => {
+                route_id.or(self.current_route_id()).and_then(|route_id| {
+                    let key = RouteShardKey {
+                        route_id,
+                        shard_id: id.to_owned(),
+                    };
+                    self.saveable_route_shards
+                        .insert(key.clone(), save_shard_state::<T>);
+                    self.restored_route_shards
+                        .remove(&key)
+                        .map(|(_, value)| value)
+                })
+            }
+        };
+        self.init_or_get_for_route(route_id, id, life_cycle, |state: ShardState<T>| {
+            if let Some(value) = restored.and_then(|value| serde_json::from_value(value).ok()) {
+                state.set(value);
+            }
+            f(state)
+        })
+    }
+
+    /// Save the navigation stack and the saveable shard state.
+    ///
+    /// Destinations are saved from the root up to the first destination that
+    /// is not saveable, see [`RouterDestination::save`].
+    pub fn save_state(&self) -> SavedRouterState {
+        let save_slot = |save: &SaveShardState, slot: Option<ShardStateSlot>| save(slot?);
+        let routes = self
+            .route_stack
+            .iter()
+            .map_while(|entry| {
+                let destination = entry.destination.save()?;
+                let shard_state = self
+                    .saveable_route_shards
+                    .iter()
+                    .filter(|saveable| saveable.key().route_id == entry.route_id)
+                    .filter_map(|saveable| {
+                        let slot = self.route_shards.get(saveable.key()).map(|slot| *slot);
+                        let value = save_slot(saveable.value(), slot)?;
+                        Some((saveable.key().shard_id.clone(), value))
+                    })
+                    .collect();
+                Some(SavedRoute {
+                    destination,
+                    shard_state,
+                })
+            })
+            .collect();
+        let scope_state = self
+            .saveable_scope_shards
+            .iter()
+            .filter_map(|saveable| {
+                let slot = self.scope_shards.get(saveable.key()).map(|slot| *slot);
+                Some((saveable.key().clone(), save_slot(saveable.value(), slot)?))
+            })
+            .collect();
+        SavedRouterState {
+            routes,
+            scope_state,
+        }
+    }
+
+    /// Replace the stack with the destinations of `saved`.
+    ///
+    /// Restoring stops at the first destination `restorer` does not know.
+    /// Saved shard state is applied when each shard is first built. Returns
+    /// `false` and leaves the stack untouched when no destination could be
+    /// restored.
+    pub fn restore_state(
+        &mut self,
+        saved: &SavedRouterState,
+        restorer: &DestinationRestorer,
+    ) -> bool {
+        let routes: Vec<_> = saved
+            .routes
+            .iter()
+            .map_while(|route| Some((restorer.decode(&route.destination)?, route)))
+            .collect();
+        if routes.is_empty() {
+            return false;
+        }
+
+        self.clear();
+        for (destination, route) in routes {
+            let route_id = RouteId::new();
+            for (shard_id, value) in &route.shard_state {
+                self.restored_route_shards.insert(
+                    RouteShardKey {
+                        route_id,
+                        shard_id: shard_id.clone(),
+                    },
+                    value.clone(),
+                );
+            }
+            self.route_stack.push(RouteEntry {
+                route_id,
+                destination,
+            });
+        }
+        for (id, value) in &saved.scope_state {
+            self.restored_scope_shards.insert(id.clone(), value.clone());
+        }
+        self.record(NavigationAction::Restored);
+        true
+    }
+
     /// Clear all destinations from the stack.
+    ///
+    /// Also drops a navigation waiting for an async guard.
     pub fn clear(&mut self) {
+        self.pending_navigation = None;
+        self.finish_transition();
         if self.route_stack.is_empty() {
             return;
         }
@@ -187,6 +788,16 @@ impl RouterController {
                 recycle_shard_state_slot(slot);
             }
         }
+        self.child_scopes.retain(|key, _| {
+            key.route_id
+                .is_none_or(|route_id| !removed_route_ids.contains(&route_id))
+        });
+        self.saveable_route_shards
+            .retain(|key, _| !removed_route_ids.contains(&key.route_id));
+        self.restored_route_shards
+            .retain(|key, _| !removed_route_ids.contains(&key.route_id));
+        self.pending_results.clear();
+        self.record(NavigationAction::Cleared);
     }
 
     /// Clear all destinations and push a new root destination.
@@ -196,9 +807,112 @@ impl RouterController {
     }
 
     /// Clear all destinations and push a shared root destination.
+    ///
+    /// The stack is kept if the guards do not allow `root_dest`.
     pub fn reset_shared(&mut self, root_dest: Arc<dyn RouterDestination>) {
-        self.clear();
-        self.push_shared(root_dest);
+        self.navigate(Navigation::new(root_dest, NavigationKind::Reset, None));
+    }
+
+    /// Starts `navigation`, replacing one still waiting for a guard.
+    ///
+    /// Returns the replaced destination if the navigation replaced one.
+    fn navigate(&mut self, navigation: Navigation) -> Option<Arc<dyn RouterDestination>> {
+        self.pending_navigation = None;
+        self.run_guards(navigation)
+    }
+
+    fn run_guards(&mut self, mut navigation: Navigation) -> Option<Arc<dyn RouterDestination>> {
+        loop {
+            let check = match &self.guards {
+                Some(guards) => guards.check(&*navigation.destination, navigation.next_guard),
+                None => GuardCheck::Passed,
+            };
+            match check {
+                GuardCheck::Passed => return self.commit_navigation(navigation),
+                GuardCheck::Cancelled => return None,
+                GuardCheck::Redirected(destination) => {
+                    if !navigation.redirect_to(destination) {
+                        return None;
+                    }
+                }
+                GuardCheck::Pending {
+                    next_guard,
+                    decision,
+                } => {
+                    navigation.next_guard = next_guard;
+                    self.pending_navigation = Some(spawn_guard(navigation, decision));
+                    return None;
+                }
+            }
+        }
+    }
+
+    fn commit_navigation(&mut self, navigation: Navigation) -> Option<Arc<dyn RouterDestination>> {
+        let Navigation {
+            destination,
+            kind,
+            result,
+            ..
+        } = navigation;
+        let previous = match kind {
+            NavigationKind::Push => {
+                self.push_route(destination);
+                None
+            }
+            NavigationKind::Replace => self.replace_route(destination),
+            NavigationKind::Reset => {
+                self.clear();
+                self.push_route(destination);
+                None
+            }
+        };
+        if let (Some(result), Some(route_id)) = (result, self.current_route_id()) {
+            self.pending_results.insert(route_id, result);
+        }
+        previous
+    }
+
+    fn record(&mut self, action: NavigationAction) {
+        self.navigation_log.record(action, self.route_stack.len());
+    }
+
+    fn transition_of(&self, destination: &dyn RouterDestination) -> RouterTransition {
+        destination
+            .transition()
+            .unwrap_or_else(|| self.default_transition.clone())
+    }
+
+    fn begin_transition(
+        &mut self,
+        spec: RouterTransition,
+        direction: TransitionDirection,
+        exiting: RouteEntry,
+        exiting_removed: bool,
+    ) {
+        self.finish_transition();
+        if !spec.is_animated() || system_preferences().reduce_motion {
+            if exiting_removed {
+                self.prune_route_shards(exiting.route_id);
+            }
+            return;
+        }
+        self.transition = Some(ActiveTransition {
+            spec,
+            direction,
+            exiting,
+            exiting_removed,
+            start_frame_nanos: current_frame_nanos(),
+        });
+    }
+
+    /// Ends the running transition and releases the state of a removed route.
+    pub(crate) fn finish_transition(&mut self) {
+        let Some(transition) = self.transition.take() else {
+            return;
+        };
+        if transition.exiting_removed {
+            self.prune_route_shards(transition.exiting.route_id);
+        }
     }
 
     fn prune_route_shards(&self, route_id: RouteId) {
@@ -213,9 +927,35 @@ impl RouterController {
                 recycle_shard_state_slot(slot);
             }
         }
+        self.child_scopes
+            .retain(|key, _| key.route_id != Some(route_id));
+        self.saveable_route_shards
+            .retain(|key, _| key.route_id != route_id);
+        self.restored_route_shards
+            .retain(|key, _| key.route_id != route_id);
+    }
+}
+
+fn spawn_guard(navigation: Navigation, decision: GuardFuture) -> PendingNavigation {
+    let (sender, result) = result_channel();
+    let task = TaskHandles::new();
+    task.spawn(async move {
+        send_result(sender, decision.await);
+    });
+    PendingNavigation {
+        navigation,
+        decision: result,
+        _task: task,
     }
 }
 
+fn save_shard_state<T>(slot: ShardStateSlot) -> Option<Value>
+where
+    T: Serialize + Send + Sync + 'static,
+{
+    ShardState::<T>::from_slot(slot).with(|value| serde_json::to_value(value).ok())
+}
+
 impl Default for RouterController {
     fn default() -> Self {
         Self::new()
@@ -247,12 +987,29 @@ impl Drop for RouterController {
 #[cfg(test)]
 mod tests {
     use std::{
+        any::Any,
+        cell::Cell,
         panic::{AssertUnwindSafe, catch_unwind},
-        sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+        sync::{
+            Arc,
+            atomic::{AtomicU64, AtomicUsize, Ordering},
+        },
+        time::{Duration, Instant},
     };
 
+    use futures_util::{FutureExt, future};
+    use serde::{Deserialize, Serialize};
+    use tessera_ui::{State, remember, tessera};
+
     use super::RouterController;
-    use crate::{RouterDestination, ShardStateLifeCycle};
+    use crate::{
+        RouterDestination, ShardStateLifeCycle,
+        router::{
+            DestinationRestorer, GuardDecision, NavigationAction, RouteGuards, SavedDestination,
+            SerializableDestination,
+        },
+        shard_home,
+    };
 
     static TEST_SHARD_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
 
@@ -276,6 +1033,30 @@ mod tests {
         }
     }
 
+    struct SplitPanes;
+
+    impl RouterDestination for SplitPanes {
+        fn exec_component(&self) {
+            shard_home().name("list").root(DummyDestination);
+            shard_home().name("detail").root(DummyDestination);
+        }
+
+        fn destination_id() -> &'static str {
+            "split_panes"
+        }
+    }
+
+    thread_local! {
+        static SPLIT_PANES_ROUTER: Cell<Option<State<RouterController>>> = const { Cell::new(None) };
+    }
+
+    #[tessera]
+    fn split_panes_app() {
+        let router = remember(|| RouterController::with_root(SplitPanes));
+        SPLIT_PANES_ROUTER.with(|cell| cell.set(Some(router)));
+        shard_home().controller(router);
+    }
+
     fn increment_state(
         router: &RouterController,
         shard_id: &str,
@@ -348,4 +1129,208 @@ mod tests {
         }));
         assert!(result.is_err());
     }
+
+    #[derive(Serialize, Deserialize)]
+    struct PageDestination {
+        page: u32,
+    }
+
+    impl RouterDestination for PageDestination {
+        fn exec_component(&self) {}
+
+        fn destination_id() -> &'static str {
+            "page"
+        }
+
+        fn save(&self) -> Option<SavedDestination> {
+            SavedDestination::new(self)
+        }
+    }
+
+    impl SerializableDestination for PageDestination {}
+
+    fn saveable_value(router: &RouterController, shard_id: &str, set: Option<u32>) -> u32 {
+        router.init_or_get_saveable_for_route::<u32, _, _>(
+            None,
+            shard_id,
+            ShardStateLifeCycle::Shard,
+            |state| {
+                if let Some(value) = set {
+                    state.set(value);
+                }
+                state.get()
+            },
+        )
+    }
+
+    #[test]
+    fn saved_state_restores_stack_up_to_first_unsaveable_destination() {
+        let shard_id = unique_shard_id("saveable");
+        let mut router = RouterController::with_root(PageDestination { page: 1 });
+        router.push(PageDestination { page: 2 });
+        saveable_value(&router, shard_id, Some(7));
+        router.push(DummyDestination);
+
+        let saved = router.save_state();
+        assert_eq!(saved.len(), 2);
+
+        let restorer = DestinationRestorer::new().destination::<PageDestination>();
+        let mut restored = RouterController::new();
+        assert!(restored.restore_state(&saved, &restorer));
+        assert_eq!(restored.len(), 2);
+        assert!(restored.current_is::<PageDestination>());
+        assert_eq!(saveable_value(&restored, shard_id, None), 7);
+
+        let mut untouched = RouterController::with_root(DummyDestination);
+        assert!(!untouched.restore_state(&saved, &DestinationRestorer::new()));
+        assert!(untouched.current_is::<DummyDestination>());
+    }
+
+    #[test]
+    fn navigation_result_resolves_to_none_unless_popped_with_matching_value() {
+        let mut router = RouterController::with_root(DummyDestination);
+
+        let picked = router.push_for_result::<u32, _>(DummyDestination);
+        router.pop_with_result(7_u32);
+        assert_eq!(picked.now_or_never(), Some(Some(7)));
+
+        let dismissed = router.push_for_result::<u32, _>(DummyDestination);
+        router.pop();
+        assert_eq!(dismissed.now_or_never(), Some(None));
+
+        let mistyped = router.push_for_result::<u32, _>(DummyDestination);
+        router.pop_with_result("seven");
+        assert_eq!(mistyped.now_or_never(), Some(None));
+
+        let replaced = router.push_for_result::<u32, _>(DummyDestination);
+        router.replace(DummyDestination);
+        assert_eq!(replaced.now_or_never(), Some(None));
+        assert_eq!(router.len(), 2);
+    }
+
+    #[test]
+    fn navigation_events_reach_listeners_and_breadcrumbs() {
+        let dummy = std::any::type_name::<DummyDestination>();
+        let page = std::any::type_name::<PageDestination>();
+        let mut router = RouterController::with_root(DummyDestination);
+        let seen = Arc::new(AtomicUsize::new(0));
+        let listener = router.add_navigation_listener({
+            let seen = seen.clone();
+            move |_| {
+                seen.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+        router.set_breadcrumb_limit(3);
+
+        router.push(PageDestination { page: 1 });
+        router.replace(DummyDestination);
+        router.pop();
+        assert!(router.remove_navigation_listener(listener));
+        router.push(PageDestination { page: 2 });
+
+        assert_eq!(router.current_stack(), [dummy, page]);
+        assert_eq!(seen.load(Ordering::SeqCst), 3);
+        let actions: Vec<_> = router.breadcrumbs().map(|event| event.action).collect();
+        assert_eq!(
+            actions,
+            [
+                NavigationAction::Replaced {
+                    from: page,
+                    to: dummy
+                },
+                NavigationAction::Popped { shard_id: dummy },
+                NavigationAction::Pushed { shard_id: page },
+            ]
+        );
+        assert_eq!(
+            router.breadcrumbs().last().map(|event| event.stack_len),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn guards_cancel_redirect_and_defer_navigation() {
+        let mut router = RouterController::with_root(DummyDestination);
+        router.set_guards(
+            RouteGuards::new()
+                .guard(|destination| {
+                    match (destination as &dyn Any).downcast_ref::<PageDestination>() {
+                        Some(PageDestination { page: 0 }) => GuardDecision::Cancel,
+                        Some(PageDestination { page: 1 }) => {
+                            GuardDecision::redirect(PageDestination { page: 2 })
+                        }
+                        Some(PageDestination { page: 9 }) => {
+                            GuardDecision::redirect(PageDestination { page: 9 })
+                        }
+                        _ => GuardDecision::Allow,
+                    }
+                })
+                .async_guard(|destination| {
+                    (destination as &dyn Any)
+                        .is::<PageDestination>()
+                        .then(|| future::ready(GuardDecision::Allow))
+                }),
+        );
+
+        router.push(PageDestination { page: 0 });
+        router.push(PageDestination { page: 9 });
+        assert_eq!(router.len(), 1);
+        assert!(!router.is_navigation_pending());
+
+        let result = router.push_for_result::<u32, _>(PageDestination { page: 1 });
+        assert_eq!(result.now_or_never(), Some(None));
+        assert!(router.is_navigation_pending());
+        assert_eq!(router.len(), 1);
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !router.is_pending_navigation_decided() {
+            assert!(Instant::now() < deadline, "async guard should decide");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        router.resume_pending_navigation();
+        assert!(!router.is_navigation_pending());
+        assert_eq!(router.len(), 2);
+        let top = router.last().expect("redirect target should be pushed");
+        assert!(matches!(
+            (top as &dyn Any).downcast_ref::<PageDestination>(),
+            Some(PageDestination { page: 2 })
+        ));
+    }
+
+    #[test]
+    fn named_child_scopes_keep_independent_stacks() {
+        let mut session = tessera_ui::testing::layout_test(|| {
+            split_panes_app();
+        })
+        .viewport_px(200, 200)
+        .interact();
+        session.frame();
+
+        let outer = SPLIT_PANES_ROUTER
+            .with(Cell::get)
+            .expect("split panes app should be mounted");
+        let scope = |name: &str| outer.with(|router| router.child_scope(name));
+        let list = scope("list").expect("list pane should register");
+        let detail = scope("detail").expect("detail pane should register");
+        assert!(list != detail);
+        assert!(scope("missing").is_none());
+        assert!(detail.with(|router| router.parent()) == Some(outer));
+
+        list.with_mut(|router| router.push(DummyDestination));
+        detail.with_mut(|router| {
+            router.push(DummyDestination);
+            router.push(DummyDestination);
+        });
+        session.frame();
+        assert!(scope("list") == Some(list));
+        assert!(scope("detail") == Some(detail));
+        assert_eq!(list.with(RouterController::len), 2);
+        assert_eq!(detail.with(RouterController::len), 3);
+
+        assert!(list.with_mut(|router| router.pop()).is_some());
+        session.frame();
+        assert_eq!(list.with(RouterController::len), 1);
+        assert_eq!(detail.with(RouterController::len), 3);
+        assert_eq!(outer.with(RouterController::len), 1);
+    }
 }
@@ -0,0 +1,339 @@
+use std::{fmt, str::FromStr, sync::Arc};
+
+use crate::router::RouterDestination;
+
+type DestinationFactory = dyn Fn(&RouteParams) -> Option<Arc<dyn RouterDestination>> + Send + Sync;
+
+/// Values captured while matching a deep link against a route pattern.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RouteParams {
+    path: Vec<(String, String)>,
+    query: Vec<(String, String)>,
+}
+
+impl RouteParams {
+    /// Returns the path segment captured by `{name}`.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        lookup(&self.path, name)
+    }
+
+    /// Parses the path segment captured by `{name}`.
+    pub fn parse<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.get(name)?.parse().ok()
+    }
+
+    /// Returns the first query parameter called `name`.
+    pub fn query(&self, name: &str) -> Option<&str> {
+        lookup(&self.query, name)
+    }
+}
+
+fn lookup<'a>(pairs: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    pairs
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+/// Errors returned when a deep link cannot be opened.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DeepLinkError {
+    /// The link is not a `scheme:path` URI.
+    InvalidUri(String),
+    /// No registered pattern matches the link.
+    NoMatch(String),
+    /// A pattern matched but its constructor rejected the parameters.
+    Rejected(String),
+}
+
+impl fmt::Display for DeepLinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidUri(uri) => write!(f, "`{uri}` is not a valid URI"),
+            Self::NoMatch(uri) => write!(f, "no route matches `{uri}`"),
+            Self::Rejected(uri) => write!(f, "route parameters of `{uri}` were rejected"),
+        }
+    }
+}
+
+impl std::error::Error for DeepLinkError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum PatternSegment {
+    Literal(String),
+    Param(String),
+}
+
+struct DeepLinkRoute {
+    scheme: String,
+    segments: Vec<PatternSegment>,
+    factory: Arc<DestinationFactory>,
+}
+
+/// Maps URI patterns such as `app://user/{id}` to router destinations.
+///
+/// The authority is matched like any other path segment, so
+/// `https://example.com/user/{id}` works for app links as well. Routes are
+/// tried in registration order.
+///
+/// # Examples
+///
+/// ```
+/// use tessera_shard::router::{DeepLinkRegistry, RouterDestination};
+///
+/// struct UserDestination {
+///     id: u64,
+/// }
+///
+/// impl RouterDestination for UserDestination {
+///     fn exec_component(&self) {}
+///     fn destination_id() -> &'static str {
+///         "user"
+///     }
+/// }
+///
+/// let links = DeepLinkRegistry::new().route("app://user/{id}", |params| {
+///     Some(UserDestination {
+///         id: params.parse("id")?,
+///     })
+/// });
+/// assert!(links.resolve("app://user/42").is_ok());
+/// assert!(links.resolve("app://user/me").is_err());
+/// ```
+#[derive(Default)]
+pub struct DeepLinkRegistry {
+    routes: Vec<DeepLinkRoute>,
+}
+
+impl DeepLinkRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a pattern and the constructor of its destination.
+    ///
+    /// Segments written as `{name}` capture one path segment. The constructor
+    /// returns `None` to reject captured values it cannot parse.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pattern` has no scheme or contains an empty `{}` capture.
+    pub fn route<D, F>(mut self, pattern: &str, build: F) -> Self
+    where
+        D: RouterDestination + 'static,
+        F: Fn(&RouteParams) -> Option<D> + Send + Sync + 'static,
+    {
+        let parsed = ParsedUri::parse(pattern)
+            .unwrap_or_else(|| panic!("deep link pattern `{pattern}` must start with a scheme"));
+        let segments = parsed
+            .segments
+            .into_iter()
+            .map(|segment| match segment.strip_prefix('{') {
+                Some(name) => {
+                    let name = name.strip_suffix('}').unwrap_or(name);
+                    assert!(
+                        !name.is_empty(),
+                        "deep link pattern `{pattern}` has an unnamed capture"
+                    );
+                    PatternSegment::Param(name.to_owned())
+                }
+                None => PatternSegment::Literal(segment),
+            })
+            .collect();
+        self.routes.push(DeepLinkRoute {
+            scheme: parsed.scheme,
+            segments,
+            factory: Arc::new(move |params: &RouteParams| {
+                build(params).map(|destination| Arc::new(destination) as Arc<dyn RouterDestination>)
+            }),
+        });
+        self
+    }
+
+    /// Resolve `uri` to the destination of the first matching pattern.
+    pub fn resolve(&self, uri: &str) -> Result<Arc<dyn RouterDestination>, DeepLinkError> {
+        let parsed =
+            ParsedUri::parse(uri).ok_or_else(|| DeepLinkError::InvalidUri(uri.to_owned()))?;
+        let mut matched = false;
+        for route in &self.routes {
+            let Some(path) = route.capture(&parsed) else {
+                continue;
+            };
+            matched = true;
+            let params = RouteParams {
+                path,
+                query: parsed.query.clone(),
+            };
+            if let Some(destination) = (route.factory)(&params) {
+                return Ok(destination);
+            }
+        }
+        Err(if matched {
+            DeepLinkError::Rejected(uri.to_owned())
+        } else {
+            DeepLinkError::NoMatch(uri.to_owned())
+        })
+    }
+}
+
+impl DeepLinkRoute {
+    fn capture(&self, uri: &ParsedUri) -> Option<Vec<(String, String)>> {
+        if !self.scheme.eq_ignore_ascii_case(&uri.scheme)
+            || self.segments.len() != uri.segments.len()
+        {
+            return None;
+        }
+        let mut captured = Vec::new();
+        for (pattern, segment) in self.segments.iter().zip(&uri.segments) {
+            match pattern {
+                PatternSegment::Literal(literal) if literal == segment => {}
+                PatternSegment::Literal(_) => return None,
+                PatternSegment::Param(name) => captured.push((name.clone(), segment.clone())),
+            }
+        }
+        Some(captured)
+    }
+}
+
+struct ParsedUri {
+    scheme: String,
+    segments: Vec<String>,
+    query: Vec<(String, String)>,
+}
+
+impl ParsedUri {
+    fn parse(uri: &str) -> Option<Self> {
+        let (scheme, rest) = uri.split_once(':')?;
+        let valid_scheme = scheme
+            .chars()
+            .next()
+            .is_some_and(|first| first.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+        if !valid_scheme {
+            return None;
+        }
+
+        let rest = rest.split_once('#').map_or(rest, |(rest, _)| rest);
+        let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let segments = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(percent_decode)
+            .collect();
+        let query = query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (percent_decode(key), percent_decode(value))
+            })
+            .collect();
+        Some(Self {
+            scheme: scheme.to_owned(),
+            segments,
+            query,
+        })
+    }
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escaped = (bytes[index] == b'%')
+            .then(|| text.get(index + 1..index + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::Any;
+
+    use super::{DeepLinkError, DeepLinkRegistry};
+    use crate::RouterDestination;
+
+    struct UserDestination {
+        id: u64,
+        tab: Option<String>,
+    }
+
+    impl RouterDestination for UserDestination {
+        fn exec_component(&self) {}
+
+        fn destination_id() -> &'static str {
+            "user"
+        }
+    }
+
+    struct SettingsDestination;
+
+    impl RouterDestination for SettingsDestination {
+        fn exec_component(&self) {}
+
+        fn destination_id() -> &'static str {
+            "settings"
+        }
+    }
+
+    fn registry() -> DeepLinkRegistry {
+        DeepLinkRegistry::new()
+            .route("app://user/{id}", |params| {
+                Some(UserDestination {
+                    id: params.parse("id")?,
+                    tab: params.query("tab").map(str::to_owned),
+                })
+            })
+            .route("app://settings", |_| Some(SettingsDestination))
+    }
+
+    #[test]
+    fn resolve_captures_path_and_query_parameters() {
+        let destination = registry()
+            .resolve("APP://user/42/?tab=liked%20posts#top")
+            .expect("link should resolve");
+        let user = (&*destination as &dyn Any)
+            .downcast_ref::<UserDestination>()
+            .expect("link should open the user destination");
+        assert_eq!(user.id, 42);
+        assert_eq!(user.tab.as_deref(), Some("liked posts"));
+
+        let settings = registry()
+            .resolve("app:settings")
+            .expect("link should resolve");
+        assert!((&*settings as &dyn Any).is::<SettingsDestination>());
+    }
+
+    #[test]
+    fn resolve_reports_why_a_link_failed() {
+        let registry = registry();
+        assert_eq!(
+            registry.resolve("app://user/me").err(),
+            Some(DeepLinkError::Rejected("app://user/me".to_owned()))
+        );
+        assert_eq!(
+            registry.resolve("app://user/42/posts").err(),
+            Some(DeepLinkError::NoMatch("app://user/42/posts".to_owned()))
+        );
+        assert_eq!(
+            registry.resolve("/user/42").err(),
+            Some(DeepLinkError::InvalidUri("/user/42".to_owned()))
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use std::any::Any;
 
+use crate::router::{RouterTransition, SavedDestination};
+
 /// A navigation destination produced by the `#[shard]` macro.
 pub trait RouterDestination: Any + Send + Sync {
     /// Execute the component associated with this destination.
@@ -9,4 +11,27 @@ pub trait RouterDestination: Any + Send + Sync {
     fn destination_id() -> &'static str
     where
         Self: Sized;
+
+    /// Shard id reported in navigation events.
+    ///
+    /// Defaults to the type name; `#[shard]` destinations report their
+    /// [`Self::destination_id`].
+    fn shard_id(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Transition played when this destination is pushed or popped.
+    ///
+    /// `None` uses the default transition of the router controller.
+    fn transition(&self) -> Option<RouterTransition> {
+        None
+    }
+
+    /// Save this destination for [`crate::RouterController::save_state`].
+    ///
+    /// `None` marks the destination as not saveable; implemented by
+    /// `#[shard(saveable)]`.
+    fn save(&self) -> Option<SavedDestination> {
+        None
+    }
 }
@@ -0,0 +1,167 @@
+use std::{fmt, future::Future, pin::Pin, sync::Arc};
+
+use crate::router::RouterDestination;
+
+/// What a guard decides about a navigation.
+#[derive(Clone)]
+pub enum GuardDecision {
+    /// Let the navigation through to the next guard.
+    Allow,
+    /// Drop the navigation and stay on the current destination.
+    Cancel,
+    /// Navigate to another destination instead.
+    Redirect(Arc<dyn RouterDestination>),
+}
+
+impl GuardDecision {
+    /// Navigate to `destination` instead.
+    pub fn redirect(destination: impl RouterDestination + 'static) -> Self {
+        Self::Redirect(Arc::new(destination))
+    }
+}
+
+impl fmt::Debug for GuardDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Allow => f.write_str("Allow"),
+            Self::Cancel => f.write_str("Cancel"),
+            Self::Redirect(_) => f.write_str("Redirect"),
+        }
+    }
+}
+
+pub(crate) type GuardFuture = Pin<Box<dyn Future<Output = GuardDecision> + Send>>;
+
+type SyncGuard = dyn Fn(&dyn RouterDestination) -> GuardDecision + Send + Sync;
+type AsyncGuard = dyn Fn(&dyn RouterDestination) -> Option<GuardFuture> + Send + Sync;
+
+enum Guard {
+    Sync(Arc<SyncGuard>),
+    Async(Arc<AsyncGuard>),
+}
+
+/// Outcome of running guards against a destination.
+pub(crate) enum GuardCheck {
+    Passed,
+    Cancelled,
+    Redirected(Arc<dyn RouterDestination>),
+    /// An async guard is deciding; guards from `next_guard` on run after it
+    /// allows the navigation.
+    Pending {
+        next_guard: usize,
+        decision: GuardFuture,
+    },
+}
+
+/// Checks run before a pushed, replacing or reset destination is shown.
+///
+/// Guards run in registration order and see the destination about to be
+/// shown. The first guard that cancels or redirects ends the check; a
+/// redirect target is checked by all guards again. Popping back to a
+/// destination already on the stack is not guarded.
+///
+/// # Examples
+///
+/// ```
+/// use std::{
+///     any::Any,
+///     sync::atomic::{AtomicBool, Ordering},
+/// };
+///
+/// use tessera_shard::router::{GuardDecision, RouteGuards, RouterController, RouterDestination};
+///
+/// static SIGNED_IN: AtomicBool = AtomicBool::new(false);
+///
+/// struct Profile;
+/// struct Login;
+///
+/// impl RouterDestination for Profile {
+///     fn exec_component(&self) {}
+///     fn destination_id() -> &'static str {
+///         "profile"
+///     }
+/// }
+///
+/// impl RouterDestination for Login {
+///     fn exec_component(&self) {}
+///     fn destination_id() -> &'static str {
+///         "login"
+///     }
+/// }
+///
+/// let mut router = RouterController::new();
+/// router.set_guards(RouteGuards::new().guard(|destination| {
+///     let is_profile = (destination as &dyn Any).is::<Profile>();
+///     if is_profile && !SIGNED_IN.load(Ordering::Relaxed) {
+///         GuardDecision::redirect(Login)
+///     } else {
+///         GuardDecision::Allow
+///     }
+/// }));
+/// router.push(Profile);
+/// assert!(router.current_is::<Login>());
+/// ```
+#[derive(Default)]
+pub struct RouteGuards {
+    guards: Vec<Guard>,
+}
+
+impl RouteGuards {
+    /// Create an empty guard list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a guard that decides immediately.
+    pub fn guard<F>(mut self, guard: F) -> Self
+    where
+        F: Fn(&dyn RouterDestination) -> GuardDecision + Send + Sync + 'static,
+    {
+        self.guards.push(Guard::Sync(Arc::new(guard)));
+        self
+    }
+
+    /// Add a guard that decides asynchronously, for example after asking a
+    /// server.
+    ///
+    /// Return `None` to allow a destination without waiting. While the future
+    /// runs, the current destination stays visible without receiving input
+    /// and `shard_home` renders its `pending` content; a later navigation or
+    /// the back action drops the waiting one.
+    pub fn async_guard<F, Fut>(mut self, guard: F) -> Self
+    where
+        F: Fn(&dyn RouterDestination) -> Option<Fut> + Send + Sync + 'static,
+        Fut: Future<Output = GuardDecision> + Send + 'static,
+    {
+        self.guards.push(Guard::Async(Arc::new(move |destination| {
+            guard(destination).map(|decision| Box::pin(decision) as GuardFuture)
+        })));
+        self
+    }
+
+    /// Run the guards from `start` on against `destination`.
+    pub(crate) fn check(&self, destination: &dyn RouterDestination, start: usize) -> GuardCheck {
+        for (index, guard) in self.guards.iter().enumerate().skip(start) {
+            let decision = match guard {
+                Guard::Sync(guard) => guard(destination),
+                Guard::Async(guard) => match guard(destination) {
+                    Some(decision) => {
+                        return GuardCheck::Pending {
+                            next_guard: index + 1,
+                            decision,
+                        };
+                    }
+                    None => GuardDecision::Allow,
+                },
+            };
+            match decision {
+                GuardDecision::Allow => {}
+                GuardDecision::Cancel => return GuardCheck::Cancelled,
+                GuardDecision::Redirect(destination) => {
+                    return GuardCheck::Redirected(destination);
+                }
+            }
+        }
+        GuardCheck::Passed
+    }
+}
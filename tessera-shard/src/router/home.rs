@@ -1,9 +1,24 @@
 use std::sync::Arc;
 
-use tessera_ui::{State, provide_context, remember, tessera};
+use serde::{Serialize, de::DeserializeOwned};
+use tessera_ui::{
+    FrameNanosControl, RenderSlot, State,
+    back_navigation::back_handler,
+    current_frame_nanos,
+    deep_link::take_deep_links,
+    key,
+    layout::layout,
+    provide_context, receive_frame_nanos, remember, retain_with_key,
+    saveable::{save_with, take_restored},
+    tessera, use_context,
+};
 
 use crate::{
-    router::{RouterContext, RouterController, RouterDestination},
+    router::{
+        DeepLinkRegistry, DestinationRestorer, RouteGuards, RouteId, RouteScope, RouterContext,
+        RouterController, RouterDestination, SavedRouterState,
+        shared_element::shared_element_overlays, transition::page_modifier,
+    },
     state::{ShardState, ShardStateLifeCycle},
 };
 
@@ -47,7 +62,33 @@ where
     T: Default + Send + Sync + 'static,
     F: FnOnce(ShardState<T>) -> R,
 {
-    controller.with(|router| router.init_or_get_with_lifecycle(shard_id, life_cycle, f))
+    let route_id = current_route_scope();
+    controller.with(|router| router.init_or_get_for_route(route_id, shard_id, life_cycle, f))
+}
+
+pub(crate) fn with_current_router_saveable_shard_state<T, F, R>(
+    shard_id: &str,
+    life_cycle: ShardStateLifeCycle,
+    controller: State<RouterController>,
+    f: F,
+) -> R
+where
+    T: Default + Serialize + DeserializeOwned + Send + Sync + 'static,
+    F: FnOnce(ShardState<T>) -> R,
+{
+    let route_id = current_route_scope();
+    controller
+        .with(|router| router.init_or_get_saveable_for_route(route_id, shard_id, life_cycle, f))
+}
+
+fn current_route_scope() -> Option<RouteId> {
+    let in_build = matches!(
+        tessera_ui::__private::current_phase(),
+        Some(tessera_ui::__private::RuntimePhase::Build)
+    );
+    in_build
+        .then(|| use_context::<RouteScope>().map(|scope| scope.get().route_id))
+        .flatten()
 }
 
 /// # shard_home
@@ -63,6 +104,25 @@ where
 ///
 /// - `root` — initial destination used when `controller` is omitted
 /// - `controller` — optional external router controller state
+/// - `deep_links` — optional patterns used to push destinations for queued
+///   [`tessera_ui::deep_link`] links; links matching no pattern are dropped
+/// - `save_key` — optional key under which the navigation stack is saved with
+///   [`tessera_ui::saveable`] and restored after process death; set it together
+///   with the restorable destinations through `shard_home().saveable(..)`
+/// - `guards` — optional [`RouteGuards`] checked before each push, replace and
+///   reset; set them through `shard_home().guards(..)`
+/// - `pending` — optional content rendered over the current page while an async
+///   guard decides
+/// - `name` — optional scope name; a named `shard_home` mounted inside another
+///   one registers with the enclosing router, see
+///   [`RouterController::child_scope`]
+///
+/// Each `shard_home` keeps its own back stack, and shards resolve the nearest
+/// enclosing router. The system back action pops the innermost router that has
+/// more than one destination, or drops a navigation waiting for a guard; use
+/// [`back_handler`] in a shard to intercept it.
+/// The internal controller of a named scope is retained, so its back stack
+/// survives while the pane is not rendered, for example in an inactive tab.
 ///
 /// ## Examples
 ///
@@ -85,16 +145,57 @@ where
 pub fn shard_home(
     #[prop(skip_setter)] root: Option<Arc<dyn RouterDestination>>,
     controller: Option<State<RouterController>>,
+    #[prop(skip_setter)] deep_links: Option<Arc<DeepLinkRegistry>>,
+    #[prop(skip_setter)] save_key: Option<String>,
+    #[prop(skip_setter)] restorer: Option<Arc<DestinationRestorer>>,
+    #[prop(skip_setter)] guards: Option<Arc<RouteGuards>>,
+    pending: Option<RenderSlot>,
+    #[prop(into)] name: Option<String>,
 ) {
-    let internal_controller = remember({
+    let parent = use_context::<RouterContext>().map(|context| context.get().controller());
+    let parent_route_id = use_context::<RouteScope>().map(|scope| scope.get().route_id);
+    let init_controller = {
         let root = root.clone();
         move || match root.clone() {
             Some(root) => RouterController::with_root_shared(root),
             None => RouterController::new(),
         }
-    });
+    };
+    let internal_controller = match name.as_deref() {
+        Some(name) => retain_with_key((name, parent_route_id), init_controller),
+        None => remember(init_controller),
+    };
     let controller = controller.unwrap_or(internal_controller);
 
+    if let Some(save_key) = save_key.as_deref() {
+        // Restored values are handed out once, so this only runs on the first
+        // build after a restart.
+        if let (Some(saved), Some(restorer)) = (
+            take_restored::<SavedRouterState>(save_key),
+            restorer.as_deref(),
+        ) {
+            controller.with_mut(|router| router.restore_state(&saved, restorer));
+        }
+        save_with(save_key, move || {
+            Some(controller.with(RouterController::save_state))
+        });
+    }
+
+    if let Some(name) = name.as_deref() {
+        if !controller.with(|router| router.has_parent(parent)) {
+            controller.with_mut(|router| router.set_parent(parent));
+        }
+        if let Some(parent) = parent {
+            parent.with(|router| router.register_child_scope(parent_route_id, name, controller));
+        }
+    }
+
+    if let Some(guards) = guards
+        && !controller.with(|router| router.has_guards(&guards))
+    {
+        controller.with_mut(|router| router.set_shared_guards(guards));
+    }
+
     if root.is_none()
         && controller == internal_controller
         && controller.with(RouterController::is_empty)
@@ -102,15 +203,80 @@ pub fn shard_home(
         panic!("shard_home requires `root` when `controller` is not provided");
     }
 
+    if let Some(deep_links) = deep_links.as_deref() {
+        for uri in take_deep_links() {
+            let _ = controller.with_mut(|router| router.open_deep_link(deep_links, &uri));
+        }
+    }
+
+    let can_go_back = controller.with(|router| router.len() > 1 || router.is_navigation_pending());
+    back_handler(can_go_back, move || {
+        controller.with_mut(|router| {
+            if !router.cancel_pending_navigation() {
+                router.pop();
+            }
+        });
+    });
+
     // Ensure the compiler sees field reads (actual reads happen in
     // macro-generated code in downstream crates).
     let _ = RouterContext::new(controller).controller();
 
+    if controller.with(RouterController::is_transitioning) {
+        receive_frame_nanos(move |frame_nanos| {
+            if controller.with(|router| router.is_transition_running_at(frame_nanos)) {
+                return FrameNanosControl::Continue;
+            }
+            controller.with_mut(RouterController::finish_transition);
+            FrameNanosControl::Stop
+        });
+    }
+
+    let navigation_pending = controller.with(RouterController::is_navigation_pending);
+    if navigation_pending {
+        receive_frame_nanos(move |_| {
+            if !controller.with(RouterController::is_pending_navigation_decided) {
+                return FrameNanosControl::Continue;
+            }
+            controller.with_mut(RouterController::resume_pending_navigation);
+            FrameNanosControl::Stop
+        });
+    }
+
     provide_context(
         || RouterContext::new(controller),
         || {
-            let executed = controller.with(RouterController::exec_current);
-            assert!(executed, "Router stack should not be empty");
+            let routes = controller.with(|router| router.visible_routes(current_frame_nanos()));
+            assert!(
+                !routes.is_empty() || navigation_pending,
+                "Router stack should not be empty"
+            );
+            let block_input = routes.len() > 1 || navigation_pending;
+            for route in routes {
+                let route_id = route.route_id;
+                let destination = route.destination;
+                key(route_id.0, || {
+                    layout()
+                        .modifier(page_modifier(route.style, block_input))
+                        .child(move || {
+                            let destination = destination.clone();
+                            provide_context(
+                                || RouteScope { route_id },
+                                move || destination.exec_component(),
+                            );
+                        });
+                });
+            }
+            let shared_transition =
+                controller.with(|router| router.shared_transition(current_frame_nanos()));
+            let shared_elements = controller.with(RouterController::shared_elements);
+            shared_elements.set_transition(shared_transition);
+            if let Some(transition) = shared_transition {
+                shared_element_overlays(&shared_elements, transition);
+            }
+            if navigation_pending && let Some(pending) = pending {
+                pending.render();
+            }
         },
     );
 }
@@ -123,4 +289,20 @@ impl ShardHomeBuilder {
         self.props.root = Some(Arc::new(root));
         self
     }
+
+    pub fn deep_links(mut self, deep_links: DeepLinkRegistry) -> Self {
+        self.props.deep_links = Some(Arc::new(deep_links));
+        self
+    }
+
+    pub fn guards(mut self, guards: RouteGuards) -> Self {
+        self.props.guards = Some(Arc::new(guards));
+        self
+    }
+
+    pub fn saveable(mut self, save_key: impl Into<String>, restorer: DestinationRestorer) -> Self {
+        self.props.save_key = Some(save_key.into());
+        self.props.restorer = Some(Arc::new(restorer));
+        self
+    }
 }
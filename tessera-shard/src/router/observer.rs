@@ -0,0 +1,131 @@
+use std::{collections::VecDeque, fmt, sync::Arc};
+
+use tessera_ui::time::Instant;
+
+const DEFAULT_BREADCRUMB_LIMIT: usize = 32;
+
+/// A change of a router's back stack, identified by shard ids.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NavigationAction {
+    /// A destination was pushed on top of the stack.
+    Pushed {
+        /// Shard id of the pushed destination.
+        shard_id: &'static str,
+    },
+    /// The top destination was popped.
+    Popped {
+        /// Shard id of the popped destination.
+        shard_id: &'static str,
+    },
+    /// The top destination was replaced.
+    Replaced {
+        /// Shard id of the replaced destination.
+        from: &'static str,
+        /// Shard id of the new top destination.
+        to: &'static str,
+    },
+    /// All destinations were removed.
+    Cleared,
+    /// The stack was restored from saved state.
+    Restored,
+}
+
+impl fmt::Display for NavigationAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pushed { shard_id } => write!(f, "push {shard_id}"),
+            Self::Popped { shard_id } => write!(f, "pop {shard_id}"),
+            Self::Replaced { from, to } => write!(f, "replace {from} with {to}"),
+            Self::Cleared => f.write_str("clear"),
+            Self::Restored => f.write_str("restore"),
+        }
+    }
+}
+
+/// A navigation reported to listeners and kept as a breadcrumb.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NavigationEvent {
+    /// What changed.
+    pub action: NavigationAction,
+    /// Number of destinations once the change applied.
+    pub stack_len: usize,
+    /// When the change applied.
+    pub timestamp: Instant,
+}
+
+/// Identifies a listener added with
+/// [`RouterController::add_navigation_listener`](crate::router::RouterController::add_navigation_listener).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct NavigationListenerId(u64);
+
+type NavigationListener = dyn Fn(&NavigationEvent) + Send + Sync;
+
+/// Navigation listeners and recent events of one router controller.
+pub(crate) struct NavigationLog {
+    next_listener_id: u64,
+    listeners: Vec<(NavigationListenerId, Arc<NavigationListener>)>,
+    breadcrumbs: VecDeque<NavigationEvent>,
+    breadcrumb_limit: usize,
+}
+
+impl NavigationLog {
+    pub(crate) fn add_listener<F>(&mut self, listener: F) -> NavigationListenerId
+    where
+        F: Fn(&NavigationEvent) + Send + Sync + 'static,
+    {
+        let id = NavigationListenerId(self.next_listener_id);
+        self.next_listener_id += 1;
+        self.listeners.push((id, Arc::new(listener)));
+        id
+    }
+
+    pub(crate) fn remove_listener(&mut self, id: NavigationListenerId) -> bool {
+        let len = self.listeners.len();
+        self.listeners.retain(|(listener_id, _)| *listener_id != id);
+        self.listeners.len() != len
+    }
+
+    pub(crate) fn breadcrumbs(&self) -> impl Iterator<Item = &NavigationEvent> {
+        self.breadcrumbs.iter()
+    }
+
+    pub(crate) fn set_breadcrumb_limit(&mut self, limit: usize) {
+        self.breadcrumb_limit = limit;
+        self.truncate();
+    }
+
+    pub(crate) fn record(&mut self, action: NavigationAction, stack_len: usize) {
+        let event = NavigationEvent {
+            action,
+            stack_len,
+            timestamp: Instant::now(),
+        };
+        self.breadcrumbs.push_back(event);
+        self.truncate();
+        #[cfg(feature = "devtools")]
+        tessera_ui::devtools::post_event(
+            "navigation",
+            serde_json::json!({ "action": action.to_string(), "stack_len": stack_len }),
+        );
+        for (_, listener) in &self.listeners {
+            listener(&event);
+        }
+    }
+
+    fn truncate(&mut self) {
+        while self.breadcrumbs.len() > self.breadcrumb_limit {
+            self.breadcrumbs.pop_front();
+        }
+    }
+}
+
+impl Default for NavigationLog {
+    fn default() -> Self {
+        Self {
+            next_listener_id: 0,
+            listeners: Vec::new(),
+            breadcrumbs: VecDeque::new(),
+            breadcrumb_limit: DEFAULT_BREADCRUMB_LIMIT,
+        }
+    }
+}
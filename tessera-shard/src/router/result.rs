@@ -0,0 +1,96 @@
+use std::{
+    any::Any,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll, Waker},
+};
+
+use parking_lot::Mutex;
+
+enum ResultSlot<R> {
+    Pending(Option<Waker>),
+    Ready(Option<R>),
+    Taken,
+}
+
+/// Value returned by a destination pushed with
+/// [`RouterController::push_for_result`](crate::router::RouterController::push_for_result).
+///
+/// Resolves to `Some` when the destination is popped with
+/// [`RouterController::pop_with_result`](crate::router::RouterController::pop_with_result),
+/// and to `None` when it leaves the stack in any other way. Await it in a
+/// background task, for example one spawned on `TaskHandles`.
+#[must_use = "the result is lost unless the future is awaited"]
+pub struct NavigationResult<R> {
+    slot: Arc<Mutex<ResultSlot<R>>>,
+}
+
+impl<R> NavigationResult<R> {
+    /// Whether awaiting the result would complete immediately.
+    pub(crate) fn is_ready(&self) -> bool {
+        !matches!(*self.slot.lock(), ResultSlot::Pending(_))
+    }
+}
+
+impl<R> Future for NavigationResult<R> {
+    type Output = Option<R>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = self.slot.lock();
+        match std::mem::replace(&mut *slot, ResultSlot::Taken) {
+            ResultSlot::Pending(_) => {
+                *slot = ResultSlot::Pending(Some(cx.waker().clone()));
+                Poll::Pending
+            }
+            ResultSlot::Ready(result) => Poll::Ready(result),
+            ResultSlot::Taken => Poll::Ready(None),
+        }
+    }
+}
+
+/// Sending half of a [`NavigationResult`]; dropping it resolves to `None`.
+pub(crate) struct ResultSender<R> {
+    slot: Arc<Mutex<ResultSlot<R>>>,
+}
+
+impl<R> ResultSender<R> {
+    fn complete(&self, result: Option<R>) {
+        let mut slot = self.slot.lock();
+        if !matches!(*slot, ResultSlot::Pending(_)) {
+            return;
+        }
+        if let ResultSlot::Pending(Some(waker)) =
+            std::mem::replace(&mut *slot, ResultSlot::Ready(result))
+        {
+            drop(slot);
+            waker.wake();
+        }
+    }
+}
+
+impl<R> Drop for ResultSender<R> {
+    fn drop(&mut self) {
+        self.complete(None);
+    }
+}
+
+/// Type-erased [`ResultSender`] stored per route.
+pub(crate) type PendingResult = Box<dyn Any + Send + Sync>;
+
+pub(crate) fn result_channel<R: Send + 'static>() -> (PendingResult, NavigationResult<R>) {
+    let slot = Arc::new(Mutex::new(ResultSlot::Pending(None)));
+    (
+        Box::new(ResultSender { slot: slot.clone() }),
+        NavigationResult { slot },
+    )
+}
+
+/// Delivers `value` if `pending` waits for an `R`.
+///
+/// A pending result of another type is dropped and resolves to `None`.
+pub(crate) fn send_result<R: 'static>(pending: PendingResult, value: R) {
+    if let Ok(sender) = pending.downcast::<ResultSender<R>>() {
+        sender.complete(Some(value));
+    }
+}
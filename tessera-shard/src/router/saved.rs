@@ -0,0 +1,128 @@
+use std::{collections::HashMap, sync::Arc};
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
+
+use crate::router::RouterDestination;
+
+/// A destination whose fields can be saved and restored.
+///
+/// Implemented by `#[shard(saveable)]`, which also derives `Serialize` and
+/// `Deserialize` for the generated destination.
+pub trait SerializableDestination: RouterDestination + Serialize + DeserializeOwned {}
+
+/// A saved destination: its id and serialized fields.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SavedDestination {
+    id: String,
+    fields: Value,
+}
+
+impl SavedDestination {
+    /// Save `destination`, or return `None` if its fields fail to serialize.
+    pub fn new<D: SerializableDestination>(destination: &D) -> Option<Self> {
+        Some(Self {
+            id: D::destination_id().to_owned(),
+            fields: serde_json::to_value(destination).ok()?,
+        })
+    }
+
+    /// The id of the saved destination.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+/// A saved route with the shard state it owned.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct SavedRoute {
+    pub(crate) destination: SavedDestination,
+    #[serde(default)]
+    pub(crate) shard_state: HashMap<String, Value>,
+}
+
+/// Navigation stack and saveable shard state of a router controller.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SavedRouterState {
+    pub(crate) routes: Vec<SavedRoute>,
+    #[serde(default)]
+    pub(crate) scope_state: HashMap<String, Value>,
+}
+
+impl SavedRouterState {
+    /// Number of saved destinations.
+    pub fn len(&self) -> usize {
+        self.routes.len()
+    }
+
+    /// Whether no destination was saved.
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+}
+
+type DestinationDecoder = fn(Value) -> Option<Arc<dyn RouterDestination>>;
+
+/// Destination types a router may recreate from a [`SavedRouterState`].
+///
+/// # Examples
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use tessera_shard::router::{
+///     DestinationRestorer, RouterController, RouterDestination, SerializableDestination,
+/// };
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct UserDestination {
+///     id: u64,
+/// }
+///
+/// impl RouterDestination for UserDestination {
+///     fn exec_component(&self) {}
+///     fn destination_id() -> &'static str {
+///         "user"
+///     }
+/// }
+///
+/// impl SerializableDestination for UserDestination {}
+///
+/// let mut router = RouterController::with_root(UserDestination { id: 1 });
+/// router.push(UserDestination { id: 2 });
+/// let saved = router.save_state();
+///
+/// let restorer = DestinationRestorer::new().destination::<UserDestination>();
+/// let mut restored = RouterController::new();
+/// assert!(restored.restore_state(&saved, &restorer));
+/// assert_eq!(restored.len(), 2);
+/// ```
+#[derive(Default)]
+pub struct DestinationRestorer {
+    decoders: HashMap<&'static str, DestinationDecoder>,
+}
+
+impl DestinationRestorer {
+    /// Create a restorer that knows no destination.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow `D` to be restored.
+    pub fn destination<D: SerializableDestination + 'static>(mut self) -> Self {
+        self.decoders
+            .insert(D::destination_id(), decode_destination::<D>);
+        self
+    }
+
+    pub(crate) fn decode(&self, saved: &SavedDestination) -> Option<Arc<dyn RouterDestination>> {
+        let decode = self.decoders.get(saved.id.as_str())?;
+        decode(saved.fields.clone())
+    }
+}
+
+fn decode_destination<D: SerializableDestination + 'static>(
+    fields: Value,
+) -> Option<Arc<dyn RouterDestination>> {
+    let destination: D = serde_json::from_value(fields).ok()?;
+    Some(Arc::new(destination))
+}
@@ -0,0 +1,406 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
+
+use dashmap::DashMap;
+use parking_lot::RwLock;
+use tessera_ui::{
+    ClipShape, ComputedData, Constraint, Dp, DrawModifierContent, DrawModifierContext,
+    DrawModifierNode, MeasurementError, Modifier, PlacementModifierNode, Px, PxPosition, PxRect,
+    PxSize, RenderSlot, key,
+    layout::{LayoutPolicy, LayoutResult, MeasureScope, layout},
+    modifier::ModifierCapabilityExt,
+    provide_context, remember, tessera, use_context,
+};
+
+use crate::router::{
+    RouteId, RouteScope, RouterContext, RouterController, TransitionStyle,
+    transition::page_modifier,
+};
+
+static NEXT_OWNER: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub(crate) struct SharedKey {
+    route_id: RouteId,
+    name: String,
+}
+
+/// Window-space rect and corner radius of a shared element.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct SharedBounds {
+    position: PxPosition,
+    size: PxSize,
+    corner_radius: Dp,
+}
+
+impl SharedBounds {
+    fn lerp(self, to: Self, progress: f32) -> Self {
+        let mix = |from: Px, to: Px| from + (to - from).mul_f32(progress);
+        Self {
+            position: PxPosition::new(
+                mix(self.position.x, to.position.x),
+                mix(self.position.y, to.position.y),
+            ),
+            size: PxSize::new(
+                mix(self.size.width, to.size.width),
+                mix(self.size.height, to.size.height),
+            ),
+            corner_radius: Dp(
+                self.corner_radius.0 + (to.corner_radius.0 - self.corner_radius.0) * progress
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct SharedEntry {
+    /// Registration that created the entry; only it may remove the entry.
+    owner: u64,
+    position: Option<PxPosition>,
+    size: Option<PxSize>,
+    corner_radius: Dp,
+    content: RenderSlot,
+}
+
+impl SharedEntry {
+    fn bounds(&self) -> Option<SharedBounds> {
+        Some(SharedBounds {
+            position: self.position?,
+            size: self.size?,
+            corner_radius: self.corner_radius,
+        })
+    }
+}
+
+/// The routes of the running router transition, and its eased progress.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct SharedTransition {
+    pub(crate) entering: RouteId,
+    pub(crate) exiting: RouteId,
+    pub(crate) progress: f32,
+}
+
+/// Shared elements of the routes of one router.
+///
+/// Bounds are reported by the layout of each element and read back by the
+/// next build, so morphs trail the pages by one frame.
+#[derive(Default)]
+pub(crate) struct SharedElements {
+    entries: DashMap<SharedKey, SharedEntry>,
+    transition: RwLock<Option<SharedTransition>>,
+}
+
+impl SharedElements {
+    pub(crate) fn set_transition(&self, transition: Option<SharedTransition>) {
+        *self.transition.write() = transition;
+    }
+
+    fn register(&self, key: SharedKey, owner: u64, corner_radius: Dp, content: RenderSlot) {
+        self.entries
+            .entry(key)
+            .and_modify(|entry| {
+                entry.owner = owner;
+                entry.corner_radius = corner_radius;
+                entry.content = content;
+            })
+            .or_insert(SharedEntry {
+                owner,
+                position: None,
+                size: None,
+                corner_radius,
+                content,
+            });
+    }
+
+    fn unregister(&self, key: &SharedKey, owner: u64) {
+        self.entries.remove_if(key, |_, entry| entry.owner == owner);
+    }
+
+    fn set_position(&self, key: &SharedKey, position: PxPosition) {
+        if let Some(mut entry) = self.entries.get_mut(key) {
+            entry.position = Some(position);
+        }
+    }
+
+    fn set_size(&self, key: &SharedKey, size: PxSize) {
+        if let Some(mut entry) = self.entries.get_mut(key) {
+            entry.size = Some(size);
+        }
+    }
+
+    /// Returns the entering element and where it morphs from, if `name` is
+    /// shared by both routes of `transition`.
+    fn morph(
+        &self,
+        transition: SharedTransition,
+        name: &str,
+    ) -> Option<(SharedEntry, SharedBounds)> {
+        let lookup = |route_id| {
+            self.entries
+                .get(&SharedKey {
+                    route_id,
+                    name: name.to_owned(),
+                })
+                .map(|entry| *entry)
+        };
+        let from = lookup(transition.exiting)?.bounds()?;
+        Some((lookup(transition.entering)?, from))
+    }
+
+    /// Whether the element at `key` is replaced by its morph this frame.
+    fn is_morphing(&self, key: &SharedKey) -> bool {
+        let Some(transition) = *self.transition.read() else {
+            return false;
+        };
+        (key.route_id == transition.entering || key.route_id == transition.exiting)
+            && self.morph(transition, &key.name).is_some()
+    }
+
+    fn morph_names(&self, route_id: RouteId) -> Vec<String> {
+        let mut names: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.key().route_id == route_id)
+            .map(|entry| entry.key().name.clone())
+            .collect();
+        names.sort();
+        names
+    }
+}
+
+/// Removes the entry of a shared element once its instance is dropped.
+struct SharedRegistration {
+    elements: Arc<SharedElements>,
+    key: SharedKey,
+    owner: u64,
+}
+
+impl Drop for SharedRegistration {
+    fn drop(&mut self) {
+        self.elements.unregister(&self.key, self.owner);
+    }
+}
+
+/// # shared_element
+///
+/// Mark content that morphs between screens during router transitions.
+///
+/// ## Usage
+///
+/// Wrap the same logical item, such as a photo thumbnail and the header of
+/// its detail page, in a `shared_element` with the same key on both screens.
+///
+/// ## Parameters
+///
+/// - `key` — name matching the element on the other screen; unique per screen
+/// - `corner_radius` — optional corner radius of the element on this screen
+/// - `modifier` — optional modifier chain applied to the element
+/// - `child` — optional content of the element
+///
+/// While an animated push or pop runs between two routes that both render a
+/// key, both elements are hidden and the entering one is drawn above the
+/// pages instead, moving and resizing from the rect of the exiting one to its
+/// own while its corner radius follows. Outside a `shard_home` or without a
+/// key the content renders as is.
+///
+/// ## Examples
+///
+/// ```rust
+/// use tessera_shard::router::shared_element;
+///
+/// # #[tessera_ui::tessera]
+/// # fn demo() {
+/// shared_element()
+///     .key("photo-42")
+///     .corner_radius(tessera_ui::Dp(12.0))
+///     .child(|| {});
+/// # }
+/// # demo();
+/// ```
+#[tessera(tessera_ui)]
+pub fn shared_element(
+    #[prop(into)] key: Option<String>,
+    corner_radius: Option<Dp>,
+    modifier: Option<Modifier>,
+    child: Option<RenderSlot>,
+) {
+    let modifier = modifier.unwrap_or_default();
+    let child = child.unwrap_or_else(RenderSlot::empty);
+    let router = use_context::<RouterContext>().map(|context| context.get().controller());
+    let route_id = use_context::<RouteScope>().map(|scope| scope.get().route_id);
+    let (Some(name), Some(router), Some(route_id)) = (key, router, route_id) else {
+        layout().modifier(modifier).child(move || child.render());
+        return;
+    };
+
+    let elements = router.with(RouterController::shared_elements);
+    let key = SharedKey { route_id, name };
+    let registration = remember(|| SharedRegistration {
+        elements: elements.clone(),
+        key: key.clone(),
+        owner: NEXT_OWNER.fetch_add(1, Ordering::Relaxed),
+    });
+    if registration.with(|registration| registration.key != key) {
+        registration.with_mut(|registration| {
+            registration
+                .elements
+                .unregister(&registration.key, registration.owner);
+            registration.key = key.clone();
+        });
+    }
+    let owner = registration.with(|registration| registration.owner);
+    elements.register(key.clone(), owner, corner_radius.unwrap_or(Dp(0.0)), child);
+
+    let modifier = modifier
+        .push_placement(SharedBoundsNode {
+            elements: elements.clone(),
+            key: key.clone(),
+        })
+        .push_draw(SharedDrawNode { elements, key });
+    layout().modifier(modifier).child(move || child.render());
+}
+
+/// Renders the morphs of the elements shared by the routes of `transition`.
+pub(crate) fn shared_element_overlays(elements: &SharedElements, transition: SharedTransition) {
+    for name in elements.morph_names(transition.entering) {
+        let Some((entering, from)) = elements.morph(transition, &name) else {
+            continue;
+        };
+        // The entering page has not been laid out on the first frame.
+        let to = entering.bounds().unwrap_or(from);
+        let bounds = from.lerp(to, transition.progress);
+        let content = entering.content;
+        let route_id = transition.entering;
+        key(name, || {
+            let modifier = page_modifier(TransitionStyle::IDENTITY, true)
+                .push_placement(AbsolutePlacementNode {
+                    position: bounds.position,
+                })
+                .push_draw(CornerClipNode {
+                    corner_radius: bounds.corner_radius,
+                });
+            layout()
+                .modifier(modifier)
+                .layout_policy(MorphLayout { size: bounds.size })
+                .child(move || {
+                    provide_context(|| RouteScope { route_id }, move || content.render());
+                });
+        });
+    }
+}
+
+/// Reports the window-space position of a shared element.
+struct SharedBoundsNode {
+    elements: Arc<SharedElements>,
+    key: SharedKey,
+}
+
+impl PartialEq for SharedBoundsNode {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.elements, &other.elements) && self.key == other.key
+    }
+}
+
+impl PlacementModifierNode for SharedBoundsNode {
+    fn transform_position(&self, position: PxPosition) -> PxPosition {
+        self.elements.set_position(&self.key, position);
+        position
+    }
+}
+
+/// Reports the size of a shared element and hides it while it morphs.
+struct SharedDrawNode {
+    elements: Arc<SharedElements>,
+    key: SharedKey,
+}
+
+impl DrawModifierNode for SharedDrawNode {
+    fn draw(&self, ctx: &mut DrawModifierContext<'_, '_>, content: &mut dyn DrawModifierContent) {
+        {
+            let mut metadata = ctx.render_input.metadata_mut();
+            if let Some(size) = metadata.computed_data() {
+                self.elements.set_size(&self.key, size.into());
+            }
+            if self.elements.is_morphing(&self.key) {
+                metadata.multiply_opacity(0.0);
+            }
+        }
+        content.draw(ctx.render_input);
+    }
+}
+
+#[derive(PartialEq)]
+struct AbsolutePlacementNode {
+    position: PxPosition,
+}
+
+impl PlacementModifierNode for AbsolutePlacementNode {
+    fn transform_position(&self, _position: PxPosition) -> PxPosition {
+        self.position
+    }
+}
+
+struct CornerClipNode {
+    corner_radius: Dp,
+}
+
+impl DrawModifierNode for CornerClipNode {
+    fn draw(&self, ctx: &mut DrawModifierContext<'_, '_>, content: &mut dyn DrawModifierContent) {
+        {
+            let mut metadata = ctx.render_input.metadata_mut();
+            if let Some(size) = metadata.computed_data() {
+                metadata.set_clip_shape(ClipShape::RoundedRect {
+                    rect: PxRect::from_position_size(PxPosition::ZERO, size.into()),
+                    corner_radii: [self.corner_radius.to_pixels_f32(); 4],
+                });
+            }
+        }
+        content.draw(ctx.render_input);
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct MorphLayout {
+    size: PxSize,
+}
+
+impl LayoutPolicy for MorphLayout {
+    fn measure(&self, input: &MeasureScope<'_>) -> Result<LayoutResult, MeasurementError> {
+        let mut result = LayoutResult::default();
+        if let Some(child) = input.children().first().copied() {
+            child.measure(&Constraint::exact(self.size.width, self.size.height))?;
+            result.place_child(child, PxPosition::ZERO);
+        }
+        Ok(result.with_size(ComputedData {
+            width: self.size.width,
+            height: self.size.height,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_interpolate_rect_and_corner_radius() {
+        let from = SharedBounds {
+            position: PxPosition::new(Px(10), Px(20)),
+            size: PxSize::new(Px(40), Px(40)),
+            corner_radius: Dp(20.0),
+        };
+        let to = SharedBounds {
+            position: PxPosition::new(Px(0), Px(100)),
+            size: PxSize::new(Px(200), Px(120)),
+            corner_radius: Dp(0.0),
+        };
+
+        let half = from.lerp(to, 0.5);
+        assert_eq!(half.position, PxPosition::new(Px(5), Px(60)));
+        assert_eq!(half.size, PxSize::new(Px(120), Px(80)));
+        assert_eq!(half.corner_radius, Dp(10.0));
+        assert_eq!(from.lerp(to, 1.0), to);
+    }
+}
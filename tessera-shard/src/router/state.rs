@@ -21,6 +21,12 @@ impl RouterContext {
     }
 }
 
+/// The route whose shard is being rendered.
+#[derive(Clone, Copy)]
+pub(crate) struct RouteScope {
+    pub(crate) route_id: RouteId,
+}
+
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub(crate) struct RouteId(pub(crate) u64);
 
@@ -35,3 +41,9 @@ pub(crate) struct RouteShardKey {
     pub(crate) route_id: RouteId,
     pub(crate) shard_id: String,
 }
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub(crate) struct ChildScopeKey {
+    pub(crate) route_id: Option<RouteId>,
+    pub(crate) name: String,
+}
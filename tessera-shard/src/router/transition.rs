@@ -0,0 +1,336 @@
+use std::{fmt, sync::Arc, time::Duration};
+
+use tessera_ui::{
+    ComputedData, Constraint, DrawModifierContent, DrawModifierContext, DrawModifierNode,
+    KeyboardInput, KeyboardInputModifierNode, LayoutModifierChild, LayoutModifierInput,
+    LayoutModifierNode, LayoutModifierOutput, MeasurementError, Modifier, PointerInput,
+    PointerInputModifierNode, Px, PxPosition, modifier::ModifierCapabilityExt,
+};
+
+const DEFAULT_DURATION: Duration = Duration::from_millis(300);
+/// Fraction of the page size travelled by shared-axis transitions.
+const SHARED_AXIS_DISTANCE: f32 = 0.1;
+
+/// Whether a transition was started by a push or by a pop.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransitionDirection {
+    /// A destination was pushed on top of the stack.
+    Push,
+    /// The top destination was popped.
+    Pop,
+}
+
+/// Which page of a transition a style is computed for.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransitionRole {
+    /// The page that is on top once the transition ends.
+    Entering,
+    /// The page that leaves the screen.
+    Exiting,
+}
+
+/// Axis used by [`RouterTransition::shared_axis`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransitionAxis {
+    /// Pages move horizontally.
+    X,
+    /// Pages move vertically.
+    Y,
+}
+
+/// Input of a transition style function.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TransitionFrame {
+    /// Eased progress from `0.0` to `1.0`.
+    pub progress: f32,
+    /// Whether the transition was started by a push or by a pop.
+    pub direction: TransitionDirection,
+    /// The page the style is computed for.
+    pub role: TransitionRole,
+}
+
+/// Visual state of one page during a transition.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TransitionStyle {
+    /// Opacity from `0.0` to `1.0`.
+    pub alpha: f32,
+    /// Horizontal translation as a fraction of the page width.
+    pub translation_x: f32,
+    /// Vertical translation as a fraction of the page height.
+    pub translation_y: f32,
+}
+
+impl TransitionStyle {
+    /// The style of a page at rest.
+    pub const IDENTITY: Self = Self {
+        alpha: 1.0,
+        translation_x: 0.0,
+        translation_y: 0.0,
+    };
+}
+
+type TransitionStyleFn = dyn Fn(TransitionFrame) -> TransitionStyle + Send + Sync;
+
+#[derive(Clone)]
+enum TransitionKind {
+    None,
+    Fade,
+    Slide,
+    SharedAxis(TransitionAxis),
+    Custom(Arc<TransitionStyleFn>),
+}
+
+/// Animation played when a destination is pushed or popped.
+///
+/// A push uses the transition of the pushed destination, a pop the transition
+/// of the popped one. The outgoing page keeps rendering until the animation
+/// ends, and neither page receives input meanwhile. Transitions are skipped
+/// when the system asks for reduced motion.
+#[derive(Clone)]
+pub struct RouterTransition {
+    kind: TransitionKind,
+    duration: Duration,
+}
+
+impl RouterTransition {
+    /// Swap pages instantly.
+    pub fn none() -> Self {
+        Self::new(TransitionKind::None)
+    }
+
+    /// Cross-fade the pages.
+    pub fn fade() -> Self {
+        Self::new(TransitionKind::Fade)
+    }
+
+    /// Slide the pushed page in from the trailing edge, and back out on pop.
+    pub fn slide() -> Self {
+        Self::new(TransitionKind::Slide)
+    }
+
+    /// Move both pages a short distance along `axis` while cross-fading.
+    pub fn shared_axis(axis: TransitionAxis) -> Self {
+        Self::new(TransitionKind::SharedAxis(axis))
+    }
+
+    /// Compute the style of each page with `style`.
+    pub fn custom<F>(style: F) -> Self
+    where
+        F: Fn(TransitionFrame) -> TransitionStyle + Send + Sync + 'static,
+    {
+        Self::new(TransitionKind::Custom(Arc::new(style)))
+    }
+
+    /// Set the animation duration; the default is 300 ms.
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    fn new(kind: TransitionKind) -> Self {
+        Self {
+            kind,
+            duration: DEFAULT_DURATION,
+        }
+    }
+
+    pub(crate) fn is_animated(&self) -> bool {
+        !matches!(self.kind, TransitionKind::None) && !self.duration.is_zero()
+    }
+
+    pub(crate) fn duration_nanos(&self) -> u64 {
+        self.duration.as_nanos().min(u64::MAX as u128) as u64
+    }
+
+    pub(crate) fn style(&self, frame: TransitionFrame) -> TransitionStyle {
+        let p = frame.progress.clamp(0.0, 1.0);
+        let entering = frame.role == TransitionRole::Entering;
+        let fade_alpha = if entering { p } else { 1.0 - p };
+        // Pushes move pages towards the leading edge, pops move them back.
+        let sign = match frame.direction {
+            TransitionDirection::Push => 1.0,
+            TransitionDirection::Pop => -1.0,
+        };
+        let travel = if entering {
+            sign * (1.0 - p)
+        } else {
+            -sign * p
+        };
+        match &self.kind {
+            TransitionKind::None => TransitionStyle::IDENTITY,
+            TransitionKind::Fade => TransitionStyle {
+                alpha: fade_alpha,
+                ..TransitionStyle::IDENTITY
+            },
+            TransitionKind::Slide => TransitionStyle {
+                translation_x: travel,
+                ..TransitionStyle::IDENTITY
+            },
+            TransitionKind::SharedAxis(axis) => {
+                let offset = travel * SHARED_AXIS_DISTANCE;
+                let (translation_x, translation_y) = match axis {
+                    TransitionAxis::X => (offset, 0.0),
+                    TransitionAxis::Y => (0.0, offset),
+                };
+                TransitionStyle {
+                    alpha: fade_alpha,
+                    translation_x,
+                    translation_y,
+                }
+            }
+            TransitionKind::Custom(style) => style(TransitionFrame {
+                progress: p,
+                ..frame
+            }),
+        }
+    }
+}
+
+impl Default for RouterTransition {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+impl fmt::Debug for RouterTransition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match &self.kind {
+            TransitionKind::None => "None",
+            TransitionKind::Fade => "Fade",
+            TransitionKind::Slide => "Slide",
+            TransitionKind::SharedAxis(TransitionAxis::X) => "SharedAxisX",
+            TransitionKind::SharedAxis(TransitionAxis::Y) => "SharedAxisY",
+            TransitionKind::Custom(_) => "Custom",
+        };
+        f.debug_struct("RouterTransition")
+            .field("kind", &kind)
+            .field("duration", &self.duration)
+            .finish()
+    }
+}
+
+/// Cubic ease-in-out, matching the component animations.
+pub(crate) fn ease(progress: f32) -> f32 {
+    let t = progress.clamp(0.0, 1.0);
+    if t < 0.5 {
+        4.0 * t * t * t
+    } else {
+        1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+    }
+}
+
+/// Applies `style` to a page and optionally blocks its input.
+pub(crate) fn page_modifier(style: TransitionStyle, block_input: bool) -> Modifier {
+    let mut modifier = Modifier::new();
+    if style.translation_x != 0.0 || style.translation_y != 0.0 {
+        modifier = modifier.push_layout(TranslateModifierNode {
+            x: style.translation_x,
+            y: style.translation_y,
+        });
+    }
+    if style.alpha < 1.0 {
+        modifier = modifier.push_draw(AlphaModifierNode {
+            alpha: style.alpha.max(0.0),
+        });
+    }
+    if block_input {
+        modifier = modifier
+            .push_pointer_preview_input(BlockInputModifierNode)
+            .push_keyboard_preview_input(BlockInputModifierNode);
+    }
+    modifier
+}
+
+struct TranslateModifierNode {
+    x: f32,
+    y: f32,
+}
+
+impl LayoutModifierNode for TranslateModifierNode {
+    fn measure(
+        &self,
+        input: &LayoutModifierInput<'_>,
+        child: &mut dyn LayoutModifierChild,
+    ) -> Result<LayoutModifierOutput, MeasurementError> {
+        let constraint = Constraint::new(
+            input.layout_input.parent_constraint().width(),
+            input.layout_input.parent_constraint().height(),
+        );
+        let size: ComputedData = child.measure(&constraint)?;
+        child.place(PxPosition::new(
+            Px((size.width.0 as f32 * self.x).round() as i32),
+            Px((size.height.0 as f32 * self.y).round() as i32),
+        ));
+        Ok(LayoutModifierOutput { size })
+    }
+}
+
+struct AlphaModifierNode {
+    alpha: f32,
+}
+
+impl DrawModifierNode for AlphaModifierNode {
+    fn draw(&self, ctx: &mut DrawModifierContext<'_, '_>, content: &mut dyn DrawModifierContent) {
+        ctx.render_input.metadata_mut().multiply_opacity(self.alpha);
+        content.draw(ctx.render_input);
+    }
+}
+
+struct BlockInputModifierNode;
+
+impl PointerInputModifierNode for BlockInputModifierNode {
+    fn on_pointer_input(&self, mut input: PointerInput<'_>) {
+        input.consume_pointer_changes();
+    }
+}
+
+impl KeyboardInputModifierNode for BlockInputModifierNode {
+    fn on_keyboard_input(&self, mut input: KeyboardInput<'_>) {
+        input.block_keyboard();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(
+        progress: f32,
+        direction: TransitionDirection,
+        role: TransitionRole,
+    ) -> TransitionFrame {
+        TransitionFrame {
+            progress,
+            direction,
+            role,
+        }
+    }
+
+    #[test]
+    fn slide_moves_pushed_page_in_and_popped_page_out_from_the_trailing_edge() {
+        let slide = RouterTransition::slide();
+        let push_start = frame(0.0, TransitionDirection::Push, TransitionRole::Entering);
+        let push_end = frame(1.0, TransitionDirection::Push, TransitionRole::Entering);
+        let pop_end = frame(1.0, TransitionDirection::Pop, TransitionRole::Exiting);
+        let covered_end = frame(1.0, TransitionDirection::Push, TransitionRole::Exiting);
+
+        assert_eq!(slide.style(push_start).translation_x, 1.0);
+        assert_eq!(slide.style(push_end), TransitionStyle::IDENTITY);
+        assert_eq!(slide.style(pop_end).translation_x, 1.0);
+        assert_eq!(slide.style(covered_end).translation_x, -1.0);
+    }
+
+    #[test]
+    fn fade_cross_fades_and_none_is_not_animated() {
+        let fade = RouterTransition::fade();
+        let half = |role| {
+            fade.style(frame(0.25, TransitionDirection::Pop, role))
+                .alpha
+        };
+        assert_eq!(half(TransitionRole::Entering), 0.25);
+        assert_eq!(half(TransitionRole::Exiting), 0.75);
+        assert!(fade.is_animated());
+        assert!(!RouterTransition::none().is_animated());
+        assert!(!fade.duration(Duration::ZERO).is_animated());
+    }
+}
@@ -0,0 +1,68 @@
+//! Benchmarks for the per-frame runtime hot paths.
+//!
+//! Run with `cargo bench -p tessera-ui --features bench`.
+
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+use tessera_ui::bench::{
+    SyntheticTree, build_synthetic_tree, plan_passes, reorder_commands, synthetic_render_graph,
+};
+
+fn tree_build(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tree_build");
+    for node_count in [100, 1_000, 10_000] {
+        let mut tree = build_synthetic_tree(node_count);
+        group.bench_function(BenchmarkId::from_parameter(node_count), |b| {
+            b.iter(|| tree.rebuild());
+        });
+    }
+    group.finish();
+}
+
+fn measure(c: &mut Criterion) {
+    let mut group = c.benchmark_group("measure");
+    for node_count in [100, 1_000, 10_000] {
+        let mut tree = build_synthetic_tree(node_count);
+        group.bench_function(BenchmarkId::new("balanced", node_count), |b| {
+            b.iter(|| tree.measure());
+        });
+    }
+    // Deep chains recurse once per node while building and measuring, so they
+    // stay small enough for the default thread stack.
+    for node_count in [64, 256] {
+        let mut tree = SyntheticTree::deep(node_count);
+        group.bench_function(BenchmarkId::new("deep", node_count), |b| {
+            b.iter(|| tree.measure());
+        });
+    }
+    for node_count in [1_000, 10_000] {
+        let mut tree = SyntheticTree::wide(node_count);
+        group.bench_function(BenchmarkId::new("wide", node_count), |b| {
+            b.iter(|| tree.measure());
+        });
+    }
+    group.finish();
+}
+
+fn commands(c: &mut Criterion) {
+    let mut group = c.benchmark_group("commands");
+    for op_count in [100, 500, 2_000] {
+        group.bench_function(BenchmarkId::new("reorder", op_count), |b| {
+            b.iter_batched(
+                || synthetic_render_graph(op_count),
+                reorder_commands,
+                BatchSize::SmallInput,
+            );
+        });
+        group.bench_function(BenchmarkId::new("plan_passes", op_count), |b| {
+            b.iter_batched(
+                || reorder_commands(synthetic_render_graph(op_count)),
+                plan_passes,
+                BatchSize::SmallInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, tree_build, measure, commands);
+criterion_main!(benches);
@@ -0,0 +1,240 @@
+//! Fuzzes layout measurement with random constraint trees.
+//!
+//! Every node sizes each axis as wrap content, fill max, a fixed size or a
+//! random min/max interval, and stacks its children vertically. The target
+//! checks that:
+//!
+//! - measurement never panics, including on overflowing sizes,
+//! - no node reports a negative size,
+//! - wrap and fill children never exceed their parent's bounds,
+//! - measuring the same tree again yields the same sizes.
+//!
+//! Run with `cargo fuzz run layout_constraints` from `tessera-ui`.
+
+#![no_main]
+
+use std::sync::Mutex;
+
+use arbitrary::Unstructured;
+use libfuzzer_sys::fuzz_target;
+use tessera_foundation::modifier::ModifierExt as _;
+use tessera_ui::{
+    AxisConstraint, ComputedData, Constraint, MeasurementError, Modifier, NoopRenderPolicy, Px,
+    PxPosition,
+    layout::{LayoutPolicy, LayoutResult, MeasureScope, layout},
+    tessera,
+    testing::layout_test,
+};
+
+const MAX_NODES: usize = 64;
+const MAX_DEPTH: usize = 8;
+const MAX_CHILDREN: usize = 6;
+
+#[derive(Clone, Copy, Debug)]
+enum Sizing {
+    Wrap,
+    Fill,
+    Fixed(Px),
+    Range(AxisConstraint),
+}
+
+impl Sizing {
+    fn generate(u: &mut Unstructured<'_>) -> arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=3)? {
+            0 => Self::Wrap,
+            1 => Self::Fill,
+            2 => Self::Fixed(Px::new(u.int_in_range(0..=4096)?)),
+            _ => {
+                let min = Px::new(u.int_in_range(0..=i32::MAX)?);
+                let max = if u.arbitrary()? {
+                    Some(Px::new(u.int_in_range(0..=i32::MAX)?))
+                } else {
+                    None
+                };
+                Self::Range(AxisConstraint::new(min, max))
+            }
+        })
+    }
+
+    fn is_explicit(self) -> bool {
+        matches!(self, Self::Fixed(_) | Self::Range(_))
+    }
+
+    fn override_axis(self) -> Option<AxisConstraint> {
+        match self {
+            Self::Fixed(size) => Some(AxisConstraint::exact(size)),
+            Self::Range(axis) => Some(axis),
+            Self::Wrap | Self::Fill => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct FuzzNode {
+    width: Sizing,
+    height: Sizing,
+    children: Vec<usize>,
+}
+
+impl FuzzNode {
+    fn modifier(&self) -> Modifier {
+        let mut modifier = Modifier::new();
+        if matches!(self.width, Sizing::Fill) {
+            modifier = modifier.fill_max_width();
+        }
+        if matches!(self.height, Sizing::Fill) {
+            modifier = modifier.fill_max_height();
+        }
+        let width = self.width.override_axis();
+        let height = self.height.override_axis();
+        if width.is_some() || height.is_some() {
+            modifier = modifier.constrain(width, height);
+        }
+        modifier
+    }
+}
+
+/// Generates a tree in pre-order; the root is node `0`.
+fn generate_tree(u: &mut Unstructured<'_>) -> arbitrary::Result<Vec<FuzzNode>> {
+    fn generate_node(
+        u: &mut Unstructured<'_>,
+        depth: usize,
+        nodes: &mut Vec<FuzzNode>,
+    ) -> arbitrary::Result<usize> {
+        let index = nodes.len();
+        nodes.push(FuzzNode {
+            width: Sizing::generate(u)?,
+            height: Sizing::generate(u)?,
+            children: Vec::new(),
+        });
+        if depth < MAX_DEPTH {
+            let child_count = u.int_in_range(0..=MAX_CHILDREN)?;
+            for _ in 0..child_count {
+                if nodes.len() >= MAX_NODES {
+                    break;
+                }
+                let child = generate_node(u, depth + 1, nodes)?;
+                nodes[index].children.push(child);
+            }
+        }
+        Ok(index)
+    }
+
+    let mut nodes = Vec::new();
+    generate_node(u, 0, &mut nodes)?;
+    Ok(nodes)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Measurement {
+    constraint: Constraint,
+    size: ComputedData,
+}
+
+static TREE: Mutex<Vec<FuzzNode>> = Mutex::new(Vec::new());
+static MEASUREMENTS: Mutex<Vec<Option<Measurement>>> = Mutex::new(Vec::new());
+
+#[tessera]
+fn fuzz_node(index: Option<usize>) {
+    let index = index.unwrap_or_default();
+    let node = TREE.lock().unwrap()[index].clone();
+    layout()
+        .layout_policy(CheckedStackPolicy { index })
+        .render_policy(NoopRenderPolicy)
+        .modifier(node.modifier())
+        .child(move || {
+            for &child in &node.children {
+                fuzz_node().index(child);
+            }
+        });
+}
+
+/// Stacks children vertically and checks the sizes they report.
+#[derive(Clone, PartialEq)]
+struct CheckedStackPolicy {
+    index: usize,
+}
+
+impl LayoutPolicy for CheckedStackPolicy {
+    fn measure(&self, input: &MeasureScope<'_>) -> Result<LayoutResult, MeasurementError> {
+        let constraint = *input.parent_constraint().as_ref();
+        let child_constraint = input.parent_constraint().without_min();
+        let child_nodes = TREE.lock().unwrap()[self.index].children.clone();
+
+        let mut result = LayoutResult::default();
+        let mut current_y = Px::ZERO;
+        let mut max_width = Px::ZERO;
+        for (child, child_index) in input.children().into_iter().zip(child_nodes) {
+            let child_size = child.measure(&child_constraint)?;
+            let child_node = TREE.lock().unwrap()[child_index].clone();
+            check_child_axis(
+                child_size.width,
+                constraint.width,
+                child_node.width,
+                child_index,
+            );
+            check_child_axis(
+                child_size.height,
+                constraint.height,
+                child_node.height,
+                child_index,
+            );
+            result.place_child(child, PxPosition::new(Px::ZERO, current_y));
+            current_y = current_y.saturating_add(child_size.height);
+            max_width = max_width.max(child_size.width);
+        }
+
+        let size = ComputedData {
+            width: constraint.width.clamp(max_width),
+            height: constraint.height.clamp(current_y),
+        };
+        MEASUREMENTS.lock().unwrap()[self.index] = Some(Measurement { constraint, size });
+        Ok(result.with_size(size))
+    }
+}
+
+/// Explicit sizes may overflow their parent by design, like `size` does when
+/// the parent is smaller.
+fn check_child_axis(size: Px, parent: AxisConstraint, sizing: Sizing, index: usize) {
+    assert!(size >= Px::ZERO, "node {index} has negative size {size:?}");
+    if let Some(max) = parent.max
+        && !sizing.is_explicit()
+    {
+        assert!(
+            size <= max,
+            "node {index} ({sizing:?}) measured {size:?}, past its parent's max {max:?}"
+        );
+    }
+}
+
+fn measure_tree(viewport: (u32, u32), node_count: usize) -> Vec<Option<Measurement>> {
+    *MEASUREMENTS.lock().unwrap() = vec![None; node_count];
+    layout_test(|| {
+        fuzz_node().index(0);
+    })
+    .viewport_px(viewport.0, viewport.1)
+    .run();
+    std::mem::take(&mut *MEASUREMENTS.lock().unwrap())
+}
+
+fuzz_target!(|data: &[u8]| {
+    let mut u = Unstructured::new(data);
+    let Ok(viewport) = u
+        .int_in_range(1..=4096)
+        .and_then(|width| Ok((width, u.int_in_range(1..=4096)?)))
+    else {
+        return;
+    };
+    let Ok(tree) = generate_tree(&mut u) else {
+        return;
+    };
+    let node_count = tree.len();
+    *TREE.lock().unwrap() = tree;
+
+    let first = measure_tree(viewport, node_count);
+    let second = measure_tree(viewport, node_count);
+    assert_eq!(
+        first, second,
+        "re-measuring the same tree changed its layout"
+    );
+});
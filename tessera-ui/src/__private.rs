@@ -12,6 +12,10 @@ use crate::{
     runtime::TesseraRuntime,
 };
 
+pub use inventory;
+#[cfg(target_family = "wasm")]
+pub use wasm_bindgen;
+
 pub use crate::{
     layout::layout,
     prop::{Prop, make_component_runner},
@@ -77,6 +81,7 @@ pub fn register_component_node(fn_name: &str, _component_type_id: u64) -> NodeId
             render_policy: Box::new(NoopRenderPolicy),
             replay: None,
             props_unchanged_from_previous: false,
+            memo: false,
         })
     })
 }
@@ -108,6 +113,7 @@ pub fn register_layout_node(fn_name: &str, _component_type_id: u64) -> NodeId {
             render_policy: Box::new(NoopRenderPolicy),
             replay: None,
             props_unchanged_from_previous: false,
+            memo: false,
         })
     })
 }
@@ -129,5 +135,31 @@ pub fn set_current_component_replay<P>(runner: Arc<dyn ErasedComponentRunner>, p
 where
     P: crate::prop::Prop,
 {
-    TesseraRuntime::with_mut(|runtime| runtime.set_current_component_replay(runner, props))
+    TesseraRuntime::with_mut(|runtime| runtime.set_current_component_replay(runner, props, false))
+}
+
+pub fn set_current_memo_component_replay<P>(
+    runner: Arc<dyn ErasedComponentRunner>,
+    props: &P,
+) -> bool
+where
+    P: crate::prop::Prop,
+{
+    TesseraRuntime::with_mut(|runtime| runtime.set_current_component_replay(runner, props, true))
+}
+
+pub const fn preview(
+    name: &'static str,
+    module_path: &'static str,
+    size: Option<(crate::Dp, crate::Dp)>,
+    theme: Option<crate::preview::PreviewTheme>,
+    render: fn(),
+) -> crate::preview::Preview {
+    crate::preview::Preview {
+        name,
+        module_path,
+        size,
+        theme,
+        render,
+    }
 }
@@ -7,16 +7,34 @@
 //! ## Usage
 //!
 //! Accessibility metadata is typically attached through semantics and
-//! interaction modifiers in higher-level component crates. Framework internals
-//! may also write accessibility state directly while building node-local
-//! modifier chains.
+//! interaction modifiers in higher-level component crates. Components can also
+//! describe themselves declaratively with [`semantics`], which populates the
+//! node of the component currently being built. Framework internals may also
+//! write accessibility state directly while building node-local modifier
+//! chains. Transient messages such as "Item deleted" are delivered through
+//! live regions with [`announce`], and [`dump_tree`] snapshots the current tree
+//! for accessibility regression tests.
 
+mod announcer;
+mod dump;
 mod tree_builder;
 
+use std::ops::Range;
+
 use accesskit::{Action, NodeId as AccessKitNodeId, Role, Toggled};
 
-use crate::Px;
+use crate::{
+    Px,
+    prop::Callback,
+    runtime::{TesseraRuntime, ensure_build_phase},
+};
 
+pub use announcer::{Politeness, announce, clear_announcements};
+pub use dump::{
+    AccessibilityIssue, AccessibilityIssueKind, AccessibilityNodeDump, AccessibilityTreeDump,
+    dump_tree,
+};
+pub use tree_builder::current_action_data;
 pub(crate) use tree_builder::{build_tree_update, dispatch_action};
 
 /// A stable identifier for accessibility nodes.
@@ -157,6 +175,9 @@ pub struct AccessibilityNode {
     pub collection_item_info: Option<(usize, usize, usize, usize, bool)>,
     /// Optional editable text flag.
     pub is_editable_text: bool,
+    /// Optional text selection within `value`, as byte offsets from anchor
+    /// to focus. A collapsed range marks the caret.
+    pub text_selection: Option<Range<usize>>,
 }
 
 impl AccessibilityNode {
@@ -244,6 +265,12 @@ impl AccessibilityNode {
         self
     }
 
+    /// Sets the text selection within the value, as byte offsets.
+    pub fn with_text_selection(mut self, selection: Range<usize>) -> Self {
+        self.text_selection = Some(selection);
+        self
+    }
+
     /// Sets a custom accessibility key for stable ID generation.
     pub fn with_key(mut self, key: impl Into<String>) -> Self {
         self.key = Some(key.into());
@@ -282,6 +309,7 @@ impl Default for AccessibilityNode {
             collection_info: None,
             collection_item_info: None,
             is_editable_text: false,
+            text_selection: None,
         }
     }
 }
@@ -289,5 +317,201 @@ impl Default for AccessibilityNode {
 /// Handler for accessibility actions.
 ///
 /// When an assistive technology requests an action (like clicking a button),
-/// this handler is invoked.
+/// this handler is invoked. Data carried by the action is available through
+/// [`current_action_data`].
 pub type AccessibilityActionHandler = Box<dyn Fn(Action) + Send + Sync>;
+
+/// Data attached to an accessibility action request.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccessibilityActionData {
+    /// Replacement text for [`Action::SetValue`].
+    Value(String),
+    /// Replacement number for [`Action::SetValue`].
+    NumericValue(f64),
+    /// Requested selection for [`Action::SetTextSelection`], as byte offsets
+    /// into the node value from anchor to focus.
+    TextSelection(Range<usize>),
+}
+
+/// Declarative builder for the semantics of the current component.
+///
+/// Obtained through [`semantics`]. Each setter writes directly into the
+/// component's [`AccessibilityNode`], and action callbacks are collected into
+/// a single [`AccessibilityActionHandler`].
+pub struct SemanticsScope {
+    node: AccessibilityNode,
+    handlers: Vec<(Action, Callback)>,
+}
+
+impl SemanticsScope {
+    fn new(node: AccessibilityNode) -> Self {
+        Self {
+            node,
+            handlers: Vec::new(),
+        }
+    }
+
+    /// Sets the role of this node.
+    pub fn role(&mut self, role: Role) -> &mut Self {
+        self.node.role = Some(role);
+        self
+    }
+
+    /// Sets the label announced by assistive technologies.
+    pub fn label(&mut self, label: impl Into<String>) -> &mut Self {
+        self.node.label = Some(label.into());
+        self
+    }
+
+    /// Sets the description announced after the label.
+    pub fn description(&mut self, description: impl Into<String>) -> &mut Self {
+        self.node.description = Some(description.into());
+        self
+    }
+
+    /// Sets the value text of this node.
+    pub fn value(&mut self, value: impl Into<String>) -> &mut Self {
+        self.node.value = Some(value.into());
+        self
+    }
+
+    /// Sets the numeric value and its range.
+    pub fn numeric_value(&mut self, value: f64, min: f64, max: f64) -> &mut Self {
+        self.node.numeric_value = Some(value);
+        self.node.min_numeric_value = Some(min);
+        self.node.max_numeric_value = Some(max);
+        self
+    }
+
+    /// Sets the state description announced in addition to the label.
+    pub fn state_description(&mut self, description: impl Into<String>) -> &mut Self {
+        self.node.state_description = Some(description.into());
+        self
+    }
+
+    /// Sets the toggled/checked state of this node.
+    pub fn toggled(&mut self, toggled: Toggled) -> &mut Self {
+        self.node.toggled = Some(toggled);
+        self
+    }
+
+    /// Sets the heading level (1-based) of this node.
+    pub fn heading_level(&mut self, level: u32) -> &mut Self {
+        self.node.heading_level = Some(level);
+        self
+    }
+
+    /// Sets the live region politeness of this node.
+    pub fn live(&mut self, live: accesskit::Live) -> &mut Self {
+        self.node.live = Some(live);
+        self
+    }
+
+    /// Marks whether this node can receive focus.
+    pub fn focusable(&mut self, focusable: bool) -> &mut Self {
+        self.node.focusable = focusable;
+        self
+    }
+
+    /// Marks whether this node is disabled.
+    pub fn disabled(&mut self, disabled: bool) -> &mut Self {
+        self.node.disabled = disabled;
+        self
+    }
+
+    /// Marks whether this node is hidden from accessibility.
+    pub fn hidden(&mut self, hidden: bool) -> &mut Self {
+        self.node.hidden = hidden;
+        self
+    }
+
+    /// Sets a testing tag, mapped to the accessibility key.
+    pub fn test_tag(&mut self, tag: impl Into<String>) -> &mut Self {
+        self.node.key = Some(tag.into());
+        self
+    }
+
+    /// Clears descendant semantics so only this node is exposed.
+    pub fn clear_descendants(&mut self) -> &mut Self {
+        self.node.merge_descendants = false;
+        self
+    }
+
+    /// Exposes [`Action::Click`] and runs `on_click` when it is requested.
+    pub fn on_click(&mut self, on_click: impl Into<Callback>) -> &mut Self {
+        self.on_action(Action::Click, on_click)
+    }
+
+    /// Exposes `action` and runs `handler` when it is requested.
+    ///
+    /// Handlers do not run while the node is disabled.
+    pub fn on_action(&mut self, action: Action, handler: impl Into<Callback>) -> &mut Self {
+        if !self.node.actions.contains(&action) {
+            self.node.actions.push(action);
+        }
+        self.handlers.retain(|(existing, _)| *existing != action);
+        self.handlers.push((action, handler.into()));
+        self
+    }
+}
+
+/// Populates the accessibility node of the current component.
+///
+/// This is the declarative alternative to building an [`AccessibilityNode`]
+/// and an [`AccessibilityActionHandler`] by hand. Semantics already attached
+/// through modifiers are kept and refined by `build`; actions that `build`
+/// does not handle still reach the previous handler.
+///
+/// # Examples
+///
+/// ```
+/// use tessera_ui::{accesskit::Role, semantics, tessera};
+///
+/// #[tessera]
+/// fn send_button() {
+///     semantics(|s| {
+///         s.role(Role::Button);
+///         s.label("Send");
+///         s.on_click(|| {});
+///     });
+/// }
+/// ```
+///
+/// # Panics
+///
+/// This function must be called during a component's build phase.
+pub fn semantics<F>(build: F)
+where
+    F: FnOnce(&mut SemanticsScope),
+{
+    ensure_build_phase();
+
+    let existing = TesseraRuntime::with_mut(|runtime| {
+        let mut existing = None;
+        runtime.update_current_accessibility(|node, _| existing = node.clone());
+        existing
+    });
+    let mut scope = SemanticsScope::new(existing.unwrap_or_default());
+    build(&mut scope);
+
+    let SemanticsScope { node, handlers } = scope;
+    let disabled = node.disabled;
+    TesseraRuntime::with_mut(|runtime| {
+        runtime.update_current_accessibility(move |accessibility, action_handler| {
+            *accessibility = Some(node);
+            if handlers.is_empty() {
+                return;
+            }
+            let fallback = action_handler.take();
+            *action_handler = Some(Box::new(move |action| {
+                if let Some((_, handler)) = handlers.iter().find(|(kind, _)| *kind == action) {
+                    if !disabled {
+                        handler.call();
+                    }
+                } else if let Some(fallback) = &fallback {
+                    fallback(action);
+                }
+            }));
+        });
+    });
+}
@@ -0,0 +1,179 @@
+//! Live region announcements
+//!
+//! This module keeps the latest transient messages and exposes them to
+//! assistive technologies through two live regions attached to the root of the
+//! AccessKit tree.
+
+use std::cell::RefCell;
+
+use accesskit::{Live, Node, NodeId as AccessKitNodeId, Role};
+
+use crate::runtime::schedule_runtime_redraw;
+
+/// Reserved AccessKit id of the polite live region.
+const POLITE_REGION_ID: u64 = u64::MAX - 1;
+/// Reserved AccessKit id of the assertive live region.
+const ASSERTIVE_REGION_ID: u64 = u64::MAX - 2;
+/// Base of the AccessKit ids used for announcement nodes.
+///
+/// Component nodes use small 1-based arena indices, so ids above this base
+/// never collide with them.
+const ANNOUNCEMENT_ID_BASE: u64 = 1 << 62;
+
+/// How urgently an announcement is delivered to the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Politeness {
+    /// Announced once the screen reader finishes its current speech.
+    #[default]
+    Polite,
+    /// Interrupts the current speech, for errors and time-critical updates.
+    Assertive,
+}
+
+struct Announcement {
+    serial: u64,
+    message: String,
+}
+
+#[derive(Default)]
+struct AnnouncerState {
+    next_serial: u64,
+    polite: Option<Announcement>,
+    assertive: Option<Announcement>,
+}
+
+thread_local! {
+    static ANNOUNCER_STATE: RefCell<AnnouncerState> = RefCell::new(AnnouncerState::default());
+}
+
+fn with_state<R>(f: impl FnOnce(&mut AnnouncerState) -> R) -> R {
+    ANNOUNCER_STATE.with(|state| f(&mut state.borrow_mut()))
+}
+
+/// Announces a transient message to screen reader users.
+///
+/// The message replaces the previous announcement of the same politeness and
+/// is spoken even when it repeats the previous text. Empty messages are
+/// ignored.
+///
+/// # Examples
+///
+/// ```
+/// use tessera_ui::accessibility::{Politeness, announce};
+///
+/// announce("Item deleted", Politeness::Polite);
+/// ```
+pub fn announce(message: impl Into<String>, politeness: Politeness) {
+    let message = message.into();
+    if message.is_empty() {
+        return;
+    }
+
+    with_state(|state| {
+        let serial = state.next_serial;
+        state.next_serial = state.next_serial.wrapping_add(1);
+        let announcement = Some(Announcement { serial, message });
+        match politeness {
+            Politeness::Polite => state.polite = announcement,
+            Politeness::Assertive => state.assertive = announcement,
+        }
+    });
+    schedule_runtime_redraw();
+}
+
+/// Clears every pending announcement.
+pub fn clear_announcements() {
+    with_state(|state| {
+        state.polite = None;
+        state.assertive = None;
+    });
+}
+
+/// Appends the live region nodes to `nodes` and returns their ids.
+///
+/// The regions are always present so assistive technologies observe the
+/// insertion of each announcement rather than the region itself.
+pub(crate) fn append_live_regions(
+    nodes: &mut Vec<(AccessKitNodeId, Node)>,
+) -> [AccessKitNodeId; 2] {
+    with_state(|state| {
+        [
+            append_region(nodes, POLITE_REGION_ID, Live::Polite, &state.polite),
+            append_region(
+                nodes,
+                ASSERTIVE_REGION_ID,
+                Live::Assertive,
+                &state.assertive,
+            ),
+        ]
+    })
+}
+
+fn append_region(
+    nodes: &mut Vec<(AccessKitNodeId, Node)>,
+    region_id: u64,
+    live: Live,
+    announcement: &Option<Announcement>,
+) -> AccessKitNodeId {
+    let region_id = AccessKitNodeId(region_id);
+    let mut region = Node::new(match live {
+        Live::Assertive => Role::Alert,
+        _ => Role::Status,
+    });
+    region.set_live(live);
+
+    if let Some(announcement) = announcement {
+        let announcement_id = AccessKitNodeId(ANNOUNCEMENT_ID_BASE + announcement.serial);
+        let mut node = Node::new(Role::Label);
+        node.set_label(announcement.message.clone());
+        region.push_child(announcement_id);
+        nodes.push((announcement_id, node));
+    }
+
+    nodes.push((region_id, region));
+    region_id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(nodes: &[(AccessKitNodeId, Node)]) -> Vec<String> {
+        nodes
+            .iter()
+            .filter_map(|(_, node)| node.label().map(str::to_string))
+            .collect()
+    }
+
+    #[test]
+    fn announcements_replace_previous_message_with_fresh_node() {
+        clear_announcements();
+        announce("Item deleted", Politeness::Polite);
+        let mut first = Vec::new();
+        append_live_regions(&mut first);
+
+        announce("Item deleted", Politeness::Polite);
+        announce("Upload failed", Politeness::Assertive);
+        let mut second = Vec::new();
+        let regions = append_live_regions(&mut second);
+
+        assert_eq!(labels(&first), vec!["Item deleted".to_string()]);
+        assert_eq!(
+            labels(&second),
+            vec!["Item deleted".to_string(), "Upload failed".to_string()]
+        );
+        assert_eq!(
+            regions,
+            [
+                AccessKitNodeId(POLITE_REGION_ID),
+                AccessKitNodeId(ASSERTIVE_REGION_ID)
+            ]
+        );
+        assert_ne!(first[0].0, second[0].0);
+
+        clear_announcements();
+        let mut cleared = Vec::new();
+        append_live_regions(&mut cleared);
+        assert!(labels(&cleared).is_empty());
+    }
+}
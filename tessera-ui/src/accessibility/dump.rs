@@ -0,0 +1,287 @@
+//! Accessibility tree dumps
+//!
+//! This module snapshots the AccessKit tree of the current frame into plain
+//! data and validates it, so accessibility regressions can be caught by tests.
+
+use std::fmt;
+
+use accesskit::{Action, Node, NodeId as AccessKitNodeId, Role, TreeUpdate};
+use rustc_hash::FxHashMap as HashMap;
+
+use crate::runtime::TesseraRuntime;
+
+/// Actions recorded in a dump, in the order they are listed.
+const DUMPED_ACTIONS: [Action; 9] = [
+    Action::Click,
+    Action::Focus,
+    Action::SetValue,
+    Action::Increment,
+    Action::Decrement,
+    Action::SetTextSelection,
+    Action::ScrollIntoView,
+    Action::Expand,
+    Action::Collapse,
+];
+
+/// Actions that make a node interactive.
+const INTERACTIVE_ACTIONS: [Action; 5] = [
+    Action::Click,
+    Action::Focus,
+    Action::SetValue,
+    Action::Increment,
+    Action::Decrement,
+];
+
+/// A snapshot of the AccessKit tree built for the current frame.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AccessibilityTreeDump {
+    /// The root window node.
+    pub root: AccessibilityNodeDump,
+    /// Id of the node reported as focused.
+    pub focus: u64,
+}
+
+/// A single node of an [`AccessibilityTreeDump`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AccessibilityNodeDump {
+    /// The AccessKit node id.
+    pub id: u64,
+    /// The debug name of the AccessKit role, e.g. `Button`.
+    pub role: String,
+    /// The accessible name.
+    pub label: Option<String>,
+    /// The accessible description.
+    pub description: Option<String>,
+    /// The value text.
+    pub value: Option<String>,
+    /// Supported actions, by debug name.
+    pub actions: Vec<String>,
+    /// Whether the node is disabled.
+    pub disabled: bool,
+    /// Bounds as `[x0, y0, x1, y1]` in physical pixels.
+    pub bounds: Option<[f64; 4]>,
+    /// Child nodes in tree order.
+    pub children: Vec<AccessibilityNodeDump>,
+}
+
+/// The kind of problem reported by [`AccessibilityTreeDump::validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum AccessibilityIssueKind {
+    /// An interactive node has no name, neither its own nor from its content.
+    UnlabeledInteractiveNode,
+}
+
+/// A problem found in an accessibility tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AccessibilityIssue {
+    /// The kind of problem.
+    pub kind: AccessibilityIssueKind,
+    /// The id of the offending node.
+    pub node_id: u64,
+    /// The role of the offending node.
+    pub role: String,
+}
+
+impl fmt::Display for AccessibilityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            AccessibilityIssueKind::UnlabeledInteractiveNode => write!(
+                f,
+                "interactive {} node {} has no accessible label",
+                self.role, self.node_id
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AccessibilityIssue {}
+
+impl AccessibilityTreeDump {
+    /// Returns every node in depth-first order, starting with the root.
+    pub fn nodes(&self) -> Vec<&AccessibilityNodeDump> {
+        let mut nodes = Vec::new();
+        self.root.collect(&mut nodes);
+        nodes
+    }
+
+    /// Returns the first node with the given label.
+    pub fn find_by_label(&self, label: &str) -> Option<&AccessibilityNodeDump> {
+        self.nodes()
+            .into_iter()
+            .find(|node| node.label.as_deref() == Some(label))
+    }
+
+    /// Flags interactive nodes that screen readers cannot name.
+    ///
+    /// A node is interactive when it supports clicking, focusing or changing
+    /// its value. It is named when it or one of its descendants carries a
+    /// label or value.
+    pub fn validate(&self) -> Vec<AccessibilityIssue> {
+        self.nodes()
+            .into_iter()
+            .filter(|node| node.is_interactive() && !node.has_name())
+            .map(|node| AccessibilityIssue {
+                kind: AccessibilityIssueKind::UnlabeledInteractiveNode,
+                node_id: node.id,
+                role: node.role.clone(),
+            })
+            .collect()
+    }
+}
+
+impl AccessibilityNodeDump {
+    fn collect<'a>(&'a self, nodes: &mut Vec<&'a AccessibilityNodeDump>) {
+        nodes.push(self);
+        for child in &self.children {
+            child.collect(nodes);
+        }
+    }
+
+    fn is_interactive(&self) -> bool {
+        !self.disabled
+            && INTERACTIVE_ACTIONS
+                .iter()
+                .any(|action| self.actions.contains(&format!("{action:?}")))
+    }
+
+    fn has_name(&self) -> bool {
+        let named = |text: &Option<String>| text.as_deref().is_some_and(|text| !text.is_empty());
+        named(&self.label) || named(&self.value) || self.children.iter().any(Self::has_name)
+    }
+}
+
+/// Dumps the accessibility tree of the most recent frame.
+///
+/// Returns `None` when no frame has been built yet.
+///
+/// # Examples
+///
+/// ```
+/// let issues = tessera_ui::accessibility::dump_tree()
+///     .map(|tree| tree.validate())
+///     .unwrap_or_default();
+/// assert!(issues.is_empty());
+/// ```
+pub fn dump_tree() -> Option<AccessibilityTreeDump> {
+    let update = TesseraRuntime::with(|runtime| {
+        let tree = runtime.component_tree.tree();
+        let root_node_id =
+            tree.get_node_id_at(std::num::NonZero::new(1).expect("root index is non-zero"))?;
+        super::build_tree_update(
+            tree,
+            runtime.component_tree.metadatas(),
+            root_node_id,
+            runtime
+                .component_tree
+                .focus_owner()
+                .active_component_node_id(),
+            None,
+        )
+    })?;
+    dump_tree_update(&update)
+}
+
+pub(crate) fn dump_tree_update(update: &TreeUpdate) -> Option<AccessibilityTreeDump> {
+    let root_id = update.tree.as_ref()?.root;
+    let nodes: HashMap<AccessKitNodeId, &Node> =
+        update.nodes.iter().map(|(id, node)| (*id, node)).collect();
+    Some(AccessibilityTreeDump {
+        root: dump_node(root_id, &nodes)?,
+        focus: update.focus.0,
+    })
+}
+
+fn dump_node(
+    id: AccessKitNodeId,
+    nodes: &HashMap<AccessKitNodeId, &Node>,
+) -> Option<AccessibilityNodeDump> {
+    let node = nodes.get(&id)?;
+    let role: Role = node.role();
+    Some(AccessibilityNodeDump {
+        id: id.0,
+        role: format!("{role:?}"),
+        label: node.label().map(str::to_string),
+        description: node.description().map(str::to_string),
+        value: node.value().map(str::to_string),
+        actions: DUMPED_ACTIONS
+            .iter()
+            .filter(|action| node.supports_action(**action))
+            .map(|action| format!("{action:?}"))
+            .collect(),
+        disabled: node.is_disabled(),
+        bounds: node
+            .bounds()
+            .map(|bounds| [bounds.x0, bounds.y0, bounds.x1, bounds.y1]),
+        children: node
+            .children()
+            .iter()
+            .filter_map(|child| dump_node(*child, nodes))
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use accesskit::{Tree, TreeId};
+
+    use super::*;
+
+    fn update(nodes: Vec<(AccessKitNodeId, Node)>) -> TreeUpdate {
+        TreeUpdate {
+            nodes,
+            tree: Some(Tree::new(AccessKitNodeId(1))),
+            tree_id: TreeId::ROOT,
+            focus: AccessKitNodeId(1),
+        }
+    }
+
+    #[test]
+    fn validate_flags_only_unnamed_interactive_nodes() {
+        let mut root = Node::new(Role::Window);
+        root.set_children(vec![
+            AccessKitNodeId(2),
+            AccessKitNodeId(3),
+            AccessKitNodeId(5),
+        ]);
+
+        let mut labeled = Node::new(Role::Button);
+        labeled.set_label("Send");
+        labeled.add_action(Action::Click);
+
+        let mut named_by_content = Node::new(Role::Button);
+        named_by_content.add_action(Action::Click);
+        named_by_content.push_child(AccessKitNodeId(4));
+        let mut content = Node::new(Role::Label);
+        content.set_label("Cancel");
+
+        let mut unlabeled = Node::new(Role::Button);
+        unlabeled.add_action(Action::Click);
+
+        let dump = dump_tree_update(&update(vec![
+            (AccessKitNodeId(1), root),
+            (AccessKitNodeId(2), labeled),
+            (AccessKitNodeId(3), named_by_content),
+            (AccessKitNodeId(4), content),
+            (AccessKitNodeId(5), unlabeled),
+        ]))
+        .expect("dump must resolve the root");
+
+        assert_eq!(dump.nodes().len(), 5);
+        assert_eq!(
+            dump.find_by_label("Send").map(|node| node.actions.clone()),
+            Some(vec!["Click".to_string()])
+        );
+        assert_eq!(
+            dump.validate(),
+            vec![AccessibilityIssue {
+                kind: AccessibilityIssueKind::UnlabeledInteractiveNode,
+                node_id: 5,
+                role: "Button".to_string(),
+            }]
+        );
+    }
+}
@@ -3,14 +3,21 @@
 //! This module contains the logic to build AccessKit TreeUpdates from Tessera's
 //! component tree.
 
+use std::cell::RefCell;
+
 use accesskit::{Node, NodeId as AccessKitNodeId, Rect, Tree, TreeId, TreeUpdate};
 use indextree::NodeId as ComponentNodeId;
+use unicode_segmentation::UnicodeSegmentation;
 
 use crate::{
-    accessibility::AccessibilityId,
-    component_tree::{ComponentNodeMetaDatas, ComponentNodeTree, ComputedData},
+    accessibility::{
+        AccessibilityActionData, AccessibilityId, AccessibilityNode, announcer::append_live_regions,
+    },
+    component_tree::{
+        ComponentNodeMetaDatas, ComponentNodeTree, ComputedData, direct_layout_children,
+    },
     focus::{FocusOwner, bind_focus_owner},
-    px::PxPosition,
+    px::{Px, PxPosition},
 };
 
 /// Builds an AccessKit TreeUpdate from the component tree.
@@ -19,13 +26,15 @@ use crate::{
 /// 1. Traverses the component tree starting from the root
 /// 2. Collects all nodes that have accessibility metadata
 /// 3. Builds AccessKit nodes with proper parent-child relationships
-/// 4. Determines the current focus
+/// 4. Determines the current focus, preferring the node that owns keyboard
+///    focus so programmatic focus changes reach assistive technologies
 ///
 /// # Arguments
 ///
 /// * `tree` - The component tree structure (indextree::Arena)
 /// * `metadatas` - Component metadata including accessibility information
 /// * `root_node_id` - The root node of the component tree
+/// * `focused_node_id` - The component node that currently owns keyboard focus
 /// * `root_label` - Optional label used when synthesizing a root window node
 ///
 /// # Returns
@@ -36,6 +45,7 @@ pub fn build_tree_update(
     tree: &ComponentNodeTree,
     metadatas: &ComponentNodeMetaDatas,
     root_node_id: ComponentNodeId,
+    focused_node_id: Option<ComponentNodeId>,
     root_label: Option<&str>,
 ) -> Option<TreeUpdate> {
     let mut nodes = Vec::new();
@@ -60,6 +70,28 @@ pub fn build_tree_update(
         return None;
     }
 
+    // Live regions hang off the root so announcements work on every screen
+    let region_ids = append_live_regions(&mut nodes);
+    let root_id = root_accesskit_id.to_accesskit_id();
+    if let Some((_, root)) = nodes.iter_mut().find(|(node_id, _)| *node_id == root_id) {
+        for region_id in region_ids {
+            root.push_child(region_id);
+        }
+    }
+
+    // Keyboard focus wins over the `focused` flag so both stay in sync
+    if let Some(focused) = focused_node_id.and_then(|node_id| {
+        node_id.ancestors(tree).find_map(|candidate| {
+            let id = AccessibilityId::from_component_node_id(candidate).to_accesskit_id();
+            nodes
+                .iter()
+                .any(|(node_id, _)| *node_id == id)
+                .then_some(id)
+        })
+    }) {
+        focus = Some(focused);
+    }
+
     // Create the tree structure
     let tree_struct = Tree::new(root_accesskit_id.to_accesskit_id());
 
@@ -91,8 +123,10 @@ fn traverse_and_collect(
     };
 
     let accessibility_node = metadata.accessibility.clone();
-    let abs_position = metadata.abs_position;
-    let computed_data = metadata.computed_data;
+    let (abs_position, computed_data) = match (metadata.abs_position, metadata.computed_data) {
+        (Some(position), Some(size)) => (Some(position), Some(size)),
+        _ => layout_children_geometry(tree, metadatas, node_id),
+    };
 
     let mut has_accessible_descendants = false;
 
@@ -161,6 +195,41 @@ fn traverse_and_collect(
             node.set_level(level as usize);
         }
 
+        // Editable and selectable text is exposed through a single text run
+        let mut text_run = None;
+        if let Some(value) = accessibility_node.value.as_deref()
+            && (accessibility_node.is_editable_text || accessibility_node.text_selection.is_some())
+        {
+            let run_id = text_run_id(accesskit_id);
+            let mut run = Node::new(accesskit::Role::TextRun);
+            run.set_value(value.to_string());
+            run.set_character_lengths(
+                value
+                    .graphemes(true)
+                    .map(|grapheme| grapheme.len().min(u8::MAX as usize) as u8)
+                    .collect::<Vec<_>>(),
+            );
+            if let Some(selection) = accessibility_node.text_selection.clone() {
+                node.set_text_selection(accesskit::TextSelection {
+                    anchor: accesskit::TextPosition {
+                        node: run_id,
+                        character_index: byte_to_character_index(value, selection.start),
+                    },
+                    focus: accesskit::TextPosition {
+                        node: run_id,
+                        character_index: byte_to_character_index(value, selection.end),
+                    },
+                });
+            }
+            node.add_action(accesskit::Action::SetTextSelection);
+            accessible_children.push(run_id);
+            text_run = Some((run_id, run));
+        }
+
+        if accessibility_node.is_editable_text {
+            node.add_action(accesskit::Action::SetValue);
+        }
+
         // Set value
         if let Some(value) = accessibility_node.value {
             node.set_value(value);
@@ -218,6 +287,7 @@ fn traverse_and_collect(
         // Set focusable
         if accessibility_node.focusable {
             node.add_action(accesskit::Action::Focus);
+            node.add_action(accesskit::Action::ScrollIntoView);
         }
 
         // Set focused (and remember for TreeUpdate)
@@ -259,6 +329,13 @@ fn traverse_and_collect(
             node.set_bounds(bounds);
         }
 
+        if let Some((run_id, mut run)) = text_run {
+            if let Some(bounds) = bounds {
+                run.set_bounds(bounds);
+            }
+            nodes.push((run_id, run));
+        }
+
         // Add to collection
         nodes.push((accesskit_id.to_accesskit_id(), node));
 
@@ -290,6 +367,70 @@ fn traverse_and_collect(
     }
 }
 
+/// Resolves the geometry of a composition node, which is never measured, as
+/// the union of its direct layout children.
+fn layout_children_geometry(
+    tree: &ComponentNodeTree,
+    metadatas: &ComponentNodeMetaDatas,
+    node_id: ComponentNodeId,
+) -> (Option<PxPosition>, Option<ComputedData>) {
+    let mut bounds: Option<(Px, Px, Px, Px)> = None;
+    for child_id in direct_layout_children(node_id, tree) {
+        let Some(metadata) = metadatas.get(&child_id) else {
+            continue;
+        };
+        let (Some(position), Some(size)) = (metadata.abs_position, metadata.computed_data) else {
+            continue;
+        };
+        let (x1, y1) = (position.x + size.width, position.y + size.height);
+        bounds = Some(match bounds {
+            Some((min_x, min_y, max_x, max_y)) => (
+                min_x.min(position.x),
+                min_y.min(position.y),
+                max_x.max(x1),
+                max_y.max(y1),
+            ),
+            None => (position.x, position.y, x1, y1),
+        });
+    }
+
+    match bounds {
+        Some((min_x, min_y, max_x, max_y)) => (
+            Some(PxPosition::new(min_x, min_y)),
+            Some(ComputedData {
+                width: max_x - min_x,
+                height: max_y - min_y,
+            }),
+        ),
+        None => (None, None),
+    }
+}
+
+/// Base of the AccessKit ids used for text runs.
+///
+/// Component nodes use small 1-based arena indices, so ids above this base
+/// never collide with them.
+const TEXT_RUN_ID_BASE: u64 = 1 << 61;
+
+fn text_run_id(accesskit_id: AccessibilityId) -> AccessKitNodeId {
+    AccessKitNodeId(TEXT_RUN_ID_BASE + accesskit_id.0)
+}
+
+fn byte_to_character_index(value: &str, byte_offset: usize) -> usize {
+    value
+        .grapheme_indices(true)
+        .take_while(|(index, _)| *index < byte_offset)
+        .count()
+}
+
+fn character_to_byte_index(value: &str, character_index: usize) -> usize {
+    value
+        .grapheme_indices(true)
+        .nth(character_index)
+        .map(|(index, _)| index)
+        .unwrap_or(value.len())
+}
+
 fn rect_from_geometry(
     abs_position: Option<PxPosition>,
     computed_data: Option<ComputedData>,
@@ -326,7 +467,10 @@ fn rect_from_geometry_with_padding(
 /// This function:
 /// 1. Converts the AccessKit NodeId back to a component NodeId
 /// 2. Looks up the component's metadata
-/// 3. Calls the component's accessibility_action_handler if present
+/// 3. Moves keyboard focus to the target for `Action::Focus` and reveals it for
+///    `Action::ScrollIntoView`
+/// 4. Calls the component's accessibility_action_handler if present, exposing
+///    the action data through [`current_action_data`]
 ///
 /// # Arguments
 ///
@@ -355,16 +499,89 @@ pub fn dispatch_action(
     let index = std::num::NonZero::new(accessibility_id.0 as usize);
     let component_node_id = index.and_then(|idx| tree.get_node_id_at(idx));
 
+    let mut handled = false;
+    if let Some(node_id) = component_node_id {
+        match action_request.action {
+            accesskit::Action::Focus => {
+                handled = focus_owner.request_accessibility_focus(node_id, tree);
+            }
+            accesskit::Action::ScrollIntoView => {
+                handled = focus_owner.request_reveal_of_component_node(node_id, tree);
+            }
+            _ => {}
+        }
+    }
+
     // Look up the component's metadata and call handler
     if let Some(node_id) = component_node_id
         && let Some(metadata) = metadatas.get(&node_id)
         && let Some(handler) = &metadata.accessibility_action_handler
     {
+        let data = action_request.data.and_then(|data| {
+            resolve_action_data(
+                data,
+                AccessibilityId::from_component_node_id(node_id),
+                metadata.accessibility.as_ref(),
+            )
+        });
+
         // Call the handler
         let _focus_owner_guard = bind_focus_owner(focus_owner);
+        let _action_data_guard = bind_action_data(data);
         handler(action_request.action);
         return true;
     }
 
-    false
+    handled
+}
+
+thread_local! {
+    static CURRENT_ACTION_DATA: RefCell<Option<AccessibilityActionData>> =
+        const { RefCell::new(None) };
+}
+
+struct ActionDataGuard;
+
+impl Drop for ActionDataGuard {
+    fn drop(&mut self) {
+        CURRENT_ACTION_DATA.with(|data| data.borrow_mut().take());
+    }
+}
+
+fn bind_action_data(data: Option<AccessibilityActionData>) -> ActionDataGuard {
+    CURRENT_ACTION_DATA.with(|current| *current.borrow_mut() = data);
+    ActionDataGuard
+}
+
+/// Returns the data attached to the accessibility action being dispatched.
+///
+/// Only meaningful inside an [`crate::AccessibilityActionHandler`]; returns
+/// `None` elsewhere and for actions without data.
+pub fn current_action_data() -> Option<AccessibilityActionData> {
+    CURRENT_ACTION_DATA.with(|data| data.borrow().clone())
+}
+
+fn resolve_action_data(
+    data: accesskit::ActionData,
+    accesskit_id: AccessibilityId,
+    accessibility: Option<&AccessibilityNode>,
+) -> Option<AccessibilityActionData> {
+    match data {
+        accesskit::ActionData::Value(value) => Some(AccessibilityActionData::Value(value.into())),
+        accesskit::ActionData::NumericValue(value) => {
+            Some(AccessibilityActionData::NumericValue(value))
+        }
+        accesskit::ActionData::SetTextSelection(selection) => {
+            let run_id = text_run_id(accesskit_id);
+            if selection.anchor.node != run_id || selection.focus.node != run_id {
+                return None;
+            }
+            let value = accessibility?.value.as_deref().unwrap_or_default();
+            Some(AccessibilityActionData::TextSelection(
+                character_to_byte_index(value, selection.anchor.character_index)
+                    ..character_to_byte_index(value, selection.focus.character_index),
+            ))
+        }
+        _ => None,
+    }
 }
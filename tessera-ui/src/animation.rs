@@ -0,0 +1,637 @@
+//! Value animation driven by the frame clock.
+//!
+//! ## Usage
+//!
+//! Animate sizes, offsets, colors and opacity toward a target with springs,
+//! tweens or keyframes, and retarget them mid-flight without jumps.
+//!
+//! [`animate_as_state`] covers most cases: it returns the animated value for
+//! the current frame and keeps the component recomposing until the animation
+//! settles. Use [`Animatable`] directly to drive several values from one
+//! frame callback. Animations started through [`animate_as_state`] jump to
+//! their target when the user prefers reduced motion.
+
+use std::time::Duration;
+
+use crate::{
+    Color, Dp, FrameNanosControl, Px, PxPosition, PxSize, current_frame_nanos, receive_frame_nanos,
+    remember, system_preferences::system_preferences,
+};
+
+/// Longest step a spring integrates at once, in seconds.
+const MAX_SPRING_STEP: f32 = 1.0 / 240.0;
+/// Longest gap between two frames a spring integrates, in seconds, so a
+/// stalled frame does not fling the value.
+const MAX_SPRING_FRAME: f32 = 0.1;
+
+/// A value that can be animated.
+///
+/// Values are animated as up to four `f32` components.
+pub trait AnimationValue: Copy + PartialEq + Send + Sync + 'static {
+    /// Distance below which a component change is not visible. Springs settle
+    /// once every component is this close to its target.
+    const VISIBILITY_THRESHOLD: f32;
+
+    /// Returns the components of the value.
+    fn to_vector(self) -> [f32; 4];
+
+    /// Builds a value from its components.
+    fn from_vector(vector: [f32; 4]) -> Self;
+}
+
+impl AnimationValue for f32 {
+    const VISIBILITY_THRESHOLD: f32 = 0.01;
+
+    fn to_vector(self) -> [f32; 4] {
+        [self, 0.0, 0.0, 0.0]
+    }
+
+    fn from_vector(vector: [f32; 4]) -> Self {
+        vector[0]
+    }
+}
+
+impl AnimationValue for Dp {
+    const VISIBILITY_THRESHOLD: f32 = 0.1;
+
+    fn to_vector(self) -> [f32; 4] {
+        [self.0 as f32, 0.0, 0.0, 0.0]
+    }
+
+    fn from_vector(vector: [f32; 4]) -> Self {
+        Dp(vector[0] as f64)
+    }
+}
+
+impl AnimationValue for Px {
+    const VISIBILITY_THRESHOLD: f32 = 0.5;
+
+    fn to_vector(self) -> [f32; 4] {
+        [self.0 as f32, 0.0, 0.0, 0.0]
+    }
+
+    fn from_vector(vector: [f32; 4]) -> Self {
+        Px(vector[0].round() as i32)
+    }
+}
+
+impl AnimationValue for PxPosition {
+    const VISIBILITY_THRESHOLD: f32 = 0.5;
+
+    fn to_vector(self) -> [f32; 4] {
+        [self.x.0 as f32, self.y.0 as f32, 0.0, 0.0]
+    }
+
+    fn from_vector(vector: [f32; 4]) -> Self {
+        PxPosition::new(Px(vector[0].round() as i32), Px(vector[1].round() as i32))
+    }
+}
+
+impl AnimationValue for PxSize {
+    const VISIBILITY_THRESHOLD: f32 = 0.5;
+
+    fn to_vector(self) -> [f32; 4] {
+        [self.width.0 as f32, self.height.0 as f32, 0.0, 0.0]
+    }
+
+    fn from_vector(vector: [f32; 4]) -> Self {
+        PxSize::new(Px(vector[0].round() as i32), Px(vector[1].round() as i32))
+    }
+}
+
+impl AnimationValue for Color {
+    const VISIBILITY_THRESHOLD: f32 = 0.001;
+
+    fn to_vector(self) -> [f32; 4] {
+        [self.r, self.g, self.b, self.a]
+    }
+
+    fn from_vector(vector: [f32; 4]) -> Self {
+        Color::new(vector[0], vector[1], vector[2], vector[3])
+    }
+}
+
+/// Maps linear time progress to animation progress.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Easing {
+    /// Constant speed.
+    Linear,
+    /// Accelerates quickly and decelerates slowly. Suits most movement.
+    #[default]
+    FastOutSlowIn,
+    /// Decelerates to rest. Suits elements entering the screen.
+    LinearOutSlowIn,
+    /// Accelerates from rest. Suits elements leaving the screen.
+    FastOutLinearIn,
+    /// A CSS-style cubic Bézier curve through `(0, 0)`, `(x1, y1)`,
+    /// `(x2, y2)` and `(1, 1)`.
+    CubicBezier {
+        /// X of the first control point, in `0.0..=1.0`.
+        x1: f32,
+        /// Y of the first control point.
+        y1: f32,
+        /// X of the second control point, in `0.0..=1.0`.
+        x2: f32,
+        /// Y of the second control point.
+        y2: f32,
+    },
+}
+
+impl Easing {
+    /// Returns the eased progress for linear `progress` in `0.0..=1.0`.
+    pub fn transform(self, progress: f32) -> f32 {
+        let t = progress.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::FastOutSlowIn => cubic_bezier(0.4, 0.0, 0.2, 1.0, t),
+            Self::LinearOutSlowIn => cubic_bezier(0.0, 0.0, 0.2, 1.0, t),
+            Self::FastOutLinearIn => cubic_bezier(0.4, 0.0, 1.0, 1.0, t),
+            Self::CubicBezier { x1, y1, x2, y2 } => cubic_bezier(x1, y1, x2, y2, t),
+        }
+    }
+}
+
+/// Solves the curve for `x` and returns its `y`.
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, x: f32) -> f32 {
+    if x <= 0.0 || x >= 1.0 {
+        return x;
+    }
+    let bezier = |p1: f32, p2: f32, t: f32| {
+        let u = 1.0 - t;
+        3.0 * u * u * t * p1 + 3.0 * u * t * t * p2 + t * t * t
+    };
+    // Bisection converges for any monotonic x(t), which the clamped control
+    // points guarantee.
+    let (x1, x2) = (x1.clamp(0.0, 1.0), x2.clamp(0.0, 1.0));
+    let (mut low, mut high) = (0.0, 1.0);
+    let mut t = x;
+    for _ in 0..32 {
+        let estimate = bezier(x1, x2, t);
+        if (estimate - x).abs() < 1e-5 {
+            break;
+        }
+        if estimate < x {
+            low = t;
+        } else {
+            high = t;
+        }
+        t = (low + high) / 2.0;
+    }
+    bezier(y1, y2, t)
+}
+
+/// A physics-based animation that keeps its velocity when retargeted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpringSpec {
+    /// Oscillation damping. `1.0` settles without overshoot, lower values
+    /// bounce.
+    pub damping_ratio: f32,
+    /// Spring strength. Higher values settle faster.
+    pub stiffness: f32,
+}
+
+impl SpringSpec {
+    /// Settles without overshoot.
+    pub const DAMPING_RATIO_NO_BOUNCY: f32 = 1.0;
+    /// Overshoots slightly.
+    pub const DAMPING_RATIO_LOW_BOUNCY: f32 = 0.75;
+    /// Overshoots noticeably.
+    pub const DAMPING_RATIO_MEDIUM_BOUNCY: f32 = 0.5;
+    /// Settles quickly.
+    pub const STIFFNESS_HIGH: f32 = 10_000.0;
+    /// Default stiffness.
+    pub const STIFFNESS_MEDIUM: f32 = 1_500.0;
+    /// Settles gently.
+    pub const STIFFNESS_LOW: f32 = 200.0;
+
+    /// Creates a spring with the given damping ratio and stiffness.
+    pub fn new(damping_ratio: f32, stiffness: f32) -> Self {
+        Self {
+            damping_ratio,
+            stiffness,
+        }
+    }
+}
+
+impl Default for SpringSpec {
+    fn default() -> Self {
+        Self::new(Self::DAMPING_RATIO_NO_BOUNCY, Self::STIFFNESS_MEDIUM)
+    }
+}
+
+/// An animation over a fixed duration along an easing curve.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TweenSpec {
+    /// Length of the animation.
+    pub duration: Duration,
+    /// Time to wait before the animation starts moving.
+    pub delay: Duration,
+    /// Progress curve.
+    pub easing: Easing,
+}
+
+impl TweenSpec {
+    /// Creates a tween lasting `duration` with the default easing.
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            delay: Duration::ZERO,
+            easing: Easing::default(),
+        }
+    }
+
+    /// Sets the easing curve.
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Sets the start delay.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+impl Default for TweenSpec {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(300))
+    }
+}
+
+/// A value the animation passes through at a given time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Keyframe<T> {
+    /// Time of the keyframe from the start of the animation.
+    pub at: Duration,
+    /// Value at that time.
+    pub value: T,
+    /// Curve of the segment that starts at this keyframe.
+    pub easing: Easing,
+}
+
+/// An animation through intermediate values at given times.
+///
+/// The animation starts at the current value and ends at the target at
+/// `duration`; keyframes fill in the values between.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyframesSpec<T> {
+    /// Length of the animation.
+    pub duration: Duration,
+    /// Intermediate keyframes, ordered by time.
+    pub keyframes: Vec<Keyframe<T>>,
+}
+
+impl<T> KeyframesSpec<T> {
+    /// Creates a keyframes animation lasting `duration`.
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            keyframes: Vec::new(),
+        }
+    }
+
+    /// Adds a keyframe followed by a linear segment.
+    pub fn at(self, at: Duration, value: T) -> Self {
+        self.at_with(at, value, Easing::Linear)
+    }
+
+    /// Adds a keyframe followed by a segment along `easing`.
+    pub fn at_with(mut self, at: Duration, value: T, easing: Easing) -> Self {
+        let index = self.keyframes.partition_point(|keyframe| keyframe.at <= at);
+        self.keyframes.insert(index, Keyframe { at, value, easing });
+        self
+    }
+}
+
+/// How a value moves toward its target.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnimationSpec<T> {
+    /// Physics-based motion.
+    Spring(SpringSpec),
+    /// Fixed-duration motion along an easing curve.
+    Tween(TweenSpec),
+    /// Fixed-duration motion through intermediate values.
+    Keyframes(KeyframesSpec<T>),
+}
+
+impl<T> AnimationSpec<T> {
+    /// A spring with the default damping and stiffness.
+    pub fn spring() -> Self {
+        Self::Spring(SpringSpec::default())
+    }
+
+    /// A tween lasting `duration` with the default easing.
+    pub fn tween(duration: Duration) -> Self {
+        Self::Tween(TweenSpec::new(duration))
+    }
+}
+
+impl<T> Default for AnimationSpec<T> {
+    fn default() -> Self {
+        Self::spring()
+    }
+}
+
+impl<T> From<SpringSpec> for AnimationSpec<T> {
+    fn from(spec: SpringSpec) -> Self {
+        Self::Spring(spec)
+    }
+}
+
+impl<T> From<TweenSpec> for AnimationSpec<T> {
+    fn from(spec: TweenSpec) -> Self {
+        Self::Tween(spec)
+    }
+}
+
+impl<T> From<KeyframesSpec<T>> for AnimationSpec<T> {
+    fn from(spec: KeyframesSpec<T>) -> Self {
+        Self::Keyframes(spec)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Run<T> {
+    spec: AnimationSpec<T>,
+    start_value: T,
+    start_nanos: u64,
+    last_nanos: u64,
+}
+
+/// A value animating toward a target on the frame clock.
+///
+/// Call [`tick`](Self::tick) from a
+/// [`receive_frame_nanos`](crate::receive_frame_nanos) callback while
+/// [`is_running`](Self::is_running) is true. Retargeting a running animation
+/// starts from the current value; springs also keep the current velocity.
+#[derive(Debug, Clone)]
+pub struct Animatable<T: AnimationValue> {
+    value: T,
+    target: T,
+    velocity: [f32; 4],
+    run: Option<Run<T>>,
+}
+
+impl<T: AnimationValue> Animatable<T> {
+    /// Creates a value at rest.
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            target: value,
+            velocity: [0.0; 4],
+            run: None,
+        }
+    }
+
+    /// Returns the value for the last frame.
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    /// Returns the value the animation is heading to.
+    pub fn target(&self) -> T {
+        self.target
+    }
+
+    /// Returns whether the value is still moving.
+    pub fn is_running(&self) -> bool {
+        self.run.is_some()
+    }
+
+    /// Starts animating toward `target` at `frame_nanos`.
+    pub fn animate_to(&mut self, target: T, spec: impl Into<AnimationSpec<T>>, frame_nanos: u64) {
+        self.target = target;
+        let spec = spec.into();
+        if self.value == target
+            && self.velocity == [0.0; 4]
+            && !matches!(spec, AnimationSpec::Keyframes(_))
+        {
+            self.run = None;
+            return;
+        }
+        self.run = Some(Run {
+            spec,
+            start_value: self.value,
+            start_nanos: frame_nanos,
+            last_nanos: frame_nanos,
+        });
+    }
+
+    /// Jumps to `value` and stops any running animation.
+    pub fn snap_to(&mut self, value: T) {
+        self.value = value;
+        self.target = value;
+        self.velocity = [0.0; 4];
+        self.run = None;
+    }
+
+    /// Advances the animation to `frame_nanos`, returning whether it is still
+    /// running.
+    pub fn tick(&mut self, frame_nanos: u64) -> bool {
+        let Some(mut run) = self.run.take() else {
+            return false;
+        };
+        let dt = frame_nanos.saturating_sub(run.last_nanos) as f32 / 1e9;
+        let elapsed = Duration::from_nanos(frame_nanos.saturating_sub(run.start_nanos));
+        run.last_nanos = frame_nanos;
+
+        let target = self.target.to_vector();
+        let finished = match &run.spec {
+            AnimationSpec::Spring(spring) => {
+                let mut value = self.value.to_vector();
+                step_spring(&mut value, &mut self.velocity, target, *spring, dt);
+                let threshold = T::VISIBILITY_THRESHOLD;
+                let settled = (0..4).all(|i| {
+                    (value[i] - target[i]).abs() < threshold && self.velocity[i].abs() < threshold
+                });
+                self.value = T::from_vector(value);
+                settled
+            }
+            AnimationSpec::Tween(tween) => {
+                let moving = elapsed.saturating_sub(tween.delay);
+                let progress = fraction(moving, tween.duration);
+                let start = run.start_value.to_vector();
+                let next = lerp(start, target, tween.easing.transform(progress));
+                self.set_with_velocity(next, dt);
+                progress >= 1.0
+            }
+            AnimationSpec::Keyframes(keyframes) => {
+                let next = keyframes_value(keyframes, run.start_value.to_vector(), target, elapsed);
+                self.set_with_velocity(next, dt);
+                elapsed >= keyframes.duration
+            }
+        };
+        if finished {
+            self.snap_to(self.target);
+        } else {
+            self.run = Some(run);
+        }
+        !finished
+    }
+
+    fn set_with_velocity(&mut self, next: [f32; 4], dt: f32) {
+        let previous = self.value.to_vector();
+        if dt > 0.0 {
+            self.velocity = std::array::from_fn(|i| (next[i] - previous[i]) / dt);
+        }
+        self.value = T::from_vector(next);
+    }
+}
+
+fn fraction(elapsed: Duration, duration: Duration) -> f32 {
+    if duration.is_zero() {
+        return 1.0;
+    }
+    (elapsed.as_secs_f32() / duration.as_secs_f32()).clamp(0.0, 1.0)
+}
+
+fn lerp(start: [f32; 4], end: [f32; 4], t: f32) -> [f32; 4] {
+    std::array::from_fn(|i| start[i] + (end[i] - start[i]) * t)
+}
+
+/// Integrates a damped spring with semi-implicit Euler steps.
+fn step_spring(
+    value: &mut [f32; 4],
+    velocity: &mut [f32; 4],
+    target: [f32; 4],
+    spring: SpringSpec,
+    dt: f32,
+) {
+    let stiffness = spring.stiffness.max(0.0);
+    if stiffness == 0.0 {
+        *value = target;
+        *velocity = [0.0; 4];
+        return;
+    }
+    let damping = 2.0 * spring.damping_ratio.max(0.0) * stiffness.sqrt();
+    let mut remaining = dt.clamp(0.0, MAX_SPRING_FRAME);
+    while remaining > 0.0 {
+        let step = remaining.min(MAX_SPRING_STEP);
+        for ((value, velocity), target) in value.iter_mut().zip(velocity.iter_mut()).zip(target) {
+            let acceleration = -stiffness * (*value - target) - damping * *velocity;
+            *velocity += acceleration * step;
+            *value += *velocity * step;
+        }
+        remaining -= step;
+    }
+}
+
+fn keyframes_value<T: AnimationValue>(
+    spec: &KeyframesSpec<T>,
+    start: [f32; 4],
+    end: [f32; 4],
+    elapsed: Duration,
+) -> [f32; 4] {
+    let mut from = (Duration::ZERO, start, Easing::Linear);
+    for keyframe in &spec.keyframes {
+        if keyframe.at > elapsed {
+            let progress = fraction(elapsed - from.0, keyframe.at - from.0);
+            return lerp(
+                from.1,
+                keyframe.value.to_vector(),
+                from.2.transform(progress),
+            );
+        }
+        from = (keyframe.at, keyframe.value.to_vector(), keyframe.easing);
+    }
+    let remaining = spec.duration.saturating_sub(from.0);
+    let progress = fraction(elapsed.saturating_sub(from.0), remaining);
+    lerp(from.1, end, from.2.transform(progress))
+}
+
+/// Returns `target`, animated with a spring whenever it changes.
+///
+/// Must be called inside a component build.
+pub fn animate_as_state<T: AnimationValue>(target: T) -> T {
+    animate_as_state_with(target, AnimationSpec::spring())
+}
+
+/// Returns `target`, animated with `spec` whenever it changes.
+///
+/// The component recomposes every frame while the value moves. When the user
+/// prefers reduced motion the value jumps to its target.
+///
+/// Must be called inside a component build.
+pub fn animate_as_state_with<T: AnimationValue>(target: T, spec: impl Into<AnimationSpec<T>>) -> T {
+    let animatable = remember(|| Animatable::new(target));
+    if animatable.with(|animatable| animatable.target() != target) {
+        let reduce_motion = system_preferences().reduce_motion;
+        let spec = spec.into();
+        let frame_nanos = current_frame_nanos();
+        animatable.with_mut(|animatable| {
+            if reduce_motion {
+                animatable.snap_to(target);
+            } else {
+                animatable.animate_to(target, spec, frame_nanos);
+            }
+        });
+    }
+    if animatable.with(Animatable::is_running) {
+        receive_frame_nanos(move |frame_nanos| {
+            if animatable.with_mut(|animatable| animatable.tick(frame_nanos)) {
+                FrameNanosControl::Continue
+            } else {
+                FrameNanosControl::Stop
+            }
+        });
+    }
+    animatable.with(Animatable::value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME: u64 = 16_000_000;
+
+    #[test]
+    fn tween_reaches_target_at_its_duration() {
+        let mut value = Animatable::new(0.0_f32);
+        value.animate_to(
+            10.0,
+            TweenSpec::new(Duration::from_millis(100)).easing(Easing::Linear),
+            0,
+        );
+        assert!(value.tick(50_000_000));
+        assert_eq!(value.value(), 5.0);
+        assert!(!value.tick(100_000_000));
+        assert_eq!(value.value(), 10.0);
+    }
+
+    #[test]
+    fn retargeted_spring_keeps_its_value_and_velocity() {
+        let mut value = Animatable::new(0.0_f32);
+        value.animate_to(100.0, SpringSpec::default(), 0);
+        let mut now = 0;
+        for _ in 0..3 {
+            now += FRAME;
+            value.tick(now);
+        }
+        let midway = value.value();
+        assert!(midway > 0.0 && midway < 100.0);
+
+        value.animate_to(0.0, SpringSpec::default(), now);
+        now += 1_000;
+        value.tick(now);
+        // Still moving up on momentum right after the target flipped.
+        assert!(value.value() > midway);
+
+        while value.tick(now) {
+            now += FRAME;
+        }
+        assert_eq!(value.value(), 0.0);
+    }
+
+    #[test]
+    fn keyframes_pass_through_intermediate_values() {
+        let mut value = Animatable::new(0.0_f32);
+        let spec =
+            KeyframesSpec::new(Duration::from_millis(200)).at(Duration::from_millis(100), 50.0);
+        value.animate_to(10.0, spec, 0);
+        value.tick(50_000_000);
+        assert_eq!(value.value(), 25.0);
+        value.tick(150_000_000);
+        assert_eq!(value.value(), 30.0);
+    }
+}
@@ -0,0 +1,219 @@
+//! System back navigation.
+//!
+//! ## Usage
+//!
+//! Register a back handler in a component to react to the Android back button
+//! or gesture, for example to close a sheet or to confirm discarding unsaved
+//! changes. Desktop apps can opt into Escape and Alt+Left through
+//! [`WindowConfig::keyboard_back_navigation`](crate::renderer::WindowConfig).
+
+use std::{cell::RefCell, collections::BTreeMap};
+
+use winit::{
+    event::ElementState,
+    keyboard::{Key, ModifiersState, NamedKey},
+};
+
+use crate::{
+    Callback, KeyEvent,
+    runtime::{State, remember},
+};
+
+struct BackHandlerEntry {
+    anchor: State<u64>,
+    enabled: bool,
+    on_back: Callback,
+}
+
+#[derive(Default)]
+struct BackNavigationState {
+    next_order: u64,
+    /// Handlers by mount order; the most recently mounted one runs first.
+    handlers: BTreeMap<u64, BackHandlerEntry>,
+}
+
+thread_local! {
+    static BACK_NAVIGATION_STATE: RefCell<BackNavigationState> =
+        RefCell::new(BackNavigationState::default());
+}
+
+fn with_state<R>(f: impl FnOnce(&mut BackNavigationState) -> R) -> R {
+    BACK_NAVIGATION_STATE.with(|state| f(&mut state.borrow_mut()))
+}
+
+/// Intercepts back navigation while `enabled` is `true`.
+///
+/// Among the enabled handlers, the one mounted last runs, so a dialog shown
+/// over a screen takes precedence over the screen. The handler is removed
+/// when the calling component leaves the tree. When no handler is enabled,
+/// Android finishes the activity.
+///
+/// # Panics
+///
+/// Must be called during a component build.
+///
+/// # Examples
+///
+/// ```
+/// use tessera_ui::{back_navigation::back_handler, remember, tessera};
+///
+/// #[tessera]
+/// fn editor() {
+///     let dirty = remember(|| true);
+///     back_handler(dirty.get(), move || {
+///         // Ask for confirmation instead of leaving the screen.
+///         dirty.set(false);
+///     });
+/// }
+/// ```
+pub fn back_handler<F>(enabled: bool, on_back: F)
+where
+    F: Fn() + Send + Sync + 'static,
+{
+    let anchor = remember(|| {
+        with_state(|state| {
+            state.next_order += 1;
+            state.next_order
+        })
+    });
+    let on_back = Callback::new(on_back);
+    let order = anchor.get();
+    with_state(|state| {
+        state.handlers.insert(
+            order,
+            BackHandlerEntry {
+                anchor,
+                enabled,
+                on_back,
+            },
+        );
+    });
+}
+
+/// Runs the most recently mounted enabled back handler.
+///
+/// Returns `false` when no handler is enabled. Use this for in-app back
+/// buttons so they behave like the system back action.
+pub fn dispatch_back() -> bool {
+    let on_back = with_state(|state| {
+        state
+            .handlers
+            .retain(|_, handler| handler.anchor.is_alive());
+        state
+            .handlers
+            .values()
+            .rev()
+            .find(|handler| handler.enabled)
+            .map(|handler| handler.on_back)
+    });
+    match on_back {
+        Some(on_back) => {
+            on_back.call();
+            true
+        }
+        None => false,
+    }
+}
+
+/// How a key event requests back navigation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum BackKey {
+    /// The platform back button or gesture.
+    System,
+    /// Escape or Alt+Left on a desktop keyboard.
+    Keyboard,
+}
+
+pub(crate) fn back_key(
+    event: &KeyEvent,
+    modifiers: ModifiersState,
+    keyboard_back_navigation: bool,
+) -> Option<BackKey> {
+    match &event.logical_key {
+        Key::Named(NamedKey::BrowserBack | NamedKey::GoBack) => Some(BackKey::System),
+        Key::Named(NamedKey::Escape) if keyboard_back_navigation && modifiers.is_empty() => {
+            Some(BackKey::Keyboard)
+        }
+        Key::Named(NamedKey::ArrowLeft)
+            if keyboard_back_navigation && modifiers == ModifiersState::ALT =>
+        {
+            Some(BackKey::Keyboard)
+        }
+        _ => None,
+    }
+}
+
+pub(crate) fn is_back_press(event: &KeyEvent) -> bool {
+    event.state == ElementState::Pressed && !event.repeat
+}
+
+/// Finishes the activity, the default Android reaction to an unhandled back.
+#[cfg(target_os = "android")]
+pub(crate) fn finish_activity(android_app: &winit::platform::android::activity::AndroidApp) {
+    use jni::{JavaVM, objects::JObject};
+
+    // SAFETY: The pointers come from the running `AndroidApp` and stay valid
+    // for the lifetime of the activity.
+    let result = unsafe { JavaVM::from_raw(android_app.vm_as_ptr().cast()) }.and_then(|vm| {
+        let mut env = vm.attach_current_thread()?;
+        let activity = unsafe { JObject::from_raw(android_app.activity_as_ptr().cast()) };
+        env.call_method(&activity, "finish", "()V", &[])?;
+        Ok(())
+    });
+    if let Err(err) = result {
+        tracing::warn!("Failed to finish activity after back navigation: {err}");
+        return;
+    }
+    #[cfg(feature = "saveable")]
+    crate::saveable::discard_app_storage(android_app);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn platform_back_keys_always_request_back() {
+        for key in [NamedKey::BrowserBack, NamedKey::GoBack] {
+            let event = KeyEvent::press(Key::Named(key));
+            assert_eq!(
+                back_key(&event, ModifiersState::empty(), false),
+                Some(BackKey::System)
+            );
+        }
+    }
+
+    #[test]
+    fn keyboard_back_keys_require_opt_in_and_exact_modifiers() {
+        let escape = KeyEvent::press(Key::Named(NamedKey::Escape));
+        let left = KeyEvent::press(Key::Named(NamedKey::ArrowLeft));
+        assert_eq!(
+            back_key(&escape, ModifiersState::empty(), true),
+            Some(BackKey::Keyboard)
+        );
+        assert_eq!(
+            back_key(&left, ModifiersState::ALT, true),
+            Some(BackKey::Keyboard)
+        );
+        assert_eq!(back_key(&escape, ModifiersState::empty(), false), None);
+        assert_eq!(back_key(&left, ModifiersState::ALT, false), None);
+        assert_eq!(back_key(&escape, ModifiersState::SHIFT, true), None);
+        assert_eq!(back_key(&left, ModifiersState::empty(), true), None);
+        assert_eq!(
+            back_key(&left, ModifiersState::ALT | ModifiersState::CONTROL, true),
+            None
+        );
+    }
+
+    #[test]
+    fn only_first_presses_trigger_back() {
+        let press = KeyEvent::press(Key::Named(NamedKey::Escape));
+        let mut repeat = press.clone();
+        repeat.repeat = true;
+        assert!(is_back_press(&press));
+        assert!(!is_back_press(&repeat));
+        assert!(!is_back_press(&KeyEvent::release(Key::Named(
+            NamedKey::Escape
+        ))));
+    }
+}
@@ -0,0 +1,279 @@
+//! Synthetic workloads for benchmarking runtime hot paths.
+//!
+//! ## Usage
+//!
+//! Enable the `bench` feature to build synthetic component trees and render
+//! graphs outside a window, then time the tree build, layout and command
+//! ordering passes on them. The crate's own criterion benches use these
+//! helpers; run them with `cargo bench -p tessera-ui --features bench`.
+//!
+//! A [`SyntheticTree`] owns the runtime's component tree while it is alive, so
+//! only one should exist at a time.
+
+use crate::{
+    ComputedData, DrawCommand, MeasurementError, NoopRenderPolicy, PaddingRect, Px, PxPosition,
+    PxSize, SampleRegion,
+    build_tree::build_component_tree,
+    component_tree::{ComputeMode, ComputeParams, clear_layout_snapshots},
+    layout::{LayoutPolicy, LayoutResult, MeasureScope, layout},
+    modifier::Modifier,
+    render_graph::{RenderFragment, RenderGraph, RenderGraphBuilder, RenderGraphOp},
+    render_pass::RenderPassGraph,
+    runtime::{TesseraRuntime, take_layout_dirty_nodes},
+    tessera,
+    testing::reset_runtime_for_layout_test,
+};
+
+/// Children per node in trees from [`build_synthetic_tree`].
+const BALANCED_FANOUT: usize = 4;
+/// Side length of a synthetic leaf, in pixels.
+const LEAF_SIZE: i32 = 8;
+/// Viewport synthetic trees are laid out in.
+const VIEWPORT: (u32, u32) = (1920, 1080);
+
+/// Builds a balanced synthetic tree of `node_count` components, four children
+/// per node.
+///
+/// # Examples
+///
+/// ```
+/// let mut tree = tessera_ui::bench::build_synthetic_tree(1_000);
+/// tree.measure();
+/// assert_eq!(tree.node_count(), 1_000);
+/// ```
+pub fn build_synthetic_tree(node_count: usize) -> SyntheticTree {
+    SyntheticTree::new(node_count, BALANCED_FANOUT)
+}
+
+/// A mounted tree of synthetic components.
+///
+/// Every node stacks its children vertically and leaves have a fixed size, so
+/// measuring the tree exercises the layout pass without any text or image
+/// work.
+pub struct SyntheticTree {
+    node_count: usize,
+    fanout: usize,
+}
+
+impl SyntheticTree {
+    /// Builds a chain of `node_count` nested components.
+    pub fn deep(node_count: usize) -> Self {
+        Self::new(node_count, 1)
+    }
+
+    /// Builds a root with `node_count - 1` leaf children.
+    pub fn wide(node_count: usize) -> Self {
+        Self::new(node_count, node_count.saturating_sub(1))
+    }
+
+    fn new(node_count: usize, fanout: usize) -> Self {
+        reset_runtime_for_layout_test(VIEWPORT);
+        let mut tree = Self {
+            node_count: node_count.max(1),
+            fanout: fanout.max(1),
+        };
+        tree.rebuild();
+        tree.measure();
+        tree
+    }
+
+    /// Returns the number of synthetic components in the tree.
+    pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    /// Drops the component tree and builds it again from the root.
+    pub fn rebuild(&mut self) {
+        TesseraRuntime::with_mut(|runtime| runtime.component_tree.clear());
+        let (node_count, fanout) = (self.node_count, self.fanout);
+        let _ = build_component_tree(&|| {
+            synthetic_node().nodes(node_count).fanout(fanout);
+        });
+    }
+
+    /// Measures and places every node, bypassing the layout cache.
+    pub fn measure(&mut self) {
+        clear_layout_snapshots();
+        let layout_dirty_nodes = take_layout_dirty_nodes();
+        let screen_size = PxSize::new(Px::new(VIEWPORT.0 as i32), Px::new(VIEWPORT.1 as i32));
+        TesseraRuntime::with_mut(|runtime| {
+            let _ = runtime.component_tree.compute(
+                ComputeParams {
+                    screen_size,
+                    cursor_position: None,
+                    pointer_changes: Vec::new(),
+                    keyboard_events: Vec::new(),
+                    ime_events: Vec::new(),
+                    retry_focus_move: None,
+                    retry_focus_reveal: false,
+                    modifiers: winit::keyboard::ModifiersState::default(),
+                    layout_dirty_nodes: &layout_dirty_nodes,
+                },
+                ComputeMode::LayoutOnly,
+            );
+        });
+    }
+}
+
+#[tessera(crate)]
+fn synthetic_node(nodes: Option<usize>, fanout: Option<usize>) {
+    let descendants = nodes.unwrap_or(1).saturating_sub(1);
+    let fanout = fanout.unwrap_or(1).max(1);
+    layout()
+        .layout_policy(SyntheticStackPolicy)
+        .render_policy(NoopRenderPolicy)
+        .modifier(Modifier::new())
+        .child(move || {
+            let children = fanout.min(descendants);
+            for index in 0..children {
+                let nodes = descendants / children + usize::from(index < descendants % children);
+                synthetic_node().nodes(nodes).fanout(fanout);
+            }
+        });
+}
+
+#[derive(Clone, Copy, PartialEq)]
+struct SyntheticStackPolicy;
+
+impl LayoutPolicy for SyntheticStackPolicy {
+    fn measure(&self, input: &MeasureScope<'_>) -> Result<LayoutResult, MeasurementError> {
+        let children = input.children();
+        if children.is_empty() {
+            return Ok(LayoutResult::new(ComputedData {
+                width: Px(LEAF_SIZE),
+                height: Px(LEAF_SIZE),
+            }));
+        }
+
+        let mut result = LayoutResult::default();
+        let child_constraint = input.parent_constraint().without_min();
+        let mut current_y = Px::ZERO;
+        let mut max_width = Px::ZERO;
+        for child in children {
+            let child_size = child.measure(&child_constraint)?;
+            result.place_child(child, PxPosition::new(Px::ZERO, current_y));
+            current_y += child_size.height;
+            max_width = max_width.max(child_size.width);
+        }
+        Ok(result.with_size(ComputedData {
+            width: max_width,
+            height: current_y,
+        }))
+    }
+}
+
+#[derive(Clone)]
+struct SyntheticFill;
+
+impl DrawCommand for SyntheticFill {
+    fn apply_opacity(&mut self, _opacity: f32) {}
+}
+
+#[derive(Clone)]
+struct SyntheticOutline;
+
+impl DrawCommand for SyntheticOutline {
+    fn apply_opacity(&mut self, _opacity: f32) {}
+}
+
+#[derive(Clone)]
+struct SyntheticBackdrop;
+
+impl DrawCommand for SyntheticBackdrop {
+    fn sample_region(&self) -> Option<SampleRegion> {
+        Some(SampleRegion::PaddedLocal(PaddingRect::ZERO))
+    }
+
+    fn apply_opacity(&mut self, _opacity: f32) {}
+}
+
+/// Builds a render graph of `op_count` draw ops in authoring order.
+///
+/// Ops alternate between two command types on a grid of overlapping tiles,
+/// and every sixteenth op samples the scene behind it, like a backdrop blur.
+/// This gives command ordering both batching opportunities and barriers to
+/// respect.
+///
+/// # Examples
+///
+/// ```
+/// use tessera_ui::bench::{plan_passes, reorder_commands, synthetic_render_graph};
+///
+/// let ops = reorder_commands(synthetic_render_graph(256));
+/// assert_eq!(ops.len(), 256);
+/// assert!(plan_passes(ops) >= 1);
+/// ```
+pub fn synthetic_render_graph(op_count: usize) -> RenderGraph {
+    const COLUMNS: usize = 64;
+    const STEP: i32 = 24;
+    let tile = PxSize::new(Px(32), Px(32));
+
+    let mut builder = RenderGraphBuilder::new();
+    for index in 0..op_count {
+        let mut fragment = RenderFragment::default();
+        if index % 16 == 15 {
+            fragment.push_draw_command(SyntheticBackdrop);
+        } else if index % 2 == 0 {
+            fragment.push_draw_command(SyntheticFill);
+        } else {
+            fragment.push_draw_command(SyntheticOutline);
+        }
+        let position = PxPosition::new(
+            Px((index % COLUMNS) as i32 * STEP),
+            Px((index / COLUMNS) as i32 * STEP),
+        );
+        builder.append_fragment(fragment, tile, position, 1.0);
+    }
+    builder.finish()
+}
+
+/// Reorders the ops of `graph` for execution, batching commands of the same
+/// type where overlaps allow it.
+pub fn reorder_commands(graph: RenderGraph) -> Vec<RenderGraphOp> {
+    graph.into_execution().ops
+}
+
+/// Splits ordered ops into render passes and returns the pass count.
+///
+/// Each draw command is compared with the ones already in the current pass to
+/// decide whether it needs a new pass.
+pub fn plan_passes(ops: Vec<RenderGraphOp>) -> usize {
+    let texture_size = wgpu::Extent3d {
+        width: VIEWPORT.0,
+        height: VIEWPORT.1,
+        depth_or_array_layers: 1,
+    };
+    RenderPassGraph::build(ops, &[], &[], texture_size)
+        .into_passes()
+        .len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mounted_synthetic_nodes() -> usize {
+        TesseraRuntime::with(|runtime| {
+            runtime
+                .component_tree
+                .tree()
+                .iter()
+                .filter(|node| !node.is_removed() && node.get().fn_name == "synthetic_node")
+                .count()
+        })
+    }
+
+    #[test]
+    fn synthetic_trees_mount_the_requested_node_count() {
+        let tree = build_synthetic_tree(341);
+        assert_eq!(mounted_synthetic_nodes(), tree.node_count());
+        let tree = SyntheticTree::deep(64);
+        assert_eq!(mounted_synthetic_nodes(), tree.node_count());
+        let tree = SyntheticTree::wide(200);
+        assert_eq!(mounted_synthetic_nodes(), tree.node_count());
+
+        let ops = reorder_commands(synthetic_render_graph(256));
+        assert_eq!(ops.len(), 256);
+        assert!(plan_passes(ops) > 1);
+    }
+}
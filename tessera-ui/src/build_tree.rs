@@ -18,6 +18,7 @@ use crate::{
         remove_context_read_dependencies, remove_previous_component_context_snapshots,
         reset_context_read_dependencies, with_context_snapshot,
     },
+    deep_link::{remove_deep_link_read_dependencies, reset_deep_link_read_dependencies},
     layout::{DefaultLayoutPolicy, NoopRenderPolicy},
     modifier::Modifier,
     runtime::{
@@ -31,13 +32,17 @@ use crate::{
         reset_render_slot_read_dependencies, reset_state_read_dependencies,
         take_build_invalidations, with_build_dirty_instance_keys, with_replay_scope,
     },
+    safe_area::{remove_safe_area_read_dependencies, reset_safe_area_read_dependencies},
+    system_preferences::{
+        remove_system_preference_read_dependencies, reset_system_preference_read_dependencies,
+    },
     time::Instant,
 };
 
 #[cfg(feature = "profiling")]
 use crate::context::live_context_slot_instance_logic_ids;
 #[cfg(feature = "profiling")]
-use crate::profiler::BuildMode;
+use crate::profiler::{BuildMode, Phase as ProfilerPhase, ScopeGuard as ProfilerScopeGuard};
 #[cfg(feature = "profiling")]
 use crate::runtime::live_slot_instance_logic_ids;
 
@@ -421,68 +426,157 @@ impl Drop for BuildRootNodeGuard {
 
 fn push_build_root_node() -> BuildRootNodeGuard {
     TesseraRuntime::with_mut(|runtime| {
-        runtime.component_tree.add_node(ComponentNode {
-            fn_name: "__tessera_build_root".to_string(),
-            role: NodeRole::Composition,
-            instance_logic_id: 0,
-            instance_key: 0,
-            pointer_preview_handlers: Vec::new(),
-            pointer_handlers: Vec::new(),
-            pointer_final_handlers: Vec::new(),
-            keyboard_preview_handlers: Vec::new(),
-            keyboard_handlers: Vec::new(),
-            ime_preview_handlers: Vec::new(),
-            ime_handlers: Vec::new(),
-            focus_requester_binding: None,
-            focus_registration: None,
-            focus_restorer_fallback: None,
-            focus_traversal_policy: None,
-            focus_changed_handler: None,
-            focus_event_handler: None,
-            focus_beyond_bounds_handler: None,
-            focus_reveal_handler: None,
-            modifier: Modifier::default(),
-            layout_policy: Box::new(DefaultLayoutPolicy),
-            render_policy: Box::new(NoopRenderPolicy),
-            replay: None,
-            props_unchanged_from_previous: false,
-        });
+        runtime.component_tree.add_node(build_root_node());
     });
     BuildRootNodeGuard
 }
 
+fn build_root_node() -> ComponentNode {
+    ComponentNode {
+        fn_name: "__tessera_build_root".to_string(),
+        role: NodeRole::Composition,
+        instance_logic_id: 0,
+        instance_key: 0,
+        pointer_preview_handlers: Vec::new(),
+        pointer_handlers: Vec::new(),
+        pointer_final_handlers: Vec::new(),
+        keyboard_preview_handlers: Vec::new(),
+        keyboard_handlers: Vec::new(),
+        ime_preview_handlers: Vec::new(),
+        ime_handlers: Vec::new(),
+        focus_requester_binding: None,
+        focus_registration: None,
+        focus_restorer_fallback: None,
+        focus_traversal_policy: None,
+        focus_changed_handler: None,
+        focus_event_handler: None,
+        focus_beyond_bounds_handler: None,
+        focus_reveal_handler: None,
+        modifier: Modifier::default(),
+        layout_policy: Box::new(DefaultLayoutPolicy),
+        render_policy: Box::new(NoopRenderPolicy),
+        replay: None,
+        props_unchanged_from_previous: false,
+        memo: false,
+    }
+}
+
+/// Forgets what the given instances read, so their next build records it
+/// again.
+fn remove_read_dependencies(instance_keys: &HashSet<u64>) {
+    remove_frame_nanos_receivers(instance_keys);
+    remove_focus_read_dependencies(instance_keys);
+    remove_render_slot_read_dependencies(instance_keys);
+    remove_state_read_dependencies(instance_keys);
+    remove_system_preference_read_dependencies(instance_keys);
+    remove_safe_area_read_dependencies(instance_keys);
+    remove_deep_link_read_dependencies(instance_keys);
+    remove_context_read_dependencies(instance_keys);
+}
+
+/// Drops everything kept for instances that left the tree.
+fn remove_stale_instances(
+    stale_instance_keys: &HashSet<u64>,
+    stale_instance_logic_ids: &HashSet<u64>,
+) {
+    remove_previous_component_replay_nodes(stale_instance_keys);
+    remove_read_dependencies(stale_instance_keys);
+    crate::runtime::remove_build_invalidations(stale_instance_keys);
+    remove_previous_component_context_snapshots(stale_instance_keys);
+    drop_slots_for_instance_logic_ids(stale_instance_logic_ids);
+    drop_context_slots_for_instance_logic_ids(stale_instance_logic_ids);
+}
+
 #[instrument(level = "debug", skip(entry_point))]
 pub(crate) fn build_component_tree<F: Fn()>(entry_point: &F) -> BuildTreeResult {
-    let run_root_recompose = || {
+    #[cfg(feature = "profiling")]
+    let _profiler_guard =
+        ProfilerScopeGuard::new(ProfilerPhase::BuildTree, None, None, Some("build_tree"));
+    // `memo_dirty_instance_keys` enables reuse of `#[tessera(memo)]` subtrees
+    // that contain none of the given instances.
+    let run_root_recompose = |memo_dirty_instance_keys: Option<&HashSet<u64>>| {
         let recomposed_state_instance_logic_ids = live_slot_instance_logic_ids();
         let recomposed_context_instance_logic_ids = live_context_slot_instance_logic_ids();
         let tree_timer = Instant::now();
         debug!("Building component tree...");
-        clear_frame_nanos_receivers();
-        reset_focus_read_dependencies();
-        reset_render_slot_read_dependencies();
-        reset_state_read_dependencies();
-        reset_context_read_dependencies();
-        TesseraRuntime::with_mut(|runtime| runtime.component_tree.clear());
+        let reuse_guard_dirty_instance_keys =
+            memo_dirty_instance_keys.map(expand_dirty_instance_keys_for_reuse);
+        let memo_context = reuse_guard_dirty_instance_keys.as_ref().and_then(|_| {
+            TesseraRuntime::with_mut(|runtime| {
+                runtime
+                    .component_tree
+                    .begin_memo_root_recompose(build_root_node())
+            })
+        });
+        if let Some(memo_context) = &memo_context {
+            remove_read_dependencies(&memo_context.recomposed_instance_keys());
+        } else {
+            clear_frame_nanos_receivers();
+            reset_focus_read_dependencies();
+            reset_render_slot_read_dependencies();
+            reset_state_read_dependencies();
+            reset_context_read_dependencies();
+            reset_system_preference_read_dependencies();
+            reset_safe_area_read_dependencies();
+            reset_deep_link_read_dependencies();
+            TesseraRuntime::with_mut(|runtime| runtime.component_tree.clear());
+        }
         begin_frame_component_replay_tracking();
         begin_frame_component_context_tracking();
         begin_frame_layout_dirty_tracking();
         begin_recompose_slot_epoch();
         begin_recompose_context_slot_epoch();
         let _phase_guard = crate::runtime::push_phase(crate::runtime::RuntimePhase::Build);
-        let _root_guard = push_build_root_node();
-        entry_point();
-        finalize_frame_component_replay_tracking();
-        finalize_frame_component_context_tracking();
+        let Some(memo_context) = memo_context else {
+            let _root_guard = push_build_root_node();
+            entry_point();
+            finalize_frame_component_replay_tracking();
+            finalize_frame_component_context_tracking();
+            finalize_frame_layout_dirty_tracking();
+            crate::runtime::recycle_recomposed_slots_for_instance_logic_ids(
+                &recomposed_state_instance_logic_ids,
+            );
+            crate::context::recycle_recomposed_context_slots_for_instance_logic_ids(
+                &recomposed_context_instance_logic_ids,
+            );
+            let build_tree_cost = tree_timer.elapsed();
+            debug!("Component tree built in {build_tree_cost:?}");
+            return BuildTreeResult::root_recompose(build_tree_cost);
+        };
+
+        // The root node is already on the node queue.
+        let root_guard = BuildRootNodeGuard;
+        let reuse_guard_dirty_instance_keys = reuse_guard_dirty_instance_keys.unwrap_or_default();
+        with_build_dirty_instance_keys(&reuse_guard_dirty_instance_keys, entry_point);
+        drop(root_guard);
+        let memo_result = TesseraRuntime::with_mut(|runtime| {
+            runtime
+                .component_tree
+                .finish_memo_root_recompose(memo_context)
+        });
+        // Reused subtrees recorded nothing this frame, so keep their
+        // snapshots from the previous one.
+        finalize_frame_component_replay_tracking_partial();
+        finalize_frame_component_context_tracking_partial();
         finalize_frame_layout_dirty_tracking();
+        remove_stale_instances(
+            &memo_result.stale_instance_keys,
+            &memo_result.stale_instance_logic_ids,
+        );
         crate::runtime::recycle_recomposed_slots_for_instance_logic_ids(
-            &recomposed_state_instance_logic_ids,
+            &recomposed_state_instance_logic_ids
+                .difference(&memo_result.reused_instance_logic_ids)
+                .copied()
+                .collect(),
         );
         crate::context::recycle_recomposed_context_slots_for_instance_logic_ids(
-            &recomposed_context_instance_logic_ids,
+            &recomposed_context_instance_logic_ids
+                .difference(&memo_result.reused_instance_logic_ids)
+                .copied()
+                .collect(),
         );
         let build_tree_cost = tree_timer.elapsed();
-        debug!("Component tree built in {build_tree_cost:?}");
+        debug!("Component tree built with memoized subtrees in {build_tree_cost:?}");
         BuildTreeResult::root_recompose(build_tree_cost)
     };
 
@@ -492,7 +586,7 @@ pub(crate) fn build_component_tree<F: Fn()>(entry_point: &F) -> BuildTreeResult
     let had_invalidations = !invalidations.dirty_instance_keys.is_empty();
     with_build_dirty_instance_keys(&invalidations.dirty_instance_keys, || {
         if tree_is_empty {
-            let result = run_root_recompose();
+            let result = run_root_recompose(None);
             #[cfg(feature = "debug-dirty-overlay")]
             let result = result.with_dirty_replay_info(had_invalidations, Vec::new());
             return result;
@@ -510,7 +604,7 @@ pub(crate) fn build_component_tree<F: Fn()>(entry_point: &F) -> BuildTreeResult
             retain_live_dirty_instance_keys(&invalidations.dirty_instance_keys);
         let initial_dirty_roots = collect_dirty_replay_roots(&initial_live_dirty_instance_keys);
         if dirty_roots_include_tree_root(&initial_dirty_roots) {
-            let result = run_root_recompose();
+            let result = run_root_recompose(Some(&initial_live_dirty_instance_keys));
             #[cfg(feature = "debug-dirty-overlay")]
             let result = result.with_dirty_replay_info(had_invalidations, Vec::new());
             return result;
@@ -682,7 +776,7 @@ pub(crate) fn build_component_tree<F: Fn()>(entry_point: &F) -> BuildTreeResult
         }
 
         if fallback_to_root_recompose {
-            let result = run_root_recompose();
+            let result = run_root_recompose(None);
             #[cfg(feature = "debug-dirty-overlay")]
             let result = result.with_dirty_replay_info(had_invalidations, Vec::new());
             return result;
@@ -690,16 +784,7 @@ pub(crate) fn build_component_tree<F: Fn()>(entry_point: &F) -> BuildTreeResult
         finalize_frame_component_replay_tracking_partial();
         finalize_frame_component_context_tracking_partial();
         finalize_frame_layout_dirty_tracking();
-        remove_previous_component_replay_nodes(&stale_instance_keys);
-        remove_frame_nanos_receivers(&stale_instance_keys);
-        remove_focus_read_dependencies(&stale_instance_keys);
-        remove_render_slot_read_dependencies(&stale_instance_keys);
-        remove_state_read_dependencies(&stale_instance_keys);
-        crate::runtime::remove_build_invalidations(&stale_instance_keys);
-        remove_previous_component_context_snapshots(&stale_instance_keys);
-        remove_context_read_dependencies(&stale_instance_keys);
-        drop_slots_for_instance_logic_ids(&stale_instance_logic_ids);
-        drop_context_slots_for_instance_logic_ids(&stale_instance_logic_ids);
+        remove_stale_instances(&stale_instance_keys, &stale_instance_logic_ids);
         crate::runtime::recycle_recomposed_slots_for_instance_logic_ids(
             &recomposed_instance_logic_ids,
         );
@@ -0,0 +1,427 @@
+//! # Command Trace
+//!
+//! ## Usage
+//!
+//! Capture the per-frame render command stream for debugging ordering,
+//! batching and barrier issues.
+//!
+//! When the `command-trace` feature is enabled, every rendered frame appends
+//! one record describing the render graph ops handed to the renderer, after
+//! composite expansion. The output path is taken from the
+//! `TESSERA_COMMAND_TRACE_OUTPUT` environment variable, falling back to
+//! [`TesseraConfig::command_trace_output_path`](crate::renderer::TesseraConfig).
+//!
+//! Use `cargo tessera trace <FILE>` to replay a captured trace frame by frame.
+//!
+//! ## Format
+//!
+//! The output file contains one JSON object per line. The first line is a
+//! header, see [`TraceHeader`]:
+//!
+//! ```jsonl
+//! {"version":1,"format":"tessera-command-trace","generated_at":"1767008877"}
+//! ```
+//!
+//! Each subsequent line is a frame record, see [`FrameTrace`] and
+//! [`OpTrace`]. The record below is formatted for readability:
+//!
+//! ```jsonl
+//! {
+//!   "frame": 12,
+//!   "screen": { "w": 800, "h": 600 },
+//!   "resources": [{ "w": 400, "h": 300, "format": "Rgba8Unorm" }],
+//!   "external_resources": [],
+//!   "ops": [
+//!     {
+//!       "index": 0,
+//!       "kind": "draw",
+//!       "type_name": "tessera_components::pipelines::shape::command::ShapeCommand",
+//!       "pos": { "x": 0, "y": 0 },
+//!       "size": { "w": 800, "h": 600 },
+//!       "opacity": 1.0,
+//!       "sequence_index": 0,
+//!       "read": null,
+//!       "write": "scene_color",
+//!       "deps": [],
+//!       "barrier": null
+//!     },
+//!     {
+//!       "index": 1,
+//!       "kind": "compute",
+//!       "type_name": "tessera_components::pipelines::blur::command::DualBlurCommand",
+//!       "pos": { "x": 10, "y": 10 },
+//!       "size": { "w": 200, "h": 80 },
+//!       "opacity": 1.0,
+//!       "sequence_index": 1,
+//!       "read": "scene_color",
+//!       "write": "local:0",
+//!       "deps": [0],
+//!       "barrier": { "kind": "padded_local", "top": 8, "right": 8, "bottom": 8, "left": 8 }
+//!     }
+//!   ]
+//! }
+//! ```
+use std::{
+    fs::{File, OpenOptions, create_dir_all},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::OnceLock,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use tracing::error;
+
+use crate::{
+    ClipShape, Command, SampleRegion,
+    px::{PxPosition, PxRect, PxSize},
+    render_graph::{ExternalTextureDesc, RenderGraphOp, RenderResource, RenderResourceId},
+};
+
+static OUTPUT_PATH: OnceLock<PathBuf> = OnceLock::new();
+static WRITER: OnceLock<Mutex<Option<TraceWriter>>> = OnceLock::new();
+
+struct TraceWriter {
+    writer: BufWriter<File>,
+}
+
+fn output_path() -> PathBuf {
+    OUTPUT_PATH
+        .get()
+        .cloned()
+        .unwrap_or_else(|| PathBuf::from("tessera-command-trace.jsonl"))
+}
+
+/// Set command trace output path. Must be called before the first frame is
+/// captured.
+pub fn set_output_path(path: impl AsRef<Path>) {
+    let _ = OUTPUT_PATH.set(path.as_ref().to_path_buf());
+}
+
+fn open_writer() -> Option<TraceWriter> {
+    let path = output_path();
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+        && let Err(err) = create_dir_all(parent)
+    {
+        error!("failed to create command trace directory: {err}");
+        return None;
+    }
+    let file = match OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .open(&path)
+    {
+        Ok(file) => file,
+        Err(err) => {
+            error!(
+                "failed to open command trace output {}: {err}",
+                path.display()
+            );
+            return None;
+        }
+    };
+    let mut writer = BufWriter::new(file);
+    if serde_json::to_writer(&mut writer, &TraceHeader::new()).is_err() {
+        return None;
+    }
+    let _ = writer.write_all(b"\n");
+    Some(TraceWriter { writer })
+}
+
+/// Appends the ops of one frame to the trace output.
+pub(crate) fn capture_frame(
+    frame: u64,
+    screen_size: PxSize,
+    ops: &[RenderGraphOp],
+    resources: &[RenderResource],
+    external_resources: &[ExternalTextureDesc],
+) {
+    let record = FrameTrace {
+        frame,
+        screen: SizeTrace::from(screen_size),
+        resources: resources.iter().map(ResourceTrace::from).collect(),
+        external_resources: external_resources
+            .iter()
+            .map(ExternalResourceTrace::from)
+            .collect(),
+        ops: ops
+            .iter()
+            .enumerate()
+            .map(|(index, op)| OpTrace::new(index, op))
+            .collect(),
+    };
+
+    let mut guard = WRITER.get_or_init(|| Mutex::new(open_writer())).lock();
+    let Some(state) = guard.as_mut() else {
+        return;
+    };
+    if serde_json::to_writer(&mut state.writer, &record).is_ok() {
+        let _ = state.writer.write_all(b"\n");
+    }
+    let _ = state.writer.flush();
+}
+
+/// Command trace output header.
+#[derive(Serialize)]
+pub struct TraceHeader {
+    /// Command trace format version.
+    version: u32,
+    /// File format identifier.
+    format: &'static str,
+    /// Timestamp when the trace was generated.
+    generated_at: String,
+}
+
+impl TraceHeader {
+    fn new() -> Self {
+        let generated_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| format!("{}", d.as_secs()))
+            .unwrap_or_else(|_| String::from("unknown"));
+        Self {
+            version: 1,
+            format: "tessera-command-trace",
+            generated_at,
+        }
+    }
+}
+
+/// Command stream of a single frame.
+#[derive(Serialize)]
+pub struct FrameTrace {
+    /// Frame index.
+    frame: u64,
+    /// Render target size.
+    screen: SizeTrace,
+    /// Local textures allocated by render fragments.
+    resources: Vec<ResourceTrace>,
+    /// Persistent textures referenced by the frame.
+    external_resources: Vec<ExternalResourceTrace>,
+    /// Ops in submission order.
+    ops: Vec<OpTrace>,
+}
+
+/// A single render graph op.
+#[derive(Serialize)]
+pub struct OpTrace {
+    /// Position of the op in the frame.
+    index: usize,
+    /// `draw`, `compute`, `composite`, `clip_push` or `clip_pop`.
+    kind: &'static str,
+    /// Rust type name of the command, if it is a draw or compute command.
+    type_name: Option<&'static str>,
+    /// Absolute position of the op.
+    pos: PosTrace,
+    /// Measured size of the op.
+    size: SizeTrace,
+    /// Opacity multiplier applied during record.
+    opacity: f32,
+    /// Authoring order of the op.
+    sequence_index: usize,
+    /// Resource read by the op.
+    read: Option<String>,
+    /// Resource written by the op.
+    write: Option<String>,
+    /// Indices of ops this op depends on.
+    deps: Vec<usize>,
+    /// Barrier requirement of the command.
+    barrier: Option<BarrierTrace>,
+    /// Clip shape of a `clip_push` op.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    clip: Option<ClipTrace>,
+}
+
+impl OpTrace {
+    fn new(index: usize, op: &RenderGraphOp) -> Self {
+        let (kind, type_name, clip) = match &op.command {
+            Command::Draw(command) => ("draw", Some(command.command_name()), None),
+            Command::Compute(command) => ("compute", Some(command.command_name()), None),
+            Command::Composite(_) => ("composite", None, None),
+            Command::ClipPush(shape) => ("clip_push", None, Some(ClipTrace::from(*shape))),
+            Command::ClipPop => ("clip_pop", None, None),
+        };
+        Self {
+            index,
+            kind,
+            type_name,
+            pos: PosTrace::from(op.position),
+            size: SizeTrace::from(op.size),
+            opacity: op.opacity,
+            sequence_index: op.sequence_index,
+            read: op.read.map(resource_label),
+            write: op.write.map(resource_label),
+            deps: op.deps.to_vec(),
+            barrier: op.command.barrier().map(BarrierTrace::from),
+            clip,
+        }
+    }
+}
+
+fn resource_label(id: RenderResourceId) -> String {
+    match id {
+        RenderResourceId::SceneColor => "scene_color".to_string(),
+        RenderResourceId::SceneDepth => "scene_depth".to_string(),
+        RenderResourceId::Local(index) => format!("local:{index}"),
+        RenderResourceId::External(index) => format!("external:{index}"),
+    }
+}
+
+#[derive(Serialize)]
+struct PosTrace {
+    x: i32,
+    y: i32,
+}
+
+impl From<PxPosition> for PosTrace {
+    fn from(value: PxPosition) -> Self {
+        Self {
+            x: value.x.0,
+            y: value.y.0,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SizeTrace {
+    w: i32,
+    h: i32,
+}
+
+impl From<PxSize> for SizeTrace {
+    fn from(value: PxSize) -> Self {
+        Self {
+            w: value.width.0,
+            h: value.height.0,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct RectTrace {
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32,
+}
+
+impl From<PxRect> for RectTrace {
+    fn from(value: PxRect) -> Self {
+        Self {
+            x: value.x.0,
+            y: value.y.0,
+            w: value.width.0,
+            h: value.height.0,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ResourceTrace {
+    w: i32,
+    h: i32,
+    format: String,
+}
+
+impl From<&RenderResource> for ResourceTrace {
+    fn from(value: &RenderResource) -> Self {
+        match value {
+            RenderResource::Texture(desc) => Self {
+                w: desc.size.width.0,
+                h: desc.size.height.0,
+                format: format!("{:?}", desc.format),
+            },
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ExternalResourceTrace {
+    handle_id: u32,
+    w: i32,
+    h: i32,
+    format: String,
+    sample_count: u32,
+}
+
+impl From<&ExternalTextureDesc> for ExternalResourceTrace {
+    fn from(value: &ExternalTextureDesc) -> Self {
+        Self {
+            handle_id: value.handle_id,
+            w: value.size.width.0,
+            h: value.size.height.0,
+            format: format!("{:?}", value.format),
+            sample_count: value.sample_count,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum BarrierTrace {
+    Global,
+    PaddedLocal {
+        top: i32,
+        right: i32,
+        bottom: i32,
+        left: i32,
+    },
+    Absolute(RectTrace),
+}
+
+impl From<SampleRegion> for BarrierTrace {
+    fn from(value: SampleRegion) -> Self {
+        match value {
+            SampleRegion::Global => Self::Global,
+            SampleRegion::PaddedLocal(padding) => Self::PaddedLocal {
+                top: padding.top.0,
+                right: padding.right.0,
+                bottom: padding.bottom.0,
+                left: padding.left.0,
+            },
+            SampleRegion::Absolute(rect) => Self::Absolute(RectTrace::from(rect)),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(tag = "shape", rename_all = "snake_case")]
+enum ClipTrace {
+    Rect(RectTrace),
+    RoundedRect {
+        #[serde(flatten)]
+        rect: RectTrace,
+        corner_radii: [f32; 4],
+    },
+    Ellipse(RectTrace),
+    Rotated {
+        #[serde(flatten)]
+        rect: RectTrace,
+        corner_radii: [f32; 4],
+        rotation: f32,
+    },
+}
+
+impl From<ClipShape> for ClipTrace {
+    fn from(value: ClipShape) -> Self {
+        match value {
+            ClipShape::Rect(rect) => Self::Rect(rect.into()),
+            ClipShape::RoundedRect { rect, corner_radii } => Self::RoundedRect {
+                rect: rect.into(),
+                corner_radii,
+            },
+            ClipShape::Ellipse(rect) => Self::Ellipse(rect.into()),
+            ClipShape::Rotated {
+                rect,
+                corner_radii,
+                rotation,
+            } => Self::Rotated {
+                rect: rect.into(),
+                corner_radii,
+                rotation,
+            },
+        }
+    }
+}
@@ -0,0 +1,69 @@
+//! Per-component tracing spans.
+//!
+//! ## Usage
+//!
+//! Enable the `component-spans` feature to break the tree build and layout
+//! down by component in flame graphs. Every component gets a `build` span
+//! while its function runs and a `measure` span while it is measured, both
+//! with `component` (the function name) and `instance_key` fields, nested
+//! like the component tree.
+//!
+//! Spans are emitted at the `debug` level under this module's target, so a
+//! filter like `tessera_ui::component_spans=debug` selects them. Flame graph
+//! layers usually group spans by name only; show the `component` field to tell
+//! them apart, e.g. with `tracing-chrome`'s `include_args`.
+//!
+//! Tracing every component is costly in large trees, so only one frame out of
+//! [`TesseraConfig::component_span_sampling`](crate::renderer::TesseraConfig)
+//! is traced. The `TESSERA_COMPONENT_SPAN_SAMPLING` environment variable
+//! overrides it.
+
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+use tracing::span::EnteredSpan;
+
+static SAMPLING: AtomicU32 = AtomicU32::new(1);
+static FRAME_SAMPLED: AtomicBool = AtomicBool::new(true);
+
+/// Traces one frame out of `sampling`; `0` is treated as `1`.
+pub(crate) fn set_sampling(sampling: u32) {
+    SAMPLING.store(sampling.max(1), Ordering::Relaxed);
+}
+
+/// Decides whether the frame about to be built is traced.
+pub(crate) fn begin_frame(frame_idx: u64) {
+    let sampling = u64::from(SAMPLING.load(Ordering::Relaxed));
+    FRAME_SAMPLED.store(frame_idx.is_multiple_of(sampling), Ordering::Relaxed);
+}
+
+/// Enters the build span of a component, if the frame is traced.
+pub(crate) fn enter_build(fn_name: &str, instance_key: u64) -> Option<EnteredSpan> {
+    FRAME_SAMPLED
+        .load(Ordering::Relaxed)
+        .then(|| tracing::debug_span!("build", component = fn_name, instance_key).entered())
+}
+
+/// Enters the measure span of a component, if the frame is traced.
+pub(crate) fn enter_measure(fn_name: &str, instance_key: u64) -> Option<EnteredSpan> {
+    FRAME_SAMPLED
+        .load(Ordering::Relaxed)
+        .then(|| tracing::debug_span!("measure", component = fn_name, instance_key).entered())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_every_nth_frame_is_sampled() {
+        set_sampling(3);
+        let sampled: Vec<bool> = (0..7)
+            .map(|frame_idx| {
+                begin_frame(frame_idx);
+                FRAME_SAMPLED.load(Ordering::Relaxed)
+            })
+            .collect();
+        assert_eq!(sampled, [true, false, false, true, false, false, true]);
+        set_sampling(1);
+    }
+}
@@ -7,7 +7,7 @@ use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 use tracing::{debug, warn};
 
 use crate::{
-    ComputeResourceManager, NodeId, Px, PxRect,
+    CallbackWith, ComputeResourceManager, NodeId, Px, PxRect,
     cursor::{CursorEventContent, PointerChange},
     focus::{
         FocusDirection, FocusHandleId, FocusOwner, PendingFocusCallbackInvocation, bind_focus_owner,
@@ -19,10 +19,12 @@ use crate::{
     },
     px::{PxPosition, PxSize},
     render_graph::{RenderGraph, RenderGraphBuilder},
+    render_scene::ClipShape,
     runtime::{
         LayoutDirtyNodes, RuntimePhase, StructureReconcileResult, TesseraRuntime,
         push_current_component_instance_key, push_current_node_with_instance_logic_id, push_phase,
     },
+    shortcuts::{ShortcutCandidate, dispatch_shortcuts},
     time::Instant,
 };
 
@@ -35,7 +37,7 @@ pub use node::{
 
 pub(crate) use node::{
     ComponentNode, ComponentNodeMetaData, ComponentNodeMetaDatas, ComponentNodeTree, NodeRole,
-    WindowRequests, direct_layout_children, measure_node,
+    PointerCaptureMap, WindowRequests, direct_layout_children, measure_node,
 };
 
 #[cfg(feature = "profiling")]
@@ -123,6 +125,7 @@ pub struct LayoutFrameDiagnostics {
     pub dirty_nodes_structural: u64,
     pub dirty_nodes_with_ancestors: u64,
     pub dirty_expand_ns: u64,
+    pub measure_ns: u64,
     pub measure_node_calls: u64,
     pub cache_hits_direct: u64,
     pub cache_hits_boundary: u64,
@@ -154,6 +157,7 @@ impl LayoutDiagnosticsCollector {
         dirty_nodes_structural: u64,
         dirty_nodes_with_ancestors: u64,
         dirty_expand_ns: u64,
+        measure_ns: u64,
     ) -> LayoutFrameDiagnostics {
         let cache_hits_direct = self.cache_hits_direct;
         let cache_hits_boundary = self.cache_hits_boundary;
@@ -162,6 +166,7 @@ impl LayoutDiagnosticsCollector {
             dirty_nodes_structural,
             dirty_nodes_with_ancestors,
             dirty_expand_ns,
+            measure_ns,
             measure_node_calls: self.measure_node_calls,
             cache_hits_direct,
             cache_hits_boundary,
@@ -289,7 +294,7 @@ pub(crate) struct ComputeParams<'a> {
     pub screen_size: PxSize,
     pub cursor_position: Option<PxPosition>,
     pub pointer_changes: Vec<PointerChange>,
-    pub keyboard_events: Vec<winit::event::KeyEvent>,
+    pub keyboard_events: Vec<crate::KeyEvent>,
     pub ime_events: Vec<winit::event::Ime>,
     pub retry_focus_move: Option<FocusDirection>,
     pub retry_focus_reveal: bool,
@@ -305,6 +310,9 @@ pub(crate) enum ComputeMode<'a> {
     },
     #[cfg(feature = "testing")]
     LayoutOnly,
+    /// Runs layout and input dispatch without recording GPU commands.
+    #[cfg(feature = "testing")]
+    Headless,
 }
 
 /// Respents a component tree
@@ -321,6 +329,16 @@ pub struct ComponentTree {
     /// Active pointer hit paths keyed by pointer id.
     /// Each path stores node instance keys from root to leaf.
     active_pointer_paths: HashMap<u64, Vec<u64>>,
+    /// Pointers captured by a node, keyed by pointer id.
+    /// Captured changes are routed to the capturing node path instead of the
+    /// hit path.
+    pointer_captures: PointerCaptureMap,
+    /// Hover handlers under the pointer, keyed by instance key and handler
+    /// index.
+    ///
+    /// Kept across rebuilds so rebuilt nodes that stay hovered are not entered
+    /// again.
+    hovered_nodes: HashSet<(u64, usize)>,
     /// Per-tree focus owner used for keyboard and IME routing.
     focus_owner: FocusOwner,
 }
@@ -352,6 +370,34 @@ pub(crate) struct ReplayReplaceContext {
     removed_instance_logic_ids: HashSet<u64>,
 }
 
+/// Old tree kept while a root recomposition may reuse memoized subtrees.
+pub(crate) struct MemoRecomposeContext {
+    detached_root_ids: Vec<indextree::NodeId>,
+    previous_node_ids: HashSet<indextree::NodeId>,
+    previous_instance_keys: HashSet<u64>,
+    previous_instance_logic_ids: HashSet<u64>,
+    /// Instance keys inside memoized subtrees, which keep their dependencies
+    /// when reused.
+    pub memo_instance_keys: HashSet<u64>,
+}
+
+#[derive(Default)]
+pub(crate) struct MemoRecomposeResult {
+    pub stale_instance_keys: HashSet<u64>,
+    pub stale_instance_logic_ids: HashSet<u64>,
+    pub reused_instance_logic_ids: HashSet<u64>,
+}
+
+impl MemoRecomposeContext {
+    /// Instance keys of the old tree outside memoized subtrees.
+    pub(crate) fn recomposed_instance_keys(&self) -> HashSet<u64> {
+        self.previous_instance_keys
+            .difference(&self.memo_instance_keys)
+            .copied()
+            .collect()
+    }
+}
+
 impl Default for ComponentTree {
     fn default() -> Self {
         Self::new()
@@ -371,6 +417,8 @@ impl ComponentTree {
             layout_snapshots: LayoutSnapshotMap::default(),
             replay_reuse_candidates: HashMap::default(),
             active_pointer_paths: HashMap::default(),
+            pointer_captures: PointerCaptureMap::default(),
+            hovered_nodes: HashSet::default(),
             focus_owner: FocusOwner::new(),
         }
     }
@@ -383,11 +431,13 @@ impl ComponentTree {
         self.node_queue.clear();
         self.replay_reuse_candidates.clear();
         self.active_pointer_paths.clear();
+        self.pointer_captures.clear();
     }
 
     /// Reset the entire component tree, including focus ownership state.
     pub fn reset(&mut self) {
         self.clear();
+        self.hovered_nodes.clear();
         self.focus_owner.reset();
     }
 
@@ -599,6 +649,126 @@ impl ComponentTree {
         })
     }
 
+    /// Starts a root recomposition that can reuse `#[tessera(memo)]`
+    /// subtrees.
+    ///
+    /// Detaches the children of the root, offers every memoized node among
+    /// them as a reuse candidate and resets the root to `root`. Returns `None`
+    /// when the tree holds no memoized node; clear the tree instead then.
+    pub(crate) fn begin_memo_root_recompose(
+        &mut self,
+        root: ComponentNode,
+    ) -> Option<MemoRecomposeContext> {
+        let root_id = self
+            .tree
+            .get_node_id_at(NonZero::new(1).expect("root node index must be non-zero"))?;
+        self.tree.get(root_id)?;
+
+        let previous_node_ids = root_id.descendants(&self.tree).collect::<HashSet<_>>();
+        let mut previous_instance_keys = HashSet::default();
+        let mut previous_instance_logic_ids = HashSet::default();
+        let mut memo_instance_keys = HashSet::default();
+        let mut candidates = HashMap::default();
+        for node_id in &previous_node_ids {
+            let Some(node) = self.tree.get(*node_id).map(|node| node.get()) else {
+                continue;
+            };
+            previous_instance_keys.insert(node.instance_key);
+            previous_instance_logic_ids.insert(node.instance_logic_id);
+            if !node.memo || *node_id == root_id {
+                continue;
+            }
+            candidates.insert(node.instance_key, *node_id);
+            memo_instance_keys.extend(
+                node_id
+                    .descendants(&self.tree)
+                    .filter_map(|id| self.tree.get(id))
+                    .map(|node| node.get().instance_key),
+            );
+        }
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let detached_root_ids = root_id.children(&self.tree).collect::<Vec<_>>();
+        for detached_root_id in &detached_root_ids {
+            detached_root_id.detach(&mut self.tree);
+        }
+        if let Some(node) = self.tree.get_mut(root_id) {
+            *node.get_mut() = root;
+        }
+        self.metadatas
+            .insert(root_id, ComponentNodeMetaData::none());
+        self.node_queue.clear();
+        self.node_queue.push(root_id);
+        self.replay_reuse_candidates = candidates;
+        self.active_pointer_paths.clear();
+        self.pointer_captures.clear();
+
+        Some(MemoRecomposeContext {
+            detached_root_ids,
+            previous_node_ids,
+            previous_instance_keys,
+            previous_instance_logic_ids,
+            memo_instance_keys,
+        })
+    }
+
+    /// Removes the old nodes a memo root recomposition did not reuse.
+    pub(crate) fn finish_memo_root_recompose(
+        &mut self,
+        context: MemoRecomposeContext,
+    ) -> MemoRecomposeResult {
+        self.replay_reuse_candidates.clear();
+        for detached_root_id in &context.detached_root_ids {
+            let reused = self
+                .tree
+                .get(*detached_root_id)
+                .is_none_or(|node| node.parent().is_some());
+            if reused {
+                continue;
+            }
+            let detached_node_ids = detached_root_id.descendants(&self.tree).collect::<Vec<_>>();
+            for node_id in &detached_node_ids {
+                self.metadatas.remove(node_id);
+            }
+            detached_root_id.remove_subtree(&mut self.tree);
+        }
+
+        let mut result = MemoRecomposeResult::default();
+        let Some(root_id) = self
+            .tree
+            .get_node_id_at(NonZero::new(1).expect("root node index must be non-zero"))
+        else {
+            return result;
+        };
+        let mut instance_keys = HashSet::default();
+        let mut instance_logic_ids = HashSet::default();
+        for node_id in root_id.descendants(&self.tree) {
+            let Some(node) = self.tree.get(node_id).map(|node| node.get()) else {
+                continue;
+            };
+            instance_keys.insert(node.instance_key);
+            instance_logic_ids.insert(node.instance_logic_id);
+            if node_id != root_id && context.previous_node_ids.contains(&node_id) {
+                result
+                    .reused_instance_logic_ids
+                    .insert(node.instance_logic_id);
+            }
+        }
+        result.stale_instance_keys = context
+            .previous_instance_keys
+            .difference(&instance_keys)
+            .copied()
+            .collect();
+        result.stale_instance_logic_ids = context
+            .previous_instance_logic_ids
+            .difference(&instance_logic_ids)
+            .copied()
+            .collect();
+        result
+    }
+
     /// Get current node
     pub(crate) fn current_node(&self) -> Option<&ComponentNode> {
         self.node_queue
@@ -900,13 +1070,26 @@ impl ComponentTree {
             dirty_nodes_structural,
             dirty_nodes_effective.len() as u64,
             dirty_expand_ns,
+            measure_timer.elapsed().as_nanos() as u64,
         );
 
-        let (compute_resource_manager, gpu) = match mode {
+        let record_cost = match mode {
             ComputeMode::Full {
                 compute_resource_manager,
                 gpu,
-            } => (compute_resource_manager, gpu),
+            } => {
+                let record_timer = Instant::now();
+                record_layout_commands(
+                    root_node,
+                    &self.tree,
+                    &mut self.metadatas,
+                    compute_resource_manager,
+                    gpu,
+                );
+                record_timer.elapsed()
+            }
+            #[cfg(feature = "testing")]
+            ComputeMode::Headless => std::time::Duration::ZERO,
             #[cfg(feature = "testing")]
             ComputeMode::LayoutOnly => {
                 populate_layout_metadata(root_node, &self.tree, &mut self.metadatas);
@@ -920,16 +1103,6 @@ impl ComponentTree {
                 );
             }
         };
-
-        let record_timer = Instant::now();
-        record_layout_commands(
-            root_node,
-            &self.tree,
-            &mut self.metadatas,
-            compute_resource_manager,
-            gpu,
-        );
-        let record_cost = record_timer.elapsed();
         populate_layout_metadata(root_node, &self.tree, &mut self.metadatas);
 
         let compute_draw_timer = Instant::now();
@@ -949,6 +1122,7 @@ impl ComponentTree {
 
         let node_ids_preorder = layout_node_ids_preorder(root_node, &self.tree);
         let node_ids_postorder = layout_node_ids_postorder(root_node, &self.tree);
+        let drag_cursor_position = cursor_position;
         let pointer_change_paths = build_pointer_change_paths(
             root_node,
             &self.tree,
@@ -956,10 +1130,20 @@ impl ComponentTree {
             &pointer_changes,
             cursor_position,
             &mut self.active_pointer_paths,
+            &self.pointer_captures,
+        );
+        let cursor_hit_path =
+            hit_path_instance_keys(root_node, &self.tree, &self.metadatas, cursor_position);
+        let hover_cursor_icon =
+            resolve_hover_cursor_icon(root_node, &self.tree, &self.metadatas, cursor_position);
+        let hovered_nodes =
+            resolve_hovered_nodes(root_node, &self.tree, &self.metadatas, cursor_position);
+        dispatch_hover_changes(
+            root_node,
+            &self.tree,
+            &mut self.hovered_nodes,
+            hovered_nodes,
         );
-        window_requests.cursor_icon =
-            resolve_hover_cursor_icon(root_node, &self.tree, &self.metadatas, cursor_position)
-                .unwrap_or_default();
 
         for node_id in node_ids_preorder.iter().copied() {
             let Some(node) = self.tree.get(node_id).map(|n| n.get()) else {
@@ -971,9 +1155,11 @@ impl ComponentTree {
                 cursor_position: &mut cursor_position,
                 pointer_changes: pointer_changes.as_mut_slice(),
                 pointer_change_paths: &pointer_change_paths,
+                cursor_hit_path: &cursor_hit_path,
                 modifiers,
                 window_requests: &mut window_requests,
                 focus_owner: &mut self.focus_owner,
+                pointer_captures: &mut self.pointer_captures,
             };
             dispatch_pointer_modifiers_for_node_pass(
                 &mut dispatch_ctx,
@@ -988,9 +1174,11 @@ impl ComponentTree {
                     cursor_position: &mut cursor_position,
                     pointer_changes: pointer_changes.as_mut_slice(),
                     pointer_change_paths: &pointer_change_paths,
+                    cursor_hit_path: &cursor_hit_path,
                     modifiers,
                     window_requests: &mut window_requests,
                     focus_owner: &mut self.focus_owner,
+                    pointer_captures: &mut self.pointer_captures,
                 };
                 run_pointer_handler_for_node(
                     &mut dispatch_ctx,
@@ -1011,9 +1199,11 @@ impl ComponentTree {
                 cursor_position: &mut cursor_position,
                 pointer_changes: pointer_changes.as_mut_slice(),
                 pointer_change_paths: &pointer_change_paths,
+                cursor_hit_path: &cursor_hit_path,
                 modifiers,
                 window_requests: &mut window_requests,
                 focus_owner: &mut self.focus_owner,
+                pointer_captures: &mut self.pointer_captures,
             };
             dispatch_pointer_modifiers_for_node_pass(
                 &mut dispatch_ctx,
@@ -1028,9 +1218,11 @@ impl ComponentTree {
                     cursor_position: &mut cursor_position,
                     pointer_changes: pointer_changes.as_mut_slice(),
                     pointer_change_paths: &pointer_change_paths,
+                    cursor_hit_path: &cursor_hit_path,
                     modifiers,
                     window_requests: &mut window_requests,
                     focus_owner: &mut self.focus_owner,
+                    pointer_captures: &mut self.pointer_captures,
                 };
                 run_pointer_handler_for_node(
                     &mut dispatch_ctx,
@@ -1051,9 +1243,11 @@ impl ComponentTree {
                 cursor_position: &mut cursor_position,
                 pointer_changes: pointer_changes.as_mut_slice(),
                 pointer_change_paths: &pointer_change_paths,
+                cursor_hit_path: &cursor_hit_path,
                 modifiers,
                 window_requests: &mut window_requests,
                 focus_owner: &mut self.focus_owner,
+                pointer_captures: &mut self.pointer_captures,
             };
             dispatch_pointer_modifiers_for_node_pass(
                 &mut dispatch_ctx,
@@ -1068,9 +1262,11 @@ impl ComponentTree {
                     cursor_position: &mut cursor_position,
                     pointer_changes: pointer_changes.as_mut_slice(),
                     pointer_change_paths: &pointer_change_paths,
+                    cursor_hit_path: &cursor_hit_path,
                     modifiers,
                     window_requests: &mut window_requests,
                     focus_owner: &mut self.focus_owner,
+                    pointer_captures: &mut self.pointer_captures,
                 };
                 run_pointer_handler_for_node(
                     &mut dispatch_ctx,
@@ -1081,6 +1277,25 @@ impl ComponentTree {
             }
         }
 
+        if pointer_changes
+            .iter()
+            .any(|change| matches!(change.content, CursorEventContent::Pressed(_)))
+        {
+            self.focus_owner.set_focus_visible(false);
+        }
+        release_pointer_captures(&mut self.pointer_captures, &pointer_changes);
+        let drag_live_instance_keys = if crate::dnd::is_dragging() {
+            self.live_instance_keys()
+        } else {
+            HashSet::default()
+        };
+        crate::dnd::finish_frame(drag_cursor_position, |instance_key| {
+            drag_live_instance_keys.contains(&instance_key)
+        });
+        if let Some(icon) = hover_cursor_icon {
+            window_requests.cursor_icon = icon;
+        }
+
         self.focus_owner.commit_pending();
         let pending_focus_move_retry = retry_focus_move.and_then(|direction| {
             match try_dispatch_focus_move_request(&self.tree, direction, &mut self.focus_owner) {
@@ -1092,6 +1307,10 @@ impl ComponentTree {
             collect_focus_chain_node_ids(root_node, &self.tree, &self.focus_owner);
 
         let pending_focus_move_retry = if pending_focus_move_retry.is_none() {
+            let shortcut_candidates =
+                collect_shortcut_candidates(&self.tree, &node_ids_preorder, &focus_chain_node_ids);
+            dispatch_shortcuts(&shortcut_candidates, &mut keyboard_events, modifiers);
+
             let mut keyboard_dispatch_ctx = KeyboardInputDispatchContext {
                 tree: &self.tree,
                 metadatas: &self.metadatas,
@@ -1410,6 +1629,104 @@ fn resolve_hover_cursor_icon(
         })
 }
 
+fn resolve_hovered_nodes(
+    root_node: indextree::NodeId,
+    tree: &ComponentNodeTree,
+    metadatas: &ComponentNodeMetaDatas,
+    position: Option<PxPosition>,
+) -> HashMap<(u64, usize), CallbackWith<bool>> {
+    let mut hovered = HashMap::default();
+    let Some(position) = position else {
+        return hovered;
+    };
+    for node_id in hit_path_node_ids(root_node, tree, metadatas, Some(position)) {
+        let Some(node) = tree.get(node_id).map(|node| node.get()) else {
+            continue;
+        };
+        let Some((base_abs_pos, size)) = metadatas
+            .get(&node_id)
+            .and_then(|metadata| Some((metadata.base_abs_position?, metadata.computed_data?)))
+        else {
+            continue;
+        };
+        let size = PxSize::new(size.width, size.height);
+        let mut current_abs_pos = base_abs_pos;
+        let mut index = 0;
+        for action in node.modifier.ordered_actions() {
+            match action {
+                OrderedModifierAction::Placement(placement) => {
+                    current_abs_pos = placement.node().transform_position(current_abs_pos);
+                }
+                OrderedModifierAction::Hover(handler) => {
+                    if PxRect::from_position_size(current_abs_pos, size).contains(position) {
+                        hovered.insert((node.instance_key, index), handler);
+                    }
+                    index += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+    hovered
+}
+
+fn dispatch_hover_changes(
+    root_node: indextree::NodeId,
+    tree: &ComponentNodeTree,
+    previous: &mut HashSet<(u64, usize)>,
+    current: HashMap<(u64, usize), CallbackWith<bool>>,
+) {
+    let left: HashSet<(u64, usize)> = previous
+        .iter()
+        .filter(|key| !current.contains_key(key))
+        .copied()
+        .collect();
+    let entered: Vec<_> = current
+        .iter()
+        .filter(|(key, _)| !previous.contains(key))
+        .map(|(_, handler)| *handler)
+        .collect();
+    *previous = current.into_keys().collect();
+    // Handlers of the last frame may belong to removed nodes whose callbacks
+    // are freed, so leaves are only sent to nodes still in the tree.
+    for handler in hover_handlers(root_node, tree, &left) {
+        handler.call(false);
+    }
+    for handler in entered {
+        handler.call(true);
+    }
+}
+
+fn hover_handlers(
+    root_node: indextree::NodeId,
+    tree: &ComponentNodeTree,
+    keys: &HashSet<(u64, usize)>,
+) -> Vec<CallbackWith<bool>> {
+    if keys.is_empty() {
+        return Vec::new();
+    }
+    let mut handlers = Vec::new();
+    for node_id in root_node.descendants(tree) {
+        let Some(node) = tree.get(node_id).map(|node| node.get()) else {
+            continue;
+        };
+        let hover_actions = node
+            .modifier
+            .ordered_actions()
+            .into_iter()
+            .filter_map(|action| match action {
+                OrderedModifierAction::Hover(handler) => Some(handler),
+                _ => None,
+            });
+        for (index, handler) in hover_actions.enumerate() {
+            if keys.contains(&(node.instance_key, index)) {
+                handlers.push(handler);
+            }
+        }
+    }
+    handlers
+}
+
 fn node_handles_pointer_at_position(
     node: &crate::component_tree::ComponentNode,
     base_abs_pos: PxPosition,
@@ -1424,6 +1741,7 @@ fn node_handles_pointer_at_position(
                 current_abs_pos = placement.node().transform_position(current_abs_pos);
             }
             OrderedModifierAction::Cursor(_)
+            | OrderedModifierAction::Hover(_)
             | OrderedModifierAction::PointerPreviewInput(_)
             | OrderedModifierAction::PointerInput(_)
             | OrderedModifierAction::PointerFinalInput(_) => {
@@ -1471,9 +1789,17 @@ fn build_pointer_change_paths(
     pointer_changes: &[PointerChange],
     cursor_position: Option<PxPosition>,
     active_pointer_paths: &mut HashMap<u64, Vec<u64>>,
+    pointer_captures: &PointerCaptureMap,
 ) -> Vec<Vec<u64>> {
     let mut paths = Vec::with_capacity(pointer_changes.len());
     for change in pointer_changes {
+        if let Some(captured_path) = pointer_captures.get(&change.pointer_id) {
+            if matches!(change.content, CursorEventContent::Released(_)) {
+                active_pointer_paths.remove(&change.pointer_id);
+            }
+            paths.push(captured_path.clone());
+            continue;
+        }
         let debug_position = match &change.content {
             CursorEventContent::Moved(position) => Some(*position),
             _ => cursor_position,
@@ -1511,12 +1837,28 @@ fn build_pointer_change_paths(
                 .unwrap_or_else(|| {
                     hit_path_instance_keys(root_node, tree, metadatas, debug_position)
                 }),
+            CursorEventContent::Pinch(_)
+            | CursorEventContent::Rotate(_)
+            | CursorEventContent::SmartMagnify => {
+                hit_path_instance_keys(root_node, tree, metadatas, debug_position)
+            }
         };
         paths.push(path);
     }
     paths
 }
 
+fn release_pointer_captures(
+    pointer_captures: &mut PointerCaptureMap,
+    pointer_changes: &[PointerChange],
+) {
+    for change in pointer_changes {
+        if matches!(change.content, CursorEventContent::Released(_)) {
+            pointer_captures.remove(&change.pointer_id);
+        }
+    }
+}
+
 fn hit_path_instance_keys(
     root_node: indextree::NodeId,
     tree: &ComponentNodeTree,
@@ -1570,9 +1912,11 @@ struct PointerInputDispatchContext<'a> {
     cursor_position: &'a mut Option<PxPosition>,
     pointer_changes: &'a mut [PointerChange],
     pointer_change_paths: &'a [Vec<u64>],
+    cursor_hit_path: &'a [u64],
     modifiers: winit::keyboard::ModifiersState,
     window_requests: &'a mut WindowRequests,
     focus_owner: &'a mut FocusOwner,
+    pointer_captures: &'a mut PointerCaptureMap,
 }
 
 fn dispatch_pointer_modifiers_for_node_pass(
@@ -1686,12 +2030,20 @@ fn run_pointer_input_for_node<F>(
 
     let mut cursor_position_ref = &mut *dispatch_ctx.cursor_position;
     let mut dummy_cursor_position = None;
+    let captures_pointer = dispatch_ctx
+        .pointer_captures
+        .values()
+        .any(|path| path.last() == Some(&instance_key));
     if let (Some(cursor_pos), Some(clip_rect)) = (*cursor_position_ref, event_clip_rect)
         && !clip_rect.contains(cursor_pos)
+        && !captures_pointer
     {
         cursor_position_ref = &mut dummy_cursor_position;
     }
     let current_cursor_position = cursor_position_ref.map(|pos| pos - abs_pos);
+    let hovered =
+        cursor_position_ref.is_some() && dispatch_ctx.cursor_hit_path.contains(&instance_key);
+    let mut is_target = false;
     let mut selected_change_indices = Vec::new();
     let mut local_pointer_changes = Vec::new();
     for (index, change) in dispatch_ctx.pointer_changes.iter().enumerate() {
@@ -1702,6 +2054,7 @@ fn run_pointer_input_for_node<F>(
             continue;
         };
         if path.contains(&instance_key) {
+            is_target |= path.last() == Some(&instance_key);
             selected_change_indices.push(index);
             local_pointer_changes.push(change.clone());
         }
@@ -1728,8 +2081,15 @@ fn run_pointer_input_for_node<F>(
         cursor_position_abs: cursor_position_ref,
         pointer_changes: &mut local_pointer_changes,
         key_modifiers: dispatch_ctx.modifiers,
+        cursor_icon: &mut dispatch_ctx.window_requests.cursor_icon,
         ime_request: &mut dispatch_ctx.window_requests.ime_request,
         request_window_drag: &mut dispatch_ctx.window_requests.request_window_drag,
+        request_window_resize: &mut dispatch_ctx.window_requests.request_window_resize,
+        node_id,
+        tree: dispatch_ctx.tree,
+        pointer_captures: dispatch_ctx.pointer_captures,
+        hovered,
+        is_target,
     };
     dispatch(input);
     for (local_change, &original_index) in local_pointer_changes
@@ -1763,7 +2123,7 @@ fn run_keyboard_handler_for_node(
 struct KeyboardInputDispatchContext<'a> {
     tree: &'a ComponentNodeTree,
     metadatas: &'a ComponentNodeMetaDatas,
-    keyboard_events: &'a mut Vec<winit::event::KeyEvent>,
+    keyboard_events: &'a mut Vec<crate::KeyEvent>,
     modifiers: winit::keyboard::ModifiersState,
     window_requests: &'a mut WindowRequests,
     focus_owner: &'a mut FocusOwner,
@@ -1933,9 +2293,33 @@ fn run_ime_input_for_node<F>(
     attach_ime_position_if_needed(window_requests, abs_pos);
 }
 
+fn collect_shortcut_candidates(
+    tree: &ComponentNodeTree,
+    node_ids_preorder: &[indextree::NodeId],
+    focus_chain_node_ids: &[indextree::NodeId],
+) -> Vec<ShortcutCandidate> {
+    let mut candidates = Vec::new();
+    for node_id in node_ids_preorder.iter().copied() {
+        let Some(node) = tree.get(node_id).map(|n| n.get()) else {
+            continue;
+        };
+        let focus_depth = focus_chain_node_ids.iter().position(|id| *id == node_id);
+        for action in node.modifier.ordered_actions() {
+            if let OrderedModifierAction::Shortcut(shortcut) = action {
+                candidates.push(ShortcutCandidate {
+                    shortcut,
+                    focus_depth,
+                    order: candidates.len(),
+                });
+            }
+        }
+    }
+    candidates
+}
+
 fn dispatch_default_focus_keyboard_navigation(
     tree: &ComponentNodeTree,
-    keyboard_events: &mut Vec<winit::event::KeyEvent>,
+    keyboard_events: &mut Vec<crate::KeyEvent>,
     modifiers: winit::keyboard::ModifiersState,
     focus_owner: &mut FocusOwner,
 ) -> Option<FocusDirection> {
@@ -1952,8 +2336,12 @@ fn dispatch_default_focus_keyboard_navigation(
             return true;
         };
         match try_dispatch_focus_move_request(tree, direction, focus_owner) {
-            FocusMoveRequestResult::Moved => false,
+            FocusMoveRequestResult::Moved => {
+                focus_owner.set_focus_visible(true);
+                false
+            }
             FocusMoveRequestResult::Retry(direction) => {
+                focus_owner.set_focus_visible(true);
                 pending_focus_move_retry = Some(direction);
                 false
             }
@@ -1964,7 +2352,7 @@ fn dispatch_default_focus_keyboard_navigation(
 }
 
 fn default_focus_navigation_direction(
-    event: &winit::event::KeyEvent,
+    event: &crate::KeyEvent,
     modifiers: winit::keyboard::ModifiersState,
 ) -> Option<FocusDirection> {
     if event.state != winit::event::ElementState::Pressed {
@@ -2251,6 +2639,7 @@ struct PreparedLayoutMetadata {
     size: PxSize,
     node_rect: PxRect,
     clips_children: bool,
+    clip_shape: Option<ClipShape>,
     child_clip_rect: Option<PxRect>,
     cumulative_opacity: f32,
 }
@@ -2302,11 +2691,15 @@ fn prepare_layout_metadata_for_node(
         height: size.height,
     };
     let clips_children = metadata.clips_children;
+    let clip_shape = metadata
+        .clip_shape
+        .map(|shape| shape.translate(self_position));
     let child_clip_rect = if clips_children {
+        let clip_bounds = clip_shape.map_or(node_rect, |shape| shape.bounds());
         Some(
             parent_clip_rect
-                .and_then(|existing| existing.intersection(&node_rect))
-                .unwrap_or(node_rect),
+                .and_then(|existing| existing.intersection(&clip_bounds))
+                .unwrap_or(clip_bounds),
         )
     } else {
         parent_clip_rect
@@ -2317,6 +2710,7 @@ fn prepare_layout_metadata_for_node(
         size,
         node_rect,
         clips_children,
+        clip_shape,
         child_clip_rect,
         cumulative_opacity,
     })
@@ -2441,9 +2835,11 @@ fn build_render_graph_inner(
     };
 
     if prepared.clips_children {
-        context
-            .builder
-            .push_clip_push(prepared.child_clip_rect.unwrap_or(PxRect::ZERO));
+        let shape = match prepared.clip_shape {
+            Some(shape) if shape.needs_stencil() => shape,
+            _ => ClipShape::Rect(prepared.child_clip_rect.unwrap_or(PxRect::ZERO)),
+        };
+        context.builder.push_clip_push(shape);
     }
 
     let fragment = match context.metadatas.get_mut(&node_id) {
@@ -2523,6 +2919,7 @@ mod tests {
             render_policy: Box::new(NoopRenderPolicy),
             replay: None,
             props_unchanged_from_previous: false,
+            memo: false,
         }
     }
 
@@ -2647,6 +3044,50 @@ mod tests {
         assert!(!replace_result.removed_instance_logic_ids.contains(&3));
     }
 
+    #[test]
+    fn memo_root_recompose_reuses_memoized_subtree_and_removes_the_rest() {
+        let mut tree = ComponentTree::new();
+
+        let root = tree.add_node(node("root", 1, 1));
+        tree.add_node(node("plain", 2, 2));
+        tree.pop_node();
+        tree.add_node(ComponentNode {
+            memo: true,
+            ..node("memo", 3, 3)
+        });
+        tree.add_node(node("memo_child", 4, 4));
+        tree.pop_node();
+        tree.pop_node();
+        tree.pop_node();
+
+        let context = tree
+            .begin_memo_root_recompose(node("root", 1, 1))
+            .expect("memoized node should enable memo recomposition");
+        assert!(context.memo_instance_keys.contains(&3));
+        assert!(context.memo_instance_keys.contains(&4));
+        assert!(!context.recomposed_instance_keys().contains(&4));
+        assert_eq!(root.children(tree.tree()).count(), 0);
+
+        tree.add_node(node("memo", 3, 3));
+        assert!(tree.try_reuse_current_subtree(3, 3));
+        tree.pop_node();
+        tree.pop_node();
+
+        let result = tree.finish_memo_root_recompose(context);
+
+        let root_children = root
+            .children(tree.tree())
+            .map(|id| tree.get(id).expect("child must exist").fn_name.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(root_children, vec!["memo"]);
+        assert!(tree.find_node_id_by_instance_key(4).is_some());
+        assert!(tree.find_node_id_by_instance_key(2).is_none());
+        assert!(result.stale_instance_keys.contains(&2));
+        assert!(!result.stale_instance_keys.contains(&4));
+        assert!(result.reused_instance_logic_ids.contains(&3));
+        assert!(result.reused_instance_logic_ids.contains(&4));
+    }
+
     #[test]
     fn layout_node_input_traversal_skips_composition_nodes() {
         let mut tree = ComponentTree::new();
@@ -2668,4 +3109,229 @@ mod tests {
         assert_eq!(postorder, vec![layout_b, layout_a]);
         assert_eq!(composition.children(tree.tree()).count(), 1);
     }
+
+    type PointerLog = Arc<parking_lot::Mutex<Vec<(CursorEventContent, Option<PxPosition>)>>>;
+
+    fn capturing_node(log: PointerLog) -> ComponentNode {
+        let mut capturing = node("capturing", 2, 2);
+        capturing
+            .pointer_handlers
+            .push(Box::new(move |mut input: PointerInput| {
+                for change in input.pointer_changes.clone() {
+                    if matches!(change.content, CursorEventContent::Pressed(_)) {
+                        input.capture_pointer(change.pointer_id);
+                    }
+                    log.lock().push((change.content, input.cursor_position_rel));
+                }
+            }));
+        capturing
+    }
+
+    fn place(
+        metadatas: &mut ComponentNodeMetaDatas,
+        node_id: indextree::NodeId,
+        size: i32,
+        event_clip_rect: Option<PxRect>,
+    ) {
+        let metadata = metadatas.entry_or_default(node_id);
+        metadata.computed_data = Some(ComputedData {
+            width: Px(size),
+            height: Px(size),
+        });
+        metadata.base_abs_position = Some(PxPosition::ZERO);
+        metadata.abs_position = Some(PxPosition::ZERO);
+        metadata.event_clip_rect = event_clip_rect;
+    }
+
+    fn dispatch_pointer_frame(
+        tree: &ComponentTree,
+        root: indextree::NodeId,
+        pointer_captures: &mut PointerCaptureMap,
+        active_pointer_paths: &mut HashMap<u64, Vec<u64>>,
+        cursor_position: PxPosition,
+        content: CursorEventContent,
+    ) {
+        let mut pointer_changes = vec![PointerChange {
+            timestamp: Instant::now(),
+            pointer_id: crate::MOUSE_POINTER_ID,
+            content,
+            consumed: false,
+        }];
+        let pointer_change_paths = build_pointer_change_paths(
+            root,
+            tree.tree(),
+            tree.metadatas(),
+            &pointer_changes,
+            Some(cursor_position),
+            active_pointer_paths,
+            pointer_captures,
+        );
+        let cursor_hit_path =
+            hit_path_instance_keys(root, tree.tree(), tree.metadatas(), Some(cursor_position));
+        let mut cursor_position = Some(cursor_position);
+        let mut window_requests = WindowRequests::default();
+        let mut focus_owner = FocusOwner::new();
+        for node_id in layout_node_ids_postorder(root, tree.tree()) {
+            let Some(node) = tree.get(node_id) else {
+                continue;
+            };
+            for handler in &node.pointer_handlers {
+                let mut dispatch_ctx = PointerInputDispatchContext {
+                    tree: tree.tree(),
+                    metadatas: tree.metadatas(),
+                    cursor_position: &mut cursor_position,
+                    pointer_changes: pointer_changes.as_mut_slice(),
+                    pointer_change_paths: &pointer_change_paths,
+                    cursor_hit_path: &cursor_hit_path,
+                    modifiers: winit::keyboard::ModifiersState::empty(),
+                    window_requests: &mut window_requests,
+                    focus_owner: &mut focus_owner,
+                    pointer_captures: &mut *pointer_captures,
+                };
+                run_pointer_handler_for_node(
+                    &mut dispatch_ctx,
+                    node_id,
+                    PointerEventPass::Main,
+                    handler.as_ref(),
+                );
+            }
+        }
+        release_pointer_captures(pointer_captures, &pointer_changes);
+    }
+
+    fn capture_fixture(log: PointerLog) -> (ComponentTree, indextree::NodeId) {
+        let mut tree = ComponentTree::new();
+        let root = tree.add_node(node("root", 1, 1));
+        let child = tree.add_node(capturing_node(log));
+        tree.pop_node();
+        tree.pop_node();
+
+        let clip = PxRect::from_position_size(PxPosition::ZERO, PxSize::new(Px(50), Px(50)));
+        place(tree.metadatas_mut(), root, 200, None);
+        place(tree.metadatas_mut(), child, 50, Some(clip));
+        (tree, root)
+    }
+
+    #[test]
+    fn captured_pointer_receives_moves_outside_bounds_and_clip() {
+        let log = PointerLog::default();
+        let (tree, root) = capture_fixture(log.clone());
+        let mut pointer_captures = PointerCaptureMap::default();
+        let mut active_pointer_paths = HashMap::default();
+
+        let inside = PxPosition::new(Px(10), Px(10));
+        let outside = PxPosition::new(Px(150), Px(150));
+        dispatch_pointer_frame(
+            &tree,
+            root,
+            &mut pointer_captures,
+            &mut active_pointer_paths,
+            inside,
+            CursorEventContent::Pressed(crate::PressKeyEventType::Left),
+        );
+        assert_eq!(
+            pointer_captures.get(&crate::MOUSE_POINTER_ID),
+            Some(&vec![1, 2])
+        );
+
+        dispatch_pointer_frame(
+            &tree,
+            root,
+            &mut pointer_captures,
+            &mut active_pointer_paths,
+            outside,
+            CursorEventContent::Moved(outside),
+        );
+        let log = log.lock();
+        assert_eq!(log.len(), 2);
+        assert!(matches!(log[1].0, CursorEventContent::Moved(_)));
+        assert_eq!(log[1].1, Some(outside));
+    }
+
+    #[test]
+    fn pointer_capture_is_dropped_on_release() {
+        let log = PointerLog::default();
+        let (tree, root) = capture_fixture(log.clone());
+        let mut pointer_captures = PointerCaptureMap::default();
+        let mut active_pointer_paths = HashMap::default();
+
+        let inside = PxPosition::new(Px(10), Px(10));
+        let outside = PxPosition::new(Px(150), Px(150));
+        for (position, content) in [
+            (
+                inside,
+                CursorEventContent::Pressed(crate::PressKeyEventType::Left),
+            ),
+            (
+                outside,
+                CursorEventContent::Released(crate::PressKeyEventType::Left),
+            ),
+        ] {
+            dispatch_pointer_frame(
+                &tree,
+                root,
+                &mut pointer_captures,
+                &mut active_pointer_paths,
+                position,
+                content,
+            );
+        }
+        assert!(pointer_captures.is_empty());
+        assert!(matches!(
+            log.lock().last(),
+            Some((CursorEventContent::Released(_), Some(position))) if *position == outside
+        ));
+
+        dispatch_pointer_frame(
+            &tree,
+            root,
+            &mut pointer_captures,
+            &mut active_pointer_paths,
+            outside,
+            CursorEventContent::Moved(outside),
+        );
+        assert_eq!(log.lock().len(), 2);
+    }
+
+    type HoverLog = Arc<parking_lot::Mutex<Vec<(u64, bool, bool)>>>;
+
+    fn hover_recording_node(instance_key: u64, log: HoverLog) -> ComponentNode {
+        let mut recording = node("hover_recording", instance_key, instance_key);
+        recording
+            .pointer_handlers
+            .push(Box::new(move |input: PointerInput| {
+                log.lock()
+                    .push((instance_key, input.is_hovered(), input.is_hit_target()));
+            }));
+        recording
+    }
+
+    #[test]
+    fn covered_sibling_is_not_hovered() {
+        let log = HoverLog::default();
+        let mut tree = ComponentTree::new();
+        let root = tree.add_node(node("root", 1, 1));
+        let below = tree.add_node(hover_recording_node(2, log.clone()));
+        tree.pop_node();
+        let above = tree.add_node(hover_recording_node(3, log.clone()));
+        tree.pop_node();
+        tree.pop_node();
+        place(tree.metadatas_mut(), root, 200, None);
+        place(tree.metadatas_mut(), below, 100, None);
+        place(tree.metadatas_mut(), above, 100, None);
+
+        let position = PxPosition::new(Px(50), Px(50));
+        dispatch_pointer_frame(
+            &tree,
+            root,
+            &mut PointerCaptureMap::default(),
+            &mut HashMap::default(),
+            position,
+            CursorEventContent::Moved(position),
+        );
+
+        let mut log = log.lock().clone();
+        log.sort_unstable();
+        assert_eq!(log, vec![(2, false, false), (3, true, true)]);
+    }
 }
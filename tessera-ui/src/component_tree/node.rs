@@ -7,12 +7,12 @@ use std::{
 use indextree::NodeId;
 use rustc_hash::FxHashMap;
 use tracing::debug;
-use winit::window::CursorIcon;
+use winit::window::{CursorIcon, ResizeDirection};
 
 use crate::{
     Px,
     accessibility::{AccessibilityActionHandler, AccessibilityNode},
-    cursor::{CursorEventContent, PointerChange},
+    cursor::{CursorEventContent, PointerChange, PointerId},
     focus::{
         FocusDirection, FocusRegistration, FocusRequester, FocusRevealRequest, FocusState,
         FocusTraversalPolicy,
@@ -25,6 +25,7 @@ use crate::{
     prop::CallbackWith,
     px::{PxPosition, PxSize},
     render_graph::RenderFragment,
+    render_scene::ClipShape,
     runtime::{
         RuntimePhase, push_current_component_instance_key,
         push_current_node_with_instance_logic_id, push_phase,
@@ -101,10 +102,15 @@ pub(crate) struct ComponentNode {
     pub(crate) replay: Option<crate::prop::ComponentReplayData>,
     /// Whether props are equal to the previous frame snapshot.
     pub(crate) props_unchanged_from_previous: bool,
+    /// Whether the component opted into `#[tessera(memo)]` reuse.
+    pub(crate) memo: bool,
 }
 
 /// Contains metadata of the component node.
 pub(crate) struct ComponentNodeMetaData {
+    /// The constraint the node was measured under.
+    /// None if the node is not measured yet.
+    pub constraint: Option<Constraint>,
     /// The computed data (size) of the node.
     /// None if the node is not computed yet.
     pub computed_data: Option<ComputedData>,
@@ -135,6 +141,10 @@ pub(crate) struct ComponentNodeMetaData {
     pub(crate) fragment: RenderFragment,
     /// Whether this node clips its children.
     pub clips_children: bool,
+    /// Shape used to clip children, relative to the node origin.
+    ///
+    /// `None` clips children to the node bounds.
+    pub clip_shape: Option<ClipShape>,
     /// Opacity multiplier applied to this node and its descendants.
     pub opacity: f32,
     /// Accessibility information for this node.
@@ -147,6 +157,7 @@ impl ComponentNodeMetaData {
     /// Creates a new `ComponentNodeMetaData` with default values.
     pub fn none() -> Self {
         Self {
+            constraint: None,
             computed_data: None,
             layout_cache_hit: false,
             placement_order: None,
@@ -156,6 +167,7 @@ impl ComponentNodeMetaData {
             event_clip_rect: None,
             fragment: RenderFragment::default(),
             clips_children: false,
+            clip_shape: None,
             opacity: 1.0,
             accessibility: None,
             accessibility_action_handler: None,
@@ -201,8 +213,13 @@ pub(crate) fn direct_layout_children(node_id: NodeId, tree: &ComponentNodeTree)
     children
 }
 
-fn reset_frame_metadata(node_id: NodeId, component_node_metadatas: &mut ComponentNodeMetaDatas) {
+fn reset_frame_metadata(
+    node_id: NodeId,
+    constraint: &Constraint,
+    component_node_metadatas: &mut ComponentNodeMetaDatas,
+) {
     let metadata = component_node_metadatas.entry_or_default(node_id);
+    metadata.constraint = Some(*constraint);
     metadata.computed_data = None;
     metadata.layout_cache_hit = false;
     metadata.placement_order = None;
@@ -212,12 +229,16 @@ fn reset_frame_metadata(node_id: NodeId, component_node_metadatas: &mut Componen
     metadata.event_clip_rect = None;
     metadata.fragment = RenderFragment::default();
     metadata.clips_children = false;
+    metadata.clip_shape = None;
     metadata.opacity = 1.0;
 }
 
 /// A tree of component nodes, using `indextree::Arena` for storage.
 pub(crate) type ComponentNodeTree = indextree::Arena<ComponentNode>;
 
+/// Captured pointers mapped to the instance-key path of the capturing node.
+pub(crate) type PointerCaptureMap = FxHashMap<PointerId, Vec<u64>>;
+
 /// Stores component metadata for a single UI tree.
 #[derive(Default)]
 pub(crate) struct ComponentNodeMetaDatas {
@@ -284,6 +305,12 @@ pub enum MeasurementError {
 }
 
 /// Pointer input dispatch pass.
+///
+/// Pointer changes are routed along the hit-tested path from the root to the
+/// deepest node under the pointer (or to the capturing node). Each pass walks
+/// that path, so [`Initial`](Self::Initial) acts as the capture phase,
+/// [`Main`](Self::Main) delivers to the target first and then bubbles toward
+/// the root, and [`Final`](Self::Final) lets ancestors observe the outcome.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PointerEventPass {
     /// Dispatch from root to leaf before the main pointer pass.
@@ -329,12 +356,25 @@ pub struct PointerInput<'a> {
     pub pointer_changes: &'a mut Vec<PointerChange>,
     /// The current state of the keyboard modifiers at the time of the event.
     pub key_modifiers: winit::keyboard::ModifiersState,
+    pub(crate) cursor_icon: &'a mut CursorIcon,
     pub(crate) ime_request: &'a mut Option<ImeRequest>,
     pub(crate) request_window_drag: &'a mut bool,
+    pub(crate) request_window_resize: &'a mut Option<ResizeDirection>,
+    pub(crate) node_id: NodeId,
+    pub(crate) tree: &'a ComponentNodeTree,
+    pub(crate) pointer_captures: &'a mut PointerCaptureMap,
+    pub(crate) hovered: bool,
+    pub(crate) is_target: bool,
 }
 
 impl PointerInput<'_> {
     /// Marks all current pointer changes as consumed.
+    ///
+    /// Consumed changes are not delivered to later handlers, so this is how a
+    /// handler stops propagation. During [`PointerEventPass::Initial`] it
+    /// intercepts the changes before descendants see them; during
+    /// [`PointerEventPass::Main`] it prevents ancestors from receiving the
+    /// bubbled changes.
     pub fn consume_pointer_changes(&mut self) {
         for change in self.pointer_changes.iter_mut() {
             change.consume();
@@ -348,6 +388,23 @@ impl PointerInput<'_> {
         })
     }
 
+    /// Returns whether this node is the deepest hit-tested node for at least
+    /// one of the current pointer changes.
+    ///
+    /// Ancestors receive the same changes while they bubble, but only the
+    /// target reports `true`.
+    pub fn is_hit_target(&self) -> bool {
+        self.is_target
+    }
+
+    /// Returns whether the cursor hit path passes through this node.
+    ///
+    /// Unlike a plain bounds check on `cursor_position_rel`, this is `false`
+    /// when the node is covered by a sibling drawn above it.
+    pub fn is_hovered(&self) -> bool {
+        self.hovered
+    }
+
     /// Returns the absolute cursor position in window coordinates.
     pub fn cursor_position_abs(&self) -> Option<PxPosition> {
         *self.cursor_position_abs
@@ -364,11 +421,62 @@ impl PointerInput<'_> {
         self.block_cursor();
     }
 
+    /// Captures a pointer so its subsequent changes are delivered to this node
+    /// even after the pointer leaves the node bounds or clip.
+    ///
+    /// The capture is released automatically when the pointer is released, or
+    /// explicitly with [`PointerInput::release_pointer_capture`].
+    pub fn capture_pointer(&mut self, pointer_id: PointerId) {
+        let mut path: Vec<u64> = self
+            .node_id
+            .ancestors(self.tree)
+            .filter_map(|node_id| self.tree.get(node_id).map(|node| node.get().instance_key))
+            .collect();
+        path.reverse();
+        self.pointer_captures.insert(pointer_id, path);
+    }
+
+    /// Releases a pointer captured by this node.
+    pub fn release_pointer_capture(&mut self, pointer_id: PointerId) {
+        if self.has_pointer_capture(pointer_id) {
+            self.pointer_captures.remove(&pointer_id);
+        }
+    }
+
+    /// Returns whether this node currently captures the pointer.
+    pub fn has_pointer_capture(&self, pointer_id: PointerId) -> bool {
+        let instance_key = self.node_instance_key();
+        self.pointer_captures
+            .get(&pointer_id)
+            .is_some_and(|path| path.last().copied() == instance_key)
+    }
+
+    /// Returns the stable instance key of the node handling this input.
+    pub(crate) fn node_instance_key(&self) -> Option<u64> {
+        self.tree
+            .get(self.node_id)
+            .map(|node| node.get().instance_key)
+    }
+
+    /// Requests the window cursor icon for the current frame.
+    ///
+    /// Icons set with
+    /// [`hover_cursor_icon`](crate::modifier::CursorModifierExt::hover_cursor_icon)
+    /// on a node under the pointer win over this request.
+    pub fn request_cursor_icon(&mut self, icon: CursorIcon) {
+        *self.cursor_icon = icon;
+    }
+
     /// Begins a system drag move for the current window.
     pub fn drag_window(&mut self) {
         *self.request_window_drag = true;
     }
 
+    /// Begins a system resize of the current window from the given edge.
+    pub fn drag_resize_window(&mut self, direction: ResizeDirection) {
+        *self.request_window_resize = Some(direction);
+    }
+
     /// Returns the IME session bridge for the current frame.
     pub fn ime_session(&mut self) -> ImeSession<'_> {
         ImeSession {
@@ -386,7 +494,7 @@ pub struct KeyboardInput<'a> {
     /// The size of the component node, computed during the measure stage.
     pub computed_data: ComputedData,
     /// Keyboard events from the event loop, if any.
-    pub keyboard_events: &'a mut Vec<winit::event::KeyEvent>,
+    pub keyboard_events: &'a mut Vec<crate::KeyEvent>,
     /// The current state of the keyboard modifiers at the time of the event.
     pub key_modifiers: winit::keyboard::ModifiersState,
     pub(crate) ime_request: &'a mut Option<ImeRequest>,
@@ -439,7 +547,8 @@ impl ImeInput<'_> {
 pub(crate) struct WindowRequests {
     /// The cursor icon requested by a component. If multiple components request
     /// a cursor, the last one to make a request in a frame "wins", since
-    /// it's executed later.
+    /// it's executed later. A hover cursor icon of a node under the pointer
+    /// replaces it once pointer dispatch finishes.
     pub cursor_icon: CursorIcon,
     /// An Input Method Editor (IME) request.
     /// If multiple components request IME, the one from the "newer" component
@@ -448,6 +557,9 @@ pub(crate) struct WindowRequests {
     pub ime_request: Option<ImeRequest>,
     /// Whether a node requested a native window drag for the current frame.
     pub request_window_drag: bool,
+    /// The window edge a node requested a native resize from for the current
+    /// frame.
+    pub request_window_resize: Option<ResizeDirection>,
 }
 
 /// Frame-local IME bridge used by input handlers to publish text input state.
@@ -514,6 +626,14 @@ impl ImeRequest {
         self
     }
 
+    /// Sets the caret rectangle, relative to the requesting node, that the
+    /// platform uses to place the candidate window.
+    pub fn with_caret_rect(mut self, caret_rect: crate::PxRect) -> Self {
+        self.local_position = PxPosition::new(caret_rect.x, caret_rect.y);
+        self.size = PxSize::new(caret_rect.width, caret_rect.height);
+        self
+    }
+
     /// Sets the current ordered text composition range.
     pub fn with_composition_range(
         mut self,
@@ -571,6 +691,7 @@ fn restore_cached_subtree_metadata(
 
     {
         let metadata = component_node_metadatas.entry_or_default(node_id);
+        metadata.constraint = Some(entry.constraint_key);
         metadata.computed_data = Some(size);
         metadata.layout_cache_hit = true;
         if let Some(position) = rel_position {
@@ -763,6 +884,9 @@ pub(crate) fn measure_node(
         node_data_ref.parent(),
         Some(node_data.fn_name.as_str()),
     ));
+    #[cfg(feature = "component-spans")]
+    let _span_guard =
+        crate::component_spans::enter_measure(node_data.fn_name.as_str(), node_data.instance_key);
 
     let children = direct_layout_children(node_id, tree);
     let timer = Instant::now();
@@ -896,7 +1020,7 @@ pub(crate) fn measure_node(
                         }
                     }
                     if restored {
-                        reset_frame_metadata(node_id, component_node_metadatas);
+                        reset_frame_metadata(node_id, parent_constraint, component_node_metadatas);
                         let placements = if node_self_placement_dirty {
                             match relayout_base_layout(
                                 layout_policy.as_ref(),
@@ -964,7 +1088,7 @@ pub(crate) fn measure_node(
         }
     }
 
-    reset_frame_metadata(node_id, component_node_metadatas);
+    reset_frame_metadata(node_id, parent_constraint, component_node_metadatas);
     let measure_layout_ctx = MeasureLayoutContext {
         tree,
         children: &children,
@@ -7,20 +7,21 @@
 use std::{
     any::{Any, TypeId},
     cell::RefCell,
+    hash::{Hash, Hasher},
     marker::PhantomData,
     sync::Arc,
 };
 
 use im::HashMap as ImHashMap;
 use parking_lot::RwLock;
-use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet, FxHasher};
 
 use crate::{
     execution_context::{with_execution_context, with_execution_context_mut},
     runtime::{
         RuntimePhase, compute_context_slot_key, current_phase,
         current_replay_boundary_instance_key_from_scope, ensure_build_phase,
-        record_replay_boundary_invalidation_for_instance_key,
+        is_instance_key_build_dirty, record_replay_boundary_invalidation_for_instance_key,
     },
 };
 
@@ -44,6 +45,9 @@ struct SlotEntry {
     key: SlotKey,
     generation: u64,
     value: Option<Arc<dyn Any + Send + Sync>>,
+    /// Hash of the key the value was produced for by
+    /// [`provide_context_keyed`].
+    producer_key: u64,
     last_alive_epoch: u64,
 }
 
@@ -267,6 +271,18 @@ pub(crate) fn record_current_context_snapshot_for(instance_key: u64) {
     });
 }
 
+/// Whether the contexts in scope are the ones `instance_key` saw in its
+/// previous build.
+pub(crate) fn current_context_matches_previous_snapshot(instance_key: u64) -> bool {
+    let current = current_context_map();
+    with_context_snapshot_tracker(|tracker| {
+        tracker
+            .previous_by_instance_key
+            .get(&instance_key)
+            .is_some_and(|previous| *previous == current)
+    })
+}
+
 pub(crate) fn with_context_snapshot<R>(snapshot: &ContextMap, f: impl FnOnce() -> R) -> R {
     struct ContextSnapshotGuard {
         previous_stack: Option<Vec<ContextMap>>,
@@ -614,6 +630,63 @@ fn pop_context_layer() {
 /// }
 /// ```
 pub fn provide_context<T, I, F, R>(init: I, f: F) -> R
+where
+    T: Send + Sync + 'static,
+    I: FnOnce() -> T,
+    F: FnOnce() -> R,
+{
+    provide_context_for(None, init, f)
+}
+
+/// Provides a typed context value that is produced again whenever `key`
+/// changes.
+///
+/// Like [`provide_context`], `init` runs when the context slot is created, and
+/// additionally when `key` differs from the key of the previous build. The new
+/// value replaces the old one in place, so descendants keep their state and
+/// readers of the context are rebuilt.
+///
+/// # Examples
+///
+/// ```
+/// use tessera_ui::{Color, provide_context_keyed, tessera, use_context};
+///
+/// struct Theme {
+///     primary: Color,
+/// }
+///
+/// #[tessera]
+/// fn root(dark: bool) {
+///     provide_context_keyed(
+///         dark,
+///         || Theme {
+///             primary: if dark { Color::WHITE } else { Color::BLACK },
+///         },
+///         || {
+///             leaf();
+///         },
+///     );
+/// }
+///
+/// #[tessera]
+/// fn leaf() {
+///     let theme = use_context::<Theme>().expect("Theme must be provided");
+///     theme.with(|t| assert_ne!(t.primary, Color::RED));
+/// }
+/// ```
+pub fn provide_context_keyed<K, T, I, F, R>(key: K, init: I, f: F) -> R
+where
+    K: Hash,
+    T: Send + Sync + 'static,
+    I: FnOnce() -> T,
+    F: FnOnce() -> R,
+{
+    let mut hasher = FxHasher::default();
+    key.hash(&mut hasher);
+    provide_context_for(Some(hasher.finish()), init, f)
+}
+
+fn provide_context_for<T, I, F, R>(producer_key: Option<u64>, init: I, f: F) -> R
 where
     T: Send + Sync + 'static,
     I: FnOnce() -> T,
@@ -629,7 +702,7 @@ where
         type_id,
     };
 
-    let (slot, generation) = {
+    let (slot, generation, reproduced) = {
         with_slot_table_mut(|table| {
             let epoch = table.epoch;
 
@@ -640,13 +713,20 @@ where
                     .expect("context slot entry should exist");
                 entry.last_alive_epoch = epoch;
 
+                let mut reproduced = false;
                 if entry.value.is_none() {
                     entry.value = Some(Arc::new(RwLock::new(init())));
                     entry.generation = entry.generation.wrapping_add(1);
+                } else if let Some(producer_key) = producer_key
+                    && producer_key != entry.producer_key
+                {
+                    entry.value = Some(Arc::new(RwLock::new(init())));
+                    reproduced = true;
                 }
+                entry.producer_key = producer_key.unwrap_or_default();
 
                 let generation = entry.generation;
-                (slot, generation)
+                (slot, generation, reproduced)
             } else if let Some(slot) = table.free_list.pop() {
                 let entry = table
                     .entries
@@ -655,11 +735,12 @@ where
 
                 entry.key = slot_key;
                 entry.value = Some(Arc::new(RwLock::new(init())));
+                entry.producer_key = producer_key.unwrap_or_default();
                 entry.last_alive_epoch = epoch;
 
                 let generation = entry.generation;
                 table.key_to_slot.insert(slot_key, slot);
-                (slot, generation)
+                (slot, generation, false)
             } else {
                 let generation = 0;
                 let slot = table.entries.len() as u32;
@@ -667,14 +748,26 @@ where
                     key: slot_key,
                     generation,
                     value: Some(Arc::new(RwLock::new(init()))),
+                    producer_key: producer_key.unwrap_or_default(),
                     last_alive_epoch: epoch,
                 });
                 table.key_to_slot.insert(slot_key, slot);
-                (slot, generation)
+                (slot, generation, false)
             }
         })
     };
 
+    if reproduced {
+        let provider_instance_key = current_replay_boundary_instance_key_from_scope();
+        for instance_key in context_read_subscribers(slot, generation) {
+            if Some(instance_key) != provider_instance_key
+                && !is_instance_key_build_dirty(instance_key)
+            {
+                record_replay_boundary_invalidation_for_instance_key(instance_key);
+            }
+        }
+    }
+
     push_context_layer(type_id, slot, generation, slot_key);
 
     struct ContextScopeGuard;
@@ -815,6 +908,7 @@ mod tests {
                     key,
                     generation: 2,
                     value: Some(Arc::new(RwLock::new(42_u8))),
+                    producer_key: 0,
                     last_alive_epoch: 1,
                 });
                 table.key_to_slot.insert(key, 0);
@@ -858,12 +952,14 @@ mod tests {
                     key: old_key,
                     generation: 9,
                     value: None,
+                    producer_key: 0,
                     last_alive_epoch: 0,
                 });
                 table.entries.push(SlotEntry {
                     key,
                     generation: 4,
                     value: Some(Arc::new(RwLock::new(7_u16))),
+                    producer_key: 0,
                     last_alive_epoch: 2,
                 });
                 table.key_to_slot.insert(key, 1);
@@ -3,11 +3,18 @@
 //! This module provides comprehensive cursor and touch event handling for the
 //! Tessera UI framework. It manages cursor position tracking, event queuing,
 //! touch gesture recognition, and scroll event generation for smooth user
-//! interactions.
+//! interactions. It also provides the velocity tracking and fling physics
+//! shared by scrolling containers.
 
-use std::collections::{HashMap, VecDeque};
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
 
-use crate::{PxPosition, time::Instant};
+use crate::{
+    PxPosition,
+    time::{self, Instant},
+};
 
 /// Pointer identifier used by input changes.
 pub type PointerId = u64;
@@ -29,11 +36,6 @@ struct TouchPointState {
     last_position: PxPosition,
     /// Timestamp of the last position update.
     last_update_time: Instant,
-    /// Tracks whether this touch gesture generated a scroll event.
-    ///
-    /// When set, the gesture should be treated as a drag/scroll rather than a
-    /// tap.
-    generated_scroll_event: bool,
 }
 
 /// Configuration settings for touch scrolling behavior.
@@ -78,6 +80,8 @@ pub struct CursorState {
     touch_scroll_config: TouchScrollConfig,
     /// If true, the cursor position will be cleared on the next frame.
     clear_position_on_next_frame: bool,
+    /// Whether a phased wheel or trackpad gesture is in progress.
+    wheel_gesture_active: bool,
 }
 
 impl CursorState {
@@ -150,6 +154,34 @@ impl CursorState {
         self.update_position(None);
         self.touch_points.clear();
         self.clear_position_on_next_frame = false;
+        self.wheel_gesture_active = false;
+    }
+
+    /// Resolves the phase of a wheel event from the platform touch phase.
+    ///
+    /// Platforms report inertial scrolling as `Moved` events that follow the
+    /// `Ended` event of the finger gesture; those are reported as
+    /// [`ScrollPhase::Momentum`].
+    pub(crate) fn resolve_wheel_phase(
+        &mut self,
+        delta: winit::event::MouseScrollDelta,
+        phase: winit::event::TouchPhase,
+    ) -> ScrollPhase {
+        if matches!(delta, winit::event::MouseScrollDelta::LineDelta(..)) {
+            return ScrollPhase::Discrete;
+        }
+        match phase {
+            winit::event::TouchPhase::Started => {
+                self.wheel_gesture_active = true;
+                ScrollPhase::Started
+            }
+            winit::event::TouchPhase::Moved if self.wheel_gesture_active => ScrollPhase::Changed,
+            winit::event::TouchPhase::Moved => ScrollPhase::Momentum,
+            winit::event::TouchPhase::Ended | winit::event::TouchPhase::Cancelled => {
+                self.wheel_gesture_active = false;
+                ScrollPhase::Ended
+            }
+        }
     }
 
     /// Returns the current cursor position, if any.
@@ -176,22 +208,28 @@ impl CursorState {
     /// * `position` - Initial position of the touch in pixel coordinates
     pub fn handle_touch_start(&mut self, touch_id: u64, position: PxPosition) {
         self.clear_position_on_next_frame = false;
-        let now = Instant::now();
+        let now = time::now();
 
         self.touch_points.insert(
             touch_id,
             TouchPointState {
                 last_position: position,
                 last_update_time: now,
-                generated_scroll_event: false,
             },
         );
         self.update_position(position);
+        // Report the touch position first so per-pointer recognizers know
+        // where each touch landed.
+        self.push_event(PointerChange {
+            timestamp: now,
+            pointer_id: touch_id,
+            content: CursorEventContent::Moved(position),
+            consumed: false,
+        });
         let press_event = PointerChange {
             timestamp: now,
             pointer_id: touch_id,
             content: CursorEventContent::Pressed(PressKeyEventType::Left),
-            gesture_state: GestureState::TapCandidate,
             consumed: false,
         };
         self.push_event(press_event);
@@ -217,14 +255,13 @@ impl CursorState {
         touch_id: u64,
         current_position: PxPosition,
     ) -> Option<PointerChange> {
-        let now = Instant::now();
+        let now = time::now();
         self.update_position(current_position);
 
         self.push_event(PointerChange {
             timestamp: now,
             pointer_id: touch_id,
             content: CursorEventContent::Moved(current_position),
-            gesture_state: GestureState::TapCandidate,
             consumed: false,
         });
 
@@ -240,8 +277,6 @@ impl CursorState {
             touch_state.last_update_time = now;
 
             if move_distance >= self.touch_scroll_config.min_move_threshold {
-                touch_state.generated_scroll_event = true;
-
                 // Return a scroll event for immediate feedback.
                 return Some(PointerChange {
                     timestamp: now,
@@ -251,8 +286,8 @@ impl CursorState {
                         delta_y,
                         unit: ScrollDeltaUnit::Pixel,
                         source: ScrollEventSource::Touch,
+                        phase: ScrollPhase::Changed,
                     }),
-                    gesture_state: GestureState::Dragged,
                     consumed: false,
                 });
             }
@@ -262,32 +297,20 @@ impl CursorState {
 
     /// Handles the end of a touch gesture and emits a release event.
     ///
-    /// This method processes the end of a touch interaction by:
-    /// - Determining whether the gesture was a drag
-    /// - Generating a release event
-    /// - Cleaning up touch point tracking
+    /// This method processes the end of a touch interaction by generating a
+    /// release event and cleaning up touch point tracking.
     ///
     /// # Arguments
     ///
     /// * `touch_id` - Unique identifier for the touch point that ended
     pub fn handle_touch_end(&mut self, touch_id: u64) {
-        let now = Instant::now();
-        let mut was_drag = false;
-
-        if let Some(touch_state) = self.touch_points.get_mut(&touch_id) {
-            was_drag |= touch_state.generated_scroll_event;
-        }
+        let now = time::now();
 
         self.touch_points.remove(&touch_id);
         let release_event = PointerChange {
             timestamp: now,
             pointer_id: touch_id,
             content: CursorEventContent::Released(PressKeyEventType::Left),
-            gesture_state: if was_drag {
-                GestureState::Dragged
-            } else {
-                GestureState::TapCandidate
-            },
             consumed: false,
         };
         self.push_event(release_event);
@@ -310,13 +333,10 @@ pub struct PointerChange {
     /// Pointer identifier for this input stream.
     pub pointer_id: PointerId,
     /// The specific type and data of this pointer change.
-    pub content: CursorEventContent,
-    /// Classification of the gesture associated with this event.
     ///
-    /// Events originating from touch scrolling will mark this as
-    /// [`GestureState::Dragged`], allowing downstream components to
-    /// distinguish tap candidates from scroll gestures.
-    pub gesture_state: GestureState,
+    /// Use the recognizers in [`crate::gesture`] to resolve taps, drags and
+    /// other gestures from a stream of changes.
+    pub content: CursorEventContent,
     /// Whether this change has been consumed by a handler.
     pub(crate) consumed: bool,
 }
@@ -339,6 +359,7 @@ impl PointerChange {
 /// with positive values typically indicating rightward/downward movement
 /// and negative values indicating leftward/upward movement.
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub struct ScrollEventContent {
     /// Horizontal scroll distance in pixels.
     pub delta_x: f32,
@@ -348,6 +369,9 @@ pub struct ScrollEventContent {
     pub unit: ScrollDeltaUnit,
     /// The input source that produced the scroll event.
     pub source: ScrollEventSource,
+    /// Gesture phase of the scroll, used to tell finger-driven trackpad
+    /// scrolling apart from inertial momentum.
+    pub phase: ScrollPhase,
 }
 
 /// Enumeration of all possible cursor event types.
@@ -365,16 +389,32 @@ pub enum CursorEventContent {
     Released(PressKeyEventType),
     /// A scroll action occurred (mouse wheel or touch drag).
     Scroll(ScrollEventContent),
+    /// A touchpad pinch gesture changed the magnification.
+    Pinch(PinchEventContent),
+    /// A touchpad rotation gesture.
+    Rotate(RotateEventContent),
+    /// A touchpad smart-magnify gesture, such as a two-finger double tap on
+    /// macOS.
+    SmartMagnify,
 }
 
-/// Describes the high-level gesture classification of a cursor event.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub enum GestureState {
-    /// Indicates the event is part of a potential tap/click interaction.
-    #[default]
-    TapCandidate,
-    /// Indicates the event happened during a drag/scroll gesture.
-    Dragged,
+/// Touchpad pinch gesture data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PinchEventContent {
+    /// Magnification delta; positive values zoom in and `0.0` means no
+    /// change.
+    pub delta: f32,
+    /// Gesture phase reported by the platform.
+    pub phase: ScrollPhase,
+}
+
+/// Touchpad rotation gesture data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RotateEventContent {
+    /// Rotation delta in radians; positive values rotate clockwise.
+    pub delta: f32,
+    /// Gesture phase reported by the platform.
+    pub phase: ScrollPhase,
 }
 
 impl CursorEventContent {
@@ -402,7 +442,9 @@ impl CursorEventContent {
             winit::event::MouseButton::Left => PressKeyEventType::Left,
             winit::event::MouseButton::Right => PressKeyEventType::Right,
             winit::event::MouseButton::Middle => PressKeyEventType::Middle,
-            _ => return None, // Ignore other buttons
+            winit::event::MouseButton::Back => PressKeyEventType::Back,
+            winit::event::MouseButton::Forward => PressKeyEventType::Forward,
+            winit::event::MouseButton::Other(button) => PressKeyEventType::Other(button),
         };
         let state = match state {
             winit::event::ElementState::Pressed => Self::Pressed(event_type),
@@ -426,6 +468,18 @@ impl CursorEventContent {
     /// A `CursorEventContent::Scroll` event with raw line or pixel delta
     /// values.
     pub fn from_scroll_event(delta: winit::event::MouseScrollDelta) -> Self {
+        Self::from_scroll_event_with_phase(delta, ScrollPhase::Discrete)
+    }
+
+    /// Creates a scroll event from winit mouse wheel events with an explicit
+    /// gesture phase.
+    ///
+    /// Use this for precise trackpad deltas whose phase distinguishes active
+    /// scrolling from momentum.
+    pub fn from_scroll_event_with_phase(
+        delta: winit::event::MouseScrollDelta,
+        phase: ScrollPhase,
+    ) -> Self {
         let (delta_x, delta_y, unit) = match delta {
             winit::event::MouseScrollDelta::LineDelta(x, y) => (x, y, ScrollDeltaUnit::Line),
             winit::event::MouseScrollDelta::PixelDelta(delta) => {
@@ -438,12 +492,14 @@ impl CursorEventContent {
             delta_y,
             unit,
             source: ScrollEventSource::Wheel,
+            phase,
         })
     }
 }
 
 /// Represents the different types of cursor buttons or touch interactions.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum PressKeyEventType {
     /// The primary mouse button (typically left button) or primary touch.
     Left,
@@ -451,6 +507,43 @@ pub enum PressKeyEventType {
     Right,
     /// The middle mouse button (typically scroll wheel click).
     Middle,
+    /// The back mouse button (typically a side button).
+    Back,
+    /// The forward mouse button (typically a side button).
+    Forward,
+    /// Any other mouse button, identified by the platform button index.
+    Other(u16),
+}
+
+/// Gesture phase of a scroll or touchpad gesture event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ScrollPhase {
+    /// A discrete step without gesture phases, such as a notched mouse wheel.
+    Discrete,
+    /// Fingers started scrolling on a trackpad.
+    Started,
+    /// Scrolling continues while fingers are down, or a touch drag moved.
+    Changed,
+    /// Fingers lifted or the gesture was cancelled.
+    Ended,
+    /// Inertial scrolling generated by the platform after fingers lifted.
+    Momentum,
+}
+
+impl ScrollPhase {
+    /// Returns whether the event was produced by platform momentum.
+    pub fn is_momentum(self) -> bool {
+        self == Self::Momentum
+    }
+
+    pub(crate) fn from_touch_phase(phase: winit::event::TouchPhase) -> Self {
+        match phase {
+            winit::event::TouchPhase::Started => Self::Started,
+            winit::event::TouchPhase::Moved => Self::Changed,
+            winit::event::TouchPhase::Ended | winit::event::TouchPhase::Cancelled => Self::Ended,
+        }
+    }
 }
 
 /// Indicates the input source for a scroll event.
@@ -470,3 +563,366 @@ pub enum ScrollDeltaUnit {
     /// Delta is expressed in pixels.
     Pixel,
 }
+
+/// Window of recent samples used to estimate pointer velocity.
+pub const VELOCITY_SAMPLE_WINDOW: Duration = Duration::from_millis(90);
+/// Time without new samples after which a tracked gesture is considered
+/// released.
+pub const VELOCITY_IDLE_CUTOFF: Duration = Duration::from_millis(65);
+/// Maximum fling velocity in pixels per second.
+pub const MAX_FLING_VELOCITY: f32 = 6000.0;
+/// Minimum velocity in pixels per second required to start a fling.
+pub const FLING_START_THRESHOLD: f32 = 50.0;
+
+/// Clamps a velocity vector to [`MAX_FLING_VELOCITY`], mapping non-finite
+/// values to zero.
+pub fn clamp_fling_velocity(velocity_x: f32, velocity_y: f32) -> (f32, f32) {
+    if !velocity_x.is_finite() || !velocity_y.is_finite() {
+        return (0.0, 0.0);
+    }
+
+    let magnitude_sq = velocity_x * velocity_x + velocity_y * velocity_y;
+    if !magnitude_sq.is_finite() {
+        return (0.0, 0.0);
+    }
+
+    let magnitude = magnitude_sq.sqrt();
+    if magnitude > MAX_FLING_VELOCITY {
+        let scale = MAX_FLING_VELOCITY / magnitude;
+        return (velocity_x * scale, velocity_y * scale);
+    }
+
+    (velocity_x, velocity_y)
+}
+
+/// Estimates the velocity of a dragged pointer from its recent movement.
+///
+/// Samples are weighted towards the most recent movement and the estimate
+/// fades out when the pointer has been still for [`VELOCITY_IDLE_CUTOFF`], so
+/// releasing after a pause does not fling.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct VelocityTracker {
+    samples: VecDeque<(Instant, f32, f32)>,
+    last_sample_time: Option<Instant>,
+}
+
+impl VelocityTracker {
+    /// Creates an empty tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a movement delta in pixels at the given time.
+    pub fn add_delta(&mut self, timestamp: Instant, delta_x: f32, delta_y: f32) {
+        let Some(last_sample_time) = self.last_sample_time.replace(timestamp) else {
+            return;
+        };
+        let delta_time = timestamp.duration_since(last_sample_time).as_secs_f32();
+        if delta_time <= 0.0 {
+            return;
+        }
+
+        let (velocity_x, velocity_y) =
+            clamp_fling_velocity(delta_x / delta_time, delta_y / delta_time);
+        self.samples.push_back((timestamp, velocity_x, velocity_y));
+        self.prune(timestamp);
+    }
+
+    /// Returns the estimated velocity in pixels per second at `now`, or
+    /// `None` when there are no recent samples.
+    pub fn velocity(&mut self, now: Instant) -> Option<(f32, f32)> {
+        self.prune(now);
+        let last_sample_time = self.last_sample_time?;
+        if self.samples.is_empty() {
+            return None;
+        }
+
+        let window_secs = VELOCITY_SAMPLE_WINDOW.as_secs_f32().max(f32::EPSILON);
+        let mut weighted_sum_x = 0.0f32;
+        let mut weighted_sum_y = 0.0f32;
+        let mut total_weight = 0.0f32;
+        for &(timestamp, velocity_x, velocity_y) in &self.samples {
+            let age_secs = now
+                .duration_since(timestamp)
+                .as_secs_f32()
+                .clamp(0.0, window_secs);
+            let weight = (window_secs - age_secs).max(0.0);
+            if weight > 0.0 {
+                weighted_sum_x += velocity_x * weight;
+                weighted_sum_y += velocity_y * weight;
+                total_weight += weight;
+            }
+        }
+
+        if total_weight <= f32::EPSILON {
+            self.samples.clear();
+            return None;
+        }
+
+        let idle_secs = now.duration_since(last_sample_time).as_secs_f32();
+        let damping = (1.0 - idle_secs / VELOCITY_IDLE_CUTOFF.as_secs_f32()).clamp(0.0, 1.0);
+        Some(clamp_fling_velocity(
+            weighted_sum_x / total_weight * damping,
+            weighted_sum_y / total_weight * damping,
+        ))
+    }
+
+    /// Returns the velocity to fling with at `now`, or `None` when it is
+    /// below [`FLING_START_THRESHOLD`].
+    pub fn fling_velocity(&mut self, now: Instant) -> Option<(f32, f32)> {
+        self.velocity(now).filter(|(velocity_x, velocity_y)| {
+            (velocity_x * velocity_x + velocity_y * velocity_y).sqrt() > FLING_START_THRESHOLD
+        })
+    }
+
+    /// Returns whether no sample was recorded for [`VELOCITY_IDLE_CUTOFF`].
+    pub fn is_idle(&self, now: Instant) -> bool {
+        self.last_sample_time
+            .is_some_and(|last| now.duration_since(last) >= VELOCITY_IDLE_CUTOFF)
+    }
+
+    /// Clears all samples.
+    pub fn reset(&mut self) {
+        self.samples.clear();
+        self.last_sample_time = None;
+    }
+
+    fn prune(&mut self, now: Instant) {
+        while let Some(&(timestamp, _, _)) = self.samples.front() {
+            if now.duration_since(timestamp) > VELOCITY_SAMPLE_WINDOW {
+                self.samples.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+/// Exponentially decaying fling motion.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FlingDecay {
+    velocity_x: f32,
+    velocity_y: f32,
+    decay_constant: f32,
+    min_velocity: f32,
+}
+
+impl FlingDecay {
+    /// Default exponential decay rate per second.
+    pub const DEFAULT_DECAY_CONSTANT: f32 = 5.0;
+    /// Default velocity in pixels per second below which a fling stops.
+    pub const DEFAULT_MIN_VELOCITY: f32 = 10.0;
+
+    /// Starts a fling with an initial velocity in pixels per second.
+    pub fn new(velocity_x: f32, velocity_y: f32) -> Self {
+        let (velocity_x, velocity_y) = clamp_fling_velocity(velocity_x, velocity_y);
+        Self {
+            velocity_x,
+            velocity_y,
+            decay_constant: Self::DEFAULT_DECAY_CONSTANT,
+            min_velocity: Self::DEFAULT_MIN_VELOCITY,
+        }
+    }
+
+    /// Sets the exponential decay rate per second; larger values stop sooner.
+    pub fn with_decay_constant(mut self, decay_constant: f32) -> Self {
+        self.decay_constant = decay_constant.max(f32::EPSILON);
+        self
+    }
+
+    /// Returns the current velocity in pixels per second.
+    pub fn velocity(&self) -> (f32, f32) {
+        (self.velocity_x, self.velocity_y)
+    }
+
+    /// Returns the remaining travel distance if the fling runs to rest.
+    pub fn projected_distance(&self) -> (f32, f32) {
+        (
+            self.velocity_x / self.decay_constant,
+            self.velocity_y / self.decay_constant,
+        )
+    }
+
+    /// Advances the fling by `delta_secs` and returns the distance travelled.
+    pub fn step(&mut self, delta_secs: f32) -> (f32, f32) {
+        if delta_secs <= 0.0 {
+            return (0.0, 0.0);
+        }
+        let distance = (self.velocity_x * delta_secs, self.velocity_y * delta_secs);
+        let decay = (-self.decay_constant * delta_secs).exp();
+        self.velocity_x *= decay;
+        self.velocity_y *= decay;
+        distance
+    }
+
+    /// Stops horizontal motion, typically after hitting an edge.
+    pub fn stop_x(&mut self) {
+        self.velocity_x = 0.0;
+    }
+
+    /// Stops vertical motion, typically after hitting an edge.
+    pub fn stop_y(&mut self) {
+        self.velocity_y = 0.0;
+    }
+
+    /// Returns whether the fling slowed below its minimum velocity.
+    pub fn is_finished(&self) -> bool {
+        self.velocity_x.abs() < self.min_velocity && self.velocity_y.abs() < self.min_velocity
+    }
+}
+
+/// Maps a raw overscroll distance to a damped rubber-band offset.
+///
+/// The result approaches `extent` asymptotically, so dragging past an edge
+/// feels increasingly stiff.
+pub fn rubber_band(overscroll: f32, extent: f32) -> f32 {
+    const RUBBER_BAND_COEFFICIENT: f32 = 0.55;
+
+    if extent <= 0.0 || !overscroll.is_finite() {
+        return 0.0;
+    }
+    let magnitude = overscroll.abs();
+    let damped = (1.0 - 1.0 / (magnitude * RUBBER_BAND_COEFFICIENT / extent + 1.0)) * extent;
+    damped.copysign(overscroll)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steady_drag_reports_its_velocity() {
+        let mut tracker = VelocityTracker::new();
+        let start = Instant::now();
+        for frame in 0..=5 {
+            tracker.add_delta(start + Duration::from_millis(frame * 10), 0.0, 10.0);
+        }
+        let now = start + Duration::from_millis(50);
+        let (velocity_x, velocity_y) = tracker.velocity(now).expect("recent samples");
+        assert!(velocity_x.abs() < 1e-3);
+        assert!((velocity_y - 1000.0).abs() < 1.0);
+        assert!(tracker.fling_velocity(now).is_some());
+    }
+
+    #[test]
+    fn pausing_before_release_cancels_the_fling() {
+        let mut tracker = VelocityTracker::new();
+        let start = Instant::now();
+        tracker.add_delta(start, 0.0, 10.0);
+        tracker.add_delta(start + Duration::from_millis(10), 0.0, 10.0);
+        let later = start + Duration::from_millis(10) + VELOCITY_IDLE_CUTOFF;
+        assert!(tracker.is_idle(later));
+        assert_eq!(tracker.fling_velocity(later), None);
+    }
+
+    #[test]
+    fn fling_decays_to_rest() {
+        let mut fling = FlingDecay::new(1000.0, 0.0);
+        let (distance_x, _) = fling.step(0.1);
+        assert!((distance_x - 100.0).abs() < 1e-3);
+        assert!(fling.velocity().0 < 1000.0);
+        for _ in 0..100 {
+            fling.step(0.1);
+        }
+        assert!(fling.is_finished());
+    }
+
+    fn pixels(y: f64) -> winit::event::MouseScrollDelta {
+        winit::event::MouseScrollDelta::PixelDelta(winit::dpi::PhysicalPosition::new(0.0, y))
+    }
+
+    #[test]
+    fn line_deltas_are_discrete_in_any_phase() {
+        let mut state = CursorState::default();
+        let lines = winit::event::MouseScrollDelta::LineDelta(0.0, 1.0);
+        for phase in [
+            winit::event::TouchPhase::Started,
+            winit::event::TouchPhase::Moved,
+            winit::event::TouchPhase::Ended,
+        ] {
+            assert_eq!(
+                state.resolve_wheel_phase(lines, phase),
+                ScrollPhase::Discrete
+            );
+        }
+        // A line delta never opens a gesture, so later pixel moves are momentum.
+        assert_eq!(
+            state.resolve_wheel_phase(pixels(4.0), winit::event::TouchPhase::Moved),
+            ScrollPhase::Momentum
+        );
+    }
+
+    #[test]
+    fn moves_after_a_trackpad_gesture_ends_are_momentum() {
+        use winit::event::TouchPhase;
+
+        let mut state = CursorState::default();
+        let phases: Vec<_> = [
+            TouchPhase::Started,
+            TouchPhase::Moved,
+            TouchPhase::Moved,
+            TouchPhase::Ended,
+            TouchPhase::Moved,
+            TouchPhase::Moved,
+            TouchPhase::Started,
+            TouchPhase::Moved,
+            TouchPhase::Cancelled,
+        ]
+        .into_iter()
+        .map(|phase| state.resolve_wheel_phase(pixels(4.0), phase))
+        .collect();
+        assert_eq!(
+            phases,
+            [
+                ScrollPhase::Started,
+                ScrollPhase::Changed,
+                ScrollPhase::Changed,
+                ScrollPhase::Ended,
+                ScrollPhase::Momentum,
+                ScrollPhase::Momentum,
+                ScrollPhase::Started,
+                ScrollPhase::Changed,
+                ScrollPhase::Ended,
+            ]
+        );
+        assert!(phases[4].is_momentum() && !phases[1].is_momentum());
+    }
+
+    #[test]
+    fn clearing_the_state_ends_a_wheel_gesture() {
+        let mut state = CursorState::default();
+        state.resolve_wheel_phase(pixels(4.0), winit::event::TouchPhase::Started);
+        state.clear();
+        assert_eq!(
+            state.resolve_wheel_phase(pixels(4.0), winit::event::TouchPhase::Moved),
+            ScrollPhase::Momentum
+        );
+    }
+
+    #[test]
+    fn extra_mouse_buttons_are_reported() {
+        use winit::event::{ElementState, MouseButton};
+
+        assert_eq!(
+            CursorEventContent::from_press_event(ElementState::Pressed, MouseButton::Back),
+            Some(CursorEventContent::Pressed(PressKeyEventType::Back))
+        );
+        assert_eq!(
+            CursorEventContent::from_press_event(ElementState::Released, MouseButton::Forward),
+            Some(CursorEventContent::Released(PressKeyEventType::Forward))
+        );
+        assert_eq!(
+            CursorEventContent::from_press_event(ElementState::Pressed, MouseButton::Other(9)),
+            Some(CursorEventContent::Pressed(PressKeyEventType::Other(9)))
+        );
+    }
+
+    #[test]
+    fn rubber_band_is_damped_and_keeps_direction() {
+        assert_eq!(rubber_band(0.0, 100.0), 0.0);
+        let pulled = rubber_band(50.0, 100.0);
+        assert!(pulled > 0.0 && pulled < 50.0);
+        assert_eq!(rubber_band(-50.0, 100.0), -pulled);
+        assert!(rubber_band(10_000.0, 100.0) < 100.0);
+    }
+}
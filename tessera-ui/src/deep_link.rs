@@ -0,0 +1,164 @@
+//! Inbox for URIs that open the app from outside, such as custom-scheme links.
+//!
+//! ## Usage
+//!
+//! Collect links delivered by the platform at startup or while running, and
+//! hand them to a router that turns them into destinations.
+
+use std::cell::RefCell;
+
+use rustc_hash::FxHashSet as HashSet;
+
+use crate::runtime::{
+    RuntimePhase, current_phase, current_replay_boundary_instance_key_from_scope,
+    record_replay_boundary_invalidation_for_instance_key,
+};
+
+#[derive(Default)]
+struct DeepLinkState {
+    pending: Vec<String>,
+    readers: HashSet<u64>,
+}
+
+thread_local! {
+    static DEEP_LINK_STATE: RefCell<DeepLinkState> = RefCell::new(DeepLinkState::default());
+}
+
+fn with_state<R>(f: impl FnOnce(&mut DeepLinkState) -> R) -> R {
+    DEEP_LINK_STATE.with(|state| f(&mut state.borrow_mut()))
+}
+
+/// Queues a deep link for the app.
+///
+/// The entry point queues links found on the command line and in the Android
+/// launch intent. Call this for links the platform delivers later, for example
+/// from a single-instance handoff or `onNewIntent`. Components that took links
+/// during their last build are recomposed.
+///
+/// # Examples
+///
+/// ```
+/// use tessera_ui::deep_link::{push_deep_link, take_deep_links};
+///
+/// push_deep_link("app://user/42");
+/// assert_eq!(take_deep_links(), vec!["app://user/42".to_string()]);
+/// ```
+pub fn push_deep_link(uri: impl Into<String>) {
+    let readers: Vec<u64> = with_state(|state| {
+        state.pending.push(uri.into());
+        state.readers.iter().copied().collect()
+    });
+    for reader in readers {
+        record_replay_boundary_invalidation_for_instance_key(reader);
+    }
+}
+
+/// Takes every queued deep link, oldest first.
+///
+/// When called while building a component, that component is recomposed
+/// whenever a new link is queued.
+pub fn take_deep_links() -> Vec<String> {
+    let reader = matches!(current_phase(), Some(RuntimePhase::Build))
+        .then(current_replay_boundary_instance_key_from_scope)
+        .flatten();
+    with_state(|state| {
+        if let Some(reader) = reader {
+            state.readers.insert(reader);
+        }
+        std::mem::take(&mut state.pending)
+    })
+}
+
+/// Queues the command line arguments that use one of `schemes`.
+///
+/// Desktop platforms launch the registered handler of a custom scheme with the
+/// link as an argument.
+#[cfg(all(not(target_os = "android"), not(target_family = "wasm")))]
+pub(crate) fn push_deep_links_from_args(schemes: &[String]) {
+    for arg in std::env::args().skip(1) {
+        if uri_has_scheme(&arg, schemes) {
+            push_deep_link(arg);
+        }
+    }
+}
+
+/// Queues the data URI of the intent that launched the activity.
+#[cfg(target_os = "android")]
+pub(crate) fn push_deep_link_from_launch_intent(
+    android_app: &winit::platform::android::activity::AndroidApp,
+    schemes: &[String],
+) {
+    match launch_intent_uri(android_app) {
+        Ok(Some(uri)) if uri_has_scheme(&uri, schemes) => push_deep_link(uri),
+        Ok(_) => {}
+        Err(err) => tracing::warn!("Failed to read launch intent data: {err}"),
+    }
+}
+
+#[cfg(target_os = "android")]
+fn launch_intent_uri(
+    android_app: &winit::platform::android::activity::AndroidApp,
+) -> Result<Option<String>, jni::errors::Error> {
+    use jni::{
+        JavaVM,
+        objects::{JObject, JString},
+    };
+
+    // SAFETY: The pointers come from the running `AndroidApp` and stay valid
+    // for the lifetime of the activity.
+    let vm = unsafe { JavaVM::from_raw(android_app.vm_as_ptr().cast()) }?;
+    let mut env = vm.attach_current_thread()?;
+    let activity = unsafe { JObject::from_raw(android_app.activity_as_ptr().cast()) };
+
+    let intent = env
+        .call_method(&activity, "getIntent", "()Landroid/content/Intent;", &[])?
+        .l()?;
+    if intent.is_null() {
+        return Ok(None);
+    }
+    let data = env
+        .call_method(&intent, "getDataString", "()Ljava/lang/String;", &[])?
+        .l()?;
+    if data.is_null() {
+        return Ok(None);
+    }
+    Ok(Some(env.get_string(&JString::from(data))?.into()))
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn uri_has_scheme(uri: &str, schemes: &[String]) -> bool {
+    uri.split_once(':').is_some_and(|(scheme, _)| {
+        schemes
+            .iter()
+            .any(|expected| expected.eq_ignore_ascii_case(scheme))
+    })
+}
+
+pub(crate) fn remove_deep_link_read_dependencies(instance_keys: &HashSet<u64>) {
+    if instance_keys.is_empty() {
+        return;
+    }
+    with_state(|state| {
+        state
+            .readers
+            .retain(|instance_key| !instance_keys.contains(instance_key));
+    });
+}
+
+pub(crate) fn reset_deep_link_read_dependencies() {
+    with_state(|state| state.readers.clear());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scheme_match_ignores_case_and_requires_separator() {
+        let schemes = vec!["app".to_string()];
+        assert!(uri_has_scheme("app://user/42", &schemes));
+        assert!(uri_has_scheme("APP:settings", &schemes));
+        assert!(!uri_has_scheme("https://example.com", &schemes));
+        assert!(!uri_has_scheme("--verbose", &schemes));
+    }
+}
@@ -0,0 +1,360 @@
+//! Remote devtools over a local WebSocket.
+//!
+//! ## Usage
+//!
+//! Attach an external devtools app or a browser page to a running app to
+//! browse its component tree, saved state, frame timings and navigation.
+//!
+//! When the `devtools` feature is enabled, desktop and Android apps serve a
+//! WebSocket on `127.0.0.1`, port [`DEFAULT_PORT`] unless the
+//! `TESSERA_DEVTOOLS_PORT` environment variable or
+//! [`TesseraConfig::devtools_port`](crate::renderer::TesseraConfig) picks
+//! another one. On Android, forward the port to the host first:
+//!
+//! ```sh
+//! adb forward tcp:9330 tcp:9330
+//! ```
+//!
+//! ## Protocol
+//!
+//! Every message is a JSON object whose `type` field names it. A client is
+//! greeted with:
+//!
+//! ```json
+//! {"type":"hello","version":1}
+//! ```
+//!
+//! After every rendered frame, clients receive its timings in milliseconds:
+//!
+//! ```json
+//! {"type":"frame","frame":42,"build_ms":0.8,"draw_ms":1.2,"render_ms":3.4}
+//! ```
+//!
+//! Clients send requests, which are answered after the next frame:
+//!
+//! - `{"type":"get_tree"}` is answered with a `tree` message listing the
+//!   components in preorder. `parent` is the index of the parent in `nodes`;
+//!   `rect` and `constraint` are in physical pixels, and a `null` maximum is
+//!   unbounded.
+//!
+//!   ```json
+//!   {"type":"tree","frame":42,"nodes":[{"fn_name":"app","instance_key":7,
+//!    "parent":null,"rect":{"x":0,"y":0,"width":800,"height":600},
+//!    "constraint":{"width":{"min":0,"max":800},"height":{"min":0,"max":
+//! 600}}}]}   ```
+//!
+//! - `{"type":"get_state"}` is answered with `{"type":"state","values":{...}}`,
+//!   holding the values kept with
+//!   [`remember_saveable`](crate::saveable::remember_saveable) by their keys.
+//! - `{"type":"highlight","instance_key":7}` shows the inspector overlay with
+//!   that component selected; a `null` key clears the selection.
+//!
+//! Malformed or unknown requests are answered with
+//! `{"type":"error","message":"..."}`.
+//!
+//! Libraries report their own events with [`post_event`], which clients
+//! receive as `{"type":"event","kind":"navigation","data":{...}}`.
+//! `tessera-shard` reports router navigation this way when its `devtools`
+//! feature is enabled.
+
+use std::{
+    io,
+    net::{Ipv4Addr, TcpListener, TcpStream},
+    sync::{
+        OnceLock,
+        mpsc::{self, Sender},
+    },
+    thread,
+    time::Duration,
+};
+
+use parking_lot::Mutex;
+use serde_json::{Value, json};
+use tracing::{debug, info, warn};
+use tungstenite::Message;
+
+use crate::{
+    component_tree::AxisConstraint,
+    inspector::{self, InspectedNode},
+    runtime::TesseraRuntime,
+};
+
+/// Port the devtools server listens on by default.
+pub const DEFAULT_PORT: u16 = 9330;
+/// Version sent in the `hello` message, bumped on breaking protocol changes.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// How long a client connection waits for requests before sending queued
+/// messages.
+const POLL_INTERVAL: Duration = Duration::from_millis(15);
+
+struct Client {
+    id: u64,
+    outgoing: Sender<String>,
+}
+
+struct Hub {
+    clients: Mutex<Vec<Client>>,
+    /// Requests waiting for the next frame, by client id.
+    requests: Mutex<Vec<(u64, Value)>>,
+    /// Schedules a frame so requests are answered while the app is idle.
+    wake: Box<dyn Fn() + Send + Sync>,
+}
+
+static HUB: OnceLock<Hub> = OnceLock::new();
+
+/// Starts serving devtools clients on `port`.
+pub(crate) fn start(port: u16, wake: impl Fn() + Send + Sync + 'static) {
+    let listener = match TcpListener::bind((Ipv4Addr::LOCALHOST, port)) {
+        Ok(listener) => listener,
+        Err(err) => {
+            warn!("Failed to start devtools server on port {port}: {err}");
+            return;
+        }
+    };
+    let hub = Hub {
+        clients: Mutex::new(Vec::new()),
+        requests: Mutex::new(Vec::new()),
+        wake: Box::new(wake),
+    };
+    if HUB.set(hub).is_err() {
+        return;
+    }
+    let spawned = thread::Builder::new()
+        .name("tessera-devtools".into())
+        .spawn(move || {
+            for (id, stream) in (0..).zip(listener.incoming()) {
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                let spawned = thread::Builder::new()
+                    .name(format!("tessera-devtools-{id}"))
+                    .spawn(move || serve_client(id, stream));
+                if let Err(err) = spawned {
+                    warn!("Failed to serve devtools client: {err}");
+                }
+            }
+        });
+    match spawned {
+        Ok(_) => info!("Devtools listening on ws://127.0.0.1:{port}"),
+        Err(err) => warn!("Failed to start devtools server: {err}"),
+    }
+}
+
+fn serve_client(id: u64, stream: TcpStream) {
+    let Some(hub) = HUB.get() else {
+        return;
+    };
+    let mut socket = match tungstenite::accept(stream) {
+        Ok(socket) => socket,
+        Err(err) => {
+            debug!("Devtools handshake failed: {err}");
+            return;
+        }
+    };
+    if let Err(err) = socket.get_mut().set_read_timeout(Some(POLL_INTERVAL)) {
+        warn!("Failed to configure devtools connection: {err}");
+        return;
+    }
+    let (sender, outgoing) = mpsc::channel();
+    let _ = sender.send(json!({ "type": "hello", "version": PROTOCOL_VERSION }).to_string());
+    hub.clients.lock().push(Client {
+        id,
+        outgoing: sender.clone(),
+    });
+    debug!("Devtools client {id} attached");
+
+    'connection: loop {
+        for message in outgoing.try_iter() {
+            if socket.send(Message::text(message)).is_err() {
+                break 'connection;
+            }
+        }
+        match socket.read() {
+            Ok(Message::Text(text)) => match serde_json::from_str(text.as_str()) {
+                Ok(request) => {
+                    hub.requests.lock().push((id, request));
+                    (hub.wake)();
+                }
+                Err(err) => {
+                    let message = error_value(&format!("invalid request: {err}"));
+                    let _ = sender.send(message.to_string());
+                }
+            },
+            Ok(Message::Close(_)) => break,
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(err))
+                if matches!(
+                    err.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) => {}
+            Err(_) => break,
+        }
+    }
+
+    hub.clients.lock().retain(|client| client.id != id);
+    debug!("Devtools client {id} detached");
+}
+
+/// Sends an event to every attached client.
+///
+/// Clients receive it as `{"type":"event","kind":kind,"data":data}`. Does
+/// nothing when no client is attached, so it is cheap to call freely.
+pub fn post_event(kind: &str, data: Value) {
+    let Some(hub) = HUB.get() else {
+        return;
+    };
+    let mut clients = hub.clients.lock();
+    if clients.is_empty() {
+        return;
+    }
+    let message = json!({ "type": "event", "kind": kind, "data": data }).to_string();
+    clients.retain(|client| client.outgoing.send(message.clone()).is_ok());
+}
+
+/// Timings of a rendered frame.
+pub(crate) struct FrameTimings {
+    pub frame_idx: u64,
+    pub build: Duration,
+    pub draw: Duration,
+    pub render: Duration,
+}
+
+/// Reports the frame to attached clients and answers their requests.
+///
+/// Must run on the UI thread once the frame is rendered.
+pub(crate) fn end_frame(timings: FrameTimings) {
+    let Some(hub) = HUB.get() else {
+        return;
+    };
+    if hub.clients.lock().is_empty() {
+        hub.requests.lock().clear();
+        return;
+    }
+    let frame_idx = timings.frame_idx;
+    post(
+        hub,
+        None,
+        json!({
+            "type": "frame",
+            "frame": frame_idx,
+            "build_ms": timings.build.as_secs_f64() * 1000.0,
+            "draw_ms": timings.draw.as_secs_f64() * 1000.0,
+            "render_ms": timings.render.as_secs_f64() * 1000.0,
+        }),
+    );
+
+    let requests = std::mem::take(&mut *hub.requests.lock());
+    for (client_id, request) in requests {
+        let response = match request.get("type").and_then(Value::as_str) {
+            Some("get_tree") => {
+                let nodes =
+                    TesseraRuntime::with(|runtime| inspector::snapshot(&runtime.component_tree));
+                json!({
+                    "type": "tree",
+                    "frame": frame_idx,
+                    "nodes": nodes.iter().map(node_json).collect::<Vec<_>>(),
+                })
+            }
+            Some("get_state") => {
+                let values: Value = serde_json::from_str(&crate::saveable::save_snapshot())
+                    .unwrap_or_else(|_| json!({}));
+                json!({ "type": "state", "values": values })
+            }
+            Some("highlight") => match request.get("instance_key") {
+                Some(Value::Null) | None => {
+                    inspector::select(None);
+                    (hub.wake)();
+                    continue;
+                }
+                Some(key) => match key.as_u64() {
+                    Some(key) => {
+                        inspector::select(Some(key));
+                        (hub.wake)();
+                        continue;
+                    }
+                    None => error_value("`instance_key` must be an unsigned integer or null"),
+                },
+            },
+            Some(other) => error_value(&format!("unknown request type `{other}`")),
+            None => error_value("request has no `type`"),
+        };
+        post(hub, Some(client_id), response);
+    }
+}
+
+/// Sends `message` to one client, or to all of them.
+fn post(hub: &Hub, client_id: Option<u64>, message: Value) {
+    let message = message.to_string();
+    hub.clients.lock().retain(|client| {
+        if client_id.is_some_and(|id| id != client.id) {
+            return true;
+        }
+        client.outgoing.send(message.clone()).is_ok()
+    });
+}
+
+fn error_value(message: &str) -> Value {
+    json!({ "type": "error", "message": message })
+}
+
+fn node_json(node: &InspectedNode) -> Value {
+    json!({
+        "fn_name": node.fn_name,
+        "instance_key": node.instance_key,
+        "parent": node.parent,
+        "rect": node.rect.map(|rect| json!({
+            "x": rect.x.0,
+            "y": rect.y.0,
+            "width": rect.width.0,
+            "height": rect.height.0,
+        })),
+        "constraint": node.constraint.map(|constraint| json!({
+            "width": axis_json(constraint.width),
+            "height": axis_json(constraint.height),
+        })),
+    })
+}
+
+fn axis_json(axis: AxisConstraint) -> Value {
+    json!({ "min": axis.min.0, "max": axis.max.map(|max| max.0) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Px, PxRect, component_tree::Constraint};
+
+    #[test]
+    fn tree_nodes_serialize_with_unbounded_constraints_as_null() {
+        let node = InspectedNode {
+            fn_name: "column".to_string(),
+            instance_key: 7,
+            parent: Some(0),
+            rect: Some(PxRect::new(Px(1), Px(2), Px(30), Px(40))),
+            constraint: Some(Constraint {
+                width: AxisConstraint {
+                    min: Px(0),
+                    max: Some(Px(800)),
+                },
+                height: AxisConstraint {
+                    min: Px(0),
+                    max: None,
+                },
+            }),
+        };
+        assert_eq!(
+            node_json(&node),
+            json!({
+                "fn_name": "column",
+                "instance_key": 7,
+                "parent": 0,
+                "rect": { "x": 1, "y": 2, "width": 30, "height": 40 },
+                "constraint": {
+                    "width": { "min": 0, "max": 800 },
+                    "height": { "min": 0, "max": null },
+                },
+            })
+        );
+    }
+}
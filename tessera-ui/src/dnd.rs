@@ -0,0 +1,664 @@
+//! Drag-and-drop sessions between drag sources, drop targets and the OS.
+//!
+//! ## Usage
+//!
+//! Reorder items inside an app or accept files dropped onto the window.
+
+use std::{any::Any, cell::RefCell, fmt, path::PathBuf, sync::Arc};
+
+use crate::{
+    CallbackWith, CursorEventContent, MOUSE_POINTER_ID, Modifier, PointerEventPass, PointerId,
+    PointerInput, PointerInputModifierNode, PressKeyEventType, Px, PxPosition, RenderSlot,
+    modifier::ModifierCapabilityExt as _,
+};
+
+/// Distance a pressed pointer must travel before a drag session starts.
+const DRAG_START_SLOP_PX: f32 = 8.0;
+
+/// Data carried by a drag session.
+///
+/// Payloads are either an in-app value of any `Send + Sync` type or a list of
+/// file paths dropped from the operating system.
+#[derive(Clone)]
+pub struct DragPayload {
+    repr: PayloadRepr,
+}
+
+#[derive(Clone)]
+enum PayloadRepr {
+    Value(Arc<dyn Any + Send + Sync>),
+    Files(Arc<Vec<PathBuf>>),
+}
+
+impl DragPayload {
+    /// Creates a payload carrying an in-app value.
+    pub fn new<T>(value: T) -> Self
+    where
+        T: Any + Send + Sync,
+    {
+        Self {
+            repr: PayloadRepr::Value(Arc::new(value)),
+        }
+    }
+
+    /// Creates a payload carrying file paths.
+    pub fn from_files(files: impl IntoIterator<Item = PathBuf>) -> Self {
+        Self {
+            repr: PayloadRepr::Files(Arc::new(files.into_iter().collect())),
+        }
+    }
+
+    /// Returns the carried value if it has type `T`.
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        match &self.repr {
+            PayloadRepr::Value(value) => value.downcast_ref::<T>(),
+            PayloadRepr::Files(_) => None,
+        }
+    }
+
+    /// Returns whether the payload carries a value of type `T`.
+    pub fn is<T: Any>(&self) -> bool {
+        self.get::<T>().is_some()
+    }
+
+    /// Returns the carried file paths, if this is a file payload.
+    pub fn files(&self) -> Option<&[PathBuf]> {
+        match &self.repr {
+            PayloadRepr::Value(_) => None,
+            PayloadRepr::Files(files) => Some(files.as_slice()),
+        }
+    }
+
+    fn push_file(&mut self, path: PathBuf) {
+        match &mut self.repr {
+            PayloadRepr::Files(files) => Arc::make_mut(files).push(path),
+            PayloadRepr::Value(_) => *self = Self::from_files([path]),
+        }
+    }
+}
+
+impl fmt::Debug for DragPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.repr {
+            PayloadRepr::Value(_) => f.write_str("DragPayload::Value(..)"),
+            PayloadRepr::Files(files) => f.debug_tuple("DragPayload::Files").field(files).finish(),
+        }
+    }
+}
+
+/// Where a drag session originated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DragOrigin {
+    /// The session was started by a [`DragSource`] inside the app.
+    App,
+    /// The session was started by the operating system, e.g. a file drag.
+    System,
+}
+
+/// Drop delivered to a [`DropTarget`].
+#[derive(Clone, Debug)]
+pub struct DropEvent {
+    /// Payload carried by the session.
+    pub payload: DragPayload,
+    /// Drop position relative to the target node.
+    pub position: PxPosition,
+    /// Where the session originated.
+    pub origin: DragOrigin,
+}
+
+/// Result reported to the drag source when its session ends.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DropOutcome {
+    /// A drop target accepted the payload.
+    Dropped,
+    /// The session ended without an accepting target.
+    Canceled,
+}
+
+/// Preview content for the active session, positioned at the pointer.
+#[derive(Clone, Copy, PartialEq)]
+pub struct DragPreview {
+    /// Content rendered for the dragged item.
+    pub content: RenderSlot,
+    /// Pointer position in window coordinates.
+    pub position: PxPosition,
+}
+
+/// Drag source configuration for [`DragAndDropModifierExt::drag_source`].
+#[derive(Clone, Copy)]
+pub struct DragSource {
+    payload: CallbackWith<(), Option<DragPayload>>,
+    preview: Option<RenderSlot>,
+    on_drag_end: Option<CallbackWith<DropOutcome>>,
+}
+
+impl DragSource {
+    /// Creates a drag source whose payload is produced when a drag starts.
+    ///
+    /// Returning `None` from `payload` suppresses the drag.
+    pub fn new(payload: impl Into<CallbackWith<(), Option<DragPayload>>>) -> Self {
+        Self {
+            payload: payload.into(),
+            preview: None,
+            on_drag_end: None,
+        }
+    }
+
+    /// Sets content rendered under the pointer while dragging.
+    pub fn preview(mut self, preview: RenderSlot) -> Self {
+        self.preview = Some(preview);
+        self
+    }
+
+    /// Sets the callback invoked with the session outcome.
+    pub fn on_drag_end(mut self, on_drag_end: impl Into<CallbackWith<DropOutcome>>) -> Self {
+        self.on_drag_end = Some(on_drag_end.into());
+        self
+    }
+}
+
+/// Drop target configuration for [`DragAndDropModifierExt::drop_target`].
+#[derive(Clone, Copy)]
+pub struct DropTarget {
+    accepts: Option<CallbackWith<DragPayload, bool>>,
+    on_drop: CallbackWith<DropEvent, bool>,
+    on_hover_changed: Option<CallbackWith<bool>>,
+}
+
+impl DropTarget {
+    /// Creates a drop target.
+    ///
+    /// `on_drop` returns whether the payload was taken; a `false` return
+    /// reports [`DropOutcome::Canceled`] to the source.
+    pub fn new(on_drop: impl Into<CallbackWith<DropEvent, bool>>) -> Self {
+        Self {
+            accepts: None,
+            on_drop: on_drop.into(),
+            on_hover_changed: None,
+        }
+    }
+
+    /// Restricts the payloads this target reacts to.
+    pub fn accepts(mut self, accepts: impl Into<CallbackWith<DragPayload, bool>>) -> Self {
+        self.accepts = Some(accepts.into());
+        self
+    }
+
+    /// Sets the callback invoked when an accepted drag enters or leaves.
+    pub fn on_hover_changed(mut self, on_hover_changed: impl Into<CallbackWith<bool>>) -> Self {
+        self.on_hover_changed = Some(on_hover_changed.into());
+        self
+    }
+}
+
+#[derive(Clone, Copy)]
+struct DropCandidate {
+    instance_key: u64,
+    position: PxPosition,
+    target: DropTarget,
+}
+
+struct ActiveDrag {
+    pointer_id: PointerId,
+    origin: DragOrigin,
+    payload: DragPayload,
+    source_key: Option<u64>,
+    preview: Option<RenderSlot>,
+    on_drag_end: Option<CallbackWith<DropOutcome>>,
+    position: Option<PxPosition>,
+    hovered: Option<DropCandidate>,
+    candidate: Option<DropCandidate>,
+    released: bool,
+    canceled: bool,
+}
+
+impl ActiveDrag {
+    fn new(pointer_id: PointerId, origin: DragOrigin, payload: DragPayload) -> Self {
+        Self {
+            pointer_id,
+            origin,
+            payload,
+            source_key: None,
+            preview: None,
+            on_drag_end: None,
+            position: None,
+            hovered: None,
+            candidate: None,
+            released: false,
+            canceled: false,
+        }
+    }
+}
+
+struct PendingPress {
+    pointer_id: PointerId,
+    source_key: u64,
+    position: PxPosition,
+}
+
+#[derive(Default)]
+struct DragAndDropState {
+    pending: Vec<PendingPress>,
+    active: Option<ActiveDrag>,
+    system_dropped: bool,
+}
+
+thread_local! {
+    static DND_STATE: RefCell<DragAndDropState> = RefCell::new(DragAndDropState::default());
+}
+
+fn with_state<R>(f: impl FnOnce(&mut DragAndDropState) -> R) -> R {
+    DND_STATE.with(|state| f(&mut state.borrow_mut()))
+}
+
+/// Returns whether a drag session is in progress.
+pub fn is_dragging() -> bool {
+    with_state(|state| state.active.is_some())
+}
+
+/// Returns the payload of the active drag session.
+pub fn current_payload() -> Option<DragPayload> {
+    with_state(|state| state.active.as_ref().map(|drag| drag.payload.clone()))
+}
+
+/// Returns the preview of the active drag session, if it has one.
+///
+/// The preview disappears as soon as the session is released or canceled.
+pub fn drag_preview() -> Option<DragPreview> {
+    with_state(|state| {
+        let drag = state
+            .active
+            .as_ref()
+            .filter(|drag| !drag.released && !drag.canceled)?;
+        Some(DragPreview {
+            content: drag.preview?,
+            position: drag.position?,
+        })
+    })
+}
+
+/// Cancels the active drag session at the end of the current frame.
+pub fn cancel_drag() {
+    with_state(|state| {
+        if let Some(drag) = state.active.as_mut() {
+            drag.canceled = true;
+        }
+    });
+}
+
+/// Starts or extends a system file drag hovering over the window.
+pub(crate) fn handle_system_file_hovered(path: PathBuf) {
+    with_state(|state| match state.active.as_mut() {
+        Some(drag) if drag.origin == DragOrigin::System => drag.payload.push_file(path),
+        Some(_) => {}
+        None => {
+            state.system_dropped = false;
+            state.active = Some(ActiveDrag::new(
+                MOUSE_POINTER_ID,
+                DragOrigin::System,
+                DragPayload::from_files([path]),
+            ));
+        }
+    });
+}
+
+/// Records a file dropped onto the window by the system.
+///
+/// Platforms deliver one event per file; the first drop replaces the hovered
+/// file list so the payload matches exactly what was dropped.
+pub(crate) fn handle_system_file_dropped(path: PathBuf) {
+    with_state(|state| {
+        let first_drop = !state.system_dropped;
+        state.system_dropped = true;
+        match state.active.as_mut() {
+            Some(drag) if drag.origin == DragOrigin::System => {
+                if first_drop {
+                    drag.payload = DragPayload::from_files([path]);
+                } else {
+                    drag.payload.push_file(path);
+                }
+                drag.released = true;
+            }
+            Some(_) => {}
+            None => {
+                let mut drag = ActiveDrag::new(
+                    MOUSE_POINTER_ID,
+                    DragOrigin::System,
+                    DragPayload::from_files([path]),
+                );
+                drag.released = true;
+                state.active = Some(drag);
+            }
+        }
+    });
+}
+
+/// Cancels a system file drag that left the window.
+pub(crate) fn handle_system_file_hover_cancelled() {
+    with_state(|state| {
+        if let Some(drag) = state
+            .active
+            .as_mut()
+            .filter(|drag| drag.origin == DragOrigin::System)
+        {
+            drag.canceled = true;
+        }
+    });
+}
+
+/// Resolves hover changes and drops after pointer dispatch.
+///
+/// `is_live` reports whether a node instance is still in the tree. A session
+/// whose source node is gone is canceled, since the source would never see
+/// the release of its captured pointer.
+pub(crate) fn finish_frame(cursor_position: Option<PxPosition>, is_live: impl Fn(u64) -> bool) {
+    let Some((candidate, previous, finished)) = with_state(|state| {
+        let drag = state.active.as_mut()?;
+        if drag.origin == DragOrigin::System || drag.pointer_id == MOUSE_POINTER_ID {
+            drag.position = cursor_position.or(drag.position);
+        }
+        if drag
+            .source_key
+            .is_some_and(|source_key| !is_live(source_key))
+        {
+            drag.canceled = true;
+        }
+        let candidate = drag.candidate.take().filter(|_| !drag.canceled);
+        let previous = drag.hovered;
+        drag.hovered = candidate;
+        let finished = if drag.released || drag.canceled {
+            state.system_dropped = false;
+            state.active.take()
+        } else {
+            None
+        };
+        Some((candidate, previous, finished))
+    }) else {
+        return;
+    };
+
+    // Handlers of the previous frame may belong to nodes removed since then.
+    let call_live = |callback: Option<CallbackWith<bool>>, value: bool| {
+        if let Some(callback) = callback.filter(CallbackWith::is_alive) {
+            callback.call(value);
+        }
+    };
+    let previous_key = previous.map(|hovered| hovered.instance_key);
+    let candidate_key = candidate.map(|hovered| hovered.instance_key);
+    if previous_key != candidate_key {
+        call_live(
+            previous.and_then(|hovered| hovered.target.on_hover_changed),
+            false,
+        );
+        call_live(
+            candidate.and_then(|hovered| hovered.target.on_hover_changed),
+            true,
+        );
+    }
+
+    let Some(drag) = finished else {
+        return;
+    };
+    let outcome = match candidate {
+        Some(candidate) if !drag.canceled && candidate.target.on_drop.is_alive() => {
+            let accepted = candidate.target.on_drop.call(DropEvent {
+                payload: drag.payload,
+                position: candidate.position,
+                origin: drag.origin,
+            });
+            if accepted {
+                DropOutcome::Dropped
+            } else {
+                DropOutcome::Canceled
+            }
+        }
+        _ => DropOutcome::Canceled,
+    };
+    call_live(
+        candidate.and_then(|hovered| hovered.target.on_hover_changed),
+        false,
+    );
+    if let Some(on_drag_end) = drag.on_drag_end.filter(CallbackWith::is_alive) {
+        on_drag_end.call(outcome);
+    }
+}
+
+/// Drag-and-drop extensions for [`Modifier`].
+pub trait DragAndDropModifierExt {
+    /// Makes this node start a drag session when pressed and moved past the
+    /// touch slop.
+    ///
+    /// The dragging pointer is captured by the node until it is released.
+    /// Removing the node cancels the session, so hide a dragged item instead
+    /// of removing it while it is dragged.
+    fn drag_source(self, source: DragSource) -> Modifier;
+
+    /// Makes this node receive drops from in-app and system drag sessions.
+    ///
+    /// The innermost accepting target under the pointer wins.
+    fn drop_target(self, target: DropTarget) -> Modifier;
+}
+
+struct DragSourceModifierNode {
+    source: DragSource,
+}
+
+impl PointerInputModifierNode for DragSourceModifierNode {
+    fn on_pointer_input(&self, mut input: PointerInput<'_>) {
+        if input.pass != PointerEventPass::Main {
+            return;
+        }
+        let Some(source_key) = input.node_instance_key() else {
+            return;
+        };
+        let within_bounds = input.cursor_position_rel.is_some_and(|position| {
+            position.x >= Px::ZERO
+                && position.y >= Px::ZERO
+                && position.x < input.computed_data.width
+                && position.y < input.computed_data.height
+        });
+        let cursor_position = input.cursor_position_abs();
+
+        let mut started = Vec::new();
+        let mut dragging_pointer = with_state(|state| {
+            state
+                .active
+                .as_ref()
+                .filter(|drag| drag.source_key == Some(source_key))
+                .map(|drag| drag.pointer_id)
+        });
+        for change in input.pointer_changes.iter().filter(|c| !c.is_consumed()) {
+            let pointer_id = change.pointer_id;
+            match change.content {
+                CursorEventContent::Pressed(PressKeyEventType::Left) if within_bounds => {
+                    let Some(position) = cursor_position else {
+                        continue;
+                    };
+                    with_state(|state| {
+                        state.pending.retain(|press| press.pointer_id != pointer_id);
+                        state.pending.push(PendingPress {
+                            pointer_id,
+                            source_key,
+                            position,
+                        });
+                    });
+                }
+                CursorEventContent::Moved(position) if dragging_pointer.is_none() => {
+                    let past_slop = with_state(|state| {
+                        state.active.is_none()
+                            && state.pending.iter().any(|press| {
+                                press.pointer_id == pointer_id
+                                    && press.source_key == source_key
+                                    && press.position.distance_to(position) > DRAG_START_SLOP_PX
+                            })
+                    });
+                    if past_slop {
+                        started.push((pointer_id, position));
+                    }
+                }
+                CursorEventContent::Released(_) => {
+                    with_state(|state| {
+                        state.pending.retain(|press| press.pointer_id != pointer_id);
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        if let Some((pointer_id, position)) = started.into_iter().next()
+            && let Some(payload) = self.source.payload.call(())
+        {
+            with_state(|state| {
+                state.pending.retain(|press| press.pointer_id != pointer_id);
+                let mut drag = ActiveDrag::new(pointer_id, DragOrigin::App, payload);
+                drag.source_key = Some(source_key);
+                drag.preview = self.source.preview;
+                drag.on_drag_end = self.source.on_drag_end;
+                drag.position = Some(position);
+                state.active = Some(drag);
+            });
+            input.capture_pointer(pointer_id);
+            dragging_pointer = Some(pointer_id);
+        }
+
+        let Some(pointer_id) = dragging_pointer else {
+            return;
+        };
+        let mut released = false;
+        let mut position = None;
+        for change in input
+            .pointer_changes
+            .iter_mut()
+            .filter(|change| change.pointer_id == pointer_id)
+        {
+            match change.content {
+                CursorEventContent::Moved(moved) => position = Some(moved),
+                CursorEventContent::Released(_) => released = true,
+                _ => {}
+            }
+            change.consume();
+        }
+        with_state(|state| {
+            if let Some(drag) = state.active.as_mut() {
+                drag.position = position.or(drag.position);
+                drag.released |= released;
+            }
+        });
+    }
+}
+
+struct DropTargetModifierNode {
+    target: DropTarget,
+}
+
+impl PointerInputModifierNode for DropTargetModifierNode {
+    fn on_pointer_input(&self, input: PointerInput<'_>) {
+        if input.pass != PointerEventPass::Main {
+            return;
+        }
+        let Some(instance_key) = input.node_instance_key() else {
+            return;
+        };
+        let payload = with_state(|state| {
+            let drag = state.active.as_ref()?;
+            let position = drag.position?;
+            if drag.candidate.is_some() || drag.source_key == Some(instance_key) {
+                return None;
+            }
+            Some((drag.payload.clone(), position))
+        });
+        let Some((payload, drag_position)) = payload else {
+            return;
+        };
+        let Some(cursor_position) = input.cursor_position_rel else {
+            return;
+        };
+        // Captured drags are routed to the source only, so hit-test the drag
+        // position against this node's bounds directly.
+        let origin = input
+            .cursor_position_abs()
+            .map_or(PxPosition::ZERO, |abs| abs - cursor_position);
+        let position = drag_position - origin;
+        let within_bounds = position.x >= Px::ZERO
+            && position.y >= Px::ZERO
+            && position.x < input.computed_data.width
+            && position.y < input.computed_data.height;
+        if !within_bounds {
+            return;
+        }
+        if let Some(accepts) = self.target.accepts
+            && !accepts.call(payload)
+        {
+            return;
+        }
+        with_state(|state| {
+            if let Some(drag) = state.active.as_mut()
+                && drag.candidate.is_none()
+            {
+                drag.candidate = Some(DropCandidate {
+                    instance_key,
+                    position,
+                    target: self.target,
+                });
+            }
+        });
+    }
+}
+
+impl DragAndDropModifierExt for Modifier {
+    fn drag_source(self, source: DragSource) -> Modifier {
+        self.push_pointer_input(DragSourceModifierNode { source })
+    }
+
+    fn drop_target(self, target: DropTarget) -> Modifier {
+        self.push_pointer_input(DropTargetModifierNode { target })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payload_downcasts_to_its_own_type_only() {
+        let payload = DragPayload::new(42_u32);
+        assert_eq!(payload.get::<u32>(), Some(&42));
+        assert!(payload.is::<u32>());
+        assert!(!payload.is::<i32>());
+        assert!(payload.files().is_none());
+    }
+
+    #[test]
+    fn file_payload_collects_pushed_paths() {
+        let mut payload = DragPayload::from_files([PathBuf::from("a.png")]);
+        payload.push_file(PathBuf::from("b.png"));
+        assert_eq!(
+            payload.files(),
+            Some([PathBuf::from("a.png"), PathBuf::from("b.png")].as_slice())
+        );
+        assert!(!payload.is::<Vec<PathBuf>>());
+    }
+
+    #[test]
+    fn system_drop_replaces_hovered_files_and_ends_session() {
+        handle_system_file_hovered(PathBuf::from("hovered.txt"));
+        assert!(is_dragging());
+        handle_system_file_dropped(PathBuf::from("dropped.txt"));
+        handle_system_file_dropped(PathBuf::from("second.txt"));
+        let payload = current_payload().expect("drop keeps session until frame end");
+        assert_eq!(
+            payload.files(),
+            Some([PathBuf::from("dropped.txt"), PathBuf::from("second.txt")].as_slice())
+        );
+        finish_frame(None, |_| true);
+        assert!(!is_dragging());
+    }
+
+    #[test]
+    fn cancelled_system_hover_ends_session() {
+        handle_system_file_hovered(PathBuf::from("file.txt"));
+        handle_system_file_hover_cancelled();
+        finish_frame(None, |_| true);
+        assert!(!is_dragging());
+    }
+}
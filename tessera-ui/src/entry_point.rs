@@ -22,9 +22,11 @@ use winit::platform::android::activity::AndroidApp;
 
 /// Builder for application entry configuration and startup.
 pub struct EntryPoint {
-    entry: Box<dyn Fn()>,
+    pub(crate) entry: Box<dyn Fn()>,
     registry: EntryRegistry,
     config: TesseraConfig,
+    deep_link_schemes: Vec<String>,
+    log_filter: Option<String>,
 }
 
 impl EntryPoint {
@@ -34,6 +36,8 @@ impl EntryPoint {
             entry: Box::new(entry),
             registry: EntryRegistry::new(),
             config: TesseraConfig::default(),
+            deep_link_schemes: Vec::new(),
+            log_filter: None,
         }
     }
 
@@ -67,10 +71,51 @@ impl EntryPoint {
         self
     }
 
+    /// Accepts launch links that use `scheme`, e.g. `"app"` for `app://...`.
+    ///
+    /// Matching links from the command line on desktop and from the launch
+    /// intent on Android are queued in [`crate::deep_link`]. The scheme itself
+    /// must still be registered with the operating system by the installer or
+    /// the Android manifest.
+    pub fn deep_link_scheme(mut self, scheme: impl Into<String>) -> Self {
+        self.deep_link_schemes.push(scheme.into());
+        self
+    }
+
+    /// Sets the log filter used when `RUST_LOG` is not set.
+    ///
+    /// Uses the `tracing_subscriber::EnvFilter` syntax, e.g.
+    /// `"warn,my_app=debug"`.
+    pub fn log_filter(mut self, filter: impl Into<String>) -> Self {
+        self.log_filter = Some(filter.into());
+        self
+    }
+
     /// Runs the entry point on desktop platforms.
-    #[cfg(all(not(target_os = "android"), not(target_family = "wasm")))]
+    #[cfg(all(
+        not(target_os = "android"),
+        not(target_os = "ios"),
+        not(target_family = "wasm")
+    ))]
     pub fn run_desktop(self) -> Result<(), winit::error::EventLoopError> {
-        init_tracing();
+        init_tracing(self.log_filter.as_deref());
+        init_deadlock_detection();
+        crate::deep_link::push_deep_links_from_args(&self.deep_link_schemes);
+        #[cfg(feature = "hot-reload")]
+        let entry = crate::hot_reload::reloadable_entry(self.entry);
+        #[cfg(not(feature = "hot-reload"))]
+        let entry = self.entry;
+        Renderer::run_with_config(entry, self.registry.finish(), self.config)
+    }
+
+    /// Runs the entry point on iOS.
+    ///
+    /// Called from the `start_app` function that the Xcode project's `main`
+    /// invokes. UIKit owns the main thread afterwards, so this only returns if
+    /// the event loop fails to start.
+    #[cfg(target_os = "ios")]
+    pub fn run_ios(self) -> Result<(), winit::error::EventLoopError> {
+        init_tracing(self.log_filter.as_deref());
         init_deadlock_detection();
         Renderer::run_with_config(self.entry, self.registry.finish(), self.config)
     }
@@ -78,15 +123,18 @@ impl EntryPoint {
     /// Runs the entry point on web platforms.
     #[cfg(target_family = "wasm")]
     pub fn run_web(self) -> Result<(), winit::error::EventLoopError> {
-        init_tracing();
+        init_tracing(self.log_filter.as_deref());
         Renderer::run_web_with_config(self.entry, self.registry.finish(), self.config)
     }
 
     /// Runs the entry point on Android.
     #[cfg(target_os = "android")]
     pub fn run_android(self, android_app: AndroidApp) -> Result<(), winit::error::EventLoopError> {
-        init_tracing();
+        init_tracing(self.log_filter.as_deref());
         init_deadlock_detection();
+        crate::deep_link::push_deep_link_from_launch_intent(&android_app, &self.deep_link_schemes);
+        #[cfg(feature = "saveable")]
+        crate::saveable::restore_from_app_storage(&android_app);
         Renderer::run_with_config(self.entry, self.registry.finish(), android_app, self.config)
     }
 }
@@ -118,32 +166,38 @@ fn init_deadlock_detection() {
     });
 }
 
-fn init_tracing() {
+/// Parses the configured log filter, falling back to `default` when it is
+/// missing or invalid.
+fn configured_filter(log_filter: Option<&str>, default: &str) -> tracing_subscriber::EnvFilter {
+    log_filter
+        .and_then(|filter| {
+            tracing_subscriber::EnvFilter::try_new(filter)
+                .inspect_err(|err| eprintln!("Invalid log filter `{filter}`: {err}"))
+                .ok()
+        })
+        .unwrap_or_else(|| tracing_subscriber::EnvFilter::new(default))
+}
+
+fn init_tracing(log_filter: Option<&str>) {
     #[cfg(target_family = "wasm")]
     {
         let _ = tracing_subscriber::fmt()
-            .with_max_level(tracing::Level::INFO)
+            .with_env_filter(configured_filter(log_filter, "info"))
             .without_time()
             .try_init();
     }
 
     #[cfg(target_os = "android")]
     {
-        let _ = tracing_subscriber::fmt()
-            .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
-            .with_max_level(tracing::Level::INFO)
-            .try_init();
+        let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+            .unwrap_or_else(|_| configured_filter(log_filter, "info"));
+        let _ = tracing_subscriber::fmt().with_env_filter(filter).try_init();
     }
 
     #[cfg(all(not(target_os = "android"), not(target_family = "wasm")))]
     {
-        let filter = match tracing_subscriber::EnvFilter::try_from_default_env() {
-            Ok(filter) => filter,
-            Err(_) => match tracing_subscriber::EnvFilter::try_new("error,tessera_ui=info") {
-                Ok(filter) => filter,
-                Err(_) => tracing_subscriber::EnvFilter::new("error"),
-            },
-        };
+        let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+            .unwrap_or_else(|_| configured_filter(log_filter, "error,tessera_ui=info"));
 
         let _ = tracing_subscriber::fmt()
             .pretty()
@@ -152,3 +206,37 @@ fn init_tracing() {
             .try_init();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[crate::entry(
+        crate,
+        deep_link_schemes = ["app"],
+        log_filter = "warn",
+        desktop(deep_link_schemes = ["app-desktop"]),
+        android(deep_link_schemes = ["app-android"]),
+        ios(deep_link_schemes = ["app-ios"]),
+    )]
+    fn run() -> EntryPoint {
+        EntryPoint::new(|| {}).deep_link_scheme("body")
+    }
+
+    #[test]
+    fn entry_attribute_applies_shared_and_platform_settings() {
+        let entry = run();
+
+        assert_eq!(entry.log_filter.as_deref(), Some("warn"));
+        #[cfg(all(
+            not(target_os = "android"),
+            not(target_os = "ios"),
+            not(target_family = "wasm")
+        ))]
+        assert_eq!(entry.deep_link_schemes, ["body", "app", "app-desktop"]);
+        #[cfg(target_os = "android")]
+        assert_eq!(entry.deep_link_schemes, ["body", "app", "app-android"]);
+        #[cfg(target_os = "ios")]
+        assert_eq!(entry.deep_link_schemes, ["body", "app", "app-ios"]);
+    }
+}
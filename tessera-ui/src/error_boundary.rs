@@ -0,0 +1,129 @@
+//! Errors returned by component bodies.
+//!
+//! ## Usage
+//!
+//! Let pages that load files or parse input propagate failures with `?` and
+//! show a fallback screen instead of unwrapping.
+
+use std::fmt;
+
+use crate::{RenderSlot, RenderSlotWith, provide_context, remember, runtime::State, tessera};
+
+/// An error returned by a component body.
+///
+/// Components declared as `fn page() -> Result<(), E>` report their `Err` to
+/// the nearest enclosing [`error_boundary`] as a `ComponentError`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComponentError {
+    component: &'static str,
+    message: String,
+}
+
+impl ComponentError {
+    /// Create an error for `component` described by `error`.
+    pub fn new(component: &'static str, error: impl fmt::Display) -> Self {
+        Self {
+            component,
+            message: error.to_string(),
+        }
+    }
+
+    /// Name of the component that failed.
+    pub fn component(&self) -> &'static str {
+        self.component
+    }
+
+    /// The reported error, formatted with `Display`.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ComponentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` failed: {}", self.component, self.message)
+    }
+}
+
+impl std::error::Error for ComponentError {}
+
+struct ErrorBoundaryScope {
+    error: State<Option<ComponentError>>,
+}
+
+/// Renders `content` until a component inside it fails, then `fallback`.
+///
+/// The first reported error is kept for as long as the boundary stays in the
+/// tree, so content that fails while building is not retried every frame.
+/// Without a `fallback`, nothing is rendered once content failed.
+///
+/// # Examples
+///
+/// ```
+/// use tessera_ui::{
+///     error_boundary::{ComponentError, error_boundary},
+///     tessera,
+/// };
+///
+/// #[tessera]
+/// fn settings_page() -> Result<(), std::io::Error> {
+///     let _config = std::fs::read_to_string("settings.toml")?;
+///     Ok(())
+/// }
+///
+/// #[tessera]
+/// fn app() {
+///     error_boundary(|| settings_page()).fallback(|error: ComponentError| {
+///         eprintln!("{error}");
+///     });
+/// }
+/// ```
+#[tessera(crate)]
+pub fn error_boundary(
+    #[children] content: RenderSlot,
+    #[slot] fallback: Option<RenderSlotWith<ComponentError>>,
+) {
+    let error = remember(|| None::<ComponentError>);
+    if let Some(error) = error.get() {
+        if let Some(fallback) = fallback {
+            fallback.render(error);
+        }
+        return;
+    }
+    provide_context(|| ErrorBoundaryScope { error }, || content.render());
+}
+
+/// Reports that `component` failed with `error`.
+///
+/// The nearest enclosing [`error_boundary`] switches to its fallback; without
+/// one, the error is logged. `#[tessera]` components returning `Result` call
+/// this for their `Err`.
+///
+/// # Panics
+///
+/// Must be called during a component build.
+pub fn report_component_error(component: &'static str, error: impl fmt::Display) {
+    let error = ComponentError::new(component, error);
+    match crate::use_context::<ErrorBoundaryScope>() {
+        Some(scope) => scope.with(|scope| {
+            if scope.error.with(Option::is_none) {
+                scope.error.set(Some(error));
+            }
+        }),
+        None => tracing::error!("{error}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn component_error_names_the_failed_component() {
+        let error = ComponentError::new("settings_page", "file not found");
+
+        assert_eq!(error.component(), "settings_page");
+        assert_eq!(error.message(), "file not found");
+        assert_eq!(error.to_string(), "`settings_page` failed: file not found");
+    }
+}
@@ -22,8 +22,9 @@ use crate::{
     px::PxSize,
     runtime::{
         TesseraRuntime, focus_read_subscribers, focus_requester_read_subscribers,
-        has_persistent_focus_handle, record_replay_boundary_invalidation_for_instance_key,
-        track_focus_read_dependency, track_focus_requester_read_dependency,
+        focus_visibility_read_subscribers, has_persistent_focus_handle,
+        record_replay_boundary_invalidation_for_instance_key, track_focus_read_dependency,
+        track_focus_requester_read_dependency, track_focus_visibility_read_dependency,
     },
 };
 
@@ -333,9 +334,11 @@ impl FocusRevealRequest {
 /// let props = FocusProperties::new()
 ///     .can_focus(true)
 ///     .skip_traversal(false)
+///     .traversal_index(1)
 ///     .next(next)
 ///     .right(right);
 ///
+/// assert_eq!(props.traversal_index, 1);
 /// assert_eq!(props.next, Some(next));
 /// assert_eq!(props.right, Some(right));
 /// assert!(props.can_focus);
@@ -349,6 +352,11 @@ pub struct FocusProperties {
     pub can_request_focus: bool,
     /// Whether traversal should skip this node.
     pub skip_traversal: bool,
+    /// Explicit traversal index among siblings.
+    ///
+    /// Lower indices are visited first by `Tab`; siblings with equal indices
+    /// keep their layout placement order.
+    pub traversal_index: i32,
     /// Explicit target for `Next` traversal.
     pub next: Option<FocusRequester>,
     /// Explicit target for `Previous` traversal.
@@ -391,6 +399,12 @@ impl FocusProperties {
         self
     }
 
+    /// Sets the explicit traversal index among siblings.
+    pub fn traversal_index(mut self, traversal_index: i32) -> Self {
+        self.traversal_index = traversal_index;
+        self
+    }
+
     /// Sets the explicit requester for `Next` traversal.
     pub fn next(mut self, requester: FocusRequester) -> Self {
         self.next = Some(requester);
@@ -446,6 +460,7 @@ impl Default for FocusProperties {
             can_focus: true,
             can_request_focus: true,
             skip_traversal: false,
+            traversal_index: 0,
             next: None,
             previous: None,
             up: None,
@@ -801,6 +816,16 @@ impl FocusManager {
     pub fn move_focus(self, direction: FocusDirection) -> bool {
         with_focus_owner_mut(|owner| owner.move_focus(direction))
     }
+
+    /// Returns whether focus should be indicated visually.
+    ///
+    /// Focus becomes visible when it is moved with the keyboard and hidden
+    /// again on the next pointer press, so focus rings only appear for
+    /// keyboard navigation.
+    pub fn is_focus_visible(self) -> bool {
+        track_focus_visibility_read_dependency();
+        with_focus_owner(|owner| owner.focus_visible())
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -919,6 +944,7 @@ pub(crate) struct FocusOwner {
     captured: Option<FocusHandleId>,
     suspended: Option<FocusHandleId>,
     owner_focused: bool,
+    focus_visible: bool,
     pending: Vec<FocusCommand>,
     pending_notifications: Vec<FocusNotification>,
     pending_reveal: Option<FocusHandleId>,
@@ -937,6 +963,7 @@ impl FocusOwner {
             captured: None,
             suspended: None,
             owner_focused: true,
+            focus_visible: false,
             pending: Vec::new(),
             pending_notifications: Vec::new(),
             pending_reveal: None,
@@ -970,6 +997,61 @@ impl FocusOwner {
             .and_then(|attachment| attachment.live_node_id)
     }
 
+    /// Resolves the focus handle attached to `node_id`, falling back to the
+    /// first handle attached inside its subtree.
+    pub(crate) fn handle_id_of_component_node(
+        &self,
+        node_id: NodeId,
+        tree: &ComponentNodeTree,
+    ) -> Option<FocusHandleId> {
+        let mut handles_by_node: HashMap<NodeId, FocusHandleId> = HashMap::default();
+        for (&handle_id, node) in &self.nodes {
+            if let Some(live_node_id) = node
+                .attachment
+                .and_then(|attachment| attachment.live_node_id)
+            {
+                handles_by_node.entry(live_node_id).or_insert(handle_id);
+            }
+        }
+        node_id
+            .descendants(tree)
+            .find_map(|candidate| handles_by_node.get(&candidate).copied())
+    }
+
+    /// Moves focus to the node focused by an assistive technology.
+    ///
+    /// The focus is made visible and revealed, just like keyboard navigation,
+    /// so components draw their indicators and scroll the node into view.
+    pub(crate) fn request_accessibility_focus(
+        &mut self,
+        node_id: NodeId,
+        tree: &ComponentNodeTree,
+    ) -> bool {
+        let Some(handle_id) = self.handle_id_of_component_node(node_id, tree) else {
+            return false;
+        };
+        if !self.apply_command(FocusCommand::Request(handle_id)) {
+            return false;
+        }
+        self.set_focus_visible(true);
+        self.pending_reveal = self.active;
+        true
+    }
+
+    /// Reveals the node targeted by an assistive technology without moving
+    /// focus.
+    pub(crate) fn request_reveal_of_component_node(
+        &mut self,
+        node_id: NodeId,
+        tree: &ComponentNodeTree,
+    ) -> bool {
+        let Some(handle_id) = self.handle_id_of_component_node(node_id, tree) else {
+            return false;
+        };
+        self.pending_reveal = Some(handle_id);
+        true
+    }
+
     pub(crate) fn active_handle_id(&self) -> Option<FocusHandleId> {
         self.active
     }
@@ -1040,6 +1122,20 @@ impl FocusOwner {
         }
     }
 
+    pub(crate) fn focus_visible(&self) -> bool {
+        self.focus_visible
+    }
+
+    pub(crate) fn set_focus_visible(&mut self, visible: bool) {
+        if self.focus_visible == visible {
+            return;
+        }
+        self.focus_visible = visible;
+        for reader in focus_visibility_read_subscribers() {
+            record_replay_boundary_invalidation_for_instance_key(reader);
+        }
+    }
+
     pub(crate) fn set_owner_focused(&mut self, focused: bool) {
         if self.owner_focused == focused {
             return;
@@ -1370,13 +1466,14 @@ impl FocusOwner {
     }
 
     fn sort_children_by_traversal_order(&mut self) {
-        let order_by_handle: HashMap<FocusHandleId, (u64, u64)> = self
+        let order_by_handle: HashMap<FocusHandleId, (i32, u64, u64)> = self
             .nodes
             .iter()
             .map(|(&handle_id, node)| {
                 (
                     handle_id,
                     (
+                        node.props.traversal_index,
                         node.attachment
                             .and_then(|attachment| attachment.traversal_order)
                             .unwrap_or(u64::MAX),
@@ -1393,7 +1490,7 @@ impl FocusOwner {
                 order_by_handle
                     .get(child)
                     .copied()
-                    .unwrap_or((u64::MAX, u64::MAX))
+                    .unwrap_or((0, u64::MAX, u64::MAX))
             });
         }
     }
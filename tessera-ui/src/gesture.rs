@@ -0,0 +1,1927 @@
+//! Composable pointer gesture recognizers with disambiguation.
+//!
+//! ## Usage
+//!
+//! Attach a gesture detector to a node to receive taps, double taps, long
+//! presses, pans, pinches and rotations instead of raw pointer changes, or
+//! listen for secondary activation to open context menus.
+
+use std::time::Duration;
+
+use crate::{
+    CallbackWith, CursorEventContent, MOUSE_POINTER_ID, Modifier, PointerChange, PointerEventPass,
+    PointerId, PointerInput, PointerInputModifierNode, PressKeyEventType, Px, PxPosition, PxSize,
+    ScrollDeltaUnit, ScrollEventContent, ScrollEventSource, ScrollPhase, State,
+    modifier::ModifierCapabilityExt as _,
+    time::{self, Instant},
+};
+
+const DEFAULT_SLOP_PX: f32 = 8.0;
+const DEFAULT_DOUBLE_TAP_TIMEOUT: Duration = Duration::from_millis(300);
+const DEFAULT_LONG_PRESS_THRESHOLD: Duration = Duration::from_millis(500);
+
+/// Configuration for tap gesture recognition.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TapSettings {
+    /// Mouse button or press key that starts the gesture.
+    pub button: PressKeyEventType,
+    /// Maximum pointer travel before the tap is canceled.
+    pub slop_px: f32,
+    /// Whether to consume the press event immediately.
+    pub consume_on_press: bool,
+    /// Whether to consume the release event.
+    pub consume_on_release: bool,
+    /// Whether to consume the completed tap event.
+    pub consume_on_tap: bool,
+}
+
+impl Default for TapSettings {
+    fn default() -> Self {
+        Self {
+            button: PressKeyEventType::Left,
+            slop_px: DEFAULT_SLOP_PX,
+            consume_on_press: false,
+            consume_on_release: false,
+            consume_on_tap: false,
+        }
+    }
+}
+
+/// Per-update tap recognition output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct TapResult {
+    /// Whether a press started this update.
+    pub pressed: bool,
+    /// Whether a release happened this update.
+    pub released: bool,
+    /// Whether a full tap gesture completed this update.
+    pub tapped: bool,
+    /// Timestamp for the press, when present.
+    pub press_timestamp: Option<Instant>,
+    /// Timestamp for the release, when present.
+    pub release_timestamp: Option<Instant>,
+}
+
+/// Stateful tap gesture recognizer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TapRecognizer {
+    settings: TapSettings,
+    active_pointer: Option<PointerId>,
+    press_position: Option<PxPosition>,
+    canceled: bool,
+}
+
+impl TapRecognizer {
+    /// Creates a tap recognizer with custom settings.
+    pub fn new(settings: TapSettings) -> Self {
+        Self {
+            settings,
+            active_pointer: None,
+            press_position: None,
+            canceled: false,
+        }
+    }
+
+    /// Updates the recognizer with the current pointer pass and events.
+    pub fn update(
+        &mut self,
+        pass: PointerEventPass,
+        pointer_changes: &mut [PointerChange],
+        cursor_position: Option<PxPosition>,
+        within_bounds: bool,
+    ) -> TapResult {
+        if pass != PointerEventPass::Main {
+            return TapResult::default();
+        }
+
+        let mut result = TapResult::default();
+        for change in pointer_changes.iter_mut() {
+            if change.is_consumed() {
+                continue;
+            }
+            match change.content {
+                CursorEventContent::Pressed(button)
+                    if button == self.settings.button && within_bounds =>
+                {
+                    self.active_pointer = Some(change.pointer_id);
+                    self.press_position = cursor_position;
+                    self.canceled = false;
+                    result.pressed = true;
+                    result.press_timestamp = Some(change.timestamp);
+                    if self.settings.consume_on_press {
+                        change.consume();
+                    }
+                }
+                CursorEventContent::Moved(_) => {
+                    if Some(change.pointer_id) == self.active_pointer
+                        && let Some(start) = self.press_position
+                        && let Some(position) = cursor_position
+                        && start.distance_to(position) > self.settings.slop_px
+                    {
+                        self.canceled = true;
+                    }
+                }
+                CursorEventContent::Scroll(_) if Some(change.pointer_id) == self.active_pointer => {
+                    self.canceled = true;
+                }
+                CursorEventContent::Released(button) if button == self.settings.button => {
+                    if Some(change.pointer_id) != self.active_pointer {
+                        continue;
+                    }
+                    result.released = true;
+                    result.release_timestamp = Some(change.timestamp);
+                    let tapped = within_bounds && !self.canceled;
+                    if tapped {
+                        result.tapped = true;
+                    }
+                    if self.settings.consume_on_release || (self.settings.consume_on_tap && tapped)
+                    {
+                        change.consume();
+                    }
+                    self.reset();
+                }
+                _ => {}
+            }
+        }
+        result
+    }
+
+    /// Cancels the active press so its release does not produce a tap.
+    pub fn cancel(&mut self) {
+        if self.active_pointer.is_some() {
+            self.canceled = true;
+        }
+    }
+
+    fn reset(&mut self) {
+        self.active_pointer = None;
+        self.press_position = None;
+        self.canceled = false;
+    }
+}
+
+impl Default for TapRecognizer {
+    fn default() -> Self {
+        Self::new(TapSettings::default())
+    }
+}
+
+/// Configuration for drag gesture recognition.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DragSettings {
+    /// Minimum pointer travel required to start dragging.
+    pub slop_px: f32,
+    /// Whether drag events should be consumed after dragging starts.
+    pub consume_when_dragging: bool,
+    /// Optional axis lock for drag movement.
+    pub axis: Option<DragAxis>,
+}
+
+impl Default for DragSettings {
+    fn default() -> Self {
+        Self {
+            slop_px: DEFAULT_SLOP_PX,
+            consume_when_dragging: true,
+            axis: None,
+        }
+    }
+}
+
+/// Axis constraint for drag gesture recognition.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DragAxis {
+    /// Horizontal-only dragging.
+    Horizontal,
+    /// Vertical-only dragging.
+    Vertical,
+}
+
+/// Per-update drag recognition output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct DragResult {
+    /// Whether dragging started this update.
+    pub started: bool,
+    /// Whether a drag delta was produced this update.
+    pub updated: bool,
+    /// Whether dragging ended this update.
+    pub ended: bool,
+    /// Horizontal drag delta for this update.
+    pub delta_x: Px,
+    /// Vertical drag delta for this update.
+    pub delta_y: Px,
+}
+
+/// Stateful drag gesture recognizer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DragRecognizer {
+    settings: DragSettings,
+    active_pointer: Option<PointerId>,
+    start_position: Option<PxPosition>,
+    last_position: Option<PxPosition>,
+    dragging: bool,
+}
+
+impl DragRecognizer {
+    /// Creates a drag recognizer with custom settings.
+    pub fn new(settings: DragSettings) -> Self {
+        Self {
+            settings,
+            active_pointer: None,
+            start_position: None,
+            last_position: None,
+            dragging: false,
+        }
+    }
+
+    /// Updates the recognizer with the current pointer pass and events.
+    pub fn update(
+        &mut self,
+        pass: PointerEventPass,
+        pointer_changes: &mut [PointerChange],
+        cursor_position: Option<PxPosition>,
+        within_bounds: bool,
+    ) -> DragResult {
+        if pass != PointerEventPass::Main {
+            return DragResult::default();
+        }
+
+        let mut result = DragResult::default();
+        for change in pointer_changes.iter_mut() {
+            if change.is_consumed() {
+                continue;
+            }
+            match change.content {
+                CursorEventContent::Pressed(PressKeyEventType::Left) if within_bounds => {
+                    self.active_pointer = Some(change.pointer_id);
+                    self.start_position = cursor_position;
+                    self.last_position = cursor_position;
+                    self.dragging = false;
+                }
+                CursorEventContent::Moved(_) => {
+                    if Some(change.pointer_id) != self.active_pointer {
+                        continue;
+                    }
+                    let Some(position) = cursor_position else {
+                        continue;
+                    };
+                    if !self.dragging
+                        && let Some(start) = self.start_position
+                    {
+                        let delta_x = (position.x - start.x).to_f32();
+                        let delta_y = (position.y - start.y).to_f32();
+                        let should_start = match self.settings.axis {
+                            Some(DragAxis::Horizontal) => {
+                                delta_x.abs() > self.settings.slop_px
+                                    && delta_x.abs() >= delta_y.abs()
+                            }
+                            Some(DragAxis::Vertical) => {
+                                delta_y.abs() > self.settings.slop_px
+                                    && delta_y.abs() >= delta_x.abs()
+                            }
+                            None => start.distance_to(position) > self.settings.slop_px,
+                        };
+                        if should_start {
+                            self.dragging = true;
+                            result.started = true;
+                        }
+                    }
+                    if self.dragging {
+                        if let Some(last) = self.last_position {
+                            let raw_dx = position.x - last.x;
+                            let raw_dy = position.y - last.y;
+                            let (dx, dy) = match self.settings.axis {
+                                Some(DragAxis::Horizontal) => (raw_dx, Px::ZERO),
+                                Some(DragAxis::Vertical) => (Px::ZERO, raw_dy),
+                                None => (raw_dx, raw_dy),
+                            };
+                            if dx != Px::ZERO || dy != Px::ZERO {
+                                result.delta_x += dx;
+                                result.delta_y += dy;
+                                result.updated = true;
+                            }
+                        }
+                        self.last_position = Some(position);
+                        if self.settings.consume_when_dragging {
+                            change.consume();
+                        }
+                    }
+                }
+                CursorEventContent::Released(PressKeyEventType::Left) => {
+                    if Some(change.pointer_id) != self.active_pointer {
+                        continue;
+                    }
+                    result.ended = true;
+                    if self.dragging && self.settings.consume_when_dragging {
+                        change.consume();
+                    }
+                    self.reset();
+                }
+                CursorEventContent::Scroll(_) => {}
+                _ => {}
+            }
+        }
+        result
+    }
+
+    /// Replaces the recognizer settings while preserving active gesture state.
+    pub fn set_settings(&mut self, settings: DragSettings) {
+        self.settings = settings;
+    }
+
+    /// Returns whether dragging is currently active.
+    pub fn is_dragging(&self) -> bool {
+        self.dragging
+    }
+
+    /// Returns the pointer tracked since the last press, if any.
+    pub fn active_pointer(&self) -> Option<PointerId> {
+        self.active_pointer
+    }
+
+    /// Abandons the active drag without reporting an end.
+    pub fn cancel(&mut self) {
+        self.reset();
+    }
+
+    fn reset(&mut self) {
+        self.active_pointer = None;
+        self.start_position = None;
+        self.last_position = None;
+        self.dragging = false;
+    }
+}
+
+impl Default for DragRecognizer {
+    fn default() -> Self {
+        Self::new(DragSettings::default())
+    }
+}
+
+/// Configuration for long-press gesture recognition.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LongPressSettings {
+    /// Minimum press duration before the gesture triggers.
+    pub threshold: Duration,
+    /// Maximum pointer travel before the press is canceled.
+    pub slop_px: f32,
+    /// Whether to consume the triggering event.
+    pub consume_on_trigger: bool,
+}
+
+impl Default for LongPressSettings {
+    fn default() -> Self {
+        Self {
+            threshold: DEFAULT_LONG_PRESS_THRESHOLD,
+            slop_px: DEFAULT_SLOP_PX,
+            consume_on_trigger: false,
+        }
+    }
+}
+
+/// Per-update long-press recognition output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct LongPressResult {
+    /// Whether the long press triggered this update.
+    pub triggered: bool,
+    /// Whether the pointer was released this update.
+    pub released: bool,
+}
+
+/// Stateful long-press gesture recognizer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LongPressRecognizer {
+    settings: LongPressSettings,
+    active_pointer: Option<PointerId>,
+    press_position: Option<PxPosition>,
+    press_time: Option<Instant>,
+    canceled: bool,
+    triggered: bool,
+}
+
+impl LongPressRecognizer {
+    /// Creates a long-press recognizer with custom settings.
+    pub fn new(settings: LongPressSettings) -> Self {
+        Self {
+            settings,
+            active_pointer: None,
+            press_position: None,
+            press_time: None,
+            canceled: false,
+            triggered: false,
+        }
+    }
+
+    /// Updates the recognizer with the current pointer pass and events.
+    pub fn update(
+        &mut self,
+        pass: PointerEventPass,
+        pointer_changes: &mut [PointerChange],
+        cursor_position: Option<PxPosition>,
+        within_bounds: bool,
+    ) -> LongPressResult {
+        if pass != PointerEventPass::Main {
+            return LongPressResult::default();
+        }
+
+        let mut result = LongPressResult::default();
+        for change in pointer_changes.iter_mut() {
+            if change.is_consumed() {
+                continue;
+            }
+            match change.content {
+                CursorEventContent::Pressed(PressKeyEventType::Left) if within_bounds => {
+                    self.active_pointer = Some(change.pointer_id);
+                    self.press_position = cursor_position;
+                    self.press_time = Some(change.timestamp);
+                    self.canceled = false;
+                    self.triggered = false;
+                }
+                CursorEventContent::Moved(_) => {
+                    if Some(change.pointer_id) != self.active_pointer {
+                        continue;
+                    }
+                    let Some(position) = cursor_position else {
+                        continue;
+                    };
+                    if let Some(start) = self.press_position
+                        && start.distance_to(position) > self.settings.slop_px
+                    {
+                        self.canceled = true;
+                    }
+                    self.try_trigger(change.timestamp, within_bounds, change, &mut result);
+                }
+                CursorEventContent::Scroll(_) if Some(change.pointer_id) == self.active_pointer => {
+                    self.canceled = true;
+                }
+                CursorEventContent::Released(PressKeyEventType::Left) => {
+                    if Some(change.pointer_id) != self.active_pointer {
+                        continue;
+                    }
+                    self.try_trigger(change.timestamp, within_bounds, change, &mut result);
+                    result.released = true;
+                    self.reset();
+                }
+                _ => {}
+            }
+        }
+        result
+    }
+
+    fn try_trigger(
+        &mut self,
+        now: Instant,
+        within_bounds: bool,
+        change: &mut PointerChange,
+        result: &mut LongPressResult,
+    ) {
+        if self.triggered || self.canceled || !within_bounds {
+            return;
+        }
+        let Some(start) = self.press_time else {
+            return;
+        };
+        if now.duration_since(start) >= self.settings.threshold {
+            self.triggered = true;
+            result.triggered = true;
+            if self.settings.consume_on_trigger {
+                change.consume();
+            }
+        }
+    }
+
+    /// Cancels the active press so it can no longer trigger.
+    pub fn cancel(&mut self) {
+        if self.active_pointer.is_some() {
+            self.canceled = true;
+        }
+    }
+
+    fn reset(&mut self) {
+        self.active_pointer = None;
+        self.press_position = None;
+        self.press_time = None;
+        self.canceled = false;
+        self.triggered = false;
+    }
+}
+
+impl Default for LongPressRecognizer {
+    fn default() -> Self {
+        Self::new(LongPressSettings::default())
+    }
+}
+
+/// Input that produced a secondary activation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SecondaryActivationSource {
+    /// The right mouse button was pressed.
+    RightClick,
+    /// A touch was held in place past the long-press threshold.
+    LongPress,
+}
+
+/// A request to show contextual actions, such as a context menu.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SecondaryActivation {
+    /// Pointer position relative to the node.
+    pub position: PxPosition,
+    /// Input that produced the activation.
+    pub source: SecondaryActivationSource,
+}
+
+/// Configuration for secondary activation recognition.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SecondaryActivationSettings {
+    /// Long-press threshold for touch pointers.
+    pub long_press_threshold: Duration,
+    /// Maximum touch travel before the long press is canceled.
+    pub slop_px: f32,
+    /// Whether to consume the event that triggered the activation.
+    pub consume: bool,
+}
+
+impl Default for SecondaryActivationSettings {
+    fn default() -> Self {
+        Self {
+            long_press_threshold: DEFAULT_LONG_PRESS_THRESHOLD,
+            slop_px: DEFAULT_SLOP_PX,
+            consume: true,
+        }
+    }
+}
+
+/// Recognizes right clicks from the mouse and long presses from touch
+/// pointers as a single secondary activation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SecondaryActivationRecognizer {
+    settings: SecondaryActivationSettings,
+    long_press: LongPressRecognizer,
+    touch_pressed: bool,
+}
+
+impl SecondaryActivationRecognizer {
+    /// Creates a secondary activation recognizer with custom settings.
+    pub fn new(settings: SecondaryActivationSettings) -> Self {
+        Self {
+            settings,
+            long_press: LongPressRecognizer::new(LongPressSettings {
+                threshold: settings.long_press_threshold,
+                slop_px: settings.slop_px,
+                consume_on_trigger: settings.consume,
+            }),
+            touch_pressed: false,
+        }
+    }
+
+    /// Updates the recognizer with the current pointer pass and events.
+    pub fn update(
+        &mut self,
+        pass: PointerEventPass,
+        pointer_changes: &mut [PointerChange],
+        cursor_position: Option<PxPosition>,
+        within_bounds: bool,
+    ) -> Option<SecondaryActivation> {
+        if pass != PointerEventPass::Main {
+            return None;
+        }
+
+        let mut activation = None;
+        for change in pointer_changes.iter_mut() {
+            if change.is_consumed() {
+                continue;
+            }
+            match change.content {
+                CursorEventContent::Pressed(PressKeyEventType::Right) if within_bounds => {
+                    if let Some(position) = cursor_position {
+                        activation = Some(SecondaryActivation {
+                            position,
+                            source: SecondaryActivationSource::RightClick,
+                        });
+                        if self.settings.consume {
+                            change.consume();
+                        }
+                    }
+                }
+                CursorEventContent::Pressed(PressKeyEventType::Left) if within_bounds => {
+                    self.touch_pressed = change.pointer_id != MOUSE_POINTER_ID;
+                }
+                _ => {}
+            }
+        }
+
+        let long_press =
+            self.long_press
+                .update(pass, pointer_changes, cursor_position, within_bounds);
+        if long_press.triggered
+            && self.touch_pressed
+            && activation.is_none()
+            && let Some(position) = cursor_position
+        {
+            activation = Some(SecondaryActivation {
+                position,
+                source: SecondaryActivationSource::LongPress,
+            });
+        }
+        if long_press.released {
+            self.touch_pressed = false;
+        }
+        activation
+    }
+}
+
+impl Default for SecondaryActivationRecognizer {
+    fn default() -> Self {
+        Self::new(SecondaryActivationSettings::default())
+    }
+}
+
+/// Configuration for scroll gesture aggregation.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct ScrollSettings {
+    /// Whether to consume scroll events after they are aggregated.
+    pub consume: bool,
+}
+
+/// Aggregated scroll recognition output.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScrollResult {
+    /// Number of scroll events observed in this update.
+    pub event_count: usize,
+    /// Total horizontal scroll delta.
+    pub delta_x: f32,
+    /// Total vertical scroll delta.
+    pub delta_y: f32,
+    /// Source of the first observed scroll event.
+    pub source: Option<ScrollEventSource>,
+    /// Delta unit of the first observed scroll event.
+    pub unit: Option<ScrollDeltaUnit>,
+}
+
+impl Default for ScrollResult {
+    fn default() -> Self {
+        Self {
+            event_count: 0,
+            delta_x: 0.0,
+            delta_y: 0.0,
+            source: None,
+            unit: None,
+        }
+    }
+}
+
+impl ScrollResult {
+    /// Returns whether any scroll event was observed in this update.
+    pub fn has_scroll(&self) -> bool {
+        self.event_count > 0
+    }
+}
+
+/// Metadata for a single scroll event routed through
+/// `ScrollRecognizer::for_each`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScrollChangeContext {
+    /// Pointer id that produced the scroll event.
+    pub pointer_id: PointerId,
+    /// Timestamp of the scroll event.
+    pub timestamp: Instant,
+}
+
+/// Stateful scroll event recognizer and aggregator.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScrollRecognizer {
+    settings: ScrollSettings,
+}
+
+impl ScrollRecognizer {
+    /// Creates a scroll recognizer with custom settings.
+    pub fn new(settings: ScrollSettings) -> Self {
+        Self { settings }
+    }
+
+    /// Aggregates scroll events and allows each one to be handled before
+    /// optional consumption.
+    pub fn for_each(
+        &mut self,
+        pass: PointerEventPass,
+        pointer_changes: &mut [PointerChange],
+        mut handle_scroll: impl FnMut(ScrollChangeContext, &mut ScrollEventContent),
+    ) -> ScrollResult {
+        if pass != PointerEventPass::Main {
+            return ScrollResult::default();
+        }
+
+        let mut result = ScrollResult::default();
+        for change in pointer_changes.iter_mut() {
+            if change.is_consumed() {
+                continue;
+            }
+            let CursorEventContent::Scroll(ref mut scroll) = change.content else {
+                continue;
+            };
+            if result.source.is_none() {
+                result.source = Some(scroll.source);
+            }
+            if result.unit.is_none() {
+                result.unit = Some(scroll.unit);
+            }
+            result.event_count += 1;
+            result.delta_x += scroll.delta_x;
+            result.delta_y += scroll.delta_y;
+
+            handle_scroll(
+                ScrollChangeContext {
+                    pointer_id: change.pointer_id,
+                    timestamp: change.timestamp,
+                },
+                scroll,
+            );
+
+            if self.settings.consume
+                || (scroll.delta_x.abs() <= f32::EPSILON && scroll.delta_y.abs() <= f32::EPSILON)
+            {
+                change.consume();
+            }
+        }
+        result
+    }
+
+    /// Aggregates scroll events and optionally consumes them.
+    pub fn update(
+        &mut self,
+        pass: PointerEventPass,
+        pointer_changes: &mut [PointerChange],
+    ) -> ScrollResult {
+        if pass != PointerEventPass::Main {
+            return ScrollResult::default();
+        }
+
+        let mut result = ScrollResult::default();
+        for change in pointer_changes.iter_mut() {
+            if change.is_consumed() {
+                continue;
+            }
+            let CursorEventContent::Scroll(ref scroll) = change.content else {
+                continue;
+            };
+            if result.source.is_none() {
+                result.source = Some(scroll.source);
+            }
+            if result.unit.is_none() {
+                result.unit = Some(scroll.unit);
+            }
+            result.event_count += 1;
+            result.delta_x += scroll.delta_x;
+            result.delta_y += scroll.delta_y;
+            if self.settings.consume {
+                change.consume();
+            }
+        }
+        result
+    }
+}
+
+impl Default for ScrollRecognizer {
+    fn default() -> Self {
+        Self::new(ScrollSettings::default())
+    }
+}
+
+/// Configuration for double-tap gesture recognition.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DoubleTapSettings {
+    /// Settings applied to each individual tap.
+    pub tap: TapSettings,
+    /// Maximum delay between the first and second tap releases.
+    pub timeout: Duration,
+    /// Maximum distance between the first and second tap positions.
+    pub slop_px: f32,
+}
+
+impl Default for DoubleTapSettings {
+    fn default() -> Self {
+        Self {
+            tap: TapSettings::default(),
+            timeout: DEFAULT_DOUBLE_TAP_TIMEOUT,
+            slop_px: DEFAULT_SLOP_PX * 4.0,
+        }
+    }
+}
+
+/// Per-update double-tap recognition output.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct DoubleTapResult {
+    /// Whether a tap completed that may still become the first half of a
+    /// double tap.
+    pub tapped: bool,
+    /// Whether a double tap completed this update.
+    pub double_tapped: bool,
+    /// Position of the completed tap, when present.
+    pub position: Option<PxPosition>,
+}
+
+/// Stateful double-tap gesture recognizer.
+///
+/// A completed tap is held as pending until either a second tap completes a
+/// double tap or [`DoubleTapRecognizer::expired_tap`] reports that the timeout
+/// elapsed, confirming a single tap.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DoubleTapRecognizer {
+    settings: DoubleTapSettings,
+    tap: TapRecognizer,
+    pending: Option<(Instant, PxPosition)>,
+}
+
+impl DoubleTapRecognizer {
+    /// Creates a double-tap recognizer with custom settings.
+    pub fn new(settings: DoubleTapSettings) -> Self {
+        Self {
+            settings,
+            tap: TapRecognizer::new(settings.tap),
+            pending: None,
+        }
+    }
+
+    /// Updates the recognizer with the current pointer pass and events.
+    pub fn update(
+        &mut self,
+        pass: PointerEventPass,
+        pointer_changes: &mut [PointerChange],
+        cursor_position: Option<PxPosition>,
+        within_bounds: bool,
+    ) -> DoubleTapResult {
+        let tap = self
+            .tap
+            .update(pass, pointer_changes, cursor_position, within_bounds);
+        let mut result = DoubleTapResult::default();
+        if !tap.tapped {
+            return result;
+        }
+        let (Some(timestamp), Some(position)) = (tap.release_timestamp, cursor_position) else {
+            return result;
+        };
+        result.position = Some(position);
+        match self.pending.take() {
+            Some((first_time, first_position))
+                if timestamp.duration_since(first_time) <= self.settings.timeout
+                    && first_position.distance_to(position) <= self.settings.slop_px =>
+            {
+                result.double_tapped = true;
+            }
+            _ => {
+                result.tapped = true;
+                self.pending = Some((timestamp, position));
+            }
+        }
+        result
+    }
+
+    /// Returns and clears the pending tap once the double-tap timeout has
+    /// elapsed at `now`.
+    pub fn expired_tap(&mut self, now: Instant) -> Option<PxPosition> {
+        let (timestamp, position) = self.pending?;
+        if now.duration_since(timestamp) > self.settings.timeout {
+            self.pending = None;
+            Some(position)
+        } else {
+            None
+        }
+    }
+
+    /// Returns and clears the pending tap regardless of the timeout.
+    pub fn take_pending_tap(&mut self) -> Option<PxPosition> {
+        self.pending.take().map(|(_, position)| position)
+    }
+
+    /// Cancels the active press so its release does not produce a tap.
+    pub fn cancel(&mut self) {
+        self.tap.cancel();
+    }
+}
+
+impl Default for DoubleTapRecognizer {
+    fn default() -> Self {
+        Self::new(DoubleTapSettings::default())
+    }
+}
+
+const MAX_TRACKED_POINTERS: usize = 4;
+
+/// Configuration for multi-pointer transform (pinch and rotate) recognition.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TransformSettings {
+    /// Minimum combined span change, rotation arc and centroid travel before
+    /// the transform starts.
+    pub slop_px: f32,
+    /// Whether pointer changes should be consumed while transforming.
+    pub consume_when_transforming: bool,
+}
+
+impl Default for TransformSettings {
+    fn default() -> Self {
+        Self {
+            slop_px: DEFAULT_SLOP_PX,
+            consume_when_transforming: true,
+        }
+    }
+}
+
+/// Per-update transform recognition output.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TransformResult {
+    /// Whether the transform started this update.
+    pub started: bool,
+    /// Whether a transform delta was produced this update.
+    pub updated: bool,
+    /// Whether the transform ended this update.
+    pub ended: bool,
+    /// Horizontal centroid movement for this update.
+    pub pan_x: Px,
+    /// Vertical centroid movement for this update.
+    pub pan_y: Px,
+    /// Multiplicative pinch zoom for this update, `1.0` when unchanged.
+    pub zoom: f32,
+    /// Rotation for this update in radians, clockwise in screen space.
+    pub rotation: f32,
+    /// Centroid of the transforming pointers, when known.
+    pub centroid: Option<PxPosition>,
+}
+
+impl Default for TransformResult {
+    fn default() -> Self {
+        Self {
+            started: false,
+            updated: false,
+            ended: false,
+            pan_x: Px::ZERO,
+            pan_y: Px::ZERO,
+            zoom: 1.0,
+            rotation: 0.0,
+            centroid: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct TrackedPointer {
+    id: PointerId,
+    position: Option<PxPosition>,
+    pressed: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct TransformFrame {
+    pair: (PointerId, PointerId),
+    centroid: PxPosition,
+    span: f32,
+    angle: f32,
+}
+
+/// Stateful two-pointer transform recognizer for pinch zoom and rotation.
+///
+/// Pointer positions are taken from `Moved` changes, so results are expressed
+/// in the same coordinate space as the incoming pointer changes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TransformRecognizer {
+    settings: TransformSettings,
+    pointers: [Option<TrackedPointer>; MAX_TRACKED_POINTERS],
+    travel: f32,
+    transforming: bool,
+}
+
+impl TransformRecognizer {
+    /// Creates a transform recognizer with custom settings.
+    pub fn new(settings: TransformSettings) -> Self {
+        Self {
+            settings,
+            pointers: [None; MAX_TRACKED_POINTERS],
+            travel: 0.0,
+            transforming: false,
+        }
+    }
+
+    /// Updates the recognizer with the current pointer pass and events.
+    pub fn update(
+        &mut self,
+        pass: PointerEventPass,
+        pointer_changes: &mut [PointerChange],
+        within_bounds: bool,
+    ) -> TransformResult {
+        if pass != PointerEventPass::Main {
+            return TransformResult::default();
+        }
+
+        let mut result = TransformResult::default();
+        for change in pointer_changes.iter_mut() {
+            if change.is_consumed() {
+                continue;
+            }
+            match change.content {
+                CursorEventContent::Pressed(PressKeyEventType::Left)
+                    if within_bounds || self.pressed_pointer_count() > 0 =>
+                {
+                    if let Some(pointer) = self.track(change.pointer_id) {
+                        pointer.pressed = true;
+                    }
+                }
+                CursorEventContent::Moved(position) => {
+                    let before = self.frame();
+                    if let Some(pointer) = self.track(change.pointer_id) {
+                        pointer.position = Some(position);
+                    }
+                    let after = self.frame();
+                    let (Some(before), Some(after)) = (before, after) else {
+                        continue;
+                    };
+                    if before.pair != after.pair {
+                        continue;
+                    }
+                    let zoom = if before.span > f32::EPSILON {
+                        after.span / before.span
+                    } else {
+                        1.0
+                    };
+                    let rotation = normalize_angle(after.angle - before.angle);
+                    let pan_x = after.centroid.x - before.centroid.x;
+                    let pan_y = after.centroid.y - before.centroid.y;
+                    if !self.transforming {
+                        self.travel += (after.span - before.span).abs()
+                            + rotation.abs() * after.span * 0.5
+                            + before.centroid.distance_to(after.centroid);
+                        if self.travel > self.settings.slop_px {
+                            self.transforming = true;
+                            result.started = true;
+                        }
+                    }
+                    if self.transforming {
+                        result.zoom *= zoom;
+                        result.rotation += rotation;
+                        result.pan_x += pan_x;
+                        result.pan_y += pan_y;
+                        result.centroid = Some(after.centroid);
+                        result.updated = true;
+                        if self.settings.consume_when_transforming {
+                            change.consume();
+                        }
+                    }
+                }
+                CursorEventContent::Scroll(_)
+                    if self.transforming && self.is_tracked(change.pointer_id) =>
+                {
+                    if self.settings.consume_when_transforming {
+                        change.consume();
+                    }
+                }
+                CursorEventContent::Released(PressKeyEventType::Left) => {
+                    if !self.is_tracked(change.pointer_id) {
+                        continue;
+                    }
+                    self.untrack(change.pointer_id);
+                    if self.pressed_pointer_count() < 2 {
+                        if self.transforming {
+                            result.ended = true;
+                            if self.settings.consume_when_transforming {
+                                change.consume();
+                            }
+                        }
+                        self.transforming = false;
+                        self.travel = 0.0;
+                    }
+                }
+                _ => {}
+            }
+        }
+        result
+    }
+
+    /// Returns whether a transform is currently active.
+    pub fn is_transforming(&self) -> bool {
+        self.transforming
+    }
+
+    /// Returns the number of pointers currently pressed on this recognizer.
+    pub fn pressed_pointer_count(&self) -> usize {
+        self.pointers
+            .iter()
+            .flatten()
+            .filter(|pointer| pointer.pressed)
+            .count()
+    }
+
+    fn is_tracked(&self, id: PointerId) -> bool {
+        self.pointers
+            .iter()
+            .flatten()
+            .any(|pointer| pointer.id == id && pointer.pressed)
+    }
+
+    fn track(&mut self, id: PointerId) -> Option<&mut TrackedPointer> {
+        let index = self
+            .pointers
+            .iter()
+            .position(|slot| slot.is_some_and(|pointer| pointer.id == id))
+            .or_else(|| self.pointers.iter().position(Option::is_none))
+            .or_else(|| {
+                self.pointers
+                    .iter()
+                    .position(|slot| slot.is_some_and(|pointer| !pointer.pressed))
+            })?;
+        let slot = &mut self.pointers[index];
+        if slot.is_none_or(|pointer| pointer.id != id) {
+            *slot = Some(TrackedPointer {
+                id,
+                position: None,
+                pressed: false,
+            });
+        }
+        slot.as_mut()
+    }
+
+    fn untrack(&mut self, id: PointerId) {
+        for slot in &mut self.pointers {
+            if slot.is_some_and(|pointer| pointer.id == id) {
+                *slot = None;
+            }
+        }
+    }
+
+    fn frame(&self) -> Option<TransformFrame> {
+        let mut pressed = self
+            .pointers
+            .iter()
+            .flatten()
+            .filter(|pointer| pointer.pressed)
+            .filter_map(|pointer| pointer.position.map(|position| (pointer.id, position)));
+        let (first_id, first) = pressed.next()?;
+        let (second_id, second) = pressed.next()?;
+        let dx = (second.x - first.x).to_f32();
+        let dy = (second.y - first.y).to_f32();
+        Some(TransformFrame {
+            pair: (first_id, second_id),
+            centroid: PxPosition::new(
+                Px::from_f32((first.x.to_f32() + second.x.to_f32()) * 0.5),
+                Px::from_f32((first.y.to_f32() + second.y.to_f32()) * 0.5),
+            ),
+            span: (dx * dx + dy * dy).sqrt(),
+            angle: dy.atan2(dx),
+        })
+    }
+}
+
+impl Default for TransformRecognizer {
+    fn default() -> Self {
+        Self::new(TransformSettings::default())
+    }
+}
+
+fn normalize_angle(radians: f32) -> f32 {
+    use std::f32::consts::{PI, TAU};
+
+    let wrapped = radians.rem_euclid(TAU);
+    if wrapped > PI { wrapped - TAU } else { wrapped }
+}
+
+/// A gesture resolved by a [`GestureDetector`].
+///
+/// Positions are relative to the node the detector is attached to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Gesture {
+    /// A single tap completed.
+    ///
+    /// When double taps are enabled this is delivered once the double-tap
+    /// timeout elapses without a second tap.
+    Tap {
+        /// Position of the tap.
+        position: PxPosition,
+    },
+    /// Two taps completed within the double-tap timeout and slop.
+    DoubleTap {
+        /// Position of the second tap.
+        position: PxPosition,
+    },
+    /// A press was held in place past the long-press threshold.
+    LongPress {
+        /// Position of the press when it triggered.
+        position: PxPosition,
+    },
+    /// A single pointer moved past the slop and started panning.
+    PanStart {
+        /// Pointer position when panning started.
+        position: PxPosition,
+    },
+    /// The panning pointer moved.
+    Pan {
+        /// Horizontal movement since the previous pan event.
+        delta_x: Px,
+        /// Vertical movement since the previous pan event.
+        delta_y: Px,
+    },
+    /// Panning ended because the pointer was released or a second pointer
+    /// started a transform.
+    PanEnd,
+    /// Two pointers moved past the slop and started a transform.
+    TransformStart {
+        /// Centroid of the transforming pointers.
+        centroid: PxPosition,
+    },
+    /// The transforming pointers pinched, rotated or moved together.
+    Transform {
+        /// Centroid of the transforming pointers.
+        centroid: PxPosition,
+        /// Horizontal centroid movement since the previous transform event.
+        pan_x: Px,
+        /// Vertical centroid movement since the previous transform event.
+        pan_y: Px,
+        /// Multiplicative pinch zoom since the previous transform event.
+        zoom: f32,
+        /// Rotation in radians since the previous transform event.
+        rotation: f32,
+    },
+    /// Fewer than two pointers remain pressed, or the touchpad gesture
+    /// finished, and the transform ended.
+    TransformEnd,
+    /// A touchpad smart-magnify gesture toggled zoom at a position.
+    SmartMagnify {
+        /// Pointer position relative to the node.
+        position: PxPosition,
+    },
+}
+
+/// Configuration for a [`GestureDetector`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GestureSettings {
+    /// Button that produces taps and long presses.
+    pub button: PressKeyEventType,
+    /// Maximum pointer travel before a press stops being a tap or long press,
+    /// and the travel required to start panning.
+    pub slop_px: f32,
+    /// Double-tap timeout, or `None` to deliver taps immediately.
+    pub double_tap_timeout: Option<Duration>,
+    /// Long-press threshold, or `None` to disable long presses.
+    pub long_press_threshold: Option<Duration>,
+    /// Whether single-pointer panning is recognized.
+    pub pan: bool,
+    /// Optional axis lock for panning.
+    pub pan_axis: Option<DragAxis>,
+    /// Whether two-pointer pinch and rotate transforms are recognized.
+    pub transform: bool,
+    /// Whether pointer changes are consumed once a gesture is recognized.
+    pub consume: bool,
+}
+
+impl Default for GestureSettings {
+    fn default() -> Self {
+        Self {
+            button: PressKeyEventType::Left,
+            slop_px: DEFAULT_SLOP_PX,
+            double_tap_timeout: None,
+            long_press_threshold: Some(DEFAULT_LONG_PRESS_THRESHOLD),
+            pan: true,
+            pan_axis: None,
+            transform: true,
+            consume: true,
+        }
+    }
+}
+
+/// Composes the individual recognizers and resolves conflicts between them.
+///
+/// A long press or pan cancels the pending tap, and a second pointer cancels
+/// tap, long-press and pan recognition until every pointer is released so the
+/// transform owns the interaction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GestureDetector {
+    settings: GestureSettings,
+    tap: DoubleTapRecognizer,
+    long_press: LongPressRecognizer,
+    drag: DragRecognizer,
+    transform: TransformRecognizer,
+    multi_touch: bool,
+    touchpad_transforming: bool,
+}
+
+impl GestureDetector {
+    /// Creates a gesture detector with custom settings.
+    pub fn new(settings: GestureSettings) -> Self {
+        let tap = TapSettings {
+            button: settings.button,
+            slop_px: settings.slop_px,
+            ..TapSettings::default()
+        };
+        Self {
+            settings,
+            tap: DoubleTapRecognizer::new(DoubleTapSettings {
+                tap,
+                timeout: settings
+                    .double_tap_timeout
+                    .unwrap_or(DEFAULT_DOUBLE_TAP_TIMEOUT),
+                ..DoubleTapSettings::default()
+            }),
+            long_press: LongPressRecognizer::new(LongPressSettings {
+                threshold: settings
+                    .long_press_threshold
+                    .unwrap_or(DEFAULT_LONG_PRESS_THRESHOLD),
+                slop_px: settings.slop_px,
+                consume_on_trigger: false,
+            }),
+            drag: DragRecognizer::new(DragSettings {
+                slop_px: settings.slop_px,
+                consume_when_dragging: false,
+                axis: settings.pan_axis,
+            }),
+            transform: TransformRecognizer::new(TransformSettings {
+                slop_px: settings.slop_px,
+                consume_when_transforming: false,
+            }),
+            multi_touch: false,
+            touchpad_transforming: false,
+        }
+    }
+
+    /// Returns the detector settings.
+    pub fn settings(&self) -> GestureSettings {
+        self.settings
+    }
+
+    /// Feeds the current pointer input through every enabled recognizer and
+    /// returns the gestures resolved this update, in recognition order.
+    pub fn update(&mut self, input: &mut PointerInput<'_>) -> Vec<Gesture> {
+        if input.pass != PointerEventPass::Main {
+            return Vec::new();
+        }
+
+        let cursor_position = input.cursor_position_rel;
+        let cursor_position_abs = input.cursor_position_abs();
+        let origin = cursor_position_abs
+            .zip(cursor_position)
+            .map(|(abs, rel)| abs - rel);
+        let to_local = |position: PxPosition| origin.map_or(position, |origin| position - origin);
+        let within_bounds = cursor_within_bounds(
+            cursor_position,
+            PxSize::new(input.computed_data.width, input.computed_data.height),
+        );
+        let pass = input.pass;
+        let changes = input.pointer_changes.as_mut_slice();
+
+        let mut gestures = Vec::new();
+        if let Some(position) = self.tap.expired_tap(time::now()) {
+            gestures.push(Gesture::Tap { position });
+        }
+
+        if self.settings.transform {
+            let transform = self.transform.update(pass, changes, within_bounds);
+            if self.transform.pressed_pointer_count() > 1 && !self.multi_touch {
+                self.multi_touch = true;
+                self.tap.cancel();
+                self.long_press.cancel();
+                if self.drag.is_dragging() {
+                    gestures.push(Gesture::PanEnd);
+                }
+                self.drag.cancel();
+            }
+            if transform.started
+                && let Some(centroid) = transform.centroid
+            {
+                gestures.push(Gesture::TransformStart {
+                    centroid: to_local(centroid),
+                });
+            }
+            if transform.updated
+                && let Some(centroid) = transform.centroid
+            {
+                gestures.push(Gesture::Transform {
+                    centroid: to_local(centroid),
+                    pan_x: transform.pan_x,
+                    pan_y: transform.pan_y,
+                    zoom: transform.zoom,
+                    rotation: transform.rotation,
+                });
+            }
+            if transform.ended {
+                gestures.push(Gesture::TransformEnd);
+            }
+            self.update_touchpad_transform(changes, cursor_position, within_bounds, &mut gestures);
+        }
+
+        if !self.multi_touch {
+            if self.settings.pan {
+                let was_dragging = self.drag.is_dragging();
+                let drag = self
+                    .drag
+                    .update(pass, changes, cursor_position_abs, within_bounds);
+                if drag.started {
+                    self.tap.cancel();
+                    self.long_press.cancel();
+                    if let Some(position) = cursor_position {
+                        gestures.push(Gesture::PanStart { position });
+                    }
+                }
+                if drag.updated {
+                    gestures.push(Gesture::Pan {
+                        delta_x: drag.delta_x,
+                        delta_y: drag.delta_y,
+                    });
+                }
+                if drag.ended && (was_dragging || drag.started) {
+                    gestures.push(Gesture::PanEnd);
+                }
+            }
+
+            if self.settings.long_press_threshold.is_some() {
+                let long_press =
+                    self.long_press
+                        .update(pass, changes, cursor_position, within_bounds);
+                if long_press.triggered {
+                    self.tap.cancel();
+                    if let Some(position) = cursor_position {
+                        gestures.push(Gesture::LongPress { position });
+                    }
+                }
+            }
+
+            let tap = self
+                .tap
+                .update(pass, changes, cursor_position, within_bounds);
+            if tap.double_tapped
+                && let Some(position) = tap.position
+            {
+                gestures.push(Gesture::DoubleTap { position });
+            }
+            if tap.tapped
+                && self.settings.double_tap_timeout.is_none()
+                && let Some(position) = self.tap.take_pending_tap()
+            {
+                gestures.push(Gesture::Tap { position });
+            }
+        }
+
+        if self.transform.pressed_pointer_count() == 0 {
+            self.multi_touch = false;
+        }
+
+        let active = self.drag.is_dragging()
+            || self.transform.is_transforming()
+            || self.touchpad_transforming;
+        if self.settings.consume && (active || !gestures.is_empty()) {
+            input.consume_pointer_changes();
+        }
+        gestures
+    }
+}
+
+impl GestureDetector {
+    /// Maps touchpad pinch, rotation and smart-magnify events onto the same
+    /// transform gestures produced by multi-touch input.
+    fn update_touchpad_transform(
+        &mut self,
+        changes: &[PointerChange],
+        cursor_position: Option<PxPosition>,
+        within_bounds: bool,
+        gestures: &mut Vec<Gesture>,
+    ) {
+        for change in changes.iter().filter(|change| !change.is_consumed()) {
+            let (zoom, rotation, phase) = match &change.content {
+                CursorEventContent::Pinch(pinch) => (1.0 + pinch.delta, 0.0, pinch.phase),
+                CursorEventContent::Rotate(rotate) => (1.0, rotate.delta, rotate.phase),
+                CursorEventContent::SmartMagnify => {
+                    if within_bounds && let Some(position) = cursor_position {
+                        gestures.push(Gesture::SmartMagnify { position });
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
+            if !within_bounds && !self.touchpad_transforming {
+                continue;
+            }
+            let centroid = cursor_position.unwrap_or(PxPosition::ZERO);
+            if !self.touchpad_transforming {
+                if phase == ScrollPhase::Ended {
+                    continue;
+                }
+                self.touchpad_transforming = true;
+                gestures.push(Gesture::TransformStart { centroid });
+            }
+            if phase == ScrollPhase::Ended {
+                self.touchpad_transforming = false;
+                gestures.push(Gesture::TransformEnd);
+            } else {
+                gestures.push(Gesture::Transform {
+                    centroid,
+                    pan_x: Px::ZERO,
+                    pan_y: Px::ZERO,
+                    zoom,
+                    rotation,
+                });
+            }
+        }
+    }
+}
+
+impl Default for GestureDetector {
+    fn default() -> Self {
+        Self::new(GestureSettings::default())
+    }
+}
+
+fn cursor_within_bounds(position: Option<PxPosition>, size: PxSize) -> bool {
+    let Some(position) = position else {
+        return false;
+    };
+
+    position.x >= Px::ZERO
+        && position.y >= Px::ZERO
+        && position.x < size.width
+        && position.y < size.height
+}
+
+/// Gesture detection extensions for [`Modifier`].
+pub trait GestureModifierExt {
+    /// Recognizes gestures over this node and reports each resolved gesture to
+    /// `on_gesture`.
+    ///
+    /// `detector` keeps recognizer state across frames; create it with
+    /// `remember(GestureDetector::default)` or with custom
+    /// [`GestureSettings`].
+    fn detect_gestures(
+        self,
+        detector: State<GestureDetector>,
+        on_gesture: CallbackWith<Gesture>,
+    ) -> Modifier;
+
+    /// Reports right clicks and touch long presses over this node to
+    /// `on_activate`, typically to open a context menu.
+    ///
+    /// `recognizer` keeps state across frames; create it with
+    /// `remember(SecondaryActivationRecognizer::default)`.
+    fn on_secondary_activation(
+        self,
+        recognizer: State<SecondaryActivationRecognizer>,
+        on_activate: CallbackWith<SecondaryActivation>,
+    ) -> Modifier;
+}
+
+struct SecondaryActivationModifierNode {
+    recognizer: State<SecondaryActivationRecognizer>,
+    on_activate: CallbackWith<SecondaryActivation>,
+}
+
+impl PointerInputModifierNode for SecondaryActivationModifierNode {
+    fn on_pointer_input(&self, mut input: PointerInput<'_>) {
+        let within_bounds = cursor_within_bounds(
+            input.cursor_position_rel,
+            PxSize::new(input.computed_data.width, input.computed_data.height),
+        );
+        let pass = input.pass;
+        let cursor_position = input.cursor_position_rel;
+        let activation = self.recognizer.with_mut(|recognizer| {
+            recognizer.update(
+                pass,
+                input.pointer_changes.as_mut_slice(),
+                cursor_position,
+                within_bounds,
+            )
+        });
+        if let Some(activation) = activation {
+            self.on_activate.call(activation);
+        }
+    }
+}
+
+struct GestureModifierNode {
+    detector: State<GestureDetector>,
+    on_gesture: CallbackWith<Gesture>,
+}
+
+impl PointerInputModifierNode for GestureModifierNode {
+    fn on_pointer_input(&self, mut input: PointerInput<'_>) {
+        let gestures = self
+            .detector
+            .with_mut(|detector| detector.update(&mut input));
+        for gesture in gestures {
+            self.on_gesture.call(gesture);
+        }
+    }
+}
+
+impl GestureModifierExt for Modifier {
+    fn detect_gestures(
+        self,
+        detector: State<GestureDetector>,
+        on_gesture: CallbackWith<Gesture>,
+    ) -> Modifier {
+        self.push_pointer_input(GestureModifierNode {
+            detector,
+            on_gesture,
+        })
+    }
+
+    fn on_secondary_activation(
+        self,
+        recognizer: State<SecondaryActivationRecognizer>,
+        on_activate: CallbackWith<SecondaryActivation>,
+    ) -> Modifier {
+        self.push_pointer_input(SecondaryActivationModifierNode {
+            recognizer,
+            on_activate,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(
+        pointer_id: PointerId,
+        timestamp: Instant,
+        content: CursorEventContent,
+    ) -> PointerChange {
+        PointerChange {
+            timestamp,
+            pointer_id,
+            content,
+            consumed: false,
+        }
+    }
+
+    fn moved(pointer_id: PointerId, timestamp: Instant, x: i32, y: i32) -> PointerChange {
+        change(
+            pointer_id,
+            timestamp,
+            CursorEventContent::Moved(PxPosition::new(Px::new(x), Px::new(y))),
+        )
+    }
+
+    fn pressed(pointer_id: PointerId, timestamp: Instant) -> PointerChange {
+        change(
+            pointer_id,
+            timestamp,
+            CursorEventContent::Pressed(PressKeyEventType::Left),
+        )
+    }
+
+    fn released(pointer_id: PointerId, timestamp: Instant) -> PointerChange {
+        change(
+            pointer_id,
+            timestamp,
+            CursorEventContent::Released(PressKeyEventType::Left),
+        )
+    }
+
+    #[test]
+    fn second_tap_within_timeout_is_a_double_tap() {
+        let mut recognizer = DoubleTapRecognizer::default();
+        let start = Instant::now();
+        let position = Some(PxPosition::new(Px::new(10), Px::new(10)));
+        let mut first = vec![pressed(0, start), released(0, start)];
+        let result = recognizer.update(PointerEventPass::Main, &mut first, position, true);
+        assert!(result.tapped);
+        assert!(!result.double_tapped);
+
+        let later = start + Duration::from_millis(100);
+        let mut second = vec![pressed(0, later), released(0, later)];
+        let result = recognizer.update(PointerEventPass::Main, &mut second, position, true);
+        assert!(result.double_tapped);
+        assert_eq!(recognizer.take_pending_tap(), None);
+    }
+
+    #[test]
+    fn pending_tap_expires_after_timeout() {
+        let mut recognizer = DoubleTapRecognizer::default();
+        let start = Instant::now();
+        let position = PxPosition::new(Px::new(4), Px::new(4));
+        let mut changes = vec![pressed(0, start), released(0, start)];
+        recognizer.update(PointerEventPass::Main, &mut changes, Some(position), true);
+        assert_eq!(recognizer.expired_tap(start), None);
+        let expired = start + DEFAULT_DOUBLE_TAP_TIMEOUT + Duration::from_millis(1);
+        assert_eq!(recognizer.expired_tap(expired), Some(position));
+        assert_eq!(recognizer.expired_tap(expired), None);
+    }
+
+    #[test]
+    fn spreading_two_pointers_reports_zoom() {
+        let mut recognizer = TransformRecognizer::default();
+        let now = Instant::now();
+        let mut down = vec![
+            moved(1, now, 100, 100),
+            pressed(1, now),
+            moved(2, now, 200, 100),
+            pressed(2, now),
+        ];
+        let result = recognizer.update(PointerEventPass::Main, &mut down, true);
+        assert!(!result.started);
+        assert_eq!(recognizer.pressed_pointer_count(), 2);
+
+        let mut spread = vec![moved(1, now, 50, 100), moved(2, now, 250, 100)];
+        let result = recognizer.update(PointerEventPass::Main, &mut spread, true);
+        assert!(result.started);
+        assert!(recognizer.is_transforming());
+        assert!((result.zoom - 2.0).abs() < 1e-3);
+        assert!(result.rotation.abs() < 1e-3);
+
+        let mut up = vec![released(2, now)];
+        let result = recognizer.update(PointerEventPass::Main, &mut up, true);
+        assert!(result.ended);
+        assert!(!recognizer.is_transforming());
+    }
+
+    #[test]
+    fn rotating_two_pointers_reports_rotation() {
+        let mut recognizer = TransformRecognizer::default();
+        let now = Instant::now();
+        let mut down = vec![
+            moved(1, now, 0, 0),
+            pressed(1, now),
+            moved(2, now, 100, 0),
+            pressed(2, now),
+        ];
+        recognizer.update(PointerEventPass::Main, &mut down, true);
+        let mut rotate = vec![moved(2, now, 0, 100)];
+        let result = recognizer.update(PointerEventPass::Main, &mut rotate, true);
+        assert!(result.started);
+        assert!((result.rotation - std::f32::consts::FRAC_PI_2).abs() < 1e-3);
+        assert!((result.zoom - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn right_click_is_a_secondary_activation() {
+        let mut recognizer = SecondaryActivationRecognizer::default();
+        let now = Instant::now();
+        let position = PxPosition::new(Px::new(12), Px::new(8));
+        let mut changes = vec![change(
+            MOUSE_POINTER_ID,
+            now,
+            CursorEventContent::Pressed(PressKeyEventType::Right),
+        )];
+        let activation =
+            recognizer.update(PointerEventPass::Main, &mut changes, Some(position), true);
+        assert_eq!(
+            activation,
+            Some(SecondaryActivation {
+                position,
+                source: SecondaryActivationSource::RightClick,
+            })
+        );
+        assert!(changes[0].is_consumed());
+    }
+
+    #[test]
+    fn touch_long_press_is_a_secondary_activation_but_mouse_hold_is_not() {
+        let start = Instant::now();
+        let later = start + DEFAULT_LONG_PRESS_THRESHOLD + Duration::from_millis(10);
+        let position = Some(PxPosition::new(Px::new(5), Px::new(5)));
+
+        let mut touch = SecondaryActivationRecognizer::default();
+        let mut down = vec![pressed(3, start)];
+        assert_eq!(
+            touch.update(PointerEventPass::Main, &mut down, position, true),
+            None
+        );
+        let mut hold = vec![moved(3, later, 5, 5)];
+        let activation = touch.update(PointerEventPass::Main, &mut hold, position, true);
+        assert_eq!(
+            activation.map(|activation| activation.source),
+            Some(SecondaryActivationSource::LongPress)
+        );
+
+        let mut mouse = SecondaryActivationRecognizer::default();
+        let mut down = vec![pressed(MOUSE_POINTER_ID, start)];
+        mouse.update(PointerEventPass::Main, &mut down, position, true);
+        let mut hold = vec![moved(MOUSE_POINTER_ID, later, 5, 5)];
+        assert_eq!(
+            mouse.update(PointerEventPass::Main, &mut hold, position, true),
+            None
+        );
+    }
+
+    fn pinch(delta: f32, phase: ScrollPhase) -> PointerChange {
+        change(
+            MOUSE_POINTER_ID,
+            Instant::now(),
+            CursorEventContent::Pinch(crate::PinchEventContent { delta, phase }),
+        )
+    }
+
+    fn rotate(delta: f32, phase: ScrollPhase) -> PointerChange {
+        change(
+            MOUSE_POINTER_ID,
+            Instant::now(),
+            CursorEventContent::Rotate(crate::RotateEventContent { delta, phase }),
+        )
+    }
+
+    fn touchpad_gestures(
+        detector: &mut GestureDetector,
+        changes: &[PointerChange],
+        within_bounds: bool,
+    ) -> Vec<Gesture> {
+        let mut gestures = Vec::new();
+        let position = Some(PxPosition::new(Px::new(10), Px::new(20)));
+        detector.update_touchpad_transform(changes, position, within_bounds, &mut gestures);
+        gestures
+    }
+
+    #[test]
+    fn touchpad_pinch_and_rotation_map_to_transform_gestures() {
+        let mut detector = GestureDetector::default();
+        let centroid = PxPosition::new(Px::new(10), Px::new(20));
+        let transform = |zoom, rotation| Gesture::Transform {
+            centroid,
+            pan_x: Px::ZERO,
+            pan_y: Px::ZERO,
+            zoom,
+            rotation,
+        };
+        let gestures = touchpad_gestures(
+            &mut detector,
+            &[
+                pinch(0.0, ScrollPhase::Started),
+                pinch(0.25, ScrollPhase::Changed),
+                rotate(0.5, ScrollPhase::Changed),
+                pinch(0.0, ScrollPhase::Ended),
+            ],
+            true,
+        );
+        assert_eq!(
+            gestures,
+            [
+                Gesture::TransformStart { centroid },
+                transform(1.0, 0.0),
+                transform(1.25, 0.0),
+                transform(1.0, 0.5),
+                Gesture::TransformEnd,
+            ]
+        );
+        assert!(!detector.touchpad_transforming);
+    }
+
+    #[test]
+    fn touchpad_transform_continues_outside_bounds_until_it_ends() {
+        let mut detector = GestureDetector::default();
+        let gestures = touchpad_gestures(&mut detector, &[pinch(0.5, ScrollPhase::Started)], true);
+        assert_eq!(gestures.len(), 2);
+        assert!(detector.touchpad_transforming);
+
+        let gestures = touchpad_gestures(
+            &mut detector,
+            &[
+                pinch(0.5, ScrollPhase::Changed),
+                pinch(0.0, ScrollPhase::Ended),
+            ],
+            false,
+        );
+        assert!(matches!(gestures[0], Gesture::Transform { zoom, .. } if zoom == 1.5));
+        assert_eq!(gestures[1], Gesture::TransformEnd);
+
+        // A new gesture only starts inside the node.
+        let gestures = touchpad_gestures(
+            &mut detector,
+            &[
+                pinch(0.5, ScrollPhase::Started),
+                pinch(0.5, ScrollPhase::Changed),
+            ],
+            false,
+        );
+        assert!(gestures.is_empty());
+        assert!(!detector.touchpad_transforming);
+    }
+
+    #[test]
+    fn touchpad_end_without_start_and_consumed_changes_are_ignored() {
+        let mut detector = GestureDetector::default();
+        let mut consumed = pinch(0.5, ScrollPhase::Started);
+        consumed.consume();
+        let gestures = touchpad_gestures(
+            &mut detector,
+            &[pinch(0.0, ScrollPhase::Ended), consumed],
+            true,
+        );
+        assert!(gestures.is_empty());
+        assert!(!detector.touchpad_transforming);
+    }
+
+    #[test]
+    fn smart_magnify_is_reported_inside_the_node() {
+        let mut detector = GestureDetector::default();
+        let magnify = change(
+            MOUSE_POINTER_ID,
+            Instant::now(),
+            CursorEventContent::SmartMagnify,
+        );
+        assert_eq!(
+            touchpad_gestures(&mut detector, std::slice::from_ref(&magnify), true),
+            [Gesture::SmartMagnify {
+                position: PxPosition::new(Px::new(10), Px::new(20)),
+            }]
+        );
+        assert!(touchpad_gestures(&mut detector, &[magnify], false).is_empty());
+    }
+
+    #[test]
+    fn angles_wrap_into_half_turns() {
+        use std::f32::consts::PI;
+
+        assert!((normalize_angle(1.5 * PI) + 0.5 * PI).abs() < 1e-5);
+        assert!((normalize_angle(-1.5 * PI) - 0.5 * PI).abs() < 1e-5);
+    }
+}
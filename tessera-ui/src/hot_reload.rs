@@ -0,0 +1,163 @@
+//! Swapping rebuilt app code into the running process.
+//!
+//! ## Usage
+//!
+//! Give the app a `hot-reload` feature that enables `tessera-dylib` and run
+//! it with `cargo tessera dev`. Edits to the app library are loaded into the
+//! open window instead of restarting it.
+//!
+//! A reload swaps only the root UI function, which has two limits:
+//!
+//! - Values kept with [`remember_saveable`](crate::saveable::remember_saveable)
+//!   survive a reload. All other remembered state starts over, because a
+//!   rebuilt type keeps its `TypeId` even when its layout changed, so old
+//!   values can't be reused safely.
+//! - Packages, plugins, render modules and config set up by the reloaded entry
+//!   point are not applied. Restart the app after changing them.
+//!
+//! The first reload logs a warning as a reminder.
+//!
+//! `cargo tessera preview` uses the same mechanism, except that the preview
+//! window keeps running and only picks up the reloaded previews.
+
+use std::{
+    cell::RefCell,
+    io::{self, BufRead},
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::Once,
+    thread,
+};
+
+use libloading::Library;
+use tracing::{info, warn};
+
+use crate::EntryPoint;
+
+/// Set by `cargo tessera dev` when it sends library paths on stdin.
+const HOT_RELOAD_ENV: &str = "TESSERA_HOT_RELOAD";
+/// [`HOT_RELOAD_ENV`] value of preview windows, which load libraries only for
+/// the previews they register.
+const PREVIEWS_MODE: &str = "previews";
+/// Exported by `#[entry]` on desktop.
+const ENTRY_SYMBOL: &[u8] = b"tessera_hot_reload_entry";
+
+thread_local! {
+    static LOADED_ENTRY: RefCell<Option<Rc<dyn Fn()>>> = RefCell::new(None);
+}
+
+/// Wraps `entry` so the most recently loaded library's entry runs instead.
+pub(crate) fn reloadable_entry(entry: Box<dyn Fn()>) -> Box<dyn Fn()> {
+    Box::new(move || {
+        let loaded = LOADED_ENTRY.with(|loaded| loaded.borrow().clone());
+        match loaded {
+            Some(loaded) => loaded(),
+            None => entry(),
+        }
+    })
+}
+
+/// Calls `on_reload` with every library path `cargo tessera dev` sends.
+///
+/// Does nothing unless the app was started by `cargo tessera dev`.
+pub(crate) fn listen(on_reload: impl Fn(PathBuf) + Send + 'static) {
+    if std::env::var_os(HOT_RELOAD_ENV).is_none() {
+        return;
+    }
+    let spawned = thread::Builder::new()
+        .name("tessera-hot-reload".into())
+        .spawn(move || {
+            for line in io::stdin().lock().lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                let path = line.trim();
+                if !path.is_empty() {
+                    on_reload(PathBuf::from(path));
+                }
+            }
+        });
+    if let Err(err) = spawned {
+        warn!("Failed to start hot reload listener: {err}");
+    }
+}
+
+/// Loads the library at `path` and makes its entry the one that runs, or in
+/// a preview window just registers its previews.
+///
+/// Returns `false` and keeps the current entry if the library can't be used.
+/// Loaded libraries are never closed: values and callbacks created by their
+/// code can outlive the reload.
+pub(crate) fn load(path: &Path) -> bool {
+    // SAFETY: The library is built from the running app by `cargo tessera
+    // dev` and links the same `tessera-ui` dylib, so its initializers and the
+    // entry signature match this process.
+    let library = match unsafe { Library::new(path) } {
+        Ok(library) => library,
+        Err(err) => {
+            warn!("Failed to load {}: {err}", path.display());
+            return false;
+        }
+    };
+    if std::env::var(HOT_RELOAD_ENV).is_ok_and(|mode| mode == PREVIEWS_MODE) {
+        // Loading ran the library's initializers, which registered its
+        // previews.
+        std::mem::forget(library);
+        info!("Hot reloaded previews from {}", path.display());
+        return true;
+    }
+    // SAFETY: `#[entry]` exports the symbol with exactly this signature.
+    let entry = match unsafe { library.get::<fn() -> EntryPoint>(ENTRY_SYMBOL) } {
+        Ok(symbol) => *symbol,
+        Err(err) => {
+            warn!("{} has no hot reload entry: {err}", path.display());
+            return false;
+        }
+    };
+    std::mem::forget(library);
+
+    let entry: Rc<dyn Fn()> = Rc::from(entry().entry);
+    LOADED_ENTRY.with(|loaded| *loaded.borrow_mut() = Some(entry));
+    info!("Hot reloaded {}", path.display());
+    warn_reload_limits();
+    true
+}
+
+fn warn_reload_limits() {
+    static WARNED: Once = Once::new();
+    WARNED.call_once(|| {
+        warn!(
+            "Hot reload only swaps the UI: state outside `remember_saveable` was reset, and \
+             changes to packages, plugins, render modules or config need a restart"
+        );
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn loaded_entry_replaces_the_original() {
+        thread_local! {
+            static CALLS: Cell<(u32, u32)> = const { Cell::new((0, 0)) };
+        }
+        let entry = reloadable_entry(Box::new(|| {
+            CALLS.with(|calls| calls.set((calls.get().0 + 1, calls.get().1)));
+        }));
+
+        entry();
+        LOADED_ENTRY.with(|loaded| {
+            *loaded.borrow_mut() = Some(Rc::new(|| {
+                CALLS.with(|calls| calls.set((calls.get().0, calls.get().1 + 1)));
+            }));
+        });
+        entry();
+
+        assert_eq!(CALLS.with(Cell::get), (1, 1));
+        assert!(!load(Path::new("missing-hot-reload-library")));
+        LOADED_ENTRY.with(|loaded| loaded.borrow_mut().take());
+    }
+}
@@ -1,8 +1,8 @@
 //! # IME State Management
 //!
-//! This module provides IME state management.
+//! This module provides IME state management and preedit styling.
 
-use std::collections::VecDeque;
+use std::{collections::VecDeque, ops::Range};
 
 /// Maximum number of IME events to keep in the queue.
 ///
@@ -77,3 +77,139 @@ impl ImeState {
         self.events.drain(..).collect()
     }
 }
+
+/// Underline style of a preedit segment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PreeditUnderline {
+    /// Regular underline for text that is still being composed.
+    Thin,
+    /// Emphasized underline for the clause currently being converted.
+    Thick,
+}
+
+/// A styled range of preedit text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PreeditSegment {
+    /// Byte range within the preedit text.
+    pub range: Range<usize>,
+    /// Underline used to draw the segment.
+    pub underline: PreeditUnderline,
+}
+
+/// Preedit text reported by the input method, split into styled segments.
+///
+/// Platforms report the active clause of a conversion as the preedit cursor
+/// range; it is drawn with a thick underline and the remaining text with a
+/// thin one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImePreedit {
+    /// The composed text.
+    pub text: String,
+    /// Byte range of the preedit cursor, or `None` when it should be hidden.
+    pub cursor: Option<Range<usize>>,
+    /// Styled segments covering the whole text in order.
+    pub segments: Vec<PreeditSegment>,
+}
+
+impl ImePreedit {
+    /// Builds the preedit for an IME event.
+    ///
+    /// Returns `None` for events other than a non-empty
+    /// [`winit::event::Ime::Preedit`]. An empty preedit ends the composition
+    /// without committing text.
+    pub fn from_event(event: &winit::event::Ime) -> Option<Self> {
+        let winit::event::Ime::Preedit(text, cursor) = event else {
+            return None;
+        };
+        if text.is_empty() {
+            return None;
+        }
+        let len = text.len();
+        let cursor = cursor.map(|(start, end)| start.min(end).min(len)..start.max(end).min(len));
+        let mut segments = Vec::new();
+        match cursor.clone().filter(|cursor| !cursor.is_empty()) {
+            Some(clause) => {
+                if clause.start > 0 {
+                    segments.push(PreeditSegment {
+                        range: 0..clause.start,
+                        underline: PreeditUnderline::Thin,
+                    });
+                }
+                segments.push(PreeditSegment {
+                    range: clause.clone(),
+                    underline: PreeditUnderline::Thick,
+                });
+                if clause.end < len {
+                    segments.push(PreeditSegment {
+                        range: clause.end..len,
+                        underline: PreeditUnderline::Thin,
+                    });
+                }
+            }
+            None => segments.push(PreeditSegment {
+                range: 0..len,
+                underline: PreeditUnderline::Thin,
+            }),
+        }
+        Some(Self {
+            text: text.clone(),
+            cursor,
+            segments,
+        })
+    }
+
+    /// Returns the range of the clause being converted, if any.
+    pub fn active_clause(&self) -> Option<Range<usize>> {
+        self.segments
+            .iter()
+            .find(|segment| segment.underline == PreeditUnderline::Thick)
+            .map(|segment| segment.range.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ImePreedit, PreeditUnderline};
+
+    #[test]
+    fn collapsed_cursor_underlines_whole_preedit() {
+        let preedit = ImePreedit::from_event(&winit::event::Ime::Preedit(
+            "nihao".to_string(),
+            Some((5, 5)),
+        ))
+        .expect("non-empty preedit");
+        assert_eq!(preedit.cursor, Some(5..5));
+        assert_eq!(preedit.segments.len(), 1);
+        assert_eq!(preedit.segments[0].range, 0..5);
+        assert_eq!(preedit.active_clause(), None);
+    }
+
+    #[test]
+    fn selected_cursor_marks_active_clause() {
+        let preedit = ImePreedit::from_event(&winit::event::Ime::Preedit(
+            "abcdef".to_string(),
+            Some((2, 4)),
+        ))
+        .expect("non-empty preedit");
+        let underlines: Vec<_> = preedit
+            .segments
+            .iter()
+            .map(|segment| (segment.range.clone(), segment.underline))
+            .collect();
+        assert_eq!(
+            underlines,
+            vec![
+                (0..2, PreeditUnderline::Thin),
+                (2..4, PreeditUnderline::Thick),
+                (4..6, PreeditUnderline::Thin),
+            ]
+        );
+        assert_eq!(preedit.active_clause(), Some(2..4));
+    }
+
+    #[test]
+    fn empty_preedit_is_not_a_composition() {
+        assert!(ImePreedit::from_event(&winit::event::Ime::Preedit(String::new(), None)).is_none());
+        assert!(ImePreedit::from_event(&winit::event::Ime::Commit("a".to_string())).is_none());
+    }
+}
@@ -0,0 +1,573 @@
+//! Input recording and replay.
+//!
+//! ## Usage
+//!
+//! Reproduce user-reported bugs and script complex gestures by recording a
+//! session's input and feeding it back into the app later.
+//!
+//! When the `input-replay` feature is enabled, input is recorded to the file
+//! named by the `TESSERA_INPUT_RECORD` environment variable or
+//! [`TesseraConfig::input_record_path`](crate::renderer::TesseraConfig).
+//! Pointer, touch, touchpad gesture, keyboard, modifier, IME and window size
+//! events are recorded, and every event is flushed as it happens so a
+//! crashing session is still captured.
+//!
+//! A recording named by `TESSERA_INPUT_REPLAY` or
+//! [`TesseraConfig::input_replay_path`](crate::renderer::TesseraConfig) is
+//! replayed once the window opens, at the cadence it was recorded. Live input
+//! keeps working during a replay, and nothing is recorded while replaying.
+//!
+//! Key events keep their logical and physical key, location, state, repeat
+//! flag and text. winit can't create keyboard events, so they are replayed
+//! as [`KeyEvent`]s straight into the keyboard pipeline rather than as window
+//! events.
+//!
+//! ## Format
+//!
+//! The file contains one JSON object per line. The first line is a header:
+//!
+//! ```jsonl
+//! {"format":"tessera-input-recording","version":1}
+//! ```
+//!
+//! Each subsequent line is an event, where `t_ms` is the time since recording
+//! started in milliseconds and positions are in physical pixels:
+//!
+//! ```jsonl
+//! {"t_ms":1532.4,"kind":"cursor_moved","x":120.0,"y":48.5}
+//! {"t_ms":1610.0,"kind":"mouse_input","pressed":true,"button":"left"}
+//! {"t_ms":2204.7,"kind":"touch","id":3,"phase":"moved","x":88.0,"y":310.5}
+//! {"t_ms":2810.2,"kind":"key","physical_key":{"Code":"KeyS"},"logical_key":{"Character":"s"},"text":"s","location":"Standard","pressed":true,"repeat":false}
+//! ```
+
+use std::{
+    fs::{File, create_dir_all},
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use winit::{
+    dpi::{PhysicalPosition, PhysicalSize},
+    event::{
+        DeviceId, ElementState, Ime, MouseButton, MouseScrollDelta, Touch, TouchPhase, WindowEvent,
+    },
+    keyboard::{Key, KeyLocation, ModifiersState, PhysicalKey},
+};
+
+use crate::{KeyEvent, time::Instant};
+
+const FORMAT: &str = "tessera-input-recording";
+const VERSION: u32 = 1;
+
+static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+static PENDING_REPLAY: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Header {
+    format: String,
+    version: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct RecordedEvent {
+    t_ms: f64,
+    #[serde(flatten)]
+    input: RecordedInput,
+}
+
+/// An input event in a recording.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub(crate) enum RecordedInput {
+    Resized {
+        width: u32,
+        height: u32,
+    },
+    CursorMoved {
+        x: f64,
+        y: f64,
+    },
+    CursorLeft,
+    MouseInput {
+        pressed: bool,
+        button: RecordedButton,
+    },
+    MouseWheel {
+        delta: RecordedScrollDelta,
+        phase: RecordedPhase,
+    },
+    Touch {
+        id: u64,
+        phase: RecordedPhase,
+        x: f64,
+        y: f64,
+    },
+    Pinch {
+        delta: f64,
+        phase: RecordedPhase,
+    },
+    Rotation {
+        delta: f32,
+        phase: RecordedPhase,
+    },
+    DoubleTap,
+    Key {
+        physical_key: PhysicalKey,
+        logical_key: Key,
+        text: Option<String>,
+        location: KeyLocation,
+        pressed: bool,
+        repeat: bool,
+    },
+    Modifiers {
+        bits: u32,
+    },
+    ImeEnabled,
+    ImePreedit {
+        text: String,
+        cursor: Option<(usize, usize)>,
+    },
+    ImeCommit {
+        text: String,
+    },
+    ImeDisabled,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum RecordedButton {
+    Left,
+    Right,
+    Middle,
+    Back,
+    Forward,
+    Other(u16),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum RecordedScrollDelta {
+    Lines { x: f32, y: f32 },
+    Pixels { x: f64, y: f64 },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum RecordedPhase {
+    Started,
+    Moved,
+    Ended,
+    Cancelled,
+}
+
+impl From<TouchPhase> for RecordedPhase {
+    fn from(phase: TouchPhase) -> Self {
+        match phase {
+            TouchPhase::Started => Self::Started,
+            TouchPhase::Moved => Self::Moved,
+            TouchPhase::Ended => Self::Ended,
+            TouchPhase::Cancelled => Self::Cancelled,
+        }
+    }
+}
+
+impl From<RecordedPhase> for TouchPhase {
+    fn from(phase: RecordedPhase) -> Self {
+        match phase {
+            RecordedPhase::Started => Self::Started,
+            RecordedPhase::Moved => Self::Moved,
+            RecordedPhase::Ended => Self::Ended,
+            RecordedPhase::Cancelled => Self::Cancelled,
+        }
+    }
+}
+
+impl From<MouseButton> for RecordedButton {
+    fn from(button: MouseButton) -> Self {
+        match button {
+            MouseButton::Left => Self::Left,
+            MouseButton::Right => Self::Right,
+            MouseButton::Middle => Self::Middle,
+            MouseButton::Back => Self::Back,
+            MouseButton::Forward => Self::Forward,
+            MouseButton::Other(code) => Self::Other(code),
+        }
+    }
+}
+
+impl From<RecordedButton> for MouseButton {
+    fn from(button: RecordedButton) -> Self {
+        match button {
+            RecordedButton::Left => Self::Left,
+            RecordedButton::Right => Self::Right,
+            RecordedButton::Middle => Self::Middle,
+            RecordedButton::Back => Self::Back,
+            RecordedButton::Forward => Self::Forward,
+            RecordedButton::Other(code) => Self::Other(code),
+        }
+    }
+}
+
+/// What a recorded input is replayed as.
+pub(crate) enum ReplayedInput {
+    Window(WindowEvent),
+    Key(KeyEvent),
+}
+
+impl RecordedInput {
+    fn from_key_event(event: KeyEvent) -> Self {
+        Self::Key {
+            physical_key: event.physical_key,
+            logical_key: event.logical_key,
+            text: event.text.map(|text| text.to_string()),
+            location: event.location,
+            pressed: event.state.is_pressed(),
+            repeat: event.repeat,
+        }
+    }
+
+    /// Converts a window event, or returns `None` for events that aren't
+    /// recorded.
+    fn from_window_event(event: &WindowEvent) -> Option<Self> {
+        Some(match event {
+            WindowEvent::Resized(size) => Self::Resized {
+                width: size.width,
+                height: size.height,
+            },
+            WindowEvent::CursorMoved { position, .. } => Self::CursorMoved {
+                x: position.x,
+                y: position.y,
+            },
+            WindowEvent::CursorLeft { .. } => Self::CursorLeft,
+            WindowEvent::MouseInput { state, button, .. } => Self::MouseInput {
+                pressed: state.is_pressed(),
+                button: (*button).into(),
+            },
+            WindowEvent::MouseWheel { delta, phase, .. } => Self::MouseWheel {
+                delta: match *delta {
+                    MouseScrollDelta::LineDelta(x, y) => RecordedScrollDelta::Lines { x, y },
+                    MouseScrollDelta::PixelDelta(position) => RecordedScrollDelta::Pixels {
+                        x: position.x,
+                        y: position.y,
+                    },
+                },
+                phase: (*phase).into(),
+            },
+            WindowEvent::Touch(touch) => Self::Touch {
+                id: touch.id,
+                phase: touch.phase.into(),
+                x: touch.location.x,
+                y: touch.location.y,
+            },
+            WindowEvent::PinchGesture { delta, phase, .. } => Self::Pinch {
+                delta: *delta,
+                phase: (*phase).into(),
+            },
+            WindowEvent::RotationGesture { delta, phase, .. } => Self::Rotation {
+                delta: *delta,
+                phase: (*phase).into(),
+            },
+            WindowEvent::DoubleTapGesture { .. } => Self::DoubleTap,
+            WindowEvent::KeyboardInput { event, .. } => {
+                Self::from_key_event(KeyEvent::from(event.clone()))
+            }
+            WindowEvent::ModifiersChanged(modifiers) => Self::Modifiers {
+                bits: modifiers.state().bits(),
+            },
+            WindowEvent::Ime(Ime::Enabled) => Self::ImeEnabled,
+            WindowEvent::Ime(Ime::Preedit(text, cursor)) => Self::ImePreedit {
+                text: text.clone(),
+                cursor: *cursor,
+            },
+            WindowEvent::Ime(Ime::Commit(text)) => Self::ImeCommit { text: text.clone() },
+            WindowEvent::Ime(Ime::Disabled) => Self::ImeDisabled,
+            _ => return None,
+        })
+    }
+
+    /// Converts the input back into the event it was recorded from.
+    pub(crate) fn into_replayed(self) -> ReplayedInput {
+        // SAFETY: `dummy` has no safety requirements beyond not relying on
+        // the id being distinct from real devices, and Tessera ignores device
+        // ids.
+        let device_id = unsafe { DeviceId::dummy() };
+        ReplayedInput::Window(match self {
+            Self::Resized { width, height } => {
+                WindowEvent::Resized(PhysicalSize::new(width, height))
+            }
+            Self::CursorMoved { x, y } => WindowEvent::CursorMoved {
+                device_id,
+                position: PhysicalPosition::new(x, y),
+            },
+            Self::CursorLeft => WindowEvent::CursorLeft { device_id },
+            Self::MouseInput { pressed, button } => WindowEvent::MouseInput {
+                device_id,
+                state: if pressed {
+                    ElementState::Pressed
+                } else {
+                    ElementState::Released
+                },
+                button: button.into(),
+            },
+            Self::MouseWheel { delta, phase } => WindowEvent::MouseWheel {
+                device_id,
+                delta: match delta {
+                    RecordedScrollDelta::Lines { x, y } => MouseScrollDelta::LineDelta(x, y),
+                    RecordedScrollDelta::Pixels { x, y } => {
+                        MouseScrollDelta::PixelDelta(PhysicalPosition::new(x, y))
+                    }
+                },
+                phase: phase.into(),
+            },
+            Self::Touch { id, phase, x, y } => WindowEvent::Touch(Touch {
+                device_id,
+                phase: phase.into(),
+                location: PhysicalPosition::new(x, y),
+                force: None,
+                id,
+            }),
+            Self::Pinch { delta, phase } => WindowEvent::PinchGesture {
+                device_id,
+                delta,
+                phase: phase.into(),
+            },
+            Self::Rotation { delta, phase } => WindowEvent::RotationGesture {
+                device_id,
+                delta,
+                phase: phase.into(),
+            },
+            Self::DoubleTap => WindowEvent::DoubleTapGesture { device_id },
+            Self::Key {
+                physical_key,
+                logical_key,
+                text,
+                location,
+                pressed,
+                repeat,
+            } => {
+                return ReplayedInput::Key(KeyEvent {
+                    physical_key,
+                    logical_key,
+                    text: text.map(Into::into),
+                    location,
+                    state: if pressed {
+                        ElementState::Pressed
+                    } else {
+                        ElementState::Released
+                    },
+                    repeat,
+                });
+            }
+            Self::Modifiers { bits } => {
+                WindowEvent::ModifiersChanged(ModifiersState::from_bits_truncate(bits).into())
+            }
+            Self::ImeEnabled => WindowEvent::Ime(Ime::Enabled),
+            Self::ImePreedit { text, cursor } => WindowEvent::Ime(Ime::Preedit(text, cursor)),
+            Self::ImeCommit { text } => WindowEvent::Ime(Ime::Commit(text)),
+            Self::ImeDisabled => WindowEvent::Ime(Ime::Disabled),
+        })
+    }
+}
+
+struct Recorder {
+    writer: BufWriter<File>,
+    started_at: Instant,
+}
+
+impl Recorder {
+    fn create(path: &Path) -> io::Result<Self> {
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            create_dir_all(parent)?;
+        }
+        let mut writer = BufWriter::new(File::create(path)?);
+        let header = Header {
+            format: FORMAT.to_string(),
+            version: VERSION,
+        };
+        serde_json::to_writer(&mut writer, &header)?;
+        writer.write_all(b"\n")?;
+        writer.flush()?;
+        Ok(Self {
+            writer,
+            started_at: Instant::now(),
+        })
+    }
+
+    fn write(&mut self, input: RecordedInput) -> io::Result<()> {
+        let event = RecordedEvent {
+            t_ms: self.started_at.elapsed().as_secs_f64() * 1000.0,
+            input,
+        };
+        serde_json::to_writer(&mut self.writer, &event)?;
+        self.writer.write_all(b"\n")?;
+        self.writer.flush()
+    }
+}
+
+/// Sets up recording and replay; a replay path disables recording.
+pub(crate) fn init(record_path: Option<PathBuf>, replay_path: Option<PathBuf>) {
+    if let Some(replay_path) = replay_path {
+        if record_path.is_some() {
+            warn!("Input recording is disabled while replaying input");
+        }
+        *PENDING_REPLAY.lock() = Some(replay_path);
+        return;
+    }
+    let Some(record_path) = record_path else {
+        return;
+    };
+    match Recorder::create(&record_path) {
+        Ok(recorder) => {
+            info!("Recording input to {}", record_path.display());
+            *RECORDER.lock() = Some(recorder);
+        }
+        Err(err) => warn!(
+            "Failed to start recording input to {}: {err}",
+            record_path.display()
+        ),
+    }
+}
+
+/// Appends `event` to the recording, if one is in progress.
+pub(crate) fn record(event: &WindowEvent) {
+    let mut recorder = RECORDER.lock();
+    let Some(active) = recorder.as_mut() else {
+        return;
+    };
+    let Some(input) = RecordedInput::from_window_event(event) else {
+        return;
+    };
+    if let Err(err) = active.write(input) {
+        warn!("Failed to record input, recording stopped: {err}");
+        *recorder = None;
+    }
+}
+
+/// Starts the pending replay, sending each input through `send` when it is
+/// due. Replay stops early when `send` returns `false`.
+pub(crate) fn start_replay(send: impl Fn(RecordedInput) -> bool + Send + 'static) {
+    let Some(path) = PENDING_REPLAY.lock().take() else {
+        return;
+    };
+    let events = match load(&path) {
+        Ok(events) => events,
+        Err(err) => {
+            warn!("Failed to load input recording {}: {err}", path.display());
+            return;
+        }
+    };
+    info!(
+        "Replaying {} input events from {}",
+        events.len(),
+        path.display()
+    );
+    let spawned = thread::Builder::new()
+        .name("tessera-input-replay".into())
+        .spawn(move || {
+            let started_at = Instant::now();
+            for event in events {
+                let due = Duration::from_secs_f64(event.t_ms.max(0.0) / 1000.0);
+                thread::sleep(due.saturating_sub(started_at.elapsed()));
+                if !send(event.input) {
+                    return;
+                }
+            }
+            info!("Input replay finished");
+        });
+    if let Err(err) = spawned {
+        warn!("Failed to start input replay: {err}");
+    }
+}
+
+fn load(path: &Path) -> io::Result<Vec<RecordedEvent>> {
+    let mut lines = BufReader::new(File::open(path)?).lines();
+    let header: Header = match lines.next() {
+        Some(line) => serde_json::from_str(&line?)?,
+        None => return Ok(Vec::new()),
+    };
+    if header.format != FORMAT || header.version != VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "expected a {FORMAT} version {VERSION} file, found {} version {}",
+                header.format, header.version
+            ),
+        ));
+    }
+    let mut events = Vec::new();
+    for line in lines {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        events.push(serde_json::from_str(&line)?);
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recorded_inputs_round_trip_through_window_events() {
+        let inputs = [
+            RecordedInput::MouseWheel {
+                delta: RecordedScrollDelta::Pixels { x: 0.0, y: -12.5 },
+                phase: RecordedPhase::Moved,
+            },
+            RecordedInput::Touch {
+                id: 3,
+                phase: RecordedPhase::Started,
+                x: 88.0,
+                y: 310.5,
+            },
+            RecordedInput::Modifiers {
+                bits: (ModifiersState::CONTROL | ModifiersState::SHIFT).bits(),
+            },
+        ];
+        for input in inputs {
+            let event = RecordedEvent {
+                t_ms: 1.5,
+                input: input.clone(),
+            };
+            let line = serde_json::to_string(&event).unwrap();
+            assert_eq!(serde_json::from_str::<RecordedEvent>(&line).unwrap(), event);
+            let ReplayedInput::Window(window_event) = input.clone().into_replayed() else {
+                panic!("{input:?} must replay as a window event");
+            };
+            assert_eq!(RecordedInput::from_window_event(&window_event), Some(input));
+        }
+    }
+
+    #[test]
+    fn recorded_keys_round_trip_through_key_events() {
+        let mut repeated = KeyEvent::press(Key::Character("s".into()));
+        repeated.physical_key = PhysicalKey::Code(winit::keyboard::KeyCode::KeyS);
+        repeated.repeat = true;
+        let events = [
+            repeated,
+            KeyEvent::release(Key::Named(winit::keyboard::NamedKey::Enter)),
+        ];
+        for key_event in events {
+            let event = RecordedEvent {
+                t_ms: 2.0,
+                input: RecordedInput::from_key_event(key_event.clone()),
+            };
+            let line = serde_json::to_string(&event).unwrap();
+            let parsed = serde_json::from_str::<RecordedEvent>(&line).unwrap();
+            assert_eq!(parsed, event);
+            let ReplayedInput::Key(replayed) = parsed.input.into_replayed() else {
+                panic!("key inputs must replay as key events");
+            };
+            assert_eq!(replayed, key_event);
+        }
+    }
+}
@@ -0,0 +1,401 @@
+//! # Inspector
+//!
+//! ## Usage
+//!
+//! Inspect the component tree of a running app: where each component was
+//! placed, the constraint it was measured under and the size it picked.
+//!
+//! When the `inspector` feature is enabled, pressing F12 toggles an overlay
+//! that outlines the bounds of every component. The component under the
+//! cursor is highlighted, and a panel shows its path from the root, its
+//! direct children with their instance keys, and its constraint, size and
+//! position. Clicking selects the component under the cursor so the panel
+//! stays on it while the cursor moves; clicking outside every component
+//! clears the selection.
+//!
+//! While the overlay is shown, left clicks go to the inspector instead of the
+//! app.
+
+use std::num::NonZero;
+
+use parking_lot::Mutex;
+use winit::{
+    event::{ElementState, MouseButton},
+    keyboard::{Key, NamedKey},
+};
+
+use crate::{
+    Color, Dp, KeyEvent, Px, PxPosition, PxRect, PxSize,
+    component_tree::{AxisConstraint, ComponentTree, Constraint},
+    render_graph::{RenderFragment, RenderGraph, RenderGraphBuilder},
+    renderer::drawer::{
+        sdf::{SdfShape, SdfShapeCommand},
+        text::{FontFamily, FontSettings, LineBreaking, TextCommand, TextConstraint, TextData},
+    },
+};
+
+/// Children listed in the panel before the rest are summarized.
+const MAX_LISTED_CHILDREN: usize = 12;
+const FONT_SIZE: Dp = Dp(12.0);
+const LINE_HEIGHT: Dp = Dp(16.0);
+const PANEL_PADDING: Dp = Dp(8.0);
+const PANEL_MARGIN: Dp = Dp(12.0);
+
+const OUTLINE_COLOR: Color = Color::new(0.0, 0.8, 1.0, 0.5);
+const HOVER_COLOR: Color = Color::new(0.0, 0.6, 1.0, 0.25);
+const SELECTED_COLOR: Color = Color::new(1.0, 0.5, 0.0, 0.3);
+const SELECTED_BORDER_COLOR: Color = Color::ORANGE;
+const PANEL_COLOR: Color = Color::new(0.08, 0.08, 0.1, 0.88);
+const TEXT_COLOR: Color = Color::new(0.92, 0.92, 0.92, 1.0);
+
+struct InspectorState {
+    active: bool,
+    /// Instance key of the selected component.
+    selected: Option<u64>,
+    /// Click waiting to be resolved against the next frame's tree.
+    pending_pick: Option<PxPosition>,
+}
+
+static STATE: Mutex<InspectorState> = Mutex::new(InspectorState {
+    active: false,
+    selected: None,
+    pending_pick: None,
+});
+
+/// Toggles the overlay on F12, returning whether the key was consumed.
+pub(crate) fn handle_key_event(event: &KeyEvent) -> bool {
+    if event.logical_key != Key::Named(NamedKey::F12) {
+        return false;
+    }
+    if event.state == ElementState::Pressed && !event.repeat {
+        let mut state = STATE.lock();
+        state.active = !state.active;
+        state.pending_pick = None;
+    }
+    true
+}
+
+/// Picks the component at `position` on left click while the overlay is
+/// shown, returning whether the button was consumed.
+pub(crate) fn handle_mouse_input(
+    state: ElementState,
+    button: MouseButton,
+    position: Option<PxPosition>,
+) -> bool {
+    let mut inspector = STATE.lock();
+    if !inspector.active || button != MouseButton::Left {
+        return false;
+    }
+    if state == ElementState::Pressed {
+        inspector.pending_pick = position;
+    }
+    true
+}
+
+/// Shows the overlay with the component `instance_key` selected, or clears
+/// the selection.
+#[cfg(feature = "devtools")]
+pub(crate) fn select(instance_key: Option<u64>) {
+    let mut state = STATE.lock();
+    state.active |= instance_key.is_some();
+    state.selected = instance_key;
+}
+
+/// A component as shown by the inspector.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct InspectedNode {
+    pub fn_name: String,
+    pub instance_key: u64,
+    /// Index of the parent in the snapshot.
+    pub parent: Option<usize>,
+    /// Absolute bounds, when the node was placed this frame.
+    pub rect: Option<PxRect>,
+    pub constraint: Option<Constraint>,
+}
+
+/// Collects the tree in preorder, so children follow their parent and paint
+/// order is preserved among siblings.
+pub(crate) fn snapshot(component_tree: &ComponentTree) -> Vec<InspectedNode> {
+    let tree = component_tree.tree();
+    let metadatas = component_tree.metadatas();
+    let Some(root) =
+        tree.get_node_id_at(NonZero::new(1).expect("root node index must be non-zero"))
+    else {
+        return Vec::new();
+    };
+    let mut nodes = Vec::new();
+    let mut stack = vec![(root, None)];
+    while let Some((node_id, parent)) = stack.pop() {
+        let Some(node) = tree.get(node_id) else {
+            continue;
+        };
+        let metadata = metadatas.get(&node_id);
+        let rect = metadata.and_then(|metadata| {
+            let position = metadata.abs_position?;
+            let size = metadata.computed_data?;
+            Some(PxRect::from_position_size(
+                position,
+                PxSize::new(size.width, size.height),
+            ))
+        });
+        let index = nodes.len();
+        nodes.push(InspectedNode {
+            fn_name: node.get().fn_name.clone(),
+            instance_key: node.get().instance_key,
+            parent,
+            rect,
+            constraint: metadata.and_then(|metadata| metadata.constraint),
+        });
+        let children: Vec<_> = node_id.children(tree).collect();
+        stack.extend(children.into_iter().rev().map(|child| (child, Some(index))));
+    }
+    nodes
+}
+
+/// Returns the deepest node containing `point`, preferring later siblings
+/// since they paint on top.
+fn hit_test(nodes: &[InspectedNode], point: PxPosition) -> Option<usize> {
+    nodes.iter().rposition(|node| {
+        node.rect
+            .is_some_and(|rect| rect.width.0 > 0 && rect.height.0 > 0 && rect.contains(point))
+    })
+}
+
+/// Appends the overlay to the frame graph when the inspector is shown.
+pub(crate) fn append_overlay(
+    graph: RenderGraph,
+    component_tree: &ComponentTree,
+    screen_size: PxSize,
+    cursor_position: Option<PxPosition>,
+) -> RenderGraph {
+    let mut state = STATE.lock();
+    if !state.active {
+        return graph;
+    }
+    let nodes = snapshot(component_tree);
+    if let Some(point) = state.pending_pick.take() {
+        state.selected = hit_test(&nodes, point).map(|index| nodes[index].instance_key);
+    }
+    let selected = state
+        .selected
+        .and_then(|key| nodes.iter().position(|node| node.instance_key == key));
+    drop(state);
+    let hovered = cursor_position.and_then(|point| hit_test(&nodes, point));
+
+    let mut builder = RenderGraphBuilder::from_graph(graph);
+    for (index, node) in nodes.iter().enumerate() {
+        let Some(rect) = node.rect else {
+            continue;
+        };
+        let command = if Some(index) == selected {
+            SdfShapeCommand::new(SdfShape::RECT, SELECTED_COLOR)
+                .with_border(2.0, SELECTED_BORDER_COLOR)
+        } else if Some(index) == hovered {
+            SdfShapeCommand::new(SdfShape::RECT, HOVER_COLOR).with_border(1.0, OUTLINE_COLOR)
+        } else {
+            SdfShapeCommand::new(SdfShape::RECT, Color::TRANSPARENT).with_border(1.0, OUTLINE_COLOR)
+        };
+        append_command(&mut builder, command, rect);
+    }
+    if let Some(focused) = selected.or(hovered) {
+        append_panel(
+            &mut builder,
+            &describe(&nodes, focused, selected.is_some()),
+            screen_size,
+            cursor_position,
+        );
+    }
+    builder.finish()
+}
+
+fn append_command(
+    builder: &mut RenderGraphBuilder,
+    command: impl crate::renderer::DrawCommand + 'static,
+    rect: PxRect,
+) {
+    let mut fragment = RenderFragment::default();
+    fragment.push_draw_command(command);
+    builder.append_fragment(
+        fragment,
+        PxSize::new(rect.width, rect.height),
+        PxPosition::new(rect.x, rect.y),
+        1.0,
+    );
+}
+
+/// Draws `text` in a panel on the side of the window away from the cursor.
+fn append_panel(
+    builder: &mut RenderGraphBuilder,
+    text: &str,
+    screen_size: PxSize,
+    cursor_position: Option<PxPosition>,
+) {
+    let font = FontSettings::new(FontFamily::SansSerif);
+    let font_size = FONT_SIZE.to_pixels_f32();
+    let line_height = LINE_HEIGHT.to_pixels_f32();
+    let padding = PANEL_PADDING.to_px();
+    let margin = PANEL_MARGIN.to_px();
+    let max_width = (screen_size.width.0 / 2 - margin.0 - padding.0 * 2).max(1);
+    let info = TextData::measure(
+        text.to_string(),
+        TEXT_COLOR,
+        font_size,
+        line_height,
+        &font,
+        LineBreaking::NONE,
+        TextConstraint {
+            max_width: Some(max_width as f32),
+            max_height: None,
+        },
+    );
+    let data = TextData::get(
+        text.to_string(),
+        TEXT_COLOR,
+        font_size,
+        line_height,
+        &font,
+        LineBreaking::NONE,
+        info.size,
+    );
+
+    let panel_size = PxSize::new(
+        Px(info.size[0] as i32) + padding * 2,
+        Px(info.size[1] as i32) + padding * 2,
+    );
+    let cursor_on_left = cursor_position.is_none_or(|point| point.x.0 < screen_size.width.0 / 2);
+    let x = if cursor_on_left {
+        screen_size.width - panel_size.width - margin
+    } else {
+        margin
+    };
+    let panel = PxRect::from_position_size(PxPosition::new(x.max(Px(0)), margin), panel_size);
+    append_command(
+        builder,
+        SdfShapeCommand::new(SdfShape::rounded(6.0), PANEL_COLOR),
+        panel,
+    );
+    append_command(
+        builder,
+        TextCommand {
+            data,
+            offset: PxPosition::new(padding, padding),
+        },
+        panel,
+    );
+}
+
+/// Describes the focused node: its path from the root, its children and its
+/// layout.
+fn describe(nodes: &[InspectedNode], focused: usize, selected: bool) -> String {
+    let mut path = vec![focused];
+    while let Some(parent) = nodes[*path.last().expect("path is never empty")].parent {
+        path.push(parent);
+    }
+    path.reverse();
+
+    let mut lines = vec![if selected {
+        "Inspector · click empty space to deselect, F12 to close".to_string()
+    } else {
+        "Inspector · click to select, F12 to close".to_string()
+    }];
+    for (depth, &index) in path.iter().enumerate() {
+        let marker = if index == focused { "▶ " } else { "" };
+        lines.push(format!(
+            "{}{marker}{}",
+            "  ".repeat(depth),
+            node_label(&nodes[index])
+        ));
+    }
+    let children: Vec<_> = nodes
+        .iter()
+        .filter(|node| node.parent == Some(focused))
+        .collect();
+    let indent = "  ".repeat(path.len());
+    for child in children.iter().take(MAX_LISTED_CHILDREN) {
+        lines.push(format!("{indent}{}", node_label(child)));
+    }
+    if children.len() > MAX_LISTED_CHILDREN {
+        lines.push(format!(
+            "{indent}… {} more",
+            children.len() - MAX_LISTED_CHILDREN
+        ));
+    }
+
+    let node = &nodes[focused];
+    lines.push(String::new());
+    lines.push(format!("instance key  {:#x}", node.instance_key));
+    lines.push(match node.constraint {
+        Some(constraint) => format!(
+            "constraint    w {}  h {}",
+            format_axis(constraint.width),
+            format_axis(constraint.height)
+        ),
+        None => "constraint    not measured".to_string(),
+    });
+    match node.rect {
+        Some(rect) => {
+            lines.push(format!(
+                "size          {} × {} px",
+                rect.width.0, rect.height.0
+            ));
+            lines.push(format!("position      ({}, {}) px", rect.x.0, rect.y.0));
+        }
+        None => lines.push("size          not placed".to_string()),
+    }
+    lines.join("\n")
+}
+
+fn node_label(node: &InspectedNode) -> String {
+    match node.rect {
+        Some(rect) => format!(
+            "{} #{:x}  {}×{}",
+            node.fn_name, node.instance_key, rect.width.0, rect.height.0
+        ),
+        None => format!("{} #{:x}", node.fn_name, node.instance_key),
+    }
+}
+
+fn format_axis(axis: AxisConstraint) -> String {
+    match axis.max {
+        Some(max) if max == axis.min => format!("={}", max.0),
+        Some(max) => format!("{}..{}", axis.min.0, max.0),
+        None => format!("{}..∞", axis.min.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(fn_name: &str, parent: Option<usize>, rect: PxRect) -> InspectedNode {
+        InspectedNode {
+            fn_name: fn_name.to_string(),
+            instance_key: 0,
+            parent,
+            rect: Some(rect),
+            constraint: None,
+        }
+    }
+
+    #[test]
+    fn hit_test_picks_the_deepest_topmost_node() {
+        let nodes = vec![
+            node("root", None, PxRect::new(Px(0), Px(0), Px(100), Px(100))),
+            node(
+                "column",
+                Some(0),
+                PxRect::new(Px(0), Px(0), Px(50), Px(100)),
+            ),
+            node("button", Some(1), PxRect::new(Px(0), Px(0), Px(50), Px(20))),
+            node(
+                "overlay",
+                Some(0),
+                PxRect::new(Px(40), Px(0), Px(60), Px(60)),
+            ),
+        ];
+        assert_eq!(hit_test(&nodes, PxPosition::new(Px(10), Px(10))), Some(2));
+        assert_eq!(hit_test(&nodes, PxPosition::new(Px(45), Px(10))), Some(3));
+        assert_eq!(hit_test(&nodes, PxPosition::new(Px(10), Px(80))), Some(1));
+        assert_eq!(hit_test(&nodes, PxPosition::new(Px(200), Px(10))), None);
+    }
+}
@@ -0,0 +1,64 @@
+//! UIKit helpers for the iOS renderer.
+
+use objc2::rc::Retained;
+use objc2_ui_kit::UIView;
+use tracing::warn;
+use winit::{
+    raw_window_handle::{HasWindowHandle, RawWindowHandle},
+    window::Window,
+};
+
+use crate::{dp::Dp, safe_area::SafeAreaInsets};
+
+fn ui_view(window: &Window) -> Option<Retained<UIView>> {
+    let raw_window_handle = match window.window_handle() {
+        Ok(handle) => handle.as_raw(),
+        Err(err) => {
+            warn!("Failed to fetch native window handle: {}", err);
+            return None;
+        }
+    };
+    let RawWindowHandle::UiKit(uikit) = raw_window_handle else {
+        return None;
+    };
+
+    // SAFETY: The pointer comes from winit's window handle and is valid for
+    // the lifetime of this call on the main thread.
+    let view = unsafe { Retained::<UIView>::retain(uikit.ui_view.as_ptr().cast()) };
+    if view.is_none() {
+        warn!("Failed to retain UIView from raw window handle");
+    }
+    view
+}
+
+/// Reads the safe area of the window's root view, in points.
+pub(crate) fn safe_area_insets(window: &Window) -> SafeAreaInsets {
+    let Some(view) = ui_view(window) else {
+        return SafeAreaInsets::default();
+    };
+    // UIKit points match dp: both are scaled to pixels by the window's scale
+    // factor.
+    let insets = view.safeAreaInsets();
+    SafeAreaInsets {
+        top: Dp(insets.top),
+        right: Dp(insets.right),
+        bottom: Dp(insets.bottom),
+        left: Dp(insets.left),
+    }
+}
+
+/// Shows the software keyboard by making the window's view the first
+/// responder, or hides it by resigning.
+pub(crate) fn set_soft_keyboard_visible(window: &Window, visible: bool) {
+    let Some(view) = ui_view(window) else {
+        return;
+    };
+    let changed = if visible {
+        view.becomeFirstResponder()
+    } else {
+        view.resignFirstResponder()
+    };
+    if !changed {
+        warn!("UIKit refused to change the first responder (visible: {visible})");
+    }
+}
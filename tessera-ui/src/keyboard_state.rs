@@ -4,7 +4,76 @@
 
 use std::collections::VecDeque;
 
-use winit::keyboard::ModifiersState;
+use winit::{
+    event::ElementState,
+    keyboard::{Key, KeyLocation, ModifiersState, NamedKey, NativeKeyCode, PhysicalKey, SmolStr},
+};
+
+/// A keyboard event delivered to components.
+///
+/// Mirrors the public fields of [`winit::event::KeyEvent`], which can only
+/// be created by the windowing backend, so tests and input replay can
+/// synthesize key presses.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeyEvent {
+    /// The key's position on the keyboard, independent of layout.
+    pub physical_key: PhysicalKey,
+    /// The key after applying the keyboard layout and modifiers.
+    pub logical_key: Key,
+    /// Text produced by this key press, if any.
+    pub text: Option<SmolStr>,
+    /// Which of several identical keys was used.
+    pub location: KeyLocation,
+    /// Whether the key was pressed or released.
+    pub state: ElementState,
+    /// Whether this is an auto-repeated press.
+    pub repeat: bool,
+}
+
+impl KeyEvent {
+    /// Creates a non-repeated key event for `logical_key`.
+    ///
+    /// The physical key is unidentified, and `text` is derived from
+    /// character keys and Space on press.
+    pub fn new(logical_key: Key, state: ElementState) -> Self {
+        let text = match (&logical_key, state) {
+            (Key::Character(text), ElementState::Pressed) => Some(text.clone()),
+            (Key::Named(NamedKey::Space), ElementState::Pressed) => Some(SmolStr::new(" ")),
+            _ => None,
+        };
+        Self {
+            physical_key: PhysicalKey::Unidentified(NativeKeyCode::Unidentified),
+            logical_key,
+            text,
+            location: KeyLocation::Standard,
+            state,
+            repeat: false,
+        }
+    }
+
+    /// Creates a key press for `logical_key`.
+    pub fn press(logical_key: Key) -> Self {
+        Self::new(logical_key, ElementState::Pressed)
+    }
+
+    /// Creates a key release for `logical_key`.
+    pub fn release(logical_key: Key) -> Self {
+        Self::new(logical_key, ElementState::Released)
+    }
+}
+
+impl From<winit::event::KeyEvent> for KeyEvent {
+    fn from(event: winit::event::KeyEvent) -> Self {
+        Self {
+            physical_key: event.physical_key,
+            logical_key: event.logical_key,
+            text: event.text,
+            location: event.location,
+            state: event.state,
+            repeat: event.repeat,
+        }
+    }
+}
 
 /// Maximum number of keyboard events to keep in the queue.
 ///
@@ -33,7 +102,7 @@ pub struct KeyboardState {
     /// maintaining FIFO (First In, First Out) ordering. The queue is
     /// automatically bounded by [`KEEP_EVENTS_COUNT`] to prevent memory
     /// issues.
-    events: VecDeque<winit::event::KeyEvent>,
+    events: VecDeque<KeyEvent>,
     /// Current state of the keyboard modifiers (e.g., Shift, Ctrl, Alt).
     modifiers: ModifiersState,
 }
@@ -49,8 +118,9 @@ impl KeyboardState {
     /// ## Parameters
     ///
     /// * `event` - The keyboard event to add to the queue. This should be a
-    ///   [`winit::event::KeyEvent`] received from the windowing system.
-    pub fn push_event(&mut self, event: winit::event::KeyEvent) {
+    ///   [`KeyEvent`] received from the windowing system or synthesized by a
+    ///   test.
+    pub fn push_event(&mut self, event: KeyEvent) {
         // Add the event to the deque
         self.events.push_back(event);
         // If the events deque is too long, we remove the oldest one
@@ -70,10 +140,10 @@ impl KeyboardState {
     ///
     /// ## Returns
     ///
-    /// A `Vec<winit::event::KeyEvent>` containing all keyboard events that were
+    /// A `Vec<KeyEvent>` containing all keyboard events that were
     /// in the queue, ordered from oldest to newest. If the queue was empty,
     /// returns an empty vector.
-    pub fn take_events(&mut self) -> Vec<winit::event::KeyEvent> {
+    pub fn take_events(&mut self) -> Vec<KeyEvent> {
         self.events.drain(..).collect()
     }
 
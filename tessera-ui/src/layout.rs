@@ -18,6 +18,7 @@ use crate::{
     prop::Prop,
     px::PxPosition,
     render_graph::RenderFragment,
+    render_scene::ClipShape,
     runtime::TesseraRuntime,
     tessera,
 };
@@ -525,6 +526,15 @@ impl RenderMetadataMut<'_> {
         self.metadata.clips_children = clips_children;
     }
 
+    /// Clips children of the current node to `shape`.
+    ///
+    /// The shape is expressed relative to the node origin. Non-rectangular
+    /// shapes are applied through the clip stencil buffer.
+    pub fn set_clip_shape(&mut self, shape: ClipShape) {
+        self.metadata.clips_children = true;
+        self.metadata.clip_shape = Some(shape);
+    }
+
     /// Multiplies the current node opacity by the provided factor.
     pub fn multiply_opacity(&mut self, opacity: f32) {
         self.metadata.opacity *= opacity;
@@ -280,26 +280,49 @@ extern crate self as tessera_ui;
 #[doc(hidden)]
 pub mod __private;
 pub mod accessibility;
+pub mod animation;
 #[cfg(target_os = "android")]
 pub mod android;
 pub mod asset;
+pub mod back_navigation;
+#[cfg(feature = "bench")]
+pub mod bench;
 mod build_tree;
 pub mod color;
+#[cfg(feature = "command-trace")]
+pub mod command_trace;
+#[cfg(feature = "component-spans")]
+mod component_spans;
 mod component_tree;
 pub mod context;
 mod cursor;
+pub mod deep_link;
+#[cfg(feature = "devtools")]
+pub mod devtools;
+pub mod dnd;
 pub mod dp;
 pub mod entry_point;
 pub mod entry_registry;
+pub mod error_boundary;
 mod execution_context;
 pub mod focus;
+pub mod gesture;
+#[cfg(feature = "hot-reload")]
+mod hot_reload;
 mod ime_state;
+#[cfg(feature = "input-replay")]
+mod input_replay;
+#[cfg(feature = "inspector")]
+mod inspector;
+#[cfg(target_os = "ios")]
+mod ios;
 mod keyboard_state;
 pub mod layout;
 pub mod modifier;
 pub(crate) mod pipeline_cache;
 pub mod pipeline_context;
 pub mod plugin;
+pub mod preview;
 #[cfg(feature = "profiling")]
 pub mod profiler;
 mod prop;
@@ -310,20 +333,29 @@ mod render_pass;
 pub mod render_scene;
 pub mod renderer;
 mod runtime;
+pub mod safe_area;
 pub mod scroll;
+pub mod shortcuts;
+#[cfg(feature = "saveable")]
+pub mod saveable;
+pub mod system_preferences;
 #[cfg(feature = "testing")]
 pub mod testing;
 mod thread_utils;
 pub mod time;
 
 pub use accesskit;
+pub use glyphon;
 pub use indextree::{Arena, NodeId};
-pub use tessera_macros::{entry, tessera};
+pub use tessera_macros::{entry, preview, tessera};
 pub use wgpu;
 pub use winit;
 
 pub use crate::{
-    accessibility::{AccessibilityActionHandler, AccessibilityId, AccessibilityNode},
+    accessibility::{
+        AccessibilityActionData, AccessibilityActionHandler, AccessibilityId, AccessibilityNode,
+        SemanticsScope, semantics,
+    },
     asset::AssetExt,
     color::Color,
     component_tree::{
@@ -331,10 +363,17 @@ pub use crate::{
         ImeRequest, ImeSession, KeyboardInput, KeyboardInputHandlerFn, MeasurementError,
         ParentConstraint, PointerEventPass, PointerInput, PointerInputHandlerFn,
     },
-    context::{Context, provide_context, use_context},
+    context::{Context, provide_context, provide_context_keyed, use_context},
     cursor::{
-        CursorEventContent, GestureState, MOUSE_POINTER_ID, PointerChange, PointerId,
-        PressKeyEventType, ScrollDeltaUnit, ScrollEventContent, ScrollEventSource,
+        CursorEventContent, FLING_START_THRESHOLD, FlingDecay, MAX_FLING_VELOCITY,
+        MOUSE_POINTER_ID, PinchEventContent, PointerChange, PointerId, PressKeyEventType,
+        RotateEventContent, ScrollDeltaUnit, ScrollEventContent, ScrollEventSource, ScrollPhase,
+        VELOCITY_IDLE_CUTOFF, VELOCITY_SAMPLE_WINDOW, VelocityTracker, clamp_fling_velocity,
+        rubber_band,
+    },
+    dnd::{
+        DragAndDropModifierExt, DragOrigin, DragPayload, DragSource, DropEvent, DropOutcome,
+        DropTarget,
     },
     dp::Dp,
     entry_point::EntryPoint,
@@ -343,6 +382,12 @@ pub use crate::{
         FocusDirection, FocusGroupNode, FocusManager, FocusProperties, FocusRequester,
         FocusScopeNode, FocusState, FocusTraversalPolicy, FocusTraversalStrategy,
     },
+    gesture::{
+        Gesture, GestureDetector, GestureModifierExt, GestureSettings, SecondaryActivation,
+        SecondaryActivationRecognizer, SecondaryActivationSettings, SecondaryActivationSource,
+    },
+    ime_state::{ImePreedit, PreeditSegment, PreeditUnderline},
+    keyboard_state::KeyEvent,
     layout::{
         DefaultLayoutPolicy, LayoutPolicy, LayoutResult, NoopRenderPolicy, RenderInput,
         RenderMetadataMut, RenderPolicy,
@@ -366,7 +411,7 @@ pub use crate::{
         RenderGraphParts, RenderResource, RenderResourceId, RenderTextureDesc,
     },
     render_module::RenderModule,
-    render_scene::{Command, CompositeCommand, DrawRegion, PaddingRect, SampleRegion},
+    render_scene::{ClipShape, Command, CompositeCommand, DrawRegion, PaddingRect, SampleRegion},
     renderer::{
         Renderer,
         composite::{
@@ -377,14 +422,19 @@ pub use crate::{
             self, ComputablePipeline, ComputeCommand, ComputePipelineRegistry, ComputeResource,
             ComputeResourceManager, ComputeResourceRef,
         },
-        drawer::{self, DrawCommand, DrawablePipeline, PipelineRegistry, command},
-        external::{ExternalTextureHandle, ExternalTextureRegistry},
+        drawer::{
+            self, AttributedString, DrawCommand, DrawablePipeline, FontFamily, FontSettings,
+            FontWeight, GradientStop, InstancedDrawCommand, Paint, PipelineRegistry, SpanStyle,
+            TextCommand, TextConstraint, TextData, TileMode, command, font_registry,
+        },
+        external::{ExternalTextureHandle, ExternalTextureRegistry, ImportedTexture},
     },
     runtime::{
         FrameNanosControl, State, current_frame_nanos, current_frame_time, frame_delta, key,
         receive_frame_nanos, remember, remember_with_key, retain, retain_with_key,
     },
     scroll::{PlatformScrollConfig, normalize_platform_scroll_delta, platform_scroll_config},
+    shortcuts::{KeyChord, Shortcut, ShortcutModifierExt, ShortcutScope},
 };
 
 use ime_state::ImeState;
@@ -24,6 +24,7 @@ use crate::{
     layout::{LayoutInput, RenderInput},
     prop::CallbackWith,
     runtime::{TesseraRuntime, ensure_build_phase},
+    shortcuts::Shortcut,
     winit::window::CursorIcon,
 };
 
@@ -323,6 +324,7 @@ enum ModifierAction {
     Build(Arc<dyn BuildModifierNode>),
     Semantics(Arc<dyn SemanticsModifierNode>),
     Cursor(Arc<dyn CursorModifierNode>),
+    Hover(CallbackWith<bool>),
     PointerPreviewInput(Arc<dyn PointerInputModifierNode>),
     PointerInput(Arc<dyn PointerInputModifierNode>),
     PointerFinalInput(Arc<dyn PointerInputModifierNode>),
@@ -330,6 +332,7 @@ enum ModifierAction {
     KeyboardInput(Arc<dyn KeyboardInputModifierNode>),
     ImePreviewInput(Arc<dyn ImeInputModifierNode>),
     ImeInput(Arc<dyn ImeInputModifierNode>),
+    Shortcut(Arc<Shortcut>),
     Focus(FocusModifierOp),
 }
 
@@ -340,6 +343,7 @@ pub(crate) enum OrderedModifierAction {
     Draw(Arc<dyn DrawModifierNode>),
     ParentData(Arc<dyn ParentDataModifierNode>),
     Cursor(Arc<dyn CursorModifierNode>),
+    Hover(CallbackWith<bool>),
     PointerPreviewInput(Arc<dyn PointerInputModifierNode>),
     PointerInput(Arc<dyn PointerInputModifierNode>),
     PointerFinalInput(Arc<dyn PointerInputModifierNode>),
@@ -347,6 +351,7 @@ pub(crate) enum OrderedModifierAction {
     KeyboardInput(Arc<dyn KeyboardInputModifierNode>),
     ImePreviewInput(Arc<dyn ImeInputModifierNode>),
     ImeInput(Arc<dyn ImeInputModifierNode>),
+    Shortcut(Arc<Shortcut>),
 }
 
 #[derive(Clone)]
@@ -431,10 +436,23 @@ pub trait FocusModifierExt {
         F: Into<CallbackWith<FocusRevealRequest, bool>>;
 }
 
-/// Cursor-specific modifier extensions for [`Modifier`].
+/// Hover and cursor modifier extensions for [`Modifier`].
 pub trait CursorModifierExt {
     /// Sets the cursor icon used while the pointer hovers this node.
+    ///
+    /// When hovered nodes overlap, the innermost topmost node wins. The icon
+    /// also wins over icons requested by pointer handlers through
+    /// [`PointerInput::request_cursor_icon`](crate::PointerInput::request_cursor_icon).
     fn hover_cursor_icon(self, icon: CursorIcon) -> Modifier;
+
+    /// Registers a callback invoked with `true` when the pointer enters this
+    /// node and `false` when it leaves.
+    ///
+    /// Only the topmost node under the pointer and its ancestors count as
+    /// hovered, so nodes covered by siblings do not receive enter events.
+    fn on_hover<F>(self, handler: F) -> Modifier
+    where
+        F: Into<CallbackWith<bool>>;
 }
 
 impl Modifier {
@@ -513,6 +531,10 @@ impl Modifier {
         self.push_action(ModifierAction::Semantics(Arc::new(node)))
     }
 
+    pub(crate) fn push_shortcut(self, shortcut: Shortcut) -> Self {
+        self.push_action(ModifierAction::Shortcut(Arc::new(shortcut)))
+    }
+
     fn push_cursor<N>(self, node: N) -> Self
     where
         N: CursorModifierNode,
@@ -706,6 +728,7 @@ impl Modifier {
                 ModifierAction::Draw(node) => Some(OrderedModifierAction::Draw(node)),
                 ModifierAction::ParentData(node) => Some(OrderedModifierAction::ParentData(node)),
                 ModifierAction::Cursor(node) => Some(OrderedModifierAction::Cursor(node)),
+                ModifierAction::Hover(handler) => Some(OrderedModifierAction::Hover(handler)),
                 ModifierAction::PointerPreviewInput(node) => {
                     Some(OrderedModifierAction::PointerPreviewInput(node))
                 }
@@ -725,6 +748,9 @@ impl Modifier {
                     Some(OrderedModifierAction::ImePreviewInput(node))
                 }
                 ModifierAction::ImeInput(node) => Some(OrderedModifierAction::ImeInput(node)),
+                ModifierAction::Shortcut(shortcut) => {
+                    Some(OrderedModifierAction::Shortcut(shortcut))
+                }
                 ModifierAction::Build(_)
                 | ModifierAction::Semantics(_)
                 | ModifierAction::Focus(_) => None,
@@ -915,6 +941,13 @@ impl CursorModifierExt for Modifier {
     fn hover_cursor_icon(self, icon: CursorIcon) -> Modifier {
         self.push_cursor(StaticCursorModifierNode { icon })
     }
+
+    fn on_hover<F>(self, handler: F) -> Modifier
+    where
+        F: Into<CallbackWith<bool>>,
+    {
+        self.push_action(ModifierAction::Hover(handler.into()))
+    }
 }
 
 impl FocusModifierExt for Modifier {
@@ -2,7 +2,9 @@
 
 use crate::{
     CompositeCommand, ComputablePipeline, ComputeCommand, DrawCommand, DrawablePipeline,
-    renderer::{RenderCore, RenderResources, composite::CompositePipeline},
+    renderer::{
+        ExternalTextureRegistry, RenderCore, RenderResources, composite::CompositePipeline,
+    },
 };
 
 /// Context passed to pipeline initialization functions.
@@ -21,6 +23,15 @@ impl<'a> PipelineContext<'a> {
         self.core.resources()
     }
 
+    /// Returns the registry used to import externally produced textures.
+    ///
+    /// Render modules can keep a clone together with
+    /// [`RenderResources::device`] to create and import textures later, e.g.
+    /// from a video decoder.
+    pub fn external_textures(&self) -> ExternalTextureRegistry {
+        self.core.external_textures().clone()
+    }
+
     /// Registers a draw pipeline for a specific command type.
     pub fn register_draw_pipeline<T, P>(&mut self, pipeline: P)
     where
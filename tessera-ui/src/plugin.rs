@@ -7,7 +7,7 @@ use std::{
     sync::{Arc, OnceLock},
 };
 
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use tracing::{error, warn};
 use winit::window::Window;
 
@@ -19,6 +19,11 @@ pub type PluginResult = Result<(), Box<dyn Error + Send + Sync>>;
 
 type DesktopWakeHandler = Arc<dyn Fn() + Send + Sync>;
 
+type UiTask = Box<dyn FnOnce() + Send>;
+
+/// Tasks queued from any thread to run on the UI thread before the next frame.
+pub(crate) type UiTaskQueue = Arc<Mutex<Vec<UiTask>>>;
+
 /// Host-managed desktop window actions exposed to UI and platform plugins.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum DesktopWindowAction {
@@ -30,6 +35,12 @@ pub enum DesktopWindowAction {
     ToggleMaximize,
     /// Requests application shutdown through the renderer host.
     Close,
+    /// Hides the active window, for example to a tray icon.
+    Hide,
+    /// Shows, restores and focuses the active window.
+    Show,
+    /// Shows or hides the native title bar and borders.
+    SetDecorations(bool),
 }
 
 impl DesktopWindowAction {
@@ -46,6 +57,7 @@ impl DesktopWindowAction {
 pub struct DesktopPlatformContext {
     window: Arc<Window>,
     pending_action: Arc<RwLock<Option<DesktopWindowAction>>>,
+    ui_tasks: UiTaskQueue,
     wake_handler: DesktopWakeHandler,
 }
 
@@ -80,6 +92,35 @@ impl DesktopPlatformContext {
         self.request_action(DesktopWindowAction::Close);
     }
 
+    /// Hides the current window without closing the application.
+    pub fn hide(&self) {
+        self.request_action(DesktopWindowAction::Hide);
+    }
+
+    /// Shows the current window again, restoring and focusing it.
+    pub fn show(&self) {
+        self.request_action(DesktopWindowAction::Show);
+    }
+
+    /// Shows or hides the native title bar and borders of the current window.
+    ///
+    /// Without decorations, the window edges resize the window and apps draw
+    /// their own title bar with drag regions.
+    pub fn set_decorations(&self, decorations: bool) {
+        self.request_action(DesktopWindowAction::SetDecorations(decorations));
+    }
+
+    /// Runs `task` on the UI thread before the next frame is built.
+    ///
+    /// Platform callbacks such as notification clicks often arrive on
+    /// background threads. Runtime state like callbacks and
+    /// [`deep_link`](crate::deep_link) lives on the UI thread, so hand such
+    /// work over with this.
+    pub fn run_on_ui_thread(&self, task: impl FnOnce() + Send + 'static) {
+        self.ui_tasks.lock().push(Box::new(task));
+        (self.wake_handler)();
+    }
+
     fn request_action(&self, action: DesktopWindowAction) {
         let mut pending_action = self.pending_action.write();
         let next_action = DesktopWindowAction::merge_pending(*pending_action, action);
@@ -95,11 +136,13 @@ impl DesktopPlatformContext {
     pub(crate) fn new(
         window: Arc<Window>,
         pending_action: Arc<RwLock<Option<DesktopWindowAction>>>,
+        ui_tasks: UiTaskQueue,
         wake_handler: DesktopWakeHandler,
     ) -> Self {
         Self {
             window,
             pending_action,
+            ui_tasks,
             wake_handler,
         }
     }
@@ -0,0 +1,115 @@
+//! Component previews registered with `#[preview]`.
+//!
+//! ## Usage
+//!
+//! Render a component on its own, at a fixed size and theme, from a preview
+//! runner instead of navigating to it in the full app. `cargo tessera preview`
+//! opens a window listing every preview of a package.
+
+use crate::dp::Dp;
+
+/// Theme a preview asks the preview runner to apply.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PreviewTheme {
+    /// Light color scheme.
+    Light,
+    /// Dark color scheme.
+    Dark,
+}
+
+/// Locale a preview runner asks previews to render in, provided as context.
+///
+/// Localized components can read it with
+/// [`use_context`](crate::context::use_context) to pick their strings.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PreviewLocale(pub String);
+
+/// A component registered with `#[preview]`.
+#[derive(Debug)]
+pub struct Preview {
+    pub(crate) name: &'static str,
+    pub(crate) module_path: &'static str,
+    pub(crate) size: Option<(Dp, Dp)>,
+    pub(crate) theme: Option<PreviewTheme>,
+    pub(crate) render: fn(),
+}
+
+impl Preview {
+    /// Name shown by preview runners, the function name unless given.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Module the preview was declared in.
+    pub fn module_path(&self) -> &'static str {
+        self.module_path
+    }
+
+    /// Width and height to render the preview at, if fixed.
+    pub fn size(&self) -> Option<(Dp, Dp)> {
+        self.size
+    }
+
+    /// Theme to render the preview with, if fixed.
+    pub fn theme(&self) -> Option<PreviewTheme> {
+        self.theme
+    }
+
+    /// Builds the previewed component.
+    ///
+    /// # Panics
+    ///
+    /// Must be called during a component build.
+    pub fn render(&self) {
+        (self.render)();
+    }
+}
+
+inventory::collect!(Preview);
+
+/// Returns every preview linked into the binary, ordered by module and name.
+///
+/// A hot reloaded library registers its previews again; only the most
+/// recently registered copy of each is returned.
+///
+/// # Examples
+///
+/// ```
+/// use tessera_ui::preview::previews;
+///
+/// for preview in previews() {
+///     println!("{}::{}", preview.module_path(), preview.name());
+/// }
+/// ```
+pub fn previews() -> Vec<&'static Preview> {
+    // Newer registrations come first, and the stable sort keeps that order
+    // among copies of the same preview.
+    let mut previews: Vec<_> = inventory::iter::<Preview>.into_iter().collect();
+    previews.sort_by_key(|preview| (preview.module_path, preview.name));
+    previews.dedup_by_key(|preview| (preview.module_path, preview.name));
+    previews
+}
+
+/// Returns the first preview called `name`.
+pub fn find_preview(name: &str) -> Option<&'static Preview> {
+    previews().into_iter().find(|preview| preview.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{preview, tessera};
+
+    #[preview(crate_path = crate, name = "Dark card", size = (320, 200), theme = dark)]
+    #[tessera(crate)]
+    fn dark_card_preview() {}
+
+    #[test]
+    fn preview_attribute_registers_the_component() {
+        let preview = find_preview("Dark card").expect("preview should be registered");
+
+        assert_eq!(preview.module_path(), module_path!());
+        assert_eq!(preview.size(), Some((Dp(320.0), Dp(200.0))));
+        assert_eq!(preview.theme(), Some(PreviewTheme::Dark));
+    }
+}
@@ -98,6 +98,15 @@
 //!
 //! See [`FrameEventRecord`] and [`ComponentRecord`] for equivalent Rust
 //! structures.
+//!
+//! ## Chrome Trace
+//!
+//! When [`set_trace_output_path`] is called, every timed span is also
+//! written as a Chrome trace event, for timeline viewers such as
+//! [Perfetto](https://ui.perfetto.dev). Frames, the build, draw and render
+//! stages and each component's build, measure and record spans are on the
+//! `UI` track. When the device supports timestamp queries, the GPU time of
+//! each frame is on the `GPU` track.
 use std::{
     collections::HashMap,
     fs::{File, OpenOptions, create_dir_all},
@@ -132,6 +141,26 @@ pub enum Phase {
     Input,
     /// GPU render stage (frame-level).
     RenderFrame,
+    /// Whole frame, from build to present (frame-level).
+    Frame,
+    /// Component tree build stage (frame-level).
+    BuildTree,
+    /// Measure and record stages (frame-level).
+    Draw,
+}
+
+impl Phase {
+    fn trace_category(self) -> &'static str {
+        match self {
+            Self::Build | Self::BuildTree => "build",
+            Self::Measure => "measure",
+            Self::Record => "record",
+            Self::Input => "input",
+            Self::RenderFrame => "render",
+            Self::Frame => "frame",
+            Self::Draw => "draw",
+        }
+    }
 }
 
 /// Component tree build strategy used for a frame.
@@ -164,6 +193,8 @@ pub enum RedrawReason {
     MouseWheel,
     /// Touch event.
     TouchInput,
+    /// Touchpad pinch, rotation or smart-magnify gesture.
+    TouchpadGesture,
     /// Scale factor change event.
     ScaleFactorChanged,
     /// Keyboard input event.
@@ -174,6 +205,8 @@ pub enum RedrawReason {
     ImeEvent,
     /// Focus change event.
     FocusChanged,
+    /// OS file drag-and-drop event.
+    FileDragAndDrop,
     /// Runtime state invalidation requires next frame.
     RuntimeInvalidation,
     /// Frame awaiter callback requires next frame.
@@ -288,6 +321,16 @@ pub struct RuntimeMeta {
     pub kind: RuntimeEventKind,
 }
 
+/// GPU execution time of a frame, measured with timestamp queries.
+pub struct GpuMeta {
+    /// Frame index the GPU work belongs to.
+    pub frame_idx: u64,
+    /// When the frame's commands were submitted to the queue.
+    pub submitted_at: Instant,
+    /// Time the GPU spent executing the frame's commands.
+    pub gpu_time_ns: u64,
+}
+
 /// # Examples
 /// A minimal frame event written to the profiler output:
 ///
@@ -303,6 +346,7 @@ enum Message {
     FrameMeta(FrameMeta),
     WakeMeta(WakeMeta),
     RuntimeMeta(RuntimeMeta),
+    GpuMeta(GpuMeta),
 }
 
 struct ProfilerRuntime {
@@ -313,11 +357,13 @@ struct WorkerState {
     frames: HashMap<u64, Vec<Sample>>,
     writer: BufWriter<File>,
     header_written: bool,
+    trace: Option<ChromeTraceWriter>,
 }
 
 static RUNTIME: OnceLock<ProfilerRuntime> = OnceLock::new();
 static FRAME_INDEX: AtomicU64 = AtomicU64::new(0);
 static OUTPUT_PATH: OnceLock<PathBuf> = OnceLock::new();
+static TRACE_OUTPUT: OnceLock<(PathBuf, Instant)> = OnceLock::new();
 
 fn output_path() -> PathBuf {
     OUTPUT_PATH
@@ -331,6 +377,16 @@ pub fn set_output_path(path: impl AsRef<Path>) {
     let _ = OUTPUT_PATH.set(path.as_ref().to_path_buf());
 }
 
+/// Also write a Chrome trace of every timed span to `path`. Must be called
+/// before any profiling begins.
+///
+/// The trace can be opened in Perfetto or `chrome://tracing`. It is streamed
+/// in the JSON array format, whose closing bracket is optional, so traces of
+/// apps that were killed still load.
+pub fn set_trace_output_path(path: impl AsRef<Path>) {
+    let _ = TRACE_OUTPUT.set((path.as_ref().to_path_buf(), Instant::now()));
+}
+
 fn profiler_runtime() -> &'static ProfilerRuntime {
     RUNTIME.get_or_init(|| {
         let (sender, receiver) = mpsc::channel::<Message>();
@@ -371,16 +427,26 @@ fn worker_loop(receiver: mpsc::Receiver<Message>) {
         frames: HashMap::new(),
         writer: BufWriter::new(file),
         header_written: false,
+        trace: TRACE_OUTPUT
+            .get()
+            .and_then(|(path, epoch)| ChromeTraceWriter::create(path, *epoch)),
     };
 
     for msg in receiver {
         match msg {
             Message::Sample(sample) => {
-                state
-                    .frames
-                    .entry(sample.frame_idx)
-                    .or_default()
-                    .push(sample);
+                if let Some(trace) = &mut state.trace {
+                    trace.write_sample(&sample);
+                }
+                // Frame-level samples are only traced; frame records are
+                // built from component samples.
+                if sample.node_id.is_some() {
+                    state
+                        .frames
+                        .entry(sample.frame_idx)
+                        .or_default()
+                        .push(sample);
+                }
             }
             Message::FrameMeta(frame_meta) => {
                 let samples = state
@@ -403,10 +469,143 @@ fn worker_loop(receiver: mpsc::Receiver<Message>) {
                 });
                 write_event(&mut state, &event);
             }
+            Message::GpuMeta(gpu_meta) => {
+                if let Some(trace) = &mut state.trace {
+                    trace.write_gpu(&gpu_meta);
+                }
+            }
         };
     }
 }
 
+/// Thread ids of the tracks in the Chrome trace.
+const TRACE_UI_TID: u32 = 1;
+const TRACE_GPU_TID: u32 = 2;
+
+/// Event in the Chrome trace event format.
+#[derive(Serialize)]
+struct ChromeTraceEvent<'a> {
+    name: &'a str,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    cat: &'a str,
+    ph: &'static str,
+    /// Microseconds since the trace started.
+    ts: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dur: Option<f64>,
+    pid: u32,
+    tid: u32,
+    args: serde_json::Value,
+}
+
+/// Streams spans as Chrome trace events.
+struct ChromeTraceWriter {
+    writer: BufWriter<File>,
+    epoch: Instant,
+    events_written: bool,
+}
+
+impl ChromeTraceWriter {
+    fn create(path: &Path, epoch: Instant) -> Option<Self> {
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+            && let Err(err) = create_dir_all(parent)
+        {
+            error!(
+                "tessera profiler failed to create trace directory {}: {err}",
+                parent.display()
+            );
+            return None;
+        }
+        let file = match File::create(path) {
+            Ok(file) => file,
+            Err(err) => {
+                error!(
+                    "tessera profiler failed to open trace file {}: {err}",
+                    path.display()
+                );
+                return None;
+            }
+        };
+        let mut writer = Self {
+            writer: BufWriter::new(file),
+            epoch,
+            events_written: false,
+        };
+        let _ = writer.writer.write_all(b"[");
+        for (tid, name) in [(TRACE_UI_TID, "UI"), (TRACE_GPU_TID, "GPU")] {
+            writer.write(&ChromeTraceEvent {
+                name: "thread_name",
+                cat: "",
+                ph: "M",
+                ts: 0.0,
+                dur: None,
+                pid: 1,
+                tid,
+                args: serde_json::json!({ "name": name }),
+            });
+        }
+        Some(writer)
+    }
+
+    fn micros_since_epoch(&self, instant: Instant) -> f64 {
+        instant.saturating_duration_since(self.epoch).as_nanos() as f64 / 1000.0
+    }
+
+    fn write_sample(&mut self, sample: &Sample) {
+        let name = match (sample.phase, sample.fn_name.as_deref()) {
+            (Phase::Frame, _) => format!("frame {}", sample.frame_idx),
+            (_, Some(fn_name)) => mangle_component_fn_name(fn_name),
+            (phase, None) => phase.trace_category().to_string(),
+        };
+        let mut args = serde_json::json!({ "frame": sample.frame_idx });
+        if let Some((w, h)) = sample.computed_size {
+            args["size"] = serde_json::json!({ "w": w, "h": h });
+        }
+        self.write(&ChromeTraceEvent {
+            name: &name,
+            cat: sample.phase.trace_category(),
+            ph: "X",
+            ts: self.micros_since_epoch(sample.start),
+            dur: Some(
+                sample
+                    .end
+                    .saturating_duration_since(sample.start)
+                    .as_nanos() as f64
+                    / 1000.0,
+            ),
+            pid: 1,
+            tid: TRACE_UI_TID,
+            args,
+        });
+    }
+
+    /// GPU timestamps aren't on the CPU clock, so the span is placed at the
+    /// frame's submit time.
+    fn write_gpu(&mut self, gpu_meta: &GpuMeta) {
+        self.write(&ChromeTraceEvent {
+            name: &format!("gpu frame {}", gpu_meta.frame_idx),
+            cat: "gpu",
+            ph: "X",
+            ts: self.micros_since_epoch(gpu_meta.submitted_at),
+            dur: Some(gpu_meta.gpu_time_ns as f64 / 1000.0),
+            pid: 1,
+            tid: TRACE_GPU_TID,
+            args: serde_json::json!({ "frame": gpu_meta.frame_idx }),
+        });
+    }
+
+    fn write(&mut self, event: &ChromeTraceEvent<'_>) {
+        let separator: &[u8] = if self.events_written { b",\n" } else { b"\n" };
+        if self.writer.write_all(separator).is_ok()
+            && serde_json::to_writer(&mut self.writer, event).is_ok()
+        {
+            self.events_written = true;
+        }
+        let _ = self.writer.flush();
+    }
+}
+
 fn flush_frame(state: &mut WorkerState, frame_meta: FrameMeta, samples: Vec<Sample>) {
     if let Some(record) = build_frame_record(frame_meta, samples) {
         let event = TraceEvent::Frame(record);
@@ -442,7 +641,7 @@ pub fn begin_frame(frame_idx: u64) {
 /// Samples are sent immediately; no-op for compatibility.
 pub fn end_frame() {}
 
-fn current_frame_idx() -> u64 {
+pub(crate) fn current_frame_idx() -> u64 {
     FRAME_INDEX.load(Ordering::Relaxed)
 }
 
@@ -884,6 +1083,13 @@ pub fn submit_runtime_meta(runtime_meta: RuntimeMeta) {
     }
 }
 
+/// Submit GPU frame timing metadata.
+pub fn submit_gpu_meta(gpu_meta: GpuMeta) {
+    if let Err(err) = profiler_runtime().sender.send(Message::GpuMeta(gpu_meta)) {
+        eprintln!("tessera profiler gpu meta send failed: {err}");
+    }
+}
+
 /// Construct a build-phase scope guard using the provided function name.
 pub fn make_build_scope_guard(
     node_id: NodeId,
@@ -950,7 +1156,12 @@ impl Drop for ScopeGuard {
 
 #[cfg(test)]
 mod tests {
-    use super::{mangle_component_fn_name, mangle_component_fn_name_opt};
+    use std::time::Duration;
+
+    use super::{
+        ChromeTraceWriter, Instant, Phase, Sample, mangle_component_fn_name,
+        mangle_component_fn_name_opt,
+    };
 
     #[test]
     fn mangle_shard_component_name() {
@@ -972,4 +1183,38 @@ mod tests {
             Some("text_input".to_string())
         );
     }
+
+    #[test]
+    fn chrome_trace_is_a_json_array_of_spans() {
+        let path = std::env::temp_dir().join(format!(
+            "tessera-profiler-trace-{}.json",
+            std::process::id()
+        ));
+        let epoch = Instant::now();
+        let mut writer = ChromeTraceWriter::create(&path, epoch).expect("trace file");
+        writer.write_sample(&Sample {
+            phase: Phase::Build,
+            frame_idx: 3,
+            node_id: None,
+            parent_node_id: None,
+            fn_name: Some("__home_shard_component".to_string()),
+            abs_pos: None,
+            start: epoch + Duration::from_micros(10),
+            end: epoch + Duration::from_micros(25),
+            computed_size: None,
+        });
+        drop(writer);
+
+        let mut trace = std::fs::read_to_string(&path).expect("trace contents");
+        let _ = std::fs::remove_file(&path);
+        trace.push(']');
+        let events: Vec<serde_json::Value> = serde_json::from_str(&trace).expect("valid trace");
+        let span = events.last().expect("span event");
+        assert_eq!(events.len(), 3);
+        assert_eq!(span["name"], "home");
+        assert_eq!(span["cat"], "build");
+        assert_eq!(span["ts"], 10.0);
+        assert_eq!(span["dur"], 15.0);
+        assert_eq!(span["args"]["frame"], 3);
+    }
 }
@@ -10,8 +10,8 @@ use std::{any::Any, marker::PhantomData, ptr, sync::Arc};
 use crate::{
     runtime::{
         FunctorHandle, invoke_callback_handle, invoke_callback_with_handle,
-        invoke_render_slot_handle, invoke_render_slot_with_handle, remember_callback_handle,
-        remember_callback_with_handle, remember_render_slot_handle,
+        invoke_render_slot_handle, invoke_render_slot_with_handle, is_functor_handle_alive,
+        remember_callback_handle, remember_callback_with_handle, remember_render_slot_handle,
         remember_render_slot_with_handle, track_render_slot_read_dependency,
     },
     tessera,
@@ -177,6 +177,17 @@ where
         }
     }
 
+    /// Returns whether the closure behind the handle still exists.
+    ///
+    /// Handles kept past the build of the component that created them go
+    /// stale once it stops creating them; calling a stale handle panics.
+    pub(crate) fn is_alive(&self) -> bool {
+        match self.repr {
+            CallbackWithRepr::Handle(handle) => is_functor_handle_alive(handle),
+            CallbackWithRepr::Static(_) => true,
+        }
+    }
+
     fn from_static(handler: fn(T) -> R) -> Self {
         Self {
             repr: CallbackWithRepr::Static(handler),
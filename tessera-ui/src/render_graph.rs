@@ -12,7 +12,7 @@ use std::{
 use smallvec::SmallVec;
 
 use crate::{
-    Command, CompositeCommand, ComputeCommand, DrawCommand, DrawRegion, SampleRegion,
+    ClipShape, Command, CompositeCommand, ComputeCommand, DrawCommand, DrawRegion, SampleRegion,
     px::{Px, PxPosition, PxRect, PxSize},
 };
 
@@ -109,6 +109,24 @@ impl RenderFragment {
 
     /// Adds a draw command with default scene resource bindings.
     pub fn push_draw_command<C: DrawCommand + 'static>(&mut self, command: C) -> u32 {
+        let op = Self::draw_op(command);
+        self.push_op(op)
+    }
+
+    /// Adds a draw command covering `rect`, relative to the component origin,
+    /// instead of the component bounds.
+    pub fn push_draw_command_in<C: DrawCommand + 'static>(
+        &mut self,
+        command: C,
+        rect: PxRect,
+    ) -> u32 {
+        let mut op = Self::draw_op(command);
+        op.position_override = Some(PxPosition::new(rect.x, rect.y));
+        op.size_override = Some(PxSize::new(rect.width, rect.height));
+        self.push_op(op)
+    }
+
+    fn draw_op<C: DrawCommand + 'static>(command: C) -> RenderFragmentOp {
         let type_id = TypeId::of::<C>();
         let read = command
             .sample_region()
@@ -116,7 +134,7 @@ impl RenderFragment {
             .then_some(RenderResourceId::SceneColor);
         let write = Some(RenderResourceId::SceneColor);
 
-        let op = RenderFragmentOp {
+        RenderFragmentOp {
             command: Command::Draw(Box::new(command)),
             type_id,
             read,
@@ -124,8 +142,7 @@ impl RenderFragment {
             deps: SmallVec::new(),
             size_override: None,
             position_override: None,
-        };
-        self.push_op(op)
+        }
     }
 
     /// Adds a compute command with default scene resource bindings.
@@ -291,10 +308,27 @@ impl RenderGraphBuilder {
         }
     }
 
+    /// Creates a builder that appends after the ops of `graph`.
+    #[cfg(feature = "inspector")]
+    pub(crate) fn from_graph(graph: RenderGraph) -> Self {
+        let sequence_index = graph
+            .ops
+            .iter()
+            .map(|op| op.sequence_index + 1)
+            .max()
+            .unwrap_or(0);
+        Self {
+            ops: graph.ops,
+            resources: graph.resources,
+            external_resources: graph.external_resources,
+            sequence_index,
+        }
+    }
+
     /// Pushes a clip push op into the graph.
-    pub(crate) fn push_clip_push(&mut self, rect: PxRect) {
+    pub(crate) fn push_clip_push(&mut self, shape: ClipShape) {
         self.ops.push(RenderGraphOp {
-            command: Command::ClipPush(rect),
+            command: Command::ClipPush(shape),
             type_id: TypeId::of::<Command>(),
             read: None,
             write: None,
@@ -3,7 +3,8 @@
 use smallvec::SmallVec;
 
 use crate::{
-    Command, ComputeCommand, DrawCommand, DrawRegion, Px, PxPosition, PxRect, PxSize, SampleRegion,
+    ClipShape, Command, ComputeCommand, DrawCommand, DrawRegion, Px, PxPosition, PxRect, PxSize,
+    SampleRegion,
     render_graph::{ExternalTextureDesc, RenderGraphOp, RenderResource, RenderResourceId},
 };
 
@@ -127,9 +128,9 @@ impl RenderPassGraph {
                 Command::Composite(_) => {
                     panic!("Composite commands must be expanded before render pass planning");
                 }
-                Command::ClipPush(rect) => {
+                Command::ClipPush(shape) => {
                     flush_compute_pass(&mut passes, &mut compute_builder);
-                    draw_builder.push_clip(ClipOps::Push(rect));
+                    draw_builder.push_clip(ClipOps::Push(shape));
                 }
                 Command::ClipPop => {
                     flush_compute_pass(&mut passes, &mut compute_builder);
@@ -288,7 +289,7 @@ pub(crate) enum DrawOrClip {
 
 /// Clip stack operations for a render pass.
 pub(crate) enum ClipOps {
-    Push(PxRect),
+    Push(ClipShape),
     Pop,
 }
 
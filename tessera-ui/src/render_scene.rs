@@ -5,7 +5,7 @@ use dyn_clone::DynClone;
 
 use crate::{
     ComputeCommand, DrawCommand,
-    px::{Px, PxRect},
+    px::{Px, PxPosition, PxRect},
 };
 
 /// Defines the sampling requirements for a rendering command that needs a
@@ -81,6 +81,106 @@ impl SampleRegion {
     }
 }
 
+/// Shape of a clip region pushed with [`Command::ClipPush`].
+///
+/// Rectangles are applied as scissor rects. Other shapes are rasterized into
+/// the clip stencil buffer, using their bounds as the scissor rect.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClipShape {
+    /// An axis-aligned rectangle.
+    Rect(PxRect),
+    /// A rectangle with rounded corners.
+    RoundedRect {
+        /// Bounds of the rectangle.
+        rect: PxRect,
+        /// Corner radii in pixels (tl, tr, br, bl).
+        corner_radii: [f32; 4],
+    },
+    /// An ellipse inscribed in the rectangle.
+    Ellipse(PxRect),
+    /// A rectangle with optionally rounded corners, rotated about its center.
+    Rotated {
+        /// Bounds of the rectangle before rotation.
+        rect: PxRect,
+        /// Corner radii in pixels (tl, tr, br, bl).
+        corner_radii: [f32; 4],
+        /// Clockwise rotation in degrees.
+        rotation: f32,
+    },
+}
+
+impl ClipShape {
+    /// Returns the axis-aligned bounds of the shape.
+    pub fn bounds(&self) -> PxRect {
+        match self {
+            Self::Rect(rect) | Self::RoundedRect { rect, .. } | Self::Ellipse(rect) => *rect,
+            Self::Rotated { rect, rotation, .. } => rotated_bounds(*rect, *rotation),
+        }
+    }
+
+    /// Returns `true` if the shape needs the clip stencil buffer.
+    pub fn needs_stencil(&self) -> bool {
+        match self {
+            Self::Rect(_) => false,
+            Self::RoundedRect { corner_radii, .. } => corner_radii.iter().any(|r| *r > 0.0),
+            Self::Ellipse(_) => true,
+            Self::Rotated {
+                corner_radii,
+                rotation,
+                ..
+            } => rotation % 90.0 != 0.0 || corner_radii.iter().any(|r| *r > 0.0),
+        }
+    }
+
+    /// Returns the same shape moved by `offset`.
+    pub fn translate(self, offset: PxPosition) -> Self {
+        let move_rect = |rect: PxRect| PxRect {
+            x: rect.x + offset.x,
+            y: rect.y + offset.y,
+            ..rect
+        };
+        match self {
+            Self::Rect(rect) => Self::Rect(move_rect(rect)),
+            Self::RoundedRect { rect, corner_radii } => Self::RoundedRect {
+                rect: move_rect(rect),
+                corner_radii,
+            },
+            Self::Ellipse(rect) => Self::Ellipse(move_rect(rect)),
+            Self::Rotated {
+                rect,
+                corner_radii,
+                rotation,
+            } => Self::Rotated {
+                rect: move_rect(rect),
+                corner_radii,
+                rotation,
+            },
+        }
+    }
+}
+
+fn rotated_bounds(rect: PxRect, rotation: f32) -> PxRect {
+    let (sin, cos) = rotation.to_radians().sin_cos();
+    let (half_width, half_height) = (rect.width.to_f32() * 0.5, rect.height.to_f32() * 0.5);
+    let extent_x = half_width * cos.abs() + half_height * sin.abs();
+    let extent_y = half_width * sin.abs() + half_height * cos.abs();
+    let center_x = rect.x.to_f32() + half_width;
+    let center_y = rect.y.to_f32() + half_height;
+    // Absorbs trig error so quarter turns keep exact integer bounds.
+    const SNAP: f32 = 1e-3;
+    let left = (center_x - extent_x + SNAP).floor() as i32;
+    let top = (center_y - extent_y + SNAP).floor() as i32;
+    let right = (center_x + extent_x - SNAP).ceil() as i32;
+    let bottom = (center_y + extent_y - SNAP).ceil() as i32;
+    PxRect::new(Px(left), Px(top), Px(right - left), Px(bottom - top))
+}
+
+impl From<PxRect> for ClipShape {
+    fn from(rect: PxRect) -> Self {
+        Self::Rect(rect)
+    }
+}
+
 /// Trait for composite rendering commands that expand into draw/compute ops.
 pub trait CompositeCommand: DynClone + Downcast + Send + Sync {}
 
@@ -100,9 +200,9 @@ pub enum Command {
     Compute(Box<dyn ComputeCommand>),
     /// A composite command that expands into draw/compute operations.
     Composite(Box<dyn CompositeCommand>),
-    /// A command to push a clipping rectangle onto the stack.
-    ClipPush(PxRect),
-    /// A command to pop the most recent clipping rectangle from the stack.
+    /// A command to push a clipping shape onto the stack.
+    ClipPush(ClipShape),
+    /// A command to pop the most recent clipping shape from the stack.
     ClipPop,
 }
 
@@ -129,7 +229,7 @@ impl Clone for Command {
             Self::Draw(cmd) => Self::Draw(cmd.clone()),
             Self::Compute(cmd) => Self::Compute(cmd.clone()),
             Self::Composite(cmd) => Self::Composite(cmd.clone()),
-            Self::ClipPush(rect) => Self::ClipPush(*rect),
+            Self::ClipPush(shape) => Self::ClipPush(*shape),
             Self::ClipPop => Self::ClipPop,
         }
     }
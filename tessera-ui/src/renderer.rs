@@ -24,23 +24,26 @@ use winit::{
     error::EventLoopError,
     event::WindowEvent,
     event_loop::{ActiveEventLoop, ControlFlow, EventLoop},
+    keyboard::{KeyCode, ModifiersState, PhysicalKey},
     window::{ResizeDirection, Window, WindowId},
 };
 
 use crate::{
-    ImeRequest, ImeState, PxPosition,
+    Color, ImeRequest, ImeState, PxPosition,
+    back_navigation::{BackKey, back_key, dispatch_back, is_back_press},
     build_tree::build_component_tree,
     component_tree::{LayoutFrameDiagnostics, WindowRequests, clear_layout_snapshots},
     context::{reset_component_context_tracking, reset_context_read_dependencies},
     cursor::{
-        CursorEventContent, CursorState, GestureState, MOUSE_POINTER_ID, PointerChange,
-        PressKeyEventType,
+        CursorEventContent, CursorState, MOUSE_POINTER_ID, PinchEventContent, PointerChange,
+        PressKeyEventType, RotateEventContent, ScrollPhase,
     },
+    deep_link::reset_deep_link_read_dependencies,
     dp::SCALE_FACTOR,
     focus::{FocusDirection, flush_pending_focus_callbacks},
     keyboard_state::KeyboardState,
     pipeline_context::PipelineContext,
-    plugin::{DesktopPlatformContext, DesktopWindowAction, PluginContext, PluginHost},
+    plugin::{DesktopPlatformContext, DesktopWindowAction, PluginContext, PluginHost, UiTaskQueue},
     px::PxSize,
     render_graph::{RenderGraph, RenderGraphExecution},
     render_module::RenderModule,
@@ -52,19 +55,21 @@ use crate::{
         reset_state_read_dependencies, retain_persistent_focus_handles, take_layout_dirty_nodes,
         tick_frame_nanos_receivers,
     },
+    safe_area::{refresh_safe_area_insets, reset_safe_area_read_dependencies},
+    system_preferences::{refresh_system_preferences, reset_system_preference_read_dependencies},
     thread_utils,
     time::Instant,
 };
 
 pub use crate::render_scene::{Command, DrawRegion, PaddingRect, SampleRegion};
 
-use self::core::RenderTimingBreakdown;
+use self::core::{FrameHudTimings, RenderTimingBreakdown};
 
 pub use compute::{
     ComputablePipeline, ComputeBatchItem, ComputePipelineRegistry, ErasedComputeBatchItem,
 };
 pub use drawer::{DrawCommand, DrawablePipeline, PipelineRegistry};
-pub use external::{ExternalTextureHandle, ExternalTextureRegistry};
+pub use external::{ExternalTextureHandle, ExternalTextureRegistry, ImportedTexture};
 
 #[cfg(feature = "debug-dirty-overlay")]
 use crate::PxRect;
@@ -81,7 +86,11 @@ use crate::profiler::{
 use crate::runtime::frame_delta;
 #[cfg(feature = "profiling")]
 use std::collections::BTreeSet;
-#[cfg(feature = "profiling")]
+#[cfg(any(
+    feature = "profiling",
+    feature = "command-trace",
+    feature = "input-replay"
+))]
 use std::path::PathBuf;
 
 #[cfg(target_family = "wasm")]
@@ -106,6 +115,10 @@ enum RendererUserEvent {
     RuntimeRedrawWake,
     #[cfg(target_family = "wasm")]
     WebInitReady(u64),
+    #[cfg(feature = "hot-reload")]
+    HotReload(std::path::PathBuf),
+    #[cfg(feature = "input-replay")]
+    ReplayInput(crate::input_replay::RecordedInput),
 }
 
 impl From<AccessKitEvent> for RendererUserEvent {
@@ -148,6 +161,14 @@ impl RuntimePendingWork {
     }
 }
 
+#[cfg(feature = "profiling")]
+fn init_profiler_output(config: &TesseraConfig) {
+    crate::profiler::set_output_path(resolve_profiler_output_path(config));
+    if let Some(path) = resolve_profiler_trace_output_path(config) {
+        crate::profiler::set_trace_output_path(path);
+    }
+}
+
 #[cfg(feature = "profiling")]
 fn resolve_profiler_output_path(config: &TesseraConfig) -> PathBuf {
     if let Ok(path) = std::env::var("TESSERA_PROFILING_OUTPUT")
@@ -163,6 +184,67 @@ fn resolve_profiler_output_path(config: &TesseraConfig) -> PathBuf {
     config.profiler_output_path.clone()
 }
 
+#[cfg(feature = "profiling")]
+fn resolve_profiler_trace_output_path(config: &TesseraConfig) -> Option<PathBuf> {
+    if let Ok(path) = std::env::var("TESSERA_PROFILING_TRACE")
+        && !path.trim().is_empty()
+    {
+        return Some(PathBuf::from(path));
+    }
+    config.profiler_trace_output_path.clone()
+}
+
+#[cfg(feature = "devtools")]
+fn start_devtools(
+    config: &TesseraConfig,
+    proxy: winit::event_loop::EventLoopProxy<RendererUserEvent>,
+) {
+    let port = std::env::var("TESSERA_DEVTOOLS_PORT")
+        .ok()
+        .and_then(|port| port.trim().parse().ok())
+        .unwrap_or(config.devtools_port);
+    crate::devtools::start(port, move || {
+        let _ = proxy.send_event(RendererUserEvent::RuntimeRedrawWake);
+    });
+}
+
+#[cfg(feature = "command-trace")]
+fn resolve_command_trace_output_path(config: &TesseraConfig) -> PathBuf {
+    if let Ok(path) = std::env::var("TESSERA_COMMAND_TRACE_OUTPUT")
+        && !path.trim().is_empty()
+    {
+        return PathBuf::from(path);
+    }
+    config.command_trace_output_path.clone()
+}
+
+#[cfg(feature = "input-replay")]
+fn init_input_replay(config: &TesseraConfig) {
+    let env_path = |name: &str| {
+        std::env::var(name)
+            .ok()
+            .filter(|path| !path.trim().is_empty())
+            .map(PathBuf::from)
+    };
+    crate::input_replay::init(
+        env_path("TESSERA_INPUT_RECORD").or_else(|| config.input_record_path.clone()),
+        env_path("TESSERA_INPUT_REPLAY").or_else(|| config.input_replay_path.clone()),
+    );
+}
+
+#[cfg(feature = "component-spans")]
+fn resolve_component_span_sampling(config: &TesseraConfig) -> u32 {
+    std::env::var("TESSERA_COMPONENT_SPAN_SAMPLING")
+        .ok()
+        .and_then(|sampling| sampling.trim().parse().ok())
+        .unwrap_or(config.component_span_sampling)
+}
+
+fn is_frame_hud_hotkey(event: &crate::KeyEvent, modifiers: ModifiersState) -> bool {
+    event.physical_key == PhysicalKey::Code(KeyCode::KeyH)
+        && modifiers == ModifiersState::CONTROL | ModifiersState::SHIFT
+}
+
 /// Window creation options for desktop platforms.
 #[derive(Debug, Clone)]
 pub struct WindowConfig {
@@ -172,6 +254,9 @@ pub struct WindowConfig {
     pub transparent: bool,
     /// Whether the window is resizable.
     pub resizable: bool,
+    /// Whether Escape and Alt+Left trigger back navigation when a back
+    /// handler is enabled. See [`crate::back_navigation`].
+    pub keyboard_back_navigation: bool,
 }
 
 impl Default for WindowConfig {
@@ -180,6 +265,86 @@ impl Default for WindowConfig {
             decorations: true,
             transparent: true,
             resizable: true,
+            keyboard_back_navigation: false,
+        }
+    }
+}
+
+/// Frame timing HUD options.
+///
+/// The HUD shows an FPS graph, the build, measure, draw and render times of
+/// the frame, and its draw command and render pass counts. The render core
+/// draws it with its own pipeline, so it keeps working when component
+/// pipelines misbehave.
+#[derive(Debug, Clone)]
+pub struct FrameHudConfig {
+    /// Whether the HUD is shown at startup. Defaults to `false`.
+    pub visible: bool,
+    /// Whether Ctrl+Shift+H toggles the HUD. Defaults to `true` in debug
+    /// builds.
+    pub hotkey: bool,
+}
+
+impl Default for FrameHudConfig {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            hotkey: cfg!(debug_assertions),
+        }
+    }
+}
+
+/// Startup splash options.
+///
+/// The splash frame is presented as soon as the window surface exists, before
+/// render modules register their pipelines and the first component tree is
+/// built, so cold starts do not show an empty or garbage window. The render
+/// core draws it with its own pipeline.
+#[derive(Debug, Clone)]
+pub struct SplashConfig {
+    /// Color filling the window. Defaults to white.
+    pub background: Color,
+    /// Image centered on the background.
+    pub logo: Option<SplashLogo>,
+}
+
+impl Default for SplashConfig {
+    fn default() -> Self {
+        Self {
+            background: Color::WHITE,
+            logo: None,
+        }
+    }
+}
+
+/// An image shown on the startup splash frame.
+#[derive(Debug, Clone)]
+pub struct SplashLogo {
+    pub(crate) rgba: Arc<[u8]>,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+}
+
+impl SplashLogo {
+    /// Creates a logo from sRGB RGBA8 pixels, row by row.
+    ///
+    /// The logo is drawn `width` by `height` logical pixels, shrunk to fit
+    /// half of the window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rgba` does not hold `width * height` pixels.
+    pub fn from_rgba(rgba: impl Into<Arc<[u8]>>, width: u32, height: u32) -> Self {
+        let rgba = rgba.into();
+        assert_eq!(
+            rgba.len(),
+            width as usize * height as usize * 4,
+            "splash logo must hold width * height RGBA pixels"
+        );
+        Self {
+            rgba,
+            width,
+            height,
         }
     }
 }
@@ -245,6 +410,18 @@ pub struct TesseraConfig {
     /// - Mobile devices may have limited support for higher sample counts
     /// - Consider using lower values on resource-constrained devices
     pub sample_count: u32,
+    /// Resolution scale applied to the offscreen scene.
+    ///
+    /// The scene is rendered at `render_scale` times the surface resolution
+    /// and upscaled when presented. Lower values trade sharpness for GPU time
+    /// and power, which is useful on low-power devices or in battery saver
+    /// modes.
+    ///
+    /// ## Notes
+    /// - Values are clamped to `0.25..=1.0`
+    /// - `1.0` (the default) renders at native resolution
+    /// - Layout and input are unaffected; only rasterization is scaled
+    pub render_scale: f32,
     /// The title of the application window.
     /// Defaults to "Tessera" if not specified.
     pub window_title: String,
@@ -252,9 +429,33 @@ pub struct TesseraConfig {
     pub window: WindowConfig,
     /// Web host configuration for browser platforms.
     pub web: WebConfig,
+    /// Frame timing HUD options.
+    pub frame_hud: FrameHudConfig,
+    /// Startup splash frame options.
+    pub splash: SplashConfig,
     /// Path to write profiler output when `profiling` is enabled.
     #[cfg(feature = "profiling")]
     pub profiler_output_path: PathBuf,
+    /// Path to also write a Chrome trace of profiler spans to, when
+    /// `profiling` is enabled.
+    #[cfg(feature = "profiling")]
+    pub profiler_trace_output_path: Option<PathBuf>,
+    /// Path to write the draw command trace when `command-trace` is enabled.
+    #[cfg(feature = "command-trace")]
+    pub command_trace_output_path: PathBuf,
+    /// Local port of the devtools WebSocket when `devtools` is enabled.
+    #[cfg(feature = "devtools")]
+    pub devtools_port: u16,
+    /// Traces one frame out of this many with per-component spans when
+    /// `component-spans` is enabled. `1` traces every frame.
+    #[cfg(feature = "component-spans")]
+    pub component_span_sampling: u32,
+    /// Path to record input to when `input-replay` is enabled.
+    #[cfg(feature = "input-replay")]
+    pub input_record_path: Option<PathBuf>,
+    /// Path of an input recording to replay when `input-replay` is enabled.
+    #[cfg(feature = "input-replay")]
+    pub input_replay_path: Option<PathBuf>,
 }
 
 impl Default for TesseraConfig {
@@ -263,11 +464,26 @@ impl Default for TesseraConfig {
     fn default() -> Self {
         Self {
             sample_count: 1,
+            render_scale: 1.0,
             window_title: "Tessera".to_string(),
             window: WindowConfig::default(),
             web: WebConfig::default(),
+            frame_hud: FrameHudConfig::default(),
+            splash: SplashConfig::default(),
             #[cfg(feature = "profiling")]
             profiler_output_path: PathBuf::from("tessera-profiler.jsonl"),
+            #[cfg(feature = "profiling")]
+            profiler_trace_output_path: None,
+            #[cfg(feature = "command-trace")]
+            command_trace_output_path: PathBuf::from("tessera-command-trace.jsonl"),
+            #[cfg(feature = "devtools")]
+            devtools_port: crate::devtools::DEFAULT_PORT,
+            #[cfg(feature = "component-spans")]
+            component_span_sampling: 1,
+            #[cfg(feature = "input-replay")]
+            input_record_path: None,
+            #[cfg(feature = "input-replay")]
+            input_replay_path: None,
         }
     }
 }
@@ -395,6 +611,8 @@ pub struct Renderer<F: Fn()> {
     /// Pending programmatic desktop window action requested through plugin
     /// APIs.
     pending_desktop_window_action: Arc<RwLock<Option<DesktopWindowAction>>>,
+    /// Tasks platform plugins queued from other threads for the UI thread.
+    pending_ui_tasks: UiTaskQueue,
     /// Incrementing frame index for profiling and debugging.
     frame_index: u64,
     /// Global redraw gate. While `true`, redraw requests are coalesced until
@@ -534,13 +752,28 @@ impl<F: Fn()> Renderer<F> {
     ) -> Result<(), EventLoopError> {
         let event_loop = EventLoop::<RendererUserEvent>::with_user_event().build()?;
         let event_loop_proxy = event_loop.create_proxy();
+        #[cfg(feature = "hot-reload")]
+        crate::hot_reload::listen({
+            let proxy = event_loop_proxy.clone();
+            move |path| {
+                let _ = proxy.send_event(RendererUserEvent::HotReload(path));
+            }
+        });
         let app = None;
         let cursor_state = CursorState::default();
         let keyboard_state = KeyboardState::default();
         let ime_state = ImeState::default();
         let ime_bridge_state = RendererImeBridgeState::default();
         #[cfg(feature = "profiling")]
-        crate::profiler::set_output_path(resolve_profiler_output_path(&config));
+        init_profiler_output(&config);
+        #[cfg(feature = "command-trace")]
+        crate::command_trace::set_output_path(resolve_command_trace_output_path(&config));
+        #[cfg(feature = "component-spans")]
+        crate::component_spans::set_sampling(resolve_component_span_sampling(&config));
+        #[cfg(feature = "input-replay")]
+        init_input_replay(&config);
+        #[cfg(feature = "devtools")]
+        start_devtools(&config, event_loop_proxy.clone());
         let mut renderer = Self {
             app,
             entry_point,
@@ -554,6 +787,7 @@ impl<F: Fn()> Renderer<F> {
             accessibility_adapter: None,
             event_loop_proxy: Some(event_loop_proxy),
             pending_desktop_window_action: Arc::new(RwLock::new(None)),
+            pending_ui_tasks: Arc::default(),
             frame_index: 0,
             redraw_request_pending: Arc::new(AtomicBool::new(false)),
             pending_close_requested: false,
@@ -602,7 +836,13 @@ impl<F: Fn()> Renderer<F> {
         let ime_state = ImeState::default();
         let ime_bridge_state = RendererImeBridgeState::default();
         #[cfg(feature = "profiling")]
-        crate::profiler::set_output_path(resolve_profiler_output_path(&config));
+        init_profiler_output(&config);
+        #[cfg(feature = "command-trace")]
+        crate::command_trace::set_output_path(resolve_command_trace_output_path(&config));
+        #[cfg(feature = "component-spans")]
+        crate::component_spans::set_sampling(resolve_component_span_sampling(&config));
+        #[cfg(feature = "input-replay")]
+        init_input_replay(&config);
         let renderer = Self {
             app,
             entry_point,
@@ -616,6 +856,7 @@ impl<F: Fn()> Renderer<F> {
             accessibility_adapter: None,
             event_loop_proxy: Some(event_loop_proxy),
             pending_desktop_window_action: Arc::new(RwLock::new(None)),
+            pending_ui_tasks: Arc::default(),
             frame_index: 0,
             redraw_request_pending: Arc::new(AtomicBool::new(false)),
             pending_close_requested: false,
@@ -738,7 +979,15 @@ impl<F: Fn()> Renderer<F> {
         let ime_state = ImeState::default();
         let ime_bridge_state = RendererImeBridgeState::default();
         #[cfg(feature = "profiling")]
-        crate::profiler::set_output_path(resolve_profiler_output_path(&config));
+        init_profiler_output(&config);
+        #[cfg(feature = "command-trace")]
+        crate::command_trace::set_output_path(resolve_command_trace_output_path(&config));
+        #[cfg(feature = "component-spans")]
+        crate::component_spans::set_sampling(resolve_component_span_sampling(&config));
+        #[cfg(feature = "input-replay")]
+        init_input_replay(&config);
+        #[cfg(feature = "devtools")]
+        start_devtools(&config, event_loop_proxy.clone());
         let mut renderer = Self {
             app,
             entry_point,
@@ -753,6 +1002,7 @@ impl<F: Fn()> Renderer<F> {
             accessibility_adapter: None,
             event_loop_proxy: Some(event_loop_proxy),
             pending_desktop_window_action: Arc::new(RwLock::new(None)),
+            pending_ui_tasks: Arc::default(),
             frame_index: 0,
             redraw_request_pending: Arc::new(AtomicBool::new(false)),
             pending_close_requested: false,
@@ -793,6 +1043,7 @@ struct RenderFrameContext<'a, F: Fn()> {
 struct RenderFrameOutcome {
     accessibility_update: Option<TreeUpdate>,
     request_window_drag: bool,
+    request_window_resize: Option<ResizeDirection>,
     runtime_pending_work: RuntimePendingWork,
     #[cfg(feature = "debug-dirty-overlay")]
     overlay_clear_pending: bool,
@@ -1091,6 +1342,9 @@ Fps: {:.2}
         retry_focus_move: Option<FocusDirection>,
         retry_focus_reveal: bool,
     ) -> RenderComputationOutput {
+        #[cfg(feature = "profiling")]
+        let _profiler_guard =
+            ProfilerScopeGuard::new(ProfilerPhase::Draw, None, None, Some("draw"));
         let draw_timer = Instant::now();
         debug!("Computing draw commands...");
         let cursor_position = args.cursor_state.position();
@@ -1288,7 +1542,11 @@ Fps: {:.2}
         let frame_timer = Instant::now();
         #[cfg(feature = "profiling")]
         profiler_begin_frame(frame_idx);
+        #[cfg(feature = "profiling")]
+        let _frame_guard = ProfilerScopeGuard::new(ProfilerPhase::Frame, None, None, Some("frame"));
         begin_frame_clock(Instant::now());
+        #[cfg(feature = "component-spans")]
+        crate::component_spans::begin_frame(frame_idx);
         // Tick frame-nanos receivers before build so their state writes are
         // consumed by the current recomposition pass.
         tick_frame_nanos_receivers();
@@ -1362,7 +1620,16 @@ Fps: {:.2}
             });
         }
         #[cfg(not(feature = "profiling"))]
-        let _ = (layout_diagnostics, record_cost);
+        let _ = record_cost;
+        #[cfg(feature = "inspector")]
+        let new_graph = TesseraRuntime::with(|runtime| {
+            crate::inspector::append_overlay(
+                new_graph,
+                &runtime.component_tree,
+                screen_size,
+                args.cursor_state.position(),
+            )
+        });
         #[cfg(feature = "debug-dirty-overlay")]
         let dirty_overlay_rects =
             Self::collect_dirty_overlay_rects(screen_size, &build_tree_result);
@@ -1372,19 +1639,27 @@ Fps: {:.2}
             args.app.composite_context_parts(screen_size, frame_idx);
         let new_graph =
             composite::expand_composites(new_graph, composite_context, composite_registry);
-        let RenderGraphExecution {
-            ops,
-            resources,
-            external_resources,
-        } = new_graph.into_execution();
+        let execution = new_graph.into_execution();
+        #[cfg(feature = "command-trace")]
+        crate::command_trace::capture_frame(
+            frame_idx,
+            screen_size,
+            &execution.ops,
+            &execution.resources,
+            &execution.external_resources,
+        );
+        if args.app.frame_hud_visible() {
+            let measure = std::time::Duration::from_nanos(layout_diagnostics.measure_ns);
+            args.app.set_frame_hud_timings(FrameHudTimings {
+                build: build_tree_result.duration(),
+                measure,
+                draw: draw_cost.saturating_sub(measure),
+            });
+        }
         // Perform GPU render every frame.
         let render_cost = Self::perform_render(
             args,
-            RenderGraphExecution {
-                ops,
-                resources,
-                external_resources,
-            },
+            execution,
             #[cfg(feature = "debug-dirty-overlay")]
             &dirty_overlay_rects,
         );
@@ -1396,6 +1671,13 @@ Fps: {:.2}
             render_cost,
             render_breakdown,
         );
+        #[cfg(feature = "devtools")]
+        crate::devtools::end_frame(crate::devtools::FrameTimings {
+            frame_idx,
+            build: build_tree_result.duration(),
+            draw: draw_cost,
+            render: render_cost,
+        });
 
         #[cfg(feature = "profiling")]
         {
@@ -1478,6 +1760,9 @@ Fps: {:.2}
 
         let request_window_drag =
             Self::supports_native_window_frame_controls() && window_requests.request_window_drag;
+        let request_window_resize = window_requests
+            .request_window_resize
+            .filter(|_| Self::supports_native_window_frame_controls() && !decorations);
 
         let ime_bridge_update = args
             .ime_bridge_state
@@ -1485,6 +1770,8 @@ Fps: {:.2}
         if let Some(allowed) = ime_bridge_update.allowed {
             #[cfg(not(target_os = "android"))]
             args.app.window().set_ime_allowed(allowed);
+            #[cfg(target_os = "ios")]
+            crate::ios::set_soft_keyboard_visible(args.app.window(), allowed);
             #[cfg(target_os = "android")]
             {
                 if allowed {
@@ -1522,6 +1809,7 @@ Fps: {:.2}
         RenderFrameOutcome {
             accessibility_update,
             request_window_drag,
+            request_window_resize,
             runtime_pending_work,
             #[cfg(feature = "debug-dirty-overlay")]
             overlay_clear_pending,
@@ -1534,12 +1822,21 @@ impl<F: Fn()> Renderer<F> {
         let app = self.app.as_ref()?;
         let redraw_pending = self.redraw_request_pending.clone();
         let window = app.window_arc();
+        let proxy = self.event_loop_proxy.clone();
         let wake_handler = Arc::new(move || {
-            Self::try_request_redraw(window.as_ref(), redraw_pending.as_ref());
+            // Hidden windows get no redraws, so wake the event loop itself when
+            // possible; `about_to_wait` then applies the request.
+            match &proxy {
+                Some(proxy) => {
+                    let _ = proxy.send_event(RendererUserEvent::RuntimeRedrawWake);
+                }
+                None => Self::try_request_redraw(window.as_ref(), redraw_pending.as_ref()),
+            }
         });
         Some(DesktopPlatformContext::new(
             app.window_arc(),
             self.pending_desktop_window_action.clone(),
+            self.pending_ui_tasks.clone(),
             wake_handler,
         ))
     }
@@ -1548,6 +1845,13 @@ impl<F: Fn()> Renderer<F> {
         self.pending_desktop_window_action.write().take()
     }
 
+    fn run_pending_ui_tasks(&self) {
+        let ui_tasks = std::mem::take(&mut *self.pending_ui_tasks.lock());
+        for task in ui_tasks {
+            task();
+        }
+    }
+
     #[cfg(target_os = "android")]
     fn plugin_context(&self, event_loop: &ActiveEventLoop) -> Option<PluginContext> {
         let desktop = self.desktop_platform_context()?;
@@ -1578,6 +1882,51 @@ impl<F: Fn()> Renderer<F> {
         }
     }
 
+    /// Drops the component tree and everything remembered while building it.
+    fn reset_component_state() {
+        TesseraRuntime::with_mut(|runtime| {
+            runtime.component_tree.reset();
+            runtime.cursor_icon_request = None;
+        });
+        clear_layout_snapshots();
+        reset_layout_dirty_tracking();
+        reset_component_replay_tracking();
+        reset_focus_read_dependencies();
+        reset_render_slot_read_dependencies();
+        reset_state_read_dependencies();
+        reset_component_context_tracking();
+        reset_context_read_dependencies();
+        reset_system_preference_read_dependencies();
+        reset_safe_area_read_dependencies();
+        reset_deep_link_read_dependencies();
+        reset_build_invalidations();
+        clear_persistent_focus_handles();
+        crate::runtime::reset_slots();
+    }
+
+    /// Runs the entry of the library at `path` from the next frame on.
+    ///
+    /// The tree is rebuilt from scratch because remembered values may have
+    /// been created by code that changed; saveable values are carried over.
+    #[cfg(feature = "hot-reload")]
+    fn apply_hot_reload(&mut self, path: &std::path::Path) {
+        if !crate::hot_reload::load(path) {
+            return;
+        }
+        let snapshot = crate::saveable::save_snapshot();
+        Self::reset_component_state();
+        if let Err(err) = crate::saveable::restore_snapshot(&snapshot) {
+            warn!("Failed to restore saved state after hot reload: {err}");
+        }
+        #[cfg(feature = "profiling")]
+        self.request_redraw_with_reasons(
+            WakeSource::Runtime,
+            vec![RedrawReason::RuntimeInvalidation],
+        );
+        #[cfg(not(feature = "profiling"))]
+        self.request_redraw_now();
+    }
+
     fn install_runtime_redraw_waker(&self) {
         let Some(proxy) = self.event_loop_proxy.clone() else {
             clear_redraw_waker();
@@ -1599,9 +1948,11 @@ impl<F: Fn()> Renderer<F> {
         let epoch = self.web_init_epoch;
         let pending_web_inits = self.pending_web_inits.clone();
         let sample_count = self.config.sample_count;
+        let render_scale = self.config.render_scale;
         let transparent = self.config.window.transparent;
         spawn_local(async move {
-            let render_core = RenderCore::new(window, sample_count, transparent).await;
+            let render_core =
+                RenderCore::new(window, sample_count, render_scale, transparent).await;
             pending_web_inits.borrow_mut().push((epoch, render_core));
             let _ = proxy.send_event(RendererUserEvent::WebInitReady(epoch));
         });
@@ -1623,6 +1974,8 @@ impl<F: Fn()> Renderer<F> {
         if epoch != self.web_init_epoch {
             return false;
         }
+        render_core.set_frame_hud_visible(self.config.frame_hud.visible);
+        render_core.render_splash(&self.config.splash);
 
         let mut context = PipelineContext::new(&mut render_core);
         for module in &self.modules {
@@ -1692,6 +2045,15 @@ impl<F: Fn()> Renderer<F> {
         self.update_native_window_shape(window);
     }
 
+    fn apply_window_resize(&mut self, window: &Window, direction: ResizeDirection) {
+        if let Err(err) = window.drag_resize_window(direction) {
+            warn!("Failed to start window resize: {}", err);
+            return;
+        }
+        self.resize_in_progress = true;
+        self.cursor_state.clear();
+    }
+
     fn apply_desktop_window_action(&mut self, window: &Window, action: DesktopWindowAction) {
         match action {
             DesktopWindowAction::Minimize => {
@@ -1706,6 +2068,18 @@ impl<F: Fn()> Renderer<F> {
             DesktopWindowAction::Close => {
                 self.pending_close_requested = true;
             }
+            DesktopWindowAction::Hide => {
+                window.set_visible(false);
+            }
+            DesktopWindowAction::Show => {
+                window.set_visible(true);
+                window.set_minimized(false);
+                window.focus_window();
+            }
+            DesktopWindowAction::SetDecorations(decorations) => {
+                window.set_decorations(decorations);
+                self.config.window.decorations = decorations;
+            }
         }
         self.update_native_window_shape(window);
     }
@@ -1734,6 +2108,7 @@ impl<F: Fn()> Renderer<F> {
             app.resize(size);
         }
         self.update_native_window_shape(&window);
+        refresh_safe_area_insets(&window);
     }
 
     fn handle_cursor_moved(&mut self, position: winit::dpi::PhysicalPosition<f64>) {
@@ -1747,7 +2122,6 @@ impl<F: Fn()> Renderer<F> {
             timestamp: Instant::now(),
             pointer_id: MOUSE_POINTER_ID,
             content: CursorEventContent::Moved(px_position),
-            gesture_state: GestureState::TapCandidate,
             consumed: false,
         });
         debug!("Cursor moved to: {}, {}", position.x, position.y);
@@ -1779,10 +2153,15 @@ impl<F: Fn()> Renderer<F> {
             let root_node_id = tree.get_node_id_at(
                 std::num::NonZero::new(1).expect("root node index must be non-zero"),
             )?;
+            let focused_node_id = runtime
+                .component_tree
+                .focus_owner()
+                .active_component_node_id();
             crate::accessibility::build_tree_update(
                 tree,
                 metadatas,
                 root_node_id,
+                focused_node_id,
                 Some(window_label),
             )
         })
@@ -1806,6 +2185,12 @@ impl<F: Fn()> Renderer<F> {
             }
             return;
         }
+        #[cfg(feature = "inspector")]
+        {
+            if crate::inspector::handle_mouse_input(state, button, self.cursor_state.position()) {
+                return;
+            }
+        }
 
         if matches!(
             event_content,
@@ -1835,27 +2220,43 @@ impl<F: Fn()> Renderer<F> {
             timestamp: Instant::now(),
             pointer_id: MOUSE_POINTER_ID,
             content: event_content,
-            gesture_state: GestureState::TapCandidate,
             consumed: false,
         };
         self.cursor_state.push_event(event);
         debug!("Mouse input: {state:?} button {button:?}");
     }
 
-    fn handle_mouse_wheel(&mut self, delta: winit::event::MouseScrollDelta) {
+    fn handle_mouse_wheel(
+        &mut self,
+        delta: winit::event::MouseScrollDelta,
+        phase: winit::event::TouchPhase,
+    ) {
         if self.resize_in_progress {
             return;
         }
-        let event_content = CursorEventContent::from_scroll_event(delta);
+        let phase = self.cursor_state.resolve_wheel_phase(delta, phase);
+        let event_content = CursorEventContent::from_scroll_event_with_phase(delta, phase);
         let event = PointerChange {
             timestamp: Instant::now(),
             pointer_id: MOUSE_POINTER_ID,
             content: event_content,
-            gesture_state: GestureState::Dragged,
             consumed: false,
         };
         self.cursor_state.push_event(event);
-        debug!("Mouse scroll: {delta:?}");
+        debug!("Mouse scroll: {delta:?} ({phase:?})");
+    }
+
+    fn handle_touchpad_gesture(&mut self, content: CursorEventContent) {
+        if self.resize_in_progress {
+            return;
+        }
+        debug!("Touchpad gesture: {content:?}");
+        self.cursor_state.push_event(PointerChange {
+            timestamp: Instant::now(),
+            pointer_id: MOUSE_POINTER_ID,
+            content,
+            consumed: false,
+        });
     }
 
     fn handle_touch(&mut self, touch_event: winit::event::Touch) {
@@ -1887,16 +2288,94 @@ impl<F: Fn()> Renderer<F> {
         }
     }
 
-    fn handle_keyboard_input(&mut self, event: winit::event::KeyEvent) {
+    fn handle_keyboard_input(
+        &mut self,
+        #[cfg(target_os = "android")] event_loop: &ActiveEventLoop,
+        event: crate::KeyEvent,
+    ) {
         debug!("Keyboard input: {event:?}");
+        #[cfg(feature = "inspector")]
+        {
+            if crate::inspector::handle_key_event(&event) {
+                return;
+            }
+        }
+        if self.config.frame_hud.hotkey
+            && is_frame_hud_hotkey(&event, self.keyboard_state.modifiers())
+        {
+            if !event.repeat
+                && event.state == winit::event::ElementState::Pressed
+                && let Some(app) = self.app.as_mut()
+            {
+                app.set_frame_hud_visible(!app.frame_hud_visible());
+            }
+            return;
+        }
+        match back_key(
+            &event,
+            self.keyboard_state.modifiers(),
+            self.config.window.keyboard_back_navigation,
+        ) {
+            // The platform back action never reaches components.
+            Some(BackKey::System) => {
+                let handled = !is_back_press(&event) || dispatch_back();
+                #[cfg(target_os = "android")]
+                if !handled {
+                    crate::back_navigation::finish_activity(event_loop.android_app());
+                }
+                #[cfg(not(target_os = "android"))]
+                let _ = handled;
+                return;
+            }
+            // Unhandled keyboard shortcuts fall through to components.
+            Some(BackKey::Keyboard) if is_back_press(&event) && dispatch_back() => return,
+            _ => {}
+        }
         self.keyboard_state.push_event(event);
     }
 
+    #[cfg(feature = "input-replay")]
+    fn replay_input(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        input: crate::input_replay::RecordedInput,
+    ) {
+        use crate::input_replay::ReplayedInput;
+
+        let Some(app) = self.app.as_ref() else {
+            return;
+        };
+        let window_id = app.window().id();
+        match input.into_replayed() {
+            // Resize the real window so the layout matches the recording; its
+            // own resize event follows.
+            ReplayedInput::Window(WindowEvent::Resized(size)) => {
+                let _ = app.window().request_inner_size(size);
+            }
+            ReplayedInput::Window(event) => self.window_event(event_loop, window_id, event),
+            ReplayedInput::Key(event) => {
+                self.handle_keyboard_input(
+                    #[cfg(target_os = "android")]
+                    event_loop,
+                    event,
+                );
+                #[cfg(feature = "profiling")]
+                self.request_redraw_with_reasons(
+                    WakeSource::WindowEvent,
+                    vec![RedrawReason::KeyboardInput],
+                );
+                #[cfg(not(feature = "profiling"))]
+                self.request_redraw_now();
+            }
+        }
+    }
+
     fn handle_redraw_requested(
         &mut self,
         #[cfg(target_os = "android")] event_loop: &ActiveEventLoop,
     ) {
         self.redraw_request_pending.store(false, Ordering::Release);
+        self.run_pending_ui_tasks();
         let mut app = match self.app.take() {
             Some(app) => app,
             None => return,
@@ -1912,6 +2391,7 @@ impl<F: Fn()> Renderer<F> {
         let RenderFrameOutcome {
             accessibility_update,
             request_window_drag,
+            request_window_resize,
             runtime_pending_work,
             #[cfg(feature = "debug-dirty-overlay")]
             overlay_clear_pending,
@@ -1942,6 +2422,9 @@ impl<F: Fn()> Renderer<F> {
         if request_window_drag {
             self.apply_window_drag(app.window());
         }
+        if let Some(direction) = request_window_resize {
+            self.apply_window_resize(app.window(), direction);
+        }
         if let Some(action) = self.take_pending_desktop_window_action() {
             self.apply_desktop_window_action(app.window(), action);
         }
@@ -2005,6 +2488,11 @@ impl<F: Fn()> ApplicationHandler<RendererUserEvent> for Renderer<F> {
     #[tracing::instrument(level = "debug", skip(self, event_loop))]
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
         event_loop.set_control_flow(ControlFlow::Wait);
+        #[cfg(target_os = "android")]
+        crate::system_preferences::attach_android_app(event_loop.android_app());
+        refresh_system_preferences();
+        #[cfg(all(feature = "saveable", target_os = "android"))]
+        crate::saveable::clear_app_storage(event_loop.android_app());
         #[cfg(feature = "profiling")]
         submit_runtime_meta(RuntimeMeta {
             kind: RuntimeEventKind::Resumed,
@@ -2062,6 +2550,7 @@ impl<F: Fn()> ApplicationHandler<RendererUserEvent> for Renderer<F> {
         // Now show the window after AccessKit is initialized
         window.set_visible(true);
         self.update_native_window_shape(&window);
+        refresh_safe_area_insets(&window);
 
         #[cfg(target_family = "wasm")]
         {
@@ -2074,8 +2563,11 @@ impl<F: Fn()> ApplicationHandler<RendererUserEvent> for Renderer<F> {
             let mut render_core = pollster::block_on(RenderCore::new(
                 window.clone(),
                 self.config.sample_count,
+                self.config.render_scale,
                 self.config.window.transparent,
             ));
+            render_core.set_frame_hud_visible(self.config.frame_hud.visible);
+            render_core.render_splash(&self.config.splash);
 
             // Register pipelines
             let mut context = PipelineContext::new(&mut render_core);
@@ -2085,6 +2577,14 @@ impl<F: Fn()> ApplicationHandler<RendererUserEvent> for Renderer<F> {
 
             self.app = Some(render_core);
             self.install_runtime_redraw_waker();
+            #[cfg(feature = "input-replay")]
+            if let Some(proxy) = self.event_loop_proxy.clone() {
+                crate::input_replay::start_replay(move |input| {
+                    proxy
+                        .send_event(RendererUserEvent::ReplayInput(input))
+                        .is_ok()
+                });
+            }
             #[cfg(feature = "profiling")]
             self.request_redraw_with_reasons(WakeSource::Lifecycle, vec![RedrawReason::Startup]);
             #[cfg(not(feature = "profiling"))]
@@ -2118,6 +2618,10 @@ impl<F: Fn()> ApplicationHandler<RendererUserEvent> for Renderer<F> {
             self.plugins.suspended(&context);
         }
 
+        // Android may kill the process while it is in the background.
+        #[cfg(all(feature = "saveable", target_os = "android"))]
+        crate::saveable::persist_to_app_storage(event_loop.android_app());
+
         if let Some(mut app) = self.app.take() {
             app.compute_resource_manager_mut().clear();
         }
@@ -2143,24 +2647,12 @@ impl<F: Fn()> ApplicationHandler<RendererUserEvent> for Renderer<F> {
         }
 
         TesseraRuntime::with_mut(|runtime| {
-            runtime.component_tree.reset();
-            runtime.cursor_icon_request = None;
             runtime.window_minimized = false;
             runtime.window_size = [0, 0];
         });
-        clear_layout_snapshots();
-        reset_layout_dirty_tracking();
-        reset_component_replay_tracking();
-        reset_focus_read_dependencies();
-        reset_render_slot_read_dependencies();
-        reset_state_read_dependencies();
-        reset_component_context_tracking();
-        reset_context_read_dependencies();
-        reset_build_invalidations();
+        Self::reset_component_state();
         reset_frame_clock();
         clear_redraw_waker();
-        clear_persistent_focus_handles();
-        crate::runtime::reset_slots();
         #[cfg(feature = "profiling")]
         self.pending_redraw_reasons.clear();
     }
@@ -2177,6 +2669,8 @@ impl<F: Fn()> ApplicationHandler<RendererUserEvent> for Renderer<F> {
             self.handle_close_requested(event_loop);
             return;
         }
+        #[cfg(feature = "input-replay")]
+        crate::input_replay::record(&event);
 
         // Forward event to AccessKit adapter
         if let (Some(adapter), Some(app)) = (&mut self.accessibility_adapter, &self.app) {
@@ -2225,19 +2719,62 @@ impl<F: Fn()> ApplicationHandler<RendererUserEvent> for Renderer<F> {
             WindowEvent::MouseWheel {
                 device_id: _,
                 delta,
-                phase: _,
+                phase,
             } => {
-                self.handle_mouse_wheel(delta);
+                self.handle_mouse_wheel(delta, phase);
                 request_redraw = true;
                 #[cfg(feature = "profiling")]
                 redraw_reasons.push(RedrawReason::MouseWheel);
             }
+            WindowEvent::PinchGesture { delta, phase, .. } => {
+                self.handle_touchpad_gesture(CursorEventContent::Pinch(PinchEventContent {
+                    delta: delta as f32,
+                    phase: ScrollPhase::from_touch_phase(phase),
+                }));
+                request_redraw = true;
+                #[cfg(feature = "profiling")]
+                redraw_reasons.push(RedrawReason::TouchpadGesture);
+            }
+            WindowEvent::RotationGesture { delta, phase, .. } => {
+                // Winit reports counterclockwise degrees.
+                self.handle_touchpad_gesture(CursorEventContent::Rotate(RotateEventContent {
+                    delta: -delta.to_radians(),
+                    phase: ScrollPhase::from_touch_phase(phase),
+                }));
+                request_redraw = true;
+                #[cfg(feature = "profiling")]
+                redraw_reasons.push(RedrawReason::TouchpadGesture);
+            }
+            WindowEvent::DoubleTapGesture { .. } => {
+                self.handle_touchpad_gesture(CursorEventContent::SmartMagnify);
+                request_redraw = true;
+                #[cfg(feature = "profiling")]
+                redraw_reasons.push(RedrawReason::TouchpadGesture);
+            }
             WindowEvent::Touch(touch_event) => {
                 self.handle_touch(touch_event);
                 request_redraw = true;
                 #[cfg(feature = "profiling")]
                 redraw_reasons.push(RedrawReason::TouchInput);
             }
+            WindowEvent::HoveredFile(path) => {
+                crate::dnd::handle_system_file_hovered(path);
+                request_redraw = true;
+                #[cfg(feature = "profiling")]
+                redraw_reasons.push(RedrawReason::FileDragAndDrop);
+            }
+            WindowEvent::DroppedFile(path) => {
+                crate::dnd::handle_system_file_dropped(path);
+                request_redraw = true;
+                #[cfg(feature = "profiling")]
+                redraw_reasons.push(RedrawReason::FileDragAndDrop);
+            }
+            WindowEvent::HoveredFileCancelled => {
+                crate::dnd::handle_system_file_hover_cancelled();
+                request_redraw = true;
+                #[cfg(feature = "profiling")]
+                redraw_reasons.push(RedrawReason::FileDragAndDrop);
+            }
             WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
                 if let Some(scale_factor_lock) = SCALE_FACTOR.get() {
                     *scale_factor_lock.write() = scale_factor;
@@ -2246,13 +2783,18 @@ impl<F: Fn()> ApplicationHandler<RendererUserEvent> for Renderer<F> {
                 }
                 if let Some(app) = self.app.as_ref() {
                     self.update_native_window_shape(app.window());
+                    refresh_safe_area_insets(app.window());
                 }
                 request_redraw = true;
                 #[cfg(feature = "profiling")]
                 redraw_reasons.push(RedrawReason::ScaleFactorChanged);
             }
             WindowEvent::KeyboardInput { event, .. } => {
-                self.handle_keyboard_input(event);
+                self.handle_keyboard_input(
+                    #[cfg(target_os = "android")]
+                    event_loop,
+                    event.into(),
+                );
                 request_redraw = true;
                 #[cfg(feature = "profiling")]
                 redraw_reasons.push(RedrawReason::KeyboardInput);
@@ -2279,6 +2821,10 @@ impl<F: Fn()> ApplicationHandler<RendererUserEvent> for Renderer<F> {
                         .set_owner_focused(focused);
                 });
                 flush_pending_focus_callbacks();
+                // Settings may have changed while another app had focus.
+                if focused {
+                    refresh_system_preferences();
+                }
                 if self.resize_in_progress {
                     self.resize_in_progress = false;
                     self.cursor_state.clear();
@@ -2287,6 +2833,9 @@ impl<F: Fn()> ApplicationHandler<RendererUserEvent> for Renderer<F> {
                 #[cfg(feature = "profiling")]
                 redraw_reasons.push(RedrawReason::FocusChanged);
             }
+            WindowEvent::ThemeChanged(_) => {
+                refresh_system_preferences();
+            }
             WindowEvent::RedrawRequested => {
                 #[cfg(target_os = "android")]
                 self.handle_redraw_requested(event_loop);
@@ -2323,6 +2872,14 @@ impl<F: Fn()> ApplicationHandler<RendererUserEvent> for Renderer<F> {
             RendererUserEvent::WebInitReady(epoch) => {
                 self.finish_web_initialization(event_loop, epoch);
             }
+            #[cfg(feature = "hot-reload")]
+            RendererUserEvent::HotReload(path) => {
+                self.apply_hot_reload(&path);
+            }
+            #[cfg(feature = "input-replay")]
+            RendererUserEvent::ReplayInput(input) => {
+                self.replay_input(event_loop, input);
+            }
             RendererUserEvent::AccessKit(event) => {
                 if self.accessibility_adapter.is_none() {
                     return;
@@ -2345,7 +2902,16 @@ impl<F: Fn()> ApplicationHandler<RendererUserEvent> for Renderer<F> {
                         });
                         flush_pending_focus_callbacks();
 
-                        if !handled {
+                        if handled {
+                            // Focus, reveal and edits take effect on the next frame
+                            #[cfg(feature = "profiling")]
+                            self.request_redraw_with_reasons(
+                                WakeSource::Runtime,
+                                vec![RedrawReason::RuntimeInvalidation],
+                            );
+                            #[cfg(not(feature = "profiling"))]
+                            self.request_redraw_now();
+                        } else {
                             debug!("Action was not handled by any component");
                         }
                     }
@@ -2356,6 +2922,19 @@ impl<F: Fn()> ApplicationHandler<RendererUserEvent> for Renderer<F> {
             }
         }
     }
+
+    fn about_to_wait(&mut self, _event_loop: &ActiveEventLoop) {
+        self.run_pending_ui_tasks();
+        // Hidden windows do not redraw, so window actions such as showing
+        // the window again cannot wait for the next frame.
+        let Some(app) = self.app.take() else {
+            return;
+        };
+        if let Some(action) = self.take_pending_desktop_window_action() {
+            self.apply_desktop_window_action(app.window(), action);
+        }
+        self.app = Some(app);
+    }
 }
 
 #[cfg(test)]
@@ -14,6 +14,12 @@ pub trait ComputeCommand: DynClone + Downcast + Send + Sync {
     /// Declares the dependency on previously rendered content for barrier
     /// planning.
     fn barrier(&self) -> SampleRegion;
+
+    /// Returns the type name of the command, used by debug tooling such as the
+    /// command trace.
+    fn command_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
 }
 
 impl_downcast!(ComputeCommand);
@@ -9,7 +9,8 @@ use std::{io, sync::Arc, time::Duration};
 use winit::window::Window;
 
 use crate::{
-    CompositeCommand, ComputablePipeline, ComputeCommand, DrawCommand, DrawablePipeline, PxSize,
+    CompositeCommand, ComputablePipeline, ComputeCommand, DrawCommand, DrawablePipeline, Px,
+    PxSize,
     compute::resource::ComputeResourceManager,
     pipeline_cache::save_cache,
     render_graph::RenderTextureDesc,
@@ -21,8 +22,17 @@ use crate::{
 
 use super::{compute::ComputePipelineRegistry, drawer::Drawer};
 
+mod clip;
 mod frame;
+#[cfg(feature = "profiling")]
+mod gpu_timer;
+#[cfg(feature = "testing")]
+mod headless;
+mod hud;
 mod init;
+mod splash;
+
+pub(crate) use hud::FrameHudTimings;
 
 struct RenderPipelines {
     drawer: Drawer,
@@ -36,6 +46,36 @@ struct FrameTargets {
     msaa_texture: Option<wgpu::Texture>,
     msaa_view: Option<wgpu::TextureView>,
     sample_count: u32,
+    render_scale: f32,
+    scene_extent: wgpu::Extent3d,
+}
+
+impl FrameTargets {
+    fn scene_size(&self) -> PxSize {
+        PxSize::new(
+            Px(self.scene_extent.width as i32),
+            Px(self.scene_extent.height as i32),
+        )
+    }
+}
+
+const MIN_RENDER_SCALE: f32 = 0.25;
+
+fn clamp_render_scale(render_scale: f32) -> f32 {
+    if render_scale.is_finite() {
+        render_scale.clamp(MIN_RENDER_SCALE, 1.0)
+    } else {
+        1.0
+    }
+}
+
+fn scaled_extent(width: u32, height: u32, render_scale: f32) -> wgpu::Extent3d {
+    let scale = |value: u32| ((value as f32 * render_scale).round() as u32).max(1);
+    wgpu::Extent3d {
+        width: scale(width),
+        height: scale(height),
+        depth_or_array_layers: 1,
+    }
 }
 
 /// Timing breakdown for the most recent render call.
@@ -66,6 +106,7 @@ struct BlitState {
     pipeline_rgba: wgpu::RenderPipeline,
     bind_group_layout: wgpu::BindGroupLayout,
     sampler: wgpu::Sampler,
+    present_sampler: wgpu::Sampler,
     #[cfg(feature = "debug-dirty-overlay")]
     dirty_overlay_pipeline: wgpu::RenderPipeline,
 }
@@ -251,17 +292,36 @@ fn create_msaa_view(
     texture.create_view(&wgpu::TextureViewDescriptor::default())
 }
 
+/// Destination of rendered frames.
+enum RenderOutput {
+    /// Frames are presented to the surface of a window.
+    Window {
+        window: Arc<Window>,
+        /// WGPU instance used to recreate surfaces when the swapchain is lost.
+        instance: wgpu::Instance,
+        surface: wgpu::Surface<'static>,
+    },
+    /// Frames are rendered into a texture that tests read back.
+    #[cfg(feature = "testing")]
+    Headless { texture: wgpu::Texture },
+}
+
+impl RenderOutput {
+    fn scale_factor(&self) -> f64 {
+        match self {
+            Self::Window { window, .. } => window.scale_factor(),
+            #[cfg(feature = "testing")]
+            Self::Headless { .. } => 1.0,
+        }
+    }
+}
+
 /// Render core holding device, surface, pipelines, and frame resources.
 pub struct RenderCore {
-    /// Avoiding release the window
-    #[allow(unused)]
-    window: Arc<Window>,
-    /// WGPU instance used to recreate surfaces when the swapchain is lost.
-    instance: wgpu::Instance,
+    /// Where frames are presented
+    output: RenderOutput,
     /// WGPU device
     device: wgpu::Device,
-    /// WGPU surface
-    surface: wgpu::Surface<'static>,
     /// WGPU queue
     queue: wgpu::Queue,
     /// WGPU surface configuration
@@ -284,6 +344,8 @@ pub struct RenderCore {
     compute: ComputeState,
     /// Blit resources for partial copies.
     blit: BlitState,
+    /// Stencil resources for shaped clips.
+    clip: clip::ClipStencilState,
     /// Pool of local textures declared by render graph resources.
     local_textures: LocalTexturePool,
     /// Registry of external textures owned by pipelines.
@@ -292,6 +354,11 @@ pub struct RenderCore {
     frame_index: u64,
     /// Timing breakdown for the last render call.
     last_render_breakdown: Option<RenderTimingBreakdown>,
+    /// GPU frame timer, when the device supports timestamp queries.
+    #[cfg(feature = "profiling")]
+    gpu_timer: Option<gpu_timer::GpuTimer>,
+    /// Frame timing HUD drawn over the presented frame.
+    hud: hud::FrameHud,
 }
 
 /// Shared GPU resources used when creating pipelines.
@@ -320,14 +387,37 @@ impl RenderCore {
         }
     }
 
+    /// Returns the registry of persistent and imported textures.
+    pub fn external_textures(&self) -> &ExternalTextureRegistry {
+        &self.external_textures
+    }
+
     /// Returns the current window handle.
+    ///
+    /// # Panics
+    ///
+    /// Panics for render cores created by test harnesses, which have no
+    /// window.
     pub fn window(&self) -> &Window {
-        &self.window
+        match &self.output {
+            RenderOutput::Window { window, .. } => window,
+            #[cfg(feature = "testing")]
+            RenderOutput::Headless { .. } => panic!("headless render core has no window"),
+        }
     }
 
     /// Returns a cloned window handle for external storage.
+    ///
+    /// # Panics
+    ///
+    /// Panics for render cores created by test harnesses, which have no
+    /// window.
     pub fn window_arc(&self) -> Arc<Window> {
-        self.window.clone()
+        match &self.output {
+            RenderOutput::Window { window, .. } => window.clone(),
+            #[cfg(feature = "testing")]
+            RenderOutput::Headless { .. } => panic!("headless render core has no window"),
+        }
     }
 
     /// Returns the WGPU device.
@@ -355,6 +445,24 @@ impl RenderCore {
         self.targets.sample_count
     }
 
+    /// Returns the resolution scale applied to the offscreen scene.
+    pub fn render_scale(&self) -> f32 {
+        self.targets.render_scale
+    }
+
+    /// Updates the resolution scale applied to the offscreen scene.
+    ///
+    /// The value is clamped to `0.25..=1.0`. Scene targets are recreated
+    /// immediately when the effective scale changes.
+    pub fn set_render_scale(&mut self, render_scale: f32) {
+        let render_scale = clamp_render_scale(render_scale);
+        if (render_scale - self.targets.render_scale).abs() <= f32::EPSILON {
+            return;
+        }
+        self.targets.render_scale = render_scale;
+        self.rebuild_pass_targets();
+    }
+
     /// Returns the frame-local compute resource manager.
     pub fn compute_resource_manager_mut(&mut self) -> &mut ComputeResourceManager {
         &mut self.compute.resource_manager
@@ -370,6 +478,21 @@ impl RenderCore {
         self.last_render_breakdown
     }
 
+    /// Returns whether the frame timing HUD is shown.
+    pub(crate) fn frame_hud_visible(&self) -> bool {
+        self.hud.is_visible()
+    }
+
+    /// Shows or hides the frame timing HUD.
+    pub(crate) fn set_frame_hud_visible(&mut self, visible: bool) {
+        self.hud.set_visible(visible);
+    }
+
+    /// Sets the CPU timings the frame timing HUD shows for the next frame.
+    pub(crate) fn set_frame_hud_timings(&mut self, timings: FrameHudTimings) {
+        self.hud.set_timings(timings);
+    }
+
     /// Registers a new drawable pipeline for a specific command type.
     ///
     /// This method takes ownership of the pipeline and wraps it in a
@@ -444,3 +567,27 @@ impl RenderCore {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scaled_extent_rounds_and_keeps_minimum_size() {
+        let extent = scaled_extent(1001, 3, 0.5);
+        assert_eq!(extent.width, 501);
+        assert_eq!(extent.height, 2);
+
+        let extent = scaled_extent(1, 1, MIN_RENDER_SCALE);
+        assert_eq!(extent.width, 1);
+        assert_eq!(extent.height, 1);
+    }
+
+    #[test]
+    fn clamp_render_scale_rejects_out_of_range_values() {
+        assert_eq!(clamp_render_scale(0.0), MIN_RENDER_SCALE);
+        assert_eq!(clamp_render_scale(4.0), 1.0);
+        assert_eq!(clamp_render_scale(f32::NAN), 1.0);
+        assert_eq!(clamp_render_scale(0.5), 0.5);
+    }
+}
@@ -0,0 +1,234 @@
+//! Stencil-based clipping for non-rectangular clip shapes.
+//!
+//! Rectangular clips are applied as scissor rects. Rounded, rotated and
+//! elliptical clips are rasterized into the clip stencil buffer instead:
+//! pushing a shaped clip increments the stencil value inside the shape, popping
+//! it decrements the same area again. Draw pipelines compare against the number
+//! of active shaped clips, so nested shapes intersect naturally.
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::{
+    ClipShape, PxSize,
+    renderer::drawer::{CLIP_STENCIL_FORMAT, InstanceBuffer, TransientTexturePool},
+};
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct ClipInstance {
+    rect: [f32; 4],
+    corner_radii: [f32; 4],
+    params: [f32; 4],
+}
+
+impl ClipInstance {
+    fn new(shape: &ClipShape, target_size: PxSize) -> Self {
+        let (rect, corner_radii, kind, rotation) = match *shape {
+            ClipShape::Rect(rect) => (rect, [0.0; 4], 0.0, 0.0),
+            ClipShape::RoundedRect { rect, corner_radii } => (rect, corner_radii, 0.0, 0.0),
+            ClipShape::Ellipse(rect) => (rect, [0.0; 4], 1.0, 0.0),
+            ClipShape::Rotated {
+                rect,
+                corner_radii,
+                rotation,
+            } => (rect, corner_radii, 0.0, rotation.to_radians()),
+        };
+        Self {
+            rect: [
+                rect.x.to_f32(),
+                rect.y.to_f32(),
+                rect.width.to_f32(),
+                rect.height.to_f32(),
+            ],
+            corner_radii,
+            params: [
+                target_size.width.to_f32(),
+                target_size.height.to_f32(),
+                kind,
+                rotation,
+            ],
+        }
+    }
+}
+
+/// Stencil operation applied by a clip write.
+///
+/// `depth` is the number of shaped clips active before a push, or including
+/// the popped shape for a pop.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(super) enum ClipStencilOp {
+    /// Enters a shaped clip.
+    Push { depth: u32 },
+    /// Leaves a shaped clip.
+    Pop { depth: u32 },
+}
+
+pub(super) struct ClipStencilState {
+    push_pipeline: wgpu::RenderPipeline,
+    pop_pipeline: wgpu::RenderPipeline,
+    instances: InstanceBuffer<ClipInstance>,
+    targets: TransientTexturePool,
+    // Every pass clears the stencil on load, so one texture per target size
+    // is shared by all passes of a frame.
+    frame_views: Vec<(StencilKey, wgpu::TextureView)>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct StencilKey {
+    width: u32,
+    height: u32,
+    sample_count: u32,
+}
+
+impl ClipStencilState {
+    pub(super) fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        sample_count: u32,
+        pipeline_cache: Option<&wgpu::PipelineCache>,
+    ) -> Self {
+        let shader =
+            device.create_shader_module(wgpu::include_wgsl!("../shaders/clip_stencil.wgsl"));
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Clip Stencil Pipeline Layout"),
+            bind_group_layouts: &[],
+            immediate_size: 0,
+        });
+        let create_pipeline = |label: &str, pass_op: wgpu::StencilOperation| {
+            let face = wgpu::StencilFaceState {
+                compare: wgpu::CompareFunction::Equal,
+                fail_op: wgpu::StencilOperation::Keep,
+                depth_fail_op: wgpu::StencilOperation::Keep,
+                pass_op,
+            };
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(label),
+                layout: Some(&layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &[wgpu::VertexBufferLayout {
+                        array_stride: InstanceBuffer::<ClipInstance>::stride(),
+                        step_mode: wgpu::VertexStepMode::Instance,
+                        attributes: &wgpu::vertex_attr_array![
+                            0 => Float32x4,
+                            1 => Float32x4,
+                            2 => Float32x4
+                        ],
+                    }],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: Some("fs_main"),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format,
+                        blend: None,
+                        write_mask: wgpu::ColorWrites::empty(),
+                    })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: Some(wgpu::DepthStencilState {
+                    format: CLIP_STENCIL_FORMAT,
+                    depth_write_enabled: None,
+                    depth_compare: None,
+                    stencil: wgpu::StencilState {
+                        front: face,
+                        back: face,
+                        read_mask: 0xff,
+                        write_mask: 0xff,
+                    },
+                    bias: wgpu::DepthBiasState::default(),
+                }),
+                multisample: wgpu::MultisampleState {
+                    count: sample_count,
+                    mask: !0,
+                    alpha_to_coverage_enabled: false,
+                },
+                multiview_mask: None,
+                cache: pipeline_cache,
+            })
+        };
+
+        Self {
+            push_pipeline: create_pipeline(
+                "Clip Stencil Push Pipeline",
+                wgpu::StencilOperation::IncrementClamp,
+            ),
+            pop_pipeline: create_pipeline(
+                "Clip Stencil Pop Pipeline",
+                wgpu::StencilOperation::DecrementClamp,
+            ),
+            instances: InstanceBuffer::new("Clip Stencil Instances"),
+            targets: TransientTexturePool::new("Clip Stencil Texture"),
+            frame_views: Vec::new(),
+        }
+    }
+
+    pub(super) fn begin_frame(&mut self) {
+        self.instances.reset();
+        self.targets.begin_frame();
+        self.frame_views.clear();
+    }
+
+    /// Returns the frame's stencil view matching `color_target`.
+    pub(super) fn stencil_view(
+        &mut self,
+        device: &wgpu::Device,
+        color_target: &wgpu::TextureView,
+    ) -> wgpu::TextureView {
+        let texture = color_target.texture();
+        let size = texture.size();
+        let key = StencilKey {
+            width: size.width,
+            height: size.height,
+            sample_count: texture.sample_count(),
+        };
+        if let Some((_, view)) = self.frame_views.iter().find(|(entry, _)| *entry == key) {
+            return view.clone();
+        }
+        let view = self
+            .targets
+            .acquire(
+                device,
+                (key.width, key.height),
+                CLIP_STENCIL_FORMAT,
+                wgpu::TextureUsages::RENDER_ATTACHMENT,
+                key.sample_count,
+            )
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        self.frame_views.push((key, view.clone()));
+        view
+    }
+
+    /// Rasterizes `shape` into the stencil buffer.
+    ///
+    /// The caller restores the stencil reference and scissor rect for
+    /// subsequent draws.
+    pub(super) fn write(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        rpass: &mut wgpu::RenderPass<'_>,
+        shape: &ClipShape,
+        target_size: PxSize,
+        op: ClipStencilOp,
+    ) {
+        let instance = ClipInstance::new(shape, target_size);
+        let Some(region) = self.instances.upload(device, queue, &[instance]) else {
+            return;
+        };
+        let Some(slice) = self.instances.slice(&region) else {
+            return;
+        };
+        let (pipeline, depth) = match op {
+            ClipStencilOp::Push { depth } => (&self.push_pipeline, depth),
+            ClipStencilOp::Pop { depth } => (&self.pop_pipeline, depth),
+        };
+        rpass.set_pipeline(pipeline);
+        rpass.set_stencil_reference(depth);
+        rpass.set_vertex_buffer(0, slice);
+        rpass.draw(0..6, region.instances());
+    }
+}
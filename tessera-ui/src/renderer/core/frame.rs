@@ -5,7 +5,7 @@ use smallvec::SmallVec;
 use tracing::{debug, error, warn};
 
 use crate::{
-    DrawCommand, Px, PxPosition, PxRect, PxSize,
+    ClipShape, Command, DrawCommand, Px, PxPosition, PxRect, PxSize,
     render_graph::{
         ExternalTextureDesc, RenderGraphExecution, RenderResource, RenderResourceId,
         RenderTextureDesc,
@@ -21,7 +21,10 @@ use crate::{
     time::Instant,
 };
 
-use super::*;
+use super::{
+    clip::{ClipStencilOp, ClipStencilState},
+    *,
+};
 
 fn compute_last_use_passes(passes: &[RenderPassPlan], local_count: usize) -> Vec<usize> {
     let mut last_use = vec![0usize; local_count];
@@ -43,6 +46,31 @@ fn compute_last_use_passes(passes: &[RenderPassPlan], local_count: usize) -> Vec
     last_use
 }
 
+/// Texture a frame is drawn into.
+pub(super) enum OutputFrame {
+    Surface(wgpu::SurfaceTexture),
+    #[cfg(feature = "testing")]
+    Headless(wgpu::Texture),
+}
+
+impl OutputFrame {
+    pub(super) fn texture(&self) -> &wgpu::Texture {
+        match self {
+            Self::Surface(frame) => &frame.texture,
+            #[cfg(feature = "testing")]
+            Self::Headless(texture) => texture,
+        }
+    }
+
+    pub(super) fn present(self) {
+        match self {
+            Self::Surface(frame) => frame.present(),
+            #[cfg(feature = "testing")]
+            Self::Headless(_) => {}
+        }
+    }
+}
+
 struct RenderPassParams<'a, 'b> {
     msaa_view: Option<wgpu::TextureView>,
     clear_target: bool,
@@ -55,8 +83,10 @@ struct RenderPassParams<'a, 'b> {
     queue: &'a wgpu::Queue,
     config: &'a wgpu::SurfaceConfiguration,
     target_size: PxSize,
-    clip_stack: &'a mut SmallVec<[PxRect; 16]>,
+    scissor_scale: f32,
+    clip_stack: &'a mut SmallVec<[ClipShape; 16]>,
     apply_clip: bool,
+    clip: &'a mut ClipStencilState,
     resources: &'a mut FrameResources<'b>,
 }
 
@@ -67,7 +97,7 @@ struct RenderPassExecParams<'a, 'b> {
     resources: &'a mut FrameResources<'b>,
     clear_state: &'a mut RenderPassClearState,
     pass: &'a mut RenderPassPlan,
-    clip_stack: &'a mut SmallVec<[PxRect; 16]>,
+    clip_stack: &'a mut SmallVec<[ClipShape; 16]>,
 }
 
 struct BlitParams<'a> {
@@ -217,6 +247,7 @@ struct RenderCoreFrameState<'a> {
     pipelines: &'a mut RenderPipelines,
     compute: &'a mut ComputeState,
     blit: &'a BlitState,
+    clip: &'a mut ClipStencilState,
 }
 
 struct RenderPassClearState {
@@ -345,7 +376,8 @@ struct SubmitBatchParams<'a, 'b> {
     resources: SubmitContext<'a>,
     scene_texture_view: &'a wgpu::TextureView,
     target_size: PxSize,
-    clip_stack: &'b [PxRect],
+    scissor_scale: f32,
+    clip_stack: &'b [ClipShape],
     current_batch_draw_rect: &'a mut Option<PxRect>,
 }
 
@@ -357,6 +389,7 @@ struct ComputePassParams<'a> {
     queue: &'a wgpu::Queue,
     config: &'a wgpu::SurfaceConfiguration,
     resource_manager: &'a mut ComputeResourceManager,
+    scale: f32,
 }
 
 impl RenderCore {
@@ -381,36 +414,16 @@ impl RenderCore {
     ) {
         let render_start = Instant::now();
         let current_frame = self.frame_index;
-        self.last_render_breakdown = None;
+        let previous_render = self
+            .last_render_breakdown
+            .take()
+            .map(|breakdown| breakdown.total);
+        if self.hud.is_visible() {
+            self.hud.record_frame(render_start);
+        }
         let acquire_start = Instant::now();
-        let (output_frame, reconfigure_after_present) = match self.surface.get_current_texture() {
-            wgpu::CurrentSurfaceTexture::Success(frame) => (frame, false),
-            wgpu::CurrentSurfaceTexture::Suboptimal(frame) => {
-                debug!("Surface suboptimal, rendering frame before reconfiguring...");
-                (frame, true)
-            }
-            wgpu::CurrentSurfaceTexture::Timeout => {
-                warn!("Surface timeout. Frame will be dropped.");
-                return;
-            }
-            wgpu::CurrentSurfaceTexture::Occluded => {
-                warn!("Surface occluded. Frame will be dropped.");
-                return;
-            }
-            wgpu::CurrentSurfaceTexture::Outdated => {
-                debug!("Surface outdated, reconfiguring...");
-                self.resize_surface();
-                return;
-            }
-            wgpu::CurrentSurfaceTexture::Lost => {
-                warn!("Surface lost, recreating surface...");
-                self.recreate_surface();
-                return;
-            }
-            wgpu::CurrentSurfaceTexture::Validation => {
-                error!("Surface validation error. Frame will be dropped.");
-                return;
-            }
+        let Some((output_frame, reconfigure_after_present)) = self.acquire_output_frame() else {
+            return;
         };
         let acquire = acquire_start.elapsed();
 
@@ -425,6 +438,11 @@ impl RenderCore {
             resources,
             external_resources,
         } = execution;
+        let draw_command_count = ops
+            .iter()
+            .filter(|op| matches!(op.command, Command::Draw(_)))
+            .count();
+        self.external_textures.begin_frame(current_frame);
         for resource in &external_resources {
             self.external_textures
                 .mark_used(resource.handle_id, current_frame);
@@ -440,6 +458,10 @@ impl RenderCore {
             .create_command_encoder(&wgpu::CommandEncoderDescriptor {
                 label: Some("Render Encoder"),
             });
+        #[cfg(feature = "profiling")]
+        if let Some(timer) = &mut self.gpu_timer {
+            timer.begin_frame(&self.device, &mut encoder);
+        }
 
         let device = &self.device;
         let queue = &self.queue;
@@ -449,9 +471,11 @@ impl RenderCore {
         let targets = &mut self.targets;
         let compute = &mut self.compute;
         let local_textures = &mut self.local_textures;
+        let clip = &mut self.clip;
 
         let encode_start = Instant::now();
         local_textures.begin_frame(current_frame);
+        clip.begin_frame();
 
         // Frame-level begin for all pipelines
         pipelines
@@ -461,7 +485,7 @@ impl RenderCore {
 
         let mut scene_texture_view = targets.offscreen.clone();
         let mut scene_source = SceneSource::Offscreen;
-        let mut clip_stack: SmallVec<[PxRect; 16]> = SmallVec::new();
+        let mut clip_stack: SmallVec<[ClipShape; 16]> = SmallVec::new();
         let mut frame_resources = FrameResources::new(FrameResourcesParams {
             pool: local_textures,
             device,
@@ -474,7 +498,7 @@ impl RenderCore {
         });
 
         let output_view = output_frame
-            .texture
+            .texture()
             .create_view(&wgpu::TextureViewDescriptor::default());
 
         let mut clear_state = RenderPassClearState::new(resources.len(), &external_resources);
@@ -487,6 +511,7 @@ impl RenderCore {
             pipelines,
             compute,
             blit,
+            clip,
         };
 
         for (pass_index, pass) in passes.iter_mut().enumerate() {
@@ -512,7 +537,7 @@ impl RenderCore {
             source: &scene_texture_view,
             target: &output_view,
             bind_group_layout: &blit.bind_group_layout,
-            sampler: &blit.sampler,
+            sampler: &blit.present_sampler,
             pipeline: &blit.pipeline,
             target_size,
             scissor_rect: None,
@@ -525,6 +550,21 @@ impl RenderCore {
             target_size,
             dirty_overlay_rects,
         );
+        if self.hud.is_visible() {
+            self.hud.draw(
+                device,
+                queue,
+                &mut encoder,
+                &output_view,
+                target_size,
+                self.output.scale_factor() as f32,
+                &hud::FrameHudStats {
+                    draw_commands: draw_command_count,
+                    passes: passes.len(),
+                    render: previous_render,
+                },
+            );
+        }
 
         // Frame-level end for all pipelines
         frame_state
@@ -532,11 +572,19 @@ impl RenderCore {
             .drawer
             .pipeline_registry
             .end_all_frames(device, queue, config);
+        #[cfg(feature = "profiling")]
+        if let Some(timer) = &mut self.gpu_timer {
+            timer.end_frame(&mut encoder);
+        }
         let encode = encode_start.elapsed();
 
         let submit_start = Instant::now();
         queue.submit(Some(encoder.finish()));
         let submit = submit_start.elapsed();
+        #[cfg(feature = "profiling")]
+        if let Some(timer) = &mut self.gpu_timer {
+            timer.after_submit(submit_start);
+        }
 
         let present_start = Instant::now();
         output_frame.present();
@@ -556,6 +604,49 @@ impl RenderCore {
         });
     }
 
+    /// Acquires the texture the frame is drawn into, along with whether the
+    /// surface must be reconfigured after presenting it.
+    pub(super) fn acquire_output_frame(&mut self) -> Option<(OutputFrame, bool)> {
+        let surface = match &self.output {
+            RenderOutput::Window { surface, .. } => surface,
+            #[cfg(feature = "testing")]
+            RenderOutput::Headless { texture } => {
+                return Some((OutputFrame::Headless(texture.clone()), false));
+            }
+        };
+        match surface.get_current_texture() {
+            wgpu::CurrentSurfaceTexture::Success(frame) => {
+                Some((OutputFrame::Surface(frame), false))
+            }
+            wgpu::CurrentSurfaceTexture::Suboptimal(frame) => {
+                debug!("Surface suboptimal, rendering frame before reconfiguring...");
+                Some((OutputFrame::Surface(frame), true))
+            }
+            wgpu::CurrentSurfaceTexture::Timeout => {
+                warn!("Surface timeout. Frame will be dropped.");
+                None
+            }
+            wgpu::CurrentSurfaceTexture::Occluded => {
+                warn!("Surface occluded. Frame will be dropped.");
+                None
+            }
+            wgpu::CurrentSurfaceTexture::Outdated => {
+                debug!("Surface outdated, reconfiguring...");
+                self.resize_surface();
+                None
+            }
+            wgpu::CurrentSurfaceTexture::Lost => {
+                warn!("Surface lost, recreating surface...");
+                self.recreate_surface();
+                None
+            }
+            wgpu::CurrentSurfaceTexture::Validation => {
+                error!("Surface validation error. Frame will be dropped.");
+                None
+            }
+        }
+    }
+
     fn execute_render_pass(
         state: &mut RenderCoreFrameState<'_>,
         params: RenderPassExecParams<'_, '_>,
@@ -615,10 +706,7 @@ impl RenderCore {
                                 bind_group_layout: &state.blit.bind_group_layout,
                                 sampler: &state.blit.sampler,
                                 pipeline: &state.blit.pipeline_rgba,
-                                target_size: PxSize::new(
-                                    Px(state.config.width as i32),
-                                    Px(state.config.height as i32),
-                                ),
+                                target_size: state.targets.scene_size(),
                                 scissor_rect: None,
                             });
                         }
@@ -630,12 +718,9 @@ impl RenderCore {
                             queue: state.queue,
                             config: state.config,
                             resource_manager: &mut state.compute.resource_manager,
+                            scale: state.targets.render_scale,
                         };
-                        let texture_size = wgpu::Extent3d {
-                            width: state.config.width,
-                            height: state.config.height,
-                            depth_or_array_layers: 1,
-                        };
+                        let texture_size = state.targets.scene_extent;
                         do_compute_with_targets(
                             params,
                             if read_resource == RenderResourceId::SceneColor {
@@ -657,6 +742,7 @@ impl RenderCore {
                             queue: state.queue,
                             config: state.config,
                             resource_manager: &mut state.compute.resource_manager,
+                            scale: 1.0,
                         };
                         let Some(slot) = resources.local_slot_mut(write_resource) else {
                             return;
@@ -684,6 +770,7 @@ impl RenderCore {
                             queue: state.queue,
                             config: state.config,
                             resource_manager: &mut state.compute.resource_manager,
+                            scale: 1.0,
                         };
                         let Some(slot) = resources.external_slot_mut(write_resource) else {
                             return;
@@ -722,6 +809,8 @@ impl RenderCore {
                 let write_resource = pass.write_resource;
                 let reads_scene = pass.read_resource == Some(RenderResourceId::SceneColor);
                 let (write_target, msaa_view, target_size) = match write_resource {
+                    // Pipelines keep mapping commands against the logical surface size; the
+                    // scaled attachment only affects scissor rects.
                     RenderResourceId::SceneColor => (
                         state.targets.offscreen.clone(),
                         state.targets.msaa_view.clone(),
@@ -769,10 +858,7 @@ impl RenderCore {
                         bind_group_layout: &state.blit.bind_group_layout,
                         sampler: &state.blit.sampler,
                         pipeline: &state.blit.pipeline,
-                        target_size: PxSize::new(
-                            Px(state.config.width as i32),
-                            Px(state.config.height as i32),
-                        ),
+                        target_size: state.targets.scene_size(),
                         scissor_rect: None,
                     });
                     scene_view = copy_view;
@@ -791,8 +877,14 @@ impl RenderCore {
                     queue: state.queue,
                     config: state.config,
                     target_size,
+                    scissor_scale: if write_resource == RenderResourceId::SceneColor {
+                        state.targets.render_scale
+                    } else {
+                        1.0
+                    },
                     clip_stack,
                     apply_clip: write_resource == RenderResourceId::SceneColor,
+                    clip: state.clip,
                     resources,
                 });
 
@@ -942,9 +1034,9 @@ fn do_compute_with_targets<T: ComputeTargets>(
             batch_sampling_rects.push(sampling_area);
             batch_items.push(ErasedComputeBatchItem {
                 command: &*candidate.command,
-                size: candidate.size,
-                position: candidate.start_pos,
-                target_area: candidate.target_rect,
+                size: scale_size(candidate.size, params.scale),
+                position: scale_position(candidate.start_pos, params.scale),
+                target_area: scale_rect(candidate.target_rect, params.scale),
             });
             cursor += 1;
         }
@@ -953,9 +1045,9 @@ fn do_compute_with_targets<T: ComputeTargets>(
             batch_sampling_rects.push(command.sampling_rect);
             batch_items.push(ErasedComputeBatchItem {
                 command: &*command.command,
-                size: command.size,
-                position: command.start_pos,
-                target_area: command.target_rect,
+                size: scale_size(command.size, params.scale),
+                position: scale_position(command.start_pos, params.scale),
+                target_area: scale_rect(command.target_rect, params.scale),
             });
             cursor = index + 1;
         }
@@ -1050,8 +1142,10 @@ fn render_current_pass(params: RenderPassParams<'_, '_>) {
         queue,
         config,
         target_size,
+        scissor_scale,
         clip_stack,
         apply_clip,
+        clip,
         resources,
     } = params;
 
@@ -1067,6 +1161,10 @@ fn render_current_pass(params: RenderPassParams<'_, '_>) {
         wgpu::LoadOp::Load
     };
 
+    // The frame's stencil texture is cleared for every pass; shaped clips
+    // still active from earlier passes are written again below.
+    let stencil_view = clip.stencil_view(device, view);
+    let attachment_extent = view.texture().size();
     let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
         label: Some("Render Pass"),
         color_attachments: &[Some(wgpu::RenderPassColorAttachment {
@@ -1078,9 +1176,31 @@ fn render_current_pass(params: RenderPassParams<'_, '_>) {
                 store: wgpu::StoreOp::Store,
             },
         })],
+        depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+            view: &stencil_view,
+            depth_ops: None,
+            stencil_ops: Some(wgpu::Operations {
+                load: wgpu::LoadOp::Clear(0),
+                store: wgpu::StoreOp::Discard,
+            }),
+        }),
         ..Default::default()
     });
 
+    if apply_clip {
+        let shaped = clip_stack.iter().filter(|shape| shape.needs_stencil());
+        for (depth, shape) in (0..).zip(shaped) {
+            clip.write(
+                device,
+                queue,
+                &mut rpass,
+                shape,
+                target_size,
+                ClipStencilOp::Push { depth },
+            );
+        }
+    }
+
     drawer.begin_pass(
         device,
         queue,
@@ -1117,6 +1237,7 @@ fn render_current_pass(params: RenderPassParams<'_, '_>) {
                             },
                             scene_texture_view: &scene_view,
                             target_size,
+                            scissor_scale,
                             clip_stack: if apply_clip {
                                 clip_stack.as_slice()
                             } else {
@@ -1130,13 +1251,25 @@ fn render_current_pass(params: RenderPassParams<'_, '_>) {
                     current_batch_read = None;
                 }
                 // Update clip stack
-                match clip_ops {
-                    ClipOps::Push(rect) => {
-                        clip_stack.push(rect);
+                let (shape, op) = match clip_ops {
+                    ClipOps::Push(shape) => {
+                        let depth = stencil_depth(clip_stack);
+                        clip_stack.push(shape);
+                        (shape, ClipStencilOp::Push { depth })
                     }
                     ClipOps::Pop => {
-                        clip_stack.pop();
+                        let Some(shape) = clip_stack.pop() else {
+                            continue;
+                        };
+                        let depth = stencil_depth(clip_stack) + 1;
+                        (shape, ClipStencilOp::Pop { depth })
                     }
+                };
+                if apply_clip && shape.needs_stencil() {
+                    // Clip writes must cover the whole shape regardless of the
+                    // scissor left behind by the previous batch.
+                    rpass.set_scissor_rect(0, 0, attachment_extent.width, attachment_extent.height);
+                    clip.write(device, queue, &mut rpass, &shape, target_size, op);
                 }
                 // continue to next command
                 continue;
@@ -1167,6 +1300,7 @@ fn render_current_pass(params: RenderPassParams<'_, '_>) {
                     },
                     scene_texture_view: &scene_view,
                     target_size,
+                    scissor_scale,
                     clip_stack: if apply_clip {
                         clip_stack.as_slice()
                     } else {
@@ -1205,6 +1339,7 @@ fn render_current_pass(params: RenderPassParams<'_, '_>) {
                 },
                 scene_texture_view: &scene_view,
                 target_size,
+                scissor_scale,
                 clip_stack: if apply_clip {
                     clip_stack.as_slice()
                 } else {
@@ -1257,6 +1392,7 @@ fn submit_buffered_commands(
         resources,
         scene_texture_view,
         target_size,
+        scissor_scale,
         clip_stack,
         current_batch_draw_rect,
     } = params;
@@ -1279,7 +1415,15 @@ fn submit_buffered_commands(
     let Some(rect) = *current_batch_draw_rect else {
         return;
     };
+    let attachment_size = scale_size(target_size, scissor_scale);
+    let Some(rect) = clamp_rect_to_target(scale_rect(rect, scissor_scale), attachment_size) else {
+        *current_batch_draw_rect = None;
+        return;
+    };
     set_scissor_rect_from_pxrect(rpass, rect);
+    rpass.set_stencil_reference(stencil_depth(clip_stack));
+    let current_clip_rect = current_clip_rect
+        .and_then(|clip| clamp_rect_to_target(scale_rect(clip, scissor_scale), attachment_size));
 
     drawer.submit(
         ErasedDrawContext {
@@ -1310,23 +1454,65 @@ fn clamp_rect_to_target(rect: PxRect, target_size: PxSize) -> Option<PxRect> {
     rect.intersection(&target_rect)
 }
 
+fn scale_px(value: Px, scale: f32) -> Px {
+    Px((value.0 as f32 * scale).round() as i32)
+}
+
+fn scale_position(position: PxPosition, scale: f32) -> PxPosition {
+    if scale == 1.0 {
+        return position;
+    }
+    PxPosition::new(scale_px(position.x, scale), scale_px(position.y, scale))
+}
+
+fn scale_size(size: PxSize, scale: f32) -> PxSize {
+    if scale == 1.0 {
+        return size;
+    }
+    PxSize::new(
+        Px((size.width.0 as f32 * scale).round().max(1.0) as i32),
+        Px((size.height.0 as f32 * scale).round().max(1.0) as i32),
+    )
+}
+
+/// Scales a rectangle outward so the scaled rect fully covers the original
+/// pixels.
+fn scale_rect(rect: PxRect, scale: f32) -> PxRect {
+    if scale == 1.0 {
+        return rect;
+    }
+    let left = (rect.x.0 as f32 * scale).floor() as i32;
+    let top = (rect.y.0 as f32 * scale).floor() as i32;
+    let right = ((rect.x.0 + rect.width.0) as f32 * scale).ceil() as i32;
+    let bottom = ((rect.y.0 + rect.height.0) as f32 * scale).ceil() as i32;
+    PxRect::new(Px(left), Px(top), Px(right - left), Px(bottom - top))
+}
+
+/// Number of active clips that live in the clip stencil buffer.
+fn stencil_depth(clip_stack: &[ClipShape]) -> u32 {
+    clip_stack
+        .iter()
+        .filter(|shape| shape.needs_stencil())
+        .count() as u32
+}
+
 /// Apply clip_stack to current_batch_draw_rect. Returns false if intersection
 /// yields nothing (meaning there is nothing to submit), true otherwise.
 ///
 /// Also returns the current clipping rectangle (if any) for potential use by
 /// the caller.
 fn apply_clip_to_batch_rect(
-    clip_stack: &[PxRect],
+    clip_stack: &[ClipShape],
     current_batch_draw_rect: &mut Option<PxRect>,
     target_size: PxSize,
 ) -> (Option<PxRect>, bool) {
     let clipped_rect = if clip_stack.is_empty() {
         None
     } else {
-        clip_stack.iter().copied().try_fold(
+        clip_stack.iter().try_fold(
             PxRect::from_position_size(PxPosition::ZERO, target_size),
-            |current, rect| {
-                let clamped = clamp_rect_to_target(rect, target_size)?;
+            |current, shape| {
+                let clamped = clamp_rect_to_target(shape.bounds(), target_size)?;
                 current.intersection(&clamped)
             },
         )
@@ -1383,8 +1569,8 @@ mod tests {
     fn apply_clip_to_batch_rect_intersects_full_clip_stack() {
         let target_size = PxSize::new(Px(400), Px(400));
         let clip_stack = [
-            PxRect::new(Px(0), Px(0), Px(200), Px(200)),
-            PxRect::new(Px(50), Px(50), Px(200), Px(200)),
+            ClipShape::Rect(PxRect::new(Px(0), Px(0), Px(200), Px(200))),
+            ClipShape::Rect(PxRect::new(Px(50), Px(50), Px(200), Px(200))),
         ];
         let mut batch_rect = Some(PxRect::new(Px(25), Px(25), Px(200), Px(200)));
 
@@ -1406,8 +1592,8 @@ mod tests {
     fn apply_clip_to_batch_rect_drops_batch_when_nested_clips_do_not_overlap() {
         let target_size = PxSize::new(Px(400), Px(400));
         let clip_stack = [
-            PxRect::new(Px(0), Px(0), Px(100), Px(100)),
-            PxRect::new(Px(200), Px(200), Px(100), Px(100)),
+            ClipShape::Rect(PxRect::new(Px(0), Px(0), Px(100), Px(100))),
+            ClipShape::Rect(PxRect::new(Px(200), Px(200), Px(100), Px(100))),
         ];
         let mut batch_rect = Some(PxRect::new(Px(0), Px(0), Px(300), Px(300)));
 
@@ -1418,4 +1604,59 @@ mod tests {
         assert_eq!(clip_rect, None);
         assert_eq!(batch_rect, None);
     }
+
+    #[test]
+    fn stencil_depth_counts_only_shaped_clips() {
+        let rect = PxRect::new(Px(0), Px(0), Px(100), Px(100));
+        let clip_stack = [
+            ClipShape::Rect(rect),
+            ClipShape::RoundedRect {
+                rect,
+                corner_radii: [8.0; 4],
+            },
+            ClipShape::RoundedRect {
+                rect,
+                corner_radii: [0.0; 4],
+            },
+            ClipShape::Ellipse(rect),
+        ];
+
+        assert_eq!(stencil_depth(&clip_stack), 2);
+        assert_eq!(stencil_depth(&clip_stack[..1]), 0);
+    }
+
+    #[test]
+    fn rotated_clip_scissors_by_its_rotated_bounds() {
+        let rect = PxRect::new(Px(0), Px(0), Px(100), Px(20));
+        let quarter_turn = ClipShape::Rotated {
+            rect,
+            corner_radii: [0.0; 4],
+            rotation: 90.0,
+        };
+        let tilted = ClipShape::Rotated {
+            rect,
+            corner_radii: [0.0; 4],
+            rotation: 30.0,
+        };
+
+        assert!(!quarter_turn.needs_stencil());
+        assert_eq!(
+            quarter_turn.bounds(),
+            PxRect::new(Px(40), Px(-40), Px(20), Px(100))
+        );
+        assert!(tilted.needs_stencil());
+        let bounds = tilted.bounds();
+        assert!(bounds.width > rect.width && bounds.height > rect.height);
+    }
+
+    #[test]
+    fn scale_rect_expands_to_cover_partial_pixels() {
+        let rect = PxRect::new(Px(3), Px(5), Px(10), Px(7));
+
+        assert_eq!(scale_rect(rect, 1.0), rect);
+        assert_eq!(
+            scale_rect(rect, 0.5),
+            PxRect::new(Px(1), Px(2), Px(6), Px(4))
+        );
+    }
 }
@@ -0,0 +1,154 @@
+//! GPU frame timing for the profiler.
+//!
+//! Timestamps are written at the start and end of the frame's command
+//! encoder and read back asynchronously, so a frame's GPU time is reported a
+//! few frames later. Frames are skipped while a readback is still in flight.
+
+use std::sync::{Arc, OnceLock};
+
+use crate::{
+    profiler::{self, GpuMeta},
+    time::Instant,
+};
+
+/// Device features the timer needs.
+const FEATURES: wgpu::Features =
+    wgpu::Features::TIMESTAMP_QUERY.union(wgpu::Features::TIMESTAMP_QUERY_INSIDE_ENCODERS);
+/// Two `u64` timestamps.
+const RESOLVE_SIZE: wgpu::BufferAddress = 2 * std::mem::size_of::<u64>() as wgpu::BufferAddress;
+
+/// Returns the timer features the adapter supports, to be requested with the
+/// device.
+pub(super) fn supported_features(adapter: &wgpu::Adapter) -> wgpu::Features {
+    if adapter.features().contains(FEATURES) {
+        FEATURES
+    } else {
+        wgpu::Features::empty()
+    }
+}
+
+struct PendingReadback {
+    frame_idx: u64,
+    submitted_at: Instant,
+    /// Set by the map callback to whether mapping succeeded.
+    mapped: Arc<OnceLock<bool>>,
+}
+
+pub(super) struct GpuTimer {
+    query_set: wgpu::QuerySet,
+    resolve_buffer: wgpu::Buffer,
+    readback_buffer: wgpu::Buffer,
+    /// Nanoseconds per timestamp tick.
+    period: f32,
+    recording: bool,
+    pending: Option<PendingReadback>,
+}
+
+impl GpuTimer {
+    /// Creates the timer, or `None` when the device lacks timestamp queries.
+    pub(super) fn new(device: &wgpu::Device, queue: &wgpu::Queue) -> Option<Self> {
+        if !device.features().contains(FEATURES) {
+            return None;
+        }
+        let query_set = device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: Some("GPU Frame Timer Queries"),
+            ty: wgpu::QueryType::Timestamp,
+            count: 2,
+        });
+        let resolve_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("GPU Frame Timer Resolve"),
+            size: RESOLVE_SIZE,
+            usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("GPU Frame Timer Readback"),
+            size: RESOLVE_SIZE,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        Some(Self {
+            query_set,
+            resolve_buffer,
+            readback_buffer,
+            period: queue.get_timestamp_period(),
+            recording: false,
+            pending: None,
+        })
+    }
+
+    /// Reports a finished readback, then starts timing this frame if the
+    /// readback buffer is free.
+    pub(super) fn begin_frame(
+        &mut self,
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        self.collect(device);
+        self.recording = self.pending.is_none();
+        if self.recording {
+            encoder.write_timestamp(&self.query_set, 0);
+        }
+    }
+
+    pub(super) fn end_frame(&mut self, encoder: &mut wgpu::CommandEncoder) {
+        if !self.recording {
+            return;
+        }
+        encoder.write_timestamp(&self.query_set, 1);
+        encoder.resolve_query_set(&self.query_set, 0..2, &self.resolve_buffer, 0);
+        encoder.copy_buffer_to_buffer(
+            &self.resolve_buffer,
+            0,
+            &self.readback_buffer,
+            0,
+            RESOLVE_SIZE,
+        );
+    }
+
+    /// Starts reading back the frame's timestamps once its commands were
+    /// submitted at `submitted_at`.
+    pub(super) fn after_submit(&mut self, submitted_at: Instant) {
+        if !std::mem::take(&mut self.recording) {
+            return;
+        }
+        let mapped = Arc::new(OnceLock::new());
+        let callback_mapped = mapped.clone();
+        self.readback_buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                let _ = callback_mapped.set(result.is_ok());
+            });
+        self.pending = Some(PendingReadback {
+            frame_idx: profiler::current_frame_idx(),
+            submitted_at,
+            mapped,
+        });
+    }
+
+    fn collect(&mut self, device: &wgpu::Device) {
+        let Some(pending) = &self.pending else {
+            return;
+        };
+        let _ = device.poll(wgpu::PollType::Poll);
+        let Some(&mapped) = pending.mapped.get() else {
+            return;
+        };
+        if mapped {
+            let (start, end) = {
+                let data = self.readback_buffer.slice(..).get_mapped_range();
+                let timestamps: &[u64] = bytemuck::cast_slice(&data);
+                (timestamps[0], timestamps[1])
+            };
+            self.readback_buffer.unmap();
+            if end > start {
+                profiler::submit_gpu_meta(GpuMeta {
+                    frame_idx: pending.frame_idx,
+                    submitted_at: pending.submitted_at,
+                    gpu_time_ns: ((end - start) as f64 * f64::from(self.period)) as u64,
+                });
+            }
+        }
+        self.pending = None;
+    }
+}
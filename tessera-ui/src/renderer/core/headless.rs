@@ -0,0 +1,125 @@
+//! Windowless render cores for tests.
+//!
+//! Frames are drawn into an RGBA texture instead of a surface and can be read
+//! back to compare against golden images.
+
+use tracing::info;
+
+use super::{RenderCore, RenderOutput};
+
+/// Format of headless frames, chosen so read back pixels are plain sRGB RGBA.
+const HEADLESS_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+impl RenderCore {
+    /// Creates a render core that draws `width` by `height` frames without a
+    /// window.
+    pub(crate) async fn new_headless(width: u32, height: u32, sample_count: u32) -> Self {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::new_without_display_handle());
+        let adapter = match instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                compatible_surface: None,
+                force_fallback_adapter: false,
+            })
+            .await
+        {
+            Ok(adapter) => adapter,
+            Err(e) => panic!("Failed to find an adapter for headless rendering: {e:?}"),
+        };
+        let adapter_info = adapter.get_info();
+        info!("Using WGPU adapter for headless rendering: {adapter_info:#?}");
+        let (device, queue) = Self::request_device_and_queue_for_adapter(&adapter).await;
+        let config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: HEADLESS_FORMAT,
+            width: width.max(1),
+            height: height.max(1),
+            present_mode: wgpu::PresentMode::Fifo,
+            alpha_mode: wgpu::CompositeAlphaMode::Opaque,
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        };
+        let texture = Self::create_headless_target(&device, &config);
+        Self::assemble(
+            RenderOutput::Headless { texture },
+            adapter_info,
+            device,
+            queue,
+            config,
+            sample_count,
+            1.0,
+        )
+    }
+
+    pub(super) fn create_headless_target(
+        device: &wgpu::Device,
+        config: &wgpu::SurfaceConfiguration,
+    ) -> wgpu::Texture {
+        device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Headless Output"),
+            size: wgpu::Extent3d {
+                width: config.width,
+                height: config.height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: config.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        })
+    }
+
+    /// Reads back the last rendered frame as tightly packed sRGB RGBA rows.
+    ///
+    /// Returns `None` for render cores that present to a window.
+    pub(crate) fn read_output_pixels(&self) -> Option<Vec<u8>> {
+        let RenderOutput::Headless { texture } = &self.output else {
+            return None;
+        };
+        let width = texture.width();
+        let height = texture.height();
+        let row_bytes = width * 4;
+        let padded_row_bytes = row_bytes.next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Headless Readback"),
+            size: u64::from(padded_row_bytes) * u64::from(height),
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Headless Readback Encoder"),
+            });
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_bytes),
+                    rows_per_image: Some(height),
+                },
+            },
+            texture.size(),
+        );
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice = buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| {});
+        if let Err(err) = self.device.poll(wgpu::PollType::wait_indefinitely()) {
+            panic!("Failed to read back the headless frame: {err:?}");
+        }
+        let mapped = slice.get_mapped_range();
+        let pixels = mapped
+            .chunks_exact(padded_row_bytes as usize)
+            .flat_map(|row| &row[..row_bytes as usize])
+            .copied()
+            .collect();
+        drop(mapped);
+        buffer.unmap();
+        Some(pixels)
+    }
+}
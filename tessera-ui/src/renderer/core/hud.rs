@@ -0,0 +1,405 @@
+//! Frame timing HUD drawn by the render core.
+//!
+//! The HUD has its own pipeline and is drawn onto the surface after the scene
+//! has been blitted to it, so it stays readable when user pipelines
+//! misbehave. Text uses a built-in 3x5 pixel font rather than the text
+//! pipeline for the same reason.
+
+use std::{collections::VecDeque, time::Duration};
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::{PxSize, time::Instant};
+
+/// Number of frame intervals shown in the graph.
+const HISTORY_LEN: usize = 120;
+/// Frame interval at 60 FPS, in milliseconds.
+const TARGET_INTERVAL_MS: f32 = 1000.0 / 60.0;
+/// Frame interval at the top of the graph, in milliseconds.
+const GRAPH_MAX_MS: f32 = 50.0;
+
+const GLYPH_WIDTH: usize = 3;
+const GLYPH_HEIGHT: usize = 5;
+
+const PANEL_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 0.72];
+const TEXT_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+const GUIDE_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 0.35];
+const ON_TIME_COLOR: [f32; 4] = [0.3, 0.85, 0.35, 1.0];
+const SLOW_COLOR: [f32; 4] = [0.95, 0.8, 0.2, 1.0];
+const JANK_COLOR: [f32; 4] = [0.95, 0.3, 0.25, 1.0];
+
+/// CPU timings of a frame, measured before it reaches the render core.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct FrameHudTimings {
+    /// Time spent building the component tree.
+    pub build: Duration,
+    /// Time spent measuring the component tree.
+    pub measure: Duration,
+    /// Time spent placing components and recording draw commands.
+    pub draw: Duration,
+}
+
+/// Counts gathered while rendering a frame.
+pub(super) struct FrameHudStats {
+    pub draw_commands: usize,
+    pub passes: usize,
+    /// Duration of the previous render call, as the current one is still
+    /// running when the HUD is drawn.
+    pub render: Option<Duration>,
+}
+
+/// A filled rectangle, in pixels until it is mapped to clip space for upload.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+struct HudRect {
+    rect: [f32; 4],
+    color: [f32; 4],
+}
+
+impl HudRect {
+    fn new(x0: f32, y0: f32, x1: f32, y1: f32, color: [f32; 4]) -> Self {
+        Self {
+            rect: [x0, y0, x1, y1],
+            color,
+        }
+    }
+}
+
+pub(super) struct FrameHud {
+    pipeline: wgpu::RenderPipeline,
+    instances: Option<wgpu::Buffer>,
+    visible: bool,
+    timings: FrameHudTimings,
+    last_frame_at: Option<Instant>,
+    /// Intervals between recent frames, in milliseconds.
+    intervals: VecDeque<f32>,
+}
+
+impl FrameHud {
+    pub(super) fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        pipeline_cache: Option<&wgpu::PipelineCache>,
+    ) -> Self {
+        let shader = device.create_shader_module(wgpu::include_wgsl!("../shaders/frame_hud.wgsl"));
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Frame HUD Pipeline Layout"),
+            bind_group_layouts: &[],
+            immediate_size: 0,
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Frame HUD Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<HudRect>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &wgpu::vertex_attr_array![0 => Float32x4, 1 => Float32x4],
+                }],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleStrip,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview_mask: None,
+            cache: pipeline_cache,
+        });
+        Self {
+            pipeline,
+            instances: None,
+            visible: false,
+            timings: FrameHudTimings::default(),
+            last_frame_at: None,
+            intervals: VecDeque::with_capacity(HISTORY_LEN),
+        }
+    }
+
+    pub(super) fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Shows or hides the HUD, starting a fresh graph when it is shown.
+    pub(super) fn set_visible(&mut self, visible: bool) {
+        if visible && !self.visible {
+            self.last_frame_at = None;
+            self.intervals.clear();
+        }
+        self.visible = visible;
+    }
+
+    pub(super) fn set_timings(&mut self, timings: FrameHudTimings) {
+        self.timings = timings;
+    }
+
+    /// Records that a frame started rendering at `now`.
+    pub(super) fn record_frame(&mut self, now: Instant) {
+        let Some(last) = self.last_frame_at.replace(now) else {
+            return;
+        };
+        if self.intervals.len() == HISTORY_LEN {
+            self.intervals.pop_front();
+        }
+        self.intervals
+            .push_back(now.duration_since(last).as_secs_f32() * 1000.0);
+    }
+
+    /// Draws the HUD over `target`, scaled by `scale_factor`.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn draw(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &wgpu::TextureView,
+        target_size: PxSize,
+        scale_factor: f32,
+        stats: &FrameHudStats,
+    ) {
+        let unit = scale_factor.round().max(1.0);
+        let mut rects = layout(&self.intervals, &self.timings, stats, unit);
+        let width = target_size.width.0.max(1) as f32;
+        let height = target_size.height.0.max(1) as f32;
+        for rect in &mut rects {
+            let [x0, y0, x1, y1] = rect.rect;
+            rect.rect = [
+                x0 / width * 2.0 - 1.0,
+                1.0 - y0 / height * 2.0,
+                x1 / width * 2.0 - 1.0,
+                1.0 - y1 / height * 2.0,
+            ];
+        }
+
+        let size = std::mem::size_of_val(rects.as_slice()) as wgpu::BufferAddress;
+        if self
+            .instances
+            .as_ref()
+            .is_none_or(|buffer| buffer.size() < size)
+        {
+            self.instances = Some(device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Frame HUD Instances"),
+                size: size.next_power_of_two(),
+                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }));
+        }
+        let instances = self
+            .instances
+            .as_ref()
+            .expect("HUD instance buffer must exist after allocation");
+        queue.write_buffer(instances, 0, bytemuck::cast_slice(&rects));
+
+        let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Frame HUD Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: target,
+                resolve_target: None,
+                depth_slice: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            ..Default::default()
+        });
+        rpass.set_pipeline(&self.pipeline);
+        rpass.set_vertex_buffer(0, instances.slice(..size));
+        rpass.draw(0..4, 0..rects.len() as u32);
+    }
+}
+
+/// Lays the HUD out in pixels, with the panel first so it is drawn below
+/// everything else.
+fn layout(
+    intervals: &VecDeque<f32>,
+    timings: &FrameHudTimings,
+    stats: &FrameHudStats,
+    unit: f32,
+) -> Vec<HudRect> {
+    let cell = unit * 2.0;
+    let margin = unit * 8.0;
+    let padding = unit * 6.0;
+    let line_height = cell * (GLYPH_HEIGHT + 2) as f32;
+    let graph_width = unit * HISTORY_LEN as f32;
+    let graph_height = unit * 40.0;
+
+    let fps = if intervals.is_empty() {
+        "FPS -".to_string()
+    } else {
+        let mean = intervals.iter().sum::<f32>() / intervals.len() as f32;
+        format!("FPS {:.1}", 1000.0 / mean.max(f32::EPSILON))
+    };
+    let ms = |duration: Duration| format!("{:.2} MS", duration.as_secs_f64() * 1000.0);
+    let lines = [
+        format!("BUILD {}", ms(timings.build)),
+        format!("MEASURE {}", ms(timings.measure)),
+        format!("DRAW {}", ms(timings.draw)),
+        format!(
+            "RENDER {}",
+            stats.render.map_or_else(|| "-".to_string(), ms)
+        ),
+        format!("CMDS {}", stats.draw_commands),
+        format!("PASSES {}", stats.passes),
+    ];
+    let content_width = lines
+        .iter()
+        .chain([&fps])
+        .map(|line| text_width(line, cell))
+        .fold(graph_width, f32::max);
+
+    let mut rects = vec![HudRect::zeroed()];
+    let left = margin + padding;
+    let mut y = margin + padding;
+    push_text(&mut rects, &fps, left, y, cell);
+    y += line_height;
+
+    let graph_bottom = y + graph_height;
+    let bar_width = graph_width / HISTORY_LEN as f32;
+    let graph_right = left + graph_width;
+    for (age, &interval) in intervals.iter().rev().enumerate() {
+        let x1 = graph_right - age as f32 * bar_width;
+        let bar_height = (interval / GRAPH_MAX_MS).min(1.0) * graph_height;
+        // Frames that missed one vsync are slow, later ones are jank.
+        let color = if interval <= TARGET_INTERVAL_MS * 1.2 {
+            ON_TIME_COLOR
+        } else if interval <= TARGET_INTERVAL_MS * 2.2 {
+            SLOW_COLOR
+        } else {
+            JANK_COLOR
+        };
+        rects.push(HudRect::new(
+            x1 - bar_width,
+            graph_bottom - bar_height,
+            x1,
+            graph_bottom,
+            color,
+        ));
+    }
+    let guide_y = graph_bottom - TARGET_INTERVAL_MS / GRAPH_MAX_MS * graph_height;
+    rects.push(HudRect::new(
+        left,
+        guide_y,
+        graph_right,
+        guide_y + unit,
+        GUIDE_COLOR,
+    ));
+    y = graph_bottom + cell * 2.0;
+
+    for line in &lines {
+        push_text(&mut rects, line, left, y, cell);
+        y += line_height;
+    }
+
+    let bottom = y - line_height + GLYPH_HEIGHT as f32 * cell + padding;
+    rects[0] = HudRect::new(
+        margin,
+        margin,
+        left + content_width + padding,
+        bottom,
+        PANEL_COLOR,
+    );
+    rects
+}
+
+fn text_width(text: &str, cell: f32) -> f32 {
+    let chars = text.chars().count();
+    (chars * (GLYPH_WIDTH + 1)).saturating_sub(1) as f32 * cell
+}
+
+/// Pushes the lit pixels of `text`, merging horizontal runs into one rect.
+fn push_text(rects: &mut Vec<HudRect>, text: &str, x: f32, y: f32, cell: f32) {
+    let lit = |bits: u8, column: usize| bits & (1 << (GLYPH_WIDTH - 1 - column)) != 0;
+    for (index, ch) in text.chars().enumerate() {
+        let Some(rows) = glyph(ch) else {
+            continue;
+        };
+        let glyph_x = x + (index * (GLYPH_WIDTH + 1)) as f32 * cell;
+        for (row, &bits) in rows.iter().enumerate() {
+            let top = y + row as f32 * cell;
+            let mut column = 0;
+            while column < GLYPH_WIDTH {
+                if !lit(bits, column) {
+                    column += 1;
+                    continue;
+                }
+                let start = column;
+                while column < GLYPH_WIDTH && lit(bits, column) {
+                    column += 1;
+                }
+                rects.push(HudRect::new(
+                    glyph_x + start as f32 * cell,
+                    top,
+                    glyph_x + column as f32 * cell,
+                    top + cell,
+                    TEXT_COLOR,
+                ));
+            }
+        }
+    }
+}
+
+/// Rows of a 3x5 glyph, top to bottom, with the leftmost pixel in bit 2.
+fn glyph(ch: char) -> Option<[u8; GLYPH_HEIGHT]> {
+    Some(match ch {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        ' ' => [0b000; GLYPH_HEIGHT],
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+        'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glyph_rows_merge_into_runs() {
+        let mut rects = Vec::new();
+        push_text(&mut rects, "0", 10.0, 20.0, 2.0);
+        // 111 and the bottom 111 are single runs, the three 101 rows are two.
+        assert_eq!(rects.len(), 8);
+        assert_eq!(rects[0].rect, [10.0, 20.0, 16.0, 22.0]);
+        assert_eq!(rects[1].rect, [10.0, 22.0, 12.0, 24.0]);
+        assert_eq!(rects[2].rect, [14.0, 22.0, 16.0, 24.0]);
+    }
+}
@@ -15,7 +15,10 @@ use crate::{
     },
 };
 
-use super::{BlitState, ComputeState, FrameTargets, LocalTexturePool, RenderCore, RenderPipelines};
+use super::{
+    BlitState, ComputeState, FrameTargets, LocalTexturePool, RenderCore, RenderOutput,
+    RenderPipelines, clamp_render_scale, clip::ClipStencilState, scaled_extent,
+};
 
 impl RenderCore {
     fn pick_alpha_mode(
@@ -70,7 +73,7 @@ impl RenderCore {
         }
     }
 
-    async fn request_device_and_queue_for_adapter(
+    pub(super) async fn request_device_and_queue_for_adapter(
         adapter: &wgpu::Adapter,
     ) -> (wgpu::Device, wgpu::Queue) {
         let required_limits = if cfg!(target_arch = "wasm32") {
@@ -79,6 +82,8 @@ impl RenderCore {
             wgpu::Limits::default()
         };
         let base_features = wgpu::Features::CLEAR_TEXTURE;
+        #[cfg(feature = "profiling")]
+        let base_features = base_features | super::gpu_timer::supported_features(adapter);
         let supports_pipeline_cache = adapter.features().contains(wgpu::Features::PIPELINE_CACHE);
         let requested_features = if supports_pipeline_cache {
             base_features | wgpu::Features::PIPELINE_CACHE
@@ -127,15 +132,12 @@ impl RenderCore {
         device: &wgpu::Device,
         sample_count: u32,
         config: &wgpu::SurfaceConfiguration,
+        extent: wgpu::Extent3d,
     ) -> (Option<wgpu::Texture>, Option<wgpu::TextureView>) {
         if sample_count > 1 {
             let texture = device.create_texture(&wgpu::TextureDescriptor {
                 label: Some("MSAA Framebuffer"),
-                size: wgpu::Extent3d {
-                    width: config.width,
-                    height: config.height,
-                    depth_or_array_layers: 1,
-                },
+                size: extent,
                 mip_level_count: 1,
                 sample_count,
                 dimension: wgpu::TextureDimension::D2,
@@ -154,6 +156,7 @@ impl RenderCore {
     pub(crate) async fn new(
         window: Arc<Window>,
         sample_count: u32,
+        render_scale: f32,
         window_transparent: bool,
     ) -> Self {
         // Looking for adapters
@@ -209,38 +212,87 @@ impl RenderCore {
         };
         surface.configure(&device, &config);
 
+        // Set scale factor for dp conversion
+        let scale_factor = window.scale_factor();
+        info!("Window scale factor: {scale_factor}");
+        let _ = SCALE_FACTOR.set(RwLock::new(scale_factor));
+
+        let output = RenderOutput::Window {
+            window,
+            instance,
+            surface,
+        };
+        Self::assemble(
+            output,
+            adapter_info,
+            device,
+            queue,
+            config,
+            sample_count,
+            render_scale,
+        )
+    }
+
+    /// Creates the pipelines and frame targets for a configured output.
+    pub(super) fn assemble(
+        output: RenderOutput,
+        adapter_info: wgpu::AdapterInfo,
+        device: wgpu::Device,
+        queue: wgpu::Queue,
+        config: wgpu::SurfaceConfiguration,
+        sample_count: u32,
+        render_scale: f32,
+    ) -> Self {
+        let size = winit::dpi::PhysicalSize::new(config.width, config.height);
+
         // Create pipeline cache if supported
         let pipeline_cache = initialize_cache(&device, &adapter_info);
 
+        // Scene targets are rendered at the scaled resolution and upscaled on present
+        let render_scale = clamp_render_scale(render_scale);
+        let scene_extent = scaled_extent(config.width, config.height, render_scale);
+        info!("Using render scale: {render_scale} ({scene_extent:?})");
+
         // Create MSAA Target
-        let (msaa_texture, msaa_view) = Self::make_msaa_resources(&device, sample_count, &config);
+        let (msaa_texture, msaa_view) =
+            Self::make_msaa_resources(&device, sample_count, &config, scene_extent);
 
         // Create Pass Targets (Offscreen and Compute)
-        let offscreen_texture = Self::create_pass_target(&device, &config, "Offscreen");
-        let offscreen_copy_texture = Self::create_pass_target(&device, &config, "Offscreen Copy");
+        let offscreen_texture =
+            Self::create_pass_target(&device, &config, scene_extent, "Offscreen");
+        let offscreen_copy_texture =
+            Self::create_pass_target(&device, &config, scene_extent, "Offscreen Copy");
         let compute_target_a = Self::create_compute_pass_target(
             &device,
-            &config,
+            scene_extent,
             TextureFormat::Rgba8Unorm,
             "Compute A",
         );
         let compute_target_b = Self::create_compute_pass_target(
             &device,
-            &config,
+            scene_extent,
             TextureFormat::Rgba8Unorm,
             "Compute B",
         );
 
-        let drawer = Drawer::new();
-
-        // Set scale factor for dp conversion
-        let scale_factor = window.scale_factor();
-        info!("Window scale factor: {scale_factor}");
-        let _ = SCALE_FACTOR.set(RwLock::new(scale_factor));
+        let mut drawer = Drawer::new();
+        drawer.register_builtin_pipelines(
+            &device,
+            &queue,
+            config.format,
+            sample_count,
+            pipeline_cache.as_ref(),
+        );
 
         // Create blit pipeline resources
         let blit_shader = device.create_shader_module(wgpu::include_wgsl!("../shaders/blit.wgsl"));
         let blit_sampler = device.create_sampler(&wgpu::SamplerDescriptor::default());
+        let present_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Present Sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
         let blit_bind_group_layout =
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
                 label: Some("Blit Bind Group Layout"),
@@ -361,6 +413,13 @@ impl RenderCore {
                 cache: pipeline_cache.as_ref(),
             });
 
+        let clip = ClipStencilState::new(
+            &device,
+            config.format,
+            sample_count,
+            pipeline_cache.as_ref(),
+        );
+
         let pipelines = RenderPipelines {
             drawer,
             compute_registry: ComputePipelineRegistry::new(),
@@ -373,6 +432,8 @@ impl RenderCore {
             msaa_texture,
             msaa_view,
             sample_count,
+            render_scale,
+            scene_extent,
         };
 
         let compute = ComputeState {
@@ -386,15 +447,16 @@ impl RenderCore {
             pipeline_rgba: blit_pipeline_rgba,
             bind_group_layout: blit_bind_group_layout,
             sampler: blit_sampler,
+            present_sampler,
             #[cfg(feature = "debug-dirty-overlay")]
             dirty_overlay_pipeline,
         };
 
+        let hud = super::hud::FrameHud::new(&device, config.format, pipeline_cache.as_ref());
+
         Self {
-            window,
-            instance,
+            output,
             device,
-            surface,
             queue,
             config,
             size,
@@ -405,26 +467,27 @@ impl RenderCore {
             targets,
             compute,
             blit,
+            clip,
             local_textures: LocalTexturePool::new(),
             external_textures: ExternalTextureRegistry::new(),
             frame_index: 0,
             last_render_breakdown: None,
+            #[cfg(feature = "profiling")]
+            gpu_timer: super::gpu_timer::GpuTimer::new(&device, &queue),
+            hud,
         }
     }
 
     fn create_pass_target(
         device: &wgpu::Device,
         config: &wgpu::SurfaceConfiguration,
+        extent: wgpu::Extent3d,
         label_suffix: &str,
     ) -> wgpu::TextureView {
         let label = format!("Pass {label_suffix} Texture");
         let texture_descriptor = wgpu::TextureDescriptor {
             label: Some(&label),
-            size: wgpu::Extent3d {
-                width: config.width,
-                height: config.height,
-                depth_or_array_layers: 1,
-            },
+            size: extent,
             mip_level_count: 1,
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
@@ -442,18 +505,14 @@ impl RenderCore {
 
     fn create_compute_pass_target(
         device: &wgpu::Device,
-        config: &wgpu::SurfaceConfiguration,
+        extent: wgpu::Extent3d,
         format: TextureFormat,
         label_suffix: &str,
     ) -> wgpu::TextureView {
         let label = format!("Compute {label_suffix} Texture");
         let texture_descriptor = wgpu::TextureDescriptor {
             label: Some(&label),
-            size: wgpu::Extent3d {
-                width: config.width,
-                height: config.height,
-                depth_or_array_layers: 1,
-            },
+            size: extent,
             mip_level_count: 1,
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
@@ -488,26 +547,37 @@ impl RenderCore {
         if self.size.width > 0 && self.size.height > 0 {
             self.config.width = self.size.width;
             self.config.height = self.size.height;
-            self.surface.configure(&self.device, &self.config);
+            match &mut self.output {
+                RenderOutput::Window { surface, .. } => {
+                    surface.configure(&self.device, &self.config)
+                }
+                #[cfg(feature = "testing")]
+                RenderOutput::Headless { texture } => {
+                    texture.destroy();
+                    *texture = Self::create_headless_target(&self.device, &self.config);
+                }
+            }
             self.rebuild_pass_targets();
         }
     }
 
     pub(crate) fn recreate_surface(&mut self) {
-        let surface = match self.instance.create_surface(self.window.clone()) {
-            Ok(surface) => surface,
-            Err(err) => {
-                error!("Failed to recreate surface: {err:?}");
-                return;
-            }
-        };
-        self.surface = surface;
-        if self.size.width > 0 && self.size.height > 0 {
-            self.config.width = self.size.width;
-            self.config.height = self.size.height;
-            self.surface.configure(&self.device, &self.config);
-            self.rebuild_pass_targets();
+        match &mut self.output {
+            RenderOutput::Window {
+                window,
+                instance,
+                surface,
+            } => match instance.create_surface(window.clone()) {
+                Ok(new_surface) => *surface = new_surface,
+                Err(err) => {
+                    error!("Failed to recreate surface: {err:?}");
+                    return;
+                }
+            },
+            #[cfg(feature = "testing")]
+            RenderOutput::Headless { .. } => return,
         }
+        self.resize_surface();
     }
 
     pub(crate) fn rebuild_pass_targets(&mut self) {
@@ -517,18 +587,25 @@ impl RenderCore {
         self.compute.target_a.texture().destroy();
         self.compute.target_b.texture().destroy();
 
-        self.targets.offscreen = Self::create_pass_target(&self.device, &self.config, "Offscreen");
+        let scene_extent = scaled_extent(
+            self.config.width,
+            self.config.height,
+            self.targets.render_scale,
+        );
+        self.targets.scene_extent = scene_extent;
+        self.targets.offscreen =
+            Self::create_pass_target(&self.device, &self.config, scene_extent, "Offscreen");
         self.targets.offscreen_copy =
-            Self::create_pass_target(&self.device, &self.config, "Offscreen Copy");
+            Self::create_pass_target(&self.device, &self.config, scene_extent, "Offscreen Copy");
         self.compute.target_a = Self::create_compute_pass_target(
             &self.device,
-            &self.config,
+            scene_extent,
             TextureFormat::Rgba8Unorm,
             "Compute A",
         );
         self.compute.target_b = Self::create_compute_pass_target(
             &self.device,
-            &self.config,
+            scene_extent,
             TextureFormat::Rgba8Unorm,
             "Compute B",
         );
@@ -537,8 +614,12 @@ impl RenderCore {
             if let Some(t) = self.targets.msaa_texture.take() {
                 t.destroy();
             }
-            let (msaa_texture, msaa_view) =
-                Self::make_msaa_resources(&self.device, self.targets.sample_count, &self.config);
+            let (msaa_texture, msaa_view) = Self::make_msaa_resources(
+                &self.device,
+                self.targets.sample_count,
+                &self.config,
+                scene_extent,
+            );
             self.targets.msaa_texture = msaa_texture;
             self.targets.msaa_view = msaa_view;
         }
@@ -0,0 +1,246 @@
+//! Startup splash frame drawn by the render core.
+//!
+//! The splash is presented right after the surface is configured, before
+//! render modules register their pipelines. The logo pipeline is created for
+//! this one frame and dropped afterwards.
+
+use wgpu::util::DeviceExt;
+
+use crate::{
+    Px, PxSize,
+    renderer::{SplashConfig, SplashLogo},
+};
+
+use super::RenderCore;
+
+/// Fraction of the window the logo may cover in each direction.
+const MAX_LOGO_FRACTION: f32 = 0.5;
+
+impl RenderCore {
+    /// Presents a frame with the splash background and logo.
+    pub(crate) fn render_splash(&mut self, splash: &SplashConfig) {
+        let Some((output_frame, reconfigure_after_present)) = self.acquire_output_frame() else {
+            return;
+        };
+        let output_view = output_frame
+            .texture()
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        let target_size = PxSize::new(Px(self.config.width as i32), Px(self.config.height as i32));
+        let logo = splash.logo.as_ref().map(|logo| {
+            let rect = logo_rect(logo, target_size, self.output.scale_factor() as f32);
+            LogoDraw::new(&self.device, &self.queue, self.config.format, logo, rect)
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Splash Encoder"),
+            });
+        {
+            let background = splash.background;
+            let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Splash Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &output_view,
+                    resolve_target: None,
+                    depth_slice: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color {
+                            r: background.r as f64,
+                            g: background.g as f64,
+                            b: background.b as f64,
+                            a: background.a as f64,
+                        }),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                ..Default::default()
+            });
+            if let Some(logo) = &logo {
+                rpass.set_pipeline(&logo.pipeline);
+                rpass.set_bind_group(0, &logo.bind_group, &[]);
+                rpass.set_vertex_buffer(0, logo.rect.slice(..));
+                rpass.draw(0..4, 0..1);
+            }
+        }
+        self.queue.submit(Some(encoder.finish()));
+        output_frame.present();
+        if reconfigure_after_present {
+            self.resize_surface();
+        }
+    }
+}
+
+/// GPU resources for drawing the logo once.
+struct LogoDraw {
+    pipeline: wgpu::RenderPipeline,
+    bind_group: wgpu::BindGroup,
+    rect: wgpu::Buffer,
+}
+
+impl LogoDraw {
+    fn new(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        format: wgpu::TextureFormat,
+        logo: &SplashLogo,
+        rect: [f32; 4],
+    ) -> Self {
+        let size = wgpu::Extent3d {
+            width: logo.width.max(1),
+            height: logo.height.max(1),
+            depth_or_array_layers: 1,
+        };
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Splash Logo"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        if logo.width > 0 && logo.height > 0 {
+            queue.write_texture(
+                wgpu::TexelCopyTextureInfo {
+                    texture: &texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d::ZERO,
+                    aspect: wgpu::TextureAspect::All,
+                },
+                &logo.rgba,
+                wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(4 * logo.width),
+                    rows_per_image: Some(logo.height),
+                },
+                size,
+            );
+        }
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Splash Logo Sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Splash Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Splash Bind Group"),
+            layout: &bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&sampler),
+                },
+            ],
+        });
+
+        let shader = device.create_shader_module(wgpu::include_wgsl!("../shaders/splash.wgsl"));
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Splash Pipeline Layout"),
+            bind_group_layouts: &[Some(&bind_group_layout)],
+            immediate_size: 0,
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Splash Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<[f32; 4]>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &wgpu::vertex_attr_array![0 => Float32x4],
+                }],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleStrip,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview_mask: None,
+            cache: None,
+        });
+        let rect = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Splash Logo Rect"),
+            contents: bytemuck::cast_slice(&rect),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        Self {
+            pipeline,
+            bind_group,
+            rect,
+        }
+    }
+}
+
+/// Returns the centered logo corners in clip space: x0, y0, x1, y1.
+fn logo_rect(logo: &SplashLogo, target_size: PxSize, scale_factor: f32) -> [f32; 4] {
+    let target_width = target_size.width.0.max(1) as f32;
+    let target_height = target_size.height.0.max(1) as f32;
+    let width = logo.width as f32 * scale_factor;
+    let height = logo.height as f32 * scale_factor;
+    let fit = (target_width * MAX_LOGO_FRACTION / width.max(1.0))
+        .min(target_height * MAX_LOGO_FRACTION / height.max(1.0))
+        .min(1.0);
+    // Half extents in clip space, where the window spans -1..1.
+    let half_width = width * fit / target_width;
+    let half_height = height * fit / target_height;
+    [-half_width, half_height, half_width, -half_height]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn logo_rect_scales_and_fits_half_the_window() {
+        let logo = SplashLogo::from_rgba(vec![0; 100 * 50 * 4], 100, 50);
+        let rect = logo_rect(&logo, PxSize::new(Px(1000), Px(1000)), 2.0);
+        assert_eq!(rect, [-0.2, 0.1, 0.2, -0.1]);
+
+        let rect = logo_rect(&logo, PxSize::new(Px(200), Px(1000)), 1.0);
+        assert_eq!(rect, [-0.5, 0.05, 0.5, -0.05]);
+    }
+}
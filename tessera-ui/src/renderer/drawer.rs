@@ -4,12 +4,36 @@
 //! system, handling graphics pipeline registration and command dispatch.
 
 pub mod command;
+pub mod instancing;
+pub mod paint;
+pub mod path;
 pub mod pipeline;
+pub mod pool;
+pub mod sdf;
+pub mod text;
+pub mod texture;
 
 use crate::{PxPosition, px::PxSize};
 
 pub use command::DrawCommand;
-pub use pipeline::{DrawablePipeline, ErasedDrawContext, PipelineRegistry};
+pub use instancing::{InstanceBuffer, InstanceRegion, InstancedDrawCommand};
+pub use paint::{
+    GradientStop, ImagePaint, LinearGradient, Paint, PaintUniform, RadialGradient, SweepGradient,
+    TileMode,
+};
+pub use path::{Path, PathBuilder, PathCommand, PathStyle, StrokeStyle};
+pub use pipeline::{
+    CLIP_STENCIL_FORMAT, DrawablePipeline, ErasedDrawContext, PipelineRegistry,
+    clip_depth_stencil_state,
+};
+pub use pool::{TransientBufferPool, TransientTexturePool};
+pub use sdf::{SdfShape, SdfShapeCommand};
+pub use text::{
+    AttributedString, FontFamily, FontFeature, FontRegistry, FontSettings, FontVariation,
+    FontWeight, Hyphens, LineBreaking, PlaceholderAlignment, SpanStyle, TextCommand,
+    TextConstraint, TextData, TextMeasureInfo, TextOverflow, font_registry,
+};
+pub use texture::{TextureAlphaMode, TextureCommand};
 
 /// Drawer manages graphics pipelines and processes draw commands.
 ///
@@ -35,6 +59,47 @@ impl Drawer {
         }
     }
 
+    /// Registers the pipelines for draw commands defined by this crate, such
+    /// as [`PathCommand`], [`SdfShapeCommand`], [`TextCommand`] and
+    /// [`TextureCommand`].
+    ///
+    /// Render modules registering a pipeline for the same command type replace
+    /// the built-in one.
+    pub(crate) fn register_builtin_pipelines(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        format: wgpu::TextureFormat,
+        sample_count: u32,
+        pipeline_cache: Option<&wgpu::PipelineCache>,
+    ) {
+        self.pipeline_registry.register(path::PathPipeline::new(
+            device,
+            format,
+            sample_count,
+            pipeline_cache,
+        ));
+        self.pipeline_registry.register(sdf::SdfShapePipeline::new(
+            device,
+            format,
+            sample_count,
+            pipeline_cache,
+        ));
+        self.pipeline_registry.register(text::TextPipeline::new(
+            device,
+            queue,
+            format,
+            sample_count,
+        ));
+        self.pipeline_registry
+            .register(texture::TexturePipeline::new(
+                device,
+                format,
+                sample_count,
+                pipeline_cache,
+            ));
+    }
+
     /// Initialize all pipelines at the beginning of each render pass.
     ///
     /// This method calls the `begin_pass` method on all registered pipelines,
@@ -70,6 +70,12 @@ pub trait DrawCommand: DynClone + Downcast + Send + Sync {
     fn ordering_rect(&self, _position: PxPosition, _size: PxSize) -> Option<PxRect> {
         None
     }
+
+    /// Returns the type name of the command, used by debug tooling such as the
+    /// command trace.
+    fn command_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
 }
 
 impl_downcast!(DrawCommand);
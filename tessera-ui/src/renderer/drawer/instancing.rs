@@ -0,0 +1,246 @@
+//! Instanced submission helpers for draw pipelines.
+//!
+//! Pipelines that render many homogeneous commands (for example hundreds of
+//! identical list item backgrounds) can describe each command as a small
+//! per-instance record and submit the whole batch with a single instanced draw
+//! call, instead of updating uniforms per command.
+//!
+//! # Usage
+//!
+//! 1. Implement [`InstancedDrawCommand`] for the command type.
+//! 2. Keep an [`InstanceBuffer`] in the pipeline and call
+//!    [`InstanceBuffer::reset`] from
+//!    [`DrawablePipeline::begin_frame`](super::DrawablePipeline::begin_frame).
+//! 3. In [`DrawablePipeline::draw`](super::DrawablePipeline::draw), call
+//!    [`DrawContext::bind_instances`] and issue one draw for the returned
+//!    instance range.
+
+use std::{marker::PhantomData, ops::Range};
+
+use bytemuck::Pod;
+
+use crate::{
+    px::{PxPosition, PxSize},
+    renderer::drawer::{DrawCommand, pipeline::DrawContext},
+};
+
+/// A draw command that can be rendered as one instance of a shared mesh.
+///
+/// # Example
+///
+/// ```
+/// use tessera_ui::{DrawCommand, PxPosition, PxSize, renderer::drawer::InstancedDrawCommand};
+///
+/// #[derive(Clone, PartialEq)]
+/// struct DotCommand {
+///     color: [f32; 4],
+/// }
+///
+/// impl DrawCommand for DotCommand {
+///     fn apply_opacity(&mut self, opacity: f32) {
+///         self.color[3] *= opacity.clamp(0.0, 1.0);
+///     }
+/// }
+///
+/// #[repr(C)]
+/// #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+/// struct DotInstance {
+///     rect: [f32; 4],
+///     color: [f32; 4],
+/// }
+///
+/// impl InstancedDrawCommand for DotCommand {
+///     type Instance = DotInstance;
+///
+///     fn instance(&self, size: PxSize, position: PxPosition, _target: PxSize) -> DotInstance {
+///         DotInstance {
+///             rect: [
+///                 position.x.to_f32(),
+///                 position.y.to_f32(),
+///                 size.width.to_f32(),
+///                 size.height.to_f32(),
+///             ],
+///             color: self.color,
+///         }
+///     }
+/// }
+/// ```
+pub trait InstancedDrawCommand: DrawCommand {
+    /// Per-instance record uploaded to the instance vertex buffer.
+    ///
+    /// The size of this type must be a multiple of 4 bytes.
+    type Instance: Pod;
+
+    /// Builds the instance record for this command.
+    ///
+    /// `size` and `position` describe the command placement and
+    /// `target_size` is the size of the current render target.
+    fn instance(&self, size: PxSize, position: PxPosition, target_size: PxSize) -> Self::Instance;
+}
+
+/// A growable vertex buffer shared by all instanced batches of a pipeline.
+///
+/// Each batch uploaded during a frame is written to its own region of the
+/// buffer, so batches recorded earlier in the frame are never overwritten by
+/// later ones. Call [`InstanceBuffer::reset`] once per frame to reuse the
+/// buffer from the start.
+pub struct InstanceBuffer<I: Pod> {
+    label: &'static str,
+    buffer: Option<wgpu::Buffer>,
+    capacity: u64,
+    cursor: u64,
+    _marker: PhantomData<I>,
+}
+
+impl<I: Pod> InstanceBuffer<I> {
+    const MIN_CAPACITY: u64 = 4096;
+
+    /// Creates an empty instance buffer. GPU memory is allocated on first
+    /// upload.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of `I` is zero or not a multiple of 4 bytes.
+    pub fn new(label: &'static str) -> Self {
+        let stride = std::mem::size_of::<I>() as u64;
+        assert!(
+            stride > 0 && stride.is_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT),
+            "instance size must be a non-zero multiple of {} bytes",
+            wgpu::COPY_BUFFER_ALIGNMENT
+        );
+        Self {
+            label,
+            buffer: None,
+            capacity: 0,
+            cursor: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Size of one instance record in bytes.
+    pub fn stride() -> wgpu::BufferAddress {
+        std::mem::size_of::<I>() as wgpu::BufferAddress
+    }
+
+    /// Starts a new frame, making the whole buffer available again.
+    pub fn reset(&mut self) {
+        self.cursor = 0;
+    }
+
+    /// Uploads `instances` and returns the region they occupy.
+    ///
+    /// Returns `None` when `instances` is empty.
+    pub fn upload(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        instances: &[I],
+    ) -> Option<InstanceRegion> {
+        if instances.is_empty() {
+            return None;
+        }
+        let bytes: &[u8] = bytemuck::cast_slice(instances);
+        let len = bytes.len() as u64;
+        if self.buffer.is_none() || self.cursor + len > self.capacity {
+            // Regions handed out earlier this frame stay valid because the
+            // render pass keeps the previous buffer alive.
+            let capacity = next_capacity(self.capacity, self.cursor + len, Self::MIN_CAPACITY);
+            self.buffer = Some(device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(self.label),
+                size: capacity,
+                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }));
+            self.capacity = capacity;
+            self.cursor = 0;
+        }
+        let buffer = self.buffer.as_ref()?;
+        let offset = self.cursor;
+        queue.write_buffer(buffer, offset, bytes);
+        self.cursor = align_to(offset + len, wgpu::COPY_BUFFER_ALIGNMENT);
+        Some(InstanceRegion {
+            offset,
+            len,
+            count: instances.len() as u32,
+        })
+    }
+
+    /// Returns the buffer slice for a region returned by
+    /// [`InstanceBuffer::upload`].
+    pub fn slice(&self, region: &InstanceRegion) -> Option<wgpu::BufferSlice<'_>> {
+        self.buffer
+            .as_ref()
+            .map(|buffer| buffer.slice(region.offset..region.offset + region.len))
+    }
+}
+
+/// A region of an [`InstanceBuffer`] written during the current frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InstanceRegion {
+    offset: wgpu::BufferAddress,
+    len: wgpu::BufferAddress,
+    count: u32,
+}
+
+impl InstanceRegion {
+    /// Instance range to pass to `draw` or `draw_indexed`.
+    pub fn instances(&self) -> Range<u32> {
+        0..self.count
+    }
+}
+
+impl<T: InstancedDrawCommand> DrawContext<'_, '_, '_, T> {
+    /// Collects the instance records for every command in the batch.
+    pub fn instances(&self) -> Vec<T::Instance> {
+        self.commands
+            .iter()
+            .map(|(command, size, position)| command.instance(*size, *position, self.target_size))
+            .collect()
+    }
+
+    /// Uploads the batch into `buffer` and binds it to vertex buffer `slot`.
+    ///
+    /// Returns the instance range to draw, or `None` if the batch is empty.
+    pub fn bind_instances(
+        &mut self,
+        buffer: &mut InstanceBuffer<T::Instance>,
+        slot: u32,
+    ) -> Option<Range<u32>> {
+        let instances = self.instances();
+        let region = buffer.upload(self.device, self.queue, &instances)?;
+        let slice = buffer.slice(&region)?;
+        self.render_pass.set_vertex_buffer(slot, slice);
+        Some(region.instances())
+    }
+}
+
+fn align_to(value: u64, alignment: u64) -> u64 {
+    value.div_ceil(alignment) * alignment
+}
+
+fn next_capacity(current: u64, required: u64, minimum: u64) -> u64 {
+    let mut capacity = current.max(minimum);
+    while capacity < required {
+        capacity = capacity.saturating_mul(2);
+    }
+    capacity
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_capacity_doubles_until_request_fits() {
+        assert_eq!(next_capacity(0, 10, 4096), 4096);
+        assert_eq!(next_capacity(4096, 5000, 4096), 8192);
+        assert_eq!(next_capacity(4096, 40_000, 4096), 65_536);
+    }
+
+    #[test]
+    fn align_to_rounds_up_to_copy_alignment() {
+        assert_eq!(align_to(0, 4), 0);
+        assert_eq!(align_to(5, 4), 8);
+        assert_eq!(align_to(8, 4), 8);
+    }
+}
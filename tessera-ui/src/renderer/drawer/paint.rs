@@ -0,0 +1,396 @@
+//! Paint descriptions shared by draw commands.
+//!
+//! A [`Paint`] describes how the covered area of a draw command is filled:
+//! with a solid color, a linear/radial/sweep gradient, or an image pattern.
+//! Command types store a `Paint` instead of a plain color. Pipelines upload the
+//! [`PaintUniform`] encoding of non-solid paints to a separate storage array
+//! indexed by the painted instances, so solid fills keep their instance size.
+//!
+//! # Shader side
+//!
+//! [`PAINT_WGSL`] contains the matching `Paint` struct and the `paint_color`
+//! and `paint_image_uv` helpers. Prepend it to a pipeline shader to evaluate
+//! paints without reimplementing gradient math:
+//!
+//! ```
+//! use tessera_ui::renderer::drawer::paint::PAINT_WGSL;
+//!
+//! let source = format!("{PAINT_WGSL}\n{}", "/* pipeline shader */");
+//! assert!(source.contains("fn paint_color"));
+//! ```
+//!
+//! Gradient geometry is expressed in unit space of the command bounds: `(0,
+//! 0)` is the top-left corner and `(1, 1)` the bottom-right corner. Radial
+//! radii are relative to the shorter side of the bounds.
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::Color;
+
+/// Shared WGSL definitions for evaluating [`PaintUniform`] data.
+pub const PAINT_WGSL: &str = include_str!("../shaders/paint.wgsl");
+
+/// Maximum number of gradient stops encoded in a [`PaintUniform`].
+///
+/// Extra stops are dropped when encoding.
+pub const MAX_GRADIENT_STOPS: usize = 8;
+
+/// How a gradient or image pattern behaves outside of its defined range.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TileMode {
+    /// Extends the edge colors.
+    #[default]
+    Clamp,
+    /// Repeats the pattern.
+    Repeat,
+    /// Repeats the pattern, mirroring every other repetition.
+    Mirror,
+    /// Leaves the area outside the range transparent.
+    Decal,
+}
+
+impl TileMode {
+    fn code(self) -> f32 {
+        match self {
+            Self::Clamp => 0.0,
+            Self::Repeat => 1.0,
+            Self::Mirror => 2.0,
+            Self::Decal => 3.0,
+        }
+    }
+}
+
+/// A color at a position along a gradient.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GradientStop {
+    /// Position of the stop in `0.0..=1.0`.
+    pub offset: f32,
+    /// Color at the stop.
+    pub color: Color,
+}
+
+impl GradientStop {
+    /// Creates a gradient stop.
+    pub const fn new(offset: f32, color: Color) -> Self {
+        Self { offset, color }
+    }
+}
+
+/// A gradient along the line from `start` to `end`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearGradient {
+    /// Start point in unit space.
+    pub start: [f32; 2],
+    /// End point in unit space.
+    pub end: [f32; 2],
+    /// Color stops, ordered by offset.
+    pub stops: Vec<GradientStop>,
+    /// Behavior outside of `start..end`.
+    pub tile_mode: TileMode,
+}
+
+/// A gradient radiating from `center`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RadialGradient {
+    /// Center point in unit space.
+    pub center: [f32; 2],
+    /// Radius relative to the shorter side of the bounds.
+    pub radius: f32,
+    /// Color stops, ordered by offset.
+    pub stops: Vec<GradientStop>,
+    /// Behavior outside of the radius.
+    pub tile_mode: TileMode,
+}
+
+/// A gradient sweeping clockwise around `center`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepGradient {
+    /// Center point in unit space.
+    pub center: [f32; 2],
+    /// Angle in degrees where the gradient starts, measured clockwise from
+    /// the positive x axis.
+    pub start_angle: f32,
+    /// Angle in degrees where the gradient ends.
+    pub end_angle: f32,
+    /// Color stops, ordered by offset.
+    pub stops: Vec<GradientStop>,
+    /// Behavior outside of `start_angle..end_angle`.
+    pub tile_mode: TileMode,
+}
+
+/// An image repeated or stretched over the command bounds.
+///
+/// Pipelines that support image paints bind [`ImagePaint::view`] themselves
+/// and sample it at `paint_image_uv`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImagePaint {
+    /// Texture sampled by the pattern.
+    pub view: wgpu::TextureView,
+    /// Top-left corner of one image tile in unit space.
+    pub offset: [f32; 2],
+    /// Size of one image tile in unit space.
+    pub scale: [f32; 2],
+    /// Behavior outside of the first tile.
+    pub tile_mode: TileMode,
+    /// Color multiplied with the sampled texels.
+    pub tint: Color,
+}
+
+/// Describes how a draw command fills the area it covers.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Paint {
+    /// A single color.
+    Solid(Color),
+    /// A linear gradient.
+    LinearGradient(LinearGradient),
+    /// A radial gradient.
+    RadialGradient(RadialGradient),
+    /// A sweep (conic) gradient.
+    SweepGradient(SweepGradient),
+    /// An image pattern.
+    Image(ImagePaint),
+}
+
+impl Default for Paint {
+    fn default() -> Self {
+        Self::Solid(Color::TRANSPARENT)
+    }
+}
+
+impl From<Color> for Paint {
+    fn from(color: Color) -> Self {
+        Self::Solid(color)
+    }
+}
+
+impl Paint {
+    /// Creates a linear gradient from evenly spaced colors.
+    pub fn linear(start: [f32; 2], end: [f32; 2], colors: &[Color]) -> Self {
+        Self::LinearGradient(LinearGradient {
+            start,
+            end,
+            stops: evenly_spaced_stops(colors),
+            tile_mode: TileMode::Clamp,
+        })
+    }
+
+    /// Creates a top-to-bottom gradient from evenly spaced colors.
+    pub fn vertical(colors: &[Color]) -> Self {
+        Self::linear([0.5, 0.0], [0.5, 1.0], colors)
+    }
+
+    /// Creates a left-to-right gradient from evenly spaced colors.
+    pub fn horizontal(colors: &[Color]) -> Self {
+        Self::linear([0.0, 0.5], [1.0, 0.5], colors)
+    }
+
+    /// Creates a radial gradient from evenly spaced colors.
+    pub fn radial(center: [f32; 2], radius: f32, colors: &[Color]) -> Self {
+        Self::RadialGradient(RadialGradient {
+            center,
+            radius,
+            stops: evenly_spaced_stops(colors),
+            tile_mode: TileMode::Clamp,
+        })
+    }
+
+    /// Creates a full-turn sweep gradient from evenly spaced colors.
+    pub fn sweep(center: [f32; 2], colors: &[Color]) -> Self {
+        Self::SweepGradient(SweepGradient {
+            center,
+            start_angle: 0.0,
+            end_angle: 360.0,
+            stops: evenly_spaced_stops(colors),
+            tile_mode: TileMode::Clamp,
+        })
+    }
+
+    /// Returns `true` if the paint is a single color.
+    pub fn is_solid(&self) -> bool {
+        matches!(self, Self::Solid(_))
+    }
+
+    /// Returns the texture of an image paint.
+    pub fn image_view(&self) -> Option<&wgpu::TextureView> {
+        match self {
+            Self::Image(image) => Some(&image.view),
+            _ => None,
+        }
+    }
+
+    /// Multiplies every color of the paint by `opacity`.
+    ///
+    /// Call this from
+    /// [`DrawCommand::apply_opacity`](super::DrawCommand::apply_opacity).
+    pub fn apply_opacity(&mut self, opacity: f32) {
+        let factor = opacity.clamp(0.0, 1.0);
+        let scale = |color: &mut Color| *color = color.with_alpha(color.a * factor);
+        match self {
+            Self::Solid(color) => scale(color),
+            Self::LinearGradient(LinearGradient { stops, .. })
+            | Self::RadialGradient(RadialGradient { stops, .. })
+            | Self::SweepGradient(SweepGradient { stops, .. }) => {
+                stops.iter_mut().for_each(|stop| scale(&mut stop.color));
+            }
+            Self::Image(image) => scale(&mut image.tint),
+        }
+    }
+
+    /// Encodes the paint for upload to the GPU.
+    pub fn uniform(&self) -> PaintUniform {
+        let mut uniform = PaintUniform::zeroed();
+        match self {
+            Self::Solid(color) => {
+                uniform.header = [PaintUniform::KIND_SOLID, 0.0, 0.0, 0.0];
+                uniform.color = color.to_array();
+            }
+            Self::LinearGradient(gradient) => {
+                uniform.geometry = [
+                    gradient.start[0],
+                    gradient.start[1],
+                    gradient.end[0],
+                    gradient.end[1],
+                ];
+                uniform.write_stops(
+                    PaintUniform::KIND_LINEAR,
+                    &gradient.stops,
+                    gradient.tile_mode,
+                );
+            }
+            Self::RadialGradient(gradient) => {
+                uniform.geometry = [gradient.center[0], gradient.center[1], gradient.radius, 0.0];
+                uniform.write_stops(
+                    PaintUniform::KIND_RADIAL,
+                    &gradient.stops,
+                    gradient.tile_mode,
+                );
+            }
+            Self::SweepGradient(gradient) => {
+                uniform.geometry = [
+                    gradient.center[0],
+                    gradient.center[1],
+                    gradient.start_angle.to_radians(),
+                    gradient.end_angle.to_radians(),
+                ];
+                uniform.write_stops(
+                    PaintUniform::KIND_SWEEP,
+                    &gradient.stops,
+                    gradient.tile_mode,
+                );
+            }
+            Self::Image(image) => {
+                uniform.header = [PaintUniform::KIND_IMAGE, 0.0, image.tile_mode.code(), 0.0];
+                uniform.geometry = [
+                    image.offset[0],
+                    image.offset[1],
+                    image.scale[0],
+                    image.scale[1],
+                ];
+                uniform.color = image.tint.to_array();
+            }
+        }
+        uniform
+    }
+}
+
+/// GPU encoding of a [`Paint`], matching the `Paint` struct in
+/// [`PAINT_WGSL`].
+///
+/// Every field is a `vec4<f32>`, so the layout is identical in uniform and
+/// storage buffers.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+pub struct PaintUniform {
+    /// Kind, stop count, tile mode and padding.
+    pub header: [f32; 4],
+    /// Kind-specific geometry in unit space.
+    pub geometry: [f32; 4],
+    /// Solid color or image tint.
+    pub color: [f32; 4],
+    /// Stop offsets, four per vector.
+    pub offsets: [[f32; 4]; MAX_GRADIENT_STOPS / 4],
+    /// Stop colors.
+    pub colors: [[f32; 4]; MAX_GRADIENT_STOPS],
+}
+
+impl PaintUniform {
+    const KIND_SOLID: f32 = 0.0;
+    const KIND_LINEAR: f32 = 1.0;
+    const KIND_RADIAL: f32 = 2.0;
+    const KIND_SWEEP: f32 = 3.0;
+    const KIND_IMAGE: f32 = 4.0;
+
+    fn write_stops(&mut self, kind: f32, stops: &[GradientStop], tile_mode: TileMode) {
+        let count = stops.len().min(MAX_GRADIENT_STOPS);
+        let mut previous = 0.0_f32;
+        for (index, stop) in stops.iter().take(count).enumerate() {
+            // Offsets must be monotonic for the shader's segment search.
+            let offset = stop.offset.clamp(previous, 1.0);
+            previous = offset;
+            self.offsets[index / 4][index % 4] = offset;
+            self.colors[index] = stop.color.to_array();
+        }
+        self.header = [kind, count as f32, tile_mode.code(), 0.0];
+    }
+}
+
+impl Default for PaintUniform {
+    fn default() -> Self {
+        Paint::default().uniform()
+    }
+}
+
+fn evenly_spaced_stops(colors: &[Color]) -> Vec<GradientStop> {
+    let last = colors.len().saturating_sub(1).max(1) as f32;
+    colors
+        .iter()
+        .enumerate()
+        .map(|(index, color)| GradientStop::new(index as f32 / last, *color))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evenly_spaced_stops_cover_unit_range() {
+        let stops = evenly_spaced_stops(&[Color::RED, Color::GREEN, Color::BLUE]);
+        let offsets: Vec<f32> = stops.iter().map(|stop| stop.offset).collect();
+        assert_eq!(offsets, vec![0.0, 0.5, 1.0]);
+        assert_eq!(evenly_spaced_stops(&[Color::RED])[0].offset, 0.0);
+    }
+
+    #[test]
+    fn uniform_clamps_stop_count_and_keeps_offsets_monotonic() {
+        let stops = (0..12)
+            .map(|index| {
+                GradientStop::new(
+                    if index == 3 { 0.0 } else { 0.1 * index as f32 },
+                    Color::WHITE,
+                )
+            })
+            .collect();
+        let paint = Paint::LinearGradient(LinearGradient {
+            start: [0.0, 0.0],
+            end: [1.0, 0.0],
+            stops,
+            tile_mode: TileMode::Repeat,
+        });
+
+        let uniform = paint.uniform();
+        assert_eq!(uniform.header[1], MAX_GRADIENT_STOPS as f32);
+        assert_eq!(uniform.header[2], TileMode::Repeat.code());
+        assert_eq!(uniform.offsets[0][3], uniform.offsets[0][2]);
+    }
+
+    #[test]
+    fn apply_opacity_scales_gradient_stops() {
+        let mut paint = Paint::horizontal(&[Color::RED, Color::BLUE]);
+        paint.apply_opacity(0.5);
+        let Paint::LinearGradient(gradient) = paint else {
+            panic!("expected a linear gradient");
+        };
+        assert!(gradient.stops.iter().all(|stop| stop.color.a == 0.5));
+    }
+}
@@ -0,0 +1,317 @@
+use std::{borrow::Cow, collections::HashMap};
+
+use bytemuck::{Pod, Zeroable};
+use lyon_tessellation::{
+    BuffersBuilder, FillOptions, FillTessellator, FillVertex, StrokeOptions, StrokeTessellator,
+    StrokeVertex, VertexBuffers,
+};
+use wgpu::util::DeviceExt;
+
+use crate::renderer::drawer::{
+    TransientBufferPool, clip_depth_stencil_state,
+    paint::{PAINT_WGSL, PaintUniform},
+    pipeline::{DrawContext, DrawablePipeline, FrameContext},
+    pool::DEFAULT_MAX_IDLE_FRAMES,
+};
+
+use super::{FillRule, LineCap, LineJoin, Path, PathCommand, PathStyle, StrokeStyle};
+
+/// Maximum distance between a curve and its flattened approximation, in
+/// pixels.
+const TESSELLATION_TOLERANCE: f32 = 0.25;
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct PathVertex {
+    position: [f32; 2],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct PathUniforms {
+    rect: [f32; 4],
+    screen_size: [f32; 4],
+    paint: PaintUniform,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct MeshKey {
+    path: u64,
+    style: [u32; 4],
+}
+
+struct PathMesh {
+    vertices: Option<wgpu::Buffer>,
+    indices: Option<wgpu::Buffer>,
+    index_count: u32,
+    last_used_frame: u64,
+}
+
+/// Renders [`PathCommand`]s from cached tessellated meshes.
+pub(crate) struct PathPipeline {
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    buffer_pool: TransientBufferPool,
+    meshes: HashMap<MeshKey, PathMesh>,
+    fill_tessellator: FillTessellator,
+    stroke_tessellator: StrokeTessellator,
+    frame: u64,
+}
+
+impl PathPipeline {
+    pub(crate) fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        sample_count: u32,
+        pipeline_cache: Option<&wgpu::PipelineCache>,
+    ) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Path Shader"),
+            source: wgpu::ShaderSource::Wgsl(Cow::Owned(format!(
+                "{PAINT_WGSL}\n{}",
+                include_str!("../../shaders/path.wgsl")
+            ))),
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Path Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Storage { read_only: true },
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Path Pipeline Layout"),
+            bind_group_layouts: &[Some(&bind_group_layout)],
+            immediate_size: 0,
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Path Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<PathVertex>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Vertex,
+                    attributes: &wgpu::vertex_attr_array![0 => Float32x2],
+                }],
+                compilation_options: Default::default(),
+            },
+            // Tessellated meshes mix windings, so culling stays disabled.
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: Some(clip_depth_stencil_state()),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: Default::default(),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            multiview_mask: None,
+            cache: pipeline_cache,
+        });
+
+        Self {
+            pipeline,
+            bind_group_layout,
+            buffer_pool: TransientBufferPool::new("Path Uniform Buffer"),
+            meshes: HashMap::new(),
+            fill_tessellator: FillTessellator::new(),
+            stroke_tessellator: StrokeTessellator::new(),
+            frame: 0,
+        }
+    }
+
+    fn prepare_mesh(&mut self, device: &wgpu::Device, path: &Path, style: &PathStyle) -> MeshKey {
+        let key = MeshKey {
+            path: path.content_hash(),
+            style: style.cache_key(),
+        };
+        let frame = self.frame;
+        if let Some(mesh) = self.meshes.get_mut(&key) {
+            mesh.last_used_frame = frame;
+            return key;
+        }
+
+        let geometry = self.tessellate(path, style);
+        let (vertices, indices) = if geometry.indices.is_empty() {
+            (None, None)
+        } else {
+            (
+                Some(
+                    device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                        label: Some("Path Vertex Buffer"),
+                        contents: bytemuck::cast_slice(&geometry.vertices),
+                        usage: wgpu::BufferUsages::VERTEX,
+                    }),
+                ),
+                Some(
+                    device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                        label: Some("Path Index Buffer"),
+                        contents: bytemuck::cast_slice(&geometry.indices),
+                        usage: wgpu::BufferUsages::INDEX,
+                    }),
+                ),
+            )
+        };
+        self.meshes.insert(
+            key,
+            PathMesh {
+                vertices,
+                indices,
+                index_count: geometry.indices.len() as u32,
+                last_used_frame: frame,
+            },
+        );
+        key
+    }
+
+    fn tessellate(&mut self, path: &Path, style: &PathStyle) -> VertexBuffers<PathVertex, u32> {
+        let mut geometry = VertexBuffers::new();
+        let result = match style {
+            PathStyle::Fill(rule) => self.fill_tessellator.tessellate_path(
+                path.lyon(),
+                &fill_options(*rule),
+                &mut BuffersBuilder::new(&mut geometry, |vertex: FillVertex| PathVertex {
+                    position: vertex.position().to_array(),
+                }),
+            ),
+            PathStyle::Stroke(stroke) => self.stroke_tessellator.tessellate_path(
+                path.lyon(),
+                &stroke_options(stroke),
+                &mut BuffersBuilder::new(&mut geometry, |vertex: StrokeVertex| PathVertex {
+                    position: vertex.position().to_array(),
+                }),
+            ),
+        };
+        if let Err(err) = result {
+            tracing::warn!("Failed to tessellate path: {err:?}");
+            geometry.vertices.clear();
+            geometry.indices.clear();
+        }
+        geometry
+    }
+}
+
+fn fill_options(rule: FillRule) -> FillOptions {
+    let rule = match rule {
+        FillRule::NonZero => lyon_tessellation::FillRule::NonZero,
+        FillRule::EvenOdd => lyon_tessellation::FillRule::EvenOdd,
+    };
+    FillOptions::tolerance(TESSELLATION_TOLERANCE).with_fill_rule(rule)
+}
+
+fn stroke_options(stroke: &StrokeStyle) -> StrokeOptions {
+    let cap = match stroke.cap {
+        LineCap::Butt => lyon_tessellation::LineCap::Butt,
+        LineCap::Round => lyon_tessellation::LineCap::Round,
+        LineCap::Square => lyon_tessellation::LineCap::Square,
+    };
+    let join = match stroke.join {
+        LineJoin::Miter => lyon_tessellation::LineJoin::Miter,
+        LineJoin::Round => lyon_tessellation::LineJoin::Round,
+        LineJoin::Bevel => lyon_tessellation::LineJoin::Bevel,
+    };
+    StrokeOptions::tolerance(TESSELLATION_TOLERANCE)
+        .with_line_width(stroke.width.max(0.0))
+        .with_line_cap(cap)
+        .with_line_join(join)
+        .with_miter_limit(stroke.miter_limit.max(StrokeOptions::MINIMUM_MITER_LIMIT))
+}
+
+impl DrawablePipeline<PathCommand> for PathPipeline {
+    fn begin_frame(&mut self, _context: &FrameContext<'_>) {
+        self.frame = self.frame.wrapping_add(1);
+        self.buffer_pool.begin_frame();
+        let frame = self.frame;
+        self.meshes.retain(|_, mesh| {
+            frame.saturating_sub(mesh.last_used_frame) <= DEFAULT_MAX_IDLE_FRAMES
+        });
+    }
+
+    fn draw(&mut self, context: &mut DrawContext<PathCommand>) {
+        if context.commands.is_empty() {
+            return;
+        }
+
+        let screen_size = [
+            context.target_size.width.to_f32(),
+            context.target_size.height.to_f32(),
+            0.0,
+            0.0,
+        ];
+        let mut uniforms = Vec::with_capacity(context.commands.len());
+        let mut keys = Vec::with_capacity(context.commands.len());
+        for (command, size, position) in context.commands.iter() {
+            keys.push(self.prepare_mesh(context.device, &command.path, &command.style));
+            uniforms.push(PathUniforms {
+                rect: [
+                    position.x.to_f32(),
+                    position.y.to_f32(),
+                    size.width.to_f32(),
+                    size.height.to_f32(),
+                ],
+                screen_size,
+                paint: command.paint.uniform(),
+            });
+        }
+
+        let content: &[u8] = bytemuck::cast_slice(&uniforms);
+        let buffer = self.buffer_pool.acquire(
+            context.device,
+            content.len() as u64,
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        );
+        context.queue.write_buffer(&buffer, 0, content);
+        let bind_group = context
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Path Bind Group"),
+                layout: &self.bind_group_layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                        buffer: &buffer,
+                        offset: 0,
+                        size: wgpu::BufferSize::new(content.len() as u64),
+                    }),
+                }],
+            });
+
+        context.render_pass.set_pipeline(&self.pipeline);
+        context.render_pass.set_bind_group(0, &bind_group, &[]);
+        for (instance, key) in keys.iter().enumerate() {
+            let Some(mesh) = self.meshes.get(key) else {
+                continue;
+            };
+            let (Some(vertices), Some(indices)) = (&mesh.vertices, &mesh.indices) else {
+                continue;
+            };
+            let instance = instance as u32;
+            context.render_pass.set_vertex_buffer(0, vertices.slice(..));
+            context
+                .render_pass
+                .set_index_buffer(indices.slice(..), wgpu::IndexFormat::Uint32);
+            context
+                .render_pass
+                .draw_indexed(0..mesh.index_count, 0, instance..instance + 1);
+        }
+    }
+}
@@ -94,6 +94,15 @@
 //! The `scene_texture_view` parameter provides access to the current scene
 //! texture, enabling effects that sample from the background or perform
 //! post-processing.
+//!
+//! ## Shaped Clipping
+//!
+//! Every draw pass carries a [`CLIP_STENCIL_FORMAT`] attachment that holds
+//! rounded, rotated and elliptical clips. Render pipelines used in draw passes
+//! must set [`clip_depth_stencil_state`] as their `depth_stencil` state so
+//! their output is masked by the active clip. Pipelines created with
+//! `depth_stencil: None` are incompatible with draw passes and fail wgpu
+//! validation.
 
 use std::{any::TypeId, collections::HashMap};
 
@@ -102,6 +111,34 @@ use crate::{
     renderer::DrawCommand,
 };
 
+/// Format of the clip stencil attachment bound to every draw pass.
+pub const CLIP_STENCIL_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Stencil8;
+
+/// Returns the depth-stencil state draw pipelines use to honor shaped clips.
+///
+/// Fragments pass only where the stencil value equals the current clip depth,
+/// which the renderer sets as the stencil reference before each batch.
+pub fn clip_depth_stencil_state() -> wgpu::DepthStencilState {
+    let face = wgpu::StencilFaceState {
+        compare: wgpu::CompareFunction::Equal,
+        fail_op: wgpu::StencilOperation::Keep,
+        depth_fail_op: wgpu::StencilOperation::Keep,
+        pass_op: wgpu::StencilOperation::Keep,
+    };
+    wgpu::DepthStencilState {
+        format: CLIP_STENCIL_FORMAT,
+        depth_write_enabled: None,
+        depth_compare: None,
+        stencil: wgpu::StencilState {
+            front: face,
+            back: face,
+            read_mask: 0xff,
+            write_mask: 0,
+        },
+        bias: wgpu::DepthBiasState::default(),
+    }
+}
+
 /// Provides context for operations that occur once per frame.
 ///
 /// This struct bundles essential WGPU resources and configuration that are
@@ -0,0 +1,242 @@
+//! Frame-aware pools for transient GPU buffers and textures.
+//!
+//! Pipelines often need scratch buffers or textures whose contents only live
+//! for a single frame (instance data, per-batch uniforms, intermediate
+//! targets). Creating them every frame causes allocator churn, especially on
+//! mobile GPUs. The pools in this module hand out resources that are reused
+//! across frames and trimmed automatically once they stay idle.
+//!
+//! # Usage
+//!
+//! Keep a pool inside the pipeline, call `begin_frame` from
+//! [`DrawablePipeline::begin_frame`](super::DrawablePipeline::begin_frame), and
+//! call `acquire` whenever a transient resource is needed. Resources acquired
+//! during a frame are never handed out twice in the same frame.
+
+/// Number of frames a pooled resource may stay unused before it is released.
+pub const DEFAULT_MAX_IDLE_FRAMES: u64 = 120;
+
+const MIN_BUFFER_SIZE: wgpu::BufferAddress = 256;
+
+struct PooledBuffer {
+    buffer: wgpu::Buffer,
+    usage: wgpu::BufferUsages,
+    in_use: bool,
+    last_used_frame: u64,
+}
+
+/// A pool of transient GPU buffers reused across frames.
+pub struct TransientBufferPool {
+    label: &'static str,
+    entries: Vec<PooledBuffer>,
+    frame: u64,
+    max_idle_frames: u64,
+}
+
+impl TransientBufferPool {
+    /// Creates an empty pool that releases buffers after
+    /// [`DEFAULT_MAX_IDLE_FRAMES`] idle frames.
+    pub fn new(label: &'static str) -> Self {
+        Self::with_max_idle_frames(label, DEFAULT_MAX_IDLE_FRAMES)
+    }
+
+    /// Creates an empty pool with a custom idle budget.
+    pub fn with_max_idle_frames(label: &'static str, max_idle_frames: u64) -> Self {
+        Self {
+            label,
+            entries: Vec::new(),
+            frame: 0,
+            max_idle_frames,
+        }
+    }
+
+    /// Starts a new frame, returning every buffer to the pool and releasing
+    /// buffers that have been idle for too long.
+    pub fn begin_frame(&mut self) {
+        self.frame = self.frame.wrapping_add(1);
+        let frame = self.frame;
+        let max_idle_frames = self.max_idle_frames;
+        self.entries.retain_mut(|entry| {
+            entry.in_use = false;
+            frame.saturating_sub(entry.last_used_frame) <= max_idle_frames
+        });
+    }
+
+    /// Returns a buffer with at least `size` bytes and the requested `usage`.
+    ///
+    /// The returned buffer may be larger than requested. Bind only the range
+    /// you wrote when the shader derives array lengths from the binding size.
+    pub fn acquire(
+        &mut self,
+        device: &wgpu::Device,
+        size: wgpu::BufferAddress,
+        usage: wgpu::BufferUsages,
+    ) -> wgpu::Buffer {
+        let frame = self.frame;
+        let best = self
+            .entries
+            .iter_mut()
+            .filter(|entry| !entry.in_use && entry.usage == usage && entry.buffer.size() >= size)
+            .min_by_key(|entry| entry.buffer.size());
+        if let Some(entry) = best {
+            entry.in_use = true;
+            entry.last_used_frame = frame;
+            return entry.buffer.clone();
+        }
+
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(self.label),
+            size: pooled_buffer_size(size),
+            usage,
+            mapped_at_creation: false,
+        });
+        self.entries.push(PooledBuffer {
+            buffer: buffer.clone(),
+            usage,
+            in_use: true,
+            last_used_frame: frame,
+        });
+        buffer
+    }
+
+    /// Returns the number of buffers currently owned by the pool.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the pool owns no buffers.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct TextureKey {
+    width: u32,
+    height: u32,
+    format: wgpu::TextureFormat,
+    usage: wgpu::TextureUsages,
+    sample_count: u32,
+}
+
+struct PooledTexture {
+    key: TextureKey,
+    texture: wgpu::Texture,
+    in_use: bool,
+    last_used_frame: u64,
+}
+
+/// A pool of transient 2D textures reused across frames.
+pub struct TransientTexturePool {
+    label: &'static str,
+    entries: Vec<PooledTexture>,
+    frame: u64,
+    max_idle_frames: u64,
+}
+
+impl TransientTexturePool {
+    /// Creates an empty pool that releases textures after
+    /// [`DEFAULT_MAX_IDLE_FRAMES`] idle frames.
+    pub fn new(label: &'static str) -> Self {
+        Self::with_max_idle_frames(label, DEFAULT_MAX_IDLE_FRAMES)
+    }
+
+    /// Creates an empty pool with a custom idle budget.
+    pub fn with_max_idle_frames(label: &'static str, max_idle_frames: u64) -> Self {
+        Self {
+            label,
+            entries: Vec::new(),
+            frame: 0,
+            max_idle_frames,
+        }
+    }
+
+    /// Starts a new frame, returning every texture to the pool and releasing
+    /// textures that have been idle for too long.
+    pub fn begin_frame(&mut self) {
+        self.frame = self.frame.wrapping_add(1);
+        let frame = self.frame;
+        let max_idle_frames = self.max_idle_frames;
+        self.entries.retain_mut(|entry| {
+            entry.in_use = false;
+            frame.saturating_sub(entry.last_used_frame) <= max_idle_frames
+        });
+    }
+
+    /// Returns a 2D texture with exactly the requested size, format, usage and
+    /// sample count.
+    pub fn acquire(
+        &mut self,
+        device: &wgpu::Device,
+        size: (u32, u32),
+        format: wgpu::TextureFormat,
+        usage: wgpu::TextureUsages,
+        sample_count: u32,
+    ) -> wgpu::Texture {
+        let key = TextureKey {
+            width: size.0.max(1),
+            height: size.1.max(1),
+            format,
+            usage,
+            sample_count,
+        };
+        let frame = self.frame;
+        if let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|entry| !entry.in_use && entry.key == key)
+        {
+            entry.in_use = true;
+            entry.last_used_frame = frame;
+            return entry.texture.clone();
+        }
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(self.label),
+            size: wgpu::Extent3d {
+                width: key.width,
+                height: key.height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage,
+            view_formats: &[],
+        });
+        self.entries.push(PooledTexture {
+            key,
+            texture: texture.clone(),
+            in_use: true,
+            last_used_frame: frame,
+        });
+        texture
+    }
+
+    /// Returns the number of textures currently owned by the pool.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the pool owns no textures.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+fn pooled_buffer_size(size: wgpu::BufferAddress) -> wgpu::BufferAddress {
+    size.max(MIN_BUFFER_SIZE).next_power_of_two()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pooled_buffer_size_rounds_to_power_of_two() {
+        assert_eq!(pooled_buffer_size(0), MIN_BUFFER_SIZE);
+        assert_eq!(pooled_buffer_size(300), 512);
+        assert_eq!(pooled_buffer_size(4096), 4096);
+    }
+}
@@ -0,0 +1,162 @@
+use std::borrow::Cow;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::renderer::drawer::{
+    TransientBufferPool, clip_depth_stencil_state,
+    paint::{PAINT_WGSL, PaintUniform},
+    pipeline::{DrawContext, DrawablePipeline, FrameContext},
+};
+
+use super::SdfShapeCommand;
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct SdfShapeInstance {
+    rect: [f32; 4],
+    corner_radii: [f32; 4],
+    // x: shape kind, y: border width, zw: render target size.
+    params: [f32; 4],
+    border_color: [f32; 4],
+    paint: PaintUniform,
+}
+
+/// Renders every [`SdfShapeCommand`] of a batch with one instanced draw.
+pub(crate) struct SdfShapePipeline {
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    buffer_pool: TransientBufferPool,
+}
+
+impl SdfShapePipeline {
+    pub(crate) fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        sample_count: u32,
+        pipeline_cache: Option<&wgpu::PipelineCache>,
+    ) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("SDF Shape Shader"),
+            source: wgpu::ShaderSource::Wgsl(Cow::Owned(format!(
+                "{PAINT_WGSL}\n{}",
+                include_str!("../../shaders/sdf_shape.wgsl")
+            ))),
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("SDF Shape Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Storage { read_only: true },
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("SDF Shape Pipeline Layout"),
+            bind_group_layouts: &[Some(&bind_group_layout)],
+            immediate_size: 0,
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("SDF Shape Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: Some(clip_depth_stencil_state()),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: Default::default(),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            multiview_mask: None,
+            cache: pipeline_cache,
+        });
+
+        Self {
+            pipeline,
+            bind_group_layout,
+            buffer_pool: TransientBufferPool::new("SDF Shape Instance Buffer"),
+        }
+    }
+}
+
+impl DrawablePipeline<SdfShapeCommand> for SdfShapePipeline {
+    fn begin_frame(&mut self, _context: &FrameContext<'_>) {
+        self.buffer_pool.begin_frame();
+    }
+
+    fn draw(&mut self, context: &mut DrawContext<SdfShapeCommand>) {
+        if context.commands.is_empty() {
+            return;
+        }
+
+        let target_width = context.target_size.width.to_f32();
+        let target_height = context.target_size.height.to_f32();
+        let instances: Vec<SdfShapeInstance> = context
+            .commands
+            .iter()
+            .map(|(command, size, position)| {
+                let (kind, corner_radii) = command.shape.encode();
+                SdfShapeInstance {
+                    rect: [
+                        position.x.to_f32(),
+                        position.y.to_f32(),
+                        size.width.to_f32(),
+                        size.height.to_f32(),
+                    ],
+                    corner_radii,
+                    params: [kind, command.border_width, target_width, target_height],
+                    border_color: command.border_color.to_array(),
+                    paint: command.paint.uniform(),
+                }
+            })
+            .collect();
+
+        let content: &[u8] = bytemuck::cast_slice(&instances);
+        let buffer = self.buffer_pool.acquire(
+            context.device,
+            content.len() as u64,
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        );
+        context.queue.write_buffer(&buffer, 0, content);
+        let bind_group = context
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("SDF Shape Bind Group"),
+                layout: &self.bind_group_layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                        buffer: &buffer,
+                        offset: 0,
+                        size: wgpu::BufferSize::new(content.len() as u64),
+                    }),
+                }],
+            });
+
+        context.render_pass.set_pipeline(&self.pipeline);
+        context.render_pass.set_bind_group(0, &bind_group, &[]);
+        context.render_pass.draw(0..6, 0..instances.len() as u32);
+    }
+}
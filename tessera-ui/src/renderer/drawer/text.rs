@@ -0,0 +1,124 @@
+//! Text layout and rendering.
+//!
+//! Text is shaped with the shared glyphon font system, rasterized into a
+//! glyph atlas and drawn with [`TextCommand`]. Components measure text with
+//! [`TextData::measure`] during layout and fetch the shaped result with
+//! [`TextData::get`] when recording, so shaping happens once per distinct text
+//! and constraint.
+//!
+//! Fonts and fallback chains are managed through [`FontRegistry`]. Caret,
+//! hit-testing and selection geometry for shaped text live in [`hit_test`],
+//! so every selectable text component shares one implementation.
+//!
+//! Text is either a plain string or an [`AttributedString`], whose spans
+//! override color, weight and style, carry underlines and link targets, and
+//! whose placeholders reserve inline boxes for child components.
+//!
+//! The pipeline for [`TextCommand`] is registered by the renderer itself, so
+//! component libraries can draw text without shipping their own text stack.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use tessera_ui::{
+//!     Color,
+//!     renderer::drawer::text::{
+//!         FontFamily, FontSettings, FontWeight, LineBreaking, TextConstraint, TextData,
+//!     },
+//! };
+//!
+//! let font = FontSettings::new(FontFamily::SansSerif).weight(FontWeight::MEDIUM);
+//! let info = TextData::measure(
+//!     "Hello".to_string(),
+//!     Color::BLACK,
+//!     16.0,
+//!     20.0,
+//!     &font,
+//!     LineBreaking::ellipsis(2),
+//!     TextConstraint::NONE,
+//! );
+//! let data = TextData::get(
+//!     "Hello".to_string(),
+//!     Color::BLACK,
+//!     16.0,
+//!     20.0,
+//!     &font,
+//!     LineBreaking::ellipsis(2),
+//!     info.size,
+//! );
+//! assert_eq!(data.size, info.size);
+//! assert!(!info.truncated);
+//! ```
+
+mod font;
+pub mod hit_test;
+mod layout;
+mod overflow;
+mod pipeline;
+mod rich;
+mod style;
+
+use crate::{PxPosition, SampleRegion, renderer::drawer::DrawCommand};
+
+pub use font::{FontFamily, FontRegistry, font_registry, read_font_system, write_font_system};
+pub use layout::{TextData, TextDecoration, TextMeasureInfo};
+pub use overflow::{Hyphens, LineBreaking, TextOverflow};
+pub(crate) use pipeline::TextPipeline;
+pub use rich::{
+    AttributedString, OBJECT_REPLACEMENT, Placeholder, PlaceholderAlignment, SpanStyle, TextSpan,
+};
+pub use style::{FontFeature, FontSettings, FontVariation, FontWeight};
+
+/// Draw command carrying formatted text layout data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextCommand {
+    /// Glyph layout and styling for the text draw.
+    pub data: TextData,
+    /// Offset applied to the command relative to the component origin.
+    pub offset: PxPosition,
+}
+
+impl DrawCommand for TextCommand {
+    fn sample_region(&self) -> Option<SampleRegion> {
+        // No specific barrier requirements for text commands
+        None
+    }
+
+    fn apply_opacity(&mut self, opacity: f32) {
+        self.data.apply_opacity(opacity);
+    }
+}
+
+/// Describes size constraints for a text draw
+#[derive(Debug, PartialEq, Clone)]
+pub struct TextConstraint {
+    /// Maximum width of the text
+    /// If None, it will be calculated by the text renderer
+    pub max_width: Option<f32>,
+    /// Maximum height of the text
+    /// If None, it will be calculated by the text renderer
+    pub max_height: Option<f32>,
+}
+
+impl TextConstraint {
+    /// A constraint that lets the text take its natural size.
+    pub const NONE: Self = Self {
+        max_width: None,
+        max_height: None,
+    };
+}
+
+impl std::hash::Hash for TextConstraint {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        if let Some(w) = self.max_width {
+            w.to_bits().hash(state);
+        } else {
+            0u32.hash(state); // Hash a constant for None
+        }
+        if let Some(h) = self.max_height {
+            h.to_bits().hash(state);
+        } else {
+            0u32.hash(state); // Hash a constant for None
+        }
+    }
+}
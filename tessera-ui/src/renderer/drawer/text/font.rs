@@ -0,0 +1,579 @@
+//! Shared glyphon font system and font registry.
+//!
+//! All text shares one glyphon font system. [`FontRegistry`] is the public
+//! entry point for adding fonts to it and for describing fallback chains,
+//! e.g. a CJK and an emoji font behind the primary UI font.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use tessera_ui::renderer::drawer::text::{FontFamily, font_registry};
+//!
+//! let mut registry = font_registry();
+//! registry.register_font_data(std::fs::read("assets/Inter.ttf").unwrap());
+//! registry.set_generic_family(FontFamily::SansSerif, "Inter");
+//! registry.add_common_fallback(FontFamily::named("Noto Sans CJK SC"));
+//! registry.add_common_fallback(FontFamily::named("Noto Color Emoji"));
+//! ```
+
+use std::{
+    collections::HashMap,
+    io,
+    ops::Range,
+    path::Path,
+    sync::{Arc, OnceLock},
+};
+
+use glyphon::fontdb;
+use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::layout::clear_text_cache;
+
+/// It costs a lot to create a glyphon font system, so we use a static one
+/// to share it every where and avoid creating it multiple times.
+static FONT_SYSTEM: OnceLock<RwLock<glyphon::FontSystem>> = OnceLock::new();
+
+static FONT_REGISTRY: OnceLock<RwLock<FontRegistry>> = OnceLock::new();
+
+/// Color emoji families shipped with the platform, used as common fallbacks
+/// by default. Families missing from the font database are skipped.
+#[cfg(target_vendor = "apple")]
+const PLATFORM_EMOJI_FAMILIES: &[&str] = &["Apple Color Emoji"];
+#[cfg(target_os = "windows")]
+const PLATFORM_EMOJI_FAMILIES: &[&str] = &["Segoe UI Emoji"];
+#[cfg(not(any(target_vendor = "apple", target_os = "windows")))]
+const PLATFORM_EMOJI_FAMILIES: &[&str] = &["Noto Color Emoji", "Twemoji"];
+
+#[cfg(target_os = "android")]
+fn init_font_system() -> RwLock<glyphon::FontSystem> {
+    let mut font_system = glyphon::FontSystem::new();
+
+    {
+        let db = font_system.db_mut();
+        db.load_fonts_dir("/system/fonts");
+        db.set_sans_serif_family("Roboto");
+        db.set_serif_family("Noto Serif");
+        db.set_monospace_family("Droid Sans Mono");
+        db.set_cursive_family("Dancing Script");
+        db.set_fantasy_family("Dancing Script");
+    }
+
+    RwLock::new(font_system)
+}
+
+#[cfg(not(target_os = "android"))]
+fn init_font_system() -> RwLock<glyphon::FontSystem> {
+    RwLock::new(glyphon::FontSystem::new())
+}
+
+/// It costs a lot to create a glyphon font system, so we use a static one
+/// to share it every where and avoid creating it multiple times.
+/// This function returns a read lock of the font system.
+pub fn read_font_system() -> RwLockReadGuard<'static, glyphon::FontSystem> {
+    FONT_SYSTEM.get_or_init(init_font_system).read()
+}
+
+/// It costs a lot to create a glyphon font system, so we use a static one
+/// to share it every where and avoid creating it multiple times.
+/// This function returns a write lock of the font system.
+///
+/// Prefer [`font_registry`] for loading fonts.
+pub fn write_font_system() -> RwLockWriteGuard<'static, glyphon::FontSystem> {
+    FONT_SYSTEM.get_or_init(init_font_system).write()
+}
+
+/// Returns a write lock of the global [`FontRegistry`].
+pub fn font_registry() -> RwLockWriteGuard<'static, FontRegistry> {
+    FONT_REGISTRY
+        .get_or_init(|| RwLock::new(FontRegistry::with_platform_fallbacks()))
+        .write()
+}
+
+/// Returns the fallback chain of `family` from the global registry.
+///
+/// Must not be called while holding the font system lock, as registry methods
+/// lock the font system while holding the registry lock.
+pub(crate) fn resolve_fallback_chain(family: &FontFamily) -> Vec<FontFamily> {
+    FONT_REGISTRY
+        .get_or_init(|| RwLock::new(FontRegistry::with_platform_fallbacks()))
+        .read()
+        .fallback_chain(family)
+}
+
+/// A font family used to style text.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub enum FontFamily {
+    /// The platform sans-serif family.
+    #[default]
+    SansSerif,
+    /// The platform serif family.
+    Serif,
+    /// The platform monospace family.
+    Monospace,
+    /// The platform cursive family.
+    Cursive,
+    /// The platform fantasy family.
+    Fantasy,
+    /// A family by name, such as `"Inter"`.
+    Named(Arc<str>),
+}
+
+impl FontFamily {
+    /// Creates a family by name.
+    pub fn named(name: impl Into<Arc<str>>) -> Self {
+        Self::Named(name.into())
+    }
+
+    pub(crate) fn as_fontdb(&self) -> fontdb::Family<'_> {
+        match self {
+            Self::SansSerif => fontdb::Family::SansSerif,
+            Self::Serif => fontdb::Family::Serif,
+            Self::Monospace => fontdb::Family::Monospace,
+            Self::Cursive => fontdb::Family::Cursive,
+            Self::Fantasy => fontdb::Family::Fantasy,
+            Self::Named(name) => fontdb::Family::Name(name),
+        }
+    }
+}
+
+impl From<&str> for FontFamily {
+    fn from(name: &str) -> Self {
+        Self::named(name)
+    }
+}
+
+impl From<String> for FontFamily {
+    fn from(name: String) -> Self {
+        Self::named(name)
+    }
+}
+
+/// Registry of fonts and fallback chains used by the text pipeline.
+///
+/// Fonts registered here are loaded into the shared font system. Register
+/// fonts before the first frame; components that already measured text keep
+/// their layout until they are measured again. When text is
+/// laid out, characters the requested family cannot display are assigned to
+/// the first family of its fallback chain that covers them: first the chain
+/// set with [`FontRegistry::set_fallback_chain`], then the common fallbacks.
+/// Characters no listed family covers are left to glyphon's own fallback.
+///
+/// The global registry starts with the platform color emoji font as a common
+/// fallback. Emoji are matched per grapheme cluster, and clusters with emoji
+/// presentation prefer families with color glyphs.
+#[derive(Debug, Default)]
+pub struct FontRegistry {
+    fallback_chains: HashMap<FontFamily, Vec<FontFamily>>,
+    common_fallbacks: Vec<FontFamily>,
+}
+
+impl FontRegistry {
+    fn with_platform_fallbacks() -> Self {
+        Self {
+            fallback_chains: HashMap::new(),
+            common_fallbacks: PLATFORM_EMOJI_FAMILIES
+                .iter()
+                .map(|name| FontFamily::named(*name))
+                .collect(),
+        }
+    }
+
+    /// Loads a font file or collection from memory and returns the families
+    /// it contains.
+    pub fn register_font_data(&mut self, data: impl Into<Vec<u8>>) -> Vec<FontFamily> {
+        let mut font_system = write_font_system();
+        let db = font_system.db_mut();
+        let before: Vec<_> = db.faces().map(|face| face.id).collect();
+        db.load_font_data(data.into());
+        let families = new_families(db, &before);
+        clear_text_cache();
+        families
+    }
+
+    /// Loads a font file and returns the families it contains.
+    pub fn register_font_file(&mut self, path: impl AsRef<Path>) -> io::Result<Vec<FontFamily>> {
+        let mut font_system = write_font_system();
+        let db = font_system.db_mut();
+        let before: Vec<_> = db.faces().map(|face| face.id).collect();
+        db.load_font_file(path)?;
+        let families = new_families(db, &before);
+        clear_text_cache();
+        Ok(families)
+    }
+
+    /// Loads every font in a directory, recursively.
+    pub fn register_fonts_dir(&mut self, path: impl AsRef<Path>) {
+        write_font_system().db_mut().load_fonts_dir(path);
+        clear_text_cache();
+    }
+
+    /// Loads the fonts installed on the system.
+    ///
+    /// Desktop platforms load system fonts on startup already.
+    pub fn load_system_fonts(&mut self) {
+        write_font_system().db_mut().load_system_fonts();
+        clear_text_cache();
+    }
+
+    /// Maps a generic family such as [`FontFamily::SansSerif`] to a family
+    /// name. Named families are ignored.
+    pub fn set_generic_family(&mut self, generic: FontFamily, name: impl Into<String>) {
+        let mut font_system = write_font_system();
+        let db = font_system.db_mut();
+        let name = name.into();
+        match generic {
+            FontFamily::SansSerif => db.set_sans_serif_family(name),
+            FontFamily::Serif => db.set_serif_family(name),
+            FontFamily::Monospace => db.set_monospace_family(name),
+            FontFamily::Cursive => db.set_cursive_family(name),
+            FontFamily::Fantasy => db.set_fantasy_family(name),
+            FontFamily::Named(_) => return,
+        }
+        clear_text_cache();
+    }
+
+    /// Sets the families tried, in order, for characters `family` cannot
+    /// display.
+    pub fn set_fallback_chain(
+        &mut self,
+        family: FontFamily,
+        chain: impl IntoIterator<Item = FontFamily>,
+    ) {
+        self.fallback_chains
+            .insert(family, chain.into_iter().collect());
+        clear_text_cache();
+    }
+
+    /// Appends a family tried after the family-specific chain for every
+    /// family, e.g. an emoji font.
+    pub fn add_common_fallback(&mut self, family: FontFamily) {
+        if !self.common_fallbacks.contains(&family) {
+            self.common_fallbacks.push(family);
+            clear_text_cache();
+        }
+    }
+
+    /// Returns `family` followed by its fallback chain and the common
+    /// fallbacks, without duplicates.
+    pub fn fallback_chain(&self, family: &FontFamily) -> Vec<FontFamily> {
+        let mut chain = vec![family.clone()];
+        let specific = self.fallback_chains.get(family).into_iter().flatten();
+        for candidate in specific.chain(&self.common_fallbacks) {
+            if !chain.contains(candidate) {
+                chain.push(candidate.clone());
+            }
+        }
+        chain
+    }
+
+    /// Returns the names of all families in the font database.
+    pub fn families(&self) -> Vec<String> {
+        let font_system = read_font_system();
+        let mut names: Vec<String> = font_system
+            .db()
+            .faces()
+            .flat_map(|face| face.families.iter().map(|(name, _)| name.clone()))
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+}
+
+fn new_families(db: &fontdb::Database, before: &[fontdb::ID]) -> Vec<FontFamily> {
+    let mut families = Vec::new();
+    for face in db.faces().filter(|face| !before.contains(&face.id)) {
+        if let Some((name, _)) = face.families.first() {
+            let family = FontFamily::named(name.as_str());
+            if !families.contains(&family) {
+                families.push(family);
+            }
+        }
+    }
+    families
+}
+
+/// Sets `text` on `buffer`, splitting it into runs over `chain`, the result
+/// of [`resolve_fallback_chain`].
+///
+/// `styles` are non-overlapping, ordered byte ranges drawn with their own
+/// attributes; the rest of the text uses `attrs`.
+pub(crate) fn set_buffer_text(
+    buffer: &mut glyphon::Buffer,
+    font_system: &mut glyphon::FontSystem,
+    text: &str,
+    attrs: &glyphon::Attrs<'_>,
+    styles: &[(Range<usize>, glyphon::Attrs<'_>)],
+    chain: &[FontFamily],
+) {
+    let attrs = attrs.clone().family(chain[0].as_fontdb());
+    let plain_families = chain.len() == 1 || text.is_ascii();
+    if plain_families && styles.is_empty() {
+        buffer.set_text(font_system, text, &attrs, glyphon::Shaping::Advanced, None);
+        return;
+    }
+
+    let family_runs = if plain_families {
+        vec![(0..text.len(), 0)]
+    } else {
+        let assignment = assign_families(font_system.db(), text, chain, &attrs);
+        split_runs(text, &assignment)
+    };
+    let spans = style_runs(text.len(), &attrs, styles).into_iter().flat_map(
+        |(style_range, style_attrs)| {
+            family_runs.iter().filter_map(move |(range, index)| {
+                let start = range.start.max(style_range.start);
+                let end = range.end.min(style_range.end);
+                (start < end).then(|| {
+                    (
+                        &text[start..end],
+                        style_attrs.clone().family(chain[*index].as_fontdb()),
+                    )
+                })
+            })
+        },
+    );
+    buffer.set_rich_text(font_system, spans, &attrs, glyphon::Shaping::Advanced, None);
+}
+
+/// Fills the gaps between `styles` with `attrs`, so the runs cover the whole
+/// text.
+fn style_runs<'a>(
+    len: usize,
+    attrs: &glyphon::Attrs<'a>,
+    styles: &[(Range<usize>, glyphon::Attrs<'a>)],
+) -> Vec<(Range<usize>, glyphon::Attrs<'a>)> {
+    let mut runs = Vec::with_capacity(styles.len() * 2 + 1);
+    let mut covered = 0;
+    for (range, style) in styles {
+        let start = range.start.max(covered);
+        let end = range.end.min(len);
+        if start >= end {
+            continue;
+        }
+        if covered < start {
+            runs.push((covered..start, attrs.clone()));
+        }
+        runs.push((start..end, style.clone()));
+        covered = end;
+    }
+    if covered < len {
+        runs.push((covered..len, attrs.clone()));
+    }
+    runs
+}
+
+/// A grapheme cluster of the text being assigned to a family.
+#[derive(Debug, PartialEq)]
+struct Cluster {
+    /// Char range of the cluster.
+    chars: Range<usize>,
+    /// Whether the cluster should be drawn with a color emoji font.
+    prefers_color: bool,
+}
+
+/// Splits `text` into grapheme clusters, so emoji sequences joined with
+/// ZWJ, skin tone modifiers or variation selectors stay in one family.
+fn clusters(text: &str) -> Vec<Cluster> {
+    let mut start = 0;
+    text.graphemes(true)
+        .map(|grapheme| {
+            let len = grapheme.chars().count();
+            let cluster = Cluster {
+                chars: start..start + len,
+                prefers_color: prefers_color(grapheme),
+            };
+            start += len;
+            cluster
+        })
+        .collect()
+}
+
+/// Approximates emoji presentation: an explicit emoji variation selector or
+/// a pictograph from the supplementary emoji blocks, unless the text
+/// variation selector is present.
+fn prefers_color(grapheme: &str) -> bool {
+    if grapheme.contains('\u{FE0E}') {
+        return false;
+    }
+    grapheme
+        .chars()
+        .any(|ch| ch == '\u{FE0F}' || matches!(ch as u32, 0x1F000..=0x1FAFF))
+}
+
+/// Joiners and variation selectors, which fonts need not map to glyphs.
+fn is_ignorable(ch: char) -> bool {
+    matches!(ch, '\u{200D}' | '\u{FE00}'..='\u{FE0F}')
+}
+
+/// Returns whether the face carries color glyphs as COLR layers, CBDT or
+/// sbix bitmaps, or SVG documents.
+fn has_color_glyphs(face: &ttf_parser::Face<'_>) -> bool {
+    let tables = face.tables();
+    tables.colr.is_some() || tables.cbdt.is_some() || tables.sbix.is_some() || tables.svg.is_some()
+}
+
+/// Returns, for every char of `text`, the index of the first family in
+/// `chain` that has glyphs for its whole grapheme cluster, or 0 if none does.
+///
+/// Clusters with emoji presentation prefer families with color glyphs and
+/// only fall back to the first outline family covering them.
+fn assign_families(
+    db: &fontdb::Database,
+    text: &str,
+    chain: &[FontFamily],
+    attrs: &glyphon::Attrs<'_>,
+) -> Vec<usize> {
+    let chars: Vec<char> = text.chars().collect();
+    let clusters = clusters(text);
+    let mut assignment: Vec<Option<usize>> = vec![None; clusters.len()];
+    let mut outline_fallback: Vec<Option<usize>> = vec![None; clusters.len()];
+    for (index, family) in chain.iter().enumerate() {
+        let query = fontdb::Query {
+            families: &[family.as_fontdb()],
+            weight: attrs.weight,
+            stretch: attrs.stretch,
+            style: attrs.style,
+        };
+        let Some(id) = db.query(&query) else {
+            continue;
+        };
+        db.with_face_data(id, |data, face_index| {
+            let Ok(face) = ttf_parser::Face::parse(data, face_index) else {
+                return;
+            };
+            let color = has_color_glyphs(&face);
+            for ((slot, fallback), cluster) in assignment
+                .iter_mut()
+                .zip(&mut outline_fallback)
+                .zip(&clusters)
+            {
+                if slot.is_some() {
+                    continue;
+                }
+                let covered = chars[cluster.chars.clone()]
+                    .iter()
+                    .all(|ch| is_ignorable(*ch) || face.glyph_index(*ch).is_some());
+                if !covered {
+                    continue;
+                }
+                if color || !cluster.prefers_color {
+                    *slot = Some(index);
+                } else if fallback.is_none() {
+                    *fallback = Some(index);
+                }
+            }
+        });
+        if assignment.iter().all(Option::is_some) {
+            break;
+        }
+    }
+
+    let mut result = vec![0; chars.len()];
+    for ((slot, fallback), cluster) in assignment.into_iter().zip(outline_fallback).zip(clusters) {
+        let index = slot.or(fallback).unwrap_or(0);
+        for char_index in cluster.chars {
+            let ch = chars[char_index];
+            // Keep whitespace and controls in the primary family.
+            if !(ch.is_whitespace() || ch.is_control()) {
+                result[char_index] = index;
+            }
+        }
+    }
+    result
+}
+
+/// Groups consecutive chars assigned to the same family into byte ranges.
+///
+/// Chars assigned to the primary family (index 0) join the preceding run, so
+/// spaces inside a fallback run do not split it.
+fn split_runs(text: &str, assignment: &[usize]) -> Vec<(Range<usize>, usize)> {
+    let mut runs: Vec<(Range<usize>, usize)> = Vec::new();
+    for ((offset, ch), &index) in text.char_indices().zip(assignment) {
+        let end = offset + ch.len_utf8();
+        match runs.last_mut() {
+            Some((range, current)) if *current == index || (index == 0 && ch.is_whitespace()) => {
+                range.end = end;
+            }
+            _ => runs.push((offset..end, index)),
+        }
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fallback_chain_orders_specific_before_common() {
+        let mut registry = FontRegistry::default();
+        registry.set_fallback_chain(FontFamily::SansSerif, [FontFamily::named("CJK")]);
+        registry.add_common_fallback(FontFamily::named("Emoji"));
+        registry.add_common_fallback(FontFamily::named("CJK"));
+        assert_eq!(
+            registry.fallback_chain(&FontFamily::SansSerif),
+            vec![
+                FontFamily::SansSerif,
+                FontFamily::named("CJK"),
+                FontFamily::named("Emoji"),
+            ]
+        );
+        assert_eq!(
+            registry.fallback_chain(&FontFamily::Serif),
+            vec![
+                FontFamily::Serif,
+                FontFamily::named("Emoji"),
+                FontFamily::named("CJK")
+            ]
+        );
+    }
+
+    #[test]
+    fn split_runs_merges_spaces_into_fallback_runs() {
+        let text = "ab 中 文c";
+        let assignment = [0, 0, 0, 1, 0, 1, 0];
+        let runs = split_runs(text, &assignment);
+        assert_eq!(runs, vec![(0..3, 0), (3..10, 1), (10..11, 0)]);
+    }
+
+    #[test]
+    fn style_runs_fill_the_gaps_between_styles() {
+        let attrs = glyphon::Attrs::new();
+        let styles = [
+            (2..4, attrs.clone().metadata(1)),
+            (4..5, attrs.clone().metadata(2)),
+        ];
+        let runs: Vec<_> = style_runs(8, &attrs, &styles)
+            .into_iter()
+            .map(|(range, attrs)| (range, attrs.metadata))
+            .collect();
+        assert_eq!(runs, vec![(0..2, 0), (2..4, 1), (4..5, 2), (5..8, 0)]);
+    }
+
+    #[test]
+    fn clusters_keep_emoji_sequences_together() {
+        // Thumbs up with a skin tone, a ZWJ family, text and emoji hearts.
+        let text = "a\u{1F44D}\u{1F3FD}\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}\u{2764}\u{2764}\u{FE0F}";
+        let clusters = clusters(text);
+        let expected = [
+            (0..1, false),
+            (1..3, true),
+            (3..8, true),
+            (8..9, false),
+            (9..11, true),
+        ];
+        assert_eq!(clusters.len(), expected.len());
+        for (cluster, (chars, prefers_color)) in clusters.iter().zip(expected) {
+            assert_eq!(cluster.chars, chars);
+            assert_eq!(cluster.prefers_color, prefers_color);
+        }
+    }
+
+    #[test]
+    fn text_variation_selector_disables_color() {
+        assert!(!prefers_color("\u{1F600}\u{FE0E}"));
+        assert!(prefers_color("1\u{FE0F}\u{20E3}"));
+    }
+}
@@ -0,0 +1,413 @@
+//! Hit-testing, caret movement and selection geometry for shaped text.
+//!
+//! Offsets are byte offsets into the source text of a buffer, line endings
+//! included, so they index the same string the buffer was built from.
+//! Geometry is relative to the text origin and follows the visual order of
+//! each line: a selection crossing BiDi runs yields one rectangle per
+//! contiguous visual segment instead of one box spanning the whole line.
+//!
+//! Word movement and selection follow Unicode words (UAX #29). Character
+//! movement is left to the shaper's cursor motions, which are visual in BiDi
+//! text and already step over whole grapheme clusters.
+
+use std::ops::Range;
+
+use glyphon::{Buffer, Cursor, cosmic_text::LayoutGlyph};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{Px, PxRect};
+
+/// Metrics of one visual line of shaped text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineMetrics {
+    /// Byte range of the line in the source text, without the line ending.
+    pub range: Range<usize>,
+    /// Top of the line box.
+    pub top: f32,
+    /// Height of the line box.
+    pub height: f32,
+    /// Baseline offset from the text origin.
+    pub baseline: f32,
+    /// Width of the laid out glyphs.
+    pub width: f32,
+    /// Whether the base direction of the line is right-to-left.
+    pub rtl: bool,
+}
+
+/// Returns the byte offset of every buffer line in the source text.
+fn line_start_offsets(buffer: &Buffer) -> Vec<usize> {
+    let mut offset = 0;
+    buffer
+        .lines
+        .iter()
+        .map(|line| {
+            let start = offset;
+            offset += line.text().len() + line.ending().as_str().len();
+            start
+        })
+        .collect()
+}
+
+/// Converts a buffer cursor into a byte offset of the source text.
+pub fn cursor_to_offset(buffer: &Buffer, cursor: Cursor) -> usize {
+    let mut offset = 0usize;
+    for (line_index, line) in buffer.lines.iter().enumerate() {
+        let line_len = line.text().len();
+        if line_index == cursor.line {
+            return offset + cursor.index.min(line_len);
+        }
+        offset += line_len + line.ending().as_str().len();
+    }
+    offset
+}
+
+/// Converts a byte offset of the source text into a buffer cursor.
+///
+/// Offsets inside a line ending map to the end of that line, offsets past the
+/// text to the end of the last line.
+pub fn offset_to_cursor(buffer: &Buffer, offset: usize) -> Cursor {
+    if buffer.lines.is_empty() {
+        return Cursor::new(0, 0);
+    }
+
+    let mut remaining = offset;
+    for (line_index, line) in buffer.lines.iter().enumerate() {
+        let line_len = line.text().len();
+        if remaining <= line_len {
+            return Cursor::new(line_index, remaining);
+        }
+
+        let line_total_len = line_len + line.ending().as_str().len();
+        if remaining < line_total_len {
+            return Cursor::new(line_index, line_len);
+        }
+        remaining = remaining.saturating_sub(line_total_len);
+    }
+
+    let last_line_index = buffer.lines.len().saturating_sub(1);
+    let last_line_len = buffer.lines[last_line_index].text().len();
+    Cursor::new(last_line_index, last_line_len)
+}
+
+/// Returns the offset closest to `(x, y)`, relative to the text origin.
+///
+/// Points above, below or between lines snap to the nearest line. Returns
+/// `None` if the buffer has no laid out lines.
+pub fn hit_test(buffer: &Buffer, x: f32, y: f32) -> Option<usize> {
+    let cursor = buffer
+        .hit(x, y)
+        .or_else(|| nearest_line_hit(buffer, x, y))?;
+    Some(cursor_to_offset(buffer, cursor))
+}
+
+fn nearest_line_hit(buffer: &Buffer, x: f32, y: f32) -> Option<Cursor> {
+    let mut closest_line_y = None::<(f32, f32)>;
+
+    for run in buffer.layout_runs() {
+        let line_top = run.line_top;
+        let line_bottom = line_top + run.line_height;
+        let distance = if y < line_top {
+            line_top - y
+        } else if y >= line_bottom {
+            y - line_bottom
+        } else {
+            0.0
+        };
+        let line_mid_y = line_top + (run.line_height * 0.5);
+
+        match closest_line_y {
+            Some((best_distance, _)) if best_distance <= distance => {}
+            _ => {
+                closest_line_y = Some((distance, line_mid_y));
+            }
+        }
+    }
+
+    let (_, line_mid_y) = closest_line_y?;
+    buffer.hit(x, line_mid_y)
+}
+
+/// Returns the metrics of every laid out line, in visual order.
+pub fn line_metrics(buffer: &Buffer) -> Vec<LineMetrics> {
+    let line_starts = line_start_offsets(buffer);
+    buffer
+        .layout_runs()
+        .map(|run| {
+            let line_start = line_starts.get(run.line_i).copied().unwrap_or_default();
+            let start = run.glyphs.iter().map(|glyph| glyph.start).min();
+            let end = run.glyphs.iter().map(|glyph| glyph.end).max();
+            let range = match (start, end) {
+                (Some(start), Some(end)) => line_start + start..line_start + end,
+                _ => line_start..line_start,
+            };
+            LineMetrics {
+                range,
+                top: run.line_top,
+                height: run.line_height,
+                baseline: run.line_y,
+                width: run.line_w,
+                rtl: run.rtl,
+            }
+        })
+        .collect()
+}
+
+/// Returns the caret at `offset` as a zero-width rectangle spanning its line.
+///
+/// An offset at a soft wrap belongs to the start of the following line.
+/// Callers widen the rectangle to their caret thickness.
+pub fn caret_rect(buffer: &Buffer, offset: usize) -> Option<PxRect> {
+    let cursor = offset_to_cursor(buffer, offset);
+    let mut end_edge = None;
+    for run in buffer.layout_runs().filter(|run| run.line_i == cursor.line) {
+        let line = (run.line_top, run.line_height);
+        if run.glyphs.is_empty() {
+            end_edge = Some((if run.rtl { run.line_w } else { 0.0 }, line));
+            continue;
+        }
+        for glyph in run.glyphs {
+            if glyph.start == cursor.index {
+                return Some(caret(leading_edge(glyph), line));
+            }
+            if glyph.start < cursor.index && cursor.index < glyph.end {
+                let (x0, x1) = glyph_span(run.text, glyph, glyph.start, cursor.index);
+                let x = if glyph.level.is_rtl() { x0 } else { x1 };
+                return Some(caret(x, line));
+            }
+            if glyph.end == cursor.index {
+                end_edge = Some((trailing_edge(glyph), line));
+            }
+        }
+    }
+    end_edge.map(|(x, line)| caret(x, line))
+}
+
+fn caret(x: f32, (top, height): (f32, f32)) -> PxRect {
+    PxRect::new(
+        Px(x.round() as i32),
+        Px(top as i32),
+        Px::ZERO,
+        Px(height as i32),
+    )
+}
+
+fn leading_edge(glyph: &LayoutGlyph) -> f32 {
+    if glyph.level.is_rtl() {
+        glyph.x + glyph.w
+    } else {
+        glyph.x
+    }
+}
+
+fn trailing_edge(glyph: &LayoutGlyph) -> f32 {
+    if glyph.level.is_rtl() {
+        glyph.x
+    } else {
+        glyph.x + glyph.w
+    }
+}
+
+/// Returns the horizontal span covered by bytes `start..end` of `glyph`.
+///
+/// Glyphs covering several graphemes, such as ligatures, are split evenly
+/// between them.
+fn glyph_span(text: &str, glyph: &LayoutGlyph, start: usize, end: usize) -> (f32, f32) {
+    if start <= glyph.start && end >= glyph.end {
+        return (glyph.x, glyph.x + glyph.w);
+    }
+    let graphemes = |range: Range<usize>| text.get(range).map_or(0, |s| s.graphemes(true).count());
+    let total = graphemes(glyph.start..glyph.end).max(1) as f32;
+    let before = graphemes(glyph.start..start) as f32;
+    let inside = graphemes(start..end) as f32;
+    let advance = glyph.w / total;
+    if glyph.level.is_rtl() {
+        let x1 = glyph.x + glyph.w - before * advance;
+        (x1 - inside * advance, x1)
+    } else {
+        let x0 = glyph.x + before * advance;
+        (x0, x0 + inside * advance)
+    }
+}
+
+/// Returns the highlight rectangles of the byte `range`, in either order.
+///
+/// Each visual line yields one rectangle per contiguous segment of selected
+/// glyphs. Empty lines inside the range get a narrow rectangle so selected
+/// blank lines stay visible.
+pub fn selection_rects(buffer: &Buffer, range: Range<usize>) -> Vec<PxRect> {
+    let (start, end) = if range.start <= range.end {
+        (range.start, range.end)
+    } else {
+        (range.end, range.start)
+    };
+    if start == end {
+        return Vec::new();
+    }
+    let start = offset_to_cursor(buffer, start);
+    let end = offset_to_cursor(buffer, end);
+    let empty_line_width = buffer.metrics().font_size * 0.5;
+
+    let mut rects = Vec::new();
+    for run in buffer.layout_runs() {
+        if run.line_i < start.line || run.line_i > end.line {
+            continue;
+        }
+        let lo = if run.line_i == start.line {
+            start.index
+        } else {
+            0
+        };
+        let hi = if run.line_i == end.line {
+            end.index
+        } else {
+            usize::MAX
+        };
+
+        let mut segments: Vec<(f32, f32)> = run
+            .glyphs
+            .iter()
+            .filter_map(|glyph| {
+                let lo = lo.max(glyph.start);
+                let hi = hi.min(glyph.end);
+                (lo < hi).then(|| glyph_span(run.text, glyph, lo, hi))
+            })
+            .collect();
+        if run.glyphs.is_empty() && run.line_i < end.line {
+            let x = if run.rtl {
+                run.line_w - empty_line_width
+            } else {
+                0.0
+            };
+            segments.push((x, x + empty_line_width));
+        }
+
+        segments.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut merged: Vec<(f32, f32)> = Vec::with_capacity(segments.len());
+        for (x0, x1) in segments {
+            match merged.last_mut() {
+                Some(last) if x0 <= last.1 + 0.5 => last.1 = last.1.max(x1),
+                _ => merged.push((x0, x1)),
+            }
+        }
+
+        let top = Px(run.line_top as i32);
+        let height = Px(run.line_height as i32);
+        rects.extend(merged.into_iter().map(|(x0, x1)| {
+            let x = x0.floor();
+            PxRect::new(Px(x as i32), top, Px((x1.ceil() - x) as i32), height)
+        }));
+    }
+    rects
+}
+
+/// Splits `text` into UAX #29 word segments, flagging those that are words
+/// rather than spaces or punctuation.
+fn word_segments(text: &str) -> impl DoubleEndedIterator<Item = (Range<usize>, bool)> + '_ {
+    text.split_word_bound_indices().map(|(start, segment)| {
+        let is_word = segment.chars().any(|ch| ch.is_alphanumeric() || ch == '_');
+        (start..start + segment.len(), is_word)
+    })
+}
+
+/// Returns the end of the word at or after `offset`, or the text length.
+pub fn next_word_boundary(text: &str, offset: usize) -> usize {
+    word_segments(text)
+        .find(|(range, is_word)| *is_word && range.end > offset)
+        .map_or(text.len(), |(range, _)| range.end)
+}
+
+/// Returns the start of the word at or before `offset`, or 0.
+pub fn previous_word_boundary(text: &str, offset: usize) -> usize {
+    word_segments(text)
+        .rev()
+        .find(|(range, is_word)| *is_word && range.start < offset)
+        .map_or(0, |(range, _)| range.start)
+}
+
+/// Returns the word containing `offset`, or the nearest word, preferring the
+/// following one. Returns `None` if the text has no words.
+pub fn word_range_at(text: &str, offset: usize) -> Option<Range<usize>> {
+    let segments: Vec<_> = word_segments(text).collect();
+    let index = segments
+        .iter()
+        .position(|(range, _)| offset < range.end)
+        .unwrap_or(segments.len().checked_sub(1)?);
+    segments[index..]
+        .iter()
+        .find(|(_, is_word)| *is_word)
+        .or_else(|| segments[..index].iter().rev().find(|(_, is_word)| *is_word))
+        .map(|(range, _)| range.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_FONT: &[u8] = include_bytes!("../../../../assets/DejaVuSansMono.ttf");
+
+    /// Shapes with only the bundled font, so results don't depend on the
+    /// fonts installed on the machine running the tests.
+    fn buffer(text: &str) -> Buffer {
+        let mut db = glyphon::fontdb::Database::new();
+        db.load_font_data(TEST_FONT.to_vec());
+        let mut font_system = glyphon::FontSystem::new_with_locale_and_db("en-US".into(), db);
+        let mut buffer = Buffer::new(&mut font_system, glyphon::Metrics::new(14.0, 18.0));
+        buffer.set_size(&mut font_system, Some(400.0), None);
+        buffer.set_text(
+            &mut font_system,
+            text,
+            &glyphon::Attrs::new().family(glyphon::fontdb::Family::Name("DejaVu Sans Mono")),
+            glyphon::Shaping::Advanced,
+            None,
+        );
+        buffer.shape_until_scroll(&mut font_system, false);
+        buffer
+    }
+
+    #[test]
+    fn offsets_round_trip_across_line_endings() {
+        let text = "ab\r\ncd\nef";
+        let buffer = buffer(text);
+        for offset in [0, 2, 4, 6, 7, 9] {
+            let cursor = offset_to_cursor(&buffer, offset);
+            assert_eq!(cursor_to_offset(&buffer, cursor), offset);
+        }
+        // Offsets inside a line ending snap to the end of the line.
+        assert_eq!(offset_to_cursor(&buffer, 3), Cursor::new(0, 2));
+        assert_eq!(offset_to_cursor(&buffer, 100), Cursor::new(2, 2));
+    }
+
+    #[test]
+    fn selection_rects_cover_each_selected_line() {
+        let buffer = buffer("hello\n\nworld");
+        let rects = selection_rects(&buffer, 12..2);
+        assert_eq!(rects.len(), 3);
+        assert!(rects[0].y < rects[1].y && rects[1].y < rects[2].y);
+        assert!(selection_rects(&buffer, 3..3).is_empty());
+    }
+
+    #[test]
+    fn line_metrics_report_source_ranges() {
+        let buffer = buffer("hello\nworld");
+        let lines = line_metrics(&buffer);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].range, 0..5);
+        assert_eq!(lines[1].range, 6..11);
+        assert!(lines[1].top > lines[0].top);
+    }
+
+    #[test]
+    fn word_boundaries_follow_unicode_words() {
+        let text = "don't  \u{645}\u{631}\u{62D}\u{628}\u{627}, x_y";
+        let arabic = 7..17;
+        assert_eq!(next_word_boundary(text, 0), 5);
+        assert_eq!(next_word_boundary(text, 5), arabic.end);
+        assert_eq!(previous_word_boundary(text, text.len()), 19);
+        assert_eq!(previous_word_boundary(text, arabic.end), arabic.start);
+        assert_eq!(word_range_at(text, 9), Some(arabic.clone()));
+        // Spaces select the following word.
+        assert_eq!(word_range_at(text, 5), Some(arabic));
+        assert_eq!(word_range_at(text, text.len()), Some(19..22));
+        assert_eq!(word_range_at(" , ", 1), None);
+    }
+}
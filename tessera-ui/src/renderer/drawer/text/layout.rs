@@ -0,0 +1,796 @@
+//! Text shaping, measurement and caching.
+
+use std::{num::NonZero, ops::Range, sync::OnceLock};
+
+use parking_lot::{RwLock, RwLockWriteGuard};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{Color, Px, PxPosition, PxRect};
+
+use super::{
+    TextConstraint,
+    font::{FontFamily, resolve_fallback_chain, set_buffer_text, write_font_system},
+    hit_test::{self, LineMetrics},
+    overflow::{DisplayMap, Hyphens, LineBreaking, SOFT_HYPHEN, TextOverflow},
+    rich::{AttributedString, PlaceholderAlignment, TextSpan},
+    style::FontSettings,
+};
+
+const LRU_CAPACITY: usize = 1024;
+/// Underline position below the baseline, in ems.
+const UNDERLINE_OFFSET: f32 = 0.1;
+/// Underline thickness, in ems.
+const UNDERLINE_THICKNESS: f32 = 0.06;
+
+/// Create TextData is a heavy operation, so we provide a lru cache to store
+/// recently used TextData.
+static TEXT_DATA_CACHE: OnceLock<RwLock<lru::LruCache<LruKey, TextData>>> = OnceLock::new();
+
+fn linear_to_srgb_channel(value: f32) -> f32 {
+    let value = value.clamp(0.0, 1.0);
+    if value <= 0.003_130_8 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+pub(super) fn color_to_glyphon(color: Color) -> glyphon::Color {
+    let r = (linear_to_srgb_channel(color.r) * 255.0 + 0.5) as u8;
+    let g = (linear_to_srgb_channel(color.g) * 255.0 + 0.5) as u8;
+    let b = (linear_to_srgb_channel(color.b) * 255.0 + 0.5) as u8;
+    let a = (color.a.clamp(0.0, 1.0) * 255.0 + 0.5) as u8;
+    glyphon::Color::rgba(r, g, b, a)
+}
+
+#[derive(PartialEq)]
+struct LruKey {
+    text: AttributedString,
+    color: Color,
+    font_size: f32,
+    line_height: f32,
+    font: FontSettings,
+    line_breaking: LineBreaking,
+    /// The final computed bounds, used as the cache key instead of constraint.
+    bounds: [u32; 2],
+}
+
+impl Eq for LruKey {}
+
+impl std::hash::Hash for LruKey {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.text.hash(state);
+        self.color.r.to_bits().hash(state);
+        self.color.g.to_bits().hash(state);
+        self.color.b.to_bits().hash(state);
+        self.color.a.to_bits().hash(state);
+        self.font_size.to_bits().hash(state);
+        self.line_height.to_bits().hash(state);
+        self.font.hash(state);
+        self.line_breaking.hash(state);
+        self.bounds.hash(state);
+    }
+}
+
+fn write_lru_cache() -> RwLockWriteGuard<'static, lru::LruCache<LruKey, TextData>> {
+    TEXT_DATA_CACHE
+        .get_or_init(|| {
+            RwLock::new(lru::LruCache::new(
+                NonZero::new(LRU_CAPACITY).expect("text cache size must be non-zero"),
+            ))
+        })
+        .write()
+}
+
+/// Drops all cached layouts, e.g. after fonts changed.
+pub(super) fn clear_text_cache() {
+    write_lru_cache().clear();
+}
+
+/// Text data for rendering, including buffer and size.
+#[derive(Debug, Clone)]
+pub struct TextData {
+    /// glyphon text buffer
+    text_buffer: glyphon::Buffer,
+    /// text area size
+    pub size: [u32; 2],
+    /// Baseline offset of the first visible line, relative to the text origin.
+    pub first_baseline: f32,
+    /// Baseline offset of the last visible line, relative to the text origin.
+    pub last_baseline: f32,
+    /// Number of visible layout lines.
+    pub line_count: u32,
+    /// Whether lines were dropped to fit the line limit or height.
+    pub truncated: bool,
+    /// Size of the drawn glyphs, larger than `size` for visible overflow.
+    draw_size: [u32; 2],
+    /// Offset mapping between `text` and the displayed text.
+    display_map: DisplayMap,
+    base_color: Color,
+    current_color: Color,
+    opacity: f32,
+    text: AttributedString,
+    font_size: f32,
+    line_height: f32,
+    font: FontSettings,
+    line_breaking: LineBreaking,
+}
+
+/// A line drawn under a span of text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextDecoration {
+    /// Bounds of the line, relative to the text origin.
+    pub rect: PxRect,
+    /// Color of the line.
+    pub color: Color,
+}
+
+/// Measurement result returned by `TextData::measure()`.
+#[derive(Debug, Clone, PartialEq, Copy)]
+pub struct TextMeasureInfo {
+    /// The computed size of the text area.
+    pub size: [u32; 2],
+    /// Baseline offset of the first visible line.
+    pub first_baseline: f32,
+    /// Baseline offset of the last visible line.
+    pub last_baseline: f32,
+    /// Number of visible layout lines.
+    pub line_count: u32,
+    /// Whether lines were dropped to fit the line limit or height, e.g. to
+    /// show the full text in a tooltip.
+    pub truncated: bool,
+}
+
+impl PartialEq for TextData {
+    fn eq(&self, other: &Self) -> bool {
+        self.size == other.size
+            && self.first_baseline == other.first_baseline
+            && self.last_baseline == other.last_baseline
+            && self.line_count == other.line_count
+            && self.truncated == other.truncated
+            && self.base_color == other.base_color
+            && self.current_color == other.current_color
+            && self.text == other.text
+            && self.font_size == other.font_size
+            && self.line_height == other.line_height
+            && self.font == other.font
+            && self.line_breaking == other.line_breaking
+    }
+}
+
+/// Result of shaping and fitting text into its constraint.
+struct ShapedText {
+    buffer: glyphon::Buffer,
+    size: [u32; 2],
+    draw_size: [u32; 2],
+    first_baseline: f32,
+    last_baseline: f32,
+    line_count: u32,
+    truncated: bool,
+    display_map: DisplayMap,
+}
+
+/// Shapes `text` without a height limit.
+fn shape_buffer(
+    text: &str,
+    metrics: glyphon::Metrics,
+    attrs: &glyphon::Attrs<'_>,
+    styles: &[(Range<usize>, glyphon::Attrs<'_>)],
+    chain: &[FontFamily],
+    max_width: Option<f32>,
+) -> glyphon::Buffer {
+    let mut font_system = write_font_system();
+    let mut buffer = glyphon::Buffer::new(&mut font_system, metrics);
+    // Break at words so joined scripts such as Arabic are not split
+    // mid-word; words wider than the line still break between glyphs.
+    buffer.set_wrap(&mut font_system, glyphon::Wrap::WordOrGlyph);
+    buffer.set_size(&mut font_system, max_width, None);
+    set_buffer_text(&mut buffer, &mut font_system, text, attrs, styles, chain);
+    buffer.shape_until_scroll(&mut font_system, false);
+    buffer
+}
+
+/// Returns the source offsets of the soft hyphens at which `buffer` wraps.
+fn soft_hyphen_breaks(
+    buffer: &glyphon::Buffer,
+    source: &str,
+    display: &str,
+    map: &DisplayMap,
+) -> Vec<usize> {
+    hit_test::line_metrics(buffer)
+        .windows(2)
+        .filter_map(|lines| {
+            let start = lines[1].range.start;
+            let previous = display.get(..start)?.chars().next_back()?;
+            let offset = map.to_source(start - previous.len_utf8());
+            source[offset..].starts_with(SOFT_HYPHEN).then_some(offset)
+        })
+        .collect()
+}
+
+impl TextData {
+    /// Measures text layout and caches the result.
+    ///
+    /// This method performs the expensive text shaping and layout computation,
+    /// stores the result in the LRU cache, and returns measurement information.
+    /// The cached result can later be retrieved using [`TextData::get()`].
+    ///
+    /// # Parameters
+    /// - `text`: The text, plain or an [`AttributedString`].
+    /// - `color`: The text color.
+    /// - `font_size`: Font size.
+    /// - `line_height`: Line height.
+    /// - `font`: Font family, weight, axes and features.
+    /// - `line_breaking`: Line limit, overflow and hyphenation.
+    /// - `constraint`: Text constraint for layout.
+    pub fn measure(
+        text: impl Into<AttributedString>,
+        color: Color,
+        font_size: f32,
+        line_height: f32,
+        font: &FontSettings,
+        line_breaking: LineBreaking,
+        constraint: TextConstraint,
+    ) -> TextMeasureInfo {
+        let text = text.into();
+        let shaped = Self::build_buffer(
+            &text,
+            color,
+            1.0,
+            font_size,
+            line_height,
+            font,
+            line_breaking,
+            &constraint,
+        );
+        let info = TextMeasureInfo {
+            size: shaped.size,
+            first_baseline: shaped.first_baseline,
+            last_baseline: shaped.last_baseline,
+            line_count: shaped.line_count,
+            truncated: shaped.truncated,
+        };
+
+        // Build cache key using bounds (not constraint)
+        let key = LruKey {
+            text: text.clone(),
+            color,
+            font_size,
+            line_height,
+            font: font.clone(),
+            line_breaking,
+            bounds: shaped.size,
+        };
+
+        // Store in cache
+        let data = Self::from_shaped(
+            shaped,
+            text,
+            color,
+            font_size,
+            line_height,
+            font,
+            line_breaking,
+        );
+        write_lru_cache().put(key, data);
+
+        info
+    }
+
+    /// Retrieves cached text data using the computed bounds.
+    ///
+    /// This method attempts to retrieve cached data from a prior
+    /// [`TextData::measure()`] call. If the cache entry was evicted (due to LRU
+    /// limits), it will automatically recompute the text layout using the
+    /// bounds as the constraint.
+    ///
+    /// # Parameters
+    /// - `text`: The text, plain or an [`AttributedString`].
+    /// - `color`: The text color.
+    /// - `font_size`: Font size.
+    /// - `line_height`: Line height.
+    /// - `font`: Font family, weight, axes and features.
+    /// - `line_breaking`: Line limit, overflow and hyphenation.
+    /// - `bounds`: The computed bounds from measurement (width, height).
+    pub fn get(
+        text: impl Into<AttributedString>,
+        color: Color,
+        font_size: f32,
+        line_height: f32,
+        font: &FontSettings,
+        line_breaking: LineBreaking,
+        bounds: [u32; 2],
+    ) -> Self {
+        let text = text.into();
+        let key = LruKey {
+            text: text.clone(),
+            color,
+            font_size,
+            line_height,
+            font: font.clone(),
+            line_breaking,
+            bounds,
+        };
+
+        // Try to get from cache first
+        if let Some(cached) = write_lru_cache().get(&key).cloned() {
+            return cached;
+        }
+
+        // Cache miss (possibly evicted by LRU), recompute using bounds as constraint
+        let constraint = TextConstraint {
+            max_width: Some(bounds[0] as f32),
+            max_height: Some(bounds[1] as f32),
+        };
+        let shaped = Self::build_buffer(
+            &text,
+            color,
+            1.0,
+            font_size,
+            line_height,
+            font,
+            line_breaking,
+            &constraint,
+        );
+        let data = Self::from_shaped(
+            shaped,
+            text,
+            color,
+            font_size,
+            line_height,
+            font,
+            line_breaking,
+        );
+
+        // Store back in cache
+        write_lru_cache().put(key, data.clone());
+        data
+    }
+
+    fn from_shaped(
+        shaped: ShapedText,
+        text: AttributedString,
+        color: Color,
+        font_size: f32,
+        line_height: f32,
+        font: &FontSettings,
+        line_breaking: LineBreaking,
+    ) -> Self {
+        Self {
+            text_buffer: shaped.buffer,
+            size: shaped.size,
+            first_baseline: shaped.first_baseline,
+            last_baseline: shaped.last_baseline,
+            line_count: shaped.line_count,
+            truncated: shaped.truncated,
+            draw_size: shaped.draw_size,
+            display_map: shaped.display_map,
+            base_color: color,
+            current_color: color,
+            opacity: 1.0,
+            text,
+            font_size,
+            line_height,
+            font: font.clone(),
+            line_breaking,
+        }
+    }
+
+    /// Builds [`TextData`] directly from a pre-shaped glyphon buffer.
+    pub fn from_buffer(text_buffer: glyphon::Buffer) -> Self {
+        // Calculate total height including descender for the last line
+        let metrics = text_buffer.metrics();
+        // Calculate text bounds
+        let mut run_width: f32 = 0.0;
+        let mut first_baseline = 0.0;
+        let mut last_baseline = 0.0;
+        let mut line_count: u32 = 0;
+        for run in text_buffer.layout_runs() {
+            // Take the max. width of all lines.
+            run_width = run_width.max(run.line_w);
+            if line_count == 0 {
+                first_baseline = run.line_y;
+            }
+            last_baseline = run.line_y;
+            line_count += 1;
+        }
+        let descent_amount = (metrics.line_height - metrics.font_size).max(0.0);
+        let total_height = line_count as f32 * metrics.line_height + descent_amount;
+        let size = [run_width as u32, total_height.ceil() as u32];
+        // build text data
+        Self {
+            text_buffer,
+            size,
+            first_baseline,
+            last_baseline,
+            line_count,
+            truncated: false,
+            draw_size: size,
+            display_map: DisplayMap::default(),
+            base_color: Color::WHITE,
+            current_color: Color::WHITE,
+            opacity: 1.0,
+            text: AttributedString::default(),
+            font_size: metrics.font_size,
+            line_height: metrics.line_height,
+            font: FontSettings::default(),
+            line_breaking: LineBreaking::NONE,
+        }
+    }
+
+    /// Sets `text` on an editor's `buffer`, styling its spans as
+    /// [`TextData::get`] does.
+    ///
+    /// Editors keep their own buffer so an edit reshapes only the changed
+    /// lines. Draw the buffer through [`TextData::from_attributed_buffer`].
+    pub fn set_attributed_buffer_text(
+        buffer: &mut glyphon::Buffer,
+        text: &AttributedString,
+        color: Color,
+        font: &FontSettings,
+    ) {
+        let chain = resolve_fallback_chain(&font.family);
+        let attrs = font.apply(glyphon::Attrs::new().color(color_to_glyphon(color)));
+        let styles = if text.is_plain() {
+            Vec::new()
+        } else {
+            text.attrs_runs(&attrs, buffer.metrics(), 1.0, &DisplayMap::default())
+        };
+        set_buffer_text(
+            buffer,
+            &mut write_font_system(),
+            text.text(),
+            &attrs,
+            &styles,
+            &chain,
+        );
+    }
+
+    /// Builds [`TextData`] from a buffer filled by
+    /// [`TextData::set_attributed_buffer_text`], so the underlines and links
+    /// of `text` resolve.
+    pub fn from_attributed_buffer(
+        text_buffer: glyphon::Buffer,
+        text: AttributedString,
+        color: Color,
+    ) -> Self {
+        Self {
+            base_color: color,
+            current_color: color,
+            text,
+            ..Self::from_buffer(text_buffer)
+        }
+    }
+
+    /// Returns the byte offset of the text closest to `position`, relative to
+    /// the text origin.
+    ///
+    /// Positions on an ellipsis map to the start of the truncated tail.
+    pub fn hit_test(&self, position: PxPosition) -> usize {
+        let offset =
+            hit_test::hit_test(&self.text_buffer, position.x.to_f32(), position.y.to_f32())
+                .unwrap_or_default();
+        self.display_map.to_source(offset)
+    }
+
+    /// Returns the highlight rectangles of the byte `range` of the text.
+    pub fn selection_rects(&self, range: Range<usize>) -> Vec<PxRect> {
+        let range =
+            self.display_map.to_display(range.start)..self.display_map.to_display(range.end);
+        hit_test::selection_rects(&self.text_buffer, range)
+    }
+
+    /// Returns the zero-width caret rectangle at byte `offset` of the text.
+    pub fn caret_rect(&self, offset: usize) -> Option<PxRect> {
+        hit_test::caret_rect(&self.text_buffer, self.display_map.to_display(offset))
+    }
+
+    /// Returns the metrics of every visible line.
+    pub fn line_metrics(&self) -> Vec<LineMetrics> {
+        let mut lines = hit_test::line_metrics(&self.text_buffer);
+        for line in &mut lines {
+            line.range = self.display_map.to_source(line.range.start)
+                ..self.display_map.to_source(line.range.end);
+        }
+        lines
+    }
+
+    /// Returns the bounds of every placeholder of the text, relative to the
+    /// text origin, or `None` for placeholders dropped by truncation.
+    pub fn placeholder_rects(&self) -> Vec<Option<PxRect>> {
+        let placeholders = self.text.placeholders();
+        let mut rects = vec![None; placeholders.len()];
+        for run in self
+            .text_buffer
+            .layout_runs()
+            .take(self.line_count as usize)
+        {
+            for glyph in run.glyphs {
+                let Some(index) = self.text.placeholder_for_metadata(glyph.metadata) else {
+                    continue;
+                };
+                let placeholder = &placeholders[index];
+                let top = match placeholder.alignment {
+                    PlaceholderAlignment::Baseline => run.line_y - placeholder.height,
+                    PlaceholderAlignment::Center => {
+                        run.line_top + (run.line_height - placeholder.height) / 2.0
+                    }
+                };
+                rects[index] = Some(PxRect::new(
+                    Px(glyph.x.round() as i32),
+                    Px(top.round() as i32),
+                    Px(placeholder.width.round() as i32),
+                    Px(placeholder.height.round() as i32),
+                ));
+            }
+        }
+        rects
+    }
+
+    /// Returns the underlines of the underlined spans, one per span and line.
+    pub fn decorations(&self) -> Vec<TextDecoration> {
+        let mut decorations = Vec::new();
+        let thickness = (self.font_size * UNDERLINE_THICKNESS).max(1.0);
+        for run in self
+            .text_buffer
+            .layout_runs()
+            .take(self.line_count as usize)
+        {
+            let mut segments: Vec<(usize, f32, f32)> = Vec::new();
+            for glyph in run.glyphs {
+                let Some(span) = self.text.span_for_metadata(glyph.metadata) else {
+                    continue;
+                };
+                if !span.style.underline {
+                    continue;
+                }
+                match segments.last_mut() {
+                    Some((metadata, start, end)) if *metadata == glyph.metadata => {
+                        *start = start.min(glyph.x);
+                        *end = end.max(glyph.x + glyph.w);
+                    }
+                    _ => segments.push((glyph.metadata, glyph.x, glyph.x + glyph.w)),
+                }
+            }
+            for (metadata, start, end) in segments {
+                let color = self
+                    .text
+                    .span_for_metadata(metadata)
+                    .and_then(|span| span.style.color)
+                    .unwrap_or(self.base_color);
+                let top = run.line_y + self.font_size * UNDERLINE_OFFSET;
+                let rect = PxRect::new(
+                    Px(start.floor() as i32),
+                    Px(top.round() as i32),
+                    Px((end.ceil() - start.floor()) as i32),
+                    Px(thickness.round() as i32),
+                );
+                decorations.push(TextDecoration {
+                    rect,
+                    color: color.with_alpha(color.a * self.opacity),
+                });
+            }
+        }
+        decorations
+    }
+
+    /// Returns the styled span drawn at `position`, relative to the text
+    /// origin, e.g. to resolve a clicked link.
+    pub fn span_at(&self, position: PxPosition) -> Option<&TextSpan> {
+        let (x, y) = (position.x.to_f32(), position.y.to_f32());
+        self.text_buffer
+            .layout_runs()
+            .take(self.line_count as usize)
+            .filter(|run| y >= run.line_top && y < run.line_top + run.line_height)
+            .flat_map(|run| run.glyphs.iter())
+            .find(|glyph| x >= glyph.x && x < glyph.x + glyph.w)
+            .and_then(|glyph| self.text.span_for_metadata(glyph.metadata))
+    }
+
+    /// Returns the link target of the span drawn at `position`.
+    pub fn link_at(&self, position: PxPosition) -> Option<&str> {
+        self.span_at(position)?.style.link.as_deref()
+    }
+
+    /// Get the glyphon text area from the text data
+    pub(super) fn text_area(&'_ self, start_pos: PxPosition) -> glyphon::TextArea<'_> {
+        let bounds = glyphon::TextBounds {
+            left: start_pos.x.raw(),
+            top: start_pos.y.raw(),
+            right: start_pos.x.raw() + self.draw_size[0] as i32,
+            bottom: start_pos.y.raw() + self.draw_size[1] as i32,
+        };
+        glyphon::TextArea {
+            buffer: &self.text_buffer,
+            left: start_pos.x.to_f32(),
+            top: start_pos.y.to_f32(),
+            scale: 1.0,
+            bounds,
+            default_color: color_to_glyphon(self.current_color),
+            custom_glyphs: &[],
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn build_buffer(
+        text: &AttributedString,
+        color: Color,
+        opacity: f32,
+        size: f32,
+        line_height: f32,
+        font: &FontSettings,
+        line_breaking: LineBreaking,
+        constraint: &TextConstraint,
+    ) -> ShapedText {
+        let source = text.text();
+        let chain = resolve_fallback_chain(&font.family);
+        let color = color.with_alpha(color.a * opacity);
+        let attrs = font.apply(glyphon::Attrs::new().color(color_to_glyphon(color)));
+        let metrics = glyphon::Metrics::new(size, line_height);
+        let shape = |display: &str, map: &DisplayMap| {
+            let styles = if text.is_plain() {
+                Vec::new()
+            } else {
+                text.attrs_runs(&attrs, metrics, opacity, map)
+            };
+            shape_buffer(
+                display,
+                metrics,
+                &attrs,
+                &styles,
+                &chain,
+                constraint.max_width,
+            )
+        };
+
+        let mut display = source.to_string();
+        let mut display_map = DisplayMap::default();
+        let mut buffer = shape(&display, &display_map);
+
+        // Drawing a hyphen can move the break, so settle the hyphenated
+        // breaks over a few passes.
+        if line_breaking.hyphens == Hyphens::Manual && source.contains(SOFT_HYPHEN) {
+            let mut hyphenated = Vec::new();
+            for _ in 0..3 {
+                let breaks = soft_hyphen_breaks(&buffer, source, &display, &display_map);
+                if breaks == hyphenated {
+                    break;
+                }
+                hyphenated = breaks;
+                (display, display_map) = DisplayMap::hyphenate(source, &hyphenated);
+                buffer = shape(&display, &display_map);
+            }
+        }
+
+        let lines = hit_test::line_metrics(&buffer);
+        let mut visible = lines.len();
+        if let Some(max_lines) = line_breaking.max_lines {
+            visible = visible.min(max_lines.max(1) as usize);
+        }
+        if let Some(max_height) = constraint.max_height {
+            let fitting = lines
+                .iter()
+                .take_while(|line| line.top + line.height <= max_height + 0.5)
+                .count();
+            visible = visible.min(fitting.max(1));
+        }
+        let truncated = visible < lines.len();
+
+        let descent_amount = (metrics.line_height - metrics.font_size).max(0.0);
+        let mut draw_height = None;
+        if truncated {
+            let last = &lines[visible - 1];
+            match line_breaking.overflow {
+                TextOverflow::Visible => {
+                    draw_height = lines
+                        .last()
+                        .map(|line| line.top + line.height + descent_amount);
+                }
+                TextOverflow::Clip => {
+                    display = display_map.truncate(&display, source.len(), last.range.end, "");
+                    buffer = shape(&display, &display_map);
+                }
+                TextOverflow::Ellipsis => {
+                    let suffix = line_breaking.ellipsis_str();
+                    let truncate = |cut: usize| {
+                        let cut = display[..cut].trim_end().len();
+                        let mut map = display_map.clone();
+                        let truncated = map.truncate(&display, source.len(), cut, suffix);
+                        (truncated, map)
+                    };
+                    let fits = |cut: usize| {
+                        let (candidate, map) = truncate(cut);
+                        shape(&candidate, &map).layout_runs().count() <= visible
+                    };
+                    let cuts: Vec<usize> = display[last.range.start..last.range.end]
+                        .grapheme_indices(true)
+                        .map(|(offset, _)| last.range.start + offset)
+                        .chain([last.range.end])
+                        .collect();
+                    let fitting = cuts.partition_point(|&cut| fits(cut));
+                    let (truncated, map) = truncate(cuts[fitting.saturating_sub(1)]);
+                    display = truncated;
+                    display_map = map;
+                    buffer = shape(&display, &display_map);
+                }
+            }
+        }
+
+        // Calculate text bounds and baselines. Lines holding placeholders can
+        // be taller than the line height.
+        let mut run_width: f32 = 0.0;
+        let mut first_baseline = 0.0;
+        let mut last_baseline = 0.0;
+        let mut bottom = 0.0;
+        let mut line_count: u32 = 0;
+        for run in buffer.layout_runs().take(visible) {
+            run_width = run_width.max(run.line_w);
+            if line_count == 0 {
+                first_baseline = run.line_y;
+            }
+            last_baseline = run.line_y;
+            bottom = run.line_top + run.line_height;
+            line_count += 1;
+        }
+        let total_height = bottom + descent_amount;
+        let size = [run_width.ceil() as u32, total_height.ceil() as u32];
+        let draw_size = match draw_height {
+            Some(height) => {
+                let width = buffer
+                    .layout_runs()
+                    .map(|run| run.line_w)
+                    .fold(run_width, f32::max);
+                [width.ceil() as u32, height.ceil() as u32]
+            }
+            None => size,
+        };
+        ShapedText {
+            buffer,
+            size,
+            draw_size,
+            first_baseline,
+            last_baseline,
+            line_count,
+            truncated,
+            display_map,
+        }
+    }
+
+    pub(crate) fn apply_opacity(&mut self, opacity: f32) {
+        let target_alpha = (self.base_color.a * opacity).clamp(0.0, 1.0);
+        let target_color = self.base_color.with_alpha(target_alpha);
+        if (target_color.a - self.current_color.a).abs() <= f32::EPSILON
+            && (target_color.r - self.current_color.r).abs() <= f32::EPSILON
+            && (target_color.g - self.current_color.g).abs() <= f32::EPSILON
+            && (target_color.b - self.current_color.b).abs() <= f32::EPSILON
+        {
+            return;
+        }
+
+        // Use the current size as constraint for rebuilding with new color
+        let constraint = TextConstraint {
+            max_width: Some(self.size[0] as f32),
+            max_height: Some(self.size[1] as f32),
+        };
+        let shaped = Self::build_buffer(
+            &self.text,
+            self.base_color,
+            opacity,
+            self.font_size,
+            self.line_height,
+            &self.font,
+            self.line_breaking,
+            &constraint,
+        );
+        self.text_buffer = shaped.buffer;
+        self.size = shaped.size;
+        self.draw_size = shaped.draw_size;
+        self.first_baseline = shaped.first_baseline;
+        self.last_baseline = shaped.last_baseline;
+        self.line_count = shaped.line_count;
+        self.truncated = shaped.truncated;
+        self.display_map = shaped.display_map;
+        self.current_color = target_color;
+        self.opacity = opacity;
+    }
+}
@@ -0,0 +1,194 @@
+//! Line limits, overflow handling and soft-hyphen hyphenation.
+
+use std::ops::Range;
+
+/// The soft hyphen, an invisible break opportunity.
+pub(super) const SOFT_HYPHEN: char = '\u{AD}';
+/// Drawn in place of a soft hyphen that ends a line.
+const HYPHEN: &str = "-";
+/// Drawn after the last visible character of ellipsized text.
+const ELLIPSIS: &str = "\u{2026}";
+
+/// How text that does not fit its line limit or height is shown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TextOverflow {
+    /// Drops the lines that do not fit.
+    #[default]
+    Clip,
+    /// Drops the lines that do not fit and ends the last visible line with an
+    /// ellipsis.
+    Ellipsis,
+    /// Reports the limited size but draws every line, past the text bounds.
+    Visible,
+}
+
+/// Whether words are hyphenated at line breaks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Hyphens {
+    /// Soft hyphens (U+00AD) are break opportunities, drawn as nothing.
+    #[default]
+    None,
+    /// Soft hyphens (U+00AD) that end a line are drawn as a hyphen.
+    Manual,
+}
+
+/// Line limit and overflow settings for a text layout.
+///
+/// Whether lines were dropped is reported as
+/// [`TextMeasureInfo::truncated`](super::TextMeasureInfo::truncated).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct LineBreaking {
+    /// Maximum number of visible lines; `None` shows as many as the height
+    /// allows.
+    pub max_lines: Option<u32>,
+    /// How lines past the limit are shown.
+    pub overflow: TextOverflow,
+    /// Hyphenation at line breaks.
+    pub hyphens: Hyphens,
+}
+
+impl LineBreaking {
+    /// Unlimited lines, clipped to the height.
+    pub const NONE: Self = Self {
+        max_lines: None,
+        overflow: TextOverflow::Clip,
+        hyphens: Hyphens::None,
+    };
+
+    /// Limits the text to `max_lines`, ending the last one with an ellipsis.
+    pub const fn ellipsis(max_lines: u32) -> Self {
+        Self {
+            max_lines: Some(max_lines),
+            overflow: TextOverflow::Ellipsis,
+            hyphens: Hyphens::None,
+        }
+    }
+
+    pub(super) fn ellipsis_str(&self) -> &'static str {
+        match self.overflow {
+            TextOverflow::Ellipsis => ELLIPSIS,
+            TextOverflow::Clip | TextOverflow::Visible => "",
+        }
+    }
+}
+
+/// A replaced span of the source text.
+#[derive(Debug, Clone, PartialEq)]
+struct Edit {
+    source: Range<usize>,
+    display_len: usize,
+}
+
+/// Maps byte offsets between the source text and the displayed text, which
+/// differ where soft hyphens became hyphens or the tail was truncated.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(super) struct DisplayMap {
+    /// Non-overlapping edits, ordered by source offset.
+    edits: Vec<Edit>,
+}
+
+impl DisplayMap {
+    /// Maps a source offset to the displayed text. Offsets inside a replaced
+    /// span map to its start.
+    pub(super) fn to_display(&self, offset: usize) -> usize {
+        let mut display = offset as isize;
+        for edit in &self.edits {
+            if offset >= edit.source.end {
+                display += edit.display_len as isize - edit.source.len() as isize;
+            } else if offset > edit.source.start {
+                return (edit.source.start as isize + display - offset as isize) as usize;
+            } else {
+                break;
+            }
+        }
+        display as usize
+    }
+
+    /// Maps an offset of the displayed text to the source. Offsets inside a
+    /// replacement map to the start of the replaced span.
+    pub(super) fn to_source(&self, offset: usize) -> usize {
+        let mut delta = 0isize;
+        for edit in &self.edits {
+            let start = (edit.source.start as isize + delta) as usize;
+            let end = start + edit.display_len;
+            if offset >= end {
+                delta += edit.display_len as isize - edit.source.len() as isize;
+            } else if offset > start {
+                return edit.source.start;
+            } else {
+                break;
+            }
+        }
+        (offset as isize - delta) as usize
+    }
+
+    /// Returns the displayed text for `source` with the soft hyphens at the
+    /// given source offsets drawn as hyphens.
+    pub(super) fn hyphenate(source: &str, soft_hyphens: &[usize]) -> (String, Self) {
+        let mut display = String::with_capacity(source.len() + soft_hyphens.len());
+        let mut edits = Vec::with_capacity(soft_hyphens.len());
+        let mut copied = 0;
+        for &offset in soft_hyphens {
+            display.push_str(&source[copied..offset]);
+            display.push_str(HYPHEN);
+            copied = offset + SOFT_HYPHEN.len_utf8();
+            edits.push(Edit {
+                source: offset..copied,
+                display_len: HYPHEN.len(),
+            });
+        }
+        display.push_str(&source[copied..]);
+        (display, Self { edits })
+    }
+
+    /// Cuts `display` at display offset `cut` and appends `suffix`, recording
+    /// the dropped source tail.
+    pub(super) fn truncate(
+        &mut self,
+        display: &str,
+        source_len: usize,
+        cut: usize,
+        suffix: &str,
+    ) -> String {
+        let source_cut = self.to_source(cut);
+        self.edits.retain(|edit| edit.source.end <= source_cut);
+        self.edits.push(Edit {
+            source: source_cut..source_len,
+            display_len: suffix.len(),
+        });
+        let mut truncated = String::with_capacity(cut + suffix.len());
+        truncated.push_str(&display[..cut]);
+        truncated.push_str(suffix);
+        truncated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hyphenated_offsets_map_both_ways() {
+        let source = "hy\u{AD}phen\u{AD}ated";
+        let (display, map) = DisplayMap::hyphenate(source, &[2]);
+        assert_eq!(display, "hy-phen\u{AD}ated");
+        assert_eq!(map.to_display(2), 2);
+        assert_eq!(map.to_display(3), 2);
+        assert_eq!(map.to_display(4), 3);
+        assert_eq!(map.to_display(source.len()), display.len());
+        assert_eq!(map.to_source(3), 4);
+        assert_eq!(map.to_source(display.len()), source.len());
+    }
+
+    #[test]
+    fn truncation_maps_the_dropped_tail_to_the_suffix() {
+        let source = "hy\u{AD}phen tail";
+        let (display, mut map) = DisplayMap::hyphenate(source, &[2]);
+        let truncated = map.truncate(&display, source.len(), 7, ELLIPSIS);
+        assert_eq!(truncated, "hy-phen\u{2026}");
+        assert_eq!(map.to_source(7), 8);
+        assert_eq!(map.to_source(truncated.len()), source.len());
+        assert_eq!(map.to_display(10), 7);
+        assert_eq!(map.to_display(source.len()), truncated.len());
+    }
+}
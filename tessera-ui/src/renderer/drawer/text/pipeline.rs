@@ -0,0 +1,125 @@
+use crate::{
+    PxPosition,
+    renderer::drawer::{
+        clip_depth_stencil_state,
+        pipeline::{DrawContext, DrawablePipeline, FrameContext},
+    },
+};
+
+use super::{TextCommand, font::write_font_system};
+
+/// Renders [`TextCommand`]s through a glyph atlas.
+///
+/// Glyphs are rasterized on the CPU with swash at their subpixel offset and
+/// uploaded into a shared atlas, which is trimmed between frames. Outline
+/// glyphs are stored as coverage masks and tinted with the text color; color
+/// glyphs from COLR, CBDT, sbix or SVG fonts are stored as RGBA, with bitmap
+/// strikes resampled from the nearest strike to the requested size.
+pub(crate) struct TextPipeline {
+    /// Glyphon font atlas, a heavy-weight, shared resource.
+    atlas: glyphon::TextAtlas,
+    /// Glyphon viewport, holds screen-size related buffers.
+    viewport: glyphon::Viewport,
+    /// Glyphon swash cache, a CPU-side cache for glyph rasterization.
+    swash_cache: glyphon::SwashCache,
+    /// Multisample state for anti-aliasing.
+    msaa: wgpu::MultisampleState,
+    /// Glyphon text renderers, one per draw in the current frame.
+    ///
+    /// Each renderer's vertex buffer is read when the frame is submitted, so
+    /// draws within a frame can't share one. The pool only grows when a frame
+    /// draws text in more batches than any frame before it.
+    renderers: Vec<glyphon::TextRenderer>,
+    /// Number of renderers used by the current frame.
+    renderers_used: usize,
+}
+
+impl TextPipeline {
+    pub(crate) fn new(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        format: wgpu::TextureFormat,
+        sample_count: u32,
+    ) -> Self {
+        let cache = glyphon::Cache::new(device);
+        let mut atlas = glyphon::TextAtlas::new(device, queue, &cache, format);
+        let viewport = glyphon::Viewport::new(device, &cache);
+        let swash_cache = glyphon::SwashCache::new();
+        let msaa = wgpu::MultisampleState {
+            count: sample_count,
+            mask: !0,
+            alpha_to_coverage_enabled: false,
+        };
+        let renderer =
+            glyphon::TextRenderer::new(&mut atlas, device, msaa, Some(clip_depth_stencil_state()));
+
+        Self {
+            atlas,
+            viewport,
+            swash_cache,
+            msaa,
+            renderers: vec![renderer],
+            renderers_used: 0,
+        }
+    }
+}
+
+impl DrawablePipeline<TextCommand> for TextPipeline {
+    fn begin_frame(&mut self, _context: &FrameContext<'_>) {
+        // Drop glyphs that were not used by the previous frame.
+        self.atlas.trim();
+        self.renderers_used = 0;
+    }
+
+    fn draw(&mut self, context: &mut DrawContext<TextCommand>) {
+        if context.commands.is_empty() {
+            return;
+        }
+
+        self.viewport.update(
+            context.queue,
+            glyphon::Resolution {
+                width: context.target_size.width.positive(),
+                height: context.target_size.height.positive(),
+            },
+        );
+
+        let text_areas = context.commands.iter().map(|(command, _size, start_pos)| {
+            let start_pos = PxPosition::new(
+                start_pos.x + command.offset.x,
+                start_pos.y + command.offset.y,
+            );
+            command.data.text_area(start_pos)
+        });
+
+        if self.renderers_used == self.renderers.len() {
+            self.renderers.push(glyphon::TextRenderer::new(
+                &mut self.atlas,
+                context.device,
+                self.msaa,
+                Some(clip_depth_stencil_state()),
+            ));
+        }
+        let renderer = &mut self.renderers[self.renderers_used];
+        self.renderers_used += 1;
+
+        let mut font_system = write_font_system();
+        renderer
+            .prepare(
+                glyphon::PrepareContext::new(
+                    context.device,
+                    context.queue,
+                    &mut font_system,
+                    &mut self.atlas,
+                    &self.viewport,
+                    &mut self.swash_cache,
+                ),
+                text_areas,
+            )
+            .expect("glyphon prepare failed");
+
+        renderer
+            .render(&self.atlas, &self.viewport, context.render_pass)
+            .expect("glyphon render failed");
+    }
+}
@@ -0,0 +1,362 @@
+//! Attributed strings with styled spans, links and inline placeholders.
+
+use std::{ops::Range, sync::Arc};
+
+use crate::Color;
+
+use super::{overflow::DisplayMap, style::FontWeight};
+
+/// Stands in for an inline placeholder in the text.
+pub const OBJECT_REPLACEMENT: char = '\u{FFFC}';
+
+/// Placeholders are shaped at this font size so their glyph advance is
+/// negligible and the letter spacing, in ems, equals the width in pixels.
+const PLACEHOLDER_FONT_SIZE: f32 = 1.0;
+
+/// Style overrides for a span of an [`AttributedString`].
+///
+/// Unset fields inherit the style of the whole text.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpanStyle {
+    /// Text color.
+    pub color: Option<Color>,
+    /// Font weight.
+    pub weight: Option<FontWeight>,
+    /// Whether the span is drawn in italics.
+    pub italic: bool,
+    /// Whether the span is underlined.
+    pub underline: bool,
+    /// Link target reported when the span is clicked.
+    pub link: Option<Arc<str>>,
+}
+
+impl SpanStyle {
+    /// Creates a style that inherits everything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the text color.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Sets the font weight.
+    pub fn weight(mut self, weight: FontWeight) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    /// Draws the span in italics.
+    pub fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+
+    /// Underlines the span.
+    pub fn underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+
+    /// Marks the span as a link to `target`.
+    pub fn link(mut self, target: impl Into<Arc<str>>) -> Self {
+        self.link = Some(target.into());
+        self
+    }
+}
+
+impl std::hash::Hash for SpanStyle {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.color
+            .map(|color| [color.r, color.g, color.b, color.a].map(f32::to_bits))
+            .hash(state);
+        self.weight.hash(state);
+        self.italic.hash(state);
+        self.underline.hash(state);
+        self.link.hash(state);
+    }
+}
+
+/// A styled byte range of an [`AttributedString`].
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct TextSpan {
+    /// Byte range of the span in the text.
+    pub range: Range<usize>,
+    /// Style of the span.
+    pub style: SpanStyle,
+}
+
+/// Vertical alignment of an inline placeholder within its line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PlaceholderAlignment {
+    /// The bottom of the box sits on the baseline.
+    #[default]
+    Baseline,
+    /// The box is centered in the line box.
+    Center,
+}
+
+/// An inline box reserved in the text, e.g. for an icon or a mention chip.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placeholder {
+    /// Key identifying the placeholder to the component filling it.
+    pub key: Arc<str>,
+    /// Byte offset of the placeholder's [`OBJECT_REPLACEMENT`] character.
+    pub offset: usize,
+    /// Width of the box in pixels.
+    pub width: f32,
+    /// Height of the box in pixels.
+    pub height: f32,
+    /// Vertical alignment of the box.
+    pub alignment: PlaceholderAlignment,
+}
+
+impl std::hash::Hash for Placeholder {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.key.hash(state);
+        self.offset.hash(state);
+        self.width.to_bits().hash(state);
+        self.height.to_bits().hash(state);
+        self.alignment.hash(state);
+    }
+}
+
+/// Text with styled spans and inline placeholders.
+///
+/// Each placeholder occupies one [`OBJECT_REPLACEMENT`] character, so byte
+/// offsets from hit-testing index [`AttributedString::text`] directly.
+/// Placeholders are laid out with the size set by
+/// [`AttributedString::set_placeholder_size`], typically the measured size of
+/// the child drawn in their place.
+///
+/// # Example
+///
+/// ```
+/// use tessera_ui::renderer::drawer::text::{
+///     AttributedString, FontWeight, PlaceholderAlignment, SpanStyle,
+/// };
+///
+/// let text = AttributedString::new()
+///     .push("Read the ")
+///     .push_styled(
+///         "docs",
+///         SpanStyle::new()
+///             .underline()
+///             .link("https://tessera-ui.github.io"),
+///     )
+///     .push(" or ask ")
+///     .push_styled("@tessera", SpanStyle::new().weight(FontWeight::BOLD))
+///     .push_placeholder("badge", PlaceholderAlignment::Center);
+/// assert_eq!(text.spans().len(), 2);
+/// assert_eq!(text.placeholders()[0].offset, text.text().len() - 3);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Hash)]
+pub struct AttributedString {
+    text: String,
+    spans: Vec<TextSpan>,
+    placeholders: Vec<Placeholder>,
+}
+
+impl AttributedString {
+    /// Creates an empty string.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends unstyled text.
+    pub fn push(mut self, text: &str) -> Self {
+        self.text.push_str(text);
+        self
+    }
+
+    /// Appends text drawn with `style`.
+    pub fn push_styled(mut self, text: &str, style: SpanStyle) -> Self {
+        let start = self.text.len();
+        self.text.push_str(text);
+        if !text.is_empty() {
+            self.spans.push(TextSpan {
+                range: start..self.text.len(),
+                style,
+            });
+        }
+        self
+    }
+
+    /// Appends an inline placeholder identified by `key`, with an empty size
+    /// until [`AttributedString::set_placeholder_size`] is called.
+    pub fn push_placeholder(
+        mut self,
+        key: impl Into<Arc<str>>,
+        alignment: PlaceholderAlignment,
+    ) -> Self {
+        self.placeholders.push(Placeholder {
+            key: key.into(),
+            offset: self.text.len(),
+            width: 0.0,
+            height: 0.0,
+            alignment,
+        });
+        self.text.push(OBJECT_REPLACEMENT);
+        self
+    }
+
+    /// Sets the size of the placeholder at `index`, in pixels.
+    pub fn set_placeholder_size(&mut self, index: usize, width: f32, height: f32) {
+        if let Some(placeholder) = self.placeholders.get_mut(index) {
+            placeholder.width = width.max(0.0);
+            placeholder.height = height.max(0.0);
+        }
+    }
+
+    /// Returns the plain text, with one [`OBJECT_REPLACEMENT`] per
+    /// placeholder.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the styled spans, ordered by offset.
+    pub fn spans(&self) -> &[TextSpan] {
+        &self.spans
+    }
+
+    /// Returns the placeholders, ordered by offset.
+    pub fn placeholders(&self) -> &[Placeholder] {
+        &self.placeholders
+    }
+
+    /// Returns the span containing byte `offset`.
+    pub fn span_at(&self, offset: usize) -> Option<&TextSpan> {
+        self.spans.iter().find(|span| span.range.contains(&offset))
+    }
+
+    /// Returns whether the string has neither spans nor placeholders.
+    pub fn is_plain(&self) -> bool {
+        self.spans.is_empty() && self.placeholders.is_empty()
+    }
+
+    /// Returns the span with glyph `metadata`, as set by
+    /// [`AttributedString::attrs_runs`].
+    pub(super) fn span_for_metadata(&self, metadata: usize) -> Option<&TextSpan> {
+        metadata
+            .checked_sub(1)
+            .and_then(|index| self.spans.get(index))
+    }
+
+    /// Returns the index of the placeholder with glyph `metadata`.
+    pub(super) fn placeholder_for_metadata(&self, metadata: usize) -> Option<usize> {
+        metadata
+            .checked_sub(self.spans.len() + 1)
+            .filter(|index| *index < self.placeholders.len())
+    }
+
+    /// Returns the attributes of every span and placeholder as byte ranges of
+    /// the displayed text. Glyphs carry the span index plus one, or the
+    /// placeholder index after the spans, as metadata.
+    pub(super) fn attrs_runs<'a>(
+        &self,
+        attrs: &glyphon::Attrs<'a>,
+        metrics: glyphon::Metrics,
+        opacity: f32,
+        map: &DisplayMap,
+    ) -> Vec<(Range<usize>, glyphon::Attrs<'a>)> {
+        let mut runs = Vec::with_capacity(self.spans.len() + self.placeholders.len());
+        for (index, span) in self.spans.iter().enumerate() {
+            let mut span_attrs = attrs.clone().metadata(index + 1);
+            if let Some(color) = span.style.color {
+                let color = color.with_alpha(color.a * opacity);
+                span_attrs = span_attrs.color(super::layout::color_to_glyphon(color));
+            }
+            if let Some(weight) = span.style.weight {
+                span_attrs = span_attrs.weight(glyphon::Weight(weight.0));
+            }
+            if span.style.italic {
+                span_attrs = span_attrs.style(glyphon::Style::Italic);
+            }
+            runs.push((span.range.clone(), span_attrs));
+        }
+        let descent = (metrics.line_height - metrics.font_size).max(0.0);
+        for (index, placeholder) in self.placeholders.iter().enumerate() {
+            let height = match placeholder.alignment {
+                PlaceholderAlignment::Baseline => placeholder.height + descent,
+                PlaceholderAlignment::Center => placeholder.height,
+            };
+            let placeholder_attrs = attrs
+                .clone()
+                .metadata(self.spans.len() + index + 1)
+                .color(glyphon::Color::rgba(0, 0, 0, 0))
+                .metrics(glyphon::Metrics::new(
+                    PLACEHOLDER_FONT_SIZE,
+                    metrics.line_height.max(height),
+                ))
+                .letter_spacing(placeholder.width / PLACEHOLDER_FONT_SIZE);
+            let end = placeholder.offset + OBJECT_REPLACEMENT.len_utf8();
+            runs.push((placeholder.offset..end, placeholder_attrs));
+        }
+        runs.sort_by_key(|(range, _)| range.start);
+
+        runs.into_iter()
+            .filter_map(|(range, attrs)| {
+                let range = map.to_display(range.start)..map.to_display(range.end);
+                (!range.is_empty()).then_some((range, attrs))
+            })
+            .collect()
+    }
+}
+
+impl From<String> for AttributedString {
+    fn from(text: String) -> Self {
+        Self {
+            text,
+            ..Self::default()
+        }
+    }
+}
+
+impl From<&str> for AttributedString {
+    fn from(text: &str) -> Self {
+        Self::from(text.to_string())
+    }
+}
+
+impl From<&String> for AttributedString {
+    fn from(text: &String) -> Self {
+        Self::from(text.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_records_span_and_placeholder_offsets() {
+        let text = AttributedString::new()
+            .push("a ")
+            .push_styled("bold", SpanStyle::new().weight(FontWeight::BOLD))
+            .push_styled("", SpanStyle::new().underline())
+            .push_placeholder("icon", PlaceholderAlignment::Baseline)
+            .push("!");
+        assert_eq!(text.text(), "a bold\u{FFFC}!");
+        assert_eq!(text.spans().len(), 1);
+        assert_eq!(text.spans()[0].range, 2..6);
+        assert_eq!(text.placeholders()[0].offset, 6);
+        assert_eq!(text.span_at(3).map(|span| span.range.clone()), Some(2..6));
+        assert!(text.span_at(6).is_none());
+    }
+
+    #[test]
+    fn glyph_metadata_maps_back_to_spans_and_placeholders() {
+        let text = AttributedString::new()
+            .push_styled("x", SpanStyle::new().italic())
+            .push_placeholder("a", PlaceholderAlignment::Center)
+            .push_placeholder("b", PlaceholderAlignment::Center);
+        assert!(text.span_for_metadata(0).is_none());
+        assert!(text.span_for_metadata(1).is_some());
+        assert_eq!(text.placeholder_for_metadata(1), None);
+        assert_eq!(text.placeholder_for_metadata(2), Some(0));
+        assert_eq!(text.placeholder_for_metadata(3), Some(1));
+        assert_eq!(text.placeholder_for_metadata(4), None);
+    }
+}
@@ -0,0 +1,248 @@
+//! Font weight, variation axes and OpenType features.
+
+use glyphon::cosmic_text::{FeatureTag, FontFeatures};
+
+use super::FontFamily;
+
+/// Weight of a font, from 1 to 1000.
+///
+/// Variable fonts with a `wght` axis are rendered at the exact weight; other
+/// families select their closest face.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FontWeight(pub u16);
+
+impl FontWeight {
+    /// Thin, 100.
+    pub const THIN: Self = Self(100);
+    /// Extra light, 200.
+    pub const EXTRA_LIGHT: Self = Self(200);
+    /// Light, 300.
+    pub const LIGHT: Self = Self(300);
+    /// Normal, 400.
+    pub const NORMAL: Self = Self(400);
+    /// Medium, 500.
+    pub const MEDIUM: Self = Self(500);
+    /// Semi bold, 600.
+    pub const SEMI_BOLD: Self = Self(600);
+    /// Bold, 700.
+    pub const BOLD: Self = Self(700);
+    /// Extra bold, 800.
+    pub const EXTRA_BOLD: Self = Self(800);
+    /// Black, 900.
+    pub const BLACK: Self = Self(900);
+}
+
+impl Default for FontWeight {
+    fn default() -> Self {
+        Self::NORMAL
+    }
+}
+
+/// A coordinate on a variable font axis.
+///
+/// Only axes that reach both shaping and the glyph cache are accepted:
+/// `wght`, rendered at the exact weight, and `ital`, which selects the italic
+/// face from 0.5 up. Other axes such as `slnt` or `opsz` are rejected, since
+/// the shaper can only render them at the font's default coordinate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FontVariation {
+    tag: [u8; 4],
+    value: f32,
+}
+
+impl FontVariation {
+    /// Axis tags accepted by [`FontVariation::new`].
+    pub const SUPPORTED_AXES: [[u8; 4]; 2] = [*b"wght", *b"ital"];
+
+    /// Creates an axis coordinate, or returns `None` for an axis outside
+    /// [`Self::SUPPORTED_AXES`].
+    pub fn new(tag: &[u8; 4], value: f32) -> Option<Self> {
+        Self::SUPPORTED_AXES
+            .contains(tag)
+            .then_some(Self { tag: *tag, value })
+    }
+
+    /// Creates a `wght` coordinate.
+    pub const fn weight(value: f32) -> Self {
+        Self {
+            tag: *b"wght",
+            value,
+        }
+    }
+
+    /// Creates an `ital` coordinate.
+    pub const fn italic(value: f32) -> Self {
+        Self {
+            tag: *b"ital",
+            value,
+        }
+    }
+
+    /// Returns the four byte axis tag.
+    pub fn tag(&self) -> [u8; 4] {
+        self.tag
+    }
+
+    /// Returns the axis coordinate in the axis' own units.
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+}
+
+impl std::hash::Hash for FontVariation {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.tag.hash(state);
+        self.value.to_bits().hash(state);
+    }
+}
+
+/// An OpenType feature setting, such as `tnum` on or `liga` off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FontFeature {
+    /// Four byte feature tag.
+    pub tag: [u8; 4],
+    /// Feature value; 0 disables the feature, 1 enables it and larger values
+    /// pick an alternate.
+    pub value: u32,
+}
+
+impl FontFeature {
+    /// Creates a feature setting.
+    pub const fn new(tag: &[u8; 4], value: u32) -> Self {
+        Self { tag: *tag, value }
+    }
+
+    /// Enables a feature.
+    pub const fn enable(tag: &[u8; 4]) -> Self {
+        Self::new(tag, 1)
+    }
+
+    /// Disables a feature.
+    pub const fn disable(tag: &[u8; 4]) -> Self {
+        Self::new(tag, 0)
+    }
+}
+
+/// Font selection and shaping settings for a piece of text.
+///
+/// Settings take part in the layout cache key, so texts that differ only in
+/// their features or axes are shaped and cached separately. The weight and
+/// face they resolve to are part of the glyph cache key.
+///
+/// The `wght` axis overrides [`FontSettings::weight`], and an `ital`
+/// coordinate of at least 0.5 selects an italic face.
+#[derive(Debug, Clone, Default, PartialEq, Hash)]
+pub struct FontSettings {
+    /// Font family, resolved through the [`FontRegistry`] fallback chain.
+    ///
+    /// [`FontRegistry`]: super::FontRegistry
+    pub family: FontFamily,
+    /// Font weight.
+    pub weight: FontWeight,
+    /// Variable font axis coordinates.
+    pub variations: Vec<FontVariation>,
+    /// OpenType feature settings.
+    pub features: Vec<FontFeature>,
+}
+
+impl FontSettings {
+    /// Creates settings for `family` with the normal weight.
+    pub fn new(family: impl Into<FontFamily>) -> Self {
+        Self {
+            family: family.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Sets the font weight.
+    pub fn weight(mut self, weight: FontWeight) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Adds a variable font axis coordinate, replacing one with the same tag.
+    pub fn variation(mut self, variation: FontVariation) -> Self {
+        self.variations
+            .retain(|existing| existing.tag != variation.tag);
+        self.variations.push(variation);
+        self
+    }
+
+    /// Adds an OpenType feature setting, replacing one with the same tag.
+    pub fn feature(mut self, feature: FontFeature) -> Self {
+        self.features.retain(|existing| existing.tag != feature.tag);
+        self.features.push(feature);
+        self
+    }
+
+    /// Applies the weight, style and features to `attrs`. The family is set
+    /// per fallback run when the buffer text is set.
+    pub(crate) fn apply<'a>(&self, attrs: glyphon::Attrs<'a>) -> glyphon::Attrs<'a> {
+        let mut weight = self.weight.0;
+        let mut style = glyphon::Style::Normal;
+        for variation in &self.variations {
+            match &variation.tag {
+                b"wght" => weight = variation.value.round().clamp(1.0, 1000.0) as u16,
+                b"ital" if variation.value >= 0.5 => style = glyphon::Style::Italic,
+                _ => {}
+            }
+        }
+
+        let mut attrs = attrs.weight(glyphon::Weight(weight)).style(style);
+        if !self.features.is_empty() {
+            let mut features = FontFeatures::new();
+            for feature in &self.features {
+                features.set(FeatureTag::new(&feature.tag), feature.value);
+            }
+            attrs = attrs.font_features(features);
+        }
+        attrs
+    }
+}
+
+impl From<FontFamily> for FontSettings {
+    fn from(family: FontFamily) -> Self {
+        Self::new(family)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_replaces_settings_with_the_same_tag() {
+        let settings = FontSettings::new(FontFamily::Monospace)
+            .variation(FontVariation::weight(300.0))
+            .variation(FontVariation::weight(650.0))
+            .feature(FontFeature::enable(b"tnum"))
+            .feature(FontFeature::disable(b"liga"))
+            .feature(FontFeature::disable(b"tnum"));
+        assert_eq!(settings.variations, vec![FontVariation::weight(650.0)]);
+        assert_eq!(
+            settings.features,
+            vec![FontFeature::disable(b"liga"), FontFeature::disable(b"tnum")]
+        );
+    }
+
+    #[test]
+    fn weight_axis_overrides_weight() {
+        let settings = FontSettings::default()
+            .weight(FontWeight::BOLD)
+            .variation(FontVariation::weight(550.4))
+            .variation(FontVariation::italic(1.0));
+        let attrs = settings.apply(glyphon::Attrs::new());
+        assert_eq!(attrs.weight, glyphon::Weight(550));
+        assert_eq!(attrs.style, glyphon::Style::Italic);
+    }
+
+    #[test]
+    fn unsupported_axes_are_rejected() {
+        assert_eq!(
+            FontVariation::new(b"wght", 500.0),
+            Some(FontVariation::weight(500.0))
+        );
+        assert_eq!(FontVariation::new(b"slnt", -10.0), None);
+        assert_eq!(FontVariation::new(b"opsz", 14.0), None);
+    }
+}
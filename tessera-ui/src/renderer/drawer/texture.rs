@@ -0,0 +1,90 @@
+//! Draw command for imported textures.
+//!
+//! [`TextureCommand`] draws an [`ImportedTexture`] stretched over the command
+//! bounds. Because the command references the import handle rather than a
+//! specific texture, producers can call [`ImportedTexture::replace`] for every
+//! new video or camera frame without rebuilding the component tree; the next
+//! rendered frame samples the latest texture.
+//!
+//! The pipeline for [`TextureCommand`] is registered by the renderer itself.
+
+mod pipeline;
+
+use crate::{
+    Color,
+    renderer::{drawer::DrawCommand, external::ImportedTexture},
+};
+
+pub(crate) use pipeline::TexturePipeline;
+
+/// How the color channels of an imported texture relate to its alpha.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TextureAlphaMode {
+    /// Color channels are not multiplied by alpha.
+    #[default]
+    Straight,
+    /// Color channels are already multiplied by alpha.
+    Premultiplied,
+    /// The alpha channel is ignored and treated as fully opaque, as for most
+    /// video frames.
+    Opaque,
+}
+
+impl TextureAlphaMode {
+    fn code(self) -> f32 {
+        match self {
+            Self::Straight => 0.0,
+            Self::Premultiplied => 1.0,
+            Self::Opaque => 2.0,
+        }
+    }
+}
+
+/// Draws an [`ImportedTexture`] over the command bounds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextureCommand {
+    /// Texture to draw.
+    pub texture: ImportedTexture,
+    /// Source region in normalized texture coordinates (x, y, width, height).
+    pub uv_rect: [f32; 4],
+    /// Color multiplied with every sampled texel.
+    pub tint: Color,
+    /// Alpha interpretation of the texture.
+    pub alpha_mode: TextureAlphaMode,
+}
+
+impl TextureCommand {
+    /// Creates a command drawing the whole texture.
+    pub fn new(texture: ImportedTexture) -> Self {
+        Self {
+            texture,
+            uv_rect: [0.0, 0.0, 1.0, 1.0],
+            tint: Color::WHITE,
+            alpha_mode: TextureAlphaMode::Straight,
+        }
+    }
+
+    /// Draws only the given normalized source region.
+    pub fn with_uv_rect(mut self, uv_rect: [f32; 4]) -> Self {
+        self.uv_rect = uv_rect;
+        self
+    }
+
+    /// Sets the tint color.
+    pub fn with_tint(mut self, tint: Color) -> Self {
+        self.tint = tint;
+        self
+    }
+
+    /// Sets the alpha interpretation of the texture.
+    pub fn with_alpha_mode(mut self, alpha_mode: TextureAlphaMode) -> Self {
+        self.alpha_mode = alpha_mode;
+        self
+    }
+}
+
+impl DrawCommand for TextureCommand {
+    fn apply_opacity(&mut self, opacity: f32) {
+        self.tint = self.tint.with_alpha(self.tint.a * opacity.clamp(0.0, 1.0));
+    }
+}
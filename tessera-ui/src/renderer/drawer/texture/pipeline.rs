@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::renderer::drawer::{
+    TransientBufferPool, clip_depth_stencil_state,
+    pipeline::{DrawContext, DrawablePipeline, FrameContext},
+    pool::DEFAULT_MAX_IDLE_FRAMES,
+};
+
+use super::TextureCommand;
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct TextureInstance {
+    rect: [f32; 4],
+    uv_rect: [f32; 4],
+    tint: [f32; 4],
+    // x: alpha mode, zw: render target size.
+    params: [f32; 4],
+}
+
+struct TextureBinding {
+    generation: u64,
+    bind_group: wgpu::BindGroup,
+    last_used_frame: u64,
+}
+
+/// Renders [`TextureCommand`]s, one draw per command.
+pub(crate) struct TexturePipeline {
+    pipeline: wgpu::RenderPipeline,
+    instance_layout: wgpu::BindGroupLayout,
+    texture_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    buffer_pool: TransientBufferPool,
+    bindings: HashMap<u32, TextureBinding>,
+    frame: u64,
+}
+
+impl TexturePipeline {
+    pub(crate) fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        sample_count: u32,
+        pipeline_cache: Option<&wgpu::PipelineCache>,
+    ) -> Self {
+        let shader = device.create_shader_module(wgpu::include_wgsl!("../../shaders/texture.wgsl"));
+
+        let instance_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Texture Instance Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Storage { read_only: true },
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let texture_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Texture Sampling Bind Group Layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        multisampled: false,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Texture Pipeline Layout"),
+            bind_group_layouts: &[Some(&instance_layout), Some(&texture_layout)],
+            immediate_size: 0,
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Texture Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: Some(clip_depth_stencil_state()),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: Default::default(),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            multiview_mask: None,
+            cache: pipeline_cache,
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Texture Sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Self {
+            pipeline,
+            instance_layout,
+            texture_layout,
+            sampler,
+            buffer_pool: TransientBufferPool::new("Texture Instance Buffer"),
+            bindings: HashMap::new(),
+            frame: 0,
+        }
+    }
+}
+
+impl DrawablePipeline<TextureCommand> for TexturePipeline {
+    fn begin_frame(&mut self, _context: &FrameContext<'_>) {
+        self.frame = self.frame.wrapping_add(1);
+        self.buffer_pool.begin_frame();
+        let frame = self.frame;
+        self.bindings.retain(|_, binding| {
+            frame.saturating_sub(binding.last_used_frame) <= DEFAULT_MAX_IDLE_FRAMES
+        });
+    }
+
+    fn draw(&mut self, context: &mut DrawContext<TextureCommand>) {
+        if context.commands.is_empty() {
+            return;
+        }
+
+        let target_size = [
+            context.target_size.width.to_f32(),
+            context.target_size.height.to_f32(),
+        ];
+        let mut instances = Vec::with_capacity(context.commands.len());
+        let mut textures = Vec::with_capacity(context.commands.len());
+        for (command, size, position) in context.commands.iter() {
+            let Some((view, generation)) = command.texture.acquire_view() else {
+                continue;
+            };
+            let id = command.texture.id();
+            let frame = self.frame;
+            match self.bindings.get_mut(&id) {
+                Some(binding) if binding.generation == generation => {
+                    binding.last_used_frame = frame;
+                }
+                _ => {
+                    let bind_group = create_texture_bind_group(
+                        context.device,
+                        &self.texture_layout,
+                        &view,
+                        &self.sampler,
+                    );
+                    self.bindings.insert(
+                        id,
+                        TextureBinding {
+                            generation,
+                            bind_group,
+                            last_used_frame: frame,
+                        },
+                    );
+                }
+            }
+
+            textures.push(id);
+            instances.push(TextureInstance {
+                rect: [
+                    position.x.to_f32(),
+                    position.y.to_f32(),
+                    size.width.to_f32(),
+                    size.height.to_f32(),
+                ],
+                uv_rect: command.uv_rect,
+                tint: command.tint.to_array(),
+                params: [
+                    command.alpha_mode.code(),
+                    0.0,
+                    target_size[0],
+                    target_size[1],
+                ],
+            });
+        }
+        if instances.is_empty() {
+            return;
+        }
+
+        let content: &[u8] = bytemuck::cast_slice(&instances);
+        let buffer = self.buffer_pool.acquire(
+            context.device,
+            content.len() as u64,
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        );
+        context.queue.write_buffer(&buffer, 0, content);
+        let instance_bind_group = context
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Texture Instance Bind Group"),
+                layout: &self.instance_layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                        buffer: &buffer,
+                        offset: 0,
+                        size: wgpu::BufferSize::new(content.len() as u64),
+                    }),
+                }],
+            });
+
+        context.render_pass.set_pipeline(&self.pipeline);
+        context
+            .render_pass
+            .set_bind_group(0, &instance_bind_group, &[]);
+        for (instance, id) in textures.iter().enumerate() {
+            let Some(binding) = self.bindings.get(id) else {
+                continue;
+            };
+            let instance = instance as u32;
+            context
+                .render_pass
+                .set_bind_group(1, &binding.bind_group, &[]);
+            context.render_pass.draw(0..6, instance..instance + 1);
+        }
+    }
+}
+
+fn create_texture_bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    view: &wgpu::TextureView,
+    sampler: &wgpu::Sampler,
+) -> wgpu::BindGroup {
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("Texture Sampling Bind Group"),
+        layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::Sampler(sampler),
+            },
+        ],
+    })
+}
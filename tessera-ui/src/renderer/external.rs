@@ -3,13 +3,26 @@
 //! ## Usage
 //!
 //! Keep persistent atlases and caches across frames for render pipelines.
+//!
+//! ## Imported textures
+//!
+//! Textures produced outside of the renderer, such as decoded video frames or
+//! camera previews, are wrapped with [`ExternalTextureRegistry::import`] and
+//! drawn with [`TextureCommand`](crate::renderer::drawer::TextureCommand).
+//! Platform shared handles (DMA-buf, IOSurface, D3D shared handles) are first
+//! turned into a `wgpu::Texture` through the backend's `wgpu::hal` interop and
+//! then imported the same way.
+//!
+//! The registry keeps every imported texture alive until the frames that
+//! sampled it have been submitted, then runs its release callback so the
+//! producer can recycle the underlying buffer.
 
 use std::{collections::HashMap, sync::Arc};
 
 use parking_lot::{RwLock, RwLockWriteGuard};
 
 use crate::{
-    PxSize,
+    Px, PxSize,
     render_graph::{ExternalTextureDesc, RenderTextureDesc},
 };
 
@@ -84,6 +97,74 @@ impl ExternalTextureRegistry {
             }
             frame_index <= entry.last_used_frame.saturating_add(delay_frames)
         });
+
+        let expired =
+            |last_used_frame: u64| frame_index > last_used_frame.saturating_add(delay_frames);
+        let ExternalTextureRegistryInner {
+            imports, retired, ..
+        } = &mut *inner;
+        // Dropped handles keep their last frame queued until it expires.
+        let dropped: Vec<u32> = imports
+            .iter()
+            .filter(|(_, entry)| entry.ref_count == 0)
+            .map(|(id, _)| *id)
+            .collect();
+        for id in dropped {
+            if let Some(entry) = imports.remove(&id) {
+                retired.push(entry.frame);
+            }
+        }
+        let mut released = Vec::new();
+        retired.retain_mut(|frame| {
+            if expired(frame.last_used_frame) {
+                released.push(frame.on_release.take());
+                false
+            } else {
+                true
+            }
+        });
+        drop(inner);
+        // Callbacks run without the registry lock so they may import again.
+        for on_release in released.into_iter().flatten() {
+            on_release();
+        }
+    }
+
+    /// Wrap an externally produced texture so it can be drawn.
+    ///
+    /// The texture must have been created on the renderer's device with
+    /// [`wgpu::TextureUsages::TEXTURE_BINDING`] and a filterable float format.
+    pub fn import(&self, texture: wgpu::Texture) -> ImportedTexture {
+        self.import_with_release(texture, || {})
+    }
+
+    /// Like [`import`](Self::import), and calls `on_release` once the renderer
+    /// no longer uses `texture`.
+    pub fn import_with_release(
+        &self,
+        texture: wgpu::Texture,
+        on_release: impl FnOnce() + Send + Sync + 'static,
+    ) -> ImportedTexture {
+        let mut inner = self.inner.write();
+        let id = inner.next_id;
+        inner.next_id = inner.next_id.wrapping_add(1);
+        let frame = inner.frame;
+        inner.imports.insert(
+            id,
+            ImportedTextureEntry {
+                frame: ImportedFrame::new(texture, Box::new(on_release), frame),
+                generation: 0,
+                ref_count: 1,
+            },
+        );
+        ImportedTexture {
+            id,
+            registry: self.clone(),
+        }
+    }
+
+    pub(crate) fn begin_frame(&self, frame_index: u64) {
+        self.inner.write().frame = frame_index;
     }
 
     pub(crate) fn slot(&self, id: u32) -> Option<ExternalTextureSlotGuard<'_>> {
@@ -101,6 +182,112 @@ impl ExternalTextureRegistry {
     fn release(&self, id: u32) {
         self.inner.write().release(id);
     }
+
+    fn add_import_ref(&self, id: u32) {
+        if let Some(entry) = self.inner.write().imports.get_mut(&id) {
+            entry.ref_count = entry.ref_count.saturating_add(1);
+        }
+    }
+
+    fn release_import(&self, id: u32) {
+        if let Some(entry) = self.inner.write().imports.get_mut(&id) {
+            entry.ref_count = entry.ref_count.saturating_sub(1);
+        }
+    }
+}
+
+/// Handle to a texture imported with [`ExternalTextureRegistry::import`].
+///
+/// Clones share the same texture. The texture is released once every handle
+/// is dropped and no in-flight frame samples it anymore.
+pub struct ImportedTexture {
+    id: u32,
+    registry: ExternalTextureRegistry,
+}
+
+impl ImportedTexture {
+    /// Return the stable id for this texture.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Return the size of the current texture.
+    pub fn size(&self) -> PxSize {
+        self.registry
+            .inner
+            .read()
+            .imports
+            .get(&self.id)
+            .map(|entry| entry.frame.size)
+            .unwrap_or(PxSize::ZERO)
+    }
+
+    /// Replace the texture, e.g. with the next video frame.
+    ///
+    /// The previous texture is released once the frames that sampled it have
+    /// been submitted.
+    pub fn replace(&self, texture: wgpu::Texture) {
+        self.replace_with_release(texture, || {});
+    }
+
+    /// Like [`replace`](Self::replace), and calls `on_release` once the
+    /// renderer no longer uses `texture`.
+    pub fn replace_with_release(
+        &self,
+        texture: wgpu::Texture,
+        on_release: impl FnOnce() + Send + Sync + 'static,
+    ) {
+        let mut inner = self.registry.inner.write();
+        let frame = inner.frame;
+        let ExternalTextureRegistryInner {
+            imports, retired, ..
+        } = &mut *inner;
+        if let Some(entry) = imports.get_mut(&self.id) {
+            let next = ImportedFrame::new(texture, Box::new(on_release), frame);
+            retired.push(std::mem::replace(&mut entry.frame, next));
+            entry.generation = entry.generation.wrapping_add(1);
+        }
+    }
+
+    /// Returns the current view and its generation, marking it used in the
+    /// current frame.
+    pub(crate) fn acquire_view(&self) -> Option<(wgpu::TextureView, u64)> {
+        let mut inner = self.registry.inner.write();
+        let frame = inner.frame;
+        let entry = inner.imports.get_mut(&self.id)?;
+        entry.frame.last_used_frame = frame;
+        Some((entry.frame.view.clone(), entry.generation))
+    }
+}
+
+impl Clone for ImportedTexture {
+    fn clone(&self) -> Self {
+        self.registry.add_import_ref(self.id);
+        Self {
+            id: self.id,
+            registry: self.registry.clone(),
+        }
+    }
+}
+
+impl Drop for ImportedTexture {
+    fn drop(&mut self) {
+        self.registry.release_import(self.id);
+    }
+}
+
+impl PartialEq for ImportedTexture {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && Arc::ptr_eq(&self.registry.inner, &other.registry.inner)
+    }
+}
+
+impl std::fmt::Debug for ImportedTexture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ImportedTexture")
+            .field("id", &self.id)
+            .finish()
+    }
 }
 
 /// Handle to a persistent external texture stored in the registry.
@@ -203,7 +390,43 @@ impl ExternalTextureSlotGuard<'_> {
 #[derive(Default)]
 struct ExternalTextureRegistryInner {
     next_id: u32,
+    frame: u64,
     entries: HashMap<u32, ExternalTextureEntry>,
+    imports: HashMap<u32, ImportedTextureEntry>,
+    retired: Vec<ImportedFrame>,
+}
+
+type ReleaseCallback = Box<dyn FnOnce() + Send + Sync>;
+
+struct ImportedTextureEntry {
+    frame: ImportedFrame,
+    generation: u64,
+    ref_count: u32,
+}
+
+struct ImportedFrame {
+    _texture: wgpu::Texture,
+    view: wgpu::TextureView,
+    size: PxSize,
+    last_used_frame: u64,
+    on_release: Option<ReleaseCallback>,
+}
+
+impl ImportedFrame {
+    fn new(texture: wgpu::Texture, on_release: ReleaseCallback, frame: u64) -> Self {
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let size = PxSize::new(
+            Px::new(texture.width() as i32),
+            Px::new(texture.height() as i32),
+        );
+        Self {
+            _texture: texture,
+            view,
+            size,
+            last_used_frame: frame,
+            on_release: Some(on_release),
+        }
+    }
 }
 
 impl ExternalTextureRegistryInner {
@@ -0,0 +1,81 @@
+// Rasterizes rounded-rect, rotated-rect and ellipse clips into the clip stencil buffer.
+// Color writes are disabled; only the stencil operation of the pipeline matters.
+
+struct InstanceInput {
+    @location(0) rect: vec4<f32>,
+    @location(1) corner_radii: vec4<f32>,
+    // xy: screen size, z: shape kind (0 = rounded rect, 1 = ellipse),
+    // w: clockwise rotation in radians about the rect center.
+    @location(2) params: vec4<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) local_pos: vec2<f32>,
+    @location(1) half_size: vec2<f32>,
+    @location(2) corner_radii: vec4<f32>,
+    @location(3) kind: f32,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32, instance: InstanceInput) -> VertexOutput {
+    // Two triangles covering the unit quad.
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+    );
+    let corner = corners[vertex_index];
+    let local_pos = (corner - vec2<f32>(0.5)) * instance.rect.zw;
+    let c = cos(instance.params.w);
+    let s = sin(instance.params.w);
+    let rotated = vec2<f32>(local_pos.x * c - local_pos.y * s, local_pos.x * s + local_pos.y * c);
+    let pixel_pos = instance.rect.xy + instance.rect.zw * 0.5 + rotated;
+    let screen_size = instance.params.xy;
+    let clip = vec2<f32>(
+        (pixel_pos.x / screen_size.x) * 2.0 - 1.0,
+        (pixel_pos.y / screen_size.y) * -2.0 + 1.0,
+    );
+
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(clip, 0.0, 1.0);
+    out.half_size = instance.rect.zw * 0.5;
+    out.local_pos = local_pos;
+    out.corner_radii = instance.corner_radii;
+    out.kind = instance.params.z;
+    return out;
+}
+
+fn rounded_rect_sdf(p: vec2<f32>, half_size: vec2<f32>, radii: vec4<f32>) -> f32 {
+    // Radii are ordered tl, tr, br, bl with y pointing down.
+    let r = select(radii.xw, radii.yz, p.x > 0.0);
+    let radius = min(select(r.x, r.y, p.y > 0.0), min(half_size.x, half_size.y));
+    let q = abs(p) - half_size + vec2<f32>(radius);
+    return length(max(q, vec2<f32>(0.0))) + min(max(q.x, q.y), 0.0) - radius;
+}
+
+fn ellipse_sdf(p: vec2<f32>, half_size: vec2<f32>) -> f32 {
+    // Approximate distance, exact for circles.
+    let n = p / max(half_size, vec2<f32>(1e-4));
+    return (length(n) - 1.0) * min(half_size.x, half_size.y);
+}
+
+// Keep the antialiased half pixel of shapes drawn with the same outline.
+const EDGE_TOLERANCE: f32 = 0.5;
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    var distance: f32;
+    if in.kind > 0.5 {
+        distance = ellipse_sdf(in.local_pos, in.half_size);
+    } else {
+        distance = rounded_rect_sdf(in.local_pos, in.half_size, in.corner_radii);
+    }
+    if distance > EDGE_TOLERANCE {
+        discard;
+    }
+    return vec4<f32>(0.0);
+}
@@ -0,0 +1,26 @@
+struct VertexInput {
+    @builtin(vertex_index) vertex_index: u32,
+    // Rectangle corners in clip space: x0, y0, x1, y1.
+    @location(0) rect: vec4<f32>,
+    @location(1) color: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+};
+
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    let corner = vec2<f32>(f32(in.vertex_index & 1u), f32(in.vertex_index >> 1u));
+
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(mix(in.rect.xy, in.rect.zw, corner), 0.0, 1.0);
+    out.color = in.color;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return in.color;
+}
@@ -0,0 +1,132 @@
+// Shared paint evaluation helpers.
+//
+// Prepend this file to a pipeline shader (see `PAINT_WGSL`) and call
+// `paint_color` with the fragment position relative to the command bounds.
+// The layout of `Paint` matches `PaintUniform` on the CPU side.
+
+struct Paint {
+    // x: kind, y: stop count, z: tile mode, w: unused.
+    header: vec4<f32>,
+    // Linear: start.xy, end.xy. Radial: center.xy, radius.
+    // Sweep: center.xy, start/end angle in radians. Image: offset.xy, scale.xy.
+    geometry: vec4<f32>,
+    // Solid color or image tint.
+    color: vec4<f32>,
+    offsets: array<vec4<f32>, 2>,
+    colors: array<vec4<f32>, 8>,
+}
+
+const PAINT_KIND_SOLID: f32 = 0.0;
+const PAINT_KIND_LINEAR: f32 = 1.0;
+const PAINT_KIND_RADIAL: f32 = 2.0;
+const PAINT_KIND_SWEEP: f32 = 3.0;
+const PAINT_KIND_IMAGE: f32 = 4.0;
+
+const PAINT_TILE_CLAMP: f32 = 0.0;
+const PAINT_TILE_REPEAT: f32 = 1.0;
+const PAINT_TILE_MIRROR: f32 = 2.0;
+const PAINT_TILE_DECAL: f32 = 3.0;
+
+const PAINT_TAU: f32 = 6.28318530718;
+
+fn paint_is_solid(paint: Paint) -> bool {
+    return paint.header.x < 0.5;
+}
+
+fn paint_is_image(paint: Paint) -> bool {
+    return paint.header.x > PAINT_KIND_IMAGE - 0.5;
+}
+
+// Maps `t` into 0..1 according to `mode`. Returns a negative value for decal
+// tiling outside of the range.
+fn paint_tile(t: f32, mode: f32) -> f32 {
+    if mode > PAINT_TILE_DECAL - 0.5 {
+        return select(t, -1.0, t < 0.0 || t > 1.0);
+    }
+    if mode > PAINT_TILE_MIRROR - 0.5 {
+        let m = t - 2.0 * floor(t * 0.5);
+        return select(m, 2.0 - m, m > 1.0);
+    }
+    if mode > PAINT_TILE_REPEAT - 0.5 {
+        return fract(t);
+    }
+    return clamp(t, 0.0, 1.0);
+}
+
+fn paint_stop_offset(paint: Paint, index: u32) -> f32 {
+    return paint.offsets[index / 4u][index % 4u];
+}
+
+// Interpolates the gradient stops at `t` in 0..1.
+fn paint_gradient_color(paint: Paint, t: f32) -> vec4<f32> {
+    let count = u32(paint.header.y);
+    if count == 0u {
+        return vec4<f32>(0.0);
+    }
+    var color = paint.colors[0];
+    var previous = paint_stop_offset(paint, 0u);
+    for (var i = 1u; i < min(count, 8u); i = i + 1u) {
+        let offset = paint_stop_offset(paint, i);
+        if t >= offset {
+            color = paint.colors[i];
+        } else if t > previous {
+            let span = max(offset - previous, 1e-5);
+            color = mix(paint.colors[i - 1u], paint.colors[i], (t - previous) / span);
+        }
+        previous = offset;
+    }
+    return color;
+}
+
+// Evaluates the gradient parameter for `unit` (position in unit space of the
+// bounds) and `size` (bounds in pixels).
+fn paint_gradient_t(paint: Paint, unit: vec2<f32>, size: vec2<f32>) -> f32 {
+    let kind = paint.header.x;
+    let g = paint.geometry;
+    if kind < PAINT_KIND_RADIAL - 0.5 {
+        // Project onto the gradient line in pixel space to respect aspect ratio.
+        let dir = (g.zw - g.xy) * size;
+        let len_sq = max(dot(dir, dir), 1e-5);
+        return dot((unit - g.xy) * size, dir) / len_sq;
+    }
+    if kind < PAINT_KIND_SWEEP - 0.5 {
+        let radius = max(g.z * min(size.x, size.y), 1e-5);
+        return length((unit - g.xy) * size) / radius;
+    }
+    let d = (unit - g.xy) * size;
+    var angle = atan2(d.y, d.x) - g.z;
+    angle = angle - PAINT_TAU * floor(angle / PAINT_TAU);
+    let sweep = g.w - g.z;
+    return angle / select(sweep, 1e-5, abs(sweep) < 1e-5);
+}
+
+// Returns the texture coordinate of an image paint, or a negative value
+// outside of a decal-tiled image.
+fn paint_image_uv(paint: Paint, local_px: vec2<f32>, size: vec2<f32>) -> vec2<f32> {
+    let unit = local_px / max(size, vec2<f32>(1e-5));
+    let tile = (unit - paint.geometry.xy) / max(paint.geometry.zw, vec2<f32>(1e-5));
+    return vec2<f32>(
+        paint_tile(tile.x, paint.header.z),
+        paint_tile(tile.y, paint.header.z),
+    );
+}
+
+// Returns the premultiplied color of a non-image paint at `local_px`, the
+// position relative to the top-left corner of the bounds in pixels.
+// Image paints return their premultiplied tint; pipelines that sample images
+// multiply it with the texel at `paint_image_uv`.
+fn paint_color(paint: Paint, local_px: vec2<f32>, size: vec2<f32>) -> vec4<f32> {
+    let kind = paint.header.x;
+    var color: vec4<f32>;
+    if kind < PAINT_KIND_LINEAR - 0.5 || kind > PAINT_KIND_IMAGE - 0.5 {
+        color = paint.color;
+    } else {
+        let unit = local_px / max(size, vec2<f32>(1e-5));
+        let t = paint_tile(paint_gradient_t(paint, unit, size), paint.header.z);
+        if t < 0.0 {
+            return vec4<f32>(0.0);
+        }
+        color = paint_gradient_color(paint, t);
+    }
+    return vec4<f32>(color.rgb * color.a, color.a);
+}
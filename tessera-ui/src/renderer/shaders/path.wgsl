@@ -0,0 +1,50 @@
+// Renders tessellated vector paths. `Paint` and `paint_color` are provided by
+// the shared paint helpers prepended at pipeline creation.
+
+struct PathUniforms {
+    // x, y: command origin in pixels; z, w: command size.
+    rect: vec4<f32>,
+    // xy: render target size in pixels.
+    screen_size: vec4<f32>,
+    paint: Paint,
+}
+
+@group(0) @binding(0)
+var<storage, read> paths: array<PathUniforms>;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) local_px: vec2<f32>,
+    @location(1) @interpolate(flat) instance_index: u32,
+}
+
+@vertex
+fn vs_main(
+    @location(0) position: vec2<f32>,
+    @builtin(instance_index) instance_index: u32,
+) -> VertexOutput {
+    let instance = paths[instance_index];
+    let pixel_pos = instance.rect.xy + position;
+    let screen_size = instance.screen_size.xy;
+
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(
+        (pixel_pos.x / screen_size.x) * 2.0 - 1.0,
+        (pixel_pos.y / screen_size.y) * -2.0 + 1.0,
+        0.0,
+        1.0,
+    );
+    out.local_px = position;
+    out.instance_index = instance_index;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let instance = paths[in.instance_index];
+    let color = paint_color(instance.paint, in.local_px, instance.rect.zw);
+    if color.a <= 0.0 {
+        discard;
+    }
+    return color;
+}
@@ -0,0 +1,99 @@
+// Renders rounded rects, circles and capsules from their signed distance.
+// `Paint` and `paint_color` are provided by the shared paint helpers prepended
+// at pipeline creation.
+
+struct SdfShapeInstance {
+    // x, y: command origin in pixels; z, w: command size.
+    rect: vec4<f32>,
+    // tl, tr, br, bl.
+    corner_radii: vec4<f32>,
+    // x: kind (0 = rounded rect, 1 = circle, 2 = capsule), y: border width,
+    // zw: render target size.
+    params: vec4<f32>,
+    border_color: vec4<f32>,
+    paint: Paint,
+}
+
+@group(0) @binding(0)
+var<storage, read> shapes: array<SdfShapeInstance>;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    // Position relative to the shape center, in pixels.
+    @location(0) local_pos: vec2<f32>,
+    @location(1) @interpolate(flat) instance_index: u32,
+}
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32,
+) -> VertexOutput {
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+    );
+    let instance = shapes[instance_index];
+    let corner = corners[vertex_index];
+    let size = instance.rect.zw;
+    let pixel_pos = instance.rect.xy + corner * size;
+    let screen_size = instance.params.zw;
+
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(
+        (pixel_pos.x / screen_size.x) * 2.0 - 1.0,
+        (pixel_pos.y / screen_size.y) * -2.0 + 1.0,
+        0.0,
+        1.0,
+    );
+    out.local_pos = (corner - vec2<f32>(0.5)) * size;
+    out.instance_index = instance_index;
+    return out;
+}
+
+fn sdf_rounded_rect(p: vec2<f32>, half_size: vec2<f32>, radii: vec4<f32>) -> f32 {
+    // Radii are ordered tl, tr, br, bl with y pointing down.
+    let r = select(radii.xw, radii.yz, p.x > 0.0);
+    let radius = min(select(r.x, r.y, p.y > 0.0), min(half_size.x, half_size.y));
+    let q = abs(p) - half_size + vec2<f32>(radius);
+    return length(max(q, vec2<f32>(0.0))) + min(max(q.x, q.y), 0.0) - radius;
+}
+
+fn sdf_shape(p: vec2<f32>, half_size: vec2<f32>, kind: f32, radii: vec4<f32>) -> f32 {
+    let min_half = min(half_size.x, half_size.y);
+    if kind > 1.5 {
+        return sdf_rounded_rect(p, half_size, vec4<f32>(min_half));
+    }
+    if kind > 0.5 {
+        return length(p) - min_half;
+    }
+    return sdf_rounded_rect(p, half_size, radii);
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let instance = shapes[in.instance_index];
+    let size = instance.rect.zw;
+    let distance = sdf_shape(in.local_pos, size * 0.5, instance.params.x, instance.corner_radii);
+
+    // Distance change per pixel; keeps the edge exactly one pixel wide.
+    let pixel = max(length(vec2<f32>(dpdx(distance), dpdy(distance))), 1e-4);
+    let coverage = clamp(0.5 - distance / pixel, 0.0, 1.0);
+    if coverage <= 0.0 {
+        discard;
+    }
+
+    var color = paint_color(instance.paint, in.local_pos + size * 0.5, size);
+    let border_width = instance.params.y;
+    if border_width > 0.0 {
+        let border = instance.border_color;
+        let border_pm = vec4<f32>(border.rgb * border.a, border.a);
+        let inside_border = clamp(0.5 + (distance + border_width) / pixel, 0.0, 1.0);
+        color = mix(color, border_pm, inside_border);
+    }
+    return color * coverage;
+}
@@ -0,0 +1,70 @@
+// Draws imported textures stretched over the command bounds.
+
+struct TextureInstance {
+    // x, y: command origin in pixels; z, w: command size.
+    rect: vec4<f32>,
+    // x, y: source origin; z, w: source size, in normalized coordinates.
+    uv_rect: vec4<f32>,
+    tint: vec4<f32>,
+    // x: alpha mode (0 = straight, 1 = premultiplied, 2 = opaque),
+    // zw: render target size.
+    params: vec4<f32>,
+}
+
+@group(0) @binding(0)
+var<storage, read> instances: array<TextureInstance>;
+
+@group(1) @binding(0)
+var source_texture: texture_2d<f32>;
+@group(1) @binding(1)
+var source_sampler: sampler;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) @interpolate(flat) instance_index: u32,
+}
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32,
+) -> VertexOutput {
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+    );
+    let instance = instances[instance_index];
+    let corner = corners[vertex_index];
+    let pixel_pos = instance.rect.xy + corner * instance.rect.zw;
+    let screen_size = instance.params.zw;
+
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(
+        (pixel_pos.x / screen_size.x) * 2.0 - 1.0,
+        (pixel_pos.y / screen_size.y) * -2.0 + 1.0,
+        0.0,
+        1.0,
+    );
+    out.uv = instance.uv_rect.xy + corner * instance.uv_rect.zw;
+    out.instance_index = instance_index;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let instance = instances[in.instance_index];
+    var texel = textureSample(source_texture, source_sampler, in.uv);
+    let mode = instance.params.x;
+    if mode > 1.5 {
+        texel = vec4<f32>(texel.rgb, 1.0);
+    } else if mode < 0.5 {
+        texel = vec4<f32>(texel.rgb * texel.a, texel.a);
+    }
+    let tint = instance.tint;
+    return texel * vec4<f32>(tint.rgb * tint.a, tint.a);
+}
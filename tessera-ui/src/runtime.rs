@@ -587,6 +587,7 @@ struct FocusReadDependencyKey {
 enum FocusReadDependencyKind {
     Handle(FocusHandleId),
     Requester(FocusRequesterId),
+    Visibility,
 }
 
 #[derive(Default)]
@@ -657,7 +658,7 @@ fn with_redraw_waker_mut<R>(f: impl FnOnce(&mut Option<RedrawWaker>) -> R) -> R
     RUNTIME_GLOBALS.with(|globals| f(&mut globals.redraw_waker.borrow_mut()))
 }
 
-fn schedule_runtime_redraw() {
+pub(crate) fn schedule_runtime_redraw() {
     let callback = with_redraw_waker(Clone::clone);
     if let Some(callback) = callback {
         callback();
@@ -959,6 +960,10 @@ pub(crate) fn track_focus_requester_read_dependency(requester_id: FocusRequester
     track_focus_dependency(FocusReadDependencyKind::Requester(requester_id));
 }
 
+pub(crate) fn track_focus_visibility_read_dependency() {
+    track_focus_dependency(FocusReadDependencyKind::Visibility);
+}
+
 pub(crate) fn focus_read_subscribers(handle_id: FocusHandleId) -> Vec<u64> {
     focus_read_subscribers_by_kind(FocusReadDependencyKind::Handle(handle_id))
 }
@@ -967,6 +972,10 @@ pub(crate) fn focus_requester_read_subscribers(requester_id: FocusRequesterId) -
     focus_read_subscribers_by_kind(FocusReadDependencyKind::Requester(requester_id))
 }
 
+pub(crate) fn focus_visibility_read_subscribers() -> Vec<u64> {
+    focus_read_subscribers_by_kind(FocusReadDependencyKind::Visibility)
+}
+
 pub(crate) fn track_render_slot_read_dependency(handle: FunctorHandle) {
     if !matches!(current_phase(), Some(RuntimePhase::Build)) {
         return;
@@ -1532,7 +1541,11 @@ impl<T> State<T>
 where
     T: Send + Sync + 'static,
 {
-    fn is_alive(&self) -> bool {
+    /// Whether the value still exists.
+    ///
+    /// State created with [`remember`] is dropped once its component stops
+    /// calling it; accessing it afterwards panics.
+    pub fn is_alive(&self) -> bool {
         with_slot_table(|table| {
             let Some(entry) = table.entries.get(self.slot) else {
                 return false;
@@ -1708,14 +1721,22 @@ impl TesseraRuntime {
     }
 
     /// Stores replay metadata for the current component node.
+    ///
+    /// Returns whether the previous subtree was reused. A `memo` component is
+    /// also reused by root recomposition, and only while the contexts it sees
+    /// are unchanged.
     pub(crate) fn set_current_component_replay<P>(
         &mut self,
         runner: Arc<dyn ErasedComponentRunner>,
         props: &P,
+        memo: bool,
     ) -> bool
     where
         P: Prop,
     {
+        if let Some(node) = self.component_tree.current_node_mut() {
+            node.memo = memo;
+        }
         let current_node_info = self
             .component_tree
             .current_node()
@@ -1742,6 +1763,7 @@ impl TesseraRuntime {
             && let Some(replay) = previous_replay.clone()
             && !is_instance_key_build_dirty(instance_key)
             && !pending_dirty
+            && (!memo || crate::context::current_context_matches_previous_snapshot(instance_key))
             && self
                 .component_tree
                 .try_reuse_current_subtree(instance_key, instance_logic_id)
@@ -1839,6 +1861,25 @@ impl TesseraRuntime {
         }
     }
 
+    pub(crate) fn update_current_accessibility<F>(&mut self, update: F)
+    where
+        F: FnOnce(&mut Option<AccessibilityNode>, &mut Option<AccessibilityActionHandler>),
+    {
+        if let Some(node_id) = current_node_id()
+            && let Some(metadata) = self.component_tree.metadatas_mut().get_mut(&node_id)
+        {
+            update(
+                &mut metadata.accessibility,
+                &mut metadata.accessibility_action_handler,
+            );
+        } else {
+            debug_assert!(
+                false,
+                "update_current_accessibility must be called inside a component build"
+            );
+        }
+    }
+
     pub(crate) fn bind_current_focus_requester(&mut self, requester: FocusRequester) {
         if let Some(current) = self.component_tree.current_node_mut() {
             current.focus_requester_binding = Some(requester);
@@ -2042,6 +2083,8 @@ pub struct NodeContextGuard {
     instance_logic_id_popped: bool,
     #[cfg(feature = "profiling")]
     profiling_guard: Option<crate::profiler::ScopeGuard>,
+    #[cfg(feature = "component-spans")]
+    span_guard: Option<tracing::span::EnteredSpan>,
 }
 
 /// Guard that keeps the current component instance key on the execution stack.
@@ -2100,6 +2143,10 @@ impl NodeContextGuard {
 
 impl Drop for NodeContextGuard {
     fn drop(&mut self) {
+        #[cfg(feature = "component-spans")]
+        {
+            let _ = self.span_guard.take();
+        }
         #[cfg(feature = "profiling")]
         {
             let _ = self.profiling_guard.take();
@@ -2141,7 +2188,7 @@ pub fn push_current_node(
     component_type_id: u64,
     fn_name: &str,
 ) -> NodeContextGuard {
-    #[cfg(not(feature = "profiling"))]
+    #[cfg(not(any(feature = "profiling", feature = "component-spans")))]
     let _ = fn_name;
     #[allow(unused_variables)]
     let parent_node_id = with_execution_context_mut(|context| {
@@ -2207,12 +2254,21 @@ pub fn push_current_node(
         }
         _ => None,
     };
+    #[cfg(feature = "component-spans")]
+    let span_guard = match current_phase() {
+        Some(RuntimePhase::Build) => {
+            crate::component_spans::enter_build(fn_name, current_instance_key())
+        }
+        _ => None,
+    };
 
     NodeContextGuard {
         popped: false,
         instance_logic_id_popped: false,
         #[cfg(feature = "profiling")]
         profiling_guard,
+        #[cfg(feature = "component-spans")]
+        span_guard,
     }
 }
 
@@ -2255,6 +2311,8 @@ pub fn push_current_node_with_instance_logic_id(
         instance_logic_id_popped: false,
         #[cfg(feature = "profiling")]
         profiling_guard,
+        #[cfg(feature = "component-spans")]
+        span_guard: None,
     }
 }
 
@@ -2639,6 +2697,15 @@ where
     })
 }
 
+pub(crate) fn is_functor_handle_alive(handle: FunctorHandle) -> bool {
+    with_slot_table(|table| {
+        table
+            .entries
+            .get(handle.slot)
+            .is_some_and(|entry| entry.generation == handle.generation && entry.value.is_some())
+    })
+}
+
 fn load_functor_cell<T>(handle: FunctorHandle) -> Arc<T>
 where
     T: Send + Sync + 'static,
@@ -3227,6 +3294,36 @@ mod tests {
         }
     }
 
+    #[tessera(crate)]
+    fn attribute_defaulted_component(
+        #[prop(default = 7)] value: usize,
+        #[prop(default)] offset: usize,
+        on_value: Option<CallbackWith<usize>>,
+    ) {
+        if let Some(on_value) = on_value {
+            on_value.call(value + offset);
+        }
+    }
+
+    #[tessera(crate)]
+    fn slotted_component(#[children] content: RenderSlot, #[slot] header: Option<RenderSlot>) {
+        if let Some(header) = header {
+            header.render();
+        }
+        content.render();
+    }
+
+    #[tessera(crate)]
+    fn borrowed_label_component(
+        label: &str,
+        suffix: Option<&str>,
+        on_label: Option<CallbackWith<String>>,
+    ) {
+        if let Some(on_label) = on_label {
+            on_label.call(format!("{label}{}", suffix.unwrap_or_default()));
+        }
+    }
+
     fn panic_message(err: Box<dyn std::any::Any + Send>) -> String {
         match err.downcast::<String>() {
             Ok(message) => *message,
@@ -3566,6 +3663,68 @@ mod tests {
         });
     }
 
+    type BranchVisit = (Vec<u64>, usize);
+
+    mod branch_before_insert {
+        use super::*;
+
+        #[tessera(crate)]
+        pub(super) fn branch_state(on_visit: Option<CallbackWith<BranchVisit>>) {
+            if let Some(on_visit) = on_visit {
+                let visits = remember(|| 0usize);
+                visits.with_mut(|visits| *visits += 1);
+                on_visit.call((current_group_path(), visits.get()));
+            }
+        }
+    }
+
+    mod branch_after_insert {
+        use super::*;
+
+        #[tessera(crate)]
+        pub(super) fn branch_state(on_visit: Option<CallbackWith<BranchVisit>>) {
+            if on_visit.is_none() {
+                let _ = remember(|| 0usize);
+            }
+            if let Some(on_visit) = on_visit {
+                let visits = remember(|| 0usize);
+                visits.with_mut(|visits| *visits += 1);
+                on_visit.call((current_group_path(), visits.get()));
+            }
+        }
+    }
+
+    #[test]
+    fn inserting_an_if_keeps_following_branch_group_and_state() {
+        reset_slots();
+        let visits = Arc::new(parking_lot::Mutex::new(Vec::<BranchVisit>::new()));
+        let record = || {
+            let visits = Arc::clone(&visits);
+            move |visit: BranchVisit| visits.lock().push(visit)
+        };
+
+        // Replaying under one instance logic id stands in for recompiling the
+        // same component with an `if` inserted above its existing branch.
+        begin_recompose_slot_epoch();
+        with_test_component_scope(13001, || {
+            with_replay_scope(13002, &[], None, || {
+                branch_before_insert::branch_state().on_visit(record());
+            });
+        });
+        begin_recompose_slot_epoch();
+        with_test_component_scope(13001, || {
+            with_replay_scope(13002, &[], None, || {
+                branch_after_insert::branch_state().on_visit(record());
+            });
+        });
+
+        let visits = visits.lock();
+        assert_eq!(visits.len(), 2);
+        assert!(!visits[0].0.is_empty());
+        assert_eq!(visits[0].0, visits[1].0);
+        assert_eq!((visits[0].1, visits[1].1), (1, 2));
+    }
+
     #[test]
     fn conditional_frame_receiver_does_not_shift_following_remember_slots() {
         reset_slots();
@@ -3676,6 +3835,57 @@ mod tests {
         assert_eq!(observed.load(Ordering::SeqCst), 13);
     }
 
+    #[test]
+    fn tessera_uses_prop_attribute_defaults_for_omitted_props() {
+        let observed = Arc::new(AtomicUsize::new(0));
+
+        with_test_component_scope(11018, || {
+            let observed = Arc::clone(&observed);
+            attribute_defaulted_component().on_value(move |value: usize| {
+                observed.store(value, Ordering::SeqCst);
+            });
+        });
+        assert_eq!(observed.load(Ordering::SeqCst), 7);
+
+        with_test_component_scope(11019, || {
+            let observed = Arc::clone(&observed);
+            attribute_defaulted_component()
+                .offset(2)
+                .on_value(move |value: usize| {
+                    observed.store(value, Ordering::SeqCst);
+                });
+        });
+        assert_eq!(observed.load(Ordering::SeqCst), 9);
+    }
+
+    #[test]
+    fn tessera_renders_children_and_named_slots() {
+        let rendered = Arc::new(parking_lot::Mutex::new(Vec::new()));
+
+        with_test_component_scope(11020, || {
+            let content_rendered = Arc::clone(&rendered);
+            let header_rendered = Arc::clone(&rendered);
+            slotted_component(move || content_rendered.lock().push("content"))
+                .header(move || header_rendered.lock().push("header"));
+        });
+
+        assert_eq!(*rendered.lock(), ["header", "content"]);
+    }
+
+    #[test]
+    fn tessera_lends_borrowed_props_to_the_component_body() {
+        let observed = Arc::new(parking_lot::Mutex::new(String::new()));
+
+        with_test_component_scope(11017, || {
+            let observed = Arc::clone(&observed);
+            borrowed_label_component("title")
+                .suffix("!")
+                .on_label(move |label: String| *observed.lock() = label);
+        });
+
+        assert_eq!(*observed.lock(), "title!");
+    }
+
     #[test]
     fn render_slot_update_invalidates_reader_instance() {
         reset_slots();
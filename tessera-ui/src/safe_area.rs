@@ -0,0 +1,149 @@
+//! Window areas obscured by system UI.
+//!
+//! ## Usage
+//!
+//! Pad screens so content stays clear of notches, rounded corners, the status
+//! bar and the home indicator.
+
+use std::cell::RefCell;
+
+use rustc_hash::FxHashSet as HashSet;
+
+use crate::{
+    dp::Dp,
+    runtime::{
+        RuntimePhase, current_phase, current_replay_boundary_instance_key_from_scope,
+        record_replay_boundary_invalidation_for_instance_key,
+    },
+};
+
+/// Distances from each window edge that system UI may cover.
+///
+/// All insets are zero on platforms without such UI.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SafeAreaInsets {
+    /// Inset from the top edge.
+    pub top: Dp,
+    /// Inset from the right edge.
+    pub right: Dp,
+    /// Inset from the bottom edge.
+    pub bottom: Dp,
+    /// Inset from the left edge.
+    pub left: Dp,
+}
+
+#[derive(Default)]
+struct SafeAreaState {
+    insets: SafeAreaInsets,
+    readers: HashSet<u64>,
+}
+
+thread_local! {
+    static SAFE_AREA_STATE: RefCell<SafeAreaState> = RefCell::new(SafeAreaState::default());
+}
+
+fn with_state<R>(f: impl FnOnce(&mut SafeAreaState) -> R) -> R {
+    SAFE_AREA_STATE.with(|state| f(&mut state.borrow_mut()))
+}
+
+/// Returns the safe area insets of the app window.
+///
+/// When called while building a component, that component is recomposed
+/// whenever the insets change, for example after the device rotated.
+///
+/// # Examples
+///
+/// ```
+/// use tessera_ui::safe_area::safe_area_insets;
+///
+/// let insets = safe_area_insets();
+/// assert!(insets.top.0 >= 0.0);
+/// ```
+pub fn safe_area_insets() -> SafeAreaInsets {
+    let reader = matches!(current_phase(), Some(RuntimePhase::Build))
+        .then(current_replay_boundary_instance_key_from_scope)
+        .flatten();
+    with_state(|state| {
+        if let Some(reader) = reader {
+            state.readers.insert(reader);
+        }
+        state.insets
+    })
+}
+
+/// Re-reads the insets of `window` from the operating system.
+pub(crate) fn refresh_safe_area_insets(window: &winit::window::Window) {
+    #[cfg(target_os = "ios")]
+    let insets = crate::ios::safe_area_insets(window);
+    #[cfg(not(target_os = "ios"))]
+    let insets = {
+        let _ = window;
+        SafeAreaInsets::default()
+    };
+    set_safe_area_insets(insets);
+}
+
+fn set_safe_area_insets(insets: SafeAreaInsets) {
+    let readers = with_state(|state| {
+        if state.insets == insets {
+            return Vec::new();
+        }
+        state.insets = insets;
+        state.readers.iter().copied().collect()
+    });
+    for reader in readers {
+        record_replay_boundary_invalidation_for_instance_key(reader);
+    }
+}
+
+pub(crate) fn remove_safe_area_read_dependencies(instance_keys: &HashSet<u64>) {
+    if instance_keys.is_empty() {
+        return;
+    }
+    with_state(|state| {
+        state
+            .readers
+            .retain(|instance_key| !instance_keys.contains(instance_key));
+    });
+}
+
+pub(crate) fn reset_safe_area_read_dependencies() {
+    with_state(|state| state.readers.clear());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changed_insets_invalidate_readers() {
+        reset_safe_area_read_dependencies();
+        with_state(|state| {
+            state.readers.insert(7);
+        });
+
+        let insets = SafeAreaInsets {
+            top: Dp(47.0),
+            bottom: Dp(34.0),
+            ..SafeAreaInsets::default()
+        };
+        set_safe_area_insets(insets);
+        assert_eq!(safe_area_insets(), insets);
+        assert!(
+            crate::runtime::take_build_invalidations()
+                .dirty_instance_keys
+                .contains(&7)
+        );
+
+        set_safe_area_insets(insets);
+        assert!(
+            crate::runtime::take_build_invalidations()
+                .dirty_instance_keys
+                .is_empty()
+        );
+
+        set_safe_area_insets(SafeAreaInsets::default());
+        reset_safe_area_read_dependencies();
+        let _ = crate::runtime::take_build_invalidations();
+    }
+}
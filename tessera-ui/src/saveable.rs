@@ -0,0 +1,218 @@
+//! State that survives the app process being killed in the background.
+//!
+//! ## Usage
+//!
+//! Keep form input, selections or navigation stacks across process death by
+//! saving them under stable keys. Android writes the saved state to the app's
+//! internal storage when the app is suspended and restores it on the next
+//! launch; other platforms can persist [`save_snapshot`] themselves.
+
+use std::{cell::RefCell, sync::Arc};
+
+use rustc_hash::FxHashMap as HashMap;
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+
+use crate::runtime::{State, remember};
+
+type SaveFn = Arc<dyn Fn() -> Option<Value> + Send + Sync>;
+
+struct Saver {
+    anchor: State<()>,
+    save: SaveFn,
+}
+
+#[derive(Default)]
+struct SaveableState {
+    restored: HashMap<String, Value>,
+    savers: HashMap<String, Saver>,
+    /// Set once the app is leaving for good, so nothing is saved anymore.
+    discarded: bool,
+}
+
+thread_local! {
+    static SAVEABLE_STATE: RefCell<SaveableState> = RefCell::new(SaveableState::default());
+}
+
+fn with_state<R>(f: impl FnOnce(&mut SaveableState) -> R) -> R {
+    SAVEABLE_STATE.with(|state| f(&mut state.borrow_mut()))
+}
+
+/// Like [`remember`], but the value is saved under `key` and restored after
+/// process death.
+///
+/// `key` must be unique among the saveable values of the app.
+///
+/// # Panics
+///
+/// Must be called during a component build.
+///
+/// # Examples
+///
+/// ```
+/// use tessera_ui::{saveable::remember_saveable, tessera};
+///
+/// #[tessera]
+/// fn search_bar() {
+///     let query = remember_saveable("search.query", String::new);
+///     query.with_mut(|query| query.push('a'));
+/// }
+/// ```
+pub fn remember_saveable<T, F>(key: &str, init: F) -> State<T>
+where
+    T: Serialize + DeserializeOwned + Send + Sync + 'static,
+    F: FnOnce() -> T,
+{
+    let state = remember(|| take_restored(key).unwrap_or_else(init));
+    register_saver(
+        key,
+        Arc::new(move || {
+            state
+                .is_alive()
+                .then(|| state.with(|value| serde_json::to_value(value).ok()))
+                .flatten()
+        }),
+    );
+    state
+}
+
+/// Saves the value returned by `save` under `key` while the calling component
+/// is mounted.
+///
+/// `save` runs only when a snapshot is taken; returning `None` saves nothing.
+/// Read the value back with [`take_restored`].
+///
+/// # Panics
+///
+/// Must be called during a component build.
+pub fn save_with<T, F>(key: &str, save: F)
+where
+    T: Serialize,
+    F: Fn() -> Option<T> + Send + Sync + 'static,
+{
+    register_saver(
+        key,
+        Arc::new(move || save().and_then(|value| serde_json::to_value(value).ok())),
+    );
+}
+
+/// Takes the value restored under `key`, if any.
+///
+/// Each restored value is handed out once; values that fail to deserialize
+/// are dropped.
+pub fn take_restored<T: DeserializeOwned>(key: &str) -> Option<T> {
+    let value = with_state(|state| state.restored.remove(key))?;
+    match serde_json::from_value(value) {
+        Ok(value) => Some(value),
+        Err(err) => {
+            tracing::warn!("Failed to restore saved state `{key}`: {err}");
+            None
+        }
+    }
+}
+
+/// Serializes every value currently saved by mounted components.
+pub fn save_snapshot() -> String {
+    let savers: Vec<(String, SaveFn)> = with_state(|state| {
+        state.savers.retain(|_, saver| saver.anchor.is_alive());
+        state
+            .savers
+            .iter()
+            .map(|(key, saver)| (key.clone(), saver.save.clone()))
+            .collect()
+    });
+    let snapshot: serde_json::Map<String, Value> = savers
+        .into_iter()
+        .filter_map(|(key, save)| Some((key, save()?)))
+        .collect();
+    Value::Object(snapshot).to_string()
+}
+
+/// Makes the values of `snapshot` available to [`take_restored`].
+///
+/// Call this before the first frame is built.
+pub fn restore_snapshot(snapshot: &str) -> Result<(), serde_json::Error> {
+    let restored: HashMap<String, Value> = serde_json::from_str(snapshot)?;
+    with_state(|state| state.restored = restored);
+    Ok(())
+}
+
+fn register_saver(key: &str, save: SaveFn) {
+    let anchor = remember(|| ());
+    with_state(|state| {
+        state.savers.insert(key.to_owned(), Saver { anchor, save });
+    });
+}
+
+#[cfg(target_os = "android")]
+const SNAPSHOT_FILE_NAME: &str = "tessera-saved-state.json";
+
+#[cfg(target_os = "android")]
+fn snapshot_path(
+    android_app: &winit::platform::android::activity::AndroidApp,
+) -> Option<std::path::PathBuf> {
+    android_app
+        .internal_data_path()
+        .map(|path| path.join(SNAPSHOT_FILE_NAME))
+}
+
+/// Restores the snapshot written before the process was killed, then removes
+/// it so a later cold start begins fresh.
+#[cfg(target_os = "android")]
+pub(crate) fn restore_from_app_storage(
+    android_app: &winit::platform::android::activity::AndroidApp,
+) {
+    let Some(path) = snapshot_path(android_app) else {
+        return;
+    };
+    let Ok(snapshot) = std::fs::read_to_string(&path) else {
+        return;
+    };
+    let _ = std::fs::remove_file(&path);
+    if let Err(err) = restore_snapshot(&snapshot) {
+        tracing::warn!("Failed to parse saved state: {err}");
+    }
+}
+
+#[cfg(target_os = "android")]
+pub(crate) fn persist_to_app_storage(android_app: &winit::platform::android::activity::AndroidApp) {
+    if with_state(|state| state.discarded) {
+        return;
+    }
+    let Some(path) = snapshot_path(android_app) else {
+        return;
+    };
+    if let Err(err) = std::fs::write(&path, save_snapshot()) {
+        tracing::warn!("Failed to write saved state: {err}");
+    }
+}
+
+/// Removes the written snapshot once the process is known to be alive again.
+#[cfg(target_os = "android")]
+pub(crate) fn clear_app_storage(android_app: &winit::platform::android::activity::AndroidApp) {
+    if let Some(path) = snapshot_path(android_app) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// Drops the saved state when the user leaves the app on purpose.
+#[cfg(target_os = "android")]
+pub(crate) fn discard_app_storage(android_app: &winit::platform::android::activity::AndroidApp) {
+    with_state(|state| state.discarded = true);
+    clear_app_storage(android_app);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restored_values_are_taken_once_and_checked_against_their_type() {
+        restore_snapshot(r#"{"count": 3, "name": "tessera"}"#).expect("snapshot should parse");
+
+        assert_eq!(take_restored::<u32>("count"), Some(3));
+        assert_eq!(take_restored::<u32>("count"), None);
+        assert_eq!(take_restored::<u32>("name"), None);
+        assert_eq!(take_restored::<u32>("missing"), None);
+    }
+}
@@ -0,0 +1,424 @@
+//! Keyboard shortcuts bound to key chords with scope priorities.
+//!
+//! ## Usage
+//!
+//! Bind app-wide or focus-scoped accelerators such as "Ctrl+S" without
+//! decoding modifiers in keyboard handlers.
+
+use std::{fmt, str::FromStr, sync::Arc};
+
+use winit::{
+    event::ElementState,
+    keyboard::{Key, ModifiersState, NamedKey},
+};
+
+use crate::{Callback, KeyEvent, Modifier};
+
+/// The key part of a [`KeyChord`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ChordKey {
+    /// A character key, compared case-insensitively.
+    Character(String),
+    /// A named key such as Enter or F5.
+    Named(NamedKey),
+}
+
+/// A key combined with an exact set of modifiers, e.g. `Ctrl+Shift+P`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    key: ChordKey,
+    modifiers: ModifiersState,
+}
+
+impl KeyChord {
+    /// Creates a chord for a character key.
+    pub fn character(character: impl AsRef<str>, modifiers: ModifiersState) -> Self {
+        Self {
+            key: ChordKey::Character(character.as_ref().to_lowercase()),
+            modifiers,
+        }
+    }
+
+    /// Creates a chord for a named key.
+    pub fn named(key: NamedKey, modifiers: ModifiersState) -> Self {
+        Self {
+            key: ChordKey::Named(key),
+            modifiers,
+        }
+    }
+
+    /// Parses a chord such as `"Ctrl+S"`, `"Cmd+Shift+P"` or `"Mod+Enter"`.
+    ///
+    /// `Mod` (also `Primary` or `CmdOrCtrl`) resolves to Cmd on macOS and to
+    /// Ctrl elsewhere.
+    pub fn parse(chord: &str) -> Result<Self, ShortcutParseError> {
+        chord.parse()
+    }
+
+    /// Returns the key of this chord.
+    pub fn key(&self) -> &ChordKey {
+        &self.key
+    }
+
+    /// Returns the modifiers of this chord.
+    pub fn modifiers(&self) -> ModifiersState {
+        self.modifiers
+    }
+
+    /// Returns whether a key press with the given modifiers triggers this
+    /// chord.
+    pub fn matches(&self, event: &KeyEvent, modifiers: ModifiersState) -> bool {
+        if event.state != ElementState::Pressed || !same_modifiers(self.modifiers, modifiers) {
+            return false;
+        }
+        match (&self.key, &event.logical_key) {
+            (ChordKey::Character(expected), Key::Character(actual)) => {
+                actual.to_lowercase() == *expected
+            }
+            (ChordKey::Character(expected), Key::Named(NamedKey::Space)) => expected == " ",
+            (ChordKey::Named(expected), Key::Named(actual)) => expected == actual,
+            _ => false,
+        }
+    }
+}
+
+fn same_modifiers(expected: ModifiersState, actual: ModifiersState) -> bool {
+    expected.control_key() == actual.control_key()
+        && expected.shift_key() == actual.shift_key()
+        && expected.alt_key() == actual.alt_key()
+        && expected.super_key() == actual.super_key()
+}
+
+fn primary_modifier() -> ModifiersState {
+    if cfg!(target_os = "macos") {
+        ModifiersState::SUPER
+    } else {
+        ModifiersState::CONTROL
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = ShortcutParseError;
+
+    fn from_str(chord: &str) -> Result<Self, Self::Err> {
+        let chord = chord.trim();
+        if chord.is_empty() {
+            return Err(ShortcutParseError::Empty);
+        }
+        // A trailing "++" binds the plus key itself.
+        let (modifier_part, key_part) = match chord.strip_suffix("++") {
+            Some(rest) => (rest, "+"),
+            None if chord == "+" => ("", "+"),
+            None => match chord.rsplit_once('+') {
+                Some((rest, key)) => (rest, key),
+                None => ("", chord),
+            },
+        };
+
+        let mut modifiers = ModifiersState::empty();
+        for token in modifier_part.split('+').filter(|token| !token.is_empty()) {
+            let modifier = match token.trim().to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => ModifiersState::CONTROL,
+                "shift" => ModifiersState::SHIFT,
+                "alt" | "option" | "opt" => ModifiersState::ALT,
+                "cmd" | "command" | "super" | "meta" | "win" => ModifiersState::SUPER,
+                "mod" | "primary" | "cmdorctrl" => primary_modifier(),
+                _ => return Err(ShortcutParseError::UnknownModifier(token.to_string())),
+            };
+            modifiers |= modifier;
+        }
+
+        let key_part = key_part.trim();
+        if key_part.is_empty() {
+            return Err(ShortcutParseError::MissingKey);
+        }
+        let key = match parse_named_key(key_part) {
+            Some(named) => ChordKey::Named(named),
+            None if key_part.chars().count() == 1 => ChordKey::Character(key_part.to_lowercase()),
+            None => return Err(ShortcutParseError::UnknownKey(key_part.to_string())),
+        };
+        Ok(Self { key, modifiers })
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.control_key() {
+            f.write_str("Ctrl+")?;
+        }
+        if self.modifiers.alt_key() {
+            f.write_str("Alt+")?;
+        }
+        if self.modifiers.shift_key() {
+            f.write_str("Shift+")?;
+        }
+        if self.modifiers.super_key() {
+            f.write_str("Cmd+")?;
+        }
+        match &self.key {
+            ChordKey::Character(character) => f.write_str(&character.to_uppercase()),
+            ChordKey::Named(named) => write!(f, "{named:?}"),
+        }
+    }
+}
+
+fn parse_named_key(key: &str) -> Option<NamedKey> {
+    let named = match key.to_ascii_lowercase().as_str() {
+        "enter" | "return" => NamedKey::Enter,
+        "escape" | "esc" => NamedKey::Escape,
+        "tab" => NamedKey::Tab,
+        "space" => NamedKey::Space,
+        "backspace" => NamedKey::Backspace,
+        "delete" | "del" => NamedKey::Delete,
+        "insert" | "ins" => NamedKey::Insert,
+        "home" => NamedKey::Home,
+        "end" => NamedKey::End,
+        "pageup" => NamedKey::PageUp,
+        "pagedown" => NamedKey::PageDown,
+        "up" | "arrowup" => NamedKey::ArrowUp,
+        "down" | "arrowdown" => NamedKey::ArrowDown,
+        "left" | "arrowleft" => NamedKey::ArrowLeft,
+        "right" | "arrowright" => NamedKey::ArrowRight,
+        "f1" => NamedKey::F1,
+        "f2" => NamedKey::F2,
+        "f3" => NamedKey::F3,
+        "f4" => NamedKey::F4,
+        "f5" => NamedKey::F5,
+        "f6" => NamedKey::F6,
+        "f7" => NamedKey::F7,
+        "f8" => NamedKey::F8,
+        "f9" => NamedKey::F9,
+        "f10" => NamedKey::F10,
+        "f11" => NamedKey::F11,
+        "f12" => NamedKey::F12,
+        _ => return None,
+    };
+    Some(named)
+}
+
+/// Errors returned when parsing a [`KeyChord`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ShortcutParseError {
+    /// The chord string was empty.
+    Empty,
+    /// The chord ended with a modifier and had no key.
+    MissingKey,
+    /// A modifier token was not recognized.
+    UnknownModifier(String),
+    /// The key token was not recognized.
+    UnknownKey(String),
+}
+
+impl fmt::Display for ShortcutParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("shortcut is empty"),
+            Self::MissingKey => f.write_str("shortcut has no key"),
+            Self::UnknownModifier(token) => write!(f, "unknown shortcut modifier `{token}`"),
+            Self::UnknownKey(token) => write!(f, "unknown shortcut key `{token}`"),
+        }
+    }
+}
+
+impl std::error::Error for ShortcutParseError {}
+
+/// Where a shortcut is active.
+///
+/// Focused shortcuts win over window shortcuts; among focused shortcuts the
+/// one registered closest to the focused node wins.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ShortcutScope {
+    /// Active anywhere in the window.
+    #[default]
+    Window,
+    /// Active only while the registering node is on the focus chain.
+    Focused,
+}
+
+/// A key chord bound to a handler.
+#[derive(Clone)]
+pub struct Shortcut {
+    chord: KeyChord,
+    handler: Callback,
+    scope: ShortcutScope,
+    priority: i32,
+    allow_repeat: bool,
+    enabled: bool,
+}
+
+impl Shortcut {
+    /// Creates a window-scoped shortcut.
+    pub fn new(chord: KeyChord, handler: impl Into<Callback>) -> Self {
+        Self {
+            chord,
+            handler: handler.into(),
+            scope: ShortcutScope::Window,
+            priority: 0,
+            allow_repeat: false,
+            enabled: true,
+        }
+    }
+
+    /// Creates a shortcut from a chord string.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `chord` cannot be parsed. Use [`KeyChord::parse`] for
+    /// chords that are not known at compile time.
+    pub fn parse(chord: &str, handler: impl Into<Callback>) -> Self {
+        let chord = KeyChord::parse(chord)
+            .unwrap_or_else(|err| panic!("invalid shortcut `{chord}`: {err}"));
+        Self::new(chord, handler)
+    }
+
+    /// Sets the scope in which the shortcut is active.
+    pub fn scope(mut self, scope: ShortcutScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Sets the priority used to break ties within the same scope.
+    ///
+    /// Higher priorities win.
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Sets whether held-key repeats trigger the shortcut again.
+    pub fn allow_repeat(mut self, allow_repeat: bool) -> Self {
+        self.allow_repeat = allow_repeat;
+        self
+    }
+
+    /// Sets whether the shortcut is enabled.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Returns the chord of this shortcut.
+    pub fn chord(&self) -> &KeyChord {
+        &self.chord
+    }
+}
+
+/// Shortcut registration extensions for [`Modifier`].
+pub trait ShortcutModifierExt {
+    /// Registers a shortcut owned by this node.
+    ///
+    /// Shortcuts are dispatched before keyboard handlers; a triggered
+    /// shortcut consumes its key event.
+    fn shortcut(self, shortcut: Shortcut) -> Modifier;
+}
+
+impl ShortcutModifierExt for Modifier {
+    fn shortcut(self, shortcut: Shortcut) -> Modifier {
+        self.push_shortcut(shortcut)
+    }
+}
+
+/// A shortcut registered in the tree together with its owner's focus depth.
+pub(crate) struct ShortcutCandidate {
+    pub(crate) shortcut: Arc<Shortcut>,
+    /// Depth of the owner on the focus chain, `None` when off the chain.
+    pub(crate) focus_depth: Option<usize>,
+    /// Registration order in preorder traversal.
+    pub(crate) order: usize,
+}
+
+impl ShortcutCandidate {
+    fn rank(&self) -> Option<(u8, usize, i32, usize)> {
+        match self.shortcut.scope {
+            ShortcutScope::Focused => self
+                .focus_depth
+                .map(|depth| (1, depth, self.shortcut.priority, self.order)),
+            ShortcutScope::Window => Some((0, 0, self.shortcut.priority, self.order)),
+        }
+    }
+}
+
+/// Triggers the best matching shortcut for each key press and removes the
+/// consumed events.
+pub(crate) fn dispatch_shortcuts(
+    candidates: &[ShortcutCandidate],
+    keyboard_events: &mut Vec<KeyEvent>,
+    modifiers: ModifiersState,
+) {
+    if candidates.is_empty() || keyboard_events.is_empty() {
+        return;
+    }
+    let mut triggered = Vec::new();
+    keyboard_events.retain(|event| {
+        let best = candidates
+            .iter()
+            .filter(|candidate| {
+                let shortcut = &candidate.shortcut;
+                shortcut.enabled
+                    && (shortcut.allow_repeat || !event.repeat)
+                    && shortcut.chord.matches(event, modifiers)
+            })
+            .filter_map(|candidate| candidate.rank().map(|rank| (rank, candidate)))
+            .max_by_key(|(rank, _)| *rank);
+        match best {
+            Some((_, candidate)) => {
+                triggered.push(candidate.shortcut.handler);
+                false
+            }
+            None => true,
+        }
+    });
+    for handler in triggered {
+        handler.call();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_modifiers_and_keys() {
+        let chord = KeyChord::parse("Ctrl+Shift+P").unwrap();
+        assert_eq!(chord.key(), &ChordKey::Character("p".to_string()));
+        assert_eq!(
+            chord.modifiers(),
+            ModifiersState::CONTROL | ModifiersState::SHIFT
+        );
+
+        let chord = KeyChord::parse("cmd+enter").unwrap();
+        assert_eq!(chord.key(), &ChordKey::Named(NamedKey::Enter));
+        assert_eq!(chord.modifiers(), ModifiersState::SUPER);
+    }
+
+    #[test]
+    fn parses_plus_key() {
+        let chord = KeyChord::parse("Ctrl++").unwrap();
+        assert_eq!(chord.key(), &ChordKey::Character("+".to_string()));
+        assert_eq!(chord.modifiers(), ModifiersState::CONTROL);
+    }
+
+    #[test]
+    fn rejects_invalid_chords() {
+        assert_eq!(KeyChord::parse(""), Err(ShortcutParseError::Empty));
+        assert_eq!(
+            KeyChord::parse("Ctrl+"),
+            Err(ShortcutParseError::MissingKey)
+        );
+        assert_eq!(
+            KeyChord::parse("Hyper+S"),
+            Err(ShortcutParseError::UnknownModifier("Hyper".to_string()))
+        );
+        assert_eq!(
+            KeyChord::parse("Ctrl+Foo"),
+            Err(ShortcutParseError::UnknownKey("Foo".to_string()))
+        );
+    }
+
+    #[test]
+    fn display_round_trips() {
+        let chord = KeyChord::parse("shift+ctrl+s").unwrap();
+        assert_eq!(chord.to_string(), "Ctrl+Shift+S");
+        assert_eq!(KeyChord::parse(&chord.to_string()).unwrap(), chord);
+    }
+}
@@ -0,0 +1,513 @@
+//! Operating system appearance and accessibility preferences.
+//!
+//! ## Usage
+//!
+//! Read whether the user asked for dark mode, reduced motion, high contrast or
+//! larger text, and which accent color they picked, and recompose
+//! automatically when those settings change.
+
+use std::cell::RefCell;
+
+use rustc_hash::FxHashSet as HashSet;
+
+use crate::{
+    color::Color,
+    runtime::{
+        RuntimePhase, current_phase, current_replay_boundary_instance_key_from_scope,
+        record_replay_boundary_invalidation_for_instance_key,
+    },
+};
+
+/// Light or dark appearance requested by the operating system.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ColorScheme {
+    /// Dark content on light backgrounds.
+    #[default]
+    Light,
+    /// Light content on dark backgrounds.
+    Dark,
+}
+
+impl ColorScheme {
+    /// Returns whether this is [`ColorScheme::Dark`].
+    pub fn is_dark(self) -> bool {
+        self == Self::Dark
+    }
+}
+
+/// Appearance and accessibility preferences reported by the operating system.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SystemPreferences {
+    /// Light or dark appearance chosen by the user.
+    pub color_scheme: ColorScheme,
+    /// Accent color chosen by the user, when the platform has one.
+    pub accent_color: Option<Color>,
+    /// The user asked to minimize non-essential motion.
+    pub reduce_motion: bool,
+    /// The user enabled a high contrast mode.
+    pub high_contrast: bool,
+    /// Multiplier applied to text sizes, `1.0` being the platform default.
+    pub text_scale: f32,
+}
+
+impl Default for SystemPreferences {
+    fn default() -> Self {
+        Self {
+            color_scheme: ColorScheme::Light,
+            accent_color: None,
+            reduce_motion: false,
+            high_contrast: false,
+            text_scale: 1.0,
+        }
+    }
+}
+
+#[derive(Default)]
+struct SystemPreferencesState {
+    detected: SystemPreferences,
+    overridden: Option<SystemPreferences>,
+    readers: HashSet<u64>,
+}
+
+impl SystemPreferencesState {
+    fn current(&self) -> SystemPreferences {
+        self.overridden.unwrap_or(self.detected)
+    }
+}
+
+thread_local! {
+    static SYSTEM_PREFERENCES_STATE: RefCell<SystemPreferencesState> =
+        RefCell::new(SystemPreferencesState::default());
+}
+
+fn with_state<R>(f: impl FnOnce(&mut SystemPreferencesState) -> R) -> R {
+    SYSTEM_PREFERENCES_STATE.with(|state| f(&mut state.borrow_mut()))
+}
+
+/// Returns the current system preferences.
+///
+/// When called while building a component, that component is recomposed
+/// whenever the preferences change.
+///
+/// # Examples
+///
+/// ```
+/// use tessera_ui::system_preferences::system_preferences;
+///
+/// let preferences = system_preferences();
+/// assert!(preferences.text_scale > 0.0);
+/// ```
+pub fn system_preferences() -> SystemPreferences {
+    let reader = matches!(current_phase(), Some(RuntimePhase::Build))
+        .then(current_replay_boundary_instance_key_from_scope)
+        .flatten();
+    with_state(|state| {
+        if let Some(reader) = reader {
+            state.readers.insert(reader);
+        }
+        state.current()
+    })
+}
+
+/// Overrides the detected preferences, or restores detection with `None`.
+///
+/// Useful for in-app appearance and accessibility settings and for tests.
+pub fn set_system_preferences(preferences: Option<SystemPreferences>) {
+    update(|state| state.overridden = preferences);
+}
+
+/// Re-reads the preferences from the operating system.
+pub(crate) fn refresh_system_preferences() {
+    let detected = detect();
+    update(|state| state.detected = detected);
+}
+
+pub(crate) fn remove_system_preference_read_dependencies(instance_keys: &HashSet<u64>) {
+    if instance_keys.is_empty() {
+        return;
+    }
+    with_state(|state| {
+        state
+            .readers
+            .retain(|instance_key| !instance_keys.contains(instance_key));
+    });
+}
+
+pub(crate) fn reset_system_preference_read_dependencies() {
+    with_state(|state| state.readers.clear());
+}
+
+fn update(f: impl FnOnce(&mut SystemPreferencesState)) {
+    let readers = with_state(|state| {
+        let previous = state.current();
+        f(state);
+        if state.current() == previous {
+            Vec::new()
+        } else {
+            state.readers.iter().copied().collect()
+        }
+    });
+    for reader in readers {
+        record_replay_boundary_invalidation_for_instance_key(reader);
+    }
+}
+
+#[cfg(all(
+    target_family = "unix",
+    not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+))]
+fn detect() -> SystemPreferences {
+    fn gsettings(schema: &str, key: &str) -> Option<String> {
+        let output = std::process::Command::new("gsettings")
+            .args(["get", schema, key])
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    const INTERFACE: &str = "org.gnome.desktop.interface";
+
+    // Older desktops only offer dark mode through a dark GTK theme.
+    let dark = gsettings(INTERFACE, "color-scheme").is_some_and(|value| value == "'prefer-dark'")
+        || gsettings(INTERFACE, "gtk-theme")
+            .is_some_and(|value| value.to_ascii_lowercase().contains("dark"));
+    // GNOME 47 accent names and their colors.
+    let accent_color = gsettings(INTERFACE, "accent-color").and_then(|value| {
+        let rgb = match value.trim_matches('\'') {
+            "blue" => (0x35, 0x84, 0xe4),
+            "teal" => (0x21, 0x90, 0xa4),
+            "green" => (0x3a, 0x94, 0x4a),
+            "yellow" => (0xc8, 0x88, 0x00),
+            "orange" => (0xed, 0x5b, 0x00),
+            "red" => (0xe6, 0x2d, 0x42),
+            "pink" => (0xd5, 0x61, 0x99),
+            "purple" => (0x91, 0x41, 0xac),
+            "slate" => (0x6f, 0x83, 0x96),
+            _ => return None,
+        };
+        Some(Color::from_rgb_u8(rgb.0, rgb.1, rgb.2))
+    });
+
+    let defaults = SystemPreferences::default();
+    SystemPreferences {
+        color_scheme: if dark {
+            ColorScheme::Dark
+        } else {
+            ColorScheme::Light
+        },
+        accent_color,
+        reduce_motion: gsettings(INTERFACE, "enable-animations")
+            .is_some_and(|value| value == "false"),
+        high_contrast: gsettings("org.gnome.desktop.a11y.interface", "high-contrast")
+            .is_some_and(|value| value == "true"),
+        text_scale: gsettings(INTERFACE, "text-scaling-factor")
+            .and_then(|value| value.parse::<f32>().ok())
+            .filter(|scale| *scale > 0.0)
+            .unwrap_or(defaults.text_scale),
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn detect() -> SystemPreferences {
+    use windows::{
+        Win32::{
+            System::Registry::{HKEY_CURRENT_USER, RRF_RT_REG_DWORD, RegGetValueW},
+            UI::{
+                Accessibility::{HCF_HIGHCONTRASTON, HIGHCONTRASTW},
+                WindowsAndMessaging::{
+                    SPI_GETCLIENTAREAANIMATION, SPI_GETHIGHCONTRAST,
+                    SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS, SystemParametersInfoW,
+                },
+            },
+        },
+        core::{PCWSTR, w},
+    };
+
+    fn registry_dword(key: PCWSTR, value: PCWSTR) -> Option<u32> {
+        let mut data = 0u32;
+        let mut size = std::mem::size_of::<u32>() as u32;
+        // SAFETY: `data` and `size` are live locals sized for a DWORD.
+        unsafe {
+            RegGetValueW(
+                HKEY_CURRENT_USER,
+                key,
+                value,
+                RRF_RT_REG_DWORD,
+                None,
+                Some((&mut data as *mut u32).cast()),
+                Some(&mut size),
+            )
+        }
+        .is_ok()
+        .then_some(data)
+    }
+
+    let mut animations = windows::core::BOOL(1);
+    let mut high_contrast = HIGHCONTRASTW {
+        cbSize: std::mem::size_of::<HIGHCONTRASTW>() as u32,
+        ..Default::default()
+    };
+    // SAFETY: Both out-pointers reference live, correctly sized locals.
+    let (animations_read, high_contrast_read) = unsafe {
+        (
+            SystemParametersInfoW(
+                SPI_GETCLIENTAREAANIMATION,
+                0,
+                Some((&mut animations as *mut windows::core::BOOL).cast()),
+                SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+            )
+            .is_ok(),
+            SystemParametersInfoW(
+                SPI_GETHIGHCONTRAST,
+                high_contrast.cbSize,
+                Some((&mut high_contrast as *mut HIGHCONTRASTW).cast()),
+                SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+            )
+            .is_ok(),
+        )
+    };
+
+    let light_apps = registry_dword(
+        w!(r"Software\Microsoft\Windows\CurrentVersion\Themes\Personalize"),
+        w!("AppsUseLightTheme"),
+    );
+    // Stored as 0xAABBGGRR.
+    let accent_color = registry_dword(w!(r"Software\Microsoft\Windows\DWM"), w!("AccentColor"))
+        .map(|abgr| Color::from_rgb_u8(abgr as u8, (abgr >> 8) as u8, (abgr >> 16) as u8));
+
+    SystemPreferences {
+        color_scheme: if light_apps == Some(0) {
+            ColorScheme::Dark
+        } else {
+            ColorScheme::Light
+        },
+        accent_color,
+        reduce_motion: animations_read && !animations.as_bool(),
+        high_contrast: high_contrast_read && high_contrast.dwFlags.contains(HCF_HIGHCONTRASTON),
+        ..SystemPreferences::default()
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn detect() -> SystemPreferences {
+    use objc2_app_kit::{NSColor, NSColorSpace, NSWorkspace};
+    use objc2_foundation::{NSUserDefaults, ns_string};
+
+    // Only set while dark mode is on.
+    let dark = NSUserDefaults::standardUserDefaults()
+        .stringForKey(ns_string!("AppleInterfaceStyle"))
+        .is_some_and(|style| style.to_string() == "Dark");
+    let accent_color = NSColor::controlAccentColor()
+        .colorUsingColorSpace(&NSColorSpace::sRGBColorSpace())
+        .map(|color| {
+            Color::from_rgb(
+                color.redComponent() as f32,
+                color.greenComponent() as f32,
+                color.blueComponent() as f32,
+            )
+        });
+
+    let workspace = NSWorkspace::sharedWorkspace();
+    SystemPreferences {
+        color_scheme: if dark {
+            ColorScheme::Dark
+        } else {
+            ColorScheme::Light
+        },
+        accent_color,
+        reduce_motion: workspace.accessibilityDisplayShouldReduceMotion(),
+        high_contrast: workspace.accessibilityDisplayShouldIncreaseContrast(),
+        ..SystemPreferences::default()
+    }
+}
+
+#[cfg(target_family = "wasm")]
+fn detect() -> SystemPreferences {
+    let matches = |query: &str| {
+        web_sys::window()
+            .and_then(|window| window.match_media(query).ok().flatten())
+            .is_some_and(|list| list.matches())
+    };
+    SystemPreferences {
+        color_scheme: if matches("(prefers-color-scheme: dark)") {
+            ColorScheme::Dark
+        } else {
+            ColorScheme::Light
+        },
+        reduce_motion: matches("(prefers-reduced-motion: reduce)"),
+        high_contrast: matches("(prefers-contrast: more)") || matches("(forced-colors: active)"),
+        ..SystemPreferences::default()
+    }
+}
+
+#[cfg(target_os = "ios")]
+fn detect() -> SystemPreferences {
+    use objc2::MainThreadMarker;
+    use objc2_ui_kit::{UITraitCollection, UIUserInterfaceStyle};
+
+    let dark = MainThreadMarker::new().is_some_and(|mtm| {
+        UITraitCollection::currentTraitCollection(mtm).userInterfaceStyle()
+            == UIUserInterfaceStyle::Dark
+    });
+    SystemPreferences {
+        color_scheme: if dark {
+            ColorScheme::Dark
+        } else {
+            ColorScheme::Light
+        },
+        ..SystemPreferences::default()
+    }
+}
+
+#[cfg(target_os = "android")]
+thread_local! {
+    static ANDROID_APP: RefCell<Option<winit::platform::android::activity::AndroidApp>> =
+        const { RefCell::new(None) };
+}
+
+/// Sets the app whose configuration [`refresh_system_preferences`] reads.
+#[cfg(target_os = "android")]
+pub(crate) fn attach_android_app(android_app: &winit::platform::android::activity::AndroidApp) {
+    ANDROID_APP.with(|app| *app.borrow_mut() = Some(android_app.clone()));
+}
+
+#[cfg(target_os = "android")]
+fn detect() -> SystemPreferences {
+    let Some(android_app) = ANDROID_APP.with(|app| app.borrow().clone()) else {
+        return SystemPreferences::default();
+    };
+    match android_appearance(&android_app) {
+        Ok((color_scheme, accent_color)) => SystemPreferences {
+            color_scheme,
+            accent_color,
+            ..SystemPreferences::default()
+        },
+        Err(err) => {
+            tracing::warn!("Failed to read the Android configuration: {err}");
+            SystemPreferences::default()
+        }
+    }
+}
+
+#[cfg(target_os = "android")]
+fn android_appearance(
+    android_app: &winit::platform::android::activity::AndroidApp,
+) -> Result<(ColorScheme, Option<Color>), jni::errors::Error> {
+    use jni::{
+        JavaVM,
+        objects::{JObject, JValue},
+    };
+
+    // Values of `Configuration.UI_MODE_NIGHT_MASK` and `UI_MODE_NIGHT_YES`.
+    const UI_MODE_NIGHT_MASK: i32 = 0x30;
+    const UI_MODE_NIGHT_YES: i32 = 0x20;
+
+    // SAFETY: The pointers come from the running `AndroidApp` and stay valid
+    // for the lifetime of the activity.
+    let vm = unsafe { JavaVM::from_raw(android_app.vm_as_ptr().cast()) }?;
+    let mut env = vm.attach_current_thread()?;
+    let activity = unsafe { JObject::from_raw(android_app.activity_as_ptr().cast()) };
+
+    let resources = env
+        .call_method(
+            &activity,
+            "getResources",
+            "()Landroid/content/res/Resources;",
+            &[],
+        )?
+        .l()?;
+    let configuration = env
+        .call_method(
+            &resources,
+            "getConfiguration",
+            "()Landroid/content/res/Configuration;",
+            &[],
+        )?
+        .l()?;
+    let ui_mode = env.get_field(&configuration, "uiMode", "I")?.i()?;
+    let color_scheme = if ui_mode & UI_MODE_NIGHT_MASK == UI_MODE_NIGHT_YES {
+        ColorScheme::Dark
+    } else {
+        ColorScheme::Light
+    };
+
+    // Android 12 derives this accent from the wallpaper; older versions lack
+    // the resource.
+    let name = env.new_string("system_accent1_500")?;
+    let kind = env.new_string("color")?;
+    let package = env.new_string("android")?;
+    let id = env
+        .call_method(
+            &resources,
+            "getIdentifier",
+            "(Ljava/lang/String;Ljava/lang/String;Ljava/lang/String;)I",
+            &[
+                JValue::Object(&name),
+                JValue::Object(&kind),
+                JValue::Object(&package),
+            ],
+        )?
+        .i()?;
+    let accent_color = if id == 0 {
+        None
+    } else {
+        let argb = env
+            .call_method(
+                &resources,
+                "getColor",
+                "(ILandroid/content/res/Resources$Theme;)I",
+                &[JValue::Int(id), JValue::Object(&JObject::null())],
+            )?
+            .i()? as u32;
+        Some(Color::from_rgb_u8(
+            (argb >> 16) as u8,
+            (argb >> 8) as u8,
+            argb as u8,
+        ))
+    };
+    Ok((color_scheme, accent_color))
+}
+
+#[cfg(not(any(target_family = "unix", target_os = "windows", target_family = "wasm")))]
+fn detect() -> SystemPreferences {
+    SystemPreferences::default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn override_takes_precedence_and_invalidates_readers_on_change() {
+        reset_system_preference_read_dependencies();
+        with_state(|state| {
+            state.readers.insert(42);
+        });
+
+        let preferences = SystemPreferences {
+            reduce_motion: true,
+            ..SystemPreferences::default()
+        };
+        set_system_preferences(Some(preferences));
+        assert_eq!(system_preferences(), preferences);
+        assert!(
+            crate::runtime::take_build_invalidations()
+                .dirty_instance_keys
+                .contains(&42)
+        );
+
+        set_system_preferences(Some(preferences));
+        assert!(
+            crate::runtime::take_build_invalidations()
+                .dirty_instance_keys
+                .is_empty()
+        );
+
+        set_system_preferences(None);
+        reset_system_preference_read_dependencies();
+        let _ = crate::runtime::take_build_invalidations();
+    }
+}
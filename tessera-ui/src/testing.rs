@@ -2,18 +2,43 @@
 //!
 //! ## Usage
 //!
-//! Assert positions and sizes of tagged nodes without creating a real renderer.
+//! Assert positions and sizes of tagged nodes without creating a real renderer,
+//! or drive components with synthetic pointer, keyboard and IME input through
+//! [`LayoutTestHarness::interact`].
+//!
+//! [`TestHarness`] mounts a component at a fixed size and finds nodes the way
+//! users and assistive technologies see them, by text or by role, so tests
+//! can click them and assert on their bounds and accessibility properties.
+//! It can also render frames offscreen and compare them against golden
+//! images with [`assert_snapshot!`](crate::assert_snapshot).
+
+mod snapshot;
 
-use std::{collections::BTreeSet, time::Duration};
+use std::{collections::BTreeSet, path::Path, time::Duration};
 
 use rustc_hash::FxHashMap as HashMap;
 
+pub use snapshot::{
+    SnapshotDiff, SnapshotImage, SnapshotTolerance, SnapshotTolerances, compare_images,
+};
+
 use crate::{
-    Px, PxPosition, PxSize,
+    CursorEventContent, CursorState, ImeRequest, KeyEvent, MOUSE_POINTER_ID, PointerChange,
+    PointerId, PressKeyEventType, Px, PxPosition, PxSize,
+    accessibility::{AccessibilityNodeDump, AccessibilityTreeDump, dump_tree},
+    accesskit::{Action, Role},
     build_tree::build_component_tree,
-    component_tree::{ComputeMode, ComputeParams, clear_layout_snapshots},
+    component_tree::{ComputeMode, ComputeParams, WindowRequests, clear_layout_snapshots},
     context::{reset_component_context_tracking, reset_context_read_dependencies},
+    deep_link::reset_deep_link_read_dependencies,
+    entry_registry::{EntryRegistry, TesseraPackage},
     focus::flush_pending_focus_callbacks,
+    ime_state::ImeState,
+    keyboard_state::KeyboardState,
+    pipeline_context::PipelineContext,
+    render_graph::RenderGraph,
+    render_module::RenderModule,
+    renderer::{RenderCore, composite::expand_composites},
     runtime::{
         TesseraRuntime, begin_frame_clock, clear_persistent_focus_handles, clear_redraw_waker,
         reset_build_invalidations, reset_component_replay_tracking, reset_focus_read_dependencies,
@@ -21,6 +46,8 @@ use crate::{
         reset_slots, reset_state_read_dependencies, take_layout_dirty_nodes,
         tick_frame_nanos_receivers,
     },
+    safe_area::reset_safe_area_read_dependencies,
+    system_preferences::reset_system_preference_read_dependencies,
     time::Instant,
 };
 
@@ -59,6 +86,617 @@ where
         let mut session = __private::start_layout_test_session(self);
         __private::pump_layout_test_session(&mut session, 0)
     }
+
+    /// Start an interaction session that accepts synthetic input.
+    ///
+    /// The first frame is built immediately so hit testing has a layout to
+    /// work with.
+    pub fn interact(self) -> InteractionSession<F> {
+        reset_runtime_for_layout_test(self.viewport);
+        let mut session = InteractionSession {
+            content: self.content,
+            viewport: self.viewport,
+            frame_origin: Instant::now(),
+            current_frame_nanos: 0,
+            cursor_state: CursorState::default(),
+            keyboard_state: KeyboardState::default(),
+            ime_state: ImeState::default(),
+            window_requests: WindowRequests::default(),
+        };
+        session.frame();
+        session
+    }
+}
+
+/// Drives a component with synthetic input in a headless test.
+///
+/// Input is queued in the same cursor, keyboard and IME queues the renderer
+/// fills from winit, and is delivered by the next [`frame`](Self::frame)
+/// through the regular hit-tested dispatch. Positions are in physical pixels
+/// relative to the window.
+pub struct InteractionSession<F>
+where
+    F: Fn(),
+{
+    content: F,
+    viewport: (u32, u32),
+    frame_origin: Instant,
+    current_frame_nanos: u64,
+    cursor_state: CursorState,
+    keyboard_state: KeyboardState,
+    ime_state: ImeState,
+    window_requests: WindowRequests,
+}
+
+impl<F> InteractionSession<F>
+where
+    F: Fn(),
+{
+    /// Moves the mouse cursor to a window position.
+    pub fn move_cursor(&mut self, x: i32, y: i32) -> &mut Self {
+        let position = PxPosition::new(Px::new(x), Px::new(y));
+        self.cursor_state.update_position(position);
+        self.push_mouse_event(CursorEventContent::Moved(position));
+        self
+    }
+
+    /// Moves the mouse cursor out of the window.
+    pub fn leave_window(&mut self) -> &mut Self {
+        self.cursor_state.clear();
+        self
+    }
+
+    /// Presses a mouse button at the current cursor position.
+    pub fn press(&mut self, button: PressKeyEventType) -> &mut Self {
+        self.push_mouse_event(CursorEventContent::Pressed(button));
+        self
+    }
+
+    /// Releases a mouse button at the current cursor position.
+    pub fn release(&mut self, button: PressKeyEventType) -> &mut Self {
+        self.push_mouse_event(CursorEventContent::Released(button));
+        self
+    }
+
+    /// Moves to a position and clicks the left mouse button.
+    ///
+    /// The press and the release are delivered in separate frames, matching
+    /// what a real click looks like to components.
+    pub fn click(&mut self, x: i32, y: i32) -> &mut Self {
+        self.move_cursor(x, y)
+            .press(PressKeyEventType::Left)
+            .frame();
+        self.release(PressKeyEventType::Left)
+    }
+
+    /// Scrolls the mouse wheel by a line delta.
+    pub fn scroll_lines(&mut self, delta_x: f32, delta_y: f32) -> &mut Self {
+        self.scroll(winit::event::MouseScrollDelta::LineDelta(delta_x, delta_y))
+    }
+
+    /// Scrolls by a pixel delta, as a touchpad would.
+    pub fn scroll_pixels(&mut self, delta_x: f64, delta_y: f64) -> &mut Self {
+        self.scroll(winit::event::MouseScrollDelta::PixelDelta(
+            winit::dpi::PhysicalPosition::new(delta_x, delta_y),
+        ))
+    }
+
+    fn scroll(&mut self, delta: winit::event::MouseScrollDelta) -> &mut Self {
+        let phase = self
+            .cursor_state
+            .resolve_wheel_phase(delta, winit::event::TouchPhase::Moved);
+        self.push_mouse_event(CursorEventContent::from_scroll_event_with_phase(
+            delta, phase,
+        ));
+        self
+    }
+
+    /// Starts a touch with the given id at a window position.
+    pub fn touch_start(&mut self, id: PointerId, x: i32, y: i32) -> &mut Self {
+        self.cursor_state
+            .handle_touch_start(id, PxPosition::new(Px::new(x), Px::new(y)));
+        self
+    }
+
+    /// Moves an active touch to a window position.
+    pub fn touch_move(&mut self, id: PointerId, x: i32, y: i32) -> &mut Self {
+        if let Some(scroll_event) = self
+            .cursor_state
+            .handle_touch_move(id, PxPosition::new(Px::new(x), Px::new(y)))
+        {
+            self.cursor_state.push_event(scroll_event);
+        }
+        self
+    }
+
+    /// Lifts an active touch.
+    pub fn touch_end(&mut self, id: PointerId) -> &mut Self {
+        self.cursor_state.handle_touch_end(id);
+        self
+    }
+
+    /// Queues a keyboard event.
+    pub fn key_event(&mut self, event: impl Into<KeyEvent>) -> &mut Self {
+        self.keyboard_state.push_event(event.into());
+        self
+    }
+
+    /// Presses and releases a key with the given modifiers held.
+    ///
+    /// The modifiers stay in effect for subsequent input until changed with
+    /// [`Self::modifiers`].
+    pub fn press_key(
+        &mut self,
+        key: winit::keyboard::Key,
+        modifiers: winit::keyboard::ModifiersState,
+    ) -> &mut Self {
+        self.keyboard_state.update_modifiers(modifiers);
+        self.keyboard_state.push_event(KeyEvent::press(key.clone()));
+        self.keyboard_state.push_event(KeyEvent::release(key));
+        self
+    }
+
+    /// Sets the keyboard modifiers reported with subsequent input.
+    pub fn modifiers(&mut self, modifiers: winit::keyboard::ModifiersState) -> &mut Self {
+        self.keyboard_state.update_modifiers(modifiers);
+        self
+    }
+
+    /// Queues an IME event for the focused component.
+    pub fn ime(&mut self, event: winit::event::Ime) -> &mut Self {
+        self.ime_state.push_event(event);
+        self
+    }
+
+    /// Commits text through the IME, as typing would on most platforms.
+    pub fn commit_text(&mut self, text: impl Into<String>) -> &mut Self {
+        self.ime(winit::event::Ime::Commit(text.into()))
+    }
+
+    /// Returns the IME request made by the focused component in the last
+    /// frame, if any.
+    pub fn ime_request(&self) -> Option<&ImeRequest> {
+        self.window_requests.ime_request.as_ref()
+    }
+
+    /// Returns the cursor icon requested in the last frame.
+    pub fn cursor_icon(&self) -> winit::window::CursorIcon {
+        self.window_requests.cursor_icon
+    }
+
+    /// Runs one frame, delivering all queued input, and captures its layout.
+    pub fn frame(&mut self) -> LayoutSnapshot {
+        self.run_frame(ComputeMode::Headless).0
+    }
+
+    fn run_frame(&mut self, mode: ComputeMode<'_>) -> (LayoutSnapshot, RenderGraph) {
+        let frame_time = self.frame_origin + Duration::from_nanos(self.current_frame_nanos);
+        begin_frame_clock(frame_time);
+        tick_frame_nanos_receivers();
+        let _ = build_component_tree(&self.content);
+        let layout_dirty_nodes = take_layout_dirty_nodes();
+        let screen_size = PxSize::new(
+            Px::new(self.viewport.0 as i32),
+            Px::new(self.viewport.1 as i32),
+        );
+
+        let (graph, window_requests, ..) = TesseraRuntime::with_mut(|runtime| {
+            runtime.component_tree.compute(
+                ComputeParams {
+                    screen_size,
+                    cursor_position: self.cursor_state.position(),
+                    pointer_changes: self.cursor_state.take_events(),
+                    keyboard_events: self.keyboard_state.take_events(),
+                    ime_events: self.ime_state.take_events(),
+                    retry_focus_move: None,
+                    retry_focus_reveal: false,
+                    modifiers: self.keyboard_state.modifiers(),
+                    layout_dirty_nodes: &layout_dirty_nodes,
+                },
+                mode,
+            )
+        });
+        flush_pending_focus_callbacks();
+        self.cursor_state.frame_cleanup();
+        self.window_requests = window_requests;
+
+        (LayoutSnapshot::capture(), graph)
+    }
+
+    /// Advances the frame clock and runs one frame.
+    pub fn advance_by_nanos(&mut self, delta_nanos: u64) -> LayoutSnapshot {
+        self.current_frame_nanos = self.current_frame_nanos.saturating_add(delta_nanos);
+        self.frame()
+    }
+
+    fn push_mouse_event(&mut self, content: CursorEventContent) {
+        // Stamp input with the runtime clock so gesture timing is
+        // deterministic.
+        let timestamp = self.frame_origin + Duration::from_nanos(self.current_frame_nanos);
+        self.cursor_state.push_event(PointerChange {
+            timestamp,
+            pointer_id: MOUSE_POINTER_ID,
+            content,
+            consumed: false,
+        });
+    }
+}
+
+/// Mounts a component in a headless window and drives it like a user would.
+///
+/// Nodes are found through the accessibility tree of the last frame, so
+/// queries see the same roles, labels and values as assistive technologies.
+/// Every input method runs the frame that delivers it.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tessera_ui::{accesskit::Role, testing::TestHarness};
+/// # #[tessera_ui::tessera]
+/// # fn app() {}
+///
+/// let mut harness = TestHarness::mount(|| app(), 400, 300);
+/// let send = harness.find_by_text("Send");
+/// send.assert_role(Role::Button).assert_enabled();
+/// harness.click(&send);
+/// harness.find_by_role(Role::Label).assert_label("Sent");
+/// ```
+pub struct TestHarness<F>
+where
+    F: Fn(),
+{
+    session: InteractionSession<F>,
+    layout: LayoutSnapshot,
+    modules: Vec<Box<dyn RenderModule>>,
+    render_core: Option<RenderCore>,
+    rendered_frames: u64,
+}
+
+impl<F> TestHarness<F>
+where
+    F: Fn(),
+{
+    /// Mounts `content` in a window of `width` by `height` physical pixels
+    /// and runs the first frame.
+    pub fn mount(content: F, width: u32, height: u32) -> Self {
+        let mut session = layout_test(content).viewport_px(width, height).interact();
+        let layout = session.frame();
+        Self {
+            session,
+            layout,
+            modules: Vec::new(),
+            render_core: None,
+            rendered_frames: 0,
+        }
+    }
+
+    /// Adds a render module whose pipelines draw [`render`](Self::render)ed
+    /// frames.
+    ///
+    /// Modules must be added before the first frame is rendered.
+    pub fn module(mut self, module: impl RenderModule + 'static) -> Self {
+        self.modules.push(Box::new(module));
+        self
+    }
+
+    /// Adds the render modules of a package, as
+    /// [`EntryPoint::package`](crate::EntryPoint::package) would.
+    ///
+    /// Packages must be added before the first frame is rendered.
+    pub fn package(mut self, package: impl TesseraPackage) -> Self {
+        let mut registry = EntryRegistry::new();
+        registry.register_package(package);
+        self.modules.extend(registry.finish());
+        self
+    }
+
+    /// Runs one frame without advancing time.
+    pub fn frame(&mut self) -> &mut Self {
+        self.layout = self.session.frame();
+        self
+    }
+
+    /// Runs `count` frames, each `frame_interval` after the previous one.
+    pub fn advance_frames(&mut self, count: usize, frame_interval: Duration) -> &mut Self {
+        for _ in 0..count {
+            self.advance_time(frame_interval);
+        }
+        self
+    }
+
+    /// Advances the runtime clock by `delta` and runs one frame.
+    ///
+    /// Animations that read [`time::now`](crate::time::now) or the frame
+    /// nanos see exactly `delta` pass.
+    pub fn advance_time(&mut self, delta: Duration) -> &mut Self {
+        let delta_nanos = u64::try_from(delta.as_nanos()).unwrap_or(u64::MAX);
+        self.layout = self.session.advance_by_nanos(delta_nanos);
+        self
+    }
+
+    /// Returns the layout of the last frame, for assertions by test tag.
+    pub fn layout(&self) -> &LayoutSnapshot {
+        &self.layout
+    }
+
+    /// Returns the accessibility tree of the last frame.
+    pub fn accessibility_tree(&self) -> AccessibilityTreeDump {
+        dump_tree().expect("test harness has built a frame")
+    }
+
+    /// Returns the only node whose label or value is `text`.
+    ///
+    /// Panics when no node or more than one node matches.
+    pub fn find_by_text(&self, text: &str) -> FoundNode {
+        self.find_one(&format!("text `{text}`"), |node| {
+            node.label.as_deref() == Some(text) || node.value.as_deref() == Some(text)
+        })
+    }
+
+    /// Returns the only node with the given role.
+    ///
+    /// Panics when no node or more than one node matches.
+    pub fn find_by_role(&self, role: Role) -> FoundNode {
+        let role = format!("{role:?}");
+        self.find_one(&format!("role `{role}`"), |node| node.role == role)
+    }
+
+    /// Returns every node with the given role, in tree order.
+    pub fn find_all_by_role(&self, role: Role) -> Vec<FoundNode> {
+        let role = format!("{role:?}");
+        self.find_all(|node| node.role == role)
+    }
+
+    /// Clicks the center of a node with the left mouse button.
+    pub fn click(&mut self, node: &FoundNode) -> &mut Self {
+        let (x, y) = node.center();
+        self.session.click(x, y);
+        self.frame()
+    }
+
+    /// Clicks a window position with the left mouse button.
+    pub fn click_at(&mut self, x: i32, y: i32) -> &mut Self {
+        self.session.click(x, y);
+        self.frame()
+    }
+
+    /// Types text into the focused component.
+    ///
+    /// The text is committed through the IME, which is how text fields
+    /// receive typed characters.
+    pub fn type_text(&mut self, text: impl Into<String>) -> &mut Self {
+        self.session.commit_text(text);
+        self.frame()
+    }
+
+    /// Delivers a keyboard event.
+    pub fn key_event(&mut self, event: impl Into<KeyEvent>) -> &mut Self {
+        self.session.key_event(event);
+        self.frame()
+    }
+
+    /// Presses and releases a key with the given modifiers held.
+    ///
+    /// See [`InteractionSession::press_key`].
+    pub fn press_key(
+        &mut self,
+        key: winit::keyboard::Key,
+        modifiers: winit::keyboard::ModifiersState,
+    ) -> &mut Self {
+        self.session.press_key(key, modifiers);
+        self.frame()
+    }
+
+    /// Runs one frame and renders it offscreen.
+    ///
+    /// The first call creates a GPU device without a window, so rendering
+    /// needs a GPU or a software adapter such as lavapipe or WARP.
+    pub fn render(&mut self) -> SnapshotImage {
+        let (width, height) = self.session.viewport;
+        let modules = &self.modules;
+        let render_core = self.render_core.get_or_insert_with(|| {
+            let mut render_core = pollster::block_on(RenderCore::new_headless(width, height, 1));
+            let mut context = PipelineContext::new(&mut render_core);
+            for module in modules {
+                module.register_pipelines(&mut context);
+            }
+            render_core
+        });
+
+        render_core.compute_resource_manager_mut().clear();
+        let (gpu, compute_resource_manager) = render_core.record_resources();
+        let (layout, graph) = self.session.run_frame(ComputeMode::Full {
+            compute_resource_manager,
+            gpu,
+        });
+        self.layout = layout;
+
+        let screen_size = PxSize::new(Px::new(width as i32), Px::new(height as i32));
+        let (composite_context, composite_registry) =
+            render_core.composite_context_parts(screen_size, self.rendered_frames);
+        let graph = expand_composites(graph, composite_context, composite_registry);
+        render_core.render(
+            graph.into_execution(),
+            #[cfg(feature = "debug-dirty-overlay")]
+            &[],
+        );
+        self.rendered_frames += 1;
+
+        let pixels = render_core
+            .read_output_pixels()
+            .expect("test harness render core is headless");
+        SnapshotImage::new(width, height, pixels)
+    }
+
+    /// Renders a frame and compares it against the golden image `name.png`
+    /// in `dir`.
+    ///
+    /// Prefer [`assert_snapshot!`](crate::assert_snapshot), which keeps
+    /// golden images in the crate's `tests/snapshots` directory.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the frame differs from the golden image beyond the
+    /// tolerance for the current platform, or when the golden image is
+    /// missing. The frame and a diff image are written to `dir/failures`.
+    pub fn assert_snapshot(
+        &mut self,
+        dir: impl AsRef<Path>,
+        name: &str,
+        tolerances: impl Into<SnapshotTolerances>,
+    ) {
+        let frame = self.render();
+        if let Err(message) =
+            snapshot::check_snapshot(&frame, dir.as_ref(), name, tolerances.into().current())
+        {
+            panic!("{message}");
+        }
+    }
+
+    /// Returns the underlying session for input this harness doesn't wrap.
+    ///
+    /// Queued input is delivered by the next frame run through the harness.
+    pub fn session(&mut self) -> &mut InteractionSession<F> {
+        &mut self.session
+    }
+
+    fn find_one(
+        &self,
+        description: &str,
+        matches: impl Fn(&AccessibilityNodeDump) -> bool,
+    ) -> FoundNode {
+        let mut found = self.find_all(matches);
+        match found.len() {
+            1 => found.remove(0),
+            0 => panic!(
+                "no node with {description}; accessibility tree: {:#?}",
+                self.accessibility_tree().root
+            ),
+            count => panic!("{count} nodes with {description}; expected exactly one"),
+        }
+    }
+
+    fn find_all(&self, matches: impl Fn(&AccessibilityNodeDump) -> bool) -> Vec<FoundNode> {
+        self.accessibility_tree()
+            .nodes()
+            .into_iter()
+            .filter(|node| matches(node))
+            .map(|node| FoundNode { node: node.clone() })
+            .collect()
+    }
+}
+
+/// A node found by a [`TestHarness`] query, as of the frame it was found in.
+#[derive(Debug, Clone)]
+pub struct FoundNode {
+    node: AccessibilityNodeDump,
+}
+
+impl FoundNode {
+    /// Returns the accessibility data of the node.
+    pub fn accessibility(&self) -> &AccessibilityNodeDump {
+        &self.node
+    }
+
+    /// Returns the node bounds as `(x, y, width, height)` in physical pixels.
+    pub fn rect(&self) -> (i32, i32, i32, i32) {
+        let [x0, y0, x1, y1] = self.bounds();
+        (
+            x0.round() as i32,
+            y0.round() as i32,
+            (x1 - x0).round() as i32,
+            (y1 - y0).round() as i32,
+        )
+    }
+
+    /// Returns the center of the node bounds in physical pixels.
+    pub fn center(&self) -> (i32, i32) {
+        let [x0, y0, x1, y1] = self.bounds();
+        (
+            ((x0 + x1) / 2.0).round() as i32,
+            ((y0 + y1) / 2.0).round() as i32,
+        )
+    }
+
+    /// Assert the node bounds in physical pixels.
+    pub fn assert_rect(&self, x: i32, y: i32, width: i32, height: i32) -> &Self {
+        assert_eq!(
+            self.rect(),
+            (x, y, width, height),
+            "node {} rect (x, y, width, height) mismatch",
+            self.describe()
+        );
+        self
+    }
+
+    /// Assert the node role.
+    pub fn assert_role(&self, role: Role) -> &Self {
+        assert_eq!(
+            self.node.role,
+            format!("{role:?}"),
+            "node {} role mismatch",
+            self.describe()
+        );
+        self
+    }
+
+    /// Assert the node label.
+    pub fn assert_label(&self, label: &str) -> &Self {
+        assert_eq!(
+            self.node.label.as_deref(),
+            Some(label),
+            "node {} label mismatch",
+            self.describe()
+        );
+        self
+    }
+
+    /// Assert the node value.
+    pub fn assert_value(&self, value: &str) -> &Self {
+        assert_eq!(
+            self.node.value.as_deref(),
+            Some(value),
+            "node {} value mismatch",
+            self.describe()
+        );
+        self
+    }
+
+    /// Assert that the node supports an action.
+    pub fn assert_action(&self, action: Action) -> &Self {
+        assert!(
+            self.node.actions.contains(&format!("{action:?}")),
+            "node {} does not support {action:?}; actions: {:?}",
+            self.describe(),
+            self.node.actions
+        );
+        self
+    }
+
+    /// Assert that the node is enabled.
+    pub fn assert_enabled(&self) -> &Self {
+        assert!(!self.node.disabled, "node {} is disabled", self.describe());
+        self
+    }
+
+    /// Assert that the node is disabled.
+    pub fn assert_disabled(&self) -> &Self {
+        assert!(self.node.disabled, "node {} is enabled", self.describe());
+        self
+    }
+
+    fn bounds(&self) -> [f64; 4] {
+        self.node
+            .bounds
+            .unwrap_or_else(|| panic!("node {} has no bounds", self.describe()))
+    }
+
+    fn describe(&self) -> String {
+        match &self.node.label {
+            Some(label) => format!("{} `{label}`", self.node.role),
+            None => format!("{} #{}", self.node.role, self.node.id),
+        }
+    }
 }
 
 #[doc(hidden)]
@@ -527,7 +1165,7 @@ impl<'a> NodeAssert<'a> {
     }
 }
 
-fn reset_runtime_for_layout_test(viewport: (u32, u32)) {
+pub(crate) fn reset_runtime_for_layout_test(viewport: (u32, u32)) {
     TesseraRuntime::with_mut(|runtime| {
         runtime.component_tree.reset();
         runtime.cursor_icon_request = None;
@@ -542,6 +1180,9 @@ fn reset_runtime_for_layout_test(viewport: (u32, u32)) {
     reset_state_read_dependencies();
     reset_component_context_tracking();
     reset_context_read_dependencies();
+    reset_system_preference_read_dependencies();
+    reset_safe_area_read_dependencies();
+    reset_deep_link_read_dependencies();
     reset_build_invalidations();
     reset_frame_clock();
     clear_redraw_waker();
@@ -549,6 +1190,49 @@ fn reset_runtime_for_layout_test(viewport: (u32, u32)) {
     reset_slots();
 }
 
+/// Renders a [`TestHarness`](crate::testing::TestHarness) frame and compares it
+/// against a golden image.
+///
+/// Golden images live in `tests/snapshots/<name>.png` under the calling
+/// crate. Run the test with `TESSERA_UPDATE_SNAPSHOTS=1` to record them. An
+/// optional third argument sets the
+/// [`SnapshotTolerances`](crate::testing::SnapshotTolerances).
+///
+/// # Examples
+///
+/// ```no_run
+/// use tessera_ui::{assert_snapshot, testing::TestHarness};
+/// # struct ComponentsPackage;
+/// # impl tessera_ui::TesseraPackage for ComponentsPackage {
+/// #     fn register(self, _registry: &mut tessera_ui::EntryRegistry) {}
+/// # }
+/// # #[tessera_ui::tessera]
+/// # fn app() {}
+///
+/// let mut harness = TestHarness::mount(|| app(), 400, 300).package(ComponentsPackage);
+/// harness.click(&harness.find_by_text("Send"));
+/// assert_snapshot!(harness, "button_pressed");
+/// ```
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($harness:expr, $name:expr $(,)?) => {
+        $crate::assert_snapshot!(
+            $harness,
+            $name,
+            $crate::testing::SnapshotTolerances::default()
+        )
+    };
+    ($harness:expr, $name:expr, $tolerances:expr $(,)?) => {
+        $harness.assert_snapshot(
+            ::std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests")
+                .join("snapshots"),
+            $name,
+            $tolerances,
+        )
+    };
+}
+
 /// Assert layout relationships in a headless Tessera layout test.
 #[doc(hidden)]
 #[macro_export]
@@ -616,15 +1300,31 @@ macro_rules! assert_layout {
 
 #[cfg(test)]
 mod tests {
-    use std::num::NonZero;
+    use std::{
+        cell::RefCell,
+        num::NonZero,
+        sync::{
+            Mutex,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::Duration,
+    };
+
+    use winit::keyboard::{Key, ModifiersState};
 
     use super::reset_runtime_for_layout_test;
 
     use crate::{
-        AccessibilityActionHandler, AccessibilityNode, AxisConstraint, ComputedData, Constraint,
-        FrameNanosControl, LayoutModifierChild, LayoutModifierInput, LayoutModifierNode,
-        LayoutPolicy, LayoutResult, Modifier, NoopRenderPolicy, PlacementModifierNode, Px,
-        PxPosition, RenderSlot, SemanticsModifierNode,
+        AccessibilityActionHandler, AccessibilityNode, AxisConstraint, Callback, ComputedData,
+        Constraint, CursorEventContent, CursorModifierExt as _, DragAndDropModifierExt as _,
+        DragPayload, DragSource, DropEvent, DropOutcome, DropTarget, FocusDirection, FocusManager,
+        FocusModifierExt as _, FocusProperties, FrameNanosControl, KeyEvent, LayoutModifierChild,
+        LayoutModifierInput, LayoutModifierNode, LayoutPolicy, LayoutResult, Modifier,
+        NoopRenderPolicy, PlacementModifierNode, PointerEventPass, PointerInput,
+        PointerInputModifierNode, PressKeyEventType, Px, PxPosition, RenderSlot,
+        SemanticsModifierNode, Shortcut, ShortcutModifierExt as _,
+        accesskit::{Action, Role},
+        back_navigation::{back_handler, dispatch_back},
         component_tree::{NodeRole, direct_layout_children},
         layout::MeasureScope,
         receive_frame_nanos, remember,
@@ -808,6 +1508,41 @@ mod tests {
             .modifier(Modifier::new().push_semantics(TestTagSemanticsModifier { tag }));
     }
 
+    static PRESSES: AtomicUsize = AtomicUsize::new(0);
+    static RELEASES: AtomicUsize = AtomicUsize::new(0);
+
+    struct PressCounter;
+
+    impl PointerInputModifierNode for PressCounter {
+        fn on_pointer_input(&self, input: PointerInput<'_>) {
+            if input.pass != PointerEventPass::Main || !input.is_hit_target() {
+                return;
+            }
+            for change in input.pointer_changes.iter() {
+                match change.content {
+                    CursorEventContent::Pressed(_) => {
+                        PRESSES.fetch_add(1, Ordering::SeqCst);
+                    }
+                    CursorEventContent::Released(_) => {
+                        RELEASES.fetch_add(1, Ordering::SeqCst);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    #[tessera(crate)]
+    fn pressable_box() {
+        crate::layout::layout()
+            .layout_policy(FixedSizePolicy {
+                width: 100,
+                height: 100,
+            })
+            .render_policy(NoopRenderPolicy)
+            .modifier(Modifier::new().push_pointer_input(PressCounter));
+    }
+
     #[tessera(crate)]
     fn responsive_box(tag: Option<String>) {
         let tag = tag.unwrap_or_default();
@@ -1035,6 +1770,843 @@ mod tests {
         });
     }
 
+    #[tessera(crate)]
+    fn semantic_button() {
+        crate::semantics(|s| {
+            s.role(Role::Button);
+            s.label("Send");
+            s.on_click(|| {});
+        });
+        crate::layout::layout()
+            .layout_policy(FixedSizePolicy {
+                width: 80,
+                height: 40,
+            })
+            .render_policy(NoopRenderPolicy);
+    }
+
+    #[test]
+    fn semantics_populates_current_component_node() {
+        reset_runtime_for_layout_test((100, 100));
+        let _ = crate::build_tree::build_component_tree(&|| {
+            semantic_button();
+        });
+
+        TesseraRuntime::with(|runtime| {
+            let tree = runtime.component_tree.tree();
+            let metadatas = runtime.component_tree.metadatas();
+            let root = tree
+                .get_node_id_at(NonZero::new(1).expect("root node index must be non-zero"))
+                .expect("root node must exist after build");
+            let node_id = root
+                .descendants(tree)
+                .find(|node_id| {
+                    tree.get(*node_id).is_some_and(|node| {
+                        let node = node.get();
+                        node.fn_name == "semantic_button" && node.role == NodeRole::Composition
+                    })
+                })
+                .expect("semantic_button composition node must exist");
+            let metadata = metadatas
+                .get(&node_id)
+                .expect("semantic_button metadata must exist");
+            let accessibility = metadata
+                .accessibility
+                .as_ref()
+                .expect("semantics must populate the accessibility node");
+
+            assert_eq!(accessibility.role, Some(Role::Button));
+            assert_eq!(accessibility.label.as_deref(), Some("Send"));
+            assert_eq!(accessibility.actions, vec![Action::Click]);
+            assert!(metadata.accessibility_action_handler.is_some());
+        });
+    }
+
+    #[tessera(crate)]
+    fn accessible_focus_target() {
+        crate::layout::layout()
+            .layout_policy(FixedSizePolicy {
+                width: 80,
+                height: 40,
+            })
+            .render_policy(NoopRenderPolicy)
+            .modifier(
+                Modifier::new()
+                    .focusable()
+                    .push_semantics(TestTagSemanticsModifier {
+                        tag: "focus_target".to_string(),
+                    }),
+            );
+    }
+
+    #[test]
+    fn accessibility_focus_action_moves_keyboard_focus() {
+        let mut session = super::layout_test(|| {
+            accessible_focus_target();
+        })
+        .viewport_px(200, 200)
+        .interact();
+        session.frame();
+
+        TesseraRuntime::with_mut(|runtime| {
+            let tree = runtime.component_tree.tree();
+            let metadatas = runtime.component_tree.metadatas();
+            let root = tree
+                .get_node_id_at(NonZero::new(1).expect("root node index must be non-zero"))
+                .expect("root node must exist after build");
+            let target = root
+                .descendants(tree)
+                .find(|node_id| {
+                    metadatas
+                        .get(node_id)
+                        .and_then(|metadata| metadata.accessibility.as_ref())
+                        .and_then(|accessibility| accessibility.key.as_deref())
+                        == Some("focus_target")
+                })
+                .expect("focus target must carry semantics");
+            let target_id =
+                crate::AccessibilityId::from_component_node_id(target).to_accesskit_id();
+
+            let (tree, metadatas, focus_owner) =
+                runtime.component_tree.accessibility_dispatch_context();
+            let handled = crate::accessibility::dispatch_action(
+                tree,
+                metadatas,
+                focus_owner,
+                crate::accesskit::ActionRequest {
+                    action: Action::Focus,
+                    target_tree: crate::accesskit::TreeId::ROOT,
+                    target_node: target_id,
+                    data: None,
+                },
+            );
+            assert!(handled, "focus action must move keyboard focus");
+            assert_eq!(focus_owner.active_component_node_id(), Some(target));
+            assert!(focus_owner.focus_visible());
+
+            let update = crate::accessibility::build_tree_update(
+                tree,
+                metadatas,
+                root,
+                focus_owner.active_component_node_id(),
+                None,
+            )
+            .expect("tree update must exist");
+            assert_eq!(update.focus, target_id);
+        });
+    }
+
+    #[tessera(crate)]
+    fn indexed_focus_box(tag: Option<String>, traversal_index: Option<i32>) {
+        let tag = tag.unwrap_or_default();
+        let traversal_index = traversal_index.unwrap_or_default();
+        crate::layout::layout()
+            .layout_policy(FixedSizePolicy {
+                width: 20,
+                height: 20,
+            })
+            .render_policy(NoopRenderPolicy)
+            .modifier(
+                Modifier::new()
+                    .focusable()
+                    .focus_properties(FocusProperties::new().traversal_index(traversal_index))
+                    .push_semantics(TestTagSemanticsModifier { tag }),
+            );
+    }
+
+    #[tessera(crate)]
+    fn indexed_focus_stack() {
+        crate::layout::layout()
+            .layout_policy(VerticalStackPolicy)
+            .render_policy(NoopRenderPolicy)
+            .modifier(Modifier::new())
+            .child(|| {
+                indexed_focus_box()
+                    .tag("first".to_string())
+                    .traversal_index(1);
+                indexed_focus_box()
+                    .tag("second".to_string())
+                    .traversal_index(0);
+                indexed_focus_box().tag("third".to_string());
+            });
+    }
+
+    fn focused_tag() -> Option<String> {
+        TesseraRuntime::with(|runtime| {
+            let node_id = runtime
+                .component_tree
+                .focus_owner()
+                .active_component_node_id()?;
+            runtime
+                .component_tree
+                .metadatas()
+                .get(&node_id)?
+                .accessibility
+                .as_ref()?
+                .key
+                .clone()
+        })
+    }
+
+    #[test]
+    fn tab_traversal_orders_siblings_by_traversal_index() {
+        let mut session = super::layout_test(|| {
+            indexed_focus_stack();
+        })
+        .viewport_px(100, 100)
+        .interact();
+        session.frame();
+
+        let mut order = Vec::new();
+        for _ in 0..3 {
+            assert!(FocusManager::current().move_focus(FocusDirection::Next));
+            order.push(focused_tag().expect("traversal must focus a tagged node"));
+        }
+        assert_eq!(order, ["second", "third", "first"]);
+    }
+
+    static FOCUS_VISIBLE_READS: Mutex<Vec<bool>> = Mutex::new(Vec::new());
+
+    #[tessera(crate)]
+    fn focus_visibility_reader() {
+        FOCUS_VISIBLE_READS
+            .lock()
+            .expect("focus visibility log must not be poisoned")
+            .push(FocusManager::current().is_focus_visible());
+        crate::layout::layout()
+            .layout_policy(FixedSizePolicy {
+                width: 100,
+                height: 100,
+            })
+            .render_policy(NoopRenderPolicy);
+    }
+
+    #[test]
+    fn focus_visibility_flips_rebuild_readers() {
+        let mut session = super::layout_test(|| {
+            focus_visibility_reader();
+        })
+        .viewport_px(200, 200)
+        .interact();
+        session.frame();
+        let reads = || {
+            FOCUS_VISIBLE_READS
+                .lock()
+                .expect("focus visibility log must not be poisoned")
+                .clone()
+        };
+        assert_eq!(reads(), [false]);
+
+        TesseraRuntime::with_mut(|runtime| {
+            runtime
+                .component_tree
+                .focus_owner_mut()
+                .set_focus_visible(true);
+        });
+        session.frame();
+        assert_eq!(reads(), [false, true]);
+
+        session
+            .move_cursor(50, 50)
+            .press(PressKeyEventType::Left)
+            .frame();
+        session.frame();
+        assert_eq!(reads(), [false, true, false]);
+    }
+
+    static HOVER_EVENTS: Mutex<Vec<bool>> = Mutex::new(Vec::new());
+
+    #[tessera(crate)]
+    fn hover_logging_box() {
+        crate::layout::layout()
+            .layout_policy(FixedSizePolicy {
+                width: 100,
+                height: 100,
+            })
+            .render_policy(NoopRenderPolicy)
+            .modifier(Modifier::new().on_hover(|hovered| {
+                HOVER_EVENTS
+                    .lock()
+                    .expect("hover log must not be poisoned")
+                    .push(hovered);
+            }));
+    }
+
+    #[test]
+    fn hover_enters_and_leaves_once() {
+        let mut session = super::layout_test(|| {
+            hover_logging_box();
+        })
+        .viewport_px(200, 200)
+        .interact();
+        let events = || {
+            HOVER_EVENTS
+                .lock()
+                .expect("hover log must not be poisoned")
+                .clone()
+        };
+
+        session.move_cursor(50, 50).frame();
+        session.move_cursor(60, 60).frame();
+        session.frame();
+        assert_eq!(events(), [true]);
+
+        session.move_cursor(150, 150).frame();
+        assert_eq!(events(), [true, false]);
+    }
+
+    static REMOVED_HOVER_EVENTS: Mutex<Vec<bool>> = Mutex::new(Vec::new());
+
+    #[tessera(crate)]
+    fn self_removing_hover_box() {
+        let shown = remember(|| true);
+        crate::layout::layout()
+            .layout_policy(VerticalStackPolicy)
+            .render_policy(NoopRenderPolicy)
+            .child(move || {
+                if shown.get() {
+                    crate::layout::layout()
+                        .layout_policy(FixedSizePolicy {
+                            width: 100,
+                            height: 100,
+                        })
+                        .render_policy(NoopRenderPolicy)
+                        .modifier(Modifier::new().on_hover(move |hovered| {
+                            REMOVED_HOVER_EVENTS
+                                .lock()
+                                .expect("hover log must not be poisoned")
+                                .push(hovered);
+                            shown.set(false);
+                        }));
+                }
+            });
+    }
+
+    #[test]
+    fn removing_a_hovered_node_skips_its_leave() {
+        let mut session = super::layout_test(|| {
+            self_removing_hover_box();
+        })
+        .viewport_px(200, 200)
+        .interact();
+
+        session.move_cursor(50, 50).frame();
+        session.frame();
+        session.move_cursor(150, 150).frame();
+        assert_eq!(
+            *REMOVED_HOVER_EVENTS
+                .lock()
+                .expect("hover log must not be poisoned"),
+            [true]
+        );
+    }
+
+    struct TextCursorRequest;
+
+    impl PointerInputModifierNode for TextCursorRequest {
+        fn on_pointer_input(&self, mut input: PointerInput<'_>) {
+            if input.pass == PointerEventPass::Main && input.is_hovered() {
+                input.request_cursor_icon(winit::window::CursorIcon::Text);
+            }
+        }
+    }
+
+    #[tessera(crate)]
+    fn cursor_precedence_sample() {
+        crate::layout::layout()
+            .layout_policy(VerticalStackPolicy)
+            .render_policy(NoopRenderPolicy)
+            .modifier(Modifier::new().push_pointer_input(TextCursorRequest))
+            .child(|| {
+                crate::layout::layout()
+                    .layout_policy(FixedSizePolicy {
+                        width: 100,
+                        height: 50,
+                    })
+                    .render_policy(NoopRenderPolicy)
+                    .modifier(
+                        Modifier::new().hover_cursor_icon(winit::window::CursorIcon::Pointer),
+                    );
+                crate::layout::layout()
+                    .layout_policy(FixedSizePolicy {
+                        width: 100,
+                        height: 50,
+                    })
+                    .render_policy(NoopRenderPolicy);
+            });
+    }
+
+    #[test]
+    fn hover_cursor_icon_wins_over_handler_requests() {
+        let mut session = super::layout_test(|| {
+            cursor_precedence_sample();
+        })
+        .viewport_px(200, 200)
+        .interact();
+
+        session.move_cursor(50, 25).frame();
+        assert_eq!(session.cursor_icon(), winit::window::CursorIcon::Pointer);
+
+        session.move_cursor(50, 75).frame();
+        assert_eq!(session.cursor_icon(), winit::window::CursorIcon::Text);
+
+        session.move_cursor(150, 150).frame();
+        assert_eq!(session.cursor_icon(), winit::window::CursorIcon::Default);
+    }
+
+    thread_local! {
+        static DND_LOG: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn log_dnd(entry: String) {
+        DND_LOG.with(|log| log.borrow_mut().push(entry));
+    }
+
+    fn take_dnd_log() -> Vec<String> {
+        DND_LOG.with(|log| std::mem::take(&mut *log.borrow_mut()))
+    }
+
+    fn dnd_box(modifier: Modifier) {
+        crate::layout::layout()
+            .layout_policy(FixedSizePolicy {
+                width: 100,
+                height: 100,
+            })
+            .render_policy(NoopRenderPolicy)
+            .modifier(modifier);
+    }
+
+    #[tessera(crate)]
+    fn drag_and_drop_sample(
+        remove_source_on_drag: Option<bool>,
+        remove_target_on_hover: Option<bool>,
+    ) {
+        let remove_source_on_drag = remove_source_on_drag.unwrap_or(false);
+        let remove_target_on_hover = remove_target_on_hover.unwrap_or(false);
+        let source_shown = remember(|| true);
+        let target_shown = remember(|| true);
+        crate::layout::layout()
+            .layout_policy(VerticalStackPolicy)
+            .render_policy(NoopRenderPolicy)
+            .child(move || {
+                if source_shown.get() {
+                    dnd_box(
+                        Modifier::new().drag_source(
+                            DragSource::new(move |()| {
+                                if remove_source_on_drag {
+                                    source_shown.set(false);
+                                }
+                                Some(DragPayload::new(7_u32))
+                            })
+                            .on_drag_end(|outcome: DropOutcome| log_dnd(format!("{outcome:?}"))),
+                        ),
+                    );
+                } else {
+                    dnd_box(Modifier::new());
+                }
+                if target_shown.get() {
+                    dnd_box(
+                        Modifier::new().drop_target(
+                            DropTarget::new(|event: DropEvent| {
+                                let value = event.payload.get::<u32>().copied().unwrap_or_default();
+                                log_dnd(format!("drop {value}"));
+                                true
+                            })
+                            .on_hover_changed(move |hovered: bool| {
+                                log_dnd(format!("hover {hovered}"));
+                                if remove_target_on_hover && hovered {
+                                    target_shown.set(false);
+                                }
+                            }),
+                        ),
+                    );
+                }
+            });
+    }
+
+    fn drag_from_source_to_target(session: &mut super::InteractionSession<impl Fn()>) {
+        session
+            .move_cursor(50, 50)
+            .press(PressKeyEventType::Left)
+            .frame();
+        session.move_cursor(50, 80).frame();
+        session.move_cursor(50, 150).frame();
+        session.frame();
+        session.release(PressKeyEventType::Left).frame();
+        session.frame();
+    }
+
+    #[test]
+    fn drag_and_drop_delivers_payload_to_hovered_target() {
+        take_dnd_log();
+        let mut session = super::layout_test(|| {
+            drag_and_drop_sample();
+        })
+        .viewport_px(200, 200)
+        .interact();
+
+        drag_from_source_to_target(&mut session);
+        assert_eq!(
+            take_dnd_log(),
+            ["hover true", "drop 7", "hover false", "Dropped"]
+        );
+        assert!(!crate::dnd::is_dragging());
+    }
+
+    #[test]
+    fn removing_the_drag_source_cancels_the_session() {
+        take_dnd_log();
+        let mut session = super::layout_test(|| {
+            drag_and_drop_sample().remove_source_on_drag(true);
+        })
+        .viewport_px(200, 200)
+        .interact();
+
+        drag_from_source_to_target(&mut session);
+        let log = take_dnd_log();
+        assert!(!log.iter().any(|entry| entry.starts_with("drop")));
+        assert!(!crate::dnd::is_dragging());
+    }
+
+    #[test]
+    fn removing_the_hovered_target_skips_its_handlers() {
+        take_dnd_log();
+        let mut session = super::layout_test(|| {
+            drag_and_drop_sample().remove_target_on_hover(true);
+        })
+        .viewport_px(200, 200)
+        .interact();
+
+        drag_from_source_to_target(&mut session);
+        assert_eq!(take_dnd_log(), ["hover true", "Canceled"]);
+        assert!(!crate::dnd::is_dragging());
+    }
+
+    static SAVES: AtomicUsize = AtomicUsize::new(0);
+
+    #[tessera(crate)]
+    fn shortcut_box() {
+        crate::layout::layout()
+            .layout_policy(FixedSizePolicy {
+                width: 100,
+                height: 100,
+            })
+            .render_policy(NoopRenderPolicy)
+            .modifier(Modifier::new().shortcut(Shortcut::parse("Ctrl+S", || {
+                SAVES.fetch_add(1, Ordering::SeqCst);
+            })));
+    }
+
+    #[test]
+    fn press_key_delivers_synthetic_key_chords() {
+        let mut session = super::layout_test(|| {
+            shortcut_box();
+        })
+        .viewport_px(200, 200)
+        .interact();
+
+        session
+            .press_key(Key::Character("s".into()), ModifiersState::CONTROL)
+            .frame();
+        assert_eq!(SAVES.load(Ordering::SeqCst), 1);
+
+        session
+            .press_key(Key::Character("s".into()), ModifiersState::empty())
+            .frame();
+        assert_eq!(SAVES.load(Ordering::SeqCst), 1);
+    }
+
+    static HARNESS_SAVES: AtomicUsize = AtomicUsize::new(0);
+
+    #[tessera(crate)]
+    fn harness_shortcut_box() {
+        crate::layout::layout()
+            .layout_policy(FixedSizePolicy {
+                width: 100,
+                height: 100,
+            })
+            .render_policy(NoopRenderPolicy)
+            .modifier(Modifier::new().shortcut(Shortcut::parse("Ctrl+S", || {
+                HARNESS_SAVES.fetch_add(1, Ordering::SeqCst);
+            })));
+    }
+
+    #[test]
+    fn test_harness_delivers_key_presses() {
+        let mut harness = super::TestHarness::mount(
+            || {
+                harness_shortcut_box();
+            },
+            200,
+            200,
+        );
+
+        harness.press_key(Key::Character("s".into()), ModifiersState::CONTROL);
+        assert_eq!(HARNESS_SAVES.load(Ordering::SeqCst), 1);
+
+        harness.key_event(KeyEvent::press(Key::Character("s".into())));
+        assert_eq!(HARNESS_SAVES.load(Ordering::SeqCst), 2);
+    }
+
+    static BOUNDARY_FALLBACKS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    #[tessera(crate)]
+    fn failing_page() -> Result<(), String> {
+        Err("missing settings".to_string())
+    }
+
+    #[tessera(crate)]
+    fn guarded_failing_page() {
+        crate::error_boundary::error_boundary(|| {
+            failing_page();
+        })
+        .fallback(|error: crate::error_boundary::ComponentError| {
+            BOUNDARY_FALLBACKS
+                .lock()
+                .expect("fallback log must not be poisoned")
+                .push(error.to_string());
+        });
+    }
+
+    #[test]
+    fn error_boundary_renders_fallback_for_failed_components() {
+        let mut session = super::layout_test(|| {
+            guarded_failing_page();
+        })
+        .viewport_px(100, 100)
+        .interact();
+
+        session.frame();
+        session.frame();
+        let fallbacks = BOUNDARY_FALLBACKS
+            .lock()
+            .expect("fallback log must not be poisoned")
+            .clone();
+        assert!(!fallbacks.is_empty());
+        assert!(
+            fallbacks
+                .iter()
+                .all(|error| error == "`failing_page` failed: missing settings")
+        );
+    }
+
+    #[derive(Clone, Default)]
+    struct LogBuffer(std::sync::Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for LogBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0
+                .lock()
+                .expect("log buffer must not be poisoned")
+                .extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn component_errors_without_a_boundary_are_logged() {
+        let logs = LogBuffer::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer({
+                let logs = logs.clone();
+                move || logs.clone()
+            })
+            .with_ansi(false)
+            .finish();
+
+        tracing::subscriber::with_default(subscriber, || {
+            let mut session = super::layout_test(|| {
+                failing_page();
+            })
+            .viewport_px(100, 100)
+            .interact();
+            session.frame();
+        });
+
+        let logs = String::from_utf8(
+            logs.0
+                .lock()
+                .expect("log buffer must not be poisoned")
+                .clone(),
+        )
+        .expect("logs must be UTF-8");
+        assert!(logs.contains("ERROR"));
+        assert!(logs.contains("`failing_page` failed: missing settings"));
+    }
+
+    thread_local! {
+        static BACK_LOG: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    }
+
+    fn take_back_log() -> Vec<String> {
+        BACK_LOG.with(|log| std::mem::take(&mut *log.borrow_mut()))
+    }
+
+    #[tessera(crate)]
+    fn back_layer(name: Option<String>, enabled: Option<bool>, on_back: Option<Callback>) {
+        let name = name.unwrap_or_default();
+        back_handler(enabled.unwrap_or(true), move || {
+            BACK_LOG.with(|log| log.borrow_mut().push(name.clone()));
+            if let Some(on_back) = on_back {
+                on_back.call();
+            }
+        });
+    }
+
+    #[tessera(crate)]
+    fn back_navigation_sample() {
+        let screen_enabled = remember(|| true);
+        let dialog_shown = remember(|| true);
+        crate::layout::layout()
+            .layout_policy(VerticalStackPolicy)
+            .render_policy(NoopRenderPolicy)
+            .child(move || {
+                back_layer()
+                    .name("screen".to_string())
+                    .enabled(screen_enabled.get())
+                    .on_back(move || screen_enabled.set(false));
+                if dialog_shown.get() {
+                    back_layer()
+                        .name("dialog".to_string())
+                        .on_back(move || dialog_shown.set(false));
+                }
+                back_layer().name("sheet".to_string()).enabled(false);
+            });
+    }
+
+    #[test]
+    fn back_dispatch_runs_the_last_mounted_enabled_handler() {
+        let mut session = super::layout_test(|| {
+            back_navigation_sample();
+        })
+        .viewport_px(200, 200)
+        .interact();
+        session.frame();
+
+        // The sheet is mounted last but disabled, so the dialog wins.
+        assert!(dispatch_back());
+        assert_eq!(take_back_log(), ["dialog"]);
+
+        // The closed dialog left the tree and its handler is pruned.
+        session.frame();
+        assert!(dispatch_back());
+        assert_eq!(take_back_log(), ["screen"]);
+
+        session.frame();
+        assert!(!dispatch_back());
+        assert!(take_back_log().is_empty());
+    }
+
+    #[test]
+    fn interaction_session_delivers_synthetic_clicks_by_hit_test() {
+        let mut session = super::layout_test(|| {
+            pressable_box();
+        })
+        .viewport_px(200, 200)
+        .interact();
+
+        session.click(50, 50).frame();
+        assert_eq!(PRESSES.load(Ordering::SeqCst), 1);
+        assert_eq!(RELEASES.load(Ordering::SeqCst), 1);
+
+        session.click(150, 150).frame();
+        assert_eq!(PRESSES.load(Ordering::SeqCst), 1);
+        assert_eq!(RELEASES.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_harness_finds_nodes_by_text_and_role() {
+        let mut harness = super::TestHarness::mount(
+            || {
+                semantic_button();
+            },
+            200,
+            100,
+        );
+
+        let button = harness.find_by_text("Send");
+        button
+            .assert_role(Role::Button)
+            .assert_action(Action::Click)
+            .assert_enabled()
+            .assert_rect(0, 0, 80, 40);
+        assert_eq!(harness.find_by_role(Role::Button).center(), (40, 20));
+        harness.click(&button);
+        assert!(harness.find_all_by_role(Role::CheckBox).is_empty());
+    }
+
+    #[test]
+    fn test_harness_advances_the_runtime_clock() {
+        let mut harness = super::TestHarness::mount(
+            || {
+                semantic_button();
+            },
+            200,
+            100,
+        );
+
+        let start = crate::time::now();
+        harness.advance_time(Duration::from_millis(250));
+        assert_eq!(crate::time::now() - start, Duration::from_millis(250));
+        harness.advance_frames(3, Duration::from_millis(16));
+        assert_eq!(crate::time::now() - start, Duration::from_millis(298));
+    }
+
+    thread_local! {
+        static TOUCH_TIMESTAMPS: RefCell<Vec<crate::time::Instant>> =
+            const { RefCell::new(Vec::new()) };
+    }
+
+    struct TouchTimestamps;
+
+    impl PointerInputModifierNode for TouchTimestamps {
+        fn on_pointer_input(&self, input: PointerInput<'_>) {
+            if input.pass != PointerEventPass::Main {
+                return;
+            }
+            TOUCH_TIMESTAMPS.with(|timestamps| {
+                timestamps
+                    .borrow_mut()
+                    .extend(input.pointer_changes.iter().map(|change| change.timestamp));
+            });
+        }
+    }
+
+    #[tessera(crate)]
+    fn touch_timestamp_box() {
+        crate::layout::layout()
+            .layout_policy(FixedSizePolicy {
+                width: 100,
+                height: 100,
+            })
+            .render_policy(NoopRenderPolicy)
+            .modifier(Modifier::new().push_pointer_input(TouchTimestamps));
+    }
+
+    #[test]
+    fn touch_input_is_stamped_with_the_runtime_clock() {
+        let mut session = super::layout_test(|| {
+            touch_timestamp_box();
+        })
+        .viewport_px(200, 200)
+        .interact();
+        session.advance_by_nanos(1_000_000_000);
+
+        let stamped = crate::time::now();
+        session
+            .touch_start(1, 50, 50)
+            .touch_move(1, 60, 60)
+            .touch_end(1)
+            .frame();
+        let timestamps = TOUCH_TIMESTAMPS.with(|timestamps| timestamps.take());
+        assert!(!timestamps.is_empty());
+        assert!(timestamps.iter().all(|timestamp| *timestamp == stamped));
+    }
+
     #[test]
     fn assert_layout_macro_smoke() {
         crate::assert_layout! {
@@ -0,0 +1,383 @@
+//! Golden image comparison for rendered frames.
+//!
+//! Golden images are PNG files named after the snapshot. Frames are compared
+//! with a perceptual color metric, so small differences in antialiasing
+//! between GPUs and drivers don't fail tests, and a failed comparison writes
+//! the actual frame and a diff image next to the golden.
+//!
+//! Set `TESSERA_UPDATE_SNAPSHOTS=1` to record or overwrite golden images
+//! instead of comparing against them.
+
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, BufWriter},
+    path::Path,
+};
+
+/// Environment variable that records golden images instead of comparing.
+const UPDATE_SNAPSHOTS_ENV: &str = "TESSERA_UPDATE_SNAPSHOTS";
+/// Largest possible YIQ color delta, between black and white.
+const MAX_YIQ_DELTA: f32 = 35215.0;
+
+/// An RGBA image with 8-bit sRGB channels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotImage {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl SnapshotImage {
+    /// Creates an image from tightly packed RGBA rows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pixels` doesn't hold exactly `width * height` pixels.
+    pub fn new(width: u32, height: u32, pixels: Vec<u8>) -> Self {
+        assert_eq!(
+            pixels.len(),
+            width as usize * height as usize * 4,
+            "snapshot pixels don't match a {width}x{height} RGBA image"
+        );
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Returns the width in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the height in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the RGBA pixel at a position.
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let offset = (y as usize * self.width as usize + x as usize) * 4;
+        let mut pixel = [0; 4];
+        pixel.copy_from_slice(&self.pixels[offset..offset + 4]);
+        pixel
+    }
+
+    /// Returns the tightly packed RGBA rows.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Loads an 8-bit RGBA PNG.
+    pub fn load_png(path: impl AsRef<Path>) -> io::Result<Self> {
+        let decoder = png::Decoder::new(BufReader::new(File::open(path)?));
+        let mut reader = decoder.read_info().map_err(io::Error::other)?;
+        let buffer_size = reader
+            .output_buffer_size()
+            .ok_or_else(|| io::Error::other("PNG image is too large"))?;
+        let mut pixels = vec![0; buffer_size];
+        let info = reader.next_frame(&mut pixels).map_err(io::Error::other)?;
+        if info.color_type != png::ColorType::Rgba || info.bit_depth != png::BitDepth::Eight {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "expected an 8-bit RGBA PNG, found {:?} at {:?}",
+                    info.color_type, info.bit_depth
+                ),
+            ));
+        }
+        pixels.truncate(info.buffer_size());
+        Ok(Self::new(info.width, info.height, pixels))
+    }
+
+    /// Saves the image as an 8-bit RGBA PNG, creating parent directories.
+    pub fn save_png(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut encoder =
+            png::Encoder::new(BufWriter::new(File::create(path)?), self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer
+            .write_image_data(&self.pixels)
+            .map_err(io::Error::other)?;
+        writer.finish().map_err(io::Error::other)
+    }
+}
+
+/// How far a frame may drift from its golden image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SnapshotTolerance {
+    /// Perceptual color distance, from `0.0` to `1.0`, under which two
+    /// pixels are considered equal.
+    pub color_threshold: f32,
+    /// Fraction of pixels, from `0.0` to `1.0`, allowed to differ.
+    pub max_diff_ratio: f32,
+}
+
+impl SnapshotTolerance {
+    /// Requires every pixel to match exactly.
+    pub const EXACT: Self = Self {
+        color_threshold: 0.0,
+        max_diff_ratio: 0.0,
+    };
+
+    /// Creates a tolerance.
+    pub const fn new(color_threshold: f32, max_diff_ratio: f32) -> Self {
+        Self {
+            color_threshold,
+            max_diff_ratio,
+        }
+    }
+}
+
+impl Default for SnapshotTolerance {
+    fn default() -> Self {
+        Self::new(0.1, 0.001)
+    }
+}
+
+/// Snapshot tolerances that can differ per operating system.
+///
+/// Golden images are usually recorded on one platform; other platforms
+/// rasterize edges and text slightly differently and may need looser bounds.
+///
+/// # Examples
+///
+/// ```
+/// use tessera_ui::testing::{SnapshotTolerance, SnapshotTolerances};
+///
+/// // Goldens are recorded on Linux; allow more drift elsewhere.
+/// let tolerances = SnapshotTolerances::default()
+///     .on("macos", SnapshotTolerance::new(0.1, 0.01))
+///     .on("windows", SnapshotTolerance::new(0.15, 0.01));
+/// # let _ = tolerances.current();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SnapshotTolerances {
+    fallback: SnapshotTolerance,
+    platforms: Vec<(&'static str, SnapshotTolerance)>,
+}
+
+impl SnapshotTolerances {
+    /// Uses `tolerance` on every platform without its own tolerance.
+    pub fn new(tolerance: SnapshotTolerance) -> Self {
+        Self {
+            fallback: tolerance,
+            platforms: Vec::new(),
+        }
+    }
+
+    /// Uses `tolerance` on the operating system named like
+    /// [`std::env::consts::OS`], e.g. `"macos"`.
+    pub fn on(mut self, os: &'static str, tolerance: SnapshotTolerance) -> Self {
+        self.platforms.retain(|(platform, _)| *platform != os);
+        self.platforms.push((os, tolerance));
+        self
+    }
+
+    /// Returns the tolerance for the current operating system.
+    pub fn current(&self) -> SnapshotTolerance {
+        self.platforms
+            .iter()
+            .find(|(platform, _)| *platform == std::env::consts::OS)
+            .map_or(self.fallback, |(_, tolerance)| *tolerance)
+    }
+}
+
+impl From<SnapshotTolerance> for SnapshotTolerances {
+    fn from(tolerance: SnapshotTolerance) -> Self {
+        Self::new(tolerance)
+    }
+}
+
+/// Result of comparing a frame against its golden image.
+#[derive(Debug, Clone)]
+pub struct SnapshotDiff {
+    /// Number of pixels that differ beyond the color threshold.
+    pub diff_pixels: usize,
+    /// Total number of compared pixels.
+    pub total_pixels: usize,
+    /// Faded copy of the golden image with differing pixels in red.
+    pub image: SnapshotImage,
+}
+
+impl SnapshotDiff {
+    /// Returns the fraction of pixels that differ.
+    pub fn diff_ratio(&self) -> f32 {
+        if self.total_pixels == 0 {
+            0.0
+        } else {
+            self.diff_pixels as f32 / self.total_pixels as f32
+        }
+    }
+}
+
+/// Compares two images of the same size with a perceptual color metric.
+///
+/// Pixels are compared in the YIQ color space, weighted like the human eye,
+/// after blending them over white so transparent pixels compare by what they
+/// show.
+///
+/// # Panics
+///
+/// Panics if the images differ in size.
+pub fn compare_images(
+    expected: &SnapshotImage,
+    actual: &SnapshotImage,
+    color_threshold: f32,
+) -> SnapshotDiff {
+    assert_eq!(
+        (expected.width, expected.height),
+        (actual.width, actual.height),
+        "compared snapshot images differ in size"
+    );
+    let max_delta = MAX_YIQ_DELTA * color_threshold * color_threshold;
+    let mut diff_pixels = 0;
+    let mut image = Vec::with_capacity(expected.pixels.len());
+    for (expected, actual) in expected
+        .pixels
+        .chunks_exact(4)
+        .zip(actual.pixels.chunks_exact(4))
+    {
+        if expected == actual || color_delta(expected, actual) <= max_delta {
+            let gray = blend_over_white(expected);
+            let luma = rgb_to_y(gray) as u8;
+            // Fade matching pixels so the differences stand out.
+            let faded = 255 - (255 - luma) / 10;
+            image.extend_from_slice(&[faded, faded, faded, 255]);
+        } else {
+            diff_pixels += 1;
+            image.extend_from_slice(&[255, 0, 0, 255]);
+        }
+    }
+    SnapshotDiff {
+        diff_pixels,
+        total_pixels: expected.pixels.len() / 4,
+        image: SnapshotImage::new(expected.width, expected.height, image),
+    }
+}
+
+/// Compares a frame against the golden image `name.png` in `dir`.
+///
+/// On failure the frame and the diff image are written to `dir/failures` and
+/// the returned message points at them.
+pub(crate) fn check_snapshot(
+    actual: &SnapshotImage,
+    dir: &Path,
+    name: &str,
+    tolerance: SnapshotTolerance,
+) -> Result<(), String> {
+    let golden_path = dir.join(format!("{name}.png"));
+    if std::env::var(UPDATE_SNAPSHOTS_ENV).is_ok_and(|value| !value.is_empty() && value != "0") {
+        return actual
+            .save_png(&golden_path)
+            .map_err(|err| format!("failed to write {}: {err}", golden_path.display()));
+    }
+    if !golden_path.exists() {
+        return Err(format!(
+            "golden image {} does not exist; run with {UPDATE_SNAPSHOTS_ENV}=1 to record it",
+            golden_path.display()
+        ));
+    }
+    let expected = SnapshotImage::load_png(&golden_path)
+        .map_err(|err| format!("failed to read {}: {err}", golden_path.display()))?;
+
+    let failures = dir.join("failures");
+    let actual_path = failures.join(format!("{name}.actual.png"));
+    if (expected.width, expected.height) != (actual.width, actual.height) {
+        write_failure_image(actual, &actual_path)?;
+        return Err(format!(
+            "snapshot `{name}` is {}x{} but the golden image is {}x{}; actual frame written to {}",
+            actual.width,
+            actual.height,
+            expected.width,
+            expected.height,
+            actual_path.display()
+        ));
+    }
+
+    let diff = compare_images(&expected, actual, tolerance.color_threshold);
+    if diff.diff_ratio() <= tolerance.max_diff_ratio {
+        return Ok(());
+    }
+    let diff_path = failures.join(format!("{name}.diff.png"));
+    write_failure_image(actual, &actual_path)?;
+    write_failure_image(&diff.image, &diff_path)?;
+    Err(format!(
+        "snapshot `{name}` differs from {} in {} of {} pixels ({:.3}%, allowed {:.3}%); \
+         actual frame written to {}, diff to {}",
+        golden_path.display(),
+        diff.diff_pixels,
+        diff.total_pixels,
+        diff.diff_ratio() * 100.0,
+        tolerance.max_diff_ratio * 100.0,
+        actual_path.display(),
+        diff_path.display()
+    ))
+}
+
+fn write_failure_image(image: &SnapshotImage, path: &Path) -> Result<(), String> {
+    image
+        .save_png(path)
+        .map_err(|err| format!("failed to write {}: {err}", path.display()))
+}
+
+fn blend_over_white(pixel: &[u8]) -> [f32; 3] {
+    let alpha = f32::from(pixel[3]) / 255.0;
+    let blend = |channel: u8| 255.0 + (f32::from(channel) - 255.0) * alpha;
+    [blend(pixel[0]), blend(pixel[1]), blend(pixel[2])]
+}
+
+fn rgb_to_y([r, g, b]: [f32; 3]) -> f32 {
+    r * 0.298_895_3 + g * 0.586_622_5 + b * 0.114_482_23
+}
+
+fn rgb_to_i([r, g, b]: [f32; 3]) -> f32 {
+    r * 0.595_978 - g * 0.274_176_1 - b * 0.321_801_9
+}
+
+fn rgb_to_q([r, g, b]: [f32; 3]) -> f32 {
+    r * 0.211_470_17 - g * 0.522_617_1 + b * 0.311_146_94
+}
+
+/// Squared perceptual distance between two RGBA pixels.
+fn color_delta(a: &[u8], b: &[u8]) -> f32 {
+    let a = blend_over_white(a);
+    let b = blend_over_white(b);
+    let y = rgb_to_y(a) - rgb_to_y(b);
+    let i = rgb_to_i(a) - rgb_to_i(b);
+    let q = rgb_to_q(a) - rgb_to_q(b);
+    0.5053 * y * y + 0.299 * i * i + 0.1957 * q * q
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(pixels: &[[u8; 4]]) -> SnapshotImage {
+        SnapshotImage::new(
+            pixels.len() as u32,
+            1,
+            pixels.iter().flatten().copied().collect(),
+        )
+    }
+
+    #[test]
+    fn perceptual_diff_ignores_small_shifts_and_marks_real_changes() {
+        let expected = solid(&[[40, 120, 200, 255], [255, 255, 255, 255], [0, 0, 0, 0]]);
+        let actual = solid(&[[42, 118, 201, 255], [0, 0, 255, 255], [0, 0, 0, 0]]);
+
+        let diff = compare_images(&expected, &actual, 0.1);
+        assert_eq!(diff.diff_pixels, 1);
+        assert_eq!(diff.total_pixels, 3);
+        assert_eq!(diff.image.pixel(1, 0), [255, 0, 0, 255]);
+        assert_ne!(diff.image.pixel(0, 0), [255, 0, 0, 255]);
+
+        assert_eq!(compare_images(&expected, &actual, 0.0).diff_pixels, 2);
+    }
+}
@@ -2,11 +2,22 @@
 //!
 //! ## Usage
 //!
-//! Use `Instant` for frame timing and animations on both native and web
-//! targets.
+//! Use `Instant` for frame timing on both native and web targets, and read
+//! the time animations run on from [`now`], which test harnesses control.
 
 #[cfg(not(target_family = "wasm"))]
 pub use std::time::Instant;
 
 #[cfg(target_family = "wasm")]
 pub use web_time::Instant;
+
+/// Returns the current time on the runtime clock.
+///
+/// Inside the runtime this is the timestamp of the frame being built, so
+/// everything in a frame sees the same time and
+/// [`TestHarness::advance_time`](crate::testing::TestHarness::advance_time)
+/// can step animations deterministically. Before the first frame it is the
+/// system time.
+pub fn now() -> Instant {
+    crate::runtime::current_frame_time().unwrap_or_else(Instant::now)
+}